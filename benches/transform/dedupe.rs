@@ -43,6 +43,7 @@ fn dedupe(c: &mut Criterion) {
                     "message",
                 )])),
                 cache: CacheConfig { num_events: 4 },
+                state: Default::default(),
             },
         },
         // Modification of previous where field "message" is matched.
@@ -52,6 +53,7 @@ fn dedupe(c: &mut Criterion) {
             dedupe_config: DedupeConfig {
                 fields: Some(FieldMatchConfig::MatchFields(vec![String::from("message")])),
                 cache: CacheConfig { num_events: 4 },
+                state: Default::default(),
             },
         },
         // Measurement where ignore fields do not exist in the event.
@@ -67,6 +69,7 @@ fn dedupe(c: &mut Criterion) {
                     String::from("cdeab"),
                     String::from("bcdea"),
                 ])),
+                state: Default::default(),
             },
         },
         // Modification of previous where match fields do not exist in the
@@ -83,6 +86,7 @@ fn dedupe(c: &mut Criterion) {
                     String::from("cdeab"),
                     String::from("bcdea"),
                 ])),
+                state: Default::default(),
             },
         },
     ] {
@@ -0,0 +1,505 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use apache_avro::{rabin::Rabin, types::Value as AvroValue, Schema};
+use bytes::{Buf, Bytes};
+use chrono::{TimeZone, Utc};
+use lookup::lookup_v2::parse_value_path;
+use ordered_float::NotNan;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+use super::Deserializer;
+use crate::encoding::AvroSchemaRegistryAuth;
+
+/// The marker starting messages in the single-object encoding.
+const SINGLE_OBJECT_MARKER: [u8; 2] = [0xc3, 0x01];
+
+/// The magic byte starting messages in the Confluent wire format.
+const CONFLUENT_MAGIC_BYTE: u8 = 0;
+
+/// Config used to build an `AvroDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct AvroDeserializerConfig {
+    /// Options for the Avro deserializer.
+    pub avro: AvroDeserializerOptions,
+}
+
+impl AvroDeserializerConfig {
+    /// Creates a new `AvroDeserializerConfig`.
+    pub const fn new(avro: AvroDeserializerOptions) -> Self {
+        Self { avro }
+    }
+
+    /// Build the `AvroDeserializer` from this configuration.
+    pub fn build(&self) -> AvroDeserializer {
+        AvroDeserializer {
+            format: self.avro.format,
+            // The schema is validated when the configuration is loaded.
+            schema: self
+                .avro
+                .schema
+                .as_deref()
+                .and_then(|schema| Schema::parse_str(schema).ok())
+                .map(Arc::new),
+            registry: self
+                .avro
+                .schema_registry
+                .as_ref()
+                .map(|_| AvroSchemaCache::default()),
+        }
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        // TODO: Convert the Avro schema to a vector schema definition.
+        let kind = Kind::json().or_timestamp();
+        match log_namespace {
+            LogNamespace::Legacy => schema::Definition::empty_legacy_namespace()
+                .unknown_fields(kind)
+                .try_with_field(
+                    &parse_value_path(log_schema().timestamp_key()).expect("valid timestamp key"),
+                    Kind::json().or_timestamp(),
+                    Some("timestamp"),
+                ),
+            LogNamespace::Vector => {
+                schema::Definition::new_with_default_metadata(kind, [log_namespace])
+            }
+        }
+    }
+}
+
+/// Apache Avro deserializer options.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct AvroDeserializerOptions {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub format: AvroEncodingFormat,
+
+    /// The Avro schema.
+    ///
+    /// Required unless the schemas are fetched from `schema_registry`.
+    #[serde(default, deserialize_with = "deserialize_schema")]
+    #[configurable(metadata(
+        docs::examples = r#"{ "type": "record", "name": "log", "fields": [{ "name": "message", "type": "string" }] }"#
+    ))]
+    pub schema: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_registry: Option<AvroDeserializerRegistryOptions>,
+}
+
+/// The encoding of the Avro messages.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AvroEncodingFormat {
+    /// Each message is a bare Avro datum, written with `schema`.
+    #[default]
+    Datum,
+
+    /// Each message is in the [single-object encoding][single_object]: the `C3 01` marker,
+    /// followed by the CRC-64-AVRO fingerprint of the schema, and the datum.
+    ///
+    /// Messages written with a schema other than `schema` are rejected.
+    ///
+    /// [single_object]: https://avro.apache.org/docs/1.11.1/specification/#single-object-encoding
+    SingleObject,
+
+    /// Each message is in the Confluent [wire format][wire_format]: a zero magic byte, followed by
+    /// the ID of the schema in the schema registry, as a 32-bit unsigned integer, big endian, and
+    /// the datum.
+    ///
+    /// The datum is read with the schema fetched from `schema_registry` if set, or with `schema`
+    /// otherwise.
+    ///
+    /// [wire_format]: https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format
+    Confluent,
+}
+
+/// Confluent-compatible schema registry options.
+///
+/// The schemas referenced by the messages are fetched from the registry the first time they are
+/// seen, and cached for the lifetime of the source.
+///
+/// The schema registry is only supported by the `kafka` source, with the `confluent` format.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AvroDeserializerRegistryOptions {
+    /// The URL of the schema registry.
+    #[configurable(metadata(docs::examples = "http://localhost:8081"))]
+    pub url: String,
+
+    #[configurable(derived)]
+    pub auth: Option<AvroSchemaRegistryAuth>,
+}
+
+fn deserialize_schema<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let schema = Option::<String>::deserialize(deserializer)?;
+    if let Some(schema) = &schema {
+        Schema::parse_str(schema)
+            .map_err(|error| serde::de::Error::custom(format!("invalid Avro schema: {}", error)))?;
+    }
+    Ok(schema)
+}
+
+/// Avro schemas fetched from a schema registry, by ID.
+///
+/// Clones of the cache share the same schemas.
+#[derive(Debug, Clone, Default)]
+pub struct AvroSchemaCache {
+    schemas: Arc<RwLock<HashMap<u32, Arc<Schema>>>>,
+}
+
+impl AvroSchemaCache {
+    /// Returns the ID of the schema referenced by a message in the Confluent wire format, if the
+    /// schema isn't cached yet.
+    pub fn missing_schema_id(&self, message: &[u8]) -> Option<u32> {
+        let id = confluent_schema_id(message)?;
+        let schemas = self.schemas.read().expect("lock poisoned");
+        (!schemas.contains_key(&id)).then_some(id)
+    }
+
+    /// Parses and caches the schema registered with an ID.
+    pub fn insert(&self, id: u32, schema: &str) -> vector_common::Result<()> {
+        let schema = Schema::parse_str(schema)
+            .map_err(|error| format!("Invalid Avro schema with ID {}: {}", id, error))?;
+        self.schemas
+            .write()
+            .expect("lock poisoned")
+            .insert(id, Arc::new(schema));
+        Ok(())
+    }
+
+    fn get(&self, id: u32) -> Option<Arc<Schema>> {
+        self.schemas
+            .read()
+            .expect("lock poisoned")
+            .get(&id)
+            .cloned()
+    }
+}
+
+fn confluent_schema_id(mut message: &[u8]) -> Option<u32> {
+    (message.len() >= 5 && message[0] == CONFLUENT_MAGIC_BYTE).then(|| {
+        message.advance(1);
+        message.get_u32()
+    })
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing an Apache Avro message.
+#[derive(Debug, Clone)]
+pub struct AvroDeserializer {
+    format: AvroEncodingFormat,
+    schema: Option<Arc<Schema>>,
+    registry: Option<AvroSchemaCache>,
+}
+
+impl AvroDeserializer {
+    /// Creates a new `AvroDeserializer` reading messages written with `schema`.
+    pub fn new(format: AvroEncodingFormat, schema: Schema) -> Self {
+        Self {
+            format,
+            schema: Some(Arc::new(schema)),
+            registry: None,
+        }
+    }
+
+    /// The cache of the schemas fetched from the schema registry, if one is configured.
+    ///
+    /// The sources supporting the schema registry fill the cache before decoding the messages.
+    pub const fn schema_cache(&self) -> Option<&AvroSchemaCache> {
+        self.registry.as_ref()
+    }
+
+    fn configured_schema(&self) -> vector_common::Result<Arc<Schema>> {
+        self.schema
+            .clone()
+            .ok_or_else(|| "Avro deserializer requires a `schema`".into())
+    }
+}
+
+impl Deserializer for AvroDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        let mut datum = bytes.as_ref();
+        let schema = match self.format {
+            AvroEncodingFormat::Datum => self.configured_schema()?,
+            AvroEncodingFormat::SingleObject => {
+                let schema = self.configured_schema()?;
+                if datum.len() < 10 || datum[..2] != SINGLE_OBJECT_MARKER {
+                    return Err("Avro message isn't in the single-object encoding".into());
+                }
+                if datum[2..10] != schema.fingerprint::<Rabin>().bytes[..] {
+                    return Err("Avro message was written with another schema".into());
+                }
+                datum.advance(10);
+                schema
+            }
+            AvroEncodingFormat::Confluent => {
+                let id = confluent_schema_id(datum)
+                    .ok_or("Avro message isn't in the Confluent wire format")?;
+                datum.advance(5);
+                match &self.registry {
+                    Some(registry) => registry
+                        .get(id)
+                        .ok_or_else(|| format!("Avro schema with ID {} is unknown", id))?,
+                    None => self.configured_schema()?,
+                }
+            }
+        };
+
+        let value = apache_avro::from_avro_datum(&schema, &mut datum, None)
+            .map_err(|error| format!("Error parsing Avro: {}", error))?;
+        let mut log = match to_value(value)? {
+            Value::Object(fields) => LogEvent::from(fields),
+            value => {
+                return Err(format!(
+                    "Attempted to convert a non-record Avro value of type {} into an event.",
+                    value.kind_str()
+                )
+                .into())
+            }
+        };
+
+        if log_namespace == LogNamespace::Legacy {
+            let timestamp_key = log_schema().timestamp_key();
+            if !log.contains(timestamp_key) {
+                log.insert(timestamp_key, Utc::now());
+            }
+        }
+
+        Ok(smallvec![log.into()])
+    }
+}
+
+impl From<&AvroDeserializerConfig> for AvroDeserializer {
+    fn from(config: &AvroDeserializerConfig) -> Self {
+        config.build()
+    }
+}
+
+fn to_value(value: AvroValue) -> vector_common::Result<Value> {
+    Ok(match value {
+        AvroValue::Null => Value::Null,
+        AvroValue::Boolean(boolean) => Value::Boolean(boolean),
+        AvroValue::Int(integer) | AvroValue::Date(integer) | AvroValue::TimeMillis(integer) => {
+            Value::Integer(integer.into())
+        }
+        AvroValue::Long(integer) | AvroValue::TimeMicros(integer) => Value::Integer(integer),
+        AvroValue::Float(float) => NotNan::new(f64::from(float))
+            .map(Value::Float)
+            .unwrap_or(Value::Null),
+        AvroValue::Double(float) => NotNan::new(float).map(Value::Float).unwrap_or(Value::Null),
+        AvroValue::Bytes(bytes) | AvroValue::Fixed(_, bytes) => Value::Bytes(bytes.into()),
+        AvroValue::String(string) | AvroValue::Enum(_, string) => Value::Bytes(string.into()),
+        AvroValue::Uuid(uuid) => Value::Bytes(uuid.to_string().into()),
+        AvroValue::TimestampMillis(millis) => Utc
+            .timestamp_millis_opt(millis)
+            .single()
+            .map_or(Value::Integer(millis), Value::Timestamp),
+        AvroValue::TimestampMicros(micros) => micros
+            .checked_mul(1_000)
+            .map_or(Value::Integer(micros), |nanos| {
+                Value::Timestamp(Utc.timestamp_nanos(nanos))
+            }),
+        AvroValue::Union(_, value) => to_value(*value)?,
+        AvroValue::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .map(to_value)
+                .collect::<vector_common::Result<_>>()?,
+        ),
+        AvroValue::Map(entries) => Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| Ok((key, to_value(value)?)))
+                .collect::<vector_common::Result<_>>()?,
+        ),
+        AvroValue::Record(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| Ok((key, to_value(value)?)))
+                .collect::<vector_common::Result<_>>()?,
+        ),
+        value => return Err(format!("Unsupported Avro value: {:?}", value).into()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "Log",
+        "fields": [
+            { "name": "message", "type": "string" },
+            { "name": "count", "type": ["null", "long"] },
+            { "name": "timestamp", "type": { "type": "long", "logicalType": "timestamp-millis" } },
+            { "name": "tags", "type": { "type": "map", "values": "string" } }
+        ]
+    }"#;
+
+    fn datum() -> Vec<u8> {
+        let schema = Schema::parse_str(SCHEMA).unwrap();
+        let value = AvroValue::Record(vec![
+            ("message".to_owned(), AvroValue::String("hello".to_owned())),
+            (
+                "count".to_owned(),
+                AvroValue::Union(1, Box::new(AvroValue::Long(3))),
+            ),
+            ("timestamp".to_owned(), AvroValue::TimestampMillis(1_500)),
+            (
+                "tags".to_owned(),
+                AvroValue::Map(
+                    [("env".to_owned(), AvroValue::String("prod".to_owned()))]
+                        .into_iter()
+                        .collect(),
+                ),
+            ),
+        ]);
+        apache_avro::to_avro_datum(&schema, value).unwrap()
+    }
+
+    fn deserializer(format: AvroEncodingFormat) -> AvroDeserializer {
+        AvroDeserializerConfig::new(AvroDeserializerOptions {
+            format,
+            schema: Some(SCHEMA.to_owned()),
+            schema_registry: None,
+        })
+        .build()
+    }
+
+    fn parse(deserializer: &AvroDeserializer, message: Vec<u8>) -> LogEvent {
+        let mut events = deserializer
+            .parse(message.into(), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        events.pop().unwrap().into_log()
+    }
+
+    fn assert_log(log: &LogEvent) {
+        assert_eq!(log.get("message"), Some(&Value::from("hello")));
+        assert_eq!(log.get("count"), Some(&Value::from(3)));
+        assert_eq!(
+            log.get("timestamp"),
+            Some(&Value::from(Utc.timestamp_opt(1, 500_000_000).unwrap()))
+        );
+        assert_eq!(log.get("tags.env"), Some(&Value::from("prod")));
+    }
+
+    #[test]
+    fn deserialize_datum() {
+        let log = parse(&deserializer(AvroEncodingFormat::Datum), datum());
+        assert_log(&log);
+    }
+
+    #[test]
+    fn deserialize_single_object() {
+        let schema = Schema::parse_str(SCHEMA).unwrap();
+        let mut message = SINGLE_OBJECT_MARKER.to_vec();
+        message.extend(schema.fingerprint::<Rabin>().bytes);
+        message.extend(datum());
+
+        let log = parse(&deserializer(AvroEncodingFormat::SingleObject), message);
+        assert_log(&log);
+    }
+
+    #[test]
+    fn rejects_single_object_of_other_schema() {
+        let mut message = SINGLE_OBJECT_MARKER.to_vec();
+        message.extend([0; 8]);
+        message.extend(datum());
+
+        assert!(deserializer(AvroEncodingFormat::SingleObject)
+            .parse(message.into(), LogNamespace::Vector)
+            .is_err());
+    }
+
+    #[test]
+    fn deserialize_confluent_with_schema() {
+        let mut message = vec![0, 0, 0, 0, 42];
+        message.extend(datum());
+
+        let log = parse(&deserializer(AvroEncodingFormat::Confluent), message);
+        assert_log(&log);
+    }
+
+    #[test]
+    fn deserialize_confluent_with_registry() {
+        let deserializer = AvroDeserializerConfig::new(AvroDeserializerOptions {
+            format: AvroEncodingFormat::Confluent,
+            schema: None,
+            schema_registry: Some(AvroDeserializerRegistryOptions {
+                url: "http://localhost:8081".to_owned(),
+                auth: None,
+            }),
+        })
+        .build();
+        let mut message = vec![0, 0, 0, 0, 42];
+        message.extend(datum());
+
+        let cache = deserializer.schema_cache().unwrap().clone();
+        assert_eq!(cache.missing_schema_id(&message), Some(42));
+        assert!(deserializer
+            .parse(message.clone().into(), LogNamespace::Vector)
+            .is_err());
+
+        cache.insert(42, SCHEMA).unwrap();
+        assert_eq!(cache.missing_schema_id(&message), None);
+        // Clones of the deserializer share the cache.
+        let log = parse(&deserializer.clone(), message);
+        assert_log(&log);
+    }
+
+    #[test]
+    fn rejects_invalid_schema() {
+        let error = serde_json::from_str::<AvroDeserializerOptions>(r#"{ "schema": "{" }"#)
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("invalid Avro schema"), "{}", error);
+    }
+
+    #[test]
+    fn deserialize_legacy_timestamp() {
+        let schema = r#"{ "type": "record", "name": "Log", "fields": [{ "name": "message", "type": "string" }] }"#;
+        let deserializer = AvroDeserializer::new(
+            AvroEncodingFormat::Datum,
+            Schema::parse_str(schema).unwrap(),
+        );
+
+        let events = deserializer
+            .parse(Bytes::from_static(b"\x0ahello"), LogNamespace::Legacy)
+            .unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log.get("message"), Some(&Value::from("hello")));
+        assert!(log
+            .get(log_schema().timestamp_key())
+            .unwrap()
+            .is_timestamp());
+    }
+}
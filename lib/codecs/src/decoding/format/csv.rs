@@ -0,0 +1,346 @@
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use chrono::Utc;
+use lookup::{
+    lookup_v2::{parse_value_path, ConfigOwnedValuePath},
+    OwnedValuePath, PathPrefix,
+};
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use value::{kind::Collection, Kind};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent},
+    schema,
+};
+
+use super::Deserializer;
+
+/// Config used to build a `CsvDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CsvDeserializerConfig {
+    /// Options for the CSV deserializer.
+    #[serde(default)]
+    pub csv: CsvDeserializerOptions,
+}
+
+impl CsvDeserializerConfig {
+    /// Creates a new `CsvDeserializerConfig`.
+    pub const fn new(csv: CsvDeserializerOptions) -> Self {
+        Self { csv }
+    }
+
+    /// Build the `CsvDeserializer` from this configuration.
+    pub fn build(&self) -> CsvDeserializer {
+        CsvDeserializer::new(
+            self.fields(),
+            self.csv.headers,
+            self.csv.delimiter,
+            self.csv.quote,
+        )
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        let fields = self.fields();
+        let mut definition = match log_namespace {
+            LogNamespace::Legacy => schema::Definition::empty_legacy_namespace().try_with_field(
+                &parse_value_path(log_schema().timestamp_key()).expect("valid timestamp key"),
+                // Like the JSON decoder, the CSV decoder only inserts the timestamp if no column
+                // holds it.
+                Kind::bytes().or_timestamp(),
+                Some("timestamp"),
+            ),
+            LogNamespace::Vector => schema::Definition::new_with_default_metadata(
+                Kind::object(Collection::empty()),
+                [log_namespace],
+            ),
+        };
+        if fields.is_empty() {
+            definition = definition.unknown_fields(Kind::bytes());
+        }
+        // Rows can have fewer columns than there are fields.
+        for field in &fields {
+            definition = definition.optional_field(field, Kind::bytes(), None);
+        }
+        definition
+    }
+
+    fn fields(&self) -> Vec<OwnedValuePath> {
+        self.csv
+            .fields
+            .iter()
+            .map(|field| field.0.clone())
+            .collect()
+    }
+}
+
+/// CSV deserializer options.
+#[configurable_component]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvDeserializerOptions {
+    /// The fields the columns are decoded into, in the order the columns appear in the rows.
+    ///
+    /// Columns beyond the last field are ignored, and fields beyond the last column of a row are
+    /// left out of the event.
+    ///
+    /// If empty, the first row of each stream, such as each file of the `file` source or each
+    /// connection of the `socket` source, is read as a header row, and the columns are decoded into
+    /// the top-level fields named by the header row.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "timestamp", docs::examples = "message"))]
+    pub fields: Vec<ConfigOwnedValuePath>,
+
+    /// Skips the first row of each stream as a header row.
+    ///
+    /// Only relevant if `fields` is set, as the header row is always read if `fields` is empty.
+    #[serde(default)]
+    pub headers: bool,
+
+    /// The ASCII (7-bit) character that delimits the columns.
+    #[serde(default = "default_delimiter", with = "vector_core::serde::ascii_char")]
+    pub delimiter: u8,
+
+    /// The ASCII (7-bit) character that quotes columns holding delimiters, quotes, or line breaks.
+    ///
+    /// Quotes inside quoted columns are escaped by doubling them.
+    #[serde(default = "default_quote", with = "vector_core::serde::ascii_char")]
+    pub quote: u8,
+}
+
+const fn default_delimiter() -> u8 {
+    b','
+}
+
+const fn default_quote() -> u8 {
+    b'"'
+}
+
+impl Default for CsvDeserializerOptions {
+    fn default() -> Self {
+        Self {
+            fields: Vec::new(),
+            headers: false,
+            delimiter: default_delimiter(),
+            quote: default_quote(),
+        }
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing CSV rows.
+///
+/// The header row is read by the deserializer itself, so each stream needs its own deserializer,
+/// which sources get by cloning the deserializer they built.
+#[derive(Debug)]
+pub struct CsvDeserializer {
+    delimiter: u8,
+    quote: u8,
+    /// The fields of the columns, which are `None` until the header row is read.
+    fields: Mutex<Option<Vec<OwnedValuePath>>>,
+    /// Whether the next row is a header row to skip.
+    skip_header: Mutex<bool>,
+}
+
+impl CsvDeserializer {
+    /// Creates a new `CsvDeserializer`.
+    ///
+    /// If `fields` is empty, the fields are read from the header row.
+    pub fn new(fields: Vec<OwnedValuePath>, headers: bool, delimiter: u8, quote: u8) -> Self {
+        let reads_header = fields.is_empty();
+        Self {
+            delimiter,
+            quote,
+            fields: Mutex::new((!reads_header).then_some(fields)),
+            skip_header: Mutex::new(headers && !reads_header),
+        }
+    }
+
+    /// Whether the next row is read as the header row of the stream.
+    pub fn awaits_header(&self) -> bool {
+        self.fields.lock().expect("lock poisoned").is_none()
+            || *self.skip_header.lock().expect("lock poisoned")
+    }
+}
+
+impl Clone for CsvDeserializer {
+    fn clone(&self) -> Self {
+        Self {
+            delimiter: self.delimiter,
+            quote: self.quote,
+            fields: Mutex::new(self.fields.lock().expect("lock poisoned").clone()),
+            skip_header: Mutex::new(*self.skip_header.lock().expect("lock poisoned")),
+        }
+    }
+}
+
+impl Deserializer for CsvDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .from_reader(bytes.as_ref());
+
+        let mut fields = self.fields.lock().expect("lock poisoned");
+        let mut skip_header = self.skip_header.lock().expect("lock poisoned");
+        let mut events = SmallVec::new();
+        for record in reader.byte_records() {
+            let record = record.map_err(|error| format!("Error parsing CSV: {}", error))?;
+
+            let paths = match fields.as_ref() {
+                Some(_) if *skip_header => {
+                    *skip_header = false;
+                    continue;
+                }
+                Some(paths) => paths,
+                None => {
+                    *fields = Some(
+                        record
+                            .iter()
+                            .map(|name| {
+                                OwnedValuePath::single_field(&String::from_utf8_lossy(name))
+                            })
+                            .collect(),
+                    );
+                    continue;
+                }
+            };
+
+            let mut log = LogEvent::default();
+            for (path, column) in paths.iter().zip(record.iter()) {
+                log.insert((PathPrefix::Event, path), Bytes::copy_from_slice(column));
+            }
+            if log_namespace == LogNamespace::Legacy {
+                let timestamp_key = log_schema().timestamp_key();
+                if !log.contains(timestamp_key) {
+                    log.insert(timestamp_key, Utc::now());
+                }
+            }
+            events.push(log.into());
+        }
+
+        Ok(events)
+    }
+}
+
+impl From<&CsvDeserializerConfig> for CsvDeserializer {
+    fn from(config: &CsvDeserializerConfig) -> Self {
+        config.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::Value;
+
+    use super::*;
+
+    fn deserializer(fields: &[&str], headers: bool) -> CsvDeserializer {
+        CsvDeserializerConfig::new(CsvDeserializerOptions {
+            fields: fields
+                .iter()
+                .map(|field| field.to_string().try_into().unwrap())
+                .collect(),
+            headers,
+            ..Default::default()
+        })
+        .build()
+    }
+
+    fn parse(deserializer: &CsvDeserializer, input: &'static str) -> Vec<LogEvent> {
+        deserializer
+            .parse(Bytes::from(input), LogNamespace::Vector)
+            .unwrap()
+            .into_iter()
+            .map(Event::into_log)
+            .collect()
+    }
+
+    #[test]
+    fn deserialize_fixed_fields() {
+        let deserializer = deserializer(&["a", "b.c"], false);
+
+        let events = parse(&deserializer, "1,\"x,\"\"y\"\"\",extra");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].get("a"), Some(&Value::from("1")));
+        assert_eq!(events[0].get("b.c"), Some(&Value::from("x,\"y\"")));
+
+        let events = parse(&deserializer, "2");
+        assert_eq!(events[0].get("a"), Some(&Value::from("2")));
+        assert!(!events[0].contains("b"));
+    }
+
+    #[test]
+    fn deserialize_skipped_header() {
+        let deserializer = deserializer(&["a"], true);
+
+        assert!(parse(&deserializer, "name").is_empty());
+        let events = parse(&deserializer, "1");
+        assert_eq!(events[0].get("a"), Some(&Value::from("1")));
+    }
+
+    #[test]
+    fn deserialize_inferred_header() {
+        let deserializer = deserializer(&[], false);
+        let stream = deserializer.clone();
+
+        assert!(stream.awaits_header());
+        assert!(parse(&stream, "host,message").is_empty());
+        assert!(!stream.awaits_header());
+        let events = parse(&stream, "a,hello\nb,world");
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].get("host"), Some(&Value::from("a")));
+        assert_eq!(events[1].get("message"), Some(&Value::from("world")));
+
+        // Another stream reads its own header row.
+        let stream = deserializer.clone();
+        assert!(parse(&stream, "message").is_empty());
+        let events = parse(&stream, "other");
+        assert_eq!(events[0].get("message"), Some(&Value::from("other")));
+    }
+
+    #[test]
+    fn deserialize_delimiter_and_quote() {
+        let deserializer = CsvDeserializerConfig::new(CsvDeserializerOptions {
+            fields: vec![
+                "a".to_string().try_into().unwrap(),
+                "b".to_string().try_into().unwrap(),
+            ],
+            delimiter: b';',
+            quote: b'\'',
+            ..Default::default()
+        })
+        .build();
+
+        let events = parse(&deserializer, "'x;y';z");
+        assert_eq!(events[0].get("a"), Some(&Value::from("x;y")));
+        assert_eq!(events[0].get("b"), Some(&Value::from("z")));
+    }
+
+    #[test]
+    fn deserialize_legacy_timestamp() {
+        let deserializer = deserializer(&["message"], false);
+
+        let events = deserializer
+            .parse(Bytes::from("hello"), LogNamespace::Legacy)
+            .unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log.get("message"), Some(&Value::from("hello")));
+        assert!(log
+            .get(log_schema().timestamp_key())
+            .unwrap()
+            .is_timestamp());
+    }
+}
@@ -3,20 +3,33 @@
 
 #![deny(missing_docs)]
 
+mod avro;
 mod bytes;
+mod csv;
 mod gelf;
 mod json;
+mod msgpack;
 mod native;
 mod native_json;
+#[cfg(feature = "parquet")]
+mod parquet;
 #[cfg(feature = "syslog")]
 mod syslog;
 
 use ::bytes::Bytes;
+pub use avro::{
+    AvroDeserializer, AvroDeserializerConfig, AvroDeserializerOptions,
+    AvroDeserializerRegistryOptions, AvroEncodingFormat, AvroSchemaCache,
+};
+pub use csv::{CsvDeserializer, CsvDeserializerConfig, CsvDeserializerOptions};
 use dyn_clone::DynClone;
 pub use gelf::{GelfDeserializer, GelfDeserializerConfig};
 pub use json::{JsonDeserializer, JsonDeserializerConfig};
-pub use native::{NativeDeserializer, NativeDeserializerConfig};
+pub use msgpack::{MsgpackDeserializer, MsgpackDeserializerConfig};
+pub use native::{NativeDeserializer, NativeDeserializerConfig, NativeDeserializerOptions};
 pub use native_json::{NativeJsonDeserializer, NativeJsonDeserializerConfig};
+#[cfg(feature = "parquet")]
+pub use parquet::{ParquetDeserializer, ParquetDeserializerConfig, ParquetDeserializerOptions};
 use smallvec::SmallVec;
 use vector_core::config::LogNamespace;
 use vector_core::event::Event;
@@ -0,0 +1,273 @@
+use std::collections::BTreeMap;
+
+use bytes::{Buf, Bytes};
+use chrono::{TimeZone, Utc};
+use lookup::lookup_v2::parse_value_path;
+use ordered_float::NotNan;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use value::Kind;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+use super::Deserializer;
+
+/// Config used to build a `MsgpackDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MsgpackDeserializerConfig;
+
+impl MsgpackDeserializerConfig {
+    /// Creates a new `MsgpackDeserializerConfig`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Build the `MsgpackDeserializer` from this configuration.
+    pub fn build(&self) -> MsgpackDeserializer {
+        MsgpackDeserializer
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        // MessagePack values are a superset of JSON values, adding binary values and timestamps.
+        let kind = Kind::json().or_timestamp();
+        match log_namespace {
+            LogNamespace::Legacy => schema::Definition::empty_legacy_namespace()
+                .unknown_fields(kind)
+                .try_with_field(
+                    &parse_value_path(log_schema().timestamp_key()).expect("valid timestamp key"),
+                    Kind::json().or_timestamp(),
+                    Some("timestamp"),
+                ),
+            LogNamespace::Vector => {
+                schema::Definition::new_with_default_metadata(kind, [log_namespace])
+            }
+        }
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing MessagePack.
+///
+/// The frame can hold several concatenated MessagePack maps, each of them decoded as an event.
+/// Top-level arrays are split into one event per element, like the JSON deserializer does.
+#[derive(Debug, Clone, Default)]
+pub struct MsgpackDeserializer;
+
+impl MsgpackDeserializer {
+    /// Creates a new `MsgpackDeserializer`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Deserializer for MsgpackDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        let mut events = SmallVec::new();
+        let mut reader = bytes.reader();
+        while reader.get_ref().has_remaining() {
+            let value = rmpv::decode::read_value(&mut reader)
+                .map_err(|error| format!("Error parsing MessagePack: {}", error))?;
+            match value {
+                rmpv::Value::Array(values) => {
+                    for value in values {
+                        events.push(to_event(value)?);
+                    }
+                }
+                value => events.push(to_event(value)?),
+            }
+        }
+
+        if log_namespace == LogNamespace::Legacy {
+            let timestamp = Utc::now();
+            let timestamp_key = log_schema().timestamp_key();
+            for event in &mut events {
+                let log = event.as_mut_log();
+                if !log.contains(timestamp_key) {
+                    log.insert(timestamp_key, timestamp);
+                }
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+fn to_event(value: rmpv::Value) -> vector_common::Result<Event> {
+    match to_value(value) {
+        Value::Object(fields) => Ok(LogEvent::from(fields).into()),
+        value => Err(format!(
+            "Attempted to convert a non-map MessagePack value of type {} into an event.",
+            value.kind_str()
+        )
+        .into()),
+    }
+}
+
+/// The type of the MessagePack timestamp extension.
+const TIMESTAMP_EXT_TYPE: i8 = -1;
+
+fn to_value(value: rmpv::Value) -> Value {
+    match value {
+        rmpv::Value::Nil => Value::Null,
+        rmpv::Value::Boolean(boolean) => Value::Boolean(boolean),
+        rmpv::Value::Integer(integer) => integer
+            .as_i64()
+            .map(Value::Integer)
+            // Integers too large for an `i64` are kept as strings, the same as for JSON.
+            .unwrap_or_else(|| Value::Bytes(integer.to_string().into())),
+        rmpv::Value::F32(float) => NotNan::new(f64::from(float))
+            .map(Value::Float)
+            .unwrap_or(Value::Null),
+        rmpv::Value::F64(float) => NotNan::new(float).map(Value::Float).unwrap_or(Value::Null),
+        rmpv::Value::String(string) => Value::Bytes(string.into_bytes().into()),
+        rmpv::Value::Binary(bytes) => Value::Bytes(bytes.into()),
+        rmpv::Value::Array(values) => Value::Array(values.into_iter().map(to_value).collect()),
+        rmpv::Value::Map(entries) => Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        rmpv::Value::String(key) => {
+                            String::from_utf8_lossy(key.as_bytes()).into_owned()
+                        }
+                        key => key.to_string(),
+                    };
+                    (key, to_value(value))
+                })
+                .collect(),
+        ),
+        rmpv::Value::Ext(TIMESTAMP_EXT_TYPE, data) => {
+            to_timestamp(&data).unwrap_or_else(|| ext_value(TIMESTAMP_EXT_TYPE, data))
+        }
+        rmpv::Value::Ext(code, data) => ext_value(code, data),
+    }
+}
+
+/// Decodes the timestamp extension, in any of its 32, 64, or 96 bits formats.
+fn to_timestamp(mut data: &[u8]) -> Option<Value> {
+    let (seconds, nanoseconds) = match data.len() {
+        4 => (i64::from(data.get_u32()), 0),
+        8 => {
+            let value = data.get_u64();
+            ((value & 0x3_ffff_ffff) as i64, (value >> 34) as u32)
+        }
+        12 => {
+            let nanoseconds = data.get_u32();
+            (data.get_i64(), nanoseconds)
+        }
+        _ => return None,
+    };
+    Utc.timestamp_opt(seconds, nanoseconds)
+        .single()
+        .map(Value::Timestamp)
+}
+
+/// Extension types other than timestamps are kept as their type and raw data, the same as the
+/// `fluent` source does.
+fn ext_value(code: i8, data: Vec<u8>) -> Value {
+    let mut fields = BTreeMap::new();
+    fields.insert(
+        "msgpack_extension_code".to_owned(),
+        Value::Integer(code.into()),
+    );
+    fields.insert("bytes".to_owned(), Value::Bytes(data.into()));
+    Value::Object(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(values: &[rmpv::Value]) -> Bytes {
+        let mut bytes = Vec::new();
+        for value in values {
+            rmpv::encode::write_value(&mut bytes, value).unwrap();
+        }
+        bytes.into()
+    }
+
+    fn map(entries: Vec<(&str, rmpv::Value)>) -> rmpv::Value {
+        rmpv::Value::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn deserialize_msgpack() {
+        let input = encode(&[map(vec![
+            ("message", "hello".into()),
+            ("status", 200.into()),
+            ("big", u64::MAX.into()),
+            ("tags", rmpv::Value::Array(vec!["a".into(), "b".into()])),
+            (
+                "time",
+                rmpv::Value::Ext(-1, vec![0, 0, 0, 5, 0, 0, 0, 0, 0x65, 0x53, 0xf1, 0x00]),
+            ),
+        ])]);
+        let deserializer = MsgpackDeserializer::new();
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let events = deserializer.parse(input.clone(), namespace).unwrap();
+            assert_eq!(events.len(), 1);
+
+            let log = events[0].as_log();
+            assert_eq!(log["message"], "hello".into());
+            assert_eq!(log["status"], 200.into());
+            assert_eq!(log["big"], u64::MAX.to_string().into());
+            assert_eq!(log["tags"], vec!["a", "b"].into());
+            assert_eq!(
+                log["time"],
+                Utc.timestamp_opt(1_700_000_000, 5).unwrap().into()
+            );
+            assert_eq!(
+                log.get(log_schema().timestamp_key()).is_some(),
+                namespace == LogNamespace::Legacy
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_concatenated_and_arrays() {
+        let input = encode(&[
+            map(vec![("n", 1.into())]),
+            rmpv::Value::Array(vec![map(vec![("n", 2.into())]), map(vec![("n", 3.into())])]),
+        ]);
+
+        let events = MsgpackDeserializer::new()
+            .parse(input, LogNamespace::Vector)
+            .unwrap();
+
+        let numbers = events
+            .iter()
+            .map(|event| event.as_log()["n"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, vec![1.into(), 2.into(), 3.into()]);
+    }
+
+    #[test]
+    fn deserialize_error_invalid_msgpack() {
+        let deserializer = MsgpackDeserializer::new();
+
+        assert!(deserializer
+            .parse(encode(&["not a map".into()]), LogNamespace::Vector)
+            .is_err());
+        assert!(deserializer
+            .parse(Bytes::from_static(b"\x82\xa1a"), LogNamespace::Vector)
+            .is_err());
+    }
+}
@@ -3,6 +3,7 @@ use prost::Message;
 use serde::{Deserialize, Serialize};
 use smallvec::{smallvec, SmallVec};
 use value::Kind;
+use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 use vector_core::{
     config::DataType,
@@ -11,15 +12,25 @@ use vector_core::{
 };
 
 use super::Deserializer;
+use crate::NativeVersion;
 
 /// Config used to build a `NativeDeserializer`.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
-pub struct NativeDeserializerConfig;
+pub struct NativeDeserializerConfig {
+    /// Options for the native deserializer.
+    #[serde(default)]
+    pub native: NativeDeserializerOptions,
+}
 
 impl NativeDeserializerConfig {
+    /// Creates a new `NativeDeserializerConfig`.
+    pub const fn new(native: NativeDeserializerOptions) -> Self {
+        Self { native }
+    }
+
     /// Build the `NativeDeserializer` from this configuration.
-    pub fn build(&self) -> NativeDeserializer {
-        NativeDeserializer::default()
+    pub const fn build(&self) -> NativeDeserializer {
+        NativeDeserializer::new(self.native.reject_newer_versions)
     }
 
     /// Return the type of event build by this deserializer.
@@ -38,9 +49,33 @@ impl NativeDeserializerConfig {
     }
 }
 
+/// Native deserializer options.
+#[configurable_component]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NativeDeserializerOptions {
+    /// Whether to reject the events encoded with a newer version of the native format.
+    ///
+    /// By default, such events are decoded on a best-effort basis, leaving out the parts of the
+    /// events that this version of Vector doesn't know about, so that Vector instances can be
+    /// upgraded in any order.
+    #[serde(default)]
+    pub reject_newer_versions: bool,
+}
+
 /// Deserializer that builds `Event`s from a byte frame containing Vector's native protobuf format.
 #[derive(Debug, Clone, Default)]
-pub struct NativeDeserializer;
+pub struct NativeDeserializer {
+    reject_newer_versions: bool,
+}
+
+impl NativeDeserializer {
+    /// Creates a new `NativeDeserializer`.
+    pub const fn new(reject_newer_versions: bool) -> Self {
+        Self {
+            reject_newer_versions,
+        }
+    }
+}
 
 impl Deserializer for NativeDeserializer {
     fn parse(
@@ -53,7 +88,17 @@ impl Deserializer for NativeDeserializer {
         if bytes.is_empty() {
             Ok(smallvec![])
         } else {
-            let event_array = EventArray::from(proto::EventArray::decode(bytes)?);
+            let proto = proto::EventArray::decode(bytes)?;
+            // Events encoded before the version was introduced have a version of 0.
+            if self.reject_newer_versions && proto.version > NativeVersion::LATEST.as_u32() {
+                return Err(format!(
+                    "Unsupported native format version {}, the latest supported version is {}",
+                    proto.version,
+                    NativeVersion::LATEST.as_u32()
+                )
+                .into());
+            }
+            let event_array = EventArray::from(proto);
             Ok(event_array.into_events().collect())
         }
     }
@@ -0,0 +1,335 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use lookup::lookup_v2::parse_value_path;
+use ordered_float::NotNan;
+use parquet::{
+    file::reader::{FileReader, SerializedFileReader},
+    record::{Field, Row},
+    schema::types::Type,
+};
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+use super::Deserializer;
+
+/// Config used to build a `ParquetDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ParquetDeserializerConfig {
+    /// Options for the Parquet deserializer.
+    #[serde(default)]
+    pub parquet: ParquetDeserializerOptions,
+}
+
+impl ParquetDeserializerConfig {
+    /// Creates a new `ParquetDeserializerConfig`.
+    pub const fn new(parquet: ParquetDeserializerOptions) -> Self {
+        Self { parquet }
+    }
+
+    /// Build the `ParquetDeserializer` from this configuration.
+    pub fn build(&self) -> ParquetDeserializer {
+        ParquetDeserializer::new(self.parquet.fields.clone())
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        // The columns hold JSON-like values, adding binary values and timestamps.
+        let kind = Kind::json().or_timestamp();
+        match log_namespace {
+            LogNamespace::Legacy => schema::Definition::empty_legacy_namespace()
+                .unknown_fields(kind)
+                .try_with_field(
+                    &parse_value_path(log_schema().timestamp_key()).expect("valid timestamp key"),
+                    Kind::json().or_timestamp(),
+                    Some("timestamp"),
+                ),
+            LogNamespace::Vector => {
+                schema::Definition::new_with_default_metadata(kind, [log_namespace])
+            }
+        }
+    }
+}
+
+/// Parquet deserializer options.
+#[configurable_component]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParquetDeserializerOptions {
+    /// The names of the top-level columns to read.
+    ///
+    /// Only these columns are read from the files, and decoded into the top-level fields of the
+    /// same names. If empty, all the columns are read.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "timestamp", docs::examples = "message"))]
+    pub fields: Vec<String>,
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing a whole Parquet file.
+///
+/// The row groups of the file are read one after the other, and each row is decoded as an event.
+/// As the footer of the file is needed to read it, the frame must hold the whole file, which the
+/// default `bytes` framing of the codec ensures.
+#[derive(Debug, Clone, Default)]
+pub struct ParquetDeserializer {
+    fields: Arc<Vec<String>>,
+}
+
+impl ParquetDeserializer {
+    /// Creates a new `ParquetDeserializer`, reading the columns named by `fields`, or all the
+    /// columns if empty.
+    pub fn new(fields: Vec<String>) -> Self {
+        Self {
+            fields: Arc::new(fields),
+        }
+    }
+
+    /// Projects the schema of the file onto the configured columns.
+    fn projection(&self, schema: &Type) -> vector_common::Result<Option<Type>> {
+        if self.fields.is_empty() {
+            return Ok(None);
+        }
+
+        let mut columns = schema
+            .get_fields()
+            .iter()
+            .filter(|column| self.fields.iter().any(|field| field == column.name()))
+            .cloned()
+            .collect::<Vec<_>>();
+        if columns.is_empty() {
+            return Err("None of the configured fields is a column of the Parquet file.".into());
+        }
+
+        Ok(Some(
+            Type::group_type_builder(schema.name())
+                .with_fields(&mut columns)
+                .build()?,
+        ))
+    }
+}
+
+impl Deserializer for ParquetDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        let reader = SerializedFileReader::new(bytes)
+            .map_err(|error| format!("Error parsing Parquet: {}", error))?;
+        let projection = self.projection(reader.metadata().file_metadata().schema())?;
+
+        let timestamp = Utc::now();
+        let mut events = SmallVec::new();
+        for index in 0..reader.num_row_groups() {
+            let rows = reader
+                .get_row_group(index)
+                .and_then(|row_group| row_group.get_row_iter(projection.clone()))
+                .map_err(|error| format!("Error reading Parquet row group: {}", error))?;
+            for row in rows {
+                let mut log = LogEvent::from(to_fields(&row));
+                if log_namespace == LogNamespace::Legacy {
+                    let timestamp_key = log_schema().timestamp_key();
+                    if !log.contains(timestamp_key) {
+                        log.insert(timestamp_key, timestamp);
+                    }
+                }
+                events.push(log.into());
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+impl From<&ParquetDeserializerConfig> for ParquetDeserializer {
+    fn from(config: &ParquetDeserializerConfig) -> Self {
+        config.build()
+    }
+}
+
+fn to_fields(row: &Row) -> BTreeMap<String, Value> {
+    row.get_column_iter()
+        .map(|(name, field)| (name.clone(), to_value(field)))
+        .collect()
+}
+
+fn to_value(field: &Field) -> Value {
+    match field {
+        Field::Null => Value::Null,
+        Field::Bool(boolean) => Value::Boolean(*boolean),
+        Field::Byte(integer) => Value::Integer(i64::from(*integer)),
+        Field::Short(integer) => Value::Integer(i64::from(*integer)),
+        Field::Int(integer) => Value::Integer(i64::from(*integer)),
+        Field::Long(integer) => Value::Integer(*integer),
+        Field::UByte(integer) => Value::Integer(i64::from(*integer)),
+        Field::UShort(integer) => Value::Integer(i64::from(*integer)),
+        Field::UInt(integer) => Value::Integer(i64::from(*integer)),
+        // Integers too large for an `i64` are kept as strings, the same as for JSON.
+        Field::ULong(integer) => i64::try_from(*integer)
+            .map(Value::Integer)
+            .unwrap_or_else(|_| Value::Bytes(integer.to_string().into())),
+        Field::Float(float) => NotNan::new(f64::from(*float))
+            .map(Value::Float)
+            .unwrap_or(Value::Null),
+        Field::Double(float) => NotNan::new(*float).map(Value::Float).unwrap_or(Value::Null),
+        Field::Str(string) => Value::Bytes(Bytes::copy_from_slice(string.as_bytes())),
+        Field::Bytes(bytes) => Value::Bytes(Bytes::copy_from_slice(bytes.data())),
+        Field::TimestampMillis(millis) => i64::try_from(*millis)
+            .ok()
+            .and_then(|millis| Utc.timestamp_millis_opt(millis).single())
+            .map(Value::Timestamp)
+            .unwrap_or_else(|| Value::Bytes(field.to_string().into())),
+        Field::TimestampMicros(micros) => i64::try_from(*micros)
+            .ok()
+            .and_then(|micros| {
+                Utc.timestamp_opt(
+                    micros.div_euclid(1_000_000),
+                    (micros.rem_euclid(1_000_000) * 1_000) as u32,
+                )
+                .single()
+            })
+            .map(Value::Timestamp)
+            .unwrap_or_else(|| Value::Bytes(field.to_string().into())),
+        Field::Group(row) => Value::Object(to_fields(row)),
+        Field::ListInternal(list) => Value::Array(list.elements().iter().map(to_value).collect()),
+        Field::MapInternal(map) => Value::Object(
+            map.entries()
+                .iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        Field::Str(key) => key.clone(),
+                        key => key.to_string(),
+                    };
+                    (key, to_value(value))
+                })
+                .collect(),
+        ),
+        // Dates, as days since the epoch, and decimals are kept in their display form.
+        field => Value::Bytes(field.to_string().into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parquet::{
+        column::writer::ColumnWriter,
+        data_type::ByteArray,
+        file::{properties::WriterProperties, writer::SerializedFileWriter},
+        schema::parser::parse_message_type,
+    };
+
+    use super::*;
+
+    /// Writes a Parquet file with one row group per slice of rows.
+    fn write(row_groups: &[&[(i64, &str)]]) -> Bytes {
+        let schema = Arc::new(
+            parse_message_type(
+                "message test { REQUIRED INT64 id; REQUIRED BINARY message (UTF8); }",
+            )
+            .unwrap(),
+        );
+        let mut buffer = Vec::new();
+        let mut writer = SerializedFileWriter::new(
+            &mut buffer,
+            schema,
+            Arc::new(WriterProperties::builder().build()),
+        )
+        .unwrap();
+        for rows in row_groups {
+            let mut row_group = writer.next_row_group().unwrap();
+
+            let mut column = row_group.next_column().unwrap().unwrap();
+            if let ColumnWriter::Int64ColumnWriter(writer) = column.untyped() {
+                let ids = rows.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+                writer.write_batch(&ids, None, None).unwrap();
+            }
+            column.close().unwrap();
+
+            let mut column = row_group.next_column().unwrap().unwrap();
+            if let ColumnWriter::ByteArrayColumnWriter(writer) = column.untyped() {
+                let messages = rows
+                    .iter()
+                    .map(|(_, message)| ByteArray::from(*message))
+                    .collect::<Vec<_>>();
+                writer.write_batch(&messages, None, None).unwrap();
+            }
+            column.close().unwrap();
+
+            row_group.close().unwrap();
+        }
+        writer.close().unwrap();
+        buffer.into()
+    }
+
+    fn parse(deserializer: &ParquetDeserializer, bytes: Bytes) -> Vec<LogEvent> {
+        deserializer
+            .parse(bytes, LogNamespace::Vector)
+            .unwrap()
+            .into_iter()
+            .map(Event::into_log)
+            .collect()
+    }
+
+    #[test]
+    fn deserialize_row_groups() {
+        let bytes = write(&[&[(1, "foo"), (2, "bar")], &[(3, "baz")]]);
+
+        let events = parse(&ParquetDeserializer::new(Vec::new()), bytes);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].get("id"), Some(&Value::from(1)));
+        assert_eq!(events[0].get("message"), Some(&Value::from("foo")));
+        assert_eq!(events[2].get("id"), Some(&Value::from(3)));
+        assert_eq!(events[2].get("message"), Some(&Value::from("baz")));
+    }
+
+    #[test]
+    fn deserialize_projected_fields() {
+        let bytes = write(&[&[(1, "foo")]]);
+
+        let events = parse(&ParquetDeserializer::new(vec!["message".to_owned()]), bytes);
+        assert_eq!(events[0].get("message"), Some(&Value::from("foo")));
+        assert!(!events[0].contains("id"));
+    }
+
+    #[test]
+    fn deserialize_unknown_fields() {
+        let bytes = write(&[&[(1, "foo")]]);
+
+        assert!(ParquetDeserializer::new(vec!["other".to_owned()])
+            .parse(bytes, LogNamespace::Vector)
+            .is_err());
+    }
+
+    #[test]
+    fn deserialize_invalid_file() {
+        assert!(ParquetDeserializer::new(Vec::new())
+            .parse(Bytes::from("not parquet"), LogNamespace::Vector)
+            .is_err());
+    }
+
+    #[test]
+    fn deserialize_legacy_timestamp() {
+        let bytes = write(&[&[(1, "foo")]]);
+
+        let events = ParquetDeserializer::new(Vec::new())
+            .parse(bytes, LogNamespace::Legacy)
+            .unwrap();
+        assert!(events[0]
+            .as_log()
+            .get(log_schema().timestamp_key())
+            .unwrap()
+            .is_timestamp());
+    }
+}
@@ -8,10 +8,16 @@ pub mod framing;
 use bytes::{Bytes, BytesMut};
 pub use error::StreamDecodingError;
 pub use format::{
-    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, GelfDeserializer,
-    GelfDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, NativeDeserializer,
-    NativeDeserializerConfig, NativeJsonDeserializer, NativeJsonDeserializerConfig,
+    AvroDeserializer, AvroDeserializerConfig, AvroDeserializerOptions,
+    AvroDeserializerRegistryOptions, AvroEncodingFormat, AvroSchemaCache, BoxedDeserializer,
+    BytesDeserializer, BytesDeserializerConfig, CsvDeserializer, CsvDeserializerConfig,
+    CsvDeserializerOptions, GelfDeserializer, GelfDeserializerConfig, JsonDeserializer,
+    JsonDeserializerConfig, MsgpackDeserializer, MsgpackDeserializerConfig, NativeDeserializer,
+    NativeDeserializerConfig, NativeDeserializerOptions, NativeJsonDeserializer,
+    NativeJsonDeserializerConfig,
 };
+#[cfg(feature = "parquet")]
+pub use format::{ParquetDeserializer, ParquetDeserializerConfig, ParquetDeserializerOptions};
 #[cfg(feature = "syslog")]
 pub use format::{SyslogDeserializer, SyslogDeserializerConfig};
 pub use framing::{
@@ -261,7 +267,11 @@ pub enum DeserializerConfig {
     ///
     /// [vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
     /// [experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
-    Native,
+    Native {
+        /// Native-specific decoding options.
+        #[serde(default)]
+        native: NativeDeserializerOptions,
+    },
 
     /// Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
     ///
@@ -275,6 +285,52 @@ pub enum DeserializerConfig {
     ///
     /// [gelf]: https://docs.graylog.org/docs/gelf
     Gelf,
+
+    /// Decodes the raw bytes as [MessagePack][msgpack].
+    ///
+    /// Each frame can hold several concatenated MessagePack maps, which are decoded as separate
+    /// events. Binary values are decoded as bytes, and values of the timestamp extension type as
+    /// timestamps.
+    ///
+    /// [msgpack]: https://msgpack.org/
+    Msgpack,
+
+    /// Decodes the raw bytes as [CSV][csv] rows.
+    ///
+    /// The columns are decoded as strings into the configured fields, or into the fields named by
+    /// the header row of each stream.
+    ///
+    /// [csv]: https://www.rfc-editor.org/rfc/rfc4180
+    Csv {
+        /// CSV-specific decoding options.
+        #[serde(default)]
+        csv: CsvDeserializerOptions,
+    },
+
+    /// Decodes the raw bytes as an [Apache Avro][apache_avro] message.
+    ///
+    /// Messages can be bare datums, or be in the single-object encoding or the Confluent wire
+    /// format, whose schemas can be fetched from a schema registry by the `kafka` source.
+    ///
+    /// [apache_avro]: https://avro.apache.org/
+    Avro {
+        /// Apache Avro-specific decoding options.
+        #[serde(default)]
+        avro: AvroDeserializerOptions,
+    },
+
+    #[cfg(feature = "parquet")]
+    /// Decodes the raw bytes as an [Apache Parquet][apache_parquet] file.
+    ///
+    /// Each row of the file is decoded as an event, reading the row groups one after the other.
+    /// As Parquet files can only be read as a whole, the default framing of this codec is `bytes`.
+    ///
+    /// [apache_parquet]: https://parquet.apache.org/
+    Parquet {
+        /// Apache Parquet-specific decoding options.
+        #[serde(default)]
+        parquet: ParquetDeserializerOptions,
+    },
 }
 
 impl From<BytesDeserializerConfig> for DeserializerConfig {
@@ -289,6 +345,14 @@ impl From<JsonDeserializerConfig> for DeserializerConfig {
     }
 }
 
+impl From<NativeDeserializerConfig> for DeserializerConfig {
+    fn from(config: NativeDeserializerConfig) -> Self {
+        Self::Native {
+            native: config.native,
+        }
+    }
+}
+
 #[cfg(feature = "syslog")]
 impl From<SyslogDeserializerConfig> for DeserializerConfig {
     fn from(_: SyslogDeserializerConfig) -> Self {
@@ -302,6 +366,33 @@ impl From<GelfDeserializerConfig> for DeserializerConfig {
     }
 }
 
+impl From<MsgpackDeserializerConfig> for DeserializerConfig {
+    fn from(_: MsgpackDeserializerConfig) -> Self {
+        Self::Msgpack
+    }
+}
+
+impl From<CsvDeserializerConfig> for DeserializerConfig {
+    fn from(config: CsvDeserializerConfig) -> Self {
+        Self::Csv { csv: config.csv }
+    }
+}
+
+impl From<AvroDeserializerConfig> for DeserializerConfig {
+    fn from(config: AvroDeserializerConfig) -> Self {
+        Self::Avro { avro: config.avro }
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<ParquetDeserializerConfig> for DeserializerConfig {
+    fn from(config: ParquetDeserializerConfig) -> Self {
+        Self::Parquet {
+            parquet: config.parquet,
+        }
+    }
+}
+
 impl DeserializerConfig {
     /// Build the `Deserializer` from this configuration.
     pub fn build(&self) -> Deserializer {
@@ -312,22 +403,40 @@ impl DeserializerConfig {
             DeserializerConfig::Syslog => {
                 Deserializer::Syslog(SyslogDeserializerConfig::default().build())
             }
-            DeserializerConfig::Native => Deserializer::Native(NativeDeserializerConfig.build()),
+            DeserializerConfig::Native { native } => {
+                Deserializer::Native(NativeDeserializerConfig::new(native.clone()).build())
+            }
             DeserializerConfig::NativeJson => {
                 Deserializer::NativeJson(NativeJsonDeserializerConfig.build())
             }
             DeserializerConfig::Gelf => Deserializer::Gelf(GelfDeserializerConfig.build()),
+            DeserializerConfig::Msgpack => Deserializer::Msgpack(MsgpackDeserializerConfig.build()),
+            DeserializerConfig::Csv { csv } => {
+                Deserializer::Csv(CsvDeserializerConfig::new(csv.clone()).build())
+            }
+            DeserializerConfig::Avro { avro } => {
+                Deserializer::Avro(AvroDeserializerConfig::new(avro.clone()).build())
+            }
+            #[cfg(feature = "parquet")]
+            DeserializerConfig::Parquet { parquet } => {
+                Deserializer::Parquet(ParquetDeserializerConfig::new(parquet.clone()).build())
+            }
         }
     }
 
     /// Return an appropriate default framer for the given deserializer
     pub fn default_stream_framing(&self) -> FramingConfig {
         match self {
-            DeserializerConfig::Native => FramingConfig::LengthDelimited,
+            DeserializerConfig::Native { .. }
+            | DeserializerConfig::Msgpack
+            | DeserializerConfig::Avro { .. } => FramingConfig::LengthDelimited,
+            #[cfg(feature = "parquet")]
+            DeserializerConfig::Parquet { .. } => FramingConfig::Bytes,
             DeserializerConfig::Bytes
             | DeserializerConfig::Json
             | DeserializerConfig::Gelf
-            | DeserializerConfig::NativeJson => FramingConfig::NewlineDelimited {
+            | DeserializerConfig::NativeJson
+            | DeserializerConfig::Csv { .. } => FramingConfig::NewlineDelimited {
                 newline_delimited: Default::default(),
             },
             #[cfg(feature = "syslog")]
@@ -344,9 +453,22 @@ impl DeserializerConfig {
             DeserializerConfig::Json => JsonDeserializerConfig.output_type(),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog => SyslogDeserializerConfig::default().output_type(),
-            DeserializerConfig::Native => NativeDeserializerConfig.output_type(),
+            DeserializerConfig::Native { native } => {
+                NativeDeserializerConfig::new(native.clone()).output_type()
+            }
             DeserializerConfig::NativeJson => NativeJsonDeserializerConfig.output_type(),
             DeserializerConfig::Gelf => GelfDeserializerConfig.output_type(),
+            DeserializerConfig::Msgpack => MsgpackDeserializerConfig.output_type(),
+            DeserializerConfig::Csv { csv } => {
+                CsvDeserializerConfig::new(csv.clone()).output_type()
+            }
+            DeserializerConfig::Avro { avro } => {
+                AvroDeserializerConfig::new(avro.clone()).output_type()
+            }
+            #[cfg(feature = "parquet")]
+            DeserializerConfig::Parquet { parquet } => {
+                ParquetDeserializerConfig::new(parquet.clone()).output_type()
+            }
         }
     }
 
@@ -359,11 +481,26 @@ impl DeserializerConfig {
             DeserializerConfig::Syslog => {
                 SyslogDeserializerConfig::default().schema_definition(log_namespace)
             }
-            DeserializerConfig::Native => NativeDeserializerConfig.schema_definition(log_namespace),
+            DeserializerConfig::Native { native } => {
+                NativeDeserializerConfig::new(native.clone()).schema_definition(log_namespace)
+            }
             DeserializerConfig::NativeJson => {
                 NativeJsonDeserializerConfig.schema_definition(log_namespace)
             }
             DeserializerConfig::Gelf => GelfDeserializerConfig.schema_definition(log_namespace),
+            DeserializerConfig::Msgpack => {
+                MsgpackDeserializerConfig.schema_definition(log_namespace)
+            }
+            DeserializerConfig::Csv { csv } => {
+                CsvDeserializerConfig::new(csv.clone()).schema_definition(log_namespace)
+            }
+            DeserializerConfig::Avro { avro } => {
+                AvroDeserializerConfig::new(avro.clone()).schema_definition(log_namespace)
+            }
+            #[cfg(feature = "parquet")]
+            DeserializerConfig::Parquet { parquet } => {
+                ParquetDeserializerConfig::new(parquet.clone()).schema_definition(log_namespace)
+            }
         }
     }
 
@@ -386,7 +523,12 @@ impl DeserializerConfig {
                         },
                 },
             ) => "application/json",
-            (DeserializerConfig::Native, _) => "application/octet-stream",
+            (DeserializerConfig::Native { .. }, _) => "application/octet-stream",
+            (DeserializerConfig::Avro { .. }, _) => "application/avro",
+            (DeserializerConfig::Msgpack, _) => "application/msgpack",
+            #[cfg(feature = "parquet")]
+            (DeserializerConfig::Parquet { .. }, _) => "application/vnd.apache.parquet",
+            (DeserializerConfig::Csv { .. }, _) => "text/csv",
             (
                 DeserializerConfig::Json
                 | DeserializerConfig::NativeJson
@@ -418,6 +560,15 @@ pub enum Deserializer {
     Boxed(BoxedDeserializer),
     /// Uses a `GelfDeserializer` for deserialization.
     Gelf(GelfDeserializer),
+    /// Uses a `MsgpackDeserializer` for deserialization.
+    Msgpack(MsgpackDeserializer),
+    /// Uses a `CsvDeserializer` for deserialization.
+    Csv(CsvDeserializer),
+    /// Uses an `AvroDeserializer` for deserialization.
+    Avro(AvroDeserializer),
+    #[cfg(feature = "parquet")]
+    /// Uses a `ParquetDeserializer` for deserialization.
+    Parquet(ParquetDeserializer),
 }
 
 impl format::Deserializer for Deserializer {
@@ -435,6 +586,11 @@ impl format::Deserializer for Deserializer {
             Deserializer::NativeJson(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Boxed(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Gelf(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Msgpack(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Csv(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Avro(deserializer) => deserializer.parse(bytes, log_namespace),
+            #[cfg(feature = "parquet")]
+            Deserializer::Parquet(deserializer) => deserializer.parse(bytes, log_namespace),
         }
     }
 }
@@ -0,0 +1,380 @@
+use crate::encoding::BuildError;
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray, TimestampMicrosecondArray,
+    },
+    datatypes::{DataType as ArrowDataType, Field, Schema, SchemaRef, TimeUnit},
+    ipc::writer::StreamWriter,
+    record_batch::RecordBatch,
+};
+use bytes::{BufMut, BytesMut};
+use lookup::{lookup_v2::parse_value_path, OwnedValuePath, PathPrefix};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::DataType,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// Config used to build an `ArrowStreamSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ArrowStreamSerializerConfig {
+    /// Options for the Arrow IPC stream serializer.
+    pub arrow_stream: ArrowStreamSerializerOptions,
+}
+
+impl ArrowStreamSerializerConfig {
+    /// Creates a new `ArrowStreamSerializerConfig`.
+    pub const fn new(arrow_stream: ArrowStreamSerializerOptions) -> Self {
+        Self { arrow_stream }
+    }
+
+    /// Build the `ArrowStreamSerializer` from this configuration.
+    pub fn build(&self) -> Result<ArrowStreamSerializer, BuildError> {
+        if self.arrow_stream.fields.is_empty() {
+            return Err("Arrow stream serializer requires at least one field".into());
+        }
+
+        let mut columns = Vec::with_capacity(self.arrow_stream.fields.len());
+        let mut fields = Vec::with_capacity(self.arrow_stream.fields.len());
+        for field in &self.arrow_stream.fields {
+            let path = parse_value_path(&field.name)
+                .map_err(|error| format!("Invalid Arrow field `{}`: {}", field.name, error))?;
+            columns.push(Field::new(&field.name, field.r#type.data_type(), true));
+            fields.push((path, field.r#type));
+        }
+
+        Ok(ArrowStreamSerializer {
+            schema: Arc::new(Schema::new(columns)),
+            fields,
+        })
+    }
+
+    /// The data type of events that are accepted by `ArrowStreamSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Apache Arrow IPC stream serializer options.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ArrowStreamSerializerOptions {
+    /// The fields to encode, which make up the columns of the schema of the streams, in order.
+    ///
+    /// Fields missing from an event, or holding a null value, are encoded as null values. Events
+    /// with fields holding values that don't match the type of their column fail to encode.
+    pub fields: Vec<ArrowField>,
+}
+
+/// A field encoded as a column of the Arrow record batches.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ArrowField {
+    /// The path of the field, which is also the name of its column.
+    #[configurable(metadata(docs::examples = "message", docs::examples = "status"))]
+    pub name: String,
+
+    /// The type of the column.
+    #[serde(rename = "type")]
+    pub r#type: ArrowFieldType,
+}
+
+/// The type of a column of the Arrow record batches.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArrowFieldType {
+    /// A UTF-8 string.
+    ///
+    /// Values other than strings are encoded as their string representation, with arrays and
+    /// objects encoded as JSON.
+    String,
+
+    /// A 64-bit signed integer.
+    Integer,
+
+    /// A 64-bit floating point number.
+    ///
+    /// Integer values are converted to floating point numbers.
+    Float,
+
+    /// A boolean.
+    Boolean,
+
+    /// A UTC timestamp, with microsecond precision.
+    Timestamp,
+}
+
+impl ArrowFieldType {
+    fn data_type(self) -> ArrowDataType {
+        match self {
+            Self::String => ArrowDataType::Utf8,
+            Self::Integer => ArrowDataType::Int64,
+            Self::Float => ArrowDataType::Float64,
+            Self::Boolean => ArrowDataType::Boolean,
+            Self::Timestamp => {
+                ArrowDataType::Timestamp(TimeUnit::Microsecond, Some("UTC".to_owned()))
+            }
+        }
+    }
+}
+
+/// Serializer that converts batches of events to bytes using the Apache Arrow IPC streaming
+/// format.
+///
+/// Each batch of events is encoded as a complete stream, made of the schema, a single record
+/// batch holding the events, and the end of stream marker. Events encoded on their own are
+/// encoded as a stream holding a single row.
+#[derive(Debug, Clone)]
+pub struct ArrowStreamSerializer {
+    schema: SchemaRef,
+    fields: Vec<(OwnedValuePath, ArrowFieldType)>,
+}
+
+impl ArrowStreamSerializer {
+    /// Encodes the events as an Arrow IPC stream.
+    pub fn encode_batch(
+        &self,
+        events: Vec<Event>,
+        buffer: &mut BytesMut,
+    ) -> Result<(), vector_common::Error> {
+        let logs = events.into_iter().map(Event::into_log).collect::<Vec<_>>();
+        let columns = self
+            .fields
+            .iter()
+            .map(|(path, field_type)| build_column(path, *field_type, &logs))
+            .collect::<Result<Vec<_>, _>>()?;
+        let batch = RecordBatch::try_new(Arc::clone(&self.schema), columns)?;
+
+        let mut stream = Vec::new();
+        let mut writer = StreamWriter::try_new(&mut stream, &self.schema)?;
+        writer.write(&batch)?;
+        writer.finish()?;
+        drop(writer);
+
+        buffer.put_slice(&stream);
+        Ok(())
+    }
+}
+
+impl Encoder<Event> for ArrowStreamSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode_batch(vec![event], buffer)
+    }
+}
+
+/// Builds the column of a field from its values in the rows.
+fn build_column(
+    path: &OwnedValuePath,
+    field_type: ArrowFieldType,
+    rows: &[LogEvent],
+) -> Result<ArrayRef, vector_common::Error> {
+    let values = rows
+        .iter()
+        .map(|log| match log.get((PathPrefix::Event, path)) {
+            None | Some(Value::Null) => None,
+            Some(value) => Some(value),
+        })
+        .collect::<Vec<_>>();
+    let mismatch = |value: &Value| {
+        format!(
+            "Field `{}` of type {:?} can't hold a value of type {}",
+            path,
+            field_type,
+            value.kind_str()
+        )
+    };
+
+    Ok(match field_type {
+        ArrowFieldType::String => Arc::new(StringArray::from(
+            values
+                .into_iter()
+                .map(|value| {
+                    value
+                        .map(|value| match value {
+                            Value::Array(_) | Value::Object(_) => serde_json::to_string(value),
+                            value => Ok(value.to_string_lossy().into_owned()),
+                        })
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        ArrowFieldType::Integer => Arc::new(Int64Array::from(
+            values
+                .into_iter()
+                .map(|value| {
+                    value
+                        .map(|value| match value {
+                            Value::Integer(integer) => Ok(*integer),
+                            value => Err(mismatch(value)),
+                        })
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        ArrowFieldType::Float => Arc::new(Float64Array::from(
+            values
+                .into_iter()
+                .map(|value| {
+                    value
+                        .map(|value| match value {
+                            Value::Float(float) => Ok(float.into_inner()),
+                            Value::Integer(integer) => Ok(*integer as f64),
+                            value => Err(mismatch(value)),
+                        })
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        ArrowFieldType::Boolean => Arc::new(BooleanArray::from(
+            values
+                .into_iter()
+                .map(|value| {
+                    value
+                        .map(|value| match value {
+                            Value::Boolean(boolean) => Ok(*boolean),
+                            value => Err(mismatch(value)),
+                        })
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        ArrowFieldType::Timestamp => Arc::new(
+            TimestampMicrosecondArray::from(
+                values
+                    .into_iter()
+                    .map(|value| {
+                        value
+                            .map(|value| match value {
+                                Value::Timestamp(timestamp) => Ok(timestamp.timestamp_micros()),
+                                value => Err(mismatch(value)),
+                            })
+                            .transpose()
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+            .with_timezone("UTC".to_owned()),
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::{array::Array, ipc::reader::StreamReader};
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn serializer(fields: &[(&str, ArrowFieldType)]) -> ArrowStreamSerializer {
+        ArrowStreamSerializerConfig::new(ArrowStreamSerializerOptions {
+            fields: fields
+                .iter()
+                .map(|(name, r#type)| ArrowField {
+                    name: name.to_string(),
+                    r#type: *r#type,
+                })
+                .collect(),
+        })
+        .build()
+        .unwrap()
+    }
+
+    fn column<T: 'static>(batch: &RecordBatch, index: usize) -> &T {
+        batch.column(index).as_any().downcast_ref::<T>().unwrap()
+    }
+
+    fn read(bytes: BytesMut) -> Vec<RecordBatch> {
+        StreamReader::try_new(bytes.as_ref(), None)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn serialize_batch() {
+        let timestamp = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let events = (0..3)
+            .map(|n| {
+                let mut log = LogEvent::from(format!("message {}", n));
+                log.insert("status", n * 100);
+                log.insert("timestamp", timestamp);
+                if n != 1 {
+                    log.insert("ok", n == 0);
+                }
+                Event::from(log)
+            })
+            .collect();
+        let serializer = serializer(&[
+            ("message", ArrowFieldType::String),
+            ("status", ArrowFieldType::Integer),
+            ("ok", ArrowFieldType::Boolean),
+            ("timestamp", ArrowFieldType::Timestamp),
+        ]);
+        let mut bytes = BytesMut::new();
+
+        serializer.encode_batch(events, &mut bytes).unwrap();
+
+        let batches = read(bytes);
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(column::<StringArray>(batch, 0).value(2), "message 2");
+        assert_eq!(column::<Int64Array>(batch, 1).value(2), 200);
+        let ok = column::<BooleanArray>(batch, 2);
+        assert!(ok.value(0));
+        assert!(ok.is_null(1));
+        assert_eq!(
+            column::<TimestampMicrosecondArray>(batch, 3).value(0),
+            timestamp.timestamp_micros()
+        );
+    }
+
+    #[test]
+    fn serialize_nested_values_as_json() {
+        let mut log = LogEvent::default();
+        log.insert("tags.env", "prod");
+        let serializer = serializer(&[("tags", ArrowFieldType::String)]);
+        let mut bytes = BytesMut::new();
+
+        serializer
+            .encode_batch(vec![log.into()], &mut bytes)
+            .unwrap();
+
+        let batches = read(bytes);
+        assert_eq!(
+            column::<StringArray>(&batches[0], 0).value(0),
+            r#"{"env":"prod"}"#
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_values() {
+        let mut log = LogEvent::default();
+        log.insert("status", "ok");
+        let serializer = serializer(&[("status", ArrowFieldType::Integer)]);
+
+        assert!(serializer
+            .encode_batch(vec![log.into()], &mut BytesMut::new())
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_empty_fields() {
+        let config =
+            ArrowStreamSerializerConfig::new(ArrowStreamSerializerOptions { fields: Vec::new() });
+
+        assert!(config.build().is_err());
+    }
+}
@@ -1,7 +1,10 @@
 use crate::encoding::BuildError;
+use std::num::NonZeroU32;
+
 use bytes::{BufMut, BytesMut};
 use serde::{Deserialize, Serialize};
 use tokio_util::codec::Encoder;
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 use vector_core::{config::DataType, event::Event, schema};
 
@@ -16,15 +19,31 @@ impl AvroSerializerConfig {
     /// Creates a new `AvroSerializerConfig`.
     pub const fn new(schema: String) -> Self {
         Self {
-            avro: AvroSerializerOptions { schema },
+            avro: AvroSerializerOptions {
+                schema: Some(schema),
+                schema_registry: None,
+            },
         }
     }
 
     /// Build the `AvroSerializer` from this configuration.
+    ///
+    /// Schemas fetched from a schema registry are resolved by the sinks supporting them, which
+    /// build the serializer with `AvroSerializer::registered`.
     pub fn build(&self) -> Result<AvroSerializer, BuildError> {
-        let schema = apache_avro::Schema::parse_str(&self.avro.schema)
-            .map_err(|error| format!("Failed building Avro serializer: {}", error))?;
-        Ok(AvroSerializer { schema })
+        match (&self.avro.schema, &self.avro.schema_registry) {
+            (Some(schema), None) => Ok(AvroSerializer {
+                schema: parse_schema(schema)?,
+                schema_id: None,
+            }),
+            (Some(_), Some(_)) => {
+                Err("Avro serializer can't have both a `schema` and a `schema_registry`".into())
+            }
+            (None, Some(_)) => Err("Avro schema registry isn't supported by this sink".into()),
+            (None, None) => {
+                Err("Avro serializer requires either a `schema` or a `schema_registry`".into())
+            }
+        }
     }
 
     /// The data type of events that are accepted by `AvroSerializer`.
@@ -44,22 +63,95 @@ impl AvroSerializerConfig {
 #[derive(Clone, Debug)]
 pub struct AvroSerializerOptions {
     /// The Avro schema.
+    ///
+    /// Either `schema` or `schema_registry` must be set.
     #[configurable(metadata(
         docs::examples = r#"{ "type": "record", "name": "log", "fields": [{ "name": "message", "type": "string" }] }"#
     ))]
-    pub schema: String,
+    pub schema: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_registry: Option<AvroSchemaRegistryOptions>,
+}
+
+/// Confluent-compatible schema registry options.
+///
+/// The schema is fetched from the registry when the sink starts, and each event is prefixed with
+/// the [wire format][wire_format] header referencing it: a zero magic byte followed by the ID of
+/// the schema, as a 32-bit unsigned integer, big endian.
+///
+/// The schema registry is only supported by the `kafka` sink.
+///
+/// [wire_format]: https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AvroSchemaRegistryOptions {
+    /// The URL of the schema registry.
+    #[configurable(metadata(docs::examples = "http://localhost:8081"))]
+    pub url: String,
+
+    /// The subject the schema is registered under.
+    ///
+    /// With the default subject name strategy of Kafka clients, the subject of the schema of the
+    /// values of a topic is the name of the topic suffixed with `-value`.
+    #[configurable(metadata(docs::examples = "logs-value"))]
+    pub subject: String,
+
+    /// The version of the schema.
+    ///
+    /// Defaults to the latest version registered under the subject.
+    #[configurable(metadata(docs::examples = 3))]
+    pub version: Option<NonZeroU32>,
+
+    #[configurable(derived)]
+    pub auth: Option<AvroSchemaRegistryAuth>,
+}
+
+/// Basic authentication to the schema registry.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AvroSchemaRegistryAuth {
+    /// The basic authentication username.
+    #[configurable(metadata(docs::examples = "${SCHEMA_REGISTRY_USERNAME}"))]
+    pub user: String,
+
+    /// The basic authentication password.
+    #[configurable(metadata(docs::examples = "${SCHEMA_REGISTRY_PASSWORD}"))]
+    pub password: SensitiveString,
+}
+
+fn parse_schema(schema: &str) -> Result<apache_avro::Schema, BuildError> {
+    apache_avro::Schema::parse_str(schema)
+        .map_err(|error| format!("Failed building Avro serializer: {}", error).into())
 }
 
 /// Serializer that converts an `Event` to bytes using the Apache Avro format.
 #[derive(Debug, Clone)]
 pub struct AvroSerializer {
     schema: apache_avro::Schema,
+    /// The ID of the schema in a schema registry, written before the events when set.
+    schema_id: Option<u32>,
 }
 
 impl AvroSerializer {
     /// Creates a new `AvroSerializer`.
     pub const fn new(schema: apache_avro::Schema) -> Self {
-        Self { schema }
+        Self {
+            schema,
+            schema_id: None,
+        }
+    }
+
+    /// Creates a new `AvroSerializer` for a schema fetched from a schema registry, prefixing the
+    /// events with the wire format header referencing the schema.
+    pub fn registered(schema: &str, schema_id: u32) -> Result<Self, BuildError> {
+        Ok(Self {
+            schema: parse_schema(schema)?,
+            schema_id: Some(schema_id),
+        })
     }
 }
 
@@ -71,6 +163,10 @@ impl Encoder<Event> for AvroSerializer {
         let value = apache_avro::to_value(log)?;
         let value = value.resolve(&self.schema)?;
         let bytes = apache_avro::to_avro_datum(&self.schema, value)?;
+        if let Some(schema_id) = self.schema_id {
+            buffer.put_u8(0);
+            buffer.put_u32(schema_id);
+        }
         buffer.put_slice(&bytes);
         Ok(())
     }
@@ -111,4 +207,30 @@ mod tests {
 
         assert_eq!(bytes.freeze(), b"\0\x06bar".as_slice());
     }
+
+    #[test]
+    fn serialize_avro_registered() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "foo" => Value::from("bar")
+        }));
+        let schema = r#"{ "type": "record", "name": "Log", "fields": [{ "name": "foo", "type": ["string"] }] }"#;
+        let mut serializer = AvroSerializer::registered(schema, 42).unwrap();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event, &mut bytes).unwrap();
+
+        assert_eq!(bytes.freeze(), b"\0\0\0\0\x2a\0\x06bar".as_slice());
+    }
+
+    #[test]
+    fn rejects_missing_schema() {
+        let config = AvroSerializerConfig {
+            avro: AvroSerializerOptions {
+                schema: None,
+                schema_registry: None,
+            },
+        };
+
+        assert!(config.build().is_err());
+    }
 }
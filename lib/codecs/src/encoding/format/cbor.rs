@@ -0,0 +1,217 @@
+use bytes::{BufMut, BytesMut};
+use ciborium_ll::Header;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::DataType,
+    event::{Event, Value},
+    schema,
+};
+
+/// Config used to build a `CborSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CborSerializerConfig {
+    /// Options for the CBOR serializer.
+    #[serde(default)]
+    pub cbor: CborSerializerOptions,
+}
+
+impl CborSerializerConfig {
+    /// Creates a new `CborSerializerConfig`.
+    pub const fn new(cbor: CborSerializerOptions) -> Self {
+        Self { cbor }
+    }
+
+    /// Build the `CborSerializer` from this configuration.
+    pub const fn build(&self) -> CborSerializer {
+        CborSerializer {
+            packed: self.cbor.packed,
+            sequence: self.cbor.sequence,
+        }
+    }
+
+    /// The data type of events that are accepted by `CborSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// CBOR serializer options.
+#[configurable_component]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CborSerializerOptions {
+    /// Encode maps and arrays with their definite length.
+    ///
+    /// By default, maps and arrays are encoded with an indefinite length, terminated by a break
+    /// marker. Definite lengths make for smaller items, which are simpler to decode on constrained
+    /// devices.
+    #[serde(default)]
+    pub packed: bool,
+
+    /// Encode batches of events as a [CBOR sequence][cbor_seq].
+    ///
+    /// The items of a sequence are concatenated without any framing, so events are framed with
+    /// the `bytes` framing by default, rather than with the `length_delimited` one.
+    ///
+    /// [cbor_seq]: https://www.rfc-editor.org/rfc/rfc8742
+    #[serde(default)]
+    pub sequence: bool,
+}
+
+/// Serializer that converts an `Event` to bytes using the CBOR format.
+///
+/// Strings that aren't valid UTF-8 are encoded as byte strings, and timestamps as epoch-based
+/// date/time values.
+#[derive(Debug, Clone)]
+pub struct CborSerializer {
+    packed: bool,
+    sequence: bool,
+}
+
+impl CborSerializer {
+    /// Creates a new `CborSerializer`.
+    pub const fn new(packed: bool, sequence: bool) -> Self {
+        Self { packed, sequence }
+    }
+
+    /// Whether batches of events are encoded as a CBOR sequence.
+    pub const fn is_sequence(&self) -> bool {
+        self.sequence
+    }
+}
+
+impl Encoder<Event> for CborSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        let mut encoder = ciborium_ll::Encoder::from(buffer.writer());
+        encode_value(&mut encoder, log.value(), self.packed)?;
+        Ok(())
+    }
+}
+
+/// The tag of epoch-based date/time values.
+const EPOCH_DATE_TIME_TAG: u64 = 1;
+
+type CborEncoder<'a> = ciborium_ll::Encoder<bytes::buf::Writer<&'a mut BytesMut>>;
+
+fn encode_value(encoder: &mut CborEncoder<'_>, value: &Value, packed: bool) -> std::io::Result<()> {
+    match value {
+        Value::Bytes(bytes) => match std::str::from_utf8(bytes) {
+            Ok(string) => encoder.text(string, None),
+            Err(_) => encoder.bytes(bytes, None),
+        },
+        Value::Regex(regex) => encoder.text(regex.as_str(), None),
+        Value::Integer(integer) => encoder.push(integer_header(*integer)),
+        // Floats are encoded in the shortest of their half, single, and double precision forms
+        // that holds them without loss.
+        Value::Float(float) => encoder.push(Header::Float(float.into_inner())),
+        Value::Boolean(boolean) => encoder.push(Header::Simple(if *boolean {
+            ciborium_ll::simple::TRUE
+        } else {
+            ciborium_ll::simple::FALSE
+        })),
+        Value::Timestamp(timestamp) => {
+            encoder.push(Header::Tag(EPOCH_DATE_TIME_TAG))?;
+            match timestamp.timestamp_subsec_nanos() {
+                0 => encoder.push(integer_header(timestamp.timestamp())),
+                nanoseconds => encoder.push(Header::Float(
+                    timestamp.timestamp() as f64 + f64::from(nanoseconds) / 1e9,
+                )),
+            }
+        }
+        Value::Object(object) => {
+            encoder.push(Header::Map(packed.then_some(object.len())))?;
+            for (key, value) in object {
+                encoder.text(key, None)?;
+                encode_value(encoder, value, packed)?;
+            }
+            end_indefinite(encoder, packed)
+        }
+        Value::Array(array) => {
+            encoder.push(Header::Array(packed.then_some(array.len())))?;
+            for value in array {
+                encode_value(encoder, value, packed)?;
+            }
+            end_indefinite(encoder, packed)
+        }
+        Value::Null => encoder.push(Header::Simple(ciborium_ll::simple::NULL)),
+    }
+}
+
+const fn integer_header(integer: i64) -> Header {
+    if integer < 0 {
+        // Negative integers are encoded as `-1 - n`, which is the bitwise complement of `n`.
+        Header::Negative(!integer as u64)
+    } else {
+        Header::Positive(integer as u64)
+    }
+}
+
+fn end_indefinite(encoder: &mut CborEncoder<'_>, packed: bool) -> std::io::Result<()> {
+    if packed {
+        Ok(())
+    } else {
+        encoder.push(Header::Break)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn serialize(packed: bool, log: LogEvent) -> BytesMut {
+        let mut bytes = BytesMut::new();
+        CborSerializer::new(packed, false)
+            .encode(log.into(), &mut bytes)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn serialize_indefinite_length() {
+        let mut log = LogEvent::default();
+        log.insert("a", 1);
+        log.insert("b", vec![-2]);
+
+        assert_eq!(
+            serialize(false, log).as_ref(),
+            b"\xbf\x61a\x01\x61b\x9f\x21\xff\xff"
+        );
+    }
+
+    #[test]
+    fn serialize_packed() {
+        let mut log = LogEvent::default();
+        log.insert("a", 1);
+        log.insert("b", vec![-2]);
+        log.insert("c", bytes::Bytes::from_static(b"\xff"));
+
+        assert_eq!(
+            serialize(true, log).as_ref(),
+            b"\xa3\x61a\x01\x61b\x81\x21\x61c\x41\xff"
+        );
+    }
+
+    #[test]
+    fn serialize_timestamps() {
+        let mut log = LogEvent::default();
+        log.insert("t", Utc.timestamp_opt(1_700_000_000, 0).unwrap());
+        log.insert("u", Utc.timestamp_opt(1, 500_000_000).unwrap());
+
+        assert_eq!(
+            serialize(true, log).as_ref(),
+            b"\xa2\x61t\xc1\x1a\x65\x53\xf1\x00\x61u\xc1\xf9\x3e\x00"
+        );
+    }
+}
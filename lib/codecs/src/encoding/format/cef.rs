@@ -0,0 +1,360 @@
+use crate::encoding::BuildError;
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use bytes::BytesMut;
+use lookup::{lookup_v2::parse_value_path, OwnedValuePath, PathPrefix};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::DataType,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// Config used to build a `CefSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CefSerializerConfig {
+    /// Options for the CEF serializer.
+    pub cef: CefSerializerOptions,
+}
+
+impl CefSerializerConfig {
+    /// Creates a new `CefSerializerConfig`.
+    pub const fn new(cef: CefSerializerOptions) -> Self {
+        Self { cef }
+    }
+
+    /// Build the `CefSerializer` from this configuration.
+    pub fn build(&self) -> Result<CefSerializer, BuildError> {
+        let parse_path = |path: &str| {
+            parse_value_path(path)
+                .map_err(|error| format!("Invalid CEF field path `{}`: {}", path, error))
+        };
+
+        if self.cef.default_severity > MAX_SEVERITY {
+            return Err(format!(
+                "CEF default severity must be between 0 and {}, got {}",
+                MAX_SEVERITY, self.cef.default_severity
+            )
+            .into());
+        }
+        if let Some((value, severity)) = self
+            .cef
+            .severity_mapping
+            .iter()
+            .find(|(_, severity)| **severity > MAX_SEVERITY)
+        {
+            return Err(format!(
+                "CEF severity of `{}` must be between 0 and {}, got {}",
+                value, MAX_SEVERITY, severity
+            )
+            .into());
+        }
+
+        let extensions = self
+            .cef
+            .extensions
+            .iter()
+            .map(|(key, path)| {
+                if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric()) {
+                    return Err(format!(
+                        "CEF extension key `{}` must only hold ASCII letters and digits",
+                        key
+                    ));
+                }
+                Ok((key.clone(), parse_path(path)?))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let header = [
+            &self.cef.device_vendor,
+            &self.cef.device_product,
+            &self.cef.device_version,
+            &self.cef.device_event_class_id,
+        ]
+        .iter()
+        .map(|field| escape_header(field))
+        .collect::<Vec<_>>()
+        .join("|");
+
+        Ok(CefSerializer {
+            header,
+            name_key: parse_path(&self.cef.name_key)?,
+            severity_key: parse_path(&self.cef.severity_key)?,
+            severity_mapping: self.cef.severity_mapping.clone(),
+            default_severity: self.cef.default_severity,
+            extensions,
+        })
+    }
+
+    /// The data type of events that are accepted by `CefSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// The highest CEF severity.
+const MAX_SEVERITY: u8 = 10;
+
+/// CEF serializer options.
+#[configurable_component]
+#[derive(Debug, Clone)]
+pub struct CefSerializerOptions {
+    /// The vendor of the device that produced the events.
+    #[configurable(metadata(docs::examples = "Acme"))]
+    pub device_vendor: String,
+
+    /// The product that produced the events.
+    #[configurable(metadata(docs::examples = "Firewall"))]
+    pub device_product: String,
+
+    /// The version of the product that produced the events.
+    #[configurable(metadata(docs::examples = "1.0"))]
+    pub device_version: String,
+
+    /// The identifier of the type of the events.
+    #[configurable(metadata(docs::examples = "100", docs::examples = "login"))]
+    pub device_event_class_id: String,
+
+    /// The field holding the human-readable description of events, used as their name.
+    #[serde(default = "default_name_key")]
+    #[configurable(metadata(docs::examples = "message", docs::examples = "event.name"))]
+    pub name_key: String,
+
+    /// The field holding the severity of events.
+    ///
+    /// Integers from 0 to 10 are used as the severity as is, and other values are mapped with
+    /// `severity_mapping`.
+    #[serde(default = "default_severity_key")]
+    #[configurable(metadata(docs::examples = "severity", docs::examples = "level"))]
+    pub severity_key: String,
+
+    /// A mapping of the values of the severity field to CEF severities, from 0 to 10.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "A CEF severity, from 0 to 10."
+    ))]
+    #[configurable(metadata(docs::examples = "example_severity_mapping()"))]
+    pub severity_mapping: BTreeMap<String, u8>,
+
+    /// The CEF severity of events whose severity field is missing, or holds a value that isn't
+    /// mapped by `severity_mapping`.
+    #[serde(default = "default_severity")]
+    pub default_severity: u8,
+
+    /// A mapping of CEF extension keys to the fields holding their values.
+    ///
+    /// Extensions whose field is missing from an event, or holds a null value, are left out.
+    /// Timestamps are encoded as milliseconds since the Unix epoch.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The path of the field holding the value of the extension."
+    ))]
+    #[configurable(metadata(docs::examples = "example_extensions()"))]
+    pub extensions: BTreeMap<String, String>,
+}
+
+fn default_name_key() -> String {
+    "message".to_owned()
+}
+
+fn default_severity_key() -> String {
+    "severity".to_owned()
+}
+
+const fn default_severity() -> u8 {
+    5
+}
+
+fn example_severity_mapping() -> BTreeMap<String, u8> {
+    BTreeMap::from([
+        ("info".to_owned(), 3),
+        ("warning".to_owned(), 6),
+        ("error".to_owned(), 8),
+    ])
+}
+
+fn example_extensions() -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("src".to_owned(), "client.ip".to_owned()),
+        ("suser".to_owned(), "user.name".to_owned()),
+        ("rt".to_owned(), "timestamp".to_owned()),
+    ])
+}
+
+/// Serializer that converts an `Event` to bytes using the [ArcSight Common Event Format][cef].
+///
+/// [cef]: https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors-8.3/cef-implementation-standard/
+#[derive(Debug, Clone)]
+pub struct CefSerializer {
+    /// The escaped device vendor, product, version, and event class ID, joined by pipes.
+    header: String,
+    name_key: OwnedValuePath,
+    severity_key: OwnedValuePath,
+    severity_mapping: BTreeMap<String, u8>,
+    default_severity: u8,
+    extensions: Vec<(String, OwnedValuePath)>,
+}
+
+impl CefSerializer {
+    fn severity(&self, log: &LogEvent) -> u8 {
+        match log.get((PathPrefix::Event, &self.severity_key)) {
+            Some(Value::Integer(severity)) if (0..=i64::from(MAX_SEVERITY)).contains(severity) => {
+                *severity as u8
+            }
+            Some(value) => self
+                .severity_mapping
+                .get(value.to_string_lossy().as_ref())
+                .copied()
+                .unwrap_or(self.default_severity),
+            None => self.default_severity,
+        }
+    }
+}
+
+impl Encoder<Event> for CefSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        let name = log
+            .get((PathPrefix::Event, &self.name_key))
+            .map(|name| escape_header(&name.to_string_lossy()))
+            .unwrap_or_default();
+
+        write!(
+            buffer,
+            "CEF:0|{}|{}|{}|",
+            self.header,
+            name,
+            self.severity(&log)
+        )?;
+
+        let mut separator = "";
+        for (key, path) in &self.extensions {
+            let value = match log.get((PathPrefix::Event, path)) {
+                None | Some(Value::Null) => continue,
+                Some(Value::Timestamp(timestamp)) => timestamp.timestamp_millis().to_string(),
+                Some(value) => escape_extension(&value.to_string_lossy()),
+            };
+            write!(buffer, "{}{}={}", separator, key, value)?;
+            separator = " ";
+        }
+
+        Ok(())
+    }
+}
+
+/// Escapes pipes and backslashes of header fields, which can't span several lines.
+pub(super) fn escape_header(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | '|' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\r' | '\n' => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes equal signs, backslashes, and line breaks of extension values.
+fn escape_extension(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | '=' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\r' => escaped.push_str("\\r"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn options() -> CefSerializerOptions {
+        CefSerializerOptions {
+            device_vendor: "Acme".to_owned(),
+            device_product: "Fire|wall".to_owned(),
+            device_version: "1.0".to_owned(),
+            device_event_class_id: "login".to_owned(),
+            name_key: default_name_key(),
+            severity_key: default_severity_key(),
+            severity_mapping: example_severity_mapping(),
+            default_severity: default_severity(),
+            extensions: example_extensions(),
+        }
+    }
+
+    fn serialize(options: CefSerializerOptions, log: LogEvent) -> String {
+        let mut bytes = BytesMut::new();
+        CefSerializerConfig::new(options)
+            .build()
+            .unwrap()
+            .encode(log.into(), &mut bytes)
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn serialize_cef() {
+        let mut log = LogEvent::from("User logged in");
+        log.insert("severity", "warning");
+        log.insert("client.ip", "10.0.0.1");
+        log.insert("user.name", "a=b\\c\nd");
+        log.insert("timestamp", Utc.timestamp_opt(1_700_000_000, 0).unwrap());
+
+        assert_eq!(
+            serialize(options(), log),
+            "CEF:0|Acme|Fire\\|wall|1.0|login|User logged in|6|rt=1700000000000 src=10.0.0.1 suser=a\\=b\\\\c\\nd"
+        );
+    }
+
+    #[test]
+    fn serialize_severities() {
+        let severity = |value: Option<Value>| {
+            let mut log = LogEvent::from("event");
+            if let Some(value) = value {
+                log.insert("severity", value);
+            }
+            let cef = serialize(options(), log);
+            cef.rsplit('|').nth(1).unwrap().to_owned()
+        };
+
+        assert_eq!(severity(Some(Value::from(9))), "9");
+        assert_eq!(severity(Some(Value::from("error"))), "8");
+        assert_eq!(severity(Some(Value::from(42))), "5");
+        assert_eq!(severity(None), "5");
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let mut invalid_key = options();
+        invalid_key
+            .extensions
+            .insert("bad key".to_owned(), "message".to_owned());
+        let mut invalid_severity = options();
+        invalid_severity.default_severity = 11;
+
+        assert!(CefSerializerConfig::new(invalid_key).build().is_err());
+        assert!(CefSerializerConfig::new(invalid_severity).build().is_err());
+    }
+}
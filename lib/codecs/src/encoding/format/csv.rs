@@ -0,0 +1,684 @@
+use crate::encoding::BuildError;
+use std::io;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use csv_core::WriteResult;
+use lookup::{lookup_v2::parse_value_path, OwnedValuePath, PathPrefix};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::DataType,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// Config used to build a `CsvSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CsvSerializerConfig {
+    /// Options for the CSV serializer.
+    pub csv: CsvSerializerOptions,
+}
+
+impl CsvSerializerConfig {
+    /// Creates a new `CsvSerializerConfig`.
+    pub const fn new(csv: CsvSerializerOptions) -> Self {
+        Self { csv }
+    }
+
+    /// Build the `CsvSerializer` from this configuration.
+    pub fn build(&self) -> Result<CsvSerializer, BuildError> {
+        let fields = self
+            .csv
+            .fields
+            .iter()
+            .map(|field| {
+                parse_value_path(field)
+                    .map_err(|error| format!("Invalid CSV field `{}`: {}", field, error))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let terminator = self.csv.terminator;
+        let (delimiter, quote) = (self.csv.delimiter, self.csv.quote);
+        let discovering = fields.is_empty();
+        let header = (self.csv.headers && !discovering)
+            .then(|| header_row(terminator, delimiter, quote, &self.csv.fields))
+            .transpose()?;
+        Ok(CsvSerializer {
+            fields,
+            discovering,
+            headers: self.csv.headers,
+            header,
+            terminator,
+            delimiter,
+            quote,
+            nested_value_handling: self.csv.nested_value_handling.clone(),
+            null_value: Bytes::copy_from_slice(self.csv.null_value.as_bytes()),
+            missing_value: Bytes::copy_from_slice(self.csv.missing_value.as_bytes()),
+            writer: terminator.record_writer(delimiter, quote),
+        })
+    }
+
+    /// The data type of events that are accepted by `CsvSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// CSV serializer options.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct CsvSerializerOptions {
+    /// The fields to encode, in the order they appear in the output.
+    ///
+    /// Fields missing from the event are encoded as `missing_value`, fields holding null values are
+    /// encoded as `null_value`, and fields holding arrays, objects, or regular expressions are
+    /// encoded according to `nested_value_handling`.
+    ///
+    /// If empty, the fields are discovered from the first event of each batch: the top-level fields
+    /// of that event, ordered by name, are encoded for the events of the batch. This is useful for
+    /// ad-hoc exports of events whose fields aren't known ahead of time.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "timestamp", docs::examples = "message"))]
+    pub fields: Vec<String>,
+
+    /// Emits a header row with the names of the fields before the events.
+    ///
+    /// The header row is written once at the start of each batch of events, so sinks writing each
+    /// batch as an object, such as `aws_s3`, write it once per object, while the `file` sink writes
+    /// it once at the start of each file.
+    ///
+    /// When the fields are discovered, the header row is written along with the first event of the
+    /// batch, once its fields are known.
+    #[serde(default)]
+    pub headers: bool,
+
+    /// The terminator written after each record.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub terminator: CsvTerminator,
+
+    /// The ASCII (7-bit) character written between the fields of a record.
+    #[serde(default = "default_delimiter", with = "vector_core::serde::ascii_char")]
+    pub delimiter: u8,
+
+    /// The ASCII (7-bit) character that quotes fields holding delimiters, quotes, or line breaks.
+    ///
+    /// Quotes inside quoted fields are escaped by doubling them.
+    #[serde(default = "default_quote", with = "vector_core::serde::ascii_char")]
+    pub quote: u8,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub nested_value_handling: CsvNestedValueHandling,
+
+    /// The placeholder written for fields holding a null value.
+    ///
+    /// Defaults to an empty string, which can't be told apart from an empty string value. A
+    /// placeholder such as `\N`, expected by MySQL's `LOAD DATA`, lets consumers distinguish them.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    #[configurable(metadata(docs::examples = "\\N", docs::examples = "NULL"))]
+    pub null_value: String,
+
+    /// The placeholder written for fields missing from the event.
+    ///
+    /// Defaults to an empty string, which can't be told apart from an empty string value.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    #[configurable(metadata(docs::examples = "\\N", docs::examples = "NULL"))]
+    pub missing_value: String,
+}
+
+const fn default_delimiter() -> u8 {
+    b','
+}
+
+const fn default_quote() -> u8 {
+    b'"'
+}
+
+/// How the CSV serializer encodes fields holding arrays, objects, or regular expressions.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+#[configurable(metadata(
+    docs::enum_tag_description = "The strategy for encoding arrays, objects, and regular expressions."
+))]
+pub enum CsvNestedValueHandling {
+    /// Encodes the field as an empty string.
+    #[default]
+    Empty,
+
+    /// Encodes the field as JSON.
+    Json,
+
+    /// Encodes the values nested in the field, joined by a delimiter.
+    ///
+    /// Nested arrays and objects are flattened, and the values of objects are ordered by key.
+    FlattenJoin {
+        /// The delimiter written between the nested values.
+        #[configurable(metadata(docs::examples = "|", docs::examples = ";"))]
+        delimiter: String,
+    },
+}
+
+impl CsvNestedValueHandling {
+    /// The bytes the value of a field is encoded as.
+    fn field_bytes(&self, value: Option<&Value>) -> Result<Bytes, vector_common::Error> {
+        let value = match value {
+            Some(value @ (Value::Array(_) | Value::Object(_) | Value::Regex(_))) => value,
+            value => return Ok(scalar_bytes(value)),
+        };
+        Ok(match self {
+            Self::Empty => Bytes::new(),
+            Self::Json => serde_json::to_vec(value)?.into(),
+            Self::FlattenJoin { delimiter } => {
+                let mut leaves = Vec::new();
+                flatten(value, &mut leaves);
+                let leaves = leaves
+                    .into_iter()
+                    .map(|leaf| match leaf {
+                        Value::Regex(_) => leaf.to_string_lossy().into_owned().into(),
+                        leaf => scalar_bytes(Some(leaf)),
+                    })
+                    .collect::<Vec<_>>();
+                leaves.join(delimiter.as_bytes()).into()
+            }
+        })
+    }
+}
+
+/// The terminator of CSV records.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvTerminator {
+    /// Records aren't terminated, leaving their separation to the framing, which defaults to
+    /// `newline_delimited`.
+    #[default]
+    None,
+
+    /// Records are terminated by a line feed (`\n`).
+    Lf,
+
+    /// Records are terminated by a carriage return followed by a line feed (`\r\n`), as expected by
+    /// Windows consumers.
+    ///
+    /// This is the terminator defined by [RFC 4180][rfc4180].
+    ///
+    /// [rfc4180]: https://www.rfc-editor.org/rfc/rfc4180
+    Crlf,
+}
+
+impl CsvTerminator {
+    /// Creates a CSV writer terminating records with this terminator, or with a line feed if
+    /// records aren't terminated.
+    fn writer<W: io::Write>(self, delimiter: u8, quote: u8, writer: W) -> csv::Writer<W> {
+        let terminator = match self {
+            Self::None | Self::Lf => csv::Terminator::Any(b'\n'),
+            Self::Crlf => csv::Terminator::CRLF,
+        };
+        csv::WriterBuilder::new()
+            .terminator(terminator)
+            .delimiter(delimiter)
+            .quote(quote)
+            .from_writer(writer)
+    }
+
+    /// Creates a CSV record writer terminating records with this terminator, or with a line feed if
+    /// records aren't terminated.
+    fn record_writer(self, delimiter: u8, quote: u8) -> csv_core::Writer {
+        let terminator = match self {
+            Self::None | Self::Lf => csv_core::Terminator::Any(b'\n'),
+            Self::Crlf => csv_core::Terminator::CRLF,
+        };
+        csv_core::WriterBuilder::new()
+            .terminator(terminator)
+            .delimiter(delimiter)
+            .quote(quote)
+            .build()
+    }
+}
+
+/// Writes the header row holding the names of the fields.
+///
+/// The header row is written before the framed events, so it's always terminated.
+fn header_row<T: AsRef<[u8]>>(
+    terminator: CsvTerminator,
+    delimiter: u8,
+    quote: u8,
+    names: &[T],
+) -> Result<Bytes, BuildError> {
+    let header_terminator = match terminator {
+        CsvTerminator::None => CsvTerminator::Lf,
+        terminator => terminator,
+    };
+    let mut writer = header_terminator.writer(delimiter, quote, BytesMut::new().writer());
+    writer.write_record(names)?;
+    let writer = writer.into_inner().map_err(|error| error.into_error())?;
+    Ok(writer.into_inner().freeze())
+}
+
+/// Serializer that converts an `Event` to bytes using the CSV format.
+#[derive(Debug, Clone)]
+pub struct CsvSerializer {
+    fields: Vec<OwnedValuePath>,
+    /// Whether the fields are still to be discovered from the next event.
+    discovering: bool,
+    headers: bool,
+    header: Option<Bytes>,
+    terminator: CsvTerminator,
+    delimiter: u8,
+    quote: u8,
+    nested_value_handling: CsvNestedValueHandling,
+    null_value: Bytes,
+    missing_value: Bytes,
+    /// Writes the records directly into the buffer of the encoder, so that encoding an event
+    /// doesn't allocate a writer and its buffer.
+    writer: csv_core::Writer,
+}
+
+impl CsvSerializer {
+    /// Creates a new `CsvSerializer`.
+    pub fn new(fields: Vec<OwnedValuePath>) -> Self {
+        Self {
+            fields,
+            discovering: false,
+            headers: false,
+            header: None,
+            terminator: CsvTerminator::None,
+            delimiter: default_delimiter(),
+            quote: default_quote(),
+            nested_value_handling: CsvNestedValueHandling::Empty,
+            null_value: Bytes::new(),
+            missing_value: Bytes::new(),
+            writer: CsvTerminator::None.record_writer(default_delimiter(), default_quote()),
+        }
+    }
+
+    /// Whether the records are terminated by the serializer, rather than by the framing.
+    pub fn is_terminated(&self) -> bool {
+        self.terminator != CsvTerminator::None
+    }
+
+    /// The header row to write before the events, including its terminator, which is empty if
+    /// headers aren't enabled or the fields are still to be discovered.
+    pub fn header(&self) -> &[u8] {
+        self.header.as_deref().unwrap_or_default()
+    }
+
+    /// Discovers the fields from the top-level fields of the event, writing the header row to the
+    /// buffer if headers are enabled.
+    fn discover_fields(
+        &mut self,
+        log: &LogEvent,
+        buffer: &mut BytesMut,
+    ) -> Result<(), vector_common::Error> {
+        let names = log
+            .as_map()
+            .map(|map| map.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        if self.headers {
+            let header = header_row(self.terminator, self.delimiter, self.quote, &names)?;
+            buffer.extend_from_slice(&header);
+            self.header = Some(header);
+        }
+        self.fields = names
+            .iter()
+            .map(|name| OwnedValuePath::single_field(name))
+            .collect();
+        self.discovering = false;
+        Ok(())
+    }
+
+    /// Forgets the discovered fields, so that they're discovered again from the next event.
+    fn forget_fields(&mut self) {
+        self.fields.clear();
+        self.header = None;
+        self.discovering = true;
+    }
+}
+
+impl Encoder<Event> for CsvSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        let start = buffer.len();
+        let discovered = self.discovering;
+        if discovered {
+            self.discover_fields(&log, buffer)?;
+        }
+        for (index, field) in self.fields.iter().enumerate() {
+            let bytes = match log.get((PathPrefix::Event, field)) {
+                None => self.missing_value.clone(),
+                Some(Value::Null) => self.null_value.clone(),
+                value => match self.nested_value_handling.field_bytes(value) {
+                    Ok(bytes) => bytes,
+                    Err(error) => {
+                        // The partially written record is discarded, along with the state of the
+                        // writer.
+                        buffer.truncate(start);
+                        self.writer = self.terminator.record_writer(self.delimiter, self.quote);
+                        if discovered {
+                            self.forget_fields();
+                        }
+                        return Err(error);
+                    }
+                },
+            };
+            if index > 0 {
+                write_all(buffer, &[], |_, output| {
+                    let (result, written) = self.writer.delimiter(output);
+                    (result, 0, written)
+                });
+            }
+            write_all(buffer, &bytes, |input, output| {
+                self.writer.field(input, output)
+            });
+        }
+        write_all(buffer, &[], |_, output| {
+            let (result, written) = self.writer.terminator(output);
+            (result, 0, written)
+        });
+
+        if self.terminator == CsvTerminator::None {
+            // The line feed is removed, as separating records is left to the framer.
+            buffer.truncate(buffer.len() - 1);
+        }
+        Ok(())
+    }
+}
+
+/// Runs a write of the CSV record writer into the buffer, growing it until the input is written.
+fn write_all(
+    buffer: &mut BytesMut,
+    mut input: &[u8],
+    mut write: impl FnMut(&[u8], &mut [u8]) -> (WriteResult, usize, usize),
+) {
+    loop {
+        let len = buffer.len();
+        // Quoting a field at most doubles it, and adds the quotes and the terminator.
+        buffer.resize(len + 2 * input.len() + 4, 0);
+        let (result, read, written) = write(input, &mut buffer[len..]);
+        buffer.truncate(len + written);
+        input = &input[read..];
+        if let WriteResult::InputEmpty = result {
+            return;
+        }
+    }
+}
+
+/// The bytes a value that isn't nested is encoded as.
+fn scalar_bytes(value: Option<&Value>) -> Bytes {
+    match value {
+        Some(Value::Bytes(bytes)) => bytes.clone(),
+        Some(
+            value @ (Value::Integer(_) | Value::Float(_) | Value::Boolean(_) | Value::Timestamp(_)),
+        ) => value.to_string_lossy().into_owned().into(),
+        Some(Value::Null | Value::Array(_) | Value::Object(_) | Value::Regex(_)) | None => {
+            Bytes::new()
+        }
+    }
+}
+
+/// Collects the values nested in arrays and objects, in order.
+fn flatten<'a>(value: &'a Value, leaves: &mut Vec<&'a Value>) {
+    match value {
+        Value::Array(values) => values.iter().for_each(|value| flatten(value, leaves)),
+        Value::Object(map) => map.values().for_each(|value| flatten(value, leaves)),
+        leaf => leaves.push(leaf),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BytesMut;
+    use chrono::{TimeZone, Utc};
+    use vector_common::btreemap;
+
+    fn serializer(fields: &[&str], headers: bool) -> CsvSerializer {
+        terminated_serializer(fields, headers, CsvTerminator::None)
+    }
+
+    fn terminated_serializer(
+        fields: &[&str],
+        headers: bool,
+        terminator: CsvTerminator,
+    ) -> CsvSerializer {
+        CsvSerializerConfig::new(CsvSerializerOptions {
+            fields: fields.iter().map(|field| field.to_string()).collect(),
+            headers,
+            terminator,
+            delimiter: b',',
+            quote: b'"',
+            nested_value_handling: CsvNestedValueHandling::Empty,
+            null_value: String::new(),
+            missing_value: String::new(),
+        })
+        .build()
+        .unwrap()
+    }
+
+    #[test]
+    fn serialize_fields() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "message" => Value::from("hello, \"world\""),
+            "count" => Value::from(3),
+            "ok" => Value::from(true),
+            "timestamp" => Value::from(
+                Utc.ymd(2023, 3, 14)
+                    .and_hms_opt(12, 0, 0)
+                    .expect("invalid timestamp"),
+            ),
+            "nested" => Value::from(btreemap! { "field" => Value::from("value") }),
+        }));
+        let mut serializer = serializer(
+            &[
+                "timestamp",
+                "message",
+                "count",
+                "ok",
+                "missing",
+                "nested.field",
+                "nested",
+            ],
+            false,
+        );
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event, &mut bytes).unwrap();
+
+        assert_eq!(
+            bytes.freeze(),
+            r#"2023-03-14T12:00:00Z,"hello, ""world""",3,true,,value,"#
+        );
+        assert_eq!(serializer.header(), b"");
+    }
+
+    #[test]
+    fn serialize_header() {
+        let serializer = serializer(&["timestamp", "message"], true);
+
+        assert_eq!(serializer.header(), b"timestamp,message\n");
+    }
+
+    #[test]
+    fn serialize_delimiter_and_quote() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "a" => Value::from("x;y"),
+            "b" => Value::from("z"),
+        }));
+        let mut serializer = CsvSerializerConfig::new(CsvSerializerOptions {
+            fields: vec!["a".to_owned(), "b".to_owned()],
+            headers: true,
+            terminator: CsvTerminator::None,
+            delimiter: b';',
+            quote: b'\'',
+            nested_value_handling: CsvNestedValueHandling::Empty,
+            null_value: String::new(),
+            missing_value: String::new(),
+        })
+        .build()
+        .unwrap();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event, &mut bytes).unwrap();
+
+        assert_eq!(bytes.freeze(), "'x;y';z");
+        assert_eq!(serializer.header(), b"a;b\n");
+    }
+
+    #[test]
+    fn serialize_terminator() {
+        let event = Event::Log(LogEvent::from("hello"));
+        for (terminator, expected) in [
+            (CsvTerminator::None, "hello"),
+            (CsvTerminator::Lf, "hello\n"),
+            (CsvTerminator::Crlf, "hello\r\n"),
+        ] {
+            let mut serializer = terminated_serializer(&["message"], true, terminator);
+            let mut bytes = BytesMut::new();
+
+            serializer.encode(event.clone(), &mut bytes).unwrap();
+
+            assert_eq!(bytes.freeze(), expected);
+            assert_eq!(
+                serializer.is_terminated(),
+                terminator != CsvTerminator::None
+            );
+        }
+
+        let serializer = terminated_serializer(&["message"], true, CsvTerminator::Crlf);
+        assert_eq!(serializer.header(), b"message\r\n");
+    }
+
+    #[test]
+    fn serialize_consecutive_records() {
+        let mut serializer =
+            terminated_serializer(&["message", "missing"], false, CsvTerminator::Lf);
+        let mut bytes = BytesMut::new();
+
+        for message in ["hello, world", "", "\"quoted\""] {
+            serializer
+                .encode(Event::Log(LogEvent::from(message)), &mut bytes)
+                .unwrap();
+        }
+
+        assert_eq!(
+            bytes.freeze(),
+            "\"hello, world\",\n,\n\"\"\"quoted\"\"\",\n"
+        );
+    }
+
+    #[test]
+    fn serialize_nested_values() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "tags" => Value::from(vec![Value::from("a"), Value::from(1), Value::Null]),
+            "nested" => Value::from(btreemap! {
+                "b" => Value::from(vec![Value::from(2), Value::from(3)]),
+                "a" => Value::from("x,y"),
+            }),
+        }));
+        for (nested_value_handling, expected) in [
+            (CsvNestedValueHandling::Empty, ","),
+            (
+                CsvNestedValueHandling::Json,
+                r#""[""a"",1,null]","{""a"":""x,y"",""b"":[2,3]}""#,
+            ),
+            (
+                CsvNestedValueHandling::FlattenJoin {
+                    delimiter: "|".to_owned(),
+                },
+                r#"a|1|,"x,y|2|3""#,
+            ),
+        ] {
+            let mut serializer = CsvSerializerConfig::new(CsvSerializerOptions {
+                fields: vec!["tags".to_owned(), "nested".to_owned()],
+                headers: false,
+                terminator: CsvTerminator::None,
+                delimiter: b',',
+                quote: b'"',
+                nested_value_handling,
+                null_value: String::new(),
+                missing_value: String::new(),
+            })
+            .build()
+            .unwrap();
+            let mut bytes = BytesMut::new();
+
+            serializer.encode(event.clone(), &mut bytes).unwrap();
+
+            assert_eq!(bytes.freeze(), expected);
+        }
+    }
+
+    #[test]
+    fn serialize_placeholders() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "message" => Value::from(""),
+            "user" => Value::Null,
+        }));
+        let mut serializer = CsvSerializerConfig::new(CsvSerializerOptions {
+            fields: vec![
+                "message".to_owned(),
+                "user".to_owned(),
+                "missing".to_owned(),
+            ],
+            headers: false,
+            terminator: CsvTerminator::None,
+            delimiter: b',',
+            quote: b'"',
+            nested_value_handling: CsvNestedValueHandling::Empty,
+            null_value: "\\N".to_owned(),
+            missing_value: "NULL".to_owned(),
+        })
+        .build()
+        .unwrap();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event, &mut bytes).unwrap();
+
+        assert_eq!(bytes.freeze(), r#",\N,NULL"#);
+    }
+
+    #[test]
+    fn serialize_discovered_fields() {
+        let mut serializer = terminated_serializer(&[], true, CsvTerminator::Lf);
+        assert_eq!(serializer.header(), b"");
+        let mut bytes = BytesMut::new();
+
+        for event in [
+            btreemap! {
+                "message" => Value::from("hello"),
+                "count" => Value::from(3),
+            },
+            btreemap! {
+                "message" => Value::from("world"),
+                "host" => Value::from("web-1"),
+            },
+        ] {
+            serializer
+                .encode(Event::Log(LogEvent::from(event)), &mut bytes)
+                .unwrap();
+        }
+
+        assert_eq!(bytes.freeze(), "count,message\n3,hello\n,world\n");
+        assert_eq!(serializer.header(), b"count,message\n");
+    }
+}
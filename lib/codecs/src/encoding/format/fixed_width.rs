@@ -0,0 +1,277 @@
+use crate::encoding::BuildError;
+use std::num::NonZeroUsize;
+
+use bytes::BytesMut;
+use lookup::{lookup_v2::parse_value_path, OwnedValuePath, PathPrefix};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::DataType,
+    event::{Event, Value},
+    schema,
+};
+
+/// Config used to build a `FixedWidthSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FixedWidthSerializerConfig {
+    /// Options for the fixed-width serializer.
+    pub fixed_width: FixedWidthSerializerOptions,
+}
+
+impl FixedWidthSerializerConfig {
+    /// Creates a new `FixedWidthSerializerConfig`.
+    pub const fn new(fixed_width: FixedWidthSerializerOptions) -> Self {
+        Self { fixed_width }
+    }
+
+    /// Build the `FixedWidthSerializer` from this configuration.
+    pub fn build(&self) -> Result<FixedWidthSerializer, BuildError> {
+        if self.fixed_width.columns.is_empty() {
+            return Err("At least one fixed-width column must be set".into());
+        }
+
+        let columns = self
+            .fixed_width
+            .columns
+            .iter()
+            .map(|column| {
+                if !column.padding.is_ascii() || column.padding.is_ascii_control() {
+                    return Err(format!(
+                        "Fixed-width padding of `{}` must be a printable ASCII character",
+                        column.field
+                    ));
+                }
+                let path = parse_value_path(&column.field).map_err(|error| {
+                    format!(
+                        "Invalid fixed-width field path `{}`: {}",
+                        column.field, error
+                    )
+                })?;
+                Ok((column.clone(), path))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(FixedWidthSerializer { columns })
+    }
+
+    /// The data type of events that are accepted by `FixedWidthSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Fixed-width serializer options.
+#[configurable_component]
+#[derive(Debug, Clone)]
+pub struct FixedWidthSerializerOptions {
+    /// The columns of the records, in order.
+    pub columns: Vec<FixedWidthColumn>,
+}
+
+/// A column of fixed-width records.
+#[configurable_component]
+#[derive(Debug, Clone)]
+pub struct FixedWidthColumn {
+    /// The field holding the value of the column.
+    ///
+    /// If the field is missing from an event, or holds a null value, the column only holds padding.
+    #[configurable(metadata(docs::examples = "account.id", docs::examples = "amount"))]
+    pub field: String,
+
+    /// The width of the column, in characters.
+    #[configurable(metadata(docs::examples = 10))]
+    pub width: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub alignment: FixedWidthAlignment,
+
+    /// The ASCII (7-bit) character padding the values narrower than the column.
+    #[serde(default = "default_padding", with = "vector_core::serde::ascii_char")]
+    pub padding: u8,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub truncation: FixedWidthTruncation,
+}
+
+const fn default_padding() -> u8 {
+    b' '
+}
+
+/// The alignment of the values in their column.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FixedWidthAlignment {
+    /// Align the values to the left, padding them on the right.
+    #[default]
+    Left,
+
+    /// Align the values to the right, padding them on the left.
+    ///
+    /// This is usually used for numbers, such as with a padding of `0`.
+    Right,
+}
+
+/// What to do with the values wider than their column.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FixedWidthTruncation {
+    /// Keep the start of the values, dropping the characters past the width of the column.
+    #[default]
+    End,
+
+    /// Keep the end of the values, dropping their first characters.
+    Start,
+
+    /// Drop the events, with an error.
+    Error,
+}
+
+/// Serializer that converts an `Event` to bytes as a fixed-width record, in which each value is
+/// padded to the width of its column.
+///
+/// Line breaks in the values are replaced by spaces, so that each record spans a single line.
+#[derive(Debug, Clone)]
+pub struct FixedWidthSerializer {
+    columns: Vec<(FixedWidthColumn, OwnedValuePath)>,
+}
+
+impl Encoder<Event> for FixedWidthSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        let mut record = String::new();
+
+        for (column, path) in &self.columns {
+            let value = match log.get((PathPrefix::Event, path)) {
+                None | Some(Value::Null) => String::new(),
+                Some(value) => value.to_string_lossy().replace(['\n', '\r'], " "),
+            };
+
+            let width = column.width.get();
+            let length = value.chars().count();
+            let value = if length > width {
+                match column.truncation {
+                    FixedWidthTruncation::End => value.chars().take(width).collect(),
+                    FixedWidthTruncation::Start => value.chars().skip(length - width).collect(),
+                    FixedWidthTruncation::Error => {
+                        return Err(format!(
+                            "Value of `{}` is {} characters wide, more than its column of {}",
+                            column.field, length, width
+                        )
+                        .into())
+                    }
+                }
+            } else {
+                value
+            };
+
+            let padding = char::from(column.padding)
+                .to_string()
+                .repeat(width - value.chars().count());
+            match column.alignment {
+                FixedWidthAlignment::Left => {
+                    record.push_str(&value);
+                    record.push_str(&padding);
+                }
+                FixedWidthAlignment::Right => {
+                    record.push_str(&padding);
+                    record.push_str(&value);
+                }
+            }
+        }
+
+        buffer.extend_from_slice(record.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn column(field: &str, width: usize) -> FixedWidthColumn {
+        FixedWidthColumn {
+            field: field.to_owned(),
+            width: NonZeroUsize::new(width).unwrap(),
+            alignment: FixedWidthAlignment::Left,
+            padding: default_padding(),
+            truncation: FixedWidthTruncation::End,
+        }
+    }
+
+    fn serialize(columns: Vec<FixedWidthColumn>, log: LogEvent) -> Result<String, String> {
+        let mut bytes = BytesMut::new();
+        FixedWidthSerializerConfig::new(FixedWidthSerializerOptions { columns })
+            .build()
+            .unwrap()
+            .encode(log.into(), &mut bytes)
+            .map_err(|error| error.to_string())?;
+        Ok(String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[test]
+    fn serialize_fixed_width() {
+        let mut log = LogEvent::default();
+        log.insert("account.id", "AB12");
+        log.insert("name", "Jane\nDoe");
+        log.insert("amount", 4200);
+        let columns = vec![
+            column("account.id", 6),
+            column("name", 10),
+            FixedWidthColumn {
+                alignment: FixedWidthAlignment::Right,
+                padding: b'0',
+                ..column("amount", 8)
+            },
+            column("missing", 3),
+        ];
+
+        assert_eq!(
+            serialize(columns, log).unwrap(),
+            "AB12  Jane Doe  00004200   "
+        );
+    }
+
+    #[test]
+    fn serialize_truncated() {
+        let mut log = LogEvent::default();
+        log.insert("code", "ABCDEF");
+
+        assert_eq!(
+            serialize(vec![column("code", 4)], log.clone()).unwrap(),
+            "ABCD"
+        );
+        assert_eq!(
+            serialize(
+                vec![FixedWidthColumn {
+                    truncation: FixedWidthTruncation::Start,
+                    ..column("code", 4)
+                }],
+                log.clone()
+            )
+            .unwrap(),
+            "CDEF"
+        );
+        assert!(serialize(
+            vec![FixedWidthColumn {
+                truncation: FixedWidthTruncation::Error,
+                ..column("code", 4)
+            }],
+            log
+        )
+        .is_err());
+    }
+}
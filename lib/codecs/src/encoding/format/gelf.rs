@@ -1,9 +1,12 @@
-use crate::{gelf_fields::*, VALID_FIELD_REGEX};
-use bytes::{BufMut, BytesMut};
+use crate::{encoding::BuildError, gelf_fields::*, VALID_FIELD_REGEX};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use flate2::write::{GzEncoder, ZlibEncoder};
 use lookup::event_path;
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
 use vector_core::{
     config::{log_schema, DataType},
     event::Event,
@@ -37,21 +40,32 @@ pub enum GelfSerializerError {
     },
     #[snafu(display(r#"LogEvent contains an invalid field name. field = "{}""#, field))]
     InvalidFieldName { field: String },
+    #[snafu(display(
+        "Encoded message of {} bytes needs {} chunks, more than the maximum of {}",
+        size,
+        chunks,
+        GELF_MAX_CHUNKS
+    ))]
+    TooManyChunks { size: usize, chunks: usize },
 }
 
 /// Config used to build a `GelfSerializer`.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
-pub struct GelfSerializerConfig;
+pub struct GelfSerializerConfig {
+    /// Options for the GELF serializer.
+    #[serde(default)]
+    pub gelf: GelfSerializerOptions,
+}
 
 impl GelfSerializerConfig {
     /// Creates a new `GelfSerializerConfig`.
-    pub const fn new() -> Self {
-        Self
+    pub const fn new(gelf: GelfSerializerOptions) -> Self {
+        Self { gelf }
     }
 
     /// Build the `GelfSerializer` from this configuration.
     pub fn build(&self) -> GelfSerializer {
-        GelfSerializer::new()
+        GelfSerializer::with_compression(self.gelf.compression)
     }
 
     /// The data type of events that are accepted by `GelfSerializer`.
@@ -67,15 +81,54 @@ impl GelfSerializerConfig {
     }
 }
 
+/// GELF serializer options.
+#[configurable_component]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GelfSerializerOptions {
+    /// The compression of the messages.
+    ///
+    /// Graylog only supports compressed messages over UDP, so compression should only be used by
+    /// the `socket` sink in `udp` mode.
+    #[serde(default)]
+    pub compression: GelfCompression,
+}
+
+/// The compression of GELF messages.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GelfCompression {
+    /// No compression.
+    #[default]
+    None,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// [Zlib][zlib] compression.
+    ///
+    /// [zlib]: https://zlib.net/
+    Zlib,
+}
+
 /// Serializer that converts an `Event` to bytes using the GELF format.
 /// Spec: <https://docs.graylog.org/docs/gelf>
 #[derive(Debug, Clone)]
-pub struct GelfSerializer;
+pub struct GelfSerializer {
+    compression: GelfCompression,
+}
 
 impl GelfSerializer {
     /// Creates a new `GelfSerializer`.
     pub fn new() -> Self {
-        GelfSerializer
+        Self::with_compression(GelfCompression::None)
+    }
+
+    /// Creates a new `GelfSerializer` that compresses the messages.
+    pub const fn with_compression(compression: GelfCompression) -> Self {
+        Self { compression }
     }
 
     /// Encode event and represent it as JSON value.
@@ -98,11 +151,92 @@ impl Encoder<Event> for GelfSerializer {
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
         let log = to_gelf_event(event.into_log())?;
         let writer = buffer.writer();
-        serde_json::to_writer(writer, &log)?;
+        match self.compression {
+            GelfCompression::None => serde_json::to_writer(writer, &log)?,
+            GelfCompression::Gzip => {
+                let mut encoder = GzEncoder::new(writer, flate2::Compression::default());
+                serde_json::to_writer(&mut encoder, &log)?;
+                encoder.finish()?;
+            }
+            GelfCompression::Zlib => {
+                let mut encoder = ZlibEncoder::new(writer, flate2::Compression::default());
+                serde_json::to_writer(&mut encoder, &log)?;
+                encoder.finish()?;
+            }
+        }
         Ok(())
     }
 }
 
+/// The magic bytes that start GELF chunks.
+const GELF_CHUNK_MAGIC: [u8; 2] = [0x1e, 0x0f];
+
+/// The size of the header of GELF chunks, which holds the magic bytes, the ID of the message, and
+/// the sequence number and count of the chunk.
+const GELF_CHUNK_HEADER_SIZE: usize = 12;
+
+/// The maximum number of chunks of a GELF message.
+const GELF_MAX_CHUNKS: usize = 128;
+
+/// Splits encoded GELF messages that don't fit in a UDP datagram into [chunks][gelf_chunking].
+///
+/// [gelf_chunking]: https://go2docs.graylog.org/5-0/getting_in_log_data/gelf.html#GELFviaUDP
+#[derive(Debug, Clone, Copy)]
+pub struct GelfChunker {
+    max_datagram_size: usize,
+}
+
+impl GelfChunker {
+    /// Creates a new `GelfChunker` for datagrams of up to `max_datagram_size` bytes.
+    pub fn new(max_datagram_size: usize) -> Result<Self, BuildError> {
+        if max_datagram_size <= GELF_CHUNK_HEADER_SIZE {
+            return Err(format!(
+                "The maximum datagram size must be larger than the GELF chunk header of {} bytes",
+                GELF_CHUNK_HEADER_SIZE
+            )
+            .into());
+        }
+        Ok(Self { max_datagram_size })
+    }
+
+    /// Splits the encoded message into chunks, or returns it as is if it fits in a datagram.
+    ///
+    /// The ID of the message must be unique to the message, such as a random one.
+    pub fn chunk(
+        &self,
+        message: Bytes,
+        message_id: [u8; 8],
+    ) -> Result<Vec<Bytes>, GelfSerializerError> {
+        if message.len() <= self.max_datagram_size {
+            return Ok(vec![message]);
+        }
+
+        let chunk_size = self.max_datagram_size - GELF_CHUNK_HEADER_SIZE;
+        let chunks = (message.len() + chunk_size - 1) / chunk_size;
+        if chunks > GELF_MAX_CHUNKS {
+            return TooManyChunksSnafu {
+                size: message.len(),
+                chunks,
+            }
+            .fail();
+        }
+
+        Ok(message
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(sequence_number, data)| {
+                let mut chunk = BytesMut::with_capacity(GELF_CHUNK_HEADER_SIZE + data.len());
+                chunk.put_slice(&GELF_CHUNK_MAGIC);
+                chunk.put_slice(&message_id);
+                chunk.put_u8(sequence_number as u8);
+                chunk.put_u8(chunks as u8);
+                chunk.put_slice(data);
+                chunk.freeze()
+            })
+            .collect())
+    }
+}
+
 /// Returns Error for invalid type.
 fn err_invalid_type(
     field: &str,
@@ -233,7 +367,7 @@ mod tests {
         expect_success: bool,
         event_fields: BTreeMap<String, Value>,
     ) -> Option<serde_json::Value> {
-        let config = GelfSerializerConfig::new();
+        let config = GelfSerializerConfig::default();
         let mut serializer = config.build();
         let event: Event = LogEvent::from_map(event_fields, EventMetadata::default()).into();
         let mut buffer = BytesMut::new();
@@ -252,7 +386,11 @@ mod tests {
 
     #[test]
     fn gelf_serde_json_to_value_supported_success() {
-        let serializer = SerializerConfig::Gelf.build().unwrap();
+        let serializer = SerializerConfig::Gelf {
+            gelf: Default::default(),
+        }
+        .build()
+        .unwrap();
 
         let event_fields = btreemap! {
             VERSION => "1.1",
@@ -267,7 +405,11 @@ mod tests {
 
     #[test]
     fn gelf_serde_json_to_value_supported_failure_to_encode() {
-        let serializer = SerializerConfig::Gelf.build().unwrap();
+        let serializer = SerializerConfig::Gelf {
+            gelf: Default::default(),
+        }
+        .build()
+        .unwrap();
         let event_fields = btreemap! {};
         let log_event: Event = LogEvent::from_map(event_fields, EventMetadata::default()).into();
         assert!(serializer.supports_json());
@@ -400,4 +542,62 @@ mod tests {
             do_serialize(false, event_fields);
         }
     }
+
+    #[test]
+    fn gelf_compression() {
+        use std::io::Read;
+
+        let event: Event = LogEvent::from_map(
+            btreemap! {
+                HOST => "example.org",
+                SHORT_MESSAGE => "Some message",
+            },
+            EventMetadata::default(),
+        )
+        .into();
+
+        let mut gzip = BytesMut::new();
+        GelfSerializer::with_compression(GelfCompression::Gzip)
+            .encode(event.clone(), &mut gzip)
+            .unwrap();
+        let mut zlib = BytesMut::new();
+        GelfSerializer::with_compression(GelfCompression::Zlib)
+            .encode(event, &mut zlib)
+            .unwrap();
+
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(&gzip[..])
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert!(decoded.contains(r#""short_message":"Some message""#));
+        decoded.clear();
+        flate2::read::ZlibDecoder::new(&zlib[..])
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert!(decoded.contains(r#""short_message":"Some message""#));
+    }
+
+    #[test]
+    fn gelf_chunking() {
+        let chunker = GelfChunker::new(16).unwrap();
+        let id = [1, 2, 3, 4, 5, 6, 7, 8];
+
+        assert_eq!(
+            chunker.chunk(Bytes::from("short"), id).unwrap(),
+            vec![Bytes::from("short")]
+        );
+
+        let chunks = chunker.chunk(Bytes::from("0123456789"), id).unwrap();
+        assert_eq!(
+            chunks,
+            vec![
+                Bytes::from(&b"\x1e\x0f\x01\x02\x03\x04\x05\x06\x07\x08\x00\x030123"[..]),
+                Bytes::from(&b"\x1e\x0f\x01\x02\x03\x04\x05\x06\x07\x08\x01\x034567"[..]),
+                Bytes::from(&b"\x1e\x0f\x01\x02\x03\x04\x05\x06\x07\x08\x02\x0389"[..]),
+            ]
+        );
+
+        assert!(chunker.chunk(Bytes::from(vec![0; 4 * 129]), id).is_err());
+        assert!(GelfChunker::new(GELF_CHUNK_HEADER_SIZE).is_err());
+    }
 }
@@ -1,4 +1,7 @@
+use std::num::NonZeroUsize;
+
 use bytes::{BufMut, BytesMut};
+use serde::Serialize;
 use tokio_util::codec::Encoder;
 use vector_core::{config::DataType, event::Event, schema};
 
@@ -17,17 +20,28 @@ pub struct JsonSerializerConfig {
         skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
     )]
     pub metric_tag_values: MetricTagValues,
+
+    /// Options for the JSON serializer.
+    #[serde(
+        default,
+        rename = "json",
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub options: JsonSerializerOptions,
 }
 
 impl JsonSerializerConfig {
     /// Creates a new `JsonSerializerConfig`.
-    pub const fn new(metric_tag_values: MetricTagValues) -> Self {
-        Self { metric_tag_values }
+    pub const fn new(metric_tag_values: MetricTagValues, options: JsonSerializerOptions) -> Self {
+        Self {
+            metric_tag_values,
+            options,
+        }
     }
 
     /// Build the `JsonSerializer` from this configuration.
-    pub const fn build(&self) -> JsonSerializer {
-        JsonSerializer::new(self.metric_tag_values)
+    pub fn build(&self) -> JsonSerializer {
+        JsonSerializer::new(self.metric_tag_values, self.options.clone())
     }
 
     /// The data type of events that are accepted by `JsonSerializer`.
@@ -43,16 +57,102 @@ impl JsonSerializerConfig {
     }
 }
 
+/// JSON serializer options.
+#[crate::configurable_component]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonSerializerOptions {
+    /// Whether to pretty-print the JSON, with indentation and line breaks.
+    #[serde(default)]
+    pub pretty: bool,
+
+    /// Whether to sort the keys of objects.
+    ///
+    /// The fields of logs and traces are always sorted, so this mostly makes the encoding of
+    /// metrics deterministic.
+    #[serde(default)]
+    pub sort_keys: bool,
+
+    /// The maximum size of encoded events.
+    ///
+    /// Larger events are handled according to `oversize_action`.
+    #[serde(default)]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 1048576))]
+    pub max_bytes: Option<NonZeroUsize>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub oversize_action: JsonOversizeAction,
+}
+
+/// What to do with the events encoded to more than `max_bytes`.
+#[crate::configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonOversizeAction {
+    /// Drop the events, with an error.
+    #[default]
+    Drop,
+
+    /// Truncate the longest string values of the events until they fit.
+    ///
+    /// The events are dropped, with an error, if they don't fit once all their string values are
+    /// empty.
+    Truncate,
+}
+
 /// Serializer that converts an `Event` to bytes using the JSON format.
 #[derive(Debug, Clone)]
 pub struct JsonSerializer {
     metric_tag_values: MetricTagValues,
+    options: JsonSerializerOptions,
 }
 
 impl JsonSerializer {
     /// Creates a new `JsonSerializer`.
-    pub const fn new(metric_tag_values: MetricTagValues) -> Self {
-        Self { metric_tag_values }
+    pub const fn new(metric_tag_values: MetricTagValues, options: JsonSerializerOptions) -> Self {
+        Self {
+            metric_tag_values,
+            options,
+        }
+    }
+
+    fn write<T: Serialize>(&self, value: &T, buffer: &mut BytesMut) -> serde_json::Result<()> {
+        if self.options.pretty {
+            serde_json::to_writer_pretty(buffer.writer(), value)
+        } else {
+            serde_json::to_writer(buffer.writer(), value)
+        }
+    }
+
+    /// Encodes an event through its JSON value, to sort its keys or truncate its strings.
+    fn encode_value(
+        &self,
+        event: Event,
+        buffer: &mut BytesMut,
+    ) -> Result<(), vector_common::Error> {
+        let mut value = self.to_json_value(event)?;
+        if self.options.sort_keys {
+            sort_keys(&mut value);
+        }
+
+        let start = buffer.len();
+        self.write(&value, buffer)?;
+
+        if let (Some(max_bytes), JsonOversizeAction::Truncate) =
+            (self.options.max_bytes, self.options.oversize_action)
+        {
+            while buffer.len() - start > max_bytes.get() {
+                let excess = buffer.len() - start - max_bytes.get();
+                buffer.truncate(start);
+                if !truncate_longest_string(&mut value, excess) {
+                    break;
+                }
+                self.write(&value, buffer)?;
+            }
+        }
+
+        Ok(())
     }
 
     /// Encode event and represent it as JSON value.
@@ -70,18 +170,87 @@ impl Encoder<Event> for JsonSerializer {
     type Error = vector_common::Error;
 
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
-        let writer = buffer.writer();
-        match event {
-            Event::Log(log) => serde_json::to_writer(writer, &log),
-            Event::Metric(mut metric) => {
-                if self.metric_tag_values == MetricTagValues::Single {
-                    metric.reduce_tags_to_single();
-                }
-                serde_json::to_writer(writer, &metric)
+        let event = match event {
+            Event::Metric(mut metric) if self.metric_tag_values == MetricTagValues::Single => {
+                metric.reduce_tags_to_single();
+                Event::Metric(metric)
+            }
+            event => event,
+        };
+
+        let start = buffer.len();
+        let truncate = self.options.max_bytes.is_some()
+            && self.options.oversize_action == JsonOversizeAction::Truncate;
+        if self.options.sort_keys || truncate {
+            self.encode_value(event, buffer)?;
+        } else {
+            match &event {
+                Event::Log(log) => self.write(log, buffer),
+                Event::Metric(metric) => self.write(metric, buffer),
+                Event::Trace(trace) => self.write(trace, buffer),
+            }?;
+        }
+
+        if let Some(max_bytes) = self.options.max_bytes {
+            let size = buffer.len() - start;
+            if size > max_bytes.get() {
+                buffer.truncate(start);
+                return Err(format!(
+                    "Encoded event is {} bytes, more than `max_bytes` of {}",
+                    size, max_bytes
+                )
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sorts the keys of the objects of a JSON value, recursively.
+fn sort_keys(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries = std::mem::take(map).into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (_, value) in &mut entries {
+                sort_keys(value);
+            }
+            *map = entries.into_iter().collect();
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(sort_keys),
+        _ => {}
+    }
+}
+
+/// Shortens the longest string of a JSON value by `excess` bytes, returning whether there was a
+/// non-empty string to shorten.
+fn truncate_longest_string(value: &mut serde_json::Value, excess: usize) -> bool {
+    match longest_string(value) {
+        Some(string) if !string.is_empty() => {
+            let mut len = string.len().saturating_sub(excess);
+            while !string.is_char_boundary(len) {
+                len -= 1;
             }
-            Event::Trace(trace) => serde_json::to_writer(writer, &trace),
+            string.truncate(len);
+            true
         }
-        .map_err(Into::into)
+        _ => false,
+    }
+}
+
+fn longest_string(value: &mut serde_json::Value) -> Option<&mut String> {
+    match value {
+        serde_json::Value::String(string) => Some(string),
+        serde_json::Value::Array(values) => values
+            .iter_mut()
+            .filter_map(longest_string)
+            .max_by_key(|string| string.len()),
+        serde_json::Value::Object(map) => map
+            .values_mut()
+            .filter_map(longest_string)
+            .max_by_key(|string| string.len()),
+        _ => None,
     }
 }
 
@@ -191,9 +360,7 @@ mod tests {
     #[test]
     fn serialize_metric_tags_full() {
         let bytes = serialize(
-            JsonSerializerConfig {
-                metric_tag_values: MetricTagValues::Full,
-            },
+            JsonSerializerConfig::new(MetricTagValues::Full, Default::default()),
             metric2(),
         );
 
@@ -206,9 +373,7 @@ mod tests {
     #[test]
     fn serialize_metric_tags_single() {
         let bytes = serialize(
-            JsonSerializerConfig {
-                metric_tag_values: MetricTagValues::Single,
-            },
+            JsonSerializerConfig::new(MetricTagValues::Single, Default::default()),
             metric2(),
         );
 
@@ -218,6 +383,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serialize_pretty_sorted() {
+        let options = JsonSerializerOptions {
+            pretty: true,
+            sort_keys: true,
+            ..Default::default()
+        };
+        let bytes = serialize(
+            JsonSerializerConfig::new(MetricTagValues::Single, options),
+            metric2(),
+        );
+
+        assert_eq!(
+            bytes,
+            "{\n  \"counter\": {\n    \"value\": 1.0\n  },\n  \"kind\": \"incremental\",\n  \"name\": \"counter\",\n  \"tags\": {\n    \"a\": \"second\"\n  }\n}"
+        );
+    }
+
+    #[test]
+    fn serialize_max_bytes() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "message" => Value::from("a".repeat(100)),
+            "id" => Value::from("abc"),
+        }));
+        let config = |oversize_action| {
+            JsonSerializerConfig::new(
+                MetricTagValues::default(),
+                JsonSerializerOptions {
+                    max_bytes: NonZeroUsize::new(40),
+                    oversize_action,
+                    ..Default::default()
+                },
+            )
+        };
+
+        let mut buffer = BytesMut::new();
+        assert!(config(JsonOversizeAction::Drop)
+            .build()
+            .encode(event.clone(), &mut buffer)
+            .is_err());
+        assert!(buffer.is_empty());
+
+        let bytes = serialize(config(JsonOversizeAction::Truncate), event);
+        assert_eq!(bytes.len(), 40);
+        assert_eq!(
+            bytes,
+            format!(r#"{{"id":"abc","message":"{}"}}"#, "a".repeat(14))
+        );
+    }
+
     fn metric2() -> Event {
         Event::Metric(
             Metric::new(
@@ -0,0 +1,244 @@
+use crate::encoding::BuildError;
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use bytes::BytesMut;
+use lookup::{lookup_v2::parse_value_path, OwnedValuePath, PathPrefix};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::DataType,
+    event::{Event, Value},
+    schema,
+};
+
+use super::cef::escape_header;
+
+/// Config used to build a `LeefSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LeefSerializerConfig {
+    /// Options for the LEEF serializer.
+    pub leef: LeefSerializerOptions,
+}
+
+impl LeefSerializerConfig {
+    /// Creates a new `LeefSerializerConfig`.
+    pub const fn new(leef: LeefSerializerOptions) -> Self {
+        Self { leef }
+    }
+
+    /// Build the `LeefSerializer` from this configuration.
+    pub fn build(&self) -> Result<LeefSerializer, BuildError> {
+        let delimiter = char::from(self.leef.delimiter);
+        if delimiter == '=' || delimiter == '|' || delimiter == '\n' || delimiter == '\r' {
+            return Err(format!("LEEF delimiter can't be {:?}", delimiter).into());
+        }
+
+        let attributes = self
+            .leef
+            .attributes
+            .iter()
+            .map(|(key, path)| {
+                if key.is_empty() || key.contains(['=', delimiter, '\n', '\r']) {
+                    return Err(format!("Invalid LEEF attribute key `{}`", key));
+                }
+                let path = parse_value_path(path)
+                    .map_err(|error| format!("Invalid LEEF field path `{}`: {}", path, error))?;
+                Ok((key.clone(), path))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The delimiter is written as its hexadecimal value, so that characters such as tabs can be
+        // told apart from whitespace.
+        let header = format!(
+            "LEEF:2.0|{}|{}|{}|{}|x{:02X}|",
+            escape_header(&self.leef.vendor),
+            escape_header(&self.leef.product),
+            escape_header(&self.leef.product_version),
+            escape_header(&self.leef.event_id),
+            self.leef.delimiter
+        );
+
+        Ok(LeefSerializer {
+            header,
+            delimiter,
+            attributes,
+        })
+    }
+
+    /// The data type of events that are accepted by `LeefSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// LEEF serializer options.
+#[configurable_component]
+#[derive(Debug, Clone)]
+pub struct LeefSerializerOptions {
+    /// The vendor of the product that produced the events.
+    #[configurable(metadata(docs::examples = "Acme"))]
+    pub vendor: String,
+
+    /// The product that produced the events.
+    #[configurable(metadata(docs::examples = "Firewall"))]
+    pub product: String,
+
+    /// The version of the product that produced the events.
+    #[configurable(metadata(docs::examples = "1.0"))]
+    pub product_version: String,
+
+    /// The identifier of the type of the events.
+    #[configurable(metadata(docs::examples = "100", docs::examples = "login"))]
+    pub event_id: String,
+
+    /// The ASCII (7-bit) character that delimits the attributes.
+    ///
+    /// The delimiter can't be `=` or `|`. Occurrences of the delimiter in the values of attributes
+    /// are replaced by spaces, as LEEF has no way to escape them.
+    #[serde(default = "default_delimiter", with = "vector_core::serde::ascii_char")]
+    pub delimiter: u8,
+
+    /// A mapping of LEEF attribute keys to the fields holding their values.
+    ///
+    /// Attributes whose field is missing from an event, or holds a null value, are left out.
+    /// Timestamps are encoded as milliseconds since the Unix epoch, which is the default format of
+    /// the `devTime` attribute.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The path of the field holding the value of the attribute."
+    ))]
+    #[configurable(metadata(docs::examples = "example_attributes()"))]
+    pub attributes: BTreeMap<String, String>,
+}
+
+const fn default_delimiter() -> u8 {
+    b'\t'
+}
+
+fn example_attributes() -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("src".to_owned(), "client.ip".to_owned()),
+        ("usrName".to_owned(), "user.name".to_owned()),
+        ("devTime".to_owned(), "timestamp".to_owned()),
+    ])
+}
+
+/// Serializer that converts an `Event` to bytes using the [Log Event Extended Format][leef] 2.0
+/// of IBM QRadar.
+///
+/// [leef]: https://www.ibm.com/docs/en/dsm?topic=leef-overview
+#[derive(Debug, Clone)]
+pub struct LeefSerializer {
+    /// The header, with its escaped fields, up to and including the pipe preceding the attributes.
+    header: String,
+    delimiter: char,
+    attributes: Vec<(String, OwnedValuePath)>,
+}
+
+impl Encoder<Event> for LeefSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        buffer.extend_from_slice(self.header.as_bytes());
+
+        let mut first = true;
+        for (key, path) in &self.attributes {
+            let value = match log.get((PathPrefix::Event, path)) {
+                None | Some(Value::Null) => continue,
+                Some(Value::Timestamp(timestamp)) => timestamp.timestamp_millis().to_string(),
+                Some(value) => value
+                    .to_string_lossy()
+                    .replace([self.delimiter, '\n', '\r'], " "),
+            };
+            if !first {
+                buffer.extend_from_slice(self.delimiter.encode_utf8(&mut [0; 4]).as_bytes());
+            }
+            write!(buffer, "{}={}", key, value)?;
+            first = false;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn options() -> LeefSerializerOptions {
+        LeefSerializerOptions {
+            vendor: "Acme".to_owned(),
+            product: "Fire|wall".to_owned(),
+            product_version: "1.0".to_owned(),
+            event_id: "login".to_owned(),
+            delimiter: default_delimiter(),
+            attributes: example_attributes(),
+        }
+    }
+
+    fn serialize(options: LeefSerializerOptions, log: LogEvent) -> String {
+        let mut bytes = BytesMut::new();
+        LeefSerializerConfig::new(options)
+            .build()
+            .unwrap()
+            .encode(log.into(), &mut bytes)
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn serialize_leef() {
+        let mut log = LogEvent::default();
+        log.insert("client.ip", "10.0.0.1");
+        log.insert("user.name", "a\tb\nc");
+        log.insert("timestamp", Utc.timestamp_opt(1_700_000_000, 0).unwrap());
+
+        assert_eq!(
+            serialize(options(), log),
+            "LEEF:2.0|Acme|Fire\\|wall|1.0|login|x09|devTime=1700000000000\tsrc=10.0.0.1\tusrName=a b c"
+        );
+    }
+
+    #[test]
+    fn serialize_custom_delimiter() {
+        let mut log = LogEvent::default();
+        log.insert("client.ip", "10.0.0.1");
+        log.insert("user.name", "a^b");
+        let options = LeefSerializerOptions {
+            delimiter: b'^',
+            ..options()
+        };
+
+        assert_eq!(
+            serialize(options, log),
+            "LEEF:2.0|Acme|Fire\\|wall|1.0|login|x5E|src=10.0.0.1^usrName=a b"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let invalid_delimiter = LeefSerializerOptions {
+            delimiter: b'=',
+            ..options()
+        };
+        let mut invalid_key = options();
+        invalid_key
+            .attributes
+            .insert("bad=key".to_owned(), "message".to_owned());
+
+        assert!(LeefSerializerConfig::new(invalid_delimiter)
+            .build()
+            .is_err());
+        assert!(LeefSerializerConfig::new(invalid_key).build().is_err());
+    }
+}
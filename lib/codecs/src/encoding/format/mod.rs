@@ -3,27 +3,76 @@
 
 #![deny(missing_docs)]
 
+#[cfg(feature = "arrow")]
+mod arrow;
 mod avro;
+mod cbor;
+mod cef;
+mod csv;
+mod fixed_width;
 mod gelf;
 mod json;
+mod leef;
 mod logfmt;
+mod msgpack;
 mod native;
 mod native_json;
+#[cfg(feature = "parquet")]
+mod parquet;
+mod pretty;
+mod protobuf;
 mod raw_message;
+mod syslog;
+mod template;
 mod text;
+mod xml;
 
 use std::fmt::Debug;
 
-pub use avro::{AvroSerializer, AvroSerializerConfig, AvroSerializerOptions};
+#[cfg(feature = "arrow")]
+pub use arrow::{
+    ArrowField, ArrowFieldType, ArrowStreamSerializer, ArrowStreamSerializerConfig,
+    ArrowStreamSerializerOptions,
+};
+pub use avro::{
+    AvroSchemaRegistryAuth, AvroSchemaRegistryOptions, AvroSerializer, AvroSerializerConfig,
+    AvroSerializerOptions,
+};
+pub use cbor::{CborSerializer, CborSerializerConfig, CborSerializerOptions};
+pub use cef::{CefSerializer, CefSerializerConfig, CefSerializerOptions};
+pub use csv::{
+    CsvNestedValueHandling, CsvSerializer, CsvSerializerConfig, CsvSerializerOptions, CsvTerminator,
+};
 use dyn_clone::DynClone;
-pub use gelf::{GelfSerializer, GelfSerializerConfig};
-pub use json::{JsonSerializer, JsonSerializerConfig};
+pub use fixed_width::{
+    FixedWidthAlignment, FixedWidthColumn, FixedWidthSerializer, FixedWidthSerializerConfig,
+    FixedWidthSerializerOptions, FixedWidthTruncation,
+};
+pub use gelf::{
+    GelfChunker, GelfCompression, GelfSerializer, GelfSerializerConfig, GelfSerializerError,
+    GelfSerializerOptions,
+};
+pub use json::{JsonOversizeAction, JsonSerializer, JsonSerializerConfig, JsonSerializerOptions};
+pub use leef::{LeefSerializer, LeefSerializerConfig, LeefSerializerOptions};
 pub use logfmt::{LogfmtSerializer, LogfmtSerializerConfig};
-pub use native::{NativeSerializer, NativeSerializerConfig};
+pub use msgpack::{MsgpackSerializer, MsgpackSerializerConfig};
+pub use native::{NativeSerializer, NativeSerializerConfig, NativeSerializerOptions};
 pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
+#[cfg(feature = "parquet")]
+pub use parquet::{
+    ParquetCompression, ParquetField, ParquetFieldType, ParquetSerializer, ParquetSerializerConfig,
+    ParquetSerializerOptions,
+};
+pub use pretty::{PrettyColors, PrettySerializer, PrettySerializerConfig, PrettySerializerOptions};
+pub use protobuf::{ProtobufSerializer, ProtobufSerializerConfig, ProtobufSerializerOptions};
 pub use raw_message::{RawMessageSerializer, RawMessageSerializerConfig};
-pub use text::{TextSerializer, TextSerializerConfig};
+pub use syslog::{
+    SyslogFacility, SyslogRfc, SyslogSerializer, SyslogSerializerConfig, SyslogSerializerOptions,
+    SyslogSeverity,
+};
+pub use text::{TextSerializer, TextSerializerConfig, TextSerializerOptions};
 use vector_core::event::Event;
+pub use xml::{XmlSerializer, XmlSerializerConfig, XmlSerializerOptions};
 
 /// Serialize a structured event into a byte frame.
 pub trait Serializer:
@@ -0,0 +1,144 @@
+use bytes::{BufMut, BytesMut};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_core::{
+    config::DataType,
+    event::{Event, Value},
+    schema,
+};
+
+/// Config used to build a `MsgpackSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MsgpackSerializerConfig;
+
+impl MsgpackSerializerConfig {
+    /// Creates a new `MsgpackSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `MsgpackSerializer` from this configuration.
+    pub const fn build(&self) -> MsgpackSerializer {
+        MsgpackSerializer
+    }
+
+    /// The data type of events that are accepted by `MsgpackSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the MessagePack format.
+///
+/// Strings that aren't valid UTF-8 are encoded as binary values, and timestamps are encoded with
+/// the MessagePack timestamp extension type.
+#[derive(Debug, Clone)]
+pub struct MsgpackSerializer;
+
+impl MsgpackSerializer {
+    /// Creates a new `MsgpackSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<Event> for MsgpackSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        let value = to_msgpack_value(log.value());
+        rmpv::encode::write_value(&mut buffer.writer(), &value)?;
+        Ok(())
+    }
+}
+
+/// The type of the MessagePack timestamp extension.
+const TIMESTAMP_EXT_TYPE: i8 = -1;
+
+fn to_msgpack_value(value: &Value) -> rmpv::Value {
+    match value {
+        Value::Bytes(bytes) => match std::str::from_utf8(bytes) {
+            Ok(string) => rmpv::Value::from(string),
+            Err(_) => rmpv::Value::Binary(bytes.to_vec()),
+        },
+        Value::Regex(regex) => rmpv::Value::from(regex.as_str()),
+        Value::Integer(integer) => rmpv::Value::from(*integer),
+        Value::Float(float) => rmpv::Value::F64(float.into_inner()),
+        Value::Boolean(boolean) => rmpv::Value::Boolean(*boolean),
+        Value::Timestamp(timestamp) => {
+            // The timestamp 96 format, which holds any timestamp with nanosecond precision.
+            let mut data = Vec::with_capacity(12);
+            data.put_u32(timestamp.timestamp_subsec_nanos());
+            data.put_i64(timestamp.timestamp());
+            rmpv::Value::Ext(TIMESTAMP_EXT_TYPE, data)
+        }
+        Value::Object(object) => rmpv::Value::Map(
+            object
+                .iter()
+                .map(|(key, value)| (rmpv::Value::from(key.as_str()), to_msgpack_value(value)))
+                .collect(),
+        ),
+        Value::Array(array) => rmpv::Value::Array(array.iter().map(to_msgpack_value).collect()),
+        Value::Null => rmpv::Value::Nil,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn serialize(log: LogEvent) -> rmpv::Value {
+        let mut bytes = BytesMut::new();
+        MsgpackSerializer::new()
+            .encode(log.into(), &mut bytes)
+            .unwrap();
+        rmpv::decode::read_value(&mut bytes.as_ref()).unwrap()
+    }
+
+    #[test]
+    fn serialize_msgpack() {
+        let mut log = LogEvent::from("hello");
+        log.insert("status", 200);
+        log.insert("tags", vec!["a", "b"]);
+        log.insert("raw", bytes::Bytes::from_static(b"\xff\xfe"));
+
+        let value = serialize(log);
+
+        let map = value.as_map().unwrap();
+        let get = |key: &str| {
+            map.iter()
+                .find(|(k, _)| k.as_str() == Some(key))
+                .map(|(_, v)| v.clone())
+                .unwrap()
+        };
+        assert_eq!(get("message"), rmpv::Value::from("hello"));
+        assert_eq!(get("status"), rmpv::Value::from(200));
+        assert_eq!(
+            get("tags"),
+            rmpv::Value::Array(vec!["a".into(), "b".into()])
+        );
+        assert_eq!(get("raw"), rmpv::Value::Binary(vec![0xff, 0xfe]));
+    }
+
+    #[test]
+    fn serialize_timestamp_extension() {
+        let mut log = LogEvent::default();
+        log.insert("timestamp", Utc.timestamp_opt(1_700_000_000, 5).unwrap());
+
+        let value = serialize(log);
+
+        assert_eq!(
+            value.as_map().unwrap()[0].1,
+            rmpv::Value::Ext(-1, vec![0, 0, 0, 5, 0, 0, 0, 0, 0x65, 0x53, 0xf1, 0x00])
+        );
+    }
+}
@@ -2,20 +2,32 @@ use bytes::BytesMut;
 use prost::Message;
 use serde::{Deserialize, Serialize};
 use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
 use vector_core::{
     config::DataType,
     event::{proto, Event, EventArray},
     schema,
 };
 
+use crate::NativeVersion;
+
 /// Config used to build a `NativeSerializer`.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
-pub struct NativeSerializerConfig;
+pub struct NativeSerializerConfig {
+    /// Options for the native serializer.
+    #[serde(default)]
+    pub native: NativeSerializerOptions,
+}
 
 impl NativeSerializerConfig {
+    /// Creates a new `NativeSerializerConfig`.
+    pub const fn new(native: NativeSerializerOptions) -> Self {
+        Self { native }
+    }
+
     /// Build the `NativeSerializer` from this configuration.
     pub const fn build(&self) -> NativeSerializer {
-        NativeSerializer
+        NativeSerializer::new(self.native.version)
     }
 
     /// The data type of events that are accepted by `NativeSerializer`.
@@ -29,16 +41,52 @@ impl NativeSerializerConfig {
     }
 }
 
+/// Native serializer options.
+#[configurable_component]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NativeSerializerOptions {
+    /// The version of the native format to encode events with.
+    ///
+    /// During rolling upgrades, set this to the version supported by the oldest Vector instances
+    /// decoding the events.
+    #[serde(default)]
+    pub version: NativeVersion,
+}
+
 /// Serializer that converts an `Event` to bytes using the Vector native protobuf format.
 #[derive(Debug, Clone)]
-pub struct NativeSerializer;
+pub struct NativeSerializer {
+    version: NativeVersion,
+}
+
+impl NativeSerializer {
+    /// Creates a new `NativeSerializer`.
+    pub const fn new(version: NativeVersion) -> Self {
+        Self { version }
+    }
+}
+
+impl Default for NativeSerializer {
+    fn default() -> Self {
+        Self::new(NativeVersion::LATEST)
+    }
+}
 
 impl Encoder<Event> for NativeSerializer {
     type Error = vector_common::Error;
 
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
         let array = EventArray::from(event);
-        let proto = proto::EventArray::from(array);
+        let mut proto = proto::EventArray::from(array);
+        if self.version == NativeVersion::V1 {
+            // The single values of tags are already encoded in `tags_v1`.
+            if let Some(proto::event_array::Events::Metrics(metrics)) = &mut proto.events {
+                for metric in &mut metrics.metrics {
+                    metric.tags_v2.clear();
+                }
+            }
+        }
+        proto.version = self.version.as_u32();
         proto.encode(buffer)?;
         Ok(())
     }
@@ -0,0 +1,445 @@
+use crate::encoding::BuildError;
+use std::{num::NonZeroUsize, sync::Arc};
+
+use bytes::{BufMut, BytesMut};
+use lookup::{lookup_v2::parse_value_path, OwnedValuePath, PathPrefix};
+use parquet::{
+    basic::{Compression, ConvertedType, Repetition, Type as PhysicalType},
+    column::writer::ColumnWriter,
+    data_type::ByteArray,
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::types::{Type, TypePtr},
+};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::DataType,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// Config used to build a `ParquetSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ParquetSerializerConfig {
+    /// Options for the Parquet serializer.
+    pub parquet: ParquetSerializerOptions,
+}
+
+impl ParquetSerializerConfig {
+    /// Creates a new `ParquetSerializerConfig`.
+    pub const fn new(parquet: ParquetSerializerOptions) -> Self {
+        Self { parquet }
+    }
+
+    /// Build the `ParquetSerializer` from this configuration.
+    pub fn build(&self) -> Result<ParquetSerializer, BuildError> {
+        if self.parquet.fields.is_empty() {
+            return Err("Parquet serializer requires at least one field".into());
+        }
+
+        let mut columns = Vec::with_capacity(self.parquet.fields.len());
+        let mut paths = Vec::with_capacity(self.parquet.fields.len());
+        for field in &self.parquet.fields {
+            let path = parse_value_path(&field.name)
+                .map_err(|error| format!("Invalid Parquet field `{}`: {}", field.name, error))?;
+            columns.push(Arc::new(field.r#type.column_type(&field.name)?));
+            paths.push(path);
+        }
+        let schema = Type::group_type_builder("vector")
+            .with_fields(&mut columns)
+            .build()?;
+
+        let properties = WriterProperties::builder()
+            .set_compression(self.parquet.compression.into())
+            .set_max_row_group_size(self.parquet.row_group_size.get())
+            .build();
+
+        Ok(ParquetSerializer {
+            schema: Arc::new(schema),
+            properties: Arc::new(properties),
+            fields: self
+                .parquet
+                .fields
+                .iter()
+                .zip(paths)
+                .map(|(field, path)| (path, field.r#type))
+                .collect(),
+            row_group_size: self.parquet.row_group_size.get(),
+        })
+    }
+
+    /// The data type of events that are accepted by `ParquetSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Apache Parquet serializer options.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ParquetSerializerOptions {
+    /// The fields to encode, which make up the columns of the schema of the files, in order.
+    ///
+    /// Fields missing from an event, or holding a null value, are encoded as null values. Events
+    /// with fields holding values that don't match the type of their column fail to encode.
+    pub fields: Vec<ParquetField>,
+
+    /// The maximum number of events in each row group of the files.
+    ///
+    /// Larger row groups compress better and are read more efficiently, at the cost of the memory
+    /// needed to write and read them.
+    #[serde(default = "default_row_group_size")]
+    #[configurable(metadata(docs::type_unit = "events"))]
+    pub row_group_size: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: ParquetCompression,
+}
+
+fn default_row_group_size() -> NonZeroUsize {
+    NonZeroUsize::new(10_000).expect("static non-zero number")
+}
+
+/// A field encoded as a column of the Parquet files.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ParquetField {
+    /// The path of the field, which is also the name of its column.
+    #[configurable(metadata(docs::examples = "message", docs::examples = "status"))]
+    pub name: String,
+
+    /// The type of the column.
+    #[serde(rename = "type")]
+    pub r#type: ParquetFieldType,
+}
+
+/// The type of a column of the Parquet files.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetFieldType {
+    /// A UTF-8 string.
+    ///
+    /// Values other than strings are encoded as their string representation, with arrays and
+    /// objects encoded as JSON.
+    String,
+
+    /// A 64-bit signed integer.
+    Integer,
+
+    /// A 64-bit floating point number.
+    ///
+    /// Integer values are converted to floating point numbers.
+    Float,
+
+    /// A boolean.
+    Boolean,
+
+    /// A timestamp, with microsecond precision.
+    Timestamp,
+}
+
+impl ParquetFieldType {
+    fn column_type(self, name: &str) -> parquet::errors::Result<Type> {
+        let (physical_type, converted_type) = match self {
+            Self::String => (PhysicalType::BYTE_ARRAY, ConvertedType::UTF8),
+            Self::Integer => (PhysicalType::INT64, ConvertedType::NONE),
+            Self::Float => (PhysicalType::DOUBLE, ConvertedType::NONE),
+            Self::Boolean => (PhysicalType::BOOLEAN, ConvertedType::NONE),
+            Self::Timestamp => (PhysicalType::INT64, ConvertedType::TIMESTAMP_MICROS),
+        };
+        Type::primitive_type_builder(name, physical_type)
+            .with_repetition(Repetition::OPTIONAL)
+            .with_converted_type(converted_type)
+            .build()
+    }
+}
+
+/// The compression codec of the column chunks of the Parquet files.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetCompression {
+    /// No compression.
+    None,
+
+    /// [Snappy][snappy] compression.
+    ///
+    /// [snappy]: https://github.com/google/snappy
+    #[default]
+    Snappy,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
+impl From<ParquetCompression> for Compression {
+    fn from(compression: ParquetCompression) -> Self {
+        match compression {
+            ParquetCompression::None => Compression::UNCOMPRESSED,
+            ParquetCompression::Snappy => Compression::SNAPPY,
+            ParquetCompression::Gzip => Compression::GZIP(Default::default()),
+            ParquetCompression::Zstd => Compression::ZSTD(Default::default()),
+        }
+    }
+}
+
+/// Serializer that converts batches of events to bytes using the Apache Parquet format.
+///
+/// Parquet is a file format: each batch of events is encoded as a complete file, holding the
+/// footer with the schema and the metadata of its row groups. Events encoded on their own are
+/// encoded as a file holding a single row.
+#[derive(Debug, Clone)]
+pub struct ParquetSerializer {
+    schema: TypePtr,
+    properties: Arc<WriterProperties>,
+    fields: Vec<(OwnedValuePath, ParquetFieldType)>,
+    row_group_size: usize,
+}
+
+impl ParquetSerializer {
+    /// Encodes the events as a Parquet file.
+    pub fn encode_batch(
+        &self,
+        events: Vec<Event>,
+        buffer: &mut BytesMut,
+    ) -> Result<(), vector_common::Error> {
+        let logs = events.into_iter().map(Event::into_log).collect::<Vec<_>>();
+
+        let mut file = Vec::new();
+        let mut writer = SerializedFileWriter::new(
+            &mut file,
+            Arc::clone(&self.schema),
+            Arc::clone(&self.properties),
+        )?;
+        for rows in logs.chunks(self.row_group_size) {
+            let mut row_group = writer.next_row_group()?;
+            for (path, field_type) in &self.fields {
+                let mut column = row_group
+                    .next_column()?
+                    .ok_or("Parquet schema has fewer columns than fields")?;
+                write_column(column.untyped(), path, *field_type, rows)?;
+                column.close()?;
+            }
+            row_group.close()?;
+        }
+        writer.close()?;
+
+        buffer.put_slice(&file);
+        Ok(())
+    }
+}
+
+impl Encoder<Event> for ParquetSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode_batch(vec![event], buffer)
+    }
+}
+
+/// Writes the values of a field of the rows to its column.
+///
+/// Null values aren't written, but recorded in the definition levels of the column, which are 0
+/// for null values and 1 for present ones.
+fn write_column(
+    column: &mut ColumnWriter<'_>,
+    path: &OwnedValuePath,
+    field_type: ParquetFieldType,
+    rows: &[LogEvent],
+) -> Result<(), vector_common::Error> {
+    let values = rows
+        .iter()
+        .map(|log| match log.get((PathPrefix::Event, path)) {
+            None | Some(Value::Null) => None,
+            Some(value) => Some(value),
+        })
+        .collect::<Vec<_>>();
+    let levels = values
+        .iter()
+        .map(|value| i16::from(value.is_some()))
+        .collect::<Vec<_>>();
+    let present = values.into_iter().flatten();
+    let mismatch = |value: &Value| {
+        format!(
+            "Field `{}` of type {:?} can't hold a value of type {}",
+            path,
+            field_type,
+            value.kind_str()
+        )
+    };
+
+    match column {
+        ColumnWriter::ByteArrayColumnWriter(writer) => {
+            let values = present
+                .map(|value| match value {
+                    Value::Bytes(bytes) => Ok(ByteArray::from(bytes.to_vec())),
+                    Value::Array(_) | Value::Object(_) => {
+                        Ok(ByteArray::from(serde_json::to_vec(value)?))
+                    }
+                    value => Ok(ByteArray::from(
+                        value.to_string_lossy().into_owned().into_bytes(),
+                    )),
+                })
+                .collect::<Result<Vec<_>, vector_common::Error>>()?;
+            writer.write_batch(&values, Some(&levels), None)?;
+        }
+        ColumnWriter::Int64ColumnWriter(writer) => {
+            let values = present
+                .map(|value| match (field_type, value) {
+                    (ParquetFieldType::Integer, Value::Integer(integer)) => Ok(*integer),
+                    (ParquetFieldType::Timestamp, Value::Timestamp(timestamp)) => {
+                        Ok(timestamp.timestamp_micros())
+                    }
+                    (_, value) => Err(mismatch(value)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            writer.write_batch(&values, Some(&levels), None)?;
+        }
+        ColumnWriter::DoubleColumnWriter(writer) => {
+            let values = present
+                .map(|value| match value {
+                    Value::Float(float) => Ok(float.into_inner()),
+                    Value::Integer(integer) => Ok(*integer as f64),
+                    value => Err(mismatch(value)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            writer.write_batch(&values, Some(&levels), None)?;
+        }
+        ColumnWriter::BoolColumnWriter(writer) => {
+            let values = present
+                .map(|value| match value {
+                    Value::Boolean(boolean) => Ok(*boolean),
+                    value => Err(mismatch(value)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            writer.write_batch(&values, Some(&levels), None)?;
+        }
+        _ => return Err("Unsupported Parquet column type".into()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::{TimeZone, Utc};
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::RowAccessor,
+    };
+
+    use super::*;
+
+    fn serializer(fields: &[(&str, ParquetFieldType)], row_group_size: usize) -> ParquetSerializer {
+        ParquetSerializerConfig::new(ParquetSerializerOptions {
+            fields: fields
+                .iter()
+                .map(|(name, r#type)| ParquetField {
+                    name: name.to_string(),
+                    r#type: *r#type,
+                })
+                .collect(),
+            row_group_size: NonZeroUsize::new(row_group_size).unwrap(),
+            compression: ParquetCompression::Snappy,
+        })
+        .build()
+        .unwrap()
+    }
+
+    fn read(bytes: BytesMut) -> SerializedFileReader<Bytes> {
+        SerializedFileReader::new(bytes.freeze()).unwrap()
+    }
+
+    #[test]
+    fn serialize_batch() {
+        let timestamp = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let events = (0..3)
+            .map(|n| {
+                let mut log = LogEvent::from(format!("message {}", n));
+                log.insert("status", n * 100);
+                log.insert("timestamp", timestamp);
+                if n != 1 {
+                    log.insert("ok", n == 0);
+                }
+                Event::from(log)
+            })
+            .collect();
+        let serializer = serializer(
+            &[
+                ("message", ParquetFieldType::String),
+                ("status", ParquetFieldType::Integer),
+                ("ok", ParquetFieldType::Boolean),
+                ("timestamp", ParquetFieldType::Timestamp),
+            ],
+            2,
+        );
+        let mut bytes = BytesMut::new();
+
+        serializer.encode_batch(events, &mut bytes).unwrap();
+
+        let reader = read(bytes);
+        assert_eq!(reader.metadata().num_row_groups(), 2);
+        let rows = reader.get_row_iter(None).unwrap().collect::<Vec<_>>();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[2].get_string(0).unwrap(), "message 2");
+        assert_eq!(rows[2].get_long(1).unwrap(), 200);
+        assert!(rows[0].get_bool(2).unwrap());
+        assert!(rows[1].get_bool(2).is_err());
+        assert_eq!(
+            rows[0].get_timestamp_micros(3).unwrap() as i64,
+            timestamp.timestamp_micros()
+        );
+    }
+
+    #[test]
+    fn serialize_nested_values_as_json() {
+        let mut log = LogEvent::default();
+        log.insert("tags.env", "prod");
+        let serializer = serializer(&[("tags", ParquetFieldType::String)], 10);
+        let mut bytes = BytesMut::new();
+
+        serializer
+            .encode_batch(vec![log.into()], &mut bytes)
+            .unwrap();
+
+        let row = read(bytes).get_row_iter(None).unwrap().next().unwrap();
+        assert_eq!(row.get_string(0).unwrap(), r#"{"env":"prod"}"#);
+    }
+
+    #[test]
+    fn rejects_mismatched_values() {
+        let mut log = LogEvent::default();
+        log.insert("status", "ok");
+        let serializer = serializer(&[("status", ParquetFieldType::Integer)], 10);
+
+        assert!(serializer
+            .encode_batch(vec![log.into()], &mut BytesMut::new())
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_empty_fields() {
+        let config = ParquetSerializerConfig::new(ParquetSerializerOptions {
+            fields: Vec::new(),
+            row_group_size: default_row_group_size(),
+            compression: ParquetCompression::default(),
+        });
+
+        assert!(config.build().is_err());
+    }
+}
@@ -0,0 +1,326 @@
+use std::{borrow::Cow, fmt::Write as _, num::NonZeroUsize};
+
+use bytes::BytesMut;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// Config used to build a `PrettySerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PrettySerializerConfig {
+    /// Options for the pretty serializer.
+    #[serde(default)]
+    pub pretty: PrettySerializerOptions,
+}
+
+impl PrettySerializerConfig {
+    /// Creates a new `PrettySerializerConfig`.
+    pub const fn new(pretty: PrettySerializerOptions) -> Self {
+        Self { pretty }
+    }
+
+    /// Build the `PrettySerializer` from this configuration, for output that isn't written to a
+    /// terminal.
+    pub fn build(&self) -> PrettySerializer {
+        self.build_for_terminal(false)
+    }
+
+    /// Build the `PrettySerializer` from this configuration, coloring the output if it's written
+    /// to a terminal and colors are set to `auto`.
+    pub fn build_for_terminal(&self, is_terminal: bool) -> PrettySerializer {
+        PrettySerializer {
+            colors: match self.pretty.colors {
+                PrettyColors::Auto => is_terminal,
+                PrettyColors::Always => true,
+                PrettyColors::Never => false,
+            },
+            level_key: self.pretty.level_key.clone(),
+            max_value_length: self.pretty.max_value_length,
+            max_fields: self.pretty.max_fields,
+        }
+    }
+
+    /// The data type of events that are accepted by `PrettySerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log | DataType::Metric
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Pretty serializer options.
+#[configurable_component]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrettySerializerOptions {
+    /// Whether to color the output.
+    #[serde(default)]
+    pub colors: PrettyColors,
+
+    /// The field holding the severity level of log events.
+    ///
+    /// The level is rendered after the timestamp of the event, colored by its severity.
+    #[serde(default = "default_level_key")]
+    #[configurable(metadata(docs::examples = "level", docs::examples = "severity"))]
+    pub level_key: String,
+
+    /// The maximum number of characters rendered for a value.
+    ///
+    /// Longer values are truncated, and end with an ellipsis.
+    #[serde(default)]
+    pub max_value_length: Option<NonZeroUsize>,
+
+    /// The maximum number of fields rendered for an event, besides its timestamp, level, and
+    /// message.
+    ///
+    /// The number of the fields left out is rendered instead of them.
+    #[serde(default)]
+    pub max_fields: Option<NonZeroUsize>,
+}
+
+impl Default for PrettySerializerOptions {
+    fn default() -> Self {
+        Self {
+            colors: PrettyColors::default(),
+            level_key: default_level_key(),
+            max_value_length: None,
+            max_fields: None,
+        }
+    }
+}
+
+fn default_level_key() -> String {
+    "level".to_owned()
+}
+
+/// When to color the output of the pretty serializer.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PrettyColors {
+    /// Color the output when it's written to a terminal.
+    ///
+    /// Only the `console` sink writes to a terminal, so the output of other sinks is never colored.
+    #[default]
+    Auto,
+
+    /// Always color the output.
+    Always,
+
+    /// Never color the output.
+    Never,
+}
+
+/// Serializer that converts an `Event` to a human-readable text, for interactive debugging.
+///
+/// A log event is rendered as a line holding its timestamp, level, and message, followed by a line
+/// for each of its other fields, with their keys aligned. A metric is rendered the same way as
+/// with the `text` serializer.
+#[derive(Debug, Clone)]
+pub struct PrettySerializer {
+    colors: bool,
+    level_key: String,
+    max_value_length: Option<NonZeroUsize>,
+    max_fields: Option<NonZeroUsize>,
+}
+
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const BLUE: &str = "\x1b[34m";
+const MAGENTA: &str = "\x1b[35m";
+const CYAN: &str = "\x1b[36m";
+
+impl PrettySerializer {
+    fn paint<'a>(&self, color: Option<&str>, text: &'a str) -> Cow<'a, str> {
+        match color {
+            Some(color) if self.colors => format!("{}{}{}", color, text, RESET).into(),
+            _ => text.into(),
+        }
+    }
+
+    fn truncate<'a>(&self, value: &'a Value) -> Cow<'a, str> {
+        let text = value.to_string_lossy();
+        let text = if text.contains('\n') {
+            text.replace('\n', "\\n").into()
+        } else {
+            text
+        };
+        match self
+            .max_value_length
+            .and_then(|max| text.char_indices().nth(max.get()))
+        {
+            Some((end, _)) => format!("{}…", &text[..end]).into(),
+            None => text,
+        }
+    }
+
+    fn render_log(&self, log: &LogEvent) -> Vec<String> {
+        let message_key = log_schema().message_key();
+        let timestamp_key = log_schema().timestamp_key();
+
+        let mut header = Vec::new();
+        if let Some(timestamp) = log.get(timestamp_key) {
+            header.push(
+                self.paint(Some(DIM), &timestamp.to_string_lossy())
+                    .into_owned(),
+            );
+        }
+        if let Some(level) = log.get(self.level_key.as_str()) {
+            let level = level.to_string_lossy();
+            let text = format!("{:<5}", level.to_uppercase());
+            header.push(self.paint(level_color(&level), &text).into_owned());
+        }
+        if let Some(message) = log.get(message_key) {
+            header.push(self.truncate(message).into_owned());
+        }
+
+        let fields = log
+            .all_fields()
+            .into_iter()
+            .flatten()
+            .filter(|(key, _)| key != message_key && key != timestamp_key && *key != self.level_key)
+            .collect::<Vec<_>>();
+        let shown = self.max_fields.map_or(fields.len(), |max| max.get());
+        let width = fields
+            .iter()
+            .take(shown)
+            .map(|(key, _)| key.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let mut lines = Vec::with_capacity(fields.len() + 1);
+        if !header.is_empty() {
+            lines.push(header.join(" "));
+        }
+        for (key, value) in fields.iter().take(shown) {
+            let key = format!("{:<width$}", key, width = width);
+            lines.push(format!(
+                "  {} = {}",
+                self.paint(Some(CYAN), &key),
+                self.truncate(value)
+            ));
+        }
+        if fields.len() > shown {
+            let more = format!("… {} more fields", fields.len() - shown);
+            lines.push(format!("  {}", self.paint(Some(DIM), &more)));
+        }
+        lines
+    }
+}
+
+/// The color of a severity level, which covers both the syslog levels and the usual logging
+/// library ones.
+fn level_color(level: &str) -> Option<&'static str> {
+    match level.to_lowercase().as_str() {
+        "emerg" | "emergency" | "alert" | "crit" | "critical" | "fatal" | "err" | "error" => {
+            Some(RED)
+        }
+        "warn" | "warning" => Some(YELLOW),
+        "notice" | "info" | "informational" => Some(GREEN),
+        "debug" => Some(BLUE),
+        "trace" => Some(MAGENTA),
+        _ => None,
+    }
+}
+
+impl Encoder<Event> for PrettySerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        match event {
+            Event::Log(log) => write!(buffer, "{}", self.render_log(&log).join("\n"))?,
+            Event::Metric(metric) => write!(buffer, "{}", metric)?,
+            Event::Trace(_) => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn serialize(options: PrettySerializerOptions, log: LogEvent) -> String {
+        let mut bytes = BytesMut::new();
+        PrettySerializerConfig::new(options)
+            .build()
+            .encode(log.into(), &mut bytes)
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    fn log() -> LogEvent {
+        let mut log = LogEvent::from("user logged in");
+        log.insert("timestamp", Utc.timestamp_opt(1_700_000_000, 0).unwrap());
+        log.insert("level", "warn");
+        log.insert("user.id", 42);
+        log.insert("host", "localhost");
+        log.insert("path", "/".repeat(20));
+        log
+    }
+
+    #[test]
+    fn serialize_aligned_fields() {
+        assert_eq!(
+            serialize(PrettySerializerOptions::default(), log()),
+            [
+                "2023-11-14T22:13:20Z WARN  user logged in",
+                "  host    = localhost",
+                "  path    = ////////////////////",
+                "  user.id = 42",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn serialize_truncated() {
+        let options = PrettySerializerOptions {
+            max_value_length: NonZeroUsize::new(4),
+            max_fields: NonZeroUsize::new(2),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serialize(options, log()),
+            [
+                "2023-11-14T22:13:20Z WARN  user…",
+                "  host = loca…",
+                "  path = ////…",
+                "  … 1 more fields",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn serialize_colors() {
+        let options = PrettySerializerOptions {
+            colors: PrettyColors::Always,
+            max_fields: NonZeroUsize::new(1),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serialize(options, log()),
+            [
+                "\x1b[2m2023-11-14T22:13:20Z\x1b[0m \x1b[33mWARN \x1b[0m user logged in",
+                "  \x1b[36mhost\x1b[0m = localhost",
+                "  \x1b[2m… 2 more fields\x1b[0m",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,422 @@
+use crate::encoding::BuildError;
+use std::{collections::HashMap, path::PathBuf};
+
+use bytes::BytesMut;
+use prost::Message;
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MapKey, MessageDescriptor,
+    Value as ProtobufValue,
+};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::DataType,
+    event::{Event, Value},
+    schema,
+};
+
+/// Config used to build a `ProtobufSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProtobufSerializerConfig {
+    /// Options for the Protobuf serializer.
+    pub protobuf: ProtobufSerializerOptions,
+}
+
+impl ProtobufSerializerConfig {
+    /// Creates a new `ProtobufSerializerConfig`.
+    pub const fn new(protobuf: ProtobufSerializerOptions) -> Self {
+        Self { protobuf }
+    }
+
+    /// Build the `ProtobufSerializer` from this configuration.
+    pub fn build(&self) -> Result<ProtobufSerializer, BuildError> {
+        let descriptor_set = std::fs::read(&self.protobuf.desc_file).map_err(|error| {
+            format!(
+                "Failed reading Protobuf descriptor set `{}`: {}",
+                self.protobuf.desc_file.display(),
+                error
+            )
+        })?;
+        let pool = DescriptorPool::decode(descriptor_set.as_slice()).map_err(|error| {
+            format!(
+                "Failed decoding Protobuf descriptor set `{}`: {}",
+                self.protobuf.desc_file.display(),
+                error
+            )
+        })?;
+        let message_descriptor = pool
+            .get_message_by_name(&self.protobuf.message_type)
+            .ok_or_else(|| {
+                format!(
+                    "Protobuf message type `{}` not found in descriptor set `{}`",
+                    self.protobuf.message_type,
+                    self.protobuf.desc_file.display()
+                )
+            })?;
+
+        Ok(ProtobufSerializer { message_descriptor })
+    }
+
+    /// The data type of events that are accepted by `ProtobufSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Protobuf serializer options.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ProtobufSerializerOptions {
+    /// The path to the Protobuf descriptor set file.
+    ///
+    /// This file is the output of `protoc --include_imports --descriptor_set_out <desc_file>`, and
+    /// must include the message type and the types it depends on.
+    #[configurable(metadata(docs::examples = "/etc/vector/protobuf_descriptor_set.desc"))]
+    pub desc_file: PathBuf,
+
+    /// The fully qualified name of the message type to encode events as.
+    #[configurable(metadata(docs::examples = "package.Message"))]
+    pub message_type: String,
+}
+
+/// Serializer that converts an `Event` to bytes using a user-provided Protobuf message type.
+///
+/// The fields of the event are mapped onto the fields of the message with the same name, fields
+/// missing from the message are ignored. Values are converted to the type of their field, and
+/// events with values that can't be converted fail to encode:
+///
+/// - Integer fields accept integers in their range, and floating point fields accept integers and
+///   floating point numbers.
+/// - String fields accept strings and timestamps, which are encoded as RFC 3339 timestamps.
+/// - Enum fields accept the name or the number of a value of the enum.
+/// - `google.protobuf.Timestamp` fields accept timestamps, and other message fields accept objects.
+/// - Repeated fields accept arrays, and map fields accept objects.
+#[derive(Debug, Clone)]
+pub struct ProtobufSerializer {
+    message_descriptor: MessageDescriptor,
+}
+
+impl ProtobufSerializer {
+    /// Creates a new `ProtobufSerializer` for the given message type.
+    pub const fn new(message_descriptor: MessageDescriptor) -> Self {
+        Self { message_descriptor }
+    }
+
+    /// The message type events are encoded as.
+    pub const fn descriptor(&self) -> &MessageDescriptor {
+        &self.message_descriptor
+    }
+}
+
+impl Encoder<Event> for ProtobufSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        let message = match log.value() {
+            Value::Object(fields) => encode_message(&self.message_descriptor, fields, "")?,
+            value => {
+                return Err(format!(
+                    "Protobuf serializer requires an object, got {}",
+                    value.kind_str()
+                )
+                .into())
+            }
+        };
+        message.encode(buffer)?;
+        Ok(())
+    }
+}
+
+fn encode_message<'a>(
+    descriptor: &MessageDescriptor,
+    fields: impl IntoIterator<Item = (&'a String, &'a Value)>,
+    prefix: &str,
+) -> Result<DynamicMessage, vector_common::Error> {
+    let mut message = DynamicMessage::new(descriptor.clone());
+    for (name, value) in fields {
+        let Some(field) = descriptor.get_field_by_name(name) else {
+            continue;
+        };
+        if matches!(value, Value::Null) {
+            continue;
+        }
+        let path = format!("{}{}", prefix, name);
+        let value = encode_field(&field, value, &path)?;
+        message.try_set_field(&field, value)?;
+    }
+    Ok(message)
+}
+
+fn encode_field(
+    field: &FieldDescriptor,
+    value: &Value,
+    path: &str,
+) -> Result<ProtobufValue, vector_common::Error> {
+    if field.is_map() {
+        let Kind::Message(entry) = field.kind() else {
+            unreachable!("map fields are messages");
+        };
+        let (key_field, value_field) = (entry.map_entry_key_field(), entry.map_entry_value_field());
+        let Value::Object(entries) = value else {
+            return Err(mismatch(path, field, value));
+        };
+        return entries
+            .iter()
+            .filter(|(_, value)| !matches!(value, Value::Null))
+            .map(|(key, value)| {
+                let path = format!("{}.{}", path, key);
+                Ok((
+                    encode_map_key(&key_field, key, &path)?,
+                    encode_value(&value_field, value, &path)?,
+                ))
+            })
+            .collect::<Result<HashMap<_, _>, vector_common::Error>>()
+            .map(ProtobufValue::Map);
+    }
+
+    if field.is_list() {
+        let Value::Array(items) = value else {
+            return Err(mismatch(path, field, value));
+        };
+        return items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| encode_value(field, item, &format!("{}[{}]", path, index)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(ProtobufValue::List);
+    }
+
+    encode_value(field, value, path)
+}
+
+/// Converts a value to the kind of a field, ignoring whether the field is repeated.
+fn encode_value(
+    field: &FieldDescriptor,
+    value: &Value,
+    path: &str,
+) -> Result<ProtobufValue, vector_common::Error> {
+    let out_of_range = || format!("Field `{}` is out of the range of its type", path);
+    Ok(match (field.kind(), value) {
+        (Kind::Double, Value::Float(float)) => ProtobufValue::F64(float.into_inner()),
+        (Kind::Double, Value::Integer(integer)) => ProtobufValue::F64(*integer as f64),
+        (Kind::Float, Value::Float(float)) => ProtobufValue::F32(float.into_inner() as f32),
+        (Kind::Float, Value::Integer(integer)) => ProtobufValue::F32(*integer as f32),
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, Value::Integer(integer)) => {
+            ProtobufValue::I32(i32::try_from(*integer).map_err(|_| out_of_range())?)
+        }
+        (Kind::Int64 | Kind::Sint64 | Kind::Sfixed64, Value::Integer(integer)) => {
+            ProtobufValue::I64(*integer)
+        }
+        (Kind::Uint32 | Kind::Fixed32, Value::Integer(integer)) => {
+            ProtobufValue::U32(u32::try_from(*integer).map_err(|_| out_of_range())?)
+        }
+        (Kind::Uint64 | Kind::Fixed64, Value::Integer(integer)) => {
+            ProtobufValue::U64(u64::try_from(*integer).map_err(|_| out_of_range())?)
+        }
+        (Kind::Bool, Value::Boolean(boolean)) => ProtobufValue::Bool(*boolean),
+        (Kind::String, Value::Bytes(bytes)) => {
+            ProtobufValue::String(String::from_utf8_lossy(bytes).into_owned())
+        }
+        (Kind::String, Value::Timestamp(timestamp)) => {
+            ProtobufValue::String(timestamp.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true))
+        }
+        (Kind::Bytes, Value::Bytes(bytes)) => ProtobufValue::Bytes(bytes.clone()),
+        (Kind::Enum(descriptor), Value::Bytes(bytes)) => {
+            let name = String::from_utf8_lossy(bytes);
+            let value = descriptor.get_value_by_name(&name).ok_or_else(|| {
+                format!(
+                    "Field `{}` holds `{}`, which isn't a value of enum `{}`",
+                    path,
+                    name,
+                    descriptor.full_name()
+                )
+            })?;
+            ProtobufValue::EnumNumber(value.number())
+        }
+        (Kind::Enum(_), Value::Integer(integer)) => {
+            ProtobufValue::EnumNumber(i32::try_from(*integer).map_err(|_| out_of_range())?)
+        }
+        (Kind::Message(descriptor), Value::Timestamp(timestamp))
+            if descriptor.full_name() == "google.protobuf.Timestamp" =>
+        {
+            let mut message = DynamicMessage::new(descriptor);
+            message.try_set_field_by_name("seconds", ProtobufValue::I64(timestamp.timestamp()))?;
+            message.try_set_field_by_name(
+                "nanos",
+                ProtobufValue::I32(timestamp.timestamp_subsec_nanos() as i32),
+            )?;
+            ProtobufValue::Message(message)
+        }
+        (Kind::Message(descriptor), Value::Object(fields)) => {
+            ProtobufValue::Message(encode_message(&descriptor, fields, &format!("{}.", path))?)
+        }
+        (_, value) => return Err(mismatch(path, field, value)),
+    })
+}
+
+fn encode_map_key(
+    field: &FieldDescriptor,
+    key: &str,
+    path: &str,
+) -> Result<MapKey, vector_common::Error> {
+    let invalid = || format!("Key of field `{}` isn't a valid {:?}", path, field.kind());
+    Ok(match field.kind() {
+        Kind::String => MapKey::String(key.to_owned()),
+        Kind::Bool => MapKey::Bool(key.parse().map_err(|_| invalid())?),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => {
+            MapKey::I32(key.parse().map_err(|_| invalid())?)
+        }
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => {
+            MapKey::I64(key.parse().map_err(|_| invalid())?)
+        }
+        Kind::Uint32 | Kind::Fixed32 => MapKey::U32(key.parse().map_err(|_| invalid())?),
+        Kind::Uint64 | Kind::Fixed64 => MapKey::U64(key.parse().map_err(|_| invalid())?),
+        _ => return Err(invalid().into()),
+    })
+}
+
+fn mismatch(path: &str, field: &FieldDescriptor, value: &Value) -> vector_common::Error {
+    let kind = match field.kind() {
+        Kind::Message(descriptor) => descriptor.full_name().to_owned(),
+        Kind::Enum(descriptor) => descriptor.full_name().to_owned(),
+        kind => format!("{:?}", kind).to_lowercase(),
+    };
+    let kind = if field.is_map() {
+        "map".to_owned()
+    } else if field.is_list() {
+        format!("repeated {}", kind)
+    } else {
+        kind
+    };
+    format!(
+        "Field `{}` of type {} can't hold a value of type {}",
+        path,
+        kind,
+        value.kind_str()
+    )
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::{TimeZone, Utc};
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn test_data_dir() -> PathBuf {
+        PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap()).join("tests/data/protobuf")
+    }
+
+    fn serializer() -> ProtobufSerializer {
+        ProtobufSerializerConfig::new(ProtobufSerializerOptions {
+            desc_file: test_data_dir().join("test.desc"),
+            message_type: "test.Log".to_owned(),
+        })
+        .build()
+        .unwrap()
+    }
+
+    fn encode(log: LogEvent) -> Result<DynamicMessage, vector_common::Error> {
+        let mut serializer = serializer();
+        let mut bytes = BytesMut::new();
+        serializer.encode(log.into(), &mut bytes)?;
+        Ok(DynamicMessage::decode(serializer.descriptor().clone(), bytes.freeze()).unwrap())
+    }
+
+    #[test]
+    fn serialize_protobuf() {
+        let timestamp = Utc.timestamp_opt(1_700_000_000, 500).unwrap();
+        let mut log = LogEvent::from("hello");
+        log.insert("status", 200);
+        log.insert("duration", 12);
+        log.insert("ok", true);
+        log.insert("tags", vec!["a", "b"]);
+        log.insert("labels.env", "prod");
+        log.insert("level", "ERROR");
+        log.insert("timestamp", timestamp);
+        log.insert("host.name", "localhost");
+        log.insert("host.port", 8080);
+        log.insert("payload", Bytes::from_static(b"\x00\x01"));
+        log.insert("unknown", "ignored");
+
+        let message = encode(log).unwrap();
+
+        let field = |name| message.get_field_by_name(name).unwrap().into_owned();
+        assert_eq!(field("message"), ProtobufValue::String("hello".to_owned()));
+        assert_eq!(field("status"), ProtobufValue::I64(200));
+        assert_eq!(field("duration"), ProtobufValue::F64(12.0));
+        assert_eq!(field("ok"), ProtobufValue::Bool(true));
+        assert_eq!(
+            field("tags"),
+            ProtobufValue::List(vec![
+                ProtobufValue::String("a".to_owned()),
+                ProtobufValue::String("b".to_owned())
+            ])
+        );
+        assert_eq!(
+            field("labels"),
+            ProtobufValue::Map(HashMap::from([(
+                MapKey::String("env".to_owned()),
+                ProtobufValue::String("prod".to_owned())
+            )]))
+        );
+        assert_eq!(field("level"), ProtobufValue::EnumNumber(2));
+        assert_eq!(
+            field("payload"),
+            ProtobufValue::Bytes(Bytes::from_static(b"\x00\x01"))
+        );
+
+        let timestamp = field("timestamp");
+        let timestamp = timestamp.as_message().unwrap();
+        assert_eq!(
+            timestamp.get_field_by_name("seconds").unwrap().as_i64(),
+            Some(1_700_000_000)
+        );
+        assert_eq!(
+            timestamp.get_field_by_name("nanos").unwrap().as_i32(),
+            Some(500)
+        );
+
+        let host = field("host");
+        let host = host.as_message().unwrap();
+        assert_eq!(host.get_field_by_name("port").unwrap().as_u32(), Some(8080));
+    }
+
+    #[test]
+    fn rejects_mismatched_values() {
+        let mut log = LogEvent::default();
+        log.insert("status", "ok");
+        let error = encode(log).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Field `status` of type int64 can't hold a value of type string"
+        );
+
+        let mut log = LogEvent::default();
+        log.insert("host.port", -1);
+        assert!(encode(log).is_err());
+
+        let mut log = LogEvent::default();
+        log.insert("level", "DEBUG");
+        assert!(encode(log).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_message_type() {
+        let config = ProtobufSerializerConfig::new(ProtobufSerializerOptions {
+            desc_file: test_data_dir().join("test.desc"),
+            message_type: "test.Missing".to_owned(),
+        });
+
+        assert!(config.build().is_err());
+    }
+}
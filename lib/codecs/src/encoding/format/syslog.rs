@@ -0,0 +1,620 @@
+use super::template::FieldTemplate;
+use crate::encoding::BuildError;
+use std::fmt::Write as _;
+
+use bytes::BytesMut;
+use chrono::{DateTime, SecondsFormat, Utc};
+use lookup::{lookup_v2::parse_value_path, OwnedValuePath, PathPrefix};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// Config used to build a `SyslogSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SyslogSerializerConfig {
+    /// Options for the syslog serializer.
+    #[serde(default)]
+    pub syslog: SyslogSerializerOptions,
+}
+
+impl SyslogSerializerConfig {
+    /// Creates a new `SyslogSerializerConfig`.
+    pub const fn new(syslog: SyslogSerializerOptions) -> Self {
+        Self { syslog }
+    }
+
+    /// Build the `SyslogSerializer` from this configuration.
+    pub fn build(&self) -> Result<SyslogSerializer, BuildError> {
+        let parse_path = |path: &str| {
+            parse_value_path(path)
+                .map_err(|error| format!("Invalid syslog field path `{}`: {}", path, error))
+        };
+
+        Ok(SyslogSerializer {
+            rfc: self.syslog.rfc,
+            facility_key: parse_path(&self.syslog.facility_key)?,
+            default_facility: self.syslog.default_facility,
+            severity_key: parse_path(&self.syslog.severity_key)?,
+            default_severity: self.syslog.default_severity,
+            app_name: FieldTemplate::parse(&self.syslog.app_name)?,
+            proc_id: self
+                .syslog
+                .proc_id
+                .as_deref()
+                .map(FieldTemplate::parse)
+                .transpose()?,
+            msg_id: self
+                .syslog
+                .msg_id
+                .as_deref()
+                .map(FieldTemplate::parse)
+                .transpose()?,
+            structured_data_key: self
+                .syslog
+                .structured_data_key
+                .as_deref()
+                .map(parse_path)
+                .transpose()?,
+        })
+    }
+
+    /// The data type of events that are accepted by `SyslogSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Syslog serializer options.
+#[configurable_component]
+#[derive(Debug, Clone)]
+pub struct SyslogSerializerOptions {
+    /// The format of the messages.
+    #[serde(default)]
+    pub rfc: SyslogRfc,
+
+    /// The field holding the facility of events.
+    ///
+    /// Both the names and the numeric codes of facilities are supported.
+    #[serde(default = "default_facility_key")]
+    #[configurable(metadata(docs::examples = "facility"))]
+    pub facility_key: String,
+
+    /// The facility of events whose facility field is missing, or holds an unknown facility.
+    #[serde(default)]
+    pub default_facility: SyslogFacility,
+
+    /// The field holding the severity of events.
+    ///
+    /// Both the names and the numeric codes of severities are supported, as well as the usual
+    /// logging library levels, such as `error`, `warn`, and `fatal`.
+    #[serde(default = "default_severity_key")]
+    #[configurable(metadata(docs::examples = "severity", docs::examples = "level"))]
+    pub severity_key: String,
+
+    /// The severity of events whose severity field is missing, or holds an unknown severity.
+    #[serde(default)]
+    pub default_severity: SyslogSeverity,
+
+    /// The name of the application that produced the events.
+    ///
+    /// The name can reference the fields of events, such as `{{ service }}`. If a referenced
+    /// field is missing, the name is left out.
+    #[serde(default = "default_app_name")]
+    #[configurable(metadata(docs::examples = "vector", docs::examples = "{{ service }}"))]
+    pub app_name: String,
+
+    /// The ID of the process that produced the events.
+    ///
+    /// The ID can reference the fields of events, such as `{{ pid }}`. If a referenced field is
+    /// missing, the ID is left out.
+    #[configurable(metadata(docs::examples = "{{ pid }}"))]
+    pub proc_id: Option<String>,
+
+    /// The ID of the type of the events.
+    ///
+    /// The ID can reference the fields of events, such as `{{ event.type }}`. If a referenced
+    /// field is missing, the ID is left out. This is only supported by RFC 5424 messages.
+    #[configurable(metadata(docs::examples = "{{ event.type }}"))]
+    pub msg_id: Option<String>,
+
+    /// The field holding the structured data of events.
+    ///
+    /// The field must hold an object whose keys are the IDs of the structured data elements, and
+    /// whose values are objects holding the parameters of the elements. This is only supported by
+    /// RFC 5424 messages.
+    #[configurable(metadata(docs::examples = "structured_data"))]
+    pub structured_data_key: Option<String>,
+}
+
+impl Default for SyslogSerializerOptions {
+    fn default() -> Self {
+        Self {
+            rfc: SyslogRfc::default(),
+            facility_key: default_facility_key(),
+            default_facility: SyslogFacility::default(),
+            severity_key: default_severity_key(),
+            default_severity: SyslogSeverity::default(),
+            app_name: default_app_name(),
+            proc_id: None,
+            msg_id: None,
+            structured_data_key: None,
+        }
+    }
+}
+
+fn default_facility_key() -> String {
+    "facility".to_owned()
+}
+
+fn default_severity_key() -> String {
+    "severity".to_owned()
+}
+
+fn default_app_name() -> String {
+    "vector".to_owned()
+}
+
+/// The format of syslog messages.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogRfc {
+    /// The format of [RFC 5424][rfc5424].
+    ///
+    /// [rfc5424]: https://www.rfc-editor.org/rfc/rfc5424
+    #[default]
+    Rfc5424,
+
+    /// The legacy BSD format of [RFC 3164][rfc3164].
+    ///
+    /// [rfc3164]: https://www.rfc-editor.org/rfc/rfc3164
+    Rfc3164,
+}
+
+/// A syslog facility.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogFacility {
+    /// Kernel messages.
+    Kern,
+    /// User-level messages.
+    #[default]
+    User,
+    /// Mail system.
+    Mail,
+    /// System daemons.
+    Daemon,
+    /// Security/authorization messages.
+    Auth,
+    /// Messages generated internally by syslogd.
+    Syslog,
+    /// Line printer subsystem.
+    Lpr,
+    /// Network news subsystem.
+    News,
+    /// UUCP subsystem.
+    Uucp,
+    /// Scheduling daemon.
+    Cron,
+    /// Private security/authorization messages.
+    Authpriv,
+    /// FTP daemon.
+    Ftp,
+    /// NTP subsystem.
+    Ntp,
+    /// Log audit.
+    Audit,
+    /// Log alert.
+    Alert,
+    /// Clock daemon.
+    Clockd,
+    /// Local use 0.
+    Local0,
+    /// Local use 1.
+    Local1,
+    /// Local use 2.
+    Local2,
+    /// Local use 3.
+    Local3,
+    /// Local use 4.
+    Local4,
+    /// Local use 5.
+    Local5,
+    /// Local use 6.
+    Local6,
+    /// Local use 7.
+    Local7,
+}
+
+impl SyslogFacility {
+    const ALL: [Self; 24] = [
+        Self::Kern,
+        Self::User,
+        Self::Mail,
+        Self::Daemon,
+        Self::Auth,
+        Self::Syslog,
+        Self::Lpr,
+        Self::News,
+        Self::Uucp,
+        Self::Cron,
+        Self::Authpriv,
+        Self::Ftp,
+        Self::Ntp,
+        Self::Audit,
+        Self::Alert,
+        Self::Clockd,
+        Self::Local0,
+        Self::Local1,
+        Self::Local2,
+        Self::Local3,
+        Self::Local4,
+        Self::Local5,
+        Self::Local6,
+        Self::Local7,
+    ];
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Integer(code) => usize::try_from(*code)
+                .ok()
+                .and_then(|code| Self::ALL.get(code).copied()),
+            Value::Bytes(_) => {
+                let name = value.to_string_lossy().to_lowercase();
+                let code = match name.as_str() {
+                    "kern" => 0,
+                    "user" => 1,
+                    "mail" => 2,
+                    "daemon" => 3,
+                    "auth" | "security" => 4,
+                    "syslog" => 5,
+                    "lpr" => 6,
+                    "news" => 7,
+                    "uucp" => 8,
+                    "cron" => 9,
+                    "authpriv" => 10,
+                    "ftp" => 11,
+                    "ntp" => 12,
+                    "audit" => 13,
+                    "alert" => 14,
+                    "clockd" => 15,
+                    name => 16 + name.strip_prefix("local")?.parse::<usize>().ok()?,
+                };
+                Self::ALL.get(code).copied()
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A syslog severity.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogSeverity {
+    /// System is unusable.
+    Emerg,
+    /// Action must be taken immediately.
+    Alert,
+    /// Critical conditions.
+    Crit,
+    /// Error conditions.
+    Err,
+    /// Warning conditions.
+    Warning,
+    /// Normal but significant conditions.
+    Notice,
+    /// Informational messages.
+    #[default]
+    Info,
+    /// Debug-level messages.
+    Debug,
+}
+
+impl SyslogSeverity {
+    const ALL: [Self; 8] = [
+        Self::Emerg,
+        Self::Alert,
+        Self::Crit,
+        Self::Err,
+        Self::Warning,
+        Self::Notice,
+        Self::Info,
+        Self::Debug,
+    ];
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Integer(code) => usize::try_from(*code)
+                .ok()
+                .and_then(|code| Self::ALL.get(code).copied()),
+            Value::Bytes(_) => match value.to_string_lossy().to_lowercase().as_str() {
+                "emerg" | "emergency" | "panic" => Some(Self::Emerg),
+                "alert" => Some(Self::Alert),
+                "crit" | "critical" | "fatal" => Some(Self::Crit),
+                "err" | "error" => Some(Self::Err),
+                "warning" | "warn" => Some(Self::Warning),
+                "notice" => Some(Self::Notice),
+                "info" | "informational" => Some(Self::Info),
+                "debug" | "trace" => Some(Self::Debug),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// The value of header fields that are left out.
+const NIL_VALUE: &str = "-";
+
+/// Serializer that converts an `Event` to bytes using the syslog format of [RFC 5424][rfc5424],
+/// or the legacy BSD format of [RFC 3164][rfc3164].
+///
+/// The host, timestamp, and message of events are read from the fields of the log schema.
+///
+/// [rfc5424]: https://www.rfc-editor.org/rfc/rfc5424
+/// [rfc3164]: https://www.rfc-editor.org/rfc/rfc3164
+#[derive(Debug, Clone)]
+pub struct SyslogSerializer {
+    rfc: SyslogRfc,
+    facility_key: OwnedValuePath,
+    default_facility: SyslogFacility,
+    severity_key: OwnedValuePath,
+    default_severity: SyslogSeverity,
+    app_name: FieldTemplate,
+    proc_id: Option<FieldTemplate>,
+    msg_id: Option<FieldTemplate>,
+    structured_data_key: Option<OwnedValuePath>,
+}
+
+impl SyslogSerializer {
+    fn priority(&self, log: &LogEvent) -> u8 {
+        let facility = log
+            .get((PathPrefix::Event, &self.facility_key))
+            .and_then(SyslogFacility::from_value)
+            .unwrap_or(self.default_facility);
+        let severity = log
+            .get((PathPrefix::Event, &self.severity_key))
+            .and_then(SyslogSeverity::from_value)
+            .unwrap_or(self.default_severity);
+        facility as u8 * 8 + severity as u8
+    }
+
+    fn encode_rfc5424(&self, log: &LogEvent, buffer: &mut BytesMut) -> std::fmt::Result {
+        let render = |template: Option<&FieldTemplate>| template.and_then(|t| t.render(log));
+        let timestamp = timestamp(log).map_or_else(
+            || NIL_VALUE.to_owned(),
+            |timestamp| timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+        );
+
+        write!(
+            buffer,
+            "<{}>1 {} {} {} {} {} ",
+            self.priority(log),
+            timestamp,
+            header_field(
+                log.get(log_schema().host_key())
+                    .map(|host| host.to_string_lossy())
+                    .as_deref(),
+                255
+            ),
+            header_field(render(Some(&self.app_name)).as_deref(), 48),
+            header_field(render(self.proc_id.as_ref()).as_deref(), 128),
+            header_field(render(self.msg_id.as_ref()).as_deref(), 32),
+        )?;
+        self.write_structured_data(log, buffer)?;
+        if let Some(message) = log.get(log_schema().message_key()) {
+            write!(buffer, " {}", message.to_string_lossy())?;
+        }
+        Ok(())
+    }
+
+    fn encode_rfc3164(&self, log: &LogEvent, buffer: &mut BytesMut) -> std::fmt::Result {
+        let timestamp = timestamp(log).unwrap_or_else(Utc::now);
+        let host = log
+            .get(log_schema().host_key())
+            .map(|host| host.to_string_lossy());
+
+        write!(
+            buffer,
+            "<{}>{} {} {}",
+            self.priority(log),
+            timestamp.format("%b %e %H:%M:%S"),
+            header_field(host.as_deref(), 255),
+            header_field(self.app_name.render(log).as_deref(), 32),
+        )?;
+        if let Some(proc_id) = self.proc_id.as_ref().and_then(|t| t.render(log)) {
+            write!(buffer, "[{}]", header_field(Some(proc_id.as_str()), 128))?;
+        }
+        buffer.extend_from_slice(b":");
+        if let Some(message) = log.get(log_schema().message_key()) {
+            write!(buffer, " {}", message.to_string_lossy())?;
+        }
+        Ok(())
+    }
+
+    fn write_structured_data(&self, log: &LogEvent, buffer: &mut BytesMut) -> std::fmt::Result {
+        let elements = match self
+            .structured_data_key
+            .as_ref()
+            .and_then(|path| log.get((PathPrefix::Event, path)))
+        {
+            Some(Value::Object(elements)) => elements,
+            _ => return write!(buffer, "{}", NIL_VALUE),
+        };
+
+        let mut written = false;
+        for (id, params) in elements {
+            let id = sd_name(id);
+            if id.is_empty() {
+                continue;
+            }
+            write!(buffer, "[{}", id)?;
+            if let Value::Object(params) = params {
+                for (name, value) in params {
+                    let name = sd_name(name);
+                    if name.is_empty() || matches!(value, Value::Null) {
+                        continue;
+                    }
+                    write!(
+                        buffer,
+                        " {}=\"{}\"",
+                        name,
+                        escape_param_value(&value.to_string_lossy())
+                    )?;
+                }
+            }
+            buffer.extend_from_slice(b"]");
+            written = true;
+        }
+        if !written {
+            write!(buffer, "{}", NIL_VALUE)?;
+        }
+        Ok(())
+    }
+}
+
+fn timestamp(log: &LogEvent) -> Option<DateTime<Utc>> {
+    match log.get(log_schema().timestamp_key()) {
+        Some(Value::Timestamp(timestamp)) => Some(*timestamp),
+        _ => None,
+    }
+}
+
+/// Restricts header fields to printable ASCII characters, which excludes spaces, and to their
+/// maximum length.
+fn header_field(value: Option<&str>, max_length: usize) -> String {
+    let field = value
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_length)
+        .collect::<String>();
+    if field.is_empty() {
+        NIL_VALUE.to_owned()
+    } else {
+        field
+    }
+}
+
+/// Restricts the names of structured data elements and parameters to the printable ASCII
+/// characters they allow, and to their maximum length.
+fn sd_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_graphic() && !matches!(*c, '=' | ']' | '"'))
+        .take(32)
+        .collect()
+}
+
+/// Escapes double quotes, backslashes, and closing brackets of parameter values.
+fn escape_param_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+impl Encoder<Event> for SyslogSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        match self.rfc {
+            SyslogRfc::Rfc5424 => self.encode_rfc5424(&log, buffer)?,
+            SyslogRfc::Rfc3164 => self.encode_rfc3164(&log, buffer)?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn serialize(options: SyslogSerializerOptions, log: LogEvent) -> String {
+        let mut bytes = BytesMut::new();
+        SyslogSerializerConfig::new(options)
+            .build()
+            .unwrap()
+            .encode(log.into(), &mut bytes)
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    fn log() -> LogEvent {
+        let mut log = LogEvent::from("user logged in");
+        log.insert("timestamp", Utc.timestamp_opt(1_700_000_000, 0).unwrap());
+        log.insert("host", "web 1");
+        log.insert("service", "auth");
+        log.insert("pid", 42);
+        log.insert("severity", "warn");
+        log.insert("facility", "local3");
+        log
+    }
+
+    #[test]
+    fn serialize_rfc5424() {
+        let mut log = log();
+        log.insert("sd.origin.ip", "10.0.0.1");
+        log.insert("sd.meta.note", "a\"b]");
+        let options = SyslogSerializerOptions {
+            app_name: "{{ service }}".to_owned(),
+            proc_id: Some("{{ pid }}".to_owned()),
+            msg_id: Some("{{ missing }}".to_owned()),
+            structured_data_key: Some("sd".to_owned()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serialize(options, log),
+            "<156>1 2023-11-14T22:13:20.000Z web1 auth 42 - [meta note=\"a\\\"b\\]\"][origin ip=\"10.0.0.1\"] user logged in"
+        );
+    }
+
+    #[test]
+    fn serialize_rfc3164() {
+        let options = SyslogSerializerOptions {
+            rfc: SyslogRfc::Rfc3164,
+            proc_id: Some("{{ pid }}".to_owned()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serialize(options, log()),
+            "<156>Nov 14 22:13:20 web1 vector[42]: user logged in"
+        );
+    }
+
+    #[test]
+    fn serialize_priorities() {
+        let priority = |facility: Value, severity: Value| {
+            let mut log = LogEvent::from("event");
+            log.insert("facility", facility);
+            log.insert("severity", severity);
+            let message = serialize(SyslogSerializerOptions::default(), log);
+            message[1..message.find('>').unwrap()].to_owned()
+        };
+
+        assert_eq!(priority(Value::from(0), Value::from(0)), "0");
+        assert_eq!(priority(Value::from("auth"), Value::from("ERROR")), "35");
+        assert_eq!(priority(Value::from("local7"), Value::from("debug")), "191");
+        assert_eq!(priority(Value::from(99), Value::from("unknown")), "14");
+    }
+}
@@ -0,0 +1,71 @@
+use lookup::{lookup_v2::parse_value_path, OwnedValuePath, PathPrefix};
+use vector_core::event::{LogEvent, Value};
+
+/// A string referencing the fields of events, such as `{{ service }}`.
+#[derive(Debug, Clone)]
+pub(super) struct FieldTemplate {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Literal(String),
+    Field(OwnedValuePath),
+}
+
+impl FieldTemplate {
+    pub(super) fn parse(template: &str) -> Result<Self, String> {
+        let mut segments = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            let end = rest[start..]
+                .find("}}")
+                .ok_or_else(|| format!("Unclosed field reference in `{}`", template))?;
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_owned()));
+            }
+            let path = rest[start + 2..start + end].trim();
+            let path = parse_value_path(path)
+                .map_err(|error| format!("Invalid template field path `{}`: {}", path, error))?;
+            segments.push(Segment::Field(path));
+            rest = &rest[start + end + 2..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_owned()));
+        }
+        Ok(Self { segments })
+    }
+
+    /// Renders the template, or returns `None` if a referenced field is missing.
+    pub(super) fn render(&self, log: &LogEvent) -> Option<String> {
+        let mut rendered = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(literal) => rendered.push_str(literal),
+                Segment::Field(path) => match log.get((PathPrefix::Event, path)) {
+                    None | Some(Value::Null) => return None,
+                    Some(value) => rendered.push_str(&value.to_string_lossy()),
+                },
+            }
+        }
+        Some(rendered)
+    }
+
+    /// Renders the template, with the missing fields rendered as empty strings.
+    pub(super) fn render_lossy(&self, log: &LogEvent) -> String {
+        let mut rendered = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(literal) => rendered.push_str(literal),
+                Segment::Field(path) => {
+                    if let Some(value) = log.get((PathPrefix::Event, path)) {
+                        if !matches!(value, Value::Null) {
+                            rendered.push_str(&value.to_string_lossy());
+                        }
+                    }
+                }
+            }
+        }
+        rendered
+    }
+}
@@ -7,7 +7,8 @@ use vector_core::{
     schema,
 };
 
-use crate::MetricTagValues;
+use super::template::FieldTemplate;
+use crate::{encoding::BuildError, MetricTagValues};
 
 /// Config used to build a `TextSerializer`.
 #[crate::configurable_component]
@@ -22,17 +23,38 @@ pub struct TextSerializerConfig {
         skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
     )]
     pub metric_tag_values: MetricTagValues,
+
+    /// Options for the text serializer.
+    #[serde(
+        default,
+        rename = "text",
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub options: TextSerializerOptions,
 }
 
 impl TextSerializerConfig {
     /// Creates a new `TextSerializerConfig`.
-    pub const fn new(metric_tag_values: MetricTagValues) -> Self {
-        Self { metric_tag_values }
+    pub const fn new(metric_tag_values: MetricTagValues, options: TextSerializerOptions) -> Self {
+        Self {
+            metric_tag_values,
+            options,
+        }
     }
 
     /// Build the `TextSerializer` from this configuration.
-    pub const fn build(&self) -> TextSerializer {
-        TextSerializer::new(self.metric_tag_values)
+    pub fn build(&self) -> Result<TextSerializer, BuildError> {
+        let template = self
+            .options
+            .template
+            .as_deref()
+            .map(FieldTemplate::parse)
+            .transpose()?;
+
+        Ok(TextSerializer {
+            metric_tag_values: self.metric_tag_values,
+            template,
+        })
     }
 
     /// The data type of events that are accepted by `TextSerializer`.
@@ -42,24 +64,45 @@ impl TextSerializerConfig {
 
     /// The schema required by the serializer.
     pub fn schema_requirement(&self) -> schema::Requirement {
-        schema::Requirement::empty().required_meaning(log_schema().message_key(), Kind::any())
+        if self.options.template.is_some() {
+            schema::Requirement::empty()
+        } else {
+            schema::Requirement::empty().required_meaning(log_schema().message_key(), Kind::any())
+        }
     }
 }
 
-/// Serializer that converts a log to bytes by extracting the message key, or converts a metric
-/// to bytes by calling its `Display` implementation.
+/// Text serializer options.
+#[crate::configurable_component]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextSerializerOptions {
+    /// A template for the encoded logs, referencing their fields as `{{ field }}`.
+    ///
+    /// Missing fields are rendered as empty strings. When unset, only the message of logs is
+    /// encoded. Metrics are not affected.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "{{ host }} [{{ level }}] {{ message }}"))]
+    pub template: Option<String>,
+}
+
+/// Serializer that converts a log to bytes by extracting the message key or rendering a template,
+/// or converts a metric to bytes by calling its `Display` implementation.
 ///
 /// This serializer exists to emulate the behavior of the `StandardEncoding::Text` for backwards
 /// compatibility, until it is phased out completely.
 #[derive(Debug, Clone)]
 pub struct TextSerializer {
     metric_tag_values: MetricTagValues,
+    template: Option<FieldTemplate>,
 }
 
 impl TextSerializer {
     /// Creates a new `TextSerializer`.
     pub const fn new(metric_tag_values: MetricTagValues) -> Self {
-        Self { metric_tag_values }
+        Self {
+            metric_tag_values,
+            template: None,
+        }
     }
 }
 
@@ -71,7 +114,9 @@ impl Encoder<Event> for TextSerializer {
 
         match event {
             Event::Log(log) => {
-                if let Some(bytes) = log
+                if let Some(template) = &self.template {
+                    buffer.put(template.render_lossy(&log).as_bytes());
+                } else if let Some(bytes) = log
                     .get_by_meaning(message_key)
                     .or_else(|| log.get(message_key))
                     .map(|value| value.coerce_to_bytes())
@@ -130,6 +175,7 @@ mod tests {
         let buffer = serialize(
             TextSerializerConfig {
                 metric_tag_values: MetricTagValues::Full,
+                ..Default::default()
             },
             metric2(),
         );
@@ -144,12 +190,45 @@ mod tests {
         let buffer = serialize(
             TextSerializerConfig {
                 metric_tag_values: MetricTagValues::Single,
+                ..Default::default()
             },
             metric2(),
         );
         assert_eq!(buffer, Bytes::from(r#"counter{a="second"} + 1"#));
     }
 
+    #[test]
+    fn serialize_log_template() {
+        let mut log = LogEvent::default();
+        log.insert("host", "localhost");
+        log.insert("message", "foo");
+        log.insert("nested.level", "info");
+
+        let buffer = serialize(
+            TextSerializerConfig::new(
+                MetricTagValues::default(),
+                TextSerializerOptions {
+                    template: Some(
+                        "{{ host }} [{{ nested.level }}] {{ message }}{{ missing }}".into(),
+                    ),
+                },
+            ),
+            Event::from(log),
+        );
+        assert_eq!(buffer, Bytes::from("localhost [info] foo"));
+    }
+
+    #[test]
+    fn rejects_invalid_template() {
+        let config = TextSerializerConfig::new(
+            MetricTagValues::default(),
+            TextSerializerOptions {
+                template: Some("{{ message".into()),
+            },
+        );
+        assert!(config.build().is_err());
+    }
+
     fn metric2() -> Event {
         Event::Metric(
             Metric::new(
@@ -167,7 +246,7 @@ mod tests {
 
     fn serialize(config: TextSerializerConfig, input: Event) -> Bytes {
         let mut buffer = BytesMut::new();
-        config.build().encode(input, &mut buffer).unwrap();
+        config.build().unwrap().encode(input, &mut buffer).unwrap();
         buffer.freeze()
     }
 }
@@ -0,0 +1,299 @@
+use crate::encoding::BuildError;
+use std::{collections::BTreeSet, fmt::Write as _};
+
+use bytes::BytesMut;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::DataType,
+    event::{Event, Value},
+    schema,
+};
+
+/// Config used to build a `XmlSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct XmlSerializerConfig {
+    /// Options for the XML serializer.
+    #[serde(default)]
+    pub xml: XmlSerializerOptions,
+}
+
+impl XmlSerializerConfig {
+    /// Creates a new `XmlSerializerConfig`.
+    pub const fn new(xml: XmlSerializerOptions) -> Self {
+        Self { xml }
+    }
+
+    /// Build the `XmlSerializer` from this configuration.
+    pub fn build(&self) -> Result<XmlSerializer, BuildError> {
+        let elements = self
+            .xml
+            .root_element
+            .iter()
+            .chain(std::iter::once(&self.xml.record_element));
+        for name in elements.chain(self.xml.attributes.iter()) {
+            if !is_valid_name(name) {
+                return Err(format!("`{}` isn't a valid XML name", name).into());
+            }
+        }
+
+        Ok(XmlSerializer {
+            root_element: self.xml.root_element.clone(),
+            record_element: self.xml.record_element.clone(),
+            attributes: self.xml.attributes.iter().cloned().collect(),
+            declaration: self.xml.declaration,
+        })
+    }
+
+    /// The data type of events that are accepted by `XmlSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// XML serializer options.
+#[configurable_component]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XmlSerializerOptions {
+    /// The name of the element wrapping the record element of each event.
+    ///
+    /// If not set, the record element is the root of the document.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "Body", docs::examples = "request"))]
+    pub root_element: Option<String>,
+
+    /// The name of the element holding the fields of each event.
+    #[serde(default = "default_record_element")]
+    #[configurable(metadata(docs::examples = "event", docs::examples = "record"))]
+    pub record_element: String,
+
+    /// The top-level fields written as attributes of the record element, instead of as its child
+    /// elements.
+    ///
+    /// Fields holding objects or arrays are always written as elements.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "id", docs::examples = "timestamp"))]
+    pub attributes: Vec<String>,
+
+    /// Whether to start each document with an XML declaration.
+    #[serde(default)]
+    pub declaration: bool,
+}
+
+impl Default for XmlSerializerOptions {
+    fn default() -> Self {
+        Self {
+            root_element: None,
+            record_element: default_record_element(),
+            attributes: Vec::new(),
+            declaration: false,
+        }
+    }
+}
+
+fn default_record_element() -> String {
+    "event".to_owned()
+}
+
+/// Serializer that converts an `Event` to bytes using the XML format.
+///
+/// The fields of an event are written as child elements of its record element, with nested
+/// objects as nested elements and arrays as repeated elements. Field names that aren't valid XML
+/// names have their invalid characters replaced by underscores.
+#[derive(Debug, Clone)]
+pub struct XmlSerializer {
+    root_element: Option<String>,
+    record_element: String,
+    attributes: BTreeSet<String>,
+    declaration: bool,
+}
+
+impl Encoder<Event> for XmlSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        let mut xml = String::new();
+
+        if self.declaration {
+            xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        }
+        if let Some(root) = &self.root_element {
+            write!(xml, "<{}>", root)?;
+        }
+
+        write!(xml, "<{}", self.record_element)?;
+        let mut children = Vec::new();
+        if let Some(fields) = log.as_map() {
+            for (key, value) in fields {
+                match value {
+                    Value::Object(_) | Value::Array(_) => children.push((key, value)),
+                    Value::Null if self.attributes.contains(key.as_str()) => {}
+                    value if self.attributes.contains(key.as_str()) => {
+                        write!(
+                            xml,
+                            r#" {}="{}""#,
+                            key,
+                            escape(&value.to_string_lossy(), true)
+                        )?;
+                    }
+                    value => children.push((key, value)),
+                }
+            }
+        }
+        if children.is_empty() {
+            xml.push_str("/>");
+        } else {
+            xml.push('>');
+            for (key, value) in children {
+                write_element(&mut xml, &element_name(key), value)?;
+            }
+            write!(xml, "</{}>", self.record_element)?;
+        }
+
+        if let Some(root) = &self.root_element {
+            write!(xml, "</{}>", root)?;
+        }
+
+        buffer.extend_from_slice(xml.as_bytes());
+        Ok(())
+    }
+}
+
+fn write_element(xml: &mut String, name: &str, value: &Value) -> std::fmt::Result {
+    match value {
+        Value::Array(values) => {
+            for value in values {
+                write_element(xml, name, value)?;
+            }
+        }
+        Value::Object(fields) => {
+            write!(xml, "<{}>", name)?;
+            for (key, value) in fields {
+                write_element(xml, &element_name(key), value)?;
+            }
+            write!(xml, "</{}>", name)?;
+        }
+        Value::Null => write!(xml, "<{}/>", name)?,
+        value => write!(
+            xml,
+            "<{}>{}</{}>",
+            name,
+            escape(&value.to_string_lossy(), false),
+            name
+        )?,
+    }
+    Ok(())
+}
+
+/// Whether the name is a valid XML name, restricted to ASCII characters.
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// Turns a field name into a valid XML name, replacing its invalid characters by underscores.
+fn element_name(key: &str) -> String {
+    let mut name: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        name.insert(0, '_');
+    }
+    name
+}
+
+/// Escapes the markup characters of text, and the quotes of attribute values. Characters that
+/// aren't allowed in XML documents are dropped.
+fn escape(value: &str, attribute: bool) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            '\t' | '\n' | '\r' if attribute => write!(escaped, "&#{};", c as u32).unwrap(),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn serialize(options: XmlSerializerOptions, log: LogEvent) -> String {
+        let mut bytes = BytesMut::new();
+        XmlSerializerConfig::new(options)
+            .build()
+            .unwrap()
+            .encode(log.into(), &mut bytes)
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn serialize_elements() {
+        let mut log = LogEvent::default();
+        log.insert("message", "a < b & c");
+        log.insert("user.name", "alice");
+        log.insert("tags", vec!["a", "b"]);
+        log.insert("1st", Value::Null);
+
+        assert_eq!(
+            serialize(XmlSerializerOptions::default(), log),
+            "<event><_1st/><message>a &lt; b &amp; c</message><tags>a</tags><tags>b</tags><user><name>alice</name></user></event>"
+        );
+    }
+
+    #[test]
+    fn serialize_attributes_and_root() {
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        log.insert("id", "a\"1");
+        log.insert("count", 2);
+        let options = XmlSerializerOptions {
+            root_element: Some("Body".to_owned()),
+            record_element: "record".to_owned(),
+            attributes: vec!["id".to_owned(), "count".to_owned()],
+            declaration: true,
+        };
+
+        assert_eq!(
+            serialize(options, log),
+            r#"<?xml version="1.0" encoding="UTF-8"?><Body><record count="2" id="a&quot;1"><message>hello</message></record></Body>"#
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_names() {
+        let options = XmlSerializerOptions {
+            record_element: "1record".to_owned(),
+            ..Default::default()
+        };
+
+        assert!(XmlSerializerConfig::new(options).build().is_err());
+    }
+}
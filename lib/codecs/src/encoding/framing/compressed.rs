@@ -0,0 +1,245 @@
+use std::io::Write;
+
+use bytes::BytesMut;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+
+use super::{BoxedFramingError, BytesEncoder, LengthDelimitedEncoder, NewlineDelimitedEncoder};
+use crate::encoding::Framer;
+
+/// The highest compression level of gzip.
+const GZIP_MAX_LEVEL: u32 = 9;
+
+/// The highest compression level of zstd.
+const ZSTD_MAX_LEVEL: u32 = 22;
+
+/// Config used to build a `CompressedEncoder`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CompressedEncoderConfig {
+    /// Options for the compressed encoder.
+    pub compressed: CompressedEncoderOptions,
+}
+
+impl CompressedEncoderConfig {
+    /// Creates a `CompressedEncoderConfig` with the specified options.
+    pub const fn new(compressed: CompressedEncoderOptions) -> Self {
+        Self { compressed }
+    }
+
+    /// Build the `CompressedEncoder` from this configuration.
+    pub fn build(&self) -> CompressedEncoder {
+        let framer = match self.compressed.framing {
+            CompressedFramingMethod::Bytes => Framer::Bytes(BytesEncoder::new()),
+            CompressedFramingMethod::LengthDelimited => {
+                Framer::LengthDelimited(LengthDelimitedEncoder::new())
+            }
+            CompressedFramingMethod::NewlineDelimited => {
+                Framer::NewlineDelimited(NewlineDelimitedEncoder::new())
+            }
+        };
+        CompressedEncoder::new(self.compressed.algorithm, self.compressed.level, framer)
+    }
+}
+
+/// Configuration for compressed framing.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompressedEncoderOptions {
+    /// The compression algorithm of the frames.
+    pub algorithm: CompressionAlgorithm,
+
+    /// The compression level.
+    ///
+    /// By default, the default level of the algorithm is used. Levels above the highest level of
+    /// the algorithm, 9 for gzip and 22 for zstd, are lowered to it.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    #[configurable(metadata(docs::examples = 6))]
+    pub level: Option<u32>,
+
+    /// The framing of the event data inside the compressed frames.
+    #[serde(default)]
+    pub framing: CompressedFramingMethod,
+}
+
+/// A compression algorithm of compressed framing.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionAlgorithm {
+    /// Each frame is a [gzip][gzip] member.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// Each frame is a [Zstandard][zstd] frame.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
+/// The framing of the event data inside compressed frames.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressedFramingMethod {
+    /// Event data is not delimited at all.
+    Bytes,
+
+    /// Event data is prefixed with its length in bytes.
+    ///
+    /// The prefix is a 32-bit unsigned integer, little endian.
+    LengthDelimited,
+
+    /// Event data is delimited by a newline (LF) character.
+    #[default]
+    NewlineDelimited,
+}
+
+/// An encoder for compressing batches of event data.
+///
+/// Encoding only frames the event data with the framing inside the compressed frames, and each
+/// batch of framed event data is then compressed as a single frame by
+/// [`CompressedEncoder::compress`].
+/// Concatenated gzip members and zstd frames are valid gzip and zstd streams, so the output of
+/// several batches can be decompressed as a whole.
+#[derive(Debug, Clone)]
+pub struct CompressedEncoder {
+    algorithm: CompressionAlgorithm,
+    level: Option<u32>,
+    framer: Box<Framer>,
+}
+
+impl CompressedEncoder {
+    /// Creates a `CompressedEncoder` compressing the event data framed by `framer`.
+    pub fn new(algorithm: CompressionAlgorithm, level: Option<u32>, framer: Framer) -> Self {
+        Self {
+            algorithm,
+            level,
+            framer: Box::new(framer),
+        }
+    }
+
+    /// Compresses the framed event data of a batch, held by `buffer`, into a single frame.
+    pub fn compress(&self, buffer: &mut BytesMut) -> Result<(), BoxedFramingError> {
+        let payload = buffer.split();
+
+        let compressed = match self.algorithm {
+            CompressionAlgorithm::Gzip => {
+                let level = self
+                    .level
+                    .map_or_else(flate2::Compression::default, |level| {
+                        flate2::Compression::new(level.min(GZIP_MAX_LEVEL))
+                    });
+                let mut encoder = GzEncoder::new(Vec::new(), level);
+                encoder.write_all(&payload)?;
+                encoder.finish()?
+            }
+            CompressionAlgorithm::Zstd => {
+                // Level 0 is the default level of zstd.
+                let level = self
+                    .level
+                    .map_or(0, |level| level.min(ZSTD_MAX_LEVEL) as i32);
+                zstd::bulk::compress(&payload, level)?
+            }
+        };
+        buffer.extend_from_slice(&compressed);
+
+        Ok(())
+    }
+}
+
+impl Encoder<()> for CompressedEncoder {
+    type Error = BoxedFramingError;
+
+    fn encode(&mut self, _: (), buffer: &mut BytesMut) -> Result<(), BoxedFramingError> {
+        self.framer.encode((), buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::{GzDecoder, MultiGzDecoder};
+
+    use super::*;
+
+    /// The magic number starting each zstd frame.
+    const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+    fn encode_batch(encoder: &mut CompressedEncoder, events: &[&str]) -> BytesMut {
+        let mut output = BytesMut::new();
+        for event in events {
+            let mut buffer = BytesMut::from(*event);
+            encoder.encode((), &mut buffer).unwrap();
+            output.extend_from_slice(&buffer);
+        }
+        encoder.compress(&mut output).unwrap();
+        output
+    }
+
+    #[test]
+    fn encode_gzip() {
+        let mut encoder = CompressedEncoderConfig::new(CompressedEncoderOptions {
+            algorithm: CompressionAlgorithm::Gzip,
+            level: Some(100),
+            framing: CompressedFramingMethod::NewlineDelimited,
+        })
+        .build();
+
+        let output = encode_batch(&mut encoder, &["abc", "def"]);
+
+        // A single gzip member holds the whole batch.
+        let mut decompressed = String::new();
+        GzDecoder::new(&output[..])
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, "abc\ndef\n");
+    }
+
+    #[test]
+    fn encode_gzip_batches() {
+        let mut encoder = CompressedEncoderConfig::new(CompressedEncoderOptions {
+            algorithm: CompressionAlgorithm::Gzip,
+            level: None,
+            framing: CompressedFramingMethod::NewlineDelimited,
+        })
+        .build();
+
+        let mut output = encode_batch(&mut encoder, &["abc", "def"]);
+        output.extend_from_slice(&encode_batch(&mut encoder, &["ghi"]));
+
+        let mut decompressed = String::new();
+        MultiGzDecoder::new(&output[..])
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, "abc\ndef\nghi\n");
+    }
+
+    #[test]
+    fn encode_zstd() {
+        let mut encoder = CompressedEncoderConfig::new(CompressedEncoderOptions {
+            algorithm: CompressionAlgorithm::Zstd,
+            level: None,
+            framing: CompressedFramingMethod::LengthDelimited,
+        })
+        .build();
+
+        let output = encode_batch(&mut encoder, &["abc", "de"]);
+
+        assert_eq!(
+            output
+                .windows(ZSTD_MAGIC.len())
+                .filter(|window| *window == ZSTD_MAGIC)
+                .count(),
+            1
+        );
+        let decompressed = zstd::stream::decode_all(&output[..]).unwrap();
+        assert_eq!(decompressed, b"\0\0\0\x03abc\0\0\0\x02de");
+    }
+}
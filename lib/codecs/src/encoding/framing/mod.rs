@@ -5,6 +5,7 @@
 
 mod bytes;
 mod character_delimited;
+mod compressed;
 mod length_delimited;
 mod newline_delimited;
 
@@ -13,6 +14,10 @@ use std::fmt::Debug;
 pub use character_delimited::{
     CharacterDelimitedEncoder, CharacterDelimitedEncoderConfig, CharacterDelimitedEncoderOptions,
 };
+pub use compressed::{
+    CompressedEncoder, CompressedEncoderConfig, CompressedEncoderOptions, CompressedFramingMethod,
+    CompressionAlgorithm,
+};
 use dyn_clone::DynClone;
 pub use length_delimited::{LengthDelimitedEncoder, LengthDelimitedEncoderConfig};
 pub use newline_delimited::{NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig};
@@ -7,17 +7,40 @@ pub mod framing;
 use std::fmt::Debug;
 
 use bytes::BytesMut;
+#[cfg(feature = "arrow")]
 pub use format::{
-    AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, GelfSerializer,
-    GelfSerializerConfig, JsonSerializer, JsonSerializerConfig, LogfmtSerializer,
-    LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
-    NativeSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig, TextSerializer,
-    TextSerializerConfig,
+    ArrowField, ArrowFieldType, ArrowStreamSerializer, ArrowStreamSerializerConfig,
+    ArrowStreamSerializerOptions,
+};
+pub use format::{
+    AvroSchemaRegistryAuth, AvroSchemaRegistryOptions, AvroSerializer, AvroSerializerConfig,
+    AvroSerializerOptions, CborSerializer, CborSerializerConfig, CborSerializerOptions,
+    CefSerializer, CefSerializerConfig, CefSerializerOptions, CsvSerializer, CsvSerializerConfig,
+    CsvSerializerOptions, CsvTerminator, FixedWidthAlignment, FixedWidthColumn,
+    FixedWidthSerializer, FixedWidthSerializerConfig, FixedWidthSerializerOptions,
+    FixedWidthTruncation, GelfChunker, GelfCompression, GelfSerializer, GelfSerializerConfig,
+    GelfSerializerOptions, JsonOversizeAction, JsonSerializer, JsonSerializerConfig,
+    JsonSerializerOptions, LeefSerializer, LeefSerializerConfig, LeefSerializerOptions,
+    LogfmtSerializer, LogfmtSerializerConfig, MsgpackSerializer, MsgpackSerializerConfig,
+    NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig,
+    NativeSerializerOptions, PrettyColors, PrettySerializer, PrettySerializerConfig,
+    PrettySerializerOptions, ProtobufSerializer, ProtobufSerializerConfig,
+    ProtobufSerializerOptions, RawMessageSerializer, RawMessageSerializerConfig, SyslogFacility,
+    SyslogRfc, SyslogSerializer, SyslogSerializerConfig, SyslogSerializerOptions, SyslogSeverity,
+    TextSerializer, TextSerializerConfig, TextSerializerOptions, XmlSerializer,
+    XmlSerializerConfig, XmlSerializerOptions,
+};
+#[cfg(feature = "parquet")]
+pub use format::{
+    ParquetCompression, ParquetField, ParquetFieldType, ParquetSerializer, ParquetSerializerConfig,
+    ParquetSerializerOptions,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
-    CharacterDelimitedEncoderConfig, CharacterDelimitedEncoderOptions, LengthDelimitedEncoder,
-    LengthDelimitedEncoderConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
+    CharacterDelimitedEncoderConfig, CharacterDelimitedEncoderOptions, CompressedEncoder,
+    CompressedEncoderConfig, CompressedEncoderOptions, CompressedFramingMethod,
+    CompressionAlgorithm, LengthDelimitedEncoder, LengthDelimitedEncoderConfig,
+    NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
 };
 use vector_config::configurable_component;
 use vector_core::{config::DataType, event::Event, schema};
@@ -66,6 +89,17 @@ pub enum FramingConfig {
         character_delimited: CharacterDelimitedEncoderOptions,
     },
 
+    /// Event data is compressed, one compressed frame per batch of events.
+    ///
+    /// A batch is the payload of a request or an object for sinks batching events, and the
+    /// events written at once by the `console`, `file`, and `socket` sinks. Concatenated gzip
+    /// members and zstd frames are valid gzip and zstd streams, so the output of several batches
+    /// can be decompressed as a whole.
+    Compressed {
+        /// Options for the compressed encoder.
+        compressed: CompressedEncoderOptions,
+    },
+
     /// Event data is prefixed with its length in bytes.
     ///
     /// The prefix is a 32-bit unsigned integer, little endian.
@@ -89,6 +123,14 @@ impl From<CharacterDelimitedEncoderConfig> for FramingConfig {
     }
 }
 
+impl From<CompressedEncoderConfig> for FramingConfig {
+    fn from(config: CompressedEncoderConfig) -> Self {
+        Self::Compressed {
+            compressed: config.compressed,
+        }
+    }
+}
+
 impl From<LengthDelimitedEncoderConfig> for FramingConfig {
     fn from(_: LengthDelimitedEncoderConfig) -> Self {
         Self::LengthDelimited
@@ -114,6 +156,9 @@ impl FramingConfig {
                 }
                 .build(),
             ),
+            FramingConfig::Compressed { compressed } => {
+                Framer::Compressed(CompressedEncoderConfig::new(compressed.clone()).build())
+            }
             FramingConfig::LengthDelimited => {
                 Framer::LengthDelimited(LengthDelimitedEncoderConfig.build())
             }
@@ -131,6 +176,8 @@ pub enum Framer {
     Bytes(BytesEncoder),
     /// Uses a `CharacterDelimitedEncoder` for framing.
     CharacterDelimited(CharacterDelimitedEncoder),
+    /// Uses a `CompressedEncoder` for framing.
+    Compressed(CompressedEncoder),
     /// Uses a `LengthDelimitedEncoder` for framing.
     LengthDelimited(LengthDelimitedEncoder),
     /// Uses a `NewlineDelimitedEncoder` for framing.
@@ -151,6 +198,12 @@ impl From<CharacterDelimitedEncoder> for Framer {
     }
 }
 
+impl From<CompressedEncoder> for Framer {
+    fn from(encoder: CompressedEncoder) -> Self {
+        Self::Compressed(encoder)
+    }
+}
+
 impl From<LengthDelimitedEncoder> for Framer {
     fn from(encoder: LengthDelimitedEncoder) -> Self {
         Self::LengthDelimited(encoder)
@@ -176,6 +229,7 @@ impl tokio_util::codec::Encoder<()> for Framer {
         match self {
             Framer::Bytes(framer) => framer.encode((), buffer),
             Framer::CharacterDelimited(framer) => framer.encode((), buffer),
+            Framer::Compressed(framer) => framer.encode((), buffer),
             Framer::LengthDelimited(framer) => framer.encode((), buffer),
             Framer::NewlineDelimited(framer) => framer.encode((), buffer),
             Framer::Boxed(framer) => framer.encode((), buffer),
@@ -189,6 +243,24 @@ impl tokio_util::codec::Encoder<()> for Framer {
 #[serde(tag = "codec", rename_all = "snake_case")]
 #[configurable(metadata(docs::enum_tag_description = "The codec to use for encoding events."))]
 pub enum SerializerConfig {
+    #[cfg(feature = "arrow")]
+    /// Encodes a batch of events as an [Apache Arrow IPC stream][arrow_ipc_stream].
+    ///
+    /// Arrow is a columnar format, so events are encoded a batch at a time, with each batch
+    /// written as a complete stream holding a single record batch. This codec is meant for sinks
+    /// writing batches of events to files or objects, such as `aws_s3`, `gcp_cloud_storage`, and
+    /// `file`, for Arrow-native consumers to read. Other sinks write each event as its own stream.
+    ///
+    /// The `file` sink holds the events of each file until the file is closed, and can't append to
+    /// existing files, so its `path` should be unique to each file, such as by including a
+    /// timestamp.
+    ///
+    /// [arrow_ipc_stream]: https://arrow.apache.org/docs/format/Columnar.html#ipc-streaming-format
+    ArrowStream {
+        /// Apache Arrow IPC stream-specific encoder options.
+        arrow_stream: ArrowStreamSerializerOptions,
+    },
+
     /// Encodes an event as an [Apache Avro][apache_avro] message.
     ///
     /// [apache_avro]: https://avro.apache.org/
@@ -197,10 +269,55 @@ pub enum SerializerConfig {
         avro: AvroSerializerOptions,
     },
 
+    /// Encodes an event as a [CBOR][cbor] map.
+    ///
+    /// Strings that aren't valid UTF-8 are encoded as byte strings, and timestamps as epoch-based
+    /// date/time values.
+    ///
+    /// [cbor]: https://cbor.io/
+    Cbor {
+        /// CBOR-specific encoder options.
+        #[serde(default)]
+        cbor: CborSerializerOptions,
+    },
+
+    /// Encodes an event in the [ArcSight Common Event Format][cef] (CEF).
+    ///
+    /// The header of each event holds the configured device fields, and the name and severity of
+    /// the event. Its extensions hold the values of the configured fields.
+    ///
+    /// [cef]: https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors-8.3/cef-implementation-standard/
+    Cef {
+        /// CEF-specific encoder options.
+        cef: CefSerializerOptions,
+    },
+
+    /// Encodes an event as a [CSV][csv] row.
+    ///
+    /// [csv]: https://datatracker.ietf.org/doc/html/rfc4180
+    Csv {
+        /// CSV-specific encoder options.
+        csv: CsvSerializerOptions,
+    },
+
+    /// Encodes an event as a fixed-width record, in which each value is padded to the width of
+    /// its column.
+    ///
+    /// This is usually used for integrating with mainframes and legacy batch files, which can't
+    /// take delimited data.
+    FixedWidth {
+        /// Fixed-width-specific encoder options.
+        fixed_width: FixedWidthSerializerOptions,
+    },
+
     /// Encodes an event as a [GELF][gelf] message.
     ///
     /// [gelf]: https://docs.graylog.org/docs/gelf
-    Gelf,
+    Gelf {
+        /// GELF-specific encoder options.
+        #[serde(default)]
+        gelf: GelfSerializerOptions,
+    },
 
     /// Encodes an event as [JSON][json].
     ///
@@ -210,18 +327,41 @@ pub enum SerializerConfig {
         JsonSerializerConfig,
     ),
 
+    /// Encodes an event in the [Log Event Extended Format][leef] (LEEF) 2.0 of IBM QRadar.
+    ///
+    /// The header of each event holds the configured product fields and event ID, and the
+    /// delimiter of its attributes. Its attributes hold the values of the configured fields.
+    ///
+    /// [leef]: https://www.ibm.com/docs/en/dsm?topic=leef-overview
+    Leef {
+        /// LEEF-specific encoder options.
+        leef: LeefSerializerOptions,
+    },
+
     /// Encodes an event as a [logfmt][logfmt] message.
     ///
     /// [logfmt]: https://brandur.org/logfmt
     Logfmt,
 
+    /// Encodes an event as a [MessagePack][msgpack] map.
+    ///
+    /// Strings that aren't valid UTF-8 are encoded as binary values, and timestamps with the
+    /// MessagePack timestamp extension type.
+    ///
+    /// [msgpack]: https://msgpack.org/
+    Msgpack,
+
     /// Encodes an event in Vector’s [native Protocol Buffers format][vector_native_protobuf].
     ///
     /// This codec is **[experimental][experimental]**.
     ///
     /// [vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
     /// [experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
-    Native,
+    Native {
+        /// Native-specific encoder options.
+        #[serde(default)]
+        native: NativeSerializerOptions,
+    },
 
     /// Encodes an event in Vector’s [native JSON format][vector_native_json].
     ///
@@ -231,6 +371,47 @@ pub enum SerializerConfig {
     /// [experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
     NativeJson,
 
+    #[cfg(feature = "parquet")]
+    /// Encodes a batch of events as an [Apache Parquet][apache_parquet] file.
+    ///
+    /// Parquet is a columnar file format, so events are encoded a batch at a time, with each batch
+    /// written as a complete file. This codec is meant for sinks writing files or objects, such as
+    /// `aws_s3`, `gcp_cloud_storage`, and `file`.
+    ///
+    /// The `file` sink holds the events of each file until the file is closed, and can't append to
+    /// existing files, so its `path` should be unique to each file, such as by including a
+    /// timestamp.
+    ///
+    /// [apache_parquet]: https://parquet.apache.org/
+    Parquet {
+        /// Apache Parquet-specific encoder options.
+        parquet: ParquetSerializerOptions,
+    },
+
+    /// Encodes an event as a human-readable text, for interactive debugging.
+    ///
+    /// A log event is rendered as a line holding its timestamp, level, and message, followed by a
+    /// line for each of its other fields, with their keys aligned. A metric is rendered the same
+    /// way as with the `text` codec.
+    ///
+    /// This codec is meant for the `console` sink, which colors the output when writing to a
+    /// terminal.
+    Pretty {
+        /// Pretty-specific encoder options.
+        #[serde(default)]
+        pretty: PrettySerializerOptions,
+    },
+
+    /// Encodes an event as a [Protocol Buffers][protobuf] message of a user-provided type.
+    ///
+    /// The fields of the event are mapped onto the fields of the message with the same name.
+    ///
+    /// [protobuf]: https://protobuf.dev/
+    Protobuf {
+        /// Protobuf-specific encoder options.
+        protobuf: ProtobufSerializerOptions,
+    },
+
     /// No encoding.
     ///
     /// This "encoding" simply uses the `message` field of a log event.
@@ -240,6 +421,18 @@ pub enum SerializerConfig {
     /// could lead to the encoding emitting empty strings for the given event.
     RawMessage,
 
+    /// Encodes an event as a [syslog][syslog] message, in the format of RFC 5424 or RFC 3164.
+    ///
+    /// The facility and severity of the message are read from fields of the event, and its host,
+    /// timestamp, and message from the fields of the log schema.
+    ///
+    /// [syslog]: https://www.rfc-editor.org/rfc/rfc5424
+    Syslog {
+        /// Syslog-specific encoder options.
+        #[serde(default)]
+        syslog: SyslogSerializerOptions,
+    },
+
     /// Plain text encoding.
     ///
     /// This "encoding" simply uses the `message` field of a log event. For metrics, it uses an
@@ -252,6 +445,27 @@ pub enum SerializerConfig {
         /// Encoding options specific to the text serializer.
         TextSerializerConfig,
     ),
+
+    /// Encodes an event as an [XML][xml] document.
+    ///
+    /// The fields of the event are written as the child elements of its record element, or as its
+    /// attributes.
+    ///
+    /// [xml]: https://www.w3.org/TR/xml/
+    Xml {
+        /// XML-specific encoder options.
+        #[serde(default)]
+        xml: XmlSerializerOptions,
+    },
+}
+
+#[cfg(feature = "arrow")]
+impl From<ArrowStreamSerializerConfig> for SerializerConfig {
+    fn from(config: ArrowStreamSerializerConfig) -> Self {
+        Self::ArrowStream {
+            arrow_stream: config.arrow_stream,
+        }
+    }
 }
 
 impl From<AvroSerializerConfig> for SerializerConfig {
@@ -260,9 +474,23 @@ impl From<AvroSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<CsvSerializerConfig> for SerializerConfig {
+    fn from(config: CsvSerializerConfig) -> Self {
+        Self::Csv { csv: config.csv }
+    }
+}
+
+impl From<FixedWidthSerializerConfig> for SerializerConfig {
+    fn from(config: FixedWidthSerializerConfig) -> Self {
+        Self::FixedWidth {
+            fixed_width: config.fixed_width,
+        }
+    }
+}
+
 impl From<GelfSerializerConfig> for SerializerConfig {
-    fn from(_: GelfSerializerConfig) -> Self {
-        Self::Gelf
+    fn from(config: GelfSerializerConfig) -> Self {
+        Self::Gelf { gelf: config.gelf }
     }
 }
 
@@ -278,9 +506,17 @@ impl From<LogfmtSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<MsgpackSerializerConfig> for SerializerConfig {
+    fn from(_: MsgpackSerializerConfig) -> Self {
+        Self::Msgpack
+    }
+}
+
 impl From<NativeSerializerConfig> for SerializerConfig {
-    fn from(_: NativeSerializerConfig) -> Self {
-        Self::Native
+    fn from(config: NativeSerializerConfig) -> Self {
+        Self::Native {
+            native: config.native,
+        }
     }
 }
 
@@ -290,36 +526,133 @@ impl From<NativeJsonSerializerConfig> for SerializerConfig {
     }
 }
 
+#[cfg(feature = "parquet")]
+impl From<ParquetSerializerConfig> for SerializerConfig {
+    fn from(config: ParquetSerializerConfig) -> Self {
+        Self::Parquet {
+            parquet: config.parquet,
+        }
+    }
+}
+
+impl From<ProtobufSerializerConfig> for SerializerConfig {
+    fn from(config: ProtobufSerializerConfig) -> Self {
+        Self::Protobuf {
+            protobuf: config.protobuf,
+        }
+    }
+}
+
 impl From<RawMessageSerializerConfig> for SerializerConfig {
     fn from(_: RawMessageSerializerConfig) -> Self {
         Self::RawMessage
     }
 }
 
+impl From<CborSerializerConfig> for SerializerConfig {
+    fn from(config: CborSerializerConfig) -> Self {
+        Self::Cbor { cbor: config.cbor }
+    }
+}
+
+impl From<PrettySerializerConfig> for SerializerConfig {
+    fn from(config: PrettySerializerConfig) -> Self {
+        Self::Pretty {
+            pretty: config.pretty,
+        }
+    }
+}
+
+impl From<CefSerializerConfig> for SerializerConfig {
+    fn from(config: CefSerializerConfig) -> Self {
+        Self::Cef { cef: config.cef }
+    }
+}
+
+impl From<LeefSerializerConfig> for SerializerConfig {
+    fn from(config: LeefSerializerConfig) -> Self {
+        Self::Leef { leef: config.leef }
+    }
+}
+
+impl From<SyslogSerializerConfig> for SerializerConfig {
+    fn from(config: SyslogSerializerConfig) -> Self {
+        Self::Syslog {
+            syslog: config.syslog,
+        }
+    }
+}
+
 impl From<TextSerializerConfig> for SerializerConfig {
     fn from(config: TextSerializerConfig) -> Self {
         Self::Text(config)
     }
 }
 
+impl From<XmlSerializerConfig> for SerializerConfig {
+    fn from(config: XmlSerializerConfig) -> Self {
+        Self::Xml { xml: config.xml }
+    }
+}
+
 impl SerializerConfig {
     /// Build the `Serializer` from this configuration.
     pub fn build(&self) -> Result<Serializer, Box<dyn std::error::Error + Send + Sync + 'static>> {
         match self {
+            #[cfg(feature = "arrow")]
+            SerializerConfig::ArrowStream { arrow_stream } => Ok(Serializer::ArrowStream(
+                ArrowStreamSerializerConfig::new(arrow_stream.clone()).build()?,
+            )),
             SerializerConfig::Avro { avro } => Ok(Serializer::Avro(
-                AvroSerializerConfig::new(avro.schema.clone()).build()?,
+                AvroSerializerConfig { avro: avro.clone() }.build()?,
+            )),
+            SerializerConfig::Cbor { cbor } => Ok(Serializer::Cbor(
+                CborSerializerConfig::new(cbor.clone()).build(),
+            )),
+            SerializerConfig::Cef { cef } => Ok(Serializer::Cef(
+                CefSerializerConfig::new(cef.clone()).build()?,
+            )),
+            SerializerConfig::Csv { csv } => Ok(Serializer::Csv(
+                CsvSerializerConfig::new(csv.clone()).build()?,
+            )),
+            SerializerConfig::FixedWidth { fixed_width } => Ok(Serializer::FixedWidth(
+                FixedWidthSerializerConfig::new(fixed_width.clone()).build()?,
+            )),
+            SerializerConfig::Gelf { gelf } => Ok(Serializer::Gelf(
+                GelfSerializerConfig::new(gelf.clone()).build(),
             )),
-            SerializerConfig::Gelf => Ok(Serializer::Gelf(GelfSerializerConfig::new().build())),
             SerializerConfig::Json(config) => Ok(Serializer::Json(config.build())),
+            SerializerConfig::Leef { leef } => Ok(Serializer::Leef(
+                LeefSerializerConfig::new(leef.clone()).build()?,
+            )),
             SerializerConfig::Logfmt => Ok(Serializer::Logfmt(LogfmtSerializerConfig.build())),
-            SerializerConfig::Native => Ok(Serializer::Native(NativeSerializerConfig.build())),
+            SerializerConfig::Msgpack => Ok(Serializer::Msgpack(MsgpackSerializerConfig.build())),
+            SerializerConfig::Native { native } => Ok(Serializer::Native(
+                NativeSerializerConfig::new(native.clone()).build(),
+            )),
             SerializerConfig::NativeJson => {
                 Ok(Serializer::NativeJson(NativeJsonSerializerConfig.build()))
             }
+            #[cfg(feature = "parquet")]
+            SerializerConfig::Parquet { parquet } => Ok(Serializer::Parquet(
+                ParquetSerializerConfig::new(parquet.clone()).build()?,
+            )),
+            SerializerConfig::Pretty { pretty } => Ok(Serializer::Pretty(
+                PrettySerializerConfig::new(pretty.clone()).build(),
+            )),
+            SerializerConfig::Protobuf { protobuf } => Ok(Serializer::Protobuf(
+                ProtobufSerializerConfig::new(protobuf.clone()).build()?,
+            )),
             SerializerConfig::RawMessage => {
                 Ok(Serializer::RawMessage(RawMessageSerializerConfig.build()))
             }
-            SerializerConfig::Text(config) => Ok(Serializer::Text(config.build())),
+            SerializerConfig::Syslog { syslog } => Ok(Serializer::Syslog(
+                SyslogSerializerConfig::new(syslog.clone()).build()?,
+            )),
+            SerializerConfig::Text(config) => Ok(Serializer::Text(config.build()?)),
+            SerializerConfig::Xml { xml } => Ok(Serializer::Xml(
+                XmlSerializerConfig::new(xml.clone()).build()?,
+            )),
         }
     }
 
@@ -337,47 +670,130 @@ impl SerializerConfig {
             // we should do so accurately, even if practically it doesn't need to be.
             //
             // [1]: https://avro.apache.org/docs/1.11.1/specification/_print/#message-framing
-            SerializerConfig::Avro { .. } | SerializerConfig::Native => {
-                FramingConfig::LengthDelimited
+            SerializerConfig::Cbor { cbor } if cbor.sequence => FramingConfig::Bytes,
+            SerializerConfig::Avro { .. }
+            | SerializerConfig::Cbor { .. }
+            | SerializerConfig::Msgpack
+            | SerializerConfig::Native { .. }
+            | SerializerConfig::Protobuf { .. } => FramingConfig::LengthDelimited,
+            SerializerConfig::Csv { csv } if csv.terminator != CsvTerminator::None => {
+                FramingConfig::Bytes
             }
-            SerializerConfig::Gelf
+            #[cfg(feature = "arrow")]
+            SerializerConfig::ArrowStream { .. } => FramingConfig::Bytes,
+            #[cfg(feature = "parquet")]
+            SerializerConfig::Parquet { .. } => FramingConfig::Bytes,
+            SerializerConfig::Cef { .. }
+            | SerializerConfig::Csv { .. }
+            | SerializerConfig::FixedWidth { .. }
+            | SerializerConfig::Gelf { .. }
             | SerializerConfig::Json(_)
+            | SerializerConfig::Leef { .. }
             | SerializerConfig::Logfmt
             | SerializerConfig::NativeJson
+            | SerializerConfig::Pretty { .. }
             | SerializerConfig::RawMessage
-            | SerializerConfig::Text(_) => FramingConfig::NewlineDelimited,
+            | SerializerConfig::Syslog { .. }
+            | SerializerConfig::Text(_)
+            | SerializerConfig::Xml { .. } => FramingConfig::NewlineDelimited,
         }
     }
 
     /// The data type of events that are accepted by this `Serializer`.
     pub fn input_type(&self) -> DataType {
         match self {
+            #[cfg(feature = "arrow")]
+            SerializerConfig::ArrowStream { arrow_stream } => {
+                ArrowStreamSerializerConfig::new(arrow_stream.clone()).input_type()
+            }
             SerializerConfig::Avro { avro } => {
-                AvroSerializerConfig::new(avro.schema.clone()).input_type()
+                AvroSerializerConfig { avro: avro.clone() }.input_type()
+            }
+            SerializerConfig::Cbor { cbor } => CborSerializerConfig::new(cbor.clone()).input_type(),
+            SerializerConfig::Cef { cef } => CefSerializerConfig::new(cef.clone()).input_type(),
+            SerializerConfig::Csv { csv } => CsvSerializerConfig::new(csv.clone()).input_type(),
+            SerializerConfig::FixedWidth { fixed_width } => {
+                FixedWidthSerializerConfig::new(fixed_width.clone()).input_type()
             }
             SerializerConfig::Gelf { .. } => GelfSerializerConfig::input_type(),
             SerializerConfig::Json(config) => config.input_type(),
+            SerializerConfig::Leef { leef } => LeefSerializerConfig::new(leef.clone()).input_type(),
             SerializerConfig::Logfmt => LogfmtSerializerConfig.input_type(),
-            SerializerConfig::Native => NativeSerializerConfig.input_type(),
+            SerializerConfig::Msgpack => MsgpackSerializerConfig.input_type(),
+            SerializerConfig::Native { native } => {
+                NativeSerializerConfig::new(native.clone()).input_type()
+            }
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.input_type(),
+            #[cfg(feature = "parquet")]
+            SerializerConfig::Parquet { parquet } => {
+                ParquetSerializerConfig::new(parquet.clone()).input_type()
+            }
+            SerializerConfig::Pretty { pretty } => {
+                PrettySerializerConfig::new(pretty.clone()).input_type()
+            }
+            SerializerConfig::Protobuf { protobuf } => {
+                ProtobufSerializerConfig::new(protobuf.clone()).input_type()
+            }
             SerializerConfig::RawMessage => RawMessageSerializerConfig.input_type(),
+            SerializerConfig::Syslog { syslog } => {
+                SyslogSerializerConfig::new(syslog.clone()).input_type()
+            }
             SerializerConfig::Text(config) => config.input_type(),
+            SerializerConfig::Xml { xml } => XmlSerializerConfig::new(xml.clone()).input_type(),
         }
     }
 
     /// The schema required by the serializer.
     pub fn schema_requirement(&self) -> schema::Requirement {
         match self {
+            #[cfg(feature = "arrow")]
+            SerializerConfig::ArrowStream { arrow_stream } => {
+                ArrowStreamSerializerConfig::new(arrow_stream.clone()).schema_requirement()
+            }
             SerializerConfig::Avro { avro } => {
-                AvroSerializerConfig::new(avro.schema.clone()).schema_requirement()
+                AvroSerializerConfig { avro: avro.clone() }.schema_requirement()
+            }
+            SerializerConfig::Cbor { cbor } => {
+                CborSerializerConfig::new(cbor.clone()).schema_requirement()
+            }
+            SerializerConfig::Cef { cef } => {
+                CefSerializerConfig::new(cef.clone()).schema_requirement()
+            }
+            SerializerConfig::Csv { csv } => {
+                CsvSerializerConfig::new(csv.clone()).schema_requirement()
+            }
+            SerializerConfig::FixedWidth { fixed_width } => {
+                FixedWidthSerializerConfig::new(fixed_width.clone()).schema_requirement()
             }
             SerializerConfig::Gelf { .. } => GelfSerializerConfig::schema_requirement(),
             SerializerConfig::Json(config) => config.schema_requirement(),
+            SerializerConfig::Leef { leef } => {
+                LeefSerializerConfig::new(leef.clone()).schema_requirement()
+            }
             SerializerConfig::Logfmt => LogfmtSerializerConfig.schema_requirement(),
-            SerializerConfig::Native => NativeSerializerConfig.schema_requirement(),
+            SerializerConfig::Msgpack => MsgpackSerializerConfig.schema_requirement(),
+            SerializerConfig::Native { native } => {
+                NativeSerializerConfig::new(native.clone()).schema_requirement()
+            }
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
+            #[cfg(feature = "parquet")]
+            SerializerConfig::Parquet { parquet } => {
+                ParquetSerializerConfig::new(parquet.clone()).schema_requirement()
+            }
+            SerializerConfig::Pretty { pretty } => {
+                PrettySerializerConfig::new(pretty.clone()).schema_requirement()
+            }
+            SerializerConfig::Protobuf { protobuf } => {
+                ProtobufSerializerConfig::new(protobuf.clone()).schema_requirement()
+            }
             SerializerConfig::RawMessage => RawMessageSerializerConfig.schema_requirement(),
+            SerializerConfig::Syslog { syslog } => {
+                SyslogSerializerConfig::new(syslog.clone()).schema_requirement()
+            }
             SerializerConfig::Text(config) => config.schema_requirement(),
+            SerializerConfig::Xml { xml } => {
+                XmlSerializerConfig::new(xml.clone()).schema_requirement()
+            }
         }
     }
 }
@@ -385,22 +801,48 @@ impl SerializerConfig {
 /// Serialize structured events as bytes.
 #[derive(Debug, Clone)]
 pub enum Serializer {
+    #[cfg(feature = "arrow")]
+    /// Uses an `ArrowStreamSerializer` for serialization.
+    ArrowStream(ArrowStreamSerializer),
     /// Uses an `AvroSerializer` for serialization.
     Avro(AvroSerializer),
+    /// Uses a `CborSerializer` for serialization.
+    Cbor(CborSerializer),
+    /// Uses a `CefSerializer` for serialization.
+    Cef(CefSerializer),
+    /// Uses a `CsvSerializer` for serialization.
+    Csv(CsvSerializer),
+    /// Uses a `FixedWidthSerializer` for serialization.
+    FixedWidth(FixedWidthSerializer),
     /// Uses a `GelfSerializer` for serialization.
     Gelf(GelfSerializer),
     /// Uses a `JsonSerializer` for serialization.
     Json(JsonSerializer),
+    /// Uses a `LeefSerializer` for serialization.
+    Leef(LeefSerializer),
     /// Uses a `LogfmtSerializer` for serialization.
     Logfmt(LogfmtSerializer),
+    /// Uses a `MsgpackSerializer` for serialization.
+    Msgpack(MsgpackSerializer),
     /// Uses a `NativeSerializer` for serialization.
     Native(NativeSerializer),
     /// Uses a `NativeJsonSerializer` for serialization.
     NativeJson(NativeJsonSerializer),
+    #[cfg(feature = "parquet")]
+    /// Uses a `ParquetSerializer` for serialization.
+    Parquet(ParquetSerializer),
+    /// Uses a `PrettySerializer` for serialization.
+    Pretty(PrettySerializer),
+    /// Uses a `ProtobufSerializer` for serialization.
+    Protobuf(ProtobufSerializer),
     /// Uses a `RawMessageSerializer` for serialization.
     RawMessage(RawMessageSerializer),
+    /// Uses a `SyslogSerializer` for serialization.
+    Syslog(SyslogSerializer),
     /// Uses a `TextSerializer` for serialization.
     Text(TextSerializer),
+    /// Uses a `XmlSerializer` for serialization.
+    Xml(XmlSerializer),
 }
 
 impl Serializer {
@@ -408,11 +850,25 @@ impl Serializer {
     pub fn supports_json(&self) -> bool {
         match self {
             Serializer::Json(_) | Serializer::NativeJson(_) | Serializer::Gelf(_) => true,
+            #[cfg(feature = "arrow")]
+            Serializer::ArrowStream(_) => false,
+            #[cfg(feature = "parquet")]
+            Serializer::Parquet(_) => false,
             Serializer::Avro(_)
+            | Serializer::Cbor(_)
+            | Serializer::Cef(_)
+            | Serializer::Csv(_)
+            | Serializer::FixedWidth(_)
+            | Serializer::Leef(_)
             | Serializer::Logfmt(_)
+            | Serializer::Msgpack(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
-            | Serializer::RawMessage(_) => false,
+            | Serializer::Pretty(_)
+            | Serializer::Protobuf(_)
+            | Serializer::RawMessage(_)
+            | Serializer::Syslog(_)
+            | Serializer::Xml(_) => false,
         }
     }
 
@@ -427,23 +883,68 @@ impl Serializer {
             Serializer::Gelf(serializer) => serializer.to_json_value(event),
             Serializer::Json(serializer) => serializer.to_json_value(event),
             Serializer::NativeJson(serializer) => serializer.to_json_value(event),
+            #[cfg(feature = "arrow")]
+            Serializer::ArrowStream(_) => panic!("Serializer does not support JSON"),
+            #[cfg(feature = "parquet")]
+            Serializer::Parquet(_) => panic!("Serializer does not support JSON"),
             Serializer::Avro(_)
+            | Serializer::Cbor(_)
+            | Serializer::Cef(_)
+            | Serializer::Csv(_)
+            | Serializer::FixedWidth(_)
+            | Serializer::Leef(_)
             | Serializer::Logfmt(_)
+            | Serializer::Msgpack(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
-            | Serializer::RawMessage(_) => {
+            | Serializer::Pretty(_)
+            | Serializer::Protobuf(_)
+            | Serializer::RawMessage(_)
+            | Serializer::Syslog(_)
+            | Serializer::Xml(_) => {
                 panic!("Serializer does not support JSON")
             }
         }
     }
 }
 
+#[cfg(feature = "arrow")]
+impl From<ArrowStreamSerializer> for Serializer {
+    fn from(serializer: ArrowStreamSerializer) -> Self {
+        Self::ArrowStream(serializer)
+    }
+}
+
 impl From<AvroSerializer> for Serializer {
     fn from(serializer: AvroSerializer) -> Self {
         Self::Avro(serializer)
     }
 }
 
+impl From<CborSerializer> for Serializer {
+    fn from(serializer: CborSerializer) -> Self {
+        Self::Cbor(serializer)
+    }
+}
+
+impl From<CefSerializer> for Serializer {
+    fn from(serializer: CefSerializer) -> Self {
+        Self::Cef(serializer)
+    }
+}
+
+impl From<CsvSerializer> for Serializer {
+    fn from(serializer: CsvSerializer) -> Self {
+        Self::Csv(serializer)
+    }
+}
+
+impl From<FixedWidthSerializer> for Serializer {
+    fn from(serializer: FixedWidthSerializer) -> Self {
+        Self::FixedWidth(serializer)
+    }
+}
+
 impl From<GelfSerializer> for Serializer {
     fn from(serializer: GelfSerializer) -> Self {
         Self::Gelf(serializer)
@@ -456,12 +957,24 @@ impl From<JsonSerializer> for Serializer {
     }
 }
 
+impl From<LeefSerializer> for Serializer {
+    fn from(serializer: LeefSerializer) -> Self {
+        Self::Leef(serializer)
+    }
+}
+
 impl From<LogfmtSerializer> for Serializer {
     fn from(serializer: LogfmtSerializer) -> Self {
         Self::Logfmt(serializer)
     }
 }
 
+impl From<MsgpackSerializer> for Serializer {
+    fn from(serializer: MsgpackSerializer) -> Self {
+        Self::Msgpack(serializer)
+    }
+}
+
 impl From<NativeSerializer> for Serializer {
     fn from(serializer: NativeSerializer) -> Self {
         Self::Native(serializer)
@@ -474,31 +987,76 @@ impl From<NativeJsonSerializer> for Serializer {
     }
 }
 
+#[cfg(feature = "parquet")]
+impl From<ParquetSerializer> for Serializer {
+    fn from(serializer: ParquetSerializer) -> Self {
+        Self::Parquet(serializer)
+    }
+}
+
+impl From<PrettySerializer> for Serializer {
+    fn from(serializer: PrettySerializer) -> Self {
+        Self::Pretty(serializer)
+    }
+}
+
+impl From<ProtobufSerializer> for Serializer {
+    fn from(serializer: ProtobufSerializer) -> Self {
+        Self::Protobuf(serializer)
+    }
+}
+
 impl From<RawMessageSerializer> for Serializer {
     fn from(serializer: RawMessageSerializer) -> Self {
         Self::RawMessage(serializer)
     }
 }
 
+impl From<SyslogSerializer> for Serializer {
+    fn from(serializer: SyslogSerializer) -> Self {
+        Self::Syslog(serializer)
+    }
+}
+
 impl From<TextSerializer> for Serializer {
     fn from(serializer: TextSerializer) -> Self {
         Self::Text(serializer)
     }
 }
 
+impl From<XmlSerializer> for Serializer {
+    fn from(serializer: XmlSerializer) -> Self {
+        Self::Xml(serializer)
+    }
+}
+
 impl tokio_util::codec::Encoder<Event> for Serializer {
     type Error = vector_common::Error;
 
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
         match self {
+            #[cfg(feature = "arrow")]
+            Serializer::ArrowStream(serializer) => serializer.encode(event, buffer),
             Serializer::Avro(serializer) => serializer.encode(event, buffer),
+            Serializer::Cbor(serializer) => serializer.encode(event, buffer),
+            Serializer::Cef(serializer) => serializer.encode(event, buffer),
+            Serializer::Csv(serializer) => serializer.encode(event, buffer),
+            Serializer::FixedWidth(serializer) => serializer.encode(event, buffer),
             Serializer::Gelf(serializer) => serializer.encode(event, buffer),
             Serializer::Json(serializer) => serializer.encode(event, buffer),
+            Serializer::Leef(serializer) => serializer.encode(event, buffer),
             Serializer::Logfmt(serializer) => serializer.encode(event, buffer),
+            Serializer::Msgpack(serializer) => serializer.encode(event, buffer),
             Serializer::Native(serializer) => serializer.encode(event, buffer),
             Serializer::NativeJson(serializer) => serializer.encode(event, buffer),
+            #[cfg(feature = "parquet")]
+            Serializer::Parquet(serializer) => serializer.encode(event, buffer),
+            Serializer::Pretty(serializer) => serializer.encode(event, buffer),
+            Serializer::Protobuf(serializer) => serializer.encode(event, buffer),
             Serializer::RawMessage(serializer) => serializer.encode(event, buffer),
+            Serializer::Syslog(serializer) => serializer.encode(event, buffer),
             Serializer::Text(serializer) => serializer.encode(event, buffer),
+            Serializer::Xml(serializer) => serializer.encode(event, buffer),
         }
     }
 }
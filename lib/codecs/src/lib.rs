@@ -10,23 +10,34 @@ pub mod gelf;
 
 pub use decoding::{
     BytesDecoder, BytesDecoderConfig, BytesDeserializer, BytesDeserializerConfig,
-    CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, GelfDeserializer,
-    GelfDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, LengthDelimitedDecoder,
-    LengthDelimitedDecoderConfig, NativeDeserializer, NativeDeserializerConfig,
-    NativeJsonDeserializer, NativeJsonDeserializerConfig, NewlineDelimitedDecoder,
-    NewlineDelimitedDecoderConfig, OctetCountingDecoder, OctetCountingDecoderConfig,
-    StreamDecodingError,
+    CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, CsvDeserializer,
+    CsvDeserializerConfig, CsvDeserializerOptions, GelfDeserializer, GelfDeserializerConfig,
+    JsonDeserializer, JsonDeserializerConfig, LengthDelimitedDecoder, LengthDelimitedDecoderConfig,
+    MsgpackDeserializer, MsgpackDeserializerConfig, NativeDeserializer, NativeDeserializerConfig,
+    NativeDeserializerOptions, NativeJsonDeserializer, NativeJsonDeserializerConfig,
+    NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig, OctetCountingDecoder,
+    OctetCountingDecoderConfig, StreamDecodingError,
 };
 #[cfg(feature = "syslog")]
 pub use decoding::{SyslogDeserializer, SyslogDeserializerConfig};
+#[cfg(feature = "arrow")]
+pub use encoding::{ArrowStreamSerializer, ArrowStreamSerializerConfig};
 pub use encoding::{
-    BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder, CharacterDelimitedEncoderConfig,
-    GelfSerializer, GelfSerializerConfig, JsonSerializer, JsonSerializerConfig,
-    LengthDelimitedEncoder, LengthDelimitedEncoderConfig, LogfmtSerializer, LogfmtSerializerConfig,
-    NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig,
-    NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig, RawMessageSerializer,
-    RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+    BytesEncoder, BytesEncoderConfig, CborSerializer, CborSerializerConfig, CefSerializer,
+    CefSerializerConfig, CharacterDelimitedEncoder, CharacterDelimitedEncoderConfig,
+    CompressedEncoder, CompressedEncoderConfig, CsvSerializer, CsvSerializerConfig,
+    FixedWidthSerializer, FixedWidthSerializerConfig, GelfSerializer, GelfSerializerConfig,
+    JsonSerializer, JsonSerializerConfig, JsonSerializerOptions, LeefSerializer,
+    LeefSerializerConfig, LengthDelimitedEncoder, LengthDelimitedEncoderConfig, LogfmtSerializer,
+    LogfmtSerializerConfig, MsgpackSerializer, MsgpackSerializerConfig, NativeJsonSerializer,
+    NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig, NativeSerializerOptions,
+    NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig, PrettySerializer,
+    PrettySerializerConfig, ProtobufSerializer, ProtobufSerializerConfig, RawMessageSerializer,
+    RawMessageSerializerConfig, SyslogSerializer, SyslogSerializerConfig, TextSerializer,
+    TextSerializerConfig, TextSerializerOptions, XmlSerializer, XmlSerializerConfig,
 };
+#[cfg(feature = "parquet")]
+pub use encoding::{ParquetSerializer, ParquetSerializerConfig};
 pub use gelf::{gelf_fields, VALID_FIELD_REGEX};
 use vector_config::configurable_component;
 
@@ -43,3 +54,38 @@ pub enum MetricTagValues {
     /// All tags will be exposed as arrays of either string or null values.
     Full,
 }
+
+/// The version of Vector’s native Protocol Buffers format.
+///
+/// The events are encoded with the version number, so that decoders can tell the versions apart.
+/// Events encoded by Vector versions predating the version number are decoded as `v2` events,
+/// which are a superset of `v1` events.
+#[configurable_component]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NativeVersion {
+    /// The format of Vector versions before v0.26.
+    ///
+    /// Metric tags only have a single value, so tags with multiple values are encoded with their
+    /// last assigned value, and null values are left out.
+    V1,
+
+    /// The format of Vector versions since v0.26.
+    ///
+    /// Metric tags can have multiple values.
+    #[default]
+    V2,
+}
+
+impl NativeVersion {
+    /// The latest version of the native format.
+    pub const LATEST: Self = Self::V2;
+
+    /// Returns the version number encoded with the events.
+    pub const fn as_u32(self) -> u32 {
+        match self {
+            Self::V1 => 1,
+            Self::V2 => 2,
+        }
+    }
+}
@@ -6,11 +6,18 @@ use std::{
 
 use bytes::{Bytes, BytesMut};
 use codecs::{
-    decoding::format::Deserializer, encoding::format::Serializer, NativeDeserializerConfig,
-    NativeJsonDeserializerConfig, NativeJsonSerializerConfig, NativeSerializerConfig,
+    decoding::format::Deserializer, encoding::format::Serializer, NativeDeserializer,
+    NativeDeserializerConfig, NativeJsonDeserializerConfig, NativeJsonSerializerConfig,
+    NativeSerializer, NativeSerializerConfig, NativeVersion,
 };
+use prost::Message;
 use similar_asserts::assert_eq;
-use vector_core::{config::LogNamespace, event::Event};
+use tokio_util::codec::Encoder;
+use vector_core::{
+    config::LogNamespace,
+    event::{proto, Event, LogEvent, Metric, MetricKind, MetricValue},
+    metric_tags,
+};
 
 #[test]
 fn pre_v24_fixtures_match() {
@@ -38,8 +45,8 @@ fn roundtrip_current_native_proto_fixtures() {
     roundtrip_fixtures(
         "proto",
         "",
-        &NativeDeserializerConfig.build(),
-        &mut NativeSerializerConfig.build(),
+        &NativeDeserializerConfig::default().build(),
+        &mut NativeSerializerConfig::default().build(),
         false,
     );
 }
@@ -62,8 +69,8 @@ fn reserialize_pre_v24_native_proto_fixtures() {
     roundtrip_fixtures(
         "proto",
         "pre-v24",
-        &NativeDeserializerConfig.build(),
-        &mut NativeSerializerConfig.build(),
+        &NativeDeserializerConfig::default().build(),
+        &mut NativeSerializerConfig::default().build(),
         true,
     );
 }
@@ -76,12 +83,123 @@ fn reserialize_pre_v26_native_proto_fixtures() {
     roundtrip_fixtures(
         "proto",
         "pre-v26",
-        &NativeDeserializerConfig.build(),
-        &mut NativeSerializerConfig.build(),
+        &NativeDeserializerConfig::default().build(),
+        &mut NativeSerializerConfig::default().build(),
+        true,
+    );
+}
+
+/// The metrics encoded before v0.26 only have single tag values, which are kept when downgrading
+/// to the v1 format.
+#[test]
+fn reserialize_pre_v26_native_proto_fixtures_as_v1() {
+    roundtrip_fixtures(
+        "proto",
+        "pre-v26",
+        &NativeDeserializerConfig::default().build(),
+        &mut NativeSerializer::new(NativeVersion::V1),
+        true,
+    );
+}
+
+/// The event proto format was changed in v0.30 to include the version of the native format. This
+/// test ensures we can still load the old version binaries, and that they decode to the same events
+/// as the current ones.
+#[test]
+fn pre_v30_native_proto_decoding_matches() {
+    let deserializer = NativeDeserializerConfig::default().build();
+
+    let old_entries = list_fixtures("proto", "pre-v30");
+    let current_entries = list_fixtures("proto", "");
+    assert_eq!(old_entries.len(), current_entries.len());
+
+    for (old_path, current_path) in old_entries.into_iter().zip(current_entries.into_iter()) {
+        assert_eq!(old_path.file_name(), current_path.file_name());
+
+        let (buf, old_event) = load_deserialize(&old_path, &deserializer);
+        // The version is left unset in the old binaries.
+        assert_eq!(proto::EventArray::decode(buf).unwrap().version, 0);
+
+        let (_, current_event) = load_deserialize(&current_path, &deserializer);
+        assert_eq!(
+            old_event,
+            current_event,
+            "Parsed events don't match: {} {}",
+            old_path.display(),
+            current_path.display()
+        );
+    }
+}
+
+#[test]
+fn reserialize_pre_v30_native_proto_fixtures() {
+    roundtrip_fixtures(
+        "proto",
+        "pre-v30",
+        &NativeDeserializerConfig::default().build(),
+        &mut NativeSerializerConfig::default().build(),
         true,
     );
 }
 
+#[test]
+fn v1_encoding_keeps_single_tag_values() {
+    let metric = Metric::new(
+        "counter",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 1.0 },
+    )
+    .with_tags(Some(metric_tags!(
+        "a" => "first",
+        "a" => "second",
+        "b" => "single",
+    )));
+
+    let mut buffer = BytesMut::new();
+    NativeSerializer::new(NativeVersion::V1)
+        .encode(Event::Metric(metric), &mut buffer)
+        .unwrap();
+
+    let array = proto::EventArray::decode(buffer.freeze()).unwrap();
+    assert_eq!(array.version, 1);
+    let Some(proto::event_array::Events::Metrics(metrics)) = array.events else {
+        panic!("expected metrics");
+    };
+    assert!(metrics.metrics[0].tags_v2.is_empty());
+    assert_eq!(
+        metrics.metrics[0].tags_v1,
+        [("a", "second"), ("b", "single")]
+            .into_iter()
+            .map(|(tag, value)| (tag.to_owned(), value.to_owned()))
+            .collect()
+    );
+}
+
+#[test]
+fn newer_versions_are_decoded_unless_rejected() {
+    let mut log = LogEvent::default();
+    log.insert("message", "foo");
+    let event = Event::Log(log);
+
+    let mut buffer = BytesMut::new();
+    NativeSerializer::default()
+        .encode(event.clone(), &mut buffer)
+        .unwrap();
+    let mut array = proto::EventArray::decode(buffer.freeze()).unwrap();
+    assert_eq!(array.version, NativeVersion::LATEST.as_u32());
+    array.version += 1;
+    let bytes = Bytes::from(array.encode_to_vec());
+
+    let events = NativeDeserializer::default()
+        .parse(bytes.clone(), LogNamespace::Legacy)
+        .unwrap();
+    assert_eq!(events.into_vec(), vec![event]);
+
+    assert!(NativeDeserializer::new(true)
+        .parse(bytes, LogNamespace::Legacy)
+        .is_err());
+}
+
 #[test]
 fn current_native_decoding_matches() {
     decoding_matches("");
@@ -113,8 +231,8 @@ fn rebuild_json_fixtures() {
 fn rebuild_proto_fixtures() {
     rebuild_fixtures(
         "proto",
-        &NativeDeserializerConfig.build(),
-        &mut NativeSerializerConfig.build(),
+        &NativeDeserializerConfig::default().build(),
+        &mut NativeSerializerConfig::default().build(),
     );
 }
 
@@ -135,7 +253,7 @@ fn fixtures_match(suffix: &str) {
 /// protocols.
 fn decoding_matches(suffix: &str) {
     let json_deserializer = NativeJsonDeserializerConfig.build();
-    let proto_deserializer = NativeDeserializerConfig.build();
+    let proto_deserializer = NativeDeserializerConfig::default().build();
 
     let json_entries = list_fixtures("json", suffix);
     let proto_entries = list_fixtures("proto", suffix);
@@ -195,6 +195,17 @@ impl CheckpointsView {
                 }
             }
         }
+
+        // Files identified by a fallback strategy of a chain are identified by a preferred
+        // strategy once it succeeds, so migrate their checkpoints.
+        if self.checkpoints.get(&fng).is_none() {
+            for fallback in fingerprinter.get_fallback_fingerprints(path, fng, fingerprint_buffer) {
+                if self.checkpoints.contains_key(&fallback) {
+                    self.update_key(fallback, fng);
+                    break;
+                }
+            }
+        }
     }
 }
 
@@ -232,6 +243,7 @@ impl Checkpointer {
             BytesChecksum(c) => format!("g{:x}.{}", c, pos),
             FirstLinesChecksum(c) => format!("h{:x}.{}", c, pos),
             DevInode(dev, ino) => format!("i{:x}.{:x}.{}", dev, ino, pos),
+            FileFingerprint::Path(c) => format!("j{:x}.{}", c, pos),
             Unknown(x) => format!("{:x}.{}", x, pos),
         };
         self.directory.join(path)
@@ -263,6 +275,10 @@ impl Checkpointer {
                         .unwrap();
                 (DevInode(dev, ino), pos)
             }
+            'j' => {
+                let (c, pos) = scan_fmt!(file_name, "j{x}.{}", [hex u64], FilePosition).unwrap();
+                (FileFingerprint::Path(c), pos)
+            }
             _ => {
                 let (c, pos) = scan_fmt!(file_name, "{x}.{}", [hex u64], FilePosition).unwrap();
                 (Unknown(c), pos)
@@ -570,6 +586,58 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_checkpointer_fingerprint_upgrades_chain_fallback() {
+        let log_dir = tempdir().unwrap();
+        let path = log_dir.path().join("test.log");
+        let data = "hello\n";
+        std::fs::write(&path, data).unwrap();
+
+        let fingerprinter = Fingerprinter {
+            strategy: FingerprintStrategy::Chain(vec![
+                FingerprintStrategy::FirstLinesChecksum {
+                    ignored_header_bytes: 0,
+                    lines: 1,
+                },
+                FingerprintStrategy::Path,
+            ]),
+            max_line_length: 1000,
+            ignore_not_found: false,
+        };
+        let fallback_fingerprinter = Fingerprinter {
+            strategy: FingerprintStrategy::Path,
+            max_line_length: 1000,
+            ignore_not_found: false,
+        };
+        let position: FilePosition = 6;
+
+        let mut buf = Vec::new();
+        let old_fingerprint = fallback_fingerprinter
+            .get_fingerprint_of_file(&path, &mut buf)
+            .unwrap();
+        let new_fingerprint = fingerprinter
+            .get_fingerprint_of_file(&path, &mut buf)
+            .unwrap();
+        assert_ne!(old_fingerprint, new_fingerprint);
+
+        let data_dir = tempdir().unwrap();
+        {
+            let mut chkptr = Checkpointer::new(data_dir.path());
+            chkptr.update_checkpoint(old_fingerprint, position);
+            chkptr.write_checkpoints().ok();
+        }
+        {
+            let mut chkptr = Checkpointer::new(data_dir.path());
+            chkptr.read_checkpoints(None);
+            assert_eq!(chkptr.get_checkpoint(new_fingerprint), None);
+
+            chkptr.maybe_upgrade(&path, new_fingerprint, &fingerprinter, &mut buf);
+
+            assert_eq!(chkptr.get_checkpoint(new_fingerprint), Some(position));
+            assert_eq!(chkptr.get_checkpoint(old_fingerprint), None);
+        }
+    }
+
     #[test]
     fn test_checkpointer_fingerprint_upgrades_legacy_checksum() {
         let log_dir = tempdir().unwrap();
@@ -219,6 +219,26 @@ where
                                     }
                                 }
                             }
+                        } else if let Some(old_file_id) = self
+                            .fingerprinter
+                            .get_fallback_fingerprints(&path, file_id, &mut fingerprint_buffer)
+                            .into_iter()
+                            .find(|old_file_id| {
+                                fp_map
+                                    .get(old_file_id)
+                                    .map_or(false, |watcher| watcher.path == path)
+                            })
+                        {
+                            // watched file is now identified by a preferred strategy of the chain
+                            info!(
+                                message = "Watched file has a new fingerprint.",
+                                path = ?path,
+                            );
+                            if let Some(mut watcher) = fp_map.shift_remove(&old_file_id) {
+                                watcher.set_file_findable(true);
+                                fp_map.insert(file_id, watcher);
+                            }
+                            checkpoints.update_key(old_file_id, file_id);
                         } else {
                             // untracked file fingerprint
                             self.watch_new_file(path, file_id, &mut fp_map, &checkpoints, false);
@@ -32,6 +32,9 @@ pub enum FingerprintStrategy {
         lines: usize,
     },
     DevInode,
+    Path,
+    /// Tries each strategy in turn, identifying files with the first one that succeeds.
+    Chain(Vec<FingerprintStrategy>),
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize, Ord, PartialOrd)]
@@ -42,6 +45,7 @@ pub enum FileFingerprint {
     #[serde(alias = "first_line_checksum")]
     FirstLinesChecksum(u64),
     DevInode(u64, u64),
+    Path(u64),
     Unknown(u64),
 }
 
@@ -58,6 +62,7 @@ impl FileFingerprint {
                 buf.write_all(&ino.to_be_bytes()).expect("writing to array");
                 FINGERPRINT_CRC.checksum(&buf[..])
             }
+            FileFingerprint::Path(c) => *c,
             Unknown(c) => *c,
         }
     }
@@ -75,14 +80,21 @@ impl Fingerprinter {
         path: &Path,
         buffer: &mut Vec<u8>,
     ) -> Result<FileFingerprint, io::Error> {
-        use FileFingerprint::*;
+        self.get_fingerprint_with(&self.strategy, path, buffer)
+    }
 
-        match self.strategy {
+    fn get_fingerprint_with(
+        &self,
+        strategy: &FingerprintStrategy,
+        path: &Path,
+        buffer: &mut Vec<u8>,
+    ) -> Result<FileFingerprint, io::Error> {
+        match *strategy {
             FingerprintStrategy::DevInode => {
                 let file_handle = File::open(path)?;
                 let dev = file_handle.portable_dev()?;
                 let ino = file_handle.portable_ino()?;
-                Ok(DevInode(dev, ino))
+                Ok(FileFingerprint::DevInode(dev, ino))
             }
             FingerprintStrategy::Checksum {
                 ignored_header_bytes,
@@ -98,11 +110,75 @@ impl Fingerprinter {
                 fp.seek(SeekFrom::Start(ignored_header_bytes as u64))?;
                 let bytes_read = fingerprinter_read_until(fp, b'\n', lines, buffer)?;
                 let fingerprint = FINGERPRINT_CRC.checksum(&buffer[..bytes_read]);
-                Ok(FirstLinesChecksum(fingerprint))
+                Ok(FileFingerprint::FirstLinesChecksum(fingerprint))
+            }
+            FingerprintStrategy::Path => {
+                let path = path.to_string_lossy();
+                Ok(FileFingerprint::Path(
+                    FINGERPRINT_CRC.checksum(path.as_bytes()),
+                ))
+            }
+            FingerprintStrategy::Chain(ref strategies) => {
+                let mut last_error = None;
+                for strategy in strategies {
+                    match self.get_fingerprint_with(strategy, path, buffer) {
+                        Ok(fingerprint) => return Ok(fingerprint),
+                        // A missing file can't be identified by any strategy.
+                        Err(error) if error.kind() == io::ErrorKind::NotFound => return Err(error),
+                        Err(error) => last_error = Some(error),
+                    }
+                }
+                Err(last_error.unwrap_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "no fingerprinting strategy in the chain",
+                    )
+                }))
             }
         }
     }
 
+    /// Gets the fingerprints of the file by the strategies of the chain following the strategy
+    /// that identified the file as `fresh`.
+    ///
+    /// These are the fingerprints the file had before the preferred strategies could identify it,
+    /// such as before it had enough lines to be checksummed, so checkpoints can be migrated from
+    /// them.
+    pub fn get_fallback_fingerprints(
+        &self,
+        path: &Path,
+        fresh: FileFingerprint,
+        buffer: &mut Vec<u8>,
+    ) -> Vec<FileFingerprint> {
+        match self.strategy {
+            FingerprintStrategy::Chain(ref strategies) => {
+                let mut fingerprints = strategies
+                    .iter()
+                    .filter_map(|strategy| self.get_fingerprint_with(strategy, path, buffer).ok());
+                if fingerprints.any(|fingerprint| fingerprint == fresh) {
+                    fingerprints.collect()
+                } else {
+                    Vec::new()
+                }
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The checksum strategy of the fingerprinter, on its own or in a chain.
+    fn checksum_strategy(&self) -> Option<&FingerprintStrategy> {
+        match self.strategy {
+            FingerprintStrategy::Chain(ref strategies) => strategies.iter().find(|strategy| {
+                matches!(
+                    strategy,
+                    FingerprintStrategy::Checksum { .. }
+                        | FingerprintStrategy::FirstLinesChecksum { .. }
+                )
+            }),
+            ref strategy => Some(strategy),
+        }
+    }
+
     pub fn get_fingerprint_or_log_error(
         &self,
         path: &Path,
@@ -143,12 +219,12 @@ impl Fingerprinter {
         path: &Path,
         buffer: &mut Vec<u8>,
     ) -> Result<Option<FileFingerprint>, io::Error> {
-        match self.strategy {
-            FingerprintStrategy::Checksum {
+        match self.checksum_strategy() {
+            Some(&FingerprintStrategy::Checksum {
                 bytes,
                 ignored_header_bytes,
                 lines: _,
-            } => {
+            }) => {
                 buffer.resize(bytes, 0u8);
                 let mut fp = fs::File::open(path)?;
                 fp.seek(io::SeekFrom::Start(ignored_header_bytes as u64))?;
@@ -167,16 +243,18 @@ impl Fingerprinter {
         path: &Path,
         buffer: &mut Vec<u8>,
     ) -> Result<Option<FileFingerprint>, io::Error> {
-        match self.strategy {
-            FingerprintStrategy::Checksum {
-                ignored_header_bytes,
-                bytes: _,
-                lines,
-            }
-            | FingerprintStrategy::FirstLinesChecksum {
-                ignored_header_bytes,
-                lines,
-            } => {
+        match self.checksum_strategy() {
+            Some(
+                &FingerprintStrategy::Checksum {
+                    ignored_header_bytes,
+                    bytes: _,
+                    lines,
+                }
+                | &FingerprintStrategy::FirstLinesChecksum {
+                    ignored_header_bytes,
+                    lines,
+                },
+            ) => {
                 buffer.resize(self.max_line_length, 0u8);
                 let mut fp = fs::File::open(path)?;
                 fp.seek(SeekFrom::Start(ignored_header_bytes as u64))?;
@@ -194,16 +272,18 @@ impl Fingerprinter {
         path: &Path,
         buffer: &mut Vec<u8>,
     ) -> Result<Option<FileFingerprint>, io::Error> {
-        match self.strategy {
-            FingerprintStrategy::Checksum {
-                ignored_header_bytes,
-                bytes: _,
-                lines,
-            }
-            | FingerprintStrategy::FirstLinesChecksum {
-                ignored_header_bytes,
-                lines,
-            } => {
+        match self.checksum_strategy() {
+            Some(
+                &FingerprintStrategy::Checksum {
+                    ignored_header_bytes,
+                    bytes: _,
+                    lines,
+                }
+                | &FingerprintStrategy::FirstLinesChecksum {
+                    ignored_header_bytes,
+                    lines,
+                },
+            ) => {
                 buffer.resize(self.max_line_length, 0u8);
                 let mut fp = fs::File::open(path)?;
                 fp.seek(SeekFrom::Start(ignored_header_bytes as u64))?;
@@ -285,7 +365,7 @@ mod test {
 
     use tempfile::tempdir;
 
-    use super::{FileSourceInternalEvents, FingerprintStrategy, Fingerprinter};
+    use super::{FileFingerprint, FileSourceInternalEvents, FingerprintStrategy, Fingerprinter};
 
     #[test]
     fn test_checksum_fingerprint() {
@@ -497,6 +577,44 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_chain_fingerprint() {
+        let fingerprinter = Fingerprinter {
+            strategy: FingerprintStrategy::Chain(vec![
+                FingerprintStrategy::FirstLinesChecksum {
+                    ignored_header_bytes: 0,
+                    lines: 1,
+                },
+                FingerprintStrategy::DevInode,
+            ]),
+            max_line_length: 1024,
+            ignore_not_found: false,
+        };
+
+        let target_dir = tempdir().unwrap();
+        let path = target_dir.path().join("file.log");
+        fs::write(&path, b"no newline yet").unwrap();
+
+        let mut buf = Vec::new();
+        let before = fingerprinter
+            .get_fingerprint_of_file(&path, &mut buf)
+            .unwrap();
+        assert!(matches!(before, FileFingerprint::DevInode(..)));
+        assert!(fingerprinter
+            .get_fallback_fingerprints(&path, before, &mut buf)
+            .is_empty());
+
+        fs::write(&path, b"first line\n").unwrap();
+        let after = fingerprinter
+            .get_fingerprint_of_file(&path, &mut buf)
+            .unwrap();
+        assert!(matches!(after, FileFingerprint::FirstLinesChecksum(_)));
+        assert_eq!(
+            fingerprinter.get_fallback_fingerprints(&path, after, &mut buf),
+            vec![before]
+        );
+    }
+
     #[test]
     fn no_error_on_dir() {
         let target_dir = tempdir().unwrap();
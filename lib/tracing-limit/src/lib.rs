@@ -1,6 +1,6 @@
 #![deny(warnings)]
 
-use std::fmt;
+use std::{cell::Cell, fmt};
 
 use dashmap::DashMap;
 use tracing_core::{
@@ -31,6 +31,29 @@ const MESSAGE_FIELD: &str = "message";
 const COMPONENT_ID_FIELD: &str = "component_id";
 const VRL_POSITION: &str = "vrl_position";
 
+thread_local! {
+    static CURRENT_RATE_LIMIT: Cell<Option<RateLimit>> = Cell::new(None);
+}
+
+/// The rate limiting of an internal log, reported by the events emitted in its place.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Whether the internal log is still being rate limited.
+    pub active: bool,
+    /// The number of times the internal log was suppressed in the current period.
+    pub suppressed_count: u64,
+    /// The length of the rate limiting period, in seconds.
+    pub period_secs: u64,
+}
+
+/// Returns the rate limiting reported by the event being handled, if it was emitted by a
+/// `RateLimitedLayer` in place of rate limited internal logs.
+///
+/// This is only set while the inner layer of the `RateLimitedLayer` handles the event.
+pub fn current_rate_limit() -> Option<RateLimit> {
+    CURRENT_RATE_LIMIT.with(Cell::get)
+}
+
 #[derive(Eq, PartialEq, Hash, Clone)]
 struct RateKeyIdentifier {
     callsite: Identifier,
@@ -189,7 +212,12 @@ where
                 1 => {
                     let message =
                         format!("Internal log [{}] is being rate limited.", state.message);
-                    self.create_event(&ctx, metadata, message, state.limit);
+                    let rate_limit = RateLimit {
+                        active: true,
+                        suppressed_count: 1,
+                        period_secs: state.limit,
+                    };
+                    self.create_event(&ctx, metadata, message, rate_limit);
                 }
                 _ => {}
             }
@@ -202,8 +230,13 @@ where
                     state.message,
                     previous_count - 1
                 );
+                let rate_limit = RateLimit {
+                    active: false,
+                    suppressed_count: previous_count - 1,
+                    period_secs: state.limit,
+                };
 
-                self.create_event(&ctx, metadata, message, state.limit);
+                self.create_event(&ctx, metadata, message, rate_limit);
             }
 
             // We're not rate limiting anymore, so we also emit the current event as normal.. but we update our rate
@@ -250,12 +283,13 @@ where
         ctx: &Context<S>,
         metadata: &'static Metadata<'static>,
         message: String,
-        rate_limit: u64,
+        rate_limit: RateLimit,
     ) {
         let fields = metadata.fields();
 
         let message = display(message);
 
+        CURRENT_RATE_LIMIT.with(|current| current.set(Some(rate_limit)));
         if let Some(message_field) = fields.field("message") {
             let values = [(&message_field, Some(&message as &dyn Value))];
 
@@ -265,13 +299,14 @@ where
         } else {
             let values = [(
                 &fields.field(RATE_LIMIT_FIELD).unwrap(),
-                Some(&rate_limit as &dyn Value),
+                Some(&rate_limit.period_secs as &dyn Value),
             )];
 
             let valueset = fields.value_set(&values);
             let event = Event::new(metadata, &valueset);
             self.inner.on_event(&event, ctx.clone());
         }
+        CURRENT_RATE_LIMIT.with(|current| current.set(None));
     }
 }
 
@@ -490,6 +525,59 @@ mod test {
         }
     }
 
+    #[derive(Default)]
+    struct RateLimitRecordingLayer {
+        rate_limits: Arc<Mutex<Vec<Option<RateLimit>>>>,
+    }
+
+    impl<S> Layer<S> for RateLimitRecordingLayer
+    where
+        S: Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_event(&self, _event: &Event<'_>, _ctx: Context<'_, S>) {
+            let mut rate_limits = self.rate_limits.lock().unwrap();
+            rate_limits.push(current_rate_limit());
+        }
+    }
+
+    #[test]
+    fn reports_rate_limits() {
+        let rate_limits: Arc<Mutex<Vec<Option<RateLimit>>>> = Default::default();
+
+        let recorder = RateLimitRecordingLayer {
+            rate_limits: Arc::clone(&rate_limits),
+        };
+        let sub = tracing_subscriber::registry::Registry::default()
+            .with(RateLimitedLayer::new(recorder).with_default_limit(1));
+        tracing::subscriber::with_default(sub, || {
+            for _ in 0..11 {
+                info!(message = "Hello world!", internal_log_rate_limit = true);
+                MockClock::advance(Duration::from_millis(100));
+            }
+        });
+
+        let rate_limits = rate_limits.lock().unwrap();
+
+        assert_eq!(
+            *rate_limits,
+            vec![
+                None,
+                Some(RateLimit {
+                    active: true,
+                    suppressed_count: 1,
+                    period_secs: 1,
+                }),
+                Some(RateLimit {
+                    active: false,
+                    suppressed_count: 9,
+                    period_secs: 1,
+                }),
+                None,
+            ]
+        );
+        assert_eq!(current_rate_limit(), None);
+    }
+
     #[test]
     fn rate_limits() {
         let events: Arc<Mutex<Vec<String>>> = Default::default();
@@ -24,6 +24,7 @@ pub enum TapEncodingFormat {
     Json,
     Yaml,
     Logfmt,
+    Raw,
 }
 
 /// String -> TapEncodingFormat, typically for parsing user input.
@@ -35,6 +36,7 @@ impl std::str::FromStr for TapEncodingFormat {
             "json" => Ok(Self::Json),
             "yaml" => Ok(Self::Yaml),
             "logfmt" => Ok(Self::Logfmt),
+            "raw" => Ok(Self::Raw),
             _ => Err("Invalid encoding format".to_string()),
         }
     }
@@ -49,10 +51,24 @@ impl From<TapEncodingFormat>
             TapEncodingFormat::Json => Self::JSON,
             TapEncodingFormat::Yaml => Self::YAML,
             TapEncodingFormat::Logfmt => Self::LOGFMT,
+            TapEncodingFormat::Raw => Self::RAW,
         }
     }
 }
 
+/// Server-side selection of the events returned by an output events subscription.
+#[derive(Debug, Clone, Default)]
+pub struct TapFilter {
+    /// VRL condition that events must match.
+    pub condition: Option<String>,
+
+    /// Keep one out of every `sample_rate` events matching the condition.
+    pub sample_rate: Option<i64>,
+
+    /// Maximum number of events to keep each second.
+    pub max_events_per_second: Option<i64>,
+}
+
 pub trait TapSubscriptionExt {
     /// Executes an output events subscription.
     fn output_events_by_component_id_patterns_subscription(
@@ -62,6 +78,7 @@ pub trait TapSubscriptionExt {
         encoding: TapEncodingFormat,
         limit: i64,
         interval: i64,
+        filter: TapFilter,
     ) -> crate::BoxedSubscription<OutputEventsByComponentIdPatternsSubscription>;
 }
 
@@ -74,6 +91,7 @@ impl TapSubscriptionExt for crate::SubscriptionClient {
         encoding: TapEncodingFormat,
         limit: i64,
         interval: i64,
+        filter: TapFilter,
     ) -> BoxedSubscription<OutputEventsByComponentIdPatternsSubscription> {
         let request_body = OutputEventsByComponentIdPatternsSubscription::build_query(
             output_events_by_component_id_patterns_subscription::Variables {
@@ -82,6 +100,9 @@ impl TapSubscriptionExt for crate::SubscriptionClient {
                 limit,
                 interval,
                 encoding: encoding.into(),
+                filter: filter.condition,
+                sample_rate: filter.sample_rate,
+                max_events_per_second: filter.max_events_per_second,
             },
         );
 
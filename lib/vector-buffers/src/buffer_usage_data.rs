@@ -1,17 +1,21 @@
 use std::{
+    collections::VecDeque,
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicI64, AtomicU64, Ordering},
         Arc,
     },
     time::Duration,
 };
 
-use tokio::time::interval;
+use tokio::time::{interval, Instant};
 use tracing::{Instrument, Span};
 use vector_common::internal_event::emit;
 
 use crate::{
-    internal_events::{BufferCreated, BufferEventsDropped, BufferEventsReceived, BufferEventsSent},
+    internal_events::{
+        BufferCreated, BufferEventsDropped, BufferEventsReceived, BufferEventsSent,
+        BufferOldestEventAge,
+    },
     spawn_named,
 };
 
@@ -79,6 +83,43 @@ impl CategoryMetrics {
     }
 }
 
+/// Tracks when the events held by a buffer component were received.
+///
+/// Arrivals are tracked at the granularity of the reporting interval, by recording the total number
+/// of events received by the end of each interval in which events were received. As events leave the
+/// buffer in order, the oldest event still held is the first one received after all of the events
+/// that have left it.
+#[derive(Debug, Default)]
+struct EventAges {
+    received: u64,
+    removed: u64,
+    arrivals: VecDeque<(Instant, u64)>,
+}
+
+impl EventAges {
+    /// Tracks the events received and removed since the last call.
+    fn track(&mut self, now: Instant, received: u64, removed: u64) {
+        if received > 0 {
+            self.received += received;
+            self.arrivals.push_back((now, self.received));
+        }
+
+        self.removed += removed;
+        while matches!(self.arrivals.front(), Some((_, received)) if *received <= self.removed) {
+            self.arrivals.pop_front();
+        }
+    }
+
+    /// Gets the age of the oldest event held, or zero if there are none.
+    fn oldest_event_age(&self, now: Instant) -> Duration {
+        self.arrivals
+            .front()
+            .map_or(Duration::ZERO, |(received_at, _)| {
+                now.saturating_duration_since(*received_at)
+            })
+    }
+}
+
 /// Handle to buffer usage metrics for a specific buffer stage.
 #[derive(Clone, Debug)]
 pub struct BufferUsageHandle {
@@ -118,6 +159,7 @@ impl BufferUsageHandle {
     /// This represents the events being sent into the buffer.
     pub fn increment_received_event_count_and_byte_size(&self, count: u64, byte_size: u64) {
         self.state.received.increment(count, byte_size);
+        self.state.adjust_buffered_events(count, true);
     }
 
     /// Increments the number of events (and their total size) sent by this buffer component.
@@ -125,6 +167,7 @@ impl BufferUsageHandle {
     /// This represents the events being read out of the buffer.
     pub fn increment_sent_event_count_and_byte_size(&self, count: u64, byte_size: u64) {
         self.state.sent.increment(count, byte_size);
+        self.state.adjust_buffered_events(count, false);
     }
 
     /// Increment the number of dropped events (and their total size) for this buffer component.
@@ -139,6 +182,14 @@ impl BufferUsageHandle {
         } else {
             self.state.dropped.increment(count, byte_size);
         }
+        self.state.adjust_buffered_events(count, false);
+    }
+
+    /// Gets the number of events currently held by this buffer component.
+    pub fn buffered_event_count(&self) -> u64 {
+        // Events can be counted as sent before being counted as received, as the two sides of the
+        // buffer update their metrics independently, so the count can briefly be negative.
+        u64::try_from(self.state.buffered_events.load(Ordering::Acquire)).unwrap_or(0)
     }
 }
 
@@ -150,6 +201,7 @@ struct BufferUsageData {
     dropped: CategoryMetrics,
     dropped_intentional: CategoryMetrics,
     max_size: CategoryMetrics,
+    buffered_events: AtomicI64,
 }
 
 impl BufferUsageData {
@@ -160,6 +212,15 @@ impl BufferUsageData {
         }
     }
 
+    fn adjust_buffered_events(&self, count: u64, increment: bool) {
+        let count = i64::try_from(count).unwrap_or(i64::MAX);
+        if increment {
+            self.buffered_events.fetch_add(count, Ordering::AcqRel);
+        } else {
+            self.buffered_events.fetch_sub(count, Ordering::AcqRel);
+        }
+    }
+
     fn snapshot(&self) -> BufferUsageSnapshot {
         let received = self.received.get();
         let sent = self.sent.get();
@@ -247,10 +308,15 @@ impl BufferUsage {
 
         let task = async move {
             let mut interval = interval(Duration::from_secs(2));
+            let mut ages = stages
+                .iter()
+                .map(|_| EventAges::default())
+                .collect::<Vec<_>>();
             loop {
                 interval.tick().await;
+                let now = Instant::now();
 
-                for stage in &stages {
+                for (stage, ages) in stages.iter().zip(ages.iter_mut()) {
                     let max_size = stage.max_size.get();
                     emit(BufferCreated {
                         idx: stage.idx,
@@ -300,6 +366,16 @@ impl BufferUsage {
                             byte_size: dropped_intentional.event_byte_size,
                         });
                     }
+
+                    ages.track(
+                        now,
+                        received.event_count,
+                        sent.event_count + dropped.event_count + dropped_intentional.event_count,
+                    );
+                    emit(BufferOldestEventAge {
+                        idx: stage.idx,
+                        age: ages.oldest_event_age(now),
+                    });
                 }
             }
         };
@@ -308,3 +384,39 @@ impl BufferUsage {
         spawn_named(task.instrument(span.or_current()), task_name.as_str());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::EventAges;
+
+    #[test]
+    fn oldest_event_age_follows_removals() {
+        let start = Instant::now();
+        let mut ages = EventAges::default();
+        assert_eq!(Duration::ZERO, ages.oldest_event_age(start));
+
+        ages.track(start, 10, 0);
+        ages.track(start + Duration::from_secs(2), 5, 4);
+        assert_eq!(
+            Duration::from_secs(4),
+            ages.oldest_event_age(start + Duration::from_secs(4))
+        );
+
+        // Removing the rest of the events received first leaves the ones received later.
+        ages.track(start + Duration::from_secs(4), 0, 6);
+        assert_eq!(
+            Duration::from_secs(2),
+            ages.oldest_event_age(start + Duration::from_secs(4))
+        );
+
+        ages.track(start + Duration::from_secs(6), 0, 5);
+        assert_eq!(
+            Duration::ZERO,
+            ages.oldest_event_age(start + Duration::from_secs(6))
+        );
+    }
+}
@@ -8,7 +8,9 @@ use std::{
 use serde::{de, Deserialize, Deserializer, Serialize};
 use snafu::{ResultExt, Snafu};
 use tracing::Span;
-use vector_common::{config::ComponentKey, finalization::Finalizable};
+use vector_common::{
+    config::ComponentKey, finalization::Finalizable, sensitive_string::SensitiveString,
+};
 use vector_config::configurable_component;
 
 use crate::{
@@ -16,7 +18,7 @@ use crate::{
         builder::{TopologyBuilder, TopologyError},
         channel::{BufferReceiver, BufferSender},
     },
-    variants::{DiskV2Buffer, MemoryBuffer},
+    variants::{disk_v2::RecordCipher, DiskV2Buffer, MemoryBuffer},
     Bufferable, WhenFull,
 };
 
@@ -36,9 +38,11 @@ enum BufferTypeKind {
     Memory,
     #[serde(rename = "disk")]
     DiskV2,
+    #[serde(rename = "hybrid")]
+    Hybrid,
 }
 
-const ALL_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
+const ALL_FIELDS: [&str; 5] = ["type", "max_events", "max_size", "when_full", "encryption"];
 
 struct BufferTypeVisitor;
 
@@ -51,6 +55,7 @@ impl BufferTypeVisitor {
         let mut max_events: Option<NonZeroUsize> = None;
         let mut max_size: Option<NonZeroU64> = None;
         let mut when_full: Option<WhenFull> = None;
+        let mut encryption: Option<DiskBufferEncryption> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
                 "type" => {
@@ -77,6 +82,12 @@ impl BufferTypeVisitor {
                     }
                     when_full = Some(map.next_value()?);
                 }
+                "encryption" => {
+                    if encryption.is_some() {
+                        return Err(de::Error::duplicate_field("encryption"));
+                    }
+                    encryption = Some(map.next_value()?);
+                }
                 other => {
                     return Err(de::Error::unknown_field(other, &ALL_FIELDS));
                 }
@@ -92,6 +103,12 @@ impl BufferTypeVisitor {
                         &["type", "max_events", "when_full"],
                     ));
                 }
+                if encryption.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "encryption",
+                        &["type", "max_events", "when_full"],
+                    ));
+                }
                 Ok(BufferType::Memory {
                     max_events: max_events.unwrap_or_else(memory_buffer_default_max_events),
                     when_full,
@@ -101,14 +118,21 @@ impl BufferTypeVisitor {
                 if max_events.is_some() {
                     return Err(de::Error::unknown_field(
                         "max_events",
-                        &["type", "max_size", "when_full"],
+                        &["type", "max_size", "when_full", "encryption"],
                     ));
                 }
                 Ok(BufferType::DiskV2 {
                     max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
                     when_full,
+                    encryption,
                 })
             }
+            BufferTypeKind::Hybrid => Ok(BufferType::Hybrid {
+                max_events: max_events.unwrap_or_else(memory_buffer_default_max_events),
+                max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
+                when_full,
+                encryption,
+            }),
         }
     }
 }
@@ -175,9 +199,46 @@ impl DiskUsage {
     }
 }
 
+/// Encryption at rest for disk buffers.
+///
+/// Records are encrypted with AES-256-GCM before being written to disk. Records that were written
+/// before encryption was enabled can still be read.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct DiskBufferEncryption {
+    /// The key used to encrypt records written to the buffer.
+    ///
+    /// Any string can be used as a key, as the actual encryption key is derived from it. The key
+    /// should be provided through a secrets backend rather than being written into the
+    /// configuration directly.
+    #[configurable(metadata(docs::examples = "SECRET[backend.buffer_key]"))]
+    pub key: SensitiveString,
+
+    /// Keys that were previously used to encrypt records written to the buffer.
+    ///
+    /// When rotating the encryption key, the old key should be added here so that any records
+    /// still in the buffer can be read. Once those records have been processed, the old key can be
+    /// removed.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "SECRET[backend.previous_buffer_key]"))]
+    pub previous_keys: Vec<SensitiveString>,
+}
+
+impl DiskBufferEncryption {
+    fn cipher(&self) -> RecordCipher {
+        let previous_keys = self
+            .previous_keys
+            .iter()
+            .map(SensitiveString::inner)
+            .collect::<Vec<_>>();
+        RecordCipher::new(self.key.inner(), &previous_keys)
+    }
+}
+
 /// A specific type of buffer stage.
 #[configurable_component(no_deser)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case", tag = "type")]
 #[configurable(metadata(docs::enum_tag_description = "The type of buffer to use."))]
 pub enum BufferType {
@@ -218,6 +279,45 @@ pub enum BufferType {
         #[configurable(derived)]
         #[serde(default)]
         when_full: WhenFull,
+
+        #[configurable(derived)]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        encryption: Option<DiskBufferEncryption>,
+    },
+
+    /// A buffer stage backed by memory, which spills over to disk when full.
+    ///
+    /// Events are buffered in memory until `max_events` is reached, at which point any further
+    /// events are written to disk instead. As soon as there is free space in memory again, events
+    /// go back to being buffered in memory, while the events that spilled over to disk are drained
+    /// alongside them.
+    ///
+    /// This avoids the latency cost of a disk buffer when the sink keeps up, while still absorbing
+    /// backpressure durably when it does not. Events held in memory will be lost if Vector is
+    /// restarted forcefully or crashes.
+    #[configurable(title = "Events are buffered in memory, spilling over to disk when full.")]
+    #[serde(rename = "hybrid")]
+    Hybrid {
+        /// The maximum number of events allowed in memory before spilling over to disk.
+        #[serde(default = "memory_buffer_default_max_events")]
+        max_events: NonZeroUsize,
+
+        /// The maximum size of the buffer on disk.
+        ///
+        /// Must be at least ~256 megabytes (268435488 bytes).
+        #[configurable(
+            validation(range(min = 268435488)),
+            metadata(docs::type_unit = "bytes")
+        )]
+        max_size: NonZeroU64,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        when_full: WhenFull,
+
+        #[configurable(derived)]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        encryption: Option<DiskBufferEncryption>,
     },
 }
 
@@ -249,7 +349,7 @@ impl BufferType {
             None => None,
             Some(global_data_dir) => match self {
                 Self::Memory { .. } => None,
-                Self::DiskV2 { max_size, .. } => {
+                Self::DiskV2 { max_size, .. } | Self::Hybrid { max_size, .. } => {
                     let data_dir = crate::variants::disk_v2::get_disk_v2_data_dir_path(
                         &global_data_dir,
                         id.id(),
@@ -276,19 +376,35 @@ impl BufferType {
     where
         T: Bufferable + Clone + Finalizable,
     {
-        match *self {
+        match self {
             BufferType::Memory {
                 when_full,
                 max_events,
             } => {
-                builder.stage(MemoryBuffer::new(max_events), when_full);
+                builder.stage(MemoryBuffer::new(*max_events), *when_full);
             }
             BufferType::DiskV2 {
                 when_full,
                 max_size,
+                encryption,
             } => {
                 let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
-                builder.stage(DiskV2Buffer::new(id, data_dir, max_size), when_full);
+                let buffer = DiskV2Buffer::new(id, data_dir, *max_size)
+                    .with_encryption(encryption.as_ref().map(DiskBufferEncryption::cipher));
+                builder.stage(buffer, *when_full);
+            }
+            BufferType::Hybrid {
+                max_events,
+                max_size,
+                when_full,
+                encryption,
+            } => {
+                let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
+                let buffer = DiskV2Buffer::new(id, data_dir, *max_size)
+                    .with_encryption(encryption.as_ref().map(DiskBufferEncryption::cipher));
+                builder
+                    .stage(MemoryBuffer::new(*max_events), WhenFull::Overflow)
+                    .stage(buffer, *when_full);
             }
         };
 
@@ -387,9 +503,23 @@ impl BufferConfig {
 
 #[cfg(test)]
 mod test {
-    use std::num::{NonZeroU64, NonZeroUsize};
-
-    use crate::{BufferConfig, BufferType, WhenFull};
+    use std::{
+        num::{NonZeroU64, NonZeroUsize},
+        path::Path,
+        time::Duration,
+    };
+
+    use tracing::Span;
+
+    use super::DiskBufferEncryption;
+    use crate::{
+        test::{acknowledge, with_temp_dir, SizedRecord},
+        topology::{
+            builder::TopologyBuilder,
+            channel::{BufferReceiver, BufferSender},
+        },
+        BufferConfig, BufferType, WhenFull,
+    };
 
     fn check_single_stage(source: &str, expected: BufferType) {
         let config: BufferConfig = serde_yaml::from_str(source).unwrap();
@@ -406,6 +536,53 @@ mod test {
         }
     }
 
+    async fn build_hybrid(
+        data_dir: &Path,
+        max_events: usize,
+    ) -> (BufferSender<SizedRecord>, BufferReceiver<SizedRecord>) {
+        let stage = BufferType::Hybrid {
+            max_events: NonZeroUsize::new(max_events).unwrap(),
+            max_size: NonZeroU64::new(1024 * 1024 * 1024).unwrap(),
+            when_full: WhenFull::Block,
+            encryption: None,
+        };
+
+        let mut builder = TopologyBuilder::default();
+        stage
+            .add_to_builder(&mut builder, Some(data_dir.to_path_buf()), "hybrid".into())
+            .expect("hybrid stage should be valid");
+        builder
+            .build("hybrid".into(), Span::none())
+            .await
+            .expect("hybrid buffer should build")
+    }
+
+    async fn send_all(sender: &mut BufferSender<SizedRecord>, values: impl Iterator<Item = u32>) {
+        for value in values {
+            sender
+                .send(SizedRecord::new(value))
+                .await
+                .expect("send should not fail");
+        }
+        sender.flush().await.expect("flush should not fail");
+    }
+
+    async fn receive(receiver: &mut BufferReceiver<SizedRecord>, count: usize) -> Vec<SizedRecord> {
+        let mut records = Vec::with_capacity(count);
+        for _ in 0..count {
+            let record = tokio::time::timeout(Duration::from_secs(5), receiver.next())
+                .await
+                .expect("record should be received in time")
+                .expect("buffer should not be closed");
+            records.push(record);
+        }
+        records
+    }
+
+    fn values(records: &[SizedRecord]) -> Vec<u32> {
+        records.iter().map(|record| record.0).collect()
+    }
+
     #[test]
     fn parse_empty() {
         let source = "";
@@ -522,7 +699,162 @@ max_events: 42
             BufferType::DiskV2 {
                 max_size: NonZeroU64::new(1024).unwrap(),
                 when_full: WhenFull::Block,
+                encryption: None,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_hybrid() {
+        check_single_stage(
+            r#"
+          type: hybrid
+          max_size: 1024
+          "#,
+            BufferType::Hybrid {
+                max_events: NonZeroUsize::new(500).unwrap(),
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::Block,
+                encryption: None,
+            },
+        );
+
+        check_single_stage(
+            r#"
+          type: hybrid
+          max_events: 100
+          max_size: 1024
+          when_full: drop_newest
+          "#,
+            BufferType::Hybrid {
+                max_events: NonZeroUsize::new(100).unwrap(),
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::DropNewest,
+                encryption: None,
+            },
+        );
+
+        let source = r#"
+          type: hybrid
+          max_events: 100
+          "#;
+        assert!(serde_yaml::from_str::<BufferConfig>(source).is_err());
+    }
+
+    #[test]
+    fn parse_disk_encryption() {
+        check_single_stage(
+            r#"
+          type: disk
+          max_size: 1024
+          encryption:
+            key: foo
+            previous_keys: [bar]
+          "#,
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::Block,
+                encryption: Some(DiskBufferEncryption {
+                    key: "foo".to_string().into(),
+                    previous_keys: vec!["bar".to_string().into()],
+                }),
             },
         );
+
+        let source = r#"
+          type: memory
+          encryption:
+            key: foo
+          "#;
+        assert!(serde_yaml::from_str::<BufferConfig>(source).is_err());
+    }
+
+    #[tokio::test]
+    async fn hybrid_overflows_to_disk() {
+        with_temp_dir(|dir| {
+            let data_dir = dir.to_path_buf();
+
+            async move {
+                // Fill the memory tier, and push the remainder into the disk tier, without reading
+                // anything back out.
+                let (mut sender, receiver) = build_hybrid(&data_dir, 2).await;
+                send_all(&mut sender, 1..=6).await;
+                drop(sender);
+                drop(receiver);
+
+                // Give the disk tier's finalizer task a chance to shut down and release the lock.
+                tokio::time::sleep(Duration::from_millis(100)).await;
+
+                // Only the records that overflowed to disk survive reopening the buffer.
+                let (_sender, mut receiver) = build_hybrid(&data_dir, 2).await;
+                let records = receive(&mut receiver, 4).await;
+                assert_eq!(values(&records), vec![3, 4, 5, 6]);
+            }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn hybrid_preserves_order_within_each_tier() {
+        with_temp_dir(|dir| {
+            let data_dir = dir.to_path_buf();
+
+            async move {
+                let (mut sender, mut receiver) = build_hybrid(&data_dir, 4).await;
+                send_all(&mut sender, 1..=12).await;
+
+                // Both tiers are serviced fairly, so records from the two tiers can interleave, but
+                // each tier hands its records out in the order they were sent.
+                let received = values(&receive(&mut receiver, 12).await);
+                let memory = received.iter().copied().filter(|v| *v <= 4);
+                let disk = received.iter().copied().filter(|v| *v > 4);
+                assert_eq!(memory.collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+                assert_eq!(disk.collect::<Vec<_>>(), (5..=12).collect::<Vec<_>>());
+
+                // Once the memory tier has room again, new records go back to it.
+                send_all(&mut sender, 13..=14).await;
+                let received = values(&receive(&mut receiver, 2).await);
+                assert_eq!(received, vec![13, 14]);
+            }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn hybrid_waits_for_acknowledgements_across_tiers() {
+        with_temp_dir(|dir| {
+            let data_dir = dir.to_path_buf();
+
+            async move {
+                let (mut sender, mut receiver) = build_hybrid(&data_dir, 2).await;
+                send_all(&mut sender, 1..=6).await;
+                drop(sender);
+
+                let records = receive(&mut receiver, 6).await;
+
+                // The disk tier only considers itself empty once its records are acknowledged, so
+                // the buffer stays open while any of them are still outstanding.
+                let (acked, unacked): (Vec<_>, Vec<_>) =
+                    records.into_iter().partition(|record| record.0 <= 4);
+                for record in acked {
+                    acknowledge(record).await;
+                }
+                let pending =
+                    tokio::time::timeout(Duration::from_millis(100), receiver.next()).await;
+                assert!(
+                    pending.is_err(),
+                    "buffer closed with unacknowledged records"
+                );
+
+                for record in unacked {
+                    acknowledge(record).await;
+                }
+                let closed = tokio::time::timeout(Duration::from_secs(5), receiver.next())
+                    .await
+                    .expect("buffer should close once everything is acknowledged");
+                assert!(closed.is_none());
+            }
+        })
+        .await;
     }
 }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use metrics::{counter, decrement_gauge, gauge, increment_gauge};
 use vector_common::internal_event::{error_type, InternalEvent};
 
@@ -51,6 +53,17 @@ impl InternalEvent for BufferEventsSent {
     }
 }
 
+pub struct BufferOldestEventAge {
+    pub idx: usize,
+    pub age: Duration,
+}
+
+impl InternalEvent for BufferOldestEventAge {
+    fn emit(self) {
+        gauge!("buffer_oldest_event_age_seconds", self.age.as_secs_f64(), "stage" => self.idx.to_string());
+    }
+}
+
 pub struct BufferEventsDropped {
     pub idx: usize,
     pub count: u64,
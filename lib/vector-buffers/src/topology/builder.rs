@@ -7,7 +7,7 @@ use tracing::Span;
 use super::channel::{ReceiverAdapter, SenderAdapter};
 use crate::{
     buffer_usage_data::{BufferUsage, BufferUsageHandle},
-    topology::channel::{BufferReceiver, BufferSender},
+    topology::channel::{BufferControl, BufferReceiver, BufferSender},
     variants::MemoryBuffer,
     Bufferable, WhenFull,
 };
@@ -111,6 +111,7 @@ impl<T: Bufferable> TopologyBuilder<T> {
     ) -> Result<(BufferSender<T>, BufferReceiver<T>), TopologyError> {
         // We pop stages off in reverse order to build from the inside out.
         let mut buffer_usage = BufferUsage::from_span(span);
+        let mut usage_handles = Vec::new();
         let mut current_stage = None;
 
         for (stage_idx, stage) in self.stages.into_iter().enumerate().rev() {
@@ -136,6 +137,7 @@ impl<T: Bufferable> TopologyBuilder<T> {
             // the handle to the `BufferSender`/`BufferReceiver` wrappers, but that's the price we
             // have to pay for letting each stage function in an opaque way when wrapped.
             let usage_handle = buffer_usage.add_stage(stage_idx);
+            usage_handles.push(usage_handle.clone());
             let provides_instrumentation = stage.untransformed.provides_instrumentation();
             let (sender, receiver) = stage
                 .untransformed
@@ -162,7 +164,9 @@ impl<T: Bufferable> TopologyBuilder<T> {
             current_stage = Some((sender, receiver));
         }
 
-        let (sender, receiver) = current_stage.ok_or(TopologyError::EmptyTopology)?;
+        let (sender, mut receiver) = current_stage.ok_or(TopologyError::EmptyTopology)?;
+        let (control, requeued) = BufferControl::new(usage_handles);
+        receiver.with_control(control, requeued);
 
         // Install the buffer usage handler since we successfully created the buffer topology.  This
         // spawns it in the background and periodically emits aggregated metrics about each of the
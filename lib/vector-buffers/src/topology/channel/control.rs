@@ -0,0 +1,202 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+    time::Duration,
+};
+
+use snafu::Snafu;
+use tokio::{
+    sync::{mpsc, Mutex as AsyncMutex, OwnedMutexGuard},
+    time::timeout,
+};
+
+use super::receiver::BufferReceiver;
+use crate::{buffer_usage_data::BufferUsageHandle, Bufferable};
+
+/// Maximum number of items waiting to be requeued.
+const REQUEUE_CAPACITY: usize = 16;
+
+/// Error that occurred while controlling a buffer.
+#[derive(Debug, Snafu)]
+pub enum ControlError {
+    /// The buffer isn't being read from, and can't be read from directly.
+    #[snafu(display("the buffer can't be read from"))]
+    NotReadable,
+
+    /// The component reading from the buffer didn't hand it over in time.
+    #[snafu(display("the buffer is busy being read from"))]
+    Busy,
+
+    /// The component reading from the buffer is gone.
+    #[snafu(display("the buffer is closed"))]
+    Closed,
+}
+
+struct Inner<T: Bufferable> {
+    stages: Vec<BufferUsageHandle>,
+    receiver: Mutex<Weak<AsyncMutex<BufferReceiver<T>>>>,
+    requeue: mpsc::Sender<T>,
+    requeued_events: AtomicU64,
+}
+
+/// Handle for inspecting and draining a buffer from outside of the component reading from it.
+///
+/// Draining reads the items held by the buffer directly, taking the reader over from the
+/// component, which resumes reading once the drain is done. Requeued items are handed to the
+/// component before any other item held by the buffer.
+pub struct BufferControl<T: Bufferable> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T: Bufferable> BufferControl<T> {
+    /// Creates a new [`BufferControl`] for a buffer made of the given stages, along with the
+    /// receiving side of the items requeued into it.
+    pub(crate) fn new(stages: Vec<BufferUsageHandle>) -> (Self, mpsc::Receiver<T>) {
+        let (requeue, requeued) = mpsc::channel(REQUEUE_CAPACITY);
+        let control = Self {
+            inner: Arc::new(Inner {
+                stages,
+                receiver: Mutex::new(Weak::new()),
+                requeue,
+                requeued_events: AtomicU64::new(0),
+            }),
+        };
+        (control, requeued)
+    }
+
+    /// Gets the number of events currently held by the buffer, across all of its stages, including
+    /// the events waiting to be requeued.
+    pub fn buffered_event_count(&self) -> u64 {
+        self.inner
+            .stages
+            .iter()
+            .map(BufferUsageHandle::buffered_event_count)
+            .sum::<u64>()
+            + self.inner.requeued_events.load(Ordering::Acquire)
+    }
+
+    /// Gets the usage of the buffer, between 0 and 1, as the usage of its fullest stage limited by a
+    /// number of events.
+    ///
+    /// Returns `None` if none of the stages are limited by a number of events, such as for disk
+    /// buffers.
+    pub fn usage(&self) -> Option<f64> {
+        self.inner
+            .stages
+            .iter()
+            .filter_map(|stage| {
+                let max_events = stage.snapshot().max_size_events;
+                (max_events > 0).then(|| stage.buffered_event_count() as f64 / max_events as f64)
+            })
+            .reduce(f64::max)
+    }
+
+    /// Sets the receiver read from when draining the buffer.
+    pub(crate) fn attach(&self, receiver: &Arc<AsyncMutex<BufferReceiver<T>>>) {
+        *self.inner.receiver.lock().expect("receiver lock poisoned") = Arc::downgrade(receiver);
+    }
+
+    /// Takes the reader of the buffer over, to drain the items it holds.
+    ///
+    /// The component reading from the buffer hands the reader over once it's done with the item it
+    /// is reading, if any, which must happen within `read_timeout`. The drain ends once the buffer
+    /// is empty, or when no item could be read within `read_timeout`, and the component resumes
+    /// reading once the drain is dropped.
+    ///
+    /// # Errors
+    ///
+    /// If the buffer isn't being read from, or the reader isn't handed over in time, an error
+    /// variant will be returned.
+    pub async fn drain(&self, read_timeout: Duration) -> Result<BufferDrain<T>, ControlError> {
+        let receiver = self
+            .inner
+            .receiver
+            .lock()
+            .expect("receiver lock poisoned")
+            .upgrade()
+            .ok_or(ControlError::NotReadable)?;
+        let receiver = timeout(read_timeout, receiver.lock_owned())
+            .await
+            .map_err(|_| ControlError::Busy)?;
+
+        Ok(BufferDrain {
+            receiver,
+            control: self.clone(),
+            read_timeout,
+        })
+    }
+
+    /// Requeues an item into the buffer, so it's read next.
+    ///
+    /// # Errors
+    ///
+    /// If the component reading from the buffer is gone, the item can't be requeued, and an error
+    /// variant will be returned.
+    pub async fn requeue(&self, item: T) -> Result<(), ControlError> {
+        let events = item.event_count() as u64;
+        self.inner
+            .requeued_events
+            .fetch_add(events, Ordering::AcqRel);
+        self.inner.requeue.send(item).await.map_err(|_| {
+            self.inner
+                .requeued_events
+                .fetch_sub(events, Ordering::AcqRel);
+            ControlError::Closed
+        })
+    }
+
+    /// Accounts for a requeued item being read.
+    pub(crate) fn requeued_item_read(&self, item: &T) {
+        self.inner
+            .requeued_events
+            .fetch_sub(item.event_count() as u64, Ordering::AcqRel);
+    }
+}
+
+impl<T: Bufferable> Clone for BufferControl<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: Bufferable> Default for BufferControl<T> {
+    fn default() -> Self {
+        Self::new(Vec::new()).0
+    }
+}
+
+impl<T: Bufferable> fmt::Debug for BufferControl<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferControl")
+            .field("buffered_event_count", &self.buffered_event_count())
+            .finish()
+    }
+}
+
+/// A drain of a buffer, holding the reader of the buffer until it's dropped.
+pub struct BufferDrain<T: Bufferable> {
+    receiver: OwnedMutexGuard<BufferReceiver<T>>,
+    control: BufferControl<T>,
+    read_timeout: Duration,
+}
+
+impl<T: Bufferable> BufferDrain<T> {
+    /// Reads the next item held by the buffer.
+    ///
+    /// Returns `None` once the buffer is empty, or if no item could be read within the read
+    /// timeout of the drain.
+    pub async fn next(&mut self) -> Option<T> {
+        if self.control.buffered_event_count() == 0 {
+            return None;
+        }
+        timeout(self.read_timeout, self.receiver.next())
+            .await
+            .ok()
+            .flatten()
+    }
+}
@@ -183,6 +183,23 @@ impl<T> Drop for LimitedSender<T> {
     }
 }
 
+/// A handle to the number of events queued in a channel.
+#[derive(Clone, Debug)]
+pub struct QueueDepth {
+    limit: usize,
+    limiter: Arc<Semaphore>,
+}
+
+impl QueueDepth {
+    /// Gets the number of events queued in the channel.
+    ///
+    /// Items larger than the limit of the channel only count for the limit, so this is a lower
+    /// bound when such items are queued.
+    pub fn events(&self) -> usize {
+        self.limit.saturating_sub(self.limiter.available_permits())
+    }
+}
+
 #[derive(Debug)]
 pub struct LimitedReceiver<T> {
     inner: Inner<T>,
@@ -194,6 +211,14 @@ impl<T: Send + 'static> LimitedReceiver<T> {
         self.inner.limiter.available_permits()
     }
 
+    /// Gets a handle to the number of events queued in this channel.
+    pub fn depth(&self) -> QueueDepth {
+        QueueDepth {
+            limit: self.inner.limit,
+            limiter: Arc::clone(&self.inner.limiter),
+        }
+    }
+
     pub async fn next(&mut self) -> Option<T> {
         loop {
             if let Some((_permit, item)) = self.inner.data.pop() {
@@ -287,6 +312,21 @@ mod tests {
         assert_eq!(Some(msg), assert_ready!(recv.poll()));
     }
 
+    #[tokio::test]
+    async fn depth_tracks_queued_events() {
+        let (mut tx, mut rx) = limited(10);
+        let depth = rx.depth();
+
+        assert_eq!(0, depth.events());
+
+        tx.send(Sample(42)).await.unwrap();
+        tx.send(Sample(43)).await.unwrap();
+        assert_eq!(2, depth.events());
+
+        assert_eq!(Some(Sample(42)), rx.next().await);
+        assert_eq!(1, depth.events());
+    }
+
     #[test]
     fn sender_waits_for_more_capacity_when_none_available() {
         let (mut tx, mut rx) = limited(1);
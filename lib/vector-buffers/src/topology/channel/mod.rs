@@ -1,8 +1,10 @@
+mod control;
 mod limited_queue;
 mod receiver;
 mod sender;
 
-pub use limited_queue::{limited, LimitedReceiver, LimitedSender, SendError};
+pub use control::{BufferControl, BufferDrain, ControlError};
+pub use limited_queue::{limited, LimitedReceiver, LimitedSender, QueueDepth, SendError};
 pub use receiver::*;
 pub use sender::*;
 
@@ -1,16 +1,22 @@
 use std::{
-    mem,
     pin::Pin,
+    sync::Arc,
     task::{ready, Context, Poll},
 };
 
 use async_recursion::async_recursion;
 use futures::Stream;
-use tokio::select;
+use tokio::{
+    select,
+    sync::{mpsc, Mutex},
+};
 use tokio_util::sync::ReusableBoxFuture;
 use vector_common::internal_event::emit;
 
-use super::limited_queue::LimitedReceiver;
+use super::{
+    control::BufferControl,
+    limited_queue::{LimitedReceiver, QueueDepth},
+};
 use crate::{
     buffer_usage_data::BufferUsageHandle,
     variants::disk_v2::{self, ProductionFilesystem},
@@ -77,6 +83,8 @@ pub struct BufferReceiver<T: Bufferable> {
     base: ReceiverAdapter<T>,
     overflow: Option<Box<BufferReceiver<T>>>,
     instrumentation: Option<BufferUsageHandle>,
+    control: BufferControl<T>,
+    requeued: Option<mpsc::Receiver<T>>,
 }
 
 impl<T: Bufferable> BufferReceiver<T> {
@@ -86,6 +94,8 @@ impl<T: Bufferable> BufferReceiver<T> {
             base,
             overflow: None,
             instrumentation: None,
+            control: BufferControl::default(),
+            requeued: None,
         }
     }
 
@@ -95,6 +105,8 @@ impl<T: Bufferable> BufferReceiver<T> {
             base,
             overflow: Some(Box::new(overflow)),
             instrumentation: None,
+            control: BufferControl::default(),
+            requeued: None,
         }
     }
 
@@ -107,13 +119,65 @@ impl<T: Bufferable> BufferReceiver<T> {
         self.overflow = Some(Box::new(overflow));
     }
 
+    /// Gets a handle to the number of events queued in the base buffer, if it is an in-memory
+    /// buffer.
+    pub fn depth(&self) -> Option<QueueDepth> {
+        match &self.base {
+            ReceiverAdapter::InMemory(rx) => Some(rx.depth()),
+            ReceiverAdapter::DiskV2(_) => None,
+        }
+    }
+
     /// Configures this receiver to instrument the items passing through it.
     pub fn with_instrumentation(&mut self, handle: BufferUsageHandle) {
         self.instrumentation = Some(handle);
     }
 
-    #[async_recursion]
+    /// Configures the control handle used to drain this receiver, and the items requeued through
+    /// it.
+    pub(crate) fn with_control(&mut self, control: BufferControl<T>, requeued: mpsc::Receiver<T>) {
+        self.control = control;
+        self.requeued = Some(requeued);
+    }
+
+    /// Gets a handle to inspect and drain the buffer this receiver reads from.
+    pub fn control(&self) -> BufferControl<T> {
+        self.control.clone()
+    }
+
     pub async fn next(&mut self) -> Option<T> {
+        let Self {
+            base,
+            overflow,
+            instrumentation,
+            control,
+            requeued,
+        } = self;
+
+        // Requeued items are read first, as they were held by the buffer before being drained.
+        let requeued = async {
+            match requeued.as_mut() {
+                Some(requeued) => requeued.recv().await,
+                None => None,
+            }
+        };
+        let buffered = Self::receive(base, overflow.as_mut(), instrumentation.as_ref());
+        select! {
+            biased;
+            Some(item) = requeued => {
+                control.requeued_item_read(&item);
+                Some(item)
+            }
+            item = buffered => item,
+        }
+    }
+
+    #[async_recursion]
+    async fn receive(
+        base: &mut ReceiverAdapter<T>,
+        overflow: Option<&mut Box<BufferReceiver<T>>>,
+        instrumentation: Option<&BufferUsageHandle>,
+    ) -> Option<T> {
         // We want to poll both our base and overflow receivers without waiting for one or the
         // other to entirely drain before checking the other.  This ensures that we're fairly
         // servicing both receivers, and avoiding stalls in one or the other.
@@ -122,17 +186,17 @@ impl<T: Bufferable> BufferReceiver<T> {
         // occurred, and is over, and items are flowing through the base receiver.  If we waited to
         // entirely drain the overflow receiver, we might cause another small stall of the pipeline
         // attached to the base receiver.
-        let overflow = self.overflow.as_mut().map(Pin::new);
+        let overflow = overflow.map(Pin::new);
 
         let (item, from_base) = match overflow {
-            None => match self.base.next().await {
+            None => match base.next().await {
                 Some(item) => (item, true),
                 None => return None,
             },
             Some(mut overflow) => {
                 select! {
                     Some(item) = overflow.next() => (item, false),
-                    Some(item) = self.base.next() => (item, true),
+                    Some(item) = base.next() => (item, true),
                     else => return None,
                 }
             }
@@ -140,7 +204,7 @@ impl<T: Bufferable> BufferReceiver<T> {
 
         // If instrumentation is enabled, and we got the item from the base receiver, then and only
         // then do we track sending the event out.
-        if let Some(handle) = self.instrumentation.as_ref() {
+        if let Some(handle) = instrumentation {
             if from_base {
                 handle.increment_sent_event_count_and_byte_size(
                     item.event_count() as u64,
@@ -157,62 +221,67 @@ impl<T: Bufferable> BufferReceiver<T> {
     }
 }
 
-enum StreamState<T: Bufferable> {
-    Idle(BufferReceiver<T>),
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum StreamState {
+    Idle,
     Polling,
-    Closed(BufferReceiver<T>),
+    Closed,
 }
 
+/// A stream of the items of a buffer receiver.
+///
+/// The receiver is shared with its [`BufferControl`], so the buffer can be drained directly while
+/// the stream isn't reading from it.
 pub struct BufferReceiverStream<T: Bufferable> {
-    state: StreamState<T>,
-    recv_fut: ReusableBoxFuture<'static, (Option<T>, BufferReceiver<T>)>,
+    state: StreamState,
+    receiver: Arc<Mutex<BufferReceiver<T>>>,
+    control: BufferControl<T>,
+    recv_fut: ReusableBoxFuture<'static, Option<T>>,
 }
 
 impl<T: Bufferable> BufferReceiverStream<T> {
     pub fn new(receiver: BufferReceiver<T>) -> Self {
+        let control = receiver.control();
+        let receiver = Arc::new(Mutex::new(receiver));
+        control.attach(&receiver);
         Self {
-            state: StreamState::Idle(receiver),
-            recv_fut: ReusableBoxFuture::new(make_recv_future(None)),
+            state: StreamState::Idle,
+            recv_fut: ReusableBoxFuture::new(make_recv_future(Arc::clone(&receiver))),
+            receiver,
+            control,
         }
     }
+
+    /// Gets a handle to inspect and drain the buffer this stream reads from.
+    pub fn control(&self) -> BufferControl<T> {
+        self.control.clone()
+    }
 }
 
 impl<T: Bufferable> Stream for BufferReceiverStream<T> {
     type Item = T;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        loop {
-            match mem::replace(&mut self.state, StreamState::Polling) {
-                s @ StreamState::Closed(_) => {
-                    self.state = s;
-                    return Poll::Ready(None);
-                }
-                StreamState::Idle(receiver) => {
-                    self.recv_fut.set(make_recv_future(Some(receiver)));
-                }
-                StreamState::Polling => {
-                    let (result, receiver) = ready!(self.recv_fut.poll(cx));
-                    self.state = if result.is_none() {
-                        StreamState::Closed(receiver)
-                    } else {
-                        StreamState::Idle(receiver)
-                    };
-
-                    return Poll::Ready(result);
-                }
+        match self.state {
+            StreamState::Closed => return Poll::Ready(None),
+            StreamState::Idle => {
+                let receiver = Arc::clone(&self.receiver);
+                self.recv_fut.set(make_recv_future(receiver));
+                self.state = StreamState::Polling;
             }
+            StreamState::Polling => {}
         }
+
+        let result = ready!(self.recv_fut.poll(cx));
+        self.state = if result.is_none() {
+            StreamState::Closed
+        } else {
+            StreamState::Idle
+        };
+        Poll::Ready(result)
     }
 }
 
-async fn make_recv_future<T: Bufferable>(
-    receiver: Option<BufferReceiver<T>>,
-) -> (Option<T>, BufferReceiver<T>) {
-    match receiver {
-        None => panic!("invalid to poll future in uninitialized state"),
-        Some(mut receiver) => {
-            let result = receiver.next().await;
-            (result, receiver)
-        }
-    }
+async fn make_recv_future<T: Bufferable>(receiver: Arc<Mutex<BufferReceiver<T>>>) -> Option<T> {
+    receiver.lock_owned().await.next().await
 }
@@ -1,15 +1,20 @@
 use std::{
+    num::NonZeroUsize,
     sync::Arc,
     time::{Duration, Instant},
 };
 
+use futures::StreamExt;
 use tokio::{pin, sync::Barrier, time::sleep};
+use tracing::Span;
 
 use crate::{
     topology::{
-        channel::{BufferReceiver, BufferSender},
-        test_util::{assert_current_send_capacity, build_buffer},
+        builder::TopologyBuilder,
+        channel::{BufferReceiver, BufferSender, ControlError},
+        test_util::{assert_current_send_capacity, build_buffer, Sample},
     },
+    variants::MemoryBuffer,
     Bufferable, WhenFull,
 };
 
@@ -240,3 +245,83 @@ async fn test_buffer_metrics_drop_newest() {
     assert_eq!(2, snapshot.sent_event_count);
     assert_eq!(1, snapshot.dropped_event_count_intentional);
 }
+
+#[tokio::test]
+async fn test_receiver_drain() {
+    let mut builder = TopologyBuilder::<Sample>::default();
+    builder.stage(
+        MemoryBuffer::new(NonZeroUsize::new(10).unwrap()),
+        WhenFull::Block,
+    );
+    let (mut tx, rx) = builder
+        .build(String::from("test"), Span::none())
+        .await
+        .unwrap();
+    let mut rx = rx.into_stream();
+    let control = rx.control();
+
+    for i in 1..=3 {
+        tx.send(Sample(i)).await.unwrap();
+    }
+    assert_eq!(3, control.buffered_event_count());
+
+    // The drain reads the buffered items directly, without the stream being read from, and stops
+    // once the buffer is empty.
+    let mut drain = control.drain(Duration::from_secs(1)).await.unwrap();
+    let mut drained = Vec::new();
+    while let Some(item) = drain.next().await {
+        drained.push(item);
+    }
+    assert_eq!(vec![Sample(1), Sample(2), Sample(3)], drained);
+    assert_eq!(0, control.buffered_event_count());
+
+    // The stream waits for the drain to hand the receiver back.
+    let next = tokio::spawn(async move { (rx.next().await, rx) });
+    tx.send(Sample(4)).await.unwrap();
+    sleep(Duration::from_millis(50)).await;
+    assert!(!next.is_finished());
+    drop(drain);
+    let (item, mut rx) = next.await.unwrap();
+    assert_eq!(Some(Sample(4)), item);
+
+    // While the stream waits for items, it holds the receiver.
+    let pending = tokio::spawn(async move { (rx.next().await, rx) });
+    sleep(Duration::from_millis(50)).await;
+    assert!(matches!(
+        control.drain(Duration::from_millis(50)).await,
+        Err(ControlError::Busy)
+    ));
+    tx.send(Sample(5)).await.unwrap();
+    let (item, _rx) = pending.await.unwrap();
+    assert_eq!(Some(Sample(5)), item);
+}
+
+#[tokio::test]
+async fn test_receiver_requeue() {
+    let mut builder = TopologyBuilder::<Sample>::default();
+    builder.stage(
+        MemoryBuffer::new(NonZeroUsize::new(10).unwrap()),
+        WhenFull::Block,
+    );
+    let (mut tx, rx) = builder
+        .build(String::from("test"), Span::none())
+        .await
+        .unwrap();
+    let mut rx = rx.into_stream();
+    let control = rx.control();
+
+    tx.send(Sample(2)).await.unwrap();
+    control.requeue(Sample(1)).await.unwrap();
+    assert_eq!(2, control.buffered_event_count());
+
+    // Requeued items are read before the items held by the buffer.
+    assert_eq!(Some(Sample(1)), rx.next().await);
+    assert_eq!(Some(Sample(2)), rx.next().await);
+    assert_eq!(0, control.buffered_event_count());
+
+    drop(rx);
+    assert!(matches!(
+        control.requeue(Sample(3)).await,
+        Err(ControlError::Closed)
+    ));
+}
@@ -1,5 +1,6 @@
 use std::{
     path::{Path, PathBuf},
+    sync::Arc,
     time::Duration,
 };
 
@@ -7,6 +8,7 @@ use crc32fast::Hasher;
 use snafu::Snafu;
 
 use super::{
+    encryption::RecordCipher,
     io::{Filesystem, ProductionFilesystem},
     ledger::LEDGER_LEN,
     record::RECORD_HEADER_LEN,
@@ -153,6 +155,12 @@ pub struct DiskBufferConfig<FS> {
     /// amount of data written since the last flush would be lost.
     pub(crate) flush_interval: Duration,
 
+    /// Cipher used to encrypt record payloads at rest.
+    ///
+    /// When set, all newly-written records are encrypted. Records that were written before
+    /// encryption was enabled can still be read.
+    pub(crate) encryption: Option<Arc<RecordCipher>>,
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -173,6 +181,7 @@ where
     pub(crate) max_record_size: Option<usize>,
     pub(crate) write_buffer_size: Option<usize>,
     pub(crate) flush_interval: Option<Duration>,
+    pub(crate) encryption: Option<Arc<RecordCipher>>,
     pub(crate) filesystem: FS,
 }
 
@@ -188,6 +197,7 @@ impl DiskBufferConfigBuilder {
             max_record_size: None,
             write_buffer_size: None,
             flush_interval: None,
+            encryption: None,
             filesystem: ProductionFilesystem,
         }
     }
@@ -268,6 +278,14 @@ where
         self
     }
 
+    /// Sets the cipher used to encrypt record payloads at rest.
+    ///
+    /// Defaults to no encryption.
+    pub fn encryption(mut self, cipher: Option<RecordCipher>) -> Self {
+        self.encryption = cipher.map(Arc::new);
+        self
+    }
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -287,6 +305,7 @@ where
             max_record_size: self.max_record_size,
             write_buffer_size: self.write_buffer_size,
             flush_interval: self.flush_interval,
+            encryption: self.encryption,
             filesystem,
         }
     }
@@ -301,6 +320,7 @@ where
         let max_record_size = self.max_record_size.unwrap_or(DEFAULT_MAX_RECORD_SIZE);
         let write_buffer_size = self.write_buffer_size.unwrap_or(DEFAULT_WRITE_BUFFER_SIZE);
         let flush_interval = self.flush_interval.unwrap_or(DEFAULT_FLUSH_INTERVAL);
+        let encryption = self.encryption;
         let filesystem = self.filesystem;
 
         // Validate the input parameters.
@@ -427,6 +447,7 @@ where
             max_record_size,
             write_buffer_size,
             flush_interval,
+            encryption,
             filesystem,
         })
     }
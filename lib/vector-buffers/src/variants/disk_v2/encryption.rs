@@ -0,0 +1,202 @@
+use std::fmt;
+
+use openssl::{
+    error::ErrorStack,
+    rand::rand_bytes,
+    sha::sha256,
+    symm::{decrypt_aead, encrypt_aead, Cipher},
+};
+use snafu::Snafu;
+
+/// Record metadata bit that marks a record payload as encrypted.
+///
+/// The metadata of a record is otherwise entirely defined by `Encodable::Metadata`, so we reserve
+/// the highest bit for ourselves. Records written before encryption was enabled do not have this
+/// bit set, which lets us keep reading them after encryption is turned on.
+pub const ENCRYPTED_RECORD_FLAG: u32 = 1 << 31;
+
+const KEY_ID_LEN: usize = 4;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// Number of bytes that encryption adds to a record payload.
+///
+/// An encrypted payload is laid out as `key_id || nonce || ciphertext || tag`, where the ciphertext
+/// is the same length as the plaintext.
+pub const ENCRYPTION_OVERHEAD: usize = KEY_ID_LEN + NONCE_LEN + TAG_LEN;
+
+/// Error that occurred while decrypting a record payload.
+#[derive(Debug, Snafu)]
+pub enum DecryptError {
+    /// The payload was too short to hold the encryption envelope.
+    #[snafu(display("encrypted payload too short ({} bytes)", len))]
+    Truncated { len: usize },
+
+    /// The payload was encrypted with a key that is not configured.
+    #[snafu(display("record was encrypted with an unknown key (key ID: {:02x?})", key_id))]
+    UnknownKey { key_id: [u8; KEY_ID_LEN] },
+
+    /// The payload failed authentication, or could otherwise not be decrypted.
+    #[snafu(display("failed to decrypt record: {}", source))]
+    Cipher { source: ErrorStack },
+}
+
+struct Key {
+    id: [u8; KEY_ID_LEN],
+    material: [u8; 32],
+}
+
+impl Key {
+    fn derive(secret: &str) -> Self {
+        // Keys are derived from arbitrary secret strings so that they can come straight from a
+        // secrets backend without requiring a particular encoding.
+        let material = sha256(secret.as_bytes());
+        let mut id = [0; KEY_ID_LEN];
+        id.copy_from_slice(&sha256(&material)[..KEY_ID_LEN]);
+
+        Self { id, material }
+    }
+}
+
+/// Encrypts and decrypts disk buffer record payloads with AES-256-GCM.
+///
+/// New records are always encrypted with the active key, while any of the configured keys can be
+/// used to decrypt records, which allows rotating keys without losing records that are still
+/// sitting in the buffer.
+pub struct RecordCipher {
+    active: Key,
+    previous: Vec<Key>,
+}
+
+impl RecordCipher {
+    /// Creates a new `RecordCipher` from the active key, and any previously active keys.
+    pub fn new<S: AsRef<str>>(active: &str, previous: &[S]) -> Self {
+        Self {
+            active: Key::derive(active),
+            previous: previous.iter().map(|s| Key::derive(s.as_ref())).collect(),
+        }
+    }
+
+    /// Encrypts `plaintext` with the active key, writing the encrypted payload to `dst`.
+    ///
+    /// # Errors
+    ///
+    /// If the underlying cipher fails, an error is returned.
+    pub fn encrypt(&self, plaintext: &[u8], dst: &mut Vec<u8>) -> Result<(), ErrorStack> {
+        let mut nonce = [0; NONCE_LEN];
+        rand_bytes(&mut nonce)?;
+
+        let mut tag = [0; TAG_LEN];
+        let ciphertext = encrypt_aead(
+            Cipher::aes_256_gcm(),
+            &self.active.material,
+            Some(&nonce),
+            &self.active.id,
+            plaintext,
+            &mut tag,
+        )?;
+
+        dst.clear();
+        dst.reserve(ENCRYPTION_OVERHEAD + ciphertext.len());
+        dst.extend_from_slice(&self.active.id);
+        dst.extend_from_slice(&nonce);
+        dst.extend_from_slice(&ciphertext);
+        dst.extend_from_slice(&tag);
+        Ok(())
+    }
+
+    /// Decrypts an encrypted payload.
+    ///
+    /// # Errors
+    ///
+    /// If the payload is malformed, was encrypted with a key we don't know about, or fails to
+    /// authenticate, an error variant will be returned describing the error.
+    pub fn decrypt(&self, payload: &[u8]) -> Result<Vec<u8>, DecryptError> {
+        if payload.len() < ENCRYPTION_OVERHEAD {
+            return Err(DecryptError::Truncated { len: payload.len() });
+        }
+
+        let (key_id, rest) = payload.split_at(KEY_ID_LEN);
+        let (nonce, rest) = rest.split_at(NONCE_LEN);
+        let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+
+        let key = std::iter::once(&self.active)
+            .chain(self.previous.iter())
+            .find(|key| key.id == key_id)
+            .ok_or_else(|| DecryptError::UnknownKey {
+                key_id: key_id.try_into().expect("key ID length is fixed"),
+            })?;
+
+        decrypt_aead(
+            Cipher::aes_256_gcm(),
+            &key.material,
+            Some(nonce),
+            &key.id,
+            ciphertext,
+            tag,
+        )
+        .map_err(|source| DecryptError::Cipher { source })
+    }
+}
+
+impl fmt::Debug for RecordCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordCipher")
+            .field("active_key_id", &self.active.id)
+            .field("previous_keys", &self.previous.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecryptError, RecordCipher, ENCRYPTION_OVERHEAD};
+
+    #[test]
+    fn roundtrip() {
+        let cipher = RecordCipher::new::<&str>("hunter2", &[]);
+
+        let mut encrypted = Vec::new();
+        cipher.encrypt(b"some record", &mut encrypted).unwrap();
+        assert_eq!(encrypted.len(), b"some record".len() + ENCRYPTION_OVERHEAD);
+        assert!(!encrypted
+            .windows(b"some record".len())
+            .any(|w| w == b"some record"));
+
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), b"some record");
+    }
+
+    #[test]
+    fn decrypts_with_previous_keys() {
+        let old = RecordCipher::new::<&str>("old key", &[]);
+        let mut encrypted = Vec::new();
+        old.encrypt(b"some record", &mut encrypted).unwrap();
+
+        let rotated = RecordCipher::new("new key", &["old key"]);
+        assert_eq!(rotated.decrypt(&encrypted).unwrap(), b"some record");
+
+        let unrelated = RecordCipher::new::<&str>("new key", &[]);
+        assert!(matches!(
+            unrelated.decrypt(&encrypted),
+            Err(DecryptError::UnknownKey { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_tampered_payloads() {
+        let cipher = RecordCipher::new::<&str>("hunter2", &[]);
+        let mut encrypted = Vec::new();
+        cipher.encrypt(b"some record", &mut encrypted).unwrap();
+
+        let last = encrypted.len() - 1;
+        encrypted[last] ^= 0xFF;
+        assert!(matches!(
+            cipher.decrypt(&encrypted),
+            Err(DecryptError::Cipher { .. })
+        ));
+        assert!(matches!(
+            cipher.decrypt(&encrypted[..8]),
+            Err(DecryptError::Truncated { len: 8 })
+        ));
+    }
+}
@@ -150,6 +150,7 @@ use vector_common::finalization::Finalizable;
 
 mod backed_archive;
 mod common;
+mod encryption;
 mod io;
 mod ledger;
 mod reader;
@@ -163,6 +164,7 @@ mod tests;
 use self::ledger::Ledger;
 pub use self::{
     common::{DiskBufferConfig, DiskBufferConfigBuilder},
+    encryption::{DecryptError, RecordCipher},
     io::{Filesystem, ProductionFilesystem},
     ledger::LedgerLoadCreateError,
     reader::{Reader, ReaderError},
@@ -267,6 +269,7 @@ pub struct DiskV2Buffer {
     id: String,
     data_dir: PathBuf,
     max_size: NonZeroU64,
+    encryption: Option<RecordCipher>,
 }
 
 impl DiskV2Buffer {
@@ -275,8 +278,16 @@ impl DiskV2Buffer {
             id,
             data_dir,
             max_size,
+            encryption: None,
         }
     }
+
+    /// Sets the cipher used to encrypt records written to the buffer.
+    #[must_use]
+    pub fn with_encryption(mut self, encryption: Option<RecordCipher>) -> Self {
+        self.encryption = encryption;
+        self
+    }
 }
 
 #[async_trait]
@@ -297,6 +308,7 @@ where
             &self.data_dir,
             self.id.as_str(),
             self.max_size,
+            self.encryption,
         )
         .await?;
 
@@ -309,6 +321,7 @@ async fn build_disk_v2_buffer<T>(
     data_dir: &Path,
     id: &str,
     max_size: NonZeroU64,
+    encryption: Option<RecordCipher>,
 ) -> Result<
     (
         Writer<T, ProductionFilesystem>,
@@ -324,6 +337,7 @@ where
     let buffer_path = get_disk_v2_data_dir_path(data_dir, id);
     let config = DiskBufferConfigBuilder::from_path(buffer_path)
         .max_buffer_size(max_size.get())
+        .encryption(encryption)
         .build()?;
     Buffer::from_config(config, usage_handle)
         .await
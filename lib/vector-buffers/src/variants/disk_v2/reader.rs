@@ -15,6 +15,7 @@ use vector_common::{finalization::BatchNotifier, finalizer::OrderedFinalizer};
 
 use super::{
     common::create_crc32c_hasher,
+    encryption::{DecryptError, RecordCipher, ENCRYPTED_RECORD_FLAG},
     ledger::Ledger,
     record::{validate_record_archive, ArchivedRecord, Record, RecordStatus},
     Filesystem,
@@ -108,6 +109,13 @@ where
     #[snafu(display("record version not compatible: {}", reason))]
     Incompatible { reason: String },
 
+    /// The record could not be decrypted.
+    ///
+    /// This can occur when the key used to encrypt a record is no longer configured, or if the
+    /// encrypted payload was tampered with after being written.
+    #[snafu(display("failed to decrypt record: {}", source))]
+    Decrypt { source: DecryptError },
+
     /// The reader detected that a data file contains a partially-written record.
     ///
     /// Records should never be partially written to a data file (we don't split records across data
@@ -145,6 +153,7 @@ where
             ReaderError::Checksum { .. } => "checksum_mismatch",
             ReaderError::Decode { .. } => "decode_failed",
             ReaderError::Incompatible { .. } => "incompatible_record_version",
+            ReaderError::Decrypt { .. } => "decrypt_failed",
             ReaderError::PartialWrite => "partial_write",
             ReaderError::EmptyRecord => "empty_record",
         }
@@ -160,6 +169,7 @@ where
             | ReaderError::Checksum { .. }
             | ReaderError::Decode { .. }
             | ReaderError::Incompatible { .. }
+            | ReaderError::Decrypt { .. }
             | ReaderError::PartialWrite => Some(BufferReadError { error_code, error }),
         }
     }
@@ -200,6 +210,7 @@ pub(super) struct RecordReader<R, T> {
     aligned_buf: AlignedVec,
     checksummer: Hasher,
     current_record_id: u64,
+    encryption: Option<Arc<RecordCipher>>,
     _t: PhantomData<T>,
 }
 
@@ -218,10 +229,18 @@ where
             aligned_buf: AlignedVec::new(),
             checksummer: create_crc32c_hasher(),
             current_record_id: 0,
+            encryption: None,
             _t: PhantomData,
         }
     }
 
+    /// Sets the cipher used to decrypt encrypted records.
+    #[must_use]
+    pub fn with_encryption(mut self, encryption: Option<Arc<RecordCipher>>) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
     #[cfg_attr(test, instrument(skip(self), level = "trace"))]
     async fn read_length_delimiter(
         &mut self,
@@ -376,7 +395,7 @@ where
         // - `try_next_record` does all the archive checks, checksum validation, etc
         let record = unsafe { archived_root::<Record<'_>>(&self.aligned_buf) };
 
-        decode_record_payload(record)
+        decode_record_payload(record, self.encryption.as_deref())
     }
 }
 
@@ -390,6 +409,7 @@ where
             .field("aligned_buf", &self.aligned_buf)
             .field("checksummer", &self.checksummer)
             .field("current_record_id", &self.current_record_id)
+            .field("encryption", &self.encryption)
             .finish()
     }
 }
@@ -787,7 +807,10 @@ where
                 "Opened data file for reading."
             );
 
-            self.reader = Some(RecordReader::new(data_file));
+            self.reader = Some(
+                RecordReader::new(data_file)
+                    .with_encryption(self.ledger.config().encryption.clone()),
+            );
             return Ok(());
         }
     }
@@ -852,7 +875,10 @@ where
                 } => {
                     let record = try_as_record_archive(data_file_mmap.as_ref())
                         .expect("record was already validated");
-                    let item = match decode_record_payload::<T>(record) {
+                    let item = match decode_record_payload::<T>(
+                        record,
+                        self.ledger.config().encryption.as_deref(),
+                    ) {
                         Ok(item) => item,
                         // If there's an error decoding the item, just fall back to the slow path,
                         // because this file might actually be where we left off, so we don't want
@@ -1104,10 +1130,18 @@ where
 
 pub(crate) fn decode_record_payload<T: Bufferable>(
     record: &ArchivedRecord<'_>,
+    encryption: Option<&RecordCipher>,
 ) -> Result<T, ReaderError<T>> {
+    // Encrypted records are marked with a reserved metadata bit, which we have to strip before
+    // handing the metadata to `T`.  Records without the bit set were written before encryption was
+    // enabled, and are decoded as-is.
+    let raw_metadata = record.metadata();
+    let is_encrypted = raw_metadata & ENCRYPTED_RECORD_FLAG != 0;
+    let raw_metadata = raw_metadata & !ENCRYPTED_RECORD_FLAG;
+
     // Try and convert the raw record metadata into the true metadata type used by `T`, and then
     // also verify that `T` is able to decode records with the metadata used for this record in particular.
-    let metadata = T::Metadata::from_u32(record.metadata()).ok_or(ReaderError::Incompatible {
+    let metadata = T::Metadata::from_u32(raw_metadata).ok_or(ReaderError::Incompatible {
         reason: format!("invalid metadata for {}", std::any::type_name::<T>()),
     })?;
 
@@ -1115,11 +1149,20 @@ pub(crate) fn decode_record_payload<T: Bufferable>(
         return Err(ReaderError::Incompatible {
             reason: format!(
                 "record metadata not supported (metadata: {:#036b})",
-                record.metadata()
+                raw_metadata
             ),
         });
     }
 
-    // Now we can finally try decoding.
-    T::decode(metadata, record.payload()).context(DecodeSnafu)
+    if !is_encrypted {
+        // Now we can finally try decoding.
+        return T::decode(metadata, record.payload()).context(DecodeSnafu);
+    }
+
+    let cipher = encryption.ok_or_else(|| ReaderError::Incompatible {
+        reason: "record is encrypted but no encryption key is configured".to_string(),
+    })?;
+    let payload = cipher.decrypt(record.payload()).context(DecryptSnafu)?;
+
+    T::decode(metadata, &payload[..]).context(DecodeSnafu)
 }
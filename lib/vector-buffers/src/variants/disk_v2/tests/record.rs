@@ -1,8 +1,12 @@
-use std::io::Cursor;
+use std::{io::Cursor, sync::Arc};
 
 use crate::{
     test::SizedRecord,
-    variants::disk_v2::{reader::RecordReader, writer::RecordWriter},
+    variants::disk_v2::{
+        reader::{ReaderError, RecordReader},
+        writer::RecordWriter,
+        RecordCipher,
+    },
 };
 
 #[tokio::test]
@@ -49,3 +53,58 @@ async fn record_reader_always_returns_none_when_no_data() {
         .expect("read should not fail");
     assert!(read_token.is_none());
 }
+
+#[tokio::test]
+async fn roundtrip_through_encrypted_record_writer_and_record_reader() {
+    let (writer_io, reader_io) = tokio::io::duplex(4096);
+
+    let cipher = Arc::new(RecordCipher::new("current", &["previous"]));
+    let mut record_writer = RecordWriter::new(writer_io, 0, 16_384, u64::MAX, 2048)
+        .with_encryption(Some(Arc::clone(&cipher)));
+    let mut record_reader = RecordReader::new(reader_io).with_encryption(Some(cipher));
+
+    let record = SizedRecord::new(73);
+
+    record_writer
+        .write_record(314, record.clone())
+        .await
+        .expect("write should not fail");
+    record_writer.flush().await.expect("flush should not fail");
+
+    let read_token = record_reader
+        .try_next_record(false)
+        .await
+        .expect("read should not fail")
+        .expect("record should be available");
+    assert_eq!(314, read_token.record_id());
+
+    let roundtrip_record = record_reader
+        .read_record(read_token)
+        .expect("read should not fail");
+    assert_eq!(record, roundtrip_record);
+}
+
+#[tokio::test]
+async fn encrypted_record_cannot_be_read_without_key() {
+    let (writer_io, reader_io) = tokio::io::duplex(4096);
+
+    let cipher = Arc::new(RecordCipher::new::<&str>("current", &[]));
+    let mut record_writer =
+        RecordWriter::new(writer_io, 0, 16_384, u64::MAX, 2048).with_encryption(Some(cipher));
+    let mut record_reader = RecordReader::<_, SizedRecord>::new(reader_io);
+
+    record_writer
+        .write_record(314, SizedRecord::new(73))
+        .await
+        .expect("write should not fail");
+    record_writer.flush().await.expect("flush should not fail");
+
+    let read_token = record_reader
+        .try_next_record(false)
+        .await
+        .expect("read should not fail")
+        .expect("record should be available");
+
+    let result = record_reader.read_record(read_token);
+    assert!(matches!(result, Err(ReaderError::Incompatible { .. })));
+}
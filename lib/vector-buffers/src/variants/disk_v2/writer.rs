@@ -25,6 +25,7 @@ use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use super::{
     common::{create_crc32c_hasher, DiskBufferConfig},
+    encryption::{RecordCipher, ENCRYPTED_RECORD_FLAG, ENCRYPTION_OVERHEAD},
     io::Filesystem,
     ledger::Ledger,
     record::{validate_record_archive, Record, RecordStatus},
@@ -107,6 +108,13 @@ where
     #[snafu(display("failed to serialize encoded record to buffer: {}", reason))]
     FailedToSerialize { reason: String },
 
+    /// The writer failed to encrypt the record.
+    ///
+    /// This should only occur if the underlying cryptographic library is unable to generate a
+    /// nonce, or is otherwise in a broken state.
+    #[snafu(display("failed to encrypt encoded record: {}", source))]
+    FailedToEncrypt { source: openssl::error::ErrorStack },
+
     /// The writer failed to validate the last written record.
     ///
     /// Specifically, for `Writer`, this can only ever be returned when creating the buffer, during
@@ -360,12 +368,14 @@ impl<W: fmt::Debug> fmt::Debug for TrackingBufWriter<W> {
 pub(super) struct RecordWriter<W, T> {
     writer: TrackingBufWriter<W>,
     encode_buf: Vec<u8>,
+    encrypt_buf: Vec<u8>,
     ser_buf: AlignedVec,
     ser_scratch: AlignedVec,
     checksummer: Hasher,
     max_record_size: usize,
     current_data_file_size: u64,
     max_data_file_size: u64,
+    encryption: Option<Arc<RecordCipher>>,
     _t: PhantomData<T>,
 }
 
@@ -410,16 +420,31 @@ where
         Self {
             writer: TrackingBufWriter::with_capacity(write_buffer_size, writer),
             encode_buf: Vec::with_capacity(16_384),
+            encrypt_buf: Vec::new(),
             ser_buf: AlignedVec::with_capacity(16_384),
             ser_scratch: AlignedVec::with_capacity(16_384),
             checksummer: create_crc32c_hasher(),
             max_record_size,
             current_data_file_size,
             max_data_file_size,
+            encryption: None,
             _t: PhantomData,
         }
     }
 
+    /// Sets the cipher used to encrypt records before they are written.
+    ///
+    /// As encryption adds a fixed amount of overhead to every record, the maximum size of an
+    /// encoded record is reduced accordingly.
+    #[must_use]
+    pub fn with_encryption(mut self, encryption: Option<Arc<RecordCipher>>) -> Self {
+        if encryption.is_some() {
+            self.max_record_size = self.max_record_size.saturating_sub(ENCRYPTION_OVERHEAD);
+        }
+        self.encryption = encryption;
+        self
+    }
+
     /// Gets a reference to the underlying writer.
     #[cfg(test)]
     pub fn get_ref(&self) -> &W {
@@ -480,9 +505,27 @@ where
             });
         }
 
-        let metadata = T::get_metadata().into_u32();
-        let wrapped_record =
-            Record::with_checksum(id, metadata, &self.encode_buf, &self.checksummer);
+        let mut metadata = T::get_metadata().into_u32();
+        debug_assert_eq!(
+            metadata & ENCRYPTED_RECORD_FLAG,
+            0,
+            "record metadata must not use the bit reserved for encryption"
+        );
+
+        // If encryption is enabled, the payload we actually store is the encrypted form of the
+        // encoded record, and we mark the record as such so that the reader knows to decrypt it.
+        let payload = match &self.encryption {
+            Some(cipher) => {
+                cipher
+                    .encrypt(&self.encode_buf, &mut self.encrypt_buf)
+                    .context(FailedToEncryptSnafu)?;
+                metadata |= ENCRYPTED_RECORD_FLAG;
+                &self.encrypt_buf[..]
+            }
+            None => &self.encode_buf[..],
+        };
+
+        let wrapped_record = Record::with_checksum(id, metadata, payload, &self.checksummer);
 
         // Push 8 dummy bytes where our length delimiter will sit.  We'll fix this up after
         // serialization.  Notably, `AlignedSerializer` will report the serializer position as
@@ -666,14 +709,7 @@ where
         })?;
 
         // Now we can actually decode it as `T`.
-        let record_metadata = T::Metadata::from_u32(wrapped_record.metadata()).ok_or(
-            WriterError::InconsistentState {
-                reason: "failed to decode record metadata immediately after encoding it"
-                    .to_string(),
-            },
-        )?;
-
-        T::decode(record_metadata, wrapped_record.payload()).map_err(|_| {
+        decode_record_payload(wrapped_record, self.encryption.as_deref()).map_err(|_| {
             WriterError::InconsistentState {
                 reason: "failed to decode record immediately after encoding it".to_string(),
             }
@@ -882,11 +918,11 @@ where
                 // next writer record ID should be.
                 let record = try_as_record_archive(data_file_mmap.as_ref())
                     .expect("record was already validated");
-                let item = decode_record_payload::<T>(record).map_err(|e| {
-                    WriterError::FailedToValidate {
-                        reason: e.to_string(),
-                    }
-                })?;
+                let item =
+                    decode_record_payload::<T>(record, self.ledger.config().encryption.as_deref())
+                        .map_err(|e| WriterError::FailedToValidate {
+                            reason: e.to_string(),
+                        })?;
 
                 // Since we have a valid record, checksum and all, see if the writer record ID
                 // in the ledger lines up with the record ID we have here.  Specifically, the record
@@ -1120,13 +1156,16 @@ where
                 // Make sure the file is flushed to disk, especially if we just created it.
                 data_file.sync_all().await?;
 
-                self.writer = Some(RecordWriter::new(
-                    data_file,
-                    data_file_size,
-                    self.config.write_buffer_size,
-                    self.config.max_data_file_size,
-                    self.config.max_record_size,
-                ));
+                self.writer = Some(
+                    RecordWriter::new(
+                        data_file,
+                        data_file_size,
+                        self.config.write_buffer_size,
+                        self.config.max_data_file_size,
+                        self.config.max_record_size,
+                    )
+                    .with_encryption(self.config.encryption.clone()),
+                );
                 self.data_file_size = data_file_size;
 
                 // If we opened the "next" data file, we need to increment the current writer
@@ -5,7 +5,7 @@ use toml::Value;
 use vector_config_common::attributes::CustomAttribute;
 
 use super::{ComponentMarker, GenerateConfig};
-use crate::schema::{SchemaGenerator, SchemaObject};
+use crate::schema::{RootSchema, SchemaGenerator, SchemaObject, SchemaSettings};
 use crate::{schema, Configurable, ConfigurableRef, GenerateError, Metadata};
 
 #[derive(Debug, Snafu, Clone, PartialEq, Eq)]
@@ -75,6 +75,28 @@ where
             .and_then(|t| (t.example_value)().ok_or(ExampleError::MissingExample))
     }
 
+    /// Generates the schema of the configuration of the component with the given component name.
+    ///
+    /// The schema includes the definitions it refers to. `None` is returned if no component,
+    /// identified by `T` and the given name, is registered.
+    ///
+    /// ## Errors
+    ///
+    /// If there is an error generating the schema, an error variant will be returned.
+    pub fn schema(component_name: &str) -> Result<Option<RootSchema>, GenerateError> {
+        let description = match inventory::iter::<ComponentDescription<T>>
+            .into_iter()
+            .find(|t| t.component_name == component_name)
+        {
+            Some(description) => description,
+            None => return Ok(None),
+        };
+
+        let gen = RefCell::new(SchemaSettings::new().into_generator());
+        let schema = description.generate_schema(&gen)?;
+        Ok(Some(gen.into_inner().into_root_schema(schema)))
+    }
+
     /// Gets a sorted list of all registered components of the given component type.
     pub fn types() -> Vec<&'static str> {
         let mut types = Vec::new();
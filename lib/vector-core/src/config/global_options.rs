@@ -1,11 +1,12 @@
 use std::{fs::DirBuilder, path::PathBuf, time::Duration};
 
+use indexmap::IndexMap;
 use snafu::{ResultExt, Snafu};
-use vector_common::TimeZone;
+use vector_common::{sensitive_string::SensitiveString, TimeZone};
 use vector_config::configurable_component;
 
 use super::super::default_data_dir;
-use super::{proxy::ProxyConfig, AcknowledgementsConfig, LogSchema};
+use super::{proxy::ProxyConfig, AcknowledgementsConfig, ComponentKey, LogSchema};
 use crate::serde::bool_or_struct;
 
 #[derive(Debug, Snafu)]
@@ -115,6 +116,250 @@ pub struct GlobalOptions {
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
     )]
     pub expire_metrics_secs: Option<f64>,
+
+    /// The sink to send dropped events to by default.
+    ///
+    /// Any transform that routes dropped events to its `dropped` output will have that output
+    /// automatically connected to this sink, unless the transform configures its own dead letter
+    /// queue. Sinks send the events diverted by the `dead_letter_queue` fallback of their circuit
+    /// breaker and maximum event age to this sink, unless those configure their own.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    #[configurable(metadata(docs::examples = "dead_letters"))]
+    pub dead_letter_queue: Option<ComponentKey>,
+
+    /// Named rate limits shared by groups of sinks.
+    ///
+    /// Sinks join a group with their `rate_limit_group` option, and then share a single budget of
+    /// requests and bytes, on top of their own `request` rate limits. This is useful to prevent
+    /// several sinks sending to the same service from overloading it together.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    #[configurable(metadata(docs::additional_props_description = "A rate limit group."))]
+    pub rate_limit_groups: IndexMap<String, RateLimitGroup>,
+
+    /// Event tracing configuration.
+    ///
+    /// When enabled, a sample of the events emitted by sources are followed through the topology,
+    /// recording each component that receives them, so that it is possible to find out where an
+    /// event was dropped or modified.
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub event_tracing: EventTracingConfig,
+
+    /// Whether or not to measure the end-to-end latency of events.
+    ///
+    /// When enabled, the time from when a source emits an event to when a sink acknowledges it is
+    /// recorded in the `end_to_end_latency_seconds` internal metric, for each pair of source and
+    /// sink. Events going through a disk buffer aren't measured.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub end_to_end_latency: bool,
+
+    /// Self-telemetry configuration.
+    ///
+    /// When enabled, Vector exports its own internal metrics, and the spans of its components, such
+    /// as the requests made by sinks, directly to an OpenTelemetry collector, without going through
+    /// the configured topology.
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub self_telemetry: SelfTelemetryConfig,
+
+    /// Audit log configuration.
+    ///
+    /// When set, control-plane actions, such as config reloads, API mutations, tap sessions and
+    /// drain requests, are appended to a tamper-evident audit log.
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub audit_log: Option<AuditLogConfig>,
+}
+
+/// Audit log configuration.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AuditLogConfig {
+    /// The path of the file the audit log is appended to.
+    ///
+    /// Each action is written as a JSON object on its own line, which includes the SHA-256 hash of
+    /// the previous entry, so that modifying or removing an entry breaks the chain of hashes. The
+    /// chain can be checked with `vector audit verify`.
+    #[configurable(metadata(docs::examples = "/var/log/vector/audit.log"))]
+    pub path: PathBuf,
+
+    /// The key used to authenticate the entries of the audit log.
+    ///
+    /// When set, the hash of each entry is an HMAC-SHA256 keyed with it, so that entries can't be
+    /// forged or rewritten without the key, which `vector audit verify` then needs. It can be
+    /// loaded from a secret backend with `SECRET[<backend>.<key>]`. A log must either be keyed
+    /// from its first entry, or not at all.
+    #[configurable(metadata(docs::examples = "${AUDIT_LOG_KEY}"))]
+    #[serde(default)]
+    pub key: Option<SensitiveString>,
+
+    /// Whether or not each entry is also emitted as an internal log event.
+    ///
+    /// The events can be collected with the `internal_logs` source and sent to any sink, to keep a
+    /// copy of the sequence numbers and hashes of the entries outside of this host. Removing entries
+    /// from the end of the log is detected by passing the last hash seen there to
+    /// `vector audit verify --anchor`.
+    #[serde(default)]
+    pub internal_events: bool,
+}
+
+/// Self-telemetry configuration.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SelfTelemetryConfig {
+    /// Whether or not self-telemetry is exported.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// The base URL of the OTLP/HTTP endpoint of the collector.
+    ///
+    /// Metrics are sent to `<endpoint>/v1/metrics`, and traces to `<endpoint>/v1/traces`, encoded
+    /// as JSON.
+    #[serde(default = "default_self_telemetry_endpoint")]
+    #[configurable(metadata(docs::examples = "http://otel-collector:4318"))]
+    pub endpoint: String,
+
+    /// How often telemetry is exported, in seconds.
+    #[serde(default = "default_self_telemetry_interval_secs")]
+    pub interval_secs: u64,
+
+    /// Whether or not internal metrics are exported.
+    #[serde(default = "default_true")]
+    pub metrics: bool,
+
+    /// Whether or not the spans of components are exported.
+    #[serde(default = "default_true")]
+    pub traces: bool,
+
+    /// Additional HTTP headers sent with each export, such as for authentication.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "An HTTP header.",
+        docs::examples = "example_headers()"
+    ))]
+    pub headers: IndexMap<String, String>,
+}
+
+impl Default for SelfTelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: default_self_telemetry_endpoint(),
+            interval_secs: default_self_telemetry_interval_secs(),
+            metrics: true,
+            traces: true,
+            headers: IndexMap::new(),
+        }
+    }
+}
+
+fn default_self_telemetry_endpoint() -> String {
+    "http://localhost:4318".to_string()
+}
+
+const fn default_self_telemetry_interval_secs() -> u64 {
+    10
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+fn example_headers() -> IndexMap<String, String> {
+    IndexMap::from([(
+        "Authorization".to_string(),
+        "Bearer ${OTEL_TOKEN}".to_string(),
+    )])
+}
+
+/// Event tracing configuration.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EventTracingConfig {
+    /// Whether or not event tracing is enabled.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// The rate at which events emitted by sources are sampled for tracing.
+    ///
+    /// For example, a rate of `1000` traces one out of every 1000 events.
+    #[serde(default = "default_event_tracing_sample_rate")]
+    pub sample_rate: u64,
+
+    /// The maximum number of traces to keep.
+    ///
+    /// Once this many traces are recorded, the oldest trace is discarded for each new one.
+    #[serde(default = "default_event_tracing_max_traces")]
+    pub max_traces: usize,
+}
+
+impl Default for EventTracingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sample_rate: default_event_tracing_sample_rate(),
+            max_traces: default_event_tracing_max_traces(),
+        }
+    }
+}
+
+const fn default_event_tracing_sample_rate() -> u64 {
+    1000
+}
+
+const fn default_event_tracing_max_traces() -> usize {
+    1000
+}
+
+/// A rate limit shared by a group of sinks.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitGroup {
+    /// The time window used for the `request_limit` and `byte_limit` options.
+    #[serde(default = "default_rate_limit_group_duration_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub duration_secs: u64,
+
+    /// The maximum number of requests allowed within the `duration_secs` time window, across all
+    /// sinks of the group.
+    ///
+    /// A sink that doesn't support request limits fails to build when it is part of a group with
+    /// a request limit.
+    #[configurable(metadata(docs::type_unit = "requests"))]
+    pub request_limit: Option<u64>,
+
+    /// The maximum number of bytes allowed within the `duration_secs` time window, across all sinks
+    /// of the group.
+    ///
+    /// Bytes are measured as the estimated JSON-encoded size of the events sent to the sinks.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub byte_limit: Option<u64>,
+}
+
+const fn default_rate_limit_group_duration_secs() -> u64 {
+    1
 }
 
 impl GlobalOptions {
@@ -200,6 +445,48 @@ impl GlobalOptions {
             errors.push("conflicting values for 'acknowledgements' found".to_owned());
         }
 
+        if conflicts(&self.dead_letter_queue, &with.dead_letter_queue) {
+            errors.push("conflicting values for 'dead_letter_queue' found".to_owned());
+        }
+
+        if conflicts(&self.audit_log, &with.audit_log) {
+            errors.push("conflicting values for 'audit_log' found".to_owned());
+        }
+
+        let mut rate_limit_groups = self.rate_limit_groups.clone();
+        for (name, group) in with.rate_limit_groups {
+            if conflicts(&rate_limit_groups.get(&name), &Some(&group)) {
+                errors.push(format!(
+                    "conflicting values for 'rate_limit_groups.{}' found",
+                    name
+                ));
+            } else {
+                rate_limit_groups.insert(name, group);
+            }
+        }
+
+        let event_tracing = if self.event_tracing == EventTracingConfig::default() {
+            with.event_tracing
+        } else {
+            if with.event_tracing != EventTracingConfig::default()
+                && self.event_tracing != with.event_tracing
+            {
+                errors.push("conflicting values for 'event_tracing' found".to_owned());
+            }
+            self.event_tracing.clone()
+        };
+
+        let self_telemetry = if self.self_telemetry == SelfTelemetryConfig::default() {
+            with.self_telemetry
+        } else {
+            if with.self_telemetry != SelfTelemetryConfig::default()
+                && self.self_telemetry != with.self_telemetry
+            {
+                errors.push("conflicting values for 'self_telemetry' found".to_owned());
+            }
+            self.self_telemetry.clone()
+        };
+
         let data_dir = if self.data_dir.is_none() || self.data_dir == default_data_dir() {
             with.data_dir
         } else if with.data_dir != default_data_dir() && self.data_dir != with.data_dir {
@@ -227,6 +514,12 @@ impl GlobalOptions {
                 proxy: self.proxy.merge(&with.proxy),
                 expire_metrics: self.expire_metrics.or(with.expire_metrics),
                 expire_metrics_secs: self.expire_metrics_secs.or(with.expire_metrics_secs),
+                dead_letter_queue: self.dead_letter_queue.clone().or(with.dead_letter_queue),
+                rate_limit_groups,
+                event_tracing,
+                end_to_end_latency: self.end_to_end_latency || with.end_to_end_latency,
+                self_telemetry,
+                audit_log: self.audit_log.clone().or(with.audit_log),
             })
         } else {
             Err(errors)
@@ -350,6 +643,46 @@ mod tests {
         assert_eq!(merge(Some(4.0), Some(5.0)), Ok(Some(4.0))); // Uses minimum
     }
 
+    #[test]
+    fn merges_dead_letter_queue() {
+        let merge = |a, b| merge("dead_letter_queue", a, b, |result| result.dead_letter_queue);
+
+        assert_eq!(merge(None::<&str>, None), Ok(None));
+        assert_eq!(merge(Some("dlq"), None), Ok(Some("dlq".into())));
+        assert_eq!(merge(None, Some("dlq")), Ok(Some("dlq".into())));
+        assert_eq!(merge(Some("dlq"), Some("dlq")), Ok(Some("dlq".into())));
+        assert_eq!(
+            merge(Some("dlq1"), Some("dlq2")),
+            Err(vec![
+                "conflicting values for 'dead_letter_queue' found".into()
+            ])
+        );
+    }
+
+    #[test]
+    fn merges_rate_limit_groups() {
+        let config = |s: &str| toml::from_str::<GlobalOptions>(s).unwrap();
+
+        let merged = config("rate_limit_groups.es = { request_limit = 10 }")
+            .merge(config(
+                "rate_limit_groups.es = { request_limit = 10 }\nrate_limit_groups.s3 = { byte_limit = 1000 }",
+            ))
+            .unwrap();
+        assert_eq!(
+            merged.rate_limit_groups.keys().collect::<Vec<_>>(),
+            vec!["es", "s3"]
+        );
+        assert_eq!(merged.rate_limit_groups["es"].duration_secs, 1);
+
+        assert_eq!(
+            config("rate_limit_groups.es = { request_limit = 10 }")
+                .merge(config("rate_limit_groups.es = { request_limit = 20 }")),
+            Err(vec![
+                "conflicting values for 'rate_limit_groups.es' found".into()
+            ])
+        );
+    }
+
     fn merge<P: Debug, T>(
         name: &str,
         dd1: Option<P>,
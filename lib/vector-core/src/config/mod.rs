@@ -9,7 +9,9 @@ mod log_schema;
 pub mod proxy;
 
 use crate::event::LogEvent;
-pub use global_options::GlobalOptions;
+pub use global_options::{
+    AuditLogConfig, EventTracingConfig, GlobalOptions, RateLimitGroup, SelfTelemetryConfig,
+};
 pub use log_schema::{init_log_schema, log_schema, LogSchema};
 use lookup::{lookup_v2::ValuePath, path, PathPrefix};
 use serde::{Deserialize, Serialize};
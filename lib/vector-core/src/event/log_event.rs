@@ -13,6 +13,7 @@ use std::{
 use crossbeam_utils::atomic::AtomicCell;
 use lookup::lookup_v2::TargetPath;
 use lookup::PathPrefix;
+use ordered_float::NotNan;
 use serde::{Deserialize, Serialize, Serializer};
 use vector_common::EventDataEq;
 
@@ -686,6 +687,14 @@ impl tracing::field::Visit for LogEvent {
     fn record_bool(&mut self, field: &tracing::field::Field, value: bool) {
         self.insert(field.name(), value);
     }
+
+    fn record_f64(&mut self, field: &tracing::field::Field, value: f64) {
+        if let Ok(value) = NotNan::new(value) {
+            self.insert(field.name(), value);
+        } else {
+            self.insert(field.name(), value.to_string());
+        }
+    }
 }
 
 #[cfg(test)]
@@ -2,13 +2,14 @@
 
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Instant;
 
 use serde::{Deserialize, Serialize};
 use value::{Kind, Secrets, Value};
 use vector_common::EventDataEq;
 
 use super::{BatchNotifier, EventFinalizer, EventFinalizers, EventStatus};
-use crate::config::LogNamespace;
+use crate::config::{ComponentKey, LogNamespace};
 use crate::{schema, ByteSizeOf};
 
 const DATADOG_API_KEY: &str = "datadog_api_key";
@@ -35,6 +36,15 @@ pub struct EventMetadata {
     /// TODO(Jean): must not skip serialization to track schemas across restarts.
     #[serde(default = "default_schema_definition", skip)]
     schema_definition: Arc<schema::Definition>,
+
+    /// The ID of the trace following this event through the topology, if it was sampled for event
+    /// tracing.
+    #[serde(default, skip)]
+    trace_id: Option<u64>,
+
+    /// The source that emitted this event, and when, if end-to-end latency is measured.
+    #[serde(default, skip)]
+    ingest: Option<(Arc<ComponentKey>, Instant)>,
 }
 
 fn default_metadata_value() -> Value {
@@ -89,6 +99,26 @@ impl EventMetadata {
     pub fn set_splunk_hec_token(&mut self, secret: Arc<str>) {
         self.secrets.insert(SPLUNK_HEC_TOKEN, secret);
     }
+
+    /// Returns the ID of the trace following this event, if it was sampled for event tracing.
+    pub fn trace_id(&self) -> Option<u64> {
+        self.trace_id
+    }
+
+    /// Sets the ID of the trace following this event.
+    pub fn set_trace_id(&mut self, trace_id: u64) {
+        self.trace_id = Some(trace_id);
+    }
+
+    /// Returns the source that emitted this event, and when, if end-to-end latency is measured.
+    pub fn ingest(&self) -> Option<(&Arc<ComponentKey>, Instant)> {
+        self.ingest.as_ref().map(|(source, at)| (source, *at))
+    }
+
+    /// Sets the source that emitted this event, and when.
+    pub fn set_ingest(&mut self, source: Arc<ComponentKey>, at: Instant) {
+        self.ingest = Some((source, at));
+    }
 }
 
 impl Default for EventMetadata {
@@ -98,6 +128,8 @@ impl Default for EventMetadata {
             secrets: Secrets::new(),
             finalizers: Default::default(),
             schema_definition: default_schema_definition(),
+            trace_id: None,
+            ingest: None,
         }
     }
 }
@@ -161,6 +193,8 @@ impl EventMetadata {
     pub fn merge(&mut self, other: Self) {
         self.finalizers.merge(other.finalizers);
         self.secrets.merge(other.secrets);
+        self.trace_id = self.trace_id.or(other.trace_id);
+        self.ingest = self.ingest.take().or(other.ingest);
     }
 
     /// Update the finalizer(s) status.
@@ -0,0 +1,354 @@
+use std::iter;
+
+use super::{MetricSketch, MetricValue};
+use crate::metrics::AgentDDSketch;
+
+/// The lowest scale of Prometheus native histograms.
+const NATIVE_HISTOGRAM_MIN_SCHEMA: i32 = -4;
+
+/// The highest scale of Prometheus native histograms.
+const NATIVE_HISTOGRAM_MAX_SCHEMA: i32 = 8;
+
+/// A histogram with exponentially sized buckets.
+///
+/// This is the data model shared by [OpenTelemetry exponential histograms][otlp] and [Prometheus
+/// native histograms][native]: the boundaries of the buckets are the powers of a base derived from
+/// the scale of the histogram, `base = 2^(2^-scale)`, so that the bucket at index `i` holds the
+/// values in `(base^i, base^(i+1)]`. Values whose magnitude is at most `zero_threshold` are
+/// counted in the zero bucket.
+///
+/// The indexes of the buckets follow the OpenTelemetry convention. Prometheus native histograms,
+/// whose bucket at index `i` holds the values in `(base^(i-1), base^i]`, are converted with
+/// [`ExponentialHistogram::from_native_histogram`] and [`ExponentialBuckets::to_native`].
+///
+/// [otlp]: https://opentelemetry.io/docs/specs/otel/metrics/data-model/#exponentialhistogram
+/// [native]: https://prometheus.io/docs/concepts/metric_types/#histogram
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExponentialHistogram {
+    /// The scale of the histogram, from which the base of the bucket boundaries is derived.
+    pub scale: i32,
+
+    /// The magnitude up to which values are counted in the zero bucket.
+    pub zero_threshold: f64,
+
+    /// The number of values in the zero bucket.
+    pub zero_count: u64,
+
+    /// The buckets of the positive values.
+    pub positive: ExponentialBuckets,
+
+    /// The buckets of the negative values, indexed by the magnitude of the values.
+    pub negative: ExponentialBuckets,
+
+    /// The sum of the values.
+    pub sum: f64,
+}
+
+/// A dense run of buckets of an [`ExponentialHistogram`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExponentialBuckets {
+    /// The index of the first bucket.
+    pub offset: i32,
+
+    /// The number of values in each bucket, starting at the bucket at `offset`.
+    pub counts: Vec<u64>,
+}
+
+/// A span of consecutive buckets of a Prometheus native histogram.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NativeHistogramSpan {
+    /// The gap from the end of the previous span, or the index of the first bucket for the first
+    /// span.
+    pub offset: i32,
+
+    /// The number of consecutive buckets in the span.
+    pub length: u32,
+}
+
+impl ExponentialBuckets {
+    /// Iterates over the index and the count of each bucket.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn iter(&self) -> impl Iterator<Item = (i32, u64)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(i, count)| (self.offset + i as i32, *count))
+    }
+
+    /// The total number of values in the buckets.
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Adds `count` values to the bucket at `index`, growing the run of buckets as needed.
+    pub fn increment(&mut self, index: i32, count: u64) {
+        if self.counts.is_empty() {
+            self.offset = index;
+        } else if index < self.offset {
+            let shift = (self.offset - index) as usize;
+            self.counts.splice(0..0, iter::repeat(0).take(shift));
+            self.offset = index;
+        }
+
+        let position = (index - self.offset) as usize;
+        if position >= self.counts.len() {
+            self.counts.resize(position + 1, 0);
+        }
+        self.counts[position] += count;
+    }
+
+    /// Converts the buckets to the spans and delta-encoded counts of a Prometheus native histogram.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_native(&self) -> (Vec<NativeHistogramSpan>, Vec<i64>) {
+        if self.counts.is_empty() {
+            return (Vec::new(), Vec::new());
+        }
+
+        let span = NativeHistogramSpan {
+            offset: self.offset + 1,
+            length: self.counts.len() as u32,
+        };
+        let mut previous = 0;
+        let deltas = self
+            .counts
+            .iter()
+            .map(|count| {
+                let delta = *count as i64 - previous;
+                previous = *count as i64;
+                delta
+            })
+            .collect();
+        (vec![span], deltas)
+    }
+
+    /// Decodes the spans and delta-encoded counts of a Prometheus native histogram.
+    fn from_native(spans: &[NativeHistogramSpan], deltas: &[i64]) -> Result<Self, &'static str> {
+        let mut buckets = Self::default();
+        let mut deltas = deltas.iter();
+        let mut index = 0;
+        let mut count = 0_i64;
+
+        for (i, span) in spans.iter().enumerate() {
+            if i == 0 {
+                index = span.offset;
+            } else {
+                index += span.offset;
+            }
+            for _ in 0..span.length {
+                count += deltas
+                    .next()
+                    .ok_or("native histogram has fewer bucket counts than its spans")?;
+                if count < 0 {
+                    return Err("native histogram has a negative bucket count");
+                }
+                buckets.increment(index - 1, count as u64);
+                index += 1;
+            }
+        }
+
+        if deltas.next().is_some() {
+            return Err("native histogram has more bucket counts than its spans");
+        }
+        Ok(buckets)
+    }
+}
+
+impl ExponentialHistogram {
+    /// Creates an empty `ExponentialHistogram` with the given scale.
+    pub fn new(scale: i32) -> Self {
+        Self {
+            scale,
+            ..Self::default()
+        }
+    }
+
+    /// Creates an `ExponentialHistogram` from the parts of a Prometheus native histogram.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the schema is out of the range supported by Prometheus, if the number of
+    /// bucket counts doesn't match the spans, or if the deltas yield a negative count.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_native_histogram(
+        schema: i32,
+        zero_threshold: f64,
+        zero_count: u64,
+        sum: f64,
+        positive_spans: &[NativeHistogramSpan],
+        positive_deltas: &[i64],
+        negative_spans: &[NativeHistogramSpan],
+        negative_deltas: &[i64],
+    ) -> Result<Self, &'static str> {
+        if !(NATIVE_HISTOGRAM_MIN_SCHEMA..=NATIVE_HISTOGRAM_MAX_SCHEMA).contains(&schema) {
+            return Err("native histogram schema out of range");
+        }
+
+        Ok(Self {
+            scale: schema,
+            zero_threshold,
+            zero_count,
+            positive: ExponentialBuckets::from_native(positive_spans, positive_deltas)?,
+            negative: ExponentialBuckets::from_native(negative_spans, negative_deltas)?,
+            sum,
+        })
+    }
+
+    /// The total number of values in the histogram.
+    pub fn count(&self) -> u64 {
+        self.zero_count + self.positive.count() + self.negative.count()
+    }
+
+    /// The base of the bucket boundaries.
+    pub fn base(&self) -> f64 {
+        2f64.powf(2f64.powi(-self.scale))
+    }
+
+    /// The lower and upper boundaries of the bucket at `index`.
+    pub fn bucket_bounds(&self, index: i32) -> (f64, f64) {
+        let exponent = 2f64.powi(-self.scale);
+        (
+            2f64.powf(f64::from(index) * exponent),
+            2f64.powf(f64::from(index + 1) * exponent),
+        )
+    }
+
+    /// The index of the bucket holding the given magnitude, which must be positive.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn bucket_index(&self, value: f64) -> i32 {
+        (value.log2() * 2f64.powi(self.scale)).ceil() as i32 - 1
+    }
+
+    /// Adds `count` occurrences of the given value to the histogram.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn insert_n(&mut self, value: f64, count: u64) {
+        if value.abs() <= self.zero_threshold {
+            self.zero_count += count;
+        } else if value > 0.0 {
+            self.positive.increment(self.bucket_index(value), count);
+        } else {
+            self.negative.increment(self.bucket_index(-value), count);
+        }
+        self.sum += value * count as f64;
+    }
+
+    /// Converts the histogram to a sketch, interpolating the count of each bucket across the bins
+    /// of the sketch it overlaps.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if a bucket size is greater that `u32::MAX`.
+    pub fn to_sketch(&self) -> Result<AgentDDSketch, &'static str> {
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        sketch.insert_exponential_histogram(self)?;
+        Ok(sketch)
+    }
+}
+
+impl TryFrom<&ExponentialHistogram> for MetricValue {
+    type Error = &'static str;
+
+    fn try_from(histogram: &ExponentialHistogram) -> Result<Self, Self::Error> {
+        Ok(MetricValue::Sketch {
+            sketch: MetricSketch::AgentDDSketch(histogram.to_sketch()?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(offset: i32, length: u32) -> NativeHistogramSpan {
+        NativeHistogramSpan { offset, length }
+    }
+
+    #[test]
+    fn native_histogram_buckets() {
+        // Buckets 1 and 2, then 5: counts 2, 3, and 1.
+        let histogram = ExponentialHistogram::from_native_histogram(
+            0,
+            0.001,
+            4,
+            20.0,
+            &[span(1, 2), span(2, 1)],
+            &[2, 1, -2],
+            &[span(0, 1)],
+            &[7],
+        )
+        .unwrap();
+
+        assert_eq!(histogram.positive.offset, 0);
+        assert_eq!(histogram.positive.counts, vec![2, 3, 0, 0, 1]);
+        assert_eq!(histogram.negative.offset, -1);
+        assert_eq!(histogram.negative.counts, vec![7]);
+        assert_eq!(histogram.count(), 17);
+        // The Prometheus bucket 1 holds the values in (1, 2].
+        assert_eq!(
+            histogram.bucket_bounds(histogram.positive.offset),
+            (1.0, 2.0)
+        );
+
+        let (spans, deltas) = histogram.positive.to_native();
+        assert_eq!(spans, vec![span(1, 5)]);
+        assert_eq!(deltas, vec![2, 1, -3, 0, 1]);
+        let positive = ExponentialBuckets::from_native(&spans, &deltas).unwrap();
+        assert_eq!(positive, histogram.positive);
+    }
+
+    #[test]
+    fn rejects_invalid_native_histograms() {
+        let invalid = [
+            (9, vec![span(0, 1)], vec![1]),
+            (0, vec![span(0, 2)], vec![1]),
+            (0, vec![span(0, 1)], vec![1, 1]),
+            (0, vec![span(0, 2)], vec![1, -2]),
+        ];
+        for (schema, spans, deltas) in invalid {
+            assert!(ExponentialHistogram::from_native_histogram(
+                schema,
+                0.0,
+                0,
+                0.0,
+                &spans,
+                &deltas,
+                &[],
+                &[]
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn bucket_index() {
+        let histogram = ExponentialHistogram::new(1);
+
+        for value in [0.3, 1.5, 2.0, 10.0, 1000.0] {
+            let (lower, upper) = histogram.bucket_bounds(histogram.bucket_index(value));
+            assert!(lower < value && value <= upper * (1.0 + 1e-12));
+        }
+    }
+
+    #[test]
+    fn sketch_round_trip() {
+        let mut histogram = ExponentialHistogram::new(3);
+        histogram.zero_threshold = 1e-10;
+        for value in 1..=1000 {
+            histogram.insert_n(f64::from(value), 1);
+            histogram.insert_n(-f64::from(value) / 10.0, 1);
+        }
+        histogram.insert_n(0.0, 5);
+
+        let sketch = histogram.to_sketch().unwrap();
+        assert_eq!(u64::from(sketch.count()), histogram.count());
+        assert_eq!(sketch.sum(), Some(histogram.sum));
+        let quantile = sketch.quantile(0.75).unwrap();
+        assert!((quantile - 500.0).abs() / 500.0 < 0.1, "{}", quantile);
+
+        let converted = sketch.to_exponential_histogram(3);
+        assert_eq!(converted.count(), histogram.count());
+        assert_eq!(converted.zero_count, 5);
+        assert_eq!(converted.positive.count(), 1000);
+        assert_eq!(converted.negative.count(), 1000);
+        assert_eq!(converted.sum, histogram.sum);
+    }
+}
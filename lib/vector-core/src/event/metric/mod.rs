@@ -26,6 +26,9 @@ mod arbitrary;
 mod data;
 pub use self::data::*;
 
+mod exponential;
+pub use self::exponential::*;
+
 mod series;
 pub use self::series::*;
 
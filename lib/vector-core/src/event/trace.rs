@@ -11,10 +11,18 @@ use super::{
 use crate::ByteSizeOf;
 
 /// Traces are a newtype of `LogEvent`
+///
+/// A trace event holds a single trace, or a chunk of it. The fields describing the trace as a whole,
+/// such as its ID, are at the root of the event, while its spans are objects in the array at
+/// [`TraceEvent::SPANS_KEY`]. Transforms processing traces at the span level see each span as a
+/// log event holding the fields of the span, see [`TraceEvent::span_event`].
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct TraceEvent(LogEvent);
 
 impl TraceEvent {
+    /// The field holding the spans of the trace.
+    pub const SPANS_KEY: &'static str = "spans";
+
     /// Convert a `TraceEvent` into a tuple of its components
     /// # Panics
     ///
@@ -86,6 +94,34 @@ impl TraceEvent {
     ) -> Option<Value> {
         self.0.insert(key.as_ref(), value.into())
     }
+
+    /// Returns the spans of the trace, which are empty if the trace has no spans array.
+    pub fn spans(&self) -> &[Value] {
+        self.get(Self::SPANS_KEY)
+            .and_then(Value::as_array)
+            .unwrap_or_default()
+    }
+
+    /// Removes the spans from the trace, returning them.
+    ///
+    /// The spans field is left untouched if it isn't an array.
+    pub fn take_spans(&mut self) -> Vec<Value> {
+        match self.get_mut(Self::SPANS_KEY).and_then(Value::as_array_mut) {
+            Some(spans) => std::mem::take(spans),
+            None => Vec::new(),
+        }
+    }
+
+    /// Replaces the spans of the trace.
+    pub fn set_spans(&mut self, spans: Vec<Value>) {
+        self.insert(Self::SPANS_KEY, Value::Array(spans));
+    }
+
+    /// Builds the log event a span of the trace is processed as, holding the fields of the span
+    /// along with the metadata of the trace.
+    pub fn span_event(&self, span: Value) -> LogEvent {
+        LogEvent::from_parts(span, self.metadata().clone())
+    }
 }
 
 impl From<LogEvent> for TraceEvent {
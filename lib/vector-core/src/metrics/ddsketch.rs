@@ -11,7 +11,10 @@ use vector_common::byte_size_of::ByteSizeOf;
 use vector_config::configurable_component;
 
 use crate::{
-    event::{metric::Bucket, Metric, MetricValue},
+    event::{
+        metric::{Bucket, ExponentialHistogram},
+        Metric, MetricValue,
+    },
     float_eq,
 };
 
@@ -652,6 +655,83 @@ impl AgentDDSketch {
         Ok(())
     }
 
+    /// Inserts the buckets of an exponential histogram, such as an OpenTelemetry exponential
+    /// histogram or a Prometheus native histogram, into the sketch.
+    ///
+    /// The count of each bucket is interpolated across the bins of the sketch that the bucket
+    /// overlaps, and values in the zero bucket are inserted as zero. As the sum of the histogram is
+    /// known, it is used instead of the sum estimated from the bins.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if a bucket size is greater that `u32::MAX`.
+    pub fn insert_exponential_histogram(
+        &mut self,
+        histogram: &ExponentialHistogram,
+    ) -> Result<(), &'static str> {
+        let limit = u64::from(u32::MAX);
+        if histogram.zero_count > limit
+            || histogram
+                .positive
+                .iter()
+                .chain(histogram.negative.iter())
+                .any(|(_, count)| count > limit)
+        {
+            return Err("bucket size greater than u32::MAX");
+        }
+
+        let sum = self.sum + histogram.sum;
+        for (index, count) in histogram.negative.iter().filter(|(_, count)| *count > 0) {
+            let (lower, upper) = histogram.bucket_bounds(index);
+            let count = u32::try_from(count).expect("count range has already been checked.");
+            self.insert_interpolate_bucket(-upper, -lower, count);
+        }
+        if histogram.zero_count > 0 {
+            let count =
+                u32::try_from(histogram.zero_count).expect("count range has already been checked.");
+            self.insert_n(0.0, count);
+        }
+        for (index, count) in histogram.positive.iter().filter(|(_, count)| *count > 0) {
+            let (lower, upper) = histogram.bucket_bounds(index);
+            let count = u32::try_from(count).expect("count range has already been checked.");
+            self.insert_interpolate_bucket(lower, upper, count);
+        }
+
+        if self.count > 0 {
+            self.sum = sum;
+            self.avg = sum / f64::from(self.count);
+        }
+        Ok(())
+    }
+
+    /// Converts the sketch to an exponential histogram with the given scale.
+    ///
+    /// Each bin is counted in the bucket holding the value it represents, and the zero bin is
+    /// counted in the zero bucket, whose threshold is the smallest magnitude the sketch can
+    /// represent.
+    pub fn to_exponential_histogram(&self, scale: i32) -> ExponentialHistogram {
+        let mut histogram = ExponentialHistogram::new(scale);
+        histogram.zero_threshold = self.config.norm_min;
+        for bin in &self.bins {
+            let count = u64::from(bin.n);
+            if bin.k == 0 {
+                histogram.zero_count += count;
+                continue;
+            }
+
+            // The bin of the values beyond the range of the sketch has an infinite lower bound.
+            let magnitude = self.config.bin_lower_bound(bin.k.abs()).min(f64::MAX);
+            let index = histogram.bucket_index(magnitude);
+            if bin.k > 0 {
+                histogram.positive.increment(index, count);
+            } else {
+                histogram.negative.increment(index, count);
+            }
+        }
+        histogram.sum = self.sum;
+        histogram
+    }
+
     /// Adds a bin directly into the sketch.
     ///
     /// Used only for unit testing so that we can create a sketch with an exact layout, which allows
@@ -21,7 +21,7 @@ use tonic::transport::{server::Connected, Certificate};
 
 use super::{
     CreateAcceptorSnafu, HandshakeSnafu, IncomingListenerSnafu, MaybeTlsSettings, MaybeTlsStream,
-    SslBuildSnafu, TcpBindSnafu, TlsError, TlsSettings,
+    ProxyProtocolSettings, ProxyProtocolSnafu, SslBuildSnafu, TcpBindSnafu, TlsError, TlsSettings,
 };
 use crate::tcp::{self, TcpKeepaliveConfig};
 
@@ -48,26 +48,67 @@ impl MaybeTlsSettings {
             Self::Raw(()) => None,
         };
 
-        Ok(MaybeTlsListener { listener, acceptor })
+        Ok(MaybeTlsListener {
+            listener,
+            acceptor,
+            tls: self.tls().cloned(),
+            proxy_protocol: None,
+        })
     }
 }
 
 pub struct MaybeTlsListener {
     listener: TcpListener,
     acceptor: Option<SslAcceptor>,
+    tls: Option<TlsSettings>,
+    proxy_protocol: Option<ProxyProtocolSettings>,
 }
 
 impl MaybeTlsListener {
+    /// Sets whether the accepted connections start with a PROXY protocol header, and which peers
+    /// are trusted to send it.
+    ///
+    /// The header is read before the TLS handshake, and the address of the original client it
+    /// holds becomes the peer address of the connection. The connections from untrusted peers are
+    /// closed.
+    #[must_use]
+    pub fn with_proxy_protocol(mut self, proxy_protocol: Option<ProxyProtocolSettings>) -> Self {
+        self.proxy_protocol = proxy_protocol;
+        self
+    }
+
     pub async fn accept(&mut self) -> crate::tls::Result<MaybeTlsIncomingStream<TcpStream>> {
+        self.reload_acceptor();
         self.listener
             .accept()
             .await
             .map(|(stream, peer_addr)| {
-                MaybeTlsIncomingStream::new(stream, peer_addr, self.acceptor.clone())
+                MaybeTlsIncomingStream::new(
+                    stream,
+                    peer_addr,
+                    self.acceptor.clone(),
+                    self.proxy_protocol.clone(),
+                )
             })
             .context(IncomingListenerSnafu)
     }
 
+    /// Replaces the acceptor if the certificates were reloaded, so that new connections are
+    /// accepted with them while established connections are kept.
+    fn reload_acceptor(&mut self) {
+        if let Some(tls) = self.tls.as_ref().and_then(TlsSettings::reloaded) {
+            match tls.acceptor() {
+                Ok(acceptor) => self.acceptor = Some(acceptor),
+                Err(error) => error!(
+                    message = "Failed to apply reloaded TLS certificates.",
+                    %error,
+                    internal_log_rate_limit = true,
+                ),
+            }
+            self.tls = Some(tls);
+        }
+    }
+
     async fn into_accept(
         mut self,
     ) -> (crate::tls::Result<MaybeTlsIncomingStream<TcpStream>>, Self) {
@@ -134,6 +175,8 @@ impl From<TcpListener> for MaybeTlsListener {
         Self {
             listener,
             acceptor: None,
+            tls: None,
+            proxy_protocol: None,
         }
     }
 }
@@ -146,18 +189,31 @@ pub struct MaybeTlsIncomingStream<S> {
     peer_addr: SocketAddr,
 }
 
+/// An accepted stream, and the address of the original client read from its PROXY protocol
+/// header, if any.
+type AcceptedStream<S> = (MaybeTlsStream<S>, Option<SocketAddr>);
+
 enum StreamState<S> {
     Accepted(MaybeTlsStream<S>),
-    Accepting(BoxFuture<'static, Result<SslStream<S>, TlsError>>),
+    Accepting(BoxFuture<'static, Result<AcceptedStream<S>, TlsError>>),
     AcceptError(String),
     Closed,
 }
 
 impl<S> MaybeTlsIncomingStream<S> {
+    /// The address of the peer, which is the address of the original client once the PROXY
+    /// protocol header of the connection is read.
     pub const fn peer_addr(&self) -> SocketAddr {
         self.peer_addr
     }
 
+    fn accepted(&mut self, (stream, client_addr): AcceptedStream<S>) {
+        self.state = StreamState::Accepted(stream);
+        if let Some(client_addr) = client_addr {
+            self.peer_addr = client_addr;
+        }
+    }
+
     /// None if connection still hasn't been established.
     pub fn get_ref(&self) -> Option<&S> {
         use super::MaybeTls;
@@ -201,21 +257,38 @@ impl MaybeTlsIncomingStream<TcpStream> {
         stream: TcpStream,
         peer_addr: SocketAddr,
         acceptor: Option<SslAcceptor>,
+        proxy_protocol: Option<ProxyProtocolSettings>,
     ) -> Self {
-        let state = match acceptor {
-            Some(acceptor) => StreamState::Accepting(
+        let state = if acceptor.is_none() && proxy_protocol.is_none() {
+            StreamState::Accepted(MaybeTlsStream::Raw(stream))
+        } else {
+            StreamState::Accepting(
                 async move {
-                    let ssl = Ssl::new(acceptor.context()).context(SslBuildSnafu)?;
-                    let mut stream = SslStream::new(ssl, stream).context(SslBuildSnafu)?;
-                    Pin::new(&mut stream)
-                        .accept()
-                        .await
-                        .context(HandshakeSnafu)?;
-                    Ok(stream)
+                    let mut stream = stream;
+                    let client_addr = match proxy_protocol {
+                        Some(proxy_protocol) => proxy_protocol
+                            .read_header(&mut stream, peer_addr)
+                            .await
+                            .context(ProxyProtocolSnafu)?,
+                        None => None,
+                    };
+
+                    let stream = match acceptor {
+                        Some(acceptor) => {
+                            let ssl = Ssl::new(acceptor.context()).context(SslBuildSnafu)?;
+                            let mut stream = SslStream::new(ssl, stream).context(SslBuildSnafu)?;
+                            Pin::new(&mut stream)
+                                .accept()
+                                .await
+                                .context(HandshakeSnafu)?;
+                            MaybeTlsStream::Tls(stream)
+                        }
+                        None => MaybeTlsStream::Raw(stream),
+                    };
+                    Ok((stream, client_addr))
                 }
                 .boxed(),
-            ),
-            None => StreamState::Accepted(MaybeTlsStream::Raw(stream)),
+            )
         };
         Self { state, peer_addr }
     }
@@ -223,8 +296,8 @@ impl MaybeTlsIncomingStream<TcpStream> {
     // Explicit handshake method
     pub async fn handshake(&mut self) -> crate::tls::Result<()> {
         if let StreamState::Accepting(fut) = &mut self.state {
-            let stream = fut.await?;
-            self.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+            let accepted = fut.await?;
+            self.accepted(accepted);
         }
 
         Ok(())
@@ -268,8 +341,8 @@ impl MaybeTlsIncomingStream<TcpStream> {
             return match &mut this.state {
                 StreamState::Accepted(stream) => poll_fn(Pin::new(stream), cx),
                 StreamState::Accepting(fut) => match std::task::ready!(fut.as_mut().poll(cx)) {
-                    Ok(stream) => {
-                        this.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+                    Ok(accepted) => {
+                        this.accepted(accepted);
                         continue;
                     }
                     Err(error) => {
@@ -317,8 +390,8 @@ impl AsyncWrite for MaybeTlsIncomingStream<TcpStream> {
                 poll_result => poll_result,
             },
             StreamState::Accepting(fut) => match std::task::ready!(fut.as_mut().poll(cx)) {
-                Ok(stream) => {
-                    this.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+                Ok(accepted) => {
+                    this.accepted(accepted);
                     Poll::Pending
                 }
                 Err(error) => {
@@ -394,6 +467,9 @@ impl From<X509> for CertificateMetadata {
 #[derive(Clone)]
 pub struct MaybeTlsConnectInfo {
     pub remote_addr: SocketAddr,
+    /// The certificate of the peer.
+    pub peer_cert: Option<Certificate>,
+    /// The certificate chain of the peer, which doesn't hold its own certificate on the server side.
     pub peer_certs: Option<Vec<Certificate>>,
 }
 
@@ -403,6 +479,11 @@ impl Connected for MaybeTlsIncomingStream<TcpStream> {
     fn connect_info(&self) -> Self::ConnectInfo {
         MaybeTlsConnectInfo {
             remote_addr: self.peer_addr(),
+            peer_cert: self
+                .ssl_stream()
+                .and_then(|s| s.ssl().peer_certificate())
+                .and_then(|c| c.to_pem().ok())
+                .map(Certificate::from_pem),
             peer_certs: self
                 .ssl_stream()
                 .and_then(|s| s.ssl().peer_cert_chain())
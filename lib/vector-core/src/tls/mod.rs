@@ -16,10 +16,17 @@ use crate::tcp::{self, TcpKeepaliveConfig};
 mod incoming;
 mod maybe_tls;
 mod outgoing;
+mod proxy_protocol;
+mod reload;
 mod settings;
+mod spiffe;
 
-pub use incoming::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener};
+pub use incoming::{
+    CertificateMetadata, MaybeTlsConnectInfo, MaybeTlsIncomingStream, MaybeTlsListener,
+};
 pub use maybe_tls::MaybeTls;
+pub use proxy_protocol::{ProxyProtocolConfig, ProxyProtocolSettings};
+pub use reload::TlsReloader;
 pub use settings::{
     MaybeTlsSettings, TlsConfig, TlsEnableableConfig, TlsSettings, TlsSourceConfig,
     PEM_START_MARKER, TEST_PEM_CA_PATH, TEST_PEM_CLIENT_CRT_PATH, TEST_PEM_CLIENT_KEY_PATH,
@@ -82,6 +89,10 @@ pub enum TlsError {
     MissingRequiredIdentity,
     #[snafu(display("TLS handshake failed: {}", source))]
     Handshake { source: openssl::ssl::Error },
+    #[snafu(display("{}", source))]
+    ProxyProtocol {
+        source: proxy_protocol::ProxyProtocolError,
+    },
     #[snafu(display("Incoming listener failed: {}", source))]
     IncomingListener { source: tokio::io::Error },
     #[snafu(display("Creating the TLS acceptor failed: {}", source))]
@@ -130,6 +141,13 @@ pub enum TlsError {
     NewCaStack { source: ErrorStack },
     #[snafu(display("Could not push intermediate certificate onto stack"))]
     CaStackPush { source: ErrorStack },
+    #[snafu(display("Cannot use spiffe_endpoint_socket with ca_file, crt_file, or key_file"))]
+    SpiffeWithFiles,
+    #[snafu(display(
+        "Could not get an X.509 SVID from the SPIFFE Workload API at {:?}",
+        socket
+    ))]
+    SpiffeSvidUnavailable { socket: PathBuf },
 }
 
 impl MaybeTlsStream<TcpStream> {
@@ -183,6 +201,12 @@ pub fn tls_connector_builder(settings: &MaybeTlsSettings) -> Result<SslConnector
 }
 
 fn tls_connector(settings: &MaybeTlsSettings) -> Result<ConnectConfiguration> {
+    // Connections are made with the latest certificates, if they were reloaded.
+    let reloaded = settings
+        .tls()
+        .and_then(TlsSettings::reloaded)
+        .map(MaybeTlsSettings::Tls);
+    let settings = reloaded.as_ref().unwrap_or(settings);
     let verify_hostname = settings
         .tls()
         .map_or(true, |settings| settings.verify_hostname);
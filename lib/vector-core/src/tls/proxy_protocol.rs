@@ -0,0 +1,342 @@
+//! Reading of the [PROXY protocol][proxy_protocol] header, which load balancers such as HAProxy
+//! and AWS Network Load Balancers send at the start of the connections they proxy, to pass on the
+//! address of the original client.
+//!
+//! [proxy_protocol]: https://www.haproxy.org/download/2.8/doc/proxy-protocol.txt
+
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+};
+
+use cidr_utils::cidr::IpCidr;
+use snafu::{ResultExt, Snafu};
+use tokio::io::{self, AsyncRead, AsyncReadExt};
+use vector_config::configurable_component;
+
+/// The start of the version 1 (text) header.
+const V1_PREFIX: &[u8] = b"PROXY ";
+
+/// The maximum length of the version 1 header, including its CRLF.
+const V1_MAX_LENGTH: usize = 107;
+
+/// The signature starting the version 2 (binary) header.
+const V2_SIGNATURE: [u8; 12] = *b"\r\n\r\n\0\r\nQUIT\n";
+
+/// Configuration of the [PROXY protocol][proxy_protocol] header at the start of the connections.
+///
+/// Configure this when Vector sits behind a load balancer sending the header, such as HAProxy or
+/// an AWS Network Load Balancer, so that the peer address of the events is the address of the
+/// original client instead of the load balancer. Both versions of the protocol are supported.
+///
+/// [proxy_protocol]: https://www.haproxy.org/download/2.8/doc/proxy-protocol.txt
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ProxyProtocolConfig {
+    /// The load balancers trusted to send the header, as [CIDR][cidr] blocks.
+    ///
+    /// The header is only read from the connections of these addresses, and the other connections
+    /// are closed, so that clients can't forge their address by sending a header themselves. The
+    /// connections without a valid header are closed as well.
+    ///
+    /// [cidr]: https://en.wikipedia.org/wiki/Classless_Inter-Domain_Routing
+    #[configurable(metadata(docs::examples = "10.0.0.0/8"))]
+    #[configurable(metadata(docs::examples = "192.0.2.1/32"))]
+    #[configurable(metadata(docs::examples = "2001:db8::/32"))]
+    pub trusted_proxies: Vec<String>,
+}
+
+/// PROXY protocol settings, built from a [`ProxyProtocolConfig`].
+#[derive(Clone, Debug)]
+pub struct ProxyProtocolSettings {
+    trusted_proxies: Arc<[IpCidr]>,
+}
+
+impl ProxyProtocolSettings {
+    /// Builds the settings from the configuration, parsing the trusted proxies.
+    pub fn from_config(config: &ProxyProtocolConfig) -> Result<Self, ProxyProtocolError> {
+        let trusted_proxies = config
+            .trusted_proxies
+            .iter()
+            .map(|proxy| {
+                IpCidr::from_str(proxy).map_err(|error| ProxyProtocolError::InvalidTrustedProxy {
+                    proxy: proxy.clone(),
+                    reason: error.to_string(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { trusted_proxies })
+    }
+
+    fn trusts(&self, ip: IpAddr) -> bool {
+        // Listeners bound to IPv6 addresses see the IPv4 peers as IPv4-mapped addresses.
+        let ip = match ip {
+            IpAddr::V6(ip) => ip.to_ipv4_mapped().map_or(IpAddr::V6(ip), IpAddr::V4),
+            ip => ip,
+        };
+        self.trusted_proxies.iter().any(|cidr| cidr.contains(ip))
+    }
+
+    /// Reads the PROXY protocol header at the start of `stream`, accepted from `peer_addr`,
+    /// returning the address of the original client.
+    ///
+    /// The connections from untrusted peers are refused before anything is read. See
+    /// [`read_header`] for the rest.
+    pub(super) async fn read_header<S>(
+        &self,
+        stream: &mut S,
+        peer_addr: SocketAddr,
+    ) -> Result<Option<SocketAddr>, ProxyProtocolError>
+    where
+        S: AsyncRead + Unpin,
+    {
+        if !self.trusts(peer_addr.ip()) {
+            return Err(ProxyProtocolError::UntrustedProxy {
+                addr: peer_addr.ip(),
+            });
+        }
+        read_header(stream).await
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ProxyProtocolError {
+    #[snafu(display("Invalid trusted proxy {:?}: {}", proxy, reason))]
+    InvalidTrustedProxy { proxy: String, reason: String },
+    #[snafu(display("Connection from {}, which is not a trusted proxy", addr))]
+    UntrustedProxy { addr: IpAddr },
+    #[snafu(display("Failed to read the PROXY protocol header: {}", source))]
+    ReadHeader { source: io::Error },
+    #[snafu(display("The connection does not start with a PROXY protocol header"))]
+    MissingHeader,
+    #[snafu(display("Invalid PROXY protocol header: {}", reason))]
+    InvalidHeader { reason: &'static str },
+}
+
+const fn invalid(reason: &'static str) -> ProxyProtocolError {
+    ProxyProtocolError::InvalidHeader { reason }
+}
+
+/// Reads the PROXY protocol header, of either version, at the start of `stream`, returning the
+/// address of the original client.
+///
+/// Only the header is read from the stream. The address is `None` for the connections of the load
+/// balancer itself, such as health checks, and for the connections of unsupported protocols.
+pub(super) async fn read_header<S>(stream: &mut S) -> Result<Option<SocketAddr>, ProxyProtocolError>
+where
+    S: AsyncRead + Unpin,
+{
+    // Both versions of the header are longer than the signature of version 2.
+    let mut start = [0; V2_SIGNATURE.len()];
+    stream
+        .read_exact(&mut start)
+        .await
+        .context(ReadHeaderSnafu)?;
+
+    if start == V2_SIGNATURE {
+        let mut header = [0; 4];
+        stream
+            .read_exact(&mut header)
+            .await
+            .context(ReadHeaderSnafu)?;
+        let mut addresses = vec![0; usize::from(u16::from_be_bytes([header[2], header[3]]))];
+        stream
+            .read_exact(&mut addresses)
+            .await
+            .context(ReadHeaderSnafu)?;
+        parse_v2(header[0], header[1], &addresses)
+    } else if start.starts_with(V1_PREFIX) {
+        let mut line = start.to_vec();
+        while !line.ends_with(b"\r\n") {
+            if line.len() >= V1_MAX_LENGTH {
+                return Err(invalid("the header is too long"));
+            }
+            line.push(stream.read_u8().await.context(ReadHeaderSnafu)?);
+        }
+        parse_v1(&line[..line.len() - 2])
+    } else {
+        Err(ProxyProtocolError::MissingHeader)
+    }
+}
+
+/// Parses a version 1 header, such as `PROXY TCP4 192.0.2.1 198.51.100.1 56324 443`.
+fn parse_v1(line: &[u8]) -> Result<Option<SocketAddr>, ProxyProtocolError> {
+    let line = std::str::from_utf8(line).map_err(|_| invalid("the header is not ASCII"))?;
+    let mut parts = line.split(' ').skip(1);
+    match parts.next() {
+        Some("TCP4" | "TCP6") => {
+            let ip = parts
+                .next()
+                .and_then(|ip| ip.parse::<IpAddr>().ok())
+                .ok_or_else(|| invalid("invalid source address"))?;
+            let port = parts
+                .nth(1)
+                .and_then(|port| port.parse::<u16>().ok())
+                .ok_or_else(|| invalid("invalid source port"))?;
+            Ok(Some(SocketAddr::new(ip, port)))
+        }
+        Some("UNKNOWN") => Ok(None),
+        _ => Err(invalid("unknown protocol")),
+    }
+}
+
+/// Parses the fields of a version 2 header following its signature.
+fn parse_v2(
+    version_command: u8,
+    family: u8,
+    addresses: &[u8],
+) -> Result<Option<SocketAddr>, ProxyProtocolError> {
+    if version_command >> 4 != 2 {
+        return Err(invalid("unsupported version"));
+    }
+    match version_command & 0x0f {
+        // LOCAL, for the connections of the load balancer itself.
+        0 => return Ok(None),
+        // PROXY.
+        1 => {}
+        _ => return Err(invalid("unknown command")),
+    }
+
+    match family >> 4 {
+        // AF_INET: the source and destination addresses, then the source and destination ports.
+        1 => {
+            let addresses = addresses
+                .get(..12)
+                .ok_or_else(|| invalid("truncated IPv4 addresses"))?;
+            let ip = Ipv4Addr::from(<[u8; 4]>::try_from(&addresses[..4]).expect("4 bytes"));
+            let port = u16::from_be_bytes([addresses[8], addresses[9]]);
+            Ok(Some(SocketAddr::new(ip.into(), port)))
+        }
+        // AF_INET6.
+        2 => {
+            let addresses = addresses
+                .get(..36)
+                .ok_or_else(|| invalid("truncated IPv6 addresses"))?;
+            let ip = Ipv6Addr::from(<[u8; 16]>::try_from(&addresses[..16]).expect("16 bytes"));
+            let port = u16::from_be_bytes([addresses[32], addresses[33]]);
+            Ok(Some(SocketAddr::new(ip.into(), port)))
+        }
+        // AF_UNSPEC and AF_UNIX, which have no meaningful address.
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn read(mut input: &[u8]) -> (Result<Option<SocketAddr>, ProxyProtocolError>, &[u8]) {
+        let result = read_header(&mut input).await;
+        (result, input)
+    }
+
+    #[tokio::test]
+    async fn reads_v1_header() {
+        let (result, rest) = read(b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\nhello").await;
+        assert_eq!(result.unwrap(), Some("192.0.2.1:56324".parse().unwrap()));
+        assert_eq!(rest, b"hello");
+
+        let (result, _) = read(b"PROXY TCP6 2001:db8::1 2001:db8::2 56324 443\r\n").await;
+        assert_eq!(
+            result.unwrap(),
+            Some("[2001:db8::1]:56324".parse().unwrap())
+        );
+
+        let (result, rest) = read(b"PROXY UNKNOWN\r\nhello").await;
+        assert_eq!(result.unwrap(), None);
+        assert_eq!(rest, b"hello");
+    }
+
+    #[tokio::test]
+    async fn reads_v2_header() {
+        let mut input = V2_SIGNATURE.to_vec();
+        input.extend([0x21, 0x11, 0, 12]);
+        input.extend([192, 0, 2, 1, 198, 51, 100, 1]);
+        input.extend(56324_u16.to_be_bytes());
+        input.extend(443_u16.to_be_bytes());
+        input.extend(b"hello");
+
+        let (result, rest) = read(&input).await;
+        assert_eq!(result.unwrap(), Some("192.0.2.1:56324".parse().unwrap()));
+        assert_eq!(rest, b"hello");
+    }
+
+    #[tokio::test]
+    async fn reads_v2_local_header() {
+        let mut input = V2_SIGNATURE.to_vec();
+        input.extend([0x20, 0x00, 0, 0]);
+        input.extend(b"hello");
+
+        let (result, rest) = read(&input).await;
+        assert_eq!(result.unwrap(), None);
+        assert_eq!(rest, b"hello");
+    }
+
+    fn settings(trusted_proxies: &[&str]) -> Result<ProxyProtocolSettings, ProxyProtocolError> {
+        ProxyProtocolSettings::from_config(&ProxyProtocolConfig {
+            trusted_proxies: trusted_proxies
+                .iter()
+                .map(|&proxy| proxy.to_owned())
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn trusts_configured_proxies() {
+        let settings = settings(&["192.0.2.0/24", "2001:db8::/32"]).unwrap();
+        assert!(settings.trusts("192.0.2.1".parse().unwrap()));
+        assert!(settings.trusts("::ffff:192.0.2.1".parse().unwrap()));
+        assert!(settings.trusts("2001:db8::1".parse().unwrap()));
+        assert!(!settings.trusts("198.51.100.1".parse().unwrap()));
+        assert!(!settings.trusts("2001:db9::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn rejects_invalid_trusted_proxies() {
+        assert!(matches!(
+            settings(&["192.0.2.0/24", "not-a-cidr"]),
+            Err(ProxyProtocolError::InvalidTrustedProxy { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn refuses_untrusted_peers() {
+        let settings = settings(&["192.0.2.0/24"]).unwrap();
+        let header = b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\n";
+
+        let mut input = header.as_slice();
+        let result = settings
+            .read_header(&mut input, "198.51.100.1:56324".parse().unwrap())
+            .await;
+        assert!(matches!(
+            result,
+            Err(ProxyProtocolError::UntrustedProxy { .. })
+        ));
+        assert_eq!(input, header);
+
+        let mut input = header.as_slice();
+        let result = settings
+            .read_header(&mut input, "192.0.2.10:56324".parse().unwrap())
+            .await;
+        assert_eq!(result.unwrap(), Some("192.0.2.1:56324".parse().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_headers() {
+        assert!(matches!(
+            read(b"hello world, no header here").await.0,
+            Err(ProxyProtocolError::MissingHeader)
+        ));
+        assert!(matches!(
+            read(b"PROXY TCP4 not-an-ip 198.51.100.1 56324 443\r\n")
+                .await
+                .0,
+            Err(ProxyProtocolError::InvalidHeader { .. })
+        ));
+        assert!(matches!(
+            read(&[b"PROXY ".as_slice(), &[b'x'; 200]].concat()).await.0,
+            Err(ProxyProtocolError::InvalidHeader { .. })
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+
+use openssl::{
+    error::ErrorStack,
+    ssl::{SslContext, SslRef},
+    x509::X509,
+};
+
+use super::{
+    settings::IdentityStore,
+    spiffe::{X509Source, X509Svid},
+    tls_connector_builder, MaybeTls, Result, TlsConfig, TlsError, TlsSettings,
+};
+
+/// How long to wait for the Workload API to issue the first SVID when loading the settings.
+const SVID_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The CA certificates and the identity of the settings, which are what is rotated.
+pub(super) type Certificates = (Vec<X509>, Option<IdentityStore>);
+
+/// Reloads the certificates of TLS settings as they are rotated, either because the certificate
+/// files changed, or because the SPIFFE Workload API issued a new SVID.
+///
+/// Each reload is numbered, so that all of the settings sharing it can tell whether they are up
+/// to date.
+pub(super) struct Reload {
+    options: TlsConfig,
+    check_interval: Option<Duration>,
+    spiffe: Option<Arc<X509Source>>,
+    state: Mutex<ReloadState>,
+}
+
+struct ReloadState {
+    generation: u64,
+    certificates: Certificates,
+    checked_at: Instant,
+    modified: Vec<Option<SystemTime>>,
+    svid_generation: u64,
+}
+
+impl ReloadState {
+    fn update(&mut self, certificates: Certificates) {
+        self.generation += 1;
+        self.certificates = certificates;
+        info!(message = "Reloaded TLS certificates.");
+    }
+}
+
+impl Reload {
+    /// Loads the certificates of the options, to be reloaded if they can be rotated.
+    pub(super) fn load(options: &TlsConfig) -> Result<(Certificates, Option<Arc<Self>>)> {
+        let spiffe = match &options.spiffe_endpoint_socket {
+            None => None,
+            Some(_)
+                if options.ca_file.is_some()
+                    || options.crt_file.is_some()
+                    || options.key_file.is_some() =>
+            {
+                return Err(TlsError::SpiffeWithFiles)
+            }
+            Some(socket) => Some(X509Source::get(socket)),
+        };
+        let check_interval = options
+            .reload_interval_secs
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs);
+
+        let (svid_generation, certificates) = match &spiffe {
+            Some(source) => {
+                let (generation, svid) =
+                    source
+                        .wait(SVID_TIMEOUT)
+                        .ok_or_else(|| TlsError::SpiffeSvidUnavailable {
+                            socket: options.spiffe_endpoint_socket.clone().unwrap_or_default(),
+                        })?;
+                (generation, load_svid(&svid)?)
+            }
+            None => (0, load_files(options)?),
+        };
+        if spiffe.is_none() && check_interval.is_none() {
+            return Ok((certificates, None));
+        }
+
+        let state = ReloadState {
+            generation: 0,
+            certificates: certificates.clone(),
+            checked_at: Instant::now(),
+            modified: modified(options),
+            svid_generation,
+        };
+        let reload = Self {
+            options: options.clone(),
+            check_interval,
+            spiffe,
+            state: Mutex::new(state),
+        };
+        Ok((certificates, Some(Arc::new(reload))))
+    }
+
+    /// Gets the latest certificates, along with their generation, if they are more recent than
+    /// the given generation.
+    pub(super) fn latest(&self, generation: u64) -> Option<(u64, Certificates)> {
+        let mut state = self.state.lock().expect("Poisoned lock");
+        if let Some(source) = &self.spiffe {
+            match source.latest() {
+                Some((svid_generation, svid)) if svid_generation != state.svid_generation => {
+                    // A SVID that fails to load won't load any better later on, so it is skipped.
+                    state.svid_generation = svid_generation;
+                    match load_svid(&svid) {
+                        Ok(certificates) => state.update(certificates),
+                        Err(error) => error!(
+                            message = "Failed to load the X.509 SVID issued by the SPIFFE Workload API.",
+                            %error,
+                            internal_log_rate_limit = true,
+                        ),
+                    }
+                }
+                _ => {}
+            }
+        } else if let Some(check_interval) = self.check_interval {
+            if state.checked_at.elapsed() >= check_interval {
+                state.checked_at = Instant::now();
+                let modified = modified(&self.options);
+                if modified != state.modified {
+                    // The files may be changed one by one, in which case they don't match until
+                    // all of them are, so they are only marked as loaded once they do.
+                    match load_files(&self.options) {
+                        Ok(certificates) => {
+                            state.modified = modified;
+                            state.update(certificates);
+                        }
+                        Err(error) => error!(
+                            message = "Failed to reload TLS certificates, retrying.",
+                            %error,
+                            internal_log_rate_limit = true,
+                        ),
+                    }
+                }
+            }
+        }
+
+        (state.generation != generation).then(|| (state.generation, state.certificates.clone()))
+    }
+}
+
+fn load_files(options: &TlsConfig) -> Result<Certificates> {
+    Ok((options.load_authorities()?, options.load_identity()?))
+}
+
+fn load_svid(svid: &X509Svid) -> Result<Certificates> {
+    let identity = IdentityStore::new(
+        &svid.spiffe_id,
+        &svid.key,
+        &svid.certificates[0],
+        svid.certificates[1..].iter().cloned(),
+    )?;
+    Ok((svid.bundle.clone(), Some(identity)))
+}
+
+/// The modification times of the certificate files, which are `None` for inline certificates.
+fn modified(options: &TlsConfig) -> Vec<Option<SystemTime>> {
+    [&options.ca_file, &options.crt_file, &options.key_file]
+        .into_iter()
+        .map(|path| {
+            path.as_ref()
+                .and_then(|path: &PathBuf| fs::metadata(path).and_then(|m| m.modified()).ok())
+        })
+        .collect()
+}
+
+/// Keeps the connections made by a connector up to date with the reloaded certificates of the
+/// settings it was built with.
+///
+/// Connections that are already established keep the certificates they were made with.
+pub struct TlsReloader {
+    settings: TlsSettings,
+    context: Option<SslContext>,
+}
+
+impl TlsReloader {
+    pub const fn new(settings: TlsSettings) -> Self {
+        Self {
+            settings,
+            context: None,
+        }
+    }
+
+    /// Applies the latest certificates to a connection being set up.
+    pub fn apply(&mut self, ssl: &mut SslRef) -> std::result::Result<(), ErrorStack> {
+        if let Some(settings) = self.settings.reloaded() {
+            match tls_connector_builder(&MaybeTls::Tls(settings.clone())) {
+                Ok(builder) => self.context = Some(builder.build().into_context()),
+                Err(error) => error!(
+                    message = "Failed to apply reloaded TLS certificates.",
+                    %error,
+                    internal_log_rate_limit = true,
+                ),
+            }
+            self.settings = settings;
+        }
+        if let Some(context) = &self.context {
+            ssl.set_ssl_context(context)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tls::{TEST_PEM_CA_PATH, TEST_PEM_CRT_PATH, TEST_PEM_KEY_PATH};
+
+    #[test]
+    fn reloads_changed_files() {
+        let dir = std::env::temp_dir().join(format!("vector-tls-reload-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let crt_file = dir.join("crt.pem");
+        let key_file = dir.join("key.pem");
+        fs::copy(TEST_PEM_CRT_PATH, &crt_file).unwrap();
+        fs::copy(TEST_PEM_KEY_PATH, &key_file).unwrap();
+        let options = TlsConfig {
+            crt_file: Some(crt_file.clone()),
+            key_file: Some(key_file),
+            reload_interval_secs: Some(1),
+            ..Default::default()
+        };
+
+        let settings = TlsSettings::from_options(&Some(options)).unwrap();
+        assert!(settings.reloaded().is_none());
+
+        // Not checked again before the interval.
+        std::thread::sleep(Duration::from_millis(10));
+        fs::copy(TEST_PEM_CA_PATH, &crt_file).unwrap();
+        assert!(settings.reloaded().is_none());
+
+        // The certificate doesn't match the key, so it is retried until it does.
+        std::thread::sleep(Duration::from_secs(1));
+        assert!(settings.reloaded().is_none());
+
+        fs::copy(TEST_PEM_CRT_PATH, &crt_file).unwrap();
+        std::thread::sleep(Duration::from_secs(1));
+        let reloaded = settings.reloaded().expect("Certificates were not reloaded");
+        assert!(reloaded.identity.is_some());
+        assert!(reloaded.reloaded().is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -3,21 +3,22 @@ use std::{
     fs::File,
     io::Read,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use lookup::lookup_v2::OptionalValuePath;
 use openssl::{
     pkcs12::{ParsedPkcs12, Pkcs12},
-    pkey::{PKey, Private},
+    pkey::{PKey, PKeyRef, Private},
     ssl::{ConnectConfiguration, SslContextBuilder, SslVerifyMode},
     stack::Stack,
-    x509::{store::X509StoreBuilder, X509},
+    x509::{store::X509StoreBuilder, X509Ref, X509},
 };
 use snafu::ResultExt;
 use vector_config::configurable_component;
 
 use super::{
-    AddCertToStoreSnafu, AddExtraChainCertSnafu, CaStackPushSnafu, DerExportSnafu,
+    reload::Reload, AddCertToStoreSnafu, AddExtraChainCertSnafu, CaStackPushSnafu, DerExportSnafu,
     EncodeAlpnProtocolsSnafu, FileOpenFailedSnafu, FileReadFailedSnafu, MaybeTls, NewCaStackSnafu,
     NewStoreBuilderSnafu, ParsePkcs12Snafu, Pkcs12Snafu, PrivateKeyParseSnafu, Result,
     SetAlpnProtocolsSnafu, SetCertificateSnafu, SetPrivateKeySnafu, SetVerifyCertSnafu, TlsError,
@@ -82,7 +83,7 @@ pub struct TlsSourceConfig {
 /// TLS configuration.
 #[configurable_component]
 #[configurable(metadata(docs::advanced))]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct TlsConfig {
     /// Enables certificate verification.
@@ -144,6 +145,26 @@ pub struct TlsConfig {
     #[configurable(metadata(docs::examples = "${KEY_PASS_ENV_VAR}"))]
     #[configurable(metadata(docs::examples = "PassWord1"))]
     pub key_pass: Option<String>,
+
+    /// How often the certificate files are checked for changes, in seconds.
+    ///
+    /// When the files change, new connections are made with the new certificates, while established connections are
+    /// kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+    /// yet, are retried at the next check.
+    ///
+    /// If not set, the files are only loaded at startup.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub reload_interval_secs: Option<u64>,
+
+    /// Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+    ///
+    /// When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+    /// domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+    /// API issues new ones, while established connections are kept.
+    ///
+    /// [spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+    #[configurable(metadata(docs::examples = "/run/spire/sockets/agent.sock"))]
+    pub spiffe_endpoint_socket: Option<PathBuf>,
 }
 
 impl TlsConfig {
@@ -165,11 +186,41 @@ pub struct TlsSettings {
     authorities: Vec<X509>,
     pub(super) identity: Option<IdentityStore>, // openssl::pkcs12::ParsedPkcs12 doesn't impl Clone yet
     alpn_protocols: Option<Vec<u8>>,
+    /// The generation of the certificates, if they are reloaded.
+    generation: u64,
+    reload: Option<Arc<Reload>>,
 }
 
 #[derive(Clone)]
 pub(super) struct IdentityStore(Vec<u8>, String);
 
+impl IdentityStore {
+    /// Bundles a certificate, its key, and the intermediate certificates of its chain.
+    pub(super) fn new(
+        name: &str,
+        key: &PKeyRef<Private>,
+        crt: &X509Ref,
+        chain: impl IntoIterator<Item = X509>,
+    ) -> Result<Self> {
+        let mut ca_stack = Stack::new().context(NewCaStackSnafu)?;
+        for intermediate in chain {
+            ca_stack.push(intermediate).context(CaStackPushSnafu)?;
+        }
+
+        let mut builder = Pkcs12::builder();
+        builder.ca(ca_stack);
+        let pkcs12 = builder.build("", name, key, crt).context(Pkcs12Snafu)?;
+        let identity = pkcs12.to_der().context(DerExportSnafu)?;
+
+        // Build the resulting parsed PKCS#12 archive,
+        // but don't store it, as it cannot be cloned.
+        // This is just for error checking.
+        pkcs12.parse("").context(TlsIdentitySnafu)?;
+
+        Ok(Self(identity, String::new()))
+    }
+}
+
 impl TlsSettings {
     /// Generate a filled out settings struct from the given optional
     /// option set, interpreted as client options. If `options` is
@@ -193,12 +244,28 @@ impl TlsSettings {
             }
         }
 
+        let ((authorities, identity), reload) = Reload::load(options)?;
         Ok(Self {
             verify_certificate: options.verify_certificate.unwrap_or(!for_server),
             verify_hostname: options.verify_hostname.unwrap_or(!for_server),
-            authorities: options.load_authorities()?,
-            identity: options.load_identity()?,
+            authorities,
+            identity,
             alpn_protocols: options.parse_alpn_protocols()?,
+            generation: 0,
+            reload,
+        })
+    }
+
+    /// Gets these settings with the certificates they were reloaded with, if they were reloaded
+    /// since these settings were made.
+    pub fn reloaded(&self) -> Option<Self> {
+        let (generation, (authorities, identity)) =
+            self.reload.as_ref()?.latest(self.generation)?;
+        Some(Self {
+            authorities,
+            identity,
+            generation,
+            ..self.clone()
         })
     }
 
@@ -295,11 +362,16 @@ impl TlsSettings {
 
     pub fn apply_connect_configuration(&self, connection: &mut ConnectConfiguration) {
         connection.set_verify_hostname(self.verify_hostname);
+        // The connection may be switched to the context of other settings, which only carries
+        // over the certificates.
+        if !self.verify_certificate {
+            connection.set_verify(SslVerifyMode::NONE);
+        }
     }
 }
 
 impl TlsConfig {
-    fn load_authorities(&self) -> Result<Vec<X509>> {
+    pub(super) fn load_authorities(&self) -> Result<Vec<X509>> {
         match &self.ca_file {
             None => Ok(vec![]),
             Some(filename) => {
@@ -318,7 +390,7 @@ impl TlsConfig {
         }
     }
 
-    fn load_identity(&self) -> Result<Option<IdentityStore>> {
+    pub(super) fn load_identity(&self) -> Result<Option<IdentityStore>> {
         match (&self.crt_file, &self.key_file) {
             (None, Some(_)) => Err(TlsError::MissingCrtKeyFile),
             (None, None) => Ok(None),
@@ -363,22 +435,7 @@ impl TlsConfig {
                 let crt = crt_stack.next().ok_or(TlsError::MissingCertificate)?;
                 let key = load_key(key_file, &self.key_pass)?;
 
-                let mut ca_stack = Stack::new().context(NewCaStackSnafu)?;
-                for intermediate in crt_stack {
-                    ca_stack.push(intermediate).context(CaStackPushSnafu)?;
-                }
-
-                let mut builder = Pkcs12::builder();
-                builder.ca(ca_stack);
-                let pkcs12 = builder.build("", &name, &key, &crt).context(Pkcs12Snafu)?;
-                let identity = pkcs12.to_der().context(DerExportSnafu)?;
-
-                // Build the resulting parsed PKCS#12 archive,
-                // but don't store it, as it cannot be cloned.
-                // This is just for error checking.
-                pkcs12.parse("").context(TlsIdentitySnafu)?;
-
-                Ok(Some(IdentityStore(identity, String::new())))
+                IdentityStore::new(&name, &key, &crt, crt_stack).map(Some)
             }
         }
     }
@@ -0,0 +1,265 @@
+//! Client of the [SPIFFE Workload API][workload_api], which issues X.509 SVIDs to workloads.
+//!
+//! The X.509 SVIDs are streamed by the Workload API as they are rotated, so each socket is watched
+//! by a background thread keeping the latest SVID, for the TLS settings to load it from.
+//!
+//! [workload_api]: https://github.com/spiffe/spiffe/blob/main/standards/SPIFFE_Workload_API.md
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Condvar, Mutex},
+    time::Duration,
+};
+
+use once_cell::sync::Lazy;
+use openssl::{
+    pkey::{PKey, Private},
+    x509::X509,
+};
+use tonic::{codec::ProstCodec, metadata::MetadataValue, transport::Channel};
+
+const FETCH_X509_SVID_PATH: &str = "/SpiffeWorkloadAPI/FetchX509SVID";
+
+/// The header the Workload API requires on all requests, to tell them apart from requests made by
+/// a browser on behalf of a malicious site.
+const SECURITY_HEADER: &str = "workload.spiffe.io";
+
+/// How long to wait before reconnecting to the Workload API when the stream of SVIDs fails.
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+static SOURCES: Lazy<Mutex<HashMap<PathBuf, Arc<X509Source>>>> = Lazy::new(Default::default);
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct X509SvidRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct X509SvidResponse {
+    #[prost(message, repeated, tag = "1")]
+    svids: Vec<X509SvidMessage>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct X509SvidMessage {
+    #[prost(string, tag = "1")]
+    spiffe_id: String,
+    /// The certificate chain of the SVID, as concatenated DER certificates.
+    #[prost(bytes = "vec", tag = "2")]
+    x509_svid: Vec<u8>,
+    /// The private key of the SVID, as a DER PKCS#8 key.
+    #[prost(bytes = "vec", tag = "3")]
+    x509_svid_key: Vec<u8>,
+    /// The trust bundle of the trust domain of the SVID, as concatenated DER certificates.
+    #[prost(bytes = "vec", tag = "4")]
+    bundle: Vec<u8>,
+}
+
+/// An X.509 SVID, along with the trust bundle to verify peers with.
+pub(super) struct X509Svid {
+    pub(super) spiffe_id: String,
+    pub(super) certificates: Vec<X509>,
+    pub(super) key: PKey<Private>,
+    pub(super) bundle: Vec<X509>,
+}
+
+impl TryFrom<X509SvidMessage> for X509Svid {
+    type Error = vector_common::Error;
+
+    fn try_from(message: X509SvidMessage) -> Result<Self, Self::Error> {
+        let parse_certificates = |der: &[u8]| {
+            split_der(der)?
+                .into_iter()
+                .map(|der| X509::from_der(der).map_err(Into::into))
+                .collect::<Result<Vec<_>, vector_common::Error>>()
+        };
+        let certificates = parse_certificates(&message.x509_svid)?;
+        if certificates.is_empty() {
+            return Err("SVID has no certificate".into());
+        }
+
+        Ok(Self {
+            spiffe_id: message.spiffe_id,
+            certificates,
+            key: PKey::private_key_from_pkcs8(&message.x509_svid_key)?,
+            bundle: parse_certificates(&message.bundle)?,
+        })
+    }
+}
+
+/// Splits concatenated DER certificates.
+fn split_der(mut der: &[u8]) -> Result<Vec<&[u8]>, vector_common::Error> {
+    let mut certificates = Vec::new();
+    while !der.is_empty() {
+        // Each certificate is an ASN.1 sequence, starting with its tag and length.
+        let (header_len, content_len) = match der.get(1) {
+            Some(&len) if len < 0x80 => (2, len as usize),
+            Some(&len) => {
+                let len_bytes = (len & 0x7f) as usize;
+                let bytes = der
+                    .get(2..2 + len_bytes)
+                    .filter(|bytes| bytes.len() <= std::mem::size_of::<usize>())
+                    .ok_or("Invalid DER certificate length")?;
+                let len = bytes
+                    .iter()
+                    .fold(0_usize, |len, byte| len << 8 | *byte as usize);
+                (2 + len_bytes, len)
+            }
+            None => return Err("Truncated DER certificate".into()),
+        };
+        let end = header_len
+            .checked_add(content_len)
+            .filter(|end| *end <= der.len())
+            .ok_or("Truncated DER certificate")?;
+        certificates.push(&der[..end]);
+        der = &der[end..];
+    }
+    Ok(certificates)
+}
+
+/// The latest X.509 SVID issued by the Workload API at a socket.
+pub(super) struct X509Source {
+    /// The latest SVID, and the number of SVIDs received so far.
+    latest: Mutex<Option<(u64, Arc<X509Svid>)>>,
+    received: Condvar,
+}
+
+impl X509Source {
+    /// Gets the source of the SVIDs issued at a socket, watching it if it isn't already.
+    pub(super) fn get(socket: &Path) -> Arc<Self> {
+        let mut sources = SOURCES.lock().expect("Poisoned lock");
+        if let Some(source) = sources.get(socket) {
+            return Arc::clone(source);
+        }
+
+        let source = Arc::new(Self {
+            latest: Mutex::new(None),
+            received: Condvar::new(),
+        });
+        sources.insert(socket.to_owned(), Arc::clone(&source));
+
+        // The Workload API is watched on a dedicated thread, as the TLS settings are loaded
+        // synchronously, possibly from outside of a runtime.
+        let watched = Arc::clone(&source);
+        let socket = socket.to_owned();
+        std::thread::Builder::new()
+            .name("spiffe-workload-api".into())
+            .spawn(move || {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .expect("Unable to create the SPIFFE Workload API runtime")
+                    .block_on(watched.watch(socket));
+            })
+            .expect("Unable to spawn the SPIFFE Workload API thread");
+
+        source
+    }
+
+    /// Gets the latest SVID, along with its generation.
+    pub(super) fn latest(&self) -> Option<(u64, Arc<X509Svid>)> {
+        self.latest.lock().expect("Poisoned lock").clone()
+    }
+
+    /// Waits for the first SVID to be received.
+    pub(super) fn wait(&self, timeout: Duration) -> Option<(u64, Arc<X509Svid>)> {
+        let latest = self.latest.lock().expect("Poisoned lock");
+        let (latest, _) = self
+            .received
+            .wait_timeout_while(latest, timeout, |latest| latest.is_none())
+            .expect("Poisoned lock");
+        latest.clone()
+    }
+
+    async fn watch(&self, socket: PathBuf) {
+        loop {
+            match self.fetch(&socket).await {
+                Ok(()) => debug!(message = "SPIFFE Workload API closed the stream of SVIDs."),
+                Err(error) => error!(
+                    message = "Failed to fetch X.509 SVIDs from the SPIFFE Workload API.",
+                    %error,
+                    socket = ?socket,
+                    internal_log_rate_limit = true,
+                ),
+            }
+            tokio::time::sleep(RETRY_DELAY).await;
+        }
+    }
+
+    async fn fetch(&self, socket: &Path) -> Result<(), vector_common::Error> {
+        let mut client = tonic::client::Grpc::new(connect(socket).await?);
+        client.ready().await?;
+
+        let mut request = tonic::Request::new(X509SvidRequest {});
+        request
+            .metadata_mut()
+            .insert(SECURITY_HEADER, MetadataValue::from_static("true"));
+        let mut responses = client
+            .server_streaming(
+                request,
+                http::uri::PathAndQuery::from_static(FETCH_X509_SVID_PATH),
+                ProstCodec::<X509SvidRequest, X509SvidResponse>::default(),
+            )
+            .await?
+            .into_inner();
+
+        while let Some(response) = responses.message().await? {
+            // The first SVID is the default identity of the workload.
+            let message = response
+                .svids
+                .into_iter()
+                .next()
+                .ok_or("Workload API returned no SVID")?;
+            let svid = X509Svid::try_from(message)?;
+            debug!(message = "Received X.509 SVID.", spiffe_id = %svid.spiffe_id);
+
+            let mut latest = self.latest.lock().expect("Poisoned lock");
+            let generation = latest.as_ref().map_or(0, |(generation, _)| generation + 1);
+            *latest = Some((generation, Arc::new(svid)));
+            self.received.notify_all();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+async fn connect(socket: &Path) -> Result<Channel, vector_common::Error> {
+    use tokio::net::UnixStream;
+    use tonic::transport::{Endpoint, Uri};
+    use tower::service_fn;
+
+    let socket = socket.to_owned();
+    // The URI is ignored, as the connection is made to the socket.
+    Ok(Endpoint::try_from("http://localhost")?
+        .connect_with_connector(service_fn(move |_: Uri| {
+            UnixStream::connect(socket.clone())
+        }))
+        .await?)
+}
+
+#[cfg(not(unix))]
+async fn connect(_socket: &Path) -> Result<Channel, vector_common::Error> {
+    Err("The SPIFFE Workload API is only supported on Unix".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_concatenated_der() {
+        let pem = std::fs::read(crate::tls::TEST_PEM_INTERMEDIATE_CA_PATH).unwrap();
+        let certificates = X509::stack_from_pem(&pem).unwrap();
+        let der = certificates
+            .iter()
+            .flat_map(|certificate| certificate.to_der().unwrap())
+            .collect::<Vec<u8>>();
+
+        let split = split_der(&der).unwrap();
+        assert_eq!(split.len(), 2);
+        for (der, certificate) in split.into_iter().zip(&certificates) {
+            assert_eq!(X509::from_der(der).unwrap(), *certificate);
+        }
+
+        assert!(split_der(&der[..der.len() - 1]).is_err());
+    }
+}
@@ -6,6 +6,8 @@ pub struct CompileConfig {
     /// Custom context injected by the external environment
     custom: AnyMap,
     read_only_paths: BTreeSet<ReadOnlyPath>,
+    /// Whether the compiled program records which expressions run
+    track_coverage: bool,
 }
 
 impl CompileConfig {
@@ -55,6 +57,17 @@ impl CompileConfig {
         false
     }
 
+    /// Compiles the spans of the expressions of blocks into the program, so the expressions that
+    /// run can be recorded when coverage is enabled on the runtime.
+    pub fn set_track_coverage(&mut self, track_coverage: bool) {
+        self.track_coverage = track_coverage;
+    }
+
+    #[must_use]
+    pub fn tracks_coverage(&self) -> bool {
+        self.track_coverage
+    }
+
     /// Adds a path that is considered read only. Assignments to any paths that match
     /// will fail at compile time.
     pub fn set_read_only_path(&mut self, path: OwnedTargetPath, recursive: bool) {
@@ -68,6 +81,7 @@ impl Default for CompileConfig {
         Self {
             custom: AnyMap::new(),
             read_only_paths: BTreeSet::new(),
+            track_coverage: false,
         }
     }
 }
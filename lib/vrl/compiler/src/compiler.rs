@@ -52,6 +52,9 @@ pub struct Compiler<'a> {
     // the error from the LHS)
     fallible_expression_error: Option<Box<dyn DiagnosticMessage>>,
 
+    /// The spans of the expressions of the compiled blocks, if coverage is tracked.
+    block_spans: Vec<Span>,
+
     config: CompileConfig,
 }
 
@@ -74,9 +77,12 @@ impl<'a> Compiler<'a> {
             external_assignments: vec![],
             skip_missing_query_target: vec![],
             fallible_expression_error: None,
+            block_spans: vec![],
             config,
         };
-        let expressions = compiler.compile_root_exprs(ast, &mut state);
+        let (expressions, spans) = compiler.compile_root_exprs(ast, &mut state);
+        compiler.block_spans.extend_from_slice(&spans);
+        compiler.block_spans.sort_unstable();
 
         let (errors, warnings): (Vec<_>, Vec<_>) =
             compiler.diagnostics.into_iter().partition(|diagnostic| {
@@ -89,7 +95,7 @@ impl<'a> Compiler<'a> {
 
         let result = CompilationResult {
             program: Program {
-                expressions: Block::new_inline(expressions),
+                expressions: Block::new_inline(expressions).with_spans(spans),
                 info: ProgramInfo {
                     fallible: compiler.fallible,
                     abortable: compiler.abortable,
@@ -97,6 +103,7 @@ impl<'a> Compiler<'a> {
                     target_assignments: compiler.external_assignments,
                 },
                 initial_state,
+                block_spans: compiler.block_spans,
             },
             warnings: warnings.into(),
             config: compiler.config,
@@ -225,20 +232,25 @@ impl<'a> Compiler<'a> {
         &mut self,
         nodes: impl IntoIterator<Item = Node<ast::RootExpr>>,
         state: &mut TypeState,
-    ) -> Vec<Expr> {
+    ) -> (Vec<Expr>, Vec<Span>) {
         let mut node_exprs = vec![];
+        let mut spans = vec![];
 
         for root_expr in nodes {
             match root_expr.into_inner() {
                 RootExpr::Expr(node_expr) => {
                     self.fallible_expression_error = None;
 
+                    let span = node_expr.span();
                     if let Some(expr) = self.compile_expr(node_expr, state) {
                         if let Some(error) = self.fallible_expression_error.take() {
                             self.diagnostics.push(error);
                         }
 
                         node_exprs.push(expr);
+                        if self.config.tracks_coverage() {
+                            spans.push(span);
+                        }
                     }
                 }
                 RootExpr::Error(err) => self.handle_parser_error(err),
@@ -248,7 +260,7 @@ impl<'a> Compiler<'a> {
         if node_exprs.is_empty() {
             node_exprs.push(Expr::Noop(Noop));
         }
-        node_exprs
+        (node_exprs, spans)
     }
 
     fn compile_block(&mut self, node: Node<ast::Block>, state: &mut TypeState) -> Option<Block> {
@@ -262,8 +274,15 @@ impl<'a> Compiler<'a> {
         state: &mut TypeState,
     ) -> Option<(Block, TypeDef)> {
         let original_state = state.clone();
-        let exprs = self.compile_exprs(node.into_inner().into_iter(), state)?;
-        let block = Block::new_scoped(exprs);
+        let nodes = node.into_inner().into_inner();
+        let spans = if self.config.tracks_coverage() {
+            nodes.iter().map(Node::span).collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+        let exprs = self.compile_exprs(nodes, state)?;
+        self.block_spans.extend_from_slice(&spans);
+        let block = Block::new_scoped(exprs).with_spans(spans);
 
         // The type information from `compile_exprs` doesn't applying the "scoping" from the block.
         // This is recalculated using the block.
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use diagnostic::Span;
+
 use crate::state::{TypeInfo, TypeState};
 use crate::{
     expression::{Expr, Resolved},
@@ -13,12 +15,26 @@ pub struct Block {
     // false - This is just an inline block of code
     // true - This is a block of code nested in a child scope
     new_scope: bool,
+
+    // The spans of the expressions in the source, used to track coverage. This is empty unless
+    // the program was compiled with coverage tracking.
+    spans: Vec<Span>,
 }
 
 impl Block {
     #[must_use]
     fn new(inner: Vec<Expr>, new_scope: bool) -> Self {
-        Self { inner, new_scope }
+        Self {
+            inner,
+            new_scope,
+            spans: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn with_spans(mut self, spans: Vec<Span>) -> Self {
+        self.spans = spans;
+        self
     }
 
     #[must_use]
@@ -48,10 +64,26 @@ impl Expression for Block {
         // in scope can be accessed here, so it doesn't need to be checked at runtime.
         let (last, other) = self.inner.split_last().expect("at least one expression");
 
-        other
-            .iter()
-            .try_for_each(|expr| expr.resolve(ctx).map(|_| ()))?;
+        if self.spans.is_empty() {
+            other
+                .iter()
+                .try_for_each(|expr| expr.resolve(ctx).map(|_| ()))?;
 
+            return last.resolve(ctx);
+        }
+
+        let mut spans = self.spans.iter();
+
+        for expr in other {
+            if let Some(span) = spans.next() {
+                ctx.state_mut().cover(*span);
+            }
+            expr.resolve(ctx)?;
+        }
+
+        if let Some(span) = spans.next() {
+            ctx.state_mut().cover(*span);
+        }
         last.resolve(ctx)
     }
 
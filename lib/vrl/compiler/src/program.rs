@@ -1,3 +1,4 @@
+use diagnostic::Span;
 use lookup::OwnedTargetPath;
 
 use crate::state::TypeState;
@@ -12,6 +13,7 @@ pub struct Program {
     pub(crate) initial_state: TypeState,
     pub(crate) expressions: Block,
     pub(crate) info: ProgramInfo,
+    pub(crate) block_spans: Vec<Span>,
 }
 
 impl Program {
@@ -34,6 +36,17 @@ impl Program {
         &self.info
     }
 
+    /// The spans of the expressions of all the blocks of the program, in the order they appear in
+    /// the source. This is empty unless the program was compiled with
+    /// [`CompileConfig::set_track_coverage`](crate::CompileConfig::set_track_coverage).
+    ///
+    /// When coverage is enabled on the [`Runtime`](crate::state::Runtime), the spans of the
+    /// expressions that run are recorded, so these are all of the spans that can be covered.
+    #[must_use]
+    pub fn block_spans(&self) -> &[Span] {
+        &self.block_spans
+    }
+
     /// Resolve the program to its final [`Value`].
     ///
     /// # Errors
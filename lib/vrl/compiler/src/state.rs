@@ -1,5 +1,7 @@
 use lookup::PathPrefix;
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::{hash_map::Entry, BTreeSet, HashMap};
+
+use diagnostic::Span;
 use value::{Kind, Value};
 
 use crate::{parser::ast::Ident, type_def::Details, value::Collection, TypeDef};
@@ -164,6 +166,9 @@ impl ExternalEnv {
 pub struct Runtime {
     /// The [`Value`] stored in each variable.
     variables: HashMap<Ident, Value>,
+
+    /// The spans of the expressions of blocks that ran, if coverage is tracked.
+    coverage: Option<BTreeSet<Span>>,
 }
 
 impl Runtime {
@@ -172,6 +177,30 @@ impl Runtime {
         self.variables.is_empty()
     }
 
+    /// Starts tracking which expressions of the blocks of programs run, for programs compiled
+    /// with [`CompileConfig::set_track_coverage`](crate::CompileConfig::set_track_coverage).
+    ///
+    /// Unlike variables, the coverage isn't reset by [`Runtime::clear`], so it accumulates over
+    /// runs until it is taken.
+    pub fn enable_coverage(&mut self) {
+        self.coverage.get_or_insert_with(BTreeSet::new);
+    }
+
+    /// Takes the spans of the expressions that ran since coverage was last taken, out of those
+    /// returned by [`Program::block_spans`](crate::Program::block_spans).
+    pub fn take_coverage(&mut self) -> BTreeSet<Span> {
+        self.coverage
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn cover(&mut self, span: Span) {
+        if let Some(coverage) = &mut self.coverage {
+            coverage.insert(span);
+        }
+    }
+
     pub fn clear(&mut self) {
         self.variables.clear();
     }
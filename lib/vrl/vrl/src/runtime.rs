@@ -1,6 +1,7 @@
-use std::{error::Error, fmt};
+use std::{collections::BTreeSet, error::Error, fmt};
 
 use compiler::ExpressionError;
+use diagnostic::Span;
 use lookup::OwnedTargetPath;
 use value::Value;
 
@@ -64,6 +65,16 @@ impl Runtime {
         self.state.clear();
     }
 
+    /// Starts tracking which expressions of the blocks of programs run.
+    pub fn enable_coverage(&mut self) {
+        self.state.enable_coverage();
+    }
+
+    /// Takes the spans of the expressions that ran since coverage was last taken.
+    pub fn take_coverage(&mut self) -> BTreeSet<Span> {
+        self.state.take_coverage()
+    }
+
     /// Given the provided [`Target`], resolve the provided [`Program`] to
     /// completion.
     pub fn resolve(
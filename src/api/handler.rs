@@ -1,11 +1,29 @@
-use std::sync::{
-    atomic::{self, AtomicBool},
-    Arc,
+use std::{
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
+    time::Duration,
 };
 
+use serde::Deserialize;
 use serde_json::json;
+use tokio::sync::oneshot;
+use vector_common::sensitive_string::SensitiveString;
 use warp::{reply::json, Rejection, Reply};
 
+use super::reload::{ReloadRequest, ReloadResponse, ReloadTx};
+use crate::{
+    audit,
+    config::ConfigBuilder,
+    signal::{SignalTo, SignalTx},
+    topology::{
+        drain::DrainStatus,
+        health::{self, ReadinessPolicy},
+        WatchRx,
+    },
+};
+
 // Health handler, responds with '{ ok: true }' when running and '{ ok: false}'
 // when shutting down
 pub(super) async fn health(running: Arc<AtomicBool>) -> Result<impl Reply, Rejection> {
@@ -21,3 +39,197 @@ pub(super) async fn health(running: Arc<AtomicBool>) -> Result<impl Reply, Rejec
         ))
     }
 }
+
+// Component health handlers, respond with the liveness or readiness of each component, and with
+// '503 Service Unavailable' when shutting down, or when any of the components gating the overall
+// readiness isn't live or ready respectively.
+pub(super) async fn liveness(
+    running: Arc<AtomicBool>,
+    watch_rx: WatchRx,
+    policy: Arc<ReadinessPolicy>,
+) -> Result<impl Reply, Rejection> {
+    Ok(component_health(&running, &watch_rx, &policy, false))
+}
+
+pub(super) async fn readiness(
+    running: Arc<AtomicBool>,
+    watch_rx: WatchRx,
+    policy: Arc<ReadinessPolicy>,
+) -> Result<impl Reply, Rejection> {
+    Ok(component_health(&running, &watch_rx, &policy, true))
+}
+
+fn component_health(
+    running: &AtomicBool,
+    watch_rx: &WatchRx,
+    policy: &ReadinessPolicy,
+    readiness: bool,
+) -> impl Reply {
+    let report = health::report(&watch_rx.borrow().buffers, policy);
+    let ok = running.load(atomic::Ordering::Relaxed)
+        && if readiness { report.ready } else { report.live };
+    let status = if ok {
+        warp::http::StatusCode::OK
+    } else {
+        warp::http::StatusCode::SERVICE_UNAVAILABLE
+    };
+    warp::reply::with_status(
+        json(&json!({"ok": ok, "components": report.components})),
+        status,
+    )
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(super) struct DrainQuery {
+    timeout_secs: Option<u64>,
+}
+
+// Drain handler. Authenticates the request against the configured reload token, then requests the
+// application to drain the topology and shut down, unless a drain is already underway, and
+// responds with the drain progress.
+pub(super) async fn drain(
+    token: Arc<SensitiveString>,
+    authorization: Option<String>,
+    query: DrainQuery,
+    drain_status: DrainStatus,
+    signal_tx: SignalTx,
+) -> Result<impl Reply, Rejection> {
+    if !is_authorized(&token, authorization.as_deref()) {
+        audit::record(
+            "drain_requested",
+            json!({ "timeout_secs": query.timeout_secs, "authorized": false }),
+        );
+        return Ok(warp::reply::with_status(
+            json(&json!({"reason": "invalid or missing reload token"})),
+            warp::http::StatusCode::UNAUTHORIZED,
+        ));
+    }
+
+    let requested = drain_status.request();
+    audit::record(
+        "drain_requested",
+        json!({
+            "timeout_secs": query.timeout_secs,
+            "authorized": true,
+            "already_draining": !requested,
+        }),
+    );
+    if requested {
+        let timeout = query.timeout_secs.map(Duration::from_secs);
+        if signal_tx.send(SignalTo::Drain(timeout)).is_err() {
+            return Ok(warp::reply::with_status(
+                json(&json!({"reason": "unable to request a drain"})),
+                warp::http::StatusCode::SERVICE_UNAVAILABLE,
+            ));
+        }
+    }
+
+    Ok(warp::reply::with_status(
+        json(&drain_status.progress()),
+        warp::http::StatusCode::ACCEPTED,
+    ))
+}
+
+// Drain progress handler.
+pub(super) async fn drain_progress(drain_status: DrainStatus) -> Result<impl Reply, Rejection> {
+    Ok(json(&drain_status.progress()))
+}
+
+// Checks the bearer token of a request against the configured token, in constant time.
+pub(super) fn is_authorized(token: &SensitiveString, authorization: Option<&str>) -> bool {
+    let expected = token.inner().as_bytes();
+    authorization
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map_or(false, |value| {
+            value.len() == expected.len() && openssl::memcmp::eq(value.as_bytes(), expected)
+        })
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(super) struct ReloadQuery {
+    dry_run: bool,
+}
+
+// Config reload handler. Authenticates the request against the configured reload token, and
+// forwards the new config to the application loop, responding with the outcome of the reload.
+pub(super) async fn reload(
+    token: Arc<SensitiveString>,
+    authorization: Option<String>,
+    query: ReloadQuery,
+    builder: ConfigBuilder,
+    reload_tx: ReloadTx,
+) -> Result<impl Reply, Rejection> {
+    if !is_authorized(&token, authorization.as_deref()) {
+        audit::record(
+            "api_config_reload",
+            json!({ "dry_run": query.dry_run, "authorized": false }),
+        );
+        return Ok(warp::reply::with_status(
+            json(&json!({"reason": "invalid or missing reload token"})),
+            warp::http::StatusCode::UNAUTHORIZED,
+        ));
+    }
+
+    let (respond_to, response_rx) = oneshot::channel();
+    let request = ReloadRequest {
+        builder,
+        dry_run: query.dry_run,
+        respond_to,
+    };
+    let response = match reload_tx.send(request).await {
+        Ok(()) => response_rx.await.unwrap_or(ReloadResponse::Unavailable),
+        Err(_) => ReloadResponse::Unavailable,
+    };
+    audit::record(
+        "api_config_reload",
+        json!({ "dry_run": query.dry_run, "authorized": true, "response": &response }),
+    );
+
+    Ok(warp::reply::with_status(
+        json(&response),
+        response.status_code(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::broadcast;
+    use warp::http::StatusCode;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn drain_requires_reload_token() {
+        let token = Arc::new(SensitiveString::from("secret".to_owned()));
+        let drain_status = DrainStatus::default();
+        let (signal_tx, mut signal_rx) = broadcast::channel(1);
+
+        let response = drain(
+            Arc::clone(&token),
+            Some("Bearer wrong".to_owned()),
+            DrainQuery::default(),
+            drain_status.clone(),
+            signal_tx.clone(),
+        )
+        .await
+        .unwrap()
+        .into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(signal_rx.try_recv().is_err());
+
+        let response = drain(
+            token,
+            Some("Bearer secret".to_owned()),
+            DrainQuery::default(),
+            drain_status,
+            signal_tx,
+        )
+        .await
+        .unwrap()
+        .into_response();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        assert!(matches!(signal_rx.try_recv(), Ok(SignalTo::Drain(None))));
+    }
+}
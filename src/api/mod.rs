@@ -1,5 +1,7 @@
 #![allow(missing_docs)]
 mod handler;
+mod profiling;
+pub mod reload;
 mod schema;
 mod server;
 pub mod tap;
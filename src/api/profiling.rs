@@ -0,0 +1,151 @@
+//! Profiling endpoints, serving profiles that can be read with `pprof`.
+//!
+//! CPU profiles are collected by sampling the stacks of all threads for the requested duration,
+//! and require the `profiling` feature. Heap profiles are dumped by jemalloc's sampling heap
+//! profiler, and require the `heap-profiling` feature, which enables the profiler on startup.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::json;
+use warp::{
+    http::StatusCode,
+    reply::{json, Response},
+    Rejection, Reply,
+};
+
+const DEFAULT_CPU_PROFILE_SECS: u64 = 30;
+const MAX_CPU_PROFILE_SECS: u64 = 300;
+
+/// The frequency at which stacks are sampled, in hertz. This is slightly off from 100 to avoid
+/// sampling in lockstep with periodic work.
+#[cfg(all(unix, feature = "profiling"))]
+const CPU_PROFILE_FREQUENCY: i32 = 99;
+
+enum ProfileError {
+    /// Vector wasn't built or started with support for this profile.
+    Unsupported(&'static str),
+    Failed(String),
+}
+
+impl ProfileError {
+    fn into_response(self) -> Response {
+        let (status, reason) = match self {
+            Self::Unsupported(reason) => (StatusCode::NOT_IMPLEMENTED, reason.to_string()),
+            Self::Failed(reason) => (StatusCode::INTERNAL_SERVER_ERROR, reason),
+        };
+        warp::reply::with_status(json(&json!({ "reason": reason })), status).into_response()
+    }
+}
+
+fn into_response(profile: Result<Vec<u8>, ProfileError>) -> Response {
+    match profile {
+        Ok(profile) => {
+            warp::reply::with_header(profile, "content-type", "application/octet-stream")
+                .into_response()
+        }
+        Err(error) => error.into_response(),
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(super) struct CpuProfileQuery {
+    seconds: Option<u64>,
+}
+
+// CPU profile handler. Samples the stacks of all threads for the requested number of seconds,
+// and responds with the profile encoded as a pprof protobuf.
+pub(super) async fn cpu(query: CpuProfileQuery) -> Result<impl Reply, Rejection> {
+    let seconds = query
+        .seconds
+        .unwrap_or(DEFAULT_CPU_PROFILE_SECS)
+        .clamp(1, MAX_CPU_PROFILE_SECS);
+    Ok(into_response(
+        cpu_profile(Duration::from_secs(seconds)).await,
+    ))
+}
+
+// Heap profile handler. Responds with the allocations sampled by jemalloc that are still live, in
+// jemalloc's heap profile format.
+pub(super) async fn heap() -> Result<impl Reply, Rejection> {
+    Ok(into_response(heap_profile().await))
+}
+
+#[cfg(all(unix, feature = "profiling"))]
+async fn cpu_profile(duration: Duration) -> Result<Vec<u8>, ProfileError> {
+    use pprof::protos::Message;
+
+    let failed = |error: pprof::Error| ProfileError::Failed(error.to_string());
+
+    // Only one profiler can run at a time, so a concurrent request fails here.
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(CPU_PROFILE_FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .map_err(failed)?;
+    tokio::time::sleep(duration).await;
+
+    let profile = guard
+        .report()
+        .build()
+        .map_err(failed)?
+        .pprof()
+        .map_err(failed)?;
+    Ok(profile.encode_to_vec())
+}
+
+#[cfg(not(all(unix, feature = "profiling")))]
+async fn cpu_profile(_duration: Duration) -> Result<Vec<u8>, ProfileError> {
+    Err(ProfileError::Unsupported(
+        "CPU profiling requires Vector to be built with the `profiling` feature.",
+    ))
+}
+
+#[cfg(all(unix, feature = "heap-profiling"))]
+async fn heap_profile() -> Result<Vec<u8>, ProfileError> {
+    use std::{
+        ffi::CString,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    static DUMPS: AtomicUsize = AtomicUsize::new(0);
+
+    let failed = |error: &dyn std::fmt::Display| ProfileError::Failed(error.to_string());
+
+    // Safety: `opt.prof` is a boolean.
+    let enabled = unsafe { tikv_jemalloc_ctl::raw::read::<bool>(b"opt.prof\0") }
+        .map_err(|error| failed(&error))?;
+    if !enabled {
+        return Err(ProfileError::Unsupported(
+            "Heap profiling is disabled in the allocator options.",
+        ));
+    }
+
+    // jemalloc can only write the profile to a file, which is read back and removed.
+    let path = std::env::temp_dir().join(format!(
+        "vector-{}-{}.heap",
+        std::process::id(),
+        DUMPS.fetch_add(1, Ordering::Relaxed)
+    ));
+    let c_path =
+        CString::new(path.to_string_lossy().into_owned()).map_err(|error| failed(&error))?;
+    tokio::task::spawn_blocking(move || {
+        // Safety: `prof.dump` takes a pointer to a NUL-terminated path, which outlives the call.
+        unsafe { tikv_jemalloc_ctl::raw::write(b"prof.dump\0", c_path.as_ptr()) }
+    })
+    .await
+    .map_err(|error| failed(&error))?
+    .map_err(|error| failed(&error))?;
+
+    let profile = tokio::fs::read(&path).await.map_err(|error| failed(&error));
+    let _ = tokio::fs::remove_file(&path).await;
+    profile
+}
+
+#[cfg(not(all(unix, feature = "heap-profiling")))]
+async fn heap_profile() -> Result<Vec<u8>, ProfileError> {
+    Err(ProfileError::Unsupported(
+        "Heap profiling requires Vector to be built with the `heap-profiling` feature.",
+    ))
+}
@@ -0,0 +1,336 @@
+//! Config reload over the API.
+//!
+//! The API server doesn't own the running topology, so reload requests are forwarded to the main
+//! application loop, which validates the new config, computes the diff against the running
+//! config, and (unless this is a dry run) applies it, rolling back to the previous config if any
+//! of the new components fail to start. Dry runs build the added and changed components without
+//! starting them.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+use stream_cancel::Tripwire;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use vector_buffers::{BufferConfig, BufferType};
+use warp::http::StatusCode;
+
+use crate::{
+    config::{ComponentKey, Config, ConfigBuilder, ConfigDiff, Difference},
+    topology::{builder::build_pieces, ReloadOutcome, TopologyController},
+};
+
+pub type ReloadTx = mpsc::Sender<ReloadRequest>;
+pub type ReloadRx = mpsc::Receiver<ReloadRequest>;
+
+/// Creates the channel used to forward reload requests from the API server.
+pub fn channel() -> (ReloadTx, ReloadRx) {
+    // Reloads are applied one at a time, so there is no point in queueing up more than a few.
+    mpsc::channel(1)
+}
+
+/// A request to reload the running topology with a new config.
+#[derive(Debug)]
+pub struct ReloadRequest {
+    pub builder: ConfigBuilder,
+    pub dry_run: bool,
+    pub respond_to: oneshot::Sender<ReloadResponse>,
+}
+
+/// Changes to the components of a single kind, by component ID.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ComponentChanges {
+    pub added: Vec<String>,
+    pub changed: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl ComponentChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+fn sorted_ids(keys: &HashSet<ComponentKey>) -> Vec<String> {
+    let mut ids = keys.iter().map(ToString::to_string).collect::<Vec<_>>();
+    ids.sort();
+    ids
+}
+
+impl From<&Difference> for ComponentChanges {
+    fn from(difference: &Difference) -> Self {
+        Self {
+            added: sorted_ids(&difference.to_add),
+            changed: sorted_ids(&difference.to_change),
+            removed: sorted_ids(&difference.to_remove),
+        }
+    }
+}
+
+/// Component-level difference between the running config and the new config.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ReloadDiff {
+    pub sources: ComponentChanges,
+    pub transforms: ComponentChanges,
+    pub sinks: ComponentChanges,
+    pub enrichment_tables: ComponentChanges,
+}
+
+impl ReloadDiff {
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+            && self.transforms.is_empty()
+            && self.sinks.is_empty()
+            && self.enrichment_tables.is_empty()
+    }
+}
+
+impl From<&ConfigDiff> for ReloadDiff {
+    fn from(diff: &ConfigDiff) -> Self {
+        Self {
+            sources: (&diff.sources).into(),
+            transforms: (&diff.transforms).into(),
+            sinks: (&diff.sinks).into(),
+            enrichment_tables: (&diff.enrichment_tables).into(),
+        }
+    }
+}
+
+/// Result of a reload request.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ReloadResponse {
+    /// The config is valid, but was not applied as this was a dry run.
+    Validated { diff: ReloadDiff },
+
+    /// The config was applied.
+    Applied { diff: ReloadDiff },
+
+    /// The config failed to build, and was not applied.
+    Invalid { errors: Vec<String> },
+
+    /// The config could not be applied, and the previous config was restored.
+    RolledBack { diff: ReloadDiff },
+
+    /// The config could not be applied, and the previous config could not be restored either.
+    ///
+    /// Vector will shut down after responding.
+    Failed { diff: ReloadDiff },
+
+    /// The config is missing the API key required by the enterprise integration.
+    MissingApiKey,
+
+    /// Vector is shutting down, and no longer accepts reloads.
+    Unavailable,
+}
+
+impl ReloadResponse {
+    pub const fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Validated { .. } => StatusCode::OK,
+            Self::Applied { .. } => StatusCode::CREATED,
+            Self::Invalid { .. } | Self::RolledBack { .. } | Self::MissingApiKey => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            Self::Failed { .. } | Self::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
+
+/// Applies the reload requests forwarded by the API server, until `shutdown` is triggered.
+///
+/// If a reload fails and the previous config can't be restored, a graceful shutdown is triggered
+/// through `crash_tx` after responding to the request.
+pub async fn handle_requests(
+    mut reload_rx: ReloadRx,
+    topology_controller: Arc<Mutex<TopologyController>>,
+    crash_tx: mpsc::UnboundedSender<()>,
+    shutdown: Tripwire,
+) {
+    tokio::pin!(shutdown);
+
+    loop {
+        let request = tokio::select! {
+            request = reload_rx.recv() => match request {
+                Some(request) => request,
+                None => break,
+            },
+            _ = &mut shutdown => break,
+        };
+
+        let response = reload(&topology_controller, request.builder, request.dry_run).await;
+        let failed = matches!(response, ReloadResponse::Failed { .. });
+        let _ = request.respond_to.send(response);
+
+        if failed {
+            let _ = crash_tx.send(());
+            break;
+        }
+    }
+}
+
+async fn reload(
+    topology_controller: &Mutex<TopologyController>,
+    builder: ConfigBuilder,
+    dry_run: bool,
+) -> ReloadResponse {
+    let mut new_config = match builder.build() {
+        Ok(new_config) => new_config,
+        Err(errors) => return ReloadResponse::Invalid { errors },
+    };
+
+    let mut topology_controller = topology_controller.lock().await;
+    let config_diff = ConfigDiff::new(topology_controller.topology.config(), &new_config);
+    let diff = ReloadDiff::from(&config_diff);
+
+    if dry_run {
+        return match dry_run_build(&mut new_config, &config_diff).await {
+            Ok(()) => ReloadResponse::Validated { diff },
+            Err(errors) => ReloadResponse::Invalid { errors },
+        };
+    }
+
+    match topology_controller.reload(Some(new_config)).await {
+        ReloadOutcome::Success => ReloadResponse::Applied { diff },
+        ReloadOutcome::RolledBack => ReloadResponse::RolledBack { diff },
+        ReloadOutcome::FatalError => ReloadResponse::Failed { diff },
+        ReloadOutcome::MissingApiKey => ReloadResponse::MissingApiKey,
+        ReloadOutcome::NoConfig => unreachable!("Some(config) was passed above"),
+    }
+}
+
+/// Builds the components added or changed by the given diff, without starting them.
+///
+/// Changed sinks with a disk buffer would contend with the running sink for the lock of the buffer,
+/// so they are built with an in-memory buffer instead. The config isn't applied, so it is fine to
+/// modify it here.
+async fn dry_run_build(new_config: &mut Config, diff: &ConfigDiff) -> Result<(), Vec<String>> {
+    for (key, sink) in new_config.sinks_mut() {
+        let has_disk_stage = sink
+            .buffer
+            .stages()
+            .iter()
+            .any(|stage| !matches!(stage, BufferType::Memory { .. }));
+        if has_disk_stage && diff.sinks.to_change.contains(key) {
+            sink.buffer = BufferConfig::default();
+        }
+    }
+
+    build_pieces(new_config, diff, HashMap::new(), Default::default())
+        .await
+        .map(|_pieces| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::config::{load_from_str, Format};
+
+    #[test]
+    fn diff_lists_sorted_component_ids() {
+        let old = load_from_str(
+            indoc! {r#"
+                [sources.in]
+                type = "test_basic"
+
+                [sinks.removed]
+                type = "test_basic"
+                inputs = ["in"]
+
+                [sinks.changed]
+                type = "test_basic"
+                inputs = ["in"]
+            "#},
+            Format::Toml,
+        )
+        .unwrap();
+
+        let new = load_from_str(
+            indoc! {r#"
+                [sources.in]
+                type = "test_basic"
+
+                [sinks.b_added]
+                type = "test_basic"
+                inputs = ["in"]
+
+                [sinks.a_added]
+                type = "test_basic"
+                inputs = ["in"]
+
+                [sinks.changed]
+                type = "test_basic"
+                inputs = ["in"]
+                data = "changed"
+            "#},
+            Format::Toml,
+        )
+        .unwrap();
+
+        let diff = ReloadDiff::from(&ConfigDiff::new(&old, &new));
+        assert!(diff.sources.is_empty());
+        assert!(diff.transforms.is_empty());
+        assert_eq!(
+            diff.sinks,
+            ComponentChanges {
+                added: vec!["a_added".into(), "b_added".into()],
+                changed: vec!["changed".into()],
+                removed: vec!["removed".into()],
+            }
+        );
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn response_is_tagged_by_status() {
+        let response = ReloadResponse::Invalid {
+            errors: vec!["bad".into()],
+        };
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({ "status": "invalid", "errors": ["bad"] })
+        );
+        assert_eq!(response.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[cfg(feature = "transforms-remap")]
+    #[tokio::test]
+    async fn dry_run_builds_changed_components() {
+        let load = |source: &str| {
+            load_from_str(
+                &format!(
+                    indoc! {r#"
+                        [sources.in]
+                        type = "test_basic"
+
+                        [transforms.remap]
+                        type = "remap"
+                        inputs = ["in"]
+                        source = '{}'
+
+                        [sinks.out]
+                        type = "test_basic"
+                        inputs = ["remap"]
+                    "#},
+                    source
+                ),
+                Format::Toml,
+            )
+            .unwrap()
+        };
+
+        let old = load(".foo = 1");
+        let mut valid = load(".foo = 2");
+        let diff = ConfigDiff::new(&old, &valid);
+        assert_eq!(dry_run_build(&mut valid, &diff).await, Ok(()));
+
+        let mut invalid = load(".foo = ");
+        let diff = ConfigDiff::new(&old, &invalid);
+        let errors = dry_run_build(&mut invalid, &diff).await.unwrap_err();
+        assert!(errors[0].starts_with("Transform \"remap\""), "{:?}", errors);
+    }
+}
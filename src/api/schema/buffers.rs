@@ -0,0 +1,297 @@
+use std::{
+    io,
+    path::{Component, Path, PathBuf},
+    time::Duration,
+};
+
+use async_graphql::{Context, Object, SimpleObject};
+use bytes::{BufMut, Bytes, BytesMut};
+use codecs::{decoding::format::Deserializer, NativeJsonDeserializerConfig, NativeJsonSerializer};
+use tokio::{
+    fs::{self, File, OpenOptions},
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+};
+use tokio_util::codec::Encoder;
+use vector_buffers::topology::channel::BufferControl;
+use vector_common::{finalization::EventFinalizers, sensitive_string::SensitiveString};
+use vector_core::config::LogNamespace;
+
+use crate::{
+    api::handler::is_authorized,
+    audit,
+    config::ComponentKey,
+    event::{EventArray, EventContainer, EventStatus, Finalizable},
+    topology::WatchRx,
+};
+
+/// The subdirectory of the data directory that buffers are exported to and requeued from.
+const EXPORTS_SUBDIR: &str = "buffer_exports";
+
+/// How long to wait for a sink to hand its buffer over, and then for each read from the buffer.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Options of the buffer mutations, set when the API server starts.
+pub struct BufferMutations {
+    /// The token authorizing the mutations. The mutations are rejected if it isn't set.
+    pub token: Option<SensitiveString>,
+    /// The data directory, holding the files that buffers are exported to and requeued from.
+    pub data_dir: Option<PathBuf>,
+}
+
+/// The `Authorization` header of a request.
+pub struct Authorization(pub Option<String>);
+
+/// Events removed from, or added to, the buffer of a sink
+#[derive(SimpleObject)]
+pub struct BufferDrain {
+    /// Number of events removed from, or added to, the buffer
+    events: i64,
+}
+
+impl BufferDrain {
+    fn new(events: usize) -> Self {
+        Self {
+            events: events as i64,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct BuffersMutation;
+
+#[Object]
+impl BuffersMutation {
+    /// Removes all of the events held in the buffer of a sink, and drops them. The events are
+    /// rejected, so sources acknowledging them don't consider them delivered. Requires the
+    /// `api.reload_token` as a bearer token.
+    async fn purge_buffer(
+        &self,
+        ctx: &Context<'_>,
+        component_id: String,
+    ) -> async_graphql::Result<BufferDrain> {
+        let authorized = authorize(ctx);
+        audit::record(
+            "buffer_purge",
+            serde_json::json!({ "component_id": component_id, "authorized": authorized.is_ok() }),
+        );
+        authorized?;
+
+        let control = buffer_control(ctx, &component_id)?;
+        let mut drain = control.drain(READ_TIMEOUT).await?;
+        let mut events = 0;
+        while let Some(mut array) = drain.next().await {
+            events += array.len();
+            array.take_finalizers().update_status(EventStatus::Rejected);
+        }
+
+        Ok(BufferDrain::new(events))
+    }
+
+    /// Removes all of the events held in the buffer of a sink, and writes them as newline delimited
+    /// native JSON to a new file, at the given path relative to the `buffer_exports` subdirectory
+    /// of the data directory. The events are rejected once the file is written, so sources
+    /// acknowledging them don't consider them delivered, and can be added back to the buffer with
+    /// `requeueBuffer`. Requires the `api.reload_token` as a bearer token.
+    async fn export_buffer(
+        &self,
+        ctx: &Context<'_>,
+        component_id: String,
+        path: String,
+    ) -> async_graphql::Result<BufferDrain> {
+        let authorized = authorize(ctx);
+        audit::record(
+            "buffer_export",
+            serde_json::json!({
+                "component_id": component_id,
+                "path": path,
+                "authorized": authorized.is_ok(),
+            }),
+        );
+        let config = authorized?;
+
+        let control = buffer_control(ctx, &component_id)?;
+        let path = export_path(config, &path, true).await?;
+        // The file is created first, so the events aren't removed from the buffer if it can't be.
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await
+            .map_err(|error| match error.kind() {
+                io::ErrorKind::AlreadyExists => {
+                    format!("The file {:?} already exists.", path.display())
+                }
+                _ => error.to_string(),
+            })?;
+        let mut drain = match control.drain(READ_TIMEOUT).await {
+            Ok(drain) => drain,
+            Err(error) => {
+                drop(file);
+                let _ = fs::remove_file(&path).await;
+                return Err(error.into());
+            }
+        };
+
+        // The events are only finalized once they're safely written, as they're removed from the
+        // buffer either way.
+        let mut finalizers = EventFinalizers::default();
+        let mut events = 0;
+        let mut written = Ok(());
+        while let Some(mut array) = drain.next().await {
+            finalizers.merge(array.take_finalizers());
+            events += array.len();
+            written = write_events(&mut file, array).await;
+            if written.is_err() {
+                break;
+            }
+        }
+        drop(drain);
+        if written.is_ok() {
+            written = file.sync_all().await.map_err(Into::into);
+        }
+
+        match written {
+            Ok(()) => {
+                finalizers.update_status(EventStatus::Rejected);
+                Ok(BufferDrain::new(events))
+            }
+            Err(error) => {
+                finalizers.update_status(EventStatus::Errored);
+                Err(error.into())
+            }
+        }
+    }
+
+    /// Adds the events of a file written by `exportBuffer` back to the buffer of a sink, ahead of
+    /// the events it holds. The path is relative to the `buffer_exports` subdirectory of the data
+    /// directory. Requires the `api.reload_token` as a bearer token.
+    async fn requeue_buffer(
+        &self,
+        ctx: &Context<'_>,
+        component_id: String,
+        path: String,
+    ) -> async_graphql::Result<BufferDrain> {
+        let authorized = authorize(ctx);
+        audit::record(
+            "buffer_requeue",
+            serde_json::json!({
+                "component_id": component_id,
+                "path": path,
+                "authorized": authorized.is_ok(),
+            }),
+        );
+        let config = authorized?;
+
+        let control = buffer_control(ctx, &component_id)?;
+        let path = export_path(config, &path, false).await?;
+        let mut lines = BufReader::new(File::open(&path).await?).lines();
+        let deserializer = NativeJsonDeserializerConfig.build();
+
+        let mut events = 0;
+        while let Some(line) = lines.next_line().await? {
+            let parsed = deserializer
+                .parse(Bytes::from(line), LogNamespace::Legacy)
+                .map_err(|error| {
+                    format!(
+                        "Invalid event in {:?} after {} events: {}",
+                        path.display(),
+                        events,
+                        error
+                    )
+                })?;
+            for event in parsed {
+                control.requeue(EventArray::from(event)).await?;
+                events += 1;
+            }
+        }
+
+        Ok(BufferDrain::new(events))
+    }
+}
+
+/// Checks that the request is authorized to use the buffer mutations.
+fn authorize<'a>(ctx: &Context<'a>) -> async_graphql::Result<&'a BufferMutations> {
+    let config = ctx.data::<BufferMutations>()?;
+    let token = config
+        .token
+        .as_ref()
+        .ok_or("Buffer mutations are only available when `api.reload_token` is set.")?;
+    let authorization = ctx
+        .data_opt::<Authorization>()
+        .and_then(|authorization| authorization.0.as_deref());
+    if is_authorized(token, authorization) {
+        Ok(config)
+    } else {
+        Err("Invalid or missing reload token.".into())
+    }
+}
+
+/// Resolves a path relative to the exports directory, refusing paths that lead outside of it.
+///
+/// When `create` is set, the parent directories of the path are created.
+async fn export_path(
+    config: &BufferMutations,
+    path: &str,
+    create: bool,
+) -> async_graphql::Result<PathBuf> {
+    let exports_dir = config
+        .data_dir
+        .as_ref()
+        .ok_or("Buffer exports require `data_dir` to be set.")?
+        .join(EXPORTS_SUBDIR);
+    let relative = Path::new(path);
+    if relative.file_name().is_none()
+        || !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return Err(format!(
+            "Invalid path {:?}, it must be relative to the `{}` subdirectory of the data directory.",
+            path, EXPORTS_SUBDIR
+        )
+        .into());
+    }
+
+    let path = exports_dir.join(relative);
+    let parent = path.parent().expect("path has a file name");
+    if create {
+        fs::create_dir_all(parent).await?;
+    }
+    // Symbolic links could otherwise lead outside of the exports directory.
+    let exports_dir = fs::canonicalize(&exports_dir).await?;
+    if !fs::canonicalize(parent).await?.starts_with(&exports_dir) {
+        return Err(format!(
+            "Invalid path {:?}, it leads outside of the exports directory.",
+            path
+        )
+        .into());
+    }
+    if !create && fs::symlink_metadata(&path).await?.file_type().is_symlink() {
+        return Err(format!("Invalid path {:?}, it is a symbolic link.", path).into());
+    }
+    Ok(path)
+}
+
+/// Gets the buffer control of the given sink.
+fn buffer_control(
+    ctx: &Context<'_>,
+    component_id: &str,
+) -> async_graphql::Result<BufferControl<EventArray>> {
+    ctx.data_unchecked::<WatchRx>()
+        .borrow()
+        .buffers
+        .get(&ComponentKey::from(component_id))
+        .cloned()
+        .ok_or_else(|| format!("No sink named \"{}\".", component_id).into())
+}
+
+async fn write_events(file: &mut File, array: EventArray) -> crate::Result<()> {
+    let mut serializer = NativeJsonSerializer::new();
+    let mut buffer = BytesMut::new();
+    for event in array.into_events() {
+        serializer.encode(event, &mut buffer)?;
+        buffer.put_u8(b'\n');
+    }
+    file.write_all(&buffer).await?;
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use async_graphql::{SimpleObject, Subscription};
+use chrono::{DateTime, Utc};
+use tokio_stream::{Stream, StreamExt};
+
+use crate::{event::LogEvent, trace};
+
+/// The component a diagnostic was reported by
+#[derive(SimpleObject, Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticComponent {
+    /// Component ID
+    component_id: String,
+
+    /// Component kind, such as `transform`
+    component_kind: String,
+
+    /// Component type, such as `remap`
+    component_type: String,
+}
+
+/// An error reported by a component
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct ComponentError {
+    /// Component that reported the error
+    component: DiagnosticComponent,
+
+    /// Time at which the error was reported
+    timestamp: DateTime<Utc>,
+
+    /// Description of the error
+    message: String,
+
+    /// The error itself, when reported separately from the message
+    error: Option<String>,
+
+    /// Type of the error, such as `request_failed`
+    error_type: String,
+
+    /// Code of the error, when the component reports one
+    error_code: Option<String>,
+
+    /// Stage at which the error occurred, such as `sending`
+    stage: String,
+}
+
+/// Events dropped by a component
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct ComponentEventsDropped {
+    /// Component that dropped the events
+    component: DiagnosticComponent,
+
+    /// Time at which the events were dropped
+    timestamp: DateTime<Utc>,
+
+    /// Number of events dropped
+    count: i64,
+
+    /// Why the events were dropped
+    reason: String,
+
+    /// Whether the events were dropped on purpose, such as by a `filter` transform, as opposed to
+    /// because of an error
+    intentional: bool,
+}
+
+fn get_string(log: &LogEvent, path: &str) -> Option<String> {
+    log.get(path).map(|v| v.to_string_lossy().into_owned())
+}
+
+fn get_timestamp(log: &LogEvent) -> DateTime<Utc> {
+    log.get("timestamp")
+        .and_then(|v| v.as_timestamp())
+        .copied()
+        .unwrap_or_else(Utc::now)
+}
+
+impl DiagnosticComponent {
+    fn from_log(log: &LogEvent) -> Option<Self> {
+        Some(Self {
+            component_id: get_string(log, "vector.component_id")?,
+            component_kind: get_string(log, "vector.component_kind").unwrap_or_default(),
+            component_type: get_string(log, "vector.component_type").unwrap_or_default(),
+        })
+    }
+}
+
+impl ComponentError {
+    fn from_log(log: &LogEvent) -> Option<Self> {
+        if log.contains("intentional") {
+            return None;
+        }
+        Some(Self {
+            component: DiagnosticComponent::from_log(log)?,
+            timestamp: get_timestamp(log),
+            message: get_string(log, "message").unwrap_or_default(),
+            error: get_string(log, "error"),
+            error_type: get_string(log, "error_type")?,
+            error_code: get_string(log, "error_code"),
+            stage: get_string(log, "stage").unwrap_or_default(),
+        })
+    }
+}
+
+impl ComponentEventsDropped {
+    fn from_log(log: &LogEvent) -> Option<Self> {
+        Some(Self {
+            component: DiagnosticComponent::from_log(log)?,
+            timestamp: get_timestamp(log),
+            count: log.get("count").and_then(|v| v.as_integer())?,
+            reason: get_string(log, "reason").unwrap_or_default(),
+            intentional: log.get("intentional").and_then(|v| v.as_boolean())?,
+        })
+    }
+}
+
+/// Returns a filter of the components matching any of the given patterns, or all components if
+/// there are none.
+fn component_filter(patterns: Option<Vec<String>>) -> impl Fn(&DiagnosticComponent) -> bool {
+    let patterns = patterns
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+        .collect::<Vec<_>>();
+
+    move |component| {
+        patterns.is_empty()
+            || patterns
+                .iter()
+                .any(|pattern| pattern.matches(&component.component_id))
+    }
+}
+
+#[derive(Default)]
+pub struct DiagnosticsSubscription;
+
+#[Subscription]
+impl DiagnosticsSubscription {
+    /// Errors reported by components, as they happen. Errors aren't subject to the internal log
+    /// rate limit, nor to the log level.
+    async fn component_errors(
+        &self,
+        component_id_patterns: Option<Vec<String>>,
+    ) -> impl Stream<Item = ComponentError> {
+        let matches = component_filter(component_id_patterns);
+        trace::subscribe_diagnostics().filter_map(move |log| {
+            ComponentError::from_log(&log).filter(|error| matches(&error.component))
+        })
+    }
+
+    /// Events dropped by components, as they happen. This includes events dropped on purpose,
+    /// which are only logged at the `debug` level.
+    async fn component_events_dropped(
+        &self,
+        component_id_patterns: Option<Vec<String>>,
+        intentional: Option<bool>,
+    ) -> impl Stream<Item = ComponentEventsDropped> {
+        let matches = component_filter(component_id_patterns);
+        trace::subscribe_diagnostics().filter_map(move |log| {
+            ComponentEventsDropped::from_log(&log).filter(|dropped| {
+                matches(&dropped.component)
+                    && intentional.map_or(true, |intentional| intentional == dropped.intentional)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(fields: &[(&str, value::Value)]) -> LogEvent {
+        let mut log = LogEvent::default();
+        log.insert("vector.component_id", "my_sink");
+        log.insert("vector.component_kind", "sink");
+        log.insert("vector.component_type", "http");
+        for (path, value) in fields {
+            log.insert(*path, value.clone());
+        }
+        log
+    }
+
+    #[test]
+    fn converts_diagnostics() {
+        let error = log(&[
+            ("message", "Request failed.".into()),
+            ("error_type", "request_failed".into()),
+            ("stage", "sending".into()),
+        ]);
+        let error = ComponentError::from_log(&error).unwrap();
+        assert_eq!(error.component.component_id, "my_sink");
+        assert_eq!(error.error_type, "request_failed");
+        assert_eq!(error.error_code, None);
+
+        let dropped = log(&[
+            ("message", "Events dropped".into()),
+            ("intentional", false.into()),
+            ("count", 3.into()),
+            ("reason", "Service call failed.".into()),
+        ]);
+        assert!(ComponentError::from_log(&dropped).is_none());
+        let dropped = ComponentEventsDropped::from_log(&dropped).unwrap();
+        assert_eq!(dropped.count, 3);
+        assert!(!dropped.intentional);
+    }
+
+    #[test]
+    fn filters_components() {
+        let component = DiagnosticComponent {
+            component_id: "my_sink".to_string(),
+            component_kind: "sink".to_string(),
+            component_type: "http".to_string(),
+        };
+        assert!(component_filter(None)(&component));
+        assert!(component_filter(Some(vec!["my_*".to_string()]))(&component));
+        assert!(!component_filter(Some(vec!["other".to_string()]))(
+            &component
+        ));
+    }
+}
@@ -0,0 +1,68 @@
+use async_graphql::{Object, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use crate::{config::ComponentKey, event_trace};
+
+/// A component that received a traced event.
+#[derive(SimpleObject)]
+pub struct EventTraceHop {
+    /// Component ID
+    component_id: String,
+
+    /// Component kind, such as `transform`
+    component_kind: String,
+
+    /// Time at which the component received the event
+    timestamp: DateTime<Utc>,
+
+    /// Whether the event was modified since the previous hop
+    modified: bool,
+}
+
+/// The path of a sampled event through the topology
+#[derive(SimpleObject)]
+pub struct EventTrace {
+    /// Trace ID
+    id: String,
+
+    /// Components that received the event, in order
+    hops: Vec<EventTraceHop>,
+}
+
+impl From<event_trace::EventTrace> for EventTrace {
+    fn from(trace: event_trace::EventTrace) -> Self {
+        Self {
+            id: trace.id.to_string(),
+            hops: trace
+                .hops
+                .into_iter()
+                .map(|hop| EventTraceHop {
+                    component_id: hop.component_id.id().to_string(),
+                    component_kind: hop.component_kind.to_string(),
+                    timestamp: hop.timestamp,
+                    modified: hop.modified,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct EventTracesQuery;
+
+#[Object]
+impl EventTracesQuery {
+    /// Most recent event traces, newest first. Requires the global `event_tracing` option to be
+    /// enabled.
+    async fn event_traces(
+        &self,
+        #[graphql(default = 100, validator(minimum = 1, maximum = 10_000))] limit: i32,
+        component_id: Option<String>,
+    ) -> Vec<EventTrace> {
+        let component_id = component_id.map(ComponentKey::from);
+        event_trace::traces(limit as usize, component_id.as_ref())
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+}
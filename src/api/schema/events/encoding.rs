@@ -6,4 +6,6 @@ pub(crate) enum EventEncodingType {
     Json,
     Yaml,
     Logfmt,
+    /// The message of logs, and the text representation of metrics. Traces are encoded as JSON.
+    Raw,
 }
@@ -64,6 +64,7 @@ impl Log {
                 .expect("YAML serialization of log event failed. Please report."),
             EventEncodingType::Logfmt => encode_logfmt::encode_value(self.event.value())
                 .expect("logfmt serialization of log event failed. Please report."),
+            EventEncodingType::Raw => self.get_message().map(Into::into).unwrap_or_default(),
         }
     }
 
@@ -136,6 +136,7 @@ impl Metric {
                     _ => panic!("logfmt serialization of metric event failed: metric converted to unexpected serde Value. Please report."),
                 }
             }
+            EventEncodingType::Raw => self.event.to_string(),
         }
     }
 }
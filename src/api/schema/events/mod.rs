@@ -5,7 +5,7 @@ pub mod notification;
 pub mod output;
 pub mod trace;
 
-use std::collections::HashSet;
+use std::{collections::HashSet, num::NonZeroU32};
 
 use async_graphql::{Context, Subscription};
 use encoding::EventEncodingType;
@@ -15,7 +15,11 @@ use rand::{rngs::SmallRng, Rng, SeedableRng};
 use tokio::{select, sync::mpsc, time};
 use tokio_stream::wrappers::ReceiverStream;
 
-use crate::{api::tap::TapController, topology::WatchRx};
+use crate::{
+    api::tap::{TapController, TapFilter},
+    audit,
+    topology::WatchRx,
+};
 
 /// Patterns (glob) used by tap to match against components and access events
 /// flowing into (for_inputs) or out of (for_outputs) specified components
@@ -48,7 +52,11 @@ pub struct EventsSubscription;
 
 #[Subscription]
 impl EventsSubscription {
-    /// A stream of events emitted from matched component ID patterns
+    /// A stream of events emitted from matched component ID patterns. Events can be selected
+    /// with a VRL `filter` condition, and then sampled to keep one out of every `sampleRate`
+    /// events, up to `maxEventsPerSecond`. These are applied as events are tapped, before the
+    /// `limit` of events returned each `interval`.
+    #[allow(clippy::too_many_arguments)]
     pub async fn output_events_by_component_id_patterns<'a>(
         &'a self,
         ctx: &'a Context<'a>,
@@ -56,15 +64,30 @@ impl EventsSubscription {
         inputs_patterns: Option<Vec<String>>,
         #[graphql(default = 500)] interval: u32,
         #[graphql(default = 100, validator(minimum = 1, maximum = 10_000))] limit: u32,
-    ) -> impl Stream<Item = Vec<OutputEventsPayload>> + 'a {
+        filter: Option<String>,
+        #[graphql(validator(minimum = 1))] sample_rate: Option<u32>,
+        #[graphql(validator(minimum = 1))] max_events_per_second: Option<u32>,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<OutputEventsPayload>> + 'a> {
         let watch_rx = ctx.data_unchecked::<WatchRx>().clone();
 
         let patterns = TapPatterns {
             for_outputs: outputs_patterns.into_iter().collect(),
             for_inputs: inputs_patterns.unwrap_or_default().into_iter().collect(),
         };
+        let filter = TapFilter::new(
+            filter,
+            sample_rate.and_then(NonZeroU32::new),
+            max_events_per_second.and_then(NonZeroU32::new),
+        )
+        .map_err(|error| format!("Invalid filter: {}", error))?;
         // Client input is confined to `u32` to provide sensible bounds.
-        create_events_stream(watch_rx, patterns, interval as u64, limit as usize)
+        Ok(create_events_stream(
+            watch_rx,
+            patterns,
+            filter,
+            interval as u64,
+            limit as usize,
+        ))
     }
 }
 
@@ -74,6 +97,7 @@ impl EventsSubscription {
 pub(crate) fn create_events_stream(
     watch_rx: WatchRx,
     patterns: TapPatterns,
+    filter: TapFilter,
     interval: u64,
     limit: usize,
 ) -> impl Stream<Item = Vec<OutputEventsPayload>> {
@@ -88,10 +112,16 @@ pub(crate) fn create_events_stream(
     // pipeline on slower client connections, but low enough to apply a modest cap on mem usage.
     let (event_tx, event_rx) = mpsc::channel::<Vec<OutputEventsPayload>>(10);
 
+    let session = serde_json::json!({
+        "outputs_patterns": sorted(&patterns.for_outputs),
+        "inputs_patterns": sorted(&patterns.for_inputs),
+    });
+
     tokio::spawn(async move {
+        audit::record("tap_started", session.clone());
         // Create a tap controller. When this drops out of scope, clean up will be performed on the
         // event handlers and topology observation that the tap controller provides.
-        let _tap_controller = TapController::new(watch_rx, tap_tx, patterns);
+        let _tap_controller = TapController::new(watch_rx, tap_tx, patterns, filter);
 
         // A tick interval to represent when to 'cut' the results back to the client.
         let mut interval = time::interval(time::Duration::from_millis(interval));
@@ -172,7 +202,15 @@ pub(crate) fn create_events_stream(
                 }
             }
         }
+
+        audit::record("tap_stopped", session);
     });
 
     ReceiverStream::new(event_rx)
 }
+
+fn sorted(patterns: &HashSet<String>) -> Vec<&String> {
+    let mut patterns = patterns.iter().collect::<Vec<_>>();
+    patterns.sort();
+    patterns
+}
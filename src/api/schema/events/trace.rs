@@ -37,7 +37,7 @@ impl Trace {
     /// Trace event as an encoded string format
     async fn string(&self, encoding: EventEncodingType) -> String {
         match encoding {
-            EventEncodingType::Json => serde_json::to_string(&self.event)
+            EventEncodingType::Json | EventEncodingType::Raw => serde_json::to_string(&self.event)
                 .expect("JSON serialization of log event failed. Please report."),
             EventEncodingType::Yaml => serde_yaml::to_string(&self.event)
                 .expect("YAML serialization of log event failed. Please report."),
@@ -0,0 +1,49 @@
+use async_graphql::Object;
+
+use crate::event::{Metric, MetricValue};
+
+/// The state of a sink's buffer, across all of its stages
+#[derive(Debug, Clone)]
+pub struct BufferMetrics(Vec<Metric>);
+
+impl BufferMetrics {
+    /// Returns the buffer metrics from a component's metrics, if the component has a buffer.
+    pub fn from_metrics(metrics: &[Metric]) -> Option<Self> {
+        let metrics = metrics
+            .iter()
+            .filter(|m| m.name().starts_with("buffer_"))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        (!metrics.is_empty()).then_some(Self(metrics))
+    }
+
+    fn gauges<'a>(&'a self, name: &'a str) -> impl Iterator<Item = f64> + 'a {
+        self.0
+            .iter()
+            .filter(move |m| m.name() == name)
+            .filter_map(|m| match m.value() {
+                MetricValue::Gauge { value } => Some(*value),
+                _ => None,
+            })
+    }
+}
+
+#[Object]
+impl BufferMetrics {
+    /// Number of events held in the buffer
+    pub async fn events(&self) -> f64 {
+        self.gauges("buffer_events").sum()
+    }
+
+    /// Size of the events held in the buffer, in bytes
+    pub async fn byte_size(&self) -> f64 {
+        self.gauges("buffer_byte_size").sum()
+    }
+
+    /// Age of the oldest event held in the buffer, in seconds
+    pub async fn oldest_event_age_seconds(&self) -> f64 {
+        self.gauges("buffer_oldest_event_age_seconds")
+            .fold(0.0, f64::max)
+    }
+}
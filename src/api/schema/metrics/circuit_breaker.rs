@@ -0,0 +1,31 @@
+use async_graphql::Enum;
+
+use crate::event::{Metric, MetricValue};
+
+/// The state of a sink's circuit breaker
+#[derive(Enum, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CircuitBreakerState {
+    /// Requests are sent normally
+    Closed,
+    /// Requests are failed without being sent, and events are diverted to the fallback
+    Open,
+    /// A single request is sent to probe whether the downstream service has recovered
+    HalfOpen,
+}
+
+impl CircuitBreakerState {
+    /// Returns the current circuit breaker state from a component's metrics, if the component has
+    /// a circuit breaker.
+    pub fn from_metrics(metrics: &[Metric]) -> Option<Self> {
+        metrics
+            .iter()
+            .filter(|m| m.name() == "circuit_breaker_state")
+            .find(|m| matches!(m.value(), MetricValue::Gauge { value } if *value > 0.0))
+            .and_then(|m| match m.tag_value("state").as_deref() {
+                Some("closed") => Some(Self::Closed),
+                Some("open") => Some(Self::Open),
+                Some("half_open") => Some(Self::HalfOpen),
+                _ => None,
+            })
+    }
+}
@@ -1,4 +1,6 @@
 mod allocated_bytes;
+mod buffer;
+mod circuit_breaker;
 mod errors;
 mod events_in;
 mod events_out;
@@ -18,7 +20,9 @@ mod host;
 
 pub use allocated_bytes::{AllocatedBytes, ComponentAllocatedBytes};
 use async_graphql::{Interface, Object, Subscription};
+pub use buffer::BufferMetrics;
 use chrono::{DateTime, Utc};
+pub use circuit_breaker::CircuitBreakerState;
 pub use errors::{ComponentErrorsTotal, ErrorsTotal};
 pub use events_in::EventsInTotal;
 pub use events_out::EventsOutTotal;
@@ -45,4 +45,14 @@ impl GenericSinkMetrics {
     pub async fn sent_events_total(&self) -> Option<metrics::SentEventsTotal> {
         self.0.sent_events_total()
     }
+
+    /// State of the circuit breaker of the current sink, if it has one
+    pub async fn circuit_breaker_state(&self) -> Option<metrics::CircuitBreakerState> {
+        metrics::CircuitBreakerState::from_metrics(&self.0)
+    }
+
+    /// Buffer of the current sink
+    pub async fn buffer(&self) -> Option<metrics::BufferMetrics> {
+        metrics::BufferMetrics::from_metrics(&self.0)
+    }
 }
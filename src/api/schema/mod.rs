@@ -1,4 +1,7 @@
+mod buffers;
 pub mod components;
+mod diagnostics;
+mod event_traces;
 pub mod events;
 pub mod filter;
 mod health;
@@ -7,7 +10,8 @@ mod metrics;
 mod relay;
 pub mod sort;
 
-use async_graphql::{EmptyMutation, MergedObject, MergedSubscription, Schema, SchemaBuilder};
+use async_graphql::{MergedObject, MergedSubscription, Schema, SchemaBuilder};
+pub use buffers::{Authorization, BufferMutations};
 
 #[derive(MergedObject, Default)]
 pub struct Query(
@@ -15,17 +19,26 @@ pub struct Query(
     components::ComponentsQuery,
     metrics::MetricsQuery,
     meta::MetaQuery,
+    event_traces::EventTracesQuery,
 );
 
+#[derive(MergedObject, Default)]
+pub struct Mutation(buffers::BuffersMutation);
+
 #[derive(MergedSubscription, Default)]
 pub struct Subscription(
     health::HealthSubscription,
     metrics::MetricsSubscription,
     components::ComponentsSubscription,
     events::EventsSubscription,
+    diagnostics::DiagnosticsSubscription,
 );
 
 /// Build a new GraphQL schema, comprised of Query, Mutation and Subscription types
-pub fn build_schema() -> SchemaBuilder<Query, EmptyMutation, Subscription> {
-    Schema::build(Query::default(), EmptyMutation, Subscription::default())
+pub fn build_schema() -> SchemaBuilder<Query, Mutation, Subscription> {
+    Schema::build(
+        Query::default(),
+        Mutation::default(),
+        Subscription::default(),
+    )
 }
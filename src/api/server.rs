@@ -12,11 +12,12 @@ use async_graphql_warp::{graphql_protocol, GraphQLResponse, GraphQLWebSocket};
 use tokio::sync::oneshot;
 use warp::{filters::BoxedFilter, http::Response, ws::Ws, Filter, Reply};
 
-use super::{handler, schema, ShutdownTx};
+use super::{handler, profiling, reload::ReloadTx, schema, ShutdownTx};
 use crate::{
     config,
     internal_events::{SocketBindError, SocketMode},
-    topology,
+    signal::SignalTx,
+    topology::{self, drain::DrainStatus, health::ReadinessPolicy},
 };
 
 pub struct Server {
@@ -31,8 +32,18 @@ impl Server {
         config: &config::Config,
         watch_rx: topology::WatchRx,
         running: Arc<AtomicBool>,
+        drain_status: DrainStatus,
+        signal_tx: SignalTx,
+        reload_tx: ReloadTx,
     ) -> crate::Result<Self> {
-        let routes = make_routes(config.api.playground, watch_rx, running);
+        let routes = make_routes(
+            config,
+            watch_rx,
+            running,
+            drain_status,
+            signal_tx,
+            reload_tx,
+        );
 
         let (_shutdown, rx) = oneshot::channel();
         let (addr, server) = warp::serve(routes)
@@ -73,20 +84,78 @@ impl Server {
 }
 
 fn make_routes(
-    playground: bool,
+    config: &config::Config,
     watch_tx: topology::WatchRx,
     running: Arc<AtomicBool>,
+    drain_status: DrainStatus,
+    signal_tx: SignalTx,
+    reload_tx: ReloadTx,
 ) -> BoxedFilter<(impl Reply,)> {
+    let playground = config.api.playground;
+    let reload_token = config.api.reload_token.clone();
+    let profiling = config.api.profiling;
+    let readiness_policy = readiness_policy(&config.api.readiness);
+
     // Routes...
 
-    // Health.
-    let health = warp::path("health")
-        .and(with_shared(running))
-        .and_then(handler::health);
+    // Health. `/health/live` and `/health/ready` report the health of each component, for finer
+    // grained probes than the health of Vector as a whole.
+    let health = {
+        let readiness_policy = Arc::new(readiness_policy);
+        let component_health = with_shared(Arc::clone(&running))
+            .and(with_watch(watch_tx.clone()))
+            .and(warp::any().map(move || Arc::clone(&readiness_policy)));
+        let live = warp::path("live")
+            .and(warp::path::end())
+            .and(component_health.clone())
+            .and_then(handler::liveness);
+        let ready = warp::path("ready")
+            .and(warp::path::end())
+            .and(component_health)
+            .and_then(handler::readiness);
+        let overall = with_shared(running).and_then(handler::health);
+        warp::path("health").and(live.or(ready).or(overall))
+    };
+
+    // Drain. `POST` stops all sources and shuts Vector down once the remaining events have been
+    // flushed, while `GET` reports the progress of the drain. Like config reloads, requesting a
+    // drain needs the reload token, and is only exposed when one is configured.
+    let drain = {
+        let start = match reload_token.clone() {
+            Some(token) => {
+                let token = Arc::new(token);
+                let drain_status = drain_status.clone();
+                warp::post()
+                    .and(warp::any().map(move || Arc::clone(&token)))
+                    .and(warp::header::optional::<String>("authorization"))
+                    .and(warp::query::<handler::DrainQuery>())
+                    .and(warp::any().map(move || drain_status.clone()))
+                    .and(warp::any().map(move || signal_tx.clone()))
+                    .and_then(handler::drain)
+                    .boxed()
+            }
+            None => disabled(),
+        };
+        let progress = warp::get()
+            .and(warp::any().map(move || drain_status.clone()))
+            .and_then(handler::drain_progress);
+        warp::path("drain")
+            .and(warp::path::end())
+            .and(start.or(progress))
+    };
 
     // 404.
     let not_found = warp::any().and_then(|| async { Err(warp::reject::not_found()) });
 
+    // Mutations also need to observe the topology, to reach the buffers of sinks. Like config
+    // reloads, they need the reload token, which is checked against the `Authorization` header of
+    // each request.
+    let graphql_data = watch_tx.clone();
+    let buffer_mutations = schema::BufferMutations {
+        token: reload_token.clone(),
+        data_dir: config.global.data_dir.clone(),
+    };
+
     // GraphQL subscription handler. Creates a Warp WebSocket handler and for each connection,
     // parses the required headers for GraphQL and builds per-connection context based on the
     // provided `WatchTx` channel sender. This allows GraphQL resolvers to subscribe to
@@ -117,13 +186,25 @@ fn make_routes(
     // Handle GraphQL queries. Headers will first be parsed to determine whether the query is
     // a subscription and if so, an attempt will be made to upgrade the connection to WebSockets.
     // All other queries will fall back to the default HTTP handler.
-    let graphql_handler = warp::path("graphql").and(graphql_subscription_handler.or(
-        async_graphql_warp::graphql(schema::build_schema().finish()).and_then(
-            |(schema, request): (Schema<_, _, _>, Request)| async move {
-                Ok::<_, Infallible>(GraphQLResponse::from(schema.execute(request).await))
-            },
-        ),
-    ));
+    let graphql_handler =
+        warp::path("graphql").and(
+            graphql_subscription_handler.or(
+                async_graphql_warp::graphql(
+                    schema::build_schema()
+                        .data(graphql_data)
+                        .data(buffer_mutations)
+                        .finish(),
+                )
+                .and(warp::header::optional::<String>("authorization"))
+                .and_then(
+                    |(schema, request): (Schema<_, _, _>, Request),
+                     authorization: Option<String>| async move {
+                        let request = request.data(schema::Authorization(authorization));
+                        Ok::<_, Infallible>(GraphQLResponse::from(schema.execute(request).await))
+                    },
+                ),
+            ),
+        );
 
     // Provide a playground for executing GraphQL queries/mutations/subscriptions.
     let graphql_playground = if playground {
@@ -140,11 +221,50 @@ fn make_routes(
         not_found.boxed()
     };
 
-    // Wire up the health + GraphQL endpoints. Provides a permissive CORS policy to allow for
-    // cross-origin interaction with the Vector API.
+    // Config reload. Only exposed when a reload token is configured, as it allows replacing the
+    // running config.
+    let reload = match reload_token {
+        Some(token) => {
+            let token = Arc::new(token);
+            warp::path("config")
+                .and(warp::path::end())
+                .and(warp::post())
+                .and(warp::any().map(move || Arc::clone(&token)))
+                .and(warp::header::optional::<String>("authorization"))
+                .and(warp::query::<handler::ReloadQuery>())
+                .and(warp::body::json::<config::ConfigBuilder>())
+                .and(warp::any().map(move || reload_tx.clone()))
+                .and_then(handler::reload)
+                .boxed()
+        }
+        None => disabled(),
+    };
+
+    // Profiling. Only exposed when enabled, as collecting a profile has a cost.
+    let profiling = if profiling {
+        let cpu = warp::path("profile")
+            .and(warp::path::end())
+            .and(warp::query::<profiling::CpuProfileQuery>())
+            .and_then(profiling::cpu);
+        let heap = warp::path("heap")
+            .and(warp::path::end())
+            .and_then(profiling::heap);
+        warp::path!("debug" / "pprof" / ..)
+            .and(warp::get())
+            .and(cpu.or(heap))
+            .boxed()
+    } else {
+        disabled()
+    };
+
+    // Wire up the health + drain + GraphQL + reload + profiling endpoints. Provides a permissive CORS policy to allow
+    // for cross-origin interaction with the Vector API.
     health
+        .or(drain)
         .or(graphql_handler)
         .or(graphql_playground)
+        .or(reload)
+        .or(profiling)
         .or(not_found)
         .with(
             warp::cors()
@@ -158,6 +278,7 @@ fn make_routes(
                     "Access-Control-Allow-Origin",
                     "Access-Control-Request-Headers",
                     "Content-Type",
+                    "Authorization",
                     "X-Apollo-Tracing", // for Apollo GraphQL clients
                     "Pragma",
                     "Host",
@@ -169,8 +290,34 @@ fn make_routes(
         .boxed()
 }
 
+/// Builds the readiness policy from the configured options. Invalid patterns can't match any
+/// component, so they're ignored.
+fn readiness_policy(options: &config::api::ReadinessOptions) -> ReadinessPolicy {
+    ReadinessPolicy {
+        components: options
+            .components
+            .iter()
+            .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+            .collect(),
+        max_buffer_usage: f64::from(options.max_buffer_usage_percent) / 100.0,
+    }
+}
+
+fn with_watch(
+    watch_rx: topology::WatchRx,
+) -> impl Filter<Extract = (topology::WatchRx,), Error = Infallible> + Clone {
+    warp::any().map(move || watch_rx.clone())
+}
+
 fn with_shared(
     shared: Arc<AtomicBool>,
 ) -> impl Filter<Extract = (Arc<AtomicBool>,), Error = Infallible> + Clone {
     warp::any().map(move || Arc::<AtomicBool>::clone(&shared))
 }
+
+/// Rejects every request as not found, in place of an endpoint that isn't enabled.
+fn disabled<T: Reply + Send + 'static>() -> BoxedFilter<(T,)> {
+    warp::any()
+        .and_then(|| async { Err::<T, _>(warp::reject::not_found()) })
+        .boxed()
+}
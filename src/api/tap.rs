@@ -1,6 +1,11 @@
 use std::{
     collections::{HashMap, HashSet},
-    num::NonZeroUsize,
+    num::{NonZeroU32, NonZeroUsize},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
 };
 
 use futures::{future::try_join_all, FutureExt};
@@ -20,8 +25,9 @@ use super::{
     ShutdownRx, ShutdownTx,
 };
 use crate::{
+    conditions::{AnyCondition, Condition},
     config::ComponentKey,
-    event::{EventArray, LogArray, MetricArray, TraceArray},
+    event::{Event, EventArray, EventContainer, LogArray, MetricArray, TraceArray},
     topology::{fanout, fanout::ControlChannel, TapOutput, TapResource, WatchRx},
 };
 
@@ -125,19 +131,144 @@ impl TapPayload {
     }
 }
 
+/// A `TapFilter` selects the events sent to a tap client. It's applied to events as they're
+/// tapped, so that discarded events are never queued for the client.
+///
+/// The filter is shared by all of the components matched by a tap, so that sampling and the rate
+/// limit apply to the tap as a whole.
+#[derive(Clone, Debug, Default)]
+pub struct TapFilter {
+    condition: Option<Condition>,
+    sample_rate: Option<NonZeroU32>,
+    sampled: Arc<AtomicU64>,
+    rate_limit: Option<Arc<RateLimit>>,
+}
+
+impl TapFilter {
+    /// Creates a filter from a VRL condition, keeping one out of every `sample_rate` matching
+    /// events, up to `max_events_per_second`.
+    pub fn new(
+        condition: Option<String>,
+        sample_rate: Option<NonZeroU32>,
+        max_events_per_second: Option<NonZeroU32>,
+    ) -> crate::Result<Self> {
+        let condition = condition
+            .map(|source| AnyCondition::String(source).build(&Default::default()))
+            .transpose()?;
+
+        Ok(Self {
+            condition,
+            sample_rate,
+            sampled: Arc::default(),
+            rate_limit: max_events_per_second.map(|max| Arc::new(RateLimit::new(max))),
+        })
+    }
+
+    fn is_pass_through(&self) -> bool {
+        self.condition.is_none() && self.sample_rate.is_none() && self.rate_limit.is_none()
+    }
+
+    /// Returns the selected events, or `None` if no event was selected.
+    fn apply(&self, events: EventArray) -> Option<EventArray> {
+        if self.is_pass_through() {
+            return Some(events);
+        }
+
+        let events = match events {
+            EventArray::Logs(logs) => EventArray::Logs(self.select(logs, Event::into_log)),
+            EventArray::Metrics(metrics) => {
+                EventArray::Metrics(self.select(metrics, Event::into_metric))
+            }
+            EventArray::Traces(traces) => {
+                EventArray::Traces(self.select(traces, Event::into_trace))
+            }
+        };
+        (!events.is_empty()).then_some(events)
+    }
+
+    fn select<T: Into<Event>>(&self, events: Vec<T>, from_event: fn(Event) -> T) -> Vec<T> {
+        events
+            .into_iter()
+            .filter_map(|event| self.select_event(event.into()).map(from_event))
+            .collect()
+    }
+
+    fn select_event(&self, event: Event) -> Option<Event> {
+        let event = match &self.condition {
+            Some(condition) => match condition.check(event) {
+                (true, event) => event,
+                (false, _) => return None,
+            },
+            None => event,
+        };
+
+        if let Some(sample_rate) = self.sample_rate {
+            if self.sampled.fetch_add(1, Ordering::Relaxed) % u64::from(sample_rate.get()) != 0 {
+                return None;
+            }
+        }
+
+        match &self.rate_limit {
+            Some(rate_limit) if !rate_limit.try_acquire() => None,
+            _ => Some(event),
+        }
+    }
+}
+
+/// Limits the number of events sent each second.
+#[derive(Debug)]
+struct RateLimit {
+    max_events: u32,
+    window: Mutex<(Instant, u32)>,
+}
+
+impl RateLimit {
+    fn new(max_events: NonZeroU32) -> Self {
+        Self {
+            max_events: max_events.get(),
+            window: Mutex::new((Instant::now(), 0)),
+        }
+    }
+
+    fn try_acquire(&self) -> bool {
+        let mut window = self.window.lock().expect("rate limit lock poisoned");
+        let (started, count) = &mut *window;
+        if started.elapsed() >= Duration::from_secs(1) {
+            *started = Instant::now();
+            *count = 0;
+        }
+
+        if *count < self.max_events {
+            *count += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
 /// A `TapTransformer` transforms raw events and ships them to the global tap receiver.
 #[derive(Clone)]
 pub struct TapTransformer {
     tap_tx: TapSender,
     output: TapOutput,
+    filter: TapFilter,
 }
 
 impl TapTransformer {
-    pub const fn new(tap_tx: TapSender, output: TapOutput) -> Self {
-        Self { tap_tx, output }
+    pub const fn new(tap_tx: TapSender, output: TapOutput, filter: TapFilter) -> Self {
+        Self {
+            tap_tx,
+            output,
+            filter,
+        }
     }
 
     pub fn try_send(&mut self, events: EventArray) {
+        let Some(events) = self.filter.apply(events) else {
+            return;
+        };
+
         let payload = match events {
             EventArray::Logs(logs) => TapPayload::Log(self.output.clone(), logs),
             EventArray::Metrics(metrics) => TapPayload::Metric(self.output.clone(), metrics),
@@ -165,10 +296,15 @@ impl TapController {
     /// Creates a new tap sink, and spawns a handler for watching for topology changes
     /// and a separate inner handler for events. Uses a oneshot channel to trigger shutdown
     /// of handlers when the `TapSink` drops out of scope.
-    pub fn new(watch_rx: WatchRx, tap_tx: TapSender, patterns: TapPatterns) -> Self {
+    pub fn new(
+        watch_rx: WatchRx,
+        tap_tx: TapSender,
+        patterns: TapPatterns,
+        filter: TapFilter,
+    ) -> Self {
         let (_shutdown, shutdown_rx) = oneshot::channel();
 
-        tokio::spawn(tap_handler(patterns, tap_tx, watch_rx, shutdown_rx));
+        tokio::spawn(tap_handler(patterns, filter, tap_tx, watch_rx, shutdown_rx));
 
         Self { _shutdown }
     }
@@ -237,6 +373,7 @@ async fn send_invalid_output_pattern_match(
 /// `LogEvent`s` when a component matches one or more of the provided patterns.
 async fn tap_handler(
     patterns: TapPatterns,
+    filter: TapFilter,
     tx: TapSender,
     mut watch_rx: WatchRx,
     mut shutdown_rx: ShutdownRx,
@@ -270,6 +407,7 @@ async fn tap_handler(
                     source_keys,
                     sink_keys,
                     removals,
+                    ..
                 } = watch_rx.borrow().clone();
 
                 // Remove tap sinks from components that have gone away/can no longer match.
@@ -316,7 +454,7 @@ async fn tap_handler(
                             // wrap each event payload with the necessary metadata before forwarding
                             // it to our global tap receiver.
                             let (tap_buffer_tx, mut tap_buffer_rx) = TopologyBuilder::standalone_memory(TAP_BUFFER_SIZE, WhenFull::DropNewest).await;
-                            let mut tap_transformer = TapTransformer::new(tx.clone(), output.clone());
+                            let mut tap_transformer = TapTransformer::new(tx.clone(), output.clone(), filter.clone());
 
                             tokio::spawn(async move {
                                 while let Some(events) = tap_buffer_rx.next().await {
@@ -450,6 +588,32 @@ mod tests {
         }
     }
 
+    #[test]
+    /// Filters should apply the condition, then sampling, then the rate limit.
+    fn filter_selects_events() {
+        let filter = TapFilter::new(
+            Some(r#".message != "skip""#.to_string()),
+            NonZeroU32::new(2),
+            NonZeroU32::new(2),
+        )
+        .unwrap();
+        let events = ["a", "skip", "b", "c", "d", "e", "f"]
+            .into_iter()
+            .map(LogEvent::from)
+            .collect();
+
+        let Some(EventArray::Logs(selected)) = filter.apply(EventArray::Logs(events)) else {
+            panic!("Expected logs to be selected.");
+        };
+        let messages = selected
+            .iter()
+            .map(|log| log.get("message").unwrap().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["a", "c"]);
+
+        assert!(TapFilter::new(Some(".message ==".to_string()), None, None).is_err());
+    }
+
     #[tokio::test]
     /// A tap sink should match a pattern, receive the correct notifications,
     /// and receive events
@@ -474,6 +638,7 @@ mod tests {
             source_keys: Vec::new(),
             sink_keys: Vec::new(),
             removals: HashSet::new(),
+            buffers: HashMap::new(),
         };
 
         let (watch_tx, watch_rx) = watch::channel(TapResource::default());
@@ -486,6 +651,7 @@ mod tests {
                 HashSet::from([pattern_matched.to_string(), pattern_not_matched.to_string()]),
                 HashSet::new(),
             ),
+            TapFilter::default(),
         );
 
         // Add the outputs to trigger a change event.
@@ -596,6 +762,7 @@ mod tests {
         let source_tap_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::from(["in".to_string()]), HashSet::new()),
+            TapFilter::default(),
             500,
             100,
         );
@@ -654,6 +821,7 @@ mod tests {
         let source_tap_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::from(["to_metric".to_string()]), HashSet::new()),
+            TapFilter::default(),
             500,
             100,
         );
@@ -704,6 +872,7 @@ mod tests {
         let transform_tap_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::from(["transform".to_string()]), HashSet::new()),
+            TapFilter::default(),
             500,
             100,
         );
@@ -760,6 +929,7 @@ mod tests {
                 HashSet::new(),
                 HashSet::from(["transform".to_string(), "in".to_string()]),
             ),
+            TapFilter::default(),
             500,
             100,
         );
@@ -831,6 +1001,7 @@ mod tests {
         let tap_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::new(), HashSet::from(["out".to_string()])),
+            TapFilter::default(),
             500,
             100,
         );
@@ -894,6 +1065,7 @@ mod tests {
                 HashSet::from(["transform.dropped".to_string()]),
                 HashSet::new(),
             ),
+            TapFilter::default(),
             500,
             100,
         );
@@ -967,6 +1139,7 @@ mod tests {
         let mut transform_tap_all_outputs_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::from(["transform*".to_string()]), HashSet::new()),
+            TapFilter::default(),
             500,
             100,
         );
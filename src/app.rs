@@ -1,5 +1,5 @@
 #![allow(missing_docs)]
-use std::{collections::HashMap, num::NonZeroUsize, path::PathBuf, sync::Arc};
+use std::{collections::HashMap, num::NonZeroUsize, path::PathBuf, sync::Arc, time::Duration};
 
 use futures::StreamExt;
 #[cfg(feature = "enterprise")]
@@ -24,10 +24,11 @@ use crate::metrics;
 #[cfg(windows)]
 use crate::service;
 #[cfg(feature = "api")]
-use crate::{api, internal_events::ApiStarted};
+use crate::{api, internal_events::ApiStarted, reload};
 use crate::{
+    audit, benchmark, capture,
     cli::{handle_config_errors, Color, LogFormat, Opts, RootOpts, SubCommand},
-    config, generate, generate_schema, graph, heartbeat, list,
+    config, generate, generate_schema, graph, heartbeat, list, schema_inference, secrets,
     signal::{self, SignalTo},
     topology::{self, ReloadOutcome, RunningTopology, TopologyController},
     trace, unit_test, validate,
@@ -115,6 +116,12 @@ impl Application {
         #[cfg(not(feature = "enterprise-tests"))]
         metrics::init_global().expect("metrics initialization failed");
 
+        #[cfg(unix)]
+        crate::topology::ACCOUNT_CPU_TIME.store(
+            root_opts.cpu_time_accounting,
+            std::sync::atomic::Ordering::Relaxed,
+        );
+
         let mut rt_builder = runtime::Builder::new_multi_thread();
         rt_builder.enable_all().thread_name("vector-worker");
 
@@ -138,6 +145,7 @@ impl Application {
         let config = {
             let config_paths = root_opts.config_paths_with_formats();
             let watch_config = root_opts.watch_config;
+            let secrets_refresh_interval_secs = root_opts.secrets_refresh_interval_secs;
             let require_healthy = root_opts.require_healthy;
 
             rt.block_on(async move {
@@ -155,9 +163,12 @@ impl Application {
                         SubCommand::Generate(g) => generate::cmd(&g),
                         SubCommand::GenerateSchema => generate_schema::cmd(),
                         SubCommand::Graph(g) => graph::cmd(&g),
+                        SubCommand::Schema(s) => schema_inference::cmd(&s),
                         SubCommand::Config(c) => config::cmd(&c),
                         SubCommand::List(l) => list::cmd(&l),
                         SubCommand::Test(t) => unit_test::cmd(&t, &mut signal_handler).await,
+                        SubCommand::Benchmark(b) => benchmark::cmd(&b).await,
+                        SubCommand::Replay(r) => capture::cmd(&r).await,
                         #[cfg(windows)]
                         SubCommand::Service(s) => service::cmd(&s),
                         #[cfg(feature = "api-client")]
@@ -165,9 +176,12 @@ impl Application {
                         #[cfg(feature = "api-client")]
                         SubCommand::Tap(t) => tap::cmd(&t, signal_rx).await,
 
+                        #[cfg(feature = "api")]
+                        SubCommand::Reload(r) => reload::cmd(&r).await,
                         SubCommand::Validate(v) => validate::validate(&v, color).await,
                         #[cfg(feature = "vrl-cli")]
                         SubCommand::Vrl(s) => vrl_cli::cmd::cmd(&s),
+                        SubCommand::Audit(a) => audit::cmd(&a),
                     };
 
                     return Err(code);
@@ -186,6 +200,15 @@ impl Application {
                         })?;
                 }
 
+                if let Some(refresh_interval_secs) = secrets_refresh_interval_secs {
+                    // Reload the components using any secret that gets rotated.
+                    signal_handler.forever(secrets::watcher::poll_secrets(
+                        config_paths.clone(),
+                        Duration::from_secs(refresh_interval_secs.get()),
+                        signal_handler.clone_tx(),
+                    ));
+                }
+
                 info!(
                     message = "Loading configs.",
                     paths = ?config_paths.iter().map(<&PathBuf>::from).collect::<Vec<_>>()
@@ -225,12 +248,17 @@ impl Application {
                 };
 
                 let diff = config::ConfigDiff::initial(&config);
-                let pieces = topology::build_or_log_errors(&config, &diff, HashMap::new())
-                    .await
-                    .ok_or(exitcode::CONFIG)?;
+                let pieces = topology::build_or_log_errors(
+                    &config,
+                    &diff,
+                    HashMap::new(),
+                    Default::default(),
+                )
+                .await
+                .ok_or(exitcode::CONFIG)?;
 
                 #[cfg(feature = "api")]
-                let api = config.api;
+                let api = config.api.clone();
 
                 let result = topology::start_validated(config, diff, pieces).await;
                 let (topology, (graceful_crash_sender, graceful_crash_receiver)) =
@@ -285,13 +313,19 @@ impl Application {
             emit!(VectorStarted);
             tokio::spawn(heartbeat::heartbeat());
 
+            audit::configure(topology.config().global.audit_log.as_ref());
+            audit::record("vector_started", serde_json::json!({ "version": crate::get_version() }));
+
+            #[cfg(feature = "api")]
+            let (reload_tx, reload_rx) = api::reload::channel();
+
             // Configure the API server, if applicable.
             #[cfg(feature = "api")]
             // Assigned to prevent the API terminating when falling out of scope.
             let api_server = if api_config.enabled {
                 use std::sync::atomic::AtomicBool;
 
-                let api_server = api::Server::start(topology.config(), topology.watch(), Arc::<AtomicBool>::clone(&topology.running));
+                let api_server = api::Server::start(topology.config(), topology.watch(), Arc::<AtomicBool>::clone(&topology.running), topology.drain_status(), signal_handler.clone_tx(), reload_tx);
 
                 match api_server {
                     Ok(api_server) => {
@@ -324,6 +358,19 @@ impl Application {
             };
             let topology_controller = Arc::new(Mutex::new(topology_controller));
 
+            // Apply config reloads requested through the API.
+            #[cfg(feature = "api")]
+            let api_reload_pieces = {
+                let (shutdown_trigger, tripwire) = stream_cancel::Tripwire::new();
+                let handle = tokio::spawn(api::reload::handle_requests(
+                    reload_rx,
+                    Arc::clone(&topology_controller),
+                    self.config.graceful_crash_sender.clone(),
+                    tripwire,
+                ));
+                (shutdown_trigger, handle)
+            };
+
             // If the relevant ENV var is set, start up the control server
             #[cfg(not(windows))]
             let control_server_pieces = if let Ok(path) = std::env::var("VECTOR_CONTROL_SOCKET_PATH") {
@@ -395,16 +442,32 @@ impl Application {
                 server_handle.await.expect("control server task panicked").expect("control server error");
             }
 
+            // Stop applying API reloads
+            #[cfg(feature = "api")]
+            {
+                let (shutdown_trigger, handle) = api_reload_pieces;
+                drop(shutdown_trigger);
+                handle.await.expect("API reload task panicked");
+            }
+
             // Once any control server has stopped, we'll have the only reference to the topology
             // controller and can safely remove it from the Arc/Mutex to shut down the topology.
             let topology_controller = Arc::try_unwrap(topology_controller).expect("fail to unwrap topology controller").into_inner();
 
             match signal {
-                SignalTo::Shutdown => {
+                SignalTo::Shutdown | SignalTo::Drain(_) => {
+                    let timeout = match signal {
+                        SignalTo::Drain(Some(timeout)) => timeout,
+                        _ => Duration::from_secs(opts.graceful_shutdown_limit_secs.get()),
+                    };
+                    let draining = matches!(signal, SignalTo::Drain(_));
+
+                    audit::record("vector_stopped", serde_json::json!({ "draining": draining }));
+                    audit::flush().await;
                     emit!(VectorStopped);
                     tokio::select! {
-                        _ = topology_controller.stop() => (), // Graceful shutdown finished
-                        _ = signal_rx.recv() => {
+                        _ = topology_controller.stop(timeout) => (), // Graceful shutdown finished
+                        _ = shutdown_interrupted(&mut signal_rx, draining) => {
                             // It is highly unlikely that this event will exit from topology.
                             emit!(VectorQuit);
                             // Dropping the shutdown future will immediately shut the server down
@@ -422,6 +485,19 @@ impl Application {
     }
 }
 
+// Resolves once a graceful shutdown should be cut short by another signal. While draining, only an
+// explicit quit does so, since orchestrators commonly send a termination signal after having
+// requested a drain, which shouldn't interrupt it.
+async fn shutdown_interrupted(signal_rx: &mut signal::SignalRx, draining: bool) {
+    loop {
+        match signal_rx.recv().await {
+            Ok(SignalTo::Quit) | Err(RecvError::Closed) => return,
+            Ok(_) | Err(RecvError::Lagged(_)) if draining => continue,
+            _ => return,
+        }
+    }
+}
+
 // The `sources_finished` method on `RunningTopology` only considers sources that are currently
 // running at the time the method is called. This presents a problem when the set of running
 // sources can change while we are waiting on the resulting future to resolve.
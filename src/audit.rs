@@ -0,0 +1,484 @@
+//! Audit log of control-plane actions.
+//!
+//! When the global `audit_log` option is set, actions that change or observe the running
+//! instance, such as config reloads, API mutations, tap sessions and drain requests, are appended
+//! to a file as JSON objects, one per line. Each entry includes the hash of the previous entry and
+//! its own hash, so that modifying, reordering or removing entries breaks the chain, which
+//! `vector audit verify` checks. When a key is configured, the hashes are HMAC-SHA256s, so the
+//! chain can't be rebuilt without the key, and the entries can be mirrored as internal events, so
+//! that removing the last entries is detected against the last hash seen outside of this host.
+//!
+//! Entries are written by a dedicated thread, so recording an action doesn't block the runtime.
+#![allow(missing_docs)]
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Mutex,
+    },
+    thread,
+};
+
+use chrono::Utc;
+use clap::Parser;
+use colored::*;
+use once_cell::sync::Lazy;
+use openssl::{hash::MessageDigest, pkey::PKey, sign::Signer};
+use serde_json::{Map, Value};
+use tokio::sync::oneshot;
+use vector_core::config::AuditLogConfig;
+
+use crate::internal_events::{AuditLogEntry, AuditLogWriteError};
+
+/// The hash that the first entry of a log refers to as the previous one.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// The algorithm of the hashes of the entries of a log without a key.
+const SHA256: &str = "sha256";
+
+/// The algorithm of the hashes of the entries of a log with a key.
+const HMAC_SHA256: &str = "hmac-sha256";
+
+/// Whether the audit log is enabled, so actions aren't sent to the writer otherwise.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The messages handled by the writer thread, in order.
+static WRITER: Lazy<Mutex<mpsc::Sender<Message>>> = Lazy::new(|| {
+    let (tx, rx) = mpsc::channel();
+    thread::Builder::new()
+        .name("vector-audit-log".to_owned())
+        .spawn(move || run_writer(rx))
+        .expect("failed to spawn the audit log writer");
+    Mutex::new(tx)
+});
+
+enum Message {
+    Configure(Option<AuditLogConfig>),
+    Record {
+        action: String,
+        timestamp: String,
+        details: Value,
+    },
+    Flush(oneshot::Sender<()>),
+}
+
+fn send(message: Message) {
+    // The writer thread only stops when the process exits.
+    let _ = WRITER.lock().expect("poisoned lock").send(message);
+}
+
+fn run_writer(messages: mpsc::Receiver<Message>) {
+    let mut audit_log: Option<AuditLog> = None;
+    for message in messages {
+        match message {
+            Message::Configure(Some(config)) => {
+                if audit_log.as_ref().map(|log| &log.config) == Some(&config) {
+                    continue;
+                }
+                audit_log = match AuditLog::open(config) {
+                    Ok(log) => Some(log),
+                    Err((error, config)) => {
+                        emit!(AuditLogWriteError {
+                            error: &error,
+                            path: &config.path,
+                            action: "open",
+                        });
+                        None
+                    }
+                };
+            }
+            Message::Configure(None) => audit_log = None,
+            Message::Record {
+                action,
+                timestamp,
+                details,
+            } => {
+                if let Some(log) = audit_log.as_mut() {
+                    if let Err(error) = log.append(&action, timestamp, details) {
+                        emit!(AuditLogWriteError {
+                            error: &error,
+                            path: &log.config.path,
+                            action: &action,
+                        });
+                    }
+                }
+            }
+            Message::Flush(done) => {
+                let _ = done.send(());
+            }
+        }
+    }
+}
+
+struct AuditLog {
+    config: AuditLogConfig,
+    file: File,
+    sequence: u64,
+    previous_hash: String,
+}
+
+impl AuditLog {
+    /// Opens the log for appending, continuing the chain of the entries already in it.
+    ///
+    /// The last entry must have been hashed with the configured key, if any, so the chain of a log
+    /// isn't continued with a different key.
+    fn open(config: AuditLogConfig) -> Result<Self, (io::Error, AuditLogConfig)> {
+        match Self::open_file(&config) {
+            Ok((file, sequence, previous_hash)) => Ok(Self {
+                config,
+                file,
+                sequence,
+                previous_hash,
+            }),
+            Err(error) => Err((error, config)),
+        }
+    }
+
+    fn open_file(config: &AuditLogConfig) -> io::Result<(File, u64, String)> {
+        let key = config.key.as_ref().map(|key| key.inner().as_bytes());
+        let (sequence, previous_hash) = match File::open(&config.path) {
+            Ok(file) => match last_line(file)? {
+                Some(line) => {
+                    let mut entry = serde_json::from_str::<Map<String, Value>>(&line)?;
+                    let sequence = entry.get("sequence").and_then(Value::as_u64);
+                    let hash = match entry.remove("hash") {
+                        Some(Value::String(hash)) => Some(hash),
+                        _ => None,
+                    };
+                    let (Some(sequence), Some(hash)) = (sequence, hash) else {
+                        return Err(invalid_data("the last entry of the audit log is malformed"));
+                    };
+                    if hash_entry(&entry, key)? != hash {
+                        return Err(invalid_data(
+                            "the last entry of the audit log wasn't hashed with the configured key",
+                        ));
+                    }
+                    (sequence + 1, hash)
+                }
+                None => (0, GENESIS_HASH.to_owned()),
+            },
+            Err(error) if error.kind() == io::ErrorKind::NotFound => (0, GENESIS_HASH.to_owned()),
+            Err(error) => return Err(error),
+        };
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&config.path)?;
+        Ok((file, sequence, previous_hash))
+    }
+
+    fn append(&mut self, action: &str, timestamp: String, details: Value) -> io::Result<()> {
+        let key = self.config.key.as_ref().map(|key| key.inner().as_bytes());
+        let mut entry = Map::new();
+        entry.insert("sequence".to_owned(), self.sequence.into());
+        entry.insert("timestamp".to_owned(), timestamp.into());
+        entry.insert("action".to_owned(), action.into());
+        entry.insert("details".to_owned(), details);
+        entry.insert(
+            "previous_hash".to_owned(),
+            self.previous_hash.clone().into(),
+        );
+        entry.insert(
+            "algorithm".to_owned(),
+            if key.is_some() { HMAC_SHA256 } else { SHA256 }.into(),
+        );
+        let hash = hash_entry(&entry, key)?;
+        entry.insert("hash".to_owned(), hash.clone().into());
+
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+        self.file.write_all(&line)?;
+        // Entries are rare, so each one is persisted as soon as it's recorded.
+        self.file.sync_data()?;
+
+        if self.config.internal_events {
+            emit!(AuditLogEntry {
+                action,
+                sequence: self.sequence,
+                hash: &hash,
+            });
+        }
+
+        self.sequence += 1;
+        self.previous_hash = hash;
+        Ok(())
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn last_line(file: File) -> io::Result<Option<String>> {
+    let mut last = None;
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            last = Some(line);
+        }
+    }
+    Ok(last)
+}
+
+/// Hashes an entry, without its own hash, with the algorithm it names.
+///
+/// Entries written before the algorithm was recorded are hashed with SHA-256.
+fn hash_entry(entry: &Map<String, Value>, key: Option<&[u8]>) -> io::Result<String> {
+    let bytes = serde_json::to_vec(entry)?;
+    let algorithm = entry
+        .get("algorithm")
+        .and_then(Value::as_str)
+        .unwrap_or(SHA256);
+    let digest = match (algorithm, key) {
+        (SHA256, None) => openssl::sha::sha256(&bytes).to_vec(),
+        (HMAC_SHA256, Some(key)) => hmac_sha256(key, &bytes)?,
+        (HMAC_SHA256, None) => {
+            return Err(invalid_data(
+                "the entry is hashed with a key, which wasn't given",
+            ))
+        }
+        (SHA256, Some(_)) => {
+            return Err(invalid_data(
+                "the entry isn't hashed with a key, but one was given",
+            ))
+        }
+        _ => {
+            return Err(invalid_data(
+                "the entry is hashed with an unknown algorithm",
+            ))
+        }
+    };
+    Ok(digest.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+fn hmac_sha256(key: &[u8], bytes: &[u8]) -> io::Result<Vec<u8>> {
+    let to_io = |error| io::Error::new(io::ErrorKind::Other, error);
+    let key = PKey::hmac(key).map_err(to_io)?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key).map_err(to_io)?;
+    signer.update(bytes).map_err(to_io)?;
+    signer.sign_to_vec().map_err(to_io)
+}
+
+/// Opens or closes the audit log according to the configuration.
+pub fn configure(config: Option<&AuditLogConfig>) {
+    ENABLED.store(config.is_some(), Ordering::Release);
+    send(Message::Configure(config.cloned()));
+}
+
+/// Records an action in the audit log, if it is enabled.
+///
+/// The entry is written in the background, in the order actions are recorded.
+pub fn record(action: &str, details: Value) {
+    if ENABLED.load(Ordering::Acquire) {
+        send(Message::Record {
+            action: action.to_owned(),
+            timestamp: Utc::now().to_rfc3339(),
+            details,
+        });
+    }
+}
+
+/// Waits for the actions recorded so far to be written to the audit log.
+pub async fn flush() {
+    if ENABLED.load(Ordering::Acquire) {
+        let (tx, rx) = oneshot::channel();
+        send(Message::Flush(tx));
+        let _ = rx.await;
+    }
+}
+
+/// Checks the chain of hashes of an audit log, returning the number of entries.
+///
+/// The key must be given if the entries are hashed with one. When an anchor is given, which is the
+/// hash of an entry seen outside of this host, the log must include that entry, so that removing
+/// entries from its end is detected.
+pub fn verify(path: &Path, key: Option<&[u8]>, anchor: Option<&str>) -> Result<u64, String> {
+    let file = File::open(path).map_err(|error| error.to_string())?;
+    let mut previous_hash = GENESIS_HASH.to_owned();
+    let mut entries = 0;
+    let mut anchored = anchor.is_none();
+
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|error| error.to_string())?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_number = index + 1;
+        let mut entry = serde_json::from_str::<Map<String, Value>>(&line)
+            .map_err(|error| format!("line {}: {}", line_number, error))?;
+
+        let hash = match entry.remove("hash") {
+            Some(Value::String(hash)) => hash,
+            _ => return Err(format!("line {}: missing hash", line_number)),
+        };
+        if entry.get("sequence").and_then(Value::as_u64) != Some(entries) {
+            return Err(format!(
+                "line {}: expected entry {}, entries are missing or out of order",
+                line_number, entries
+            ));
+        }
+        if entry.get("previous_hash").and_then(Value::as_str) != Some(previous_hash.as_str()) {
+            return Err(format!(
+                "line {}: the previous hash doesn't match the previous entry",
+                line_number
+            ));
+        }
+        let expected_hash =
+            hash_entry(&entry, key).map_err(|error| format!("line {}: {}", line_number, error))?;
+        if expected_hash != hash {
+            return Err(format!(
+                "line {}: the hash doesn't match the entry, which was modified",
+                line_number
+            ));
+        }
+
+        anchored |= anchor == Some(hash.as_str());
+        previous_hash = hash;
+        entries += 1;
+    }
+
+    if !anchored {
+        return Err(format!(
+            "no entry has the anchor hash, entries were removed from the end of the log after entry {}",
+            entries.saturating_sub(1)
+        ));
+    }
+    Ok(entries)
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+enum Command {
+    /// Check that the entries of an audit log weren't modified, reordered or removed.
+    Verify(VerifyOpts),
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+struct VerifyOpts {
+    /// The path of the audit log.
+    path: PathBuf,
+
+    /// The key the entries of the audit log are hashed with, if any.
+    #[arg(long, env = "VECTOR_AUDIT_LOG_KEY", hide_env_values = true)]
+    key: Option<String>,
+
+    /// The hash of an entry seen outside of this host, such as the last one emitted as an internal
+    /// event, which the audit log must include.
+    #[arg(long)]
+    anchor: Option<String>,
+}
+
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match &opts.command {
+        Command::Verify(opts) => match verify(
+            &opts.path,
+            opts.key.as_deref().map(str::as_bytes),
+            opts.anchor.as_deref(),
+        ) {
+            Ok(entries) => {
+                #[allow(clippy::print_stdout)]
+                {
+                    println!("{} {} entries verified.", "√".green(), entries);
+                }
+                exitcode::OK
+            }
+            Err(error) => {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!("{} {}", "x".red(), error);
+                }
+                exitcode::DATAERR
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn open(path: &Path, key: Option<&str>) -> io::Result<AuditLog> {
+        AuditLog::open(AuditLogConfig {
+            path: path.to_owned(),
+            key: key.map(|key| key.to_owned().into()),
+            internal_events: false,
+        })
+        .map_err(|(error, _)| error)
+    }
+
+    fn append(log: &mut AuditLog, action: &str, details: Value) {
+        log.append(action, Utc::now().to_rfc3339(), details)
+            .unwrap();
+    }
+
+    #[test]
+    fn hash_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+
+        let mut log = open(&path, None).unwrap();
+        append(&mut log, "config_reload", json!({ "outcome": "success" }));
+        append(&mut log, "drain", json!({ "timeout_secs": 30 }));
+        drop(log);
+
+        // Reopening continues the chain.
+        let mut log = open(&path, None).unwrap();
+        append(&mut log, "tap_started", json!({}));
+        assert_eq!(verify(&path, None, None), Ok(3));
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let tampered = contents.replace("\"timeout_secs\":30", "\"timeout_secs\":60");
+        fs::write(&path, tampered).unwrap();
+        assert!(verify(&path, None, None)
+            .unwrap_err()
+            .starts_with("line 2:"));
+
+        let removed = contents.lines().skip(1).collect::<Vec<_>>().join("\n");
+        fs::write(&path, removed).unwrap();
+        assert!(verify(&path, None, None)
+            .unwrap_err()
+            .starts_with("line 1:"));
+    }
+
+    #[test]
+    fn keyed_hash_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+
+        let mut log = open(&path, Some("secret")).unwrap();
+        append(&mut log, "config_reload", json!({ "outcome": "success" }));
+        append(&mut log, "drain", json!({ "timeout_secs": 30 }));
+        let anchor = log.previous_hash.clone();
+        drop(log);
+
+        // The chain can't be continued, or checked, without the key.
+        assert!(open(&path, None).is_err());
+        assert!(open(&path, Some("other")).is_err());
+        assert!(verify(&path, None, None).is_err());
+        assert!(verify(&path, Some(b"other"), None).is_err());
+        assert_eq!(verify(&path, Some(b"secret"), Some(&anchor)), Ok(2));
+
+        // Removing the last entry keeps the chain intact, but loses the anchor.
+        let contents = fs::read_to_string(&path).unwrap();
+        let truncated = contents.lines().next().unwrap().to_owned();
+        fs::write(&path, truncated).unwrap();
+        assert_eq!(verify(&path, Some(b"secret"), None), Ok(1));
+        assert!(verify(&path, Some(b"secret"), Some(&anchor)).is_err());
+    }
+}
@@ -0,0 +1,529 @@
+//! Benchmark of codecs and configs.
+//!
+//! Events, either synthetic or recorded, are sent at full speed through encoding codecs, or
+//! through the transforms and sinks of a config whose sources are replaced, and the throughput,
+//! latency and allocations are reported, so that encodings and variants of a config can be
+//! compared.
+#![allow(missing_docs)]
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use bytes::BytesMut;
+use clap::Parser;
+use codecs::encoding::SerializerConfig;
+use colored::*;
+use futures::{stream, SinkExt};
+use serde::Deserialize;
+use tokio_util::codec::Encoder;
+use vector_core::{config::log_schema, metrics::Controller};
+
+use crate::{
+    config::{
+        self,
+        unit_test::{UnitTestStreamSinkConfig, UnitTestStreamSourceConfig},
+        ComponentKey, ConfigBuilder, ConfigDiff, SourceOuter,
+    },
+    event::{Event, LogEvent, MetricValue, Value},
+    topology,
+};
+
+/// The ID of the source sending the events to the transforms given with `--insert-at`.
+const BENCHMARK_SOURCE: &str = "benchmark";
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Vector config files to benchmark. If none are specified and no codec
+    /// is, the default config path `/etc/vector/vector.toml` will be targeted.
+    #[arg(value_delimiter(','), conflicts_with = "codec")]
+    paths: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[arg(
+        id = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        value_delimiter(','),
+        conflicts_with = "codec"
+    )]
+    config_dirs: Vec<PathBuf>,
+
+    /// Benchmark an encoding codec instead of a config. Either the name of a
+    /// codec, such as `json`, or an inline TOML table of its options, such as
+    /// `{ codec = "csv", csv.fields = ["message"] }`. Can be repeated to
+    /// compare codecs.
+    #[arg(long = "codec", value_name = "CODEC")]
+    codecs: Vec<String>,
+
+    /// Send the events to these transforms of the config, instead of to its
+    /// sources, to benchmark a chain of transforms.
+    #[arg(long, value_delimiter(','), conflicts_with = "codec")]
+    insert_at: Vec<String>,
+
+    /// Keep the sinks of the config, instead of discarding the events they
+    /// receive.
+    #[arg(long, conflicts_with = "codec")]
+    keep_sinks: bool,
+
+    /// File of recorded events, one per line. Lines that are JSON objects are
+    /// the fields of log events, and other lines their message. Synthetic log
+    /// events are sent if not set.
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    /// The number of events sent by each source, or to each codec. Recorded
+    /// events are repeated as needed.
+    #[arg(short = 'n', long, default_value = "100000")]
+    events: usize,
+}
+
+impl Opts {
+    fn paths(&self) -> Vec<config::ConfigPath> {
+        self.paths
+            .iter()
+            .map(|path| config::ConfigPath::File(path.to_path_buf(), None))
+            .chain(
+                self.config_dirs
+                    .iter()
+                    .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+            )
+            .collect()
+    }
+}
+
+pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let events = match load_events(opts) {
+        Ok(events) => events,
+        Err(error) => {
+            error!(message = "Failed to load the events.", %error);
+            return exitcode::DATAERR;
+        }
+    };
+
+    if opts.codecs.is_empty() {
+        benchmark_config(opts, events).await
+    } else {
+        benchmark_codecs(&opts.codecs, &events)
+    }
+}
+
+fn load_events(opts: &Opts) -> Result<Vec<Event>, String> {
+    match &opts.input {
+        Some(path) => {
+            let contents = fs::read_to_string(path)
+                .map_err(|error| format!("Could not read {:?}: {}", path, error))?;
+            let recorded = contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(recorded_event)
+                .collect::<Vec<_>>();
+            if recorded.is_empty() {
+                return Err(format!("No events in {:?}.", path));
+            }
+            Ok(recorded.into_iter().cycle().take(opts.events).collect())
+        }
+        None => Ok((0..opts.events).map(synthetic_event).collect()),
+    }
+}
+
+fn recorded_event(line: &str) -> Event {
+    match serde_json::from_str::<serde_json::Value>(line) {
+        Ok(fields @ serde_json::Value::Object(_)) => LogEvent::from(Value::from(fields)).into(),
+        _ => LogEvent::from_str_legacy(line).into(),
+    }
+}
+
+/// Generates a log event resembling one of an HTTP server.
+fn synthetic_event(index: usize) -> Event {
+    const METHODS: [&str; 4] = ["GET", "POST", "PUT", "DELETE"];
+    const PATHS: [&str; 5] = ["/", "/login", "/api/users", "/api/orders", "/static/app.js"];
+    const STATUSES: [i64; 7] = [200, 200, 200, 201, 304, 404, 500];
+
+    let host = format!("10.0.{}.{}", index / 256 % 256, index % 256);
+    let method = METHODS[index % METHODS.len()];
+    let path = PATHS[index % PATHS.len()];
+    let status = STATUSES[index % STATUSES.len()];
+    let bytes = (index * 7919 % 65536) as i64;
+
+    let mut log = LogEvent::from_str_legacy(format!(
+        "{} - - \"{} {} HTTP/1.1\" {} {}",
+        host, method, path, status, bytes
+    ));
+    log.insert(log_schema().host_key(), host);
+    log.insert("method", method);
+    log.insert("path", path);
+    log.insert("status", status);
+    log.insert("bytes", bytes);
+    log.into()
+}
+
+#[derive(Deserialize)]
+struct CodecSpec {
+    codec: SerializerConfig,
+}
+
+/// Parses the name of a codec, or an inline table of its options.
+fn parse_codec(spec: &str) -> Result<SerializerConfig, String> {
+    let toml = if spec.trim_start().starts_with('{') {
+        format!("codec = {}", spec)
+    } else {
+        format!("codec = {{ codec = {:?} }}", spec)
+    };
+    toml::from_str::<CodecSpec>(&toml)
+        .map(|spec| spec.codec)
+        .map_err(|error| error.to_string())
+}
+
+/// The measurements of a codec encoding the events.
+struct CodecReport {
+    events: usize,
+    bytes: usize,
+    /// The time to encode each event, sorted.
+    latencies: Vec<Duration>,
+}
+
+impl CodecReport {
+    fn total(&self) -> Duration {
+        self.latencies.iter().sum()
+    }
+
+    fn percentile(&self, percentile: usize) -> Duration {
+        match self.latencies.len() {
+            0 => Duration::ZERO,
+            len => self.latencies[(len - 1) * percentile / 100],
+        }
+    }
+}
+
+fn benchmark_codec(config: &SerializerConfig, events: &[Event]) -> Result<CodecReport, String> {
+    let mut serializer = config.build().map_err(|error| error.to_string())?;
+    let mut buffer = BytesMut::new();
+    let mut report = CodecReport {
+        events: events.len(),
+        bytes: 0,
+        latencies: Vec::with_capacity(events.len()),
+    };
+
+    for event in events.iter().cloned() {
+        let start = Instant::now();
+        serializer
+            .encode(event, &mut buffer)
+            .map_err(|error| error.to_string())?;
+        report.latencies.push(start.elapsed());
+        report.bytes += buffer.len();
+        buffer.clear();
+    }
+
+    report.latencies.sort_unstable();
+    Ok(report)
+}
+
+fn benchmark_codecs(specs: &[String], events: &[Event]) -> exitcode::ExitCode {
+    let mut code = exitcode::OK;
+    for spec in specs {
+        let report = parse_codec(spec)
+            .map_err(|error| format!("Invalid codec: {}", error))
+            .and_then(|config| benchmark_codec(&config, events));
+        match report {
+            Ok(report) => {
+                let secs = report.total().as_secs_f64().max(f64::EPSILON);
+                #[allow(clippy::print_stdout)]
+                {
+                    println!(
+                        "{}: {:.0} events/s, {:.1} MiB/s, {:.1} bytes/event, latency p50 {:?}, p99 {:?}",
+                        spec.bold(),
+                        report.events as f64 / secs,
+                        report.bytes as f64 / secs / (1024.0 * 1024.0),
+                        report.bytes as f64 / report.events.max(1) as f64,
+                        report.percentile(50),
+                        report.percentile(99),
+                    );
+                }
+            }
+            Err(error) => {
+                #[allow(clippy::print_stdout)]
+                {
+                    println!("{}: {}", spec.bold(), error.red());
+                }
+                code = exitcode::DATAERR;
+            }
+        }
+    }
+    code
+}
+
+/// Replaces the sources of the config with ones sending the events, and its sinks with ones
+/// discarding them unless they are kept, returning the kinds of the components.
+fn prepare(
+    builder: &mut ConfigBuilder,
+    opts: &Opts,
+    events: Vec<Event>,
+) -> Result<BTreeMap<String, &'static str>, String> {
+    builder.global.end_to_end_latency = true;
+
+    if opts.insert_at.is_empty() {
+        for source in builder.sources.values_mut() {
+            *source = SourceOuter::new(UnitTestStreamSourceConfig::new(stream::iter(
+                events.clone(),
+            )));
+        }
+    } else {
+        // The sources are kept so that the inputs of the transforms stay valid, but send nothing.
+        for source in builder.sources.values_mut() {
+            *source = SourceOuter::new(UnitTestStreamSourceConfig::default());
+        }
+        for id in &opts.insert_at {
+            let transform = builder
+                .transforms
+                .get_mut(&ComponentKey::from(id.as_str()))
+                .ok_or_else(|| format!("There is no transform {:?} to insert events at.", id))?;
+            transform.inputs.extend(Some(BENCHMARK_SOURCE.to_owned()));
+        }
+        builder.sources.insert(
+            ComponentKey::from(BENCHMARK_SOURCE),
+            SourceOuter::new(UnitTestStreamSourceConfig::new(stream::iter(events))),
+        );
+    }
+
+    if !opts.keep_sinks {
+        for sink in builder.sinks.values_mut() {
+            sink.inner =
+                UnitTestStreamSinkConfig::new(futures::sink::drain().sink_map_err(|_| ())).into();
+        }
+    }
+
+    let kinds = builder
+        .sources
+        .keys()
+        .map(|key| (key.id().to_owned(), "source"))
+        .chain(
+            builder
+                .transforms
+                .keys()
+                .map(|key| (key.id().to_owned(), "transform")),
+        )
+        .chain(
+            builder
+                .sinks
+                .keys()
+                .map(|key| (key.id().to_owned(), "sink")),
+        )
+        .collect();
+    Ok(kinds)
+}
+
+async fn benchmark_config(opts: &Opts, events: Vec<Event>) -> exitcode::ExitCode {
+    let paths = match config::process_paths(&opts.paths()) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+
+    let config = config::init_log_schema(&paths, true)
+        .and_then(|()| config::load_builder_from_paths(&paths))
+        .and_then(|(mut builder, _)| {
+            let kinds = prepare(&mut builder, opts, events).map_err(|error| vec![error])?;
+            Ok((builder.build()?, kinds))
+        });
+    let (config, kinds) = match config {
+        Ok(config) => config,
+        Err(errors) => {
+            error!("Failed to prepare the benchmark:\n{}", errors.join("\n"));
+            return exitcode::CONFIG;
+        }
+    };
+    let sources = kinds.values().filter(|kind| **kind == "source").count();
+
+    let diff = ConfigDiff::initial(&config);
+    let pieces =
+        match topology::build_or_log_errors(&config, &diff, HashMap::new(), Default::default())
+            .await
+        {
+            Some(pieces) => pieces,
+            None => return exitcode::CONFIG,
+        };
+
+    let start = Instant::now();
+    let (topology, _) = match topology::start_validated(config, diff, pieces).await {
+        Some(topology) => topology,
+        None => return exitcode::CONFIG,
+    };
+    topology.sources_finished().await;
+    topology.stop().await;
+    let elapsed = start.elapsed();
+
+    wait_for_allocations().await;
+    let stats = component_stats();
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!(
+            "Sent {} events from {} {} in {:.2?}.\n",
+            opts.events,
+            if opts.insert_at.is_empty() {
+                format!("each of {} sources", sources)
+            } else {
+                "the benchmark source".to_owned()
+            },
+            if opts.keep_sinks {
+                "to the sinks"
+            } else {
+                "and discarded them at the sinks"
+            },
+            elapsed
+        );
+        println!(
+            "{:<32} {:<10} {:>12} {:>12} {:>12} {:>16}",
+            "component", "kind", "received", "sent", "events/s", "allocated/event"
+        );
+    }
+
+    let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+    for (id, kind) in &kinds {
+        let stats = stats.get(id).cloned().unwrap_or_default();
+        // Sources don't receive events, so their throughput is the one of the events they send.
+        let events = if *kind == "source" {
+            stats.sent
+        } else {
+            stats.received
+        };
+        let allocated = stats
+            .allocated_bytes
+            .map(|bytes| format!("{:.0} B", bytes / events.max(1.0)))
+            .unwrap_or_else(|| "-".to_owned());
+        #[allow(clippy::print_stdout)]
+        {
+            println!(
+                "{:<32} {:<10} {:>12} {:>12} {:>12.0} {:>16}",
+                id,
+                kind,
+                stats.received,
+                stats.sent,
+                events / secs,
+                allocated
+            );
+        }
+    }
+
+    let latencies = stats
+        .iter()
+        .filter_map(|(id, stats)| stats.latency.map(|latency| (id, latency)))
+        .collect::<Vec<_>>();
+    if !latencies.is_empty() {
+        #[allow(clippy::print_stdout)]
+        {
+            println!("\nmean end-to-end latency:");
+            for (source, latency) in latencies {
+                println!("  from {}: {:.2?}", source, latency);
+            }
+        }
+    }
+
+    exitcode::OK
+}
+
+#[derive(Clone, Debug, Default)]
+struct ComponentStats {
+    received: f64,
+    sent: f64,
+    allocated_bytes: Option<f64>,
+    latency: Option<Duration>,
+}
+
+/// Gathers the stats of the components from the internal metrics.
+fn component_stats() -> HashMap<String, ComponentStats> {
+    let mut stats = HashMap::<String, ComponentStats>::new();
+    let metrics = match Controller::get() {
+        Ok(controller) => controller.capture_metrics(),
+        Err(_) => return stats,
+    };
+
+    for metric in metrics {
+        if metric.name() == "end_to_end_latency_seconds" {
+            if let (Some(source), MetricValue::AggregatedHistogram { count, sum, .. }) =
+                (metric.tag_value("source_component_id"), metric.value())
+            {
+                if *count > 0 {
+                    stats.entry(source).or_default().latency =
+                        Some(Duration::from_secs_f64(sum / *count as f64));
+                }
+            }
+            continue;
+        }
+
+        let (component, value) = match (metric.tag_value("component_id"), metric.value()) {
+            (Some(component), MetricValue::Counter { value }) => (component, *value),
+            _ => continue,
+        };
+        let stats = stats.entry(component).or_default();
+        match metric.name() {
+            "component_received_events_total" => stats.received += value,
+            // Sent events are counted per output.
+            "component_sent_events_total" => stats.sent += value,
+            "component_allocated_bytes_total" => {
+                *stats.allocated_bytes.get_or_insert(0.0) += value;
+            }
+            _ => {}
+        }
+    }
+    stats
+}
+
+/// Waits for the allocations of the components to be reported, when they are tracked.
+async fn wait_for_allocations() {
+    #[cfg(feature = "allocation-tracing")]
+    {
+        use std::sync::atomic::Ordering;
+
+        use crate::internal_telemetry::allocations::{REPORTING_INTERVAL_MS, TRACK_ALLOCATIONS};
+
+        if TRACK_ALLOCATIONS.load(Ordering::Relaxed) {
+            let interval = REPORTING_INTERVAL_MS.load(Ordering::Relaxed);
+            tokio::time::sleep(Duration::from_millis(interval * 2)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codec_specs() {
+        assert!(matches!(parse_codec("json"), Ok(SerializerConfig::Json(_))));
+        assert!(matches!(
+            parse_codec("{ codec = \"logfmt\" }"),
+            Ok(SerializerConfig::Logfmt)
+        ));
+        assert!(parse_codec("not_a_codec").is_err());
+    }
+
+    #[test]
+    fn encodes_events() {
+        let events = (0..10).map(synthetic_event).collect::<Vec<_>>();
+        let report = benchmark_codec(&parse_codec("json").unwrap(), &events).unwrap();
+        assert_eq!(report.events, 10);
+        assert_eq!(report.latencies.len(), 10);
+        assert!(report.bytes > 0);
+        assert!(report.percentile(50) <= report.percentile(99));
+    }
+
+    #[test]
+    fn recorded_events() {
+        let event = recorded_event(r#"{"message": "hello", "status": 200}"#);
+        assert_eq!(event.as_log()["status"], Value::from(200_i64));
+
+        let event = recorded_event("plain line");
+        assert_eq!(
+            event.as_log()[log_schema().message_key()],
+            Value::from("plain line")
+        );
+    }
+}
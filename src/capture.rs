@@ -0,0 +1,352 @@
+//! Capture and replay of the events ingested by sources.
+//!
+//! When the `capture` option of a source is set, the events it emits are appended to a file as
+//! JSON objects, one per line, with the time they were ingested and the source that ingested them.
+//! `vector replay` then sends the captured events through a config, in place of its sources, at
+//! their original pace or faster, so that changes to a pipeline can be tested against real
+//! traffic.
+#![allow(missing_docs)]
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use colored::*;
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use vector_config::configurable_component;
+
+use crate::{
+    config::{self, unit_test::UnitTestStreamSourceConfig, ComponentKey, ConfigDiff, SourceOuter},
+    event::{Event, EventArray, EventContainer, Value},
+    internal_events::CaptureWriteError,
+    topology,
+};
+
+/// Capture configuration for a source.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct CaptureConfig {
+    /// The file to append the events emitted by the source to.
+    ///
+    /// Each event is written as a JSON object on its own line, along with the time it was
+    /// ingested, the source and the metadata of the event. The file can be replayed with
+    /// `vector replay`.
+    #[configurable(metadata(docs::examples = "/var/lib/vector/capture/in.jsonl"))]
+    pub path: PathBuf,
+}
+
+/// A captured event.
+#[derive(Debug, Deserialize, Serialize)]
+struct CaptureRecord {
+    timestamp: DateTime<Utc>,
+    source: String,
+    source_type: String,
+    event: Event,
+    #[serde(default, skip_serializing_if = "is_empty")]
+    metadata: Value,
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+/// Writes the events emitted by a source to its capture file.
+#[derive(Clone)]
+pub(crate) struct Capture {
+    source: String,
+    source_type: &'static str,
+    path: Arc<PathBuf>,
+    writer: Arc<Mutex<BufWriter<File>>>,
+}
+
+impl Capture {
+    pub(crate) fn open(
+        config: &CaptureConfig,
+        key: &ComponentKey,
+        source_type: &'static str,
+    ) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&config.path)
+            .map_err(|error| {
+                io::Error::new(
+                    error.kind(),
+                    format!("could not open capture file {:?}: {}", config.path, error),
+                )
+            })?;
+        Ok(Self {
+            source: key.id().to_owned(),
+            source_type,
+            path: Arc::new(config.path.clone()),
+            writer: Arc::new(Mutex::new(BufWriter::new(file))),
+        })
+    }
+
+    /// Appends the events of the array. Failures are reported, but don't stop the source.
+    pub(crate) fn record(&self, array: &EventArray) {
+        if let Err(error) = self.write(array) {
+            emit!(CaptureWriteError {
+                error: &error,
+                path: &self.path,
+                component_id: &self.source,
+            });
+        }
+    }
+
+    fn write(&self, array: &EventArray) -> io::Result<()> {
+        let timestamp = Utc::now();
+        let mut writer = self.writer.lock().expect("poisoned lock");
+        for event in array.clone().into_events() {
+            let metadata = event.metadata().value().clone();
+            let record = CaptureRecord {
+                timestamp,
+                source: self.source.clone(),
+                source_type: self.source_type.to_owned(),
+                event,
+                metadata,
+            };
+            serde_json::to_writer(&mut *writer, &record)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()
+    }
+}
+
+/// Reads the records of capture files, ordered by the time they were ingested.
+fn read_records(paths: &[PathBuf]) -> Result<Vec<CaptureRecord>, String> {
+    let mut records = Vec::new();
+    for path in paths {
+        let contents = fs::read_to_string(path)
+            .map_err(|error| format!("Could not read {:?}: {}", path, error))?;
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record = serde_json::from_str::<CaptureRecord>(line)
+                .map_err(|error| format!("{:?}, line {}: {}", path, index + 1, error))?;
+            records.push(record);
+        }
+    }
+    // The sort is stable, so events ingested at the same time keep their order.
+    records.sort_by_key(|record| record.timestamp);
+    Ok(records)
+}
+
+/// The delay after the start of the replay at which an event ingested at `timestamp` is sent.
+fn replay_delay(first: DateTime<Utc>, timestamp: DateTime<Utc>, speed: u32) -> Duration {
+    (timestamp - first).to_std().unwrap_or_default() / speed
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Capture files to replay, as written by the `capture` option of sources.
+    #[arg(long, required = true, value_delimiter(','))]
+    capture: Vec<PathBuf>,
+
+    /// Vector config files to replay the events through. If none are specified,
+    /// the default config path `/etc/vector/vector.toml` will be targeted.
+    #[arg(value_delimiter(','))]
+    paths: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[arg(
+        id = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        value_delimiter(',')
+    )]
+    config_dirs: Vec<PathBuf>,
+
+    /// Replay the events this many times faster than they were ingested.
+    #[arg(long, default_value = "1", value_parser = clap::value_parser!(u32).range(1..))]
+    speed: u32,
+
+    /// Replay the events as fast as possible, ignoring the time they were
+    /// ingested.
+    #[arg(long, conflicts_with = "speed")]
+    max_speed: bool,
+}
+
+impl Opts {
+    fn paths(&self) -> Vec<config::ConfigPath> {
+        self.paths
+            .iter()
+            .map(|path| config::ConfigPath::File(path.to_path_buf(), None))
+            .chain(
+                self.config_dirs
+                    .iter()
+                    .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+            )
+            .collect()
+    }
+}
+
+pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let records = match read_records(&opts.capture) {
+        Ok(records) if records.is_empty() => {
+            error!(message = "The capture files contain no events.");
+            return exitcode::DATAERR;
+        }
+        Ok(records) => records,
+        Err(error) => {
+            error!(message = "Failed to read the capture files.", %error);
+            return exitcode::DATAERR;
+        }
+    };
+
+    let paths = match config::process_paths(&opts.paths()) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+
+    let speed = (!opts.max_speed).then_some(opts.speed);
+    let start = tokio::time::Instant::now();
+    let config = config::init_log_schema(&paths, true)
+        .and_then(|()| config::load_builder_from_paths(&paths))
+        .and_then(|(mut builder, _)| {
+            let counts = replace_sources(&mut builder.sources, records, speed, start)
+                .map_err(|error| vec![error])?;
+            Ok((builder.build()?, counts))
+        });
+    let (config, counts) = match config {
+        Ok(config) => config,
+        Err(errors) => {
+            error!("Failed to prepare the replay:\n{}", errors.join("\n"));
+            return exitcode::CONFIG;
+        }
+    };
+
+    let diff = ConfigDiff::initial(&config);
+    let pieces =
+        match topology::build_or_log_errors(&config, &diff, HashMap::new(), Default::default())
+            .await
+        {
+            Some(pieces) => pieces,
+            None => return exitcode::CONFIG,
+        };
+    let (topology, _) = match topology::start_validated(config, diff, pieces).await {
+        Some(topology) => topology,
+        None => return exitcode::CONFIG,
+    };
+    topology.sources_finished().await;
+    topology.stop().await;
+
+    #[allow(clippy::print_stdout)]
+    {
+        for (source, count) in &counts {
+            println!("{} Replayed {} events from {}.", "√".green(), count, source);
+        }
+        println!("Finished in {:.2?}.", start.elapsed());
+    }
+    exitcode::OK
+}
+
+/// Replaces the sources of the config with ones sending the captured events, returning the number
+/// of events sent by each source. Sources without captured events send none.
+fn replace_sources(
+    sources: &mut indexmap::IndexMap<ComponentKey, SourceOuter>,
+    records: Vec<CaptureRecord>,
+    speed: Option<u32>,
+    start: tokio::time::Instant,
+) -> Result<BTreeMap<String, usize>, String> {
+    let first = records[0].timestamp;
+    let mut by_source = BTreeMap::<String, Vec<(Duration, Event)>>::new();
+    for record in records {
+        let delay = speed
+            .map(|speed| replay_delay(first, record.timestamp, speed))
+            .unwrap_or_default();
+        let mut event = record.event;
+        if !matches!(record.metadata, Value::Null) {
+            *event.metadata_mut().value_mut() = record.metadata;
+        }
+        by_source
+            .entry(record.source)
+            .or_default()
+            .push((delay, event));
+    }
+
+    for source in by_source.keys() {
+        if !sources.contains_key(&ComponentKey::from(source.as_str())) {
+            return Err(format!(
+                "Events were captured from source {:?}, which isn't in the config.",
+                source
+            ));
+        }
+    }
+
+    let mut counts = BTreeMap::new();
+    for (key, source) in sources.iter_mut() {
+        let events = by_source.remove(key.id()).unwrap_or_default();
+        counts.insert(key.id().to_owned(), events.len());
+        let events = stream::iter(events).then(move |(delay, event)| async move {
+            tokio::time::sleep_until(start + delay).await;
+            event
+        });
+        *source = SourceOuter::new(UnitTestStreamSourceConfig::new(events));
+    }
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn records_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.jsonl");
+        let config = CaptureConfig { path: path.clone() };
+
+        let capture = Capture::open(&config, &ComponentKey::from("in"), "socket").unwrap();
+        let mut log = LogEvent::from("first");
+        *log.metadata_mut().value_mut() = vrl::value!({ "vector": { "ingest": "udp" } });
+        capture.record(&EventArray::from(vec![log, LogEvent::from("second")]));
+        capture.record(&EventArray::from(vec![LogEvent::from("third")]));
+        drop(capture);
+
+        let records = read_records(&[path]).unwrap();
+        let messages = records
+            .iter()
+            .map(|record| {
+                assert_eq!(record.source, "in");
+                assert_eq!(record.source_type, "socket");
+                record.event.as_log()["message"].to_string_lossy()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["first", "second", "third"]);
+        assert_eq!(
+            records[0].metadata,
+            vrl::value!({ "vector": { "ingest": "udp" } })
+        );
+        assert!(is_empty(&records[1].metadata));
+    }
+
+    #[test]
+    fn replay_delays() {
+        let first = Utc::now();
+        let later = first + chrono::Duration::seconds(10);
+        assert_eq!(replay_delay(first, first, 1), Duration::ZERO);
+        assert_eq!(replay_delay(first, later, 1), Duration::from_secs(10));
+        assert_eq!(replay_delay(first, later, 4), Duration::from_millis(2500));
+    }
+}
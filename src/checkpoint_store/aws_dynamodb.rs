@@ -0,0 +1,148 @@
+use async_trait::async_trait;
+use aws_types::{credentials::SharedCredentialsProvider, region::Region};
+use bytes::Bytes;
+use http::{Request, Uri};
+use hyper::Body;
+use serde_json::{json, Value};
+use vector_config::configurable_component;
+
+use super::Backend;
+use crate::{
+    aws::{AwsAuthentication, RegionOrEndpoint},
+    config::ProxyConfig,
+    http::HttpClient,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// The attribute holding the key of a checkpoint, which is the partition key of the table.
+const KEY_ATTRIBUTE: &str = "key";
+const VALUE_ATTRIBUTE: &str = "checkpoint";
+
+/// AWS DynamoDB checkpoint store configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct AwsDynamodbBackendConfig {
+    /// The name of the table the checkpoints are stored in.
+    ///
+    /// The partition key of the table must be a string attribute named `key`.
+    #[configurable(metadata(docs::examples = "vector-checkpoints"))]
+    pub table: String,
+
+    #[serde(flatten)]
+    #[configurable(derived)]
+    pub region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+impl AwsDynamodbBackendConfig {
+    pub(super) async fn build(&self, proxy: &ProxyConfig) -> crate::Result<AwsDynamodbBackend> {
+        let region = crate::aws::resolve_region(self.region.region()).await?;
+        let uri = match &self.region.endpoint {
+            Some(endpoint) => endpoint.parse::<Uri>()?,
+            None => format!("https://dynamodb.{}.amazonaws.com/", region).parse::<Uri>()?,
+        };
+        let credentials_provider = self.auth.credentials_provider(region.clone()).await?;
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, proxy)?;
+
+        Ok(AwsDynamodbBackend {
+            client,
+            uri,
+            table: self.table.clone(),
+            region: Some(region),
+            credentials_provider,
+        })
+    }
+}
+
+pub(super) struct AwsDynamodbBackend {
+    client: HttpClient,
+    uri: Uri,
+    table: String,
+    region: Option<Region>,
+    credentials_provider: SharedCredentialsProvider,
+}
+
+impl AwsDynamodbBackend {
+    /// Sends a request to the DynamoDB JSON API, returning the response body.
+    async fn send(&self, operation: &str, body: Value) -> crate::Result<Value> {
+        let mut request = Request::post(self.uri.clone())
+            .header("Content-Type", "application/x-amz-json-1.0")
+            .header("X-Amz-Target", format!("DynamoDB_20120810.{}", operation))
+            .body(Bytes::from(serde_json::to_vec(&body)?))?;
+        crate::aws::sign_request(
+            "dynamodb",
+            &mut request,
+            &self.credentials_provider,
+            &self.region,
+        )
+        .await?;
+
+        let response = self.client.send(request.map(Body::from)).await?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        if !status.is_success() {
+            return Err(format!(
+                "DynamoDB {} request failed with status {}: {}",
+                operation,
+                status,
+                String::from_utf8_lossy(&body)
+            )
+            .into());
+        }
+        Ok(serde_json::from_slice(&body)?)
+    }
+}
+
+#[async_trait]
+impl Backend for AwsDynamodbBackend {
+    async fn get(&self, key: &str) -> crate::Result<Option<String>> {
+        let response = self
+            .send(
+                "GetItem",
+                json!({
+                    "TableName": self.table,
+                    "Key": { KEY_ATTRIBUTE: { "S": key } },
+                    "ConsistentRead": true,
+                }),
+            )
+            .await?;
+        Ok(response
+            .pointer(&format!("/Item/{}/S", VALUE_ATTRIBUTE))
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned))
+    }
+
+    async fn set(&self, key: &str, value: &str) -> crate::Result<()> {
+        self.send(
+            "PutItem",
+            json!({
+                "TableName": self.table,
+                "Item": {
+                    KEY_ATTRIBUTE: { "S": key },
+                    VALUE_ATTRIBUTE: { "S": value },
+                },
+            }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> crate::Result<()> {
+        self.send(
+            "DeleteItem",
+            json!({
+                "TableName": self.table,
+                "Key": { KEY_ATTRIBUTE: { "S": key } },
+            }),
+        )
+        .await?;
+        Ok(())
+    }
+}
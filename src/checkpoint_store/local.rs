@@ -0,0 +1,72 @@
+use std::{io, path::PathBuf};
+
+use async_trait::async_trait;
+use vector_config::configurable_component;
+
+use super::Backend;
+
+/// Local checkpoint store configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct LocalBackendConfig {
+    /// The directory the checkpoints are stored in.
+    ///
+    /// Make sure the running user has write permissions to this directory.
+    #[configurable(metadata(docs::examples = "/mnt/checkpoints"))]
+    pub path: PathBuf,
+}
+
+impl LocalBackendConfig {
+    pub(super) fn build(&self) -> crate::Result<LocalBackend> {
+        std::fs::create_dir_all(&self.path)
+            .map_err(|error| format!("Could not create directory {:?}: {}", self.path, error))?;
+        Ok(LocalBackend {
+            path: self.path.clone(),
+        })
+    }
+}
+
+/// Stores each checkpoint in its own file.
+pub(super) struct LocalBackend {
+    path: PathBuf,
+}
+
+impl LocalBackend {
+    fn file(&self, key: &str) -> PathBuf {
+        let name = key
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect::<String>();
+        self.path.join(format!("{}.checkpoint", name))
+    }
+}
+
+#[async_trait]
+impl Backend for LocalBackend {
+    async fn get(&self, key: &str) -> crate::Result<Option<String>> {
+        match tokio::fs::read_to_string(self.file(key)).await {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn set(&self, key: &str, value: &str) -> crate::Result<()> {
+        // Written to a temporary file first, so that the checkpoint is replaced atomically.
+        let file = self.file(key);
+        let tmp_file = file.with_extension("checkpoint.tmp");
+        tokio::fs::write(&tmp_file, value).await?;
+        tokio::fs::rename(&tmp_file, &file).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> crate::Result<()> {
+        match tokio::fs::remove_file(self.file(key)).await {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error.into()),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Shared store of source checkpoints.
+//!
+//! The `file`, `journald`, and `kubernetes_logs` sources keep their position in what they read in
+//! a checkpoint file in the data directory. A [`CheckpointStore`] keeps a copy of those checkpoints in a backend that outlives
+//! the instance, such as Redis or DynamoDB, so that horizontally scaled or ephemeral instances
+//! resume where the previous instance running the source left off.
+//!
+//! Sources tracking their position outside of the data directory, such as `kafka` with its
+//! consumer group offsets, keep relying on their own mechanisms.
+//!
+//! Checkpoints are namespaced by the ID of the source using the store.
+#![allow(missing_docs)]
+
+#[cfg(feature = "checkpoint-stores-aws_dynamodb")]
+mod aws_dynamodb;
+mod local;
+#[cfg(feature = "checkpoint-stores-redis")]
+mod redis;
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use vector_config::configurable_component;
+use vector_core::source::Source;
+
+use crate::{config::SourceContext, internal_events::CheckpointStoreError};
+
+/// Checkpoint store configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct CheckpointStoreConfig {
+    #[serde(flatten)]
+    backend: CheckpointBackendConfig,
+
+    /// How often the checkpoints are copied to the store, in seconds.
+    ///
+    /// The checkpoints are also copied when the source stops.
+    #[serde(default = "default_sync_interval_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub sync_interval_secs: u64,
+}
+
+const fn default_sync_interval_secs() -> u64 {
+    5
+}
+
+/// Backend of the checkpoint store.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The backend of the checkpoint store."))]
+pub enum CheckpointBackendConfig {
+    /// Stores the checkpoints in a directory, such as a volume that outlives the instance.
+    Local(local::LocalBackendConfig),
+
+    /// Stores the checkpoints in Redis.
+    #[cfg(feature = "checkpoint-stores-redis")]
+    Redis(redis::RedisBackendConfig),
+
+    /// Stores the checkpoints in an AWS DynamoDB table.
+    #[cfg(feature = "checkpoint-stores-aws_dynamodb")]
+    AwsDynamodb(aws_dynamodb::AwsDynamodbBackendConfig),
+}
+
+impl CheckpointStoreConfig {
+    /// Builds the store for the source being built.
+    pub async fn build(&self, cx: &SourceContext) -> crate::Result<CheckpointStore> {
+        if self.sync_interval_secs == 0 {
+            return Err("`checkpoint_store.sync_interval_secs` must be greater than zero.".into());
+        }
+        let backend: Arc<dyn Backend> = match &self.backend {
+            CheckpointBackendConfig::Local(config) => Arc::new(config.build()?),
+            #[cfg(feature = "checkpoint-stores-redis")]
+            CheckpointBackendConfig::Redis(config) => Arc::new(config.build().await?),
+            #[cfg(feature = "checkpoint-stores-aws_dynamodb")]
+            CheckpointBackendConfig::AwsDynamodb(config) => {
+                Arc::new(config.build(&cx.proxy).await?)
+            }
+        };
+
+        Ok(CheckpointStore {
+            backend,
+            namespace: cx.key.id().to_owned(),
+            sync_interval: Duration::from_secs(self.sync_interval_secs),
+        })
+    }
+}
+
+/// A backend of the checkpoint store, holding checkpoints by key.
+#[async_trait]
+trait Backend: Send + Sync {
+    async fn get(&self, key: &str) -> crate::Result<Option<String>>;
+
+    async fn set(&self, key: &str, value: &str) -> crate::Result<()>;
+
+    async fn delete(&self, key: &str) -> crate::Result<()>;
+}
+
+/// The checkpoints of a source, kept in a shared backend.
+#[derive(Clone)]
+pub struct CheckpointStore {
+    backend: Arc<dyn Backend>,
+    namespace: String,
+    sync_interval: Duration,
+}
+
+impl CheckpointStore {
+    fn key(&self, name: &str) -> String {
+        format!("{}/{}", self.namespace, name)
+    }
+
+    pub async fn get(&self, name: &str) -> crate::Result<Option<String>> {
+        self.backend.get(&self.key(name)).await
+    }
+
+    pub async fn set(&self, name: &str, value: &str) -> crate::Result<()> {
+        self.backend.set(&self.key(name), value).await
+    }
+
+    pub async fn delete(&self, name: &str) -> crate::Result<()> {
+        self.backend.delete(&self.key(name)).await
+    }
+
+    /// Restores a checkpoint file from the store, unless the file already exists, as it is then at
+    /// least as recent as the copy in the store.
+    pub async fn restore_file(&self, name: &str, path: &Path) -> crate::Result<()> {
+        if tokio::fs::try_exists(path).await? {
+            return Ok(());
+        }
+        if let Some(contents) = self.get(name).await? {
+            debug!(message = "Restoring checkpoints from the checkpoint store.", path = ?path);
+            tokio::fs::write(path, contents).await?;
+        }
+        Ok(())
+    }
+
+    /// Runs the source, copying its checkpoint file to the store periodically, and once more when
+    /// the source stops.
+    pub fn mirror_file(self, name: impl Into<String>, path: PathBuf, source: Source) -> Source {
+        let name = name.into();
+        Box::pin(async move {
+            let mut source = source;
+            let mut interval = tokio::time::interval(self.sync_interval);
+            let mut synced = None;
+            let result = loop {
+                tokio::select! {
+                    result = &mut source => break result,
+                    _ = interval.tick() => self.sync_file(&name, &path, &mut synced).await,
+                }
+            };
+            self.sync_file(&name, &path, &mut synced).await;
+            result
+        })
+    }
+
+    /// Copies the checkpoint file to the store, if it changed since it was last copied.
+    async fn sync_file(&self, name: &str, path: &Path, synced: &mut Option<String>) {
+        let contents = match tokio::fs::read_to_string(path).await {
+            Ok(contents) => contents,
+            // The source hasn't written any checkpoints yet.
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return,
+            Err(error) => {
+                emit!(CheckpointStoreError {
+                    error: error.into(),
+                    component_id: &self.namespace,
+                });
+                return;
+            }
+        };
+        if synced.as_ref() == Some(&contents) {
+            return;
+        }
+        match self.set(name, &contents).await {
+            Ok(()) => *synced = Some(contents),
+            Err(error) => emit!(CheckpointStoreError {
+                error,
+                component_id: &self.namespace,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(dir: &Path) -> CheckpointStore {
+        let config: CheckpointStoreConfig =
+            toml::from_str(&format!("type = \"local\"\npath = {:?}", dir)).unwrap();
+        let backend = match &config.backend {
+            CheckpointBackendConfig::Local(config) => config.build().unwrap(),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        };
+        CheckpointStore {
+            backend: Arc::new(backend),
+            namespace: "in".to_owned(),
+            sync_interval: Duration::from_millis(10),
+        }
+    }
+
+    #[tokio::test]
+    async fn local_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(dir.path());
+
+        assert_eq!(store.get("offsets").await.unwrap(), None);
+        store.set("offsets", "{\"a\":1}").await.unwrap();
+        store.set("offsets", "{\"a\":2}").await.unwrap();
+        assert_eq!(
+            store.get("offsets").await.unwrap().as_deref(),
+            Some("{\"a\":2}")
+        );
+        store.delete("offsets").await.unwrap();
+        assert_eq!(store.get("offsets").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn mirrors_checkpoint_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(&dir.path().join("store"));
+        let path = dir.path().join("checkpoints.json");
+
+        let source_path = path.clone();
+        let source = Box::pin(async move {
+            tokio::fs::write(&source_path, "first").await.unwrap();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            tokio::fs::write(&source_path, "last").await.unwrap();
+            Ok(())
+        });
+        store
+            .clone()
+            .mirror_file("checkpoints", path.clone(), source)
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get("checkpoints").await.unwrap().as_deref(),
+            Some("last")
+        );
+
+        // An existing file is more recent than the store.
+        tokio::fs::write(&path, "local").await.unwrap();
+        store.restore_file("checkpoints", &path).await.unwrap();
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), "local");
+
+        tokio::fs::remove_file(&path).await.unwrap();
+        store.restore_file("checkpoints", &path).await.unwrap();
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), "last");
+    }
+}
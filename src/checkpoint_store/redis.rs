@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use vector_config::configurable_component;
+
+use super::Backend;
+
+/// Redis checkpoint store configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct RedisBackendConfig {
+    /// The URL of the Redis endpoint to connect to.
+    ///
+    /// The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
+    /// `redis` or `rediss` for connections secured via TLS.
+    #[configurable(metadata(docs::examples = "redis://127.0.0.1:6379/0"))]
+    pub endpoint: String,
+
+    /// The prefix of the Redis keys the checkpoints are stored at.
+    #[serde(default = "default_key_prefix")]
+    pub key_prefix: String,
+}
+
+fn default_key_prefix() -> String {
+    "vector:checkpoints:".to_owned()
+}
+
+impl RedisBackendConfig {
+    pub(super) async fn build(&self) -> crate::Result<RedisBackend> {
+        let client = redis::Client::open(self.endpoint.as_str())?;
+        let connection = client.get_tokio_connection_manager().await?;
+        Ok(RedisBackend {
+            connection,
+            key_prefix: self.key_prefix.clone(),
+        })
+    }
+}
+
+pub(super) struct RedisBackend {
+    connection: ConnectionManager,
+    key_prefix: String,
+}
+
+#[async_trait]
+impl Backend for RedisBackend {
+    async fn get(&self, key: &str) -> crate::Result<Option<String>> {
+        Ok(redis::cmd("GET")
+            .arg(format!("{}{}", self.key_prefix, key))
+            .query_async(&mut self.connection.clone())
+            .await?)
+    }
+
+    async fn set(&self, key: &str, value: &str) -> crate::Result<()> {
+        Ok(redis::cmd("SET")
+            .arg(format!("{}{}", self.key_prefix, key))
+            .arg(value)
+            .query_async(&mut self.connection.clone())
+            .await?)
+    }
+
+    async fn delete(&self, key: &str) -> crate::Result<()> {
+        Ok(redis::cmd("DEL")
+            .arg(format!("{}{}", self.key_prefix, key))
+            .query_async(&mut self.connection.clone())
+            .await?)
+    }
+}
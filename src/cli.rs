@@ -1,15 +1,20 @@
 #![allow(missing_docs)]
-use std::path::PathBuf;
+use std::{num::NonZeroU64, path::PathBuf};
 
 use clap::{ArgAction, CommandFactory, FromArgMatches, Parser};
 
+#[cfg(feature = "api")]
+use crate::reload;
 #[cfg(windows)]
 use crate::service;
 #[cfg(feature = "api-client")]
 use crate::tap;
 #[cfg(feature = "api-client")]
 use crate::top;
-use crate::{config, generate, get_version, graph, list, unit_test, validate};
+use crate::{
+    audit, benchmark, capture, config, generate, get_version, graph, list, schema_inference,
+    unit_test, validate,
+};
 
 #[derive(Parser, Debug)]
 #[command(rename_all = "kebab-case")]
@@ -34,7 +39,10 @@ impl Opts {
             | Some(SubCommand::Graph(_))
             | Some(SubCommand::Generate(_))
             | Some(SubCommand::List(_))
-            | Some(SubCommand::Test(_)) => {
+            | Some(SubCommand::Test(_))
+            | Some(SubCommand::Benchmark(_))
+            | Some(SubCommand::Replay(_))
+            | Some(SubCommand::Schema(_)) => {
                 if self.root.verbose == 0 {
                     (self.root.quiet + 1, self.root.verbose)
                 } else {
@@ -119,6 +127,15 @@ pub struct RootOpts {
     #[arg(short, long, env = "VECTOR_REQUIRE_HEALTHY")]
     pub require_healthy: Option<bool>,
 
+    /// The maximum amount of time, in seconds, to wait for components to drain their events
+    /// when shutting down, before forcefully stopping them.
+    #[arg(
+        long,
+        env = "VECTOR_GRACEFUL_SHUTDOWN_LIMIT_SECS",
+        default_value = "60"
+    )]
+    pub graceful_shutdown_limit_secs: NonZeroU64,
+
     /// Number of threads to use for processing (default is number of available cores)
     #[arg(short, long, env = "VECTOR_THREADS")]
     pub threads: Option<usize>,
@@ -149,6 +166,19 @@ pub struct RootOpts {
     #[arg(short, long, env = "VECTOR_WATCH_CONFIG")]
     pub watch_config: bool,
 
+    /// Periodically re-resolve secrets from the configured secret backends, every given number
+    /// of seconds, and reload the components using any secret whose value has been rotated.
+    #[arg(long, env = "VECTOR_SECRETS_REFRESH_INTERVAL_SECS")]
+    pub secrets_refresh_interval_secs: Option<NonZeroU64>,
+
+    /// Account for the CPU time spent running each component in the
+    /// `component_cpu_time_nanoseconds_total` internal metric.
+    ///
+    /// This adds a small overhead to every poll of a component task, and is only available on Unix.
+    #[cfg(unix)]
+    #[arg(long, env = "VECTOR_CPU_TIME_ACCOUNTING", default_value = "false")]
+    pub cpu_time_accounting: bool,
+
     /// Set the internal log rate limit
     #[arg(
         short,
@@ -220,9 +250,20 @@ pub enum SubCommand {
     /// For guidance on how to write unit tests check out <https://vector.dev/guides/level-up/unit-testing/>.
     Test(unit_test::Opts),
 
+    /// Send events at full speed through encoding codecs, or through the transforms and sinks of a
+    /// config, and report the throughput, latency and allocations, then exit.
+    Benchmark(benchmark::Opts),
+
+    /// Replay events captured by sources through a config, at the pace they were ingested or
+    /// faster, then exit.
+    Replay(capture::Opts),
+
     /// Output the topology as visual representation using the DOT language which can be rendered by GraphViz
     Graph(graph::Opts),
 
+    /// Infer the schema of the events encoded by sinks from the components feeding into them.
+    Schema(schema_inference::Opts),
+
     /// Display topology and metrics in the console, for a local or remote Vector instance
     #[cfg(feature = "api-client")]
     Top(top::Opts),
@@ -231,6 +272,10 @@ pub enum SubCommand {
     #[cfg(feature = "api-client")]
     Tap(tap::Opts),
 
+    /// Reload the config of a running Vector instance through its API.
+    #[cfg(feature = "api")]
+    Reload(reload::Opts),
+
     /// Manage the vector service.
     #[cfg(windows)]
     Service(service::Opts),
@@ -238,6 +283,9 @@ pub enum SubCommand {
     /// Vector Remap Language CLI
     #[cfg(feature = "vrl-cli")]
     Vrl(vrl_cli::Opts),
+
+    /// Inspect the audit log of control-plane actions.
+    Audit(audit::Opts),
 }
 
 #[derive(clap::ValueEnum, Debug, Clone, PartialEq, Eq)]
@@ -42,6 +42,11 @@ impl Decoder {
         }
     }
 
+    /// Get the deserializer.
+    pub const fn deserializer(&self) -> &Deserializer {
+        &self.deserializer
+    }
+
     /// Sets the log namespace that will be used when decoding.
     pub const fn with_log_namespace(mut self, log_namespace: LogNamespace) -> Self {
         self.log_namespace = log_namespace;
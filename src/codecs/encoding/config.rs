@@ -1,7 +1,7 @@
 use crate::codecs::Transformer;
 use codecs::{
     encoding::{Framer, FramingConfig, Serializer, SerializerConfig},
-    CharacterDelimitedEncoder, LengthDelimitedEncoder, NewlineDelimitedEncoder,
+    BytesEncoder, CharacterDelimitedEncoder, LengthDelimitedEncoder, NewlineDelimitedEncoder,
 };
 use vector_config::configurable_component;
 
@@ -104,16 +104,38 @@ impl EncodingConfigWithFraming {
                 SinkType::StreamBased => NewlineDelimitedEncoder::new().into(),
                 SinkType::MessageBased => CharacterDelimitedEncoder::new(b',').into(),
             },
-            (None, Serializer::Avro(_) | Serializer::Native(_)) => {
-                LengthDelimitedEncoder::new().into()
+            (None, Serializer::Cbor(serializer)) if serializer.is_sequence() => {
+                BytesEncoder::new().into()
             }
             (
                 None,
-                Serializer::Gelf(_)
+                Serializer::Avro(_)
+                | Serializer::Cbor(_)
+                | Serializer::Msgpack(_)
+                | Serializer::Native(_)
+                | Serializer::Protobuf(_),
+            ) => LengthDelimitedEncoder::new().into(),
+            (None, Serializer::Csv(serializer)) if serializer.is_terminated() => {
+                BytesEncoder::new().into()
+            }
+            #[cfg(feature = "codecs-arrow")]
+            (None, Serializer::ArrowStream(_)) => BytesEncoder::new().into(),
+            #[cfg(feature = "codecs-parquet")]
+            (None, Serializer::Parquet(_)) => BytesEncoder::new().into(),
+            (
+                None,
+                Serializer::Cef(_)
+                | Serializer::Csv(_)
+                | Serializer::FixedWidth(_)
+                | Serializer::Gelf(_)
+                | Serializer::Leef(_)
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
+                | Serializer::Pretty(_)
                 | Serializer::RawMessage(_)
-                | Serializer::Text(_),
+                | Serializer::Syslog(_)
+                | Serializer::Text(_)
+                | Serializer::Xml(_),
             ) => NewlineDelimitedEncoder::new().into(),
         };
 
@@ -122,6 +144,7 @@ impl EncodingConfigWithFraming {
 }
 
 /// The way a sink processes outgoing events.
+#[derive(Clone, Copy, Debug)]
 pub enum SinkType {
     /// Events are sent in a continuous stream.
     StreamBased,
@@ -144,10 +167,10 @@ where
 
 #[cfg(test)]
 mod test {
-    use lookup::lookup_v2::{parse_value_path, ConfigOwnedValuePath};
+    use lookup::lookup_v2::parse_value_path;
 
     use super::*;
-    use crate::codecs::encoding::TimestampFormat;
+    use crate::codecs::encoding::{OnlyField, TimestampFormat};
 
     #[test]
     fn deserialize_encoding_config() {
@@ -169,9 +192,7 @@ mod test {
 
         assert_eq!(
             transformer.only_fields(),
-            &Some(vec![ConfigOwnedValuePath(
-                parse_value_path("a.b[0]").unwrap()
-            )])
+            &Some(vec![OnlyField::from(parse_value_path("a.b[0]").unwrap())])
         );
         assert_eq!(
             transformer.except_fields(),
@@ -206,9 +227,7 @@ mod test {
 
         assert_eq!(
             transformer.only_fields(),
-            &Some(vec![ConfigOwnedValuePath(
-                parse_value_path("a.b[0]").unwrap()
-            )])
+            &Some(vec![OnlyField::from(parse_value_path("a.b[0]").unwrap())])
         );
         assert_eq!(
             transformer.except_fields(),
@@ -240,9 +259,7 @@ mod test {
 
         assert_eq!(
             transformer.only_fields(),
-            &Some(vec![ConfigOwnedValuePath(
-                parse_value_path("a.b[0]").unwrap()
-            )])
+            &Some(vec![OnlyField::from(parse_value_path("a.b[0]").unwrap())])
         );
         assert_eq!(
             transformer.except_fields(),
@@ -1,7 +1,7 @@
 use bytes::BytesMut;
 use codecs::{
     encoding::{Error, Framer, Serializer},
-    CharacterDelimitedEncoder, NewlineDelimitedEncoder, TextSerializerConfig,
+    CharacterDelimitedEncoder, NewlineDelimitedEncoder, TextSerializer,
 };
 use tokio_util::codec::Encoder as _;
 
@@ -24,7 +24,7 @@ impl Default for Encoder<Framer> {
     fn default() -> Self {
         Self {
             framer: NewlineDelimitedEncoder::new().into(),
-            serializer: TextSerializerConfig::default().build().into(),
+            serializer: TextSerializer::new(Default::default()).into(),
         }
     }
 }
@@ -33,7 +33,7 @@ impl Default for Encoder<()> {
     fn default() -> Self {
         Self {
             framer: (),
-            serializer: TextSerializerConfig::default().build().into(),
+            serializer: TextSerializer::new(Default::default()).into(),
         }
     }
 }
@@ -103,6 +103,88 @@ impl Encoder<Framer> {
         }
     }
 
+    /// Get the header written once at the start of each batch of events, or of each file when
+    /// events are written to files one at a time.
+    pub fn header(&self) -> &[u8] {
+        match &self.serializer {
+            Serializer::Csv(serializer) => serializer.header(),
+            _ => &[],
+        }
+    }
+
+    /// Whether the serializer encodes batches of events as a whole, such as Parquet files or Arrow
+    /// IPC streams, which can't be made of events encoded one at a time.
+    pub const fn serializes_batches(&self) -> bool {
+        match self.serializer {
+            #[cfg(feature = "codecs-arrow")]
+            Serializer::ArrowStream(_) => true,
+            #[cfg(feature = "codecs-parquet")]
+            Serializer::Parquet(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Serialize a batch of events as a whole, without applying framing.
+    ///
+    /// Serializers that don't encode batches as a whole serialize the events one after the other.
+    pub fn serialize_batch(
+        &mut self,
+        events: Vec<Event>,
+        buffer: &mut BytesMut,
+    ) -> Result<(), Error> {
+        match &self.serializer {
+            #[cfg(feature = "codecs-arrow")]
+            Serializer::ArrowStream(serializer) => serializer
+                .encode_batch(events, buffer)
+                .map_err(serializing_error),
+            #[cfg(feature = "codecs-parquet")]
+            Serializer::Parquet(serializer) => serializer
+                .encode_batch(events, buffer)
+                .map_err(serializing_error),
+            _ => events
+                .into_iter()
+                .try_for_each(|event| self.serialize(event, buffer)),
+        }
+    }
+
+    /// Whether the framer compresses each batch of encoded events into a single frame, with
+    /// [`Encoder::compress_batch`].
+    pub const fn compresses_batches(&self) -> bool {
+        matches!(self.framer, Framer::Compressed(_))
+    }
+
+    /// Compress the encoded events of a batch, held by `buffer`, into a single frame, if the framer
+    /// compresses batches.
+    pub fn compress_batch(&self, buffer: &mut BytesMut) -> Result<(), Error> {
+        match &self.framer {
+            Framer::Compressed(framer) => framer.compress(buffer).map_err(|error| {
+                emit!(EncoderFramingError { error: &error });
+                Error::FramingError(error)
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Encode a batch of events, serialized as a whole if the serializer encodes batches and
+    /// framed otherwise, and compress it into a single frame if the framer compresses batches.
+    pub fn encode_batch(&mut self, events: Vec<Event>, buffer: &mut BytesMut) -> Result<(), Error> {
+        let len = buffer.len();
+        let mut payload = buffer.split_off(len);
+
+        if self.serializes_batches() {
+            self.serialize_batch(events, &mut payload)?;
+        } else {
+            for event in events {
+                self.encode(event, &mut payload)?;
+            }
+        }
+        self.compress_batch(&mut payload)?;
+
+        buffer.unsplit(payload);
+
+        Ok(())
+    }
+
     /// Get the HTTP content type.
     pub const fn content_type(&self) -> &'static str {
         match (&self.serializer, &self.framer) {
@@ -113,14 +195,29 @@ impl Encoder<Framer> {
                 Serializer::Gelf(_) | Serializer::Json(_) | Serializer::NativeJson(_),
                 Framer::CharacterDelimited(CharacterDelimitedEncoder { delimiter: b',' }),
             ) => "application/json",
+            #[cfg(feature = "codecs-arrow")]
+            (Serializer::ArrowStream(_), _) => "application/vnd.apache.arrow.stream",
+            #[cfg(feature = "codecs-parquet")]
+            (Serializer::Parquet(_), _) => "application/octet-stream",
             (Serializer::Native(_), _) => "application/octet-stream",
+            (Serializer::Cbor(serializer), _) if serializer.is_sequence() => "application/cbor-seq",
+            (Serializer::Cbor(_), _) => "application/cbor",
+            (Serializer::Csv(_), _) => "text/csv",
+            (Serializer::Protobuf(_), _) => "application/x-protobuf",
+            (Serializer::Msgpack(_), _) => "application/msgpack",
+            (Serializer::Xml(_), _) => "application/xml",
             (
                 Serializer::Avro(_)
+                | Serializer::Cef(_)
+                | Serializer::FixedWidth(_)
                 | Serializer::Gelf(_)
                 | Serializer::Json(_)
+                | Serializer::Leef(_)
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
+                | Serializer::Pretty(_)
                 | Serializer::RawMessage(_)
+                | Serializer::Syslog(_)
                 | Serializer::Text(_),
                 _,
             ) => "text/plain",
@@ -128,6 +225,12 @@ impl Encoder<Framer> {
     }
 }
 
+#[cfg(any(feature = "codecs-arrow", feature = "codecs-parquet"))]
+fn serializing_error(error: vector_common::Error) -> Error {
+    emit!(EncoderSerializeError { error: &error });
+    Error::SerializingError(error)
+}
+
 impl Encoder<()> {
     /// Creates a new `Encoder` with the specified `Serializer` to produce bytes
     /// from a structured event.
@@ -183,7 +286,7 @@ impl tokio_util::codec::Encoder<Event> for Encoder<()> {
 #[cfg(test)]
 mod tests {
     use bytes::BufMut;
-    use codecs::encoding::BoxedFramingError;
+    use codecs::{encoding::BoxedFramingError, TextSerializerConfig};
     use futures_util::{SinkExt, StreamExt};
     use tokio_util::codec::FramedWrite;
     use vector_core::event::LogEvent;
@@ -248,7 +351,7 @@ mod tests {
     async fn test_encode_events_sink_empty() {
         let encoder = Encoder::<Framer>::new(
             Framer::Boxed(Box::new(ParenEncoder::new())),
-            TextSerializerConfig::default().build().into(),
+            TextSerializerConfig::default().build().unwrap().into(),
         );
         let source = futures::stream::iter(vec![
             Event::Log(LogEvent::from("foo")),
@@ -267,7 +370,7 @@ mod tests {
     async fn test_encode_events_sink_non_empty() {
         let encoder = Encoder::<Framer>::new(
             Framer::Boxed(Box::new(ParenEncoder::new())),
-            TextSerializerConfig::default().build().into(),
+            TextSerializerConfig::default().build().unwrap().into(),
         );
         let source = futures::stream::iter(vec![
             Event::Log(LogEvent::from("bar")),
@@ -286,7 +389,7 @@ mod tests {
     async fn test_encode_events_sink_empty_handle_framing_error() {
         let encoder = Encoder::<Framer>::new(
             Framer::Boxed(Box::new(ErrorNthEncoder::new(ParenEncoder::new(), 1))),
-            TextSerializerConfig::default().build().into(),
+            TextSerializerConfig::default().build().unwrap().into(),
         );
         let source = futures::stream::iter(vec![
             Event::Log(LogEvent::from("foo")),
@@ -306,7 +409,7 @@ mod tests {
     async fn test_encode_events_sink_non_empty_handle_framing_error() {
         let encoder = Encoder::<Framer>::new(
             Framer::Boxed(Box::new(ErrorNthEncoder::new(ParenEncoder::new(), 1))),
-            TextSerializerConfig::default().build().into(),
+            TextSerializerConfig::default().build().unwrap().into(),
         );
         let source = futures::stream::iter(vec![
             Event::Log(LogEvent::from("bar")),
@@ -1,7 +1,9 @@
 mod config;
 mod encoder;
+mod selector;
 mod transformer;
 
 pub use config::{EncodingConfig, EncodingConfigWithFraming, SinkType};
 pub use encoder::Encoder;
-pub use transformer::{TimestampFormat, Transformer};
+pub use selector::{CodecSelector, CodecSelectorConfig};
+pub use transformer::{OnlyField, Redaction, RedactionMethod, TimestampFormat, Transformer};
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use codecs::encoding::{Framer, SerializerConfig};
+use vector_config::configurable_component;
+use vector_core::config::DataType;
+
+use crate::{
+    codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    event::Event,
+    internal_events::TemplateRenderingError,
+    template::Template,
+};
+
+/// Selection of the codec of each event.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CodecSelectorConfig {
+    /// The template rendered for each event to select its codec.
+    ///
+    /// Events are encoded with the codec of `codecs` named by the rendered value. If there is no
+    /// such codec, or the template fails to render, the event is encoded with the `encoding` of
+    /// the sink.
+    #[configurable(metadata(docs::examples = "{{ format }}"))]
+    pub key: Template,
+
+    /// The codecs to select from, by the rendered value of `key`.
+    ///
+    /// The framing of the sink applies to all of them.
+    #[configurable(metadata(
+        docs::additional_props_description = "The codec of the events with this key."
+    ))]
+    pub codecs: HashMap<String, SerializerConfig>,
+}
+
+impl CodecSelectorConfig {
+    /// The types of events that all of the codecs, including the default one, can encode.
+    pub fn input_type(&self, default: &SerializerConfig) -> DataType {
+        self.codecs
+            .values()
+            .fold(default.input_type(), |types, codec| {
+                types & codec.input_type()
+            })
+    }
+
+    /// Builds the selector, with the codecs framed like the default encoding.
+    pub fn build(
+        &self,
+        encoding: &EncodingConfigWithFraming,
+        sink_type: SinkType,
+    ) -> crate::Result<CodecSelector> {
+        let (framing, _) = encoding.config();
+        let encoders = self
+            .codecs
+            .iter()
+            .map(|(key, codec)| {
+                let (framer, serializer) = EncodingConfigWithFraming::new(
+                    framing.clone(),
+                    codec.clone(),
+                    Transformer::default(),
+                )
+                .build(sink_type)?;
+                Ok((key.clone(), Encoder::<Framer>::new(framer, serializer)))
+            })
+            .collect::<crate::Result<_>>()?;
+
+        Ok(CodecSelector {
+            key: self.key.clone(),
+            encoders,
+        })
+    }
+}
+
+/// Selects the codec of each event, so that sinks can partition events by codec.
+///
+/// Only the `http` sink partitions its requests by codec, so it is the only sink accepting a
+/// selector in its config.
+#[derive(Clone, Debug)]
+pub struct CodecSelector {
+    key: Template,
+    encoders: HashMap<String, Encoder<Framer>>,
+}
+
+impl CodecSelector {
+    /// Selects the codec of the event, returning `None` for the default codec.
+    pub fn select(&self, event: &Event) -> Option<String> {
+        match self.key.render_string(event) {
+            Ok(key) => self.encoders.contains_key(&key).then_some(key),
+            Err(error) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("codec_selector.key"),
+                    drop_event: false,
+                });
+                None
+            }
+        }
+    }
+
+    /// Gets the encoder of a selected codec.
+    pub fn encoder(&self, key: &str) -> Option<&Encoder<Framer>> {
+        self.encoders.get(key)
+    }
+
+    /// Gets the encoder of a selected codec, to encode events with.
+    pub fn encoder_mut(&mut self, key: &str) -> Option<&mut Encoder<Framer>> {
+        self.encoders.get_mut(key)
+    }
+
+    /// Iterates over the encoders of the codecs that can be selected.
+    pub fn encoders(&self) -> impl Iterator<Item = &Encoder<Framer>> {
+        self.encoders.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codecs::{encoding::Serializer, JsonSerializerConfig};
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn selects_codec_by_key() {
+        let config: CodecSelectorConfig = toml::from_str(
+            r#"
+            key = "{{ format }}"
+            codecs.logfmt.codec = "logfmt"
+            codecs.text.codec = "text"
+            "#,
+        )
+        .unwrap();
+        let encoding = (
+            None::<codecs::encoding::FramingConfig>,
+            JsonSerializerConfig::default(),
+        )
+            .into();
+        let selector = config.build(&encoding, SinkType::MessageBased).unwrap();
+
+        let mut event = LogEvent::from("hello");
+        event.insert("format", "logfmt");
+        let key = selector.select(&event.clone().into());
+        assert_eq!(key.as_deref(), Some("logfmt"));
+        assert!(matches!(
+            selector.encoder("logfmt").unwrap().serializer(),
+            Serializer::Logfmt(_)
+        ));
+
+        event.insert("format", "cef");
+        assert_eq!(selector.select(&event.into()), None);
+        assert_eq!(selector.select(&LogEvent::from("hello").into()), None);
+
+        assert_eq!(
+            config.input_type(&JsonSerializerConfig::default().into()),
+            DataType::Log
+        );
+    }
+
+    #[cfg(all(feature = "sinks-aws_s3", feature = "sinks-gcp"))]
+    #[test]
+    fn rejected_by_other_sinks() {
+        let error = toml::from_str::<crate::sinks::aws_s3::S3SinkConfig>(
+            r#"
+            bucket = "logs"
+            encoding.codec = "json"
+            codec_selector.key = "{{ format }}"
+            codec_selector.codecs.text.codec = "text"
+            "#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("unknown field `codec_selector`"));
+
+        let error = toml::from_str::<crate::sinks::gcp::pubsub::PubsubConfig>(
+            r#"
+            project = "vector"
+            topic = "logs"
+            encoding.codec = "json"
+            codec_selector.key = "{{ format }}"
+            codec_selector.codecs.text.codec = "text"
+            "#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("unknown field `codec_selector`"));
+    }
+}
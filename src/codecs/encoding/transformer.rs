@@ -3,14 +3,14 @@
 use core::fmt::Debug;
 use std::collections::BTreeMap;
 
-use lookup::lookup_v2::ConfigOwnedValuePath;
 use lookup::{
     event_path,
-    lookup_v2::{parse_value_path, OwnedValuePath},
+    lookup_v2::{parse_value_path, OwnedValuePath, PathParseError},
     PathPrefix,
 };
 use serde::{Deserialize, Deserializer};
-use value::Value;
+use sha2::{Digest, Sha256};
+use value::{kind::Collection, Kind, Value};
 use vector_config::configurable_component;
 use vector_core::event::{LogEvent, MaybeAsLogMut};
 
@@ -21,8 +21,13 @@ use crate::{event::Event, serde::skip_serializing_if_default};
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Transformer {
     /// List of fields that will be included in the encoded event.
+    ///
+    /// Fields prefixed with `%metadata.` are read from the metadata of the event rather than from
+    /// the event, and are included in the encoded event under `metadata`. This gives access to the
+    /// source metadata of events from sources using the `vector` log namespace, such as
+    /// `%metadata.kafka.offset` or `%metadata.vector.ingest_timestamp`.
     #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
-    only_fields: Option<Vec<ConfigOwnedValuePath>>,
+    only_fields: Option<Vec<OnlyField>>,
 
     /// List of fields that will be excluded from the encoded event.
     #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
@@ -31,6 +36,13 @@ pub struct Transformer {
     /// Format used for timestamp fields.
     #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
     timestamp_format: Option<TimestampFormat>,
+
+    /// Fields whose values are masked or hashed in the encoded event.
+    ///
+    /// Redaction is applied last, when the event is encoded, so the values of these fields never
+    /// reach the sink, regardless of the transforms the events went through.
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    redact: Option<Redaction>,
 }
 
 impl<'de> Deserialize<'de> for Transformer {
@@ -42,19 +54,22 @@ impl<'de> Deserialize<'de> for Transformer {
         #[serde(deny_unknown_fields)]
         struct TransformerInner {
             #[serde(default)]
-            only_fields: Option<Vec<OwnedValuePath>>,
+            only_fields: Option<Vec<OnlyField>>,
             #[serde(default)]
             except_fields: Option<Vec<String>>,
             #[serde(default)]
             timestamp_format: Option<TimestampFormat>,
+            #[serde(default)]
+            redact: Option<Redaction>,
         }
 
         let inner: TransformerInner = Deserialize::deserialize(deserializer)?;
-        Self::new(
+        Self::from_fields(
             inner.only_fields,
             inner.except_fields,
             inner.timestamp_format,
         )
+        .and_then(|transformer| transformer.with_redaction(inner.redact))
         .map_err(serde::de::Error::custom)
     }
 }
@@ -68,20 +83,47 @@ impl Transformer {
         only_fields: Option<Vec<OwnedValuePath>>,
         except_fields: Option<Vec<String>>,
         timestamp_format: Option<TimestampFormat>,
+    ) -> Result<Self, crate::Error> {
+        Self::from_fields(
+            only_fields.map(|x| x.into_iter().map(OnlyField::from).collect()),
+            except_fields,
+            timestamp_format,
+        )
+    }
+
+    fn from_fields(
+        only_fields: Option<Vec<OnlyField>>,
+        except_fields: Option<Vec<String>>,
+        timestamp_format: Option<TimestampFormat>,
     ) -> Result<Self, crate::Error> {
         Self::validate_fields(only_fields.as_ref(), except_fields.as_ref())?;
 
-        let only_fields = only_fields.map(|x| x.into_iter().map(ConfigOwnedValuePath).collect());
         Ok(Self {
             only_fields,
             except_fields,
             timestamp_format,
+            redact: None,
         })
     }
 
+    /// Sets the redaction of fields in the encoded event.
+    ///
+    /// Returns `Err` if a path of the redacted fields is invalid.
+    pub fn with_redaction(mut self, redact: Option<Redaction>) -> Result<Self, crate::Error> {
+        if let Some(redact) = redact.as_ref() {
+            for field in &redact.fields {
+                parse_value_path(field).map_err(|error| {
+                    format!("Invalid redacted field path {:?}: {}", field, error)
+                })?;
+            }
+        }
+        self.redact = redact;
+        Ok(self)
+    }
+
     /// Get the `Transformer`'s `only_fields`.
     #[cfg(test)]
-    pub const fn only_fields(&self) -> &Option<Vec<ConfigOwnedValuePath>> {
+    pub const fn only_fields(&self) -> &Option<Vec<OnlyField>> {
         &self.only_fields
     }
 
@@ -99,13 +141,15 @@ impl Transformer {
     ///
     /// If an error is returned, the entire encoding configuration should be considered inoperable.
     fn validate_fields(
-        only_fields: Option<&Vec<OwnedValuePath>>,
+        only_fields: Option<&Vec<OnlyField>>,
         except_fields: Option<&Vec<String>>,
     ) -> crate::Result<()> {
         if let (Some(only_fields), Some(except_fields)) = (only_fields, except_fields) {
             if except_fields.iter().any(|f| {
                 let path_iter = parse_value_path(f).unwrap();
-                only_fields.iter().any(|v| v == &path_iter)
+                only_fields
+                    .iter()
+                    .any(|v| v.metadata.is_none() && v.path == path_iter)
             }) {
                 return Err(
                     "`except_fields` and `only_fields` should be mutually exclusive.".into(),
@@ -123,6 +167,8 @@ impl Transformer {
             self.apply_except_fields(log);
             self.apply_only_fields(log);
             self.apply_timestamp_format(log);
+            // Redaction comes last, so the fields can't be reintroduced by another rule.
+            self.apply_redaction(log);
         }
     }
 
@@ -131,8 +177,12 @@ impl Transformer {
             let old_value = std::mem::replace(log.value_mut(), Value::Object(BTreeMap::new()));
 
             for field in only_fields {
-                if let Some(value) = old_value.get(field) {
-                    log.insert((PathPrefix::Event, field), value.clone());
+                let value = match &field.metadata {
+                    Some(metadata) => log.metadata().value().get(metadata),
+                    None => old_value.get(&field.path),
+                };
+                if let Some(value) = value.cloned() {
+                    log.insert((PathPrefix::Event, &field.path), value);
                 }
             }
         }
@@ -146,6 +196,54 @@ impl Transformer {
         }
     }
 
+    /// Applies the transformation rules to the kind of the events, rather than to an event, to
+    /// infer the schema of the encoded events.
+    pub fn transform_kind(&self, mut kind: Kind) -> Kind {
+        if let Some(except_fields) = self.except_fields.as_ref() {
+            for field in except_fields {
+                if let Ok(path) = parse_value_path(field) {
+                    kind.remove(&path, false);
+                }
+            }
+        }
+
+        if let Some(only_fields) = self.only_fields.as_ref() {
+            let old_kind = std::mem::replace(&mut kind, Kind::object(Collection::empty()));
+            for field in only_fields {
+                // The kind of the metadata isn't known from the kind of the events.
+                let field_kind = match field.metadata {
+                    Some(_) => Kind::any(),
+                    None => old_kind.at_path(&field.path),
+                };
+                if field_kind.contains_any_defined() {
+                    kind.insert(&field.path, field_kind);
+                }
+            }
+        }
+
+        if let Some(TimestampFormat::Unix) = self.timestamp_format {
+            timestamps_to_integers(&mut kind);
+        }
+
+        if let Some(redact) = self.redact.as_ref() {
+            for field in &redact.fields {
+                if let Ok(path) = parse_value_path(field) {
+                    let field_kind = kind.at_path(&path);
+                    if field_kind.contains_any_defined() {
+                        let redacted_kind = if field_kind.contains_undefined() {
+                            Kind::bytes().or_undefined()
+                        } else {
+                            Kind::bytes()
+                        };
+                        kind.insert(&path, redacted_kind);
+                    }
+                }
+            }
+        }
+
+        kind
+    }
+
     fn apply_timestamp_format(&self, log: &mut LogEvent) {
         if let Some(timestamp_format) = self.timestamp_format.as_ref() {
             match timestamp_format {
@@ -178,19 +276,23 @@ impl Transformer {
         }
     }
 
+    fn apply_redaction(&self, log: &mut LogEvent) {
+        if let Some(redact) = self.redact.as_ref() {
+            for field in &redact.fields {
+                if let Some(value) = log.get_mut(field.as_str()) {
+                    *value = redact.redact(value);
+                }
+            }
+        }
+    }
+
     /// Set the `except_fields` value.
     ///
     /// Returns `Err` if the new `except_fields` fail validation, i.e. are not mutually exclusive
     /// with `only_fields`.
     #[cfg(test)]
     pub fn set_except_fields(&mut self, except_fields: Option<Vec<String>>) -> crate::Result<()> {
-        Self::validate_fields(
-            self.only_fields
-                .clone()
-                .map(|x| x.into_iter().map(|x| x.0).collect())
-                .as_ref(),
-            except_fields.as_ref(),
-        )?;
+        Self::validate_fields(self.only_fields.as_ref(), except_fields.as_ref())?;
 
         self.except_fields = except_fields;
 
@@ -198,6 +300,112 @@ impl Transformer {
     }
 }
 
+/// The prefix of the fields read from the metadata of the event.
+const METADATA_PREFIX: &str = "%metadata.";
+
+/// A field included in the encoded event.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub struct OnlyField {
+    /// The path of the field in the encoded event.
+    path: OwnedValuePath,
+
+    /// The path of the field in the metadata of the event, if it is read from the metadata.
+    metadata: Option<OwnedValuePath>,
+}
+
+impl From<OwnedValuePath> for OnlyField {
+    fn from(path: OwnedValuePath) -> Self {
+        Self {
+            path,
+            metadata: None,
+        }
+    }
+}
+
+impl TryFrom<String> for OnlyField {
+    type Error = PathParseError;
+
+    fn try_from(src: String) -> Result<Self, Self::Error> {
+        if !src.starts_with(METADATA_PREFIX) {
+            return parse_value_path(&src).map(Self::from);
+        }
+        // Without the `%`, the path is the path of the field in the encoded event, under `metadata`.
+        let path = parse_value_path(&src[1..])?;
+        let metadata = OwnedValuePath::from(path.segments[1..].to_vec());
+        Ok(Self {
+            path,
+            metadata: Some(metadata),
+        })
+    }
+}
+
+impl From<OnlyField> for String {
+    fn from(field: OnlyField) -> Self {
+        let path = String::from(field.path);
+        if field.metadata.is_some() {
+            format!("%{}", path)
+        } else {
+            path
+        }
+    }
+}
+
+/// Redaction of fields in the encoded event.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Redaction {
+    /// The paths of the redacted fields.
+    ///
+    /// Fields missing from an event are ignored.
+    #[configurable(metadata(docs::examples = "user.email"))]
+    #[configurable(metadata(docs::examples = "password"))]
+    fields: Vec<String>,
+
+    /// How the values of the fields are redacted.
+    #[serde(default)]
+    method: RedactionMethod,
+
+    /// The value replacing the values of the fields when they are masked.
+    #[serde(default = "default_mask")]
+    #[configurable(metadata(docs::examples = "***"))]
+    mask: String,
+}
+
+fn default_mask() -> String {
+    "[REDACTED]".to_string()
+}
+
+impl Redaction {
+    fn redact(&self, value: &Value) -> Value {
+        match self.method {
+            RedactionMethod::Mask => Value::from(self.mask.as_str()),
+            RedactionMethod::Hash => {
+                let digest = Sha256::digest(value.coerce_to_bytes());
+                Value::from(hex::encode(digest))
+            }
+        }
+    }
+}
+
+/// How the values of redacted fields are redacted.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RedactionMethod {
+    /// The values are replaced by `mask`.
+    #[default]
+    Mask,
+
+    /// The values are replaced by the hex-encoded SHA-256 hash of their contents.
+    ///
+    /// Equal values have equal hashes, so events can still be correlated on the fields.
+    /// Objects and arrays are hashed as JSON.
+    Hash,
+}
+
 #[configurable_component]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -210,6 +418,19 @@ pub enum TimestampFormat {
     Rfc3339,
 }
 
+/// Replaces timestamps by integers in the kind, and in the kinds of the fields of objects.
+fn timestamps_to_integers(kind: &mut Kind) {
+    if kind.remove_timestamp() {
+        kind.add_integer();
+    }
+    if let Some(object) = kind.as_object_mut() {
+        object
+            .known_mut()
+            .values_mut()
+            .for_each(timestamps_to_integers);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use indoc::indoc;
@@ -312,6 +533,76 @@ mod tests {
         assert!(!event.as_mut_log().contains("i"));
     }
 
+    #[test]
+    fn deserialize_and_transform_only_metadata() {
+        let transformer: Transformer = toml::from_str(
+            r#"only_fields = ["message", "%metadata.kafka.offset", "%metadata.file.path"]"#,
+        )
+        .unwrap();
+        let mut log = LogEvent::from("hello");
+        log.insert("offset", 1);
+        log.metadata_mut()
+            .value_mut()
+            .insert("kafka.offset", Value::from(42));
+        let mut event = Event::from(log);
+        transformer.transform(&mut event);
+
+        let log = event.as_log();
+        assert_eq!(log.get("message"), Some(&Value::from("hello")));
+        assert_eq!(log.get("metadata.kafka.offset"), Some(&Value::from(42)));
+        assert!(!log.contains("metadata.file"));
+        assert!(!log.contains("offset"));
+
+        assert_eq!(
+            serde_json::to_value(&transformer).unwrap()["only_fields"],
+            serde_json::json!(["message", "%metadata.kafka.offset", "%metadata.file.path"])
+        );
+        assert!(toml::from_str::<Transformer>(r#"only_fields = ["%kafka.offset"]"#).is_err());
+    }
+
+    #[test]
+    fn transform_kind() {
+        let kind = Kind::object(BTreeMap::from([
+            (
+                "a".into(),
+                Kind::object(BTreeMap::from([
+                    ("b".into(), Kind::bytes()),
+                    ("c".into(), Kind::integer()),
+                ])),
+            ),
+            ("d".into(), Kind::timestamp()),
+            ("e".into(), Kind::boolean()),
+        ]));
+
+        let transformer: Transformer = toml::from_str(r#"except_fields = ["a.c", "e"]"#).unwrap();
+        assert_eq!(
+            transformer.transform_kind(kind.clone()),
+            Kind::object(BTreeMap::from([
+                (
+                    "a".into(),
+                    Kind::object(BTreeMap::from([("b".into(), Kind::bytes())]))
+                ),
+                ("d".into(), Kind::timestamp()),
+            ]))
+        );
+
+        let transformer: Transformer = toml::from_str(indoc! {r#"
+            only_fields = ["a.b", "d", "f"]
+            timestamp_format = "unix"
+        "#})
+        .unwrap();
+        assert_eq!(
+            transformer.transform_kind(kind),
+            Kind::object(BTreeMap::from([
+                (
+                    "a".into(),
+                    Kind::object(BTreeMap::from([("b".into(), Kind::bytes())]))
+                ),
+                ("d".into(), Kind::integer()),
+            ]))
+        );
+    }
+
     #[test]
     fn deserialize_and_transform_timestamp() {
         let transformer: Transformer = toml::from_str(r#"timestamp_format = "unix""#).unwrap();
@@ -348,6 +639,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn deserialize_and_transform_redact() {
+        let transformer: Transformer = toml::from_str(indoc! {r#"
+            only_fields = ["user", "message"]
+            redact.fields = ["user.email", "user.password", "missing"]
+        "#})
+        .unwrap();
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        log.insert("user.email", "jane@example.com");
+        log.insert(
+            "user.password",
+            BTreeMap::from([("a".to_string(), Value::from(1))]),
+        );
+        let mut event = Event::from(log);
+        transformer.transform(&mut event);
+
+        let log = event.as_log();
+        assert_eq!(log.get("message"), Some(&Value::from("hello")));
+        assert_eq!(log.get("user.email"), Some(&Value::from("[REDACTED]")));
+        assert_eq!(log.get("user.password"), Some(&Value::from("[REDACTED]")));
+        assert!(!log.contains("missing"));
+
+        let transformer: Transformer = toml::from_str(indoc! {r#"
+            redact.fields = ["email"]
+            redact.method = "hash"
+        "#})
+        .unwrap();
+        let mut log = LogEvent::default();
+        log.insert("email", "jane@example.com");
+        let mut event = Event::from(log);
+        transformer.transform(&mut event);
+        assert_eq!(
+            event.as_log().get("email"),
+            Some(&Value::from(
+                "8c87b489ce35cf2e2f39f80e282cb2e804932a56a213983eeeb428407d43b52d"
+            ))
+        );
+
+        assert!(toml::from_str::<Transformer>(r#"redact.fields = ["a["]"#).is_err());
+    }
+
+    #[test]
+    fn transform_kind_redact() {
+        let kind = Kind::object(BTreeMap::from([
+            ("a".into(), Kind::integer()),
+            ("b".into(), Kind::timestamp()),
+        ]));
+        let transformer: Transformer = toml::from_str(r#"redact.fields = ["a", "c"]"#).unwrap();
+        assert_eq!(
+            transformer.transform_kind(kind),
+            Kind::object(BTreeMap::from([
+                ("a".into(), Kind::bytes()),
+                ("b".into(), Kind::timestamp()),
+            ]))
+        );
+    }
+
     #[test]
     fn exclusivity_violation() {
         let config: std::result::Result<Transformer, _> = toml::from_str(indoc! {r#"
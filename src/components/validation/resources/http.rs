@@ -50,7 +50,7 @@ impl HttpResourceConfig {
         codec: ResourceCodec,
         input_rx: mpsc::Receiver<TestEvent>,
         task_coordinator: &TaskCoordinator<Configuring>,
-    ) {
+    ) -> Result<(), String> {
         match direction {
             // The source will pull data from us.
             ResourceDirection::Pull => {
@@ -69,7 +69,7 @@ impl HttpResourceConfig {
         codec: ResourceCodec,
         output_tx: mpsc::Sender<Event>,
         task_coordinator: &TaskCoordinator<Configuring>,
-    ) {
+    ) -> Result<(), String> {
         match direction {
             // We'll pull data from the sink.
             ResourceDirection::Pull => {
@@ -90,7 +90,7 @@ fn spawn_input_http_server(
     codec: ResourceCodec,
     mut input_rx: mpsc::Receiver<TestEvent>,
     task_coordinator: &TaskCoordinator<Configuring>,
-) {
+) -> Result<(), String> {
     // This HTTP server will poll the input receiver for input events and buffer them. When a
     // request comes in on the right path/method, one buffered input event will be sent back. If no
     // buffered events are available when the request arrives, an empty response (204 No Content) is
@@ -98,7 +98,7 @@ fn spawn_input_http_server(
     let outstanding_events = Arc::new(Mutex::new(VecDeque::new()));
 
     // First, we'll build and spawn our HTTP server.
-    let encoder = codec.into_encoder();
+    let encoder = codec.into_encoder()?;
     let sendable_events = Arc::clone(&outstanding_events);
 
     let (resource_notifier, http_server_shutdown_tx) =
@@ -169,6 +169,8 @@ fn spawn_input_http_server(
 
         debug!("HTTP server external input resource completed.");
     });
+
+    Ok(())
 }
 
 /// Spawns an HTTP client that pushes events to a source which is accepting events over HTTP.
@@ -177,12 +179,12 @@ fn spawn_input_http_client(
     codec: ResourceCodec,
     mut input_rx: mpsc::Receiver<TestEvent>,
     task_coordinator: &TaskCoordinator<Configuring>,
-) {
+) -> Result<(), String> {
     // Spin up an HTTP client that will push the input data to the source on a
     // request-per-input-item basis. This runs serially and has no parallelism.
     let started = task_coordinator.track_started();
     let completed = task_coordinator.track_completed();
-    let mut encoder = codec.into_encoder();
+    let mut encoder = codec.into_encoder()?;
 
     tokio::spawn(async move {
         // Mark ourselves as started. We don't actually do anything until we get our first input
@@ -223,6 +225,8 @@ fn spawn_input_http_client(
 
         debug!("HTTP client external input resource completed.");
     });
+
+    Ok(())
 }
 
 /// Spawns an HTTP server that accepts events sent by a sink.
@@ -232,12 +236,12 @@ fn spawn_output_http_server(
     codec: ResourceCodec,
     output_tx: mpsc::Sender<Event>,
     task_coordinator: &TaskCoordinator<Configuring>,
-) {
+) -> Result<(), String> {
     // This HTTP server will wait for events to be sent by a sink, and collect them and send them on
     // via an output sender. We accept/collect events until we're told to shutdown.
 
     // First, we'll build and spawn our HTTP server.
-    let decoder = codec.into_decoder();
+    let decoder = codec.into_decoder()?;
 
     let (_, http_server_shutdown_tx) =
         spawn_http_server(task_coordinator, &config, move |request| {
@@ -283,6 +287,8 @@ fn spawn_output_http_server(
 
         debug!("HTTP server external output resource completed.");
     });
+
+    Ok(())
 }
 
 /// Spawns an HTTP client that pulls events by making requests to an HTTP server driven by a sink.
@@ -292,7 +298,7 @@ fn spawn_output_http_client(
     _codec: ResourceCodec,
     _output_tx: mpsc::Sender<Event>,
     _task_coordinator: &TaskCoordinator<Configuring>,
-) {
+) -> Result<(), String> {
     // TODO: The `prometheus_exporter` sink is the only sink that exposes an HTTP server which must be
     // scraped... but since we need special logic to aggregate/deduplicate scraped metrics, we can't
     // use this generically for that purpose.
@@ -426,7 +432,7 @@ fn encode_test_event(
             } else {
                 Encoder::<encoding::Framer>::new(
                     NewlineDelimitedEncoder::new().into(),
-                    JsonSerializer::new(MetricTagValues::default()).into(),
+                    JsonSerializer::new(MetricTagValues::default(), Default::default()).into(),
                 )
             };
 
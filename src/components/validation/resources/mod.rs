@@ -1,10 +1,17 @@
 mod event;
 mod http;
 
+#[cfg(feature = "codecs-parquet")]
+use std::num::NonZeroUsize;
+
 use codecs::{
-    decoding::{self, DeserializerConfig, NewlineDelimitedDecoderOptions},
+    decoding::{
+        self, AvroDeserializerOptions, AvroDeserializerRegistryOptions, AvroEncodingFormat,
+        CsvDeserializerOptions, DeserializerConfig, NewlineDelimitedDecoderOptions,
+    },
     encoding::{
-        self, Framer, FramingConfig, JsonSerializerConfig, SerializerConfig, TextSerializerConfig,
+        self, AvroSerializerOptions, CsvSerializerOptions, Framer, FramingConfig,
+        JsonSerializerConfig, SerializerConfig, TextSerializerConfig,
     },
     BytesEncoder,
 };
@@ -65,7 +72,11 @@ impl ResourceCodec {
     ///
     /// The encoder is generated as an inverse to the input codec: if a decoding configuration was
     /// given, we generate an encoder that satisfies that decoding configuration, and vise versa.
-    pub fn into_encoder(&self) -> Encoder<encoding::Framer> {
+    ///
+    /// # Errors
+    ///
+    /// If the decoding configuration has no matching encoder, an error variant will be returned.
+    pub fn into_encoder(&self) -> Result<Encoder<encoding::Framer>, String> {
         let (framer, serializer) = match self {
             Self::Encoding(config) => (
                 Framer::Bytes(BytesEncoder::new()),
@@ -84,36 +95,40 @@ impl ResourceCodec {
                 )
             }
             Self::Decoding(config) => (
-                decoder_framing_to_encoding_framer(config.framing()),
-                deserializer_config_to_serializer(config.config()),
+                decoder_framing_to_encoding_framer(config.framing())?,
+                deserializer_config_to_serializer(config.config())?,
             ),
         };
 
-        Encoder::<encoding::Framer>::new(framer, serializer)
+        Ok(Encoder::<encoding::Framer>::new(framer, serializer))
     }
 
     /// Gets a decoder for this codec.
     ///
     /// The decoder is generated as an inverse to the input codec: if an encoding configuration was
     /// given, we generate a decoder that satisfies that encoding configuration, and vise versa.
-    pub fn into_decoder(&self) -> Decoder {
+    ///
+    /// # Errors
+    ///
+    /// If the encoding configuration has no matching decoder, an error variant will be returned.
+    pub fn into_decoder(&self) -> Result<Decoder, String> {
         let (framer, deserializer) = match self {
-            Self::Decoding(config) => return config.build(),
+            Self::Decoding(config) => return Ok(config.build()),
             Self::Encoding(config) => (
-                encoder_framing_to_decoding_framer(config.config().default_stream_framing()),
-                serializer_config_to_deserializer(config.config()),
+                encoder_framing_to_decoding_framer(config.config().default_stream_framing())?,
+                serializer_config_to_deserializer(config.config())?,
             ),
             Self::EncodingWithFraming(config) => {
                 let (maybe_framing, serializer) = config.config();
                 let framing = maybe_framing.clone().unwrap_or(FramingConfig::Bytes);
                 (
-                    encoder_framing_to_decoding_framer(framing),
-                    serializer_config_to_deserializer(serializer),
+                    encoder_framing_to_decoding_framer(framing)?,
+                    serializer_config_to_deserializer(serializer)?,
                 )
             }
         };
 
-        Decoder::new(framer, deserializer)
+        Ok(Decoder::new(framer, deserializer))
     }
 }
 
@@ -135,29 +150,85 @@ impl From<DecodingConfig> for ResourceCodec {
     }
 }
 
-fn deserializer_config_to_serializer(config: &DeserializerConfig) -> encoding::Serializer {
+fn deserializer_config_to_serializer(
+    config: &DeserializerConfig,
+) -> Result<encoding::Serializer, String> {
     let serializer_config = match config {
         // TODO: This isn't necessarily a one-to-one conversion, at least not in the future when
         // "bytes" can be a top-level field and we aren't implicitly decoding everything into the
         // `message` field... but it's close enough for now.
         DeserializerConfig::Bytes => SerializerConfig::Text(TextSerializerConfig::default()),
         DeserializerConfig::Json => SerializerConfig::Json(JsonSerializerConfig::default()),
-        // TODO: We need to create an Avro serializer because, certainly, for any source decoding
-        // the data as Avro, we can't possibly send anything else without the source just
-        // immediately barfing.
         #[cfg(feature = "sources-syslog")]
         DeserializerConfig::Syslog => SerializerConfig::Logfmt,
-        DeserializerConfig::Native => SerializerConfig::Native,
+        DeserializerConfig::Native { .. } => SerializerConfig::Native {
+            native: Default::default(),
+        },
         DeserializerConfig::NativeJson => SerializerConfig::NativeJson,
-        DeserializerConfig::Gelf => SerializerConfig::Gelf,
+        DeserializerConfig::Gelf => SerializerConfig::Gelf {
+            gelf: Default::default(),
+        },
+        DeserializerConfig::Msgpack => SerializerConfig::Msgpack,
+        DeserializerConfig::Csv { csv } => SerializerConfig::Csv {
+            csv: CsvSerializerOptions {
+                fields: csv.fields.iter().cloned().map(String::from).collect(),
+                // The header row read by the deserializer is written by the serializer.
+                headers: csv.headers || csv.fields.is_empty(),
+                terminator: Default::default(),
+                delimiter: csv.delimiter,
+                quote: csv.quote,
+                nested_value_handling: Default::default(),
+                null_value: Default::default(),
+                missing_value: Default::default(),
+            },
+        },
+        // The Avro serializer only writes bare datums.
+        DeserializerConfig::Avro { avro } if avro.format == AvroEncodingFormat::Datum => {
+            SerializerConfig::Avro {
+                avro: AvroSerializerOptions {
+                    schema: avro.schema.clone(),
+                    schema_registry: None,
+                },
+            }
+        }
+        DeserializerConfig::Avro { .. } => {
+            return Err("Avro messages other than bare datums can't be encoded.".to_owned())
+        }
+        // The deserializer reads the types of the columns from the files, so the columns are
+        // written as strings.
+        #[cfg(feature = "codecs-parquet")]
+        DeserializerConfig::Parquet { parquet } if !parquet.fields.is_empty() => {
+            SerializerConfig::Parquet {
+                parquet: encoding::ParquetSerializerOptions {
+                    fields: parquet
+                        .fields
+                        .iter()
+                        .map(|name| encoding::ParquetField {
+                            name: name.clone(),
+                            r#type: encoding::ParquetFieldType::String,
+                        })
+                        .collect(),
+                    row_group_size: NonZeroUsize::new(10_000).expect("static non-zero number"),
+                    compression: Default::default(),
+                },
+            }
+        }
+        #[cfg(feature = "codecs-parquet")]
+        DeserializerConfig::Parquet { .. } => {
+            return Err(
+                "Parquet files can't be encoded without the names of the columns.".to_owned(),
+            )
+        }
     };
 
-    serializer_config
+    Ok(serializer_config
         .build()
-        .expect("building serializer should never fail")
+        .expect("building serializer should never fail"))
 }
 
-fn decoder_framing_to_encoding_framer(framing: &decoding::FramingConfig) -> encoding::Framer {
+fn decoder_framing_to_encoding_framer(
+    framing: &decoding::FramingConfig,
+) -> Result<encoding::Framer, String> {
     let framing_config = match framing {
         decoding::FramingConfig::Bytes => encoding::FramingConfig::Bytes,
         decoding::FramingConfig::CharacterDelimited {
@@ -173,27 +244,108 @@ fn decoder_framing_to_encoding_framer(framing: &decoding::FramingConfig) -> enco
         }
         // TODO: There's no equivalent octet counting framer for encoding... although
         // there's no particular reason that would make it hard to write.
-        decoding::FramingConfig::OctetCounting { .. } => todo!(),
+        decoding::FramingConfig::OctetCounting { .. } => {
+            return Err("Octet counting framing can't be encoded.".to_owned())
+        }
     };
 
-    framing_config.build()
+    Ok(framing_config.build())
 }
 
-fn serializer_config_to_deserializer(config: &SerializerConfig) -> decoding::Deserializer {
+fn serializer_config_to_deserializer(
+    config: &SerializerConfig,
+) -> Result<decoding::Deserializer, String> {
     let deserializer_config = match config {
-        SerializerConfig::Avro { .. } => todo!(),
-        SerializerConfig::Gelf => DeserializerConfig::Gelf,
+        SerializerConfig::Avro { avro } if avro.schema_registry.is_none() => {
+            DeserializerConfig::Avro {
+                avro: AvroDeserializerOptions {
+                    format: AvroEncodingFormat::Datum,
+                    schema: avro.schema.clone(),
+                    schema_registry: None,
+                },
+            }
+        }
+        #[cfg(feature = "codecs-parquet")]
+        SerializerConfig::Parquet { parquet } => DeserializerConfig::Parquet {
+            parquet: decoding::ParquetDeserializerOptions {
+                fields: parquet
+                    .fields
+                    .iter()
+                    .map(|field| field.name.clone())
+                    .collect(),
+            },
+        },
+        // The serializer writes the Confluent wire format with a schema registry.
+        SerializerConfig::Avro { avro } => DeserializerConfig::Avro {
+            avro: AvroDeserializerOptions {
+                format: AvroEncodingFormat::Confluent,
+                schema: avro.schema.clone(),
+                schema_registry: avro.schema_registry.as_ref().map(|registry| {
+                    AvroDeserializerRegistryOptions {
+                        url: registry.url.clone(),
+                        auth: registry.auth.clone(),
+                    }
+                }),
+            },
+        },
+        // Pretty-printed JSON is still JSON.
+        SerializerConfig::Pretty { .. } => DeserializerConfig::Json,
+        #[cfg(feature = "sources-syslog")]
+        SerializerConfig::Syslog { .. } => DeserializerConfig::Syslog,
+        #[cfg(not(feature = "sources-syslog"))]
+        SerializerConfig::Syslog { .. } => {
+            return Err("Syslog messages can't be decoded without the syslog codec.".to_owned())
+        }
+        #[cfg(feature = "codecs-arrow")]
+        SerializerConfig::ArrowStream { .. } => {
+            return Err(unsupported_serializer("Arrow IPC streams"))
+        }
+        SerializerConfig::Cbor { .. } => return Err(unsupported_serializer("CBOR messages")),
+        SerializerConfig::Cef { .. } => return Err(unsupported_serializer("CEF messages")),
+        SerializerConfig::FixedWidth { .. } => {
+            return Err(unsupported_serializer("Fixed-width records"))
+        }
+        SerializerConfig::Leef { .. } => return Err(unsupported_serializer("LEEF messages")),
+        SerializerConfig::Protobuf { .. } => {
+            return Err(unsupported_serializer("Protobuf messages"))
+        }
+        SerializerConfig::Xml { .. } => return Err(unsupported_serializer("XML documents")),
+        SerializerConfig::Csv { csv } => DeserializerConfig::Csv {
+            csv: CsvDeserializerOptions {
+                fields: csv
+                    .fields
+                    .iter()
+                    .map(|field| field.clone().try_into().expect("valid CSV field"))
+                    .collect(),
+                headers: csv.headers,
+                delimiter: csv.delimiter,
+                quote: csv.quote,
+            },
+        },
+        SerializerConfig::Gelf { .. } => DeserializerConfig::Gelf,
         SerializerConfig::Json(_) => DeserializerConfig::Json,
-        SerializerConfig::Logfmt => todo!(),
-        SerializerConfig::Native => DeserializerConfig::Native,
+        SerializerConfig::Logfmt => return Err(unsupported_serializer("Logfmt messages")),
+        SerializerConfig::Msgpack => DeserializerConfig::Msgpack,
+        SerializerConfig::Native { .. } => DeserializerConfig::Native {
+            native: Default::default(),
+        },
         SerializerConfig::NativeJson => DeserializerConfig::NativeJson,
         SerializerConfig::RawMessage | SerializerConfig::Text(_) => DeserializerConfig::Bytes,
     };
 
-    deserializer_config.build()
+    Ok(deserializer_config.build())
+}
+
+fn unsupported_serializer(messages: &str) -> String {
+    format!(
+        "{} can't be decoded, as there's no matching decoder.",
+        messages
+    )
 }
 
-fn encoder_framing_to_decoding_framer(framing: encoding::FramingConfig) -> decoding::Framer {
+fn encoder_framing_to_decoding_framer(
+    framing: encoding::FramingConfig,
+) -> Result<decoding::Framer, String> {
     let framing_config = match framing {
         encoding::FramingConfig::Bytes => decoding::FramingConfig::Bytes,
         encoding::FramingConfig::CharacterDelimited {
@@ -204,13 +356,16 @@ fn encoder_framing_to_decoding_framer(framing: encoding::FramingConfig) -> decod
                 max_length: None,
             },
         },
+        encoding::FramingConfig::Compressed { .. } => {
+            return Err("Compressed frames can't be decoded.".to_owned())
+        }
         encoding::FramingConfig::LengthDelimited => decoding::FramingConfig::LengthDelimited,
         encoding::FramingConfig::NewlineDelimited => decoding::FramingConfig::NewlineDelimited {
             newline_delimited: NewlineDelimitedDecoderOptions::default(),
         },
     };
 
-    framing_config.build()
+    Ok(framing_config.build())
 }
 
 /// Direction that the resource is operating in.
@@ -298,7 +453,7 @@ impl ExternalResource {
         self,
         input_rx: mpsc::Receiver<TestEvent>,
         task_coordinator: &TaskCoordinator<Configuring>,
-    ) {
+    ) -> Result<(), String> {
         match self.definition {
             ResourceDefinition::Http(http_config) => {
                 http_config.spawn_as_input(self.direction, self.codec, input_rx, task_coordinator)
@@ -311,7 +466,7 @@ impl ExternalResource {
         self,
         output_tx: mpsc::Sender<Event>,
         task_coordinator: &TaskCoordinator<Configuring>,
-    ) {
+    ) -> Result<(), String> {
         match self.definition {
             ResourceDefinition::Http(http_config) => {
                 http_config.spawn_as_output(self.direction, self.codec, output_tx, task_coordinator)
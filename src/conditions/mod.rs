@@ -1,7 +1,7 @@
 #![allow(missing_docs)]
 use vector_config::configurable_component;
 
-use crate::event::Event;
+use crate::event::{Event, TraceEvent, Value};
 
 pub(self) mod datadog_search;
 pub(crate) mod is_log;
@@ -79,6 +79,14 @@ impl Condition {
             Condition::AlwaysFail => (Ok(()), e),
         }
     }
+
+    /// Checks if a condition is true for a span of a trace.
+    ///
+    /// The span is checked as a log event holding its fields, so that the paths used by the condition
+    /// are relative to the span.
+    pub fn check_span(&self, trace: &TraceEvent, span: &Value) -> bool {
+        self.check(Event::Log(trace.span_event(span.clone()))).0
+    }
 }
 
 /// An event matching condition.
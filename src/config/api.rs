@@ -1,10 +1,11 @@
 use std::net::{Ipv4Addr, SocketAddr};
 
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
 /// API options.
 #[configurable_component]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[serde(default, deny_unknown_fields)]
 pub struct Options {
     /// Whether or not the API endpoint is available.
@@ -18,6 +19,67 @@ pub struct Options {
     /// Whether or not to expose the GraphQL playground on the API endpoint.
     #[serde(default = "default_playground")]
     pub playground: bool,
+
+    /// The token used to authenticate config reload, drain, and buffer mutation requests.
+    ///
+    /// The config reload endpoint, `POST /config`, requesting a drain with `POST /drain`, and the
+    /// buffer mutations of the GraphQL API are only available when this is set. Clients must send the token as a bearer token in the
+    /// `Authorization` header.
+    #[configurable(metadata(docs::examples = "${VECTOR_API_RELOAD_TOKEN}"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reload_token: Option<SensitiveString>,
+
+    /// Whether or not to expose the profiling endpoints on the API endpoint.
+    ///
+    /// CPU profiles are served at `/debug/pprof/profile`, and heap profiles at `/debug/pprof/heap`.
+    /// CPU profiles require Vector to be built with the `profiling` feature, and heap profiles with
+    /// the `heap-profiling` feature.
+    #[serde(default)]
+    pub profiling: bool,
+
+    /// Readiness options of the health endpoint.
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub readiness: ReadinessOptions,
+}
+
+/// Readiness options of the health endpoint.
+///
+/// `/health/ready` reports whether each component is ready: sources and transforms are ready while
+/// they're running, and sinks while they're running, haven't failed their healthcheck, and their
+/// buffer isn't close to being full.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReadinessOptions {
+    /// Patterns of the IDs of the components gating the overall readiness.
+    ///
+    /// Patterns can contain wildcards, such as `kafka_*`. All components gate the overall
+    /// readiness when none are set.
+    #[configurable(metadata(docs::examples = "kafka_*"))]
+    pub components: Vec<String>,
+
+    /// The usage of the buffer of a sink, in percent, above which the sink isn't ready.
+    ///
+    /// Only applies to buffers limited by a number of events.
+    #[serde(default = "default_max_buffer_usage_percent")]
+    pub max_buffer_usage_percent: u8,
+}
+
+impl Default for ReadinessOptions {
+    fn default() -> Self {
+        Self {
+            components: Vec::new(),
+            max_buffer_usage_percent: default_max_buffer_usage_percent(),
+        }
+    }
+}
+
+const fn default_max_buffer_usage_percent() -> u8 {
+    90
 }
 
 impl Default for Options {
@@ -26,6 +88,9 @@ impl Default for Options {
             enabled: default_enabled(),
             playground: default_playground(),
             address: default_address(),
+            reload_token: None,
+            profiling: false,
+            readiness: ReadinessOptions::default(),
         }
     }
 }
@@ -66,10 +131,30 @@ impl Options {
             }
         };
 
+        let reload_token = match (self.reload_token.take(), other.reload_token) {
+            (Some(a), Some(b)) if a != b => {
+                return Err("Conflicting `api` reload token.".to_string())
+            }
+            (a, b) => a.or(b),
+        };
+
+        let readiness = if self.readiness == ReadinessOptions::default() {
+            other.readiness
+        } else if other.readiness == ReadinessOptions::default()
+            || self.readiness == other.readiness
+        {
+            self.readiness.clone()
+        } else {
+            return Err("Conflicting `api` readiness options.".to_string());
+        };
+
         let options = Options {
             address,
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
+            reload_token,
+            profiling: self.profiling | other.profiling,
+            readiness,
         };
 
         *self = options;
@@ -83,6 +168,9 @@ fn bool_merge() {
         enabled: true,
         address: None,
         playground: false,
+        reload_token: None,
+        profiling: false,
+        readiness: ReadinessOptions::default(),
     };
 
     a.merge(Options::default()).unwrap();
@@ -93,6 +181,9 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
+            reload_token: None,
+            profiling: false,
+            readiness: ReadinessOptions::default(),
         }
     );
 }
@@ -104,6 +195,9 @@ fn bind_merge() {
         enabled: true,
         address: Some(address),
         playground: true,
+        reload_token: None,
+        profiling: false,
+        readiness: ReadinessOptions::default(),
     };
 
     a.merge(Options::default()).unwrap();
@@ -114,6 +208,9 @@ fn bind_merge() {
             enabled: true,
             address: Some(address),
             playground: true,
+            reload_token: None,
+            profiling: false,
+            readiness: ReadinessOptions::default(),
         }
     );
 }
@@ -132,3 +229,46 @@ fn bind_conflict() {
 
     assert!(a.merge(b).is_err());
 }
+
+#[test]
+fn reload_token_conflict() {
+    let mut a = Options {
+        reload_token: Some("foo".to_string().into()),
+        ..Options::default()
+    };
+
+    a.merge(Options::default()).unwrap();
+    assert_eq!(a.reload_token, Some("foo".to_string().into()));
+
+    let b = Options {
+        reload_token: Some("bar".to_string().into()),
+        ..Options::default()
+    };
+
+    assert!(a.merge(b).is_err());
+}
+
+#[test]
+fn readiness_conflict() {
+    let readiness = ReadinessOptions {
+        components: vec!["kafka_*".to_string()],
+        ..ReadinessOptions::default()
+    };
+    let mut a = Options {
+        readiness: readiness.clone(),
+        ..Options::default()
+    };
+
+    a.merge(Options::default()).unwrap();
+    assert_eq!(a.readiness, readiness);
+
+    let b = Options {
+        readiness: ReadinessOptions {
+            max_buffer_usage_percent: 50,
+            ..ReadinessOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert!(a.merge(b).is_err());
+}
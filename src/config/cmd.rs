@@ -1,9 +1,11 @@
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use serde_json::Value;
 
-use super::{load_builder_from_paths, load_source_from_paths, process_paths, ConfigBuilder};
+use super::{
+    convert, load_builder_from_paths, load_source_from_paths, process_paths, ConfigBuilder,
+};
 use crate::cli::handle_config_errors;
 use crate::config;
 
@@ -55,6 +57,18 @@ pub struct Opts {
         value_delimiter(',')
     )]
     pub config_dirs: Vec<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum Command {
+    /// Convert a config file between the TOML, YAML, and JSON formats.
+    ///
+    /// Key order is preserved, as are comments when converting from TOML to TOML or YAML.
+    /// Environment variables aren't interpolated, and options left to their defaults aren't added.
+    Convert(convert::Opts),
 }
 
 impl Opts {
@@ -163,6 +177,10 @@ fn serialize_to_json(
 /// Pipelines expansions, etc. The JSON result of this serialization can itself be used as a config,
 /// which also makes it useful for version control or treating as a singular unit of configuration.
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    if let Some(Command::Convert(opts)) = &opts.command {
+        return convert::cmd(opts);
+    }
+
     let paths = opts.paths_with_formats();
     // Start by serializing to a `ConfigBuilder`. This will leverage validation in config
     // builder fields which we'll use to error out if required.
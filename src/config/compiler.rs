@@ -1,9 +1,10 @@
 use indexmap::IndexSet;
 
 use super::{
-    builder::ConfigBuilder, graph::Graph, id::Inputs, schema, validation, Config, OutputId,
-    SourceConfig,
+    builder::ConfigBuilder, graph::Graph, id::Inputs, schema, validation, ComponentKey, Config,
+    OutputId, SourceConfig,
 };
+use crate::dead_letter::DEAD_LETTER_OUTPUT;
 
 pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<String>> {
     let mut errors = Vec::new();
@@ -23,6 +24,10 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
 
     expand_globs(&mut builder);
 
+    if let Err(dead_letter_errors) = connect_dead_letter_queues(&mut builder) {
+        errors.extend(dead_letter_errors);
+    }
+
     if let Err(type_errors) = validation::check_shape(&builder) {
         errors.extend(type_errors);
     }
@@ -35,6 +40,18 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(output_errors);
     }
 
+    if let Err(rate_limit_errors) = validation::check_rate_limit_groups(&builder) {
+        errors.extend(rate_limit_errors);
+    }
+
+    if let Err(circuit_breaker_errors) = validation::check_circuit_breakers(&builder) {
+        errors.extend(circuit_breaker_errors);
+    }
+
+    if let Err(max_event_age_errors) = validation::check_max_event_ages(&builder) {
+        errors.extend(max_event_age_errors);
+    }
+
     #[cfg(feature = "enterprise")]
     let hash = Some(builder.sha256_hash());
 
@@ -158,6 +175,63 @@ pub(crate) fn expand_globs(config: &mut ConfigBuilder) {
     }
 }
 
+/// Connects the `dropped` output of transforms to their configured dead letter queue sink.
+///
+/// A transform's own `dead_letter_queue` setting takes precedence over the global one. Transforms
+/// that don't have a `dropped` output are skipped when using the global setting, but it is an
+/// error to configure a dead letter queue directly on such a transform.
+///
+/// Sinks send events to their dead letter queue at runtime instead, through the fallbacks of their
+/// circuit breaker and maximum event age.
+pub(crate) fn connect_dead_letter_queues(config: &mut ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    let default_queue = config.global.dead_letter_queue.as_ref();
+    let log_namespace = config.schema.log_namespace();
+
+    let mut routes: Vec<(ComponentKey, String)> = Vec::new();
+    for (key, transform) in &config.transforms {
+        let has_dropped_output = transform
+            .inner
+            .outputs(&schema::Definition::any(), log_namespace)
+            .iter()
+            .any(|output| output.port.as_deref() == Some(DEAD_LETTER_OUTPUT));
+        match (transform.dead_letter_queue.as_ref(), has_dropped_output) {
+            (Some(queue), true) => routes.push((queue.clone(), key.to_string())),
+            (Some(queue), false) => errors.push(format!(
+                "Transform \"{}\" has dead letter queue \"{}\" configured, but does not send dropped events to a \"{}\" output.",
+                key, queue, DEAD_LETTER_OUTPUT
+            )),
+            (None, true) => {
+                if let Some(queue) = default_queue {
+                    routes.push((queue.clone(), key.to_string()));
+                }
+            }
+            (None, false) => {}
+        }
+    }
+
+    for (queue, component) in routes {
+        let input = format!("{}.{}", component, DEAD_LETTER_OUTPUT);
+        match config.sinks.get_mut(&queue) {
+            Some(sink) => {
+                if !sink.inputs.contains(&input) {
+                    sink.inputs.extend(Some(input));
+                }
+            }
+            None => errors.push(format!(
+                "Dead letter queue \"{}\" for component \"{}\" is not a configured sink.",
+                queue, component
+            )),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 enum InputMatcher {
     Pattern(glob::Pattern),
     String(String),
@@ -260,6 +334,70 @@ mod test {
         );
     }
 
+    #[cfg(feature = "transforms-remap")]
+    #[test]
+    fn dead_letter_queue_wiring() {
+        use crate::transforms::remap::RemapConfig;
+
+        let remap = || RemapConfig {
+            source: Some("abort".to_owned()),
+            drop_on_abort: true,
+            reroute_dropped: true,
+            ..Default::default()
+        };
+
+        let mut builder = ConfigBuilder::default();
+        builder.global.dead_letter_queue = Some("dlq".into());
+        builder.add_source("in", basic_source().1);
+        builder.add_transform("remap1", &["in"], remap());
+        builder.add_transform("remap2", &["in"], remap());
+        builder.add_transform("plain", &["in"], basic_transform("", 1.0));
+        builder.add_sink("out", &["remap1", "remap2", "plain"], basic_sink(1).1);
+        builder.add_sink("dlq", &["in"], basic_sink(1).1);
+        builder.add_sink("other_dlq", &["in"], basic_sink(1).1);
+        builder
+            .transforms
+            .get_mut(&ComponentKey::from("remap2"))
+            .unwrap()
+            .dead_letter_queue = Some("other_dlq".into());
+
+        let config = builder.build().expect("build should succeed");
+
+        let inputs_for = |sink: &str| {
+            config
+                .sinks
+                .get(&ComponentKey::from(sink))
+                .unwrap()
+                .inputs
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(inputs_for("dlq"), vec!["in", "remap1.dropped"]);
+        assert_eq!(inputs_for("other_dlq"), vec!["in", "remap2.dropped"]);
+    }
+
+    #[test]
+    fn dead_letter_queue_errors() {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", basic_source().1);
+        builder.add_transform("plain", &["in"], basic_transform("", 1.0));
+        builder.add_sink("out", &["plain"], basic_sink(1).1);
+        builder
+            .transforms
+            .get_mut(&ComponentKey::from("plain"))
+            .unwrap()
+            .dead_letter_queue = Some("out".into());
+
+        let errors = builder.build().expect_err("build should fail");
+        assert_eq!(
+            errors,
+            vec![
+                r#"Transform "plain" has dead letter queue "out" configured, but does not send dropped events to a "dropped" output."#
+            ]
+        );
+    }
+
     fn without_ports(outputs: Inputs<OutputId>) -> Vec<ComponentKey> {
         outputs
             .into_iter()
@@ -0,0 +1,688 @@
+//! Conversion of config files between formats.
+//!
+//! Configs are converted from their source text rather than from the loaded config, so
+//! environment variables aren't interpolated and options left to their defaults aren't added. Key
+//! order is preserved, as are the comments preceding keys and tables when converting from TOML to
+//! TOML or YAML. JSON has no comments, and YAML comments aren't read.
+
+use std::{fs, path::PathBuf};
+
+use clap::Parser;
+use serde_json::{Number, Value};
+use toml_edit::{ArrayOfTables, Decor, Document, InlineTable, Item, Table};
+
+use super::Format;
+
+#[derive(Parser, Debug, Clone)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// The config file to convert.
+    input: PathBuf,
+
+    /// The format of the config file to convert. Detected from the file name if not set.
+    #[arg(long, value_enum)]
+    from: Option<Format>,
+
+    /// The format to convert the config file to. Detected from the output file name if not set.
+    #[arg(long, value_enum)]
+    to: Option<Format>,
+
+    /// The file to write the converted config to, instead of stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Rewrite deprecated options to their replacements.
+    #[arg(long)]
+    rewrite_deprecated: bool,
+}
+
+/// A value of a config, along with the comments preceding its keys.
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Value(Value),
+    Array(Vec<Node>),
+    Table(Vec<Field>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Field {
+    key: String,
+    comments: Vec<String>,
+    value: Node,
+}
+
+impl Field {
+    fn new(key: impl Into<String>, value: Node) -> Self {
+        Self {
+            key: key.into(),
+            comments: Vec::new(),
+            value,
+        }
+    }
+}
+
+/// Deprecated options and their replacements, by kind and type of component.
+const DEPRECATED_OPTIONS: &[(&str, &str, &str, &str)] = &[
+    ("sources", "demo_logs", "batch_interval", "interval"),
+    ("sources", "file", "fingerprinting", "fingerprint"),
+    ("sources", "file", "ignore_older", "ignore_older_secs"),
+    ("sources", "file", "remove_after", "remove_after_secs"),
+    (
+        "sources",
+        "kubernetes_logs",
+        "annotation_fields",
+        "pod_annotation_fields",
+    ),
+    ("sources", "nats", "name", "connection_name"),
+    ("sources", "prometheus_scrape", "hosts", "endpoints"),
+    ("transforms", "aws_ec2_metadata", "host", "endpoint"),
+    (
+        "sinks",
+        "aws_cloudwatch_metrics",
+        "namespace",
+        "default_namespace",
+    ),
+    ("sinks", "clickhouse", "host", "endpoint"),
+    ("sinks", "datadog_logs", "api_key", "default_api_key"),
+    ("sinks", "datadog_metrics", "api_key", "default_api_key"),
+    ("sinks", "humio_logs", "host", "endpoint"),
+    ("sinks", "humio_metrics", "host", "endpoint"),
+    (
+        "sinks",
+        "influxdb_metrics",
+        "namespace",
+        "default_namespace",
+    ),
+    ("sinks", "kafka", "headers_field", "headers_key"),
+    ("sinks", "logdna", "host", "endpoint"),
+    (
+        "sinks",
+        "prometheus_exporter",
+        "namespace",
+        "default_namespace",
+    ),
+    ("sinks", "pulsar", "address", "endpoint"),
+    ("sinks", "redis", "list", "list_option"),
+    ("sinks", "redis", "url", "endpoint"),
+    ("sinks", "sematext_logs", "host", "endpoint"),
+    ("sinks", "splunk_hec_logs", "token", "default_token"),
+    ("sinks", "splunk_hec_metrics", "token", "default_token"),
+    ("sinks", "statsd", "namespace", "default_namespace"),
+];
+
+/// Function used by the `vector config convert` subcommand.
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let from = match opts.from.or_else(|| Format::from_path(&opts.input).ok()) {
+        Some(format) => format,
+        None => {
+            error!(
+                message = "Unable to detect the format of the config file, set it with `--from`.",
+                path = ?opts.input
+            );
+            return exitcode::USAGE;
+        }
+    };
+    let to = match opts.to.or_else(|| {
+        opts.output
+            .as_ref()
+            .and_then(|path| Format::from_path(path).ok())
+    }) {
+        Some(format) => format,
+        None => {
+            error!(message = "Unable to detect the format to convert to, set it with `--to`.");
+            return exitcode::USAGE;
+        }
+    };
+
+    let content = match fs::read_to_string(&opts.input) {
+        Ok(content) => content,
+        Err(error) => {
+            error!(message = "Unable to read the config file.", path = ?opts.input, %error);
+            return exitcode::NOINPUT;
+        }
+    };
+
+    let mut fields = match read(&content, from) {
+        Ok(fields) => fields,
+        Err(error) => {
+            error!(message = "Unable to parse the config file.", path = ?opts.input, %error);
+            return exitcode::CONFIG;
+        }
+    };
+    if opts.rewrite_deprecated {
+        for (kind, id, deprecated, replacement) in rewrite_deprecated(&mut fields) {
+            info!(
+                message = "Rewrote deprecated option.",
+                component = %format!("{}.{}", kind, id),
+                %deprecated,
+                %replacement,
+            );
+        }
+    }
+
+    let converted = write(&fields, to);
+    match &opts.output {
+        Some(path) => {
+            if let Err(error) = fs::write(path, converted) {
+                error!(message = "Unable to write the converted config.", ?path, %error);
+                return exitcode::CANTCREAT;
+            }
+        }
+        None => {
+            #[allow(clippy::print_stdout)]
+            {
+                print!("{}", converted)
+            }
+        }
+    }
+
+    exitcode::OK
+}
+
+fn read(content: &str, format: Format) -> Result<Vec<Field>, String> {
+    match format {
+        Format::Toml => {
+            let document = content
+                .parse::<Document>()
+                .map_err(|error| error.to_string())?;
+            Ok(from_toml_table(document.as_table()))
+        }
+        // YAML being a superset of JSON, both are read as YAML, which preserves key order.
+        Format::Yaml | Format::Json => {
+            match serde_yaml::from_str(content).map_err(|error| error.to_string())? {
+                serde_yaml::Value::Mapping(mapping) => Ok(from_yaml_mapping(mapping)),
+                serde_yaml::Value::Null => Ok(Vec::new()),
+                _ => Err("The config must be a map.".to_string()),
+            }
+        }
+    }
+}
+
+fn write(fields: &[Field], format: Format) -> String {
+    match format {
+        Format::Toml => to_toml(fields),
+        Format::Yaml => {
+            let mut out = String::new();
+            write_yaml_fields(&mut out, fields, 0);
+            out
+        }
+        Format::Json => {
+            let mut out = String::new();
+            write_json(&mut out, &Node::Table(fields.to_vec()), 0);
+            out.push('\n');
+            out
+        }
+    }
+}
+
+/// Renames deprecated options of the components to their replacements, unless the replacement is
+/// already set. Returns the kind and ID of the components, with the renamed options.
+fn rewrite_deprecated(fields: &mut [Field]) -> Vec<(String, String, String, String)> {
+    let mut rewritten = Vec::new();
+    for section in fields.iter_mut() {
+        let components = match &mut section.value {
+            Node::Table(components) => components,
+            _ => continue,
+        };
+        for component in components {
+            let options = match &mut component.value {
+                Node::Table(options) => options,
+                _ => continue,
+            };
+            let component_type = options.iter().find_map(|option| match &option.value {
+                Node::Value(Value::String(component_type)) if option.key == "type" => {
+                    Some(component_type.clone())
+                }
+                _ => None,
+            });
+            let component_type = match component_type {
+                Some(component_type) => component_type,
+                None => continue,
+            };
+
+            for (kind, ty, deprecated, replacement) in DEPRECATED_OPTIONS {
+                if *kind != section.key || *ty != component_type {
+                    continue;
+                }
+                if options.iter().any(|option| option.key == *replacement) {
+                    continue;
+                }
+                if let Some(option) = options.iter_mut().find(|option| option.key == *deprecated) {
+                    option.key = replacement.to_string();
+                    rewritten.push((
+                        section.key.clone(),
+                        component.key.clone(),
+                        deprecated.to_string(),
+                        replacement.to_string(),
+                    ));
+                }
+            }
+        }
+    }
+    rewritten
+}
+
+/// Gets the comments from the whitespace and comments preceding a key or a table.
+fn comments(decor: Option<&Decor>) -> Vec<String> {
+    decor
+        .and_then(Decor::prefix)
+        .and_then(|prefix| prefix.as_str())
+        .map(|prefix| {
+            prefix
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix('#'))
+                .map(|comment| comment.strip_prefix(' ').unwrap_or(comment).to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn from_toml_table(table: &Table) -> Vec<Field> {
+    table
+        .iter()
+        .filter_map(|(key, item)| {
+            let (comments, value) = match item {
+                Item::None => return None,
+                Item::Value(value) => (comments(table.key_decor(key)), from_toml_value(value)),
+                Item::Table(table) => (
+                    comments(Some(table.decor())),
+                    Node::Table(from_toml_table(table)),
+                ),
+                Item::ArrayOfTables(array) => (
+                    array
+                        .iter()
+                        .next()
+                        .map(|table| comments(Some(table.decor())))
+                        .unwrap_or_default(),
+                    Node::Array(
+                        array
+                            .iter()
+                            .map(|table| Node::Table(from_toml_table(table)))
+                            .collect(),
+                    ),
+                ),
+            };
+            Some(Field {
+                key: key.to_string(),
+                comments,
+                value,
+            })
+        })
+        .collect()
+}
+
+fn from_toml_value(value: &toml_edit::Value) -> Node {
+    use toml_edit::Value as V;
+
+    match value {
+        V::String(s) => Node::Value(Value::String(s.value().clone())),
+        V::Integer(i) => Node::Value(Value::from(*i.value())),
+        V::Float(f) => Node::Value(Number::from_f64(*f.value()).map_or(Value::Null, Value::Number)),
+        V::Boolean(b) => Node::Value(Value::Bool(*b.value())),
+        V::Datetime(d) => Node::Value(Value::String(d.value().to_string())),
+        V::Array(array) => Node::Array(array.iter().map(from_toml_value).collect()),
+        V::InlineTable(table) => Node::Table(
+            table
+                .iter()
+                .map(|(key, value)| Field::new(key, from_toml_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn from_yaml_mapping(mapping: serde_yaml::Mapping) -> Vec<Field> {
+    mapping
+        .into_iter()
+        .map(|(key, value)| {
+            let key = match key {
+                serde_yaml::Value::String(key) => key,
+                key => serde_yaml::to_string(&key)
+                    .map(|key| key.trim_end().to_string())
+                    .unwrap_or_default(),
+            };
+            Field::new(key, from_yaml_value(value))
+        })
+        .collect()
+}
+
+fn from_yaml_value(value: serde_yaml::Value) -> Node {
+    use serde_yaml::Value as V;
+
+    match value {
+        V::Null => Node::Value(Value::Null),
+        V::Bool(b) => Node::Value(Value::Bool(b)),
+        V::Number(n) => Node::Value(if let Some(i) = n.as_i64() {
+            Value::from(i)
+        } else if let Some(u) = n.as_u64() {
+            Value::from(u)
+        } else {
+            n.as_f64()
+                .and_then(Number::from_f64)
+                .map_or(Value::Null, Value::Number)
+        }),
+        V::String(s) => Node::Value(Value::String(s)),
+        V::Sequence(sequence) => Node::Array(sequence.into_iter().map(from_yaml_value).collect()),
+        V::Mapping(mapping) => Node::Table(from_yaml_mapping(mapping)),
+        V::Tagged(tagged) => from_yaml_value(tagged.value),
+    }
+}
+
+fn set_comments(decor: &mut Decor, comments: &[String]) {
+    if !comments.is_empty() {
+        let prefix = comments
+            .iter()
+            .map(|comment| format!("# {}\n", comment))
+            .collect::<String>();
+        decor.set_prefix(prefix);
+    }
+}
+
+fn to_toml(fields: &[Field]) -> String {
+    let mut document = Document::new();
+    let mut position = 0;
+    to_toml_table(document.as_table_mut(), fields, &mut position);
+    document.to_string()
+}
+
+/// Fills a TOML table with the given fields, as a table with a header if they contain tables, so
+/// that nested tables are written as sections rather than inline.
+fn to_toml_table(table: &mut Table, fields: &[Field], position: &mut usize) {
+    for field in fields {
+        match &field.value {
+            Node::Table(fields) => {
+                let mut child = Table::new();
+                *position += 1;
+                child.set_position(*position);
+                set_comments(child.decor_mut(), &field.comments);
+                // Tables only holding other tables, such as `sources`, don't need a header.
+                child.set_implicit(
+                    field.comments.is_empty()
+                        && fields
+                            .iter()
+                            .all(|field| matches!(field.value, Node::Table(_))),
+                );
+                to_toml_table(&mut child, fields, position);
+                table.insert(&field.key, Item::Table(child));
+            }
+            Node::Array(items)
+                if !items.is_empty() && items.iter().all(|item| matches!(item, Node::Table(_))) =>
+            {
+                let mut array = ArrayOfTables::new();
+                for (index, item) in items.iter().enumerate() {
+                    if let Node::Table(fields) = item {
+                        let mut child = Table::new();
+                        *position += 1;
+                        child.set_position(*position);
+                        if index == 0 {
+                            set_comments(child.decor_mut(), &field.comments);
+                        }
+                        to_toml_table(&mut child, fields, position);
+                        array.push(child);
+                    }
+                }
+                table.insert(&field.key, Item::ArrayOfTables(array));
+            }
+            value => {
+                // TOML has no null, so null options are left out, which is what they default to.
+                if let Some(value) = to_toml_value(value) {
+                    table.insert(&field.key, Item::Value(value));
+                    if let Some(decor) = table.key_decor_mut(&field.key) {
+                        set_comments(decor, &field.comments);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn to_toml_value(node: &Node) -> Option<toml_edit::Value> {
+    match node {
+        Node::Value(Value::Null) => None,
+        Node::Value(Value::Bool(b)) => Some((*b).into()),
+        Node::Value(Value::Number(n)) => n
+            .as_i64()
+            .map(Into::into)
+            .or_else(|| n.as_f64().map(Into::into)),
+        Node::Value(Value::String(s)) => Some(s.as_str().into()),
+        Node::Value(value) => Some(value.to_string().into()),
+        Node::Array(items) => Some(toml_edit::Value::Array(
+            items.iter().filter_map(to_toml_value).collect(),
+        )),
+        Node::Table(fields) => {
+            let mut table = InlineTable::new();
+            for field in fields {
+                if let Some(value) = to_toml_value(&field.value) {
+                    table.insert(&field.key, value);
+                }
+            }
+            Some(toml_edit::Value::InlineTable(table))
+        }
+    }
+}
+
+/// Formats a scalar as YAML, indenting the continuation lines of block scalars.
+fn yaml_scalar(value: &Value, indent: usize) -> String {
+    let scalar = serde_yaml::to_string(value).unwrap_or_default();
+    scalar
+        .trim_end_matches('\n')
+        .replace('\n', &format!("\n{}", " ".repeat(indent)))
+}
+
+fn write_yaml_comments(out: &mut String, comments: &[String], indent: usize) {
+    for comment in comments {
+        out.push_str(&format!("{}# {}\n", " ".repeat(indent), comment));
+    }
+}
+
+fn write_yaml_fields(out: &mut String, fields: &[Field], indent: usize) {
+    for field in fields {
+        write_yaml_comments(out, &field.comments, indent);
+        let key = yaml_scalar(&Value::String(field.key.clone()), indent);
+        out.push_str(&format!("{}{}:", " ".repeat(indent), key));
+        write_yaml_value(out, &field.value, indent, false);
+    }
+}
+
+/// Writes a value following a key or a sequence entry marker at the given indentation, on the
+/// same line for scalars and empty collections, and on the next lines otherwise.
+fn write_yaml_value(out: &mut String, node: &Node, indent: usize, in_sequence: bool) {
+    match node {
+        Node::Value(value) => out.push_str(&format!(" {}\n", yaml_scalar(value, indent + 2))),
+        Node::Array(items) if items.is_empty() => out.push_str(" []\n"),
+        Node::Table(fields) if fields.is_empty() => out.push_str(" {}\n"),
+        Node::Array(items) => {
+            // Sequences are written at the indentation of their key, but must be nested further
+            // within another sequence.
+            let indent = if in_sequence { indent + 2 } else { indent };
+            out.push('\n');
+            for item in items {
+                match item {
+                    // Maps start on the same line as their entry marker, unless they start with
+                    // comments.
+                    Node::Table(fields)
+                        if fields
+                            .first()
+                            .map_or(false, |field| field.comments.is_empty()) =>
+                    {
+                        let mut nested = String::new();
+                        write_yaml_fields(&mut nested, fields, indent + 2);
+                        out.push_str(&format!("{}- ", " ".repeat(indent)));
+                        out.push_str(&nested[indent + 2..]);
+                    }
+                    item => {
+                        out.push_str(&format!("{}-", " ".repeat(indent)));
+                        write_yaml_value(out, item, indent, true);
+                    }
+                }
+            }
+        }
+        Node::Table(fields) => {
+            out.push('\n');
+            write_yaml_fields(out, fields, indent + 2);
+        }
+    }
+}
+
+fn write_json(out: &mut String, node: &Node, indent: usize) {
+    let padding = " ".repeat(indent + 2);
+    match node {
+        Node::Value(value) => out.push_str(&value.to_string()),
+        Node::Array(items) if items.is_empty() => out.push_str("[]"),
+        Node::Table(fields) if fields.is_empty() => out.push_str("{}"),
+        Node::Array(items) => {
+            out.push_str("[\n");
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(",\n");
+                }
+                out.push_str(&padding);
+                write_json(out, item, indent + 2);
+            }
+            out.push_str(&format!("\n{}]", " ".repeat(indent)));
+        }
+        Node::Table(fields) => {
+            out.push_str("{\n");
+            for (index, field) in fields.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(",\n");
+                }
+                out.push_str(&format!(
+                    "{}{}: ",
+                    padding,
+                    Value::String(field.key.clone())
+                ));
+                write_json(out, &field.value, indent + 2);
+            }
+            out.push_str(&format!("\n{}}}", " ".repeat(indent)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML: &str = r#"# Global options
+data_dir = "/var/lib/vector"
+
+# Reads the logs
+[sources.in]
+type = "file"
+include = ["/var/log/*.log"]
+# Deprecated name
+ignore_older = 600
+
+[sinks.out]
+type = "console"
+inputs = ["in"]
+encoding.codec = "json"
+"#;
+
+    fn convert(content: &str, from: Format, to: Format) -> String {
+        write(&read(content, from).unwrap(), to)
+    }
+
+    #[test]
+    fn converts_toml_to_yaml_with_comments() {
+        let yaml = convert(TOML, Format::Toml, Format::Yaml);
+        assert_eq!(
+            yaml,
+            r#"# Global options
+data_dir: /var/lib/vector
+sources:
+  # Reads the logs
+  in:
+    type: file
+    include:
+    - /var/log/*.log
+    # Deprecated name
+    ignore_older: 600
+sinks:
+  out:
+    type: console
+    inputs:
+    - in
+    encoding:
+      codec: json
+"#
+        );
+    }
+
+    fn without_comments(fields: Vec<Field>) -> Vec<Field> {
+        fn strip(node: Node) -> Node {
+            match node {
+                Node::Value(value) => Node::Value(value),
+                Node::Array(items) => Node::Array(items.into_iter().map(strip).collect()),
+                Node::Table(fields) => Node::Table(without_comments(fields)),
+            }
+        }
+
+        fields
+            .into_iter()
+            .map(|field| Field::new(field.key, strip(field.value)))
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_between_formats() {
+        let original = without_comments(read(TOML, Format::Toml).unwrap());
+
+        for format in [Format::Toml, Format::Yaml, Format::Json] {
+            let converted = write(&original, format);
+            let fields = without_comments(read(&converted, format).unwrap());
+            assert_eq!(fields, original, "{:?}:\n{}", format, converted);
+        }
+    }
+
+    #[test]
+    fn converts_nested_sequences_to_yaml() {
+        let yaml = convert(
+            r#"{"a": [{"b": 1, "c": [[1, 2], []]}, "d\ne"]}"#,
+            Format::Json,
+            Format::Yaml,
+        );
+        assert_eq!(
+            yaml,
+            "a:\n- b: 1\n  c:\n  -\n    - 1\n    - 2\n  - []\n- |-\n    d\n    e\n"
+        );
+        assert_eq!(
+            read(&yaml, Format::Yaml).unwrap(),
+            read(
+                r#"{"a": [{"b": 1, "c": [[1, 2], []]}, "d\ne"]}"#,
+                Format::Json
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn preserves_key_order() {
+        let json = convert(
+            r#"{"b": 1, "a": {"d": true, "c": null}}"#,
+            Format::Json,
+            Format::Json,
+        );
+        assert_eq!(
+            json,
+            "{\n  \"b\": 1,\n  \"a\": {\n    \"d\": true,\n    \"c\": null\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn rewrites_deprecated_options() {
+        let mut fields = read(TOML, Format::Toml).unwrap();
+        let rewritten = rewrite_deprecated(&mut fields);
+        assert_eq!(
+            rewritten,
+            vec![(
+                "sources".to_string(),
+                "in".to_string(),
+                "ignore_older".to_string(),
+                "ignore_older_secs".to_string()
+            )]
+        );
+
+        let toml = write(&fields, Format::Toml);
+        assert!(toml.contains("# Deprecated name\nignore_older_secs = 600\n"));
+    }
+}
@@ -0,0 +1,275 @@
+//! Config composition.
+//!
+//! Any config file can pull in other config files with a top-level `includes` key, which holds a
+//! list of paths (glob patterns are supported) relative to the including file. Included files are
+//! merged together first, with the same semantics as loading multiple config files, and the
+//! including file is then laid over the result, so it can override anything it includes.
+//!
+//! Config files can also define per-environment overlays under a top-level `profiles` key. The
+//! overlay named by the `VECTOR_PROFILE` environment variable, if any, is laid over the file after
+//! its includes have been resolved.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use glob::glob;
+use toml::value::{Table, Value};
+
+/// Top-level key listing the files a config file includes.
+const INCLUDES_KEY: &str = "includes";
+
+/// Top-level key holding the per-environment overlays of a config file.
+const PROFILES_KEY: &str = "profiles";
+
+/// Environment variable naming the active profile.
+const PROFILE_ENV_VAR: &str = "VECTOR_PROFILE";
+
+/// Tracks the files visited while resolving includes, to detect cycles and avoid including the
+/// same file more than once.
+#[derive(Debug, Default)]
+pub(super) struct IncludeTracker {
+    stack: Vec<PathBuf>,
+    seen: HashSet<PathBuf>,
+}
+
+impl IncludeTracker {
+    /// Starts resolving `path`. Returns `false` if the file has already been included, in which
+    /// case it should be skipped.
+    ///
+    /// # Errors
+    ///
+    /// If `path` is already being resolved, the includes form a cycle, and an error is returned.
+    pub(super) fn enter(&mut self, path: &Path) -> Result<bool, Vec<String>> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+        if self.stack.contains(&path) {
+            let cycle = self
+                .stack
+                .iter()
+                .skip_while(|visited| **visited != path)
+                .chain(std::iter::once(&path))
+                .map(|visited| visited.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(vec![format!("Config include cycle detected: {}.", cycle)]);
+        }
+
+        if !self.seen.insert(path.clone()) {
+            return Ok(false);
+        }
+
+        self.stack.push(path);
+        Ok(true)
+    }
+
+    /// Finishes resolving the file most recently passed to `enter`.
+    pub(super) fn exit(&mut self) {
+        self.stack.pop();
+    }
+}
+
+/// Removes the `includes` key from `table`, returning the paths of the included files, in order.
+pub(super) fn take_includes(path: &Path, table: &mut Table) -> Result<Vec<PathBuf>, Vec<String>> {
+    let patterns = match table.remove(INCLUDES_KEY) {
+        None => return Ok(Vec::new()),
+        Some(Value::Array(patterns)) => patterns,
+        Some(_) => {
+            return Err(vec![format!(
+                "`{}` in config file {:?} must be a list of paths.",
+                INCLUDES_KEY, path
+            )])
+        }
+    };
+
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut paths = Vec::new();
+    let mut errors = Vec::new();
+
+    for pattern in patterns {
+        let pattern = match pattern {
+            Value::String(pattern) => base_dir.join(pattern),
+            _ => {
+                errors.push(format!(
+                    "`{}` in config file {:?} must be a list of paths.",
+                    INCLUDES_KEY, path
+                ));
+                continue;
+            }
+        };
+
+        let mut matches = match glob(&pattern.to_string_lossy()) {
+            Ok(matches) => matches.filter_map(Result::ok).collect::<Vec<_>>(),
+            Err(error) => {
+                errors.push(format!(
+                    "Invalid include pattern {:?} in config file {:?}: {}.",
+                    pattern, path, error
+                ));
+                continue;
+            }
+        };
+
+        if matches.is_empty() {
+            errors.push(format!(
+                "Included config file not found: {:?} (included from {:?}).",
+                pattern, path
+            ));
+        }
+
+        matches.sort();
+        paths.extend(matches);
+    }
+
+    if errors.is_empty() {
+        Ok(paths)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Removes the `profiles` key from `table`, returning the overlay of the active profile, if any.
+pub(super) fn take_active_profile(
+    path: &Path,
+    table: &mut Table,
+) -> Result<Option<Table>, Vec<String>> {
+    let mut profiles = match table.remove(PROFILES_KEY) {
+        None => return Ok(None),
+        Some(Value::Table(profiles)) => profiles,
+        Some(_) => {
+            return Err(vec![format!(
+                "`{}` in config file {:?} must be a table of profiles.",
+                PROFILES_KEY, path
+            )])
+        }
+    };
+
+    let active = match std::env::var(PROFILE_ENV_VAR) {
+        Ok(active) if !active.is_empty() => active,
+        _ => return Ok(None),
+    };
+
+    match profiles.remove(&active) {
+        None => Ok(None),
+        Some(Value::Table(overlay)) => Ok(Some(overlay)),
+        Some(_) => Err(vec![format!(
+            "Profile {:?} in config file {:?} must be a table.",
+            active, path
+        )]),
+    }
+}
+
+/// Lays `overlay` over `base`. Tables are merged recursively, and any other value in `overlay`
+/// replaces the value in `base`.
+pub(super) fn overlay(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(existing)), Value::Table(value)) => self::overlay(existing, value),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{load_source_from_paths, ConfigPath};
+
+    fn fixture(path: &[&str]) -> PathBuf {
+        let mut fixture = PathBuf::from(".").join("tests").join("config-includes");
+        fixture.extend(path);
+        fixture
+    }
+
+    fn table(s: &str) -> Table {
+        toml::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn overlay_merges_tables_and_replaces_values() {
+        let mut base = table(
+            r#"
+            data_dir = "/var/lib/vector"
+
+            [sinks.out]
+            type = "console"
+            inputs = ["in"]
+            encoding.codec = "json"
+            "#,
+        );
+
+        overlay(
+            &mut base,
+            table(
+                r#"
+                [sinks.out]
+                inputs = ["other"]
+                encoding.codec = "text"
+                "#,
+            ),
+        );
+
+        assert_eq!(
+            base,
+            table(
+                r#"
+                data_dir = "/var/lib/vector"
+
+                [sinks.out]
+                type = "console"
+                inputs = ["other"]
+                encoding.codec = "text"
+                "#,
+            )
+        );
+    }
+
+    #[test]
+    fn tracker_detects_cycles() {
+        let mut tracker = IncludeTracker::default();
+        assert!(tracker.enter(Path::new("/tmp/a.toml")).unwrap());
+        assert!(tracker.enter(Path::new("/tmp/b.toml")).unwrap());
+
+        let errors = tracker.enter(Path::new("/tmp/a.toml")).unwrap_err();
+        assert_eq!(
+            errors,
+            vec!["Config include cycle detected: /tmp/a.toml -> /tmp/b.toml -> /tmp/a.toml."]
+        );
+
+        tracker.exit();
+        // Already fully included, so it's skipped rather than included twice.
+        assert!(!tracker.enter(Path::new("/tmp/b.toml")).unwrap());
+    }
+
+    #[test]
+    fn loads_included_files() {
+        let path = fixture(&["success", "vector.toml"]);
+        let (table, warnings) = load_source_from_paths(&[ConfigPath::File(path, None)]).unwrap();
+        assert!(warnings.is_empty());
+        assert!(!table.contains_key(INCLUDES_KEY));
+
+        assert_eq!(
+            table["sources"]["demo"]["type"],
+            Value::String("demo_logs".into())
+        );
+        assert_eq!(
+            table["sinks"]["console"]["type"],
+            Value::String("console".into())
+        );
+        // The including file overrides the files it includes.
+        assert_eq!(
+            table["sinks"]["console"]["encoding"]["codec"],
+            Value::String("text".into())
+        );
+    }
+
+    #[test]
+    fn rejects_include_cycles() {
+        let path = fixture(&["cycle", "a.toml"]);
+        let errors = load_source_from_paths(&[ConfigPath::File(path, None)]).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("Config include cycle detected:"));
+    }
+}
@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 use serde_toml_merge::merge_into_table;
 use toml::value::{Table, Value};
 
-use super::{component_name, open_file, read_dir, Format};
+use super::{component_name, compose, open_file, read_dir, Format};
 use crate::config::format;
 
 /// Provides a hint to the loading system of the type of components that should be found
@@ -166,20 +166,75 @@ pub(super) mod process {
             }
         }
 
-        /// Loads and deserializes a file into a TOML `Table`.
+        /// Loads and deserializes a file into a TOML `Table`, resolving the files it includes and
+        /// applying the active profile.
         fn load_file(
             &mut self,
             path: &Path,
             format: Format,
         ) -> Result<Option<(String, Table, Vec<String>)>, Vec<String>> {
-            if let (Ok(name), Some(file)) = (component_name(path), open_file(path)) {
-                self.load(file, format)
-                    .map(|(value, warnings)| Some((name, value, warnings)))
+            if let Ok(name) = component_name(path) {
+                let mut tracker = compose::IncludeTracker::default();
+                self.load_composed(path, format, &mut tracker)
+                    .map(|loaded| loaded.map(|(value, warnings)| (name, value, warnings)))
             } else {
                 Ok(None)
             }
         }
 
+        /// Loads a file into a TOML `Table`, recursively merging in the files it includes, and
+        /// then laying the file itself and its active profile over the result.
+        fn load_composed(
+            &mut self,
+            path: &Path,
+            format: Format,
+            tracker: &mut compose::IncludeTracker,
+        ) -> Result<Option<(Table, Vec<String>)>, Vec<String>> {
+            if !tracker.enter(path)? {
+                // Already included through another file.
+                return Ok(Some((Table::new(), Vec::new())));
+            }
+
+            let file = match open_file(path) {
+                Some(file) => file,
+                None => return Ok(None),
+            };
+            let (mut table, mut warnings): (Table, _) = self.load(file, format)?;
+            let includes = compose::take_includes(path, &mut table)?;
+            let profile = compose::take_active_profile(path, &mut table)?;
+
+            let mut result = Table::new();
+            for included in includes {
+                let format = Format::from_path(&included).map_err(|included| {
+                    vec![format!(
+                        "Unknown format of included config file: {:?}.",
+                        included
+                    )]
+                })?;
+
+                match self.load_composed(&included, format, tracker)? {
+                    Some((table, warns)) => {
+                        merge_into_table(&mut result, table).map_err(|e| vec![e.to_string()])?;
+                        warnings.extend(warns);
+                    }
+                    None => {
+                        return Err(vec![format!(
+                            "Could not open included config file: {:?}.",
+                            included
+                        )])
+                    }
+                }
+            }
+
+            compose::overlay(&mut result, table);
+            if let Some(profile) = profile {
+                compose::overlay(&mut result, profile);
+            }
+
+            tracker.exit();
+            Ok(Some((result, warnings)))
+        }
+
         /// Loads a file, and if the path provided contains a sub-folder by the same name as the
         /// component, descend into it recursively, returning a TOML `Table`.
         fn load_file_recursive(
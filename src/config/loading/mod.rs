@@ -1,3 +1,4 @@
+mod compose;
 mod config_builder;
 mod loader;
 mod secret;
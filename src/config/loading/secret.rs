@@ -24,10 +24,11 @@ use crate::{
 // - "SECRET[backend.secret_name]" will match and capture "backend" and "secret_name"
 // - "SECRET[backend.secret.name]" will match and capture "backend" and "secret.name"
 // - "SECRET[backend..secret.name]" will match and capture "backend" and ".secret.name"
+// - "SECRET[backend.secret/path:name]" will match and capture "backend" and "secret/path:name"
 // - "SECRET[secret_name]" will not match
 // - "SECRET[.secret.name]" will not match
 static COLLECTOR: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"SECRET\[([[:word:]]+)\.([[:word:].]+)\]").unwrap());
+    Lazy::new(|| Regex::new(r"SECRET\[([[:word:]]+)\.([[:word:]./:-]+)\]").unwrap());
 
 /// Helper type for specifically deserializing secrets backends.
 #[derive(Debug, Default, Deserialize, Serialize)]
@@ -54,6 +55,22 @@ impl SecretBackendLoader {
     pub(crate) fn retrieve(
         &mut self,
         signal_rx: &mut signal::SignalRx,
+    ) -> Result<HashMap<String, String>, String> {
+        self.retrieve_secrets(signal_rx, false)
+    }
+
+    /// Retrieves the secrets again, bypassing the caches of the backends, to detect rotation.
+    pub(crate) fn refresh(
+        &mut self,
+        signal_rx: &mut signal::SignalRx,
+    ) -> Result<HashMap<String, String>, String> {
+        self.retrieve_secrets(signal_rx, true)
+    }
+
+    fn retrieve_secrets(
+        &mut self,
+        signal_rx: &mut signal::SignalRx,
+        refresh: bool,
     ) -> Result<HashMap<String, String>, String> {
         let secrets = self.secret_keys.iter().flat_map(|(backend_name, keys)| {
             match self.backends.get_mut(&ComponentKey::from(backend_name.clone())) {
@@ -62,7 +79,12 @@ impl SecretBackendLoader {
                 },
                 Some(backend) => {
                     debug!(message = "Retrieving secret from a backend.", backend = ?backend_name);
-                    match backend.retrieve(keys.clone(), signal_rx) {
+                    let secrets = if refresh {
+                        backend.refresh(keys.clone(), signal_rx)
+                    } else {
+                        backend.retrieve(keys.clone(), signal_rx)
+                    };
+                    match secrets {
                         Err(e) => {
                             vec![Err(format!("Error while retrieving secret from backend \"{}\": {}.", backend_name, e))]
                         },
@@ -203,6 +225,7 @@ mod tests {
             SECRET[second_backend.secret.key]
             SECRET[first_backend.a_third.secret_key]
             SECRET[first_backend...an_extra_secret_key]
+            SECRET[second_backend.secret/data/my-app:key]
             SECRET[non_matching_syntax]
             SECRET[.non.matching.syntax]
         "#},
@@ -220,9 +243,10 @@ mod tests {
         assert!(first_backend_keys.contains("..an_extra_secret_key"));
 
         let second_backend_keys = keys.get("second_backend").unwrap();
-        assert_eq!(second_backend_keys.len(), 2);
+        assert_eq!(second_backend_keys.len(), 3);
         assert!(second_backend_keys.contains("secret_key"));
         assert!(second_backend_keys.contains("secret.key"));
+        assert!(second_backend_keys.contains("secret/data/my-app:key"));
     }
 
     #[test]
@@ -21,6 +21,7 @@ pub mod api;
 mod builder;
 mod cmd;
 mod compiler;
+mod convert;
 mod diff;
 mod enrichment_table;
 #[cfg(feature = "enterprise")]
@@ -42,13 +43,14 @@ pub mod watcher;
 
 pub use builder::ConfigBuilder;
 pub use cmd::{cmd, Opts};
-pub use diff::ConfigDiff;
+pub use diff::{ConfigDiff, Difference};
 pub use enrichment_table::{EnrichmentTableConfig, EnrichmentTableOuter};
 pub use format::{Format, FormatHint};
 pub use id::{ComponentKey, Inputs, OutputId};
 pub use loading::{
     load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider_and_secrets,
-    load_from_str, load_source_from_paths, merge_path_lists, process_paths, CONFIG_PATHS,
+    load_from_str, load_secret_backends_from_paths, load_source_from_paths, merge_path_lists,
+    process_paths, CONFIG_PATHS,
 };
 pub use provider::ProviderConfig;
 pub use secret::SecretBackend;
@@ -140,6 +142,10 @@ impl Config {
         self.sinks.iter()
     }
 
+    pub fn sinks_mut(&mut self) -> impl Iterator<Item = (&ComponentKey, &mut SinkOuter<OutputId>)> {
+        self.sinks.iter_mut()
+    }
+
     pub fn sink(&self, id: &ComponentKey) -> Option<&SinkOuter<OutputId>> {
         self.sinks.get(id)
     }
@@ -378,19 +384,15 @@ impl TestDefinition<String> {
 
         let outputs = outputs
             .into_iter()
-            .map(|old| {
-                let TestOutput {
-                    extract_from,
-                    conditions,
-                } = old;
-
-                (extract_from.to_vec(), conditions)
-            })
-            .filter_map(|(extract_from, conditions)| {
+            .filter_map(|old| {
                 let mut outputs = Vec::new();
-                for from in extract_from {
+                for from in old.extract_from.to_vec() {
                     if let Some(output_id) = output_map.get(&from) {
                         outputs.push(output_id.clone());
+                    } else if let Some(named_outputs) = named_outputs_of(&output_map, &from) {
+                        // A transform with only named outputs, such as `route`, is checked
+                        // against the events from all of its outputs.
+                        outputs.extend(named_outputs);
                     } else {
                         errors.push(format!(
                             r#"Invalid extract_from target in test '{}': '{}' does not exist"#,
@@ -403,7 +405,10 @@ impl TestDefinition<String> {
                 } else {
                     Some(TestOutput {
                         extract_from: outputs.into(),
-                        conditions,
+                        conditions: old.conditions,
+                        metrics: old.metrics,
+                        events_file: old.events_file,
+                        ordered: old.ordered,
                     })
                 }
             })
@@ -438,6 +443,24 @@ impl TestDefinition<String> {
     }
 }
 
+/// Returns the outputs of the component with the given name, if it only has named outputs.
+fn named_outputs_of(
+    output_map: &HashMap<String, OutputId>,
+    component: &str,
+) -> Option<Vec<OutputId>> {
+    let mut outputs = output_map
+        .values()
+        .filter(|output_id| output_id.port.is_some() && output_id.component.id() == component)
+        .cloned()
+        .collect::<Vec<_>>();
+    if outputs.is_empty() {
+        None
+    } else {
+        outputs.sort_by_key(ToString::to_string);
+        Some(outputs)
+    }
+}
+
 impl TestDefinition<OutputId> {
     fn stringify(self) -> TestDefinition<String> {
         let TestDefinition {
@@ -459,6 +482,9 @@ impl TestDefinition<OutputId> {
                     .collect::<Vec<_>>()
                     .into(),
                 conditions: old.conditions,
+                metrics: old.metrics,
+                events_file: old.events_file,
+                ordered: old.ordered,
             })
             .collect();
 
@@ -543,6 +569,31 @@ pub struct TestOutput<T: 'static = OutputId> {
 
     /// The conditions to run against the output to validate that they were transformed as expected.
     pub conditions: Option<Vec<conditions::AnyCondition>>,
+
+    /// The metric events expected to be emitted.
+    ///
+    /// The events received must match these metrics exactly. A metric without a timestamp matches
+    /// regardless of the timestamp of the metric received.
+    pub metrics: Option<Vec<Metric>>,
+
+    /// The path to a JSON file containing the events expected to be emitted.
+    ///
+    /// The file must contain an array of events, each being either an object with a `log` field
+    /// holding the fields of a log event, or an object with a `metric` field holding a metric in
+    /// the same format as `metrics`. The events received must match these events exactly, except
+    /// that the timestamp field of a log event is only compared if present in the file.
+    ///
+    /// Relative paths are resolved against the current working directory.
+    #[configurable(metadata(docs::examples = "tests/golden/parse_logs.json"))]
+    pub events_file: Option<PathBuf>,
+
+    /// Whether or not the events received are checked in the order they were emitted.
+    ///
+    /// When enabled, the first condition (or expected event) is checked against the first event
+    /// received, the second against the second, and so on, and the number of events received must
+    /// match. Otherwise, each condition needs to match any one of the events received.
+    #[serde(default)]
+    pub ordered: bool,
 }
 
 #[cfg(all(test, feature = "sources-file", feature = "sinks-console"))]
@@ -561,7 +612,8 @@ mod tests {
                 let c2 = config::load_from_str(config, format).unwrap();
                 match (
                     config::warnings(&c2),
-                    topology::builder::build_pieces(&c, &diff, HashMap::new()).await,
+                    topology::builder::build_pieces(&c, &diff, HashMap::new(), Default::default())
+                        .await,
                 ) {
                     (warnings, Ok(_pieces)) => Ok(warnings),
                     (_, Err(errors)) => Err(errors),
@@ -610,6 +662,159 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn bad_rate_limit_groups() {
+        let err = load(
+            r#"
+            rate_limit_groups.empty = { duration_secs = 0, request_limit = 10 }
+
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            rate_limit_group = "missing"
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            vec![
+                "Rate limit group \"empty\" must have a `duration_secs` greater than zero.",
+                "Sink \"out\" references undefined rate limit group \"missing\".",
+            ],
+            err,
+        );
+    }
+
+    #[tokio::test]
+    async fn rate_limit_group_request_limit_needs_support() {
+        let err = load(
+            r#"
+            rate_limit_groups.requests = { request_limit = 10 }
+            rate_limit_groups.bytes = { byte_limit = 1000 }
+
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.requests]
+            type = "test_basic"
+            inputs = ["in"]
+            rate_limit_group = "requests"
+
+            [sinks.bytes]
+            type = "test_basic"
+            inputs = ["in"]
+            rate_limit_group = "bytes"
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            vec![
+                "Sink \"requests\": The `test_basic` sink doesn't support the `request_limit` of rate limit group \"requests\".",
+            ],
+            err,
+        );
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_needs_support() {
+        let err = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            circuit_breaker.failure_threshold = 3
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            vec!["Sink \"out\": The `test_basic` sink doesn't support `circuit_breaker`."],
+            err,
+        );
+    }
+
+    #[tokio::test]
+    async fn bad_circuit_breakers() {
+        let err = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            circuit_breaker.failure_threshold = 0
+            circuit_breaker.fallback = "dead_letter_queue"
+
+            [sinks.self_queue]
+            type = "test_basic"
+            inputs = ["in"]
+            circuit_breaker.fallback = "dead_letter_queue"
+            circuit_breaker.dead_letter_queue = "self_queue"
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            vec![
+                "Sink \"out\" must have a circuit breaker `failure_threshold` and `reset_timeout_secs` greater than zero.",
+                "Sink \"out\" has circuit breaker fallback `dead_letter_queue`, but no dead letter queue is configured.",
+                "Sink \"self_queue\" cannot use itself as the dead letter queue of its circuit breaker.",
+            ],
+            err,
+        );
+    }
+
+    #[tokio::test]
+    async fn bad_max_event_ages() {
+        let err = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            max_event_age.max_age_secs = 0
+            max_event_age.fallback = "dead_letter_queue"
+
+            [sinks.missing_queue]
+            type = "test_basic"
+            inputs = ["in"]
+            max_event_age.max_age_secs = 3600
+            max_event_age.fallback = "dead_letter_queue"
+            max_event_age.dead_letter_queue = "missing"
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            vec![
+                "Sink \"out\" must have a `max_event_age.max_age_secs` greater than zero.",
+                "Sink \"out\" has stale events fallback `dead_letter_queue`, but no dead letter queue is configured.",
+                "Sink \"missing_queue\" has stale events dead letter queue \"missing\", which is not a sink.",
+            ],
+            err,
+        );
+    }
+
     #[tokio::test]
     async fn duplicate_name() {
         let err = load(
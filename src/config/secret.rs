@@ -13,4 +13,14 @@ pub trait SecretBackend: NamedComponent + core::fmt::Debug + Send + Sync {
         secret_keys: HashSet<String>,
         signal_rx: &mut signal::SignalRx,
     ) -> crate::Result<HashMap<String, String>>;
+
+    /// Retrieves the secrets again to find out whether any of them was rotated, bypassing any
+    /// cache kept by the backend.
+    fn refresh(
+        &mut self,
+        secret_keys: HashSet<String>,
+        signal_rx: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        self.retrieve(secret_keys, signal_rx)
+    }
 }
@@ -1,7 +1,16 @@
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
 use async_trait::async_trait;
 use enum_dispatch::enum_dispatch;
+use indexmap::IndexMap;
 use serde::Serialize;
-use vector_buffers::{BufferConfig, BufferType};
+use vector_buffers::{BufferConfig, BufferType, WhenFull};
 use vector_config::{configurable_component, Configurable, NamedComponent};
 use vector_core::{
     config::{AcknowledgementsConfig, GlobalOptions, Input},
@@ -9,7 +18,18 @@ use vector_core::{
 };
 
 use super::{id::Inputs, schema, ComponentKey, ProxyConfig, Resource};
-use crate::sinks::{util::UriSerde, Healthcheck, Sinks};
+use crate::sinks::{
+    util::{
+        event_age::MaxEventAgeConfig,
+        schema_mapping::SchemaMappingConfig,
+        service::{
+            circuit_breaker::{CircuitBreaker, CircuitBreakerConfig},
+            rate_limit_group::SharedRateLimit,
+        },
+        UriSerde,
+    },
+    Healthcheck, Sinks,
+};
 
 /// Fully resolved sink component.
 #[configurable_component]
@@ -47,6 +67,73 @@ where
     )]
     proxy: ProxyConfig,
 
+    /// The name of the rate limit group, from the global `rate_limit_groups` option, that this
+    /// sink is part of.
+    ///
+    /// The requests and bytes sent by this sink then count against the limits shared by all sinks
+    /// of the group.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::advanced, docs::examples = "elasticsearch"))]
+    rate_limit_group: Option<String>,
+
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    circuit_breaker: Option<CircuitBreakerConfig>,
+
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_event_age: Option<MaxEventAgeConfig>,
+
+    /// The maximum number of bytes this sink can have allocated.
+    ///
+    /// When reached, the sink stops accepting events until its allocated memory falls back under
+    /// the limit, applying backpressure to upstream components. This requires Vector to be run with
+    /// `--allocation-tracing`, and the allocated memory is only measured at the allocation tracing
+    /// reporting interval.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::advanced, docs::type_unit = "bytes"))]
+    pub max_allocated_bytes: Option<NonZeroU64>,
+
+    /// A [Vector Remap Language][vrl] (VRL) expression computing the partition key of each event.
+    ///
+    /// The expression is evaluated once for each log event, and its result can be referred to as
+    /// `{{ %partition_key }}` by the templated options of the sink, such as key prefixes, topics,
+    /// indices, or labels. This allows partitioning events in ways the template syntax can't
+    /// express, such as bucketing them by a hash of their tenant ID. Events for which the
+    /// expression fails or resolves to `null` have no partition key. Only log events get a partition
+    /// key, metrics and traces never have one.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(
+        docs::advanced,
+        docs::examples = "to_string(mod(seahash(.tenant_id), 8))"
+    ))]
+    pub partition_key: Option<String>,
+
+    /// Maps the fields of log events to a target schema before they reach the sink.
+    ///
+    /// This allows a pipeline to feed backends expecting different field names, such as Elasticsearch
+    /// with the Elastic Common Schema and OpenTelemetry backends with the OpenTelemetry log data
+    /// model, without transforming the events for each of them.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_mapping: Option<SchemaMappingConfig>,
+
+    /// The priorities of the inputs of this sink, by input.
+    ///
+    /// The events of each input with a priority are queued separately from the buffer of the sink,
+    /// and the events of the inputs with the highest priority are sent to the sink first. Under
+    /// backpressure, the events of the inputs with a lower priority wait in their queue, and are
+    /// blocked or dropped when it is full, according to its `when_full` option. The inputs without
+    /// a priority have a priority of `0`, and go through the buffer of the sink.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    #[configurable(metadata(
+        docs::advanced,
+        docs::additional_props_description = "The priority of the input."
+    ))]
+    pub input_priorities: IndexMap<String, InputPriority>,
+
     #[serde(flatten)]
     #[configurable(metadata(docs::hidden))]
     pub inner: Sinks,
@@ -68,6 +155,13 @@ where
             healthcheck_uri: None,
             inner: inner.into(),
             proxy: Default::default(),
+            rate_limit_group: None,
+            circuit_breaker: None,
+            max_event_age: None,
+            max_allocated_bytes: None,
+            partition_key: None,
+            schema_mapping: None,
+            input_priorities: IndexMap::new(),
         }
     }
 
@@ -76,7 +170,9 @@ where
         for stage in self.buffer.stages() {
             match stage {
                 BufferType::Memory { .. } => {}
-                BufferType::DiskV2 { .. } => resources.push(Resource::DiskBuffer(id.to_string())),
+                BufferType::DiskV2 { .. } | BufferType::Hybrid { .. } => {
+                    resources.push(Resource::DiskBuffer(id.to_string()))
+                }
             }
         }
         resources
@@ -104,6 +200,14 @@ where
         &self.proxy
     }
 
+    pub fn rate_limit_group(&self) -> Option<&str> {
+        self.rate_limit_group.as_deref()
+    }
+
+    pub const fn circuit_breaker(&self) -> Option<&CircuitBreakerConfig> {
+        self.circuit_breaker.as_ref()
+    }
+
     pub(super) fn map_inputs<U>(self, f: impl Fn(&T) -> U) -> SinkOuter<U>
     where
         U: Configurable + Serialize,
@@ -124,6 +228,13 @@ where
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            rate_limit_group: self.rate_limit_group,
+            circuit_breaker: self.circuit_breaker,
+            max_event_age: self.max_event_age,
+            max_allocated_bytes: self.max_allocated_bytes,
+            partition_key: self.partition_key,
+            schema_mapping: self.schema_mapping,
+            input_priorities: self.input_priorities,
         }
     }
 }
@@ -168,6 +279,28 @@ impl From<UriSerde> for SinkHealthcheckOptions {
     }
 }
 
+/// Priority of a sink input.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct InputPriority {
+    /// The priority of the input.
+    ///
+    /// The events of the inputs with a higher priority are sent to the sink first.
+    pub priority: u8,
+
+    /// The maximum number of events queued for the input.
+    #[serde(default = "default_input_queue_max_events")]
+    pub max_events: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub when_full: WhenFull,
+}
+
+const fn default_input_queue_max_events() -> NonZeroUsize {
+    unsafe { NonZeroUsize::new_unchecked(500) }
+}
+
 /// Generalized interface for describing and building sink components.
 #[async_trait]
 #[enum_dispatch]
@@ -207,6 +340,16 @@ pub struct SinkContext {
     pub globals: GlobalOptions,
     pub proxy: ProxyConfig,
     pub schema: schema::Options,
+    /// The definition of the events received by the sink, from which the schema of the events it
+    /// encodes can be inferred.
+    pub schema_definition: crate::schema::Definition,
+    /// The limit shared by the sinks of the rate limit group the sink is part of, if any.
+    pub(crate) rate_limit_group: Option<SharedRateLimit>,
+    /// The circuit breaker of the sink, if it has one.
+    pub(crate) circuit_breaker: Option<CircuitBreaker>,
+    /// Set once the sink has taken the request settings of its context, which the topology checks
+    /// after building it so that a sink can't silently ignore them.
+    pub(crate) request_settings_taken: Arc<AtomicBool>,
 }
 
 impl SinkContext {
@@ -217,6 +360,10 @@ impl SinkContext {
             globals: GlobalOptions::default(),
             proxy: ProxyConfig::default(),
             schema: schema::Options::default(),
+            schema_definition: crate::schema::Definition::any(),
+            rate_limit_group: None,
+            circuit_breaker: None,
+            request_settings_taken: Arc::default(),
         }
     }
 
@@ -227,4 +374,24 @@ impl SinkContext {
     pub const fn proxy(&self) -> &ProxyConfig {
         &self.proxy
     }
+
+    /// Takes the shared limit of the rate limit group the sink is part of, if any.
+    ///
+    /// Sinks sending their events in requests apply it through
+    /// [`TowerRequestSettings::with_context`][crate::sinks::util::TowerRequestSettings::with_context].
+    /// A sink in a group with a request limit fails to build if it never takes it.
+    pub fn rate_limit_group(&self) -> Option<SharedRateLimit> {
+        self.request_settings_taken.store(true, Ordering::Relaxed);
+        self.rate_limit_group.clone()
+    }
+
+    /// Takes the circuit breaker of the sink, if it has one.
+    ///
+    /// Like the rate limit group, it is applied through
+    /// [`TowerRequestSettings::with_context`][crate::sinks::util::TowerRequestSettings::with_context],
+    /// and a sink with a circuit breaker fails to build if it never takes it.
+    pub fn circuit_breaker(&self) -> Option<CircuitBreaker> {
+        self.request_settings_taken.store(true, Ordering::Relaxed);
+        self.circuit_breaker.clone()
+    }
 }
@@ -11,7 +11,10 @@ use vector_core::{
 };
 
 use super::{schema, ComponentKey, ProxyConfig, Resource};
-use crate::{shutdown::ShutdownSignal, sources::Sources, SourceSender};
+use crate::{
+    conditions::AnyCondition, shutdown::ShutdownSignal, sinks::Healthcheck, sources::Sources,
+    SourceSender,
+};
 
 /// Fully resolved source component.
 #[configurable_component]
@@ -28,6 +31,34 @@ pub struct SourceOuter {
     #[serde(default, skip)]
     pub sink_acknowledgements: bool,
 
+    /// Captures the events emitted by this source on its default output to a file, to replay
+    /// them later with `vector replay`.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture: Option<crate::capture::CaptureConfig>,
+
+    /// A condition that the events of this source must match to be forwarded.
+    ///
+    /// The condition is matched against the events as soon as the source emits them, before they
+    /// are sent to the components consuming them, so that unwanted events, such as health checks
+    /// or debug messages, are dropped as early as possible. The events that don't match are
+    /// dropped, and counted as discarded by the source.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<AnyCondition>,
+
+    /// Runs this source on only one instance at a time, elected among the instances sharing the
+    /// same backend.
+    ///
+    /// The source is started on another instance when the instance running it stops.
+    #[cfg(any(
+        feature = "leader-election-kubernetes",
+        feature = "leader-election-redis"
+    ))]
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leader_election: Option<crate::leader_election::LeaderElectionConfig>,
+
     #[configurable(metadata(docs::hidden))]
     #[serde(flatten)]
     pub(crate) inner: Sources,
@@ -38,6 +69,13 @@ impl SourceOuter {
         Self {
             proxy: Default::default(),
             sink_acknowledgements: false,
+            capture: None,
+            filter: None,
+            #[cfg(any(
+                feature = "leader-election-kubernetes",
+                feature = "leader-election-redis"
+            ))]
+            leader_election: None,
             inner: inner.into(),
         }
     }
@@ -84,8 +122,19 @@ pub trait SourceConfig: NamedComponent + core::fmt::Debug + Send + Sync {
     /// well as emit contextual warnings when end-to-end acknowledgements are enabled, but the
     /// topology as configured does not actually support the use of end-to-end acknowledgements.
     fn can_acknowledge(&self) -> bool;
+
+    /// Builds a check that the source can reach the service it reads from, with the credentials
+    /// it is configured with.
+    ///
+    /// Unlike the healthchecks of sinks, this isn't run when the source is started, only by
+    /// `vector validate --check-connectivity`. Sources that don't read from a remote service, or
+    /// that can't be checked without consuming events, return `None`.
+    fn healthcheck(&self, _proxy: &ProxyConfig) -> Option<Healthcheck> {
+        None
+    }
 }
 
+#[derive(Clone)]
 pub struct SourceContext {
     pub key: ComponentKey,
     pub globals: GlobalOptions,
@@ -1,5 +1,6 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::num::NonZeroU64;
 
 use async_trait::async_trait;
 use dyn_clone::DynClone;
@@ -56,6 +57,23 @@ where
     #[configurable(derived)]
     pub inputs: Inputs<T>,
 
+    /// The sink to send events dropped by this transform to.
+    ///
+    /// Only applies to transforms that route dropped events to their `dropped` output. Overrides
+    /// the global `dead_letter_queue` option.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dead_letter_queue: Option<ComponentKey>,
+
+    /// The maximum number of bytes this transform can have allocated.
+    ///
+    /// When reached, the transform stops accepting events until its allocated memory falls back under
+    /// the limit, applying backpressure to upstream components. This requires Vector to be run with
+    /// `--allocation-tracing`, and the allocated memory is only measured at the allocation tracing
+    /// reporting interval.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::advanced, docs::type_unit = "bytes"))]
+    pub max_allocated_bytes: Option<NonZeroU64>,
+
     #[configurable(metadata(docs::hidden))]
     #[serde(flatten)]
     pub inner: BoxedTransform,
@@ -72,7 +90,12 @@ where
     {
         let inputs = Inputs::from_iter(inputs);
         let inner = inner.into();
-        TransformOuter { inputs, inner }
+        TransformOuter {
+            inputs,
+            dead_letter_queue: None,
+            max_allocated_bytes: None,
+            inner,
+        }
     }
 
     pub(super) fn map_inputs<U>(self, f: impl Fn(&T) -> U) -> TransformOuter<U>
@@ -90,6 +113,8 @@ where
     {
         TransformOuter {
             inputs: Inputs::from_iter(inputs),
+            dead_letter_queue: self.dead_letter_queue,
+            max_allocated_bytes: self.max_allocated_bytes,
             inner: self.inner,
         }
     }
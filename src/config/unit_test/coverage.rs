@@ -0,0 +1,334 @@
+//! Coverage of the transforms under test.
+//!
+//! When enabled, the outputs of the transforms under test are recorded as the unit tests are
+//! built, and `remap` transforms track which expressions of their program run. Once the tests
+//! have run, the internal metrics tell which transforms received events and which of their outputs
+//! sent events, so the report covers transforms, the branches of transforms with several outputs,
+//! such as `route`, and the lines of `remap` programs.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use once_cell::sync::Lazy;
+use vector_core::metrics::Controller;
+use vrl::diagnostic::Span;
+
+use crate::{
+    config::{ComponentKey, OutputId},
+    event::MetricValue,
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static COVERAGE: Lazy<Mutex<Coverage>> = Lazy::new(Default::default);
+
+#[derive(Default)]
+struct Coverage {
+    /// The outputs of the transforms under test, by transform.
+    outputs: BTreeMap<String, BTreeSet<Option<String>>>,
+    programs: BTreeMap<String, ProgramCoverage>,
+}
+
+struct ProgramCoverage {
+    source: String,
+    spans: Vec<Span>,
+    covered: BTreeSet<Span>,
+}
+
+/// Enables coverage tracking.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Records the outputs of the transforms under test.
+pub(super) fn register_outputs<'a>(outputs: impl IntoIterator<Item = &'a OutputId>) {
+    let mut coverage = COVERAGE.lock().expect("poisoned lock");
+    for output in outputs {
+        coverage
+            .outputs
+            .entry(output.component.id().to_owned())
+            .or_default()
+            .insert(output.port.clone());
+    }
+}
+
+/// Records the source of the program of a `remap` transform, and the spans of its expressions.
+///
+/// The transform is built for each unit test, so the coverage of its program accumulates over the
+/// tests as long as the program doesn't change.
+pub fn register_program(key: &ComponentKey, source: &str, spans: &[Span]) {
+    let mut coverage = COVERAGE.lock().expect("poisoned lock");
+    let program = coverage
+        .programs
+        .entry(key.id().to_owned())
+        .or_insert_with(|| ProgramCoverage {
+            source: source.to_owned(),
+            spans: spans.to_vec(),
+            covered: BTreeSet::new(),
+        });
+    if program.source != source {
+        program.source = source.to_owned();
+        program.spans = spans.to_vec();
+        program.covered.clear();
+    }
+}
+
+/// Records the spans of the expressions of the program of a `remap` transform that ran.
+pub fn record_program(key: &ComponentKey, covered: BTreeSet<Span>) {
+    if covered.is_empty() {
+        return;
+    }
+    let mut coverage = COVERAGE.lock().expect("poisoned lock");
+    if let Some(program) = coverage.programs.get_mut(key.id()) {
+        program.covered.extend(covered);
+    }
+}
+
+/// The counts of the items covered by the tests.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Counts {
+    pub covered: usize,
+    pub total: usize,
+}
+
+impl Counts {
+    fn add(&mut self, covered: bool) {
+        self.total += 1;
+        if covered {
+            self.covered += 1;
+        }
+    }
+
+    /// The percentage of the items that are covered, which is 100 when there are none.
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.covered as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+impl fmt::Display for Counts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} ({:.1}%)",
+            self.covered,
+            self.total,
+            self.percent()
+        )
+    }
+}
+
+#[derive(Debug)]
+struct ProgramReport {
+    lines: Counts,
+    uncovered_lines: Vec<usize>,
+}
+
+/// The coverage of the transforms under test.
+#[derive(Debug, Default)]
+pub struct CoverageReport {
+    transforms: BTreeMap<String, bool>,
+    branches: BTreeMap<(String, String), bool>,
+    programs: BTreeMap<String, ProgramReport>,
+}
+
+impl CoverageReport {
+    pub fn transforms(&self) -> Counts {
+        count(self.transforms.values())
+    }
+
+    pub fn branches(&self) -> Counts {
+        count(self.branches.values())
+    }
+
+    pub fn lines(&self) -> Counts {
+        self.programs
+            .values()
+            .fold(Counts::default(), |mut counts, program| {
+                counts.covered += program.lines.covered;
+                counts.total += program.lines.total;
+                counts
+            })
+    }
+
+    /// The counts of all of the transforms, branches and lines.
+    pub fn total(&self) -> Counts {
+        [self.transforms(), self.branches(), self.lines()]
+            .into_iter()
+            .fold(Counts::default(), |mut total, counts| {
+                total.covered += counts.covered;
+                total.total += counts.total;
+                total
+            })
+    }
+}
+
+fn count<'a>(covered: impl Iterator<Item = &'a bool>) -> Counts {
+    let mut counts = Counts::default();
+    covered.for_each(|covered| counts.add(*covered));
+    counts
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "transforms: {}", self.transforms())?;
+        for (transform, covered) in &self.transforms {
+            if !covered {
+                writeln!(f, "  not exercised: {}", transform)?;
+            }
+        }
+
+        writeln!(f, "branches: {}", self.branches())?;
+        for ((transform, output), covered) in &self.branches {
+            if !covered {
+                writeln!(f, "  not exercised: {}.{}", transform, output)?;
+            }
+        }
+
+        writeln!(f, "remap lines: {}", self.lines())?;
+        for (transform, program) in &self.programs {
+            if !program.uncovered_lines.is_empty() {
+                let lines = program
+                    .uncovered_lines
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>();
+                writeln!(f, "  {}: lines {} not run", transform, lines.join(", "))?;
+            }
+        }
+
+        write!(f, "total: {}", self.total())
+    }
+}
+
+/// Reports the coverage of the tests that ran.
+pub fn report() -> CoverageReport {
+    let (received, sent) = exercised();
+    let coverage = COVERAGE.lock().expect("poisoned lock");
+
+    let mut report = CoverageReport::default();
+    for (transform, outputs) in &coverage.outputs {
+        report
+            .transforms
+            .insert(transform.clone(), received.contains(transform));
+
+        // Only transforms with several outputs have branches.
+        if outputs.len() > 1 {
+            for output in outputs {
+                let covered = sent.contains(&(transform.clone(), output.clone()));
+                let output = output.clone().unwrap_or_else(|| "_default".to_owned());
+                report.branches.insert((transform.clone(), output), covered);
+            }
+        }
+    }
+    for (transform, program) in &coverage.programs {
+        report
+            .programs
+            .insert(transform.clone(), program_report(program));
+    }
+    report
+}
+
+/// Lists the transforms that received events and the outputs that sent events, from the internal
+/// metrics.
+fn exercised() -> (HashSet<String>, HashSet<(String, Option<String>)>) {
+    let mut received = HashSet::new();
+    let mut sent = HashSet::new();
+    let metrics = match Controller::get() {
+        Ok(controller) => controller.capture_metrics(),
+        Err(_) => return (received, sent),
+    };
+
+    for metric in metrics {
+        let exercised = matches!(metric.value(), MetricValue::Counter { value } if *value > 0.0);
+        let component = match metric.tag_value("component_id") {
+            Some(component) if exercised => component,
+            _ => continue,
+        };
+        match metric.name() {
+            "component_received_events_total" => {
+                received.insert(component);
+            }
+            "component_sent_events_total" => {
+                sent.insert((component, metric.tag_value("output")));
+            }
+            _ => {}
+        }
+    }
+    (received, sent)
+}
+
+/// Maps the spans of the program to lines. A line is covered when an expression starting on it ran.
+fn program_report(program: &ProgramCoverage) -> ProgramReport {
+    let line_starts = std::iter::once(0)
+        .chain(program.source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect::<Vec<_>>();
+    let line = |span: &Span| line_starts.partition_point(|start| *start <= span.start());
+
+    let mut lines = BTreeMap::<usize, bool>::new();
+    for span in &program.spans {
+        *lines.entry(line(span)).or_default() |= program.covered.contains(span);
+    }
+
+    ProgramReport {
+        lines: count(lines.values()),
+        uncovered_lines: lines
+            .into_iter()
+            .filter(|(_, covered)| !covered)
+            .map(|(line, _)| line)
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn program_lines() {
+        let source = ".a = 1\nif .b == true {\n  .c = 2\n} else {\n  .d = 3\n}\n";
+        let mut config = vrl::CompileConfig::default();
+        config.set_track_coverage(true);
+        let program =
+            vrl::compile_with_external(source, &vrl_stdlib::all(), &Default::default(), config)
+                .unwrap()
+                .program;
+
+        let mut runtime = vrl::Runtime::default();
+        runtime.enable_coverage();
+        let mut target = vrl::TargetValue {
+            value: vrl::value!({ "b": true }),
+            metadata: vrl::value!({}),
+            secrets: Default::default(),
+        };
+        runtime
+            .resolve(&mut target, &program, &vrl::TimeZone::default())
+            .unwrap();
+
+        let report = program_report(&ProgramCoverage {
+            source: source.to_owned(),
+            spans: program.block_spans().to_vec(),
+            covered: runtime.take_coverage(),
+        });
+        assert_eq!(
+            report.lines,
+            Counts {
+                covered: 3,
+                total: 4
+            }
+        );
+        assert_eq!(report.uncovered_lines, vec![5]);
+    }
+}
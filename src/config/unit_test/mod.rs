@@ -1,9 +1,11 @@
+pub mod coverage;
 #[cfg(all(test, feature = "vector-unit-test-tests"))]
 mod tests;
 mod unit_test_components;
 
 use std::{
     collections::{HashMap, HashSet},
+    path::Path,
     sync::Arc,
 };
 
@@ -19,12 +21,11 @@ use value::Kind;
 use vector_core::config::LogNamespace;
 
 pub use self::unit_test_components::{
-    UnitTestSinkCheck, UnitTestSinkConfig, UnitTestSinkResult, UnitTestSourceConfig,
-    UnitTestStreamSinkConfig, UnitTestStreamSourceConfig,
+    UnitTestOutputCheck, UnitTestSinkCheck, UnitTestSinkConfig, UnitTestSinkResult,
+    UnitTestSourceConfig, UnitTestStreamSinkConfig, UnitTestStreamSourceConfig,
 };
 use super::{compiler::expand_globs, graph::Graph, OutputId};
 use crate::{
-    conditions::Condition,
     config::{
         self, loading, ComponentKey, Config, ConfigBuilder, ConfigPath, SinkOuter, SourceOuter,
         TestDefinition, TestInput, TestInputValue, TestOutput,
@@ -103,6 +104,9 @@ pub async fn build_unit_tests(
     let mut tests = Vec::new();
     let mut build_errors = Vec::new();
     let metadata = UnitTestBuildMetadata::initialize(&mut config_builder)?;
+    if coverage::enabled() {
+        coverage::register_outputs(metadata.sink_ids.keys());
+    }
 
     for mut test_definition in test_definitions {
         let test_name = test_definition.name.clone();
@@ -427,7 +431,7 @@ async fn build_unit_test(
     }
     let config = config_builder.build()?;
     let diff = config::ConfigDiff::initial(&config);
-    let pieces = builder::build_pieces(&config, &diff, HashMap::new()).await?;
+    let pieces = builder::build_pieces(&config, &diff, HashMap::new(), Default::default()).await?;
 
     Ok(UnitTest {
         name: test.name,
@@ -535,8 +539,8 @@ fn build_and_validate_inputs(
 
 fn build_outputs(
     test_outputs: &[TestOutput],
-) -> Result<IndexMap<Vec<OutputId>, Vec<Vec<Condition>>>, Vec<String>> {
-    let mut outputs: IndexMap<Vec<OutputId>, Vec<Vec<Condition>>> = IndexMap::new();
+) -> Result<IndexMap<Vec<OutputId>, Vec<UnitTestOutputCheck>>, Vec<String>> {
+    let mut outputs: IndexMap<Vec<OutputId>, Vec<UnitTestOutputCheck>> = IndexMap::new();
     let mut errors = Vec::new();
 
     for output in test_outputs {
@@ -557,10 +561,28 @@ fn build_outputs(
             }
         }
 
+        let mut events = Vec::new();
+        match (&output.metrics, &output.events_file) {
+            (Some(_), Some(_)) => errors.push(
+                "only one of `metrics` and `events_file` can be set for a test output.".to_string(),
+            ),
+            (Some(metrics), None) => events.extend(metrics.iter().cloned().map(Event::Metric)),
+            (None, Some(path)) => match load_expected_events(path) {
+                Ok(expected) => events = expected,
+                Err(error) => errors.push(error),
+            },
+            (None, None) => {}
+        }
+
+        let check = UnitTestOutputCheck {
+            conditions,
+            events,
+            ordered: output.ordered,
+        };
         outputs
             .entry(output.extract_from.clone().to_vec())
-            .and_modify(|existing_conditions| existing_conditions.push(conditions.clone()))
-            .or_insert(vec![conditions.clone()]);
+            .or_default()
+            .push(check);
     }
 
     if errors.is_empty() {
@@ -570,6 +592,20 @@ fn build_outputs(
     }
 }
 
+fn load_expected_events(path: &Path) -> Result<Vec<Event>, String> {
+    let data = std::fs::read(path)
+        .map_err(|error| format!("failed to read events file {:?}: {}", path, error))?;
+    let events: Vec<Event> = serde_json::from_slice(&data)
+        .map_err(|error| format!("failed to parse events file {:?}: {}", path, error))?;
+    if events.iter().any(|event| matches!(event, Event::Trace(_))) {
+        return Err(format!(
+            "events file {:?} can only contain log and metric events.",
+            path
+        ));
+    }
+    Ok(events)
+}
+
 fn build_input_event(input: &TestInput) -> Result<Event, String> {
     match input.type_str.as_ref() {
         "raw" => match input.value.as_ref() {
@@ -1235,3 +1235,219 @@ async fn test_glob_input() {
     let mut tests = build_unit_tests(config).await.unwrap();
     assert!(tests.remove(0).run().await.errors.is_empty());
 }
+
+#[tokio::test]
+async fn test_ordered_conditions() {
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+          [transforms.foo]
+            inputs = ["ignored"]
+            type = "remap"
+            source = '''
+            .new_field = "new field added"
+            '''
+
+          [[tests]]
+            name = "ordered test"
+
+            [[tests.inputs]]
+              insert_at = "foo"
+              value = "first"
+
+            [[tests.inputs]]
+              insert_at = "foo"
+              value = "second"
+
+            [[tests.outputs]]
+              extract_from = "foo"
+              ordered = true
+              [[tests.outputs.conditions]]
+                type = "vrl"
+                source = 'assert_eq!(.message, "first")'
+              [[tests.outputs.conditions]]
+                type = "vrl"
+                source = 'assert_eq!(.message, "second")'
+
+          [[tests]]
+            name = "out of order test"
+
+            [[tests.inputs]]
+              insert_at = "foo"
+              value = "first"
+
+            [[tests.inputs]]
+              insert_at = "foo"
+              value = "second"
+
+            [[tests.outputs]]
+              extract_from = "foo"
+              ordered = true
+              [[tests.outputs.conditions]]
+                type = "vrl"
+                source = 'assert_eq!(.message, "second")'
+              [[tests.outputs.conditions]]
+                type = "vrl"
+                source = 'assert_eq!(.message, "first")'
+      "#})
+    .unwrap();
+
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert!(tests.remove(0).run().await.errors.is_empty());
+    assert!(!tests.remove(0).run().await.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_expected_metrics() {
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+          [transforms.foo]
+            inputs = ["ignored"]
+            type = "remap"
+            source = '''
+            .tags.new_tag = "new value added"
+            '''
+
+          [[tests]]
+            name = "expected metrics test"
+
+            [tests.input]
+              insert_at = "foo"
+              type = "metric"
+              [tests.input.metric]
+                kind = "incremental"
+                name = "foometric"
+                [tests.input.metric.counter]
+                  value = 100.0
+
+            [[tests.outputs]]
+              extract_from = "foo"
+              [[tests.outputs.metrics]]
+                kind = "incremental"
+                name = "foometric"
+                [tests.outputs.metrics.tags]
+                  new_tag = "new value added"
+                [tests.outputs.metrics.counter]
+                  value = 100.0
+
+          [[tests]]
+            name = "unexpected metrics test"
+
+            [tests.input]
+              insert_at = "foo"
+              type = "metric"
+              [tests.input.metric]
+                kind = "incremental"
+                name = "foometric"
+                [tests.input.metric.counter]
+                  value = 100.0
+
+            [[tests.outputs]]
+              extract_from = "foo"
+              [[tests.outputs.metrics]]
+                kind = "incremental"
+                name = "foometric"
+                [tests.outputs.metrics.counter]
+                  value = 100.0
+      "#})
+    .unwrap();
+
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert!(tests.remove(0).run().await.errors.is_empty());
+    assert!(!tests.remove(0).run().await.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_expected_events_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let events_file = dir.path().join("expected.json");
+    std::fs::write(
+        &events_file,
+        r#"[
+            {"log": {"message": "second", "new_field": "new field added"}},
+            {"log": {"message": "first", "new_field": "new field added"}}
+        ]"#,
+    )
+    .unwrap();
+
+    let config: ConfigBuilder = toml::from_str(&format!(
+        indoc! {r#"
+          [transforms.foo]
+            inputs = ["ignored"]
+            type = "remap"
+            source = '''
+            .new_field = "new field added"
+            '''
+
+          [[tests]]
+            name = "unordered events file test"
+
+            [[tests.inputs]]
+              insert_at = "foo"
+              value = "first"
+
+            [[tests.inputs]]
+              insert_at = "foo"
+              value = "second"
+
+            [[tests.outputs]]
+              extract_from = "foo"
+              events_file = "{path}"
+
+          [[tests]]
+            name = "ordered events file test"
+
+            [[tests.inputs]]
+              insert_at = "foo"
+              value = "first"
+
+            [[tests.inputs]]
+              insert_at = "foo"
+              value = "second"
+
+            [[tests.outputs]]
+              extract_from = "foo"
+              events_file = "{path}"
+              ordered = true
+        "#},
+        path = events_file.display()
+    ))
+    .unwrap();
+
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert!(tests.remove(0).run().await.errors.is_empty());
+    assert!(!tests.remove(0).run().await.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_extract_from_all_route_outputs() {
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+          [transforms.foo]
+            inputs = ["ignored"]
+            type = "route"
+              [transforms.foo.route]
+              first = '.message == "test swimlane 1"'
+              second = '.message == "test swimlane 2"'
+
+          [[tests]]
+            name = "all route outputs test"
+
+            [[tests.inputs]]
+              insert_at = "foo"
+              value = "test swimlane 1"
+
+            [[tests.inputs]]
+              insert_at = "foo"
+              value = "test swimlane 2"
+
+            [[tests.outputs]]
+              extract_from = "foo"
+              [[tests.outputs.conditions]]
+                type = "vrl"
+                source = 'assert_eq!(.message, "test swimlane 1")'
+              [[tests.outputs.conditions]]
+                type = "vrl"
+                source = 'assert_eq!(.message, "test swimlane 2")'
+      "#})
+    .unwrap();
+
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert!(tests.remove(0).run().await.errors.is_empty());
+}
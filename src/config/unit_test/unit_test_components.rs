@@ -5,7 +5,7 @@ use futures_util::{future, stream::BoxStream, FutureExt, StreamExt};
 use tokio::sync::{oneshot, Mutex};
 use vector_config::configurable_component;
 use vector_core::{
-    config::{DataType, Input, LogNamespace, Output},
+    config::{log_schema, DataType, Input, LogNamespace, Output},
     event::Event,
     sink::{StreamSink, VectorSink},
 };
@@ -104,10 +104,23 @@ impl SourceConfig for UnitTestStreamSourceConfig {
     }
 }
 
+/// The expectations for the events received from a set of transform outputs.
+#[derive(Clone, Default)]
+pub struct UnitTestOutputCheck {
+    /// Conditions the events received must satisfy.
+    pub conditions: Vec<Condition>,
+
+    /// Events that must be received, compared exactly.
+    pub events: Vec<Event>,
+
+    /// Whether the conditions and events are checked against the events received in order.
+    pub ordered: bool,
+}
+
 #[derive(Clone)]
 pub enum UnitTestSinkCheck {
-    /// Check all events that are received against the list of conditions.
-    Checks(Vec<Vec<Condition>>),
+    /// Check all events that are received against the list of output checks.
+    Checks(Vec<UnitTestOutputCheck>),
 
     /// Check that no events were received.
     NoOutputs,
@@ -204,23 +217,8 @@ impl StreamSink<Event> for UnitTestSink {
                         .push(format!("checks for transforms {:?} failed: no events received. Topology may be disconnected or transform is missing inputs.", self.transform_ids));
                 } else {
                     for (i, check) in checks.iter().enumerate() {
-                        let mut check_errors = Vec::new();
-                        for (j, condition) in check.iter().enumerate() {
-                            let mut condition_errors = Vec::new();
-                            for event in output_events.iter() {
-                                match condition.check_with_context(event.clone()).0 {
-                                    Ok(_) => {
-                                        condition_errors.clear();
-                                        break;
-                                    }
-                                    Err(error) => {
-                                        condition_errors
-                                            .push(format!("  condition[{}]: {}", j, error));
-                                    }
-                                }
-                            }
-                            check_errors.extend(condition_errors);
-                        }
+                        let mut check_errors = check.conditions_errors(&output_events);
+                        check_errors.extend(check.events_errors(&output_events));
                         // If there are errors, add a preamble to the output
                         if !check_errors.is_empty() {
                             check_errors.insert(
@@ -265,6 +263,112 @@ impl StreamSink<Event> for UnitTestSink {
     }
 }
 
+impl UnitTestOutputCheck {
+    fn conditions_errors(&self, events: &[Event]) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.ordered {
+            if !self.conditions.is_empty() && self.conditions.len() != events.len() {
+                errors.push(format!(
+                    "  expected {} events, received {}",
+                    self.conditions.len(),
+                    events.len()
+                ));
+            }
+            for (j, (condition, event)) in self.conditions.iter().zip(events).enumerate() {
+                if let Err(error) = condition.check_with_context(event.clone()).0 {
+                    errors.push(format!("  condition[{}]: {}", j, error));
+                }
+            }
+        } else {
+            for (j, condition) in self.conditions.iter().enumerate() {
+                let mut condition_errors = Vec::new();
+                for event in events.iter() {
+                    match condition.check_with_context(event.clone()).0 {
+                        Ok(_) => {
+                            condition_errors.clear();
+                            break;
+                        }
+                        Err(error) => {
+                            condition_errors.push(format!("  condition[{}]: {}", j, error));
+                        }
+                    }
+                }
+                errors.extend(condition_errors);
+            }
+        }
+        errors
+    }
+
+    fn events_errors(&self, events: &[Event]) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.events.is_empty() {
+            return errors;
+        }
+        if self.events.len() != events.len() {
+            errors.push(format!(
+                "  expected {} events, received {}",
+                self.events.len(),
+                events.len()
+            ));
+        }
+
+        if self.ordered {
+            for (j, (expected, event)) in self.events.iter().zip(events).enumerate() {
+                if !event_matches(expected, event) {
+                    errors.push(format!(
+                        "  event[{}]: expected {}",
+                        j,
+                        events_to_string(std::slice::from_ref(expected))
+                    ));
+                }
+            }
+        } else {
+            // Each event received can only account for a single expected event.
+            let mut unmatched = events.iter().collect::<Vec<_>>();
+            for (j, expected) in self.events.iter().enumerate() {
+                match unmatched
+                    .iter()
+                    .position(|event| event_matches(expected, event))
+                {
+                    Some(index) => {
+                        unmatched.swap_remove(index);
+                    }
+                    None => errors.push(format!(
+                        "  event[{}]: no matching event received for {}",
+                        j,
+                        events_to_string(std::slice::from_ref(expected))
+                    )),
+                }
+            }
+        }
+        errors
+    }
+}
+
+fn event_matches(expected: &Event, event: &Event) -> bool {
+    match (expected, event) {
+        (Event::Log(expected), Event::Log(log)) => {
+            // Logs are compared as JSON, so that values that can't be represented in the events
+            // file, such as timestamps, compare equal to their JSON encoding.
+            let mut log = serde_json::to_value(log).unwrap_or_default();
+            let timestamp_key = log_schema().timestamp_key();
+            if !expected.contains(timestamp_key) {
+                if let Some(log) = log.as_object_mut() {
+                    log.remove(timestamp_key);
+                }
+            }
+            serde_json::to_value(expected).map_or(false, |expected| expected == log)
+        }
+        (Event::Metric(expected), Event::Metric(metric)) => {
+            expected.series() == metric.series()
+                && expected.kind() == metric.kind()
+                && expected.value() == metric.value()
+                && (expected.timestamp().is_none() || expected.timestamp() == metric.timestamp())
+        }
+        _ => false,
+    }
+}
+
 /// Configuration for the `unit_test_stream` sink.
 #[configurable_component(sink("unit_test_stream"))]
 #[derive(Clone, Default)]
@@ -6,7 +6,12 @@ use indexmap::IndexMap;
 use std::{collections::HashMap, path::PathBuf};
 use vector_core::internal_event::DEFAULT_OUTPUT;
 
-use super::{builder::ConfigBuilder, ComponentKey, Config, OutputId, Resource, SourceConfig};
+use super::{
+    builder::ConfigBuilder, ComponentKey, Config, OutputId, Resource, SinkOuter, SourceConfig,
+};
+use crate::sinks::util::{
+    event_age::StaleEventsFallback, service::circuit_breaker::CircuitBreakerFallback,
+};
 
 /// Check that provide + topology config aren't present in the same builder, which is an error.
 pub fn check_provider(config: &ConfigBuilder) -> Result<(), Vec<String>> {
@@ -145,6 +150,166 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+/// Check that the rate limit groups joined by sinks are defined, and that their limits are usable.
+pub fn check_rate_limit_groups(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    for (name, group) in &config.global.rate_limit_groups {
+        if group.duration_secs == 0 {
+            errors.push(format!(
+                "Rate limit group {:?} must have a `duration_secs` greater than zero.",
+                name
+            ));
+        }
+        if group.request_limit == Some(0) || group.byte_limit == Some(0) {
+            errors.push(format!(
+                "Rate limit group {:?} must have limits greater than zero.",
+                name
+            ));
+        }
+    }
+
+    for (key, sink) in &config.sinks {
+        if let Some(group) = sink.rate_limit_group() {
+            if !config.global.rate_limit_groups.contains_key(group) {
+                errors.push(format!(
+                    "Sink {:?} references undefined rate limit group {:?}.",
+                    key.id(),
+                    group
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check that the circuit breakers of sinks are usable, and that the dead letter queue they fall back
+/// to, if any, is a different sink.
+pub fn check_circuit_breakers(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    for (key, sink) in &config.sinks {
+        let breaker = match sink.circuit_breaker() {
+            Some(breaker) => breaker,
+            None => continue,
+        };
+
+        if breaker.failure_threshold == 0 || breaker.reset_timeout_secs == 0 {
+            errors.push(format!(
+                "Sink {:?} must have a circuit breaker `failure_threshold` and `reset_timeout_secs` greater than zero.",
+                key.id()
+            ));
+        }
+
+        if breaker.fallback == CircuitBreakerFallback::DeadLetterQueue {
+            match breaker.dead_letter_queue(config.global.dead_letter_queue.as_ref()) {
+                None => errors.push(format!(
+                    "Sink {:?} has circuit breaker fallback `dead_letter_queue`, but no dead letter queue is configured.",
+                    key.id()
+                )),
+                Some(queue) if queue == key => errors.push(format!(
+                    "Sink {:?} cannot use itself as the dead letter queue of its circuit breaker.",
+                    key.id()
+                )),
+                Some(queue) => match config.sinks.get(queue) {
+                    None => errors.push(format!(
+                        "Sink {:?} has circuit breaker dead letter queue {:?}, which is not a sink.",
+                        key.id(),
+                        queue.id()
+                    )),
+                    // The dead letter queue holds on to the input of its own dead letter queue,
+                    // which would keep either sink from ever shutting down.
+                    Some(queue_sink) if falls_back_to_dead_letter_queue(queue_sink) => {
+                        errors.push(format!(
+                            "Sink {:?} has circuit breaker dead letter queue {:?}, which cannot itself fall back to a dead letter queue.",
+                            key.id(),
+                            queue.id()
+                        ))
+                    }
+                    Some(_) => {}
+                },
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check that the maximum event ages of sinks are usable, and that the dead letter queue they fall
+/// back to, if any, is a different sink.
+pub fn check_max_event_ages(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    for (key, sink) in &config.sinks {
+        let max_event_age = match &sink.max_event_age {
+            Some(max_event_age) => max_event_age,
+            None => continue,
+        };
+
+        if max_event_age.max_age_secs == 0 {
+            errors.push(format!(
+                "Sink {:?} must have a `max_event_age.max_age_secs` greater than zero.",
+                key.id()
+            ));
+        }
+
+        if max_event_age.fallback == StaleEventsFallback::DeadLetterQueue {
+            match max_event_age.dead_letter_queue(config.global.dead_letter_queue.as_ref()) {
+                None => errors.push(format!(
+                    "Sink {:?} has stale events fallback `dead_letter_queue`, but no dead letter queue is configured.",
+                    key.id()
+                )),
+                Some(queue) if queue == key => errors.push(format!(
+                    "Sink {:?} cannot use itself as the dead letter queue of its stale events.",
+                    key.id()
+                )),
+                Some(queue) => match config.sinks.get(queue) {
+                    None => errors.push(format!(
+                        "Sink {:?} has stale events dead letter queue {:?}, which is not a sink.",
+                        key.id(),
+                        queue.id()
+                    )),
+                    // The dead letter queue holds on to the input of its own dead letter queue,
+                    // which would keep either sink from ever shutting down.
+                    Some(queue_sink) if falls_back_to_dead_letter_queue(queue_sink) => {
+                        errors.push(format!(
+                            "Sink {:?} has stale events dead letter queue {:?}, which cannot itself fall back to a dead letter queue.",
+                            key.id(),
+                            queue.id()
+                        ))
+                    }
+                    Some(_) => {}
+                },
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Whether the sink sends events to a dead letter queue sink, either while its circuit is open or
+/// when they are stale.
+fn falls_back_to_dead_letter_queue(sink: &SinkOuter<String>) -> bool {
+    sink.circuit_breaker().map_or(false, |breaker| {
+        breaker.fallback == CircuitBreakerFallback::DeadLetterQueue
+    }) || sink.max_event_age.as_ref().map_or(false, |max_event_age| {
+        max_event_age.fallback == StaleEventsFallback::DeadLetterQueue
+    })
+}
+
 /// To avoid collisions between `output` metric tags, check that a component
 /// does not have a named output with the name [`DEFAULT_OUTPUT`]
 pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
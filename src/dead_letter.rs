@@ -0,0 +1,97 @@
+//! Dead letter queue support.
+//!
+//! Components that are unable to process an event can, instead of dropping it, forward it to a
+//! specially-named output, `dropped`. Events sent to that output are annotated with a standard
+//! envelope describing which component dropped the event and why, regardless of the component
+//! that dropped them.
+//!
+//! When a dead letter queue is configured, either globally or for a specific component, the
+//! `dropped` output of every applicable component is automatically wired up as an input of the
+//! configured dead letter queue sink.
+
+use lookup::{metadata_path, path, PathPrefix};
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{log_schema, ComponentKey},
+    event::Event,
+};
+
+/// The name of the output that dropped events are sent to.
+pub const DEAD_LETTER_OUTPUT: &str = "dropped";
+
+/// Describes why an event was sent to the dead letter queue.
+#[derive(Clone, Debug)]
+pub struct DeadLetter<'a> {
+    /// A short, machine-readable reason for the event being dropped.
+    pub reason: &'a str,
+
+    /// A human-readable message describing the error that caused the event to be dropped.
+    pub message: String,
+
+    /// The ID of the component that dropped the event.
+    pub component_id: Option<&'a ComponentKey>,
+
+    /// The type of the component that dropped the event, such as `remap`.
+    pub component_type: &'static str,
+
+    /// The kind of the component that dropped the event, such as `transform`.
+    pub component_kind: &'static str,
+}
+
+impl<'a> DeadLetter<'a> {
+    fn to_value(&self) -> serde_json::Value {
+        serde_json::json!({
+            "reason": self.reason,
+            "message": self.message,
+            "component_id": self.component_id,
+            "component_type": self.component_type,
+            "component_kind": self.component_kind,
+        })
+    }
+
+    /// Annotates the given event with the dead letter envelope.
+    ///
+    /// For logs and traces, the envelope is stored as `dropped` under the metadata key (or the
+    /// `vector` metadata namespace, when using the Vector log namespace). For metrics, the envelope
+    /// is stored as a set of `dropped.*` tags.
+    pub fn annotate(&self, event: &mut Event) {
+        match event {
+            Event::Log(ref mut log) => match log.namespace() {
+                LogNamespace::Legacy => {
+                    log.insert(
+                        (
+                            PathPrefix::Event,
+                            log_schema().metadata_key().concat(path!("dropped")),
+                        ),
+                        self.to_value(),
+                    );
+                }
+                LogNamespace::Vector => {
+                    log.insert(metadata_path!("vector", "dropped"), self.to_value());
+                }
+            },
+            Event::Metric(ref mut metric) => {
+                let m = log_schema().metadata_key();
+                metric.replace_tag(format!("{}.dropped.reason", m), self.reason.into());
+                metric.replace_tag(
+                    format!("{}.dropped.component_id", m),
+                    self.component_id
+                        .map(ToString::to_string)
+                        .unwrap_or_else(String::new),
+                );
+                metric.replace_tag(
+                    format!("{}.dropped.component_type", m),
+                    self.component_type.into(),
+                );
+                metric.replace_tag(
+                    format!("{}.dropped.component_kind", m),
+                    self.component_kind.into(),
+                );
+            }
+            Event::Trace(ref mut trace) => {
+                trace.insert(log_schema().metadata_key(), self.to_value());
+            }
+        }
+    }
+}
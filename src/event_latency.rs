@@ -0,0 +1,123 @@
+//! Topology-level end-to-end latency measurement.
+//!
+//! When the global `end_to_end_latency` option is enabled, the events emitted by sources are
+//! stamped with the source and the time they were emitted, carried in their metadata. When a sink
+//! receives events, it adds a finalizer to them, and once the sink acknowledges the events, the
+//! time elapsed since they were emitted is recorded for each pair of source and sink.
+//!
+//! Events that are rejected by the sink, or that go through a disk buffer, which doesn't persist
+//! the stamp, aren't measured.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+use tracing::Instrument;
+use vector_common::finalization::{BatchNotifier, BatchStatus, EventFinalizer};
+use vector_core::event::EventArray;
+
+use crate::{config::ComponentKey, internal_events::EndToEndLatency};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables end-to-end latency measurement.
+pub fn configure(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Stamps the events emitted by a source with the source and the current time.
+pub fn start(events: &mut EventArray, source: &Arc<ComponentKey>) {
+    if !enabled() {
+        return;
+    }
+
+    let now = Instant::now();
+    for mut event in events.iter_events_mut() {
+        event.metadata_mut().set_ingest(Arc::clone(source), now);
+    }
+}
+
+/// Tracks the events received by a sink, recording their latency once the sink acknowledges them.
+///
+/// This must be called within the span of the sink, so the latency is attributed to it.
+pub fn track(events: &mut EventArray) {
+    if !enabled() {
+        return;
+    }
+
+    // Events from the same source share a notifier, so the latencies of a whole array are
+    // recorded by a single task for each source.
+    let mut batches = HashMap::<Arc<ComponentKey>, (BatchNotifier, Vec<Instant>)>::new();
+    let mut receivers = Vec::new();
+    for mut event in events.iter_events_mut() {
+        let (source, at) = match event.metadata_mut().ingest() {
+            Some((source, at)) => (Arc::clone(source), at),
+            None => continue,
+        };
+        let (batch, ingested) = batches.entry(Arc::clone(&source)).or_insert_with(|| {
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            receivers.push((source, receiver));
+            (batch, Vec::new())
+        });
+        ingested.push(at);
+        event
+            .metadata_mut()
+            .add_finalizer(EventFinalizer::new(batch.clone()));
+    }
+
+    for (source, receiver) in receivers {
+        // The notifier is dropped here, so only the finalizers of the events hold it.
+        let ingested = match batches.remove(&source) {
+            Some((_, ingested)) => ingested,
+            None => continue,
+        };
+        tokio::spawn(
+            async move {
+                if receiver.await == BatchStatus::Delivered {
+                    let now = Instant::now();
+                    for at in ingested {
+                        emit!(EndToEndLatency {
+                            source: &source,
+                            latency: now.saturating_duration_since(at),
+                        });
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::{EventContainer, LogEvent};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn tracks_stamped_events() {
+        configure(true);
+
+        let source = Arc::new(ComponentKey::from("in"));
+        let mut events = EventArray::from(vec![LogEvent::from("first"), LogEvent::from("second")]);
+        start(&mut events, &source);
+        track(&mut events);
+
+        for event in events.into_events() {
+            let (ingest_source, _) = event.metadata().ingest().unwrap();
+            assert_eq!(ingest_source.id(), "in");
+            assert_eq!(event.metadata().finalizers().len(), 1);
+        }
+
+        configure(false);
+    }
+}
@@ -0,0 +1,257 @@
+//! Topology-level event tracing.
+//!
+//! When the global `event_tracing` option is enabled, one out of every `sample_rate` events emitted
+//! by sources is assigned a trace ID, carried in its metadata. Every component that then receives
+//! the event records a hop in the trace, along with a fingerprint of the event's contents at that
+//! point, so a trace shows both the last component an event reached, and which components modified
+//! it along the way.
+//!
+//! Traces are kept in memory, bounded by `max_traces`, and can be queried through the API.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::Hasher,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, RwLock,
+    },
+};
+
+use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use vector_core::{
+    config::EventTracingConfig,
+    event::{EventArray, EventMutRef, EventRef},
+};
+
+use crate::config::ComponentKey;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static TRACER: Lazy<RwLock<Option<Arc<EventTracer>>>> = Lazy::new(|| RwLock::new(None));
+
+/// A component that received a traced event.
+#[derive(Clone, Debug)]
+pub struct TraceHop {
+    pub component_id: ComponentKey,
+    pub component_kind: &'static str,
+    pub timestamp: DateTime<Utc>,
+    /// Whether the event differed from when it was last seen, in the previous hop.
+    pub modified: bool,
+    fingerprint: u64,
+}
+
+/// The path of a single sampled event through the topology.
+#[derive(Clone, Debug)]
+pub struct EventTrace {
+    pub id: u64,
+    pub hops: Vec<TraceHop>,
+}
+
+struct EventTracer {
+    config: EventTracingConfig,
+    seen: AtomicU64,
+    next_id: AtomicU64,
+    traces: Mutex<IndexMap<u64, EventTrace>>,
+}
+
+/// Applies the event tracing configuration.
+///
+/// Recorded traces are kept as long as the configuration doesn't change.
+pub fn configure(config: &EventTracingConfig) {
+    let mut tracer = TRACER.write().expect("poisoned lock");
+    if tracer.as_ref().map(|tracer| &tracer.config) == Some(config) {
+        return;
+    }
+
+    *tracer = config.enabled.then(|| {
+        Arc::new(EventTracer {
+            config: config.clone(),
+            seen: AtomicU64::new(0),
+            next_id: AtomicU64::new(1),
+            traces: Mutex::new(IndexMap::new()),
+        })
+    });
+    ENABLED.store(tracer.is_some(), Ordering::Relaxed);
+}
+
+fn tracer() -> Option<Arc<EventTracer>> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return None;
+    }
+    TRACER.read().expect("poisoned lock").clone()
+}
+
+/// Samples events emitted by a source for tracing, recording the source as their first hop.
+pub fn start(events: &mut EventArray, source: &ComponentKey) {
+    let tracer = match tracer() {
+        Some(tracer) => tracer,
+        None => return,
+    };
+
+    for mut event in events.iter_events_mut() {
+        let seen = tracer.seen.fetch_add(1, Ordering::Relaxed);
+        if seen % tracer.config.sample_rate.max(1) != 0 {
+            continue;
+        }
+
+        let id = tracer.next_id.fetch_add(1, Ordering::Relaxed);
+        event.metadata_mut().set_trace_id(id);
+
+        let fingerprint = fingerprint(match event {
+            EventMutRef::Log(log) => EventRef::Log(log),
+            EventMutRef::Metric(metric) => EventRef::Metric(metric),
+            EventMutRef::Trace(trace) => EventRef::Trace(trace),
+        });
+        tracer.record(id, source, "source", fingerprint);
+    }
+}
+
+/// Records a hop for each traced event received by a component.
+pub fn record(events: &EventArray, component: &ComponentKey, component_kind: &'static str) {
+    let tracer = match tracer() {
+        Some(tracer) => tracer,
+        None => return,
+    };
+
+    for event in events.iter_events() {
+        if let Some(id) = trace_id(event) {
+            tracer.record(id, component, component_kind, fingerprint(event));
+        }
+    }
+}
+
+/// Returns the most recent traces, newest first, optionally only those that reached the given
+/// component.
+pub fn traces(limit: usize, component: Option<&ComponentKey>) -> Vec<EventTrace> {
+    let tracer = match tracer() {
+        Some(tracer) => tracer,
+        None => return Vec::new(),
+    };
+
+    let traces = tracer.traces.lock().expect("poisoned lock");
+    traces
+        .values()
+        .rev()
+        .filter(|trace| {
+            component.map_or(true, |component| {
+                trace.hops.iter().any(|hop| &hop.component_id == component)
+            })
+        })
+        .take(limit)
+        .cloned()
+        .collect()
+}
+
+impl EventTracer {
+    fn record(
+        &self,
+        id: u64,
+        component_id: &ComponentKey,
+        component_kind: &'static str,
+        fingerprint: u64,
+    ) {
+        let mut traces = self.traces.lock().expect("poisoned lock");
+
+        if !traces.contains_key(&id) {
+            // A trace that isn't known anymore was already evicted, so don't start it over with a
+            // partial list of hops.
+            if component_kind != "source" {
+                return;
+            }
+            if traces.len() >= self.config.max_traces.max(1) {
+                traces.shift_remove_index(0);
+            }
+            traces.insert(
+                id,
+                EventTrace {
+                    id,
+                    hops: Vec::new(),
+                },
+            );
+        }
+        let trace = traces.get_mut(&id).expect("trace was inserted");
+
+        let modified = trace
+            .hops
+            .last()
+            .map_or(false, |hop| hop.fingerprint != fingerprint);
+        trace.hops.push(TraceHop {
+            component_id: component_id.clone(),
+            component_kind,
+            timestamp: Utc::now(),
+            modified,
+            fingerprint,
+        });
+    }
+}
+
+fn trace_id(event: EventRef<'_>) -> Option<u64> {
+    match event {
+        EventRef::Log(log) => log.metadata().trace_id(),
+        EventRef::Metric(metric) => metric.metadata().trace_id(),
+        EventRef::Trace(trace) => trace.metadata().trace_id(),
+    }
+}
+
+fn fingerprint(event: EventRef<'_>) -> u64 {
+    let encoded = match event {
+        EventRef::Log(log) => serde_json::to_vec(log),
+        EventRef::Metric(metric) => serde_json::to_vec(metric),
+        EventRef::Trace(trace) => serde_json::to_vec(trace),
+    };
+
+    let mut hasher = DefaultHasher::new();
+    hasher.write(&encoded.unwrap_or_default());
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::EventContainer;
+
+    use super::*;
+    use crate::event::{Event, LogEvent};
+
+    #[test]
+    fn records_path_of_sampled_events() {
+        configure(&EventTracingConfig {
+            enabled: true,
+            sample_rate: 2,
+            max_traces: 10,
+        });
+
+        let source = ComponentKey::from("in");
+        let transform = ComponentKey::from("remap");
+        let sink = ComponentKey::from("out");
+
+        let mut events = EventArray::from(vec![
+            LogEvent::from("first"),
+            LogEvent::from("second"),
+            LogEvent::from("third"),
+        ]);
+        start(&mut events, &source);
+        record(&events, &transform, "transform");
+
+        let mut events = events.into_events().collect::<Vec<_>>();
+        for event in &mut events {
+            event.as_mut_log().insert("modified", true);
+        }
+        let events = EventArray::from(events.into_iter().map(Event::into_log).collect::<Vec<_>>());
+        record(&events, &sink, "sink");
+
+        let traces = traces(10, Some(&sink));
+        assert_eq!(traces.len(), 2);
+        for trace in traces {
+            let hops = trace
+                .hops
+                .iter()
+                .map(|hop| (hop.component_id.id(), hop.modified))
+                .collect::<Vec<_>>();
+            assert_eq!(hops, vec![("in", false), ("remap", false), ("out", true)]);
+        }
+
+        configure(&EventTracingConfig::default());
+        assert!(super::traces(10, None).is_empty());
+    }
+}
@@ -17,6 +17,8 @@ use vector_core::{buffers::BufferConfig, config::GlobalOptions, default_data_dir
 
 use crate::config::SinkHealthcheckOptions;
 
+mod wizard;
+
 #[derive(Parser, Debug)]
 #[command(rename_all = "kebab-case")]
 pub struct Opts {
@@ -24,6 +26,11 @@ pub struct Opts {
     #[arg(short, long)]
     fragment: bool,
 
+    /// Prompt for the components of the pipeline and their required options
+    /// instead of taking them from an expression.
+    #[arg(short, long)]
+    interactive: bool,
+
     /// Generate expression, e.g. 'stdin/remap,filter/console'
     ///
     /// Three comma-separated lists of sources, transforms and sinks, divided by
@@ -53,7 +60,8 @@ pub struct Opts {
     /// from the last transform or, if none are specified, from all sources. It
     /// is then up to you to restructure the `inputs` of each component to build
     /// the topology you need.
-    expression: String,
+    #[arg(required_unless_present = "interactive")]
+    expression: Option<String>,
 
     /// Generate config as a file
     #[arg(long)]
@@ -104,6 +112,24 @@ pub(crate) fn generate_example(
     expression: &str,
     file: &Option<PathBuf>,
     transform_inputs_strategy: TransformInputsStrategy,
+) -> Result<String, Vec<String>> {
+    generate_example_with_options(
+        include_globals,
+        expression,
+        &IndexMap::new(),
+        file,
+        transform_inputs_strategy,
+    )
+}
+
+/// Generates an example configuration like [`generate_example`], with the given options, keyed by
+/// the name of their component, overriding the options of the examples.
+pub(crate) fn generate_example_with_options(
+    include_globals: bool,
+    expression: &str,
+    options: &IndexMap<String, Map<String, Value>>,
+    file: &Option<PathBuf>,
+    transform_inputs_strategy: TransformInputsStrategy,
 ) -> Result<String, Vec<String>> {
     let components: Vec<Vec<_>> = expression
         .split(|c| c == '|' || c == '/')
@@ -162,6 +188,9 @@ pub(crate) fn generate_example(
                 .as_table_mut()
                 .expect("examples are always tables")
                 .insert("type".into(), source_type.to_owned().into());
+            if let Some(options) = options.get(&name) {
+                merge_options(&mut example, options);
+            }
 
             sources.insert(name, example);
         }
@@ -225,6 +254,9 @@ pub(crate) fn generate_example(
                 .as_table_mut()
                 .expect("examples are always tables")
                 .insert("type".into(), transform_type.to_owned().into());
+            if let Some(options) = options.get(&name) {
+                merge_options(&mut example, options);
+            }
 
             transforms.insert(
                 name,
@@ -274,6 +306,9 @@ pub(crate) fn generate_example(
                 .as_table_mut()
                 .expect("examples are always tables")
                 .insert("type".into(), sink_type.to_owned().into());
+            if let Some(options) = options.get(&name) {
+                merge_options(&mut example, options);
+            }
 
             sinks.insert(
                 name,
@@ -370,13 +405,40 @@ pub(crate) fn generate_example(
     }
 }
 
+/// Merges the given options into the options of a component, recursing into tables.
+fn merge_options(example: &mut Value, options: &Map<String, Value>) {
+    let example = example.as_table_mut().expect("examples are always tables");
+    for (key, value) in options {
+        match (example.get_mut(key), value) {
+            (Some(existing @ Value::Table(_)), Value::Table(value)) => {
+                merge_options(existing, value)
+            }
+            _ => {
+                example.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
-    match generate_example(
-        !opts.fragment,
-        &opts.expression,
-        &opts.file,
-        TransformInputsStrategy::Auto,
-    ) {
+    let generated = match (opts.interactive, &opts.expression) {
+        (true, _) => wizard::run(
+            !opts.fragment,
+            &mut std::io::stdin().lock(),
+            &mut std::io::stderr(),
+            &opts.file,
+        ),
+        (false, Some(expression)) => generate_example(
+            !opts.fragment,
+            expression,
+            &opts.file,
+            TransformInputsStrategy::Auto,
+        ),
+        (false, None) => Err(vec![
+            "an expression is required unless running interactively".to_owned(),
+        ]),
+    };
+    match generated {
         Ok(s) => {
             #[allow(clippy::print_stdout)]
             {
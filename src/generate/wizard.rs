@@ -0,0 +1,597 @@
+//! Interactive generation of a pipeline.
+//!
+//! Prompts for the sources, transforms and sinks of the pipeline, then for the options required by
+//! each of them. The values entered are validated against the schema of the configuration of the
+//! component, and the options that aren't required keep the values of the component's example.
+
+use std::{
+    collections::HashSet,
+    io::{BufRead, Write},
+    path::PathBuf,
+};
+
+use indexmap::IndexMap;
+use serde_json::Value as JsonValue;
+use toml::{map::Map, Value};
+use vector_config::component::{SinkDescription, SourceDescription, TransformDescription};
+
+use super::{generate_example_with_options, write_config, TransformInputsStrategy};
+use crate::config::{self, Format};
+
+/// How deep to follow references and nested objects while looking for required options.
+const MAX_SCHEMA_DEPTH: usize = 16;
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Source,
+    Transform,
+    Sink,
+}
+
+impl Kind {
+    const fn noun(self) -> &'static str {
+        match self {
+            Self::Source => "source",
+            Self::Transform => "transform",
+            Self::Sink => "sink",
+        }
+    }
+
+    fn types(self) -> Vec<&'static str> {
+        match self {
+            Self::Source => SourceDescription::types(),
+            Self::Transform => TransformDescription::types(),
+            Self::Sink => SinkDescription::types(),
+        }
+    }
+
+    fn example(self, component_type: &str) -> Option<Value> {
+        match self {
+            Self::Source => SourceDescription::example(component_type),
+            Self::Transform => TransformDescription::example(component_type),
+            Self::Sink => SinkDescription::example(component_type),
+        }
+        .ok()
+    }
+
+    fn schema(self, component_type: &str) -> Option<JsonValue> {
+        let schema = match self {
+            Self::Source => SourceDescription::schema(component_type),
+            Self::Transform => TransformDescription::schema(component_type),
+            Self::Sink => SinkDescription::schema(component_type),
+        };
+        schema
+            .ok()
+            .flatten()
+            .and_then(|schema| serde_json::to_value(schema).ok())
+    }
+}
+
+/// An option that must be set for the configuration of a component to be valid.
+struct RequiredOption {
+    path: Vec<String>,
+    description: Option<String>,
+    schema: JsonValue,
+}
+
+struct Prompter<'a, R, W> {
+    input: &'a mut R,
+    output: &'a mut W,
+}
+
+impl<'a, R: BufRead, W: Write> Prompter<'a, R, W> {
+    fn ask(&mut self, question: &str) -> Result<String, String> {
+        write!(self.output, "{}", question).map_err(|error| error.to_string())?;
+        self.output.flush().map_err(|error| error.to_string())?;
+
+        let mut line = String::new();
+        match self.input.read_line(&mut line) {
+            Ok(0) => Err("unexpected end of input".to_owned()),
+            Ok(_) => Ok(line.trim().to_owned()),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+
+    fn say(&mut self, message: &str) -> Result<(), String> {
+        writeln!(self.output, "{}", message).map_err(|error| error.to_string())
+    }
+
+    fn ask_types(&mut self, kind: Kind, required: bool) -> Result<Vec<String>, String> {
+        let available = kind.types();
+        loop {
+            let answer = self.ask(&format!(
+                "Which {}s? (comma-separated, `?` to list them{}): ",
+                kind.noun(),
+                if required { "" } else { ", empty for none" }
+            ))?;
+            if answer == "?" {
+                self.say(&available.join(", "))?;
+                continue;
+            }
+
+            let types = answer
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_owned)
+                .collect::<Vec<_>>();
+            if required && types.is_empty() {
+                self.say(&format!("At least one {} is required.", kind.noun()))?;
+                continue;
+            }
+            match types
+                .iter()
+                .find(|component_type| !available.contains(&component_type.as_str()))
+            {
+                Some(unknown) => {
+                    self.say(&format!("Unknown {} type '{}'.", kind.noun(), unknown))?;
+                }
+                None => return Ok(types),
+            }
+        }
+    }
+
+    fn ask_name(&mut self, default: &str, names: &HashSet<String>) -> Result<String, String> {
+        loop {
+            let name = self.ask(&format!("  Name [{}]: ", default))?;
+            let name = if name.is_empty() {
+                default.to_owned()
+            } else {
+                name
+            };
+            if !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                self.say("  Names may only contain letters, digits, `_` and `-`.")?;
+            } else if names.contains(&name) {
+                self.say(&format!("  The name '{}' is already used.", name))?;
+            } else {
+                return Ok(name);
+            }
+        }
+    }
+
+    fn ask_option(
+        &mut self,
+        option: &RequiredOption,
+        default: Option<&Value>,
+    ) -> Result<Value, String> {
+        if let Some(description) = &option.description {
+            self.say(&format!("  # {}", description))?;
+        }
+        let question = match default {
+            Some(default) => format!("  {} [{}]: ", option.path.join("."), display(default)),
+            None => format!("  {}: ", option.path.join(".")),
+        };
+        loop {
+            let answer = self.ask(&question)?;
+            if answer.is_empty() {
+                match default {
+                    Some(default) => return Ok(default.clone()),
+                    None => {
+                        self.say("  A value is required.")?;
+                        continue;
+                    }
+                }
+            }
+            match parse_value(&answer, &option.schema, 0) {
+                Ok(value) => return Ok(value),
+                Err(error) => self.say(&format!("  Invalid value: {}.", error))?,
+            }
+        }
+    }
+}
+
+/// Prompts for a pipeline, returning its configuration.
+pub(super) fn run<R: BufRead, W: Write>(
+    include_globals: bool,
+    input: &mut R,
+    output: &mut W,
+    file: &Option<PathBuf>,
+) -> Result<String, Vec<String>> {
+    let mut prompter = Prompter { input, output };
+    let (expression, options) = prompt_pipeline(&mut prompter).map_err(|error| vec![error])?;
+
+    let config = generate_example_with_options(
+        include_globals,
+        &expression,
+        &options,
+        &None,
+        TransformInputsStrategy::Auto,
+    )?;
+
+    // The configuration may still be invalid, for instance if a value fails a check that isn't
+    // part of the schema, so it is still written but with the errors.
+    if let Err(errors) = config::load_from_str(&config, Format::Toml) {
+        let _ = prompter.say("The generated configuration is not valid:");
+        for error in errors {
+            let _ = prompter.say(&format!("  {}", error));
+        }
+    }
+
+    if let Some(file) = file {
+        write_config(file, &config)
+            .map_err(|error| vec![format!("failed to write to file: {}", error)])?;
+        let _ = prompter.say(&format!("Config file written to {:?}", file));
+    }
+
+    Ok(config)
+}
+
+fn prompt_pipeline<R: BufRead, W: Write>(
+    prompter: &mut Prompter<'_, R, W>,
+) -> Result<(String, IndexMap<String, Map<String, Value>>), String> {
+    let mut names = HashSet::new();
+    let mut options = IndexMap::new();
+    let mut expression = Vec::new();
+
+    for (kind, required) in [
+        (Kind::Source, true),
+        (Kind::Transform, false),
+        (Kind::Sink, true),
+    ] {
+        let mut components = Vec::new();
+        for (i, component_type) in prompter.ask_types(kind, required)?.into_iter().enumerate() {
+            prompter.say(&format!(
+                "{} '{}':",
+                capitalize(kind.noun()),
+                component_type
+            ))?;
+            let name = prompter.ask_name(&format!("{}{}", kind.noun(), i), &names)?;
+
+            let example = kind.example(&component_type);
+            let mut values = Map::new();
+            for option in kind
+                .schema(&component_type)
+                .map(|schema| required_options(&schema))
+                .unwrap_or_default()
+            {
+                let default = example
+                    .as_ref()
+                    .and_then(|example| lookup(example, &option.path));
+                let value = prompter.ask_option(&option, default)?;
+                insert(&mut values, &option.path, value);
+            }
+
+            names.insert(name.clone());
+            options.insert(name.clone(), values);
+            components.push(format!("{}:{}", name, component_type));
+        }
+        expression.push(components.join(","));
+    }
+
+    Ok((expression.join("/"), options))
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(values) => values.iter().map(display).collect::<Vec<_>>().join(","),
+        Value::Table(table) => format!(
+            "{{ {} }}",
+            table
+                .iter()
+                .map(|(key, value)| format!("{} = {}", key, inline(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        value => value.to_string(),
+    }
+}
+
+/// Formats a value as it is written in TOML, on a single line.
+fn inline(value: &Value) -> String {
+    match value {
+        Value::Array(values) => format!(
+            "[{}]",
+            values.iter().map(inline).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Table(_) => display(value),
+        value => value.to_string(),
+    }
+}
+
+fn lookup<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter()
+        .try_fold(value, |value, key| value.as_table()?.get(key))
+}
+
+fn insert(table: &mut Map<String, Value>, path: &[String], value: Value) {
+    match path {
+        [] => {}
+        [key] => {
+            table.insert(key.clone(), value);
+        }
+        [key, rest @ ..] => {
+            let entry = table
+                .entry(key.clone())
+                .or_insert_with(|| Value::Table(Map::new()));
+            if !entry.is_table() {
+                *entry = Value::Table(Map::new());
+            }
+            if let Value::Table(nested) = entry {
+                insert(nested, rest, value);
+            }
+        }
+    }
+}
+
+/// Follows the reference of a schema to its definition, if it is one.
+fn resolve<'a>(schema: &'a JsonValue, root: &'a JsonValue) -> &'a JsonValue {
+    let mut schema = schema;
+    for _ in 0..MAX_SCHEMA_DEPTH {
+        match schema
+            .get("$ref")
+            .and_then(JsonValue::as_str)
+            .and_then(|reference| reference.strip_prefix("#/definitions/"))
+            .and_then(|name| root.get("definitions")?.get(name))
+        {
+            Some(definition) => schema = definition,
+            None => break,
+        }
+    }
+    schema
+}
+
+/// Lists the options that are required by the configuration of a component, other than its type
+/// and inputs, descending into the objects that are required.
+fn required_options(root: &JsonValue) -> Vec<RequiredOption> {
+    let mut options = Vec::new();
+    collect_required(root, root, &mut Vec::new(), &mut options, 0);
+    options.retain(|option| !matches!(option.path[0].as_str(), "type" | "inputs"));
+    options
+}
+
+fn collect_required(
+    schema: &JsonValue,
+    root: &JsonValue,
+    path: &mut Vec<String>,
+    options: &mut Vec<RequiredOption>,
+    depth: usize,
+) {
+    if depth > MAX_SCHEMA_DEPTH {
+        return;
+    }
+    let schema = resolve(schema, root);
+
+    if let Some(subschemas) = schema.get("allOf").and_then(JsonValue::as_array) {
+        for subschema in subschemas {
+            collect_required(subschema, root, path, options, depth + 1);
+        }
+    }
+
+    let required = match schema.get("required").and_then(JsonValue::as_array) {
+        Some(required) => required,
+        None => return,
+    };
+    let properties = schema.get("properties");
+    for name in required.iter().filter_map(JsonValue::as_str) {
+        let property = properties
+            .and_then(|properties| properties.get(name))
+            .map(|property| resolve(property, root))
+            .cloned()
+            .unwrap_or(JsonValue::Null);
+
+        path.push(name.to_owned());
+        if is_plain_object(&property) {
+            collect_required(&property, root, path, options, depth + 1);
+        } else {
+            options.push(RequiredOption {
+                path: path.clone(),
+                description: property
+                    .get("description")
+                    .and_then(JsonValue::as_str)
+                    .and_then(|description| description.lines().next())
+                    .map(str::to_owned),
+                schema: inline_references(&property, root, depth),
+            });
+        }
+        path.pop();
+    }
+}
+
+/// Whether a schema is that of an object with a fixed set of fields, whose required options can be
+/// prompted for one by one. Other objects, such as enums of objects, are entered as a whole.
+fn is_plain_object(schema: &JsonValue) -> bool {
+    schema.get("properties").is_some()
+        && schema.get("required").is_some()
+        && ["allOf", "oneOf", "anyOf"]
+            .iter()
+            .all(|keyword| schema.get(keyword).is_none())
+}
+
+/// Replaces the references in a schema by their definitions, so it can be used on its own.
+fn inline_references(schema: &JsonValue, root: &JsonValue, depth: usize) -> JsonValue {
+    if depth > MAX_SCHEMA_DEPTH {
+        return JsonValue::Null;
+    }
+    match resolve(schema, root) {
+        JsonValue::Object(object) => JsonValue::Object(
+            object
+                .iter()
+                .map(|(key, value)| (key.clone(), inline_references(value, root, depth + 1)))
+                .collect(),
+        ),
+        JsonValue::Array(values) => JsonValue::Array(
+            values
+                .iter()
+                .map(|value| inline_references(value, root, depth + 1))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+/// Lists the values allowed by a schema that only allows constants.
+fn constants(schema: &JsonValue) -> Option<Vec<&JsonValue>> {
+    if let Some(constant) = schema.get("const") {
+        return Some(vec![constant]);
+    }
+    if let Some(values) = schema.get("enum").and_then(JsonValue::as_array) {
+        return Some(values.iter().collect());
+    }
+    let subschemas = schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))
+        .and_then(JsonValue::as_array)?;
+    subschemas
+        .iter()
+        .map(constants)
+        .collect::<Option<Vec<_>>>()
+        .map(|constants| constants.into_iter().flatten().collect())
+}
+
+fn instance_type(schema: &JsonValue) -> Option<&str> {
+    match schema.get("type")? {
+        JsonValue::String(instance_type) => Some(instance_type),
+        JsonValue::Array(instance_types) => instance_types
+            .iter()
+            .filter_map(JsonValue::as_str)
+            .find(|instance_type| *instance_type != "null"),
+        _ => None,
+    }
+}
+
+/// Parses a value entered for an option, validating it against the schema of the option.
+fn parse_value(input: &str, schema: &JsonValue, depth: usize) -> Result<Value, String> {
+    if let Some(constants) = constants(schema) {
+        return match constants
+            .iter()
+            .find(|constant| constant.as_str() == Some(input) || constant.to_string() == input)
+        {
+            Some(constant) => Value::try_from(constant).map_err(|error| error.to_string()),
+            None => Err(format!(
+                "expected one of {}",
+                constants
+                    .iter()
+                    .map(|constant| constant.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        };
+    }
+
+    match instance_type(schema) {
+        Some("string") => Ok(Value::String(input.to_owned())),
+        Some("integer") => input
+            .parse::<i64>()
+            .map(Value::Integer)
+            .map_err(|_| "expected an integer".to_owned()),
+        Some("number") => input
+            .parse::<f64>()
+            .map(Value::Float)
+            .map_err(|_| "expected a number".to_owned()),
+        Some("boolean") => input
+            .parse::<bool>()
+            .map(Value::Boolean)
+            .map_err(|_| "expected `true` or `false`".to_owned()),
+        Some("array") if depth < MAX_SCHEMA_DEPTH && !input.starts_with('[') => {
+            let items = schema.get("items").unwrap_or(&JsonValue::Null);
+            input
+                .split(',')
+                .map(|item| parse_value(item.trim(), items, depth + 1))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array)
+        }
+        _ => parse_inline(input),
+    }
+}
+
+/// Parses a value written in TOML, such as an inline table.
+fn parse_inline(input: &str) -> Result<Value, String> {
+    toml::from_str::<Map<String, Value>>(&format!("value = {}", input))
+        .map_err(|error| error.to_string())?
+        .remove("value")
+        .ok_or_else(|| "expected a value".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn finds_required_options() {
+        let schema = json!({
+            "allOf": [
+                { "$ref": "#/definitions/config" },
+                { "type": "object", "required": ["type"], "properties": { "type": { "const": "example" } } }
+            ],
+            "definitions": {
+                "config": {
+                    "type": "object",
+                    "required": ["address", "tls"],
+                    "properties": {
+                        "address": { "type": "string", "description": "The address.\n\nMore." },
+                        "timeout": { "type": "integer" },
+                        "tls": { "$ref": "#/definitions/tls" }
+                    }
+                },
+                "tls": {
+                    "type": "object",
+                    "required": ["mode"],
+                    "properties": { "mode": { "enum": ["strict", "none"] } }
+                }
+            }
+        });
+
+        let options = required_options(&schema);
+        let paths = options
+            .iter()
+            .map(|option| option.path.join("."))
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["address", "tls.mode"]);
+        assert_eq!(options[0].description.as_deref(), Some("The address."));
+    }
+
+    #[test]
+    fn validates_values() {
+        let schema = json!({ "type": "integer" });
+        assert_eq!(parse_value("42", &schema, 0), Ok(Value::Integer(42)));
+        assert!(parse_value("many", &schema, 0).is_err());
+
+        let schema = json!({ "oneOf": [{ "const": "json" }, { "const": "text" }] });
+        assert_eq!(
+            parse_value("json", &schema, 0),
+            Ok(Value::String("json".to_owned()))
+        );
+        assert!(parse_value("xml", &schema, 0).is_err());
+
+        let schema = json!({ "type": "array", "items": { "type": "string" } });
+        assert_eq!(
+            parse_value("a, b", &schema, 0),
+            Ok(Value::Array(vec!["a".into(), "b".into()]))
+        );
+
+        assert_eq!(
+            parse_value("{ codec = \"json\" }", &json!({}), 0)
+                .unwrap()
+                .get("codec"),
+            Some(&Value::String("json".to_owned()))
+        );
+    }
+
+    #[cfg(all(feature = "sources-stdin", feature = "sinks-console"))]
+    #[test]
+    fn generates_pipeline() {
+        let mut input = "?\nstdin\n\n\nconsole\nout\n\n\n\n".as_bytes();
+        let mut output = Vec::new();
+        let config = run(false, &mut input, &mut output, &None).unwrap();
+
+        assert!(config.contains("[sources.source0]"));
+        assert!(config.contains("[sinks.out]"));
+        assert!(config.contains("inputs = [\"source0\"]"));
+        assert!(config::load_from_str(&config, Format::Toml).is_ok());
+        assert!(String::from_utf8(output).unwrap().contains("stdin"));
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(missing_docs)]
 use std::{
     fmt,
+    sync::Mutex,
     task::{Context, Poll},
 };
 
@@ -14,6 +15,7 @@ use hyper::{
 };
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
+use openssl::ssl::SslContext;
 use snafu::{ResultExt, Snafu};
 use tower::Service;
 use tracing::Instrument;
@@ -23,7 +25,7 @@ use vector_config::configurable_component;
 use crate::{
     config::ProxyConfig,
     internal_events::http_client,
-    tls::{tls_connector_builder, MaybeTlsSettings, TlsError},
+    tls::{tls_connector_builder, MaybeTlsSettings, TlsError, TlsReloader, TlsSettings},
 };
 
 #[derive(Debug, Snafu)]
@@ -145,7 +147,8 @@ pub fn build_proxy_connector(
     let tls = tls_connector_builder(&tls_settings)
         .context(BuildTlsConnectorSnafu)?
         .build();
-    let https = build_tls_connector(tls_settings)?;
+    let proxy_tls = ProxyTls::new(proxy_config)?;
+    let https = build_https_connector(tls_settings, proxy_tls)?;
     let mut proxy = ProxyConnector::new(https).unwrap();
     // Make proxy connector aware of user TLS settings by setting the TLS connector:
     // https://github.com/vectordotdev/vector/issues/13683
@@ -158,6 +161,45 @@ pub fn build_proxy_connector(
 
 pub fn build_tls_connector(
     tls_settings: MaybeTlsSettings,
+) -> Result<HttpsConnector<HttpConnector>, HttpError> {
+    build_https_connector(tls_settings, None)
+}
+
+/// The TLS settings of the connections to the proxy endpoints, which differ from the settings of
+/// the connections tunneled through them.
+struct ProxyTls {
+    hosts: Vec<String>,
+    settings: TlsSettings,
+    context: SslContext,
+}
+
+impl ProxyTls {
+    fn new(proxy_config: &ProxyConfig) -> Result<Option<Self>, HttpError> {
+        if !proxy_config.enabled || proxy_config.tls.is_none() {
+            return Ok(None);
+        }
+        let settings =
+            TlsSettings::from_options(&proxy_config.tls).context(BuildTlsConnectorSnafu)?;
+        let context = tls_connector_builder(&settings.clone().into())
+            .context(BuildTlsConnectorSnafu)?
+            .build()
+            .into_context();
+        Ok(Some(Self {
+            hosts: proxy_config.hosts(),
+            settings,
+            context,
+        }))
+    }
+
+    fn matches(&self, uri: &Uri) -> bool {
+        uri.host()
+            .map_or(false, |host| self.hosts.iter().any(|proxy| proxy == host))
+    }
+}
+
+fn build_https_connector(
+    tls_settings: MaybeTlsSettings,
+    proxy_tls: Option<ProxyTls>,
 ) -> Result<HttpsConnector<HttpConnector>, HttpError> {
     let mut http = HttpConnector::new();
     http.enforce_http(false);
@@ -166,10 +208,25 @@ pub fn build_tls_connector(
     let mut https = HttpsConnector::with_connector(http, tls).context(MakeHttpsConnectorSnafu)?;
 
     let settings = tls_settings.tls().cloned();
-    https.set_callback(move |c, _uri| {
+    let reloader = settings
+        .clone()
+        .map(|settings| Mutex::new(TlsReloader::new(settings)));
+    https.set_callback(move |c, uri| {
+        // Connections to the proxy itself are made with its own TLS settings.
+        if let Some(proxy_tls) = proxy_tls
+            .as_ref()
+            .filter(|proxy_tls| proxy_tls.matches(uri))
+        {
+            c.set_ssl_context(&proxy_tls.context)?;
+            proxy_tls.settings.apply_connect_configuration(c);
+            return Ok(());
+        }
         if let Some(settings) = &settings {
             settings.apply_connect_configuration(c);
         }
+        if let Some(reloader) = &reloader {
+            reloader.lock().expect("Poisoned lock").apply(c)?;
+        }
 
         Ok(())
     });
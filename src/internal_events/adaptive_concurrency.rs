@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use metrics::{register_histogram, Histogram};
+use metrics::{register_counter, register_gauge, register_histogram, Counter, Gauge, Histogram};
 use vector_common::registered_event;
 
 #[derive(Clone, Copy)]
@@ -22,6 +22,7 @@ registered_event! {
         reached_limit: Histogram = register_histogram!("adaptive_concurrency_reached_limit"),
         back_pressure: Histogram = register_histogram!("adaptive_concurrency_back_pressure"),
         past_rtt_mean: Histogram = register_histogram!("adaptive_concurrency_past_rtt_mean"),
+        past_rtt_deviation: Histogram = register_histogram!("adaptive_concurrency_past_rtt_deviation"),
     }
 
     fn emit(&self, data: AdaptiveConcurrencyLimitData) {
@@ -31,7 +32,55 @@ registered_event! {
         let back_pressure = data.had_back_pressure.then_some(1.0).unwrap_or_default();
         self.back_pressure.record(back_pressure);
         self.past_rtt_mean.record(data.past_rtt);
-        // past_rtt_deviation is unrecorded
+        self.past_rtt_deviation.record(data.past_rtt_deviation);
+    }
+}
+
+registered_event! {
+    AdaptiveConcurrencyCurrentLimit => {
+        current_limit: Gauge = register_gauge!("adaptive_concurrency_current_limit"),
+    }
+
+    fn emit(&self, limit: u64) {
+        self.current_limit.set(limit as f64);
+    }
+}
+
+/// An adjustment of the concurrency limit decided on at the end of a window.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AdaptiveConcurrencyDecision {
+    Increase,
+    Decrease,
+    Hold,
+}
+
+registered_event! {
+    AdaptiveConcurrencyDecisions {
+        pinned: bool,
+    } => {
+        increase: Counter = register_counter!(
+            "adaptive_concurrency_decisions_total",
+            "decision" => "increase",
+            "pinned" => self.pinned.to_string()
+        ),
+        decrease: Counter = register_counter!(
+            "adaptive_concurrency_decisions_total",
+            "decision" => "decrease",
+            "pinned" => self.pinned.to_string()
+        ),
+        hold: Counter = register_counter!(
+            "adaptive_concurrency_decisions_total",
+            "decision" => "hold",
+            "pinned" => self.pinned.to_string()
+        ),
+    }
+
+    fn emit(&self, decision: AdaptiveConcurrencyDecision) {
+        match decision {
+            AdaptiveConcurrencyDecision::Increase => self.increase.increment(1),
+            AdaptiveConcurrencyDecision::Decrease => self.decrease.increment(1),
+            AdaptiveConcurrencyDecision::Hold => self.hold.increment(1),
+        }
     }
 }
 
@@ -0,0 +1,44 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type, InternalEvent};
+
+#[derive(Debug)]
+pub struct AuditLogWriteError<'a> {
+    pub error: &'a std::io::Error,
+    pub path: &'a Path,
+    pub action: &'a str,
+}
+
+impl InternalEvent for AuditLogWriteError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to write to the audit log.",
+            error = %self.error,
+            path = ?self.path,
+            action = %self.action,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+        );
+        counter!("audit_log_write_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AuditLogEntry<'a> {
+    pub action: &'a str,
+    pub sequence: u64,
+    pub hash: &'a str,
+}
+
+impl InternalEvent for AuditLogEntry<'_> {
+    fn emit(self) {
+        info!(
+            message = "Audit log entry.",
+            action = %self.action,
+            sequence = %self.sequence,
+            hash = %self.hash,
+        );
+        counter!("audit_log_entries_total", 1);
+    }
+}
@@ -33,3 +33,29 @@ impl InternalEvent for AwsCloudwatchLogsMessageSizeError {
         emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
     }
 }
+
+#[derive(Debug)]
+pub struct AwsCloudwatchLogsUnsupportedMetricError {
+    pub metric_type: &'static str,
+}
+
+impl InternalEvent for AwsCloudwatchLogsUnsupportedMetricError {
+    fn emit(self) {
+        let reason = "Metric type isn't supported by the Embedded Metric Format.";
+        error!(
+            message = reason,
+            metric_type = self.metric_type,
+            error_code = "unsupported_metric_type",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "unsupported_metric_type",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,26 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type, InternalEvent};
+
+#[derive(Debug)]
+pub struct CaptureWriteError<'a> {
+    pub error: &'a std::io::Error,
+    pub path: &'a Path,
+    pub component_id: &'a str,
+}
+
+impl InternalEvent for CaptureWriteError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to write captured events.",
+            error = %self.error,
+            path = ?self.path,
+            component_id = %self.component_id,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!("capture_write_errors_total", 1);
+    }
+}
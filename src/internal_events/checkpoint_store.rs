@@ -0,0 +1,24 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct CheckpointStoreError<'a> {
+    pub error: crate::Error,
+    pub component_id: &'a str,
+}
+
+impl<'a> InternalEvent for CheckpointStoreError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to copy checkpoints to the checkpoint store.",
+            error = %self.error,
+            component_id = %self.component_id,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!("checkpoint_store_errors_total", 1);
+    }
+}
@@ -0,0 +1,81 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+use crate::emit;
+
+/// The state of a sink's circuit breaker.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircuitBreakerState {
+    /// Requests are sent normally.
+    Closed,
+    /// Requests are failed without being sent.
+    Open,
+    /// A single request is sent to probe whether the downstream service has recovered.
+    HalfOpen,
+}
+
+impl CircuitBreakerState {
+    const ALL: [Self; 3] = [Self::Closed, Self::Open, Self::HalfOpen];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CircuitBreakerStateChanged {
+    pub state: CircuitBreakerState,
+}
+
+impl InternalEvent for CircuitBreakerStateChanged {
+    fn emit(self) {
+        match self.state {
+            CircuitBreakerState::Closed => debug!(message = "Circuit breaker closed."),
+            CircuitBreakerState::Open => warn!(
+                message = "Circuit breaker opened, requests will fail until it is reset.",
+                internal_log_rate_limit = true,
+            ),
+            CircuitBreakerState::HalfOpen => {
+                debug!(message = "Circuit breaker half-open, probing with a single request.")
+            }
+        }
+
+        for state in CircuitBreakerState::ALL {
+            gauge!(
+                "circuit_breaker_state",
+                if state == self.state { 1.0 } else { 0.0 },
+                "state" => state.as_str(),
+            );
+        }
+        counter!(
+            "circuit_breaker_transitions_total", 1,
+            "state" => self.state.as_str(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct CircuitBreakerEventsDiverted {
+    pub count: usize,
+    pub dead_lettered: bool,
+}
+
+impl InternalEvent for CircuitBreakerEventsDiverted {
+    fn emit(self) {
+        if self.dead_lettered {
+            counter!(
+                "circuit_breaker_dead_lettered_events_total",
+                self.count as u64
+            );
+        } else {
+            emit!(ComponentEventsDropped::<INTENTIONAL> {
+                count: self.count,
+                reason: "Circuit breaker is open.",
+            });
+        }
+    }
+}
@@ -161,3 +161,42 @@ impl<E: std::fmt::Display> InternalEvent for SinkRequestBuildError<E> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct PartitionKeyEvaluationError<'a> {
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for PartitionKeyEvaluationError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to evaluate the partition key.",
+            error = %self.error,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpCompressionFallback<C> {
+    pub rejected: C,
+    pub fallback: C,
+}
+
+impl<C: std::fmt::Display> InternalEvent for HttpCompressionFallback<C> {
+    fn emit(self) {
+        warn!(
+            message = "Endpoint rejected the compression of requests, falling back to another compression.",
+            rejected = %self.rejected,
+            fallback = %self.fallback,
+        );
+        counter!("http_compression_fallbacks_total", 1);
+    }
+}
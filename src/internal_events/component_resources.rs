@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use metrics::{counter, gauge, register_counter, Counter};
+use vector_common::{internal_event::InternalEvent, registered_event};
+
+registered_event! {
+    ComponentCpuTime => {
+        cpu_time: Counter = register_counter!("component_cpu_time_nanoseconds_total"),
+    }
+
+    fn emit(&self, cpu_time: Duration) {
+        self.cpu_time.increment(cpu_time.as_nanos() as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct ComponentQueuedEvents {
+    pub count: usize,
+}
+
+impl InternalEvent for ComponentQueuedEvents {
+    fn emit(self) {
+        trace!(message = "Events queued in input.", count = %self.count);
+        gauge!("component_queued_events", self.count as f64);
+    }
+}
+
+#[derive(Debug)]
+pub struct ComponentMemoryLimitReached {
+    pub allocated_bytes: u64,
+    pub limit: u64,
+}
+
+impl InternalEvent for ComponentMemoryLimitReached {
+    fn emit(self) {
+        warn!(
+            message = "Component reached its memory limit, pausing its input.",
+            allocated_bytes = %self.allocated_bytes,
+            limit = %self.limit,
+            internal_log_rate_limit = true,
+        );
+        counter!("component_memory_limit_reached_total", 1);
+    }
+}
@@ -1,3 +1,5 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
 use vector_core::internal_event::InternalEvent;
 
 #[derive(Debug)]
@@ -8,3 +10,25 @@ impl InternalEvent for DemoLogsEventProcessed {
         trace!(message = "Received one event.");
     }
 }
+
+#[derive(Debug)]
+pub struct DemoLogsVrlGenerationError<'a> {
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for DemoLogsVrlGenerationError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to generate a line with the VRL program.",
+            error = %self.error,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+use metrics::histogram;
+use vector_common::internal_event::InternalEvent;
+
+use crate::config::ComponentKey;
+
+#[derive(Debug)]
+pub struct EndToEndLatency<'a> {
+    pub source: &'a ComponentKey,
+    pub latency: Duration,
+}
+
+impl<'a> InternalEvent for EndToEndLatency<'a> {
+    fn emit(self) {
+        histogram!(
+            "end_to_end_latency_seconds",
+            self.latency,
+            "source_component_id" => self.source.id().to_string(),
+        );
+    }
+}
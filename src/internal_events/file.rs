@@ -190,6 +190,33 @@ mod source {
         }
     }
 
+    #[derive(Debug)]
+    pub struct FileHeaderReadError<'a> {
+        pub file: &'a str,
+        pub error: Error,
+    }
+
+    impl<'a> InternalEvent for FileHeaderReadError<'a> {
+        fn emit(self) {
+            error!(
+                message = "Failed reading the header row of file.",
+                file = %self.file,
+                error = %self.error,
+                error_code = "reading_header",
+                error_type = error_type::READER_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "reading_header",
+                "error_type" => error_type::READER_FAILED,
+                "stage" => error_stage::RECEIVING,
+                "file" => self.file.to_owned(),
+            );
+        }
+    }
+
     const DELETION_FAILED: &str = "deletion_failed";
 
     #[derive(Debug)]
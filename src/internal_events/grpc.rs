@@ -24,6 +24,30 @@ impl InternalEvent for GrpcInvalidCompressionSchemeError<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct GrpcClientNotAllowedError {
+    pub remote_addr: Option<std::net::SocketAddr>,
+}
+
+impl InternalEvent for GrpcClientNotAllowedError {
+    fn emit(self) {
+        error!(
+            message = "Client certificate holds none of the allowed subject alternative names.",
+            remote_addr = ?self.remote_addr,
+            error_code = "client_not_allowed",
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "client_not_allowed",
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct GrpcError<E> {
     pub error: E,
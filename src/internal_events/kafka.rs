@@ -161,6 +161,32 @@ impl InternalEvent for KafkaStatisticsReceived<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct KafkaSchemaRegistryError {
+    pub error: crate::Error,
+    pub schema_id: u32,
+}
+
+impl InternalEvent for KafkaSchemaRegistryError {
+    fn emit(self) {
+        error!(
+            message = "Failed to fetch the Avro schema from the schema registry.",
+            error = %self.error,
+            schema_id = self.schema_id,
+            error_code = "fetching_schema",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "fetching_schema",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
 pub struct KafkaHeaderExtractionError<'a> {
     pub header_field: &'a str,
 }
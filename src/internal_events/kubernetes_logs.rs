@@ -4,7 +4,7 @@ use vector_core::internal_event::InternalEvent;
 use crate::emit;
 use crate::event::Event;
 use vector_common::internal_event::{
-    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+    error_stage, error_type, ComponentEventsDropped, INTENTIONAL, UNINTENTIONAL,
 };
 
 #[derive(Debug)]
@@ -49,6 +49,39 @@ impl InternalEvent for KubernetesLogsEventsReceived<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct KubernetesLogsQuotaExceeded<'a> {
+    pub scope: &'static str,
+    pub pod_name: &'a str,
+    pub pod_namespace: &'a str,
+    pub dropped: bool,
+}
+
+impl InternalEvent for KubernetesLogsQuotaExceeded<'_> {
+    fn emit(self) {
+        debug!(
+            message = "Ingestion quota exceeded.",
+            scope = self.scope,
+            pod_name = self.pod_name,
+            pod_namespace = self.pod_namespace,
+            dropped = self.dropped,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "kubernetes_logs_quota_exceeded_total", 1,
+            "scope" => self.scope,
+            "pod_name" => self.pod_name.to_owned(),
+            "pod_namespace" => self.pod_namespace.to_owned(),
+        );
+        if self.dropped {
+            emit!(ComponentEventsDropped::<INTENTIONAL> {
+                count: 1,
+                reason: "Ingestion quota exceeded.",
+            });
+        }
+    }
+}
+
 const ANNOTATION_FAILED: &str = "annotation_failed";
 
 #[derive(Debug)]
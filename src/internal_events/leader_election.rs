@@ -0,0 +1,54 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct LeaderElectionAcquired<'a> {
+    pub component_id: &'a str,
+}
+
+impl<'a> InternalEvent for LeaderElectionAcquired<'a> {
+    fn emit(self) {
+        info!(
+            message = "Elected leader, starting source.",
+            component_id = %self.component_id,
+        );
+        gauge!("leader_election_is_leader", 1.0);
+    }
+}
+
+#[derive(Debug)]
+pub struct LeaderElectionLost<'a> {
+    pub component_id: &'a str,
+}
+
+impl<'a> InternalEvent for LeaderElectionLost<'a> {
+    fn emit(self) {
+        info!(
+            message = "No longer leader, source stopped.",
+            component_id = %self.component_id,
+        );
+        gauge!("leader_election_is_leader", 0.0);
+    }
+}
+
+#[derive(Debug)]
+pub struct LeaderElectionError<'a> {
+    pub error: crate::Error,
+    pub component_id: &'a str,
+}
+
+impl<'a> InternalEvent for LeaderElectionError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Leader election failed.",
+            error = %self.error,
+            component_id = %self.component_id,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!("leader_election_errors_total", 1);
+    }
+}
@@ -9,6 +9,7 @@ mod amqp;
 mod apache_metrics;
 #[cfg(feature = "api")]
 mod api;
+mod audit_log;
 #[cfg(feature = "aws-core")]
 mod aws;
 #[cfg(feature = "sinks-aws_cloudwatch_logs")]
@@ -27,8 +28,12 @@ mod aws_kinesis_firehose;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 mod aws_sqs;
 mod batch;
+mod capture;
+mod checkpoint_store;
+mod circuit_breaker;
 mod codecs;
 mod common;
+mod component_resources;
 mod conditions;
 #[cfg(feature = "sinks-datadog_metrics")]
 mod datadog_metrics;
@@ -43,6 +48,7 @@ mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 mod docker_logs;
 mod encoding_transcode;
+mod end_to_end_latency;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
@@ -76,6 +82,11 @@ mod journald;
 mod kafka;
 #[cfg(feature = "sources-kubernetes_logs")]
 mod kubernetes_logs;
+#[cfg(any(
+    feature = "leader-election-kubernetes",
+    feature = "leader-election-redis"
+))]
+mod leader_election;
 mod log_to_metric;
 mod logplex;
 #[cfg(feature = "sinks-loki")]
@@ -105,19 +116,28 @@ mod redis;
 mod reduce;
 mod remap;
 mod sample;
+mod self_telemetry;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
+mod size_budget;
 mod socket;
+mod source_filter;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
+mod stale_events;
+mod state_store;
 #[cfg(feature = "sinks-statsd")]
 mod statsd_sink;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 mod tag_cardinality_limit;
 mod tcp;
 mod template;
+#[cfg(feature = "transforms-tenant_quota")]
+mod tenant_quota;
 #[cfg(feature = "transforms-throttle")]
 mod throttle;
+#[cfg(feature = "transforms-transcode")]
+mod transcode;
 mod udp;
 mod unix;
 #[cfg(feature = "sinks-websocket")]
@@ -215,6 +235,11 @@ pub(crate) use self::journald::*;
 pub(crate) use self::kafka::*;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub(crate) use self::kubernetes_logs::*;
+#[cfg(any(
+    feature = "leader-election-kubernetes",
+    feature = "leader-election-redis"
+))]
+pub(crate) use self::leader_election::*;
 pub(crate) use self::log_to_metric::*;
 #[cfg(feature = "sources-heroku_logs")]
 pub(crate) use self::logplex::*;
@@ -245,14 +270,19 @@ pub(crate) use self::remap::*;
 pub(crate) use self::sample::*;
 #[cfg(feature = "sinks-sematext")]
 pub(crate) use self::sematext_metrics::*;
+pub(crate) use self::source_filter::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 pub(crate) use self::splunk_hec::*;
 #[cfg(feature = "sinks-statsd")]
 pub(crate) use self::statsd_sink::*;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub(crate) use self::tag_cardinality_limit::*;
+#[cfg(feature = "transforms-tenant_quota")]
+pub(crate) use self::tenant_quota::*;
 #[cfg(feature = "transforms-throttle")]
 pub(crate) use self::throttle::*;
+#[cfg(feature = "transforms-transcode")]
+pub(crate) use self::transcode::*;
 #[cfg(all(
     any(
         feature = "sinks-socket",
@@ -271,8 +301,10 @@ pub(crate) use self::websocket::*;
 #[cfg(windows)]
 pub(crate) use self::windows::*;
 pub(crate) use self::{
-    adaptive_concurrency::*, batch::*, common::*, conditions::*, encoding_transcode::*,
-    heartbeat::*, open::*, process::*, socket::*, tcp::*, template::*, udp::*,
+    adaptive_concurrency::*, audit_log::*, batch::*, capture::*, checkpoint_store::*,
+    circuit_breaker::*, common::*, component_resources::*, conditions::*, encoding_transcode::*,
+    end_to_end_latency::*, heartbeat::*, open::*, process::*, self_telemetry::*, size_budget::*,
+    socket::*, stale_events::*, state_store::*, tcp::*, template::*, udp::*,
 };
 
 // this version won't be needed once all `InternalEvent`s implement `name()`
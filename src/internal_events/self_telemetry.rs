@@ -0,0 +1,27 @@
+use std::fmt::Display;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type, InternalEvent};
+
+#[derive(Debug)]
+pub struct SelfTelemetryExportError<E> {
+    pub error: E,
+    pub signal: &'static str,
+}
+
+impl<E: Display> InternalEvent for SelfTelemetryExportError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to export self-telemetry.",
+            error = %self.error,
+            signal = %self.signal,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "self_telemetry_export_errors_total", 1,
+            "signal" => self.signal,
+        );
+    }
+}
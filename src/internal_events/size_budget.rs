@@ -0,0 +1,44 @@
+use metrics::counter;
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+use crate::emit;
+
+#[derive(Debug)]
+pub struct OversizedEventSplit;
+
+impl InternalEvent for OversizedEventSplit {
+    fn emit(self) {
+        counter!("oversized_events_total", 1, "action" => "split");
+    }
+}
+
+#[derive(Debug)]
+pub struct OversizedEventTruncated;
+
+impl InternalEvent for OversizedEventTruncated {
+    fn emit(self) {
+        counter!("oversized_events_total", 1, "action" => "truncated");
+    }
+}
+
+#[derive(Debug)]
+pub struct OversizedEventDropped {
+    pub size: usize,
+    pub max_bytes: usize,
+}
+
+impl InternalEvent for OversizedEventDropped {
+    fn emit(self) {
+        warn!(
+            message = "Dropping event exceeding the size budget of the sink.",
+            size = %self.size,
+            max_bytes = %self.max_bytes,
+            internal_log_rate_limit = true,
+        );
+        counter!("oversized_events_total", 1, "action" => "dropped");
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Event exceeds the size budget of the sink.",
+        });
+    }
+}
@@ -0,0 +1,16 @@
+use vector_common::internal_event::{ComponentEventsDropped, Count, Registered, INTENTIONAL};
+
+use crate::register;
+
+vector_common::registered_event! (
+    SourceFilterEventsDropped => {
+        events_dropped: Registered<ComponentEventsDropped<'static, INTENTIONAL>>
+            = register!(ComponentEventsDropped::<INTENTIONAL>::from(
+                "Events did not match the source filter."
+            )),
+    }
+
+    fn emit(&self, data: Count) {
+        self.events_dropped.emit(data);
+    }
+);
@@ -0,0 +1,25 @@
+use metrics::counter;
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+use crate::emit;
+
+#[derive(Debug)]
+pub struct StaleEventsShed {
+    pub count: usize,
+    pub dead_lettered: bool,
+}
+
+impl InternalEvent for StaleEventsShed {
+    fn emit(self) {
+        counter!(
+            "stale_events_shed_total", self.count as u64,
+            "fallback" => if self.dead_lettered { "dead_letter_queue" } else { "drop" },
+        );
+        if !self.dead_lettered {
+            emit!(ComponentEventsDropped::<INTENTIONAL> {
+                count: self.count,
+                reason: "Events are older than the maximum event age.",
+            });
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct StateStoreCheckpointed {
+    pub entries: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for StateStoreCheckpointed {
+    fn emit(self) {
+        trace!(
+            message = "Checkpointed state.",
+            entries = %self.entries,
+            byte_size = %self.byte_size,
+        );
+        gauge!("state_store_entries", self.entries as f64);
+        gauge!("state_store_byte_size", self.byte_size as f64);
+    }
+}
+
+#[derive(Debug)]
+pub struct StateStoreCheckpointError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl<'a> InternalEvent for StateStoreCheckpointError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to checkpoint state.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::IO_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct StateStoreLoadError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl<'a> InternalEvent for StateStoreLoadError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to load checkpointed state, starting with empty state.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::IO_FAILED,
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use crate::emit;
+use metrics::counter;
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+#[derive(Debug)]
+pub(crate) struct TenantQuotaEventDiscarded<'a> {
+    pub tenant: &'a str,
+}
+
+impl<'a> InternalEvent for TenantQuotaEventDiscarded<'a> {
+    fn emit(self) {
+        debug!(message = "Tenant buffer quota exceeded.", tenant = %self.tenant);
+        counter!(
+            "tenant_events_discarded_total", 1,
+            "tenant" => self.tenant.to_owned(),
+        );
+
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Tenant buffer quota exceeded."
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TenantQuotaTenantLimitReached<'a> {
+    pub tenant: &'a str,
+    pub max_tenants: usize,
+}
+
+impl<'a> InternalEvent for TenantQuotaTenantLimitReached<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Too many tenants, dropping event.",
+            tenant = %self.tenant,
+            max_tenants = %self.max_tenants,
+            internal_log_rate_limit = true
+        );
+
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Too many tenants."
+        })
+    }
+}
@@ -0,0 +1,37 @@
+use crate::emit;
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+
+#[derive(Debug)]
+pub struct TranscodeFailed<'a> {
+    pub field: &'a str,
+    pub error: vector_common::Error,
+    /// If set to true, the transcode transform has dropped the event after the failure.
+    pub drop_event: bool,
+}
+
+impl<'a> InternalEvent for TranscodeFailed<'a> {
+    fn emit(self) {
+        let reason = "Failed transcoding payload.";
+        error!(
+            message = reason,
+            field = %self.field,
+            error = %self.error,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        if self.drop_event {
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+        }
+    }
+}
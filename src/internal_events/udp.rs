@@ -63,3 +63,28 @@ impl InternalEvent for UdpSendIncompleteError {
         emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
     }
 }
+
+#[derive(Debug)]
+pub struct UdpChunkingError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for UdpChunkingError<E> {
+    fn emit(self) {
+        let reason = "Failed splitting the event into UDP datagrams.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
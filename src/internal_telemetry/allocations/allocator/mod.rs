@@ -5,6 +5,7 @@ mod tracer;
 mod tracing;
 mod tracing_allocator;
 
+pub(crate) use self::token::current_allocation_group;
 pub use self::token::AllocationGroupId;
 pub use self::token::AllocationGroupToken;
 pub use self::tracer::Tracer;
@@ -94,6 +94,17 @@ impl From<AllocationGroupId> for AllocationGroupToken {
     }
 }
 
+/// Gets the allocation group active on the current thread.
+pub(crate) fn current_allocation_group() -> AllocationGroupId {
+    LOCAL_ALLOCATION_GROUP_STACK
+        .try_with(|stack| {
+            stack
+                .try_borrow()
+                .map_or(AllocationGroupId::ROOT, |stack| stack.current())
+        })
+        .unwrap_or(AllocationGroupId::ROOT)
+}
+
 /// Calls `f` after suspending the active allocation group, if it was not already suspended.
 ///
 /// If the active allocation group is not currently suspended, then `f` is called, after suspending it, with a reference
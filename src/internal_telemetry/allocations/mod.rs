@@ -3,7 +3,7 @@
 mod allocator;
 use std::{
     sync::{
-        atomic::{AtomicBool, AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering},
         Mutex,
     },
     thread,
@@ -17,7 +17,8 @@ use rand_distr::num_traits::ToPrimitive;
 use self::allocator::Tracer;
 
 pub(crate) use self::allocator::{
-    without_allocation_tracing, AllocationGroupId, AllocationLayer, GroupedTraceableAllocator,
+    current_allocation_group, without_allocation_tracing, AllocationGroupId, AllocationLayer,
+    GroupedTraceableAllocator,
 };
 
 const NUM_GROUPS: usize = 128;
@@ -81,6 +82,9 @@ impl GroupInfo {
 
 static GROUP_INFO: [Mutex<GroupInfo>; NUM_GROUPS] = arr![Mutex::new(GroupInfo::new()); 128];
 
+/// The number of bytes allocated by each group, as of the last report.
+static GROUP_ALLOCATED_BYTES: [AtomicI64; NUM_GROUPS] = arr![AtomicI64::new(0); 128];
+
 pub type Allocator<A> = GroupedTraceableAllocator<A, MainTracer>;
 
 pub const fn get_grouped_tracing_allocator<A>(allocator: A) -> Allocator<A> {
@@ -137,6 +141,7 @@ pub fn init_allocation_tracing() {
                         continue;
                     }
                     let mem_used_diff = allocations_diff as i64 - deallocations_diff as i64;
+                    GROUP_ALLOCATED_BYTES[group_idx].fetch_add(mem_used_diff, Ordering::Relaxed);
                     let group_info = group.lock().unwrap();
                     if allocations_diff > 0 {
                         counter!(
@@ -210,3 +215,19 @@ pub fn acquire_allocation_group_id(
     info!("Maximum number of registrable allocation group IDs reached ({}). Allocations for component '{}' will be attributed to the root allocation group.", NUM_GROUPS, component_id);
     AllocationGroupId::ROOT
 }
+
+/// Gets the number of bytes allocated by the active allocation group, as of the last report.
+///
+/// Returns `None` if allocations aren't tracked, or no allocation group is active.
+pub fn current_group_allocated_bytes() -> Option<u64> {
+    if !TRACK_ALLOCATIONS.load(Ordering::Relaxed) {
+        return None;
+    }
+    let group_id = current_allocation_group();
+    if group_id == AllocationGroupId::ROOT {
+        return None;
+    }
+    GROUP_ALLOCATED_BYTES
+        .get(group_id.as_raw() as usize)
+        .map(|bytes| bytes.load(Ordering::Relaxed).max(0) as u64)
+}
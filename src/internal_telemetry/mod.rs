@@ -2,6 +2,7 @@
 
 #[cfg(feature = "allocation-tracing")]
 pub mod allocations;
+pub mod otlp;
 
 pub const fn is_allocation_tracking_enabled() -> bool {
     cfg!(feature = "allocation-tracing")
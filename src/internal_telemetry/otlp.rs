@@ -0,0 +1,448 @@
+//! Export of Vector's own telemetry to an OpenTelemetry collector.
+//!
+//! When the global `self_telemetry` option is enabled, internal metrics are captured from the
+//! metrics registry, and the spans of components are collected by [`SpanLayer`], then both are
+//! periodically sent to the collector with OTLP/HTTP, encoded as JSON. This is independent of the
+//! configured topology, so Vector can be observed even when the pipeline itself is unhealthy.
+
+use std::{
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use http::{header::CONTENT_TYPE, Request};
+use hyper::Body;
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use tokio::task::JoinHandle;
+use tracing::{span, Subscriber};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+use vector_core::{
+    config::SelfTelemetryConfig,
+    event::{Metric, MetricKind, MetricValue},
+    metrics::Controller,
+};
+
+use crate::{config::ProxyConfig, http::HttpClient, internal_events::SelfTelemetryExportError};
+
+/// The maximum number of finished spans kept between two exports. Spans finishing while the queue
+/// is full are discarded.
+const MAX_QUEUED_SPANS: usize = 10_000;
+
+static TRACES_ENABLED: AtomicBool = AtomicBool::new(false);
+static SPANS: Lazy<Mutex<Vec<FinishedSpan>>> = Lazy::new(|| Mutex::new(Vec::new()));
+static EXPORTER: Lazy<Mutex<Option<(SelfTelemetryConfig, JoinHandle<()>)>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Applies the self-telemetry configuration, restarting the export if it changed.
+pub fn configure(config: &SelfTelemetryConfig, proxy: &ProxyConfig) {
+    let mut exporter = EXPORTER.lock().expect("poisoned lock");
+    if exporter.as_ref().map(|(current, _)| current) == Some(config) {
+        return;
+    }
+
+    if let Some((_, handle)) = exporter.take() {
+        handle.abort();
+    }
+    TRACES_ENABLED.store(config.enabled && config.traces, Ordering::Relaxed);
+    SPANS.lock().expect("poisoned lock").clear();
+
+    if config.enabled {
+        match HttpClient::new(None, proxy) {
+            Ok(client) => {
+                let handle = tokio::spawn(export(config.clone(), client));
+                *exporter = Some((config.clone(), handle));
+            }
+            Err(error) => emit!(SelfTelemetryExportError {
+                error,
+                signal: "all"
+            }),
+        }
+    }
+}
+
+async fn export(config: SelfTelemetryConfig, client: HttpClient) {
+    let resource = resource();
+    let mut interval = tokio::time::interval(Duration::from_secs(config.interval_secs.max(1)));
+
+    loop {
+        interval.tick().await;
+
+        if config.metrics {
+            if let Ok(controller) = Controller::get() {
+                let body = metrics_request(&controller.capture_metrics(), &resource);
+                send(&client, &config, "metrics", body).await;
+            }
+        }
+
+        if config.traces {
+            let spans = mem::take(&mut *SPANS.lock().expect("poisoned lock"));
+            if !spans.is_empty() {
+                send(
+                    &client,
+                    &config,
+                    "traces",
+                    traces_request(&spans, &resource),
+                )
+                .await;
+            }
+        }
+    }
+}
+
+async fn send(
+    client: &HttpClient,
+    config: &SelfTelemetryConfig,
+    signal: &'static str,
+    body: Value,
+) {
+    let uri = format!("{}/v1/{}", config.endpoint.trim_end_matches('/'), signal);
+    let mut builder = Request::post(uri).header(CONTENT_TYPE, "application/json");
+    for (name, value) in &config.headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+
+    let request = match builder.body(Body::from(body.to_string())) {
+        Ok(request) => request,
+        Err(error) => {
+            emit!(SelfTelemetryExportError { error, signal });
+            return;
+        }
+    };
+    match client.send(request).await {
+        Ok(response) if response.status().is_success() => {}
+        Ok(response) => emit!(SelfTelemetryExportError {
+            error: format!("Unexpected status: {}", response.status()),
+            signal
+        }),
+        Err(error) => emit!(SelfTelemetryExportError { error, signal }),
+    }
+}
+
+fn resource() -> Value {
+    let mut attributes = vec![
+        attribute("service.name", "vector"),
+        attribute("service.version", &crate::get_version()),
+    ];
+    if let Ok(hostname) = crate::get_hostname() {
+        attributes.push(attribute("host.name", &hostname));
+    }
+    json!({ "attributes": attributes })
+}
+
+fn attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+/// Builds an OTLP `ExportMetricsServiceRequest`. Metrics without an OTLP equivalent, such as
+/// sketches, are skipped.
+fn metrics_request(metrics: &[Metric], resource: &Value) -> Value {
+    let now = unix_nanos(SystemTime::now());
+    let metrics = metrics
+        .iter()
+        .filter_map(|metric| metric_json(metric, &now))
+        .collect::<Vec<_>>();
+
+    json!({
+        "resourceMetrics": [{
+            "resource": resource,
+            "scopeMetrics": [{
+                "scope": { "name": "vector" },
+                "metrics": metrics,
+            }],
+        }],
+    })
+}
+
+fn metric_json(metric: &Metric, now: &str) -> Option<Value> {
+    let name = match metric.namespace() {
+        Some(namespace) => format!("{}_{}", namespace, metric.name()),
+        None => metric.name().to_string(),
+    };
+    let attributes = metric
+        .tags()
+        .map(|tags| {
+            tags.iter_single()
+                .map(|(key, value)| attribute(key, value))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    // Internal metrics are absolute, so their sums are cumulative.
+    let temporality = match metric.kind() {
+        MetricKind::Incremental => 1,
+        MetricKind::Absolute => 2,
+    };
+
+    let data = match metric.value() {
+        MetricValue::Counter { value } => json!({
+            "sum": {
+                "dataPoints": [{ "attributes": attributes, "timeUnixNano": now, "asDouble": value }],
+                "aggregationTemporality": temporality,
+                "isMonotonic": true,
+            }
+        }),
+        MetricValue::Gauge { value } => json!({
+            "gauge": {
+                "dataPoints": [{ "attributes": attributes, "timeUnixNano": now, "asDouble": value }],
+            }
+        }),
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => {
+            // OTLP histograms have an implicit last bucket, for the values above the last bound.
+            let bounded = buckets
+                .iter()
+                .filter(|bucket| bucket.upper_limit.is_finite())
+                .collect::<Vec<_>>();
+            let bounded_count = bounded.iter().map(|bucket| bucket.count).sum::<u64>();
+            let mut bucket_counts = bounded
+                .iter()
+                .map(|bucket| bucket.count.to_string())
+                .collect::<Vec<_>>();
+            bucket_counts.push(count.saturating_sub(bounded_count).to_string());
+            let explicit_bounds = bounded
+                .iter()
+                .map(|bucket| bucket.upper_limit)
+                .collect::<Vec<_>>();
+
+            json!({
+                "histogram": {
+                    "dataPoints": [{
+                        "attributes": attributes,
+                        "timeUnixNano": now,
+                        "count": count.to_string(),
+                        "sum": sum,
+                        "bucketCounts": bucket_counts,
+                        "explicitBounds": explicit_bounds,
+                    }],
+                    "aggregationTemporality": temporality,
+                }
+            })
+        }
+        _ => return None,
+    };
+
+    let mut metric = json!({ "name": name });
+    metric
+        .as_object_mut()
+        .expect("metric is an object")
+        .extend(data.as_object().expect("data is an object").clone());
+    Some(metric)
+}
+
+/// A span of a component that finished, waiting to be exported.
+#[derive(Clone, Debug)]
+struct FinishedSpan {
+    span: SpanData,
+    end: SystemTime,
+}
+
+/// The data of an open span, kept in its extensions.
+#[derive(Clone, Debug)]
+struct SpanData {
+    trace_id: u128,
+    span_id: u64,
+    parent_span_id: Option<u64>,
+    name: &'static str,
+    start: SystemTime,
+    attributes: Vec<(&'static str, String)>,
+    /// Whether this span is, or is within, the span of a component.
+    in_component: bool,
+}
+
+/// Builds an OTLP `ExportTraceServiceRequest`.
+fn traces_request(spans: &[FinishedSpan], resource: &Value) -> Value {
+    let spans = spans
+        .iter()
+        .map(|finished| {
+            let span = &finished.span;
+            let mut json = json!({
+                "traceId": format!("{:032x}", span.trace_id),
+                "spanId": format!("{:016x}", span.span_id),
+                "name": span.name,
+                // SPAN_KIND_INTERNAL
+                "kind": 1,
+                "startTimeUnixNano": unix_nanos(span.start),
+                "endTimeUnixNano": unix_nanos(finished.end),
+                "attributes": span
+                    .attributes
+                    .iter()
+                    .map(|(key, value)| attribute(key, value))
+                    .collect::<Vec<_>>(),
+            });
+            if let Some(parent_span_id) = span.parent_span_id {
+                json["parentSpanId"] = format!("{:016x}", parent_span_id).into();
+            }
+            json
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "resourceSpans": [{
+            "resource": resource,
+            "scopeSpans": [{
+                "scope": { "name": "vector" },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+#[derive(Default)]
+struct SpanFieldsVisitor(Vec<(&'static str, String)>);
+
+impl tracing::field::Visit for SpanFieldsVisitor {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.0.push((field.name(), value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name(), format!("{:?}", value)));
+    }
+}
+
+/// Collects the spans of components, such as the requests made by sinks, for export. Spans outside
+/// of components are ignored.
+pub struct SpanLayer;
+
+impl<S> Layer<S> for SpanLayer
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if !TRACES_ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let span = ctx.span(id).expect("span must already exist!");
+        let parent = span
+            .parent()
+            .and_then(|parent| parent.extensions().get::<SpanData>().cloned());
+
+        let mut fields = SpanFieldsVisitor::default();
+        attrs.record(&mut fields);
+        let in_component = parent.as_ref().map_or(false, |parent| parent.in_component)
+            || fields.0.iter().any(|(name, _)| *name == "component_id");
+
+        span.extensions_mut().insert(SpanData {
+            trace_id: parent
+                .as_ref()
+                .map_or_else(rand::random, |parent| parent.trace_id),
+            span_id: rand::random::<u64>().max(1),
+            parent_span_id: parent.as_ref().map(|parent| parent.span_id),
+            name: attrs.metadata().name(),
+            start: SystemTime::now(),
+            attributes: fields.0,
+            in_component,
+        });
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let span = match ctx.span(&id) {
+            Some(span) => span,
+            None => return,
+        };
+        let data = match span.extensions_mut().remove::<SpanData>() {
+            Some(data) if data.in_component => data,
+            _ => return,
+        };
+
+        if TRACES_ENABLED.load(Ordering::Relaxed) {
+            let mut spans = SPANS.lock().expect("poisoned lock");
+            if spans.len() < MAX_QUEUED_SPANS {
+                spans.push(FinishedSpan {
+                    span: data,
+                    end: SystemTime::now(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::{event::metric::Bucket, metric_tags};
+
+    use super::*;
+
+    #[test]
+    fn converts_metrics() {
+        let counter = Metric::new(
+            "component_errors_total",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 3.0 },
+        )
+        .with_namespace(Some("vector"))
+        .with_tags(Some(metric_tags!("component_id" => "out")));
+        let counter = metric_json(&counter, "1").unwrap();
+        assert_eq!(counter["name"], "vector_component_errors_total");
+        assert_eq!(counter["sum"]["aggregationTemporality"], 2);
+        assert_eq!(counter["sum"]["dataPoints"][0]["asDouble"], 3.0);
+        assert_eq!(
+            counter["sum"]["dataPoints"][0]["attributes"][0],
+            attribute("component_id", "out")
+        );
+
+        let histogram = Metric::new(
+            "latency_seconds",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 2,
+                    },
+                    Bucket {
+                        upper_limit: 2.0,
+                        count: 1,
+                    },
+                ],
+                count: 4,
+                sum: 7.0,
+            },
+        );
+        let histogram = metric_json(&histogram, "1").unwrap();
+        let point = &histogram["histogram"]["dataPoints"][0];
+        assert_eq!(point["explicitBounds"], json!([1.0, 2.0]));
+        assert_eq!(point["bucketCounts"], json!(["2", "1", "1"]));
+        assert_eq!(point["count"], "4");
+    }
+
+    #[test]
+    fn converts_spans() {
+        let start = UNIX_EPOCH + Duration::from_secs(1);
+        let span = FinishedSpan {
+            span: SpanData {
+                trace_id: 1,
+                span_id: 2,
+                parent_span_id: Some(3),
+                name: "request",
+                start,
+                attributes: vec![("request_id", "7".to_string())],
+                in_component: true,
+            },
+            end: start + Duration::from_millis(5),
+        };
+
+        let request = traces_request(&[span], &resource());
+        let span = &request["resourceSpans"][0]["scopeSpans"][0]["spans"][0];
+        assert_eq!(span["traceId"], "00000000000000000000000000000001");
+        assert_eq!(span["spanId"], "0000000000000002");
+        assert_eq!(span["parentSpanId"], "0000000000000003");
+        assert_eq!(span["startTimeUnixNano"], "1000000000");
+        assert_eq!(span["endTimeUnixNano"], "1005000000");
+    }
+}
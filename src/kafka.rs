@@ -1,4 +1,6 @@
 #![allow(missing_docs)]
+pub(crate) mod schema_registry;
+
 use std::path::{Path, PathBuf};
 
 use rdkafka::{consumer::ConsumerContext, ClientConfig, ClientContext, Statistics};
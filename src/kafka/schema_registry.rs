@@ -0,0 +1,148 @@
+//! Fetching Avro schemas from Confluent-compatible schema registries.
+
+use bytes::Bytes;
+use codecs::encoding::{AvroSchemaRegistryAuth, AvroSchemaRegistryOptions};
+use http::{Request, StatusCode};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::{de::DeserializeOwned, Deserialize};
+use snafu::{ResultExt, Snafu};
+
+use crate::{
+    config::ProxyConfig,
+    http::{Auth, HttpClient, HttpError},
+};
+
+#[derive(Debug, Snafu)]
+enum SchemaRegistryError {
+    #[snafu(display("building the schema registry request failed: {}", source))]
+    BuildRequest { source: http::Error },
+    #[snafu(display("fetching the schema from the schema registry failed: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("reading the schema registry response failed: {}", source))]
+    ReadResponse { source: hyper::Error },
+    #[snafu(display(
+        "the schema registry responded with status {}: {}",
+        status,
+        String::from_utf8_lossy(body)
+    ))]
+    UnexpectedStatus { status: StatusCode, body: Bytes },
+    #[snafu(display("parsing the schema registry response failed: {}", source))]
+    ParseResponse { source: serde_json::Error },
+}
+
+/// A schema version, as returned by the `/subjects/{subject}/versions/{version}` endpoint.
+#[derive(Deserialize)]
+pub(crate) struct RegisteredSchema {
+    pub(crate) id: u32,
+    pub(crate) schema: String,
+}
+
+/// A schema, as returned by the `/schemas/ids/{id}` endpoint.
+#[derive(Deserialize)]
+struct SchemaById {
+    schema: String,
+}
+
+/// Fetches the schema registered under the subject and version of the registry options.
+pub(crate) async fn fetch_schema_version(
+    registry: &AvroSchemaRegistryOptions,
+    proxy: &ProxyConfig,
+) -> crate::Result<RegisteredSchema> {
+    let version = registry
+        .version
+        .map_or_else(|| "latest".to_owned(), |version| version.to_string());
+    let path = format!(
+        "subjects/{}/versions/{}",
+        utf8_percent_encode(&registry.subject, NON_ALPHANUMERIC),
+        version,
+    );
+    get(&registry.url, &path, registry.auth.as_ref(), proxy).await
+}
+
+/// Fetches the schema registered with an ID.
+pub(crate) async fn fetch_schema_by_id(
+    url: &str,
+    auth: Option<&AvroSchemaRegistryAuth>,
+    id: u32,
+    proxy: &ProxyConfig,
+) -> crate::Result<String> {
+    let path = format!("schemas/ids/{}", id);
+    let response: SchemaById = get(url, &path, auth, proxy).await?;
+    Ok(response.schema)
+}
+
+async fn get<T: DeserializeOwned>(
+    url: &str,
+    path: &str,
+    auth: Option<&AvroSchemaRegistryAuth>,
+    proxy: &ProxyConfig,
+) -> crate::Result<T> {
+    let uri = format!("{}/{}", url.trim_end_matches('/'), path);
+
+    let mut request = Request::get(uri)
+        .header("Accept", "application/vnd.schemaregistry.v1+json")
+        .body(Body::empty())
+        .context(BuildRequestSnafu)?;
+    if let Some(auth) = auth {
+        Auth::Basic {
+            user: auth.user.clone(),
+            password: auth.password.clone(),
+        }
+        .apply(&mut request);
+    }
+
+    let client = HttpClient::new(None, proxy)?;
+    let response = client.send(request).await.context(RequestSnafu)?;
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .context(ReadResponseSnafu)?;
+    if status != StatusCode::OK {
+        return Err(SchemaRegistryError::UnexpectedStatus { status, body }.into());
+    }
+
+    Ok(serde_json::from_slice(&body).context(ParseResponseSnafu)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use codecs::encoding::AvroSchemaRegistryAuth;
+    use futures::StreamExt;
+    use hyper::Response;
+
+    use super::*;
+    use crate::{
+        sinks::util::test::build_test_server_generic,
+        test_util::{next_addr, trace_init},
+    };
+
+    #[tokio::test]
+    async fn fetches_schema_by_id() {
+        trace_init();
+        let addr = next_addr();
+        let (mut rx, trigger, server) = build_test_server_generic(addr, || {
+            Response::new(Body::from(r#"{"schema":"\"string\""}"#))
+        });
+        tokio::spawn(server);
+
+        let auth = AvroSchemaRegistryAuth {
+            user: "user".to_owned(),
+            password: "password".to_owned().into(),
+        };
+        let schema = fetch_schema_by_id(
+            &format!("http://{}", addr),
+            Some(&auth),
+            42,
+            &ProxyConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        let (parts, _) = rx.next().await.unwrap();
+        drop(trigger);
+        assert_eq!(parts.uri.path(), "/schemas/ids/42");
+        assert!(parts.headers.contains_key("authorization"));
+        assert_eq!(schema, r#""string""#);
+    }
+}
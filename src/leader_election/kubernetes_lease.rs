@@ -0,0 +1,219 @@
+use std::{path::PathBuf, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use k8s_openapi::{
+    api::coordination::v1::{Lease, LeaseSpec},
+    apimachinery::pkg::apis::meta::v1::{MicroTime, ObjectMeta},
+};
+use kube::{
+    api::{Api, PostParams},
+    config::{self, KubeConfigOptions},
+    Client, Config as ClientConfig,
+};
+use vector_config::configurable_component;
+
+use super::Lock;
+
+/// Kubernetes lease configuration.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct KubernetesLeaseConfig {
+    /// The namespace of the `Lease` object.
+    ///
+    /// If not set, the namespace Vector is running in is used.
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub namespace: Option<String>,
+
+    /// The name of the `Lease` object.
+    ///
+    /// If not set, the name is `vector-` followed by the ID of the source.
+    #[configurable(metadata(docs::examples = "vector-k8s-events"))]
+    pub lease_name: Option<String>,
+
+    /// Optional path to a readable [kubeconfig][kubeconfig] file.
+    ///
+    /// If not set, a connection to Kubernetes is made using the in-cluster configuration.
+    ///
+    /// [kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+    #[configurable(metadata(docs::examples = "/path/to/.kube/config"))]
+    pub kube_config_file: Option<PathBuf>,
+}
+
+impl KubernetesLeaseConfig {
+    pub(super) async fn build(
+        &self,
+        name: &str,
+        identity: String,
+        lease_duration: Duration,
+    ) -> crate::Result<KubernetesLease> {
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        let client = Client::try_from(client_config)?;
+        let api = match &self.namespace {
+            Some(namespace) => Api::namespaced(client, namespace),
+            None => Api::default_namespaced(client),
+        };
+
+        Ok(KubernetesLease {
+            api,
+            name: self
+                .lease_name
+                .clone()
+                .unwrap_or_else(|| default_lease_name(name)),
+            identity,
+            lease_duration,
+        })
+    }
+}
+
+/// Names the lease after the source, as object names are limited to lowercase alphanumeric
+/// characters, `-` and `.`.
+fn default_lease_name(name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | '0'..='9' | '-' | '.' => c,
+            'A'..='Z' => c.to_ascii_lowercase(),
+            _ => '-',
+        })
+        .collect::<String>();
+    format!("vector-{}", name)
+}
+
+pub(super) struct KubernetesLease {
+    api: Api<Lease>,
+    name: String,
+    identity: String,
+    lease_duration: Duration,
+}
+
+impl KubernetesLease {
+    fn spec(&self, acquire_time: Option<MicroTime>, transitions: i32) -> LeaseSpec {
+        let now = MicroTime(Utc::now());
+        LeaseSpec {
+            holder_identity: Some(self.identity.clone()),
+            lease_duration_seconds: Some(self.lease_duration.as_secs() as i32),
+            acquire_time: acquire_time.or_else(|| Some(now.clone())),
+            renew_time: Some(now),
+            lease_transitions: Some(transitions),
+        }
+    }
+}
+
+/// Whether the lease isn't held by anyone, as it was released or it expired.
+fn is_free(spec: &LeaseSpec) -> bool {
+    let held = spec
+        .holder_identity
+        .as_deref()
+        .map_or(false, |id| !id.is_empty());
+    match (held, &spec.renew_time, spec.lease_duration_seconds) {
+        (true, Some(renew_time), Some(duration)) => {
+            renew_time.0 + chrono::Duration::seconds(duration.into()) < Utc::now()
+        }
+        (held, _, _) => !held,
+    }
+}
+
+/// Whether the lease was modified by another instance since it was read.
+fn is_conflict(error: &kube::Error) -> bool {
+    matches!(error, kube::Error::Api(response) if response.code == 409)
+}
+
+#[async_trait]
+impl Lock for KubernetesLease {
+    async fn try_acquire(&self) -> crate::Result<bool> {
+        let result = match self.api.get_opt(&self.name).await? {
+            None => {
+                let lease = Lease {
+                    metadata: ObjectMeta {
+                        name: Some(self.name.clone()),
+                        ..Default::default()
+                    },
+                    spec: Some(self.spec(None, 0)),
+                };
+                self.api.create(&PostParams::default(), &lease).await
+            }
+            Some(mut lease) => {
+                let spec = lease.spec.take().unwrap_or_default();
+                let spec = if spec.holder_identity.as_ref() == Some(&self.identity) {
+                    self.spec(spec.acquire_time, spec.lease_transitions.unwrap_or(0))
+                } else if is_free(&spec) {
+                    self.spec(None, spec.lease_transitions.unwrap_or(0) + 1)
+                } else {
+                    return Ok(false);
+                };
+                lease.spec = Some(spec);
+                // The lease is replaced as of the version read, so that only one of the instances
+                // competing for it acquires it.
+                self.api
+                    .replace(&self.name, &PostParams::default(), &lease)
+                    .await
+            }
+        };
+
+        match result {
+            Ok(_) => Ok(true),
+            Err(error) if is_conflict(&error) => Ok(false),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn release(&self) -> crate::Result<()> {
+        let mut lease = match self.api.get_opt(&self.name).await? {
+            Some(lease) => lease,
+            None => return Ok(()),
+        };
+        match lease.spec.as_mut() {
+            Some(spec) if spec.holder_identity.as_ref() == Some(&self.identity) => {
+                spec.holder_identity = None;
+                spec.acquire_time = None;
+                spec.renew_time = None;
+            }
+            _ => return Ok(()),
+        }
+        match self
+            .api
+            .replace(&self.name, &PostParams::default(), &lease)
+            .await
+        {
+            Err(error) if !is_conflict(&error) => Err(error.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lease_name() {
+        assert_eq!(default_lease_name("k8s_Events"), "vector-k8s-events");
+    }
+
+    #[test]
+    fn expired_lease_is_free() {
+        let spec = |holder: Option<&str>, renewed_secs_ago: i64| LeaseSpec {
+            holder_identity: holder.map(ToOwned::to_owned),
+            lease_duration_seconds: Some(15),
+            renew_time: Some(MicroTime(
+                Utc::now() - chrono::Duration::seconds(renewed_secs_ago),
+            )),
+            ..Default::default()
+        };
+
+        assert!(!is_free(&spec(Some("a"), 5)));
+        assert!(is_free(&spec(Some("a"), 20)));
+        assert!(is_free(&spec(None, 5)));
+        assert!(is_free(&spec(Some(""), 5)));
+    }
+}
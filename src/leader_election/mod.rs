@@ -0,0 +1,417 @@
+//! Leader election for singleton sources.
+//!
+//! Some sources must only run on one instance at a time, such as sources polling an API or
+//! watching Kubernetes events, as running them on every instance of a replicated fleet would
+//! duplicate their events. A [`LeaderElection`] lets such a source be deployed to every instance:
+//! the instances compete for a lock held in a shared backend, and only the instance holding it
+//! runs the source. When the leader stops renewing its lock, because it shut down or lost its
+//! connection to the backend, another instance takes the lock over and starts the source.
+#![allow(missing_docs)]
+
+#[cfg(feature = "leader-election-kubernetes")]
+mod kubernetes_lease;
+#[cfg(feature = "leader-election-redis")]
+mod redis;
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::time::Instant;
+use vector_config::configurable_component;
+use vector_core::source::Source;
+
+use crate::{
+    config::{SourceConfig, SourceContext},
+    internal_events::{LeaderElectionAcquired, LeaderElectionError, LeaderElectionLost},
+    shutdown::ShutdownSignal,
+    sources::Sources,
+};
+
+/// Leader election configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct LeaderElectionConfig {
+    #[serde(flatten)]
+    backend: LeaderElectionBackendConfig,
+
+    /// How long the lock is held without being renewed, in seconds.
+    ///
+    /// If the leader fails to renew the lock for this long, another instance takes it over.
+    #[serde(default = "default_lease_duration_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub lease_duration_secs: u64,
+
+    /// How often the leader renews the lock, and followers try to acquire it, in seconds.
+    ///
+    /// Must be less than `lease_duration_secs`.
+    #[serde(default = "default_renew_interval_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub renew_interval_secs: u64,
+}
+
+const fn default_lease_duration_secs() -> u64 {
+    15
+}
+
+const fn default_renew_interval_secs() -> u64 {
+    5
+}
+
+/// Backend of the leader election.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The backend holding the lock."))]
+pub enum LeaderElectionBackendConfig {
+    /// Holds the lock in a Kubernetes `Lease` object.
+    #[cfg(feature = "leader-election-kubernetes")]
+    KubernetesLease(kubernetes_lease::KubernetesLeaseConfig),
+
+    /// Holds the lock in a Redis key.
+    #[cfg(feature = "leader-election-redis")]
+    Redis(redis::RedisLockConfig),
+}
+
+impl LeaderElectionConfig {
+    /// Builds the election of the instance running the source being built.
+    pub async fn build(&self, cx: &SourceContext) -> crate::Result<LeaderElection> {
+        if self.renew_interval_secs == 0 || self.renew_interval_secs >= self.lease_duration_secs {
+            return Err("`leader_election.renew_interval_secs` must be greater than zero and less than `leader_election.lease_duration_secs`.".into());
+        }
+        let lease_duration = Duration::from_secs(self.lease_duration_secs);
+        let lock_name = cx.key.id();
+        let lock: Box<dyn Lock> = match &self.backend {
+            #[cfg(feature = "leader-election-kubernetes")]
+            LeaderElectionBackendConfig::KubernetesLease(config) => {
+                Box::new(config.build(lock_name, identity(), lease_duration).await?)
+            }
+            #[cfg(feature = "leader-election-redis")]
+            LeaderElectionBackendConfig::Redis(config) => {
+                Box::new(config.build(lock_name, identity(), lease_duration).await?)
+            }
+        };
+
+        Ok(LeaderElection {
+            lock,
+            component_id: lock_name.to_owned(),
+            lease_duration,
+            renew_interval: Duration::from_secs(self.renew_interval_secs),
+        })
+    }
+}
+
+/// The identity of this instance in the election, unique across restarts.
+fn identity() -> String {
+    let hostname = crate::get_hostname().unwrap_or_else(|_| "vector".to_owned());
+    format!("{}-{}", hostname, uuid::Uuid::new_v4())
+}
+
+/// A lock held by at most one instance at a time, expiring unless renewed.
+#[async_trait]
+trait Lock: Send + Sync {
+    /// Acquires the lock, or renews it if it is already held by this instance, returning whether
+    /// this instance holds the lock.
+    async fn try_acquire(&self) -> crate::Result<bool>;
+
+    /// Releases the lock, if it is held by this instance.
+    async fn release(&self) -> crate::Result<()>;
+}
+
+/// The election of the instance running a singleton source.
+pub struct LeaderElection {
+    lock: Box<dyn Lock>,
+    component_id: String,
+    lease_duration: Duration,
+    renew_interval: Duration,
+}
+
+/// How a term as leader ended.
+enum TermEnd {
+    /// The source stopped on its own.
+    Stopped(Result<(), ()>),
+    /// The lock was lost, so the source was stopped.
+    Lost,
+    /// Vector is shutting down, so the source was stopped.
+    Shutdown(Result<(), ()>),
+}
+
+impl LeaderElection {
+    /// Runs the source whenever this instance is the leader.
+    ///
+    /// The source is built for each term, and stopped as if Vector was shutting down when the
+    /// term ends.
+    pub fn run(self, inner: Sources, cx: SourceContext) -> Source {
+        Box::pin(async move {
+            let mut shutdown = cx.shutdown.clone();
+            let mut interval = tokio::time::interval(self.renew_interval);
+            loop {
+                // Follow until the lock is acquired.
+                loop {
+                    tokio::select! {
+                        _ = &mut shutdown => return Ok(()),
+                        _ = interval.tick() => {},
+                    }
+                    match self.lock.try_acquire().await {
+                        Ok(true) => break,
+                        Ok(false) => {}
+                        Err(error) => emit!(LeaderElectionError {
+                            error,
+                            component_id: &self.component_id,
+                        }),
+                    }
+                }
+                emit!(LeaderElectionAcquired {
+                    component_id: &self.component_id,
+                });
+
+                let end = self.lead(&inner, &cx, &mut shutdown, &mut interval).await;
+                emit!(LeaderElectionLost {
+                    component_id: &self.component_id,
+                });
+                match end {
+                    TermEnd::Lost => continue,
+                    TermEnd::Stopped(result) | TermEnd::Shutdown(result) => {
+                        if let Err(error) = self.lock.release().await {
+                            emit!(LeaderElectionError {
+                                error,
+                                component_id: &self.component_id,
+                            });
+                        }
+                        return result;
+                    }
+                }
+            }
+        })
+    }
+
+    /// Runs the source for a term as leader, renewing the lock until the term ends.
+    async fn lead(
+        &self,
+        inner: &Sources,
+        cx: &SourceContext,
+        shutdown: &mut ShutdownSignal,
+        interval: &mut tokio::time::Interval,
+    ) -> TermEnd {
+        let (trigger, term_shutdown, _) = ShutdownSignal::new_wired();
+        let mut term_cx = cx.clone();
+        term_cx.shutdown = term_shutdown;
+        let mut source = match inner.build(term_cx).await {
+            Ok(source) => source,
+            Err(error) => {
+                emit!(LeaderElectionError {
+                    error,
+                    component_id: &self.component_id,
+                });
+                return TermEnd::Stopped(Err(()));
+            }
+        };
+
+        let mut renewed_at = Instant::now();
+        loop {
+            tokio::select! {
+                result = &mut source => return TermEnd::Stopped(result),
+                _ = &mut *shutdown => {
+                    drop(trigger);
+                    return TermEnd::Shutdown(source.await);
+                }
+                _ = interval.tick() => match self.lock.try_acquire().await {
+                    Ok(true) => renewed_at = Instant::now(),
+                    Ok(false) => break,
+                    // The lock is still held until it expires, so the renewal is retried until
+                    // then.
+                    Err(error) => {
+                        emit!(LeaderElectionError {
+                            error,
+                            component_id: &self.component_id,
+                        });
+                        if renewed_at.elapsed() >= self.lease_duration {
+                            break;
+                        }
+                    }
+                },
+            }
+        }
+
+        // Another instance may take over the lock from now on, so the source is stopped.
+        drop(trigger);
+        _ = source.await;
+        TermEnd::Lost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::VecDeque, sync::Mutex};
+
+    use stream_cancel::Trigger;
+    use tokio::{sync::mpsc, task::JoinHandle};
+
+    use super::*;
+    use crate::{test_util::mock::tripwire_source, SourceSender};
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Call {
+        TryAcquire,
+        Release,
+    }
+
+    /// A lock answering attempts to acquire it with scripted results, and not held once those run
+    /// out.
+    struct FakeLock {
+        results: Mutex<VecDeque<crate::Result<bool>>>,
+        calls: mpsc::UnboundedSender<Call>,
+    }
+
+    #[async_trait]
+    impl Lock for FakeLock {
+        async fn try_acquire(&self) -> crate::Result<bool> {
+            let _ = self.calls.send(Call::TryAcquire);
+            self.results
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or(Ok(false))
+        }
+
+        async fn release(&self) -> crate::Result<()> {
+            let _ = self.calls.send(Call::Release);
+            Ok(())
+        }
+    }
+
+    struct Election {
+        calls: mpsc::UnboundedReceiver<Call>,
+        /// Stops the source on its own, while it runs.
+        stop_source: Trigger,
+        /// Shuts Vector down.
+        shutdown: Trigger,
+        run: JoinHandle<Result<(), ()>>,
+    }
+
+    impl Election {
+        /// Runs an election with a lease of 15 seconds, renewed every 5 seconds.
+        fn start(results: Vec<crate::Result<bool>>) -> Self {
+            let (calls_tx, calls) = mpsc::unbounded_channel();
+            let election = LeaderElection {
+                lock: Box::new(FakeLock {
+                    results: Mutex::new(results.into()),
+                    calls: calls_tx,
+                }),
+                component_id: "singleton".to_owned(),
+                lease_duration: Duration::from_secs(15),
+                renew_interval: Duration::from_secs(5),
+            };
+
+            let (stop_source, source) = tripwire_source();
+            let (shutdown, shutdown_signal, _) = ShutdownSignal::new_wired();
+            let mut cx = SourceContext::new_test(SourceSender::new_test().0, None);
+            cx.shutdown = shutdown_signal;
+            let run = tokio::spawn(election.run(Sources::TestTripwire(source), cx));
+
+            Self {
+                calls,
+                stop_source,
+                shutdown,
+                run,
+            }
+        }
+
+        async fn expect_calls(&mut self, expected: &[Call]) {
+            for call in expected {
+                assert_eq!(self.calls.recv().await.as_ref(), Some(call));
+            }
+        }
+
+        /// Stops the source, which only runs on the leader, and shuts Vector down, returning the
+        /// calls to the lock made meanwhile.
+        async fn stop(mut self) -> Vec<Call> {
+            self.stop_source.cancel();
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            self.shutdown.cancel();
+            assert_eq!(self.run.await.unwrap(), Ok(()));
+
+            let mut calls = vec![];
+            while let Ok(call) = self.calls.try_recv() {
+                calls.push(call);
+            }
+            calls
+        }
+    }
+
+    fn renewal_error() -> crate::Result<bool> {
+        Err("connection refused".into())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stops_source_when_lock_is_lost() {
+        let mut election = Election::start(vec![Ok(false), Ok(true), Ok(true), Ok(false)]);
+
+        // Follows, acquires, renews, and loses the lock, then follows again, which it only does
+        // once the source stopped.
+        election
+            .expect_calls(&[
+                Call::TryAcquire,
+                Call::TryAcquire,
+                Call::TryAcquire,
+                Call::TryAcquire,
+                Call::TryAcquire,
+            ])
+            .await;
+
+        // The lock isn't held anymore, so it isn't released.
+        let calls = election.stop().await;
+        assert!(!calls.contains(&Call::Release));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn keeps_leading_through_renewal_errors_within_lease() {
+        let mut election = Election::start(vec![
+            Ok(true),
+            renewal_error(),
+            renewal_error(),
+            Ok(true),
+            renewal_error(),
+            renewal_error(),
+        ]);
+
+        // The last renewal was 10 seconds ago, so the lock is still held.
+        election.expect_calls(&[Call::TryAcquire; 6]).await;
+
+        // Still leading, the source runs until it stops on its own, then the lock is released.
+        assert_eq!(election.stop().await, vec![Call::Release]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stops_source_when_lease_expires_after_renewal_errors() {
+        let mut election = Election::start(vec![
+            Ok(true),
+            renewal_error(),
+            renewal_error(),
+            renewal_error(),
+        ]);
+
+        // The renewal 15 seconds after the lock was acquired fails, so the lease expires, and the
+        // instance follows again.
+        election.expect_calls(&[Call::TryAcquire; 5]).await;
+
+        let calls = election.stop().await;
+        assert!(!calls.contains(&Call::Release));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn releases_lock_on_shutdown() {
+        let mut election = Election::start(vec![Ok(true)]);
+        election.expect_calls(&[Call::TryAcquire]).await;
+
+        // The source is stopped by the shutdown, as the lock is held until the next renewal.
+        let Election {
+            mut calls,
+            stop_source: _stop_source,
+            shutdown,
+            run,
+        } = election;
+        shutdown.cancel();
+        assert_eq!(run.await.unwrap(), Ok(()));
+        assert_eq!(calls.recv().await, Some(Call::Release));
+    }
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use vector_config::configurable_component;
+
+use super::Lock;
+
+/// Acquires the lock if it is free, or renews it if it is held by this instance.
+const ACQUIRE_SCRIPT: &str = r#"
+local holder = redis.call("GET", KEYS[1])
+if holder == ARGV[1] then
+    redis.call("PEXPIRE", KEYS[1], ARGV[2])
+    return 1
+elseif not holder then
+    redis.call("SET", KEYS[1], ARGV[1], "PX", ARGV[2])
+    return 1
+end
+return 0
+"#;
+
+/// Releases the lock if it is held by this instance.
+const RELEASE_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    redis.call("DEL", KEYS[1])
+end
+return 0
+"#;
+
+/// Redis lock configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct RedisLockConfig {
+    /// The URL of the Redis endpoint to connect to.
+    ///
+    /// The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
+    /// `redis` or `rediss` for connections secured via TLS.
+    #[configurable(metadata(docs::examples = "redis://127.0.0.1:6379/0"))]
+    pub endpoint: String,
+
+    /// The prefix of the Redis key the lock is held at.
+    ///
+    /// The key is the prefix followed by the ID of the source.
+    #[serde(default = "default_key_prefix")]
+    pub key_prefix: String,
+}
+
+fn default_key_prefix() -> String {
+    "vector:leader:".to_owned()
+}
+
+impl RedisLockConfig {
+    pub(super) async fn build(
+        &self,
+        name: &str,
+        identity: String,
+        lease_duration: Duration,
+    ) -> crate::Result<RedisLock> {
+        let client = redis::Client::open(self.endpoint.as_str())?;
+        let connection = client.get_tokio_connection_manager().await?;
+        Ok(RedisLock {
+            connection,
+            key: format!("{}{}", self.key_prefix, name),
+            identity,
+            lease_duration,
+        })
+    }
+}
+
+pub(super) struct RedisLock {
+    connection: ConnectionManager,
+    key: String,
+    identity: String,
+    lease_duration: Duration,
+}
+
+#[async_trait]
+impl Lock for RedisLock {
+    async fn try_acquire(&self) -> crate::Result<bool> {
+        let acquired: i64 = redis::cmd("EVAL")
+            .arg(ACQUIRE_SCRIPT)
+            .arg(1)
+            .arg(&self.key)
+            .arg(&self.identity)
+            .arg(self.lease_duration.as_millis() as u64)
+            .query_async(&mut self.connection.clone())
+            .await?;
+        Ok(acquired == 1)
+    }
+
+    async fn release(&self) -> crate::Result<()> {
+        let _: i64 = redis::cmd("EVAL")
+            .arg(RELEASE_SCRIPT)
+            .arg(1)
+            .arg(&self.key)
+            .arg(&self.identity)
+            .query_async(&mut self.connection.clone())
+            .await?;
+        Ok(())
+    }
+}
@@ -36,6 +36,12 @@ static ALLOC: self::internal_telemetry::allocations::Allocator<tikv_jemallocator
         tikv_jemallocator::Jemalloc,
     );
 
+// Enables jemalloc's heap profiler, which samples an allocation every 512 KiB on average.
+#[cfg(all(unix, feature = "heap-profiling"))]
+#[allow(non_upper_case_globals)]
+#[export_name = "_rjem_malloc_conf"]
+pub static malloc_conf: &[u8] = b"prof:true,prof_active:true,lg_prof_sample:19\0";
+
 #[allow(unreachable_pub)]
 pub mod internal_telemetry;
 
@@ -48,6 +54,7 @@ pub mod components;
 pub mod conditions;
 #[cfg(not(windows))]
 pub mod control_server;
+pub mod dead_letter;
 pub mod dns;
 #[cfg(feature = "docker")]
 pub mod docker;
@@ -64,13 +71,19 @@ pub mod amqp;
 pub mod api;
 pub mod app;
 pub mod async_read;
+pub mod audit;
 #[cfg(feature = "aws-config")]
 pub mod aws;
+pub mod benchmark;
+pub mod capture;
+pub mod checkpoint_store;
 #[allow(unreachable_pub)]
 pub mod codecs;
 pub(crate) mod common;
 pub mod encoding_transcode;
 pub mod enrichment_tables;
+pub mod event_latency;
+pub mod event_trace;
 #[cfg(feature = "gcp")]
 pub mod gcp;
 pub(crate) mod graph;
@@ -81,6 +94,11 @@ pub mod http;
 pub mod kafka;
 #[allow(unreachable_pub)]
 pub mod kubernetes;
+#[cfg(any(
+    feature = "leader-election-kubernetes",
+    feature = "leader-election-redis"
+))]
+pub mod leader_election;
 pub mod line_agg;
 pub mod list;
 #[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
@@ -88,6 +106,9 @@ pub(crate) mod nats;
 #[allow(unreachable_pub)]
 pub(crate) mod proto;
 pub mod providers;
+#[cfg(feature = "api")]
+pub mod reload;
+pub mod schema_inference;
 pub mod secrets;
 pub mod serde;
 #[cfg(windows)]
@@ -99,6 +120,7 @@ pub mod sinks;
 pub mod source_sender;
 #[allow(unreachable_pub)]
 pub mod sources;
+pub mod state_store;
 pub mod stats;
 #[cfg(feature = "api-client")]
 #[allow(unreachable_pub)]
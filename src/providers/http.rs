@@ -71,7 +71,7 @@ impl Default for HttpConfig {
 }
 
 /// Makes an HTTP request to the provided endpoint, returning the String body.
-async fn http_request(
+pub(super) async fn http_request(
     url: &Url,
     tls_options: &Option<TlsConfig>,
     headers: &IndexMap<String, String>,
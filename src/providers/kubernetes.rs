@@ -0,0 +1,744 @@
+//! The `kubernetes` provider assembles the configuration from `VectorPipeline` custom resources.
+//!
+//! Each `VectorPipeline` resource holds a fragment of the configuration: a set of sources,
+//! transforms, and sinks. The IDs of the components are prefixed with the namespace of the
+//! pipeline, so that pipelines in different namespaces can't interfere with each other. Inputs are
+//! resolved within the namespace of the pipeline, unless they name the namespace explicitly, as in
+//! `namespace/component`. The types of components that pipelines can use can be restricted with
+//! `allowed_types`. The provider watches the resources, reloads the topology whenever the
+//! assembled configuration changes, and reports whether each pipeline was applied in its status.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt, mem,
+    path::PathBuf,
+};
+
+use async_stream::stream;
+use futures::{pin_mut, Stream, StreamExt};
+use kube::{
+    api::{Api, DynamicObject, ListParams, Patch, PatchParams},
+    config::{KubeConfigOptions, Kubeconfig},
+    core::{ApiResource, GroupVersionKind},
+    runtime::watcher,
+    Client, Config as ClientConfig,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use vector_config::configurable_component;
+
+use crate::{
+    config::{provider::ProviderConfig, ComponentKey, ConfigBuilder},
+    signal,
+};
+
+use super::BuildResult;
+
+const GROUP: &str = "vector.dev";
+const VERSION: &str = "v1alpha1";
+const KIND: &str = "VectorPipeline";
+const PLURAL: &str = "vectorpipelines";
+
+/// The fields a pipeline can set in its `spec`.
+const PIPELINE_FIELDS: [&str; 3] = ["sources", "transforms", "sinks"];
+
+/// Configuration for the `kubernetes` provider.
+#[configurable_component(provider("kubernetes"))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct KubernetesConfig {
+    /// The namespace to watch `VectorPipeline` resources in.
+    ///
+    /// By default, resources in all namespaces are watched.
+    #[configurable(metadata(docs::examples = "observability"))]
+    namespace: Option<String>,
+
+    /// A label selector to restrict the `VectorPipeline` resources that are watched.
+    #[configurable(metadata(docs::examples = "vector.dev/instance=aggregator"))]
+    label_selector: Option<String>,
+
+    /// Optional path to a readable [kubeconfig][kubeconfig] file.
+    ///
+    /// If not set, a connection to Kubernetes is made using the in-cluster configuration.
+    ///
+    /// [kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+    #[configurable(metadata(docs::examples = "/path/to/.kube/config"))]
+    kube_config_file: Option<PathBuf>,
+
+    /// The types of components that pipelines are allowed to use.
+    #[configurable(derived)]
+    allowed_types: AllowedTypes,
+}
+
+/// The types of components that pipelines are allowed to use.
+///
+/// Pipelines using a type that isn't allowed are left out of the configuration.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct AllowedTypes {
+    /// The types of sources that pipelines are allowed to use.
+    ///
+    /// By default, all types are allowed.
+    #[configurable(metadata(docs::examples = "http_server"))]
+    sources: Option<Vec<String>>,
+
+    /// The types of transforms that pipelines are allowed to use.
+    ///
+    /// By default, all types are allowed.
+    #[configurable(metadata(docs::examples = "remap"))]
+    transforms: Option<Vec<String>>,
+
+    /// The types of sinks that pipelines are allowed to use.
+    ///
+    /// By default, all types are allowed.
+    #[configurable(metadata(docs::examples = "aws_s3"))]
+    sinks: Option<Vec<String>>,
+}
+
+impl AllowedTypes {
+    /// Returns whether a component of the given kind, one of `PIPELINE_FIELDS`, can have the type.
+    fn allows(&self, kind: &str, component_type: &str) -> bool {
+        let allowed = match kind {
+            "sources" => &self.sources,
+            "transforms" => &self.transforms,
+            _ => &self.sinks,
+        };
+        allowed.as_ref().map_or(true, |types| {
+            types.iter().any(|allowed| allowed == component_type)
+        })
+    }
+}
+
+impl_generate_config_from_default!(KubernetesConfig);
+
+#[async_trait::async_trait]
+impl ProviderConfig for KubernetesConfig {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> BuildResult {
+        let client = self
+            .client()
+            .await
+            .map_err(|error| vec![format!("Unable to create Kubernetes client: {}", error)])?;
+
+        let api_resource = api_resource();
+        let api: Api<DynamicObject> = match &self.namespace {
+            Some(namespace) => Api::namespaced_with(client.clone(), namespace, &api_resource),
+            None => Api::all_with(client.clone(), &api_resource),
+        };
+        let mut list_params = ListParams::default();
+        if let Some(label_selector) = &self.label_selector {
+            list_params = list_params.labels(label_selector);
+        }
+
+        let pipelines = api
+            .list(&list_params)
+            .await
+            .map_err(|error| vec![format!("Unable to list {} resources: {}", KIND, error)])?;
+
+        let mut reconciler = Reconciler::new(client, api_resource, self.allowed_types.clone());
+        reconciler.replace(pipelines.items);
+        let builder = reconciler.reconcile().await?.unwrap_or_default();
+
+        // Watch for changes to the pipelines.
+        signal_handler.add(watch_pipelines(api, list_params, reconciler));
+
+        Ok(builder)
+    }
+}
+
+impl KubernetesConfig {
+    async fn client(&self) -> crate::Result<Client> {
+        let client_config = match &self.kube_config_file {
+            Some(path) => {
+                ClientConfig::from_custom_kubeconfig(
+                    Kubeconfig::read_from(path)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        Ok(Client::try_from(client_config)?)
+    }
+}
+
+fn api_resource() -> ApiResource {
+    ApiResource::from_gvk_with_plural(&GroupVersionKind::gvk(GROUP, VERSION, KIND), PLURAL)
+}
+
+/// Watches the pipelines, yielding a reload signal whenever the assembled configuration changes.
+fn watch_pipelines(
+    api: Api<DynamicObject>,
+    list_params: ListParams,
+    mut reconciler: Reconciler,
+) -> impl Stream<Item = signal::SignalTo> {
+    stream! {
+        let events = watcher(api, list_params);
+        pin_mut!(events);
+
+        while let Some(event) = events.next().await {
+            match event {
+                Ok(watcher::Event::Applied(pipeline)) => reconciler.apply(pipeline),
+                Ok(watcher::Event::Deleted(pipeline)) => reconciler.delete(&pipeline),
+                Ok(watcher::Event::Restarted(pipelines)) => reconciler.replace(pipelines),
+                Err(error) => {
+                    warn!(message = "Watcher stream received an error. Retrying.", %error);
+                    continue;
+                }
+            }
+
+            // Errors were already reported in the status of the pipelines, and the running
+            // configuration is kept as is.
+            if let Ok(Some(builder)) = reconciler.reconcile().await {
+                info!(message = "Pipelines changed, reloading configuration.", kind = KIND);
+                yield signal::SignalTo::ReloadFromConfigBuilder(builder);
+            }
+        }
+    }
+}
+
+/// Identifies a pipeline resource.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+struct PipelineKey {
+    namespace: String,
+    name: String,
+}
+
+impl PipelineKey {
+    fn of(pipeline: &DynamicObject) -> Self {
+        Self {
+            namespace: pipeline.metadata.namespace.clone().unwrap_or_default(),
+            name: pipeline.metadata.name.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl fmt::Display for PipelineKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.namespace, self.name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+enum PipelineState {
+    /// The pipeline is part of the running configuration.
+    Applied,
+    /// The pipeline is invalid on its own, and was left out of the configuration.
+    Invalid,
+    /// The configuration assembled with this pipeline is invalid, so it wasn't applied.
+    Failed,
+}
+
+/// The status reported on a pipeline resource.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PipelineStatus {
+    state: PipelineState,
+    message: Option<String>,
+    observed_generation: Option<i64>,
+}
+
+/// The outcome of assembling the configuration from a set of pipelines.
+struct Assembly {
+    /// The assembled configuration, unless it is invalid.
+    builder: Result<ConfigBuilder, Vec<String>>,
+    /// The specs of the pipelines included in the configuration.
+    applied: BTreeMap<PipelineKey, Value>,
+    statuses: BTreeMap<PipelineKey, PipelineStatus>,
+}
+
+/// Assembles the configuration from a set of pipelines.
+///
+/// Pipelines that are invalid on their own, that use types that aren't allowed, that define a component already defined by another
+/// pipeline, or that consume the outputs of components that aren't defined are left out. The
+/// remaining pipelines are then assembled into a single configuration.
+fn assemble(
+    pipelines: &BTreeMap<PipelineKey, DynamicObject>,
+    allowed_types: &AllowedTypes,
+) -> Assembly {
+    let mut statuses = BTreeMap::new();
+    let mut invalid = |key: &PipelineKey, generation: Option<i64>, errors: Vec<String>| {
+        statuses.insert(
+            key.clone(),
+            PipelineStatus {
+                state: PipelineState::Invalid,
+                message: Some(errors.join("\n")),
+                observed_generation: generation,
+            },
+        );
+    };
+
+    let mut fragments = BTreeMap::new();
+    let mut owners = HashMap::<ComponentKey, &PipelineKey>::new();
+    for (key, pipeline) in pipelines {
+        let generation = pipeline.metadata.generation;
+        let (spec, fragment) = match parse_pipeline(pipeline, allowed_types) {
+            Ok(parsed) => parsed,
+            Err(errors) => {
+                invalid(key, generation, errors);
+                continue;
+            }
+        };
+
+        let ids = component_ids(&fragment).cloned().collect::<Vec<_>>();
+        let conflicts = ids
+            .iter()
+            .filter_map(|id| {
+                owners.get(id).map(|owner| {
+                    format!(
+                        "Component \"{}\" is already defined by pipeline \"{}\".",
+                        id, owner
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        if !conflicts.is_empty() {
+            invalid(key, generation, conflicts);
+            continue;
+        }
+
+        owners.extend(ids.into_iter().map(|id| (id, key)));
+        fragments.insert(key.clone(), (generation, spec, fragment));
+    }
+
+    // Leaving out a pipeline can leave the inputs of another pipeline dangling, so keep going until
+    // all the remaining inputs are defined.
+    loop {
+        let defined = fragments
+            .values()
+            .flat_map(|(_, _, fragment)| component_ids(fragment))
+            .map(ComponentKey::id)
+            .collect::<HashSet<_>>();
+        let dangling = fragments
+            .iter()
+            .filter_map(|(key, (generation, _, fragment))| {
+                let errors = dangling_inputs(fragment, &defined);
+                (!errors.is_empty()).then(|| (key.clone(), *generation, errors))
+            })
+            .collect::<Vec<_>>();
+        if dangling.is_empty() {
+            break;
+        }
+        for (key, generation, errors) in dangling {
+            fragments.remove(&key);
+            invalid(&key, generation, errors);
+        }
+    }
+
+    let mut builder = ConfigBuilder::default();
+    let mut errors = Vec::new();
+    for (_, _, fragment) in fragments.values() {
+        if let Err(append_errors) = builder.append(fragment.clone()) {
+            errors.extend(append_errors);
+        }
+    }
+    if errors.is_empty() {
+        if let Err(build_errors) = builder.clone().build() {
+            errors = build_errors;
+        }
+    }
+
+    let (state, message) = if errors.is_empty() {
+        (PipelineState::Applied, None)
+    } else {
+        (
+            PipelineState::Failed,
+            Some(format!(
+                "The configuration assembled from all pipelines is invalid:\n{}",
+                errors.join("\n")
+            )),
+        )
+    };
+    let mut applied = BTreeMap::new();
+    for (key, (generation, spec, _)) in fragments {
+        statuses.insert(
+            key.clone(),
+            PipelineStatus {
+                state,
+                message: message.clone(),
+                observed_generation: generation,
+            },
+        );
+        applied.insert(key, spec);
+    }
+
+    Assembly {
+        builder: if errors.is_empty() {
+            Ok(builder)
+        } else {
+            Err(errors)
+        },
+        applied,
+        statuses,
+    }
+}
+
+/// Parses a pipeline, prefixing the IDs of its components and their inputs with its namespace.
+///
+/// The returned spec is the one with the prefixed IDs.
+fn parse_pipeline(
+    pipeline: &DynamicObject,
+    allowed_types: &AllowedTypes,
+) -> Result<(Value, ConfigBuilder), Vec<String>> {
+    let spec = pipeline
+        .data
+        .get("spec")
+        .and_then(Value::as_object)
+        .ok_or_else(|| vec!["The pipeline has no `spec`.".to_owned()])?;
+    let unsupported = spec
+        .keys()
+        .filter(|field| !PIPELINE_FIELDS.contains(&field.as_str()))
+        .map(|field| {
+            format!(
+                "Unsupported field `{}`, a pipeline can only define {}.",
+                field, "`sources`, `transforms`, and `sinks`"
+            )
+        })
+        .collect::<Vec<_>>();
+    if !unsupported.is_empty() {
+        return Err(unsupported);
+    }
+
+    let namespace = pipeline.metadata.namespace.as_deref().unwrap_or_default();
+    let mut spec = spec.clone();
+    let mut errors = Vec::new();
+    for kind in PIPELINE_FIELDS {
+        let Some(components) = spec.get_mut(kind).and_then(Value::as_object_mut) else {
+            // Left to deserialization to report.
+            continue;
+        };
+        for (id, mut component) in mem::take(components) {
+            if id.contains('/') {
+                errors.push(format!(
+                    "Component ID \"{}\" is invalid, as it contains `/`.",
+                    id
+                ));
+            }
+            if let Some(component_type) = component.get("type").and_then(Value::as_str) {
+                if !allowed_types.allows(kind, component_type) {
+                    errors.push(format!(
+                        "Component \"{}\" has type `{}`, which pipelines aren't allowed to use.",
+                        id, component_type
+                    ));
+                }
+            }
+            if let Some(inputs) = component.get_mut("inputs").and_then(Value::as_array_mut) {
+                for input in inputs {
+                    if let Value::String(input) = input {
+                        // Inputs that already name a namespace refer to another namespace.
+                        if !input.contains('/') {
+                            *input = namespaced(namespace, input);
+                        }
+                    }
+                }
+            }
+            components.insert(namespaced(namespace, &id), component);
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // Unlike configuration files, environment variables aren't interpolated in pipelines, as they
+    // might hold secrets that the authors of pipelines shouldn't have access to.
+    let spec = Value::Object(spec);
+    let fragment = serde_json::from_value(spec.clone())
+        .map_err(|error| vec![format!("Invalid pipeline: {}", error)])?;
+    Ok((spec, fragment))
+}
+
+fn namespaced(namespace: &str, id: &str) -> String {
+    format!("{}/{}", namespace, id)
+}
+
+fn component_ids(fragment: &ConfigBuilder) -> impl Iterator<Item = &ComponentKey> {
+    fragment
+        .sources
+        .keys()
+        .chain(fragment.transforms.keys())
+        .chain(fragment.sinks.keys())
+}
+
+/// Returns an error for each input of the fragment that doesn't refer to a defined component.
+fn dangling_inputs(fragment: &ConfigBuilder, defined: &HashSet<&str>) -> Vec<String> {
+    let inputs = fragment
+        .transforms
+        .iter()
+        .map(|(id, transform)| (id, &transform.inputs))
+        .chain(fragment.sinks.iter().map(|(id, sink)| (id, &sink.inputs)));
+
+    let mut errors = Vec::new();
+    for (id, inputs) in inputs {
+        for input in inputs {
+            // Wildcards can legitimately match no components, so they are left to the topology.
+            if input.contains('*') {
+                continue;
+            }
+            let component = input.split_once('.').map_or(input.as_str(), |(id, _)| id);
+            if !defined.contains(component) {
+                errors.push(format!(
+                    "Input \"{}\" of component \"{}\" doesn't match any component.",
+                    input, id
+                ));
+            }
+        }
+    }
+    errors
+}
+
+/// Tracks the pipelines, assembling the configuration from them and reporting their status.
+struct Reconciler {
+    client: Client,
+    api_resource: ApiResource,
+    allowed_types: AllowedTypes,
+    pipelines: BTreeMap<PipelineKey, DynamicObject>,
+    /// The specs of the pipelines in the configuration that was last assembled successfully.
+    applied: Option<BTreeMap<PipelineKey, Value>>,
+    /// The statuses that were last reported.
+    reported: HashMap<PipelineKey, PipelineStatus>,
+}
+
+impl Reconciler {
+    fn new(client: Client, api_resource: ApiResource, allowed_types: AllowedTypes) -> Self {
+        Self {
+            client,
+            api_resource,
+            allowed_types,
+            pipelines: BTreeMap::new(),
+            applied: None,
+            reported: HashMap::new(),
+        }
+    }
+
+    fn apply(&mut self, pipeline: DynamicObject) {
+        self.pipelines.insert(PipelineKey::of(&pipeline), pipeline);
+    }
+
+    fn delete(&mut self, pipeline: &DynamicObject) {
+        self.pipelines.remove(&PipelineKey::of(pipeline));
+    }
+
+    fn replace(&mut self, pipelines: Vec<DynamicObject>) {
+        self.pipelines = pipelines
+            .into_iter()
+            .map(|pipeline| (PipelineKey::of(&pipeline), pipeline))
+            .collect();
+    }
+
+    /// Assembles the configuration from the current pipelines and reports their status.
+    ///
+    /// Returns the assembled configuration, if it changed since it was last assembled.
+    async fn reconcile(&mut self) -> Result<Option<ConfigBuilder>, Vec<String>> {
+        let assembly = assemble(&self.pipelines, &self.allowed_types);
+        self.report(&assembly.statuses).await;
+
+        let builder = assembly.builder?;
+        if self.applied.as_ref() == Some(&assembly.applied) {
+            return Ok(None);
+        }
+        self.applied = Some(assembly.applied);
+        Ok(Some(builder))
+    }
+
+    /// Reports the statuses that changed since they were last reported.
+    async fn report(&mut self, statuses: &BTreeMap<PipelineKey, PipelineStatus>) {
+        self.reported.retain(|key, _| statuses.contains_key(key));
+
+        for (key, status) in statuses {
+            if self.reported.get(key) == Some(status) {
+                continue;
+            }
+            if let Some(message) = &status.message {
+                warn!(
+                    message = "Pipeline not applied.",
+                    pipeline = %key,
+                    state = ?status.state,
+                    reason = %message,
+                );
+            }
+
+            let api: Api<DynamicObject> =
+                Api::namespaced_with(self.client.clone(), &key.namespace, &self.api_resource);
+            let patch = json!({ "status": status });
+            match api
+                .patch_status(&key.name, &PatchParams::default(), &Patch::Merge(&patch))
+                .await
+            {
+                Ok(_) => {
+                    self.reported.insert(key.clone(), status.clone());
+                }
+                Err(error) => {
+                    warn!(message = "Failed to report pipeline status.", pipeline = %key, %error)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pipeline(name: &str, spec: Value) -> (PipelineKey, DynamicObject) {
+        namespaced_pipeline("default", name, spec)
+    }
+
+    fn namespaced_pipeline(
+        namespace: &str,
+        name: &str,
+        spec: Value,
+    ) -> (PipelineKey, DynamicObject) {
+        let mut pipeline = DynamicObject::new(name, &api_resource()).within(namespace);
+        pipeline.metadata.generation = Some(1);
+        pipeline.data = json!({ "spec": spec });
+        (PipelineKey::of(&pipeline), pipeline)
+    }
+
+    fn state(assembly: &Assembly, name: &str) -> PipelineState {
+        assembly.statuses[&PipelineKey {
+            namespace: "default".into(),
+            name: name.into(),
+        }]
+            .state
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<KubernetesConfig>();
+    }
+
+    #[test]
+    fn assembles_pipelines_across_resources() {
+        let pipelines = BTreeMap::from([
+            pipeline(
+                "shared",
+                json!({ "sources": { "in": { "type": "demo_logs", "format": "json" } } }),
+            ),
+            pipeline(
+                "team",
+                json!({ "sinks": { "out": { "type": "blackhole", "inputs": ["in"] } } }),
+            ),
+        ]);
+
+        let assembly = assemble(&pipelines, &AllowedTypes::default());
+        let builder = assembly.builder.unwrap();
+        assert!(builder
+            .sources
+            .contains_key(&ComponentKey::from("default/in")));
+        assert_eq!(
+            builder.sinks[&ComponentKey::from("default/out")].inputs,
+            vec!["default/in".to_owned()]
+        );
+        assert_eq!(state(&assembly, "shared"), PipelineState::Applied);
+        assert_eq!(state(&assembly, "team"), PipelineState::Applied);
+    }
+
+    #[test]
+    fn leaves_out_invalid_pipelines() {
+        let pipelines = BTreeMap::from([
+            pipeline(
+                "a",
+                json!({
+                    "sources": { "in": { "type": "demo_logs", "format": "json" } },
+                    "sinks": { "out": { "type": "blackhole", "inputs": ["in"] } },
+                }),
+            ),
+            // Conflicts with the source defined in `a`.
+            pipeline(
+                "b",
+                json!({ "sources": { "in": { "type": "demo_logs", "format": "json" } } }),
+            ),
+            // Sets global options.
+            pipeline("c", json!({ "data_dir": "/tmp" })),
+            // Consumes a component that doesn't exist.
+            pipeline(
+                "d",
+                json!({
+                    "transforms": {
+                        "parse": { "type": "remap", "inputs": ["missing"], "source": "" },
+                    },
+                }),
+            ),
+            // Consumes the outputs of the invalid pipeline `d`.
+            pipeline(
+                "e",
+                json!({ "sinks": { "parsed": { "type": "blackhole", "inputs": ["parse"] } } }),
+            ),
+        ]);
+
+        let assembly = assemble(&pipelines, &AllowedTypes::default());
+        assert!(assembly.builder.is_ok());
+        assert_eq!(state(&assembly, "a"), PipelineState::Applied);
+        for name in ["b", "c", "d", "e"] {
+            assert_eq!(state(&assembly, name), PipelineState::Invalid);
+        }
+        assert_eq!(assembly.applied.len(), 1);
+    }
+
+    #[test]
+    fn isolates_pipelines_by_namespace() {
+        let spec = json!({
+            "sources": { "in": { "type": "demo_logs", "format": "json" } },
+            "sinks": { "out": { "type": "blackhole", "inputs": ["in"] } },
+        });
+        let pipelines = BTreeMap::from([
+            namespaced_pipeline("a", "logs", spec.clone()),
+            namespaced_pipeline("b", "logs", spec),
+            // Consumes a component of another namespace explicitly.
+            namespaced_pipeline(
+                "c",
+                "logs",
+                json!({ "sinks": { "out": { "type": "blackhole", "inputs": ["a/in"] } } }),
+            ),
+            // Can't escape its namespace through the IDs of its components.
+            namespaced_pipeline(
+                "c",
+                "escape",
+                json!({ "sources": { "b/in": { "type": "demo_logs", "format": "json" } } }),
+            ),
+        ]);
+
+        let assembly = assemble(&pipelines, &AllowedTypes::default());
+        let builder = assembly.builder.unwrap();
+        for (namespace, input) in [("a", "a/in"), ("b", "b/in"), ("c", "a/in")] {
+            let sink = &builder.sinks[&ComponentKey::from(format!("{}/out", namespace))];
+            assert_eq!(sink.inputs, vec![input.to_owned()]);
+        }
+        assert_eq!(builder.sources.len(), 2);
+        assert_eq!(
+            assembly.statuses[&PipelineKey {
+                namespace: "c".into(),
+                name: "escape".into(),
+            }]
+                .state,
+            PipelineState::Invalid
+        );
+    }
+
+    #[test]
+    fn leaves_out_pipelines_using_disallowed_types() {
+        let pipelines = BTreeMap::from([
+            pipeline(
+                "allowed",
+                json!({ "sources": { "in": { "type": "demo_logs", "format": "json" } } }),
+            ),
+            pipeline(
+                "disallowed",
+                json!({
+                    "sinks": {
+                        "out": { "type": "file", "inputs": ["in"], "path": "/etc/passwd", "encoding": { "codec": "json" } },
+                    },
+                }),
+            ),
+        ]);
+        let allowed_types = AllowedTypes {
+            sinks: Some(vec!["blackhole".to_owned()]),
+            ..Default::default()
+        };
+
+        let assembly = assemble(&pipelines, &allowed_types);
+        assert!(assembly.builder.is_ok());
+        assert_eq!(state(&assembly, "allowed"), PipelineState::Applied);
+        assert_eq!(state(&assembly, "disallowed"), PipelineState::Invalid);
+    }
+}
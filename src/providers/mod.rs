@@ -8,6 +8,9 @@ use crate::{
 };
 
 pub mod http;
+#[cfg(feature = "kubernetes")]
+pub mod kubernetes;
+pub mod remote;
 
 pub type BuildResult = std::result::Result<ConfigBuilder, Vec<String>>;
 
@@ -19,6 +22,13 @@ pub type BuildResult = std::result::Result<ConfigBuilder, Vec<String>>;
 pub enum Providers {
     /// HTTP.
     Http(http::HttpConfig),
+
+    /// Kubernetes.
+    #[cfg(feature = "kubernetes")]
+    Kubernetes(kubernetes::KubernetesConfig),
+
+    /// Remote.
+    Remote(remote::RemoteConfig),
 }
 
 // TODO: Use `enum_dispatch` here.
@@ -26,6 +36,9 @@ impl NamedComponent for Providers {
     fn get_component_name(&self) -> &'static str {
         match self {
             Self::Http(config) => config.get_component_name(),
+            #[cfg(feature = "kubernetes")]
+            Self::Kubernetes(config) => config.get_component_name(),
+            Self::Remote(config) => config.get_component_name(),
         }
     }
 }
@@ -0,0 +1,640 @@
+//! The `remote` provider retrieves configuration bundles from a control plane.
+//!
+//! A bundle is a JSON document wrapping a configuration along with its version, an optional
+//! signature, and the percentage of instances it should be rolled out to:
+//!
+//! ```json
+//! {
+//!   "version": "2023-04-01.1",
+//!   "format": "toml",
+//!   "config": "[sources.in]\ntype = \"stdin\"\n...",
+//!   "signature": "<base64-encoded signature of the version and configuration>",
+//!   "rollout_percentage": 25
+//! }
+//! ```
+//!
+//! Bundles are validated before they are applied, and the last applied bundle is cached locally
+//! so Vector can start with it when the control plane is unreachable. Versions must increase with
+//! each bundle: bundles with a version older than the last applied one are rejected, so a signed
+//! bundle can't be replayed to roll an instance back.
+
+use std::{cmp::Ordering, fs, path::PathBuf, time::Duration};
+
+use async_stream::stream;
+use futures::Stream;
+use indexmap::IndexMap;
+use openssl::{
+    hash::MessageDigest,
+    pkey::{Id, PKey, Public},
+    sign::Verifier,
+};
+use serde::Deserialize;
+use tokio::time;
+use url::Url;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{self, format::Format, provider::ProviderConfig, ConfigBuilder, ProxyConfig},
+    signal,
+    tls::TlsConfig,
+};
+
+use super::{
+    http::{http_request, RequestConfig},
+    BuildResult,
+};
+
+const INSTANCE_ID_HEADER: &str = "X-Vector-Instance-Id";
+const VERSION_HEADER: &str = "X-Vector-Config-Version";
+
+/// Configuration for the `remote` provider.
+#[configurable_component(provider("remote"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct RemoteConfig {
+    /// URL of the endpoint serving the configuration bundles.
+    #[configurable(metadata(docs::examples = "https://control-plane.example.com/vector/config"))]
+    url: Option<Url>,
+
+    #[configurable(derived)]
+    request: RequestConfig,
+
+    /// How often to poll the endpoint, in seconds.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    poll_interval_secs: u64,
+
+    /// The identifier of this instance, used to decide whether it is part of a staged rollout.
+    ///
+    /// It is sent to the endpoint in the `X-Vector-Instance-Id` header, along with the version of
+    /// the running configuration in the `X-Vector-Config-Version` header. By default, the host name
+    /// is used.
+    #[configurable(metadata(docs::examples = "aggregator-0"))]
+    instance_id: Option<String>,
+
+    /// Only apply bundles with this version.
+    ///
+    /// Other versions are ignored, which allows holding an instance back on a known version
+    /// regardless of what the endpoint serves. The pinned version is applied even if it is older
+    /// than the last applied version.
+    #[configurable(metadata(docs::examples = "2023-04-01.1"))]
+    version: Option<String>,
+
+    /// Path to a PEM-encoded public key used to verify the signature of the bundles.
+    ///
+    /// RSA and ECDSA signatures must use SHA-256. Ed25519 keys are also supported. When set,
+    /// unsigned bundles are rejected.
+    #[configurable(metadata(docs::examples = "/etc/vector/remote.pub"))]
+    public_key_file: Option<PathBuf>,
+
+    /// Path to the file where the last applied bundle is cached.
+    ///
+    /// If the endpoint can't be reached when Vector starts, the cached bundle is used instead. The
+    /// version of the cached bundle is also the last applied version, so bundles older than it are
+    /// rejected after a restart.
+    #[configurable(metadata(docs::examples = "/var/lib/vector/remote_config.json"))]
+    cache_file: Option<PathBuf>,
+
+    #[serde(flatten)]
+    tls_options: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    proxy: ProxyConfig,
+}
+
+impl Default for RemoteConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            request: RequestConfig::default(),
+            poll_interval_secs: 30,
+            instance_id: None,
+            version: None,
+            public_key_file: None,
+            cache_file: None,
+            tls_options: None,
+            proxy: Default::default(),
+        }
+    }
+}
+
+impl_generate_config_from_default!(RemoteConfig);
+
+#[async_trait::async_trait]
+impl ProviderConfig for RemoteConfig {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> BuildResult {
+        let url = self
+            .url
+            .take()
+            .ok_or_else(|| vec!["URL is required for the `remote` provider.".to_owned()])?;
+
+        let public_key = self
+            .public_key_file
+            .as_ref()
+            .map(|path| {
+                fs::read(path)
+                    .map_err(|error| error.to_string())
+                    .and_then(|pem| {
+                        PKey::public_key_from_pem(&pem).map_err(|error| error.to_string())
+                    })
+                    .map_err(|error| {
+                        vec![format!(
+                            "Unable to load public key {:?}: {}",
+                            path.display(),
+                            error
+                        )]
+                    })
+            })
+            .transpose()?;
+        let instance_id = match self.instance_id.clone() {
+            Some(instance_id) => instance_id,
+            None => crate::get_hostname()
+                .map_err(|error| vec![format!("Unable to get the host name: {}", error)])?,
+        };
+
+        let mut client = RemoteClient {
+            url,
+            tls_options: self.tls_options.take(),
+            headers: self.request.headers.clone(),
+            proxy: ProxyConfig::from_env().merge(&self.proxy),
+            bundles: Bundles {
+                instance_id,
+                pinned_version: self.version.clone(),
+                public_key,
+            },
+            cache_file: self.cache_file.clone(),
+            current_version: None,
+        };
+        client.current_version = client.cached_version();
+
+        let config_builder = match client.poll().await {
+            Ok(Some(config_builder)) => config_builder,
+            Ok(None) => client.load_cache().map_err(|error| {
+                vec![format!(
+                    "The endpoint didn't serve an applicable configuration, and {}",
+                    error
+                )]
+            })?,
+            Err(error) => {
+                warn!(
+                    message = "Unable to retrieve configuration, using the cached configuration.",
+                    %error,
+                );
+                client.load_cache().map_err(|cache_error| {
+                    vec![format!(
+                        "Unable to retrieve configuration: {}, and {}",
+                        error, cache_error
+                    )]
+                })?
+            }
+        };
+
+        // Poll for changes to remote configuration.
+        signal_handler.add(poll_remote(
+            Duration::from_secs(self.poll_interval_secs),
+            client,
+        ));
+
+        Ok(config_builder)
+    }
+}
+
+/// The format of the configuration held in a bundle.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum BundleFormat {
+    #[default]
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl From<BundleFormat> for Format {
+    fn from(format: BundleFormat) -> Self {
+        match format {
+            BundleFormat::Toml => Format::Toml,
+            BundleFormat::Json => Format::Json,
+            BundleFormat::Yaml => Format::Yaml,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Bundle {
+    version: String,
+    #[serde(default)]
+    format: BundleFormat,
+    config: String,
+    signature: Option<String>,
+    #[serde(default = "default_rollout_percentage")]
+    rollout_percentage: u8,
+}
+
+const fn default_rollout_percentage() -> u8 {
+    100
+}
+
+impl Bundle {
+    /// The signed payload: the version and the configuration, separated by a newline.
+    fn signed_payload(&self) -> Vec<u8> {
+        [self.version.as_bytes(), b"\n", self.config.as_bytes()].concat()
+    }
+}
+
+/// Decides which bundles are applied.
+struct Bundles {
+    instance_id: String,
+    pinned_version: Option<String>,
+    public_key: Option<PKey<Public>>,
+}
+
+impl Bundles {
+    /// Evaluates a bundle, returning its validated configuration if it should be applied.
+    ///
+    /// A bundle is skipped if it has the version already applied, doesn't match the pinned
+    /// version, or this instance isn't part of its rollout. The rollout is ignored when no
+    /// configuration was applied yet, as an instance must start with some configuration. Unless it
+    /// is pinned, a bundle with a version older than the applied one is rejected.
+    fn evaluate(
+        &self,
+        bundle: &Bundle,
+        current_version: Option<&str>,
+    ) -> Result<Option<ConfigBuilder>, String> {
+        if current_version == Some(bundle.version.as_str()) {
+            return Ok(None);
+        }
+        match &self.pinned_version {
+            Some(pinned_version) if pinned_version != &bundle.version => {
+                debug!(
+                    message = "Skipping configuration bundle not matching the pinned version.",
+                    version = %bundle.version,
+                    %pinned_version,
+                );
+                return Ok(None);
+            }
+            Some(_) => {}
+            None => {
+                if let Some(current_version) = current_version {
+                    if compare_versions(&bundle.version, current_version) != Ordering::Greater {
+                        return Err(format!(
+                            "The version isn't newer than the applied version {:?}.",
+                            current_version
+                        ));
+                    }
+                }
+            }
+        }
+        if current_version.is_some()
+            && !in_rollout(
+                &self.instance_id,
+                &bundle.version,
+                bundle.rollout_percentage,
+            )
+        {
+            debug!(
+                message = "Skipping configuration bundle not rolled out to this instance yet.",
+                version = %bundle.version,
+                rollout_percentage = %bundle.rollout_percentage,
+            );
+            return Ok(None);
+        }
+
+        self.validate(bundle).map(Some)
+    }
+
+    /// Verifies the signature of a bundle and validates its configuration.
+    fn validate(&self, bundle: &Bundle) -> Result<ConfigBuilder, String> {
+        if let Some(public_key) = &self.public_key {
+            verify_signature(public_key, bundle)?;
+        }
+
+        let (config_builder, warnings): (ConfigBuilder, _) =
+            config::load(bundle.config.as_bytes(), bundle.format.into())
+                .map_err(|errors| errors.join("\n"))?;
+        for warning in warnings {
+            warn!("{}", warning);
+        }
+        config_builder
+            .clone()
+            .build()
+            .map_err(|errors| errors.join("\n"))?;
+
+        Ok(config_builder)
+    }
+}
+
+fn verify_signature(public_key: &PKey<Public>, bundle: &Bundle) -> Result<(), String> {
+    let signature = bundle
+        .signature
+        .as_deref()
+        .ok_or_else(|| "The configuration bundle isn't signed.".to_owned())?;
+    let signature = openssl::base64::decode_block(signature)
+        .map_err(|error| format!("Invalid signature encoding: {}", error))?;
+
+    let verifier = if public_key.id() == Id::ED25519 {
+        Verifier::new_without_digest(public_key)
+    } else {
+        Verifier::new(MessageDigest::sha256(), public_key)
+    };
+    let verified = verifier
+        .and_then(|mut verifier| verifier.verify_oneshot(&signature, &bundle.signed_payload()))
+        .map_err(|error| format!("Unable to verify signature: {}", error))?;
+
+    if verified {
+        Ok(())
+    } else {
+        Err("The signature of the configuration bundle is invalid.".to_owned())
+    }
+}
+
+/// Compares two versions, comparing their runs of digits numerically and the rest lexically.
+///
+/// For instance, `2023-04-01.2` is older than `2023-04-01.10`, and `1.9` is older than `1.10`.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    version_parts(a).cmp(&version_parts(b))
+}
+
+/// Splits a version into runs of digits and runs of other characters.
+///
+/// Runs of digits are keyed by their length without leading zeros, so they compare numerically
+/// regardless of their size, and sort before runs of other characters.
+fn version_parts(version: &str) -> Vec<(bool, usize, &str)> {
+    let mut parts = Vec::new();
+    let mut rest = version;
+    while let Some(first) = rest.chars().next() {
+        let is_digit = first.is_ascii_digit();
+        let end = rest
+            .find(|c: char| c.is_ascii_digit() != is_digit)
+            .unwrap_or(rest.len());
+        let (part, remaining) = rest.split_at(end);
+        if is_digit {
+            let number = part.trim_start_matches('0');
+            parts.push((false, number.len(), number));
+        } else {
+            parts.push((true, 0, part));
+        }
+        rest = remaining;
+    }
+    parts
+}
+
+/// Whether the instance is part of the rollout of a version.
+///
+/// Instances are assigned a stable bucket per version, so a rollout reaches the same instances as
+/// its percentage increases, while successive versions reach instances in a different order.
+fn in_rollout(instance_id: &str, version: &str, rollout_percentage: u8) -> bool {
+    let digest = openssl::sha::sha256(format!("{}\n{}", instance_id, version).as_bytes());
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bytes) % 100 < u64::from(rollout_percentage.min(100))
+}
+
+struct RemoteClient {
+    url: Url,
+    tls_options: Option<TlsConfig>,
+    headers: IndexMap<String, String>,
+    proxy: ProxyConfig,
+    bundles: Bundles,
+    cache_file: Option<PathBuf>,
+    current_version: Option<String>,
+}
+
+impl RemoteClient {
+    /// Retrieves the bundle served by the endpoint, returning its configuration if it should be
+    /// applied.
+    async fn poll(&mut self) -> Result<Option<ConfigBuilder>, String> {
+        let mut headers = self.headers.clone();
+        headers.insert(
+            INSTANCE_ID_HEADER.to_owned(),
+            self.bundles.instance_id.clone(),
+        );
+        if let Some(version) = &self.current_version {
+            headers.insert(VERSION_HEADER.to_owned(), version.clone());
+        }
+
+        let body = http_request(&self.url, &self.tls_options, &headers, &self.proxy)
+            .await
+            .map_err(ToOwned::to_owned)?;
+        let bundle: Bundle = serde_json::from_slice(&body)
+            .map_err(|error| format!("Invalid configuration bundle: {}", error))?;
+
+        let config_builder = self
+            .bundles
+            .evaluate(&bundle, self.current_version.as_deref())
+            .map_err(|error| {
+                format!(
+                    "Configuration bundle {:?} rejected: {}",
+                    bundle.version, error
+                )
+            })?;
+        if config_builder.is_some() {
+            info!(message = "Applying configuration bundle.", version = %bundle.version);
+            self.current_version = Some(bundle.version);
+            self.write_cache(&body);
+        }
+        Ok(config_builder)
+    }
+
+    /// Returns the version of the cached bundle, which is the last version applied.
+    fn cached_version(&self) -> Option<String> {
+        let path = self.cache_file.as_ref()?;
+        let data = fs::read(path).ok()?;
+        serde_json::from_slice::<Bundle>(&data)
+            .map(|bundle| bundle.version)
+            .ok()
+    }
+
+    /// Loads the configuration from the cached bundle.
+    fn load_cache(&mut self) -> Result<ConfigBuilder, String> {
+        let path = self
+            .cache_file
+            .as_ref()
+            .ok_or_else(|| "no cache file is configured.".to_owned())?;
+        let bundle: Bundle = fs::read(path)
+            .map_err(|error| error.to_string())
+            .and_then(|data| serde_json::from_slice(&data).map_err(|error| error.to_string()))
+            .map_err(|error| format!("the cached bundle couldn't be read: {}", error))?;
+
+        // The cache is verified again, as it lives outside of the control plane.
+        let config_builder = self
+            .bundles
+            .validate(&bundle)
+            .map_err(|error| format!("the cached bundle is invalid: {}", error))?;
+        info!(message = "Using cached configuration bundle.", version = %bundle.version);
+        self.current_version = Some(bundle.version);
+        Ok(config_builder)
+    }
+
+    fn write_cache(&self, bundle: &[u8]) {
+        if let Some(path) = &self.cache_file {
+            // Write to a temporary file first, so the cache is never left half written.
+            let tmp_path = path.with_extension("tmp");
+            if let Err(error) =
+                fs::write(&tmp_path, bundle).and_then(|_| fs::rename(&tmp_path, path))
+            {
+                warn!(message = "Unable to cache configuration bundle.", path = ?path, %error);
+            }
+        }
+    }
+}
+
+/// Polls the endpoint every `interval`, yielding the configurations that should be applied.
+fn poll_remote(
+    interval: Duration,
+    mut client: RemoteClient,
+) -> impl Stream<Item = signal::SignalTo> {
+    let mut interval = time::interval_at(time::Instant::now() + interval, interval);
+
+    stream! {
+        loop {
+            interval.tick().await;
+
+            // On failure, the running configuration is kept.
+            match client.poll().await {
+                Ok(Some(config_builder)) => {
+                    yield signal::SignalTo::ReloadFromConfigBuilder(config_builder);
+                }
+                Ok(None) => {}
+                Err(error) => error!(
+                    message = "Unable to retrieve configuration.",
+                    %error,
+                    url = ?client.url.as_str(),
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::{pkey::Private, sign::Signer};
+
+    use super::*;
+
+    const CONFIG: &str = r#"
+        [sources.in]
+        type = "demo_logs"
+        format = "json"
+
+        [sinks.out]
+        type = "blackhole"
+        inputs = ["in"]
+    "#;
+
+    fn bundle(version: &str, rollout_percentage: u8) -> Bundle {
+        Bundle {
+            version: version.to_owned(),
+            format: BundleFormat::Toml,
+            config: CONFIG.to_owned(),
+            signature: None,
+            rollout_percentage,
+        }
+    }
+
+    fn sign(key: &PKey<Private>, bundle: &mut Bundle) {
+        let signature = Signer::new_without_digest(key)
+            .unwrap()
+            .sign_oneshot_to_vec(&bundle.signed_payload())
+            .unwrap();
+        bundle.signature = Some(openssl::base64::encode_block(&signature));
+    }
+
+    fn bundles(public_key: Option<PKey<Public>>) -> Bundles {
+        Bundles {
+            instance_id: "vector-0".to_owned(),
+            pinned_version: None,
+            public_key,
+        }
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RemoteConfig>();
+    }
+
+    #[test]
+    fn verifies_signatures() {
+        let key = PKey::generate_ed25519().unwrap();
+        let public_key = PKey::public_key_from_der(&key.public_key_to_der().unwrap()).unwrap();
+        let bundles = bundles(Some(public_key));
+
+        let mut signed = bundle("1", 100);
+        sign(&key, &mut signed);
+        assert!(bundles.evaluate(&signed, None).unwrap().is_some());
+
+        assert!(bundles.evaluate(&bundle("1", 100), None).is_err());
+
+        signed.version = "2".to_owned();
+        assert!(bundles.evaluate(&signed, None).is_err());
+    }
+
+    #[test]
+    fn skips_running_and_unpinned_versions() {
+        let mut bundles = bundles(None);
+        assert!(bundles
+            .evaluate(&bundle("1", 100), Some("1"))
+            .unwrap()
+            .is_none());
+
+        bundles.pinned_version = Some("1".to_owned());
+        assert!(bundles.evaluate(&bundle("2", 100), None).unwrap().is_none());
+        assert!(bundles.evaluate(&bundle("1", 100), None).unwrap().is_some());
+    }
+
+    #[test]
+    fn rejects_older_versions() {
+        let mut bundles = bundles(None);
+        assert!(bundles
+            .evaluate(&bundle("2023-04-01.10", 100), Some("2023-04-01.9"))
+            .unwrap()
+            .is_some());
+        assert!(bundles
+            .evaluate(&bundle("2023-04-01.9", 100), Some("2023-04-01.10"))
+            .is_err());
+        assert!(bundles
+            .evaluate(&bundle("2023-03-31.1", 100), Some("2023-04-01.1"))
+            .is_err());
+
+        // Operators can still roll back by pinning a version.
+        bundles.pinned_version = Some("2023-03-31.1".to_owned());
+        assert!(bundles
+            .evaluate(&bundle("2023-03-31.1", 100), Some("2023-04-01.1"))
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn compares_versions_numerically() {
+        assert_eq!(compare_versions("1.9", "1.10"), Ordering::Less);
+        assert_eq!(compare_versions("1.10", "1.010"), Ordering::Equal);
+        assert_eq!(
+            compare_versions("2023-04-02", "2023-04-01.1"),
+            Ordering::Greater
+        );
+        assert_eq!(compare_versions("v2", "v10"), Ordering::Less);
+        assert_eq!(compare_versions("1", "1.1"), Ordering::Less);
+    }
+
+    #[test]
+    fn rollout_grows_with_percentage() {
+        let instances = (0..1000)
+            .map(|i| format!("vector-{}", i))
+            .collect::<Vec<_>>();
+        let rolled_out = |percentage| {
+            instances
+                .iter()
+                .filter(|instance| in_rollout(instance, "1", percentage))
+                .collect::<Vec<_>>()
+        };
+
+        let quarter = rolled_out(25);
+        let half = rolled_out(50);
+        assert!((150..350).contains(&quarter.len()));
+        assert!(quarter.iter().all(|instance| half.contains(instance)));
+        assert!(rolled_out(0).is_empty());
+        assert_eq!(rolled_out(100).len(), instances.len());
+    }
+}
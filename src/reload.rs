@@ -0,0 +1,247 @@
+#![allow(missing_docs)]
+use std::path::PathBuf;
+
+use bytes::Bytes;
+use clap::Parser;
+use colored::*;
+use exitcode::ExitCode;
+use http::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    Request, StatusCode,
+};
+use hyper::Body;
+use url::Url;
+
+use crate::{
+    api::reload::{ComponentChanges, ReloadDiff, ReloadResponse},
+    cli::handle_config_errors,
+    config::{self, ProxyConfig},
+    http::HttpClient,
+};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Send the config to the reload endpoint of a running Vector's API.
+    ///
+    /// This is currently the only supported way of reloading with this command. To reload from
+    /// the config files on disk instead, send `SIGHUP` to the Vector process.
+    #[arg(long)]
+    pub via_api: bool,
+
+    /// Vector API config reload endpoint.
+    #[arg(short, long, default_value_t = default_url())]
+    pub url: Url,
+
+    /// Token used to authenticate with the reload endpoint, as configured by `api.reload_token`.
+    #[arg(short, long, env = "VECTOR_API_RELOAD_TOKEN")]
+    pub token: String,
+
+    /// Validate the config and show what would change, without applying it.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Vector config files in TOML format to reload with.
+    #[arg(id = "config-toml", long, value_delimiter(','))]
+    pub paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format to reload with.
+    #[arg(id = "config-json", long, value_delimiter(','))]
+    pub paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format to reload with.
+    #[arg(id = "config-yaml", long, value_delimiter(','))]
+    pub paths_yaml: Vec<PathBuf>,
+
+    /// Any number of Vector config files to reload with.
+    /// Format is detected from the file name.
+    /// If none are specified the default config path `/etc/vector/vector.toml`
+    /// will be targeted.
+    #[arg(env = "VECTOR_CONFIG", value_delimiter(','))]
+    pub paths: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[arg(
+        id = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        value_delimiter(',')
+    )]
+    pub config_dirs: Vec<PathBuf>,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+}
+
+fn default_url() -> Url {
+    let address = config::api::default_address().expect("default API address is set");
+    Url::parse(&format!("http://{}/config", address)).expect("default reload URL is valid")
+}
+
+/// Sends the config to a running Vector's API to be validated and applied, and prints the
+/// resulting component-level diff.
+#[allow(clippy::print_stdout, clippy::print_stderr)]
+pub async fn cmd(opts: &Opts) -> ExitCode {
+    if !opts.via_api {
+        eprintln!(
+            "Reloading is only supported through the API, use `--via-api`. To reload from disk, send `SIGHUP` to the Vector process instead."
+        );
+        return exitcode::USAGE;
+    }
+
+    let paths = match config::process_paths(&opts.paths_with_formats()) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+    let builder = match config::load_builder_from_paths(&paths) {
+        Ok((builder, _warnings)) => builder,
+        Err(errors) => return handle_config_errors(errors),
+    };
+
+    let mut url = opts.url.clone();
+    if opts.dry_run {
+        url.query_pairs_mut().append_pair("dry_run", "true");
+    }
+
+    let (status, body) = match send(&url, &opts.token, &builder).await {
+        Ok(response) => response,
+        Err(error) => {
+            eprintln!("Unable to reach the Vector API at {}: {}.", url, error);
+            return exitcode::UNAVAILABLE;
+        }
+    };
+
+    if status == StatusCode::UNAUTHORIZED {
+        eprintln!("The Vector API rejected the reload token.");
+        return exitcode::NOPERM;
+    }
+    if status == StatusCode::NOT_FOUND {
+        eprintln!(
+            "Config reload is not enabled on the Vector API. Set `api.reload_token` to enable it."
+        );
+        return exitcode::UNAVAILABLE;
+    }
+
+    let response = match serde_json::from_slice::<ReloadResponse>(&body) {
+        Ok(response) => response,
+        Err(error) => {
+            eprintln!("Unexpected response from the Vector API: {}.", error);
+            return exitcode::PROTOCOL;
+        }
+    };
+
+    match response {
+        ReloadResponse::Validated { diff } => {
+            print_diff(&diff);
+            println!("{}", "√ Config is valid, nothing was applied.".green());
+            exitcode::OK
+        }
+        ReloadResponse::Applied { diff } => {
+            print_diff(&diff);
+            println!("{}", "√ Config applied.".green());
+            exitcode::OK
+        }
+        ReloadResponse::Invalid { errors } => {
+            eprintln!("{}", "x Config is invalid:".red());
+            for error in errors {
+                eprintln!("  {}", error);
+            }
+            exitcode::CONFIG
+        }
+        ReloadResponse::RolledBack { diff } => {
+            print_diff(&diff);
+            eprintln!(
+                "{}",
+                "x Config could not be applied, rolled back to the previous config.".red()
+            );
+            exitcode::SOFTWARE
+        }
+        ReloadResponse::Failed { diff } => {
+            print_diff(&diff);
+            eprintln!(
+                "{}",
+                "x Config could not be applied, and the previous config could not be restored. Vector is shutting down.".red()
+            );
+            exitcode::SOFTWARE
+        }
+        ReloadResponse::MissingApiKey => {
+            eprintln!("{}", "x Config is missing the enterprise API key.".red());
+            exitcode::CONFIG
+        }
+        ReloadResponse::Unavailable => {
+            eprintln!("{}", "x Vector is shutting down.".red());
+            exitcode::UNAVAILABLE
+        }
+    }
+}
+
+async fn send(
+    url: &Url,
+    token: &str,
+    builder: &config::ConfigBuilder,
+) -> crate::Result<(StatusCode, Bytes)> {
+    let client = HttpClient::new(None, &ProxyConfig::default())?;
+    let request = Request::post(url.as_str())
+        .header(CONTENT_TYPE, "application/json")
+        .header(AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::from(serde_json::to_vec(builder)?))?;
+
+    let response = client.send(request).await?;
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    Ok((status, body))
+}
+
+#[allow(clippy::print_stdout)]
+fn print_diff(diff: &ReloadDiff) {
+    if diff.is_empty() {
+        println!("No component changes.");
+        return;
+    }
+
+    for (kind, changes) in [
+        ("sources", &diff.sources),
+        ("transforms", &diff.transforms),
+        ("sinks", &diff.sinks),
+        ("enrichment_tables", &diff.enrichment_tables),
+    ] {
+        print_changes(kind, changes);
+    }
+}
+
+#[allow(clippy::print_stdout)]
+fn print_changes(kind: &str, changes: &ComponentChanges) {
+    if changes.is_empty() {
+        return;
+    }
+
+    println!("{}:", kind);
+    for id in &changes.added {
+        println!("  {}", format!("+ {}", id).green());
+    }
+    for id in &changes.changed {
+        println!("  {}", format!("~ {}", id).yellow());
+    }
+    for id in &changes.removed {
+        println!("  {}", format!("- {}", id).red());
+    }
+}
@@ -0,0 +1,465 @@
+//! Inference of the schema of the events encoded by sinks.
+//!
+//! The schema is derived from the definition of the events a sink receives, which the sources and
+//! transforms feeding into it provide, and from the fields its encoding keeps or removes, so that
+//! sinks writing to stores with a schema, and `vector schema show`, don't need the fields of the
+//! events to be listed again.
+#![allow(missing_docs)]
+use std::{collections::HashMap, path::PathBuf};
+
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+use value::{
+    kind::{Collection, Field},
+    Kind,
+};
+
+use crate::{
+    codecs::Transformer,
+    config::{self, ComponentKey, OutputId, SinkOuter},
+    schema::Definition,
+    topology::schema::sink_input_definition,
+};
+
+/// The type of the values of a field.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldType {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Timestamp,
+    Array,
+    Object,
+}
+
+/// A field of the encoded events.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct InferredField {
+    pub name: String,
+    /// The types of the values of the field. There are several when they aren't always the same.
+    pub types: Vec<FieldType>,
+    /// Whether the field may be missing or null.
+    pub optional: bool,
+    /// The fields of the values of the field, when it's an object.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<InferredField>,
+    /// The items of the values of the field, when it's an array.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<InferredField>>,
+}
+
+impl InferredField {
+    fn new(name: &str, kind: &Kind) -> Self {
+        let mut types = Vec::new();
+        if kind.contains_bytes() || kind.contains_regex() {
+            types.push(FieldType::String);
+        }
+        if kind.contains_integer() {
+            types.push(FieldType::Integer);
+        }
+        if kind.contains_float() {
+            types.push(FieldType::Float);
+        }
+        if kind.contains_boolean() {
+            types.push(FieldType::Boolean);
+        }
+        if kind.contains_timestamp() {
+            types.push(FieldType::Timestamp);
+        }
+
+        let items = kind.as_array().map(|array| {
+            types.push(FieldType::Array);
+            Box::new(Self::new("item", &array.reduced_kind()))
+        });
+        let fields = kind
+            .as_object()
+            .map(|object| {
+                types.push(FieldType::Object);
+                known_fields(object)
+            })
+            .unwrap_or_default();
+
+        Self {
+            name: name.to_owned(),
+            types,
+            optional: kind.contains_null() || kind.contains_undefined(),
+            fields,
+            items,
+        }
+    }
+
+    /// The type of the values of the field, if they always have the same.
+    fn single_type(&self) -> Option<FieldType> {
+        match self.types.as_slice() {
+            [field_type] => Some(*field_type),
+            _ => None,
+        }
+    }
+}
+
+fn known_fields(object: &Collection<Field>) -> Vec<InferredField> {
+    object
+        .known()
+        .iter()
+        .map(|(name, kind)| InferredField::new(name.as_str(), kind))
+        .collect()
+}
+
+/// The schema of the events encoded by a sink.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct InferredSchema {
+    pub fields: Vec<InferredField>,
+    /// Whether the events may have other fields than the inferred ones, such as when they come
+    /// from a source without a schema.
+    pub unknown_fields: bool,
+}
+
+impl InferredSchema {
+    /// Infers the schema of the events encoded by a sink, from the definition of the events it
+    /// receives and the transformation rules of its encoding.
+    pub fn new(definition: &Definition, transformer: &Transformer) -> Self {
+        let kind = transformer.transform_kind(definition.event_kind().clone());
+        match kind.as_object() {
+            Some(object) => Self {
+                fields: known_fields(object),
+                unknown_fields: !object.unknown_kind().without_undefined().is_never(),
+            },
+            None => Self {
+                fields: Vec::new(),
+                unknown_fields: true,
+            },
+        }
+    }
+
+    /// Renders the schema as the columns of a ClickHouse table.
+    pub fn to_clickhouse(&self) -> String {
+        let columns = self
+            .fields
+            .iter()
+            .map(|field| format!("    `{}` {}", field.name, clickhouse_type(field)))
+            .collect::<Vec<_>>();
+        format!("(\n{}\n)", columns.join(",\n"))
+    }
+
+    /// Renders the schema as a BigQuery table schema.
+    pub fn to_bigquery(&self) -> serde_json::Value {
+        self.fields.iter().map(bigquery_field).collect()
+    }
+}
+
+fn clickhouse_type(field: &InferredField) -> String {
+    let column_type = match field.single_type() {
+        Some(FieldType::String) => "String".to_owned(),
+        Some(FieldType::Integer) => "Int64".to_owned(),
+        Some(FieldType::Float) => "Float64".to_owned(),
+        Some(FieldType::Boolean) => "Bool".to_owned(),
+        Some(FieldType::Timestamp) => "DateTime64(6)".to_owned(),
+        // Arrays and tuples can't be nullable.
+        Some(FieldType::Array) => {
+            let items = field.items.as_deref().map_or_else(
+                || "String".to_owned(),
+                |items| {
+                    clickhouse_type(&InferredField {
+                        optional: false,
+                        ..items.clone()
+                    })
+                },
+            );
+            return format!("Array({})", items);
+        }
+        Some(FieldType::Object) if !field.fields.is_empty() => {
+            let elements = field
+                .fields
+                .iter()
+                .map(|field| format!("`{}` {}", field.name, clickhouse_type(field)))
+                .collect::<Vec<_>>();
+            return format!("Tuple({})", elements.join(", "));
+        }
+        // Values of several types, or objects without known fields, are kept as JSON.
+        _ => "String".to_owned(),
+    };
+
+    if field.optional {
+        format!("Nullable({})", column_type)
+    } else {
+        column_type
+    }
+}
+
+fn bigquery_field(field: &InferredField) -> serde_json::Value {
+    let (field_type, mode, fields) = match (field.single_type(), field.items.as_deref()) {
+        (Some(FieldType::Array), Some(items)) => match bigquery_field(items) {
+            // Arrays of arrays aren't supported, so they are kept as JSON.
+            item if item["mode"] == "REPEATED" => ("STRING".into(), "NULLABLE", Vec::new()),
+            item => (item["type"].clone(), "REPEATED", items.fields.clone()),
+        },
+        (field_type, _) => (
+            bigquery_type(field_type, &field.fields).into(),
+            if field.optional {
+                "NULLABLE"
+            } else {
+                "REQUIRED"
+            },
+            field.fields.clone(),
+        ),
+    };
+
+    let mut schema = serde_json::json!({
+        "name": field.name,
+        "type": field_type,
+        "mode": mode,
+    });
+    if !fields.is_empty() && schema["type"] == "RECORD" {
+        schema["fields"] = fields.iter().map(bigquery_field).collect();
+    }
+    schema
+}
+
+fn bigquery_type(field_type: Option<FieldType>, fields: &[InferredField]) -> &'static str {
+    match field_type {
+        Some(FieldType::String) => "STRING",
+        Some(FieldType::Integer) => "INTEGER",
+        Some(FieldType::Float) => "FLOAT",
+        Some(FieldType::Boolean) => "BOOLEAN",
+        Some(FieldType::Timestamp) => "TIMESTAMP",
+        Some(FieldType::Object) if !fields.is_empty() => "RECORD",
+        _ => "STRING",
+    }
+}
+
+/// Gets the transformation rules of the encoding of a sink from its configuration.
+///
+/// Sinks either have the rules in their `encoding` option, or flatten them in it along with the
+/// codec and its options, which are ignored.
+fn sink_transformer(sink: &SinkOuter<OutputId>) -> Result<Transformer, String> {
+    let config = serde_json::to_value(&sink.inner).map_err(|error| error.to_string())?;
+    let rules = match config
+        .get("encoding")
+        .and_then(|encoding| encoding.as_object())
+    {
+        Some(encoding) => encoding
+            .iter()
+            .filter(|(key, _)| {
+                ["only_fields", "except_fields", "timestamp_format"].contains(&key.as_str())
+            })
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+        None => serde_json::Map::new(),
+    };
+    serde_json::from_value(rules.into()).map_err(|error| error.to_string())
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+enum Command {
+    /// Show the schema of the events encoded by a sink, inferred from the components feeding into
+    /// it and its encoding.
+    Show(ShowOpts),
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+struct ShowOpts {
+    /// The ID of the sink.
+    sink: String,
+
+    /// Any number of Vector config files. If none are specified the default
+    /// config path `/etc/vector/vector.toml` will be targeted.
+    #[arg(value_delimiter(','))]
+    paths: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[arg(
+        id = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        value_delimiter(',')
+    )]
+    config_dirs: Vec<PathBuf>,
+
+    /// The format of the schema.
+    #[arg(long, default_value = "json")]
+    format: Format,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// The inferred fields, as JSON.
+    Json,
+    /// The columns of a ClickHouse table.
+    Clickhouse,
+    /// A BigQuery table schema.
+    Bigquery,
+}
+
+impl ShowOpts {
+    fn paths(&self) -> Vec<config::ConfigPath> {
+        self.paths
+            .iter()
+            .map(|path| config::ConfigPath::File(path.to_path_buf(), None))
+            .chain(
+                self.config_dirs
+                    .iter()
+                    .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+            )
+            .collect()
+    }
+}
+
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match &opts.command {
+        Command::Show(opts) => show(opts),
+    }
+}
+
+fn show(opts: &ShowOpts) -> exitcode::ExitCode {
+    let paths = match config::process_paths(&opts.paths()) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+    let config = match config::init_log_schema(&paths, true)
+        .and_then(|()| config::load_from_paths(&paths))
+    {
+        Ok(config) => config,
+        Err(errors) => {
+            error!("Failed to load the config:\n{}", errors.join("\n"));
+            return exitcode::CONFIG;
+        }
+    };
+
+    let sink = match config.sink(&ComponentKey::from(opts.sink.as_str())) {
+        Some(sink) => sink,
+        None => {
+            error!(message = "There is no such sink.", sink = %opts.sink);
+            return exitcode::CONFIG;
+        }
+    };
+    let transformer = match sink_transformer(sink) {
+        Ok(transformer) => transformer,
+        Err(error) => {
+            error!(message = "Invalid encoding of the sink.", %error);
+            return exitcode::CONFIG;
+        }
+    };
+    let definition = sink_input_definition(&sink.inputs, &config, &mut HashMap::new());
+    let schema = InferredSchema::new(&definition, &transformer);
+
+    if schema.unknown_fields {
+        warn!(
+            message = "The events may have other fields than the inferred ones.",
+            sink = %opts.sink
+        );
+    }
+
+    let output = match opts.format {
+        Format::Json => serde_json::to_string_pretty(&schema).expect("serializable"),
+        Format::Clickhouse => schema.to_clickhouse(),
+        Format::Bigquery => {
+            serde_json::to_string_pretty(&schema.to_bigquery()).expect("serializable")
+        }
+    };
+    #[allow(clippy::print_stdout)]
+    {
+        println!("{}", output);
+    }
+    exitcode::OK
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use vector_core::config::LogNamespace;
+
+    use super::*;
+
+    fn schema(transformer: &str) -> InferredSchema {
+        let definition = Definition::new_with_default_metadata(
+            Kind::object(BTreeMap::from([
+                ("message".into(), Kind::bytes()),
+                ("status".into(), Kind::integer().or_null()),
+                ("timestamp".into(), Kind::timestamp()),
+                (
+                    "http".into(),
+                    Kind::object(BTreeMap::from([
+                        ("method".into(), Kind::bytes()),
+                        ("path.raw".into(), Kind::bytes()),
+                    ])),
+                ),
+                (
+                    "tags".into(),
+                    Kind::array(Collection::from_unknown(Kind::bytes())),
+                ),
+            ])),
+            [LogNamespace::Vector],
+        );
+        InferredSchema::new(&definition, &toml::from_str(transformer).unwrap())
+    }
+
+    #[test]
+    fn infers_fields() {
+        let schema = schema("");
+        assert!(!schema.unknown_fields);
+        let names = schema
+            .fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["http", "message", "status", "tags", "timestamp"]);
+
+        let status = &schema.fields[2];
+        assert_eq!(status.types, [FieldType::Integer]);
+        assert!(status.optional);
+
+        let http = &schema.fields[0];
+        assert_eq!(http.types, [FieldType::Object]);
+        assert_eq!(http.fields[1].name, "path.raw");
+    }
+
+    #[test]
+    fn applies_encoding() {
+        let schema = schema(r#"only_fields = ["http.\"path.raw\"", "status"]"#);
+        assert_eq!(schema.fields.len(), 2);
+        assert_eq!(schema.fields[0].fields.len(), 1);
+        assert_eq!(schema.fields[0].fields[0].name, "path.raw");
+    }
+
+    #[test]
+    fn renders_clickhouse() {
+        let schema = schema(r#"except_fields = ["http"]"#);
+        assert_eq!(
+            schema.to_clickhouse(),
+            "(\n    `message` String,\n    `status` Nullable(Int64),\n    `tags` Array(String),\n    `timestamp` DateTime64(6)\n)"
+        );
+    }
+
+    #[test]
+    fn renders_bigquery() {
+        let schema = schema(r#"only_fields = ["http.method", "tags"]"#);
+        assert_eq!(
+            schema.to_bigquery(),
+            serde_json::json!([
+                {
+                    "name": "http",
+                    "type": "RECORD",
+                    "mode": "REQUIRED",
+                    "fields": [{ "name": "method", "type": "STRING", "mode": "REQUIRED" }],
+                },
+                { "name": "tags", "type": "STRING", "mode": "REPEATED" },
+            ])
+        );
+    }
+}
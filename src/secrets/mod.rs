@@ -8,6 +8,8 @@ use crate::{config::SecretBackend, signal};
 
 mod exec;
 mod test;
+mod vault;
+pub mod watcher;
 
 /// Configurable secret backends in Vector.
 #[configurable_component]
@@ -21,6 +23,9 @@ pub enum SecretBackends {
     /// Test.
     #[configurable(metadata(docs::hidden))]
     Test(test::TestBackend),
+
+    /// HashiCorp Vault.
+    Vault(vault::VaultBackend),
 }
 
 // TODO: Use `enum_dispatch` here.
@@ -29,6 +34,7 @@ impl NamedComponent for SecretBackends {
         match self {
             Self::Exec(config) => config.get_component_name(),
             Self::Test(config) => config.get_component_name(),
+            Self::Vault(config) => config.get_component_name(),
         }
     }
 }
@@ -0,0 +1,860 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use futures::executor;
+use http::{Method, Request, StatusCode};
+use hyper::Body;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tokio::time;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::{component::GenerateConfig, configurable_component};
+
+use crate::{
+    config::{ProxyConfig, SecretBackend},
+    http::HttpClient,
+    signal,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Configuration for the `vault` secrets backend.
+///
+/// Secrets are referenced as `SECRET[<backend>.<path>:<key>]`, where `path` is the path of the
+/// secret in Vault, such as `secret/data/my-app` for the KV v2 secrets engine, and `key` is the
+/// field of the secret to use.
+#[configurable_component(secrets("vault"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct VaultBackend {
+    /// The address of the Vault server.
+    #[configurable(metadata(docs::examples = "https://vault.example.com:8200"))]
+    pub endpoint: String,
+
+    /// The Vault Enterprise namespace to send requests to.
+    #[configurable(metadata(docs::examples = "team-a"))]
+    pub namespace: Option<String>,
+
+    #[configurable(derived)]
+    pub auth: VaultAuth,
+
+    /// The timeout, in seconds, to wait for each request to Vault to complete.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout: u64,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+/// Configuration of the authentication strategy used with Vault.
+///
+/// Tokens obtained by logging in are cached and renewed before they expire, so that reloading
+/// Vector doesn't require logging in again.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+#[configurable(metadata(docs::enum_tag_description = "The authentication strategy to use."))]
+pub enum VaultAuth {
+    /// Authenticate with a static token.
+    Token {
+        /// The Vault token.
+        #[configurable(metadata(docs::examples = "${VAULT_TOKEN}"))]
+        token: SensitiveString,
+    },
+
+    /// Authenticate with the AppRole auth method.
+    Approle {
+        /// The role ID.
+        role_id: String,
+
+        /// The secret ID.
+        #[configurable(metadata(docs::examples = "${VAULT_SECRET_ID}"))]
+        secret_id: SensitiveString,
+
+        /// The path the AppRole auth method is mounted at.
+        #[serde(default = "default_approle_mount")]
+        mount: String,
+    },
+
+    /// Authenticate with the Kubernetes auth method, using the token of the service account Vector
+    /// is running as.
+    Kubernetes {
+        /// The name of the role to log in as.
+        role: String,
+
+        /// The path of the service account token.
+        #[serde(default = "default_kubernetes_token_path")]
+        token_path: PathBuf,
+
+        /// The path the Kubernetes auth method is mounted at.
+        #[serde(default = "default_kubernetes_mount")]
+        mount: String,
+    },
+}
+
+impl GenerateConfig for VaultBackend {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(VaultBackend {
+            endpoint: String::from("https://127.0.0.1:8200"),
+            namespace: None,
+            auth: VaultAuth::Token {
+                token: String::from("${VAULT_TOKEN}").into(),
+            },
+            timeout: default_timeout_secs(),
+            tls: None,
+        })
+        .unwrap()
+    }
+}
+
+const fn default_timeout_secs() -> u64 {
+    5
+}
+
+fn default_approle_mount() -> String {
+    String::from("approle")
+}
+
+fn default_kubernetes_mount() -> String {
+    String::from("kubernetes")
+}
+
+fn default_kubernetes_token_path() -> PathBuf {
+    PathBuf::from("/var/run/secrets/kubernetes.io/serviceaccount/token")
+}
+
+// Leased secrets, and tokens obtained by logging in, are cached for the lifetime of the process.
+// Reading a leased secret, such as database credentials, creates new credentials every time, so
+// reloading Vector would otherwise leave a trail of unused credentials behind. Non-leased secrets,
+// such as those of the KV secrets engine, are never cached, so that a reload picks up any changes.
+// When polling for rotated secrets, leased secrets are read again and replace the cached ones, so
+// that the reload that follows a rotation uses the new credentials.
+static LEASED_SECRETS: Lazy<Mutex<HashMap<String, CachedSecret>>> = Lazy::new(Default::default);
+static TOKENS: Lazy<Mutex<HashMap<String, CachedToken>>> = Lazy::new(Default::default);
+
+#[derive(Clone, Debug)]
+struct CachedSecret {
+    lease_id: String,
+    data: Map<String, Value>,
+    refresh_at: Instant,
+}
+
+#[derive(Clone, Debug)]
+struct CachedToken {
+    token: SensitiveString,
+    refresh_at: Option<Instant>,
+}
+
+fn refresh_at(lease_duration: u64) -> Instant {
+    Instant::now() + renew_after(lease_duration)
+}
+
+/// Leases are renewed once two thirds of their duration have passed.
+fn renew_after(lease_duration: u64) -> Duration {
+    Duration::from_secs(lease_duration * 2 / 3).max(Duration::from_secs(1))
+}
+
+/// Splits a secret key into the path of the secret, and the field of the secret to use.
+fn parse_key(key: &str) -> crate::Result<(&str, &str)> {
+    match key.rsplit_once(':') {
+        Some((path, field)) if !path.is_empty() && !field.is_empty() => {
+            Ok((path.trim_matches('/'), field))
+        }
+        _ => Err(format!(
+            "invalid Vault secret key '{}', expected the form '<path>:<key>'",
+            key
+        )
+        .into()),
+    }
+}
+
+/// Groups secret keys by the path of their secret, so that each secret is only read once.
+fn group_keys(
+    secret_keys: impl IntoIterator<Item = String>,
+) -> crate::Result<HashMap<String, Vec<(String, String)>>> {
+    let mut keys_by_path = HashMap::<String, Vec<(String, String)>>::new();
+    for key in secret_keys {
+        let (path, field) = parse_key(&key)?;
+        let (path, field) = (path.to_string(), field.to_string());
+        keys_by_path.entry(path).or_default().push((key, field));
+    }
+    Ok(keys_by_path)
+}
+
+impl VaultBackend {
+    fn retrieve_secrets(
+        &self,
+        secret_keys: HashSet<String>,
+        signal_rx: &mut signal::SignalRx,
+        refresh: bool,
+    ) -> crate::Result<HashMap<String, String>> {
+        let keys_by_path = group_keys(secret_keys)?;
+        let client = VaultClient::new(self)?;
+        executor::block_on(async {
+            tokio::select! {
+                biased;
+                Ok(signal::SignalTo::Shutdown | signal::SignalTo::Drain(_) | signal::SignalTo::Quit) = signal_rx.recv() => {
+                    Err("Secret retrieval was interrupted.".into())
+                }
+                secrets = client.retrieve(keys_by_path, refresh) => secrets,
+            }
+        })
+    }
+}
+
+impl SecretBackend for VaultBackend {
+    fn retrieve(
+        &mut self,
+        secret_keys: HashSet<String>,
+        signal_rx: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        self.retrieve_secrets(secret_keys, signal_rx, false)
+    }
+
+    fn refresh(
+        &mut self,
+        secret_keys: HashSet<String>,
+        signal_rx: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        self.retrieve_secrets(secret_keys, signal_rx, true)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct VaultResponse {
+    #[serde(default)]
+    lease_id: String,
+    #[serde(default)]
+    lease_duration: u64,
+    #[serde(default)]
+    renewable: bool,
+    data: Option<Map<String, Value>>,
+    auth: Option<VaultAuthResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VaultAuthResponse {
+    client_token: SensitiveString,
+    #[serde(default)]
+    lease_duration: u64,
+    #[serde(default)]
+    renewable: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct VaultErrorResponse {
+    #[serde(default)]
+    errors: Vec<String>,
+}
+
+#[derive(Clone)]
+struct VaultClient {
+    http: HttpClient,
+    endpoint: String,
+    namespace: Option<String>,
+    auth: VaultAuth,
+    timeout: Duration,
+}
+
+impl VaultClient {
+    fn new(config: &VaultBackend) -> crate::Result<Self> {
+        let tls = TlsSettings::from_options(&config.tls)?;
+        Ok(Self {
+            http: HttpClient::new(tls, &ProxyConfig::default())?,
+            endpoint: config.endpoint.trim_end_matches('/').to_string(),
+            namespace: config.namespace.clone(),
+            auth: config.auth.clone(),
+            timeout: Duration::from_secs(config.timeout),
+        })
+    }
+
+    /// Key identifying this Vault server, namespace and identity in the caches.
+    fn cache_key(&self, suffix: &str) -> String {
+        let identity = match &self.auth {
+            VaultAuth::Token { .. } => String::from("token"),
+            VaultAuth::Approle { role_id, mount, .. } => format!("{}/{}", mount, role_id),
+            VaultAuth::Kubernetes { role, mount, .. } => format!("{}/{}", mount, role),
+        };
+        format!(
+            "{}|{}|{}|{}",
+            self.endpoint,
+            self.namespace.as_deref().unwrap_or_default(),
+            identity,
+            suffix
+        )
+    }
+
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        token: Option<&SensitiveString>,
+        body: Option<Value>,
+    ) -> crate::Result<VaultResponse> {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(format!("{}/v1/{}", self.endpoint, path));
+        if let Some(token) = token {
+            request = request.header("X-Vault-Token", token.inner());
+        }
+        if let Some(namespace) = &self.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+        let request = match body {
+            Some(body) => request
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body)?))?,
+            None => request.body(Body::empty())?,
+        };
+
+        let response = time::timeout(self.timeout, self.http.send(request))
+            .await
+            .map_err(|_| format!("request to Vault timed out after {:?}", self.timeout))??;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+
+        if status == StatusCode::NO_CONTENT {
+            Ok(VaultResponse::default())
+        } else if status.is_success() {
+            Ok(serde_json::from_slice(&body)?)
+        } else {
+            let errors = serde_json::from_slice::<VaultErrorResponse>(&body)
+                .unwrap_or_default()
+                .errors;
+            Err(format!(
+                "Vault responded with {} for '{}': {}",
+                status,
+                path,
+                errors.join(", ")
+            )
+            .into())
+        }
+    }
+
+    /// Returns a token to authenticate with, logging in if there is no cached token.
+    async fn token(&self) -> crate::Result<SensitiveString> {
+        if let VaultAuth::Token { token } = &self.auth {
+            return Ok(token.clone());
+        }
+
+        let cache_key = self.cache_key("");
+        if let Some(cached) = TOKENS.lock().expect("poisoned lock").get(&cache_key) {
+            if cached.refresh_at.map_or(true, |at| Instant::now() < at) {
+                return Ok(cached.token.clone());
+            }
+        }
+
+        let (mount, body) = match &self.auth {
+            VaultAuth::Token { .. } => unreachable!("handled above"),
+            VaultAuth::Approle {
+                role_id,
+                secret_id,
+                mount,
+            } => (
+                mount,
+                serde_json::json!({ "role_id": role_id, "secret_id": secret_id.inner() }),
+            ),
+            VaultAuth::Kubernetes {
+                role,
+                token_path,
+                mount,
+            } => {
+                let jwt = tokio::fs::read_to_string(token_path)
+                    .await
+                    .map_err(|error| {
+                        format!(
+                            "unable to read service account token {:?}: {}",
+                            token_path, error
+                        )
+                    })?;
+                (
+                    mount,
+                    serde_json::json!({ "role": role, "jwt": jwt.trim() }),
+                )
+            }
+        };
+
+        let path = format!("auth/{}/login", mount.trim_matches('/'));
+        let auth = self
+            .request(Method::POST, &path, None, Some(body))
+            .await?
+            .auth
+            .ok_or("Vault login response is missing the token")?;
+        debug!(message = "Logged in to Vault.", endpoint = %self.endpoint);
+
+        let expires = auth.lease_duration > 0;
+        TOKENS.lock().expect("poisoned lock").insert(
+            cache_key.clone(),
+            CachedToken {
+                token: auth.client_token.clone(),
+                refresh_at: expires.then(|| refresh_at(auth.lease_duration)),
+            },
+        );
+        if expires && auth.renewable {
+            tokio::spawn(self.clone().renew_token(
+                cache_key,
+                auth.client_token.clone(),
+                auth.lease_duration,
+            ));
+        }
+
+        Ok(auth.client_token)
+    }
+
+    /// Reads the secret at `path`, using the cached secret if it is leased, unless `refresh` is
+    /// set.
+    async fn read(
+        &self,
+        token: &SensitiveString,
+        path: &str,
+        refresh: bool,
+    ) -> crate::Result<Map<String, Value>> {
+        let cache_key = self.cache_key(path);
+        if !refresh {
+            if let Some(cached) = LEASED_SECRETS
+                .lock()
+                .expect("poisoned lock")
+                .get(&cache_key)
+            {
+                if Instant::now() < cached.refresh_at {
+                    return Ok(cached.data.clone());
+                }
+            }
+        }
+
+        let response = self.request(Method::GET, path, Some(token), None).await?;
+        let mut data = response
+            .data
+            .ok_or_else(|| format!("Vault secret '{}' has no data", path))?;
+
+        // Secrets of the KV v2 secrets engine are nested one level deeper, next to their metadata.
+        if data.contains_key("metadata") {
+            if let Some(Value::Object(inner)) = data.remove("data") {
+                data = inner;
+            }
+        }
+
+        if !response.lease_id.is_empty() && response.lease_duration > 0 {
+            LEASED_SECRETS.lock().expect("poisoned lock").insert(
+                cache_key.clone(),
+                CachedSecret {
+                    lease_id: response.lease_id.clone(),
+                    data: data.clone(),
+                    refresh_at: refresh_at(response.lease_duration),
+                },
+            );
+            if response.renewable {
+                tokio::spawn(self.clone().renew_secret_lease(
+                    cache_key,
+                    response.lease_id,
+                    response.lease_duration,
+                ));
+            }
+        }
+
+        Ok(data)
+    }
+
+    async fn retrieve(
+        &self,
+        keys_by_path: HashMap<String, Vec<(String, String)>>,
+        refresh: bool,
+    ) -> crate::Result<HashMap<String, String>> {
+        let token = self.token().await?;
+
+        let mut secrets = HashMap::new();
+        for (path, keys) in keys_by_path {
+            let data = self.read(&token, &path, refresh).await?;
+            for (key, field) in keys {
+                let value = match data.get(&field) {
+                    Some(Value::String(value)) => value.clone(),
+                    Some(Value::Null) | None => {
+                        return Err(format!("secret for key '{}' was not retrieved", key).into())
+                    }
+                    Some(value) => value.to_string(),
+                };
+                if value.is_empty() {
+                    return Err(format!("secret for key '{}' was empty", key).into());
+                }
+                secrets.insert(key, value);
+            }
+        }
+        Ok(secrets)
+    }
+
+    /// Keeps renewing the lease of a cached secret before it expires, for as long as it can be
+    /// renewed. Once renewal fails, the secret is dropped from the cache, so that it is read again
+    /// on the next reload.
+    async fn renew_secret_lease(
+        self,
+        cache_key: String,
+        lease_id: String,
+        mut lease_duration: u64,
+    ) {
+        loop {
+            time::sleep(renew_after(lease_duration)).await;
+
+            let renewed = match self.token().await {
+                Ok(token) => {
+                    let body = serde_json::json!({
+                        "lease_id": lease_id,
+                        "increment": lease_duration,
+                    });
+                    self.request(Method::PUT, "sys/leases/renew", Some(&token), Some(body))
+                        .await
+                }
+                Err(error) => Err(error),
+            };
+
+            let mut cache = LEASED_SECRETS.lock().expect("poisoned lock");
+            let cached = match cache.get_mut(&cache_key) {
+                Some(cached) if cached.lease_id == lease_id => cached,
+                // The secret was read again, and the new lease is renewed instead.
+                _ => return,
+            };
+
+            match renewed {
+                Ok(renewed) if renewed.lease_duration > 0 => {
+                    debug!(message = "Renewed Vault lease.", %lease_id);
+                    lease_duration = renewed.lease_duration;
+                    cached.refresh_at = refresh_at(lease_duration);
+                    if !renewed.renewable {
+                        return;
+                    }
+                }
+                Ok(_) => return,
+                Err(error) => {
+                    warn!(message = "Failed to renew Vault lease.", %lease_id, %error);
+                    cache.remove(&cache_key);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Keeps renewing a token obtained by logging in before it expires, for as long as it can be
+    /// renewed. Once renewal fails, the token is dropped from the cache, so that Vector logs in
+    /// again when it next needs a token.
+    async fn renew_token(self, cache_key: String, token: SensitiveString, mut lease_duration: u64) {
+        loop {
+            time::sleep(renew_after(lease_duration)).await;
+
+            let renewed = self
+                .request(Method::POST, "auth/token/renew-self", Some(&token), None)
+                .await
+                .and_then(|response| {
+                    response
+                        .auth
+                        .ok_or_else(|| "Vault token renewal response is missing the token".into())
+                });
+
+            let mut cache = TOKENS.lock().expect("poisoned lock");
+            let cached = match cache.get_mut(&cache_key) {
+                Some(cached) if cached.token == token => cached,
+                // Logged in again, and the new token is renewed instead.
+                _ => return,
+            };
+
+            match renewed {
+                Ok(renewed) if renewed.lease_duration > 0 => {
+                    debug!(message = "Renewed Vault token.", endpoint = %self.endpoint);
+                    lease_duration = renewed.lease_duration;
+                    cached.refresh_at = Some(refresh_at(lease_duration));
+                    if !renewed.renewable {
+                        return;
+                    }
+                }
+                Ok(_) => return,
+                Err(error) => {
+                    warn!(
+                        message = "Failed to renew Vault token.",
+                        endpoint = %self.endpoint,
+                        %error
+                    );
+                    cache.remove(&cache_key);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        matchers::{body_json, header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    fn backend(endpoint: String, auth: VaultAuth) -> VaultBackend {
+        VaultBackend {
+            endpoint,
+            namespace: None,
+            auth,
+            timeout: default_timeout_secs(),
+            tls: None,
+        }
+    }
+
+    fn token_auth(token: &str) -> VaultAuth {
+        VaultAuth::Token {
+            token: token.to_string().into(),
+        }
+    }
+
+    async fn retrieve(
+        backend: &VaultBackend,
+        keys: &[&str],
+    ) -> crate::Result<HashMap<String, String>> {
+        let keys_by_path = group_keys(keys.iter().map(ToString::to_string))?;
+        VaultClient::new(backend)?
+            .retrieve(keys_by_path, false)
+            .await
+    }
+
+    async fn refresh(
+        backend: &VaultBackend,
+        keys: &[&str],
+    ) -> crate::Result<HashMap<String, String>> {
+        let keys_by_path = group_keys(keys.iter().map(ToString::to_string))?;
+        VaultClient::new(backend)?
+            .retrieve(keys_by_path, true)
+            .await
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<VaultBackend>();
+    }
+
+    #[test]
+    fn parses_keys() {
+        assert_eq!(
+            parse_key("secret/data/my-app:password").unwrap(),
+            ("secret/data/my-app", "password")
+        );
+        assert_eq!(
+            parse_key("/database/creds/readonly/:username").unwrap(),
+            ("database/creds/readonly", "username")
+        );
+        assert!(parse_key("secret/data/my-app").is_err());
+        assert!(parse_key("secret/data/my-app:").is_err());
+        assert!(parse_key(":password").is_err());
+    }
+
+    #[test]
+    fn parses_auth_strategies() {
+        let config: VaultBackend = toml::from_str(
+            r#"
+            endpoint = "https://vault:8200"
+            auth.strategy = "kubernetes"
+            auth.role = "vector"
+            "#,
+        )
+        .unwrap();
+
+        match config.auth {
+            VaultAuth::Kubernetes {
+                role,
+                token_path,
+                mount,
+            } => {
+                assert_eq!(role, "vector");
+                assert_eq!(token_path, default_kubernetes_token_path());
+                assert_eq!(mount, "kubernetes");
+            }
+            auth => panic!("unexpected auth strategy: {:?}", auth),
+        }
+    }
+
+    #[test]
+    fn renews_before_expiry() {
+        assert_eq!(renew_after(300), Duration::from_secs(200));
+        assert_eq!(renew_after(0), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn retrieves_kv_v2_secrets_with_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/secret/data/my-app"))
+            .and(header("X-Vault-Token", "s.root"))
+            .and(header("X-Vault-Namespace", "team-a"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "lease_id": "",
+                "lease_duration": 0,
+                "renewable": false,
+                "data": {
+                    "data": { "username": "vector", "password": "hunter2" },
+                    "metadata": { "version": 3 },
+                },
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut backend = backend(server.uri(), token_auth("s.root"));
+        backend.namespace = Some("team-a".into());
+        let secrets = retrieve(
+            &backend,
+            &[
+                "secret/data/my-app:username",
+                "/secret/data/my-app/:password",
+            ],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(secrets["secret/data/my-app:username"], "vector");
+        assert_eq!(secrets["/secret/data/my-app/:password"], "hunter2");
+    }
+
+    #[tokio::test]
+    async fn retrieves_kv_v1_secrets() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/kv/my-app"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "port": 5432, "data": "not nested" },
+            })))
+            .mount(&server)
+            .await;
+
+        let backend = backend(server.uri(), token_auth("s.root"));
+        let secrets = retrieve(&backend, &["kv/my-app:port", "kv/my-app:data"])
+            .await
+            .unwrap();
+
+        assert_eq!(secrets["kv/my-app:port"], "5432");
+        assert_eq!(secrets["kv/my-app:data"], "not nested");
+    }
+
+    #[tokio::test]
+    async fn logs_in_with_approle() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/auth/approle/login"))
+            .and(body_json(
+                serde_json::json!({ "role_id": "vector", "secret_id": "s3cr3t" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "auth": { "client_token": "s.login", "lease_duration": 0, "renewable": false },
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/secret/data/my-app"))
+            .and(header("X-Vault-Token", "s.login"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "data": { "password": "hunter2" }, "metadata": {} },
+            })))
+            .mount(&server)
+            .await;
+
+        let backend = backend(
+            server.uri(),
+            VaultAuth::Approle {
+                role_id: "vector".into(),
+                secret_id: "s3cr3t".to_string().into(),
+                mount: default_approle_mount(),
+            },
+        );
+        let secrets = retrieve(&backend, &["secret/data/my-app:password"])
+            .await
+            .unwrap();
+        assert_eq!(secrets["secret/data/my-app:password"], "hunter2");
+
+        // The token is cached, so logging in again isn't necessary.
+        retrieve(&backend, &["secret/data/my-app:password"])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn refreshes_leased_secrets() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/database/creds/readonly"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "lease_id": "database/creds/readonly/1",
+                "lease_duration": 3600,
+                "renewable": false,
+                "data": { "username": "v-1", "password": "first" },
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/database/creds/readonly"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "lease_id": "database/creds/readonly/2",
+                "lease_duration": 3600,
+                "renewable": false,
+                "data": { "username": "v-2", "password": "second" },
+            })))
+            .mount(&server)
+            .await;
+
+        let backend = backend(server.uri(), token_auth("s.root"));
+        let keys = ["database/creds/readonly:password"];
+        assert_eq!(retrieve(&backend, &keys).await.unwrap()[keys[0]], "first");
+
+        // The leased secret is cached, so the rotated credentials are only seen when refreshing.
+        assert_eq!(retrieve(&backend, &keys).await.unwrap()[keys[0]], "first");
+        assert_eq!(refresh(&backend, &keys).await.unwrap()[keys[0]], "second");
+
+        // Refreshing replaces the cached secret, so that reloading uses the rotated credentials.
+        assert_eq!(retrieve(&backend, &keys).await.unwrap()[keys[0]], "second");
+    }
+
+    #[tokio::test]
+    async fn reports_vault_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/secret/data/denied"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+                "errors": ["permission denied"],
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/secret/data/my-app"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "data": { "username": "vector", "password": "" }, "metadata": {} },
+            })))
+            .mount(&server)
+            .await;
+
+        let backend = backend(server.uri(), token_auth("s.root"));
+        let error = retrieve(&backend, &["secret/data/denied:password"])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Vault responded with 403 Forbidden for 'secret/data/denied': permission denied"
+        );
+
+        let error = retrieve(&backend, &["secret/data/my-app:missing"])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "secret for key 'secret/data/my-app:missing' was not retrieved"
+        );
+
+        let error = retrieve(&backend, &["secret/data/my-app:password"])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "secret for key 'secret/data/my-app:password' was empty"
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! Secret rotation.
+//!
+//! Secrets are resolved once, when the config is loaded, and interpolated into the component
+//! configs. To pick up rotated credentials without a restart, the configured secret backends are
+//! periodically queried again, and if any secret has a new value, a reload from disk is triggered.
+//! As the resolved secrets are part of the component configs, only the components using a rotated
+//! secret are rebuilt by the reload, and the components they replace are drained gracefully.
+
+use std::{collections::HashMap, time::Duration};
+
+use async_stream::stream;
+use futures::Stream;
+use tokio::time;
+
+use crate::{
+    config::{self, ConfigPath},
+    signal::{SignalTo, SignalTx},
+};
+
+/// Periodically re-resolves the secrets referenced by the config at `config_paths`, yielding a
+/// reload from disk whenever any of them has been rotated.
+pub fn poll_secrets(
+    config_paths: Vec<ConfigPath>,
+    refresh_interval: Duration,
+    signal_tx: SignalTx,
+) -> impl Stream<Item = SignalTo> {
+    let mut interval = time::interval(refresh_interval);
+    let mut resolved: Option<HashMap<String, String>> = None;
+
+    info!(
+        message = "Watching secrets for rotation.",
+        refresh_interval_secs = refresh_interval.as_secs()
+    );
+
+    stream! {
+        loop {
+            interval.tick().await;
+
+            let paths = config_paths.clone();
+            let mut signal_rx = signal_tx.subscribe();
+            let secrets = tokio::task::spawn_blocking(move || {
+                let (mut loader, _) = config::load_secret_backends_from_paths(&paths)
+                    .map_err(|errors| errors.join(" "))?;
+                if loader.has_secrets_to_retrieve() {
+                    loader.refresh(&mut signal_rx)
+                } else {
+                    Ok(HashMap::new())
+                }
+            })
+            .await
+            .expect("Secret retrieval task panicked.");
+
+            let secrets = match secrets {
+                Ok(secrets) => secrets,
+                Err(error) => {
+                    warn!(message = "Unable to refresh secrets.", %error);
+                    continue;
+                }
+            };
+
+            let rotated = resolved
+                .as_ref()
+                .map(|previous| rotated_secrets(previous, &secrets))
+                .unwrap_or_default();
+            resolved = Some(secrets);
+
+            if !rotated.is_empty() {
+                info!(message = "Secrets have been rotated, reloading.", secrets = ?rotated);
+                yield SignalTo::ReloadFromDisk;
+            }
+        }
+    }
+}
+
+/// Returns the keys, in order, of the secrets whose value differs between `previous` and
+/// `current`.
+///
+/// Secrets that were added or removed are not considered rotated, as that can only happen when the
+/// config itself has changed, which is already handled by reloading the config.
+fn rotated_secrets(
+    previous: &HashMap<String, String>,
+    current: &HashMap<String, String>,
+) -> Vec<String> {
+    let mut rotated = current
+        .iter()
+        .filter(|(key, value)| matches!(previous.get(*key), Some(previous) if previous != *value))
+        .map(|(key, _)| key.clone())
+        .collect::<Vec<_>>();
+    rotated.sort();
+    rotated
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+    use crate::{config::Format, signal::SignalHandler};
+
+    fn secrets(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn detects_rotated_secrets() {
+        let previous = secrets(&[
+            ("vault.db:password", "old"),
+            ("exec.kafka_password", "old"),
+            ("exec.unchanged", "same"),
+            ("exec.removed", "gone"),
+        ]);
+        let current = secrets(&[
+            ("vault.db:password", "new"),
+            ("exec.kafka_password", "new"),
+            ("exec.unchanged", "same"),
+            ("exec.added", "new"),
+        ]);
+
+        assert_eq!(
+            rotated_secrets(&previous, &current),
+            vec!["exec.kafka_password", "vault.db:password"]
+        );
+        assert!(rotated_secrets(&current, &current).is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn reloads_when_a_leased_vault_secret_is_rotated() {
+        let server = MockServer::start().await;
+        for (lease, password) in [(1, "first"), (2, "second")] {
+            Mock::given(method("GET"))
+                .and(path("/v1/database/creds/readonly"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "lease_id": format!("database/creds/readonly/{}", lease),
+                    "lease_duration": 3600,
+                    "renewable": false,
+                    "data": { "password": password },
+                })))
+                .up_to_n_times(1)
+                .mount(&server)
+                .await;
+        }
+
+        let config_path = crate::test_util::temp_file();
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"
+                [secret.vault]
+                type = "vault"
+                endpoint = "{}"
+                auth.strategy = "token"
+                auth.token = "s.root"
+
+                [sources.in]
+                type = "demo_logs"
+                format = "shuffle"
+                lines = ["SECRET[vault.database/creds/readonly:password]"]
+                "#,
+                server.uri()
+            ),
+        )
+        .unwrap();
+
+        let (signal_handler, _) = SignalHandler::new();
+        let signals = poll_secrets(
+            vec![ConfigPath::File(config_path, Some(Format::Toml))],
+            Duration::from_millis(10),
+            signal_handler.clone_tx(),
+        );
+        futures::pin_mut!(signals);
+
+        // The leased secret is cached after the first poll, so the rotation is only noticed if the
+        // second poll reads the secret from Vault again.
+        let signal = time::timeout(Duration::from_secs(10), signals.next())
+            .await
+            .expect("Rotated secret was not noticed.");
+        assert!(matches!(signal, Some(SignalTo::ReloadFromDisk)));
+    }
+}
@@ -1,4 +1,6 @@
 #![allow(missing_docs)]
+use std::time::Duration;
+
 use tokio::sync::broadcast;
 use tokio_stream::{Stream, StreamExt};
 
@@ -18,6 +20,9 @@ pub enum SignalTo {
     ReloadFromDisk,
     /// Signal to shutdown process.
     Shutdown,
+    /// Signal to drain the topology and shutdown process, giving components the given amount of
+    /// time, or the graceful shutdown limit if not set, to flush their events.
+    Drain(Option<Duration>),
     /// Shutdown process immediately.
     Quit,
 }
@@ -127,6 +132,8 @@ pub fn os_signals() -> impl Stream<Item = SignalTo> {
     let mut sigterm = signal(SignalKind::terminate()).expect("Signal handlers should not panic.");
     let mut sigquit = signal(SignalKind::quit()).expect("Signal handlers should not panic.");
     let mut sighup = signal(SignalKind::hangup()).expect("Signal handlers should not panic.");
+    let mut sigusr2 =
+        signal(SignalKind::user_defined2()).expect("Signal handlers should not panic.");
 
     async_stream::stream! {
         loop {
@@ -135,6 +142,7 @@ pub fn os_signals() -> impl Stream<Item = SignalTo> {
                 _ = sigterm.recv() => SignalTo::Shutdown,
                 _ = sigquit.recv() => SignalTo::Quit,
                 _ = sighup.recv() => SignalTo::ReloadFromDisk,
+                _ = sigusr2.recv() => SignalTo::Drain(None),
             };
             yield signal;
         }
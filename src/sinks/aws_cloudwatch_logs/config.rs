@@ -1,6 +1,6 @@
 use aws_sdk_cloudwatchlogs::Client as CloudwatchLogsClient;
 use aws_smithy_types::retry::RetryConfig;
-use codecs::JsonSerializerConfig;
+use codecs::{encoding::SerializerConfig, JsonSerializerConfig};
 use futures::FutureExt;
 use tower::ServiceBuilder;
 use value::Kind;
@@ -19,8 +19,9 @@ use crate::{
     },
     sinks::{
         aws_cloudwatch_logs::{
-            healthcheck::healthcheck, request_builder::CloudwatchRequestBuilder,
-            retry::CloudwatchRetryLogic, service::CloudwatchLogsPartitionSvc, sink::CloudwatchSink,
+            emf::EmbeddedMetricsConfig, healthcheck::healthcheck,
+            request_builder::CloudwatchRequestBuilder, retry::CloudwatchRetryLogic,
+            service::CloudwatchLogsPartitionSvc, sink::CloudwatchSink,
         },
         util::{
             http::RequestConfig, BatchConfig, Compression, ServiceBuilderExt, SinkBatchSettings,
@@ -96,6 +97,9 @@ pub struct CloudwatchLogsSinkConfig {
     #[configurable(derived)]
     pub encoding: EncodingConfig,
 
+    #[configurable(derived)]
+    pub embedded_metrics: Option<EmbeddedMetricsConfig>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub compression: Compression,
@@ -163,11 +167,21 @@ impl CloudwatchLogsSinkConfig {
 #[async_trait::async_trait]
 impl SinkConfig for CloudwatchLogsSinkConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let embedded_metrics = match &self.embedded_metrics {
+            Some(embedded_metrics) => {
+                if !matches!(self.encoding.config(), SerializerConfig::Json(_)) {
+                    return Err("Embedded metrics must be encoded with the `json` codec.".into());
+                }
+                Some(embedded_metrics.build()?)
+            }
+            None => None,
+        };
         let batcher_settings = self.batch.into_batcher_settings()?;
         let request_settings = self
             .request
             .tower
-            .unwrap_with(&TowerRequestConfig::default());
+            .unwrap_with(&TowerRequestConfig::default())
+            .with_context(&cx);
         let client = self.create_client(cx.proxy()).await?;
         let smithy_client = self.create_smithy_client(cx.proxy()).await?;
         let svc = ServiceBuilder::new()
@@ -186,6 +200,7 @@ impl SinkConfig for CloudwatchLogsSinkConfig {
             request_builder: CloudwatchRequestBuilder {
                 group_template: self.group_name.clone(),
                 stream_template: self.stream_name.clone(),
+                embedded_metrics,
                 transformer,
                 encoder,
             },
@@ -200,7 +215,13 @@ impl SinkConfig for CloudwatchLogsSinkConfig {
         let requirement =
             schema::Requirement::empty().optional_meaning("timestamp", Kind::timestamp());
 
-        Input::new(self.encoding.config().input_type() & DataType::Log)
+        let data_type = if self.embedded_metrics.is_some() {
+            DataType::Log | DataType::Metric
+        } else {
+            DataType::Log
+        };
+
+        Input::new(self.encoding.config().input_type() & data_type)
             .with_schema_requirement(requirement)
     }
 
@@ -218,6 +239,7 @@ impl GenerateConfig for CloudwatchLogsSinkConfig {
 fn default_config(encoding: EncodingConfig) -> CloudwatchLogsSinkConfig {
     CloudwatchLogsSinkConfig {
         encoding,
+        embedded_metrics: None,
         group_name: Default::default(),
         stream_name: Default::default(),
         region: Default::default(),
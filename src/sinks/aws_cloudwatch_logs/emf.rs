@@ -0,0 +1,346 @@
+//! Support for the CloudWatch [Embedded Metric Format][emf] (EMF).
+//!
+//! [emf]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/monitoring/CloudWatch_Embedded_Metric_Format_Specification.html
+
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use lookup::{event_path, lookup_v2::parse_value_path, OwnedValuePath, PathPrefix};
+use vector_config::configurable_component;
+use vector_core::{
+    config::log_schema,
+    event::{Event, LogEvent, Metric, MetricValue, Value},
+};
+
+use crate::{
+    internal_events::{AwsCloudwatchLogsUnsupportedMetricError, TemplateRenderingError},
+    template::Template,
+};
+
+/// The header that tells CloudWatch Logs to extract the metrics of EMF documents.
+pub(super) const EMF_FORMAT_HEADER: (&str, &str) = ("x-amzn-logs-format", "json/emf");
+
+/// Embedded Metric Format configuration.
+///
+/// Events are turned into [Embedded Metric Format][emf] documents, from which CloudWatch extracts
+/// metrics. Metric events are turned into documents holding their value, with their tags as
+/// dimensions. Log events holding any of the configured metric fields are turned into documents
+/// holding these metrics, and other log events are sent as is.
+///
+/// The documents must be encoded with the `json` codec.
+///
+/// [emf]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/monitoring/CloudWatch_Embedded_Metric_Format_Specification.html
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EmbeddedMetricsConfig {
+    /// The CloudWatch namespace of the metrics.
+    #[configurable(metadata(docs::examples = "service"))]
+    #[configurable(metadata(docs::examples = "{{ namespace }}"))]
+    pub namespace: Template,
+
+    /// The metrics of log events, extracted from their fields.
+    #[serde(default)]
+    pub metrics: Vec<EmbeddedMetricField>,
+
+    /// A mapping of the dimensions of the metrics of log events to the fields holding their
+    /// values.
+    ///
+    /// Dimensions whose field is missing from an event are left out.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The path of the field holding the value of the dimension."
+    ))]
+    #[configurable(metadata(docs::examples = "example_dimensions()"))]
+    pub dimensions: BTreeMap<String, String>,
+}
+
+fn example_dimensions() -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("Service".to_owned(), "service".to_owned()),
+        ("Region".to_owned(), "aws.region".to_owned()),
+    ])
+}
+
+/// A metric extracted from the field of log events.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EmbeddedMetricField {
+    /// The name of the metric.
+    #[configurable(metadata(docs::examples = "Latency"))]
+    pub name: String,
+
+    /// The field holding the value of the metric.
+    ///
+    /// The field must hold a number, or an array of numbers.
+    #[configurable(metadata(docs::examples = "duration_ms"))]
+    pub field: String,
+
+    /// The [unit][units] of the metric.
+    ///
+    /// [units]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/APIReference/API_MetricDatum.html
+    #[configurable(metadata(docs::examples = "Milliseconds"))]
+    pub unit: Option<String>,
+}
+
+/// Builds the EMF documents of events.
+#[derive(Clone, Debug)]
+pub struct EmbeddedMetrics {
+    namespace: Template,
+    metrics: Vec<(EmbeddedMetricField, OwnedValuePath)>,
+    dimensions: Vec<(String, OwnedValuePath)>,
+}
+
+impl EmbeddedMetricsConfig {
+    pub fn build(&self) -> crate::Result<EmbeddedMetrics> {
+        let parse_path = |path: &str| {
+            parse_value_path(path)
+                .map_err(|error| format!("Invalid field path `{}`: {}", path, error))
+        };
+
+        Ok(EmbeddedMetrics {
+            namespace: self.namespace.clone(),
+            metrics: self
+                .metrics
+                .iter()
+                .map(|metric| Ok((metric.clone(), parse_path(&metric.field)?)))
+                .collect::<Result<_, String>>()?,
+            dimensions: self
+                .dimensions
+                .iter()
+                .map(|(name, path)| Ok((name.clone(), parse_path(path)?)))
+                .collect::<Result<_, String>>()?,
+        })
+    }
+}
+
+impl EmbeddedMetrics {
+    /// Turns an event into an EMF document, or returns `None` if the event must be dropped.
+    pub fn apply(&self, event: Event) -> Option<Event> {
+        let namespace = match self.namespace.render_string(&event) {
+            Ok(namespace) => namespace,
+            Err(error) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("embedded_metrics.namespace"),
+                    drop_event: true,
+                });
+                return None;
+            }
+        };
+
+        match event {
+            Event::Log(log) => Some(self.log_document(log, namespace).into()),
+            Event::Metric(metric) => metric_document(metric, namespace).map(Into::into),
+            Event::Trace(_) => None,
+        }
+    }
+
+    fn log_document(&self, mut log: LogEvent, namespace: String) -> LogEvent {
+        let mut metrics = Vec::new();
+        for (metric, path) in &self.metrics {
+            if let Some(value) = log.get((PathPrefix::Event, path)).cloned() {
+                log.insert(event_path!(metric.name.as_str()), value);
+                metrics.push(metric_directive(&metric.name, metric.unit.as_deref()));
+            }
+        }
+        if metrics.is_empty() {
+            return log;
+        }
+
+        let mut dimensions = Vec::new();
+        for (name, path) in &self.dimensions {
+            if let Some(value) = log.get((PathPrefix::Event, path)) {
+                let value = value.to_string_lossy().into_owned();
+                log.insert(event_path!(name.as_str()), value);
+                dimensions.push(Value::from(name.as_str()));
+            }
+        }
+
+        let timestamp = match log.get(log_schema().timestamp_key()) {
+            Some(Value::Timestamp(timestamp)) => *timestamp,
+            _ => Utc::now(),
+        };
+        log.insert(
+            "_aws",
+            metadata(timestamp.timestamp_millis(), namespace, dimensions, metrics),
+        );
+        log
+    }
+}
+
+fn metric_document(metric: Metric, namespace: String) -> Option<LogEvent> {
+    let value = match metric.value() {
+        MetricValue::Counter { value } | MetricValue::Gauge { value } => {
+            Value::from_f64_or_zero(*value)
+        }
+        MetricValue::Set { values } => Value::from(values.len() as i64),
+        MetricValue::Distribution { samples, .. } => Value::from(BTreeMap::from([
+            (
+                "Values".to_owned(),
+                Value::from_iter(
+                    samples
+                        .iter()
+                        .map(|sample| Value::from_f64_or_zero(sample.value)),
+                ),
+            ),
+            (
+                "Counts".to_owned(),
+                Value::from_iter(
+                    samples
+                        .iter()
+                        .map(|sample| Value::from(i64::from(sample.rate))),
+                ),
+            ),
+        ])),
+        value => {
+            emit!(AwsCloudwatchLogsUnsupportedMetricError {
+                metric_type: value.as_name(),
+            });
+            return None;
+        }
+    };
+
+    let timestamp = metric
+        .timestamp()
+        .unwrap_or_else(Utc::now)
+        .timestamp_millis();
+    let name = metric.name().to_owned();
+    let (series, _, event_metadata) = metric.into_parts();
+
+    let mut log = LogEvent::new_with_metadata(event_metadata);
+    let mut dimensions = Vec::new();
+    if let Some(tags) = series.tags() {
+        for (key, value) in tags.iter_single() {
+            log.insert(event_path!(key), value.to_owned());
+            dimensions.push(Value::from(key));
+        }
+    }
+    log.insert(event_path!(name.as_str()), value);
+    log.insert(
+        "_aws",
+        metadata(
+            timestamp,
+            namespace,
+            dimensions,
+            vec![metric_directive(&name, None)],
+        ),
+    );
+    Some(log)
+}
+
+/// The `_aws` metadata object of an EMF document.
+fn metadata(
+    timestamp: i64,
+    namespace: String,
+    dimensions: Vec<Value>,
+    metrics: Vec<Value>,
+) -> Value {
+    let directive = BTreeMap::from([
+        ("Namespace".to_owned(), Value::from(namespace)),
+        (
+            "Dimensions".to_owned(),
+            Value::from(vec![Value::from(dimensions)]),
+        ),
+        ("Metrics".to_owned(), Value::from(metrics)),
+    ]);
+    Value::from(BTreeMap::from([
+        ("Timestamp".to_owned(), Value::from(timestamp)),
+        (
+            "CloudWatchMetrics".to_owned(),
+            Value::from(vec![Value::from(directive)]),
+        ),
+    ]))
+}
+
+fn metric_directive(name: &str, unit: Option<&str>) -> Value {
+    let mut directive = BTreeMap::from([("Name".to_owned(), Value::from(name))]);
+    if let Some(unit) = unit {
+        directive.insert("Unit".to_owned(), Value::from(unit));
+    }
+    Value::from(directive)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::event::{MetricKind, MetricTags};
+
+    use super::*;
+
+    fn embedded_metrics() -> EmbeddedMetrics {
+        EmbeddedMetricsConfig {
+            namespace: "{{ namespace }}".try_into().unwrap(),
+            metrics: vec![EmbeddedMetricField {
+                name: "Latency".to_owned(),
+                field: "duration_ms".to_owned(),
+                unit: Some("Milliseconds".to_owned()),
+            }],
+            dimensions: BTreeMap::from([("Service".to_owned(), "service".to_owned())]),
+        }
+        .build()
+        .unwrap()
+    }
+
+    #[test]
+    fn log_document() {
+        let mut log = LogEvent::from("request");
+        log.insert("namespace", "app");
+        log.insert("service", "api");
+        log.insert("duration_ms", 12);
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+        );
+
+        let document = embedded_metrics().apply(log.into()).unwrap().into_log();
+
+        assert_eq!(document.get("Latency"), Some(&Value::from(12)));
+        assert_eq!(document.get("Service"), Some(&Value::from("api")));
+        assert_eq!(
+            document.get("_aws"),
+            Some(&vrl::value!({
+                "CloudWatchMetrics": [{
+                    "Dimensions": [["Service"]],
+                    "Metrics": [{ "Name": "Latency", "Unit": "Milliseconds" }],
+                    "Namespace": "app",
+                }],
+                "Timestamp": 1_700_000_000_000_i64,
+            }))
+        );
+    }
+
+    #[test]
+    fn log_without_metrics_is_unchanged() {
+        let mut log = LogEvent::from("request");
+        log.insert("namespace", "app");
+
+        let document = embedded_metrics().apply(log.clone().into()).unwrap();
+
+        assert_eq!(document.into_log(), log);
+    }
+
+    #[test]
+    fn metric_document() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 3.0 },
+        )
+        .with_tags(Some(MetricTags::from_iter([(
+            "namespace".to_owned(),
+            "app".to_owned(),
+        )])))
+        .with_timestamp(Some(Utc.timestamp_opt(1_700_000_000, 0).unwrap()));
+
+        let document = embedded_metrics().apply(metric.into()).unwrap().into_log();
+
+        assert_eq!(document.get("requests"), Some(&Value::from(3.0)));
+        assert_eq!(document.get("namespace"), Some(&Value::from("app")));
+        assert_eq!(
+            document.get("_aws.CloudWatchMetrics[0].Dimensions"),
+            Some(&vrl::value!([["namespace"]]))
+        );
+    }
+}
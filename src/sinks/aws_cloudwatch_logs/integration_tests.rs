@@ -42,6 +42,7 @@ async fn cloudwatch_insert_log_event() {
         group_name: Template::try_from(GROUP_NAME).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
         encoding: TextSerializerConfig::default().into(),
+        embedded_metrics: None,
         create_missing_group: true,
         create_missing_stream: true,
         compression: Default::default(),
@@ -92,6 +93,7 @@ async fn cloudwatch_insert_log_events_sorted() {
         group_name: Template::try_from(GROUP_NAME).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
         encoding: TextSerializerConfig::default().into(),
+        embedded_metrics: None,
         create_missing_group: true,
         create_missing_stream: true,
         compression: Default::default(),
@@ -161,6 +163,7 @@ async fn cloudwatch_insert_out_of_range_timestamp() {
         group_name: Template::try_from(GROUP_NAME).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
         encoding: TextSerializerConfig::default().into(),
+        embedded_metrics: None,
         create_missing_group: true,
         create_missing_stream: true,
         compression: Default::default(),
@@ -234,6 +237,7 @@ async fn cloudwatch_dynamic_group_and_stream_creation() {
         group_name: Template::try_from(group_name.as_str()).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
         encoding: TextSerializerConfig::default().into(),
+        embedded_metrics: None,
         create_missing_group: true,
         create_missing_stream: true,
         compression: Default::default(),
@@ -289,6 +293,7 @@ async fn cloudwatch_insert_log_event_batched() {
         group_name: Template::try_from(group_name.as_str()).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
         encoding: TextSerializerConfig::default().into(),
+        embedded_metrics: None,
         create_missing_group: true,
         create_missing_stream: true,
         compression: Default::default(),
@@ -339,6 +344,7 @@ async fn cloudwatch_insert_log_event_partitioned() {
         stream_name: Template::try_from(format!("{}-{{{{key}}}}", stream_name)).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
         encoding: TextSerializerConfig::default().into(),
+        embedded_metrics: None,
         create_missing_group: true,
         create_missing_stream: true,
         compression: Default::default(),
@@ -431,6 +437,7 @@ async fn cloudwatch_healthcheck() {
         group_name: Template::try_from(GROUP_NAME).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
         encoding: TextSerializerConfig::default().into(),
+        embedded_metrics: None,
         create_missing_group: true,
         create_missing_stream: true,
         compression: Default::default(),
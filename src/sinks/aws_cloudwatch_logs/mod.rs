@@ -1,4 +1,5 @@
 mod config;
+mod emf;
 mod healthcheck;
 mod request;
 mod request_builder;
@@ -14,7 +14,10 @@ use crate::{
     codecs::{Encoder, Transformer},
     event::{Event, Value},
     internal_events::AwsCloudwatchLogsMessageSizeError,
-    sinks::{aws_cloudwatch_logs::CloudwatchKey, util::metadata::RequestMetadataBuilder},
+    sinks::{
+        aws_cloudwatch_logs::{emf::EmbeddedMetrics, CloudwatchKey},
+        util::metadata::RequestMetadataBuilder,
+    },
     template::Template,
 };
 
@@ -47,6 +50,7 @@ impl MetaDescriptive for CloudwatchRequest {
 pub struct CloudwatchRequestBuilder {
     pub group_template: Template,
     pub stream_template: Template,
+    pub embedded_metrics: Option<EmbeddedMetrics>,
     pub transformer: Transformer,
     pub encoder: Encoder<()>,
 }
@@ -78,6 +82,10 @@ impl CloudwatchRequestBuilder {
         };
         let key = CloudwatchKey { group, stream };
 
+        if let Some(embedded_metrics) = &self.embedded_metrics {
+            event = embedded_metrics.apply(event)?;
+        }
+
         let timestamp = match event.as_mut_log().remove_timestamp() {
             Some(Value::Timestamp(ts)) => ts.timestamp_millis(),
             _ => Utc::now().timestamp_millis(),
@@ -144,6 +152,7 @@ mod tests {
         let mut request_builder = CloudwatchRequestBuilder {
             group_template: "group".try_into().unwrap(),
             stream_template: "stream".try_into().unwrap(),
+            embedded_metrics: None,
             transformer: Default::default(),
             encoder: Default::default(),
         };
@@ -30,8 +30,8 @@ use crate::{
     event::EventStatus,
     sinks::{
         aws_cloudwatch_logs::{
-            config::CloudwatchLogsSinkConfig, request, retry::CloudwatchRetryLogic,
-            sink::BatchCloudwatchRequest, CloudwatchKey,
+            config::CloudwatchLogsSinkConfig, emf::EMF_FORMAT_HEADER, request,
+            retry::CloudwatchRetryLogic, sink::BatchCloudwatchRequest, CloudwatchKey,
         },
         util::{
             retries::FixedRetryPolicy, EncodedLength, TowerRequestConfig, TowerRequestSettings,
@@ -223,8 +223,16 @@ impl CloudwatchLogsSvc {
         let create_missing_group = config.create_missing_group;
         let create_missing_stream = config.create_missing_stream;
 
+        let mut headers = config.request.headers;
+        if config.embedded_metrics.is_some() {
+            let (name, value) = EMF_FORMAT_HEADER;
+            headers
+                .entry(name.to_owned())
+                .or_insert_with(|| value.to_owned());
+        }
+
         CloudwatchLogsSvc {
-            headers: config.request.headers,
+            headers,
             client,
             smithy_client,
             stream_name,
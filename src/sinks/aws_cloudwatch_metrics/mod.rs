@@ -66,6 +66,23 @@ pub struct CloudWatchMetricsSinkConfig {
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
 
+    /// Whether to store the metrics at a [high resolution][high_resolution] of one second.
+    ///
+    /// By default, metrics are stored at a resolution of one minute. High-resolution metrics can
+    /// be retrieved with periods of one second, but cost more.
+    ///
+    /// [high_resolution]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/monitoring/cloudwatch_concepts.html#Resolution_definition
+    #[serde(default)]
+    pub high_resolution: bool,
+
+    /// The tags sent as the dimensions of the metrics, in order.
+    ///
+    /// Tags missing from a metric are left out, and other tags are dropped. By default, all the
+    /// tags are sent, ordered by name, up to the limit of 30 dimensions per metric.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "host", docs::examples = "region"))]
+    pub dimensions: Option<Vec<String>>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub compression: Compression,
@@ -127,7 +144,7 @@ impl SinkConfig for CloudWatchMetricsSinkConfig {
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let client = self.create_client(&cx.proxy).await?;
         let healthcheck = self.clone().healthcheck(client.clone()).boxed();
-        let sink = CloudWatchMetricsSvc::new(self.clone(), client)?;
+        let sink = CloudWatchMetricsSvc::new(self.clone(), client, &cx)?;
         Ok((sink, healthcheck))
     }
 
@@ -201,33 +218,79 @@ impl RetryLogic for CloudWatchMetricsRetryLogic {
     }
 }
 
-fn tags_to_dimensions(tags: &MetricTags) -> Vec<Dimension> {
+/// The maximum number of dimensions of a metric.
+const MAX_DIMENSIONS: usize = 30;
+
+/// The maximum number of metrics of a `PutMetricData` request.
+const MAX_METRICS_PER_REQUEST: usize = 1000;
+
+/// The maximum number of values of a metric.
+const MAX_VALUES_PER_METRIC: usize = 150;
+
+fn tags_to_dimensions(tags: &MetricTags, selection: Option<&[String]>) -> Vec<Dimension> {
+    let dimension =
+        |(name, value): (&str, &str)| Dimension::builder().name(name).value(value).build();
+    let dimensions: Vec<_> = match selection {
+        Some(selection) => selection
+            .iter()
+            .filter_map(|name| tags.get(name).map(|value| (name.as_str(), value)))
+            .map(dimension)
+            .collect(),
+        None => tags.iter_single().map(dimension).collect(),
+    };
+
     // according to the API, up to 30 dimensions per metric can be provided
-    tags.iter_single()
-        .take(30)
-        .map(|(k, v)| Dimension::builder().name(k).value(v).build())
-        .collect()
+    if dimensions.len() > MAX_DIMENSIONS {
+        warn!(
+            message = "Metric has too many dimensions, dropping the last ones.",
+            dimensions = dimensions.len(),
+            max_dimensions = MAX_DIMENSIONS,
+            internal_log_rate_limit = true,
+        );
+    }
+    dimensions.into_iter().take(MAX_DIMENSIONS).collect()
 }
 
 #[derive(Clone)]
 pub struct CloudWatchMetricsSvc {
     client: CloudwatchClient,
+    storage_resolution: Option<i32>,
+    dimensions: Option<Vec<String>>,
 }
 
 impl CloudWatchMetricsSvc {
     pub fn new(
         config: CloudWatchMetricsSinkConfig,
         client: CloudwatchClient,
+        cx: &SinkContext,
     ) -> crate::Result<VectorSink> {
+        if let Some(dimensions) = &config.dimensions {
+            if dimensions.len() > MAX_DIMENSIONS {
+                return Err(format!(
+                    "At most {} dimensions can be sent, got {}",
+                    MAX_DIMENSIONS,
+                    dimensions.len()
+                )
+                .into());
+            }
+        }
+
         let default_namespace = config.default_namespace.clone();
         let batch = config.batch.into_batch_settings()?;
-        let request_settings = config.request.unwrap_with(
-            &TowerRequestConfig::default()
-                .timeout_secs(30)
-                .rate_limit_num(150),
-        );
+        let request_settings = config
+            .request
+            .unwrap_with(
+                &TowerRequestConfig::default()
+                    .timeout_secs(30)
+                    .rate_limit_num(150),
+            )
+            .with_context(cx);
 
-        let service = CloudWatchMetricsSvc { client };
+        let service = CloudWatchMetricsSvc {
+            client,
+            storage_resolution: config.high_resolution.then_some(1),
+            dimensions: config.dimensions,
+        };
         let buffer = PartitionBuffer::new(MetricsBuffer::new(batch.size));
         let mut normalizer = MetricNormalizer::<AwsCloudwatchMetricNormalize>::default();
 
@@ -256,51 +319,43 @@ impl CloudWatchMetricsSvc {
     fn encode_events(&mut self, events: Vec<Metric>) -> Vec<MetricDatum> {
         events
             .into_iter()
-            .filter_map(|event| {
+            .flat_map(|event| {
                 let metric_name = event.name().to_string();
                 let timestamp = event
                     .timestamp()
                     .map(|x| AwsDateTime::from_millis(x.timestamp_millis()));
-                let dimensions = event.tags().map(tags_to_dimensions);
+                let dimensions = event
+                    .tags()
+                    .map(|tags| tags_to_dimensions(tags, self.dimensions.as_deref()));
+                let datum = MetricDatum::builder()
+                    .metric_name(metric_name)
+                    .set_timestamp(timestamp)
+                    .set_dimensions(dimensions)
+                    .set_storage_resolution(self.storage_resolution);
                 // AwsCloudwatchMetricNormalize converts these to the right MetricKind
                 match event.value() {
-                    MetricValue::Counter { value } => Some(
-                        MetricDatum::builder()
-                            .metric_name(metric_name)
-                            .value(*value)
-                            .set_timestamp(timestamp)
-                            .set_dimensions(dimensions)
-                            .build(),
-                    ),
+                    MetricValue::Counter { value } | MetricValue::Gauge { value } => {
+                        vec![datum.value(*value).build()]
+                    }
+                    // Distributions with more values than a metric can hold are split into
+                    // several metrics, which CloudWatch aggregates.
                     MetricValue::Distribution {
                         samples,
                         statistic: _,
-                    } => Some(
-                        MetricDatum::builder()
-                            .metric_name(metric_name)
-                            .set_values(Some(samples.iter().map(|s| s.value).collect()))
-                            .set_counts(Some(samples.iter().map(|s| s.rate as f64).collect()))
-                            .set_timestamp(timestamp)
-                            .set_dimensions(dimensions)
-                            .build(),
-                    ),
-                    MetricValue::Set { values } => Some(
-                        MetricDatum::builder()
-                            .metric_name(metric_name)
-                            .value(values.len() as f64)
-                            .set_timestamp(timestamp)
-                            .set_dimensions(dimensions)
-                            .build(),
-                    ),
-                    MetricValue::Gauge { value } => Some(
-                        MetricDatum::builder()
-                            .metric_name(metric_name)
-                            .value(*value)
-                            .set_timestamp(timestamp)
-                            .set_dimensions(dimensions)
-                            .build(),
-                    ),
-                    _ => None,
+                    } => samples
+                        .chunks(MAX_VALUES_PER_METRIC)
+                        .map(|samples| {
+                            datum
+                                .clone()
+                                .set_values(Some(samples.iter().map(|s| s.value).collect()))
+                                .set_counts(Some(samples.iter().map(|s| s.rate as f64).collect()))
+                                .build()
+                        })
+                        .collect(),
+                    MetricValue::Set { values } => {
+                        vec![datum.value(values.len() as f64).build()]
+                    }
+                    _ => Vec::new(),
                 }
             })
             .collect()
@@ -328,12 +383,15 @@ impl Service<PartitionInnerBuffer<Vec<Metric>, String>> for CloudWatchMetricsSvc
         let client = self.client.clone();
 
         Box::pin(async move {
-            client
-                .put_metric_data()
-                .namespace(namespace)
-                .set_metric_data(Some(metric_data))
-                .send()
-                .await?;
+            // Batches holding more metrics than a request can are sent over several requests.
+            for metric_data in metric_data.chunks(MAX_METRICS_PER_REQUEST) {
+                client
+                    .put_metric_data()
+                    .namespace(&namespace)
+                    .set_metric_data(Some(metric_data.to_vec()))
+                    .send()
+                    .await?;
+            }
             Ok(())
         })
     }
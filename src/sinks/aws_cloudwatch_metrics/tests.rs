@@ -4,7 +4,7 @@ use similar_asserts::assert_eq;
 use vector_core::metric_tags;
 
 use super::*;
-use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
+use crate::event::metric::{Metric, MetricKind, MetricValue, Sample, StatisticKind};
 
 fn timestamp(time: &str) -> DateTime {
     DateTime::from_millis(
@@ -33,7 +33,11 @@ async fn svc() -> CloudWatchMetricsSvc {
         .create_client(&ProxyConfig::from_env())
         .await
         .unwrap();
-    CloudWatchMetricsSvc { client }
+    CloudWatchMetricsSvc {
+        client,
+        storage_resolution: None,
+        dimensions: None,
+    }
 }
 
 #[tokio::test]
@@ -145,3 +149,55 @@ async fn encode_events_set() {
             .build()]
     );
 }
+
+#[tokio::test]
+async fn encode_events_high_resolution_dimensions() {
+    let events = vec![Metric::new(
+        "requests",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 1.0 },
+    )
+    .with_tags(Some(
+        metric_tags!("region" => "local", "host" => "a", "path" => "/"),
+    ))];
+
+    let mut svc = CloudWatchMetricsSvc {
+        storage_resolution: Some(1),
+        dimensions: Some(vec!["region".into(), "method".into(), "host".into()]),
+        ..svc().await
+    };
+
+    assert_eq!(
+        svc.encode_events(events),
+        vec![MetricDatum::builder()
+            .metric_name("requests")
+            .value(1.0)
+            .storage_resolution(1)
+            .dimensions(Dimension::builder().name("region").value("local").build())
+            .dimensions(Dimension::builder().name("host").value("a").build())
+            .build()]
+    );
+}
+
+#[tokio::test]
+async fn encode_events_split_distribution() {
+    let events = vec![Metric::new(
+        "latency",
+        MetricKind::Incremental,
+        MetricValue::Distribution {
+            samples: (0..200)
+                .map(|value| Sample {
+                    value: value as f64,
+                    rate: 1,
+                })
+                .collect(),
+            statistic: StatisticKind::Histogram,
+        },
+    )];
+
+    let metric_data = svc().await.encode_events(events);
+
+    assert_eq!(metric_data.len(), 2);
+    assert_eq!(metric_data[0].values().unwrap().len(), 150);
+    assert_eq!(metric_data[1].values().unwrap().len(), 50);
+}
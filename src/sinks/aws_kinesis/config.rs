@@ -11,7 +11,7 @@ use vector_core::{
 use crate::{
     aws::{AwsAuthentication, RegionOrEndpoint},
     codecs::{Encoder, EncodingConfig},
-    config::AcknowledgementsConfig,
+    config::{AcknowledgementsConfig, SinkContext},
     sinks::util::{retries::RetryLogic, Compression, ServiceBuilderExt, TowerRequestConfig},
     tls::TlsConfig,
 };
@@ -83,6 +83,7 @@ pub async fn build_sink<C, R, RR, E, RT>(
     partition_key_field: Option<String>,
     batch_settings: BatcherSettings,
     client: C,
+    cx: &SinkContext,
 ) -> crate::Result<VectorSink>
 where
     C: SendRecord + Clone + Send + Sync + 'static,
@@ -94,7 +95,10 @@ where
     E: Send + 'static,
     RT: RetryLogic<Response = KinesisResponse> + Default,
 {
-    let request_limits = config.request.unwrap_with(&TowerRequestConfig::default());
+    let request_limits = config
+        .request
+        .unwrap_with(&TowerRequestConfig::default())
+        .with_context(cx);
 
     let region = config.region.region();
     let service = ServiceBuilder::new()
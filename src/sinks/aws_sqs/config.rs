@@ -14,7 +14,7 @@ use crate::{
         AcknowledgementsConfig, DataType, GenerateConfig, Input, ProxyConfig, SinkConfig,
         SinkContext,
     },
-    sinks::util::TowerRequestConfig,
+    sinks::util::{size_budget::SizeBudgetConfig, TowerRequestConfig},
     template::{Template, TemplateParseError},
     tls::TlsConfig,
 };
@@ -69,6 +69,10 @@ pub struct SqsSinkConfig {
     #[serde(default)]
     pub request: TowerRequestConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub size_budget: Option<SizeBudgetConfig>,
+
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
 
@@ -118,7 +122,7 @@ impl SinkConfig for SqsSinkConfig {
     ) -> crate::Result<(crate::sinks::VectorSink, crate::sinks::Healthcheck)> {
         let client = self.create_client(&cx.proxy).await?;
         let healthcheck = self.clone().healthcheck(client.clone()).boxed();
-        let sink = super::sink::SqsSink::new(self.clone(), client)?;
+        let sink = super::sink::SqsSink::new(self.clone(), client, &cx)?;
         Ok((
             crate::sinks::VectorSink::from_event_streamsink(sink),
             healthcheck,
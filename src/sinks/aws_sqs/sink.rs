@@ -1,16 +1,18 @@
 use std::num::NonZeroUsize;
 
 use aws_sdk_sqs::Client as SqsClient;
-use futures::stream::BoxStream;
+use futures::stream::{self, BoxStream};
 use futures_util::StreamExt;
 use vector_core::sink::StreamSink;
 
 use super::{config::SqsSinkConfig, request_builder::SqsRequestBuilder, service::SqsService};
 use crate::internal_events::SinkRequestBuildError;
 use crate::{
+    config::SinkContext,
     event::Event,
     sinks::util::{
-        builder::SinkBuilderExt, ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig,
+        builder::SinkBuilderExt, size_budget::SizeBudget, RequestBuilder, ServiceBuilderExt,
+        SinkBatchSettings, TowerRequestConfig, TowerRequestSettings,
     },
 };
 
@@ -27,28 +29,42 @@ impl SinkBatchSettings for SqsSinkDefaultBatchSettings {
 pub(crate) struct SqsSink {
     request_builder: SqsRequestBuilder,
     service: SqsService,
-    request: TowerRequestConfig,
+    request: TowerRequestSettings,
+    size_budget: Option<SizeBudget>,
 }
 
 impl SqsSink {
-    pub fn new(config: SqsSinkConfig, client: SqsClient) -> crate::Result<Self> {
-        let request = config.request;
+    pub fn new(config: SqsSinkConfig, client: SqsClient, cx: &SinkContext) -> crate::Result<Self> {
+        let request = config
+            .request
+            .unwrap_with(&TowerRequestConfig::default().timeout_secs(30))
+            .with_context(cx);
+        let size_budget = config.size_budget.clone();
+        let request_builder = SqsRequestBuilder::new(config)?;
+        let size_budget = size_budget
+            .map(|size_budget| SizeBudget::new(&size_budget, request_builder.encoder().clone()))
+            .transpose()?;
         Ok(SqsSink {
-            request_builder: SqsRequestBuilder::new(config)?,
+            request_builder,
             service: SqsService::new(client),
             request,
+            size_budget,
         })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
-        let request = self
-            .request
-            .unwrap_with(&TowerRequestConfig::default().timeout_secs(30));
         let request_builder_concurrency_limit = NonZeroUsize::new(50);
         let service = tower::ServiceBuilder::new()
-            .settings(request, super::retry::SqsRetryLogic)
+            .settings(self.request, super::retry::SqsRetryLogic)
             .service(self.service);
 
+        let input = match self.size_budget {
+            Some(size_budget) => input
+                .flat_map(move |event| stream::iter(size_budget.apply(event)))
+                .boxed(),
+            None => input,
+        };
+
         input
             .request_builder(request_builder_concurrency_limit, self.request_builder)
             .filter_map(|req| async move {
@@ -1,16 +1,19 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, num::NonZeroU32};
 
+use http::{header::AUTHORIZATION, Request, StatusCode, Uri};
+use serde_json::json;
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
 use crate::{
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::HttpClient,
     sinks::{
         elasticsearch::{ElasticsearchApiVersion, ElasticsearchAuth, ElasticsearchConfig},
         util::{http::RequestConfig, Compression},
-        Healthcheck, VectorSink,
+        Healthcheck, HealthcheckError, VectorSink,
     },
-    tls::TlsConfig,
+    tls::{TlsConfig, TlsSettings},
 };
 
 static CLOUD_URL: &str = "https://api.axiom.co";
@@ -35,6 +38,12 @@ pub struct AxiomConfig {
     org_id: Option<String>,
 
     /// The Axiom API token.
+    ///
+    /// To rotate the token without restarting Vector, read it from a [secret backend][secrets],
+    /// such as `SECRET[vault.axiom_token]`, and run Vector with `--secrets-refresh-interval-secs`.
+    /// The sink is then reloaded with the rotated token once the secret backend returns it.
+    ///
+    /// [secrets]: https://vector.dev/docs/reference/configuration/global-options/#secret
     #[configurable(metadata(docs::examples = "${AXIOM_TOKEN}"))]
     #[configurable(metadata(docs::examples = "123abc"))]
     token: SensitiveString,
@@ -44,6 +53,10 @@ pub struct AxiomConfig {
     #[configurable(metadata(docs::examples = "vector.dev"))]
     dataset: String,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    create_dataset: CreateDatasetConfig,
+
     #[configurable(derived)]
     #[serde(default)]
     request: RequestConfig,
@@ -64,6 +77,27 @@ pub struct AxiomConfig {
     acknowledgements: AcknowledgementsConfig,
 }
 
+/// Creation of the dataset when it doesn't exist.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct CreateDatasetConfig {
+    /// Whether to create the dataset when the sink is started, if it doesn't exist yet.
+    ///
+    /// The token must be allowed to create datasets, which ingest tokens aren't.
+    #[serde(default)]
+    enabled: bool,
+
+    /// The description of the created dataset.
+    #[configurable(metadata(docs::examples = "Logs of the checkout service"))]
+    description: Option<String>,
+
+    /// The number of days the events of the created dataset are retained for.
+    ///
+    /// If not set, the retention of the organization applies.
+    #[configurable(metadata(docs::examples = 30))]
+    retention_days: Option<NonZeroU32>,
+}
+
 impl GenerateConfig for AxiomConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(
@@ -79,6 +113,12 @@ impl GenerateConfig for AxiomConfig {
 #[async_trait::async_trait]
 impl SinkConfig for AxiomConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if self.create_dataset.enabled {
+            let tls = TlsSettings::from_options(&self.tls)?;
+            let client = HttpClient::new(tls, cx.proxy())?;
+            self.ensure_dataset(&client).await?;
+        }
+
         let mut request = self.request.clone();
         request.headers.insert(
             "X-Axiom-Org-Id".to_string(),
@@ -118,14 +158,59 @@ impl SinkConfig for AxiomConfig {
 }
 
 impl AxiomConfig {
+    fn base_url(&self) -> &str {
+        self.url.as_deref().unwrap_or(CLOUD_URL)
+    }
+
     fn build_endpoint(&self) -> String {
-        let url = if let Some(url) = self.url.as_ref() {
-            url.clone()
-        } else {
-            CLOUD_URL.to_string()
-        };
+        format!("{}/v1/datasets/{}/elastic", self.base_url(), self.dataset)
+    }
+
+    fn api_request(&self, request: http::request::Builder) -> http::request::Builder {
+        let request = request.header(AUTHORIZATION, format!("Bearer {}", self.token.inner()));
+        match &self.org_id {
+            Some(org_id) => request.header("X-Axiom-Org-Id", org_id),
+            None => request,
+        }
+    }
+
+    fn create_dataset_body(&self) -> serde_json::Value {
+        let mut body = json!({
+            "name": self.dataset,
+            "description": self.create_dataset.description.as_deref().unwrap_or_default(),
+        });
+        if let Some(retention_days) = self.create_dataset.retention_days {
+            body["useRetentionPeriod"] = true.into();
+            body["retentionDays"] = retention_days.get().into();
+        }
+        body
+    }
 
-        format!("{}/v1/datasets/{}/elastic", url, self.dataset)
+    /// Creates the dataset, unless it already exists.
+    async fn ensure_dataset(&self, client: &HttpClient) -> crate::Result<()> {
+        let uri = format!("{}/v1/datasets/{}", self.base_url(), self.dataset).parse::<Uri>()?;
+        let request = self
+            .api_request(Request::get(uri))
+            .body(hyper::Body::empty())?;
+        match client.send(request).await?.status() {
+            StatusCode::OK => return Ok(()),
+            StatusCode::NOT_FOUND => (),
+            status => return Err(HealthcheckError::UnexpectedStatus { status }.into()),
+        }
+
+        let uri = format!("{}/v1/datasets", self.base_url()).parse::<Uri>()?;
+        let request = self
+            .api_request(Request::post(uri))
+            .header("Content-Type", "application/json")
+            .body(hyper::Body::from(self.create_dataset_body().to_string()))?;
+        match client.send(request).await?.status() {
+            // The dataset may have been created concurrently, such as by another Vector instance.
+            status if status.is_success() || status == StatusCode::CONFLICT => {
+                info!(message = "Created Axiom dataset.", dataset = %self.dataset);
+                Ok(())
+            }
+            status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+        }
     }
 }
 
@@ -135,6 +220,27 @@ mod test {
     fn generate_config() {
         crate::test_util::test_generate_config::<super::AxiomConfig>();
     }
+
+    #[test]
+    fn create_dataset_body() {
+        let config: super::AxiomConfig = toml::from_str(
+            r#"token = "token"
+            dataset = "logs"
+            create_dataset.enabled = true
+            create_dataset.retention_days = 30"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.create_dataset_body(),
+            serde_json::json!({
+                "name": "logs",
+                "description": "",
+                "useRetentionPeriod": true,
+                "retentionDays": 30,
+            })
+        );
+    }
 }
 
 #[cfg(feature = "axiom-integration-tests")]
@@ -179,7 +179,7 @@ impl SinkConfig for AzureBlobSinkConfig {
             self.container_name.clone(),
             Arc::clone(&client),
         )?;
-        let sink = self.build_processor(client)?;
+        let sink = self.build_processor(client, &cx)?;
         Ok((sink, healthcheck))
     }
 
@@ -197,10 +197,15 @@ const DEFAULT_FILENAME_TIME_FORMAT: &str = "%s";
 const DEFAULT_FILENAME_APPEND_UUID: bool = true;
 
 impl AzureBlobSinkConfig {
-    pub fn build_processor(&self, client: Arc<ContainerClient>) -> crate::Result<VectorSink> {
+    pub fn build_processor(
+        &self,
+        client: Arc<ContainerClient>,
+        cx: &SinkContext,
+    ) -> crate::Result<VectorSink> {
         let request_limits = self
             .request
-            .unwrap_with(&TowerRequestConfig::default().rate_limit_num(250));
+            .unwrap_with(&TowerRequestConfig::default().rate_limit_num(250))
+            .with_context(cx);
         let service = ServiceBuilder::new()
             .settings(request_limits, AzureBlobRetryLogic)
             .service(AzureBlobService::new(client));
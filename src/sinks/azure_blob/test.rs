@@ -62,7 +62,7 @@ fn azure_blob_build_request_without_compression() {
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
-                TextSerializerConfig::default().build().into(),
+                TextSerializerConfig::default().build().unwrap().into(),
             ),
         ),
         compression,
@@ -107,7 +107,7 @@ fn azure_blob_build_request_with_compression() {
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
-                TextSerializerConfig::default().build().into(),
+                TextSerializerConfig::default().build().unwrap().into(),
             ),
         ),
         compression,
@@ -151,7 +151,7 @@ fn azure_blob_build_request_with_time_format() {
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
-                TextSerializerConfig::default().build().into(),
+                TextSerializerConfig::default().build().unwrap().into(),
             ),
         ),
         compression,
@@ -199,7 +199,7 @@ fn azure_blob_build_request_with_uuid() {
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
-                TextSerializerConfig::default().build().into(),
+                TextSerializerConfig::default().build().unwrap().into(),
             ),
         ),
         compression,
@@ -191,7 +191,10 @@ impl SinkConfig for AzureMonitorLogsConfig {
         let client = HttpClient::new(Some(tls_settings), &cx.proxy)?;
 
         let sink = AzureMonitorLogsSink::new(self, time_generated_key)?;
-        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let request_settings = self
+            .request
+            .unwrap_with(&TowerRequestConfig::default())
+            .with_context(&cx);
 
         let healthcheck = healthcheck(sink.clone(), client.clone()).boxed();
 
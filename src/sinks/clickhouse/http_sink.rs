@@ -26,7 +26,10 @@ pub(crate) async fn build_http_sink(
     cx: SinkContext,
 ) -> crate::Result<(VectorSink, Healthcheck)> {
     let batch = cfg.batch.into_batch_settings()?;
-    let request = cfg.request.unwrap_with(&TowerRequestConfig::default());
+    let request = cfg
+        .request
+        .unwrap_with(&TowerRequestConfig::default())
+        .with_context(&cx);
     let tls_settings = TlsSettings::from_options(&cfg.tls)?;
     let client = HttpClient::new(tls_settings, &cx.proxy)?;
 
@@ -1,6 +1,6 @@
 use codecs::{
-    encoding::{Framer, FramingConfig},
-    JsonSerializerConfig,
+    encoding::{Framer, FramingConfig, SerializerConfig},
+    JsonSerializerConfig, PrettySerializerConfig,
 };
 use futures::{future, FutureExt};
 use tokio::io;
@@ -32,6 +32,24 @@ pub enum Target {
     Stderr,
 }
 
+impl Target {
+    /// Whether the stream is a terminal.
+    fn is_terminal(&self) -> bool {
+        #[cfg(unix)]
+        {
+            atty::is(match self {
+                Target::Stdout => atty::Stream::Stdout,
+                Target::Stderr => atty::Stream::Stderr,
+            })
+        }
+        // ANSI colors are not supported by cmd.exe
+        #[cfg(not(unix))]
+        {
+            false
+        }
+    }
+}
+
 /// Configuration for the `console` sink.
 #[configurable_component(sink("console"))]
 #[derive(Clone, Debug)]
@@ -73,6 +91,13 @@ impl SinkConfig for ConsoleSinkConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let transformer = self.encoding.transformer();
         let (framer, serializer) = self.encoding.build(SinkType::StreamBased)?;
+        // Only this sink knows whether the `pretty` codec writes to a terminal, to color its output.
+        let serializer = match self.encoding.config().1 {
+            SerializerConfig::Pretty { pretty } => PrettySerializerConfig::new(pretty.clone())
+                .build_for_terminal(self.target.is_terminal())
+                .into(),
+            _ => serializer,
+        };
         let encoder = Encoder::<Framer>::new(framer, serializer);
 
         let sink: VectorSink = match self.target {
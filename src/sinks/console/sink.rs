@@ -3,7 +3,6 @@ use bytes::BytesMut;
 use codecs::encoding::Framer;
 use futures::{stream::BoxStream, StreamExt};
 use tokio::{io, io::AsyncWriteExt};
-use tokio_util::codec::Encoder as _;
 use vector_core::{
     internal_event::{
         ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle as _, Output, Protocol,
@@ -13,7 +12,7 @@ use vector_core::{
 
 use crate::{
     codecs::{Encoder, Transformer},
-    event::{Event, EventStatus, Finalizable},
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
     sinks::util::StreamSink,
 };
 
@@ -28,16 +27,29 @@ impl<T> StreamSink<Event> for WriterSink<T>
 where
     T: io::AsyncWrite + Send + Sync + Unpin,
 {
-    async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let bytes_sent = register!(BytesSent::from(Protocol("console".into(),)));
         let events_sent = register!(EventsSent::from(Output(None)));
-        while let Some(mut event) = input.next().await {
-            let event_byte_size = event.estimated_json_encoded_size_of();
-            self.transformer.transform(&mut event);
+        // With compressed framing, the events written at once are compressed into a single frame.
+        let batch_size = if self.encoder.compresses_batches() {
+            1024
+        } else {
+            1
+        };
+        let mut input = input.ready_chunks(batch_size);
+        while let Some(mut events) = input.next().await {
+            let count = events.len();
+            let events_byte_size = events.estimated_json_encoded_size_of();
+            for event in &mut events {
+                self.transformer.transform(event);
+            }
 
-            let finalizers = event.take_finalizers();
+            let finalizers = events
+                .iter_mut()
+                .map(Finalizable::take_finalizers)
+                .collect::<EventFinalizers>();
             let mut bytes = BytesMut::new();
-            self.encoder.encode(event, &mut bytes).map_err(|_| {
+            self.encoder.encode_batch(events, &mut bytes).map_err(|_| {
                 // Error is handled by `Encoder`.
                 finalizers.update_status(EventStatus::Errored);
             })?;
@@ -53,7 +65,7 @@ where
                 Ok(()) => {
                     finalizers.update_status(EventStatus::Delivered);
 
-                    events_sent.emit(CountByteSize(1, event_byte_size));
+                    events_sent.emit(CountByteSize(count, events_byte_size));
                     bytes_sent.emit(ByteSize(bytes.len()));
                 }
             }
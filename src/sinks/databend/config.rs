@@ -114,7 +114,10 @@ impl SinkConfig for DatabendConfig {
             DatabendAPIClient::new(self.build_client(&cx)?, endpoint.clone(), auth.clone());
         let healthcheck = select_one(health_client).boxed();
 
-        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let request_settings = self
+            .request
+            .unwrap_with(&TowerRequestConfig::default())
+            .with_context(&cx);
         let batch_settings = self.batch.into_batcher_settings()?;
 
         let database = config.database;
@@ -80,7 +80,7 @@ impl DatadogEventsConfig {
         Ok(client)
     }
 
-    fn build_sink(&self, client: HttpClient) -> crate::Result<VectorSink> {
+    fn build_sink(&self, client: HttpClient, cx: &SinkContext) -> crate::Result<VectorSink> {
         let service = DatadogEventsService::new(
             self.get_api_events_endpoint(),
             self.default_api_key.clone().into(),
@@ -88,7 +88,9 @@ impl DatadogEventsConfig {
         );
 
         let request_opts = self.request;
-        let request_settings = request_opts.unwrap_with(&TowerRequestConfig::default());
+        let request_settings = request_opts
+            .unwrap_with(&TowerRequestConfig::default())
+            .with_context(cx);
         let retry_logic = HttpStatusRetryLogic::new(|req: &DatadogEventsResponse| req.http_status);
 
         let service = ServiceBuilder::new()
@@ -110,7 +112,7 @@ impl SinkConfig for DatadogEventsConfig {
             self.default_api_key.clone().into(),
             self.region.as_ref(),
         )?;
-        let sink = self.build_sink(client)?;
+        let sink = self.build_sink(client, &cx)?;
 
         Ok((sink, healthcheck))
     }
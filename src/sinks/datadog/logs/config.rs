@@ -22,6 +22,7 @@ use crate::{
         },
         Healthcheck, VectorSink,
     },
+    template::Template,
     tls::{MaybeTlsSettings, TlsEnableableConfig},
 };
 
@@ -72,10 +73,16 @@ pub struct DatadogLogsConfig {
     #[serde(default)]
     pub region: Option<Region>,
 
-    #[configurable(derived)]
+    /// The compression of the payloads.
+    ///
+    /// The Datadog Logs API accepts payloads compressed with `gzip`, `zlib`, or `zstd`.
     #[serde(default)]
     pub compression: Option<Compression>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub reserved_attributes: ReservedAttributes,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -92,6 +99,33 @@ pub struct DatadogLogsConfig {
     pub request: RequestConfig,
 }
 
+/// Datadog [reserved attributes][reserved_attributes] to set on each log event.
+///
+/// Each attribute is set to the rendered template, replacing any value the event already holds,
+/// so that events don't need to be remapped beforehand. Attributes whose template fails to render
+/// are left as is.
+///
+/// [reserved_attributes]: https://docs.datadoghq.com/logs/log_configuration/attributes_naming_convention/#reserved-attributes
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct ReservedAttributes {
+    /// The name of the application or service that generated the log events.
+    #[configurable(metadata(docs::examples = "checkout", docs::examples = "{{ app }}"))]
+    pub service: Option<Template>,
+
+    /// The name of the integration, that is the technology the log events originate from.
+    #[configurable(metadata(docs::examples = "nginx", docs::examples = "{{ source_type }}"))]
+    pub ddsource: Option<Template>,
+
+    /// The tags of the log events, as a comma-separated list of `key:value` pairs.
+    #[configurable(metadata(docs::examples = "env:prod,team:{{ team }}"))]
+    pub ddtags: Option<Template>,
+
+    /// The name of the host that generated the log events.
+    #[configurable(metadata(docs::examples = "{{ kubernetes.pod_node_name }}"))]
+    pub hostname: Option<Template>,
+}
+
 impl GenerateConfig for DatadogLogsConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(indoc! {r#"
@@ -128,9 +162,24 @@ impl DatadogLogsConfig {
         self.get_uri().scheme_str().unwrap_or("http").to_string()
     }
 
-    pub fn build_processor(&self, client: HttpClient) -> crate::Result<VectorSink> {
+    pub fn build_processor(
+        &self,
+        client: HttpClient,
+        cx: &SinkContext,
+    ) -> crate::Result<VectorSink> {
+        let compression = self.compression.unwrap_or_default();
+        if matches!(compression, Compression::Snappy) {
+            return Err(
+                "The Datadog Logs API doesn't accept payloads compressed with `snappy`.".into(),
+            );
+        }
+
         let default_api_key: Arc<str> = Arc::from(self.default_api_key.inner());
-        let request_limits = self.request.tower.unwrap_with(&Default::default());
+        let request_limits = self
+            .request
+            .tower
+            .unwrap_with(&Default::default())
+            .with_context(cx);
 
         // We forcefully cap the provided batch configuration to the size/log line limits imposed by
         // the Datadog Logs API, but we still allow them to be lowered if need be.
@@ -153,7 +202,8 @@ impl DatadogLogsConfig {
         let protocol = self.get_protocol();
 
         let sink = LogSinkBuilder::new(encoding, service, default_api_key, batch, protocol)
-            .compression(self.compression.unwrap_or_default())
+            .compression(compression)
+            .reserved_attributes(self.reserved_attributes.clone())
             .build();
 
         Ok(VectorSink::from_event_streamsink(sink))
@@ -184,7 +234,7 @@ impl SinkConfig for DatadogLogsConfig {
             self.region.as_ref(),
         )?;
 
-        let sink = self.build_processor(client)?;
+        let sink = self.build_processor(client, &cx)?;
 
         Ok((sink, healthcheck))
     }
@@ -15,10 +15,13 @@ use vector_core::{
     stream::{BatcherSettings, DriverResponse},
 };
 
-use super::{config::MAX_PAYLOAD_BYTES, service::LogApiRequest};
+use super::{
+    config::{ReservedAttributes, MAX_PAYLOAD_BYTES},
+    service::LogApiRequest,
+};
 use crate::{
     codecs::{Encoder, Transformer},
-    internal_events::SinkRequestBuildError,
+    internal_events::{SinkRequestBuildError, TemplateRenderingError},
     sinks::util::{
         encoding::{write_all, Encoder as _},
         metadata::RequestMetadataBuilder,
@@ -71,6 +74,11 @@ impl<S> LogSinkBuilder<S> {
         self
     }
 
+    pub fn reserved_attributes(mut self, reserved_attributes: ReservedAttributes) -> Self {
+        self.encoding.reserved_attributes = reserved_attributes;
+        self
+    }
+
     pub fn build(self) -> LogSink<S> {
         LogSink {
             default_api_key: self.default_api_key,
@@ -108,6 +116,7 @@ pub struct LogSink<S> {
 #[derive(Clone, Debug)]
 pub struct JsonEncoding {
     encoder: (Transformer, Encoder<Framer>),
+    reserved_attributes: ReservedAttributes,
 }
 
 impl JsonEncoding {
@@ -120,6 +129,35 @@ impl JsonEncoding {
                     JsonSerializerConfig::default().build().into(),
                 ),
             ),
+            reserved_attributes: ReservedAttributes::default(),
+        }
+    }
+
+    /// Sets the reserved attributes of the event to their rendered templates.
+    fn set_reserved_attributes(&self, event: &mut Event) {
+        let ReservedAttributes {
+            service,
+            ddsource,
+            ddtags,
+            hostname,
+        } = &self.reserved_attributes;
+        for (attribute, template) in [
+            ("service", service),
+            ("ddsource", ddsource),
+            ("ddtags", ddtags),
+            ("hostname", hostname),
+        ] {
+            let Some(template) = template else { continue };
+            match template.render_string(&*event) {
+                Ok(value) => {
+                    event.as_mut_log().insert(event_path!(attribute), value);
+                }
+                Err(error) => emit!(TemplateRenderingError {
+                    error,
+                    field: Some(attribute),
+                    drop_event: false,
+                }),
+            }
         }
     }
 }
@@ -148,6 +186,8 @@ impl crate::sinks::util::encoding::Encoder<Vec<Event>> for JsonEncoding {
                     Value::Integer(ts.timestamp_millis()),
                 );
             }
+
+            self.set_reserved_attributes(event);
         }
 
         self.encoder.encode_input(input, writer)
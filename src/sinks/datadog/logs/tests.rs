@@ -498,3 +498,55 @@ async fn error_is_retriable() {
     //       but are not straightforward to instantiate due to the design of
     //       the crates they originate from.
 }
+
+#[tokio::test]
+/// Assert that the reserved attributes are set to their rendered templates
+async fn reserved_attributes() {
+    let (mut config, cx) = load_sink::<DatadogLogsConfig>(indoc! {r#"
+            default_api_key = "atoken"
+            compression = "none"
+
+            [reserved_attributes]
+            service = "{{ app }}"
+            ddsource = "vector"
+            ddtags = "env:prod,team:{{ team }}"
+        "#})
+    .unwrap();
+
+    let addr = next_addr();
+    // Swap out the endpoint so we can force send it to our local server
+    let endpoint = format!("http://{}", addr);
+    config.dd_common.endpoint = Some(endpoint.clone());
+
+    let (sink, _) = config.build(cx).await.unwrap();
+
+    let (rx, _trigger, server) = test_server(addr, ApiStatus::OKv2);
+    tokio::spawn(server);
+
+    let mut log = LogEvent::from("hello");
+    log.insert("app", "checkout");
+    log.insert("team", "payments");
+    log.insert("service", "overridden");
+
+    sink.run_events(vec![Event::Log(log)]).await.unwrap();
+    let output: (Parts, Bytes) = rx.take(1).collect::<Vec<_>>().await.pop().unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.1[..]).unwrap();
+    let event = json.get(0).unwrap();
+    assert_eq!(event["service"], "checkout");
+    assert_eq!(event["ddsource"], "vector");
+    assert_eq!(event["ddtags"], "env:prod,team:payments");
+    assert!(event.get("hostname").is_none());
+}
+
+#[tokio::test]
+/// Assert that compression algorithms the API doesn't accept are rejected
+async fn rejects_snappy_compression() {
+    let (config, cx) = load_sink::<DatadogLogsConfig>(indoc! {r#"
+            default_api_key = "atoken"
+            compression = "snappy"
+        "#})
+    .unwrap();
+
+    assert!(config.build(cx).await.is_err());
+}
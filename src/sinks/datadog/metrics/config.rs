@@ -140,7 +140,7 @@ impl SinkConfig for DatadogMetricsConfig {
             self.default_api_key.clone().into(),
             self.region.as_ref(),
         )?;
-        let sink = self.build_sink(client)?;
+        let sink = self.build_sink(client, &cx)?;
 
         Ok((sink, healthcheck))
     }
@@ -202,13 +202,16 @@ impl DatadogMetricsConfig {
         Ok(client)
     }
 
-    fn build_sink(&self, client: HttpClient) -> crate::Result<VectorSink> {
+    fn build_sink(&self, client: HttpClient, cx: &SinkContext) -> crate::Result<VectorSink> {
         let batcher_settings = self.batch.into_batcher_settings()?;
 
         // TODO: revisit our concurrency and batching defaults
-        let request_limits = self.request.unwrap_with(
-            &TowerRequestConfig::default().retry_attempts(DEFAULT_REQUEST_RETRY_ATTEMPTS),
-        );
+        let request_limits = self
+            .request
+            .unwrap_with(
+                &TowerRequestConfig::default().retry_attempts(DEFAULT_REQUEST_RETRY_ATTEMPTS),
+            )
+            .with_context(cx);
 
         let endpoint_configuration = self.generate_metrics_endpoint_configuration()?;
         let service = ServiceBuilder::new()
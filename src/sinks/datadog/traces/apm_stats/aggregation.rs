@@ -179,10 +179,11 @@ impl Aggregator {
     pub(crate) fn handle_trace(&mut self, partition_key: &PartitionKey, trace: &TraceEvent) {
         // Based on https://github.com/DataDog/datadog-agent/blob/cfa750c7412faa98e87a015f8ee670e5828bbe7f/pkg/trace/stats/concentrator.go#L148-L184
 
-        let spans = match trace.get("spans") {
-            Some(Value::Array(v)) => v.iter().filter_map(|s| s.as_object()).collect(),
-            _ => vec![],
-        };
+        let spans = trace
+            .spans()
+            .iter()
+            .filter_map(|s| s.as_object())
+            .collect::<Vec<_>>();
 
         let weight = super::weight::extract_weight_from_root_span(&spans);
         let payload_aggkey = PayloadAggregationKey {
@@ -141,13 +141,16 @@ impl DatadogTracesConfig {
         })
     }
 
-    pub fn build_sink(&self, client: HttpClient) -> crate::Result<VectorSink> {
+    pub fn build_sink(&self, client: HttpClient, cx: &SinkContext) -> crate::Result<VectorSink> {
         let default_api_key: Arc<str> = Arc::from(self.default_api_key.inner());
-        let request_limits = self.request.unwrap_with(
-            &TowerRequestConfig::default()
-                .retry_attempts(DEFAULT_REQUEST_RETRY_ATTEMPTS)
-                .retry_max_duration_secs(DEFAULT_REQUEST_RETRY_MAX_DURATION_SECS),
-        );
+        let request_limits = self
+            .request
+            .unwrap_with(
+                &TowerRequestConfig::default()
+                    .retry_attempts(DEFAULT_REQUEST_RETRY_ATTEMPTS)
+                    .retry_max_duration_secs(DEFAULT_REQUEST_RETRY_MAX_DURATION_SECS),
+            )
+            .with_context(cx);
         let endpoints = self.generate_traces_endpoint_configuration()?;
 
         let batcher_settings = self
@@ -232,7 +235,7 @@ impl SinkConfig for DatadogTracesConfig {
             self.default_api_key.clone().into(),
             None,
         )?;
-        let sink = self.build_sink(client)?;
+        let sink = self.build_sink(client, &cx)?;
 
         Ok((sink, healthcheck))
     }
@@ -295,13 +295,11 @@ impl DatadogTracesEncoder {
             })
             .unwrap_or_default();
 
-        let spans = match trace.get("spans") {
-            Some(Value::Array(v)) => v
-                .iter()
-                .filter_map(|s| s.as_object().map(DatadogTracesEncoder::convert_span))
-                .collect(),
-            _ => vec![],
-        };
+        let spans = trace
+            .spans()
+            .iter()
+            .filter_map(|s| s.as_object().map(DatadogTracesEncoder::convert_span))
+            .collect();
 
         let chunk = dd_proto::TraceChunk {
             priority: trace
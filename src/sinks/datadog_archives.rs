@@ -295,7 +295,7 @@ impl DatadogArchivesSinkConfig {
                         .await?;
                 let client = service.client();
                 let svc = self
-                    .build_s3_sink(&s3_config.options, service)
+                    .build_s3_sink(&s3_config.options, service, &cx)
                     .map_err(|error| error.to_string())?;
                 Ok((
                     svc,
@@ -314,7 +314,7 @@ impl DatadogArchivesSinkConfig {
                     None,
                 )?;
                 let svc = self
-                    .build_azure_sink(Arc::<ContainerClient>::clone(&client))
+                    .build_azure_sink(Arc::<ContainerClient>::clone(&client), &cx)
                     .map_err(|error| error.to_string())?;
                 let healthcheck =
                     azure_common::config::build_healthcheck(self.bucket.clone(), client)?;
@@ -336,7 +336,7 @@ impl DatadogArchivesSinkConfig {
                     auth.clone(),
                 )?;
                 let sink = self
-                    .build_gcs_sink(client, base_url, auth)
+                    .build_gcs_sink(client, base_url, auth, &cx)
                     .map_err(|error| error.to_string())?;
                 Ok((sink, healthcheck))
             }
@@ -351,10 +351,14 @@ impl DatadogArchivesSinkConfig {
         &self,
         s3_options: &S3Options,
         service: S3Service,
+        cx: &SinkContext,
     ) -> Result<VectorSink, ConfigError> {
         // we use lower default limits, because we send 100mb batches,
         // thus no need of the higher number of outgoing requests
-        let request_limits = self.request.unwrap_with(&Default::default());
+        let request_limits = self
+            .request
+            .unwrap_with(&Default::default())
+            .with_context(cx);
         let service = ServiceBuilder::new()
             .settings(request_limits, S3RetryLogic)
             .service(service);
@@ -399,8 +403,12 @@ impl DatadogArchivesSinkConfig {
         client: HttpClient,
         base_url: String,
         auth: GcpAuthenticator,
+        cx: &SinkContext,
     ) -> crate::Result<VectorSink> {
-        let request = self.request.unwrap_with(&Default::default());
+        let request = self
+            .request
+            .unwrap_with(&Default::default())
+            .with_context(cx);
         let protocol = get_http_scheme_from_uri(&base_url.parse::<Uri>()?);
 
         let batcher_settings = BatchConfig::<DatadogArchivesDefaultBatchSettings>::default()
@@ -455,8 +463,15 @@ impl DatadogArchivesSinkConfig {
         Ok(VectorSink::from_event_streamsink(sink))
     }
 
-    fn build_azure_sink(&self, client: Arc<ContainerClient>) -> crate::Result<VectorSink> {
-        let request_limits = self.request.unwrap_with(&Default::default());
+    fn build_azure_sink(
+        &self,
+        client: Arc<ContainerClient>,
+        cx: &SinkContext,
+    ) -> crate::Result<VectorSink> {
+        let request_limits = self
+            .request
+            .unwrap_with(&Default::default())
+            .with_context(cx);
         let service = ServiceBuilder::new()
             .settings(request_limits, AzureBlobRetryLogic)
             .service(AzureBlobService::new(client));
@@ -18,7 +18,8 @@ use crate::{
     http::{Auth, HttpClient, MaybeAuth},
     sinks::{
         elasticsearch::{
-            ElasticsearchAuth, ElasticsearchCommonMode, ElasticsearchConfig, ParseError,
+            BulkActionParameterTemplates, ElasticsearchAuth, ElasticsearchCommonMode,
+            ElasticsearchConfig, ParseError,
         },
         util::{http::RequestConfig, TowerRequestConfig, UriSerde},
         HealthcheckError,
@@ -34,6 +35,7 @@ pub struct ElasticsearchCommon {
     pub http_auth: Option<Auth>,
     pub aws_auth: Option<SharedCredentialsProvider>,
     pub mode: ElasticsearchCommonMode,
+    pub action_parameters: BulkActionParameterTemplates,
     pub request_builder: ElasticsearchRequestBuilder,
     pub tls_settings: TlsSettings,
     pub region: Option<Region>,
@@ -87,6 +89,8 @@ impl ElasticsearchCommon {
         };
 
         let mode = config.common_mode()?;
+        let action_parameters = config.bulk_action_parameters();
+        let bulk_update = config.bulk_update();
 
         let tower_request = config
             .request
@@ -99,8 +103,13 @@ impl ElasticsearchCommon {
             format!("{}s", tower_request.timeout.as_secs()),
         );
 
-        if let Some(pipeline) = &config.pipeline {
-            query_params.insert("pipeline".into(), pipeline.into());
+        // Templated pipelines are set for each event instead.
+        if let Some(pipeline) = config
+            .pipeline
+            .as_ref()
+            .filter(|pipeline| !pipeline.is_dynamic())
+        {
+            query_params.insert("pipeline".into(), pipeline.get_ref().into());
         }
 
         let bulk_url = {
@@ -186,6 +195,7 @@ impl ElasticsearchCommon {
                 transformer: config.encoding.clone(),
                 doc_type,
                 suppress_type_name,
+                update: bulk_update,
             },
         };
 
@@ -195,6 +205,7 @@ impl ElasticsearchCommon {
             bulk_uri,
             aws_auth,
             mode,
+            action_parameters,
             request_builder,
             query_params,
             request,
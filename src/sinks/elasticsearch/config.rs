@@ -19,8 +19,8 @@ use crate::{
             retry::ElasticsearchRetryLogic,
             service::{ElasticsearchService, HttpRequestBuilder},
             sink::ElasticsearchSink,
-            ElasticsearchApiVersion, ElasticsearchAuth, ElasticsearchCommon,
-            ElasticsearchCommonMode, ElasticsearchMode,
+            BulkActionParameterTemplates, ElasticsearchApiVersion, ElasticsearchAuth,
+            ElasticsearchCommon, ElasticsearchCommonMode, ElasticsearchMode,
         },
         util::{
             http::RequestConfig, service::HealthConfig, BatchConfig, Compression,
@@ -111,10 +111,33 @@ pub struct ElasticsearchConfig {
     pub id_key: Option<String>,
 
     /// The name of the pipeline to apply.
+    ///
+    /// If templated, the pipeline is set for each event, rather than for each request.
     #[serde(default)]
     #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::templateable))]
     #[configurable(metadata(docs::examples = "pipeline-name"))]
-    pub pipeline: Option<String>,
+    #[configurable(metadata(docs::examples = "{{ pipeline }}"))]
+    pub pipeline: Option<Template>,
+
+    /// The [routing value][es_routing] used to route each event to a shard.
+    ///
+    /// [es_routing]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-routing-field.html
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(docs::examples = "{{ tenant_id }}"))]
+    pub routing: Option<Template>,
+
+    /// Whether the index of each event must be an index alias.
+    ///
+    /// Events are only required to target an alias if the rendered value is `true`.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(docs::examples = "true"))]
+    #[configurable(metadata(docs::examples = "{{ require_alias }}"))]
+    pub require_alias: Option<Template>,
 
     #[serde(default)]
     #[configurable(derived)]
@@ -203,6 +226,8 @@ impl Default for ElasticsearchConfig {
             request_retry_partial: false,
             id_key: None,
             pipeline: None,
+            routing: None,
+            require_alias: None,
             mode: Default::default(),
             compression: Default::default(),
             encoding: Default::default(),
@@ -228,6 +253,26 @@ impl ElasticsearchConfig {
             .map(|bulk_config| bulk_config.action.clone())
     }
 
+    pub fn bulk_update(&self) -> Option<BulkUpdateConfig> {
+        self.bulk
+            .as_ref()
+            .and_then(|bulk_config| bulk_config.update.clone())
+    }
+
+    /// The templates of the parameters set for each event.
+    ///
+    /// A pipeline that isn't templated is set for each request instead.
+    pub fn bulk_action_parameters(&self) -> BulkActionParameterTemplates {
+        BulkActionParameterTemplates {
+            pipeline: self
+                .pipeline
+                .clone()
+                .filter(|pipeline| pipeline.is_dynamic()),
+            routing: self.routing.clone(),
+            require_alias: self.require_alias.clone(),
+        }
+    }
+
     pub fn index(&self) -> Option<Template> {
         self.bulk
             .as_ref()
@@ -254,7 +299,8 @@ impl ElasticsearchConfig {
 pub struct BulkConfig {
     /// Action to use when making requests to the [Elasticsearch Bulk API][es_bulk].
     ///
-    /// Currently, Vector only supports `index` and `create`. `update` and `delete` actions are not supported.
+    /// Currently, Vector only supports `index`, `create`, and `update`. The `delete` action is not
+    /// supported.
     ///
     /// [es_bulk]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html
     #[serde(default = "default_bulk_action")]
@@ -267,6 +313,54 @@ pub struct BulkConfig {
     #[configurable(metadata(docs::examples = "application-{{ application_id }}-%Y-%m-%d"))]
     #[configurable(metadata(docs::examples = "{{ index }}"))]
     pub index: Template,
+
+    #[configurable(derived)]
+    pub update: Option<BulkUpdateConfig>,
+}
+
+/// Elasticsearch bulk `update` action configuration.
+///
+/// Documents are updated by ID, so events sent with the `update` action must have an ID, set by
+/// `id_key`.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BulkUpdateConfig {
+    /// Whether to index the event as a new document when the document to update doesn't exist.
+    ///
+    /// Only applies if `script` isn't set.
+    #[serde(default)]
+    pub doc_as_upsert: bool,
+
+    #[configurable(derived)]
+    pub script: Option<BulkUpdateScriptConfig>,
+}
+
+/// The script updating the documents.
+///
+/// If not set, the fields of the event are merged into the document.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BulkUpdateScriptConfig {
+    /// The source of the script.
+    ///
+    /// The event is available to the script as `params.event`.
+    #[configurable(metadata(docs::examples = "ctx._source.count += params.event.count"))]
+    pub source: String,
+
+    /// The language of the script.
+    ///
+    /// Defaults to the default language of Elasticsearch, `painless`.
+    #[configurable(metadata(docs::examples = "painless"))]
+    pub lang: Option<String>,
+
+    /// Whether to run the script when the document doesn't exist, instead of indexing the event as
+    /// a new document.
+    ///
+    /// The script then creates the document, starting from an empty `ctx._source`.
+    #[serde(default)]
+    pub scripted_upsert: bool,
 }
 
 fn default_bulk_action() -> Template {
@@ -282,6 +376,7 @@ impl Default for BulkConfig {
         Self {
             action: default_bulk_action(),
             index: default_index(),
+            update: None,
         }
     }
 }
@@ -484,7 +579,8 @@ impl SinkConfig for ElasticsearchConfig {
         let request_limits = self
             .request
             .tower
-            .unwrap_with(&TowerRequestConfig::default());
+            .unwrap_with(&TowerRequestConfig::default())
+            .with_context(&cx);
 
         let health_config = self.endpoint_health.clone().unwrap_or_default();
 
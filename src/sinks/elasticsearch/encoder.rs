@@ -1,14 +1,15 @@
 use std::{io, io::Write};
 
 use serde::Serialize;
+use serde_json::Value as JsonValue;
 use vector_buffers::EventCount;
 use vector_core::{event::Event, ByteSizeOf, EstimatedJsonEncodedSizeOf};
 
 use crate::{
     codecs::Transformer,
-    event::{EventFinalizers, Finalizable, LogEvent},
+    event::{EventFinalizers, Finalizable, LogEvent, Value},
     sinks::{
-        elasticsearch::BulkAction,
+        elasticsearch::{BulkAction, BulkUpdateConfig},
         util::encoding::{as_tracked_write, Encoder},
     },
 };
@@ -17,10 +18,47 @@ use crate::{
 pub struct ProcessedEvent {
     pub index: String,
     pub bulk_action: BulkAction,
+    pub parameters: BulkActionParameters,
     pub log: LogEvent,
     pub id: Option<String>,
 }
 
+/// The parameters of the bulk API action of an event.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BulkActionParameters {
+    pub pipeline: Option<String>,
+    pub routing: Option<String>,
+    pub require_alias: bool,
+}
+
+/// The body of an `update` action merging the event into the document.
+#[derive(Serialize)]
+struct DocumentUpdate<'a> {
+    doc: &'a LogEvent,
+    doc_as_upsert: bool,
+}
+
+/// The body of an `update` action running a script.
+#[derive(Serialize)]
+struct ScriptedUpdate<'a> {
+    script: UpdateScript<'a>,
+    upsert: &'a Value,
+    scripted_upsert: bool,
+}
+
+#[derive(Serialize)]
+struct UpdateScript<'a> {
+    source: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lang: Option<&'a str>,
+    params: UpdateScriptParams<'a>,
+}
+
+#[derive(Serialize)]
+struct UpdateScriptParams<'a> {
+    event: &'a Value,
+}
+
 impl Finalizable for ProcessedEvent {
     fn take_finalizers(&mut self) -> EventFinalizers {
         self.log.metadata_mut().take_finalizers()
@@ -29,7 +67,11 @@ impl Finalizable for ProcessedEvent {
 
 impl ByteSizeOf for ProcessedEvent {
     fn allocated_bytes(&self) -> usize {
-        self.index.allocated_bytes() + self.log.allocated_bytes() + self.id.allocated_bytes()
+        self.index.allocated_bytes()
+            + self.parameters.pipeline.allocated_bytes()
+            + self.parameters.routing.allocated_bytes()
+            + self.log.allocated_bytes()
+            + self.id.allocated_bytes()
     }
 }
 
@@ -51,6 +93,7 @@ pub struct ElasticsearchEncoder {
     pub transformer: Transformer,
     pub doc_type: String,
     pub suppress_type_name: bool,
+    pub update: Option<BulkUpdateConfig>,
 }
 
 impl Encoder<Vec<ProcessedEvent>> for ElasticsearchEncoder {
@@ -73,11 +116,17 @@ impl Encoder<Vec<ProcessedEvent>> for ElasticsearchEncoder {
                 &self.doc_type,
                 self.suppress_type_name,
                 &event.id,
+                &event.parameters,
             )?;
             written_bytes +=
                 as_tracked_write::<_, _, io::Error>(writer, &log, |mut writer, log| {
                     writer.write_all(&[b'\n'])?;
-                    serde_json::to_writer(&mut writer, log)?;
+                    match event.bulk_action {
+                        BulkAction::Update => self.write_update(&mut writer, log)?,
+                        BulkAction::Index | BulkAction::Create => {
+                            serde_json::to_writer(&mut writer, log)?
+                        }
+                    }
                     writer.write_all(&[b'\n'])?;
                     Ok(())
                 })?;
@@ -86,6 +135,45 @@ impl Encoder<Vec<ProcessedEvent>> for ElasticsearchEncoder {
     }
 }
 
+impl ElasticsearchEncoder {
+    /// Writes the body of an `update` action for the event.
+    fn write_update(&self, writer: &mut dyn Write, log: &LogEvent) -> serde_json::Result<()> {
+        let default = BulkUpdateConfig::default();
+        let update = self.update.as_ref().unwrap_or(&default);
+        match &update.script {
+            None => serde_json::to_writer(
+                writer,
+                &DocumentUpdate {
+                    doc: log,
+                    doc_as_upsert: update.doc_as_upsert,
+                },
+            ),
+            Some(script) => {
+                let event = log.value();
+                // With a scripted upsert, the script creates the missing documents itself.
+                let empty = Value::Object(Default::default());
+                let upsert = if script.scripted_upsert {
+                    &empty
+                } else {
+                    event
+                };
+                serde_json::to_writer(
+                    writer,
+                    &ScriptedUpdate {
+                        script: UpdateScript {
+                            source: &script.source,
+                            lang: script.lang.as_deref(),
+                            params: UpdateScriptParams { event },
+                        },
+                        upsert,
+                        scripted_upsert: script.scripted_upsert,
+                    },
+                )
+            }
+        }
+    }
+}
+
 fn write_bulk_action(
     writer: &mut dyn Write,
     bulk_action: &str,
@@ -93,35 +181,37 @@ fn write_bulk_action(
     doc_type: &str,
     suppress_type: bool,
     id: &Option<String>,
+    parameters: &BulkActionParameters,
 ) -> std::io::Result<usize> {
     as_tracked_write(
         writer,
-        (bulk_action, index, doc_type, id, suppress_type),
-        |writer, (bulk_action, index, doc_type, id, suppress_type)| match (id, suppress_type) {
-            (Some(id), true) => {
-                write!(
-                    writer,
-                    r#"{{"{}":{{"_index":"{}","_id":"{}"}}}}"#,
-                    bulk_action, index, id
-                )
+        (bulk_action, index, doc_type, id, suppress_type, parameters),
+        |writer, (bulk_action, index, doc_type, id, suppress_type, parameters)| {
+            write!(writer, r#"{{"{}":{{"_index":"{}""#, bulk_action, index)?;
+            if !suppress_type {
+                write!(writer, r#","_type":"{}""#, doc_type)?;
+            }
+            if let Some(id) = id {
+                write!(writer, r#","_id":"{}""#, id)?;
             }
-            (Some(id), false) => {
+            if let Some(pipeline) = &parameters.pipeline {
                 write!(
                     writer,
-                    r#"{{"{}":{{"_index":"{}","_type":"{}","_id":"{}"}}}}"#,
-                    bulk_action, index, doc_type, id
-                )
-            }
-            (None, true) => {
-                write!(writer, r#"{{"{}":{{"_index":"{}"}}}}"#, bulk_action, index)
+                    r#","pipeline":{}"#,
+                    JsonValue::from(pipeline.as_str())
+                )?;
             }
-            (None, false) => {
+            if let Some(routing) = &parameters.routing {
                 write!(
                     writer,
-                    r#"{{"{}":{{"_index":"{}","_type":"{}"}}}}"#,
-                    bulk_action, index, doc_type
-                )
+                    r#","routing":{}"#,
+                    JsonValue::from(routing.as_str())
+                )?;
+            }
+            if parameters.require_alias {
+                write!(writer, r#","require_alias":true"#)?;
             }
+            write!(writer, "}}}}")
         },
     )
 }
@@ -129,6 +219,7 @@ fn write_bulk_action(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sinks::elasticsearch::BulkUpdateScriptConfig;
 
     #[test]
     fn suppress_type_with_id() {
@@ -141,6 +232,7 @@ mod tests {
             "TYPE",
             true,
             &Some("ID".to_string()),
+            &BulkActionParameters::default(),
         );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
@@ -162,7 +254,15 @@ mod tests {
     fn suppress_type_without_id() {
         let mut writer = Vec::new();
 
-        let _ = write_bulk_action(&mut writer, "ACTION", "INDEX", "TYPE", true, &None);
+        let _ = write_bulk_action(
+            &mut writer,
+            "ACTION",
+            "INDEX",
+            "TYPE",
+            true,
+            &None,
+            &Default::default(),
+        );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
         let value = value.as_object().unwrap();
@@ -189,6 +289,7 @@ mod tests {
             "TYPE",
             false,
             &Some("ID".to_string()),
+            &BulkActionParameters::default(),
         );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
@@ -211,7 +312,15 @@ mod tests {
     fn type_without_id() {
         let mut writer = Vec::new();
 
-        let _ = write_bulk_action(&mut writer, "ACTION", "INDEX", "TYPE", false, &None);
+        let _ = write_bulk_action(
+            &mut writer,
+            "ACTION",
+            "INDEX",
+            "TYPE",
+            false,
+            &None,
+            &Default::default(),
+        );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
         let value = value.as_object().unwrap();
@@ -227,4 +336,90 @@ mod tests {
         assert!(nested.contains_key("_type"));
         assert_eq!(nested.get("_type").unwrap().as_str(), Some("TYPE"));
     }
+
+    #[test]
+    fn bulk_action_parameters() {
+        let mut writer = Vec::new();
+
+        let _ = write_bulk_action(
+            &mut writer,
+            "ACTION",
+            "INDEX",
+            "TYPE",
+            true,
+            &None,
+            &BulkActionParameters {
+                pipeline: Some("pipeline-\"1\"".to_string()),
+                routing: Some("tenant-1".to_string()),
+                require_alias: true,
+            },
+        );
+
+        let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
+        let nested = value.get("ACTION").unwrap().as_object().unwrap();
+
+        assert_eq!(
+            nested.get("pipeline").unwrap().as_str(),
+            Some("pipeline-\"1\"")
+        );
+        assert_eq!(nested.get("routing").unwrap().as_str(), Some("tenant-1"));
+        assert_eq!(nested.get("require_alias").unwrap().as_bool(), Some(true));
+    }
+
+    fn encode_update(update: Option<BulkUpdateConfig>) -> serde_json::Value {
+        let encoder = ElasticsearchEncoder {
+            update,
+            ..Default::default()
+        };
+        let event = ProcessedEvent {
+            index: "INDEX".to_string(),
+            bulk_action: BulkAction::Update,
+            parameters: BulkActionParameters::default(),
+            log: LogEvent::from("hello"),
+            id: Some("ID".to_string()),
+        };
+        let mut writer = Vec::new();
+
+        encoder.encode_input(vec![event], &mut writer).unwrap();
+
+        let body = writer.split(|byte| *byte == b'\n').nth(1).unwrap();
+        serde_json::from_slice(body).unwrap()
+    }
+
+    #[test]
+    fn update_document() {
+        let body = encode_update(Some(BulkUpdateConfig {
+            doc_as_upsert: true,
+            script: None,
+        }));
+
+        assert_eq!(
+            body,
+            serde_json::json!({ "doc": { "message": "hello" }, "doc_as_upsert": true })
+        );
+    }
+
+    #[test]
+    fn update_with_script() {
+        let body = encode_update(Some(BulkUpdateConfig {
+            doc_as_upsert: false,
+            script: Some(BulkUpdateScriptConfig {
+                source: "ctx._source.messages.add(params.event.message)".to_string(),
+                lang: None,
+                scripted_upsert: true,
+            }),
+        }));
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "script": {
+                    "source": "ctx._source.messages.add(params.event.message)",
+                    "params": { "event": { "message": "hello" } },
+                },
+                "upsert": {},
+                "scripted_upsert": true,
+            })
+        );
+    }
 }
@@ -115,7 +115,7 @@ async fn ensure_pipeline_in_params() {
             index,
             ..Default::default()
         }),
-        pipeline: Some(pipeline.clone()),
+        pipeline: Some(Template::try_from(pipeline.clone()).unwrap()),
         batch: batch_settings(),
         ..Default::default()
     };
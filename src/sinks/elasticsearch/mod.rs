@@ -18,7 +18,7 @@ use std::convert::TryFrom;
 
 pub use common::*;
 pub use config::*;
-pub use encoder::ElasticsearchEncoder;
+pub use encoder::{BulkActionParameters, ElasticsearchEncoder};
 use http::{uri::InvalidUri, Request};
 use snafu::Snafu;
 use vector_common::sensitive_string::SensitiveString;
@@ -85,6 +85,11 @@ pub enum BulkAction {
 
     /// The `create` action.
     Create,
+
+    /// The `update` action.
+    ///
+    /// The document with the ID of the event is updated, according to `bulk.update`.
+    Update,
 }
 
 #[allow(clippy::trivially_copy_pass_by_ref)]
@@ -93,6 +98,7 @@ impl BulkAction {
         match self {
             BulkAction::Index => "index",
             BulkAction::Create => "create",
+            BulkAction::Update => "update",
         }
     }
 
@@ -100,6 +106,7 @@ impl BulkAction {
         match self {
             BulkAction::Index => "/index",
             BulkAction::Create => "/create",
+            BulkAction::Update => "/update",
         }
     }
 }
@@ -111,6 +118,7 @@ impl TryFrom<&str> for BulkAction {
         match input {
             "index" => Ok(BulkAction::Index),
             "create" => Ok(BulkAction::Create),
+            "update" => Ok(BulkAction::Update),
             _ => Err(format!("Invalid bulk action: {}", input)),
         }
     }
@@ -176,6 +184,41 @@ impl ElasticsearchCommonMode {
     }
 }
 
+/// The templates of the parameters of the bulk API actions, rendered for each event.
+#[derive(Clone, Debug, Default)]
+pub struct BulkActionParameterTemplates {
+    pub pipeline: Option<Template>,
+    pub routing: Option<Template>,
+    pub require_alias: Option<Template>,
+}
+
+impl BulkActionParameterTemplates {
+    fn render(&self, log: &LogEvent) -> Option<BulkActionParameters> {
+        let render = |template: &Option<Template>, field: &'static str| {
+            template
+                .as_ref()
+                .map(|template| {
+                    template.render_string(log).map_err(|error| {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some(field),
+                            drop_event: true,
+                        });
+                    })
+                })
+                .transpose()
+        };
+        Some(BulkActionParameters {
+            pipeline: render(&self.pipeline, "pipeline").ok()?,
+            routing: render(&self.routing, "routing").ok()?,
+            require_alias: render(&self.require_alias, "require_alias")
+                .ok()?
+                .as_deref()
+                == Some("true"),
+        })
+    }
+}
+
 /// Configuration for Elasticsearch API version.
 #[configurable_component]
 #[derive(Clone, Debug, Eq, PartialEq)]
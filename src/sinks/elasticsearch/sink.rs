@@ -12,7 +12,8 @@ use crate::{
     sinks::{
         elasticsearch::{
             encoder::ProcessedEvent, request_builder::ElasticsearchRequestBuilder,
-            service::ElasticsearchRequest, BulkAction, ElasticsearchCommonMode,
+            service::ElasticsearchRequest, BulkAction, BulkActionParameterTemplates,
+            ElasticsearchCommonMode,
         },
         util::{SinkBuilderExt, StreamSink},
     },
@@ -34,6 +35,7 @@ pub struct ElasticsearchSink<S> {
     pub service: S,
     pub metric_to_log: MetricToLog,
     pub mode: ElasticsearchCommonMode,
+    pub action_parameters: BulkActionParameterTemplates,
     pub id_key_field: Option<String>,
 }
 
@@ -52,6 +54,7 @@ impl<S> ElasticsearchSink<S> {
             service,
             metric_to_log: common.metric_to_log.clone(),
             mode: common.mode.clone(),
+            action_parameters: common.action_parameters.clone(),
             id_key_field: config.id_key.clone(),
         })
     }
@@ -68,6 +71,7 @@ where
         let request_builder_concurrency_limit = NonZeroUsize::new(50);
 
         let mode = self.mode;
+        let action_parameters = self.action_parameters;
         let id_key_field = self.id_key_field;
         let transformer = self.transformer.clone();
 
@@ -86,7 +90,13 @@ where
             })
             .filter_map(|x| async move { x })
             .filter_map(move |log| {
-                future::ready(process_log(log, &mode, &id_key_field, &transformer))
+                future::ready(process_log(
+                    log,
+                    &mode,
+                    &action_parameters,
+                    &id_key_field,
+                    &transformer,
+                ))
             })
             .batched(self.batch_settings.into_byte_size_config())
             .request_builder(request_builder_concurrency_limit, self.request_builder)
@@ -110,11 +120,13 @@ where
 pub(super) fn process_log(
     mut log: LogEvent,
     mode: &ElasticsearchCommonMode,
+    action_parameters: &BulkActionParameterTemplates,
     id_key_field: &Option<String>,
     transformer: &Transformer,
 ) -> Option<ProcessedEvent> {
     let index = mode.index(&log)?;
     let bulk_action = mode.bulk_action(&log)?;
+    let parameters = action_parameters.render(&log)?;
 
     if let Some(cfg) = mode.as_data_stream_config() {
         cfg.sync_fields(&mut log);
@@ -136,6 +148,7 @@ pub(super) fn process_log(
     Some(ProcessedEvent {
         index,
         bulk_action,
+        parameters,
         log,
         id,
     })
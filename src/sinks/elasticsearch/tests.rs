@@ -29,6 +29,7 @@ async fn sets_create_action_when_configured() {
         bulk: Some(BulkConfig {
             action: parse_template("{{ action }}te"),
             index: parse_template("vector"),
+            update: None,
         }),
         endpoints: vec![String::from("https://example.com")],
         api_version: ElasticsearchApiVersion::V6,
@@ -50,7 +51,14 @@ async fn sets_create_action_when_configured() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(
+                log,
+                &es.mode,
+                &es.action_parameters,
+                &None,
+                &config.encoding,
+            )
+            .unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -101,7 +109,14 @@ async fn encode_datastream_mode() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(
+                log,
+                &es.mode,
+                &es.action_parameters,
+                &None,
+                &config.encoding,
+            )
+            .unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -149,7 +164,14 @@ async fn encode_datastream_mode_no_routing() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(
+                log,
+                &es.mode,
+                &es.action_parameters,
+                &None,
+                &config.encoding,
+            )
+            .unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -167,6 +189,7 @@ async fn handle_metrics() {
         bulk: Some(BulkConfig {
             action: parse_template("create"),
             index: parse_template("vector"),
+            update: None,
         }),
         endpoints: vec![String::from("https://example.com")],
         api_version: ElasticsearchApiVersion::V6,
@@ -185,7 +208,14 @@ async fn handle_metrics() {
     es.request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(
+                log,
+                &es.mode,
+                &es.action_parameters,
+                &None,
+                &config.encoding,
+            )
+            .unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -209,6 +239,7 @@ async fn decode_bulk_action_error() {
         bulk: Some(BulkConfig {
             action: parse_template("{{ action }}"),
             index: parse_template("vector"),
+            update: None,
         }),
         endpoints: vec![String::from("https://example.com")],
         api_version: ElasticsearchApiVersion::V7,
@@ -229,6 +260,7 @@ async fn decode_bulk_action() {
         bulk: Some(BulkConfig {
             action: parse_template("create"),
             index: parse_template("vector"),
+            update: None,
         }),
         endpoints: vec![String::from("https://example.com")],
         api_version: ElasticsearchApiVersion::V7,
@@ -279,7 +311,14 @@ async fn encode_datastream_mode_no_sync() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(
+                log,
+                &es.mode,
+                &es.action_parameters,
+                &None,
+                &config.encoding,
+            )
+            .unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -319,7 +358,14 @@ async fn allows_using_except_fields() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(
+                log,
+                &es.mode,
+                &es.action_parameters,
+                &None,
+                &config.encoding,
+            )
+            .unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -354,7 +400,14 @@ async fn allows_using_only_fields() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(
+                log,
+                &es.mode,
+                &es.action_parameters,
+                &None,
+                &config.encoding,
+            )
+            .unwrap()],
             &mut encoded,
         )
         .unwrap();
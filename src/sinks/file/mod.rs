@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::time::{Duration, Instant};
 
@@ -28,7 +29,7 @@ use vector_core::{
 use crate::{
     codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
-    event::{Event, EventStatus, Finalizable},
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
     expiring_hash_map::ExpiringHashMap,
     internal_events::{FileBytesSent, FileIoError, FileOpen, TemplateRenderingError},
     sinks::util::StreamSink,
@@ -201,6 +202,10 @@ pub struct FileSink {
     encoder: Encoder<Framer>,
     idle_timeout: Duration,
     files: ExpiringHashMap<Bytes, OutFile>,
+    /// The events of the open files, when the serializer encodes them as a whole, such as Parquet
+    /// files, written when the files are closed. With compressed framing, the events written at
+    /// once, compressed into a single frame.
+    batches: HashMap<Bytes, Vec<Event>>,
     compression: Compression,
     events_sent: Registered<EventsSent>,
 }
@@ -217,6 +222,7 @@ impl FileSink {
             encoder,
             idle_timeout: config.idle_timeout,
             files: ExpiringHashMap::default(),
+            batches: HashMap::default(),
             compression: config.compression,
             events_sent: register!(EventsSent::from(Output(None))),
         })
@@ -246,12 +252,25 @@ impl FileSink {
             .expect("unable to compute next deadline")
     }
 
-    async fn run(&mut self, mut input: BoxStream<'_, Event>) -> crate::Result<()> {
+    /// Whether the events are added to the batches of the files rather than written one by one.
+    fn batches_events(&self) -> bool {
+        self.encoder.serializes_batches() || self.encoder.compresses_batches()
+    }
+
+    async fn run(&mut self, input: BoxStream<'_, Event>) -> crate::Result<()> {
+        let mut input = input.ready_chunks(1024);
         loop {
             tokio::select! {
-                event = input.next() => {
-                    match event {
-                        Some(event) => self.process_event(event).await,
+                events = input.next() => {
+                    match events {
+                        Some(events) => {
+                            for event in events {
+                                self.process_event(event).await;
+                            }
+                            if !self.encoder.serializes_batches() {
+                                self.write_batches().await;
+                            }
+                        }
                         None => {
                             // If we got `None` - terminate the processing.
                             debug!(message = "Receiver exhausted, terminating the processing loop.");
@@ -259,6 +278,16 @@ impl FileSink {
                             // Close all the open files.
                             debug!(message = "Closing all the open files.");
                             for (path, file) in self.files.iter_mut() {
+                                if let Some(events) = self.batches.remove(path) {
+                                    write_batch_to_file(
+                                        file,
+                                        path,
+                                        events,
+                                        &mut self.encoder,
+                                        &self.events_sent,
+                                    )
+                                    .await;
+                                }
                                 if let Err(error) = file.close().await {
                                     emit!(FileIoError {
                                         error,
@@ -288,6 +317,16 @@ impl FileSink {
                         Some((mut expired_file, path)) => {
                             // We got an expired file. All we really want is to
                             // flush and close it.
+                            if let Some(events) = self.batches.remove(&path) {
+                                write_batch_to_file(
+                                    &mut expired_file,
+                                    &path,
+                                    events,
+                                    &mut self.encoder,
+                                    &self.events_sent,
+                                )
+                                .await;
+                            }
                             if let Err(error) = expired_file.close().await {
                                 emit!(FileIoError {
                                     error,
@@ -310,6 +349,16 @@ impl FileSink {
         Ok(())
     }
 
+    /// Writes the pending batches of the open files.
+    async fn write_batches(&mut self) {
+        for (path, events) in self.batches.drain() {
+            if let Some(file) = self.files.get_mut(&path) {
+                write_batch_to_file(file, &path, events, &mut self.encoder, &self.events_sent)
+                    .await;
+            }
+        }
+    }
+
     async fn process_event(&mut self, mut event: Event) {
         let path = match self.partition_event(&event) {
             Some(path) => path,
@@ -349,7 +398,46 @@ impl FileSink {
                 }
             };
 
-            let outfile = OutFile::new(file, self.compression);
+            // Files being appended to already start with the header.
+            let is_empty = file
+                .metadata()
+                .await
+                .map_or(false, |metadata| metadata.len() == 0);
+            // Files encoded as a whole can't be appended to.
+            if !is_empty && self.encoder.serializes_batches() {
+                emit!(FileIoError {
+                    code: "failed_opening_file",
+                    message: "Unable to append to a file encoded as a whole.",
+                    error: std::io::Error::new(
+                        std::io::ErrorKind::AlreadyExists,
+                        "the file already exists",
+                    ),
+                    path: &path,
+                    dropped_events: 1,
+                });
+                event.metadata().update_status(EventStatus::Errored);
+                return;
+            }
+            let mut outfile = OutFile::new(file, self.compression);
+            let mut header = BytesMut::from(self.encoder.header());
+            if is_empty && !header.is_empty() {
+                // The header is a frame of its own with compressed framing.
+                let result = match self.encoder.compress_batch(&mut header) {
+                    Ok(()) => outfile.write_all(&header).await,
+                    Err(error) => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, error)),
+                };
+                if let Err(error) = result {
+                    emit!(FileIoError {
+                        code: "failed_writing_file",
+                        message: "Failed to write the file.",
+                        error,
+                        path: &path,
+                        dropped_events: 1,
+                    });
+                    event.metadata().update_status(EventStatus::Errored);
+                    return;
+                }
+            }
 
             self.files.insert_at(path.clone(), outfile, next_deadline);
             emit!(FileOpen {
@@ -358,6 +446,13 @@ impl FileSink {
             self.files.get_mut(&path).unwrap()
         };
 
+        if self.batches_events() {
+            trace!(message = "Adding an event to the batch of the file.", path = ?path);
+            self.transformer.transform(&mut event);
+            self.batches.entry(path).or_default().push(event);
+            return;
+        }
+
         trace!(message = "Writing an event to file.", path = ?path);
         let event_size = event.estimated_json_encoded_size_of();
         let finalizers = event.take_finalizers();
@@ -414,6 +509,49 @@ async fn write_event_to_file(
     file.write_all(&buffer).await.map(|()| buffer.len())
 }
 
+/// Writes a batch of events to file, encoded as a whole or compressed into a single frame.
+async fn write_batch_to_file(
+    file: &mut OutFile,
+    path: &Bytes,
+    mut events: Vec<Event>,
+    encoder: &mut Encoder<Framer>,
+    events_sent: &Registered<EventsSent>,
+) {
+    let count = events.len();
+    let events_size = events.estimated_json_encoded_size_of();
+    let finalizers = events
+        .iter_mut()
+        .map(Finalizable::take_finalizers)
+        .collect::<EventFinalizers>();
+
+    trace!(message = "Writing the batch of events to file.", path = ?path, count);
+    let mut buffer = BytesMut::new();
+    let result = match encoder.encode_batch(events, &mut buffer) {
+        Ok(()) => file.write_all(&buffer).await,
+        Err(error) => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, error)),
+    };
+    match result {
+        Ok(()) => {
+            finalizers.update_status(EventStatus::Delivered);
+            events_sent.emit(CountByteSize(count, events_size));
+            emit!(FileBytesSent {
+                byte_size: buffer.len(),
+                file: String::from_utf8_lossy(path),
+            });
+        }
+        Err(error) => {
+            finalizers.update_status(EventStatus::Errored);
+            emit!(FileIoError {
+                code: "failed_writing_file",
+                message: "Failed to write the file.",
+                error,
+                path,
+                dropped_events: count,
+            });
+        }
+    }
+}
+
 #[async_trait]
 impl StreamSink<Event> for FileSink {
     async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
@@ -428,6 +566,7 @@ impl StreamSink<Event> for FileSink {
 mod tests {
     use std::convert::TryInto;
 
+    use codecs::{encoding::CsvSerializerOptions, CsvSerializerConfig};
     use futures::{stream, SinkExt};
     use similar_asserts::assert_eq;
     use vector_core::{event::LogEvent, sink::VectorSink};
@@ -513,6 +652,126 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn single_partition_compressed_framing() {
+        use std::io::Read;
+
+        use codecs::encoding::{
+            CompressedEncoderConfig, CompressedEncoderOptions, CompressedFramingMethod,
+            CompressionAlgorithm,
+        };
+        use flate2::bufread::GzDecoder;
+
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (
+                Some(CompressedEncoderConfig::new(CompressedEncoderOptions {
+                    algorithm: CompressionAlgorithm::Gzip,
+                    level: None,
+                    framing: CompressedFramingMethod::NewlineDelimited,
+                })),
+                TextSerializerConfig::default(),
+            )
+                .into(),
+            compression: Compression::None,
+            acknowledgements: Default::default(),
+        };
+
+        let (input, _) = random_lines_with_stream(100, 64, None);
+
+        run_assert_log_sink(config, input.clone()).await;
+
+        // The events written at once are compressed into a single gzip member.
+        let compressed = std::fs::read(template).unwrap();
+        let mut decoder = GzDecoder::new(&compressed[..]);
+        let mut output = String::new();
+        decoder.read_to_string(&mut output).unwrap();
+        assert!(decoder.into_inner().is_empty());
+        assert_eq!(input, output.lines().collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn single_partition_csv_header() {
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (
+                None::<FramingConfig>,
+                CsvSerializerConfig::new(CsvSerializerOptions {
+                    fields: vec![log_schema().message_key().to_owned()],
+                    headers: true,
+                    terminator: Default::default(),
+                    delimiter: b',',
+                    quote: b'"',
+                    nested_value_handling: Default::default(),
+                    null_value: Default::default(),
+                    missing_value: Default::default(),
+                }),
+            )
+                .into(),
+            compression: Compression::None,
+            acknowledgements: Default::default(),
+        };
+
+        let (input, _) = random_lines_with_stream(100, 64, None);
+
+        run_assert_log_sink(config, input.clone()).await;
+
+        let output = lines_from_file(template);
+        assert_eq!(output[0], log_schema().message_key());
+        for (input, output) in input.into_iter().zip(output.into_iter().skip(1)) {
+            assert_eq!(input, output);
+        }
+    }
+
+    #[cfg(feature = "codecs-parquet")]
+    #[tokio::test]
+    async fn single_partition_parquet() {
+        use std::num::NonZeroUsize;
+
+        use codecs::{
+            encoding::{
+                ParquetCompression, ParquetField, ParquetFieldType, ParquetSerializerOptions,
+            },
+            ParquetSerializerConfig,
+        };
+
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (
+                None::<FramingConfig>,
+                ParquetSerializerConfig::new(ParquetSerializerOptions {
+                    fields: vec![ParquetField {
+                        name: log_schema().message_key().to_owned(),
+                        r#type: ParquetFieldType::String,
+                    }],
+                    row_group_size: NonZeroUsize::new(10).unwrap(),
+                    compression: ParquetCompression::Snappy,
+                }),
+            )
+                .into(),
+            compression: Compression::None,
+            acknowledgements: Default::default(),
+        };
+
+        let (input, _) = random_lines_with_stream(100, 64, None);
+
+        run_assert_log_sink(config, input).await;
+
+        // The events are written as a single file when the sink shuts down.
+        let output = std::fs::read(template).unwrap();
+        assert!(output.starts_with(b"PAR1"));
+        assert!(output.ends_with(b"PAR1"));
+    }
+
     #[tokio::test]
     async fn many_partitions() {
         let directory = temp_dir();
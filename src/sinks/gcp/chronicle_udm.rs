@@ -0,0 +1,296 @@
+//! This sink sends data to Google Chronicles UDM events endpoint.
+//! See <https://cloud.google.com/chronicle/docs/reference/ingestion-api#udmevents>
+//! for more information.
+use std::io;
+
+use goauth::scopes::Scope;
+use indoc::indoc;
+use serde_json::json;
+use tower::ServiceBuilder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{AcknowledgementsConfig, Input},
+    event::Event,
+    sink::VectorSink,
+};
+
+use crate::{
+    codecs::Transformer,
+    config::{GenerateConfig, SinkConfig, SinkContext},
+    gcp::{GcpAuthConfig, GcpAuthenticator},
+    http::HttpClient,
+    sinks::{
+        gcp::chronicle_unstructured::{
+            build_healthcheck, create_endpoint, ChronicleService, Region, RequestSettings,
+        },
+        gcs_common::{config::GcsRetryLogic, sink::GcsSink},
+        util::{
+            encoding::{as_tracked_write, Encoder},
+            partitioner::KeyPartitioner,
+            BatchConfig, SinkBatchSettings, TowerRequestConfig,
+        },
+        Healthcheck,
+    },
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ChronicleUdmDefaultBatchSettings;
+
+// Chronicle Ingestion API has a 1MB limit[1] for UDM events too.
+//
+// [1]: https://cloud.google.com/chronicle/docs/reference/ingestion-api#udmevents
+impl SinkBatchSettings for ChronicleUdmDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(1_000_000);
+    const TIMEOUT_SECS: f64 = 15.0;
+}
+
+/// Configuration for the `gcp_chronicle_udm` sink.
+///
+/// Each log event is sent as a [Unified Data Model][udm] (UDM) event, so events are expected to be
+/// shaped as UDM events already, such as by a `remap` transform.
+///
+/// [udm]: https://cloud.google.com/chronicle/docs/reference/udm-field-list
+#[configurable_component(sink("gcp_chronicle_udm"))]
+#[derive(Clone, Debug)]
+pub struct ChronicleUdmConfig {
+    /// The endpoint to send data to.
+    #[configurable(metadata(
+        docs::examples = "127.0.0.1:8080",
+        docs::examples = "example.com:12345"
+    ))]
+    pub endpoint: Option<String>,
+
+    /// The GCP region to use.
+    #[configurable(derived)]
+    pub region: Option<Region>,
+
+    /// The Unique identifier (UUID) corresponding to the Chronicle instance.
+    #[configurable(validation(format = "uuid"))]
+    #[configurable(metadata(docs::examples = "c8c65bfa-5f2c-42d4-9189-64bb7b939f2c"))]
+    pub customer_id: String,
+
+    #[serde(flatten)]
+    pub auth: GcpAuthConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<ChronicleUdmDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for ChronicleUdmConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            credentials_path = "/path/to/credentials.json"
+            customer_id = "customer_id"
+            region = "us"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for ChronicleUdmConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let creds = self.auth.build(Scope::MalachiteIngestion).await?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let endpoint = create_endpoint(
+            self.endpoint.as_deref(),
+            self.region,
+            "v2/udmevents:batchCreate",
+        )?;
+
+        // For the healthcheck we see if we can fetch the list of available log types.
+        let healthcheck_endpoint =
+            create_endpoint(self.endpoint.as_deref(), self.region, "v2/logtypes")?;
+
+        let healthcheck = build_healthcheck(client.clone(), &healthcheck_endpoint, creds.clone())?;
+        creds.spawn_regenerate_token();
+        let sink = self.build_sink(client, endpoint, creds, &cx)?;
+
+        Ok((sink, healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl ChronicleUdmConfig {
+    fn build_sink(
+        &self,
+        client: HttpClient,
+        base_url: String,
+        creds: GcpAuthenticator,
+        cx: &SinkContext,
+    ) -> crate::Result<VectorSink> {
+        use crate::sinks::util::service::ServiceBuilderExt;
+
+        let request = self
+            .request
+            .unwrap_with(&TowerRequestConfig {
+                rate_limit_num: Some(1000),
+                ..Default::default()
+            })
+            .with_context(cx);
+
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        // UDM events aren't partitioned by log type, so all of them share a single partition.
+        let partitioner = KeyPartitioner::new(Template::try_from("udm")?);
+
+        let svc = ServiceBuilder::new()
+            .settings(request, GcsRetryLogic)
+            .service(ChronicleService::new(client, base_url, creds));
+
+        let request_settings = RequestSettings {
+            encoder: ChronicleUdmEncoder {
+                customer_id: self.customer_id.clone(),
+                transformer: self.encoding.clone(),
+            },
+        };
+
+        let sink = GcsSink::new(svc, request_settings, partitioner, batch_settings, "http");
+
+        Ok(VectorSink::from_event_streamsink(sink))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ChronicleUdmEncoder {
+    customer_id: String,
+    transformer: Transformer,
+}
+
+impl Encoder<(String, Vec<Event>)> for ChronicleUdmEncoder {
+    fn encode_input(
+        &self,
+        input: (String, Vec<Event>),
+        writer: &mut dyn io::Write,
+    ) -> io::Result<usize> {
+        let (_, events) = input;
+        let events = events
+            .into_iter()
+            .map(|event| self.udm_event(event))
+            .collect::<Vec<_>>();
+
+        let json = json!({
+            "customer_id": self.customer_id,
+            "events": events,
+        });
+
+        let size = as_tracked_write::<_, _, io::Error>(writer, &json, |writer, json| {
+            serde_json::to_writer(writer, json)?;
+            Ok(())
+        })?;
+
+        Ok(size)
+    }
+}
+
+impl ChronicleUdmEncoder {
+    /// Converts the event into a UDM event.
+    ///
+    /// The timestamp of the event is used as the `metadata.event_timestamp` field, which is
+    /// required by UDM, unless the event already has one.
+    fn udm_event(&self, mut event: Event) -> serde_json::Value {
+        let timestamp = event
+            .as_log()
+            .get_timestamp()
+            .and_then(|ts| ts.as_timestamp())
+            .cloned();
+        self.transformer.transform(&mut event);
+
+        let mut log = event.into_log();
+        if let Some(ts) = timestamp {
+            if !log.contains("metadata.event_timestamp") {
+                log.insert(
+                    "metadata.event_timestamp",
+                    ts.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+                );
+            }
+        }
+        json!(log)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ChronicleUdmConfig>();
+    }
+
+    #[test]
+    fn encodes_udm_events() {
+        let encoder = ChronicleUdmEncoder {
+            customer_id: "customer".to_owned(),
+            transformer: Transformer::default(),
+        };
+        let timestamp = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let mut with_timestamp = LogEvent::default();
+        with_timestamp.insert("metadata.event_type", "USER_LOGIN");
+        with_timestamp.insert("timestamp", timestamp);
+        let mut with_event_timestamp = with_timestamp.clone();
+        with_event_timestamp.insert("metadata.event_timestamp", "2023-01-01T00:00:00Z");
+
+        let mut body = Vec::new();
+        encoder
+            .encode_input(
+                (
+                    "udm".to_owned(),
+                    vec![with_timestamp.into(), with_event_timestamp.into()],
+                ),
+                &mut body,
+            )
+            .unwrap();
+
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["customer_id"], "customer");
+        assert_eq!(
+            body["events"][0]["metadata"]["event_timestamp"],
+            "2023-11-14T22:13:20Z"
+        );
+        assert_eq!(
+            body["events"][1]["metadata"]["event_timestamp"],
+            "2023-01-01T00:00:00Z"
+        );
+        assert_eq!(body["events"][1]["metadata"]["event_type"], "USER_LOGIN");
+    }
+}
@@ -69,6 +69,45 @@ pub enum Region {
 
     /// APAC region.
     Asia,
+
+    /// Canada (Toronto) region.
+    Canada,
+
+    /// Dammam region.
+    Dammam,
+
+    /// Doha region.
+    Doha,
+
+    /// Frankfurt region.
+    Frankfurt,
+
+    /// London region.
+    London,
+
+    /// Mumbai region.
+    Mumbai,
+
+    /// Paris region.
+    Paris,
+
+    /// São Paulo region.
+    SaoPaulo,
+
+    /// Sydney region.
+    Sydney,
+
+    /// Tel Aviv region.
+    TelAviv,
+
+    /// Tokyo region.
+    Tokyo,
+
+    /// Turin region.
+    Turin,
+
+    /// Zurich region.
+    Zurich,
 }
 
 impl Region {
@@ -78,6 +117,21 @@ impl Region {
             Region::Eu => "https://europe-malachiteingestion-pa.googleapis.com",
             Region::Us => "https://malachiteingestion-pa.googleapis.com",
             Region::Asia => "https://asia-southeast1-malachiteingestion-pa.googleapis.com",
+            Region::Canada => {
+                "https://northamerica-northeast2-malachiteingestion-pa.googleapis.com"
+            }
+            Region::Dammam => "https://me-central2-malachiteingestion-pa.googleapis.com",
+            Region::Doha => "https://me-central1-malachiteingestion-pa.googleapis.com",
+            Region::Frankfurt => "https://europe-west3-malachiteingestion-pa.googleapis.com",
+            Region::London => "https://europe-west2-malachiteingestion-pa.googleapis.com",
+            Region::Mumbai => "https://asia-south1-malachiteingestion-pa.googleapis.com",
+            Region::Paris => "https://europe-west9-malachiteingestion-pa.googleapis.com",
+            Region::SaoPaulo => "https://southamerica-east1-malachiteingestion-pa.googleapis.com",
+            Region::Sydney => "https://australia-southeast1-malachiteingestion-pa.googleapis.com",
+            Region::TelAviv => "https://me-west1-malachiteingestion-pa.googleapis.com",
+            Region::Tokyo => "https://asia-northeast1-malachiteingestion-pa.googleapis.com",
+            Region::Turin => "https://europe-west12-malachiteingestion-pa.googleapis.com",
+            Region::Zurich => "https://europe-west6-malachiteingestion-pa.googleapis.com",
         }
     }
 }
@@ -99,6 +153,7 @@ impl SinkBatchSettings for ChronicleUnstructuredDefaultBatchSettings {
 /// Configuration for the `gcp_chronicle_unstructured` sink.
 #[configurable_component(sink("gcp_chronicle_unstructured"))]
 #[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
 pub struct ChronicleUnstructuredConfig {
     /// The endpoint to send data to.
     #[configurable(metadata(
@@ -204,7 +259,7 @@ impl SinkConfig for ChronicleUnstructuredConfig {
 
         let healthcheck = build_healthcheck(client.clone(), &healthcheck_endpoint, creds.clone())?;
         creds.spawn_regenerate_token();
-        let sink = self.build_sink(client, endpoint, creds)?;
+        let sink = self.build_sink(client, endpoint, creds, &cx)?;
 
         Ok((sink, healthcheck))
     }
@@ -227,13 +282,17 @@ impl ChronicleUnstructuredConfig {
         client: HttpClient,
         base_url: String,
         creds: GcpAuthenticator,
+        cx: &SinkContext,
     ) -> crate::Result<VectorSink> {
         use crate::sinks::util::service::ServiceBuilderExt;
 
-        let request = self.request.unwrap_with(&TowerRequestConfig {
-            rate_limit_num: Some(1000),
-            ..Default::default()
-        });
+        let request = self
+            .request
+            .unwrap_with(&TowerRequestConfig {
+                rate_limit_num: Some(1000),
+                ..Default::default()
+            })
+            .with_context(cx);
 
         let batch_settings = self.batch.into_batcher_settings()?;
 
@@ -255,19 +314,29 @@ impl ChronicleUnstructuredConfig {
     }
 
     fn create_endpoint(&self, path: &str) -> Result<String, ChronicleError> {
-        Ok(format!(
-            "{}/{}",
-            match (&self.endpoint, self.region) {
-                (Some(endpoint), None) => endpoint.trim_end_matches('/'),
-                (None, Some(region)) => region.endpoint(),
-                (Some(_), Some(_)) => return Err(ChronicleError::BothRegionAndEndpoint),
-                (None, None) => return Err(ChronicleError::RegionOrEndpoint),
-            },
-            path
-        ))
+        create_endpoint(self.endpoint.as_deref(), self.region, path)
     }
 }
 
+/// Builds the URL of an endpoint of the Chronicle API, either at the configured endpoint or at the
+/// endpoint of the configured region.
+pub(super) fn create_endpoint(
+    endpoint: Option<&str>,
+    region: Option<Region>,
+    path: &str,
+) -> Result<String, ChronicleError> {
+    Ok(format!(
+        "{}/{}",
+        match (endpoint, region) {
+            (Some(endpoint), None) => endpoint.trim_end_matches('/'),
+            (None, Some(region)) => region.endpoint(),
+            (Some(_), Some(_)) => return Err(ChronicleError::BothRegionAndEndpoint),
+            (None, None) => return Err(ChronicleError::RegionOrEndpoint),
+        },
+        path
+    ))
+}
+
 #[derive(Clone, Debug)]
 pub struct ChronicleRequest {
     pub body: Bytes,
@@ -349,11 +418,11 @@ impl Encoder<(String, Vec<Event>)> for ChronicleEncoder {
 // request. All possible values are pre-computed for direct use in
 // producing a request.
 #[derive(Clone, Debug)]
-struct RequestSettings {
-    encoder: ChronicleEncoder,
+pub(super) struct RequestSettings<E> {
+    pub(super) encoder: E,
 }
 
-struct ChronicleRequestPayload {
+pub(super) struct ChronicleRequestPayload {
     bytes: Bytes,
 }
 
@@ -369,10 +438,13 @@ impl AsRef<[u8]> for ChronicleRequestPayload {
     }
 }
 
-impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
+impl<E> RequestBuilder<(String, Vec<Event>)> for RequestSettings<E>
+where
+    E: Encoder<(String, Vec<Event>)>,
+{
     type Metadata = EventFinalizers;
     type Events = (String, Vec<Event>);
-    type Encoder = ChronicleEncoder;
+    type Encoder = E;
     type Payload = ChronicleRequestPayload;
     type Request = ChronicleRequest;
     type Error = io::Error;
@@ -410,7 +482,7 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
     }
 }
 
-impl RequestSettings {
+impl RequestSettings<ChronicleEncoder> {
     fn new(config: &ChronicleUnstructuredConfig) -> crate::Result<Self> {
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.config().build()?;
@@ -214,7 +214,7 @@ impl SinkConfig for GcsSinkConfig {
             auth.clone(),
         )?;
         auth.spawn_regenerate_token();
-        let sink = self.build_sink(client, base_url, auth)?;
+        let sink = self.build_sink(client, base_url, auth, &cx)?;
 
         Ok((sink, healthcheck))
     }
@@ -234,11 +234,15 @@ impl GcsSinkConfig {
         client: HttpClient,
         base_url: String,
         auth: GcpAuthenticator,
+        cx: &SinkContext,
     ) -> crate::Result<VectorSink> {
-        let request = self.request.unwrap_with(&TowerRequestConfig {
-            rate_limit_num: Some(1000),
-            ..Default::default()
-        });
+        let request = self
+            .request
+            .unwrap_with(&TowerRequestConfig {
+                rate_limit_num: Some(1000),
+                ..Default::default()
+            })
+            .with_context(cx);
 
         let batch_settings = self.batch.into_batcher_settings()?;
 
@@ -434,7 +438,12 @@ mod tests {
         let config =
             default_config((None::<FramingConfig>, JsonSerializerConfig::default()).into());
         let sink = config
-            .build_sink(client, mock_endpoint.to_string(), GcpAuthenticator::None)
+            .build_sink(
+                client,
+                mock_endpoint.to_string(),
+                GcpAuthenticator::None,
+                &context,
+            )
             .expect("failed to build sink");
 
         let event = Event::Log(LogEvent::from("simple message"));
@@ -1,6 +1,6 @@
 use base64::prelude::{Engine as _, BASE64_STANDARD};
 use bytes::{Bytes, BytesMut};
-use futures::{FutureExt, SinkExt};
+use futures::{stream, FutureExt, SinkExt};
 use http::{Request, Uri};
 use hyper::Body;
 use indoc::indoc;
@@ -19,6 +19,7 @@ use crate::{
         gcs_common::config::healthcheck_response,
         util::{
             http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
+            size_budget::{SizeBudget, SizeBudgetConfig},
             BatchConfig, BoxedRawValue, JsonArrayBuffer, SinkBatchSettings, TowerRequestConfig,
         },
         Healthcheck, UriParseSnafu, VectorSink,
@@ -47,6 +48,7 @@ impl SinkBatchSettings for PubsubDefaultBatchSettings {
 /// Configuration for the `gcp_pubsub` sink.
 #[configurable_component(sink("gcp_pubsub"))]
 #[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
 pub struct PubsubConfig {
     /// The project name to which to publish events.
     #[configurable(metadata(docs::examples = "vector-123456"))]
@@ -79,6 +81,10 @@ pub struct PubsubConfig {
     #[serde(default)]
     pub request: TowerRequestConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub size_budget: Option<SizeBudgetConfig>,
+
     #[configurable(derived)]
     encoding: EncodingConfig,
 
@@ -114,12 +120,25 @@ impl GenerateConfig for PubsubConfig {
 impl SinkConfig for PubsubConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let sink = PubsubSink::from_config(self).await?;
+        let size_budget = self
+            .size_budget
+            .as_ref()
+            .map(|size_budget| {
+                SizeBudget::new(
+                    size_budget,
+                    (sink.transformer.clone(), sink.encoder.clone()),
+                )
+            })
+            .transpose()?;
         let batch_settings = self
             .batch
             .validate()?
             .limit_max_bytes(MAX_BATCH_PAYLOAD_SIZE)?
             .into_batch_settings()?;
-        let request_settings = self.request.unwrap_with(&Default::default());
+        let request_settings = self
+            .request
+            .unwrap_with(&Default::default())
+            .with_context(&cx);
         let tls_settings = TlsSettings::from_options(&self.tls)?;
         let client = HttpClient::new(tls_settings, cx.proxy())?;
 
@@ -135,7 +154,14 @@ impl SinkConfig for PubsubConfig {
         )
         .sink_map_err(|error| error!(message = "Fatal gcp_pubsub sink error.", %error));
 
-        Ok((VectorSink::from_event_sink(sink), healthcheck))
+        let sink = match size_budget {
+            Some(size_budget) => VectorSink::from_event_sink(sink.with_flat_map(move |event| {
+                stream::iter(size_budget.apply(event).into_iter().map(Ok))
+            })),
+            None => VectorSink::from_event_sink(sink),
+        };
+
+        Ok((sink, healthcheck))
     }
 
     fn input(&self) -> Input {
@@ -290,6 +316,7 @@ mod integration_tests {
             },
             batch: Default::default(),
             request: Default::default(),
+            size_budget: None,
             encoding: JsonSerializerConfig::default().into(),
             tls: Default::default(),
             acknowledgements: Default::default(),
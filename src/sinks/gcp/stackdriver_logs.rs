@@ -211,11 +211,14 @@ impl SinkConfig for StackdriverConfig {
             .validate()?
             .limit_max_bytes(MAX_BATCH_PAYLOAD_SIZE)?
             .into_batch_settings()?;
-        let request = self.request.unwrap_with(
-            &TowerRequestConfig::default()
-                .rate_limit_duration_secs(1)
-                .rate_limit_num(1000),
-        );
+        let request = self
+            .request
+            .unwrap_with(
+                &TowerRequestConfig::default()
+                    .rate_limit_duration_secs(1)
+                    .rate_limit_num(1000),
+            )
+            .with_context(&cx);
         let tls_settings = TlsSettings::from_options(&self.tls)?;
         let client = HttpClient::new(tls_settings, cx.proxy())?;
 
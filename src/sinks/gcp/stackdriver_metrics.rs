@@ -99,11 +99,14 @@ impl SinkConfig for StackdriverConfig {
 
         let healthcheck = healthcheck().boxed();
         let started = chrono::Utc::now();
-        let request = self.request.unwrap_with(
-            &TowerRequestConfig::default()
-                .rate_limit_duration_secs(1)
-                .rate_limit_num(1000),
-        );
+        let request = self
+            .request
+            .unwrap_with(
+                &TowerRequestConfig::default()
+                    .rate_limit_duration_secs(1)
+                    .rate_limit_num(1000),
+            )
+            .with_context(&cx);
         let tls_settings = TlsSettings::from_options(&self.tls)?;
         let client = HttpClient::new(tls_settings, cx.proxy())?;
         let batch_settings = self.batch.into_batch_settings()?;
@@ -1,6 +1,9 @@
+use std::collections::HashMap;
+
 use bytes::Bytes;
 use futures::{FutureExt, SinkExt};
 use http::{Request, StatusCode, Uri};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use serde_json::json;
 use value::Kind;
 use vector_common::sensitive_string::SensitiveString;
@@ -11,11 +14,14 @@ use crate::{
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     event::{Event, Value},
     http::HttpClient,
+    internal_events::TemplateRenderingError,
     schema,
     sinks::util::{
-        http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
-        BatchConfig, BoxedRawValue, JsonArrayBuffer, SinkBatchSettings, TowerRequestConfig,
+        http::{HttpEventEncoder, HttpSink, PartitionHttpSink},
+        BatchConfig, BoxedRawValue, JsonArrayBuffer, PartitionBuffer, PartitionInnerBuffer,
+        SinkBatchSettings, TowerRequestConfig,
     },
+    template::Template,
 };
 
 /// Configuration for the `honeycomb` sink.
@@ -27,15 +33,38 @@ pub struct HoneycombConfig {
     endpoint: String,
 
     /// The team key that will be used to authenticate against Honeycomb.
+    ///
+    /// Events sent to datasets that have a key in `api_keys` use that key instead.
     #[configurable(metadata(docs::examples = "${HONEYCOMB_API_KEY}"))]
     #[configurable(metadata(docs::examples = "some-api-key"))]
     api_key: SensitiveString,
 
+    /// The team keys that will be used to authenticate against Honeycomb, by dataset.
+    ///
+    /// This allows a single sink to send events to the datasets of several teams, each with their
+    /// own key. Events sent to other datasets use `api_key`.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "The team key of a dataset."))]
+    #[configurable(metadata(docs::examples = "example_api_keys()"))]
+    api_keys: HashMap<String, SensitiveString>,
+
     /// The dataset to which logs are sent.
+    ///
+    /// Events for which the template fails to render are dropped.
     #[configurable(metadata(docs::examples = "my-honeycomb-dataset"))]
-    // TODO: we probably want to make this a template
-    // but this limits us in how we can do our healthcheck.
-    dataset: String,
+    #[configurable(metadata(docs::examples = "{{ team }}-logs"))]
+    #[configurable(metadata(docs::templateable))]
+    dataset: Template,
+
+    /// Send events as [markers][markers] rather than as events.
+    ///
+    /// Markers annotate the graphs of a dataset with occurrences such as deploys. Each event is
+    /// sent as a marker, using the `message`, `type`, and `url` fields of the event as the
+    /// message, type, and URL of the marker, and the timestamp of the event as its start time.
+    ///
+    /// [markers]: https://docs.honeycomb.io/api/markers/
+    #[serde(default)]
+    markers: bool,
 
     #[configurable(derived)]
     #[serde(default)]
@@ -62,7 +91,20 @@ pub struct HoneycombConfig {
 }
 
 fn default_endpoint() -> String {
-    "https://api.honeycomb.io/1/batch".to_string()
+    "https://api.honeycomb.io".to_string()
+}
+
+fn example_api_keys() -> HashMap<String, String> {
+    HashMap::<_, _>::from_iter([
+        (
+            "payments-logs".to_owned(),
+            "${PAYMENTS_HONEYCOMB_API_KEY}".to_owned(),
+        ),
+        (
+            "search-logs".to_owned(),
+            "${SEARCH_HONEYCOMB_API_KEY}".to_owned(),
+        ),
+    ])
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -90,14 +132,21 @@ impl SinkConfig for HoneycombConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
-        let batch_settings = self.batch.into_batch_settings()?;
+        let request_settings = self
+            .request
+            .unwrap_with(&TowerRequestConfig::default())
+            .with_context(&cx);
+        let mut batch_settings = self.batch.into_batch_settings()?;
+        if self.markers {
+            // The markers API creates a single marker per request.
+            batch_settings.size.events = 1;
+        }
 
-        let buffer = JsonArrayBuffer::new(batch_settings.size);
+        let buffer = PartitionBuffer::new(JsonArrayBuffer::new(batch_settings.size));
 
         let client = HttpClient::new(None, cx.proxy())?;
 
-        let sink = BatchedHttpSink::new(
+        let sink = PartitionHttpSink::new(
             self.clone(),
             buffer,
             request_settings,
@@ -124,11 +173,28 @@ impl SinkConfig for HoneycombConfig {
 }
 
 pub struct HoneycombEventEncoder {
+    dataset: Template,
+    markers: bool,
     transformer: Transformer,
 }
 
-impl HttpEventEncoder<serde_json::Value> for HoneycombEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<serde_json::Value> {
+impl HttpEventEncoder<PartitionInnerBuffer<serde_json::Value, String>> for HoneycombEventEncoder {
+    fn encode_event(
+        &mut self,
+        mut event: Event,
+    ) -> Option<PartitionInnerBuffer<serde_json::Value, String>> {
+        let dataset = self
+            .dataset
+            .render_string(&event)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("dataset"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+
         self.transformer.transform(&mut event);
         let mut log = event.into_log();
 
@@ -138,80 +204,125 @@ impl HttpEventEncoder<serde_json::Value> for HoneycombEventEncoder {
             chrono::Utc::now()
         };
 
-        let data = json!({
-            "time": timestamp.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true),
-            "data": log.convert_to_fields(),
-        });
+        let data = if self.markers {
+            let mut marker = serde_json::Map::new();
+            marker.insert("start_time".to_owned(), json!(timestamp.timestamp()));
+            if let Some(message) = log.message_path().and_then(|path| log.get(path.as_str())) {
+                marker.insert("message".to_owned(), json!(message.to_string_lossy()));
+            }
+            for field in ["type", "url"] {
+                if let Some(value) = log.get(field) {
+                    marker.insert(field.to_owned(), json!(value.to_string_lossy()));
+                }
+            }
+            marker.into()
+        } else {
+            json!({
+                "time": timestamp.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true),
+                "data": log.convert_to_fields(),
+            })
+        };
 
-        Some(data)
+        Some(PartitionInnerBuffer::new(data, dataset))
     }
 }
 
 #[async_trait::async_trait]
 impl HttpSink for HoneycombConfig {
-    type Input = serde_json::Value;
-    type Output = Vec<BoxedRawValue>;
+    type Input = PartitionInnerBuffer<serde_json::Value, String>;
+    type Output = PartitionInnerBuffer<Vec<BoxedRawValue>, String>;
     type Encoder = HoneycombEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         HoneycombEventEncoder {
+            dataset: self.dataset.clone(),
+            markers: self.markers,
             transformer: self.encoding.clone(),
         }
     }
 
-    async fn build_request(&self, events: Self::Output) -> crate::Result<Request<Bytes>> {
-        let uri = self.build_uri();
-        let request = Request::post(uri).header("X-Honeycomb-Team", self.api_key.inner());
-        let body = crate::serde::json::to_bytes(&events).unwrap().freeze();
+    async fn build_request(&self, output: Self::Output) -> crate::Result<Request<Bytes>> {
+        let (events, dataset) = output.into_parts();
+        let (uri, body) = if self.markers {
+            let marker = events
+                .first()
+                .ok_or("Honeycomb markers are sent one at a time")?;
+            (
+                self.build_uri("markers", &dataset)?,
+                crate::serde::json::to_bytes(marker)?.freeze(),
+            )
+        } else {
+            (
+                self.build_uri("batch", &dataset)?,
+                crate::serde::json::to_bytes(&events)?.freeze(),
+            )
+        };
 
-        request.body(body).map_err(Into::into)
+        Request::post(uri)
+            .header("X-Honeycomb-Team", self.api_key(&dataset).inner())
+            .body(body)
+            .map_err(Into::into)
     }
 }
 
 impl HoneycombConfig {
-    fn build_uri(&self) -> Uri {
-        let uri = format!("{}/{}", self.endpoint, self.dataset);
+    fn build_uri(&self, api: &str, dataset: &str) -> crate::Result<Uri> {
+        let uri = format!(
+            "{}/1/{}/{}",
+            self.endpoint,
+            api,
+            utf8_percent_encode(dataset, NON_ALPHANUMERIC)
+        );
+
+        Ok(uri.parse::<Uri>()?)
+    }
 
-        uri.parse::<Uri>().expect("This should be a valid uri")
+    fn api_key(&self, dataset: &str) -> &SensitiveString {
+        self.api_keys.get(dataset).unwrap_or(&self.api_key)
     }
 }
 
+/// Checks that each of the team keys is valid, as datasets may not exist before the first events
+/// are sent to them.
 async fn healthcheck(config: HoneycombConfig, client: HttpClient) -> crate::Result<()> {
-    let req = config
-        .build_request(Vec::new())
-        .await?
-        .map(hyper::Body::from);
-
-    let res = client.send(req).await?;
-
-    let status = res.status();
-    let body = hyper::body::to_bytes(res.into_body()).await?;
-
-    if status == StatusCode::BAD_REQUEST {
-        Ok(())
-    } else if status == StatusCode::UNAUTHORIZED {
-        let json: serde_json::Value = serde_json::from_slice(&body[..])?;
-
-        let message = if let Some(s) = json
-            .as_object()
-            .and_then(|o| o.get("error"))
-            .and_then(|s| s.as_str())
-        {
-            s.to_string()
-        } else {
-            "Token is not valid, 401 returned.".to_string()
-        };
-
-        Err(message.into())
-    } else {
-        let body = String::from_utf8_lossy(&body[..]);
-
-        Err(format!(
-            "Server returned unexpected error status: {} body: {}",
-            status, body
-        )
-        .into())
+    let uri = format!("{}/1/auth", config.endpoint).parse::<Uri>()?;
+
+    for api_key in std::iter::once(&config.api_key).chain(config.api_keys.values()) {
+        let req = Request::get(&uri)
+            .header("X-Honeycomb-Team", api_key.inner())
+            .body(hyper::Body::empty())?;
+
+        let res = client.send(req).await?;
+
+        let status = res.status();
+        let body = hyper::body::to_bytes(res.into_body()).await?;
+
+        if status == StatusCode::UNAUTHORIZED {
+            let json: serde_json::Value = serde_json::from_slice(&body[..])?;
+
+            let message = if let Some(s) = json
+                .as_object()
+                .and_then(|o| o.get("error"))
+                .and_then(|s| s.as_str())
+            {
+                s.to_string()
+            } else {
+                "Token is not valid, 401 returned.".to_string()
+            };
+
+            return Err(message.into());
+        } else if status != StatusCode::OK {
+            let body = String::from_utf8_lossy(&body[..]);
+
+            return Err(format!(
+                "Server returned unexpected error status: {} body: {}",
+                status, body
+            )
+            .into());
+        }
     }
+
+    Ok(())
 }
 #[cfg(test)]
 mod test {
@@ -221,6 +332,10 @@ mod test {
 
     use crate::{
         config::{GenerateConfig, SinkConfig, SinkContext},
+        sinks::util::{
+            http::{HttpEventEncoder, HttpSink},
+            PartitionInnerBuffer,
+        },
         test_util::{
             components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
             http::{always_200_response, spawn_blackhole_http_server},
@@ -229,6 +344,23 @@ mod test {
 
     use super::HoneycombConfig;
 
+    fn config(extra: &str) -> HoneycombConfig {
+        toml::from_str(&format!(
+            r#"api_key = "default-key"
+            dataset = "{{{{ team }}}}-logs"
+            {}"#,
+            extra
+        ))
+        .unwrap()
+    }
+
+    fn event(team: &str) -> Event {
+        let mut log = LogEvent::from("deployed");
+        log.insert("team", team);
+        log.insert("url", "https://example.com/deploys/1");
+        Event::Log(log)
+    }
+
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<HoneycombConfig>();
@@ -249,4 +381,66 @@ mod test {
         let event = Event::Log(LogEvent::from("simple message"));
         run_and_assert_sink_compliance(sink, stream::once(ready(event)), &HTTP_SINK_TAGS).await;
     }
+
+    #[tokio::test]
+    async fn routes_events_to_datasets() {
+        let config = config(
+            r#"[api_keys]
+            payments-logs = "payments-key""#,
+        );
+        let mut encoder = config.build_encoder();
+
+        let payments = encoder.encode_event(event("payments")).unwrap();
+        let search = encoder.encode_event(event("search")).unwrap();
+        let (_, payments_dataset) = payments.clone().into_parts();
+        let (_, search_dataset) = search.into_parts();
+        assert_eq!(payments_dataset, "payments-logs");
+        assert_eq!(search_dataset, "search-logs");
+        assert!(encoder
+            .encode_event(Event::Log(LogEvent::from("no team")))
+            .is_none());
+
+        let (data, dataset) = payments.into_parts();
+        let body = serde_json::value::to_raw_value(&data).unwrap();
+        let request = config
+            .build_request(PartitionInnerBuffer::new(vec![body], dataset))
+            .await
+            .unwrap();
+        assert_eq!(
+            request.uri().to_string(),
+            "https://api.honeycomb.io/1/batch/payments%2Dlogs"
+        );
+        assert_eq!(request.headers()["X-Honeycomb-Team"], "payments-key");
+        assert_eq!(
+            config.api_key("search-logs").inner(),
+            "default-key",
+            "datasets without a key use the default one"
+        );
+    }
+
+    #[tokio::test]
+    async fn sends_markers() {
+        let config = config("markers = true");
+        let mut encoder = config.build_encoder();
+
+        let (data, dataset) = encoder
+            .encode_event(event("payments"))
+            .unwrap()
+            .into_parts();
+        assert_eq!(data["message"], "deployed");
+        assert_eq!(data["url"], "https://example.com/deploys/1");
+        assert!(data["start_time"].is_i64());
+
+        let body = serde_json::value::to_raw_value(&data).unwrap();
+        let request = config
+            .build_request(PartitionInnerBuffer::new(vec![body], dataset))
+            .await
+            .unwrap();
+        assert_eq!(
+            request.uri().to_string(),
+            "https://api.honeycomb.io/1/markers/payments%2Dlogs"
+        );
+        let body: serde_json::Value = serde_json::from_slice(request.body()).unwrap();
+        assert_eq!(body["message"], "deployed");
+    }
 }
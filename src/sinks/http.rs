@@ -1,8 +1,5 @@
-use std::io::Write;
-
 use bytes::{BufMut, Bytes, BytesMut};
 use codecs::encoding::{CharacterDelimitedEncoder, Framer, Serializer};
-use flate2::write::{GzEncoder, ZlibEncoder};
 use futures::{future, FutureExt, SinkExt};
 use http::{
     header::{HeaderName, HeaderValue, AUTHORIZATION},
@@ -14,7 +11,10 @@ use tokio_util::codec::Encoder as _;
 use vector_config::configurable_component;
 
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    codecs::{
+        CodecSelector, CodecSelectorConfig, Encoder, EncodingConfigWithFraming, SinkType,
+        Transformer,
+    },
     components::validation::*,
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     event::Event,
@@ -22,9 +22,9 @@ use crate::{
     register_validatable_component,
     sinks::util::{
         self,
-        http::{BatchedHttpSink, HttpEventEncoder, RequestConfig},
-        BatchConfig, Buffer, Compression, RealtimeSizeBasedDefaultBatchSettings,
-        TowerRequestConfig, UriSerde,
+        http::{CompressionNegotiator, HttpEventEncoder, PartitionHttpSink, RequestConfig},
+        BatchConfig, Buffer, Compression, Compressor, PartitionBuffer, PartitionInnerBuffer,
+        RealtimeSizeBasedDefaultBatchSettings, TowerRequestConfig, UriSerde,
     },
     tls::{TlsConfig, TlsSettings},
 };
@@ -58,9 +58,28 @@ pub struct HttpSinkConfig {
     #[serde(default)]
     pub compression: Compression,
 
+    /// The compressions to fall back to, in order, when the endpoint rejects requests with
+    /// `415 Unsupported Media Type`.
+    ///
+    /// The rejected requests are sent again with the next compression, which is then used for all
+    /// of the following requests. Add `none` to send uncompressed requests once all of the
+    /// compressions are rejected.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "gzip"))]
+    #[configurable(metadata(docs::examples = "none"))]
+    pub compression_fallbacks: Vec<Compression>,
+
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
+    /// Selects the codec of each event from one of its fields, instead of encoding all events
+    /// with `encoding.codec`.
+    ///
+    /// Events are batched by codec, so that each request is encoded with a single codec. Only the
+    /// `http` sink can select codecs, other sinks reject this option when the config is loaded.
+    #[configurable(derived)]
+    pub codec_selector: Option<CodecSelectorConfig>,
+
     /// A string to prefix the payload with.
     ///
     /// This option is ignored if the encoding is not character delimited JSON.
@@ -178,8 +197,10 @@ struct HttpSink {
     pub payload_prefix: String,
     pub payload_suffix: String,
     pub compression: Compression,
+    pub compression_negotiator: Option<CompressionNegotiator>,
     pub transformer: Transformer,
     pub encoder: Encoder<Framer>,
+    pub codec_selector: Option<CodecSelector>,
     pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
     pub tower: TowerRequestConfig,
     pub headers: IndexMap<HeaderName, HeaderValue>,
@@ -195,8 +216,10 @@ fn default_sink(encoding: EncodingConfigWithFraming) -> HttpSink {
         method: Default::default(),
         auth: Default::default(),
         compression: Default::default(),
+        compression_negotiator: None,
         transformer: Default::default(),
         encoder,
+        codec_selector: None,
         payload_prefix: Default::default(),
         payload_suffix: Default::default(),
         batch: Default::default(),
@@ -213,7 +236,7 @@ impl SinkConfig for HttpSinkConfig {
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let client = self.build_http_client(&cx)?;
 
-        let healthcheck = match cx.healthcheck.uri {
+        let healthcheck = match cx.healthcheck.uri.clone() {
             Some(healthcheck_uri) => {
                 healthcheck(healthcheck_uri, self.auth.clone(), client.clone()).boxed()
             }
@@ -230,13 +253,27 @@ impl SinkConfig for HttpSinkConfig {
         let (payload_prefix, payload_suffix) =
             validate_payload_wrapper(&self.payload_prefix, &self.payload_suffix, &encoder)?;
 
+        let codec_selector = self
+            .codec_selector
+            .as_ref()
+            .map(|selector| selector.build(&self.encoding, SinkType::MessageBased))
+            .transpose()?;
+        for encoder in codec_selector.iter().flat_map(CodecSelector::encoders) {
+            validate_payload_wrapper(&self.payload_prefix, &self.payload_suffix, encoder)?;
+        }
+
         let sink = HttpSink {
             uri: self.uri.with_default_parts(),
             method: self.method,
             auth: self.auth.choose_one(&self.uri.auth)?,
             compression: self.compression,
+            compression_negotiator: CompressionNegotiator::new(
+                self.compression,
+                &self.compression_fallbacks,
+            ),
             transformer: self.encoding.transformer(),
             encoder,
+            codec_selector,
             batch: self.batch,
             tower: request.tower,
             headers,
@@ -244,12 +281,15 @@ impl SinkConfig for HttpSinkConfig {
             payload_suffix,
         };
 
-        let request = sink.tower.unwrap_with(&TowerRequestConfig::default());
+        let request = sink
+            .tower
+            .unwrap_with(&TowerRequestConfig::default())
+            .with_context(&cx);
 
         let batch = sink.batch.into_batch_settings()?;
-        let sink = BatchedHttpSink::new(
+        let sink = PartitionHttpSink::new(
             sink,
-            Buffer::new(batch.size, Compression::None),
+            PartitionBuffer::new(Buffer::new(batch.size, Compression::None)),
             request,
             batch.timeout,
             client,
@@ -262,7 +302,11 @@ impl SinkConfig for HttpSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type())
+        let codec = self.encoding.config().1;
+        Input::new(match &self.codec_selector {
+            Some(selector) => selector.input_type(codec),
+            None => codec.input_type(),
+        })
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -281,12 +325,14 @@ impl ValidatableComponent for HttpSinkConfig {
             method: HttpMethod::Post,
             encoding: EncodingConfigWithFraming::new(
                 None,
-                JsonSerializerConfig::new(MetricTagValues::Full).into(),
+                JsonSerializerConfig::new(MetricTagValues::Full, Default::default()).into(),
                 Transformer::default(),
             ),
+            codec_selector: None,
             auth: None,
             headers: None,
             compression: Compression::default(),
+            compression_fallbacks: Vec::new(),
             batch: BatchConfig::default(),
             request: RequestConfig::default(),
             tls: None,
@@ -309,41 +355,62 @@ register_validatable_component!(HttpSinkConfig);
 
 pub struct HttpSinkEventEncoder {
     encoder: Encoder<Framer>,
+    codec_selector: Option<CodecSelector>,
     transformer: Transformer,
 }
 
-impl HttpEventEncoder<BytesMut> for HttpSinkEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<BytesMut> {
+impl HttpEventEncoder<PartitionInnerBuffer<BytesMut, Option<String>>> for HttpSinkEventEncoder {
+    fn encode_event(
+        &mut self,
+        mut event: Event,
+    ) -> Option<PartitionInnerBuffer<BytesMut, Option<String>>> {
+        // The codec is selected before the transformer runs, as it may remove the selecting field.
+        let key = self
+            .codec_selector
+            .as_ref()
+            .and_then(|selector| selector.select(&event));
+        let encoder = match (&key, self.codec_selector.as_mut()) {
+            (Some(key), Some(selector)) => selector.encoder_mut(key)?,
+            _ => &mut self.encoder,
+        };
+
         self.transformer.transform(&mut event);
 
         let mut body = BytesMut::new();
-        self.encoder.encode(event, &mut body).ok()?;
+        encoder.encode(event, &mut body).ok()?;
 
-        Some(body)
+        Some(PartitionInnerBuffer::new(body, key))
     }
 }
 
 #[async_trait::async_trait]
 impl util::http::HttpSink for HttpSink {
-    type Input = BytesMut;
-    type Output = BytesMut;
+    type Input = PartitionInnerBuffer<BytesMut, Option<String>>;
+    type Output = PartitionInnerBuffer<BytesMut, Option<String>>;
     type Encoder = HttpSinkEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         HttpSinkEventEncoder {
             encoder: self.encoder.clone(),
+            codec_selector: self.codec_selector.clone(),
             transformer: self.transformer.clone(),
         }
     }
 
-    async fn build_request(&self, mut body: Self::Output) -> crate::Result<http::Request<Bytes>> {
+    async fn build_request(&self, output: Self::Output) -> crate::Result<http::Request<Bytes>> {
         let method: Method = self.method.into();
         let uri: Uri = self.uri.uri.clone();
 
+        let (mut body, key) = output.into_parts();
+        let encoder = key
+            .as_deref()
+            .and_then(|key| self.codec_selector.as_ref()?.encoder(key))
+            .unwrap_or(&self.encoder);
+
         let content_type = {
             use Framer::*;
             use Serializer::*;
-            match (self.encoder.serializer(), self.encoder.framer()) {
+            match (encoder.serializer(), encoder.framer()) {
                 (RawMessage(_) | Text(_), _) => Some("text/plain"),
                 (Json(_), NewlineDelimited(_)) => {
                     if !body.is_empty() {
@@ -373,30 +440,22 @@ impl util::http::HttpSink for HttpSink {
             }
         };
 
+        // With compressed framing, the events of the request are compressed into a single frame.
+        encoder.compress_batch(&mut body)?;
+
         let mut builder = Request::builder().method(method).uri(uri);
 
         if let Some(content_type) = content_type {
             builder = builder.header("Content-Type", content_type);
         }
 
-        match self.compression {
-            Compression::Gzip(level) => {
-                builder = builder.header("Content-Encoding", "gzip");
-
-                let buffer = BytesMut::new();
-                let mut w = GzEncoder::new(buffer.writer(), level.as_flate2());
-                w.write_all(&body).expect("Writing to Vec can't fail");
-                body = w.finish().expect("Writing to Vec can't fail").into_inner();
-            }
-            Compression::Zlib(level) => {
-                builder = builder.header("Content-Encoding", "deflate");
-
-                let buffer = BytesMut::new();
-                let mut w = ZlibEncoder::new(buffer.writer(), level.as_flate2());
-                w.write_all(&body).expect("Writing to Vec can't fail");
-                body = w.finish().expect("Writing to Vec can't fail").into_inner();
-            }
-            Compression::None => {}
+        let compression = self
+            .compression_negotiator
+            .as_ref()
+            .map_or(self.compression, CompressionNegotiator::current);
+        if let Some(content_encoding) = compression.content_encoding() {
+            builder = builder.header("Content-Encoding", content_encoding);
+            body = Compressor::compress(compression, &body)?;
         }
 
         let headers = builder
@@ -415,6 +474,10 @@ impl util::http::HttpSink for HttpSink {
 
         Ok(request)
     }
+
+    fn compression_negotiator(&self) -> Option<CompressionNegotiator> {
+        self.compression_negotiator.clone()
+    }
 }
 
 async fn healthcheck(uri: UriSerde, auth: Option<Auth>, client: HttpClient) -> crate::Result<()> {
@@ -515,9 +578,10 @@ mod tests {
 
         let sink = default_sink((None::<FramingConfig>, TextSerializerConfig::default()).into());
         let mut encoder = sink.build_encoder();
-        let bytes = encoder.encode_event(event).unwrap();
+        let (bytes, key) = encoder.encode_event(event).unwrap().into_parts();
 
         assert_eq!(bytes, Vec::from("hello world\n"));
+        assert_eq!(key, None);
     }
 
     #[test]
@@ -532,7 +596,7 @@ mod tests {
                 .into(),
         );
         let mut encoder = sink.build_encoder();
-        let bytes = encoder.encode_event(event).unwrap();
+        let (bytes, _) = encoder.encode_event(event).unwrap().into_parts();
 
         #[derive(Deserialize, Debug)]
         #[serde(deny_unknown_fields)]
@@ -940,6 +1004,54 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn selects_codec_per_request() {
+        let in_addr = next_addr();
+
+        let config = format!(
+            r#"
+        uri = "http://{}/frames"
+        method = "post"
+        encoding.codec = "json"
+        codec_selector.key = "{{{{ format }}}}"
+        codec_selector.codecs.text.codec = "text"
+    "#,
+            in_addr
+        );
+        let config: HttpSinkConfig = toml::from_str(&config).unwrap();
+
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+        let (rx, trigger, server) = build_test_server(in_addr);
+        tokio::spawn(server);
+
+        let events = ["a", "b", "c"].into_iter().map(|message| {
+            let mut log = LogEvent::from(message);
+            if message != "c" {
+                log.insert("format", "text");
+            }
+            Event::from(log)
+        });
+        components::run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS)
+            .await;
+        drop(trigger);
+
+        let mut requests = rx
+            .map(|(parts, body)| {
+                let content_type = parts.headers["content-type"].to_str().unwrap().to_owned();
+                (content_type, String::from_utf8(body.to_vec()).unwrap())
+            })
+            .collect::<Vec<_>>()
+            .await;
+        requests.sort();
+
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].0, "application/json");
+        let json: Vec<serde_json::Value> = serde_json::from_str(&requests[0].1).unwrap();
+        assert_eq!(json.len(), 1);
+        assert_eq!(json[0]["message"], "c");
+        assert_eq!(requests[1], ("text/plain".to_owned(), "a\nb\n".to_owned()));
+    }
+
     async fn get_received(
         rx: mpsc::Receiver<(Parts, Bytes)>,
         assert_parts: impl Fn(Parts),
@@ -133,7 +133,7 @@ impl SinkConfig for InfluxDbConfig {
             client.clone(),
         )?;
         validate_quantiles(&self.quantiles)?;
-        let sink = InfluxDbSvc::new(self.clone(), client)?;
+        let sink = InfluxDbSvc::new(self.clone(), client, &cx)?;
         Ok((sink, healthcheck))
     }
 
@@ -147,7 +147,11 @@ impl SinkConfig for InfluxDbConfig {
 }
 
 impl InfluxDbSvc {
-    pub fn new(config: InfluxDbConfig, client: HttpClient) -> crate::Result<VectorSink> {
+    pub fn new(
+        config: InfluxDbConfig,
+        client: HttpClient,
+        cx: &SinkContext,
+    ) -> crate::Result<VectorSink> {
         let settings = influxdb_settings(
             config.influxdb1_settings.clone(),
             config.influxdb2_settings.clone(),
@@ -158,10 +162,13 @@ impl InfluxDbSvc {
         let protocol_version = settings.protocol_version();
 
         let batch = config.batch.into_batch_settings()?;
-        let request = config.request.unwrap_with(&TowerRequestConfig {
-            retry_attempts: Some(5),
-            ..Default::default()
-        });
+        let request = config
+            .request
+            .unwrap_with(&TowerRequestConfig {
+                retry_attempts: Some(5),
+                ..Default::default()
+            })
+            .with_context(cx);
 
         let uri = settings.write_uri(endpoint)?;
 
@@ -1124,7 +1131,7 @@ mod integration_tests {
         }
 
         let client = HttpClient::new(None, cx.proxy()).unwrap();
-        let sink = InfluxDbSvc::new(config, client).unwrap();
+        let sink = InfluxDbSvc::new(config, client, &cx).unwrap();
         run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS).await;
 
         let mut body = std::collections::HashMap::new();
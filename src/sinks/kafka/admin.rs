@@ -0,0 +1,170 @@
+use std::{
+    collections::{HashMap, HashSet},
+    num::{NonZeroU16, NonZeroU32},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use rdkafka::{
+    admin::{AdminClient, AdminOptions, NewTopic, TopicReplication},
+    client::DefaultClientContext,
+    consumer::{BaseConsumer, Consumer},
+    error::{KafkaError, RDKafkaErrorCode},
+    ClientConfig,
+};
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+
+use super::config::{KafkaRole, KafkaSinkConfig};
+
+#[derive(Debug, Snafu)]
+enum TopicError {
+    #[snafu(display("creating kafka admin client failed: {}", source))]
+    AdminClientCreateFailed { source: KafkaError },
+    #[snafu(display("fetching the metadata of topic `{}` failed: {}", topic, source))]
+    FetchMetadataFailed { topic: String, source: KafkaError },
+    #[snafu(display("topic `{}` doesn't exist", topic))]
+    TopicNotFound { topic: String },
+}
+
+/// Topic creation configuration.
+///
+/// Missing topics are created before the first event is sent to them, so that topics such as
+/// per-tenant ones don't have to be created ahead of time.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaTopicCreationConfig {
+    /// The number of partitions of the created topics.
+    ///
+    /// Defaults to the `num.partitions` option of the brokers.
+    #[configurable(metadata(docs::examples = 6))]
+    pub partitions: Option<NonZeroU32>,
+
+    /// The replication factor of the created topics.
+    ///
+    /// Defaults to the `default.replication.factor` option of the brokers.
+    #[configurable(metadata(docs::examples = 3))]
+    pub replication_factor: Option<NonZeroU16>,
+
+    /// The configuration of the created topics.
+    ///
+    /// For more information on topic configuration options, see [Topic Configs][topic_configs_docs].
+    ///
+    /// [topic_configs_docs]: https://kafka.apache.org/documentation/#topicconfigs
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "example_topic_configs()"))]
+    #[configurable(metadata(
+        docs::additional_props_description = "A topic configuration option."
+    ))]
+    pub configs: HashMap<String, String>,
+}
+
+fn example_topic_configs() -> HashMap<String, String> {
+    HashMap::<_, _>::from_iter(
+        [
+            ("cleanup.policy".to_string(), "delete".to_string()),
+            ("retention.ms".to_string(), "86400000".to_string()),
+        ]
+        .into_iter(),
+    )
+}
+
+/// Creates the topics events are sent to, when they don't exist yet.
+#[derive(Clone)]
+pub(crate) struct TopicCreator {
+    client: Arc<AdminClient<DefaultClientContext>>,
+    config: KafkaTopicCreationConfig,
+    /// The topics known to exist, which aren't created again.
+    existing: Arc<Mutex<HashSet<String>>>,
+}
+
+impl TopicCreator {
+    pub(crate) fn new(
+        client_config: &ClientConfig,
+        config: KafkaTopicCreationConfig,
+    ) -> crate::Result<Self> {
+        let client = client_config
+            .create()
+            .context(AdminClientCreateFailedSnafu)?;
+        Ok(Self {
+            client: Arc::new(client),
+            config,
+            existing: Default::default(),
+        })
+    }
+
+    /// Creates the topic, unless it's known to exist.
+    pub(crate) async fn ensure_topic(&self, topic: &str) -> Result<(), KafkaError> {
+        if self.existing.lock().unwrap().contains(topic) {
+            return Ok(());
+        }
+
+        // The brokers fall back to their defaults for partitions and replication factors of -1.
+        let partitions = self
+            .config
+            .partitions
+            .map_or(-1, |partitions| partitions.get() as i32);
+        let replication_factor = self
+            .config
+            .replication_factor
+            .map_or(-1, |replication_factor| i32::from(replication_factor.get()));
+        let mut new_topic = NewTopic::new(
+            topic,
+            partitions,
+            TopicReplication::Fixed(replication_factor),
+        );
+        for (key, value) in &self.config.configs {
+            new_topic = new_topic.set(key, value);
+        }
+
+        let options = AdminOptions::new().operation_timeout(Some(Duration::from_secs(30)));
+        let results = self.client.create_topics([&new_topic], &options).await?;
+        for result in results {
+            match result {
+                Ok(_) => debug!(message = "Created topic.", %topic),
+                Err((_, RDKafkaErrorCode::TopicAlreadyExists)) => {}
+                Err((_, code)) => return Err(KafkaError::AdminOp(code)),
+            }
+        }
+
+        self.existing.lock().unwrap().insert(topic.to_owned());
+        Ok(())
+    }
+}
+
+/// Checks that the topic of the sink exists, creating it first if topic creation is enabled.
+///
+/// Templated topics aren't known before events are sent to them, so they aren't validated.
+pub(crate) async fn validate_topic(
+    config: &KafkaSinkConfig,
+    topic_creator: Option<&TopicCreator>,
+) -> crate::Result<()> {
+    if config.topic.is_dynamic() {
+        debug!(message = "Skipping validation of templated topic.");
+        return Ok(());
+    }
+    let topic = config.topic.get_ref().to_owned();
+
+    if let Some(topic_creator) = topic_creator {
+        topic_creator.ensure_topic(&topic).await?;
+    }
+
+    let client = config.to_rdkafka(KafkaRole::Consumer)?;
+    tokio::task::spawn_blocking(move || -> crate::Result<()> {
+        let consumer: BaseConsumer = client.create()?;
+        let metadata = consumer
+            .fetch_metadata(Some(&topic), Duration::from_secs(3))
+            .context(FetchMetadataFailedSnafu { topic: &topic })?;
+        let exists = metadata
+            .topics()
+            .iter()
+            .any(|metadata| metadata.name() == topic && metadata.error().is_none());
+        if exists {
+            Ok(())
+        } else {
+            Err(TopicError::TopicNotFound { topic }.into())
+        }
+    })
+    .await?
+}
@@ -14,7 +14,11 @@ use crate::{
     kafka::{KafkaAuthConfig, KafkaCompression},
     serde::json::to_string,
     sinks::{
-        kafka::sink::{healthcheck, KafkaSink},
+        kafka::{
+            admin::{validate_topic, KafkaTopicCreationConfig, TopicCreator},
+            schema_registry::build_serializer,
+            sink::{healthcheck, KafkaSink},
+        },
         util::{BatchConfig, NoDefaultsBatchSettings},
         Healthcheck, VectorSink,
     },
@@ -110,6 +114,18 @@ pub struct KafkaSinkConfig {
     #[configurable(metadata(docs::examples = "headers"))]
     pub headers_key: Option<String>,
 
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    pub topic_creation: Option<KafkaTopicCreationConfig>,
+
+    /// Whether to check that the topic exists when the sink starts.
+    ///
+    /// If `topic_creation` is set, the topic is created first if it doesn't exist. The sink fails
+    /// to start if the topic doesn't exist. Templated topics aren't checked.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    pub validate_topic: bool,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -258,6 +274,8 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            topic_creation: None,
+            validate_topic: false,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -266,8 +284,19 @@ impl GenerateConfig for KafkaSinkConfig {
 
 #[async_trait::async_trait]
 impl SinkConfig for KafkaSinkConfig {
-    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let sink = KafkaSink::new(self.clone())?;
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let topic_creator = self
+            .topic_creation
+            .clone()
+            .map(|topic_creation| {
+                TopicCreator::new(&self.to_rdkafka(KafkaRole::Producer)?, topic_creation)
+            })
+            .transpose()?;
+        if self.validate_topic {
+            validate_topic(self, topic_creator.as_ref()).await?;
+        }
+        let serializer = build_serializer(&self.encoding, cx.proxy()).await?;
+        let sink = KafkaSink::new(self.clone(), topic_creator, serializer)?;
         let hc = healthcheck(self.clone()).boxed();
         Ok((VectorSink::from_event_streamsink(sink), hc))
     }
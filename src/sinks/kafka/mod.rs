@@ -1,5 +1,7 @@
+pub(crate) mod admin;
 pub(crate) mod config;
 pub(crate) mod request_builder;
+pub(crate) mod schema_registry;
 pub(crate) mod service;
 pub(crate) mod sink;
 pub(crate) mod tests;
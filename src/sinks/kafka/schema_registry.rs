@@ -0,0 +1,91 @@
+use codecs::encoding::{AvroSerializer, Serializer, SerializerConfig};
+
+use crate::{
+    codecs::EncodingConfig, config::ProxyConfig, kafka::schema_registry::fetch_schema_version,
+};
+
+/// Builds the serializer of the sink, fetching the Avro schema from the schema registry when one
+/// is configured.
+pub(crate) async fn build_serializer(
+    encoding: &EncodingConfig,
+    proxy: &ProxyConfig,
+) -> crate::Result<Serializer> {
+    match encoding.config() {
+        SerializerConfig::Avro { avro } if avro.schema.is_none() => match &avro.schema_registry {
+            Some(registry) => {
+                let registered = fetch_schema_version(registry, proxy).await?;
+                debug!(
+                    message = "Fetched the Avro schema from the schema registry.",
+                    subject = %registry.subject,
+                    id = registered.id,
+                );
+                Ok(Serializer::Avro(AvroSerializer::registered(
+                    &registered.schema,
+                    registered.id,
+                )?))
+            }
+            None => encoding.build(),
+        },
+        _ => encoding.build(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use codecs::encoding::{
+        AvroSchemaRegistryAuth, AvroSchemaRegistryOptions, AvroSerializerOptions,
+    };
+    use futures::StreamExt;
+    use hyper::{Body, Response};
+    use tokio_util::codec::Encoder as _;
+    use vector_core::event::{Event, LogEvent};
+
+    use super::*;
+    use crate::{
+        sinks::util::test::build_test_server_generic,
+        test_util::{next_addr, trace_init},
+    };
+
+    #[tokio::test]
+    async fn fetches_schema_from_registry() {
+        trace_init();
+        let addr = next_addr();
+        let (mut rx, trigger, server) = build_test_server_generic(addr, || {
+            Response::new(Body::from(
+                r#"{"subject":"logs-value","version":3,"id":42,"schema":"{\"type\":\"record\",\"name\":\"Log\",\"fields\":[{\"name\":\"message\",\"type\":\"string\"}]}"}"#,
+            ))
+        });
+        tokio::spawn(server);
+
+        let encoding = EncodingConfig::from(SerializerConfig::Avro {
+            avro: AvroSerializerOptions {
+                schema: None,
+                schema_registry: Some(AvroSchemaRegistryOptions {
+                    url: format!("http://{}/", addr),
+                    subject: "logs-value".to_owned(),
+                    version: NonZeroU32::new(3),
+                    auth: Some(AvroSchemaRegistryAuth {
+                        user: "user".to_owned(),
+                        password: "password".to_owned().into(),
+                    }),
+                }),
+            },
+        });
+        let mut serializer = build_serializer(&encoding, &ProxyConfig::default())
+            .await
+            .unwrap();
+
+        let (parts, _) = rx.next().await.unwrap();
+        drop(trigger);
+        assert_eq!(parts.uri.path(), "/subjects/logs%2Dvalue/versions/3");
+        assert!(parts.headers.contains_key("authorization"));
+
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        let mut bytes = bytes::BytesMut::new();
+        serializer.encode(Event::Log(log), &mut bytes).unwrap();
+        assert_eq!(bytes.as_ref(), b"\0\0\0\0\x2a\x0ahello".as_slice());
+    }
+}
@@ -17,6 +17,7 @@ use vector_core::{
     stream::DriverResponse,
 };
 
+use super::admin::TopicCreator;
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     kafka::KafkaStatisticsContext,
@@ -65,13 +66,18 @@ impl MetaDescriptive for KafkaRequest {
 #[derive(Clone)]
 pub struct KafkaService {
     kafka_producer: FutureProducer<KafkaStatisticsContext>,
+    topic_creator: Option<TopicCreator>,
     bytes_sent: Registered<BytesSent>,
 }
 
 impl KafkaService {
-    pub(crate) fn new(kafka_producer: FutureProducer<KafkaStatisticsContext>) -> KafkaService {
+    pub(crate) fn new(
+        kafka_producer: FutureProducer<KafkaStatisticsContext>,
+        topic_creator: Option<TopicCreator>,
+    ) -> KafkaService {
         KafkaService {
             kafka_producer,
+            topic_creator,
             bytes_sent: register!(BytesSent::from(Protocol("kafka".into()))),
         }
     }
@@ -92,6 +98,10 @@ impl Service<KafkaRequest> for KafkaService {
         Box::pin(async move {
             let event_byte_size = request.get_metadata().events_byte_size();
 
+            if let Some(topic_creator) = &this.topic_creator {
+                topic_creator.ensure_topic(&request.metadata.topic).await?;
+            }
+
             let mut record =
                 FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
             if let Some(key) = &request.metadata.key {
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use codecs::encoding::Serializer;
 use futures::{future, stream::BoxStream, StreamExt};
 use rdkafka::{
     consumer::{BaseConsumer, Consumer},
@@ -17,7 +18,7 @@ use crate::{
     kafka::KafkaStatisticsContext,
     sinks::{
         kafka::{
-            config::QUEUED_MIN_MESSAGES, request_builder::KafkaRequestBuilder,
+            admin::TopicCreator, config::QUEUED_MIN_MESSAGES, request_builder::KafkaRequestBuilder,
             service::KafkaService,
         },
         util::{builder::SinkBuilderExt, StreamSink},
@@ -53,18 +54,21 @@ pub(crate) fn create_producer(
 }
 
 impl KafkaSink {
-    pub(crate) fn new(config: KafkaSinkConfig) -> crate::Result<Self> {
+    pub(crate) fn new(
+        config: KafkaSinkConfig,
+        topic_creator: Option<TopicCreator>,
+        serializer: Serializer,
+    ) -> crate::Result<Self> {
         let producer_config = config.to_rdkafka(KafkaRole::Producer)?;
         let producer = create_producer(producer_config)?;
         let transformer = config.encoding.transformer();
-        let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
 
         Ok(KafkaSink {
             headers_key: config.headers_key,
             transformer,
             encoder,
-            service: KafkaService::new(producer),
+            service: KafkaService::new(producer, topic_creator),
             topic: config.topic,
             key_field: config.key_field,
         })
@@ -6,6 +6,7 @@ mod integration_test {
     use std::{
         collections::{BTreeMap, HashMap},
         future::ready,
+        num::NonZeroU32,
         thread,
         time::Duration,
     };
@@ -25,6 +26,7 @@ mod integration_test {
         kafka::{KafkaAuthConfig, KafkaCompression, KafkaSaslConfig},
         sinks::{
             kafka::{
+                admin::{validate_topic, KafkaTopicCreationConfig, TopicCreator},
                 config::{KafkaRole, KafkaSinkConfig},
                 sink::KafkaSink,
                 *,
@@ -66,11 +68,59 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            topic_creation: None,
+            validate_topic: false,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn validates_and_creates_topic() {
+        crate::test_util::trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let mut config = KafkaSinkConfig {
+            bootstrap_servers: kafka_address(9091),
+            topic: Template::try_from(topic.clone()).unwrap(),
+            key_field: None,
+            encoding: TextSerializerConfig::default().into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: Duration::from_millis(60000),
+            message_timeout_ms: Duration::from_millis(300000),
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            topic_creation: None,
+            validate_topic: true,
+            acknowledgements: Default::default(),
+        };
+        assert!(validate_topic(&config, None).await.is_err());
+
+        config.topic_creation = Some(KafkaTopicCreationConfig {
+            partitions: NonZeroU32::new(2),
+            replication_factor: None,
+            configs: HashMap::from([("retention.ms".to_owned(), "3600000".to_owned())]),
+        });
+        let topic_creator = TopicCreator::new(
+            &config.to_rdkafka(KafkaRole::Producer).unwrap(),
+            config.topic_creation.clone().unwrap(),
+        )
+        .unwrap();
+        validate_topic(&config, Some(&topic_creator)).await.unwrap();
+
+        let consumer: BaseConsumer = config
+            .to_rdkafka(KafkaRole::Consumer)
+            .unwrap()
+            .create()
+            .unwrap();
+        let metadata = consumer
+            .fetch_metadata(Some(&topic), Duration::from_secs(3))
+            .unwrap();
+        assert_eq!(metadata.topics()[0].partitions().len(), 2);
+    }
+
     #[tokio::test]
     async fn kafka_happy_path_plaintext() {
         crate::test_util::trace_init();
@@ -121,12 +171,15 @@ mod integration_test {
             batch,
             librdkafka_options,
             headers_key: None,
+            topic_creation: None,
+            validate_topic: false,
             acknowledgements: Default::default(),
         };
         config.clone().to_rdkafka(KafkaRole::Consumer)?;
         config.clone().to_rdkafka(KafkaRole::Producer)?;
         self::sink::healthcheck(config.clone()).await?;
-        KafkaSink::new(config)
+        let serializer = config.encoding.build()?;
+        KafkaSink::new(config, None, serializer)
     }
 
     #[tokio::test]
@@ -252,6 +305,8 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
+            topic_creation: None,
+            validate_topic: false,
             acknowledgements: Default::default(),
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
@@ -276,7 +331,8 @@ mod integration_test {
             events
         });
         assert_sink_compliance(&SINK_TAGS, async move {
-            let sink = KafkaSink::new(config).unwrap();
+            let serializer = config.encoding.build().unwrap();
+            let sink = KafkaSink::new(config, None, serializer).unwrap();
             let sink = VectorSink::from_event_streamsink(sink);
             sink.run(input_events).await
         })
@@ -34,11 +34,13 @@ pub enum ExtendedCompression {
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 #[serde(untagged)]
 pub enum CompressionConfigAdapter {
-    /// Basic compression.
-    Original(Compression),
-
+    // Listed first, so that `snappy` selects Protocol Buffers push requests rather than the basic
+    // Snappy compression.
     /// Loki-specific compression.
     Extended(ExtendedCompression),
+
+    /// Basic compression.
+    Original(Compression),
 }
 
 impl CompressionConfigAdapter {
@@ -212,7 +214,7 @@ impl GenerateConfig for LokiConfig {
 }
 
 impl LokiConfig {
-    pub(super) fn build_client(&self, cx: SinkContext) -> crate::Result<HttpClient> {
+    pub(super) fn build_client(&self, cx: &SinkContext) -> crate::Result<HttpClient> {
         let tls = TlsSettings::from_options(&self.tls)?;
         let client = HttpClient::new(tls, cx.proxy())?;
         Ok(client)
@@ -235,14 +237,14 @@ impl SinkConfig for LokiConfig {
             }
         }
 
-        let client = self.build_client(cx)?;
+        let client = self.build_client(&cx)?;
 
         let config = LokiConfig {
             auth: self.auth.choose_one(&self.endpoint.auth)?,
             ..self.clone()
         };
 
-        let sink = LokiSink::new(config.clone(), client.clone())?;
+        let sink = LokiSink::new(config.clone(), client.clone(), &cx)?;
 
         let healthcheck = healthcheck(config, client).boxed();
 
@@ -27,6 +27,7 @@ use crate::sinks::{
 };
 use crate::{
     codecs::{Encoder, Transformer},
+    config::SinkContext,
     http::{get_http_scheme_from_uri, HttpClient},
     internal_events::{
         LokiEventUnlabeled, LokiOutOfOrderEventDropped, LokiOutOfOrderEventRewritten,
@@ -331,7 +332,7 @@ pub struct LokiSink {
 
 impl LokiSink {
     #[allow(clippy::missing_const_for_fn)] // const cannot run destructor
-    pub fn new(config: LokiConfig, client: HttpClient) -> crate::Result<Self> {
+    pub fn new(config: LokiConfig, client: HttpClient, cx: &SinkContext) -> crate::Result<Self> {
         let compression = config.compression;
 
         // if Vector is configured to allow events with out of order timestamps, then we can
@@ -343,9 +344,15 @@ impl LokiSink {
         // requires in-order processing for version >= 2.4, instead we just keep the static limit
         // of 1 for now.
         let request_limits = match config.out_of_order_action {
-            OutOfOrderAction::Accept => config.request.unwrap_with(&Default::default()),
+            OutOfOrderAction::Accept => config
+                .request
+                .unwrap_with(&Default::default())
+                .with_context(cx),
             OutOfOrderAction::Drop | OutOfOrderAction::RewriteTimestamp => {
-                let mut settings = config.request.unwrap_with(&Default::default());
+                let mut settings = config
+                    .request
+                    .unwrap_with(&Default::default())
+                    .with_context(cx);
                 settings.concurrency = Some(1);
                 settings
             }
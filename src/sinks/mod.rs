@@ -242,6 +242,11 @@ pub enum Sinks {
     #[configurable(metadata(docs::label = "File"))]
     File(file::FileSinkConfig),
 
+    /// Store UDM events in Google Chronicle.
+    #[cfg(feature = "sinks-gcp")]
+    #[configurable(metadata(docs::label = "GCP Chronicle UDM"))]
+    GcpChronicleUdm(gcp::chronicle_udm::ChronicleUdmConfig),
+
     /// Store unstructured log events in Google Chronicle.
     #[cfg(feature = "sinks-gcp")]
     #[configurable(metadata(docs::label = "GCP Chronicle Unstructured"))]
@@ -470,6 +475,7 @@ impl NamedComponent for Sinks {
             #[cfg(feature = "sinks-file")]
             Self::File(config) => config.get_component_name(),
             #[cfg(feature = "sinks-gcp")]
+            Self::GcpChronicleUdm(config) => config.get_component_name(),
             Self::GcpChronicleUnstructured(config) => config.get_component_name(),
             #[cfg(feature = "sinks-gcp")]
             Self::GcpStackdriverLogs(config) => config.get_component_name(),
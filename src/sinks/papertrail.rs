@@ -106,6 +106,7 @@ impl SinkConfig for PapertrailConfig {
                 transformer,
                 encoder,
             },
+            None,
         )
     }
 
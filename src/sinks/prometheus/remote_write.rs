@@ -136,7 +136,10 @@ impl SinkConfig for RemoteWriteConfig {
         let endpoint = self.endpoint.parse::<Uri>().context(sinks::UriParseSnafu)?;
         let tls_settings = TlsSettings::from_options(&self.tls)?;
         let batch = self.batch.into_batch_settings()?;
-        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let request_settings = self
+            .request
+            .unwrap_with(&TowerRequestConfig::default())
+            .with_context(&cx);
         let buckets = self.buckets.clone();
         let quantiles = self.quantiles.clone();
 
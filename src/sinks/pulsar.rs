@@ -13,7 +13,10 @@ use crate::{
     sinks::util::metadata::RequestMetadataBuilder,
 };
 use bytes::BytesMut;
-use codecs::{encoding::SerializerConfig, TextSerializerConfig};
+use codecs::{
+    encoding::{AvroSerializerOptions, SerializerConfig},
+    TextSerializerConfig,
+};
 use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, Sink, Stream};
 use pulsar::authentication::oauth2::{OAuth2Authentication, OAuth2Params};
 use pulsar::compression;
@@ -39,6 +42,8 @@ use vector_config::configurable_component;
 enum BuildError {
     #[snafu(display("creating pulsar producer failed: {}", source))]
     CreatePulsarSink { source: PulsarError },
+    #[snafu(display("a {} schema requires the `{}` codec", format, format))]
+    SchemaCodecMismatch { format: &'static str },
 }
 
 /// Configuration for the `pulsar` sink.
@@ -71,6 +76,9 @@ pub struct PulsarSinkConfig {
     #[serde(default)]
     compression: PulsarCompression,
 
+    #[configurable(derived)]
+    schema: Option<SchemaConfig>,
+
     #[configurable(derived)]
     auth: Option<AuthConfig>,
 
@@ -96,6 +104,59 @@ pub struct BatchConfig {
     #[configurable(metadata(docs::type_unit = "events"))]
     #[configurable(metadata(docs::examples = 1000))]
     pub max_events: Option<u32>,
+
+    /// Whether to only batch together messages that have the same key.
+    ///
+    /// Consumers of `Key_Shared` subscriptions receive whole batches, so a batch holding messages
+    /// with different keys can deliver messages of the same key to several consumers. When
+    /// enabled, the current batch is sent whenever a message has a different key than the
+    /// messages of the batch.
+    #[serde(default)]
+    pub key_based: bool,
+}
+
+/// Schema registration configuration.
+///
+/// The schema is registered for the topic when the producer is created. If not set, the schema of
+/// the `avro` codec is registered when encoding with it.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct SchemaConfig {
+    #[configurable(derived)]
+    format: SchemaFormat,
+
+    /// The definition of the schema.
+    ///
+    /// Both Avro and JSON schemas are defined with the [Avro schema format][avro_schema].
+    ///
+    /// [avro_schema]: https://avro.apache.org/docs/1.11.1/specification/#schema-declaration
+    #[configurable(metadata(
+        docs::examples = r#"{ "type": "record", "name": "log", "fields": [{ "name": "message", "type": "string" }] }"#
+    ))]
+    definition: String,
+}
+
+/// The format of the schema.
+///
+/// The events must be encoded with the codec of the same name.
+#[configurable_component]
+#[derive(Clone, Copy, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaFormat {
+    /// An Avro schema.
+    Avro,
+
+    /// A JSON schema.
+    Json,
+}
+
+impl SchemaFormat {
+    const fn as_str(self) -> &'static str {
+        match self {
+            SchemaFormat::Avro => "avro",
+            SchemaFormat::Json => "json",
+        }
+    }
 }
 
 /// Authentication configuration.
@@ -196,6 +257,9 @@ struct PulsarSink {
     transformer: Transformer,
     encoder: Encoder<()>,
     partition_key_field: Option<String>,
+    key_based_batching: bool,
+    /// The key of the messages of the current batch, if a message was sent since it was created.
+    batch_key: Option<Option<String>>,
     state: PulsarSinkState,
     in_flight: FuturesUnordered<
         BoxFuture<
@@ -219,6 +283,7 @@ impl GenerateConfig for PulsarSinkConfig {
             partition_key_field: None,
             compression: Default::default(),
             encoding: TextSerializerConfig::default().into(),
+            schema: None,
             auth: None,
             acknowledgements: Default::default(),
             producer_name: None,
@@ -234,6 +299,19 @@ impl SinkConfig for PulsarSinkConfig {
         &self,
         _cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        if let Some(schema) = &self.schema {
+            let codec_matches = matches!(
+                (schema.format, self.encoding.config()),
+                (SchemaFormat::Avro, SerializerConfig::Avro { .. })
+                    | (SchemaFormat::Json, SerializerConfig::Json(_))
+            );
+            if !codec_matches {
+                return Err(Box::new(BuildError::SchemaCodecMismatch {
+                    format: schema.format.as_str(),
+                }));
+            }
+        }
+
         let producer = self
             .create_pulsar_producer(false)
             .await
@@ -248,6 +326,7 @@ impl SinkConfig for PulsarSinkConfig {
             transformer,
             encoder,
             self.partition_key_field.clone(),
+            self.batch.key_based,
         )?;
 
         let producer = self
@@ -336,9 +415,25 @@ impl PulsarSinkConfig {
             producer_options.batch_size = self.batch.max_events;
         }
 
-        if let SerializerConfig::Avro { avro } = self.encoding.config() {
+        if let Some(schema) = &self.schema {
+            producer_options.schema = Some(proto::Schema {
+                schema_data: schema.definition.as_bytes().into(),
+                r#type: match schema.format {
+                    SchemaFormat::Avro => proto::schema::Type::Avro,
+                    SchemaFormat::Json => proto::schema::Type::Json,
+                } as i32,
+                ..Default::default()
+            });
+        } else if let SerializerConfig::Avro {
+            avro:
+                AvroSerializerOptions {
+                    schema: Some(schema),
+                    ..
+                },
+        } = self.encoding.config()
+        {
             producer_options.schema = Some(proto::Schema {
-                schema_data: avro.schema.as_bytes().into(),
+                schema_data: schema.as_bytes().into(),
                 r#type: proto::schema::Type::Avro as i32,
                 ..Default::default()
             });
@@ -358,6 +453,7 @@ impl PulsarSink {
         transformer: Transformer,
         encoder: Encoder<()>,
         partition_key_field: Option<String>,
+        key_based_batching: bool,
     ) -> crate::Result<Self> {
         Ok(Self {
             transformer,
@@ -367,6 +463,8 @@ impl PulsarSink {
             bytes_sent: register!(BytesSent::from(Protocol::TCP)),
             events_sent: register!(EventsSent::from(Output(None))),
             partition_key_field,
+            key_based_batching,
+            batch_key: None,
         })
     }
 
@@ -431,6 +529,14 @@ impl Sink<Event> for PulsarSink {
             NonZeroUsize::new(bytes.len()).expect("payload should never be zero length");
         let metadata = metadata_builder.with_request_size(bytes_len);
 
+        // Sending the current batch before a message with a different key keeps the messages of
+        // each batch sharing the same key.
+        let send_batch = self.key_based_batching
+            && self
+                .batch_key
+                .replace(key_value.clone())
+                .map_or(false, |batch_key| batch_key != key_value);
+
         let mut producer = match std::mem::replace(&mut self.state, PulsarSinkState::None) {
             PulsarSinkState::Ready(producer) => producer,
             _ => unreachable!(),
@@ -439,6 +545,12 @@ impl Sink<Event> for PulsarSink {
         let _ = std::mem::replace(
             &mut self.state,
             PulsarSinkState::Sending(Box::pin(async move {
+                if send_batch {
+                    if let Err(error) = producer.send_batch().await {
+                        return (producer, Err(error), metadata, finalizers);
+                    }
+                }
+
                 let mut builder = producer.create_message().with_content(bytes.as_ref());
                 if let Some(ts) = event_time {
                     builder = builder.event_time(ts);
@@ -506,6 +618,23 @@ mod tests {
     fn generate_config() {
         crate::test_util::test_generate_config::<PulsarSinkConfig>();
     }
+
+    #[tokio::test]
+    async fn schema_requires_matching_codec() {
+        let config: PulsarSinkConfig = toml::from_str(
+            r#"
+            endpoint = "pulsar://127.0.0.1:6650"
+            topic = "topic-1234"
+            encoding.codec = "text"
+            schema.format = "json"
+            schema.definition = '{ "type": "record", "name": "log", "fields": [] }'
+            "#,
+        )
+        .unwrap();
+
+        let error = config.build(SinkContext::new_test()).await.err().unwrap();
+        assert_eq!(error.to_string(), "a json schema requires the `json` codec");
+    }
 }
 
 #[cfg(feature = "pulsar-integration-tests")]
@@ -539,6 +668,7 @@ mod integration_tests {
             producer_name: None,
             compression: PulsarCompression::None,
             encoding: TextSerializerConfig::default().into(),
+            schema: None,
             auth: None,
             acknowledgements: Default::default(),
             partition_key_field: Some("message".to_string()),
@@ -569,8 +699,14 @@ mod integration_tests {
         let encoder = Encoder::<()>::new(serializer);
 
         assert_sink_compliance(&SINK_TAGS, async move {
-            let sink =
-                PulsarSink::new(producer, transformer, encoder, cnf.partition_key_field).unwrap();
+            let sink = PulsarSink::new(
+                producer,
+                transformer,
+                encoder,
+                cnf.partition_key_field,
+                cnf.batch.key_based,
+            )
+            .unwrap();
             VectorSink::from_event_sink(sink).run(events).await
         })
         .await
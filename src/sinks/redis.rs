@@ -174,14 +174,14 @@ impl GenerateConfig for RedisSinkConfig {
 impl SinkConfig for RedisSinkConfig {
     async fn build(
         &self,
-        _cx: SinkContext,
+        cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         if self.key.is_empty() {
             return Err("`key` cannot be empty.".into());
         }
         let conn = self.build_client().await.context(RedisCreateFailedSnafu)?;
         let healthcheck = RedisSinkConfig::healthcheck(conn.clone()).boxed();
-        let sink = self.new(conn)?;
+        let sink = self.new(conn, &cx)?;
         Ok((sink, healthcheck))
     }
 
@@ -195,11 +195,18 @@ impl SinkConfig for RedisSinkConfig {
 }
 
 impl RedisSinkConfig {
-    pub fn new(&self, conn: ConnectionManager) -> crate::Result<super::VectorSink> {
-        let request = self.request.unwrap_with(&TowerRequestConfig {
-            concurrency: Concurrency::Fixed(1),
-            ..Default::default()
-        });
+    pub fn new(
+        &self,
+        conn: ConnectionManager,
+        cx: &SinkContext,
+    ) -> crate::Result<super::VectorSink> {
+        let request = self
+            .request
+            .unwrap_with(&TowerRequestConfig {
+                concurrency: Concurrency::Fixed(1),
+                ..Default::default()
+            })
+            .with_context(cx);
 
         let key = self.key.clone();
 
@@ -436,7 +443,7 @@ mod tests {
             evt.into(),
             &Template::try_from("key").unwrap(),
             &Default::default(),
-            &mut Encoder::<()>::new(TextSerializerConfig::default().build().into()),
+            &mut Encoder::<()>::new(TextSerializerConfig::default().build().unwrap().into()),
         )
         .unwrap()
         .item
@@ -523,7 +530,10 @@ mod integration_tests {
         let cnf2 = cnf.clone();
         assert_sink_compliance(&SINK_TAGS, async move {
             let conn = cnf2.build_client().await.unwrap();
-            cnf2.new(conn).unwrap().run(input).await
+            cnf2.new(conn, &SinkContext::new_test())
+                .unwrap()
+                .run(input)
+                .await
         })
         .await
         .expect("Running sink failed");
@@ -586,7 +596,10 @@ mod integration_tests {
         let cnf2 = cnf.clone();
         assert_sink_compliance(&SINK_TAGS, async move {
             let conn = cnf2.build_client().await.unwrap();
-            cnf2.new(conn).unwrap().run(input).await
+            cnf2.new(conn, &SinkContext::new_test())
+                .unwrap()
+                .run(input)
+                .await
         })
         .await
         .expect("Running sink failed");
@@ -654,7 +667,7 @@ mod integration_tests {
         // Publish events.
         assert_sink_compliance(&SINK_TAGS, async move {
             let conn = cnf.build_client().await.unwrap();
-            let sink = cnf.new(conn).unwrap();
+            let sink = cnf.new(conn, &SinkContext::new_test()).unwrap();
             let (_input, events) = random_lines_with_stream(100, num_events, None);
             sink.run(events).await
         })
@@ -132,7 +132,7 @@ impl SinkConfig for SematextMetricsConfig {
         };
 
         let healthcheck = healthcheck(endpoint.clone(), client.clone()).boxed();
-        let sink = SematextMetricsService::new(self.clone(), write_uri(&endpoint)?, client)?;
+        let sink = SematextMetricsService::new(self.clone(), write_uri(&endpoint)?, client, &cx)?;
 
         Ok((sink, healthcheck))
     }
@@ -163,12 +163,16 @@ impl SematextMetricsService {
         config: SematextMetricsConfig,
         endpoint: http::Uri,
         client: HttpClient,
+        cx: &SinkContext,
     ) -> Result<VectorSink> {
         let batch = config.batch.into_batch_settings()?;
-        let request = config.request.unwrap_with(&TowerRequestConfig {
-            retry_attempts: Some(5),
-            ..Default::default()
-        });
+        let request = config
+            .request
+            .unwrap_with(&TowerRequestConfig {
+                retry_attempts: Some(5),
+                ..Default::default()
+            })
+            .with_context(cx);
         let http_service = HttpBatchService::new(client, create_build_request(endpoint));
         let sematext_service = SematextMetricsService {
             config,
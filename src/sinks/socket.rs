@@ -1,5 +1,7 @@
+use std::num::NonZeroUsize;
+
 use codecs::{
-    encoding::{Framer, FramingConfig},
+    encoding::{CompressedEncoder, Framer, FramingConfig, GelfChunker, Serializer},
     TextSerializerConfig,
 };
 use vector_config::configurable_component;
@@ -9,7 +11,10 @@ use crate::sinks::util::unix::UnixSinkConfig;
 use crate::{
     codecs::{Encoder, EncodingConfig, EncodingConfigWithFraming, SinkType},
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
-    sinks::util::{tcp::TcpSinkConfig, udp::UdpSinkConfig},
+    sinks::util::{
+        tcp::{TcpLoadBalancingConfig, TcpSinkConfig},
+        udp::UdpSinkConfig,
+    },
 };
 
 /// Configuration for the `socket` sink.
@@ -54,6 +59,10 @@ pub struct TcpMode {
 
     #[serde(flatten)]
     encoding: EncodingConfigWithFraming,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    load_balancing: Option<TcpLoadBalancingConfig>,
 }
 
 /// UDP configuration.
@@ -65,6 +74,19 @@ pub struct UdpMode {
 
     #[configurable(derived)]
     encoding: EncodingConfig,
+
+    /// The maximum size of the datagrams.
+    ///
+    /// Events encoded with the `gelf` codec that are larger than this are split into
+    /// [GELF chunks][gelf_chunking], so that Graylog can put them back together. It should be set
+    /// to the largest datagram that fits in the MTU of the network, such as 1420 bytes for the
+    /// internet. Events encoded with other codecs aren't affected.
+    ///
+    /// [gelf_chunking]: https://go2docs.graylog.org/5-0/getting_in_log_data/gelf.html#GELFviaUDP
+    #[serde(default)]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 1420, docs::examples = 8192))]
+    max_datagram_size: Option<NonZeroUsize>,
 }
 
 /// Unix Domain Socket configuration.
@@ -106,12 +128,22 @@ impl SocketSinkConfig {
             Mode::Tcp(TcpMode {
                 config: TcpSinkConfig::from_address(address),
                 encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+                load_balancing: None,
             }),
             acknowledgements,
         )
     }
 }
 
+/// With compressed framing, the events sent between flushes of the connection are compressed into
+/// a single frame.
+fn compressor(framer: &Framer) -> Option<CompressedEncoder> {
+    match framer {
+        Framer::Compressed(compressor) => Some(compressor.clone()),
+        _ => None,
+    }
+}
+
 #[async_trait::async_trait]
 impl SinkConfig for SocketSinkConfig {
     async fn build(
@@ -119,24 +151,43 @@ impl SinkConfig for SocketSinkConfig {
         _cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         match &self.mode {
-            Mode::Tcp(TcpMode { config, encoding }) => {
+            Mode::Tcp(TcpMode {
+                config,
+                encoding,
+                load_balancing,
+            }) => {
                 let transformer = encoding.transformer();
                 let (framer, serializer) = encoding.build(SinkType::StreamBased)?;
+                let compressor = compressor(&framer);
                 let encoder = Encoder::<Framer>::new(framer, serializer);
-                config.build(transformer, encoder)
+                match load_balancing {
+                    Some(load_balancing) => {
+                        config.build_balanced(load_balancing, transformer, encoder, compressor)
+                    }
+                    None => config.build(transformer, encoder, compressor),
+                }
             }
-            Mode::Udp(UdpMode { config, encoding }) => {
+            Mode::Udp(UdpMode {
+                config,
+                encoding,
+                max_datagram_size,
+            }) => {
                 let transformer = encoding.transformer();
                 let serializer = encoding.build()?;
+                let chunker = match (&serializer, max_datagram_size) {
+                    (Serializer::Gelf(_), Some(size)) => Some(GelfChunker::new(size.get())?),
+                    _ => None,
+                };
                 let encoder = Encoder::<()>::new(serializer);
-                config.build(transformer, encoder)
+                config.build(transformer, encoder, chunker)
             }
             #[cfg(unix)]
             Mode::Unix(UnixMode { config, encoding }) => {
                 let transformer = encoding.transformer();
                 let (framer, serializer) = encoding.build(SinkType::StreamBased)?;
+                let compressor = compressor(&framer);
                 let encoder = Encoder::<Framer>::new(framer, serializer);
-                config.build(transformer, encoder)
+                config.build(transformer, encoder, compressor)
             }
         }
     }
@@ -196,6 +247,7 @@ mod test {
             mode: Mode::Udp(UdpMode {
                 config: UdpSinkConfig::from_address(addr.to_string()),
                 encoding: JsonSerializerConfig::default().into(),
+                max_datagram_size: None,
             }),
             acknowledgements: Default::default(),
         };
@@ -237,6 +289,52 @@ mod test {
         test_udp(next_addr_v6()).await;
     }
 
+    #[tokio::test]
+    async fn udp_gelf_chunks() {
+        trace_init();
+
+        let addr = next_addr();
+        let receiver = UdpSocket::bind(addr).unwrap();
+
+        let config = SocketSinkConfig {
+            mode: Mode::Udp(UdpMode {
+                config: UdpSinkConfig::from_address(addr.to_string()),
+                encoding: codecs::GelfSerializerConfig::default().into(),
+                max_datagram_size: NonZeroUsize::new(64),
+            }),
+            acknowledgements: Default::default(),
+        };
+
+        let context = SinkContext::new_test();
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let (sink, _healthcheck) = config.build(context).await.unwrap();
+
+            let mut log = LogEvent::from("a".repeat(200));
+            log.insert("host", "example.org");
+            sink.run(stream::once(ready(Event::Log(log).into()))).await
+        })
+        .await
+        .expect("Running sink failed");
+
+        let mut message = Vec::new();
+        let mut buf = [0; 256];
+        let (size, _src_addr) = receiver
+            .recv_from(&mut buf)
+            .expect("Did not receive message");
+        assert_eq!(size, 64);
+        assert_eq!(&buf[..2], &[0x1e, 0x0f]);
+        let count = buf[11];
+        message.extend_from_slice(&buf[12..size]);
+        for sequence_number in 1..count {
+            let (size, _src_addr) = receiver.recv_from(&mut buf).expect("Did not receive chunk");
+            assert_eq!(buf[10], sequence_number);
+            message.extend_from_slice(&buf[12..size]);
+        }
+
+        let data = serde_json::from_slice::<Value>(&message).expect("Invalid JSON received");
+        assert_eq!(data["short_message"], Value::String("a".repeat(200)));
+    }
+
     #[tokio::test]
     async fn tcp_stream() {
         trace_init();
@@ -246,6 +344,7 @@ mod test {
             mode: Mode::Tcp(TcpMode {
                 config: TcpSinkConfig::from_address(addr.to_string()),
                 encoding: (None::<FramingConfig>, JsonSerializerConfig::default()).into(),
+                load_balancing: None,
             }),
             acknowledgements: Default::default(),
         };
@@ -275,6 +374,50 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn tcp_load_balancing() {
+        trace_init();
+
+        let addrs = [next_addr(), next_addr()];
+        let config = SocketSinkConfig {
+            mode: Mode::Tcp(TcpMode {
+                config: TcpSinkConfig::from_address(addrs[0].to_string()),
+                encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+                load_balancing: Some(
+                    toml::from_str(&format!("addresses = [\"{}\"]", addrs[1])).unwrap(),
+                ),
+            }),
+            acknowledgements: Default::default(),
+        };
+
+        let mut receivers = addrs.map(CountReceiver::receive_lines);
+
+        let (mut lines, events) = random_lines_with_stream(10, 100, None);
+
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let context = SinkContext::new_test();
+            let (sink, _healthcheck) = config.build(context).await.unwrap();
+
+            sink.run(events).await
+        })
+        .await
+        .expect("Running sink failed");
+
+        let mut output = Vec::new();
+        for receiver in &mut receivers {
+            receiver.connected().await;
+        }
+        for receiver in receivers {
+            let received = receiver.await;
+            assert!(!received.is_empty());
+            output.extend(received);
+        }
+
+        lines.sort();
+        output.sort();
+        assert_eq!(lines, output);
+    }
+
     // This is a test that checks that we properly receive all events in the
     // case of a proper server side write side shutdown.
     //
@@ -329,6 +472,7 @@ mod test {
                     None,
                 ),
                 encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+                load_balancing: None,
             }),
             acknowledgements: Default::default(),
         };
@@ -448,6 +592,7 @@ mod test {
             mode: Mode::Tcp(TcpMode {
                 config: TcpSinkConfig::from_address(addr.to_string()),
                 encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+                load_balancing: None,
             }),
             acknowledgements: Default::default(),
         };
@@ -23,16 +23,14 @@ use crate::{
     },
     internal_events::StatsdInvalidMetricError,
     sinks::util::{
-        buffer::metrics::compress_distribution,
-        encode_namespace,
-        tcp::TcpSinkConfig,
-        udp::{UdpService, UdpSinkConfig},
-        BatchConfig, BatchSink, Buffer, Compression, EncodedEvent,
+        buffer::metrics::compress_distribution, encode_namespace, tcp::TcpSinkConfig,
+        udp::UdpSinkConfig, BatchConfig, BatchSettings, BatchSink, Buffer, Compression,
+        EncodedEvent,
     },
 };
 
-pub struct StatsdSvc {
-    inner: UdpService,
+pub struct StatsdSvc<S> {
+    inner: S,
 }
 
 /// Configuration for the `statsd` sink.
@@ -47,6 +45,18 @@ pub struct StatsdSinkConfig {
     #[configurable(metadata(docs::examples = "service"))]
     pub default_namespace: Option<String>,
 
+    /// The ID of the container the metrics originate from.
+    ///
+    /// When set, it is appended to every metric as the [DogStatsD container ID field][container_id]
+    /// (`|c:<id>`), which the Datadog Agent uses to tag metrics with the tags of the container
+    /// they originate from. This is only understood by DogStatsD servers.
+    ///
+    /// [container_id]: https://docs.datadoghq.com/developers/dogstatsd/datagram_shell/?tab=metrics#dogstatsd-protocol-v12
+    #[configurable(metadata(
+        docs::examples = "83c0a8a8e9b4f7a0f8b55a0bc1e5e4a6a45ad8a0e4f27ed1b5c5f3b3a8b8c4d1"
+    ))]
+    pub container_id: Option<String>,
+
     #[serde(flatten)]
     pub mode: Mode,
 
@@ -71,9 +81,15 @@ pub enum Mode {
     /// Send over UDP.
     Udp(StatsdUdpConfig),
 
-    /// Send over a Unix domain socket (UDS).
+    /// Send over a Unix domain socket (UDS), as a stream.
     #[cfg(unix)]
     Unix(UnixSinkConfig),
+
+    /// Send over a Unix domain socket (UDS), as datagrams.
+    ///
+    /// This is the type of socket the DogStatsD server of the Datadog Agent listens on.
+    #[cfg(unix)]
+    UnixDatagram(StatsdUnixDatagramConfig),
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -97,6 +113,32 @@ pub struct StatsdUdpConfig {
     pub batch: BatchConfig<StatsdDefaultBatchSettings>,
 }
 
+#[cfg(unix)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StatsdUnixDatagramDefaultBatchSettings;
+
+// Datagrams sent over Unix domain sockets aren't bound by the MTU, the DogStatsD server of the
+// Datadog Agent accepts datagrams of up to 8192 bytes by default.
+#[cfg(unix)]
+impl SinkBatchSettings for StatsdUnixDatagramDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    const MAX_BYTES: Option<usize> = Some(8192);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Unix domain socket datagram configuration.
+#[cfg(unix)]
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct StatsdUnixDatagramConfig {
+    #[serde(flatten)]
+    pub unix: UnixSinkConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<StatsdUnixDatagramDefaultBatchSettings>,
+}
+
 fn default_address() -> SocketAddr {
     SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8125)
 }
@@ -105,6 +147,7 @@ impl GenerateConfig for StatsdSinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(&Self {
             default_namespace: None,
+            container_id: None,
             mode: Mode::Udp(StatsdUdpConfig {
                 batch: Default::default(),
                 udp: UdpSinkConfig::from_address(default_address().to_string()),
@@ -121,10 +164,12 @@ impl SinkConfig for StatsdSinkConfig {
         &self,
         _cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let default_namespace = self.default_namespace.clone();
-        let mut encoder = StatsdEncoder { default_namespace };
+        let encoder = StatsdEncoder {
+            default_namespace: self.default_namespace.clone(),
+            container_id: self.container_id.clone(),
+        };
         match &self.mode {
-            Mode::Tcp(config) => config.build(Default::default(), encoder),
+            Mode::Tcp(config) => config.build(Default::default(), encoder, None),
             Mode::Udp(config) => {
                 // 1432 bytes is a recommended packet size to fit into MTU
                 // https://github.com/statsd/statsd/blob/master/docs/metric_types.md#multi-metric-packets
@@ -133,29 +178,16 @@ impl SinkConfig for StatsdSinkConfig {
                 // mentioned here https://github.com/DataDog/dd-agent/issues/2638
                 let batch = config.batch.into_batch_settings()?;
                 let (service, healthcheck) = config.udp.build_service()?;
-                let service = StatsdSvc { inner: service };
-                let sink = BatchSink::new(
-                    ServiceBuilder::new().service(service),
-                    Buffer::new(batch.size, Compression::None),
-                    batch.timeout,
-                )
-                .sink_map_err(|error| error!(message = "Fatal statsd sink error.", %error))
-                .with_flat_map(move |event: Event| {
-                    stream::iter({
-                        let byte_size = event.size_of();
-                        let mut bytes = BytesMut::new();
-
-                        // Errors are handled by `Encoder`.
-                        encoder
-                            .encode(event, &mut bytes)
-                            .map(|_| Ok(EncodedEvent::new(bytes, byte_size)))
-                    })
-                });
-
-                Ok((super::VectorSink::from_event_sink(sink), healthcheck))
+                Ok((build_batch_sink(service, batch, encoder), healthcheck))
             }
             #[cfg(unix)]
-            Mode::Unix(config) => config.build(Default::default(), encoder),
+            Mode::Unix(config) => config.build(Default::default(), encoder, None),
+            #[cfg(unix)]
+            Mode::UnixDatagram(config) => {
+                let batch = config.batch.into_batch_settings()?;
+                let (service, healthcheck) = config.unix.build_datagram_service()?;
+                Ok((build_batch_sink(service, batch, encoder), healthcheck))
+            }
         }
     }
 
@@ -168,6 +200,38 @@ impl SinkConfig for StatsdSinkConfig {
     }
 }
 
+/// Builds a sink sending batches of metrics to the service, each batch as a single datagram.
+fn build_batch_sink<S>(
+    service: S,
+    batch: BatchSettings<Buffer>,
+    mut encoder: StatsdEncoder,
+) -> super::VectorSink
+where
+    S: Service<BytesMut, Response = ()> + Send + 'static,
+    S::Error: Into<crate::Error> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    let sink = BatchSink::new(
+        ServiceBuilder::new().service(StatsdSvc { inner: service }),
+        Buffer::new(batch.size, Compression::None),
+        batch.timeout,
+    )
+    .sink_map_err(|error| error!(message = "Fatal statsd sink error.", %error))
+    .with_flat_map(move |event: Event| {
+        stream::iter({
+            let byte_size = event.size_of();
+            let mut bytes = BytesMut::new();
+
+            // Errors are handled by `Encoder`.
+            encoder
+                .encode(event, &mut bytes)
+                .map(|_| Ok(EncodedEvent::new(bytes, byte_size)))
+        })
+    });
+
+    super::VectorSink::from_event_sink(sink)
+}
+
 // Note that if multi-valued tags are present, this encoding may change the order from the input
 // event, since the tags with multiple values may not have been grouped together.
 // This is not an issue, but noting as it may be an observed behavior.
@@ -190,6 +254,7 @@ fn push_event<V: Display>(
     val: V,
     metric_type: &str,
     sample_rate: Option<u32>,
+    container_id: Option<&str>,
 ) {
     buf.push(format!("{}:{}|{}", metric.name(), val, metric_type));
 
@@ -202,11 +267,16 @@ fn push_event<V: Display>(
     if let Some(t) = metric.tags() {
         buf.push(format!("#{}", encode_tags(t)));
     };
+
+    if let Some(container_id) = container_id {
+        buf.push(format!("c:{}", container_id));
+    }
 }
 
 #[derive(Debug, Clone)]
 struct StatsdEncoder {
     default_namespace: Option<String>,
+    container_id: Option<String>,
 }
 
 impl Encoder<Event> for StatsdEncoder {
@@ -214,21 +284,31 @@ impl Encoder<Event> for StatsdEncoder {
 
     fn encode(&mut self, event: Event, bytes: &mut BytesMut) -> Result<(), Self::Error> {
         let mut buf = Vec::new();
+        let container_id = self.container_id.as_deref();
 
         let metric = event.as_metric();
         match metric.value() {
             MetricValue::Counter { value } => {
-                push_event(&mut buf, metric, value, "c", None);
+                push_event(&mut buf, metric, value, "c", None, container_id);
             }
             MetricValue::Gauge { value } => {
                 match metric.kind() {
-                    MetricKind::Incremental => {
-                        push_event(&mut buf, metric, format!("{:+}", value), "g", None)
+                    MetricKind::Incremental => push_event(
+                        &mut buf,
+                        metric,
+                        format!("{:+}", value),
+                        "g",
+                        None,
+                        container_id,
+                    ),
+                    MetricKind::Absolute => {
+                        push_event(&mut buf, metric, value, "g", None, container_id)
                     }
-                    MetricKind::Absolute => push_event(&mut buf, metric, value, "g", None),
                 };
             }
             MetricValue::Distribution { samples, statistic } => {
+                // Summaries are sent as DogStatsD distributions, which are aggregated globally
+                // instead of per host.
                 let metric_type = match statistic {
                     StatisticKind::Histogram => "h",
                     StatisticKind::Summary => "d",
@@ -251,12 +331,13 @@ impl Encoder<Event> for StatsdEncoder {
                         sample.value,
                         metric_type,
                         Some(sample.rate),
+                        container_id,
                     );
                 }
             }
             MetricValue::Set { values } => {
                 for val in values {
-                    push_event(&mut buf, metric, val, "s", None);
+                    push_event(&mut buf, metric, val, "s", None, container_id);
                 }
             }
             _ => {
@@ -282,7 +363,12 @@ impl Encoder<Event> for StatsdEncoder {
     }
 }
 
-impl Service<BytesMut> for StatsdSvc {
+impl<S> Service<BytesMut> for StatsdSvc<S>
+where
+    S: Service<BytesMut, Response = ()>,
+    S::Error: Into<crate::Error>,
+    S::Future: Send + 'static,
+{
     type Response = ();
     type Error = crate::Error;
     type Future = future::BoxFuture<'static, Result<(), Self::Error>>;
@@ -378,6 +464,7 @@ mod test {
         let event = Event::Metric(metric1.clone());
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            container_id: None,
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -396,6 +483,7 @@ mod test {
         let event = Event::Metric(metric1);
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            container_id: None,
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -416,6 +504,7 @@ mod test {
         let event = Event::Metric(metric1.clone());
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            container_id: None,
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -435,6 +524,7 @@ mod test {
         let event = Event::Metric(metric1.clone());
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            container_id: None,
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -468,6 +558,7 @@ mod test {
         let event = Event::Metric(metric1);
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            container_id: None,
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -489,6 +580,7 @@ mod test {
         let event = Event::Metric(metric1.clone());
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            container_id: None,
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -496,6 +588,26 @@ mod test {
         vector_common::assert_event_data_eq!(metric1, metric2);
     }
 
+    #[test]
+    fn test_encode_container_id() {
+        let metric = Metric::new(
+            "distribution",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vector_core::samples![1.5 => 1],
+                statistic: StatisticKind::Summary,
+            },
+        )
+        .with_tags(Some(metric_tags!("env" => "prod")));
+        let mut encoder = StatsdEncoder {
+            default_namespace: None,
+            container_id: Some("abc123".to_owned()),
+        };
+        let mut frame = BytesMut::new();
+        encoder.encode(Event::Metric(metric), &mut frame).unwrap();
+        assert_eq!(frame.as_ref(), b"distribution:1.5|d|#env:prod|c:abc123\n");
+    }
+
     #[tokio::test]
     async fn test_send_to_statsd() {
         trace_init();
@@ -506,6 +618,7 @@ mod test {
 
         let config = StatsdSinkConfig {
             default_namespace: Some("ns".into()),
+            container_id: None,
             mode: Mode::Udp(StatsdUdpConfig {
                 batch,
                 udp: UdpSinkConfig::from_address(addr.to_string()),
@@ -564,4 +677,47 @@ mod test {
             Bytes::from("vector.counter:1.5|c|#bare_tag,multi_value:true,multi_value:false,multi_value,normal_tag:value\nvector.histogram:2|h|@0.01\n"),
         );
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_send_to_unix_datagram() {
+        trace_init();
+
+        let path = tempfile::tempdir().unwrap().into_path().join("dsd.socket");
+        let socket = tokio::net::UnixDatagram::bind(&path).unwrap();
+
+        let config = StatsdSinkConfig {
+            default_namespace: None,
+            container_id: Some("abc123".to_owned()),
+            mode: Mode::UnixDatagram(StatsdUnixDatagramConfig {
+                unix: UnixSinkConfig::new(path),
+                batch: Default::default(),
+            }),
+            acknowledgements: Default::default(),
+        };
+
+        let events = vec![Event::Metric(
+            Metric::new(
+                "counter",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(metric_tags!("env" => "prod"))),
+        )];
+
+        let context = SinkContext::new_test();
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let (sink, healthcheck) = config.build(context).await.unwrap();
+            healthcheck.await.unwrap();
+
+            sink.run(stream::iter(events).map(Into::into))
+                .await
+                .expect("Running sink failed")
+        })
+        .await;
+
+        let mut buf = [0; 64];
+        let size = socket.recv(&mut buf).await.unwrap();
+        assert_eq!(&buf[..size], b"counter:1|c|#env:prod|c:abc123\n");
+    }
 }
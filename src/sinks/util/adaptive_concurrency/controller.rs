@@ -14,8 +14,9 @@ use crate::test_util::stats::{TimeHistogram, TimeWeightedSum};
 use crate::{
     http::HttpError,
     internal_events::{
-        AdaptiveConcurrencyAveragedRtt, AdaptiveConcurrencyInFlight, AdaptiveConcurrencyLimit,
-        AdaptiveConcurrencyLimitData, AdaptiveConcurrencyObservedRtt,
+        AdaptiveConcurrencyAveragedRtt, AdaptiveConcurrencyCurrentLimit,
+        AdaptiveConcurrencyDecision, AdaptiveConcurrencyDecisions, AdaptiveConcurrencyInFlight,
+        AdaptiveConcurrencyLimit, AdaptiveConcurrencyLimitData, AdaptiveConcurrencyObservedRtt,
     },
     sinks::util::retries::{RetryAction, RetryLogic},
     stats::{EwmaVar, Mean, MeanVariance},
@@ -34,6 +35,8 @@ pub(super) struct Controller<L> {
     pub(super) stats: Arc<Mutex<ControllerStatistics>>,
 
     limit: Registered<AdaptiveConcurrencyLimit>,
+    current_limit: Registered<AdaptiveConcurrencyCurrentLimit>,
+    decisions: Registered<AdaptiveConcurrencyDecisions>,
     in_flight: Registered<AdaptiveConcurrencyInFlight>,
     observed_rtt: Registered<AdaptiveConcurrencyObservedRtt>,
     averaged_rtt: Registered<AdaptiveConcurrencyAveragedRtt>,
@@ -67,9 +70,16 @@ impl<L> Controller<L> {
     ) -> Self {
         // If a `concurrency` is specified, it becomes both the
         // current limit and the maximum, effectively bypassing all the
-        // mechanisms. Otherwise, the current limit is set to 1 and the
-        // maximum to MAX_CONCURRENCY.
-        let current_limit = concurrency.unwrap_or(1);
+        // mechanisms. Otherwise, the current limit starts at the minimum
+        // of the configured bounds, or at the maximum if it is pinned.
+        let (min_concurrency, max_concurrency) = settings.bounds();
+        let current_limit = concurrency.unwrap_or(if settings.pinned {
+            max_concurrency
+        } else {
+            min_concurrency
+        });
+        let current_limit_handle = register!(AdaptiveConcurrencyCurrentLimit);
+        current_limit_handle.emit(current_limit as u64);
         Self {
             semaphore: Arc::new(ShrinkableSemaphore::new(current_limit)),
             concurrency,
@@ -87,6 +97,10 @@ impl<L> Controller<L> {
             #[cfg(test)]
             stats: Arc::new(Mutex::new(ControllerStatistics::default())),
             limit: register!(AdaptiveConcurrencyLimit),
+            current_limit: current_limit_handle,
+            decisions: register!(AdaptiveConcurrencyDecisions {
+                pinned: settings.pinned
+            }),
             in_flight: register!(AdaptiveConcurrencyInFlight),
             observed_rtt: register!(AdaptiveConcurrencyObservedRtt),
             averaged_rtt: register!(AdaptiveConcurrencyAveragedRtt),
@@ -221,33 +235,54 @@ impl<L> Controller<L> {
     ) {
         let past_rtt_deviation = past_rtt.variance.sqrt();
         let threshold = past_rtt_deviation * self.settings.rtt_deviation_scale;
+        let (min_concurrency, max_concurrency) = self.settings.bounds();
+        // A pinned limit sits at the maximum, so the bounds are ignored to
+        // report the decisions that would have been taken without them.
+        let pinned = self.settings.pinned;
 
         // Normal quick responses trigger an increase in the
         // concurrency limit. Note that we only check this if we had
         // requests to go beyond the current limit to prevent
         // increasing the limit beyond what we have evidence for.
-        if inner.current_limit < super::MAX_CONCURRENCY
+        let decision = if (pinned || inner.current_limit < max_concurrency)
             && inner.reached_limit
             && !inner.had_back_pressure
             && current_rtt.is_some()
             && current_rtt.unwrap() <= past_rtt.mean
         {
-            // Increase (additive) the current concurrency limit
-            self.semaphore.add_permits(1);
-            inner.current_limit += 1;
+            AdaptiveConcurrencyDecision::Increase
         }
         // Back pressure responses, either explicit or implicit due
         // to increasing response times, trigger a decrease in the
         // concurrency limit.
-        else if inner.current_limit > 1
+        else if (pinned || inner.current_limit > min_concurrency)
             && (inner.had_back_pressure || current_rtt.unwrap_or(0.0) >= past_rtt.mean + threshold)
         {
-            // Decrease (multiplicative) the current concurrency limit
-            let to_forget = inner.current_limit
-                - (inner.current_limit as f64 * self.settings.decrease_ratio) as usize;
-            self.semaphore.forget_permits(to_forget);
-            inner.current_limit -= to_forget;
+            AdaptiveConcurrencyDecision::Decrease
+        } else {
+            AdaptiveConcurrencyDecision::Hold
+        };
+        self.decisions.emit(decision);
+
+        match decision {
+            _ if pinned => (),
+            AdaptiveConcurrencyDecision::Increase => {
+                // Increase (additive) the current concurrency limit
+                self.semaphore.add_permits(1);
+                inner.current_limit += 1;
+            }
+            AdaptiveConcurrencyDecision::Decrease => {
+                // Decrease (multiplicative) the current concurrency limit
+                let new_limit = ((inner.current_limit as f64 * self.settings.decrease_ratio)
+                    as usize)
+                    .max(min_concurrency);
+                let to_forget = inner.current_limit - new_limit;
+                self.semaphore.forget_permits(to_forget);
+                inner.current_limit = new_limit;
+            }
+            AdaptiveConcurrencyDecision::Hold => (),
         }
+        self.current_limit.emit(inner.current_limit as u64);
         self.limit.emit(AdaptiveConcurrencyLimitData {
             concurrency: inner.current_limit as u64,
             reached_limit: inner.reached_limit,
@@ -12,6 +12,7 @@ pub mod tests;
 pub(super) const MAX_CONCURRENCY: usize = 200;
 
 pub(crate) use layer::AdaptiveConcurrencyLimitLayer;
+use serde::{de, Deserialize, Deserializer};
 pub(crate) use service::AdaptiveConcurrencyLimit;
 use vector_config::configurable_component;
 
@@ -61,6 +62,31 @@ pub struct AdaptiveConcurrencySettings {
     #[configurable(validation(range(min = 0.0)))]
     #[serde(default = "default_rtt_deviation_scale")]
     pub(super) rtt_deviation_scale: f64,
+
+    /// The minimum concurrency limit.
+    ///
+    /// The concurrency limit starts at this value, and is never decreased below it, regardless of the response
+    /// times or back pressure observed.
+    ///
+    /// Must not be greater than `max_concurrency`.
+    #[configurable(validation(range(min = 1, max = 200)))]
+    #[serde(default = "default_min_concurrency")]
+    pub(super) min_concurrency: usize,
+
+    /// The maximum concurrency limit.
+    ///
+    /// The concurrency limit is never increased above this value. Values above `200` are capped to `200`.
+    #[configurable(validation(range(min = 1, max = 200)))]
+    #[serde(default = "default_max_concurrency")]
+    pub(super) max_concurrency: usize,
+
+    /// Whether or not to pin the concurrency limit at `max_concurrency`.
+    ///
+    /// When pinned, ARC keeps measuring response times and reporting the adjustments it would have made, through
+    /// the `adaptive_concurrency_decisions_total` internal metric, but doesn't apply them. This is useful to find out
+    /// whether ARC's adjustments are responsible for a drop in throughput.
+    #[serde(default)]
+    pub(super) pinned: bool,
 }
 
 const fn default_decrease_ratio() -> f64 {
@@ -75,10 +101,40 @@ const fn default_rtt_deviation_scale() -> f64 {
     2.5
 }
 
+const fn default_min_concurrency() -> usize {
+    1
+}
+
+const fn default_max_concurrency() -> usize {
+    MAX_CONCURRENCY
+}
+
 impl AdaptiveConcurrencySettings {
     pub const fn max_concurrency() -> usize {
         MAX_CONCURRENCY
     }
+
+    /// Deserializes the settings, rejecting a minimum concurrency limit greater than the maximum.
+    pub(crate) fn deserialize_checked<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let settings = Self::deserialize(deserializer)?;
+        if settings.min_concurrency > settings.max_concurrency {
+            return Err(de::Error::custom(format!(
+                "`min_concurrency` ({}) must not be greater than `max_concurrency` ({})",
+                settings.min_concurrency, settings.max_concurrency
+            )));
+        }
+        Ok(settings)
+    }
+
+    /// The bounds of the concurrency limit, as `(min, max)`, with out of range values corrected.
+    pub(super) fn bounds(&self) -> (usize, usize) {
+        let max = self.max_concurrency.clamp(1, MAX_CONCURRENCY);
+        let min = self.min_concurrency.clamp(1, max);
+        (min, max)
+    }
 }
 
 impl Default for AdaptiveConcurrencySettings {
@@ -87,6 +143,9 @@ impl Default for AdaptiveConcurrencySettings {
             decrease_ratio: default_decrease_ratio(),
             ewma_alpha: default_ewma_alpha(),
             rtt_deviation_scale: default_rtt_deviation_scale(),
+            min_concurrency: default_min_concurrency(),
+            max_concurrency: default_max_concurrency(),
+            pinned: false,
         }
     }
 }
@@ -178,15 +178,8 @@ mod tests {
     }
 
     impl TestService {
-        fn start() -> Self {
-            let layer = AdaptiveConcurrencyLimitLayer::new(
-                None,
-                AdaptiveConcurrencySettings {
-                    decrease_ratio: 0.5,
-                    ..Default::default()
-                },
-                TestRetryLogic,
-            );
+        fn start(settings: AdaptiveConcurrencySettings) -> Self {
+            let layer = AdaptiveConcurrencyLimitLayer::new(None, settings, TestRetryLogic);
             let (service, handle) = mock::spawn_layer(layer);
             let controller = Arc::clone(&service.get_ref().controller);
             let inner = Arc::clone(&controller.inner);
@@ -205,7 +198,21 @@ mod tests {
             F: FnOnce(Self) -> Ret,
             Ret: Future<Output = ()>,
         {
-            let svc = Self::start();
+            Self::run_with(Default::default(), doit).await
+        }
+
+        async fn run_with<F, Ret>(
+            settings: AdaptiveConcurrencySettings,
+            doit: F,
+        ) -> ControllerStatistics
+        where
+            F: FnOnce(Self) -> Ret,
+            Ret: Future<Output = ()>,
+        {
+            let svc = Self::start(AdaptiveConcurrencySettings {
+                decrease_ratio: 0.5,
+                ..settings
+            });
             //let inner = svc.inner.clone();
             let stats = Arc::clone(&svc.stats);
             pause();
@@ -346,4 +353,54 @@ mod tests {
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn respects_min_concurrency() {
+        let settings = AdaptiveConcurrencySettings {
+            min_concurrency: 2,
+            ..Default::default()
+        };
+        TestService::run_with(settings, |mut svc| async move {
+            // Concurrency starts at the minimum
+            assert_eq!(svc.inner().current_limit, 2);
+            let req_1 = svc.send(true).await;
+            let req_2 = svc.send(false).await;
+            advance(Duration::from_secs(1)).await;
+            req_1.respond().await;
+            req_2.respond().await;
+
+            // Back pressure doesn't decrease the limit below the minimum
+            let req = svc.send(true).await;
+            advance(Duration::from_secs(1)).await;
+            req.defer().await;
+            assert_eq!(svc.inner().current_limit, 2);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn pinned_limit_is_not_adjusted() {
+        let settings = AdaptiveConcurrencySettings {
+            max_concurrency: 3,
+            pinned: true,
+            ..Default::default()
+        };
+        TestService::run_with(settings, |mut svc| async move {
+            // Concurrency is pinned at the maximum
+            assert_eq!(svc.inner().current_limit, 3);
+            let req_1 = svc.send(true).await;
+            let req_2 = svc.send(true).await;
+            let req_3 = svc.send(false).await;
+            advance(Duration::from_secs(1)).await;
+            req_1.respond().await;
+            req_2.respond().await;
+            req_3.respond().await;
+
+            let req = svc.send(true).await;
+            advance(Duration::from_secs(1)).await;
+            req.defer().await;
+            assert_eq!(svc.inner().current_limit, 3);
+        })
+        .await;
+    }
 }
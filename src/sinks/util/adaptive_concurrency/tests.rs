@@ -170,13 +170,16 @@ impl_generate_config_from_default!(TestConfig);
 
 #[async_trait::async_trait]
 impl SinkConfig for TestConfig {
-    async fn build(&self, _cx: SinkContext) -> Result<(VectorSink, Healthcheck), crate::Error> {
+    async fn build(&self, cx: SinkContext) -> Result<(VectorSink, Healthcheck), crate::Error> {
         let mut batch_settings = BatchSettings::default();
         batch_settings.size.bytes = 9999;
         batch_settings.size.events = 1;
         batch_settings.timeout = Duration::from_secs(9999);
 
-        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let request = self
+            .request
+            .unwrap_with(&TowerRequestConfig::default())
+            .with_context(&cx);
         let sink = request
             .batch_sink(
                 TestRetryLogic,
@@ -30,6 +30,18 @@ pub enum Compression {
     ///
     /// [zlib]: https://zlib.net/
     Zlib(CompressionLevel),
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// The compression level is used as the Zstandard compression level.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd(CompressionLevel),
+
+    /// [Snappy][snappy] compression, in the raw block format.
+    ///
+    /// [snappy]: https://github.com/google/snappy
+    Snappy,
 }
 
 impl Compression {
@@ -53,11 +65,17 @@ impl Compression {
         Compression::Zlib(CompressionLevel::const_default())
     }
 
+    pub const fn zstd_default() -> Compression {
+        Compression::Zstd(CompressionLevel::const_default())
+    }
+
     pub const fn content_encoding(self) -> Option<&'static str> {
         match self {
             Self::None => None,
             Self::Gzip(_) => Some("gzip"),
             Self::Zlib(_) => Some("deflate"),
+            Self::Zstd(_) => Some("zstd"),
+            Self::Snappy => Some("snappy"),
         }
     }
 
@@ -65,6 +83,7 @@ impl Compression {
         match self {
             Self::Gzip(_) => Some("gzip"),
             Self::Zlib(_) => Some("deflate"),
+            Self::Zstd(_) => Some("zstd"),
             _ => None,
         }
     }
@@ -74,13 +93,15 @@ impl Compression {
             Self::None => "log",
             Self::Gzip(_) => "log.gz",
             Self::Zlib(_) => "log.zz",
+            Self::Zstd(_) => "log.zst",
+            Self::Snappy => "log.snappy",
         }
     }
 
     pub const fn level(self) -> flate2::Compression {
         match self {
-            Self::None => flate2::Compression::none(),
-            Self::Gzip(level) | Self::Zlib(level) => level.as_flate2(),
+            Self::None | Self::Snappy => flate2::Compression::none(),
+            Self::Gzip(level) | Self::Zlib(level) | Self::Zstd(level) => level.as_flate2(),
         }
     }
 }
@@ -91,6 +112,8 @@ impl fmt::Display for Compression {
             Compression::None => write!(f, "none"),
             Compression::Gzip(ref level) => write!(f, "gzip({})", level.as_flate2().level()),
             Compression::Zlib(ref level) => write!(f, "zlib({})", level.as_flate2().level()),
+            Compression::Zstd(ref level) => write!(f, "zstd({})", level.as_flate2().level()),
+            Compression::Snappy => write!(f, "snappy"),
         }
     }
 }
@@ -117,9 +140,11 @@ impl<'de> de::Deserialize<'de> for Compression {
                     "none" => Ok(Compression::None),
                     "gzip" => Ok(Compression::gzip_default()),
                     "zlib" => Ok(Compression::zlib_default()),
+                    "zstd" => Ok(Compression::zstd_default()),
+                    "snappy" => Ok(Compression::Snappy),
                     _ => Err(de::Error::invalid_value(
                         de::Unexpected::Str(s),
-                        &r#""none" or "gzip" or "zlib""#,
+                        &r#""none", "gzip", "zlib", "zstd" or "snappy""#,
                     )),
                 }
             }
@@ -159,9 +184,14 @@ impl<'de> de::Deserialize<'de> for Compression {
                     },
                     "gzip" => Ok(Compression::Gzip(level.unwrap_or_default())),
                     "zlib" => Ok(Compression::Zlib(level.unwrap_or_default())),
+                    "zstd" => Ok(Compression::Zstd(level.unwrap_or_default())),
+                    "snappy" => match level {
+                        Some(_) => Err(de::Error::unknown_field("level", &[])),
+                        None => Ok(Compression::Snappy),
+                    },
                     algorithm => Err(de::Error::unknown_variant(
                         algorithm,
-                        &["none", "gzip", "zlib"],
+                        &["none", "gzip", "zlib", "zstd", "snappy"],
                     )),
                 }
             }
@@ -202,6 +232,17 @@ impl ser::Serialize for Compression {
                     serializer.serialize_str("zlib")
                 }
             }
+            Compression::Zstd(zstd_level) => {
+                if *zstd_level != default_level {
+                    let mut map = serializer.serialize_map(None)?;
+                    map.serialize_entry("algorithm", "zstd")?;
+                    map.serialize_entry("level", &zstd_level)?;
+                    map.end()
+                } else {
+                    serializer.serialize_str("zstd")
+                }
+            }
+            Compression::Snappy => serializer.serialize_str("snappy"),
         }
     }
 }
@@ -257,18 +298,30 @@ impl Configurable for Compression {
             Some("[Zlib][zlib] compression."),
             "[zlib]: https://zlib.net/",
         );
+        let zstd_string_subschema = generate_string_schema(
+            "Zstd",
+            Some("[Zstandard][zstd] compression."),
+            "[zstd]: https://facebook.github.io/zstd/",
+        );
+        let snappy_string_subschema = generate_string_schema(
+            "Snappy",
+            Some("[Snappy][snappy] compression, in the raw block format."),
+            "[snappy]: https://github.com/google/snappy",
+        );
 
         let mut all_string_oneof_subschema = generate_one_of_schema(&[
             none_string_subschema,
             gzip_string_subschema,
             zlib_string_subschema,
+            zstd_string_subschema,
+            snappy_string_subschema,
         ]);
         apply_base_metadata(&mut all_string_oneof_subschema, string_metadata);
 
         // Next we'll create a full schema for the given algorithms.
         //
-        // TODO: We're currently using all three algorithms in the enum subschema for `algorithm`,
-        // but in reality, `level` is never used when the algorithm is `none`. This is _currently_
+        // TODO: We're currently using all algorithms in the enum subschema for `algorithm`, but in
+        // reality, `level` is never used when the algorithm is `none` or `snappy`. This is _currently_
         // fine because the field is optional, and we don't use `deny_unknown_fields`, so if users
         // specify it when the algorithm is `none`: no harm, no foul.
         //
@@ -492,6 +545,16 @@ mod test {
                 r#"{"algorithm": "zlib", "level": 8}"#,
                 Compression::Zlib(CompressionLevel::new(8)),
             ),
+            (
+                r#""zstd""#,
+                Compression::Zstd(CompressionLevel::const_default()),
+            ),
+            (
+                r#"{"algorithm": "zstd", "level": 3}"#,
+                Compression::Zstd(CompressionLevel::new(3)),
+            ),
+            (r#""snappy""#, Compression::Snappy),
+            (r#"{"algorithm": "snappy"}"#, Compression::Snappy),
         ];
         for (sources, result) in fixtures_valid.iter() {
             let deserialized: Result<Compression, _> = serde_json::from_str(sources);
@@ -505,16 +568,20 @@ mod test {
             ),
             (
                 r#""b42""#,
-                r#"invalid value: string "b42", expected "none" or "gzip" or "zlib" at line 1 column 5"#,
+                r#"invalid value: string "b42", expected "none", "gzip", "zlib", "zstd" or "snappy" at line 1 column 5"#,
             ),
             (
                 r#"{"algorithm": "b42"}"#,
-                r#"unknown variant `b42`, expected one of `none`, `gzip`, `zlib` at line 1 column 20"#,
+                r#"unknown variant `b42`, expected one of `none`, `gzip`, `zlib`, `zstd`, `snappy` at line 1 column 20"#,
             ),
             (
                 r#"{"algorithm": "none", "level": "default"}"#,
                 r#"unknown field `level`, there are no fields at line 1 column 41"#,
             ),
+            (
+                r#"{"algorithm": "snappy", "level": "default"}"#,
+                r#"unknown field `level`, there are no fields at line 1 column 43"#,
+            ),
             (
                 r#"{"algorithm": "gzip", "level": -1}"#,
                 r#"invalid value: -1, expected 0, 1, 2, 3, 4, 5, 6, 7, 8 or 9 at line 1 column 33"#,
@@ -547,6 +614,8 @@ mod test {
             Compression::Gzip(CompressionLevel::new(7)),
             Compression::Zlib(CompressionLevel::best()),
             Compression::Zlib(CompressionLevel::new(7)),
+            Compression::Zstd(CompressionLevel::best()),
+            Compression::Snappy,
         ];
 
         for v in fixtures_valid {
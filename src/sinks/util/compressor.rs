@@ -1,4 +1,7 @@
-use std::io;
+use std::{
+    fmt,
+    io::{self, Write},
+};
 
 use bytes::{BufMut, BytesMut};
 use flate2::write::{GzEncoder, ZlibEncoder};
@@ -9,16 +12,39 @@ enum Writer {
     Plain(bytes::buf::Writer<BytesMut>),
     Gzip(GzEncoder<bytes::buf::Writer<BytesMut>>),
     Zlib(ZlibEncoder<bytes::buf::Writer<BytesMut>>),
+    Zstd(zstd::stream::write::Encoder<'static, bytes::buf::Writer<BytesMut>>),
+    /// Snappy's raw format compresses a whole block at once, so the input is buffered until the
+    /// compressor is finished.
+    Snappy(bytes::buf::Writer<BytesMut>),
 }
 
 impl Writer {
     pub fn get_ref(&self) -> &BytesMut {
         match self {
-            Writer::Plain(inner) => inner.get_ref(),
+            Writer::Plain(inner) | Writer::Snappy(inner) => inner.get_ref(),
             Writer::Gzip(inner) => inner.get_ref().get_ref(),
             Writer::Zlib(inner) => inner.get_ref().get_ref(),
+            Writer::Zstd(inner) => inner.get_ref().get_ref(),
         }
     }
+
+    fn finish(self) -> io::Result<BytesMut> {
+        let buf = match self {
+            Writer::Plain(writer) => writer,
+            Writer::Gzip(writer) => writer.finish()?,
+            Writer::Zlib(writer) => writer.finish()?,
+            Writer::Zstd(writer) => writer.finish()?,
+            Writer::Snappy(writer) => {
+                let compressed = snap::raw::Encoder::new()
+                    .compress_vec(writer.get_ref())
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+                return Ok(compressed.as_slice().into());
+            }
+        }
+        .into_inner();
+
+        Ok(buf)
+    }
 }
 
 impl From<Compression> for Writer {
@@ -28,6 +54,11 @@ impl From<Compression> for Writer {
             Compression::None => Writer::Plain(writer),
             Compression::Gzip(level) => Writer::Gzip(GzEncoder::new(writer, level.as_flate2())),
             Compression::Zlib(level) => Writer::Zlib(ZlibEncoder::new(writer, level.as_flate2())),
+            Compression::Zstd(level) => Writer::Zstd(
+                zstd::stream::write::Encoder::new(writer, level.as_flate2().level() as i32)
+                    .expect("zstd encoder should not fail to initialize"),
+            ),
+            Compression::Snappy => Writer::Snappy(writer),
         }
     }
 }
@@ -36,17 +67,19 @@ impl io::Write for Writer {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         #[allow(clippy::disallowed_methods)] // Caller handles the result of `write`.
         match self {
-            Writer::Plain(inner_buf) => inner_buf.write(buf),
+            Writer::Plain(inner_buf) | Writer::Snappy(inner_buf) => inner_buf.write(buf),
             Writer::Gzip(writer) => writer.write(buf),
             Writer::Zlib(writer) => writer.write(buf),
+            Writer::Zstd(writer) => writer.write(buf),
         }
     }
 
     fn flush(&mut self) -> io::Result<()> {
         match self {
-            Writer::Plain(writer) => writer.flush(),
+            Writer::Plain(writer) | Writer::Snappy(writer) => writer.flush(),
             Writer::Gzip(writer) => writer.flush(),
             Writer::Zlib(writer) => writer.flush(),
+            Writer::Zstd(writer) => writer.flush(),
         }
     }
 }
@@ -84,14 +117,7 @@ impl Compressor {
     /// If the compressor encounters an I/O error while finalizing the payload, an error
     /// variant will be returned.
     pub fn finish(self) -> io::Result<BytesMut> {
-        let buf = match self.inner {
-            Writer::Plain(writer) => writer,
-            Writer::Gzip(writer) => writer.finish()?,
-            Writer::Zlib(writer) => writer.finish()?,
-        }
-        .into_inner();
-
-        Ok(buf)
+        self.inner.finish()
     }
 
     /// Consumes the compressor, returning the internal buffer used by the compressor.
@@ -104,16 +130,30 @@ impl Compressor {
     ///
     /// Consider using `finish` if catching these scenarios is important.
     pub fn into_inner(self) -> BytesMut {
-        match self.inner {
-            Writer::Plain(writer) => writer,
-            Writer::Gzip(writer) => writer
-                .finish()
-                .expect("gzip writer should not fail to finish"),
-            Writer::Zlib(writer) => writer
-                .finish()
-                .expect("zlib writer should not fail to finish"),
-        }
-        .into_inner()
+        let compression = self.compression;
+        self.inner
+            .finish()
+            .unwrap_or_else(|_| panic!("{} writer should not fail to finish", compression))
+    }
+
+    /// Compresses a whole payload at once.
+    ///
+    /// # Errors
+    ///
+    /// If the compressor encounters an I/O error while compressing the payload, an error variant
+    /// will be returned.
+    pub fn compress(compression: Compression, payload: &[u8]) -> io::Result<BytesMut> {
+        let mut compressor = Self::from(compression);
+        compressor.write_all(payload)?;
+        compressor.finish()
+    }
+}
+
+impl fmt::Debug for Compressor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Compressor")
+            .field("compression", &self.compression)
+            .finish_non_exhaustive()
     }
 }
 
@@ -136,3 +176,26 @@ impl From<Compression> for Compressor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compresses_zstd_and_snappy() {
+        let payload = b"It's going down, I'm yelling timber".repeat(100);
+
+        let compressed = Compressor::compress(Compression::zstd_default(), &payload).unwrap();
+        assert!(compressed.len() < payload.len());
+        assert_eq!(zstd::decode_all(&compressed[..]).unwrap(), payload);
+
+        let compressed = Compressor::compress(Compression::Snappy, &payload).unwrap();
+        assert!(compressed.len() < payload.len());
+        assert_eq!(
+            snap::raw::Decoder::new()
+                .decompress_vec(&compressed)
+                .unwrap(),
+            payload
+        );
+    }
+}
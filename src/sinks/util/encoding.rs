@@ -16,45 +16,81 @@ pub trait Encoder<T> {
 }
 
 impl Encoder<Vec<Event>> for (Transformer, crate::codecs::Encoder<Framer>) {
-    fn encode_input(
-        &self,
-        mut events: Vec<Event>,
-        writer: &mut dyn io::Write,
-    ) -> io::Result<usize> {
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
         let mut encoder = self.1.clone();
-        let mut bytes_written = 0;
-        let mut n_events_pending = events.len();
-        let batch_prefix = encoder.batch_prefix();
-        write_all(writer, n_events_pending, batch_prefix)?;
-        bytes_written += batch_prefix.len();
-        if let Some(last) = events.pop() {
-            for mut event in events {
-                self.0.transform(&mut event);
-                let mut bytes = BytesMut::new();
-                encoder
-                    .encode(event, &mut bytes)
-                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
-                write_all(writer, n_events_pending, &bytes)?;
-                bytes_written += bytes.len();
-                n_events_pending -= 1;
-            }
-            let mut event = last;
-            self.0.transform(&mut event);
+        if !encoder.compresses_batches() {
+            return encode_events(&self.0, &mut encoder, events, writer);
+        }
+
+        // The whole batch is compressed into a single frame, so it's encoded in memory first.
+        let n_events_pending = events.len();
+        let mut batch = Vec::new();
+        encode_events(&self.0, &mut encoder, events, &mut batch)?;
+        let mut bytes = BytesMut::from(&batch[..]);
+        encoder
+            .compress_batch(&mut bytes)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        write_all(writer, n_events_pending, &bytes)?;
+        Ok(bytes.len())
+    }
+}
+
+/// Encodes the events of a batch, without compressing it.
+fn encode_events(
+    transformer: &Transformer,
+    encoder: &mut crate::codecs::Encoder<Framer>,
+    mut events: Vec<Event>,
+    writer: &mut dyn io::Write,
+) -> io::Result<usize> {
+    if encoder.serializes_batches() {
+        // The events can't be written one at a time, as the batch is encoded as a whole.
+        let n_events_pending = events.len();
+        for event in &mut events {
+            transformer.transform(event);
+        }
+        let mut bytes = BytesMut::new();
+        encoder
+            .serialize_batch(events, &mut bytes)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        write_all(writer, n_events_pending, &bytes)?;
+        return Ok(bytes.len());
+    }
+
+    let mut bytes_written = 0;
+    let mut n_events_pending = events.len();
+    let header = encoder.header();
+    write_all(writer, n_events_pending, header)?;
+    bytes_written += header.len();
+    let batch_prefix = encoder.batch_prefix();
+    write_all(writer, n_events_pending, batch_prefix)?;
+    bytes_written += batch_prefix.len();
+    if let Some(last) = events.pop() {
+        for mut event in events {
+            transformer.transform(&mut event);
             let mut bytes = BytesMut::new();
             encoder
-                .serialize(event, &mut bytes)
+                .encode(event, &mut bytes)
                 .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
             write_all(writer, n_events_pending, &bytes)?;
             bytes_written += bytes.len();
             n_events_pending -= 1;
         }
-        let batch_suffix = encoder.batch_suffix();
-        assert!(n_events_pending == 0);
-        write_all(writer, 0, batch_suffix)?;
-        bytes_written += batch_suffix.len();
-
-        Ok(bytes_written)
+        let mut event = last;
+        transformer.transform(&mut event);
+        let mut bytes = BytesMut::new();
+        encoder
+            .serialize(event, &mut bytes)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        write_all(writer, n_events_pending, &bytes)?;
+        bytes_written += bytes.len();
+        n_events_pending -= 1;
     }
+    let batch_suffix = encoder.batch_suffix();
+    assert!(n_events_pending == 0);
+    write_all(writer, 0, batch_suffix)?;
+    bytes_written += batch_suffix.len();
+
+    Ok(bytes_written)
 }
 
 impl Encoder<Event> for (Transformer, crate::codecs::Encoder<()>) {
@@ -124,10 +160,14 @@ where
 mod tests {
     use std::collections::BTreeMap;
 
+    use std::io::Read;
+
     use codecs::{
-        CharacterDelimitedEncoder, JsonSerializerConfig, NewlineDelimitedEncoder,
-        TextSerializerConfig,
+        encoding::{CompressionAlgorithm, CsvSerializerOptions},
+        BytesEncoder, CharacterDelimitedEncoder, CompressedEncoder, CsvSerializerConfig,
+        JsonSerializerConfig, NewlineDelimitedEncoder, TextSerializerConfig,
     };
+    use flate2::bufread::GzDecoder;
     use value::Value;
     use vector_core::event::LogEvent;
 
@@ -293,6 +333,154 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_batch_compressed_single_frame() {
+        let encoding = (
+            Transformer::default(),
+            crate::codecs::Encoder::<Framer>::new(
+                CompressedEncoder::new(
+                    CompressionAlgorithm::Gzip,
+                    None,
+                    NewlineDelimitedEncoder::new().into(),
+                )
+                .into(),
+                JsonSerializerConfig::default().build().into(),
+            ),
+        );
+
+        let mut writer = Vec::new();
+        let written = encoding
+            .encode_input(
+                vec![
+                    Event::Log(LogEvent::from(BTreeMap::from([(
+                        String::from("key"),
+                        Value::from("value1"),
+                    )]))),
+                    Event::Log(LogEvent::from(BTreeMap::from([(
+                        String::from("key"),
+                        Value::from("value2"),
+                    )]))),
+                    Event::Log(LogEvent::from(BTreeMap::from([(
+                        String::from("key"),
+                        Value::from("value3"),
+                    )]))),
+                ],
+                &mut writer,
+            )
+            .unwrap();
+        assert_eq!(written, writer.len());
+
+        // A single gzip member holds the whole batch.
+        let mut decoder = GzDecoder::new(&writer[..]);
+        let mut decompressed = String::new();
+        decoder.read_to_string(&mut decompressed).unwrap();
+        assert_eq!(
+            decompressed,
+            "{\"key\":\"value1\"}\n{\"key\":\"value2\"}\n{\"key\":\"value3\"}"
+        );
+        assert!(decoder.into_inner().is_empty());
+    }
+
+    #[test]
+    fn test_encode_batch_csv_header() {
+        let encoding = (
+            Transformer::default(),
+            crate::codecs::Encoder::<Framer>::new(
+                NewlineDelimitedEncoder::new().into(),
+                CsvSerializerConfig::new(CsvSerializerOptions {
+                    fields: vec!["key".to_owned()],
+                    headers: true,
+                    terminator: Default::default(),
+                    delimiter: b',',
+                    quote: b'"',
+                    nested_value_handling: Default::default(),
+                    null_value: Default::default(),
+                    missing_value: Default::default(),
+                })
+                .build()
+                .unwrap()
+                .into(),
+            ),
+        );
+
+        let mut writer = Vec::new();
+        let written = encoding
+            .encode_input(
+                vec![
+                    Event::Log(LogEvent::from(BTreeMap::from([(
+                        String::from("key"),
+                        Value::from("value1"),
+                    )]))),
+                    Event::Log(LogEvent::from(BTreeMap::from([(
+                        String::from("key"),
+                        Value::from("value2"),
+                    )]))),
+                ],
+                &mut writer,
+            )
+            .unwrap();
+        assert_eq!(written, 17);
+
+        assert_eq!(String::from_utf8(writer).unwrap(), "key\nvalue1\nvalue2");
+    }
+
+    #[cfg(feature = "codecs-parquet")]
+    #[test]
+    fn test_encode_batch_parquet() {
+        use std::num::NonZeroUsize;
+
+        use codecs::{
+            encoding::{
+                ParquetCompression, ParquetField, ParquetFieldType, ParquetSerializerOptions,
+            },
+            ParquetSerializerConfig,
+        };
+
+        let encoding = (
+            Transformer::default(),
+            crate::codecs::Encoder::<Framer>::new(
+                BytesEncoder::new().into(),
+                ParquetSerializerConfig::new(ParquetSerializerOptions {
+                    fields: vec![ParquetField {
+                        name: "key".to_owned(),
+                        r#type: ParquetFieldType::String,
+                    }],
+                    row_group_size: NonZeroUsize::new(1).unwrap(),
+                    compression: ParquetCompression::None,
+                })
+                .build()
+                .unwrap()
+                .into(),
+            ),
+        );
+
+        let mut writer = Vec::new();
+        let written = encoding
+            .encode_input(
+                vec![
+                    Event::Log(LogEvent::from(BTreeMap::from([(
+                        String::from("key"),
+                        Value::from("value1"),
+                    )]))),
+                    Event::Log(LogEvent::from(BTreeMap::from([(
+                        String::from("key"),
+                        Value::from("value2"),
+                    )]))),
+                ],
+                &mut writer,
+            )
+            .unwrap();
+        assert_eq!(written, writer.len());
+
+        // The batch is written as a single file, which starts and ends with the magic number.
+        assert!(writer.starts_with(b"PAR1"));
+        assert!(writer.ends_with(b"PAR1"));
+        assert_eq!(
+            writer.windows(4).filter(|window| window == b"PAR1").count(),
+            2
+        );
+    }
+
     #[test]
     fn test_encode_event_json() {
         let encoding = (
@@ -319,7 +507,9 @@ mod tests {
     fn test_encode_event_text() {
         let encoding = (
             Transformer::default(),
-            crate::codecs::Encoder::<()>::new(TextSerializerConfig::default().build().into()),
+            crate::codecs::Encoder::<()>::new(
+                TextSerializerConfig::default().build().unwrap().into(),
+            ),
         );
 
         let mut writer = Vec::new();
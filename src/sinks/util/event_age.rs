@@ -0,0 +1,205 @@
+//! Shedding of stale events before they reach a sink.
+//!
+//! After an outage, a sink can be handed events that were buffered for hours, which some
+//! destinations, such as alerting backends, have no use for. When a maximum event age is
+//! configured, the events older than it are shed before they reach the sink, and are either
+//! dropped or sent to a dead letter queue sink.
+
+use chrono::{DateTime, Duration, Utc};
+use vector_config::configurable_component;
+
+use crate::{
+    config::ComponentKey,
+    event::{Event, EventArray, EventRef, Value},
+};
+
+/// Maximum event age configuration.
+///
+/// Events older than the maximum age are shed before they reach the sink, instead of being
+/// delivered late, such as after an outage of the downstream service.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MaxEventAgeConfig {
+    /// The maximum age of the events sent to the sink.
+    ///
+    /// The age of an event is measured from its timestamp, which sources set when they ingest it,
+    /// to the time the sink receives it. Events without a timestamp, and traces, are never shed.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 3600))]
+    pub max_age_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub fallback: StaleEventsFallback,
+
+    /// The sink to send stale events to, when `fallback` is set to `dead_letter_queue`.
+    ///
+    /// Defaults to the global `dead_letter_queue` option.
+    #[configurable(metadata(docs::examples = "dead_letters"))]
+    pub dead_letter_queue: Option<ComponentKey>,
+}
+
+impl MaxEventAgeConfig {
+    /// Returns the sink that stale events are sent to, if any.
+    pub fn dead_letter_queue<'a>(
+        &'a self,
+        default: Option<&'a ComponentKey>,
+    ) -> Option<&'a ComponentKey> {
+        match self.fallback {
+            StaleEventsFallback::Drop => None,
+            StaleEventsFallback::DeadLetterQueue => self.dead_letter_queue.as_ref().or(default),
+        }
+    }
+}
+
+/// What to do with the events older than the maximum event age.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StaleEventsFallback {
+    /// Drop the events.
+    ///
+    /// The events are rejected, which is reported to sources that have end-to-end acknowledgements
+    /// enabled.
+    #[default]
+    Drop,
+
+    /// Send the events to the dead letter queue sink.
+    ///
+    /// The events are annotated with the reason they were dropped, in the same way as events sent
+    /// to the `dropped` output of other components.
+    DeadLetterQueue,
+}
+
+/// Splits the events of a sink by age.
+#[derive(Clone, Debug)]
+pub struct MaxEventAge {
+    max_age: Duration,
+}
+
+impl MaxEventAge {
+    /// Creates a splitter shedding the events older than the configured maximum age.
+    pub fn new(config: &MaxEventAgeConfig) -> Self {
+        let max_age = Duration::from_std(std::time::Duration::from_secs(config.max_age_secs))
+            .unwrap_or_else(|_| Duration::max_value());
+        Self { max_age }
+    }
+
+    /// Splits the events into the ones to send to the sink, and the stale ones.
+    pub fn split(&self, events: EventArray) -> (EventArray, Vec<Event>) {
+        self.split_at(events, Utc::now())
+    }
+
+    fn split_at(&self, events: EventArray, now: DateTime<Utc>) -> (EventArray, Vec<Event>) {
+        let cutoff = match now.checked_sub_signed(self.max_age) {
+            Some(cutoff) => cutoff,
+            None => return (events, Vec::new()),
+        };
+        let is_stale = |event: EventRef<'_>| timestamp(event).map_or(false, |at| at < cutoff);
+        if !events.iter_events().any(is_stale) {
+            return (events, Vec::new());
+        }
+
+        let mut stale = Vec::new();
+        let fresh = match events {
+            EventArray::Logs(logs) => EventArray::Logs(split_stale(logs, is_stale, &mut stale)),
+            EventArray::Metrics(metrics) => {
+                EventArray::Metrics(split_stale(metrics, is_stale, &mut stale))
+            }
+            EventArray::Traces(traces) => {
+                EventArray::Traces(split_stale(traces, is_stale, &mut stale))
+            }
+        };
+        (fresh, stale)
+    }
+}
+
+/// Moves the stale events to `stale`, returning the others.
+fn split_stale<T>(
+    events: Vec<T>,
+    is_stale: impl Fn(EventRef<'_>) -> bool,
+    stale: &mut Vec<Event>,
+) -> Vec<T>
+where
+    T: Into<Event>,
+    for<'a> &'a T: Into<EventRef<'a>>,
+{
+    let (old, fresh): (Vec<T>, Vec<T>) =
+        events.into_iter().partition(|event| is_stale(event.into()));
+    stale.extend(old.into_iter().map(Into::into));
+    fresh
+}
+
+/// The time the event was ingested at, if known.
+fn timestamp(event: EventRef<'_>) -> Option<DateTime<Utc>> {
+    match event {
+        EventRef::Log(log) => match log.get_timestamp() {
+            Some(Value::Timestamp(timestamp)) => Some(*timestamp),
+            _ => None,
+        },
+        EventRef::Metric(metric) => metric.timestamp(),
+        EventRef::Trace(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::event::{EventContainer, LogEvent, Metric, MetricKind, MetricValue};
+
+    fn max_event_age(max_age_secs: u64) -> MaxEventAge {
+        MaxEventAge::new(&MaxEventAgeConfig {
+            max_age_secs,
+            fallback: StaleEventsFallback::Drop,
+            dead_letter_queue: None,
+        })
+    }
+
+    fn log(message: &str, timestamp: Option<DateTime<Utc>>) -> LogEvent {
+        let mut log = LogEvent::from(message);
+        if let Some(timestamp) = timestamp {
+            log.insert("timestamp", timestamp);
+        }
+        log
+    }
+
+    #[test]
+    fn sheds_stale_logs() {
+        let now = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let events = EventArray::Logs(vec![
+            log("stale", Some(now - Duration::hours(2))),
+            log("fresh", Some(now - Duration::minutes(30))),
+            log("unknown", None),
+        ]);
+
+        let (fresh, stale) = max_event_age(3600).split_at(events, now);
+
+        assert_eq!(fresh.len(), 2);
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].as_log()["message"], "stale".into());
+    }
+
+    #[test]
+    fn sheds_stale_metrics() {
+        let now = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        );
+        let events = EventArray::Metrics(vec![
+            metric
+                .clone()
+                .with_timestamp(Some(now - Duration::seconds(61))),
+            metric.with_timestamp(Some(now)),
+        ]);
+
+        let (fresh, stale) = max_event_age(60).split_at(events, now);
+
+        assert!(matches!(fresh, EventArray::Metrics(metrics) if metrics.len() == 1));
+        assert_eq!(stale.len(), 1);
+    }
+}
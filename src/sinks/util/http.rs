@@ -4,7 +4,10 @@ use std::{
     hash::Hash,
     marker::PhantomData,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     task::{ready, Context, Poll},
     time::Duration,
 };
@@ -24,13 +27,13 @@ use vector_core::ByteSizeOf;
 
 use super::{
     retries::{RetryAction, RetryLogic},
-    sink, uri, Batch, EncodedEvent, Partition, TowerBatchedSink, TowerPartitionSink,
+    sink, uri, Batch, Compression, EncodedEvent, Partition, TowerBatchedSink, TowerPartitionSink,
     TowerRequestConfig, TowerRequestSettings,
 };
 use crate::{
     event::Event,
     http::{HttpClient, HttpError},
-    internal_events::{EndpointBytesSent, SinkRequestBuildError},
+    internal_events::{EndpointBytesSent, HttpCompressionFallback, SinkRequestBuildError},
 };
 
 pub trait HttpEventEncoder<Output> {
@@ -46,6 +49,12 @@ pub trait HttpSink: Send + Sync + 'static {
 
     fn build_encoder(&self) -> Self::Encoder;
     async fn build_request(&self, events: Self::Output) -> crate::Result<http::Request<Bytes>>;
+
+    /// Gets the negotiator of the compression of the requests built by this sink, if it falls
+    /// back to other compressions when the endpoint rejects them.
+    fn compression_negotiator(&self) -> Option<CompressionNegotiator> {
+        None
+    }
 }
 
 /// Provides a simple wrapper around internal tower and
@@ -132,7 +141,8 @@ where
             Box::pin(async move { sink.build_request(b).await })
         };
 
-        let svc = HttpBatchService::new(client, request_builder);
+        let svc = HttpBatchService::new(client, request_builder)
+            .with_compression_negotiator(sink.compression_negotiator());
         let inner = request_settings.batch_sink(retry_logic, svc, batch, batch_timeout);
         let encoder = sink.build_encoder();
 
@@ -274,7 +284,8 @@ where
             Box::pin(async move { sink.build_request(b).await })
         };
 
-        let svc = HttpBatchService::new(client, request_builder);
+        let svc = HttpBatchService::new(client, request_builder)
+            .with_compression_negotiator(sink.compression_negotiator());
         let inner = request_settings.partition_sink(retry_logic, svc, batch, batch_timeout);
         let encoder = sink.build_encoder();
 
@@ -353,6 +364,7 @@ where
 pub struct HttpBatchService<F, B = Bytes> {
     inner: HttpClient<Body>,
     request_builder: Arc<dyn Fn(B) -> F + Send + Sync>,
+    compression_negotiator: Option<CompressionNegotiator>,
 }
 
 impl<F, B> HttpBatchService<F, B> {
@@ -363,14 +375,28 @@ impl<F, B> HttpBatchService<F, B> {
         HttpBatchService {
             inner,
             request_builder: Arc::new(Box::new(request_builder)),
+            compression_negotiator: None,
         }
     }
+
+    /// Resends requests rejected with `415 Unsupported Media Type` once the negotiator falls back
+    /// to another compression, rebuilding them with it.
+    ///
+    /// The request builder must compress the requests with the current compression of the
+    /// negotiator.
+    pub fn with_compression_negotiator(
+        mut self,
+        compression_negotiator: Option<CompressionNegotiator>,
+    ) -> Self {
+        self.compression_negotiator = compression_negotiator;
+        self
+    }
 }
 
 impl<F, B> Service<B> for HttpBatchService<F, B>
 where
     F: Future<Output = crate::Result<hyper::Request<Bytes>>> + Send + 'static,
-    B: ByteSizeOf + Send + 'static,
+    B: ByteSizeOf + Clone + Send + 'static,
 {
     type Response = http::Response<Bytes>;
     type Error = crate::Error;
@@ -383,31 +409,50 @@ where
     fn call(&mut self, body: B) -> Self::Future {
         let request_builder = Arc::clone(&self.request_builder);
         let http_client = self.inner.clone();
+        let compression_negotiator = self.compression_negotiator.clone();
 
         Box::pin(async move {
-            let request = request_builder(body).await.map_err(|error| {
-                emit!(SinkRequestBuildError { error: &error });
-                error
-            })?;
-            let byte_size = request.body().len();
-            let request = request.map(Body::from);
-            let (protocol, endpoint) = uri::protocol_endpoint(request.uri().clone());
-
             let mut decompression_service = ServiceBuilder::new()
                 .layer(DecompressionLayer::new())
                 .service(http_client);
 
-            // Any errors raised in `http_client.call` results in a `GotHttpWarning` event being emitted
-            // in `HttpClient::send`.
-            let response = decompression_service.call(request).await?;
+            // The body is only kept to build the request again when its encoding can be
+            // negotiated.
+            let mut body = Some(body);
+            let response = loop {
+                let body = match &compression_negotiator {
+                    Some(_) => body.clone(),
+                    None => body.take(),
+                }
+                .expect("the request is only built again when the encoding is negotiated");
+                let request = request_builder(body).await.map_err(|error| {
+                    emit!(SinkRequestBuildError { error: &error });
+                    error
+                })?;
+                let byte_size = request.body().len();
+                let content_encoding = request.headers().get(header::CONTENT_ENCODING).cloned();
+                let request = request.map(Body::from);
+                let (protocol, endpoint) = uri::protocol_endpoint(request.uri().clone());
+
+                // Any errors raised in `http_client.call` results in a `GotHttpWarning` event being emitted
+                // in `HttpClient::send`.
+                let response = decompression_service.call(request).await?;
+
+                if response.status().is_success() {
+                    emit!(EndpointBytesSent {
+                        byte_size,
+                        protocol: &protocol,
+                        endpoint: &endpoint
+                    });
+                }
 
-            if response.status().is_success() {
-                emit!(EndpointBytesSent {
-                    byte_size,
-                    protocol: &protocol,
-                    endpoint: &endpoint
-                });
-            }
+                match &compression_negotiator {
+                    Some(negotiator)
+                        if response.status() == StatusCode::UNSUPPORTED_MEDIA_TYPE
+                            && negotiator.reject(content_encoding.as_ref()) => {}
+                    _ => break response,
+                }
+            };
 
             let (parts, body) = response.into_parts();
             let mut body = body::aggregate(body).await?;
@@ -424,10 +469,67 @@ impl<F, B> Clone for HttpBatchService<F, B> {
         Self {
             inner: self.inner.clone(),
             request_builder: Arc::clone(&self.request_builder),
+            compression_negotiator: self.compression_negotiator.clone(),
         }
     }
 }
 
+/// Negotiates the compression of requests with an endpoint.
+///
+/// Requests are compressed with the configured compression until the endpoint rejects them with
+/// `415 Unsupported Media Type`, after which they are compressed with the next of the fallbacks.
+/// The last fallback is kept for the lifetime of the sink.
+#[derive(Clone, Debug)]
+pub struct CompressionNegotiator {
+    compressions: Arc<[Compression]>,
+    current: Arc<AtomicUsize>,
+}
+
+impl CompressionNegotiator {
+    /// Creates a negotiator, unless there are no compressions to fall back to.
+    pub fn new(compression: Compression, fallbacks: &[Compression]) -> Option<Self> {
+        (!fallbacks.is_empty()).then(|| Self {
+            compressions: std::iter::once(compression)
+                .chain(fallbacks.iter().copied())
+                .collect(),
+            current: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    /// Gets the compression to compress requests with.
+    pub fn current(&self) -> Compression {
+        self.compressions[self.current.load(Ordering::Acquire)]
+    }
+
+    /// Falls back to the next compression after the endpoint rejected a request sent with the
+    /// given content encoding, returning whether the request should be sent again.
+    ///
+    /// Requests built before an earlier fallback are sent again with the current compression,
+    /// without falling back any further.
+    fn reject(&self, content_encoding: Option<&HeaderValue>) -> bool {
+        let content_encoding = content_encoding.and_then(|value| value.to_str().ok());
+        let current = self.current.load(Ordering::Acquire);
+        if self.compressions[current].content_encoding() != content_encoding {
+            return true;
+        }
+        if current + 1 == self.compressions.len() {
+            return false;
+        }
+        // Concurrent requests may have been rejected with the same compression.
+        if self
+            .current
+            .compare_exchange(current, current + 1, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            emit!(HttpCompressionFallback {
+                rejected: self.compressions[current],
+                fallback: self.compressions[current + 1],
+            });
+        }
+        true
+    }
+}
+
 impl<T: fmt::Debug> sink::Response for http::Response<T> {
     fn is_successful(&self) -> bool {
         self.status().is_success()
@@ -625,6 +727,33 @@ mod test {
             .is_not_retryable());
     }
 
+    #[test]
+    fn compression_negotiator_falls_back() {
+        assert!(CompressionNegotiator::new(Compression::gzip_default(), &[]).is_none());
+
+        let negotiator = CompressionNegotiator::new(
+            Compression::zstd_default(),
+            &[Compression::Snappy, Compression::None],
+        )
+        .unwrap();
+        let zstd = HeaderValue::from_static("zstd");
+        let snappy = HeaderValue::from_static("snappy");
+        assert_eq!(negotiator.current(), Compression::zstd_default());
+
+        assert!(negotiator.reject(Some(&zstd)));
+        assert_eq!(negotiator.current(), Compression::Snappy);
+
+        // A request built before the fallback doesn't fall back any further.
+        assert!(negotiator.reject(Some(&zstd)));
+        assert_eq!(negotiator.current(), Compression::Snappy);
+
+        assert!(negotiator.reject(Some(&snappy)));
+        assert_eq!(negotiator.current(), Compression::None);
+
+        assert!(!negotiator.reject(None));
+        assert_eq!(negotiator.current(), Compression::None);
+    }
+
     #[tokio::test]
     async fn util_http_it_makes_http_requests() {
         let addr = next_addr();
@@ -4,15 +4,19 @@ pub mod buffer;
 pub mod builder;
 pub mod compressor;
 pub mod encoding;
+pub mod event_age;
 pub mod http;
 pub mod metadata;
 pub mod normalizer;
+pub mod partition_key;
 pub mod partitioner;
 pub mod processed_event;
 pub mod request_builder;
 pub mod retries;
+pub mod schema_mapping;
 pub mod service;
 pub mod sink;
+pub mod size_budget;
 pub mod socket_bytes_sink;
 pub mod statistic;
 pub mod tcp;
@@ -0,0 +1,133 @@
+use lookup::metadata_path;
+use value::Value;
+use vector_common::TimeZone;
+use vector_core::compile_vrl;
+use vrl::{diagnostic::Formatter, CompilationResult, CompileConfig, Program, Runtime};
+
+use crate::{
+    event::{Event, EventArray, LogEvent, TargetEvents, VrlTarget},
+    internal_events::PartitionKeyEvaluationError,
+};
+
+/// The field of the metadata of the events that the partition key is stored in.
+pub const PARTITION_KEY_FIELD: &str = "partition_key";
+
+/// A VRL expression computing the partition key of the events of a sink.
+///
+/// The expression is evaluated once for each log event before it reaches the sink, and its result
+/// is stored in the metadata of the event, so that the templated options of the sink can refer to
+/// it as `{{ %partition_key }}`. Metrics and traces are passed through without a partition key.
+#[derive(Debug)]
+pub struct PartitionKey {
+    program: Program,
+    runtime: Runtime,
+}
+
+impl Clone for PartitionKey {
+    fn clone(&self) -> Self {
+        Self {
+            program: self.program.clone(),
+            runtime: Runtime::default(),
+        }
+    }
+}
+
+impl PartitionKey {
+    /// Compiles the expression.
+    pub fn build(
+        source: &str,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        let functions = vrl_stdlib::all()
+            .into_iter()
+            .chain(enrichment::vrl_functions().into_iter())
+            .chain(vector_vrl_functions::vrl_functions())
+            .collect::<Vec<_>>();
+
+        let state = vrl::state::TypeState::default();
+
+        let mut config = CompileConfig::default();
+        config.set_custom(enrichment_tables.clone());
+        config.set_read_only();
+
+        let CompilationResult {
+            program,
+            warnings,
+            config: _,
+        } = compile_vrl(source, &functions, &state, config)
+            .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+        if !warnings.is_empty() {
+            let warnings = Formatter::new(source, warnings).colored().to_string();
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        Ok(Self {
+            program,
+            runtime: Runtime::default(),
+        })
+    }
+
+    /// Stores the partition key in the metadata of the log events.
+    ///
+    /// Events for which the expression fails, or resolves to `null`, are left without a partition
+    /// key, so that rendering templates referring to it fails. Metrics and traces are left as is.
+    pub fn apply(&mut self, events: EventArray) -> EventArray {
+        match events {
+            EventArray::Logs(logs) => {
+                EventArray::Logs(logs.into_iter().map(|log| self.apply_log(log)).collect())
+            }
+            events => events,
+        }
+    }
+
+    fn apply_log(&mut self, log: LogEvent) -> LogEvent {
+        let mut target = VrlTarget::new(Event::Log(log), self.program.info(), false);
+        let result = self
+            .runtime
+            .resolve(&mut target, &self.program, &TimeZone::default());
+        self.runtime.clear();
+        let mut log = match target.into_events() {
+            TargetEvents::One(event) => event.into_log(),
+            _ => {
+                panic!("Event was modified by a partition key. This is an internal compiler error.")
+            }
+        };
+
+        match result {
+            Ok(Value::Null) => {}
+            Ok(value) => {
+                log.insert(metadata_path!(PARTITION_KEY_FIELD), value);
+            }
+            Err(error) => emit!(PartitionKeyEvaluationError {
+                error: error.to_string().as_ref()
+            }),
+        }
+        log
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::Template;
+
+    #[test]
+    fn stores_partition_key() {
+        let mut partition_key =
+            PartitionKey::build("to_string(mod(int!(.tenant_id), 4))", &Default::default())
+                .unwrap();
+        let template = Template::try_from("tenants/{{ %partition_key }}/").unwrap();
+
+        let mut tenant = LogEvent::from("hello");
+        tenant.insert("tenant_id", 10);
+        let logs = vec![tenant, LogEvent::from("no tenant")];
+
+        let logs = match partition_key.apply(EventArray::Logs(logs)) {
+            EventArray::Logs(logs) => logs,
+            _ => unreachable!(),
+        };
+        assert_eq!(template.render_string(&logs[0]).unwrap(), "tenants/2/");
+        assert!(template.render_string(&logs[1]).is_err());
+    }
+}
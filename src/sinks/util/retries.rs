@@ -11,7 +11,7 @@ use futures::FutureExt;
 use tokio::time::{sleep, Sleep};
 use tower::{retry::Policy, timeout::error::Elapsed};
 
-use crate::Error;
+use crate::{sinks::util::service::circuit_breaker::CircuitBreakerOpen, Error};
 
 pub enum RetryAction {
     /// Indicate that this request should be retried with a reason
@@ -123,6 +123,14 @@ where
                 RetryAction::Successful => None,
             },
             Err(error) => {
+                if error.downcast_ref::<CircuitBreakerOpen>().is_some() {
+                    debug!(
+                        message = "Circuit breaker is open; dropping the request.",
+                        internal_log_rate_limit = true
+                    );
+                    return None;
+                }
+
                 if self.remaining_attempts == 0 {
                     error!(message = "Retries exhausted; dropping the request.", %error, internal_log_rate_limit = true);
                     return None;
@@ -0,0 +1,288 @@
+use std::collections::BTreeMap;
+
+use indexmap::IndexMap;
+use lookup::{lookup_v2::parse_value_path, OwnedValuePath, PathPrefix};
+use vector_config::configurable_component;
+
+use crate::{
+    config::log_schema,
+    event::{EventArray, LogEvent, Value},
+};
+
+/// The fields of the OpenTelemetry log data model, which stay at the root of the events.
+const OPENTELEMETRY_FIELDS: [&str; 11] = [
+    "attributes",
+    "body",
+    "dropped_attributes_count",
+    "flags",
+    "observed_timestamp",
+    "resources",
+    "severity_number",
+    "severity_text",
+    "span_id",
+    "timestamp",
+    "trace_id",
+];
+
+/// The field of the OpenTelemetry log data model holding the attributes of the events.
+const OPENTELEMETRY_ATTRIBUTES: &str = "attributes";
+
+/// Schema mapping configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct SchemaMappingConfig {
+    /// The schema the fields of the events are mapped to.
+    ///
+    /// If not set, only the fields in `fields` are renamed.
+    pub target: Option<SchemaMappingTarget>,
+
+    /// Fields to rename, in addition to the ones renamed for the target schema.
+    ///
+    /// Renaming a field that the target schema also renames overrides the mapping of the target
+    /// schema.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The path the field at this path is moved to."
+    ))]
+    #[configurable(metadata(docs::examples = "fields_examples()"))]
+    pub fields: IndexMap<String, String>,
+}
+
+fn fields_examples() -> IndexMap<String, String> {
+    IndexMap::<_, _>::from_iter(
+        [
+            ("user_id".to_owned(), "user.id".to_owned()),
+            ("request.path".to_owned(), "url.path".to_owned()),
+        ]
+        .into_iter(),
+    )
+}
+
+/// A schema the fields of events can be mapped to.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaMappingTarget {
+    /// The [Elastic Common Schema][ecs] (ECS).
+    ///
+    /// [ecs]: https://www.elastic.co/guide/en/ecs/current/index.html
+    Ecs,
+
+    /// The [OpenTelemetry log data model][otel].
+    ///
+    /// The fields outside of the data model are moved to `attributes`.
+    ///
+    /// [otel]: https://opentelemetry.io/docs/specs/otel/logs/data-model/
+    Opentelemetry,
+}
+
+impl SchemaMappingTarget {
+    /// The fields renamed for this schema, from their path to the path they are moved to.
+    fn renames(self) -> Vec<(String, &'static str)> {
+        let log_schema = log_schema();
+        let (message, timestamp, host) = (
+            log_schema.message_key().to_owned(),
+            log_schema.timestamp_key().to_owned(),
+            log_schema.host_key().to_owned(),
+        );
+        match self {
+            Self::Ecs => vec![
+                (timestamp, "\"@timestamp\""),
+                (host, "host.name"),
+                ("hostname".to_owned(), "host.name"),
+                ("resources.\"host.name\"".to_owned(), "host.name"),
+                ("service".to_owned(), "service.name"),
+                ("resources.\"service.name\"".to_owned(), "service.name"),
+                ("level".to_owned(), "log.level"),
+                ("severity".to_owned(), "log.level"),
+                ("severity_text".to_owned(), "log.level"),
+                ("file".to_owned(), "log.file.path"),
+                ("facility".to_owned(), "log.syslog.facility.name"),
+                ("appname".to_owned(), "process.name"),
+                ("pid".to_owned(), "process.pid"),
+                ("procid".to_owned(), "process.pid"),
+                ("trace_id".to_owned(), "trace.id"),
+                ("span_id".to_owned(), "span.id"),
+                ("attributes".to_owned(), "labels"),
+                (message, "message"),
+            ],
+            Self::Opentelemetry => vec![
+                (message, "body"),
+                (timestamp, "timestamp"),
+                (host, "resources.\"host.name\""),
+                ("hostname".to_owned(), "resources.\"host.name\""),
+                ("service".to_owned(), "resources.\"service.name\""),
+                ("level".to_owned(), "severity_text"),
+                ("severity".to_owned(), "severity_text"),
+            ],
+        }
+    }
+}
+
+/// The mapping of the fields of the log events of a sink to a target schema.
+///
+/// The mapping is applied to the log events before they reach the sink, so that a pipeline can
+/// feed backends expecting different field names.
+#[derive(Clone, Debug)]
+pub struct SchemaMapping {
+    renames: Vec<(OwnedValuePath, OwnedValuePath)>,
+    attributes: Option<OwnedValuePath>,
+}
+
+impl SchemaMapping {
+    /// Builds the mapping, parsing the paths of the renamed fields.
+    pub fn build(config: &SchemaMappingConfig) -> crate::Result<Self> {
+        let target_renames = config
+            .target
+            .map(SchemaMappingTarget::renames)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(from, to)| from != to && !config.fields.contains_key(from))
+            .map(|(from, to)| (from, to.to_owned()));
+        let renames = target_renames
+            .chain(config.fields.clone())
+            .map(|(from, to)| Ok((parse_path(&from)?, parse_path(&to)?)))
+            .collect::<crate::Result<_>>()?;
+        let attributes = (config.target == Some(SchemaMappingTarget::Opentelemetry))
+            .then(|| parse_path(OPENTELEMETRY_ATTRIBUTES))
+            .transpose()?;
+
+        Ok(Self {
+            renames,
+            attributes,
+        })
+    }
+
+    /// Maps the fields of the log events.
+    pub fn apply(&self, events: EventArray) -> EventArray {
+        match events {
+            EventArray::Logs(mut logs) => {
+                logs.iter_mut().for_each(|log| self.apply_log(log));
+                EventArray::Logs(logs)
+            }
+            events => events,
+        }
+    }
+
+    fn apply_log(&self, log: &mut LogEvent) {
+        let values = self
+            .renames
+            .iter()
+            .filter_map(|(from, to)| {
+                log.remove_prune((PathPrefix::Event, from), true)
+                    .map(|value| (to, value))
+            })
+            .collect::<Vec<_>>();
+        for (to, value) in values {
+            log.insert((PathPrefix::Event, to), value);
+        }
+
+        if let Some(attributes) = &self.attributes {
+            let Some(map) = log.as_map_mut() else {
+                return;
+            };
+            let fields = map
+                .keys()
+                .filter(|key| !OPENTELEMETRY_FIELDS.contains(&key.as_str()))
+                .cloned()
+                .collect::<Vec<_>>();
+            let moved = fields
+                .into_iter()
+                .filter_map(|key| map.remove(&key).map(|value| (key, value)))
+                .collect::<BTreeMap<_, _>>();
+            if moved.is_empty() {
+                return;
+            }
+            match log.get_mut((PathPrefix::Event, attributes)) {
+                Some(Value::Object(existing)) => existing.extend(moved),
+                _ => {
+                    log.insert((PathPrefix::Event, attributes), Value::Object(moved));
+                }
+            }
+        }
+    }
+}
+
+fn parse_path(path: &str) -> crate::Result<OwnedValuePath> {
+    parse_value_path(path).map_err(|error| format!("Invalid field `{}`: {}", path, error).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(target: Option<SchemaMappingTarget>, fields: &[(&str, &str)]) -> SchemaMapping {
+        SchemaMapping::build(&SchemaMappingConfig {
+            target,
+            fields: fields
+                .iter()
+                .map(|(from, to)| (from.to_string(), to.to_string()))
+                .collect(),
+        })
+        .unwrap()
+    }
+
+    fn map_log(mapping: &SchemaMapping, log: LogEvent) -> LogEvent {
+        match mapping.apply(EventArray::Logs(vec![log])) {
+            EventArray::Logs(mut logs) => logs.remove(0),
+            _ => unreachable!(),
+        }
+    }
+
+    fn log() -> LogEvent {
+        let mut log = LogEvent::from("hello");
+        log.insert("host", "web-1");
+        log.insert("level", "info");
+        log.insert("trace_id", "abc");
+        log.insert("user_id", 42);
+        log
+    }
+
+    #[test]
+    fn maps_to_ecs() {
+        let log = map_log(
+            &mapping(Some(SchemaMappingTarget::Ecs), &[("user_id", "user.id")]),
+            log(),
+        );
+
+        assert_eq!(log["message"], "hello".into());
+        assert_eq!(log["host.name"], "web-1".into());
+        assert_eq!(log["log.level"], "info".into());
+        assert_eq!(log["trace.id"], "abc".into());
+        assert_eq!(log["user.id"], 42.into());
+        assert!(log.get("host").is_none());
+        assert!(log.get("user_id").is_none());
+    }
+
+    #[test]
+    fn maps_to_opentelemetry() {
+        let log = map_log(
+            &mapping(
+                Some(SchemaMappingTarget::Opentelemetry),
+                &[("level", "attributes.level")],
+            ),
+            log(),
+        );
+
+        assert_eq!(log["body"], "hello".into());
+        assert_eq!(log["resources.\"host.name\""], "web-1".into());
+        assert_eq!(log["trace_id"], "abc".into());
+        assert_eq!(log["attributes.user_id"], 42.into());
+        // Overridden by the renamed fields.
+        assert_eq!(log["attributes.level"], "info".into());
+        assert!(log.get("severity_text").is_none());
+        assert!(log.get("message").is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_paths() {
+        let config = SchemaMappingConfig {
+            target: None,
+            fields: [("user_id".to_owned(), ".[".to_owned())]
+                .into_iter()
+                .collect(),
+        };
+
+        assert!(SchemaMapping::build(&config).is_err());
+    }
+}
@@ -15,11 +15,14 @@ use tower::{
 use vector_config::configurable_component;
 
 pub use crate::sinks::util::service::{
+    circuit_breaker::{CircuitBreaker, CircuitBreakerLayer, CircuitBreakerService},
     concurrency::{concurrency_is_none, Concurrency},
     health::{HealthConfig, HealthLogic, HealthService},
     map::Map,
+    rate_limit_group::{GroupRateLimit, GroupRateLimitLayer, SharedRateLimit},
 };
 use crate::{
+    config::SinkContext,
     internal_events::OpenGauge,
     sinks::util::{
         adaptive_concurrency::{
@@ -32,17 +35,31 @@ use crate::{
     },
 };
 
+pub mod circuit_breaker;
 mod concurrency;
 mod health;
 mod map;
-
-pub type Svc<S, L> = RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>;
+pub mod rate_limit_group;
+
+pub type Svc<S, L> = GroupRateLimit<
+    RateLimit<
+        AdaptiveConcurrencyLimit<
+            Retry<FixedRetryPolicy<L>, CircuitBreakerService<Timeout<S>, L>>,
+            L,
+        >,
+    >,
+>;
 pub type TowerBatchedSink<S, B, RL> = BatchSink<Svc<S, RL>, B>;
 pub type TowerPartitionSink<S, B, RL, K> = PartitionBatchSink<Svc<S, RL>, B, K>;
 
 // Distributed service types
-pub type DistributedService<S, RL, HL, K, Req> = RateLimit<
-    Retry<FixedRetryPolicy<RL>, Buffer<Balance<DiscoveryService<S, RL, HL, K>, Req>, Req>>,
+pub type DistributedService<S, RL, HL, K, Req> = GroupRateLimit<
+    RateLimit<
+        Retry<
+            FixedRetryPolicy<RL>,
+            CircuitBreakerService<Buffer<Balance<DiscoveryService<S, RL, HL, K>, Req>, Req>, RL>,
+        >,
+    >,
 >;
 pub type DiscoveryService<S, RL, HL, K> =
     BoxStream<'static, Result<Change<K, SingleDistributedService<S, RL, HL>>, crate::Error>>;
@@ -133,7 +150,10 @@ pub struct TowerRequestConfig {
     pub retry_initial_backoff_secs: Option<u64>,
 
     #[configurable(derived)]
-    #[serde(default)]
+    #[serde(
+        default,
+        deserialize_with = "AdaptiveConcurrencySettings::deserialize_checked"
+    )]
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
 }
 
@@ -259,6 +279,8 @@ impl TowerRequestConfig {
                     .unwrap(),
             ),
             adaptive_concurrency: self.adaptive_concurrency,
+            rate_limit_group: None,
+            circuit_breaker: None,
         }
     }
 }
@@ -273,9 +295,19 @@ pub struct TowerRequestSettings {
     pub retry_max_duration_secs: Duration,
     pub retry_initial_backoff_secs: Duration,
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
+    pub rate_limit_group: Option<SharedRateLimit>,
+    pub circuit_breaker: Option<CircuitBreaker>,
 }
 
 impl TowerRequestSettings {
+    /// Applies the rate limit group and the circuit breaker of the sink being built, if it has
+    /// them.
+    pub fn with_context(mut self, cx: &SinkContext) -> Self {
+        self.rate_limit_group = cx.rate_limit_group();
+        self.circuit_breaker = cx.circuit_breaker();
+        self
+    }
+
     pub const fn retry_policy<L: RetryLogic>(&self, logic: L) -> FixedRetryPolicy<L> {
         FixedRetryPolicy::new(
             self.retry_attempts,
@@ -385,8 +417,10 @@ impl TowerRequestSettings {
 
         // Build sink service
         ServiceBuilder::new()
+            .layer(GroupRateLimitLayer::new(self.rate_limit_group))
             .rate_limit(self.rate_limit_num, self.rate_limit_duration)
             .retry(policy)
+            .layer(CircuitBreakerLayer::new(self.circuit_breaker, retry_logic))
             .layer(BufferLayer::new(max_concurrency))
             .service(Balance::new(Box::pin(stream::iter(services)) as Pin<Box<_>>))
     }
@@ -413,6 +447,9 @@ where
     fn layer(&self, inner: S) -> Self::Service {
         let policy = self.settings.retry_policy(self.retry_logic.clone());
         ServiceBuilder::new()
+            .layer(GroupRateLimitLayer::new(
+                self.settings.rate_limit_group.clone(),
+            ))
             .rate_limit(
                 self.settings.rate_limit_num,
                 self.settings.rate_limit_duration,
@@ -423,6 +460,10 @@ where
                 self.retry_logic.clone(),
             ))
             .retry(policy)
+            .layer(CircuitBreakerLayer::new(
+                self.settings.circuit_breaker.clone(),
+                self.retry_logic.clone(),
+            ))
             .timeout(self.settings.timeout)
             .service(inner)
     }
@@ -473,6 +514,23 @@ mod tests {
             .expect_err("Invalid concurrency setting didn't fail on negative number");
     }
 
+    #[test]
+    fn adaptive_concurrency_bounds_are_checked() {
+        let cfg = toml::from_str::<TowerRequestConfig>(
+            "adaptive_concurrency.min_concurrency = 10\nadaptive_concurrency.max_concurrency = 10",
+        )
+        .expect("Equal concurrency bounds failed");
+        assert_eq!(cfg.adaptive_concurrency.bounds(), (10, 10));
+
+        let error = toml::from_str::<TowerRequestConfig>(
+            "adaptive_concurrency.min_concurrency = 20\nadaptive_concurrency.max_concurrency = 10",
+        )
+        .expect_err("Inverted concurrency bounds didn't fail");
+        assert!(error
+            .to_string()
+            .contains("`min_concurrency` (20) must not be greater than `max_concurrency` (10)"));
+    }
+
     #[test]
     fn config_merging_defaults_concurrency_to_none_if_unset() {
         let cfg = TowerRequestConfig::default().unwrap_with(&TowerRequestConfig::default());
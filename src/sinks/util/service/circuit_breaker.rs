@@ -0,0 +1,422 @@
+//! Circuit breakers for sinks.
+//!
+//! A sink's circuit breaker opens after a number of consecutive failed requests, at which point
+//! requests are failed without being sent, and therefore without being retried, instead of
+//! exhausting the retry budget against a downstream service that is down. While the circuit is open,
+//! the topology also diverts the events sent to the sink to its configured fallback, so that they
+//! don't pile up in the sink's buffer.
+//!
+//! Once the reset timeout has elapsed, the circuit is half-open, and a single request is sent to
+//! probe the downstream service. The circuit is closed again if that request succeeds, and reopened
+//! otherwise.
+//!
+//! Like rate limit groups, the circuit breaker of the sink being built is passed to it through its
+//! [`SinkContext`][crate::config::SinkContext], and applied by the service layer built from
+//! [`TowerRequestSettings`][super::TowerRequestSettings].
+
+use std::{
+    error, fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::ready;
+use pin_project::pin_project;
+use tokio::time::Instant;
+use tower::{Layer, Service};
+use vector_config::configurable_component;
+
+use crate::{
+    config::ComponentKey,
+    internal_events::{CircuitBreakerState, CircuitBreakerStateChanged},
+    sinks::util::retries::{RetryAction, RetryLogic},
+};
+
+/// Circuit breaker configuration.
+///
+/// Sinks that don't send requests to a downstream service fail to build with a circuit breaker.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CircuitBreakerConfig {
+    /// The number of consecutive failed requests after which the circuit is opened.
+    ///
+    /// Requests that failed with an error that would not be retried, such as a request rejected as
+    /// invalid by the downstream service, are not counted as failures.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+
+    /// The time to wait, once the circuit is opened, before probing the downstream service with a
+    /// single request.
+    #[serde(default = "default_reset_timeout_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub reset_timeout_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub fallback: CircuitBreakerFallback,
+
+    /// The sink to send events to while the circuit is open, when `fallback` is set to
+    /// `dead_letter_queue`.
+    ///
+    /// Defaults to the global `dead_letter_queue` option.
+    #[configurable(metadata(docs::examples = "dead_letters"))]
+    pub dead_letter_queue: Option<ComponentKey>,
+}
+
+const fn default_failure_threshold() -> u32 {
+    5
+}
+
+const fn default_reset_timeout_secs() -> u64 {
+    30
+}
+
+impl CircuitBreakerConfig {
+    /// Returns the sink that events are sent to while the circuit is open, if any.
+    pub fn dead_letter_queue<'a>(
+        &'a self,
+        default: Option<&'a ComponentKey>,
+    ) -> Option<&'a ComponentKey> {
+        match self.fallback {
+            CircuitBreakerFallback::Drop => None,
+            CircuitBreakerFallback::DeadLetterQueue => self.dead_letter_queue.as_ref().or(default),
+        }
+    }
+}
+
+/// What to do with the events sent to the sink while the circuit is open.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitBreakerFallback {
+    /// Drop the events.
+    ///
+    /// The events are rejected, which is reported to sources that have end-to-end acknowledgements
+    /// enabled.
+    #[default]
+    Drop,
+
+    /// Send the events to the dead letter queue sink.
+    ///
+    /// The events are annotated with the reason they were dropped, in the same way as events sent
+    /// to the `dropped` output of other components.
+    DeadLetterQueue,
+}
+
+#[derive(Debug)]
+struct State {
+    kind: CircuitBreakerState,
+    consecutive_failures: u32,
+    opened_at: Instant,
+    probing: bool,
+}
+
+impl State {
+    fn transition(&mut self, kind: CircuitBreakerState) {
+        self.kind = kind;
+        emit!(CircuitBreakerStateChanged { state: kind });
+    }
+
+    fn open(&mut self) {
+        self.opened_at = Instant::now();
+        self.probing = false;
+        self.transition(CircuitBreakerState::Open);
+    }
+}
+
+/// The circuit breaker of a sink, shared by its services and the topology.
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    state: Arc<Mutex<State>>,
+    failure_threshold: u32,
+    reset_timeout: Duration,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: &CircuitBreakerConfig) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                kind: CircuitBreakerState::Closed,
+                consecutive_failures: 0,
+                opened_at: Instant::now(),
+                probing: false,
+            })),
+            failure_threshold: config.failure_threshold,
+            reset_timeout: Duration::from_secs(config.reset_timeout_secs),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("circuit breaker lock poisoned")
+    }
+
+    pub fn state(&self) -> CircuitBreakerState {
+        self.lock().kind
+    }
+
+    /// Emits the current state of the circuit breaker.
+    pub fn emit_state(&self) {
+        emit!(CircuitBreakerStateChanged {
+            state: self.state()
+        });
+    }
+
+    /// Whether events sent to the sink should currently be diverted to the fallback.
+    ///
+    /// This is the case while the circuit is open, up until a probe request can be sent, and while
+    /// that probe request is in flight.
+    pub fn is_open(&self) -> bool {
+        let state = self.lock();
+        match state.kind {
+            CircuitBreakerState::Closed => false,
+            CircuitBreakerState::Open => state.opened_at.elapsed() < self.reset_timeout,
+            CircuitBreakerState::HalfOpen => state.probing,
+        }
+    }
+
+    /// Returns whether a request may be sent.
+    fn allow_request(&self) -> bool {
+        let mut state = self.lock();
+        match state.kind {
+            CircuitBreakerState::Closed => true,
+            CircuitBreakerState::Open if state.opened_at.elapsed() < self.reset_timeout => false,
+            CircuitBreakerState::Open => {
+                state.probing = true;
+                state.transition(CircuitBreakerState::HalfOpen);
+                true
+            }
+            CircuitBreakerState::HalfOpen => !std::mem::replace(&mut state.probing, true),
+        }
+    }
+
+    /// Records the outcome of a request that was sent.
+    fn record(&self, success: bool) {
+        let mut state = self.lock();
+        match (state.kind, success) {
+            (CircuitBreakerState::Closed, true) => state.consecutive_failures = 0,
+            (CircuitBreakerState::Closed, false) => {
+                state.consecutive_failures += 1;
+                if state.consecutive_failures >= self.failure_threshold {
+                    state.open();
+                }
+            }
+            (CircuitBreakerState::HalfOpen, true) => {
+                state.consecutive_failures = 0;
+                state.probing = false;
+                state.transition(CircuitBreakerState::Closed);
+            }
+            (CircuitBreakerState::HalfOpen, false) => state.open(),
+            // Requests sent before the circuit was opened.
+            (CircuitBreakerState::Open, _) => {}
+        }
+    }
+}
+
+impl fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("state", &self.state())
+            .finish()
+    }
+}
+
+/// The error returned for requests that aren't sent because the circuit is open.
+#[derive(Debug)]
+pub struct CircuitBreakerOpen;
+
+impl fmt::Display for CircuitBreakerOpen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Circuit breaker is open.")
+    }
+}
+
+impl error::Error for CircuitBreakerOpen {}
+
+/// Fails requests while the circuit breaker is open, and records the outcome of the requests that
+/// are sent.
+///
+/// The retry logic of the sink is used to tell failed requests from successful ones.
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerLayer<L> {
+    breaker: Option<CircuitBreaker>,
+    logic: L,
+}
+
+impl<L> CircuitBreakerLayer<L> {
+    pub const fn new(breaker: Option<CircuitBreaker>, logic: L) -> Self {
+        Self { breaker, logic }
+    }
+}
+
+impl<S, L: Clone> Layer<S> for CircuitBreakerLayer<L> {
+    type Service = CircuitBreakerService<S, L>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CircuitBreakerService {
+            inner,
+            breaker: self.breaker.clone(),
+            logic: self.logic.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerService<S, L> {
+    inner: S,
+    breaker: Option<CircuitBreaker>,
+    logic: L,
+}
+
+impl<S, L, Request> Service<Request> for CircuitBreakerService<S, L>
+where
+    S: Service<Request>,
+    S::Error: Into<crate::Error>,
+    L: RetryLogic<Response = S::Response>,
+{
+    type Response = S::Response;
+    type Error = crate::Error;
+    type Future = ResponseFuture<S::Future, L>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        match &self.breaker {
+            Some(breaker) if !breaker.allow_request() => ResponseFuture::Rejected,
+            breaker => ResponseFuture::Sent {
+                future: self.inner.call(request),
+                breaker: breaker.clone(),
+                logic: self.logic.clone(),
+            },
+        }
+    }
+}
+
+#[pin_project(project = ResponseFutureProj)]
+pub enum ResponseFuture<F, L> {
+    Sent {
+        #[pin]
+        future: F,
+        breaker: Option<CircuitBreaker>,
+        logic: L,
+    },
+    Rejected,
+}
+
+impl<F, L, Response, E> Future for ResponseFuture<F, L>
+where
+    F: Future<Output = Result<Response, E>>,
+    E: Into<crate::Error>,
+    L: RetryLogic<Response = Response>,
+{
+    type Output = Result<Response, crate::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            ResponseFutureProj::Sent {
+                future,
+                breaker,
+                logic,
+            } => {
+                let result = ready!(future.poll(cx)).map_err(Into::into);
+                if let Some(breaker) = breaker {
+                    breaker.record(is_success(logic, &result));
+                }
+                Poll::Ready(result)
+            }
+            ResponseFutureProj::Rejected => Poll::Ready(Err(Box::new(CircuitBreakerOpen))),
+        }
+    }
+}
+
+fn is_success<L: RetryLogic>(logic: &L, result: &Result<L::Response, crate::Error>) -> bool {
+    match result {
+        Ok(response) => !matches!(logic.should_retry_response(response), RetryAction::Retry(_)),
+        Err(error) => error
+            .downcast_ref::<L::Error>()
+            .map_or(false, |error| !logic.is_retriable_error(error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(&CircuitBreakerConfig {
+            failure_threshold: 2,
+            reset_timeout_secs: 10,
+            fallback: CircuitBreakerFallback::Drop,
+            dead_letter_queue: None,
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn opens_after_consecutive_failures() {
+        let breaker = breaker();
+
+        breaker.record(false);
+        breaker.record(true);
+        breaker.record(false);
+        assert_eq!(breaker.state(), CircuitBreakerState::Closed);
+        assert!(breaker.allow_request());
+
+        breaker.record(false);
+        assert_eq!(breaker.state(), CircuitBreakerState::Open);
+        assert!(breaker.is_open());
+        assert!(!breaker.allow_request());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn probes_after_reset_timeout() {
+        let breaker = breaker();
+        breaker.record(false);
+        breaker.record(false);
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert!(!breaker.is_open());
+
+        // Only a single probe is let through.
+        assert!(breaker.allow_request());
+        assert_eq!(breaker.state(), CircuitBreakerState::HalfOpen);
+        assert!(breaker.is_open());
+        assert!(!breaker.allow_request());
+
+        // A failed probe opens the circuit again.
+        breaker.record(false);
+        assert_eq!(breaker.state(), CircuitBreakerState::Open);
+        assert!(!breaker.allow_request());
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert!(breaker.allow_request());
+        breaker.record(true);
+        assert_eq!(breaker.state(), CircuitBreakerState::Closed);
+        assert!(!breaker.is_open());
+    }
+
+    #[test]
+    fn resolves_dead_letter_queue() {
+        let default = ComponentKey::from("default");
+        let mut config = CircuitBreakerConfig {
+            failure_threshold: 5,
+            reset_timeout_secs: 30,
+            fallback: CircuitBreakerFallback::Drop,
+            dead_letter_queue: Some(ComponentKey::from("own")),
+        };
+        assert_eq!(config.dead_letter_queue(Some(&default)), None);
+
+        config.fallback = CircuitBreakerFallback::DeadLetterQueue;
+        assert_eq!(
+            config.dead_letter_queue(Some(&default)),
+            Some(&ComponentKey::from("own"))
+        );
+
+        config.dead_letter_queue = None;
+        assert_eq!(config.dead_letter_queue(Some(&default)), Some(&default));
+    }
+}
@@ -0,0 +1,287 @@
+//! Rate limits shared by groups of sinks.
+//!
+//! Sinks joining a rate limit group, through their `rate_limit_group` option, share a single budget
+//! of requests and bytes with the other sinks of the group. Requests are limited by the service
+//! layer built from [`TowerRequestSettings`][super::TowerRequestSettings], which gets the group of
+//! the sink being built from its [`SinkContext`][crate::config::SinkContext], and bytes are limited
+//! by the topology, before events reach the sink.
+//!
+//! The limits of the groups are held by the running topology, and kept across config reloads, so
+//! that rebuilt sinks keep sharing the budget of the sinks of their group that weren't rebuilt.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::ready;
+use tokio::time::{sleep, Instant, Sleep};
+use tower::{Layer, Service};
+use vector_core::config::RateLimitGroup;
+
+/// The shared limits of the rate limit groups of a topology, by group name.
+#[derive(Clone, Debug, Default)]
+pub struct RateLimitGroups {
+    groups: HashMap<String, (RateLimitGroup, SharedRateLimit)>,
+}
+
+impl RateLimitGroups {
+    /// Returns the shared limits of the group `name`, creating them if the group is new or has
+    /// changed.
+    pub fn get_or_create(&mut self, name: &str, group: &RateLimitGroup) -> SharedRateLimit {
+        match self.groups.get(name) {
+            Some((existing, limit)) if existing == group => limit.clone(),
+            _ => {
+                let limit = SharedRateLimit::new(name, group);
+                self.groups
+                    .insert(name.to_owned(), (group.clone(), limit.clone()));
+                limit
+            }
+        }
+    }
+}
+
+/// A token bucket, refilled continuously over the configured time window.
+///
+/// A single acquisition larger than the whole bucket is allowed once the bucket is full, putting it
+/// into debt, so that large batches can't be stalled forever.
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: u64, duration: Duration) -> Self {
+        let capacity = limit as f64;
+        Self {
+            capacity,
+            refill_per_sec: capacity / duration.as_secs_f64(),
+            tokens: capacity,
+            refilled_at: Instant::now(),
+        }
+    }
+
+    /// Takes `amount` tokens from the bucket, or returns how long to wait before enough tokens are
+    /// available.
+    fn try_acquire(&mut self, amount: u64) -> Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.refilled_at = now;
+
+        let needed = (amount as f64).min(self.capacity);
+        if self.tokens >= needed {
+            self.tokens -= amount as f64;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (needed - self.tokens) / self.refill_per_sec,
+            ))
+        }
+    }
+}
+
+/// The request and byte limits of a rate limit group, shared by all of its sinks.
+#[derive(Clone)]
+pub struct SharedRateLimit {
+    name: Arc<str>,
+    requests: Option<Arc<Mutex<TokenBucket>>>,
+    bytes: Option<Arc<Mutex<TokenBucket>>>,
+}
+
+impl SharedRateLimit {
+    fn new(name: &str, group: &RateLimitGroup) -> Self {
+        let duration = Duration::from_secs(group.duration_secs);
+        let bucket = |limit| Arc::new(Mutex::new(TokenBucket::new(limit, duration)));
+        Self {
+            name: name.into(),
+            requests: group.request_limit.map(bucket),
+            bytes: group.byte_limit.map(bucket),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub const fn limits_requests(&self) -> bool {
+        self.requests.is_some()
+    }
+
+    pub const fn limits_bytes(&self) -> bool {
+        self.bytes.is_some()
+    }
+
+    fn try_acquire_request(&self) -> Result<(), Duration> {
+        acquire(&self.requests, 1)
+    }
+
+    /// Waits until `bytes` bytes can be sent within the byte limit of the group.
+    pub async fn acquire_bytes(&self, bytes: usize) {
+        while let Err(wait) = acquire(&self.bytes, bytes as u64) {
+            sleep(wait).await;
+        }
+    }
+}
+
+fn acquire(bucket: &Option<Arc<Mutex<TokenBucket>>>, amount: u64) -> Result<(), Duration> {
+    bucket.as_ref().map_or(Ok(()), |bucket| {
+        bucket
+            .lock()
+            .expect("rate limit bucket lock poisoned")
+            .try_acquire(amount)
+    })
+}
+
+impl fmt::Debug for SharedRateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedRateLimit")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+/// Limits the requests sent by a service to the request limit of its rate limit group.
+#[derive(Clone, Debug)]
+pub struct GroupRateLimitLayer {
+    limit: Option<SharedRateLimit>,
+}
+
+impl GroupRateLimitLayer {
+    pub const fn new(limit: Option<SharedRateLimit>) -> Self {
+        Self { limit }
+    }
+}
+
+impl<S> Layer<S> for GroupRateLimitLayer {
+    type Service = GroupRateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GroupRateLimit {
+            inner,
+            limit: self.limit.clone(),
+            reserved: false,
+            sleep: None,
+        }
+    }
+}
+
+pub struct GroupRateLimit<S> {
+    inner: S,
+    limit: Option<SharedRateLimit>,
+    reserved: bool,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<S, Request> Service<Request> for GroupRateLimit<S>
+where
+    S: Service<Request>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if let Some(limit) = &self.limit {
+            while !self.reserved {
+                if let Some(sleep) = self.sleep.as_mut() {
+                    ready!(sleep.as_mut().poll(cx));
+                    self.sleep = None;
+                }
+
+                match limit.try_acquire_request() {
+                    Ok(()) => self.reserved = true,
+                    Err(wait) => self.sleep = Some(Box::pin(sleep(wait))),
+                }
+            }
+        }
+
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        self.reserved = false;
+        self.inner.call(request)
+    }
+}
+
+impl<S: Clone> Clone for GroupRateLimit<S> {
+    fn clone(&self) -> Self {
+        // A reservation is only valid for the service that made it.
+        Self {
+            inner: self.inner.clone(),
+            limit: self.limit.clone(),
+            reserved: false,
+            sleep: None,
+        }
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for GroupRateLimit<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GroupRateLimit")
+            .field("inner", &self.inner)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(request_limit: Option<u64>, byte_limit: Option<u64>) -> RateLimitGroup {
+        RateLimitGroup {
+            duration_secs: 1,
+            request_limit,
+            byte_limit,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn bucket_refills_over_duration() {
+        let mut bucket = TokenBucket::new(2, Duration::from_secs(1));
+        assert_eq!(bucket.try_acquire(1), Ok(()));
+        assert_eq!(bucket.try_acquire(1), Ok(()));
+        assert_eq!(bucket.try_acquire(1), Err(Duration::from_millis(500)));
+
+        tokio::time::advance(Duration::from_millis(500)).await;
+        assert_eq!(bucket.try_acquire(1), Ok(()));
+
+        // Larger than the bucket, allowed once it's full.
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert_eq!(bucket.try_acquire(5), Ok(()));
+        assert!(bucket.try_acquire(1).is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn sinks_share_group_limits() {
+        let mut groups = RateLimitGroups::default();
+        let first = groups.get_or_create("shared", &group(Some(1), None));
+        let second = groups.get_or_create("shared", &group(Some(1), None));
+        assert_eq!(first.try_acquire_request(), Ok(()));
+        assert!(second.try_acquire_request().is_err());
+
+        // The limits are shared by copies of the groups, such as the ones passed to a reload.
+        let third = groups
+            .clone()
+            .get_or_create("shared", &group(Some(1), None));
+        assert!(third.try_acquire_request().is_err());
+
+        // Changing the group resets its limits.
+        let changed = groups.get_or_create("shared", &group(Some(2), None));
+        assert_eq!(changed.try_acquire_request(), Ok(()));
+
+        // Other topologies don't share the limits.
+        let other = RateLimitGroups::default().get_or_create("shared", &group(Some(1), None));
+        assert_eq!(other.try_acquire_request(), Ok(()));
+    }
+}
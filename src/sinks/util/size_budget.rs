@@ -0,0 +1,299 @@
+//! Encoded size budgeting of the events of a sink.
+//!
+//! Destinations such as Amazon SQS or GCP Pub/Sub reject messages over a hard size limit, which
+//! fails the whole request the message is part of. With a size budget, each event is encoded
+//! before it reaches the sink, and the events whose encoding exceeds the budget are split,
+//! truncated, or dropped, according to the configured handling.
+
+use std::{io, num::NonZeroUsize};
+
+use bytes::Bytes;
+use lookup::{lookup_v2::parse_value_path, OwnedValuePath, PathPrefix};
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::{Encoder, Transformer},
+    config::log_schema,
+    event::{Event, EventStatus, Finalizable, LogEvent, Value},
+    internal_events::{OversizedEventDropped, OversizedEventSplit, OversizedEventTruncated},
+    sinks::util::encoding::Encoder as _,
+};
+
+/// The maximum number of times the field of an event is truncated before the event is dropped.
+///
+/// As escaping can make the encoding of a field longer than the field itself, truncating a field by
+/// the excess size of the event doesn't always bring it under the budget.
+const MAX_TRUNCATIONS: usize = 4;
+
+/// Size budget configuration.
+///
+/// Each event is encoded before it reaches the sink, and the events whose encoding exceeds
+/// `max_bytes` are handled according to `oversized`, instead of failing the request they are part
+/// of.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SizeBudgetConfig {
+    /// The maximum size of an encoded event.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 262144))]
+    pub max_bytes: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub oversized: OversizedEventHandling,
+}
+
+/// How the events whose encoding exceeds the size budget are handled.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+#[configurable(metadata(
+    docs::enum_tag_description = "The strategy for handling the events over the size budget."
+))]
+pub enum OversizedEventHandling {
+    /// Drop the events.
+    ///
+    /// The events are rejected, which is reported to sources that have end-to-end acknowledgements
+    /// enabled.
+    #[default]
+    Drop,
+
+    /// Truncate a string field of the events until their encoding fits in the budget.
+    ///
+    /// Events for which the field is missing, isn't a string, or can't be truncated enough are
+    /// dropped.
+    Truncate {
+        /// The field to truncate.
+        ///
+        /// Defaults to the global `log_schema.message_key` option.
+        #[configurable(metadata(docs::examples = "message"))]
+        field: Option<String>,
+    },
+
+    /// Split an array field of the events, until the encoding of each of the resulting events fits
+    /// in the budget.
+    ///
+    /// Each resulting event is a copy of the event holding part of the items of the array. Events
+    /// for which the field is missing or isn't an array, and events still over the budget with a
+    /// single item left, are dropped.
+    Split {
+        /// The field to split.
+        #[configurable(metadata(docs::examples = "records"))]
+        field: String,
+    },
+}
+
+#[derive(Clone, Debug)]
+enum Handling {
+    Drop,
+    Truncate(OwnedValuePath),
+    Split(OwnedValuePath),
+}
+
+/// Enforces the size budget of the events of a sink, measured with the encoder of the sink.
+#[derive(Clone)]
+pub struct SizeBudget {
+    max_bytes: usize,
+    handling: Handling,
+    encoder: (Transformer, Encoder<()>),
+}
+
+impl SizeBudget {
+    /// Creates the size budget, measuring events with the given encoder.
+    pub fn new(
+        config: &SizeBudgetConfig,
+        encoder: (Transformer, Encoder<()>),
+    ) -> crate::Result<Self> {
+        let handling = match &config.oversized {
+            OversizedEventHandling::Drop => Handling::Drop,
+            OversizedEventHandling::Truncate { field } => Handling::Truncate(parse_field(
+                field
+                    .as_deref()
+                    .unwrap_or_else(|| log_schema().message_key()),
+            )?),
+            OversizedEventHandling::Split { field } => Handling::Split(parse_field(field)?),
+        };
+        Ok(Self {
+            max_bytes: config.max_bytes.get(),
+            handling,
+            encoder,
+        })
+    }
+
+    /// Returns the events to send to the sink for the given event.
+    ///
+    /// Events that fail to encode are returned as is, so that the sink reports the error.
+    pub fn apply(&self, event: Event) -> Vec<Event> {
+        let size = match self.encoded_size(event.clone()) {
+            Some(size) if size > self.max_bytes => size,
+            _ => return vec![event],
+        };
+
+        let mut events = Vec::new();
+        match (&self.handling, event) {
+            (Handling::Truncate(field), Event::Log(log)) => {
+                self.truncate(log, field, size, &mut events)
+            }
+            (Handling::Split(field), Event::Log(log)) => self.split(log, field, size, &mut events),
+            (_, event) => self.drop_event(event, size),
+        }
+        events
+    }
+
+    fn encoded_size(&self, event: Event) -> Option<usize> {
+        self.encoder.encode_input(event, &mut io::sink()).ok()
+    }
+
+    fn truncate(
+        &self,
+        mut log: LogEvent,
+        field: &OwnedValuePath,
+        size: usize,
+        events: &mut Vec<Event>,
+    ) {
+        let mut size = size;
+        for _ in 0..MAX_TRUNCATIONS {
+            let excess = size - self.max_bytes;
+            match log.get_mut((PathPrefix::Event, field)) {
+                Some(Value::Bytes(bytes)) if !bytes.is_empty() => {
+                    *bytes = truncated(bytes, excess);
+                }
+                _ => break,
+            }
+
+            match self.encoded_size(log.clone().into()) {
+                Some(truncated_size) if truncated_size > self.max_bytes => size = truncated_size,
+                _ => {
+                    emit!(OversizedEventTruncated);
+                    events.push(log.into());
+                    return;
+                }
+            }
+        }
+        self.drop_event(log.into(), size);
+    }
+
+    fn split(&self, log: LogEvent, field: &OwnedValuePath, size: usize, events: &mut Vec<Event>) {
+        let items = match log.get((PathPrefix::Event, field)) {
+            Some(Value::Array(items)) if items.len() > 1 => items.clone(),
+            _ => return self.drop_event(log.into(), size),
+        };
+
+        let (first, second) = items.split_at(items.len() / 2);
+        emit!(OversizedEventSplit);
+        for half in [first, second] {
+            let mut part = log.clone();
+            part.insert((PathPrefix::Event, field), Value::Array(half.to_vec()));
+            match self.encoded_size(part.clone().into()) {
+                Some(part_size) if part_size > self.max_bytes => {
+                    self.split(part, field, part_size, events)
+                }
+                _ => events.push(part.into()),
+            }
+        }
+    }
+
+    fn drop_event(&self, mut event: Event, size: usize) {
+        event.take_finalizers().update_status(EventStatus::Rejected);
+        emit!(OversizedEventDropped {
+            size,
+            max_bytes: self.max_bytes,
+        });
+    }
+}
+
+fn parse_field(field: &str) -> crate::Result<OwnedValuePath> {
+    parse_value_path(field).map_err(|error| format!("Invalid field `{}`: {}", field, error).into())
+}
+
+/// Removes at least `excess` bytes from the end of the string, without splitting a character.
+fn truncated(bytes: &Bytes, excess: usize) -> Bytes {
+    let mut len = bytes.len().saturating_sub(excess);
+    while len > 0 && (bytes[len] & 0xC0) == 0x80 {
+        len -= 1;
+    }
+    bytes.slice(..len)
+}
+
+#[cfg(test)]
+mod tests {
+    use codecs::{encoding::Serializer, JsonSerializerConfig};
+
+    use super::*;
+    use crate::event::{Metric, MetricKind, MetricValue};
+
+    fn size_budget(max_bytes: usize, oversized: OversizedEventHandling) -> SizeBudget {
+        let serializer = Serializer::from(JsonSerializerConfig::default().build());
+        SizeBudget::new(
+            &SizeBudgetConfig {
+                max_bytes: NonZeroUsize::new(max_bytes).unwrap(),
+                oversized,
+            },
+            (Transformer::default(), Encoder::<()>::new(serializer)),
+        )
+        .unwrap()
+    }
+
+    fn log(field: &str, value: impl Into<Value>) -> Event {
+        let mut log = LogEvent::default();
+        log.insert(field, value);
+        log.into()
+    }
+
+    #[test]
+    fn keeps_events_within_budget() {
+        let budget = size_budget(100, OversizedEventHandling::Drop);
+
+        let events = budget.apply(log("message", "hello"));
+
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn drops_oversized_events() {
+        let budget = size_budget(16, OversizedEventHandling::Drop);
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        );
+
+        assert!(budget.apply(log("message", "hello, world")).is_empty());
+        assert!(budget.apply(metric.into()).is_empty());
+    }
+
+    #[test]
+    fn truncates_oversized_events() {
+        let budget = size_budget(32, OversizedEventHandling::Truncate { field: None });
+        let events = budget.apply(log("message", "héllo, ".repeat(10)));
+
+        assert_eq!(events.len(), 1);
+        let message = events[0].as_log()["message"].to_string_lossy();
+        assert!(!message.is_empty() && "héllo, ".repeat(10).starts_with(message.as_ref()));
+        assert!(budget.encoded_size(events[0].clone()).unwrap() <= 32);
+    }
+
+    #[test]
+    fn splits_oversized_events() {
+        let budget = size_budget(
+            40,
+            OversizedEventHandling::Split {
+                field: "records".to_owned(),
+            },
+        );
+        let records = (1..=10).map(|n| Value::from(n * 1000)).collect::<Vec<_>>();
+
+        let events = budget.apply(log("records", records.clone()));
+
+        assert!(events.len() > 1);
+        let split_records = events
+            .iter()
+            .flat_map(|event| event.as_log()["records"].as_array().unwrap().to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(split_records, records);
+        for event in events {
+            assert!(budget.encoded_size(event).unwrap() <= 40);
+        }
+    }
+}
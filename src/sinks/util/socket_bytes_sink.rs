@@ -5,7 +5,8 @@ use std::{
     task::{ready, Context, Poll},
 };
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
+use codecs::encoding::CompressedEncoder;
 use futures::Sink;
 use pin_project::{pin_project, pinned_drop};
 use tokio::io::AsyncWrite;
@@ -30,6 +31,7 @@ pub enum ShutdownCheck {
 /// - Flush all data on each `poll_ready` if total number of events in queue more than some limit.
 /// - Count event size on each `start_send`.
 /// - Ack all sent events on successful `poll_flush` and `poll_close` or on `Drop`.
+/// - With compressed framing, compress the events sent between flushes into a single frame.
 #[pin_project(PinnedDrop)]
 pub struct BytesSink<T>
 where
@@ -38,6 +40,9 @@ where
     #[pin]
     inner: FramedWrite<T, BytesCodec>,
     shutdown_check: Box<dyn Fn(&mut T) -> ShutdownCheck + Send>,
+    compressor: Option<CompressedEncoder>,
+    /// The events to compress on the next flush.
+    pending: BytesMut,
     state: State,
 }
 
@@ -49,10 +54,13 @@ where
         inner: T,
         shutdown_check: impl Fn(&mut T) -> ShutdownCheck + Send + 'static,
         socket_mode: SocketMode,
+        compressor: Option<CompressedEncoder>,
     ) -> Self {
         Self {
             inner: FramedWrite::new(inner, BytesCodec::new()),
             shutdown_check: Box::new(shutdown_check),
+            compressor,
+            pending: BytesMut::new(),
             state: State {
                 events_total: 0,
                 event_bytes: 0,
@@ -62,6 +70,35 @@ where
             },
         }
     }
+
+    /// Sends the pending events compressed into a single frame.
+    fn poll_send_pending(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), IoError>> {
+        let mut pinned = self.project();
+        let compressor = match pinned.compressor {
+            Some(compressor) if !pinned.pending.is_empty() => compressor,
+            _ => return Poll::Ready(Ok(())),
+        };
+
+        if let Err(error) = ready!(<FramedWrite<T, BytesCodec> as Sink<Bytes>>::poll_ready(
+            pinned.inner.as_mut(),
+            cx
+        )) {
+            return Poll::Ready(Err(error));
+        }
+
+        let mut frame = pinned.pending.split();
+        let result = compressor
+            .compress(&mut frame)
+            .map_err(|error| IoError::new(ErrorKind::InvalidData, error))
+            .and_then(|()| {
+                pinned.state.bytes_total += frame.len();
+                pinned.inner.start_send(frame.freeze())
+            });
+        if result.is_err() {
+            pinned.state.ack(EventStatus::Errored);
+        }
+        Poll::Ready(result)
+    }
 }
 
 struct State {
@@ -130,6 +167,10 @@ where
         pinned.state.finalizers.push(item.finalizers);
         pinned.state.events_total += 1;
         pinned.state.event_bytes += item.byte_size;
+        if pinned.compressor.is_some() {
+            pinned.pending.extend_from_slice(&item.item);
+            return Ok(());
+        }
         pinned.state.bytes_total += item.item.len();
 
         let result = pinned.inner.start_send(item.item);
@@ -153,6 +194,10 @@ where
             ShutdownCheck::Alive => {}
         }
 
+        if let Err(error) = ready!(self.as_mut().poll_send_pending(cx)) {
+            return Poll::Ready(Err(error));
+        }
+
         let inner = self.as_mut().project().inner;
         let result = ready!(<FramedWrite<T, BytesCodec> as Sink<Bytes>>::poll_flush(
             inner, cx
@@ -165,6 +210,10 @@ where
     }
 
     fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if let Err(error) = ready!(self.as_mut().poll_send_pending(cx)) {
+            return Poll::Ready(Err(error));
+        }
+
         let inner = self.as_mut().project().inner;
         let result = ready!(<FramedWrite<T, BytesCodec> as Sink<Bytes>>::poll_close(
             inner, cx
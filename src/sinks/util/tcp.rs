@@ -1,19 +1,27 @@
 use std::{
     io::ErrorKind,
     net::SocketAddr,
+    num::{NonZeroU64, NonZeroUsize},
     pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
     task::{Context, Poll},
     time::Duration,
 };
 
 use async_trait::async_trait;
 use bytes::{Bytes, BytesMut};
-use futures::{stream::BoxStream, task::noop_waker_ref, SinkExt, StreamExt};
+use codecs::encoding::CompressedEncoder;
+use futures::{future::join_all, stream::BoxStream, task::noop_waker_ref, SinkExt, StreamExt};
 use futures_util::{future::ready, stream};
+use rand::Rng;
 use snafu::{ResultExt, Snafu};
 use tokio::{
     io::{AsyncRead, ReadBuf},
     net::TcpStream,
+    sync::mpsc,
     time::sleep,
 };
 use tokio_util::codec::Encoder;
@@ -79,6 +87,60 @@ pub struct TcpSinkConfig {
     send_buffer_bytes: Option<usize>,
 }
 
+/// Load balancing of events across several TCP endpoints.
+///
+/// A pool of connections is kept open to every endpoint, and each event is sent over one of the
+/// healthy connections of the pool, as picked by the `strategy`. Connections are considered
+/// unhealthy when connecting to their endpoint or sending to it fails, until they reconnect, and
+/// idle connections are checked for having been closed by the remote end every
+/// `health_check_interval_secs`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TcpLoadBalancingConfig {
+    /// Additional addresses to connect to.
+    ///
+    /// Events are balanced across `address` and these addresses, which accept the same formats as
+    /// `address`.
+    #[configurable(metadata(docs::examples = "92.12.333.225:5000"))]
+    #[configurable(metadata(docs::examples = "https://otherhost:5000"))]
+    pub addresses: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub strategy: LoadBalancingStrategy,
+
+    /// The number of connections opened to each address.
+    #[serde(default = "default_connections_per_address")]
+    pub connections_per_address: NonZeroUsize,
+
+    /// The interval between checks of idle connections for having been closed by the remote end.
+    #[serde(default = "default_health_check_interval_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub health_check_interval_secs: NonZeroU64,
+}
+
+const fn default_connections_per_address() -> NonZeroUsize {
+    unsafe { NonZeroUsize::new_unchecked(1) }
+}
+
+const fn default_health_check_interval_secs() -> NonZeroU64 {
+    unsafe { NonZeroU64::new_unchecked(10) }
+}
+
+/// The strategy used to pick the connection each event is sent over.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadBalancingStrategy {
+    /// Send events over each healthy connection in turn.
+    #[default]
+    RoundRobin,
+
+    /// Send events over the healthy connection with the fewest events waiting to be sent.
+    LeastConnections,
+}
+
 impl TcpSinkConfig {
     pub const fn new(
         address: String,
@@ -107,19 +169,75 @@ impl TcpSinkConfig {
         &self,
         transformer: Transformer,
         encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
+        compressor: Option<CompressedEncoder>,
     ) -> crate::Result<(VectorSink, Healthcheck)> {
-        let uri = self.address.parse::<http::Uri>()?;
-        let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
-        let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
-        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
-        let connector = TcpConnector::new(host, port, self.keepalive, tls, self.send_buffer_bytes);
-        let sink = TcpSink::new(connector.clone(), transformer, encoder);
+        let connector = self.connector(&self.address)?;
+        let sink = TcpSink::new(connector.clone(), transformer, encoder, compressor);
 
         Ok((
             VectorSink::from_event_streamsink(sink),
             Box::pin(async move { connector.healthcheck().await }),
         ))
     }
+
+    /// Builds a sink balancing events across `address` and the addresses of the load balancing
+    /// configuration.
+    ///
+    /// The healthcheck passes as long as any of the addresses can be connected to.
+    pub fn build_balanced(
+        &self,
+        load_balancing: &TcpLoadBalancingConfig,
+        transformer: Transformer,
+        encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
+        compressor: Option<CompressedEncoder>,
+    ) -> crate::Result<(VectorSink, Healthcheck)> {
+        let connectors = std::iter::once(&self.address)
+            .chain(&load_balancing.addresses)
+            .map(|address| self.connector(address))
+            .collect::<crate::Result<Vec<_>>>()?;
+        let sink = BalancedTcpSink {
+            connectors: connectors.clone(),
+            strategy: load_balancing.strategy,
+            connections_per_address: load_balancing.connections_per_address,
+            health_check_interval: Duration::from_secs(
+                load_balancing.health_check_interval_secs.get(),
+            ),
+            transformer,
+            encoder,
+            compressor,
+        };
+
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            Box::pin(async move {
+                let results =
+                    join_all(connectors.iter().map(|connector| connector.healthcheck())).await;
+                let mut errors = results
+                    .into_iter()
+                    .filter_map(Result::err)
+                    .collect::<Vec<_>>();
+                if errors.len() == connectors.len() {
+                    Err(errors.remove(0))
+                } else {
+                    Ok(())
+                }
+            }),
+        ))
+    }
+
+    fn connector(&self, address: &str) -> crate::Result<TcpConnector> {
+        let uri = address.parse::<http::Uri>()?;
+        let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
+        let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        Ok(TcpConnector::new(
+            host,
+            port,
+            self.keepalive,
+            tls,
+            self.send_buffer_bytes,
+        ))
+    }
 }
 
 #[derive(Clone)]
@@ -191,6 +309,14 @@ impl TcpConnector {
     }
 
     async fn connect_backoff(&self) -> MaybeTlsStream<TcpStream> {
+        self.connect_backoff_notify(|| ()).await
+    }
+
+    /// Connects with backoff, calling `on_error` whenever an attempt fails.
+    ///
+    /// The delays between attempts are jittered, so that connections to the same endpoint don't
+    /// reconnect all at once.
+    async fn connect_backoff_notify(&self, on_error: impl Fn()) -> MaybeTlsStream<TcpStream> {
         let mut backoff = Self::fresh_backoff();
         loop {
             match self.connect().await {
@@ -202,7 +328,8 @@ impl TcpConnector {
                 }
                 Err(error) => {
                     emit!(TcpSocketOutgoingConnectionError { error });
-                    sleep(backoff.next().unwrap()).await;
+                    on_error();
+                    sleep(with_jitter(backoff.next().unwrap())).await;
                 }
             }
         }
@@ -213,6 +340,47 @@ impl TcpConnector {
     }
 }
 
+/// Randomizes the delay between half and all of its value.
+fn with_jitter(delay: Duration) -> Duration {
+    delay.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+}
+
+fn encode_event<E>(
+    transformer: &Transformer,
+    encoder: &mut E,
+    mut event: Event,
+) -> EncodedEvent<Bytes>
+where
+    E: Encoder<Event, Error = codecs::encoding::Error>,
+{
+    let byte_size = event.size_of();
+    let finalizers = event.metadata_mut().take_finalizers();
+    transformer.transform(&mut event);
+    let mut bytes = BytesMut::new();
+
+    // Errors are handled by `Encoder`.
+    if encoder.encode(event, &mut bytes).is_ok() {
+        let item = bytes.freeze();
+        EncodedEvent {
+            item,
+            finalizers,
+            byte_size,
+        }
+    } else {
+        EncodedEvent::new(Bytes::new(), 0)
+    }
+}
+
+fn emit_send_error(error: std::io::Error) {
+    if error.kind() == ErrorKind::Other && error.to_string() == "ShutdownCheck::Close" {
+        emit!(TcpSocketConnectionShutdown {});
+    }
+    emit!(SocketSendError {
+        mode: SocketMode::Tcp,
+        error
+    });
+}
+
 struct TcpSink<E>
 where
     E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
@@ -220,47 +388,59 @@ where
     connector: TcpConnector,
     transformer: Transformer,
     encoder: E,
+    compressor: Option<CompressedEncoder>,
 }
 
 impl<E> TcpSink<E>
 where
     E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
 {
-    const fn new(connector: TcpConnector, transformer: Transformer, encoder: E) -> Self {
+    const fn new(
+        connector: TcpConnector,
+        transformer: Transformer,
+        encoder: E,
+        compressor: Option<CompressedEncoder>,
+    ) -> Self {
         Self {
             connector,
             transformer,
             encoder,
+            compressor,
         }
     }
 
     async fn connect(&self) -> BytesSink<MaybeTlsStream<TcpStream>> {
         let stream = self.connector.connect_backoff().await;
-        BytesSink::new(stream, Self::shutdown_check, SocketMode::Tcp)
+        BytesSink::new(
+            stream,
+            shutdown_check,
+            SocketMode::Tcp,
+            self.compressor.clone(),
+        )
     }
+}
 
-    fn shutdown_check(stream: &mut MaybeTlsStream<TcpStream>) -> ShutdownCheck {
-        // Test if the remote has issued a disconnect by calling read(2)
-        // with a 1 sized buffer.
-        //
-        // This can return a proper disconnect error or `Ok(0)`
-        // which means the pipe is broken and we should try to reconnect.
-        //
-        // If this returns `Poll::Pending` we know the connection is still
-        // valid and the write will most likely succeed.
-        let mut cx = Context::from_waker(noop_waker_ref());
-        let mut buf = [0u8; 1];
-        let mut buf = ReadBuf::new(&mut buf);
-        match Pin::new(stream).poll_read(&mut cx, &mut buf) {
-            Poll::Ready(Err(error)) => ShutdownCheck::Error(error),
-            Poll::Ready(Ok(())) if buf.filled().is_empty() => {
-                // Maybe this is only a sign to close the channel,
-                // in which case we should try to flush our buffers
-                // before disconnecting.
-                ShutdownCheck::Close("ShutdownCheck::Close")
-            }
-            _ => ShutdownCheck::Alive,
+fn shutdown_check(stream: &mut MaybeTlsStream<TcpStream>) -> ShutdownCheck {
+    // Test if the remote has issued a disconnect by calling read(2)
+    // with a 1 sized buffer.
+    //
+    // This can return a proper disconnect error or `Ok(0)`
+    // which means the pipe is broken and we should try to reconnect.
+    //
+    // If this returns `Poll::Pending` we know the connection is still
+    // valid and the write will most likely succeed.
+    let mut cx = Context::from_waker(noop_waker_ref());
+    let mut buf = [0u8; 1];
+    let mut buf = ReadBuf::new(&mut buf);
+    match Pin::new(stream).poll_read(&mut cx, &mut buf) {
+        Poll::Ready(Err(error)) => ShutdownCheck::Error(error),
+        Poll::Ready(Ok(())) if buf.filled().is_empty() => {
+            // Maybe this is only a sign to close the channel,
+            // in which case we should try to flush our buffers
+            // before disconnecting.
+            ShutdownCheck::Close("ShutdownCheck::Close")
         }
+        _ => ShutdownCheck::Alive,
     }
 }
 
@@ -273,24 +453,7 @@ where
         // We need [Peekable](https://docs.rs/futures/0.3.6/futures/stream/struct.Peekable.html) for initiating
         // connection only when we have something to send.
         let mut encoder = self.encoder.clone();
-        let mut input = input.map(|mut event| {
-            let byte_size = event.size_of();
-            let finalizers = event.metadata_mut().take_finalizers();
-            self.transformer.transform(&mut event);
-            let mut bytes = BytesMut::new();
-
-            // Errors are handled by `Encoder`.
-            if encoder.encode(event, &mut bytes).is_ok() {
-                let item = bytes.freeze();
-                EncodedEvent {
-                    item,
-                    finalizers,
-                    byte_size,
-                }
-            } else {
-                EncodedEvent::new(Bytes::new(), 0)
-            }
-        });
+        let mut input = input.map(|event| encode_event(&self.transformer, &mut encoder, event));
 
         while let Some(item) = input.next().await {
             let mut sink = self.connect().await;
@@ -309,13 +472,7 @@ where
             // so we will emit `Error` / `EventsDropped` internal events regardless of if the server
             // responded with Ok(0).
             if let Err(error) = result {
-                if error.kind() == ErrorKind::Other && error.to_string() == "ShutdownCheck::Close" {
-                    emit!(TcpSocketConnectionShutdown {});
-                }
-                emit!(SocketSendError {
-                    mode: SocketMode::Tcp,
-                    error
-                });
+                emit_send_error(error);
             }
         }
 
@@ -323,6 +480,228 @@ where
     }
 }
 
+/// The number of events queued for each connection of a `BalancedTcpSink`.
+const CONNECTION_QUEUE_SIZE: usize = 100;
+
+/// A TCP sink balancing events across a pool of connections to several endpoints.
+///
+/// Each connection of the pool is driven by its own task, which reconnects on its own when
+/// sending fails. Events queued for a connection when it fails are sent once it reconnects.
+struct BalancedTcpSink<E>
+where
+    E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
+{
+    connectors: Vec<TcpConnector>,
+    strategy: LoadBalancingStrategy,
+    connections_per_address: NonZeroUsize,
+    health_check_interval: Duration,
+    transformer: Transformer,
+    encoder: E,
+    compressor: Option<CompressedEncoder>,
+}
+
+#[async_trait]
+impl<E> StreamSink<Event> for BalancedTcpSink<E>
+where
+    E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let gauge = OpenGauge::new();
+        let mut connections = Vec::new();
+        let mut tasks = Vec::new();
+        for connector in &self.connectors {
+            for _ in 0..self.connections_per_address.get() {
+                let (sender, receiver) = mpsc::channel(CONNECTION_QUEUE_SIZE);
+                let state = Arc::new(ConnectionState::default());
+                tasks.push(tokio::spawn(run_connection(
+                    connector.clone(),
+                    self.compressor.clone(),
+                    receiver,
+                    Arc::clone(&state),
+                    gauge.clone(),
+                    self.health_check_interval,
+                )));
+                connections.push(PooledConnection { sender, state });
+            }
+        }
+
+        let mut balancer = Balancer {
+            connections,
+            strategy: self.strategy,
+            next: 0,
+        };
+        let mut encoder = self.encoder.clone();
+        let mut input = input.map(|event| encode_event(&self.transformer, &mut encoder, event));
+        while let Some(item) = input.next().await {
+            if balancer.send(item).await.is_err() {
+                error!(message = "Connection task of the TCP sink stopped unexpectedly.");
+                break;
+            }
+        }
+
+        // Closing the queues lets the connections send the events left in them before closing.
+        drop(balancer);
+        join_all(tasks).await;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct ConnectionState {
+    healthy: AtomicBool,
+    pending: AtomicUsize,
+}
+
+impl Default for ConnectionState {
+    fn default() -> Self {
+        Self {
+            healthy: AtomicBool::new(true),
+            pending: AtomicUsize::new(0),
+        }
+    }
+}
+
+struct PooledConnection {
+    sender: mpsc::Sender<EncodedEvent<Bytes>>,
+    state: Arc<ConnectionState>,
+}
+
+struct Balancer {
+    connections: Vec<PooledConnection>,
+    strategy: LoadBalancingStrategy,
+    next: usize,
+}
+
+impl Balancer {
+    /// Sends the event to the connection picked by the strategy.
+    ///
+    /// When no connection is healthy, the events are still sent to the connections in turn, so
+    /// that they are sent as soon as the connections recover.
+    async fn send(
+        &mut self,
+        item: EncodedEvent<Bytes>,
+    ) -> Result<(), mpsc::error::SendError<EncodedEvent<Bytes>>> {
+        let index = match self.strategy {
+            LoadBalancingStrategy::RoundRobin => self.next_healthy(),
+            LoadBalancingStrategy::LeastConnections => self
+                .connections
+                .iter()
+                .enumerate()
+                .filter(|(_, connection)| connection.state.healthy.load(Ordering::Relaxed))
+                .min_by_key(|(_, connection)| connection.state.pending.load(Ordering::Relaxed))
+                .map(|(index, _)| index),
+        }
+        .unwrap_or_else(|| self.advance());
+
+        let connection = &self.connections[index];
+        connection.state.pending.fetch_add(1, Ordering::Relaxed);
+        connection.sender.send(item).await
+    }
+
+    fn next_healthy(&mut self) -> Option<usize> {
+        (0..self.connections.len())
+            .map(|_| self.advance())
+            .find(|index| {
+                self.connections[*index]
+                    .state
+                    .healthy
+                    .load(Ordering::Relaxed)
+            })
+    }
+
+    fn advance(&mut self) -> usize {
+        let index = self.next;
+        self.next = (self.next + 1) % self.connections.len();
+        index
+    }
+}
+
+async fn run_connection(
+    connector: TcpConnector,
+    compressor: Option<CompressedEncoder>,
+    mut receiver: mpsc::Receiver<EncodedEvent<Bytes>>,
+    state: Arc<ConnectionState>,
+    gauge: OpenGauge,
+    health_check_interval: Duration,
+) {
+    let mut queued = None;
+    loop {
+        // Keep receiving while connecting, so that the connection stops as soon as the sink is
+        // done with no event left to send, instead of retrying forever.
+        let connect =
+            connector.connect_backoff_notify(|| state.healthy.store(false, Ordering::Relaxed));
+        tokio::pin!(connect);
+        let stream = loop {
+            tokio::select! {
+                stream = &mut connect => break Some(stream),
+                item = receiver.recv(), if queued.is_none() => match item {
+                    Some(item) => queued = Some(item),
+                    None => break None,
+                },
+            }
+        };
+        let Some(stream) = stream else { return };
+
+        state.healthy.store(true, Ordering::Relaxed);
+        let mut sink = BytesSink::new(stream, shutdown_check, SocketMode::Tcp, compressor.clone());
+        let _open_token = gauge.clone().open(|count| emit!(ConnectionOpen { count }));
+
+        let result = match queued.take() {
+            Some(item) => send_queued(&mut sink, item, &mut receiver, &state).await,
+            None => Ok(()),
+        };
+        let result = match result {
+            Err(error) => Err(error),
+            Ok(()) => loop {
+                tokio::select! {
+                    item = receiver.recv() => match item {
+                        Some(item) => {
+                            let result = send_queued(&mut sink, item, &mut receiver, &state).await;
+                            if let Err(error) = result {
+                                break Err(error);
+                            }
+                        }
+                        None => break sink.close().await,
+                    },
+                    // Idle connections are flushed regularly, which checks whether they were
+                    // closed by the remote end.
+                    _ = sleep(health_check_interval) => {
+                        if let Err(error) = sink.flush().await {
+                            break Err(error);
+                        }
+                    }
+                }
+            },
+        };
+
+        match result {
+            Ok(()) => return,
+            Err(error) => {
+                state.healthy.store(false, Ordering::Relaxed);
+                emit_send_error(error);
+            }
+        }
+    }
+}
+
+/// Sends the event along with the events already queued, flushing the connection once the queue
+/// is empty.
+async fn send_queued(
+    sink: &mut BytesSink<MaybeTlsStream<TcpStream>>,
+    item: EncodedEvent<Bytes>,
+    receiver: &mut mpsc::Receiver<EncodedEvent<Bytes>>,
+    state: &ConnectionState,
+) -> Result<(), std::io::Error> {
+    let mut next = Some(item);
+    while let Some(item) = next {
+        state.pending.fetch_sub(1, Ordering::Relaxed);
+        sink.feed(item).await?;
+        next = receiver.try_recv().ok();
+    }
+    sink.flush().await
+}
+
 #[cfg(test)]
 mod test {
     use tokio::net::TcpListener;
@@ -6,7 +6,8 @@ use std::{
 };
 
 use async_trait::async_trait;
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
+use codecs::encoding::GelfChunker;
 use futures::{future::BoxFuture, stream::BoxStream, FutureExt, StreamExt};
 use snafu::{ResultExt, Snafu};
 use tokio::{net::UdpSocket, sync::oneshot, time::sleep};
@@ -24,7 +25,7 @@ use crate::{
     dns,
     event::{Event, EventStatus, Finalizable},
     internal_events::{
-        SocketEventsSent, SocketMode, SocketSendError, UdpSendIncompleteError,
+        SocketEventsSent, SocketMode, SocketSendError, UdpChunkingError, UdpSendIncompleteError,
         UdpSocketConnectionEstablished, UdpSocketOutgoingConnectionError,
     },
     sinks::{
@@ -94,13 +95,15 @@ impl UdpSinkConfig {
         ))
     }
 
+    /// Builds the sink, which splits the encoded events into GELF chunks if `chunker` is set.
     pub fn build(
         &self,
         transformer: Transformer,
         encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
+        chunker: Option<GelfChunker>,
     ) -> crate::Result<(VectorSink, Healthcheck)> {
         let connector = self.build_connector()?;
-        let sink = UdpSink::new(connector.clone(), transformer, encoder);
+        let sink = UdpSink::new(connector.clone(), transformer, encoder, chunker);
         Ok((
             VectorSink::from_event_streamsink(sink),
             async move { connector.healthcheck().await }.boxed(),
@@ -269,6 +272,7 @@ where
     connector: UdpConnector,
     transformer: Transformer,
     encoder: E,
+    chunker: Option<GelfChunker>,
     bytes_sent: Registered<BytesSent>,
 }
 
@@ -276,11 +280,17 @@ impl<E> UdpSink<E>
 where
     E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
 {
-    fn new(connector: UdpConnector, transformer: Transformer, encoder: E) -> Self {
+    fn new(
+        connector: UdpConnector,
+        transformer: Transformer,
+        encoder: E,
+        chunker: Option<GelfChunker>,
+    ) -> Self {
         Self {
             connector,
             transformer,
             encoder,
+            chunker,
             bytes_sent: register!(BytesSent::from(Protocol::UDP)),
         }
     }
@@ -309,8 +319,21 @@ where
                 if encoder.encode(event, &mut bytes).is_err() {
                     continue;
                 }
-
-                match udp_send(&mut socket, &bytes).await {
+                let byte_size_sent = bytes.len();
+
+                let datagrams = match &self.chunker {
+                    Some(chunker) => match chunker.chunk(bytes.freeze(), rand::random()) {
+                        Ok(chunks) => chunks,
+                        Err(error) => {
+                            emit!(UdpChunkingError { error });
+                            finalizers.update_status(EventStatus::Errored);
+                            continue;
+                        }
+                    },
+                    None => vec![bytes.freeze()],
+                };
+
+                match udp_send_all(&mut socket, &datagrams).await {
                     Ok(()) => {
                         emit!(SocketEventsSent {
                             mode: SocketMode::Udp,
@@ -318,7 +341,7 @@ where
                             byte_size,
                         });
 
-                        self.bytes_sent.emit(ByteSize(bytes.len()));
+                        self.bytes_sent.emit(ByteSize(byte_size_sent));
                         finalizers.update_status(EventStatus::Delivered);
                     }
                     Err(error) => {
@@ -337,6 +360,13 @@ where
     }
 }
 
+async fn udp_send_all(socket: &mut UdpSocket, datagrams: &[Bytes]) -> tokio::io::Result<()> {
+    for datagram in datagrams {
+        udp_send(socket, datagram).await?;
+    }
+    Ok(())
+}
+
 async fn udp_send(socket: &mut UdpSocket, buf: &[u8]) -> tokio::io::Result<()> {
     let sent = socket.send(buf).await?;
     if sent != buf.len() {
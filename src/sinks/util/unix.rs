@@ -1,11 +1,25 @@
-use std::{path::PathBuf, pin::Pin, time::Duration};
+use std::{
+    path::PathBuf,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use bytes::{Bytes, BytesMut};
-use futures::{stream::BoxStream, SinkExt, StreamExt};
+use codecs::encoding::CompressedEncoder;
+use futures::{future::BoxFuture, stream::BoxStream, FutureExt, SinkExt, StreamExt};
 use snafu::{ResultExt, Snafu};
-use tokio::{net::UnixStream, time::sleep};
+use tokio::{
+    net::{UnixDatagram, UnixStream},
+    sync::oneshot,
+    time::sleep,
+};
 use tokio_util::codec::Encoder;
+use tower::Service;
+use vector_common::internal_event::{
+    ByteSize, BytesSent, InternalEventHandle, Protocol, Registered,
+};
 use vector_config::configurable_component;
 use vector_core::ByteSizeOf;
 
@@ -34,6 +48,27 @@ pub enum UnixError {
         source: tokio::io::Error,
         path: PathBuf,
     },
+
+    #[snafu(display("Failed sending datagram to socket at path {}: {}", path.display(), source))]
+    SendError {
+        source: tokio::io::Error,
+        path: PathBuf,
+    },
+
+    #[snafu(display(
+        "Only {} of {} bytes were sent to socket at path {}",
+        sent,
+        size,
+        path.display()
+    ))]
+    SendIncompleteError {
+        sent: usize,
+        size: usize,
+        path: PathBuf,
+    },
+
+    #[snafu(display("Failed receiving socket from the service: {}", source))]
+    ServiceChannelRecvError { source: oneshot::error::RecvError },
 }
 
 /// A Unix Domain Socket sink.
@@ -56,14 +91,25 @@ impl UnixSinkConfig {
         &self,
         transformer: Transformer,
         encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
+        compressor: Option<CompressedEncoder>,
     ) -> crate::Result<(VectorSink, Healthcheck)> {
         let connector = UnixConnector::new(self.path.clone());
-        let sink = UnixSink::new(connector.clone(), transformer, encoder);
+        let sink = UnixSink::new(connector.clone(), transformer, encoder, compressor);
         Ok((
             VectorSink::from_event_streamsink(sink),
             Box::pin(async move { connector.healthcheck().await }),
         ))
     }
+
+    /// Builds a service sending each request as a single datagram to the socket.
+    pub fn build_datagram_service(&self) -> crate::Result<(UnixDatagramService, Healthcheck)> {
+        let connector = UnixConnector::new(self.path.clone());
+        let service = UnixDatagramService::new(connector.clone());
+        Ok((
+            service,
+            Box::pin(async move { connector.healthcheck_datagram().await }),
+        ))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -110,6 +156,135 @@ impl UnixConnector {
     async fn healthcheck(&self) -> crate::Result<()> {
         self.connect().await.map(|_| ()).map_err(Into::into)
     }
+
+    fn connect_datagram(&self) -> Result<UnixDatagram, UnixError> {
+        let socket = UnixDatagram::unbound().context(ConnectionSnafu {
+            path: self.path.clone(),
+        })?;
+        socket.connect(&self.path).context(ConnectionSnafu {
+            path: self.path.clone(),
+        })?;
+        Ok(socket)
+    }
+
+    async fn connect_datagram_backoff(&self) -> UnixDatagram {
+        let mut backoff = Self::fresh_backoff();
+        loop {
+            match self.connect_datagram() {
+                Ok(socket) => {
+                    emit!(UnixSocketConnectionEstablished { path: &self.path });
+                    return socket;
+                }
+                Err(error) => {
+                    emit!(UnixSocketOutgoingConnectionError { error });
+                    sleep(backoff.next().unwrap()).await;
+                }
+            }
+        }
+    }
+
+    async fn healthcheck_datagram(&self) -> crate::Result<()> {
+        self.connect_datagram().map(|_| ()).map_err(Into::into)
+    }
+}
+
+enum UnixDatagramServiceState {
+    /// `Disconnected` state.
+    Disconnected,
+    /// `Connecting` state, waiting for the socket to connect.
+    Connecting(BoxFuture<'static, UnixDatagram>),
+    /// `Connected` state, ready to send.
+    Connected(UnixDatagram),
+    /// `Sending` state, waiting for the socket to be given back once the datagram is sent.
+    Sending(oneshot::Receiver<Option<UnixDatagram>>),
+}
+
+/// A service sending requests as datagrams to a Unix domain socket.
+///
+/// The socket is reconnected when sending fails, such as when the receiving end of the socket was
+/// restarted.
+pub struct UnixDatagramService {
+    connector: UnixConnector,
+    state: UnixDatagramServiceState,
+    bytes_sent: Registered<BytesSent>,
+}
+
+impl UnixDatagramService {
+    fn new(connector: UnixConnector) -> Self {
+        Self {
+            connector,
+            state: UnixDatagramServiceState::Disconnected,
+            bytes_sent: register!(BytesSent::from(Protocol::UNIX)),
+        }
+    }
+}
+
+impl Service<BytesMut> for UnixDatagramService {
+    type Response = ();
+    type Error = UnixError;
+    type Future = BoxFuture<'static, Result<(), Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        loop {
+            self.state = match &mut self.state {
+                UnixDatagramServiceState::Disconnected => {
+                    let connector = self.connector.clone();
+                    UnixDatagramServiceState::Connecting(Box::pin(async move {
+                        connector.connect_datagram_backoff().await
+                    }))
+                }
+                UnixDatagramServiceState::Connecting(fut) => {
+                    let socket = ready!(fut.poll_unpin(cx));
+                    UnixDatagramServiceState::Connected(socket)
+                }
+                UnixDatagramServiceState::Connected(_) => break,
+                UnixDatagramServiceState::Sending(fut) => {
+                    match ready!(fut.poll_unpin(cx)).context(ServiceChannelRecvSnafu) {
+                        Ok(Some(socket)) => UnixDatagramServiceState::Connected(socket),
+                        Ok(None) => UnixDatagramServiceState::Disconnected,
+                        Err(error) => return Poll::Ready(Err(error)),
+                    }
+                }
+            };
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, msg: BytesMut) -> Self::Future {
+        let (sender, receiver) = oneshot::channel();
+        let path = self.connector.path.clone();
+        let bytes_sent = self.bytes_sent.clone();
+
+        let socket =
+            match std::mem::replace(&mut self.state, UnixDatagramServiceState::Sending(receiver)) {
+                UnixDatagramServiceState::Connected(socket) => socket,
+                _ => panic!("UnixDatagramService::poll_ready should be called first"),
+            };
+
+        Box::pin(async move {
+            match socket.send(&msg).await {
+                Ok(sent) => {
+                    let _ = sender.send(Some(socket));
+                    if sent != msg.len() {
+                        return Err(UnixError::SendIncompleteError {
+                            sent,
+                            size: msg.len(),
+                            path,
+                        });
+                    }
+                    bytes_sent.emit(ByteSize(sent));
+                    Ok(())
+                }
+                Err(source) => {
+                    // Drop the socket so that the next request reconnects.
+                    let _ = sender.send(None);
+                    Err(UnixError::SendError { source, path })
+                }
+            }
+        })
+    }
 }
 
 struct UnixSink<E>
@@ -119,23 +294,35 @@ where
     connector: UnixConnector,
     transformer: Transformer,
     encoder: E,
+    compressor: Option<CompressedEncoder>,
 }
 
 impl<E> UnixSink<E>
 where
     E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
 {
-    pub const fn new(connector: UnixConnector, transformer: Transformer, encoder: E) -> Self {
+    pub const fn new(
+        connector: UnixConnector,
+        transformer: Transformer,
+        encoder: E,
+        compressor: Option<CompressedEncoder>,
+    ) -> Self {
         Self {
             connector,
             transformer,
             encoder,
+            compressor,
         }
     }
 
     async fn connect(&mut self) -> BytesSink<UnixStream> {
         let stream = self.connector.connect_backoff().await;
-        BytesSink::new(stream, |_| ShutdownCheck::Alive, SocketMode::Unix)
+        BytesSink::new(
+            stream,
+            |_| ShutdownCheck::Alive,
+            SocketMode::Unix,
+            self.compressor.clone(),
+        )
     }
 }
 
@@ -196,6 +383,7 @@ where
 mod tests {
     use codecs::{encoding::Framer, NewlineDelimitedEncoder, TextSerializerConfig};
     use tokio::net::UnixListener;
+    use tower::ServiceExt;
 
     use super::*;
     use crate::{
@@ -217,7 +405,8 @@ mod tests {
         assert!(UnixSinkConfig::new(good_path)
             .build(
                 Default::default(),
-                Encoder::<()>::new(TextSerializerConfig::default().build().into())
+                Encoder::<()>::new(TextSerializerConfig::default().build().unwrap().into()),
+                None,
             )
             .unwrap()
             .1
@@ -228,7 +417,8 @@ mod tests {
         assert!(UnixSinkConfig::new(bad_path)
             .build(
                 Default::default(),
-                Encoder::<()>::new(TextSerializerConfig::default().build().into())
+                Encoder::<()>::new(TextSerializerConfig::default().build().unwrap().into()),
+                None,
             )
             .unwrap()
             .1
@@ -251,8 +441,9 @@ mod tests {
                 Default::default(),
                 Encoder::<Framer>::new(
                     NewlineDelimitedEncoder::new().into(),
-                    TextSerializerConfig::default().build().into(),
+                    TextSerializerConfig::default().build().unwrap().into(),
                 ),
+                None,
             )
             .unwrap();
 
@@ -269,4 +460,30 @@ mod tests {
         // Receive the data sent by the Sink to the receiver
         assert_eq!(input_lines, receiver.await);
     }
+
+    #[tokio::test]
+    async fn unix_datagram_service() {
+        let path = temp_uds_path("unix_datagram_test");
+        let (mut service, healthcheck) = UnixSinkConfig::new(path.clone())
+            .build_datagram_service()
+            .unwrap();
+        assert!(healthcheck.await.is_err());
+
+        let receiver = UnixDatagram::bind(&path).unwrap();
+        for message in ["first", "second"] {
+            service
+                .ready()
+                .await
+                .unwrap()
+                .call(BytesMut::from(message))
+                .await
+                .unwrap();
+        }
+
+        let mut buf = [0; 16];
+        let size = receiver.recv(&mut buf).await.unwrap();
+        assert_eq!(&buf[..size], b"first");
+        let size = receiver.recv(&mut buf).await.unwrap();
+        assert_eq!(&buf[..size], b"second");
+    }
 }
@@ -1,7 +1,10 @@
+use std::time::Duration;
+
 use http::Uri;
 use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
+use serde::{Deserialize, Deserializer};
 use tonic::body::BoxBody;
 use tower::ServiceBuilder;
 use vector_config::configurable_component;
@@ -48,14 +51,16 @@ pub struct VectorConfig {
     #[configurable(metadata(docs::examples = "https://somehost:6000"))]
     address: String,
 
-    /// Whether or not to compress requests.
-    ///
-    /// If set to `true`, requests will be compressed with [`gzip`][gzip_docs].
+    /// The compression algorithm of requests.
     ///
-    /// [gzip_docs]: https://www.gzip.org/
+    /// For compatibility, `true` and `false` are accepted as aliases of `gzip` and `none`.
     #[configurable(metadata(docs::advanced))]
+    #[serde(default, deserialize_with = "bool_or_compression")]
+    compression: VectorCompression,
+
+    #[configurable(derived)]
     #[serde(default)]
-    compression: bool,
+    http2: Http2Config,
 
     #[configurable(derived)]
     #[serde(default)]
@@ -78,6 +83,106 @@ pub struct VectorConfig {
     pub(in crate::sinks::vector) acknowledgements: AcknowledgementsConfig,
 }
 
+/// Compression algorithm of requests.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorCompression {
+    /// No compression.
+    #[default]
+    None,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// Zstandard is usually both faster and more efficient than gzip. If the downstream Vector
+    /// doesn't support it, requests are compressed with gzip instead.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
+fn bool_or_compression<'de, D>(deserializer: D) -> Result<VectorCompression, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrCompression {
+        Bool(bool),
+        Compression(VectorCompression),
+    }
+
+    Ok(match BoolOrCompression::deserialize(deserializer)? {
+        BoolOrCompression::Bool(true) => VectorCompression::Gzip,
+        BoolOrCompression::Bool(false) => VectorCompression::None,
+        BoolOrCompression::Compression(compression) => compression,
+    })
+}
+
+/// HTTP/2 settings of the connections to the downstream Vector.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Http2Config {
+    /// The interval between the pings sent to keep connections alive.
+    ///
+    /// Connections whose peer doesn't acknowledge a ping within `keepalive_timeout_secs` are
+    /// closed, which detects broken connections that wouldn't be noticed otherwise, such as behind
+    /// load balancers. If not set, no pings are sent.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 30))]
+    pub keepalive_interval_secs: Option<u64>,
+
+    /// How long to wait for the acknowledgement of a ping before closing the connection.
+    #[serde(default = "default_keepalive_timeout_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub keepalive_timeout_secs: u64,
+
+    /// Whether to send pings while no requests are in flight.
+    #[serde(default)]
+    pub keepalive_while_idle: bool,
+
+    /// The initial flow control window of streams.
+    ///
+    /// Larger windows help throughput over links with high latency.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 1048576))]
+    pub initial_stream_window_size: Option<u32>,
+
+    /// The initial flow control window of connections.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 4194304))]
+    pub initial_connection_window_size: Option<u32>,
+
+    /// Whether to adapt the flow control windows to the measured bandwidth and latency.
+    ///
+    /// If enabled, `initial_stream_window_size` and `initial_connection_window_size` are ignored.
+    #[serde(default)]
+    pub adaptive_window: bool,
+}
+
+const fn default_keepalive_timeout_secs() -> u64 {
+    20
+}
+
+impl Default for Http2Config {
+    fn default() -> Self {
+        Self {
+            keepalive_interval_secs: None,
+            keepalive_timeout_secs: default_keepalive_timeout_secs(),
+            keepalive_while_idle: false,
+            initial_stream_window_size: None,
+            initial_connection_window_size: None,
+            adaptive_window: false,
+        }
+    }
+}
+
 impl VectorConfig {
     /// Creates a `VectorConfig` with the given address.
     pub fn from_address(addr: Uri) -> Self {
@@ -96,7 +201,8 @@ fn default_config(address: &str) -> VectorConfig {
     VectorConfig {
         version: None,
         address: address.to_owned(),
-        compression: false,
+        compression: VectorCompression::None,
+        http2: Http2Config::default(),
         batch: BatchConfig::default(),
         request: TowerRequestConfig::default(),
         tls: None,
@@ -110,7 +216,7 @@ impl SinkConfig for VectorConfig {
         let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
         let uri = with_default_scheme(&self.address, tls.is_tls())?;
 
-        let client = new_client(&tls, cx.proxy())?;
+        let client = new_client(&tls, cx.proxy(), &self.http2)?;
 
         let healthcheck_uri = cx
             .healthcheck
@@ -118,10 +224,14 @@ impl SinkConfig for VectorConfig {
             .clone()
             .map(|uri| uri.uri)
             .unwrap_or_else(|| uri.clone());
-        let healthcheck_client = VectorService::new(client.clone(), healthcheck_uri, false);
+        let healthcheck_client =
+            VectorService::new(client.clone(), healthcheck_uri, VectorCompression::None);
+        let request_settings = self
+            .request
+            .unwrap_with(&TowerRequestConfig::default())
+            .with_context(&cx);
         let healthcheck = healthcheck(healthcheck_client, cx.healthcheck);
         let service = VectorService::new(client, uri, self.compression);
-        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batcher_settings()?;
 
         let service = ServiceBuilder::new()
@@ -207,6 +317,7 @@ pub fn with_default_scheme(address: &str, tls: bool) -> crate::Result<Uri> {
 fn new_client(
     tls_settings: &MaybeTlsSettings,
     proxy_config: &ProxyConfig,
+    http2: &Http2Config,
 ) -> crate::Result<hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>> {
     let mut http = HttpConnector::new();
     http.enforce_http(false);
@@ -226,7 +337,15 @@ fn new_client(
     let mut proxy = ProxyConnector::new(https).unwrap();
     proxy_config.configure(&mut proxy)?;
 
-    Ok(hyper::Client::builder().http2_only(true).build(proxy))
+    Ok(hyper::Client::builder()
+        .http2_only(true)
+        .http2_keep_alive_interval(http2.keepalive_interval_secs.map(Duration::from_secs))
+        .http2_keep_alive_timeout(Duration::from_secs(http2.keepalive_timeout_secs))
+        .http2_keep_alive_while_idle(http2.keepalive_while_idle)
+        .http2_initial_stream_window_size(http2.initial_stream_window_size)
+        .http2_initial_connection_window_size(http2.initial_connection_window_size)
+        .http2_adaptive_window(http2.adaptive_window)
+        .build(proxy))
 }
 
 #[derive(Debug, Clone)]
@@ -1,7 +1,19 @@
-use std::task::{Context, Poll};
+use std::{
+    cmp,
+    io::{self, Write},
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
 
+use bytes::{BufMut, Bytes, BytesMut};
+use flate2::write::GzEncoder;
 use futures::{future::BoxFuture, TryFutureExt};
-use http::Uri;
+use http::{request::Parts, HeaderValue, Uri};
+use http_body::{Body as _, Full};
 use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
@@ -11,7 +23,7 @@ use tower::Service;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
 
-use super::VectorSinkError;
+use super::{config::VectorCompression, VectorSinkError};
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     internal_events::EndpointBytesSent,
@@ -62,18 +74,18 @@ impl MetaDescriptive for VectorRequest {
 }
 
 impl VectorService {
-    pub fn new(
-        hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
-        uri: Uri,
-        compression: bool,
-    ) -> Self {
+    pub fn new(hyper_client: HyperClient, uri: Uri, compression: VectorCompression) -> Self {
         let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
+        // `tonic` only supports gzip, so zstd is handled by our own service.
+        let zstd =
+            (compression == VectorCompression::Zstd).then(|| Arc::new(AtomicBool::new(true)));
         let mut proto_client = proto_vector::Client::new(HyperSvc {
             uri,
             client: hyper_client,
+            zstd,
         });
 
-        if compression {
+        if compression == VectorCompression::Gzip {
             proto_client = proto_client.send_compressed(tonic::codec::CompressionEncoding::Gzip);
         }
         Self {
@@ -129,15 +141,20 @@ impl Service<VectorRequest> for VectorService {
     }
 }
 
+pub type HyperClient = hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>;
+
 #[derive(Clone, Debug)]
 pub struct HyperSvc {
     uri: Uri,
-    client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+    client: HyperClient,
+    /// Whether to compress requests with zstd, which is turned off once the downstream Vector
+    /// rejects it.
+    zstd: Option<Arc<AtomicBool>>,
 }
 
 impl Service<hyper::Request<BoxBody>> for HyperSvc {
     type Response = hyper::Response<hyper::Body>;
-    type Error = hyper::Error;
+    type Error = Error;
     type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
     // Emission of an internal event in case of errors is handled upstream by the caller.
@@ -156,6 +173,161 @@ impl Service<hyper::Request<BoxBody>> for HyperSvc {
 
         *req.uri_mut() = uri;
 
-        Box::pin(self.client.request(req))
+        match &self.zstd {
+            Some(zstd) => Box::pin(send_zstd(self.client.clone(), req, Arc::clone(zstd))),
+            None => Box::pin(self.client.request(req).err_into::<Error>()),
+        }
+    }
+}
+
+// Every gRPC message has a five byte header:
+// - a compressed flag (u8, 0/1 for compressed/decompressed)
+// - a length prefix, indicating the number of remaining bytes to read (u32)
+const GRPC_MESSAGE_HEADER_LEN: usize = mem::size_of::<u8>() + mem::size_of::<u32>();
+const GRPC_ENCODING_HEADER: &str = "grpc-encoding";
+const GRPC_ACCEPT_ENCODING_HEADER: &str = "grpc-accept-encoding";
+const GRPC_STATUS_HEADER: &str = "grpc-status";
+
+/// Sends a request with its messages compressed with zstd, or with gzip if the downstream Vector
+/// doesn't support zstd.
+async fn send_zstd(
+    client: HyperClient,
+    req: hyper::Request<BoxBody>,
+    zstd_supported: Arc<AtomicBool>,
+) -> Result<hyper::Response<hyper::Body>, Error> {
+    let (parts, body) = req.into_parts();
+    let body = hyper::body::to_bytes(body).await?;
+
+    if zstd_supported.load(Ordering::Relaxed) {
+        let messages = compress_messages(&body, |message| zstd::bulk::compress(message, 0))?;
+        let response = client
+            .request(compressed_request(&parts, "zstd", messages))
+            .await?;
+        if !rejects_zstd(&response) {
+            return Ok(response);
+        }
+
+        warn!(
+            message = "Downstream Vector doesn't support zstd compression, falling back to gzip."
+        );
+        zstd_supported.store(false, Ordering::Relaxed);
+    }
+
+    let messages = compress_messages(&body, |message| {
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(message)?;
+        encoder.finish()
+    })?;
+    Ok(client
+        .request(compressed_request(&parts, "gzip", messages))
+        .await?)
+}
+
+/// Whether the response rejects a request for using an unsupported compression scheme, which is
+/// the case of the Vector versions not supporting zstd.
+fn rejects_zstd(response: &hyper::Response<hyper::Body>) -> bool {
+    let headers = response.headers();
+    let unimplemented = headers
+        .get(GRPC_STATUS_HEADER)
+        .map_or(false, |status| status.as_bytes() == b"12");
+    let accepts_zstd = headers
+        .get(GRPC_ACCEPT_ENCODING_HEADER)
+        .and_then(|encodings| encodings.to_str().ok())
+        .map_or(false, |encodings| {
+            encodings
+                .split(',')
+                .any(|encoding| encoding.trim() == "zstd")
+        });
+
+    unimplemented && !accepts_zstd
+}
+
+fn compressed_request(
+    parts: &Parts,
+    encoding: &'static str,
+    body: Bytes,
+) -> hyper::Request<BoxBody> {
+    let body = Full::new(body)
+        .map_err(|error| match error {})
+        .boxed_unsync();
+    let mut req = hyper::Request::new(body);
+    *req.method_mut() = parts.method.clone();
+    *req.uri_mut() = parts.uri.clone();
+    *req.version_mut() = parts.version;
+    *req.headers_mut() = parts.headers.clone();
+    req.headers_mut()
+        .insert(GRPC_ENCODING_HEADER, HeaderValue::from_static(encoding));
+    req
+}
+
+/// Compresses the uncompressed gRPC messages of a request body.
+fn compress_messages<F>(body: &[u8], compress: F) -> io::Result<Bytes>
+where
+    F: Fn(&[u8]) -> io::Result<Vec<u8>>,
+{
+    let mut compressed = BytesMut::with_capacity(body.len());
+    let mut remaining = body;
+
+    while remaining.len() >= GRPC_MESSAGE_HEADER_LEN {
+        let message_len = u32::from_be_bytes(
+            remaining[1..GRPC_MESSAGE_HEADER_LEN]
+                .try_into()
+                .expect("there must be four bytes remaining in the header slice"),
+        ) as usize;
+        let end = cmp::min(GRPC_MESSAGE_HEADER_LEN + message_len, remaining.len());
+
+        if remaining[0] == 0 {
+            let message = compress(&remaining[GRPC_MESSAGE_HEADER_LEN..end])?;
+            let message_len = u32::try_from(message.len()).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "messages greater than 4GB are not supported",
+                )
+            })?;
+            compressed.put_u8(1);
+            compressed.put_u32(message_len);
+            compressed.put_slice(&message);
+        } else {
+            compressed.put_slice(&remaining[..end]);
+        }
+
+        remaining = &remaining[end..];
+    }
+    compressed.put_slice(remaining);
+
+    Ok(compressed.freeze())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    #[test]
+    fn compresses_uncompressed_messages() {
+        let mut body = BytesMut::new();
+        body.put_u8(0);
+        body.put_u32(5);
+        body.put_slice(b"hello");
+        body.put_u8(1);
+        body.put_u32(3);
+        body.put_slice(b"abc");
+
+        let compressed =
+            compress_messages(&body, |message| zstd::bulk::compress(message, 0)).unwrap();
+
+        assert_eq!(compressed[0], 1);
+        let message_len = u32::from_be_bytes(compressed[1..5].try_into().unwrap()) as usize;
+        let mut message = Vec::new();
+        zstd::stream::read::Decoder::new(&compressed[5..5 + message_len])
+            .unwrap()
+            .read_to_end(&mut message)
+            .unwrap();
+        assert_eq!(message, b"hello");
+        assert_eq!(
+            &compressed[5 + message_len..],
+            &[1, 0, 0, 0, 3, b'a', b'b', b'c']
+        );
     }
 }
@@ -2,8 +2,8 @@ use std::{convert::TryInto, io::ErrorKind};
 
 use async_compression::tokio::bufread;
 use aws_sdk_s3::types::ByteStream;
-use codecs::BytesDeserializerConfig;
-use futures::{stream, stream::StreamExt, TryStreamExt};
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use futures::{stream, stream::StreamExt, FutureExt, TryStreamExt};
 use lookup::owned_value_path;
 use snafu::Snafu;
 use tokio_util::io::StreamReader;
@@ -17,7 +17,8 @@ use crate::{
     common::{s3::S3ClientBuilder, sqs::SqsClientBuilder},
     config::{Output, ProxyConfig, SourceAcknowledgementsConfig, SourceConfig, SourceContext},
     line_agg,
-    serde::bool_or_struct,
+    serde::{bool_or_struct, default_decoding},
+    sinks::Healthcheck,
     tls::TlsConfig,
 };
 
@@ -69,7 +70,8 @@ enum Strategy {
 //
 // Maybe showing defaults at all, when there are required properties, doesn't actually make sense? :thinkies:
 #[configurable_component(source("aws_s3"))]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
 #[serde(default, deny_unknown_fields)]
 pub struct AwsS3Config {
     #[serde(flatten)]
@@ -102,6 +104,14 @@ pub struct AwsS3Config {
     #[configurable(derived)]
     multiline: Option<MultilineConfig>,
 
+    #[configurable(derived)]
+    framing: Option<FramingConfig>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: DeserializerConfig,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -139,7 +149,8 @@ impl SourceConfig for AwsS3Config {
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
         let log_namespace = global_log_namespace.merge(self.log_namespace);
-        let mut schema_definition = BytesDeserializerConfig
+        let mut schema_definition = self
+            .decoding
             .schema_definition(log_namespace)
             .with_source_metadata(
                 Self::NAME,
@@ -190,6 +201,34 @@ impl SourceConfig for AwsS3Config {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn healthcheck(&self, proxy: &ProxyConfig) -> Option<Healthcheck> {
+        // Objects are only known once their notifications are received, so only the queue can be
+        // checked.
+        let sqs = self.sqs.clone()?;
+        let config = self.clone();
+        let proxy = proxy.clone();
+        Some(
+            async move {
+                create_client::<SqsClientBuilder>(
+                    &config.auth,
+                    config.region.region(),
+                    config.region.endpoint()?,
+                    &proxy,
+                    &sqs.tls_options,
+                    false,
+                )
+                .await?
+                .get_queue_attributes()
+                .queue_url(sqs.queue_url)
+                .send()
+                .await
+                .map(|_| ())
+                .map_err(Into::into)
+            }
+            .boxed(),
+        )
+    }
 }
 
 impl AwsS3Config {
@@ -237,6 +276,11 @@ impl AwsS3Config {
                     sqs.clone(),
                     self.compression,
                     multiline,
+                    self.framing
+                        .clone()
+                        .unwrap_or_else(|| self.decoding.default_stream_framing())
+                        .build(),
+                    self.decoding.build(),
                 )
                 .await?;
 
@@ -11,8 +11,8 @@ use aws_smithy_client::SdkError;
 use aws_types::region::Region;
 use bytes::Bytes;
 use chrono::{TimeZone, Utc};
-use codecs::{decoding::FramingError, BytesDeserializer, CharacterDelimitedDecoder};
-use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
+use codecs::decoding::{self, format::Deserializer as _, Framer, FramingError};
+use futures::{stream, FutureExt, Stream, StreamExt, TryFutureExt};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::serde_as;
@@ -27,12 +27,12 @@ use vector_config::configurable_component;
 
 use crate::{
     config::{SourceAcknowledgementsConfig, SourceContext},
-    event::{BatchNotifier, BatchStatus, EstimatedJsonEncodedSizeOf},
+    event::{BatchNotifier, BatchStatus, EstimatedJsonEncodedSizeOf, Event},
     internal_events::{
-        EventsReceived, SqsMessageDeleteBatchError, SqsMessageDeletePartialError,
-        SqsMessageDeleteSucceeded, SqsMessageProcessingError, SqsMessageProcessingSucceeded,
-        SqsMessageReceiveError, SqsMessageReceiveSucceeded, SqsS3EventRecordInvalidEventIgnored,
-        StreamClosedError,
+        DecoderDeserializeError, EventsReceived, SqsMessageDeleteBatchError,
+        SqsMessageDeletePartialError, SqsMessageDeleteSucceeded, SqsMessageProcessingError,
+        SqsMessageProcessingSucceeded, SqsMessageReceiveError, SqsMessageReceiveSucceeded,
+        SqsS3EventRecordInvalidEventIgnored, StreamClosedError,
     },
     line_agg::{self, LineAgg},
     shutdown::ShutdownSignal,
@@ -189,6 +189,8 @@ pub struct State {
 
     multiline: Option<line_agg::Config>,
     compression: super::Compression,
+    framer: Framer,
+    deserializer: decoding::Deserializer,
 
     queue_url: String,
     poll_secs: i32,
@@ -209,6 +211,8 @@ impl Ingestor {
         config: Config,
         compression: super::Compression,
         multiline: Option<line_agg::Config>,
+        framer: Framer,
+        deserializer: decoding::Deserializer,
     ) -> Result<Ingestor, IngestorNewError> {
         let state = Arc::new(State {
             region,
@@ -218,6 +222,8 @@ impl Ingestor {
 
             compression,
             multiline,
+            framer,
+            deserializer,
 
             queue_url: config.queue_url,
             poll_secs: config.poll_secs as i32,
@@ -504,7 +510,7 @@ impl IngestorProcess {
         let bytes_received = self.bytes_received.clone();
         let events_received = self.events_received.clone();
         let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
-            FramedRead::new(object_reader, CharacterDelimitedDecoder::new(b'\n'))
+            FramedRead::new(object_reader, self.state.framer.clone())
                 .map(|res| {
                     res.map(|bytes| {
                         bytes_received.emit(ByteSize(bytes.len()));
@@ -530,11 +536,20 @@ impl IngestorProcess {
             None => lines,
         };
 
-        let mut stream = lines.map(|line| {
-            let deserializer = BytesDeserializer::new();
-            let mut log = deserializer
-                .parse_single(line, log_namespace)
-                .with_batch_notifier_option(&batch);
+        let deserializer = &self.state.deserializer;
+        let events = lines.flat_map(|line| {
+            let events = match deserializer.parse(line, log_namespace) {
+                Ok(events) => events,
+                Err(error) => {
+                    emit!(DecoderDeserializeError { error: &error });
+                    Default::default()
+                }
+            };
+            stream::iter(events.into_iter().filter_map(Event::try_into_log))
+        });
+
+        let mut stream = events.map(|log| {
+            let mut log = log.with_batch_notifier_option(&batch);
 
             log_namespace.insert_source_metadata(
                 AwsS3Config::NAME,
@@ -1,6 +1,7 @@
 use std::num::NonZeroUsize;
 
 use codecs::decoding::{DeserializerConfig, FramingConfig};
+use futures::FutureExt;
 use lookup::owned_value_path;
 use value::Kind;
 use vector_config::configurable_component;
@@ -12,8 +13,9 @@ use crate::common::sqs::SqsClientBuilder;
 use crate::tls::TlsConfig;
 use crate::{
     aws::{auth::AwsAuthentication, region::RegionOrEndpoint},
-    config::{Output, SourceAcknowledgementsConfig, SourceConfig, SourceContext},
+    config::{Output, ProxyConfig, SourceAcknowledgementsConfig, SourceConfig, SourceContext},
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    sinks::Healthcheck,
     sources::aws_sqs::source::SqsSource,
 };
 
@@ -106,7 +108,7 @@ impl SourceConfig for AwsSqsConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
 
-        let client = self.build_client(&cx).await?;
+        let client = self.build_client(&cx.proxy).await?;
         let decoder =
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
@@ -149,15 +151,34 @@ impl SourceConfig for AwsSqsConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn healthcheck(&self, proxy: &ProxyConfig) -> Option<Healthcheck> {
+        let config = self.clone();
+        let proxy = proxy.clone();
+        Some(
+            async move {
+                config
+                    .build_client(&proxy)
+                    .await?
+                    .get_queue_attributes()
+                    .queue_url(config.queue_url)
+                    .send()
+                    .await
+                    .map(|_| ())
+                    .map_err(Into::into)
+            }
+            .boxed(),
+        )
+    }
 }
 
 impl AwsSqsConfig {
-    async fn build_client(&self, cx: &SourceContext) -> crate::Result<aws_sdk_sqs::Client> {
+    async fn build_client(&self, proxy: &ProxyConfig) -> crate::Result<aws_sdk_sqs::Client> {
         create_client::<SqsClientBuilder>(
             &self.auth,
             self.region.region(),
             self.region.endpoint()?,
-            &cx.proxy,
+            proxy,
             &self.tls,
             false,
         )
@@ -0,0 +1,396 @@
+use std::collections::HashMap;
+
+use chrono::{
+    format::{Item, StrftimeItems},
+    Utc,
+};
+use rand::{
+    distributions::{Distribution, Uniform, WeightedIndex},
+    Rng,
+};
+use rand_distr::Normal;
+use value::Value;
+use vector_common::TimeZone;
+use vector_config::configurable_component;
+use vector_core::compile_vrl;
+use vrl::{diagnostic::Formatter, CompilationResult, CompileConfig, Program, Runtime};
+
+use crate::{
+    event::{Event, LogEvent, VrlTarget},
+    internal_events::DemoLogsVrlGenerationError,
+};
+
+/// The distribution of the values of a field of `template` lines.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The distribution of the field values."))]
+pub enum FieldGenerator {
+    /// Values chosen at random from a list.
+    Choice {
+        /// The values to choose from.
+        #[configurable(metadata(docs::examples = "choice_values_example()"))]
+        values: Vec<String>,
+
+        /// The relative weights of the values, in the same order.
+        ///
+        /// By default, all values are equally likely.
+        #[serde(default)]
+        #[configurable(metadata(docs::examples = "choice_weights_example()"))]
+        weights: Vec<f64>,
+    },
+
+    /// Integers uniformly distributed between `min` and `max`, inclusive.
+    Integer {
+        /// The smallest value.
+        min: i64,
+
+        /// The largest value.
+        max: i64,
+    },
+
+    /// Numbers following a normal distribution.
+    Normal {
+        /// The mean of the distribution.
+        mean: f64,
+
+        /// The standard deviation of the distribution.
+        std_dev: f64,
+
+        /// The number of decimal places of the values.
+        #[serde(default)]
+        precision: usize,
+    },
+
+    /// The sequence number of the line, starting at 0.
+    Sequence,
+
+    /// The current time.
+    Timestamp {
+        /// The [format][chrono_format] of the time.
+        ///
+        /// [chrono_format]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html
+        #[serde(default = "default_timestamp_format")]
+        format: String,
+    },
+}
+
+const fn choice_values_example() -> [&'static str; 3] {
+    ["GET", "POST", "DELETE"]
+}
+
+const fn choice_weights_example() -> [f64; 3] {
+    [8.0, 1.5, 0.5]
+}
+
+fn default_timestamp_format() -> String {
+    "%+".to_owned()
+}
+
+/// A sampler of the values of a field, built from a `FieldGenerator`.
+#[derive(Clone, Debug)]
+enum FieldSampler {
+    Choice {
+        values: Vec<String>,
+        weights: Option<WeightedIndex<f64>>,
+    },
+    Integer(Uniform<i64>),
+    Normal {
+        distribution: Normal<f64>,
+        precision: usize,
+    },
+    Sequence,
+    Timestamp(String),
+}
+
+impl FieldSampler {
+    fn new(name: &str, generator: &FieldGenerator) -> crate::Result<Self> {
+        Ok(match generator {
+            FieldGenerator::Choice { values, weights } => {
+                if values.is_empty() {
+                    return Err(format!("Field `{}` has no values to choose from", name).into());
+                }
+                let weights = if weights.is_empty() {
+                    None
+                } else if weights.len() != values.len() {
+                    return Err(format!(
+                        "Field `{}` has {} weights for {} values",
+                        name,
+                        weights.len(),
+                        values.len()
+                    )
+                    .into());
+                } else {
+                    Some(WeightedIndex::new(weights).map_err(|error| {
+                        format!("Invalid weights of field `{}`: {}", name, error)
+                    })?)
+                };
+                Self::Choice {
+                    values: values.clone(),
+                    weights,
+                }
+            }
+            FieldGenerator::Integer { min, max } => {
+                if min > max {
+                    return Err(format!("Field `{}` has a `min` above its `max`", name).into());
+                }
+                Self::Integer(Uniform::new_inclusive(*min, *max))
+            }
+            FieldGenerator::Normal {
+                mean,
+                std_dev,
+                precision,
+            } => {
+                if *std_dev < 0.0 {
+                    return Err(
+                        format!("Field `{}` has a negative standard deviation", name).into(),
+                    );
+                }
+                Self::Normal {
+                    distribution: Normal::new(*mean, *std_dev).map_err(|error| {
+                        format!("Invalid distribution of field `{}`: {}", name, error)
+                    })?,
+                    precision: *precision,
+                }
+            }
+            FieldGenerator::Sequence => Self::Sequence,
+            FieldGenerator::Timestamp { format } => {
+                if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                    return Err(format!("Invalid timestamp format of field `{}`", name).into());
+                }
+                Self::Timestamp(format.clone())
+            }
+        })
+    }
+
+    fn sample(&self, rng: &mut impl Rng, n: usize) -> String {
+        match self {
+            Self::Choice { values, weights } => {
+                let index = match weights {
+                    Some(weights) => weights.sample(rng),
+                    None => rng.gen_range(0..values.len()),
+                };
+                values[index].clone()
+            }
+            Self::Integer(distribution) => distribution.sample(rng).to_string(),
+            Self::Normal {
+                distribution,
+                precision,
+            } => format!("{:.*}", precision, distribution.sample(rng)),
+            Self::Sequence => n.to_string(),
+            Self::Timestamp(format) => Utc::now().format(format).to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Segment {
+    Literal(String),
+    Field(usize),
+}
+
+/// Generates lines by rendering a template with the sampled values of its fields.
+#[derive(Clone, Debug)]
+pub(super) struct TemplateGenerator {
+    segments: Vec<Segment>,
+    samplers: Vec<FieldSampler>,
+}
+
+impl TemplateGenerator {
+    pub(super) fn new(
+        template: &str,
+        fields: &HashMap<String, FieldGenerator>,
+    ) -> crate::Result<Self> {
+        let mut segments = Vec::new();
+        let mut names: Vec<&str> = Vec::new();
+        let mut samplers = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            let end = rest[start..]
+                .find("}}")
+                .ok_or_else(|| format!("Unclosed field reference in `{}`", template))?;
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_owned()));
+            }
+            let name = rest[start + 2..start + end].trim();
+            // Fields referenced several times have the same value in each line.
+            let index = match names.iter().position(|known| *known == name) {
+                Some(index) => index,
+                None => {
+                    let generator = fields.get(name).ok_or_else(|| {
+                        format!("Template references the undefined field `{}`", name)
+                    })?;
+                    samplers.push(FieldSampler::new(name, generator)?);
+                    names.push(name);
+                    names.len() - 1
+                }
+            };
+            segments.push(Segment::Field(index));
+            rest = &rest[start + end + 2..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_owned()));
+        }
+        Ok(Self { segments, samplers })
+    }
+
+    pub(super) fn generate_line(&self, n: usize) -> String {
+        let mut rng = rand::thread_rng();
+        let values = self
+            .samplers
+            .iter()
+            .map(|sampler| sampler.sample(&mut rng, n))
+            .collect::<Vec<_>>();
+
+        let mut line = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(literal) => line.push_str(literal),
+                Segment::Field(index) => line.push_str(&values[*index]),
+            }
+        }
+        line
+    }
+}
+
+/// Generates lines with a VRL program.
+#[derive(Clone, Debug)]
+pub(super) struct VrlGenerator {
+    program: Program,
+}
+
+impl VrlGenerator {
+    pub(super) fn new(source: &str) -> crate::Result<Self> {
+        let functions = vrl_stdlib::all()
+            .into_iter()
+            .chain(vector_vrl_functions::vrl_functions())
+            .collect::<Vec<_>>();
+
+        let state = vrl::state::TypeState::default();
+
+        let CompilationResult {
+            program,
+            warnings,
+            config: _,
+        } = compile_vrl(source, &functions, &state, CompileConfig::default())
+            .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+        if !warnings.is_empty() {
+            let warnings = Formatter::new(source, warnings).colored().to_string();
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        Ok(Self { program })
+    }
+
+    /// Runs the program against an event holding the `sequence` number of the line.
+    ///
+    /// The line is the value the program resolves to, encoded as JSON unless it's a string.
+    pub(super) fn generate_line(&self, n: usize) -> Option<String> {
+        let mut log = LogEvent::default();
+        log.insert("sequence", n);
+        let mut target = VrlTarget::new(Event::Log(log), self.program.info(), false);
+
+        match Runtime::default().resolve(&mut target, &self.program, &TimeZone::default()) {
+            Ok(Value::Bytes(bytes)) => Some(String::from_utf8_lossy(&bytes).into_owned()),
+            Ok(value) => match serde_json::to_string(&value) {
+                Ok(line) => Some(line),
+                Err(error) => {
+                    emit!(DemoLogsVrlGenerationError {
+                        error: error.to_string().as_ref()
+                    });
+                    None
+                }
+            },
+            Err(error) => {
+                emit!(DemoLogsVrlGenerationError {
+                    error: error.to_string().as_ref()
+                });
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(fields: &[(&str, FieldGenerator)]) -> HashMap<String, FieldGenerator> {
+        fields
+            .iter()
+            .map(|(name, generator)| ((*name).to_owned(), generator.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn renders_template() {
+        let generator = TemplateGenerator::new(
+            "{{ n }} {{ method }} {{ status }} {{ method }}",
+            &fields(&[
+                ("n", FieldGenerator::Sequence),
+                (
+                    "method",
+                    FieldGenerator::Choice {
+                        values: vec!["GET".into(), "POST".into()],
+                        weights: vec![1.0, 0.0],
+                    },
+                ),
+                ("status", FieldGenerator::Integer { min: 200, max: 200 }),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(generator.generate_line(3), "3 GET 200 GET");
+    }
+
+    #[test]
+    fn samples_normal_distribution() {
+        let generator = TemplateGenerator::new(
+            "{{ latency }}",
+            &fields(&[(
+                "latency",
+                FieldGenerator::Normal {
+                    mean: 100.0,
+                    std_dev: 0.0,
+                    precision: 2,
+                },
+            )]),
+        )
+        .unwrap();
+
+        assert_eq!(generator.generate_line(0), "100.00");
+    }
+
+    #[test]
+    fn rejects_undefined_fields() {
+        assert!(TemplateGenerator::new("{{ missing }}", &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn rejects_mismatched_weights() {
+        let error = TemplateGenerator::new(
+            "{{ method }}",
+            &fields(&[(
+                "method",
+                FieldGenerator::Choice {
+                    values: vec!["GET".into(), "POST".into()],
+                    weights: vec![1.0],
+                },
+            )]),
+        );
+        assert!(error.is_err());
+    }
+
+    #[test]
+    fn generates_with_vrl() {
+        let generator = VrlGenerator::new(r#"{ "sequence": .sequence, "level": "info" }"#).unwrap();
+        assert_eq!(
+            generator.generate_line(7).unwrap(),
+            r#"{"level":"info","sequence":7}"#
+        );
+
+        let generator = VrlGenerator::new(r#""line " + to_string!(.sequence)"#).unwrap();
+        assert_eq!(generator.generate_line(7).unwrap(), "line 7");
+    }
+}
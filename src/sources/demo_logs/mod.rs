@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use chrono::Utc;
 use codecs::{
     decoding::{DeserializerConfig, FramingConfig},
@@ -26,6 +28,14 @@ use crate::{
     SourceSender,
 };
 
+mod generator;
+mod rate;
+
+pub use generator::FieldGenerator;
+use generator::{TemplateGenerator, VrlGenerator};
+use rate::RateShaper;
+pub use rate::{BurstsConfig, DiurnalConfig, RateConfig};
+
 /// Configuration for the `demo_logs` source.
 #[serde_as]
 #[configurable_component(source("demo_logs"))]
@@ -50,6 +60,11 @@ pub struct DemoLogsConfig {
     #[serde(default = "default_count")]
     pub count: usize,
 
+    /// Shapes the rate of output lines, instead of outputting one line per `interval`.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub rate: Option<RateConfig>,
+
     #[serde(flatten)]
     #[configurable(metadata(
         docs::enum_tag_description = "The format of the randomly generated output."
@@ -84,6 +99,8 @@ const fn default_count() -> usize {
 pub enum DemoLogsConfigError {
     #[snafu(display("A non-empty list of lines is required for the shuffle format"))]
     ShuffleDemoLogsItemsEmpty,
+    #[snafu(display("Invalid `rate` option: {}", reason))]
+    InvalidRate { reason: &'static str },
 }
 
 /// Output format configuration.
@@ -132,26 +149,57 @@ pub enum OutputFormat {
     /// [json]: https://en.wikipedia.org/wiki/JSON
     #[derivative(Default)]
     Json,
+
+    /// Lines rendered from `template`, with the values of its fields generated at random.
+    Template {
+        /// The template of the lines, referencing the fields defined in `fields` as `{{ field }}`.
+        #[configurable(metadata(
+            docs::examples = "{{ client }} - - [{{ time }}] \"{{ method }} /api HTTP/1.1\" {{ status }}"
+        ))]
+        template: String,
+
+        /// The fields referenced by `template`, with the distributions of their values.
+        #[serde(default)]
+        #[configurable(metadata(docs::additional_props_description = "A field of the lines."))]
+        fields: HashMap<String, FieldGenerator>,
+    },
+
+    /// Lines generated by a [Vector Remap Language][vrl] (VRL) program.
+    ///
+    /// The program runs against an event holding the `sequence` number of the line, starting at
+    /// 0, and the line is the value the program resolves to, encoded as JSON unless it's a string.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    Vrl {
+        /// The VRL program.
+        #[configurable(metadata(
+            docs::examples = "{ \"id\": .sequence, \"level\": \"info\", \"latency_ms\": random_int(1, 500) }"
+        ))]
+        source: String,
+    },
 }
 
 const fn lines_example() -> [&'static str; 2] {
     ["line1", "line2"]
 }
 
-impl OutputFormat {
-    fn generate_line(&self, n: usize) -> String {
+/// Generates the lines of an `OutputFormat`.
+enum LineGenerator {
+    Shuffle { sequence: bool, lines: Vec<String> },
+    Fake(fn() -> String),
+    Template(TemplateGenerator),
+    Vrl(VrlGenerator),
+}
+
+impl LineGenerator {
+    fn generate_line(&self, n: usize) -> Option<String> {
         emit!(DemoLogsEventProcessed);
 
         match self {
-            Self::Shuffle {
-                sequence,
-                ref lines,
-            } => Self::shuffle_generate(*sequence, lines, n),
-            Self::ApacheCommon => apache_common_log_line(),
-            Self::ApacheError => apache_error_log_line(),
-            Self::Syslog => syslog_5424_log_line(),
-            Self::BsdSyslog => syslog_3164_log_line(),
-            Self::Json => json_log_line(),
+            Self::Shuffle { sequence, lines } => Some(Self::shuffle_generate(*sequence, lines, n)),
+            Self::Fake(generate) => Some(generate()),
+            Self::Template(generator) => Some(generator.generate_line(n)),
+            Self::Vrl(generator) => generator.generate_line(n),
         }
     }
 
@@ -165,6 +213,28 @@ impl OutputFormat {
             line.into()
         }
     }
+}
+
+impl OutputFormat {
+    fn build(&self) -> crate::Result<LineGenerator> {
+        self.validate()?;
+
+        Ok(match self {
+            Self::Shuffle { sequence, lines } => LineGenerator::Shuffle {
+                sequence: *sequence,
+                lines: lines.clone(),
+            },
+            Self::ApacheCommon => LineGenerator::Fake(apache_common_log_line),
+            Self::ApacheError => LineGenerator::Fake(apache_error_log_line),
+            Self::Syslog => LineGenerator::Fake(syslog_5424_log_line),
+            Self::BsdSyslog => LineGenerator::Fake(syslog_3164_log_line),
+            Self::Json => LineGenerator::Fake(json_log_line),
+            Self::Template { template, fields } => {
+                LineGenerator::Template(TemplateGenerator::new(template, fields)?)
+            }
+            Self::Vrl { source } => LineGenerator::Vrl(VrlGenerator::new(source)?),
+        })
+    }
 
     // Ensures that the `lines` list is non-empty if `Shuffle` is chosen
     pub(self) fn validate(&self) -> Result<(), DemoLogsConfigError> {
@@ -192,6 +262,7 @@ impl DemoLogsConfig {
         Self {
             count,
             interval,
+            rate: None,
             format: OutputFormat::Shuffle {
                 lines,
                 sequence: false,
@@ -203,10 +274,12 @@ impl DemoLogsConfig {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn demo_logs_source(
     interval: Duration,
+    rate: Option<RateConfig>,
     count: usize,
-    format: OutputFormat,
+    generator: LineGenerator,
     decoder: Decoder,
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
@@ -214,6 +287,7 @@ async fn demo_logs_source(
 ) -> Result<(), ()> {
     let interval: Option<Duration> = (interval != Duration::ZERO).then_some(interval);
     let mut interval = interval.map(time::interval);
+    let mut rate = rate.map(RateShaper::new);
 
     let bytes_received = register!(BytesReceived::from(Protocol::NONE));
     let events_received = register!(EventsReceived);
@@ -223,12 +297,16 @@ async fn demo_logs_source(
             break;
         }
 
-        if let Some(interval) = &mut interval {
+        if let Some(rate) = &mut rate {
+            rate.wait().await;
+        } else if let Some(interval) = &mut interval {
             interval.tick().await;
         }
         bytes_received.emit(ByteSize(0));
 
-        let line = format.generate_line(n);
+        let Some(line) = generator.generate_line(n) else {
+            continue;
+        };
 
         let mut stream = FramedRead::new(line.as_bytes(), decoder.clone());
         while let Some(next) = stream.next().await {
@@ -274,13 +352,17 @@ impl SourceConfig for DemoLogsConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
 
-        self.format.validate()?;
+        let generator = self.format.build()?;
+        if let Some(rate) = &self.rate {
+            rate.validate()?;
+        }
         let decoder =
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
         Ok(Box::pin(demo_logs_source(
             self.interval,
+            self.rate.clone(),
             self.count,
-            self.format.clone(),
+            generator,
             decoder,
             cx.shutdown,
             cx.out,
@@ -338,8 +420,9 @@ mod tests {
             .build();
             demo_logs_source(
                 config.interval,
+                config.rate,
                 config.count,
-                config.format,
+                config.format.build().unwrap(),
                 decoder,
                 ShutdownSignal::noop(),
                 tx,
@@ -531,4 +614,70 @@ mod tests {
         }
         assert_eq!(poll!(rx.next()), Poll::Ready(None));
     }
+
+    #[tokio::test]
+    async fn template_format_generates_output() {
+        let message_key = log_schema().message_key();
+        let mut rx = runit(
+            r#"format = "template"
+            template = "{{ n }} {{ method }}"
+            count = 3
+            fields.n.type = "sequence"
+            fields.method = { type = "choice", values = ["GET"] }"#,
+        )
+        .await;
+
+        for n in 0..3 {
+            let event = match poll!(rx.next()) {
+                Poll::Ready(event) => event.unwrap(),
+                _ => unreachable!(),
+            };
+            let log = event.as_log();
+            assert_eq!(log[&message_key].to_string_lossy(), format!("{} GET", n));
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
+
+    #[tokio::test]
+    async fn vrl_format_generates_output() {
+        let message_key = log_schema().message_key();
+        let mut rx = runit(
+            r#"format = "vrl"
+            source = "{ \"id\": .sequence }"
+            count = 2"#,
+        )
+        .await;
+
+        for n in 0..2 {
+            let event = match poll!(rx.next()) {
+                Poll::Ready(event) => event.unwrap(),
+                _ => unreachable!(),
+            };
+            let log = event.as_log();
+            assert_eq!(
+                log[&message_key].to_string_lossy(),
+                format!(r#"{{"id":{}}}"#, n)
+            );
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
+
+    #[tokio::test]
+    async fn rate_overrides_interval() {
+        let start = Instant::now();
+        let mut rx = runit(
+            r#"format = "shuffle"
+               lines = ["one"]
+               count = 10
+               interval = 10.0
+               rate.lines_per_sec = 100.0"#,
+        )
+        .await;
+
+        for _ in 0..10 {
+            assert!(poll!(rx.next()).is_ready());
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
 }
@@ -0,0 +1,248 @@
+use std::f64::consts::PI;
+
+use tokio::time::{self, Duration, Instant, Interval, MissedTickBehavior};
+use vector_config::configurable_component;
+
+use super::DemoLogsConfigError;
+
+/// How often the rate is evaluated.
+const TICK: Duration = Duration::from_millis(10);
+
+/// Rate shaping configuration.
+///
+/// The rate of output lines is the base rate, multiplied by the factors of the ramp-up, the daily
+/// cycle, and the bursts.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct RateConfig {
+    /// The base rate of output lines, in lines per second.
+    #[configurable(metadata(docs::examples = 100.0))]
+    pub lines_per_sec: f64,
+
+    /// The time, in seconds, over which the rate ramps up linearly from zero to the base rate.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = 60.0))]
+    pub ramp_up_secs: Option<f64>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub diurnal: Option<DiurnalConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub bursts: Option<BurstsConfig>,
+}
+
+/// A daily cycle of the rate, starting at its lowest.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct DiurnalConfig {
+    /// The length of the cycle, in seconds.
+    ///
+    /// Shorter cycles make it possible to go through a whole day of traffic in a load test.
+    #[serde(default = "default_period_secs")]
+    #[configurable(metadata(docs::examples = 3600.0))]
+    pub period_secs: f64,
+
+    /// The amplitude of the cycle, relative to the base rate.
+    ///
+    /// The rate varies between `1 - amplitude` and `1 + amplitude` times the base rate, so this
+    /// must be between 0 and 1.
+    #[serde(default = "default_amplitude")]
+    pub amplitude: f64,
+}
+
+const fn default_period_secs() -> f64 {
+    86400.0
+}
+
+const fn default_amplitude() -> f64 {
+    0.5
+}
+
+/// Periodic bursts of the rate, starting with the source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct BurstsConfig {
+    /// The time between the starts of consecutive bursts, in seconds.
+    #[configurable(metadata(docs::examples = 300.0))]
+    pub every_secs: f64,
+
+    /// The length of the bursts, in seconds.
+    #[configurable(metadata(docs::examples = 10.0))]
+    pub duration_secs: f64,
+
+    /// The factor the rate is multiplied by during the bursts.
+    #[configurable(metadata(docs::examples = 10.0))]
+    pub multiplier: f64,
+}
+
+impl RateConfig {
+    pub(super) fn validate(&self) -> Result<(), DemoLogsConfigError> {
+        let invalid = |reason| Err(DemoLogsConfigError::InvalidRate { reason });
+
+        if !(self.lines_per_sec.is_finite() && self.lines_per_sec > 0.0) {
+            return invalid("`lines_per_sec` must be positive");
+        }
+        if matches!(self.ramp_up_secs, Some(secs) if !(secs.is_finite() && secs >= 0.0)) {
+            return invalid("`ramp_up_secs` must not be negative");
+        }
+        if let Some(diurnal) = &self.diurnal {
+            if !(diurnal.period_secs.is_finite() && diurnal.period_secs > 0.0) {
+                return invalid("`diurnal.period_secs` must be positive");
+            }
+            if !(0.0..=1.0).contains(&diurnal.amplitude) {
+                return invalid("`diurnal.amplitude` must be between 0 and 1");
+            }
+        }
+        if let Some(bursts) = &self.bursts {
+            if !(bursts.every_secs.is_finite() && bursts.every_secs > 0.0) {
+                return invalid("`bursts.every_secs` must be positive");
+            }
+            if !(0.0..=bursts.every_secs).contains(&bursts.duration_secs) {
+                return invalid("`bursts.duration_secs` must be between 0 and `bursts.every_secs`");
+            }
+            if !(bursts.multiplier.is_finite() && bursts.multiplier >= 0.0) {
+                return invalid("`bursts.multiplier` must not be negative");
+            }
+        }
+        Ok(())
+    }
+
+    /// The rate of output lines, in lines per second, the given number of seconds after the start.
+    fn rate_at(&self, secs: f64) -> f64 {
+        let mut rate = self.lines_per_sec;
+        if let Some(ramp_up_secs) = self.ramp_up_secs.filter(|secs| *secs > 0.0) {
+            rate *= (secs / ramp_up_secs).min(1.0);
+        }
+        if let Some(diurnal) = &self.diurnal {
+            rate *= 1.0 - diurnal.amplitude * (2.0 * PI * secs / diurnal.period_secs).cos();
+        }
+        if let Some(bursts) = &self.bursts {
+            if secs % bursts.every_secs < bursts.duration_secs {
+                rate *= bursts.multiplier;
+            }
+        }
+        rate
+    }
+}
+
+/// Paces the output lines according to a `RateConfig`.
+pub(super) struct RateShaper {
+    config: RateConfig,
+    start: Instant,
+    last: Instant,
+    /// The number of lines that can be output right away.
+    budget: f64,
+    ticks: Interval,
+}
+
+impl RateShaper {
+    pub(super) fn new(config: RateConfig) -> Self {
+        let start = Instant::now();
+        let mut ticks = time::interval(TICK);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Self {
+            config,
+            start,
+            last: start,
+            budget: 0.0,
+            ticks,
+        }
+    }
+
+    /// Waits until the next line can be output.
+    pub(super) async fn wait(&mut self) {
+        while self.budget < 1.0 {
+            self.ticks.tick().await;
+            let now = Instant::now();
+            let rate = self
+                .config
+                .rate_at(now.duration_since(self.start).as_secs_f64());
+            self.budget += rate * now.duration_since(self.last).as_secs_f64();
+            // Lines that couldn't be output in time, such as when the source is blocked by
+            // backpressure, aren't caught up on for more than a second.
+            self.budget = self.budget.min(rate.max(1.0));
+            self.last = now;
+        }
+        self.budget -= 1.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> RateConfig {
+        RateConfig {
+            lines_per_sec: 100.0,
+            ramp_up_secs: None,
+            diurnal: None,
+            bursts: None,
+        }
+    }
+
+    #[test]
+    fn ramps_up() {
+        let config = RateConfig {
+            ramp_up_secs: Some(10.0),
+            ..config()
+        };
+        assert_eq!(config.rate_at(0.0), 0.0);
+        assert_eq!(config.rate_at(5.0), 50.0);
+        assert_eq!(config.rate_at(20.0), 100.0);
+    }
+
+    #[test]
+    fn follows_daily_cycle() {
+        let config = RateConfig {
+            diurnal: Some(DiurnalConfig {
+                period_secs: 100.0,
+                amplitude: 0.5,
+            }),
+            ..config()
+        };
+        assert!((config.rate_at(0.0) - 50.0).abs() < 1e-9);
+        assert!((config.rate_at(25.0) - 100.0).abs() < 1e-9);
+        assert!((config.rate_at(50.0) - 150.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bursts() {
+        let config = RateConfig {
+            bursts: Some(BurstsConfig {
+                every_secs: 60.0,
+                duration_secs: 10.0,
+                multiplier: 5.0,
+            }),
+            ..config()
+        };
+        assert_eq!(config.rate_at(5.0), 500.0);
+        assert_eq!(config.rate_at(30.0), 100.0);
+        assert_eq!(config.rate_at(65.0), 500.0);
+    }
+
+    #[test]
+    fn rejects_invalid_amplitude() {
+        let config = RateConfig {
+            diurnal: Some(DiurnalConfig {
+                period_secs: 100.0,
+                amplitude: 2.0,
+            }),
+            ..config()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn paces_lines() {
+        let mut shaper = RateShaper::new(config());
+        let start = Instant::now();
+        for _ in 0..100 {
+            shaper.wait().await;
+        }
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(990), "{:?}", elapsed);
+        assert!(elapsed <= Duration::from_millis(1010), "{:?}", elapsed);
+    }
+}
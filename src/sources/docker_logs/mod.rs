@@ -10,11 +10,11 @@ use bollard::{
     Docker,
 };
 use bytes::{Buf, Bytes};
-use chrono::{DateTime, FixedOffset, Local, ParseError, Utc};
+use chrono::{DateTime, FixedOffset, Local, ParseError, TimeZone, Utc};
 use codecs::{BytesDeserializer, BytesDeserializerConfig};
 use futures::{Stream, StreamExt};
 use lookup::{
-    lookup_v2::{parse_value_path, OptionalValuePath},
+    lookup_v2::{parse_value_path, ConfigOwnedValuePath, OptionalValuePath},
     metadata_path, owned_value_path, path, OwnedValuePath, PathPrefix,
 };
 use once_cell::sync::Lazy;
@@ -53,6 +53,17 @@ const CREATED_AT: &str = "container_created_at";
 const NAME: &str = "container_name";
 const STREAM: &str = "stream";
 const CONTAINER: &str = "container_id";
+const ACTION: &str = "action";
+const EXIT_CODE: &str = "exit_code";
+const COMPOSE: &str = "compose";
+// The labels Docker Compose sets on the containers of a project, with the fields they're added as.
+const COMPOSE_LABELS: [(&str, &str); 3] = [
+    ("com.docker.compose.project", "project"),
+    ("com.docker.compose.service", "service"),
+    ("com.docker.compose.container-number", "container_number"),
+];
+// The container events emitted as lifecycle events.
+const LIFECYCLE_ACTIONS: [&str; 3] = ["start", "stop", "die"];
 // Prevent short hostname from being wrongly recognized as a container's short ID.
 const MIN_HOSTNAME_LENGTH: usize = 6;
 
@@ -138,6 +149,35 @@ pub struct DockerLogsConfig {
     #[configurable(metadata(docs::examples = "httpd", docs::examples = "redis",))]
     include_images: Option<Vec<String>>,
 
+    /// A mapping of container labels to the fields their values are added as.
+    ///
+    /// Like the `labels` option of Docker's logging drivers, this promotes the labels used for
+    /// correlation to fields of their own. All labels are still added under `label`.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "example_label_fields()"))]
+    #[configurable(metadata(
+        docs::additional_props_description = "The field the value of the label is added as."
+    ))]
+    label_fields: HashMap<String, ConfigOwnedValuePath>,
+
+    /// Adds the Docker Compose metadata of the containers under `compose`.
+    ///
+    /// The `project`, `service`, and `container_number` fields are read from the labels Docker
+    /// Compose sets on the containers it creates, and are left out for other containers.
+    #[serde(default)]
+    compose_metadata: bool,
+
+    /// Emits the `start`, `stop`, and `die` events of the containers as log events.
+    ///
+    /// The message of these events is the action, which is also added as `action`, and the exit
+    /// code of the container is added as `exit_code` for `die` events. They have the ID, image, and
+    /// name of the container, as well as the label fields and the Docker Compose metadata, so they
+    /// can be correlated with the logs of the container.
+    ///
+    /// The events are subject to the same container filters as the logs.
+    #[serde(default)]
+    lifecycle_events: bool,
+
     /// Overrides the name of the log field used to mark an event as partial.
     ///
     /// If `auto_partial_merge` is disabled, partial events will be emitted with a log field, controlled by this
@@ -178,6 +218,9 @@ impl Default for DockerLogsConfig {
             include_containers: None,
             include_labels: None,
             include_images: None,
+            label_fields: HashMap::new(),
+            compose_metadata: false,
+            lifecycle_events: false,
             partial_event_marker_field: default_partial_event_marker_field(),
             auto_partial_merge: true,
             multiline: None,
@@ -199,6 +242,16 @@ const fn default_retry_backoff_secs() -> Duration {
     Duration::from_secs(2)
 }
 
+fn example_label_fields() -> HashMap<String, String> {
+    HashMap::<_, _>::from_iter([
+        (
+            "com.docker.compose.project".to_string(),
+            "project".to_string(),
+        ),
+        ("com.mycorp.team".to_string(), "team".to_string()),
+    ])
+}
+
 impl DockerLogsConfig {
     fn container_name_or_id_included<'a>(
         &self,
@@ -225,6 +278,40 @@ impl DockerLogsConfig {
                 .any(|name| items.iter().any(|item| name.starts_with(item)))
     }
 
+    /// Adds the label fields and the Docker Compose metadata of the container with the labels.
+    fn insert_label_metadata(
+        &self,
+        log: &mut LogEvent,
+        labels: &HashMap<String, String>,
+        log_namespace: LogNamespace,
+    ) {
+        for (label, field) in &self.label_fields {
+            if let Some(value) = labels.get(label) {
+                log_namespace.insert_source_metadata(
+                    Self::NAME,
+                    log,
+                    Some(LegacyKey::Overwrite(&field.0)),
+                    &field.0,
+                    value.clone(),
+                );
+            }
+        }
+
+        if self.compose_metadata {
+            for (label, field) in COMPOSE_LABELS {
+                if let Some(value) = labels.get(label) {
+                    log_namespace.insert_source_metadata(
+                        Self::NAME,
+                        log,
+                        Some(LegacyKey::Overwrite(path!(COMPOSE, field))),
+                        path!(COMPOSE, field),
+                        value.clone(),
+                    );
+                }
+            }
+        }
+    }
+
     fn with_empty_partial_event_marker_field_as_none(mut self) -> Self {
         if let Some(val) = &self.partial_event_marker_field {
             if val.is_empty() {
@@ -273,8 +360,16 @@ impl SourceConfig for DockerLogsConfig {
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
         let host_key = self.host_key.clone().path.map(LegacyKey::Overwrite);
+        // Lifecycle events don't have the fields specific to the logs.
+        let log_kind = |kind: Kind| {
+            if self.lifecycle_events {
+                kind.or_undefined()
+            } else {
+                kind
+            }
+        };
 
-        let schema_definition = BytesDeserializerConfig
+        let mut schema_definition = BytesDeserializerConfig
             .schema_definition(global_log_namespace.merge(self.log_namespace))
             .with_source_metadata(
                 Self::NAME,
@@ -308,7 +403,7 @@ impl SourceConfig for DockerLogsConfig {
                 Self::NAME,
                 Some(LegacyKey::Overwrite(owned_value_path!(CREATED_AT))),
                 &owned_value_path!(CREATED_AT),
-                Kind::timestamp(),
+                log_kind(Kind::timestamp()),
                 None,
             )
             .with_source_metadata(
@@ -322,7 +417,7 @@ impl SourceConfig for DockerLogsConfig {
                 Self::NAME,
                 Some(LegacyKey::Overwrite(owned_value_path!(STREAM))),
                 &owned_value_path!(STREAM),
-                Kind::bytes(),
+                log_kind(Kind::bytes()),
                 None,
             )
             .with_source_metadata(
@@ -349,6 +444,44 @@ impl SourceConfig for DockerLogsConfig {
                 None,
             );
 
+        for field in self.label_fields.values() {
+            schema_definition = schema_definition.with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(field.0.clone())),
+                &field.0,
+                Kind::bytes().or_undefined(),
+                None,
+            );
+        }
+
+        if self.compose_metadata {
+            schema_definition = schema_definition.with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!(COMPOSE))),
+                &owned_value_path!(COMPOSE),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                None,
+            );
+        }
+
+        if self.lifecycle_events {
+            schema_definition = schema_definition
+                .with_source_metadata(
+                    Self::NAME,
+                    Some(LegacyKey::Overwrite(owned_value_path!(ACTION))),
+                    &owned_value_path!(ACTION),
+                    Kind::bytes().or_undefined(),
+                    None,
+                )
+                .with_source_metadata(
+                    Self::NAME,
+                    Some(LegacyKey::Overwrite(owned_value_path!(EXIT_CODE))),
+                    &owned_value_path!(EXIT_CODE),
+                    Kind::integer().or_undefined(),
+                    None,
+                );
+        }
+
         vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
     }
 
@@ -404,15 +537,17 @@ impl DockerLogsSourceCore {
         // unpause | docker unpause
         // die    | docker restart, docker stop, docker kill, process exited, oom
         // pause  | docker pause
-        filters.insert(
-            "event".to_owned(),
-            vec![
-                "start".to_owned(),
-                "unpause".to_owned(),
-                "die".to_owned(),
-                "pause".to_owned(),
-            ],
-        );
+        let mut actions = vec![
+            "start".to_owned(),
+            "unpause".to_owned(),
+            "die".to_owned(),
+            "pause".to_owned(),
+        ];
+        // stop   | docker stop, docker restart
+        if self.config.lifecycle_events {
+            actions.push("stop".to_owned());
+        }
+        filters.insert("event".to_owned(), actions);
         filters.insert("type".to_owned(), vec!["container".to_owned()]);
 
         // Apply include filters.
@@ -609,6 +744,13 @@ impl DockerLogsSource {
 
                             emit!(DockerLogsContainerEventReceived { container_id: &id, action: &action });
 
+                            if self.esb.core.config.lifecycle_events
+                                && LIFECYCLE_ACTIONS.contains(&action.as_str())
+                            {
+                                self.send_lifecycle_event(&action, &id, event.time_nano, &attributes)
+                                    .await;
+                            }
+
                             let id = ContainerId::new(id.to_owned());
 
                             // Update container status
@@ -658,6 +800,41 @@ impl DockerLogsSource {
         }
     }
 
+    /// Sends the lifecycle event of a container, if the container is included.
+    async fn send_lifecycle_event(
+        &mut self,
+        action: &str,
+        id: &str,
+        time_nano: Option<i64>,
+        attributes: &HashMap<String, String>,
+    ) {
+        let name = attributes.get("name").map(String::as_str);
+        if self.exclude_self(id) || !self.esb.core.config.container_name_or_id_included(id, name) {
+            return;
+        }
+
+        let log_namespace = self.esb.log_namespace;
+        let log = lifecycle_event(
+            &self.esb.core.config,
+            action,
+            id,
+            time_nano,
+            attributes,
+            log_namespace,
+        );
+        let log = add_hostname(log, &self.esb.host_key.path, &self.hostname, log_namespace);
+
+        emit!(DockerLogsEventsReceived {
+            byte_size: log.estimated_json_encoded_size_of(),
+            container_id: id,
+            container_name: name.unwrap_or_default(),
+        });
+
+        if let Err(error) = self.esb.out.send_event(log).await {
+            emit!(StreamClosedError { error, count: 1 });
+        }
+    }
+
     fn exclude_self(&self, id: &str) -> bool {
         self.hostname
             .as_ref()
@@ -757,14 +934,23 @@ impl EventStreamBuilder {
         let events_stream = stream
             .map(|value| {
                 match value {
-                    Ok(message) => Ok(info.new_event(
-                        message,
-                        core.config.partial_event_marker_field.clone(),
-                        core.config.auto_partial_merge,
-                        &mut partial_event_merge_state,
-                        &bytes_received,
-                        self.log_namespace,
-                    )),
+                    Ok(message) => Ok(info
+                        .new_event(
+                            message,
+                            core.config.partial_event_marker_field.clone(),
+                            core.config.auto_partial_merge,
+                            &mut partial_event_merge_state,
+                            &bytes_received,
+                            self.log_namespace,
+                        )
+                        .map(|mut log| {
+                            core.config.insert_label_metadata(
+                                &mut log,
+                                &info.metadata.labels,
+                                self.log_namespace,
+                            );
+                            log
+                        })),
                     Err(error) => {
                         // On any error, restart connection
                         match &error {
@@ -862,6 +1048,94 @@ fn add_hostname(
     log
 }
 
+/// Builds the log event of a container lifecycle event.
+fn lifecycle_event(
+    config: &DockerLogsConfig,
+    action: &str,
+    id: &str,
+    time_nano: Option<i64>,
+    attributes: &HashMap<String, String>,
+    log_namespace: LogNamespace,
+) -> LogEvent {
+    let deserializer = BytesDeserializer::new();
+    let mut log = deserializer.parse_single(Bytes::from(action.to_owned()), log_namespace);
+
+    log_namespace.insert_source_metadata(
+        DockerLogsConfig::NAME,
+        &mut log,
+        Some(LegacyKey::Overwrite(path!(CONTAINER))),
+        path!(CONTAINER),
+        id.to_owned(),
+    );
+    if let Some(image) = attributes.get("image") {
+        log_namespace.insert_source_metadata(
+            DockerLogsConfig::NAME,
+            &mut log,
+            Some(LegacyKey::Overwrite(path!(IMAGE))),
+            path!(IMAGE),
+            image.clone(),
+        );
+    }
+    if let Some(name) = attributes.get("name") {
+        log_namespace.insert_source_metadata(
+            DockerLogsConfig::NAME,
+            &mut log,
+            Some(LegacyKey::Overwrite(path!(NAME))),
+            path!(NAME),
+            name.clone(),
+        );
+    }
+    log_namespace.insert_source_metadata(
+        DockerLogsConfig::NAME,
+        &mut log,
+        Some(LegacyKey::Overwrite(path!(ACTION))),
+        path!(ACTION),
+        action.to_owned(),
+    );
+    if let Some(exit_code) = attributes
+        .get("exitCode")
+        .and_then(|exit_code| exit_code.parse::<i64>().ok())
+    {
+        log_namespace.insert_source_metadata(
+            DockerLogsConfig::NAME,
+            &mut log,
+            Some(LegacyKey::Overwrite(path!(EXIT_CODE))),
+            path!(EXIT_CODE),
+            exit_code,
+        );
+    }
+    // The attributes of container events hold the labels of the container.
+    config.insert_label_metadata(&mut log, attributes, log_namespace);
+
+    log_namespace.insert_vector_metadata(
+        &mut log,
+        path!(log_schema().source_type_key()),
+        path!("source_type"),
+        Bytes::from_static(DockerLogsConfig::NAME.as_bytes()),
+    );
+
+    let timestamp = time_nano.map(|nanos| Utc.timestamp_nanos(nanos));
+    match log_namespace {
+        LogNamespace::Vector => {
+            if let Some(timestamp) = timestamp {
+                log.insert(
+                    metadata_path!(DockerLogsConfig::NAME, "timestamp"),
+                    timestamp,
+                );
+            }
+
+            log.insert(metadata_path!("vector", "ingest_timestamp"), Utc::now());
+        }
+        LogNamespace::Legacy => {
+            if let Some(timestamp) = timestamp {
+                log.try_insert((PathPrefix::Event, log_schema().timestamp_key()), timestamp);
+            }
+        }
+    };
+
+    log
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 enum ErrorPersistence {
     Transient,
@@ -23,6 +23,97 @@ fn exclude_self() {
     assert!(!source.exclude_self("a29d569bd46c"));
 }
 
+#[test]
+fn lifecycle_event_legacy_namespace() {
+    let config = DockerLogsConfig {
+        label_fields: HashMap::from([(
+            "com.mycorp.team".to_owned(),
+            "team".to_owned().try_into().unwrap(),
+        )]),
+        compose_metadata: true,
+        lifecycle_events: true,
+        ..DockerLogsConfig::default()
+    };
+    let attributes = HashMap::from([
+        ("name".to_owned(), "web-1".to_owned()),
+        ("image".to_owned(), "nginx".to_owned()),
+        ("exitCode".to_owned(), "137".to_owned()),
+        ("com.mycorp.team".to_owned(), "payments".to_owned()),
+        ("com.docker.compose.project".to_owned(), "shop".to_owned()),
+        ("com.docker.compose.service".to_owned(), "web".to_owned()),
+    ]);
+
+    let log = lifecycle_event(
+        &config,
+        "die",
+        "451062c59603",
+        Some(1_678_000_000_000_000_000),
+        &attributes,
+        LogNamespace::Legacy,
+    );
+
+    assert_eq!(log[log_schema().message_key()], "die".into());
+    assert_eq!(log[ACTION], "die".into());
+    assert_eq!(log[EXIT_CODE], 137.into());
+    assert_eq!(log[CONTAINER], "451062c59603".into());
+    assert_eq!(log[NAME], "web-1".into());
+    assert_eq!(log[IMAGE], "nginx".into());
+    assert_eq!(log["team"], "payments".into());
+    assert_eq!(log["compose.project"], "shop".into());
+    assert_eq!(log["compose.service"], "web".into());
+    assert!(!log.contains("compose.container_number"));
+    assert_eq!(
+        log[log_schema().timestamp_key()],
+        Utc.timestamp_nanos(1_678_000_000_000_000_000).into()
+    );
+    assert!(!log.contains(STREAM));
+}
+
+#[test]
+fn lifecycle_event_vector_namespace() {
+    let config = DockerLogsConfig {
+        compose_metadata: true,
+        lifecycle_events: true,
+        ..DockerLogsConfig::default()
+    };
+    let attributes = HashMap::from([
+        ("name".to_owned(), "web-1".to_owned()),
+        ("image".to_owned(), "nginx".to_owned()),
+        ("com.docker.compose.project".to_owned(), "shop".to_owned()),
+    ]);
+
+    let log = lifecycle_event(
+        &config,
+        "start",
+        "451062c59603",
+        Some(1_678_000_000_000_000_000),
+        &attributes,
+        LogNamespace::Vector,
+    );
+
+    assert_eq!(log.value(), &"start".into());
+    let meta = log.metadata().value();
+    assert_eq!(
+        meta.get(path!(DockerLogsConfig::NAME, ACTION)).unwrap(),
+        &vrl::value!("start")
+    );
+    assert_eq!(
+        meta.get(path!(DockerLogsConfig::NAME, COMPOSE, "project"))
+            .unwrap(),
+        &vrl::value!("shop")
+    );
+    assert!(meta.get(path!(DockerLogsConfig::NAME, EXIT_CODE)).is_none());
+
+    let schema_definition = config
+        .outputs(LogNamespace::Vector)
+        .first()
+        .unwrap()
+        .log_schema_definition
+        .clone()
+        .unwrap();
+    schema_definition.assert_valid_for_event(&log.into());
+}
+
 #[cfg(all(test, feature = "docker-logs-integration-tests"))]
 mod integration_tests {
     use bollard::{
@@ -1,15 +1,25 @@
-use std::{convert::TryInto, future, path::PathBuf, time::Duration};
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    future,
+    io::{self, Read},
+    path::PathBuf,
+    time::Duration,
+};
 
 use bytes::Bytes;
 use chrono::Utc;
-use codecs::{BytesDeserializer, BytesDeserializerConfig};
+use codecs::{
+    decoding::{format::Deserializer as _, Deserializer, DeserializerConfig},
+    BytesDeserializer, BytesDeserializerConfig,
+};
 use file_source::{
     calculate_ignore_before,
     paths_provider::glob::{Glob, MatchOptions},
     Checkpointer, FileFingerprint, FileServer, FingerprintStrategy, Fingerprinter, Line, ReadFrom,
-    ReadFromConfig,
+    ReadFromConfig, CHECKPOINT_FILE_NAME,
 };
-use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
+use futures::{FutureExt, SinkExt, Stream, StreamExt, TryFutureExt};
 use lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath};
 use regex::bytes::Regex;
 use serde_with::serde_as;
@@ -23,21 +33,25 @@ use vector_core::config::{LegacyKey, LogNamespace};
 
 use super::util::{EncodingConfig, MultilineConfig};
 use crate::{
+    checkpoint_store::CheckpointStoreConfig,
     config::{
         log_schema, DataType, Output, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
     },
     encoding_transcode::{Decoder, Encoder},
-    event::{BatchNotifier, BatchStatus, LogEvent},
+    event::{BatchNotifier, BatchStatus, Event, LogEvent},
     internal_events::{
-        FileBytesReceived, FileEventsReceived, FileOpen, FileSourceInternalEventsEmitter,
-        StreamClosedError,
+        DecoderDeserializeError, FileBytesReceived, FileEventsReceived, FileHeaderReadError,
+        FileOpen, FileSourceInternalEventsEmitter, StreamClosedError,
     },
     line_agg::{self, LineAgg},
-    serde::bool_or_struct,
+    serde::{bool_or_struct, default_decoding},
     shutdown::ShutdownSignal,
     SourceSender,
 };
 
+/// The name of the file checkpoints in the checkpoint store.
+const CHECKPOINT_STORE_KEY: &str = "checkpoints";
+
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("data_dir option required, but not given here or globally"))]
@@ -65,6 +79,18 @@ enum BuildError {
         indicator: String,
         source: regex::Error,
     },
+    #[snafu(display("override pattern {:?} is not a valid glob: {}", pattern, source))]
+    InvalidOverridePattern {
+        pattern: PathBuf,
+        source: glob::PatternError,
+    },
+    #[snafu(display("fingerprint.strategies must not be empty"))]
+    EmptyFingerprintChain,
+    #[cfg(feature = "codecs-parquet")]
+    #[snafu(display(
+        "the parquet codec decodes whole files, but the file source reads files line by line"
+    ))]
+    WholeFileDecoding,
 }
 
 /// Configuration for the `file` source.
@@ -147,6 +173,14 @@ pub struct FileConfig {
     #[configurable(metadata(docs::examples = "/var/local/lib/vector/"))]
     pub data_dir: Option<PathBuf>,
 
+    /// Keeps a copy of the file checkpoint positions in a shared store.
+    ///
+    /// The checkpoints are restored from the store when there are none in the data directory, so
+    /// that an instance replacing another one, such as in an ephemeral or horizontally scaled
+    /// deployment, resumes where the previous instance left off.
+    #[configurable(derived)]
+    pub checkpoint_store: Option<CheckpointStoreConfig>,
+
     /// Enables adding the file offset to each event and sets the name of the log field used.
     ///
     /// The value will be the byte offset of the start of the line within the file.
@@ -198,6 +232,15 @@ pub struct FileConfig {
     #[serde(default)]
     pub multiline: Option<MultilineConfig>,
 
+    /// Decoding options for the files matching specific patterns.
+    ///
+    /// Files matching the patterns of an override are read along with the files matching
+    /// `include`, and are decoded with the options of the first override matching them instead of
+    /// being read as plain text lines. This makes it possible to read files of different formats,
+    /// such as `*.json` and `*.log` files, with a single source.
+    #[serde(default)]
+    pub overrides: Vec<FileOverrideConfig>,
+
     /// An approximate limit on the amount of data read from a single file at a given time.
     #[serde(default = "default_max_read_bytes")]
     #[configurable(metadata(docs::type_unit = "bytes"))]
@@ -236,6 +279,30 @@ pub struct FileConfig {
     log_namespace: Option<bool>,
 }
 
+/// Decoding options for the files matching a set of patterns.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct FileOverrideConfig {
+    /// Array of file patterns the options apply to. [Globbing](https://vector.dev/docs/reference/configuration/sources/file/#globbing) is supported.
+    ///
+    /// The `exclude` option of the source applies to these patterns as well.
+    #[configurable(metadata(docs::examples = "/var/log/**/*.json"))]
+    pub include: Vec<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    pub decoding: DeserializerConfig,
+
+    /// Multiline aggregation configuration of the files.
+    ///
+    /// If not specified, multiline aggregation is disabled for these files, regardless of the
+    /// `multiline` option of the source.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub multiline: Option<MultilineConfig>,
+}
+
 fn default_max_line_bytes() -> usize {
     bytesize::kib(100u64) as usize
 }
@@ -310,6 +377,63 @@ pub enum FingerprintConfig {
     /// [inode]: https://en.wikipedia.org/wiki/Inode
     #[serde(rename = "device_and_inode")]
     DevInode,
+
+    /// Use a chain of strategies, identifying files with the first strategy that succeeds.
+    ///
+    /// Files that can't be identified by the preferred strategies yet, such as files that are too
+    /// short to be checksummed, are identified by the next strategies instead of being skipped.
+    /// Their checkpoints are migrated, and persisted, once a preferred strategy identifies them,
+    /// so they are not read again when their fingerprint changes.
+    ///
+    /// With the default chain, files copied or moved to another filesystem keep their checksum,
+    /// so they are not ingested again.
+    Chain {
+        /// The strategies of the chain, from the preferred one to the last resort.
+        #[serde(default = "default_chain")]
+        strategies: Vec<ChainedFingerprintStrategy>,
+
+        /// The number of bytes to skip ahead (or ignore) when reading the data used for generating the checksum.
+        ///
+        /// This can be helpful if all files share a common header that should be skipped.
+        #[serde(default)]
+        #[configurable(metadata(docs::type_unit = "bytes"))]
+        ignored_header_bytes: usize,
+
+        /// The number of lines to read for generating the checksum.
+        ///
+        /// If the file has less than this amount of lines, it is identified by the next strategies of the chain.
+        #[serde(default = "default_lines")]
+        #[configurable(metadata(docs::type_unit = "lines"))]
+        lines: usize,
+    },
+}
+
+/// A strategy of a fingerprinting chain.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainedFingerprintStrategy {
+    /// Compute a checksum over the first lines of the file.
+    Checksum,
+
+    /// Use the [device and inode][inode] of the file.
+    ///
+    /// [inode]: https://en.wikipedia.org/wiki/Inode
+    DeviceAndInode,
+
+    /// Use the path of the file.
+    ///
+    /// Rotated files that are renamed are identified as new files by this strategy, so it should
+    /// only be the last resort.
+    Path,
+}
+
+fn default_chain() -> Vec<ChainedFingerprintStrategy> {
+    vec![
+        ChainedFingerprintStrategy::Checksum,
+        ChainedFingerprintStrategy::DeviceAndInode,
+        ChainedFingerprintStrategy::Path,
+    ]
 }
 
 impl Default for FingerprintConfig {
@@ -348,6 +472,24 @@ impl From<FingerprintConfig> for FingerprintStrategy {
                 }
             }
             FingerprintConfig::DevInode => FingerprintStrategy::DevInode,
+            FingerprintConfig::Chain {
+                strategies,
+                ignored_header_bytes,
+                lines,
+            } => FingerprintStrategy::Chain(
+                strategies
+                    .into_iter()
+                    .map(|strategy| match strategy {
+                        ChainedFingerprintStrategy::Checksum => FingerprintStrategy::Checksum {
+                            bytes: 256,
+                            ignored_header_bytes,
+                            lines,
+                        },
+                        ChainedFingerprintStrategy::DeviceAndInode => FingerprintStrategy::DevInode,
+                        ChainedFingerprintStrategy::Path => FingerprintStrategy::Path,
+                    })
+                    .collect(),
+            ),
         }
     }
 }
@@ -374,10 +516,12 @@ impl Default for FileConfig {
             host_key: default_host_key(),
             offset_key: None,
             data_dir: None,
+            checkpoint_store: None,
             glob_minimum_cooldown_ms: default_glob_minimum_cooldown_ms(),
             message_start_indicator: None,
             multi_line_timeout: default_multi_line_timeout(), // millis
             multiline: None,
+            overrides: Vec::new(),
             max_read_bytes: default_max_read_bytes(),
             oldest_first: false,
             remove_after_secs: None,
@@ -402,6 +546,19 @@ impl SourceConfig for FileConfig {
             .globals
             // source are only global, name can be used for subdir
             .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let checkpoints_path = data_dir.join(CHECKPOINT_FILE_NAME);
+
+        let checkpoint_store = match &self.checkpoint_store {
+            Some(config) => {
+                let store = config.build(&cx).await?;
+                store
+                    .restore_file(CHECKPOINT_STORE_KEY, &checkpoints_path)
+                    .await
+                    .map_err(|error| format!("Could not restore checkpoints: {}", error))?;
+                Some(store)
+            }
+            None => None,
+        };
 
         // Clippy rule, because async_trait?
         #[allow(clippy::suspicious_else_formatting)]
@@ -414,20 +571,49 @@ impl SourceConfig for FileConfig {
                 Regex::new(indicator)
                     .with_context(|_| InvalidMessageStartIndicatorSnafu { indicator })?;
             }
+
+            #[cfg(feature = "codecs-parquet")]
+            if matches!(self.decoding, DeserializerConfig::Parquet { .. })
+                || self.overrides.iter().any(|file_override| {
+                    matches!(file_override.decoding, DeserializerConfig::Parquet { .. })
+                })
+            {
+                return Err(BuildError::WholeFileDecoding.into());
+            }
+
+            for file_override in &self.overrides {
+                if let Some(ref config) = file_override.multiline {
+                    let _: line_agg::Config = config.try_into()?;
+                }
+                for pattern in &file_override.include {
+                    glob::Pattern::new(&pattern.to_string_lossy())
+                        .with_context(|_| InvalidOverridePatternSnafu { pattern })?;
+                }
+            }
+
+            if let FingerprintConfig::Chain { strategies, .. } = &self.fingerprint {
+                if strategies.is_empty() {
+                    return Err(BuildError::EmptyFingerprintChain.into());
+                }
+            }
         }
 
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
 
         let log_namespace = cx.log_namespace(self.log_namespace);
 
-        Ok(file_source(
+        let source = file_source(
             self,
             data_dir,
             cx.shutdown,
             cx.out,
             acknowledgements,
             log_namespace,
-        ))
+        );
+        Ok(match checkpoint_store {
+            Some(store) => store.mirror_file(CHECKPOINT_STORE_KEY, checkpoints_path, source),
+            None => source,
+        })
     }
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
@@ -440,8 +626,14 @@ impl SourceConfig for FileConfig {
             .and_then(|k| k.path)
             .map(LegacyKey::Overwrite);
 
-        let schema_definition = BytesDeserializerConfig
-            .schema_definition(global_log_namespace.merge(self.log_namespace))
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self.overrides.iter().fold(
+            BytesDeserializerConfig.schema_definition(log_namespace),
+            |definition, file_override| {
+                definition.merge(file_override.decoding.schema_definition(log_namespace))
+            },
+        );
+        let schema_definition = schema_definition
             .with_standard_vector_source_metadata()
             .with_source_metadata(
                 Self::NAME,
@@ -482,7 +674,7 @@ pub fn file_source(
     log_namespace: LogNamespace,
 ) -> super::Source {
     // the include option must be specified but also must contain at least one entry.
-    if config.include.is_empty() {
+    if config.include.is_empty() && config.overrides.is_empty() {
         error!(message = "`include` configuration option must contain at least one file pattern.");
         return Box::pin(future::ready(Err(())));
     }
@@ -495,8 +687,14 @@ pub fn file_source(
         Some(config.read_from),
     );
 
+    let include_with_overrides = config
+        .include
+        .iter()
+        .chain(config.overrides.iter().flat_map(|o| o.include.iter()))
+        .cloned()
+        .collect::<Vec<_>>();
     let paths_provider = Glob::new(
-        &config.include,
+        &include_with_overrides,
         &config.exclude,
         MatchOptions::default(),
         FileSourceInternalEventsEmitter,
@@ -512,6 +710,12 @@ pub fn file_source(
         None => Bytes::from(config.line_delimiter.clone()),
     };
 
+    let header_reader = HeaderReader {
+        line_delimiter: line_delimiter_as_bytes.clone(),
+        max_line_bytes: config.max_line_bytes,
+        encoding_charset,
+    };
+
     let checkpointer = Checkpointer::new(&data_dir);
     let file_server = FileServer {
         paths_provider,
@@ -541,11 +745,41 @@ pub fn file_source(
         offset_key: config.offset_key.clone().and_then(|k| k.path),
     };
 
-    let include = config.include.clone();
+    let include = include_with_overrides;
     let exclude = config.exclude.clone();
     let multiline_config = config.multiline.clone();
     let message_start_indicator = config.message_start_indicator.clone();
     let multi_line_timeout = config.multi_line_timeout;
+    let override_patterns = config
+        .overrides
+        .iter()
+        .map(|file_override| {
+            file_override
+                .include
+                .iter()
+                .map(|pattern| glob::Pattern::new(&pattern.to_string_lossy()).unwrap()) // validated in build
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let override_line_aggs = config
+        .overrides
+        .iter()
+        .map(|file_override| {
+            file_override
+                .multiline
+                .as_ref()
+                .map(|config| config.try_into().unwrap()) // validated in build
+        })
+        .collect::<Vec<Option<line_agg::Config>>>();
+    // The lines of the files matched by no override are read as plain text.
+    let deserializers = std::iter::once(None)
+        .chain(
+            config
+                .overrides
+                .iter()
+                .map(|file_override| Some(file_override.decoding.build())),
+        )
+        .collect::<Vec<_>>();
 
     let (finalizer, shutdown_checkpointer) = if acknowledgements {
         // The shutdown sent in to the finalizer is the global
@@ -597,39 +831,72 @@ pub fn file_source(
                 line
             });
 
-        let messages: Box<dyn Stream<Item = Line> + Send + std::marker::Unpin> =
-            if let Some(ref multiline_config) = multiline_config {
-                wrap_with_line_agg(
-                    rx,
-                    multiline_config.try_into().unwrap(), // validated in build
-                )
-            } else if let Some(msi) = message_start_indicator {
-                wrap_with_line_agg(
-                    rx,
-                    line_agg::Config::for_legacy(
-                        Regex::new(&msi).unwrap(), // validated in build
-                        multi_line_timeout,
-                    ),
+        let line_agg_config = if let Some(ref multiline_config) = multiline_config {
+            Some(multiline_config.try_into().unwrap()) // validated in build
+        } else {
+            message_start_indicator.map(|msi| {
+                line_agg::Config::for_legacy(
+                    Regex::new(&msi).unwrap(), // validated in build
+                    multi_line_timeout,
                 )
-            } else {
-                Box::new(rx)
-            };
+            })
+        };
+
+        // The lines of each override are aggregated separately, with the options of the override.
+        let line_agg_configs = std::iter::once(line_agg_config).chain(override_line_aggs);
+        let messages = futures::stream::select_all(
+            split_by_override(rx, override_patterns)
+                .into_iter()
+                .zip(line_agg_configs)
+                .enumerate()
+                .map(|(index, (lines, line_agg_config))| {
+                    let lines = match line_agg_config {
+                        Some(config) => wrap_with_line_agg(lines, config),
+                        None => lines,
+                    };
+                    lines.map(move |line| (index, line))
+                }),
+        );
 
         // Once file server ends this will run until it has finished processing remaining
         // logs in the queue.
         let span = Span::current();
-        let mut messages = messages.map(move |line| {
-            let mut event = create_event(
-                line.text,
-                line.start_offset,
-                &line.filename,
-                &event_metadata,
-                log_namespace,
-            );
+        let mut file_deserializers = HashMap::new();
+        let mut messages = messages.flat_map(move |(index, line)| {
+            let mut events = match &deserializers[index] {
+                Some(deserializer) => {
+                    let deserializer = file_deserializer(
+                        &mut file_deserializers,
+                        deserializer,
+                        &line,
+                        &header_reader,
+                    );
+                    decode_events(
+                        line.text,
+                        line.start_offset,
+                        &line.filename,
+                        &event_metadata,
+                        deserializer,
+                        log_namespace,
+                    )
+                }
+                None => vec![create_event(
+                    line.text,
+                    line.start_offset,
+                    &line.filename,
+                    &event_metadata,
+                    log_namespace,
+                )],
+            };
 
             if let Some(finalizer) = &finalizer {
+                // Lines without events, such as lines failing to be decoded, are acknowledged
+                // once the notifier is dropped.
                 let (batch, receiver) = BatchNotifier::new_with_receiver();
-                event = event.with_batch_notifier(&batch);
+                events = events
+                    .into_iter()
+                    .map(|event| event.with_batch_notifier(&batch))
+                    .collect();
                 let entry = FinalizerEntry {
                     file_id: line.file_id,
                     offset: line.end_offset,
@@ -638,7 +905,7 @@ pub fn file_source(
             } else {
                 checkpoints.update(line.file_id, line.end_offset);
             }
-            event
+            futures::stream::iter(events)
         });
         tokio::spawn(async move {
             match out
@@ -694,6 +961,48 @@ fn reconcile_position_options(
     }
 }
 
+type LineStream = Box<dyn Stream<Item = Line> + Send + std::marker::Unpin + 'static>;
+
+/// Splits the lines by the first override matching their file.
+///
+/// The first stream holds the lines of the files matched by no override, and is the only one if
+/// there are no overrides.
+fn split_by_override(
+    mut lines: impl Stream<Item = Line> + Send + std::marker::Unpin + 'static,
+    override_patterns: Vec<Vec<glob::Pattern>>,
+) -> Vec<LineStream> {
+    if override_patterns.is_empty() {
+        return vec![Box::new(lines)];
+    }
+
+    let match_options = MatchOptions {
+        require_literal_separator: true,
+        ..MatchOptions::default()
+    };
+    let (mut senders, receivers): (Vec<_>, Vec<_>) = (0..=override_patterns.len())
+        .map(|_| futures::channel::mpsc::channel::<Line>(0))
+        .unzip();
+    tokio::spawn(async move {
+        while let Some(line) = lines.next().await {
+            let index = override_patterns
+                .iter()
+                .position(|patterns| {
+                    patterns
+                        .iter()
+                        .any(|pattern| pattern.matches_with(&line.filename, match_options))
+                })
+                .map_or(0, |index| index + 1);
+            if senders[index].send(line).await.is_err() {
+                break;
+            }
+        }
+    });
+    receivers
+        .into_iter()
+        .map(|receiver| Box::new(receiver) as LineStream)
+        .collect()
+}
+
 fn wrap_with_line_agg(
     rx: impl Stream<Item = Line> + Send + std::marker::Unpin + 'static,
     config: line_agg::Config,
@@ -743,8 +1052,125 @@ fn create_event(
     });
 
     let deserializer = BytesDeserializer::new();
-    let mut event = deserializer.parse_single(line, log_namespace);
+    let event = deserializer.parse_single(line, log_namespace);
+
+    insert_metadata(event, offset, file, meta, log_namespace)
+}
+
+/// Returns the deserializer of the file the line was read from.
+///
+/// Each file has its own deserializer, so that deserializers keeping state for each stream, such as
+/// the CSV deserializer reading the header row, keep it for each file. The deserializers are kept
+/// for as long as the source runs, which is bounded by the number of paths matched by the
+/// overrides.
+fn file_deserializer<'a>(
+    deserializers: &'a mut HashMap<String, Deserializer>,
+    deserializer: &Deserializer,
+    line: &Line,
+    header_reader: &HeaderReader,
+) -> &'a Deserializer {
+    // A line read from the start of the file starts a new stream, as the file is new or truncated.
+    if line.start_offset == 0 {
+        deserializers.remove(&line.filename);
+    }
+    deserializers
+        .entry(line.filename.clone())
+        .or_insert_with(|| {
+            let deserializer = deserializer.clone();
+            if line.start_offset > 0 {
+                // The file is read from a checkpoint, past its header row.
+                header_reader.pass_header(&deserializer, &line.filename);
+            }
+            deserializer
+        })
+}
+
+/// Reads the header row of files read from a checkpoint, for deserializers reading one.
+struct HeaderReader {
+    line_delimiter: Bytes,
+    max_line_bytes: usize,
+    encoding_charset: Option<&'static encoding_rs::Encoding>,
+}
+
+impl HeaderReader {
+    /// Passes the first line of the file to the deserializer, if it awaits the header row.
+    fn pass_header(&self, deserializer: &Deserializer, file: &str) {
+        if !matches!(deserializer, Deserializer::Csv(csv) if csv.awaits_header()) {
+            return;
+        }
+        let header = match self.read_first_line(file) {
+            Ok(Some(header)) => header,
+            Ok(None) => return,
+            Err(error) => {
+                emit!(FileHeaderReadError { file, error });
+                return;
+            }
+        };
+        let header = match self.encoding_charset {
+            Some(charset) => Decoder::new(charset).decode_to_utf8(header),
+            None => header,
+        };
+        // The header row yields no events.
+        if let Err(error) = deserializer.parse(header, LogNamespace::Legacy) {
+            emit!(DecoderDeserializeError { error: &error });
+        }
+    }
+
+    fn read_first_line(&self, file: &str) -> io::Result<Option<Bytes>> {
+        let mut buffer = Vec::new();
+        std::fs::File::open(file)?
+            .take((self.max_line_bytes + self.line_delimiter.len()) as u64)
+            .read_to_end(&mut buffer)?;
+        Ok(buffer
+            .windows(self.line_delimiter.len())
+            .position(|window| window == self.line_delimiter)
+            .map(|end| {
+                buffer.truncate(end);
+                buffer.into()
+            }))
+    }
+}
+
+/// Decodes the line of a file matched by an override into events.
+fn decode_events(
+    line: Bytes,
+    offset: u64,
+    file: &str,
+    meta: &EventMetadata,
+    deserializer: &Deserializer,
+    log_namespace: LogNamespace,
+) -> Vec<LogEvent> {
+    let byte_size = line.len();
+    let events = match deserializer.parse(line, log_namespace) {
+        Ok(events) => events,
+        Err(error) => {
+            emit!(DecoderDeserializeError { error: &error });
+            return Vec::new();
+        }
+    };
+
+    emit!(FileEventsReceived {
+        count: events.len(),
+        file,
+        byte_size,
+    });
 
+    events
+        .into_iter()
+        .filter_map(|event| match event {
+            Event::Log(log) => Some(insert_metadata(log, offset, file, meta, log_namespace)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn insert_metadata(
+    mut event: LogEvent,
+    offset: u64,
+    file: &str,
+    meta: &EventMetadata,
+    log_namespace: LogNamespace,
+) -> LogEvent {
     log_namespace.insert_vector_metadata(
         &mut event,
         log_schema().source_type_key(),
@@ -871,6 +1297,45 @@ mod tests {
         .unwrap();
         assert_eq!(config.fingerprint, FingerprintConfig::DevInode);
 
+        let config: FileConfig = toml::from_str(
+            r#"
+        include = [ "/var/log/**/*.log" ]
+        [fingerprint]
+        strategy = "chain"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.fingerprint,
+            FingerprintConfig::Chain {
+                strategies: default_chain(),
+                ignored_header_bytes: 0,
+                lines: 1,
+            }
+        );
+
+        let config: FileConfig = toml::from_str(
+            r#"
+        include = [ "/var/log/**/*.log" ]
+        [fingerprint]
+        strategy = "chain"
+        strategies = ["checksum", "device_and_inode"]
+        lines = 2
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.fingerprint,
+            FingerprintConfig::Chain {
+                strategies: vec![
+                    ChainedFingerprintStrategy::Checksum,
+                    ChainedFingerprintStrategy::DeviceAndInode,
+                ],
+                ignored_header_bytes: 0,
+                lines: 2,
+            }
+        );
+
         let config: FileConfig = toml::from_str(
             r#"
         include = [ "/var/log/**/*.log" ]
@@ -1151,6 +1616,118 @@ mod tests {
     }
 
     // https://github.com/vectordotdev/vector/issues/8363
+    #[tokio::test]
+    async fn file_overrides() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*.log")],
+            overrides: vec![FileOverrideConfig {
+                include: vec![dir.path().join("*.json")],
+                decoding: DeserializerConfig::Json,
+                multiline: None,
+            }],
+            ..test_default_file_config(&dir)
+        };
+
+        let log_path = dir.path().join("file.log");
+        let json_path = dir.path().join("file.json");
+
+        let received = run_file_source(&config, false, NoAcks, LogNamespace::Legacy, async {
+            let mut log_file = File::create(&log_path).unwrap();
+            let mut json_file = File::create(&json_path).unwrap();
+
+            sleep_500_millis().await; // The files must be observed at their original lengths before writing to them
+
+            writeln!(&mut log_file, r#"{{"message": "plain"}}"#).unwrap();
+            writeln!(
+                &mut json_file,
+                r#"{{"message": "decoded", "level": "info"}}"#
+            )
+            .unwrap();
+            writeln!(&mut json_file, "not json").unwrap();
+
+            sleep_500_millis().await;
+        })
+        .await;
+
+        assert_eq!(received.len(), 2);
+        for event in received {
+            let log = event.as_log();
+            if log["file"].to_string_lossy() == log_path.to_str().unwrap() {
+                assert_eq!(
+                    log[log_schema().message_key()].to_string_lossy(),
+                    r#"{"message": "plain"}"#
+                );
+                assert!(!log.contains("level"));
+            } else {
+                assert_eq!(log["file"].to_string_lossy(), json_path.to_str().unwrap());
+                assert_eq!(log[log_schema().message_key()].to_string_lossy(), "decoded");
+                assert_eq!(log["level"].to_string_lossy(), "info");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn file_overrides_csv_header() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![],
+            overrides: vec![FileOverrideConfig {
+                include: vec![dir.path().join("*.csv")],
+                decoding: DeserializerConfig::Csv {
+                    csv: Default::default(),
+                },
+                multiline: None,
+            }],
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file.csv");
+        let mut file = File::create(&path).unwrap();
+        writeln!(&mut file, "host,status").unwrap();
+        sleep_500_millis().await;
+
+        // Each file has its own header row.
+        {
+            let other_path = dir.path().join("other.csv");
+            let received = run_file_source(&config, true, NoAcks, LogNamespace::Legacy, async {
+                let mut other_file = File::create(&other_path).unwrap();
+                sleep_500_millis().await;
+
+                writeln!(&mut file, "a,200").unwrap();
+                writeln!(&mut other_file, "code").unwrap();
+                writeln!(&mut other_file, "404").unwrap();
+                sleep_500_millis().await;
+            })
+            .await;
+
+            assert_eq!(received.len(), 2);
+            for event in received {
+                let log = event.as_log();
+                if log["file"].to_string_lossy() == path.to_str().unwrap() {
+                    assert_eq!(log["host"].to_string_lossy(), "a");
+                    assert_eq!(log["status"].to_string_lossy(), "200");
+                } else {
+                    assert_eq!(log["code"].to_string_lossy(), "404");
+                }
+            }
+        }
+        // Restart the server, reading the header row of the file read from its checkpoint.
+        {
+            let received = run_file_source(&config, true, NoAcks, LogNamespace::Legacy, async {
+                sleep_500_millis().await;
+                writeln!(&mut file, "b,500").unwrap();
+                sleep_500_millis().await;
+            })
+            .await;
+
+            assert_eq!(received.len(), 1);
+            let log = received[0].as_log();
+            assert_eq!(log["host"].to_string_lossy(), "b");
+            assert_eq!(log["status"].to_string_lossy(), "500");
+        }
+    }
+
     #[tokio::test]
     async fn file_read_empty_lines() {
         let n = 5;
@@ -1527,6 +2104,52 @@ mod tests {
         }
     }
 
+    #[cfg(target_os = "linux")] // see #7988
+    #[tokio::test]
+    async fn file_chain_fingerprint_migrates_checkpoints() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            fingerprint: FingerprintConfig::Chain {
+                strategies: default_chain(),
+                ignored_header_bytes: 0,
+                lines: 2,
+            },
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file");
+        let mut file = File::create(&path).unwrap();
+        writeln!(&mut file, "first line").unwrap();
+        sleep_500_millis().await;
+
+        // The file is too short to be checksummed at first, so it's identified by its device and
+        // inode, then by its checksum once the second line is written, without being read again.
+        {
+            let received = run_file_source(&config, true, Acks, LogNamespace::Legacy, async {
+                sleep_500_millis().await;
+                writeln!(&mut file, "second line").unwrap();
+                sleep_500_millis().await;
+            })
+            .await;
+
+            let lines = extract_messages_string(received);
+            assert_eq!(lines, vec!["first line", "second line"]);
+        }
+        // Restart server, read file from the migrated checkpoint.
+        {
+            let received = run_file_source(&config, true, Acks, LogNamespace::Legacy, async {
+                sleep_500_millis().await;
+                writeln!(&mut file, "third line").unwrap();
+                sleep_500_millis().await;
+            })
+            .await;
+
+            let lines = extract_messages_string(received);
+            assert_eq!(lines, vec!["third line"]);
+        }
+    }
+
     #[tokio::test]
     async fn file_start_position_server_restart_unfinalized() {
         let dir = tempdir().unwrap();
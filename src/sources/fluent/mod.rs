@@ -29,7 +29,7 @@ use crate::{
     internal_events::{FluentMessageDecodeError, FluentMessageReceived},
     serde::bool_or_struct,
     tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsSettings, TlsSourceConfig},
+    tls::{MaybeTlsSettings, ProxyProtocolConfig, TlsSourceConfig},
 };
 
 mod message;
@@ -59,6 +59,9 @@ pub struct FluentConfig {
     #[configurable(derived)]
     tls: Option<TlsSourceConfig>,
 
+    #[configurable(derived)]
+    proxy_protocol: Option<ProxyProtocolConfig>,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -75,6 +78,7 @@ impl GenerateConfig for FluentConfig {
             address: SocketListenAddr::SocketAddr("0.0.0.0:24224".parse().unwrap()),
             keepalive: None,
             tls: None,
+            proxy_protocol: None,
             receive_buffer_bytes: None,
             acknowledgements: Default::default(),
             connection_limit: Some(2),
@@ -103,6 +107,7 @@ impl SourceConfig for FluentConfig {
             shutdown_secs,
             tls,
             tls_client_metadata_key,
+            self.proxy_protocol.as_ref(),
             self.receive_buffer_bytes,
             None,
             cx,
@@ -886,6 +891,7 @@ mod tests {
         let source = FluentConfig {
             address: address.into(),
             tls: None,
+            proxy_protocol: None,
             keepalive: None,
             receive_buffer_bytes: None,
             acknowledgements: true.into(),
@@ -950,6 +956,7 @@ mod tests {
         let config = FluentConfig {
             address: SocketListenAddr::SocketAddr("0.0.0.0:24224".parse().unwrap()),
             tls: None,
+            proxy_protocol: None,
             keepalive: None,
             receive_buffer_bytes: None,
             acknowledgements: false.into(),
@@ -1005,6 +1012,7 @@ mod tests {
         let config = FluentConfig {
             address: SocketListenAddr::SocketAddr("0.0.0.0:24224".parse().unwrap()),
             tls: None,
+            proxy_protocol: None,
             keepalive: None,
             receive_buffer_bytes: None,
             acknowledgements: false.into(),
@@ -1226,6 +1234,7 @@ mod integration_tests {
             FluentConfig {
                 address: address.into(),
                 tls: None,
+                proxy_protocol: None,
                 keepalive: None,
                 receive_buffer_bytes: None,
                 acknowledgements: false.into(),
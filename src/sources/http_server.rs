@@ -1,4 +1,7 @@
-use std::{collections::HashMap, net::SocketAddr};
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+};
 
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
@@ -29,7 +32,7 @@ use crate::{
     register_validatable_component,
     serde::{bool_or_struct, default_decoding},
     sources::util::{
-        http::{add_query_parameters, HttpMethod},
+        http::{add_query_parameters, path_matches, HttpMethod},
         Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
     },
     tls::TlsEnableableConfig,
@@ -136,6 +139,14 @@ pub struct SimpleHttpConfig {
     #[configurable(derived)]
     decoding: Option<DeserializerConfig>,
 
+    /// A list of routes sending the requests to specific URL paths to their own outputs.
+    ///
+    /// The requests are accepted if their URL path matches `path` or the path of a route, and are
+    /// decoded and sent according to the first route their URL path matches. The requests matching
+    /// no route are sent to the default output of the source.
+    #[serde(default)]
+    routes: Vec<HttpRouteConfig>,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -146,12 +157,43 @@ pub struct SimpleHttpConfig {
     log_namespace: Option<bool>,
 }
 
+/// A route of the `http_server` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct HttpRouteConfig {
+    /// The URL path of the requests sent to the route.
+    ///
+    /// The URL path is matched according to `strict_path`, the same as `path`.
+    #[configurable(metadata(docs::examples = "/v1/logs"))]
+    #[configurable(metadata(docs::examples = "/vendor/events"))]
+    path: String,
+
+    /// The name of the output the events of the route are sent to.
+    ///
+    /// The events can be consumed by the components using `<source_id>.<output>` as an input.
+    #[configurable(metadata(docs::examples = "logs"))]
+    output: String,
+
+    /// Framing to use in decoding the requests of the route.
+    ///
+    /// If neither `framing` nor `decoding` is set, the requests are decoded the same as the
+    /// requests sent to the default output of the source.
+    framing: Option<FramingConfig>,
+
+    /// Decoding to use in decoding the requests of the route.
+    ///
+    /// If not set, the `decoding` of the source is used.
+    decoding: Option<DeserializerConfig>,
+}
+
 impl SimpleHttpConfig {
-    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
-    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
-        let mut schema_definition = self
-            .decoding
-            .as_ref()
+    /// Builds the `schema::Definition` for this source using the provided decoding and `LogNamespace`.
+    fn schema_definition(
+        &self,
+        decoding: Option<&DeserializerConfig>,
+        log_namespace: LogNamespace,
+    ) -> Definition {
+        let mut schema_definition = decoding
             .unwrap_or(&default_decoding())
             .schema_definition(log_namespace)
             .with_source_metadata(
@@ -226,6 +268,54 @@ impl SimpleHttpConfig {
             self.log_namespace.unwrap_or(false).into(),
         ))
     }
+
+    fn get_route_decoding_config(&self, route: &HttpRouteConfig) -> crate::Result<DecodingConfig> {
+        if route.framing.is_none() && route.decoding.is_none() {
+            return self.get_decoding_config();
+        }
+
+        let decoding = route
+            .decoding
+            .clone()
+            .or_else(|| self.decoding.clone())
+            .unwrap_or_else(default_decoding);
+        let framing = route
+            .framing
+            .clone()
+            .unwrap_or_else(|| decoding.default_stream_framing());
+
+        Ok(DecodingConfig::new(
+            framing,
+            decoding,
+            self.log_namespace.unwrap_or(false).into(),
+        ))
+    }
+
+    fn build_routes(&self) -> crate::Result<Vec<HttpRoute>> {
+        let mut outputs = HashSet::new();
+        self.routes
+            .iter()
+            .map(|route| {
+                if route.output.is_empty() {
+                    return Err(
+                        format!("The output of the route `{}` is empty.", route.path).into(),
+                    );
+                }
+                if !outputs.insert(route.output.as_str()) {
+                    return Err(format!(
+                        "The output `{}` is used by more than one route.",
+                        route.output
+                    )
+                    .into());
+                }
+                Ok(HttpRoute {
+                    path: route.path.clone(),
+                    output: route.output.clone(),
+                    decoder: self.get_route_decoding_config(route)?.build(),
+                })
+            })
+            .collect()
+    }
 }
 
 impl Default for SimpleHttpConfig {
@@ -243,6 +333,7 @@ impl Default for SimpleHttpConfig {
             strict_path: true,
             framing: None,
             decoding: Some(default_decoding()),
+            routes: Vec::new(),
             acknowledgements: SourceAcknowledgementsConfig::default(),
             log_namespace: None,
         }
@@ -312,13 +403,16 @@ fn remove_duplicates(mut list: Vec<String>, list_name: &str) -> Vec<String> {
 impl SourceConfig for SimpleHttpConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let decoder = self.get_decoding_config()?.build();
+        let routes = self.build_routes()?;
         let log_namespace = cx.log_namespace(self.log_namespace);
 
         let source = SimpleHttpSource {
             headers: remove_duplicates(self.headers.clone(), "headers"),
             query_parameters: remove_duplicates(self.query_parameters.clone(), "query_parameters"),
             path_key: self.path_key.clone(),
+            strict_path: self.strict_path,
             decoder,
+            routes,
             log_namespace,
         };
         source.run(
@@ -338,15 +432,17 @@ impl SourceConfig for SimpleHttpConfig {
         // The source config overrides the global setting and is merged here.
         let log_namespace = global_log_namespace.merge(self.log_namespace);
 
-        let schema_definition = self.schema_definition(log_namespace);
+        let output = |decoding: Option<&DeserializerConfig>| {
+            Output::default(decoding.map(|d| d.output_type()).unwrap_or(DataType::Log))
+                .with_schema_definition(self.schema_definition(decoding, log_namespace))
+        };
 
-        vec![Output::default(
-            self.decoding
-                .as_ref()
-                .map(|d| d.output_type())
-                .unwrap_or(DataType::Log),
-        )
-        .with_schema_definition(schema_definition)]
+        let mut outputs = vec![output(self.decoding.as_ref())];
+        outputs.extend(self.routes.iter().map(|route| {
+            output(route.decoding.as_ref().or(self.decoding.as_ref()))
+                .with_port(route.output.clone())
+        }));
+        outputs
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -358,16 +454,32 @@ impl SourceConfig for SimpleHttpConfig {
     }
 }
 
+#[derive(Clone)]
+struct HttpRoute {
+    path: String,
+    output: String,
+    decoder: Decoder,
+}
+
 #[derive(Clone)]
 struct SimpleHttpSource {
     headers: Vec<String>,
     query_parameters: Vec<String>,
     path_key: OptionalValuePath,
+    strict_path: bool,
     decoder: Decoder,
+    routes: Vec<HttpRoute>,
     log_namespace: LogNamespace,
 }
 
 impl SimpleHttpSource {
+    /// Returns the first route matching the `request_path`, if any.
+    fn route(&self, request_path: &str) -> Option<&HttpRoute> {
+        self.routes
+            .iter()
+            .find(|route| path_matches(&route.path, request_path, self.strict_path))
+    }
+
     /// Enriches the passed in events with metadata for the `request_path` and for each of the headers.
     fn enrich_events(
         &self,
@@ -431,7 +543,10 @@ impl HttpSource for SimpleHttpSource {
         query_parameters: HashMap<String, String>,
         request_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        let mut decoder = self.decoder.clone();
+        let mut decoder = self
+            .route(request_path)
+            .map_or(&self.decoder, |route| &route.decoder)
+            .clone();
         let mut events = Vec::new();
         let mut bytes = BytesMut::new();
         bytes.extend_from_slice(&body);
@@ -457,6 +572,14 @@ impl HttpSource for SimpleHttpSource {
 
         Ok(events)
     }
+
+    fn route_paths(&self) -> Vec<String> {
+        self.routes.iter().map(|route| route.path.clone()).collect()
+    }
+
+    fn route_output(&self, request_path: &str) -> Option<String> {
+        self.route(request_path).map(|route| route.output.clone())
+    }
 }
 
 #[cfg(test)]
@@ -478,16 +601,16 @@ mod tests {
         write::{GzEncoder, ZlibEncoder},
         Compression,
     };
-    use futures::Stream;
+    use futures::{Stream, StreamExt};
     use http::{HeaderMap, Method};
     use lookup::lookup_v2::OptionalValuePath;
     use similar_asserts::assert_eq;
 
-    use super::{remove_duplicates, SimpleHttpConfig};
+    use super::{remove_duplicates, HttpRouteConfig, SimpleHttpConfig};
     use crate::sources::http_server::HttpMethod;
     use crate::{
         config::{log_schema, SourceConfig, SourceContext},
-        event::{Event, EventStatus, Value},
+        event::{into_event_stream, Event, EventStatus, Value},
         test_util::{
             components::{self, assert_source_compliance, HTTP_PUSH_SOURCE_TAGS},
             next_addr, spawn_collect_n, wait_for_tcp,
@@ -538,6 +661,7 @@ mod tests {
                 method,
                 framing,
                 decoding,
+                routes: Vec::new(),
                 acknowledgements: acknowledgements.into(),
                 log_namespace: None,
             }
@@ -1199,6 +1323,80 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn http_routes() {
+        components::init_test();
+        let (mut sender, recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let logs = sender
+            .add_outputs(EventStatus::Delivered, "logs".to_string())
+            .flat_map(into_event_stream);
+        let address = next_addr();
+        let config = SimpleHttpConfig {
+            address,
+            strict_path: false,
+            routes: vec![HttpRouteConfig {
+                path: "/v1/logs".to_owned(),
+                output: "logs".to_owned(),
+                framing: None,
+                decoding: Some(JsonDeserializerConfig::new().into()),
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.outputs(LogNamespace::Legacy)[1].port,
+            Some("logs".to_owned())
+        );
+        let source = config
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let logs = spawn_collect_n(
+            async move {
+                assert_eq!(
+                    200,
+                    send_with_path(address, "{\"key1\":\"value1\"}", "/v1/logs").await
+                );
+            },
+            logs,
+            1,
+        )
+        .await;
+        assert_eq!(logs[0].as_log()["key1"], "value1".into());
+        assert_eq!(logs[0].as_log()["path"], "/v1/logs".into());
+
+        let events = spawn_collect_n(
+            async move {
+                assert_eq!(200, send_with_path(address, "test body", "/other").await);
+            },
+            recv,
+            1,
+        )
+        .await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "test body".into()
+        );
+    }
+
+    #[test]
+    fn routes_with_duplicate_outputs() {
+        let route = HttpRouteConfig {
+            path: "/v1/logs".to_owned(),
+            output: "logs".to_owned(),
+            framing: None,
+            decoding: None,
+        };
+        let config = SimpleHttpConfig {
+            routes: vec![route.clone(), route],
+            ..Default::default()
+        };
+
+        assert!(config.build_routes().is_err());
+    }
+
     #[tokio::test]
     async fn http_delivery_failure() {
         assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
@@ -1,16 +1,20 @@
 use std::time::Duration;
 
 use futures::StreamExt;
+use glob::Pattern;
+use indexmap::IndexMap;
 use serde_with::serde_as;
 use tokio::time;
 use tokio_stream::wrappers::IntervalStream;
 use vector_common::internal_event::{CountByteSize, InternalEventHandle as _};
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
+use vector_core::event::metric::{Bucket, MetricValue};
 use vector_core::EstimatedJsonEncodedSizeOf;
 
 use crate::{
     config::{log_schema, DataType, Output, SourceConfig, SourceContext},
+    event::Metric,
     internal_events::{EventsReceived, InternalMetricsBytesReceived, StreamClosedError},
     metrics::Controller,
     shutdown::ShutdownSignal,
@@ -34,6 +38,43 @@ pub struct InternalMetricsConfig {
     /// Overrides the default namespace for the metrics emitted by the source.
     #[serde(default = "default_namespace")]
     pub namespace: String,
+
+    /// The names of the metrics to emit.
+    ///
+    /// Names can hold [glob patterns][glob_patterns], such as `*`. If empty, all metrics are
+    /// emitted, except those matching `exclude_metrics`.
+    ///
+    /// [glob_patterns]: https://en.wikipedia.org/wiki/Glob_(programming)
+    #[configurable(metadata(docs::examples = "component_*_total"))]
+    #[configurable(metadata(docs::examples = "buffer_byte_size"))]
+    pub include_metrics: Vec<String>,
+
+    /// The names of the metrics not to emit.
+    ///
+    /// Names can hold glob patterns, such as `*`. Metrics matching both `include_metrics` and
+    /// `exclude_metrics` aren't emitted.
+    #[configurable(metadata(docs::examples = "*_seconds"))]
+    pub exclude_metrics: Vec<String>,
+
+    /// The tags to aggregate the metrics over.
+    ///
+    /// These tags are removed from the metrics, and the metrics which only differed by them are
+    /// merged: counters and gauges are summed, and histograms are combined. This reduces the
+    /// cardinality of the metrics of topologies holding many components.
+    #[configurable(metadata(docs::examples = "component_id"))]
+    #[configurable(metadata(docs::examples = "component_name"))]
+    pub aggregate_tags: Vec<String>,
+
+    /// The upper limits of the buckets of histograms.
+    ///
+    /// Internal histograms are recorded in buckets whose upper limits are powers of two, from
+    /// 2^-6 to 2^12. If set, the count of each of these buckets is added to the first
+    /// configured bucket whose upper limit is greater or equal, so limits which are powers of two
+    /// keep the histograms exact. A bucket without upper limit is always added.
+    ///
+    /// If not set, the internal buckets are emitted as is.
+    #[configurable(metadata(docs::examples = 0.25, docs::examples = 1.0, docs::examples = 4.0))]
+    pub histogram_buckets: Vec<f64>,
 }
 
 impl Default for InternalMetricsConfig {
@@ -42,6 +83,10 @@ impl Default for InternalMetricsConfig {
             scrape_interval_secs: default_scrape_interval(),
             tags: TagsConfig::default(),
             namespace: default_namespace(),
+            include_metrics: Vec::new(),
+            exclude_metrics: Vec::new(),
+            aggregate_tags: Vec::new(),
+            histogram_buckets: Vec::new(),
         }
     }
 }
@@ -94,6 +139,118 @@ fn default_host_key() -> String {
 
 impl_generate_config_from_default!(InternalMetricsConfig);
 
+impl InternalMetricsConfig {
+    fn build_processor(&self) -> crate::Result<MetricsProcessor> {
+        let patterns = |names: &[String]| {
+            names
+                .iter()
+                .map(|name| {
+                    Pattern::new(name).map_err(|error| {
+                        format!("Invalid metric name pattern `{}`: {}", name, error)
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        if self.histogram_buckets.iter().any(|limit| limit.is_nan())
+            || self
+                .histogram_buckets
+                .windows(2)
+                .any(|limits| limits[0] >= limits[1])
+        {
+            return Err("`histogram_buckets` must be sorted in increasing order".into());
+        }
+
+        Ok(MetricsProcessor {
+            include: patterns(&self.include_metrics)?,
+            exclude: patterns(&self.exclude_metrics)?,
+            aggregate_tags: self.aggregate_tags.clone(),
+            histogram_buckets: self.histogram_buckets.clone(),
+        })
+    }
+}
+
+/// Selects, aggregates, and re-buckets the captured metrics.
+#[derive(Debug)]
+struct MetricsProcessor {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    aggregate_tags: Vec<String>,
+    histogram_buckets: Vec<f64>,
+}
+
+impl MetricsProcessor {
+    fn process(&self, metrics: Vec<Metric>) -> Vec<Metric> {
+        let metrics = metrics.into_iter().filter(|metric| {
+            let name = metric.name();
+            (self.include.is_empty() || self.include.iter().any(|pattern| pattern.matches(name)))
+                && !self.exclude.iter().any(|pattern| pattern.matches(name))
+        });
+
+        let metrics: Vec<Metric> = if self.aggregate_tags.is_empty() {
+            metrics.collect()
+        } else {
+            let mut aggregated = IndexMap::<_, Metric>::new();
+            let mut unmergeable = Vec::new();
+            for mut metric in metrics {
+                for tag in &self.aggregate_tags {
+                    metric.remove_tag(tag);
+                }
+                match aggregated.entry(metric.series().clone()) {
+                    indexmap::map::Entry::Vacant(entry) => {
+                        entry.insert(metric);
+                    }
+                    indexmap::map::Entry::Occupied(mut entry) => {
+                        if !entry.get_mut().value_mut().add(metric.value()) {
+                            unmergeable.push(metric);
+                        }
+                    }
+                }
+            }
+            aggregated.into_values().chain(unmergeable).collect()
+        };
+
+        if self.histogram_buckets.is_empty() {
+            return metrics;
+        }
+        metrics
+            .into_iter()
+            .map(|mut metric| {
+                if let MetricValue::AggregatedHistogram { buckets, .. } = metric.value_mut() {
+                    *buckets = self.rebucket(buckets);
+                }
+                metric
+            })
+            .collect()
+    }
+
+    /// Adds the count of each bucket to the first configured bucket whose upper limit is greater
+    /// or equal.
+    fn rebucket(&self, buckets: &[Bucket]) -> Vec<Bucket> {
+        let mut rebucketed = self
+            .histogram_buckets
+            .iter()
+            .chain(
+                (self.histogram_buckets.last() != Some(&f64::INFINITY)).then_some(&f64::INFINITY),
+            )
+            .map(|upper_limit| Bucket {
+                upper_limit: *upper_limit,
+                count: 0,
+            })
+            .collect::<Vec<_>>();
+
+        for bucket in buckets {
+            if let Some(target) = rebucketed
+                .iter_mut()
+                .find(|target| target.upper_limit >= bucket.upper_limit)
+            {
+                target.count += bucket.count;
+            }
+        }
+        rebucketed
+    }
+}
+
 #[async_trait::async_trait]
 impl SourceConfig for InternalMetricsConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
@@ -118,11 +275,14 @@ impl SourceConfig for InternalMetricsConfig {
             .as_deref()
             .and_then(|tag| (!tag.is_empty()).then(|| tag.to_owned()));
 
+        let processor = self.build_processor()?;
+
         Ok(Box::pin(
             InternalMetrics {
                 namespace,
                 host_key,
                 pid_key,
+                processor,
                 controller: Controller::get()?,
                 interval,
                 out: cx.out,
@@ -145,6 +305,7 @@ struct InternalMetrics<'a> {
     namespace: String,
     host_key: Option<String>,
     pid_key: Option<String>,
+    processor: MetricsProcessor,
     controller: &'a Controller,
     interval: time::Duration,
     out: SourceSender,
@@ -160,7 +321,7 @@ impl<'a> InternalMetrics<'a> {
             let hostname = crate::get_hostname();
             let pid = std::process::id().to_string();
 
-            let metrics = self.controller.capture_metrics();
+            let metrics = self.processor.process(self.controller.capture_metrics());
             let count = metrics.len();
             let byte_size = metrics.estimated_json_encoded_size_of();
 
@@ -205,7 +366,7 @@ mod tests {
     use super::*;
     use crate::{
         event::{
-            metric::{Metric, MetricValue},
+            metric::{Metric, MetricKind, MetricValue},
             Event,
         },
         metrics::Controller,
@@ -335,6 +496,87 @@ mod tests {
         assert!(metric.tag_value("pid").is_none());
     }
 
+    fn counter(name: &str, component_id: &str, value: f64) -> Metric {
+        Metric::new(name, MetricKind::Absolute, MetricValue::Counter { value }).with_tags(Some(
+            metric_tags!("component_id" => component_id, "host" => "a"),
+        ))
+    }
+
+    #[test]
+    fn selects_metrics() {
+        let config = InternalMetricsConfig {
+            include_metrics: vec!["component_*".to_owned()],
+            exclude_metrics: vec!["*_errors_total".to_owned()],
+            ..Default::default()
+        };
+
+        let output = config.build_processor().unwrap().process(vec![
+            counter("component_received_events_total", "in", 1.0),
+            counter("component_errors_total", "in", 1.0),
+            counter("buffer_events", "in", 1.0),
+        ]);
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].name(), "component_received_events_total");
+    }
+
+    #[test]
+    fn aggregates_tags() {
+        let config = InternalMetricsConfig {
+            aggregate_tags: vec!["component_id".to_owned()],
+            ..Default::default()
+        };
+
+        let output = config.build_processor().unwrap().process(vec![
+            counter("component_received_events_total", "in", 1.0),
+            counter("component_received_events_total", "out", 2.0),
+            counter("component_sent_events_total", "out", 4.0),
+        ]);
+
+        assert_eq!(output.len(), 2);
+        assert_eq!(output[0].value(), &MetricValue::Counter { value: 3.0 });
+        assert_eq!(output[0].tags(), Some(&metric_tags!("host" => "a")));
+        assert_eq!(output[1].value(), &MetricValue::Counter { value: 4.0 });
+    }
+
+    #[test]
+    fn rebuckets_histograms() {
+        let config = InternalMetricsConfig {
+            histogram_buckets: vec![1.0, 8.0],
+            ..Default::default()
+        };
+        let histogram = Metric::new(
+            "latency_seconds",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vector_core::buckets![0.5 => 1, 1.0 => 2, 4.0 => 3, 16.0 => 4],
+                count: 10,
+                sum: 50.0,
+            },
+        );
+
+        let output = config.build_processor().unwrap().process(vec![histogram]);
+
+        assert_eq!(
+            output[0].value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vector_core::buckets![1.0 => 3, 8.0 => 3, f64::INFINITY => 4],
+                count: 10,
+                sum: 50.0,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unsorted_histogram_buckets() {
+        let config = InternalMetricsConfig {
+            histogram_buckets: vec![8.0, 1.0],
+            ..Default::default()
+        };
+
+        assert!(config.build_processor().is_err());
+    }
+
     #[tokio::test]
     async fn namespace() {
         let namespace = "totally_custom";
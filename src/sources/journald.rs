@@ -43,6 +43,7 @@ use vector_core::{
 };
 
 use crate::{
+    checkpoint_store::CheckpointStoreConfig,
     config::{
         log_schema, DataType, Output, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
     },
@@ -60,6 +61,7 @@ use crate::{
 const BATCH_TIMEOUT: Duration = Duration::from_millis(10);
 
 const CHECKPOINT_FILENAME: &str = "checkpoint.txt";
+const CHECKPOINT_STORE_KEY: &str = "checkpoint";
 const CURSOR: &str = "__CURSOR";
 const HOSTNAME: &str = "_HOSTNAME";
 const MESSAGE: &str = "MESSAGE";
@@ -153,6 +155,14 @@ pub struct JournaldConfig {
     #[configurable(metadata(docs::examples = "/var/lib/vector"))]
     pub data_dir: Option<PathBuf>,
 
+    /// Keeps a copy of the journal checkpoint in a shared store.
+    ///
+    /// The checkpoint is restored from the store when there is none in the data directory, so that
+    /// an instance replacing another one, such as in an ephemeral deployment, resumes where the
+    /// previous instance left off.
+    #[configurable(derived)]
+    pub checkpoint_store: Option<CheckpointStoreConfig>,
+
     /// The systemd journal is read in batches, and a checkpoint is set at the end of each batch.
     ///
     /// This option limits the size of the batch.
@@ -285,6 +295,7 @@ impl Default for JournaldConfig {
             include_matches: Default::default(),
             exclude_matches: Default::default(),
             data_dir: None,
+            checkpoint_store: None,
             batch_size: default_batch_size(),
             journalctl_path: None,
             journal_directory: None,
@@ -330,6 +341,18 @@ impl SourceConfig for JournaldConfig {
         let mut checkpoint_path = data_dir;
         checkpoint_path.push(CHECKPOINT_FILENAME);
 
+        let checkpoint_store = match &self.checkpoint_store {
+            Some(config) => {
+                let store = config.build(&cx).await?;
+                store
+                    .restore_file(CHECKPOINT_STORE_KEY, &checkpoint_path)
+                    .await
+                    .map_err(|error| format!("Could not restore the checkpoint: {}", error))?;
+                Some(store)
+            }
+            None => None,
+        };
+
         let journalctl_path = self
             .journalctl_path
             .clone()
@@ -346,11 +369,11 @@ impl SourceConfig for JournaldConfig {
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
         let log_namespace = cx.log_namespace(self.log_namespace);
 
-        Ok(Box::pin(
+        let source: super::Source = Box::pin(
             JournaldSource {
                 include_matches,
                 exclude_matches,
-                checkpoint_path,
+                checkpoint_path: checkpoint_path.clone(),
                 batch_size,
                 remap_priority: self.remap_priority,
                 out: cx.out,
@@ -359,7 +382,11 @@ impl SourceConfig for JournaldConfig {
                 log_namespace,
             }
             .run_shutdown(cx.shutdown),
-        ))
+        );
+        Ok(match checkpoint_store {
+            Some(store) => store.mirror_file(CHECKPOINT_STORE_KEY, checkpoint_path, source),
+            None => source,
+        })
     }
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
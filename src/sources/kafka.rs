@@ -9,14 +9,17 @@ use async_stream::stream;
 use bytes::Bytes;
 use chrono::{DateTime, TimeZone, Utc};
 use codecs::{
-    decoding::{DeserializerConfig, FramingConfig},
+    decoding::{
+        AvroDeserializerRegistryOptions, AvroEncodingFormat, AvroSchemaCache, Deserializer,
+        DeserializerConfig, FramingConfig,
+    },
     StreamDecodingError,
 };
-use futures::{Stream, StreamExt};
+use futures::{FutureExt, Stream, StreamExt};
 use lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath};
 use once_cell::sync::OnceCell;
 use rdkafka::{
-    consumer::{CommitMode, Consumer, ConsumerContext, Rebalance, StreamConsumer},
+    consumer::{BaseConsumer, CommitMode, Consumer, ConsumerContext, Rebalance, StreamConsumer},
     message::{BorrowedMessage, Headers as _, Message},
     ClientConfig, ClientContext, Statistics,
 };
@@ -35,16 +38,18 @@ use vector_core::{
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{
-        log_schema, LogSchema, Output, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
+        log_schema, LogSchema, Output, ProxyConfig, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext,
     },
     event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{
         KafkaBytesReceived, KafkaEventsReceived, KafkaOffsetUpdateError, KafkaReadError,
-        StreamClosedError,
+        KafkaSchemaRegistryError, StreamClosedError,
     },
-    kafka,
+    kafka::{self, schema_registry::fetch_schema_by_id},
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
+    sinks::Healthcheck,
     SourceSender,
 };
 
@@ -54,6 +59,8 @@ enum BuildError {
     KafkaCreateError { source: rdkafka::error::KafkaError },
     #[snafu(display("Could not subscribe to Kafka topics: {}", source))]
     KafkaSubscribeError { source: rdkafka::error::KafkaError },
+    #[snafu(display("The Avro schema registry requires the `confluent` format"))]
+    SchemaRegistryFormat,
 }
 
 /// Metrics configuration.
@@ -287,15 +294,23 @@ impl SourceConfig for KafkaSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
 
+        if let DeserializerConfig::Avro { avro } = &self.decoding {
+            if avro.schema_registry.is_some() && avro.format != AvroEncodingFormat::Confluent {
+                return Err(BuildError::SchemaRegistryFormat.into());
+            }
+        }
+
         let consumer = create_consumer(self)?;
         let decoder =
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+        let schema_registry = SchemaRegistryLookup::new(&self.decoding, &decoder, &cx.proxy);
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
 
         Ok(Box::pin(kafka_source(
             self.clone(),
             consumer,
             decoder,
+            schema_registry,
             cx.shutdown,
             cx.out,
             acknowledgements,
@@ -360,12 +375,75 @@ impl SourceConfig for KafkaSourceConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn healthcheck(&self, _proxy: &ProxyConfig) -> Option<Healthcheck> {
+        let config = self.clone();
+        Some(
+            async move {
+                // Fetching the metadata of the cluster checks both the connection and the
+                // credentials, without joining the consumer group.
+                tokio::task::spawn_blocking(move || -> crate::Result<()> {
+                    let consumer: BaseConsumer =
+                        client_config(&config)?.create().context(KafkaCreateSnafu)?;
+                    consumer.fetch_metadata(None, config.socket_timeout_ms)?;
+                    Ok(())
+                })
+                .await?
+            }
+            .boxed(),
+        )
+    }
+}
+
+/// Fetches the Avro schemas referenced by the messages from the schema registry.
+#[derive(Clone)]
+struct SchemaRegistryLookup {
+    options: AvroDeserializerRegistryOptions,
+    cache: AvroSchemaCache,
+    proxy: ProxyConfig,
+}
+
+impl SchemaRegistryLookup {
+    fn new(decoding: &DeserializerConfig, decoder: &Decoder, proxy: &ProxyConfig) -> Option<Self> {
+        match (decoding, decoder.deserializer()) {
+            (DeserializerConfig::Avro { avro }, Deserializer::Avro(deserializer)) => Some(Self {
+                options: avro.schema_registry.clone()?,
+                cache: deserializer.schema_cache()?.clone(),
+                proxy: proxy.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Caches the schema referenced by the message, if it isn't cached yet.
+    ///
+    /// Messages whose schema can't be fetched fail to decode, which is reported by the decoder.
+    async fn fetch_missing_schema(&self, payload: &[u8]) {
+        if let Some(schema_id) = self.cache.missing_schema_id(payload) {
+            let result = fetch_schema_by_id(
+                &self.options.url,
+                self.options.auth.as_ref(),
+                schema_id,
+                &self.proxy,
+            )
+            .await
+            .and_then(|schema| self.cache.insert(schema_id, &schema));
+            match result {
+                Ok(()) => debug!(
+                    message = "Fetched the Avro schema from the schema registry.",
+                    schema_id,
+                ),
+                Err(error) => emit!(KafkaSchemaRegistryError { error, schema_id }),
+            }
+        }
+    }
 }
 
 async fn kafka_source(
     config: KafkaSourceConfig,
     consumer: StreamConsumer<CustomContext>,
     decoder: Decoder,
+    schema_registry: Option<SchemaRegistryLookup>,
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
     acknowledgements: bool,
@@ -408,6 +486,10 @@ async fn kafka_source(
                         partition: msg.partition(),
                     });
 
+                    if let (Some(schema_registry), Some(payload)) = (&schema_registry, msg.payload()) {
+                        schema_registry.fetch_missing_schema(payload).await;
+                    }
+
                     parse_message(msg, decoder.clone(), config.keys(), &finalizer, &mut out, &consumer, log_namespace).await;
                 }
             },
@@ -664,7 +746,7 @@ impl<'a> From<BorrowedMessage<'a>> for FinalizerEntry {
     }
 }
 
-fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer<CustomContext>> {
+fn client_config(config: &KafkaSourceConfig) -> crate::Result<ClientConfig> {
     let mut client_config = ClientConfig::new();
     client_config
         .set("group.id", &config.group_id)
@@ -700,7 +782,11 @@ fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer<C
         }
     }
 
-    let consumer = client_config
+    Ok(client_config)
+}
+
+fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer<CustomContext>> {
+    let consumer = client_config(config)?
         .create_with_context::<_, StreamConsumer<_>>(CustomContext::new(
             config.metrics.topic_lag_metric,
         ))
@@ -1145,6 +1231,7 @@ mod integration_test {
             config,
             consumer,
             decoder,
+            None,
             shutdown,
             tx,
             acknowledgements,
@@ -5,14 +5,14 @@
 
 #![deny(missing_docs)]
 
-use std::{path::PathBuf, time::Duration};
+use std::{path::PathBuf, pin::Pin, sync::Arc, time::Duration};
 
 use bytes::Bytes;
 use chrono::Utc;
 use codecs::{BytesDeserializer, BytesDeserializerConfig};
 use file_source::{
     calculate_ignore_before, Checkpointer, FileServer, FileServerShutdown, FingerprintStrategy,
-    Fingerprinter, Line, ReadFrom, ReadFromConfig,
+    Fingerprinter, Line, ReadFrom, ReadFromConfig, CHECKPOINT_FILE_NAME,
 };
 use futures::{future::FutureExt, stream::StreamExt};
 use futures_util::Stream;
@@ -41,6 +41,7 @@ use vector_core::{
 };
 
 use crate::{
+    checkpoint_store::CheckpointStoreConfig,
     config::{
         log_schema, ComponentKey, DataType, GenerateConfig, GlobalOptions, Output, SourceConfig,
         SourceContext,
@@ -67,6 +68,7 @@ mod parser;
 mod partial_events_merger;
 mod path_helpers;
 mod pod_metadata_annotator;
+mod quotas;
 mod transform_utils;
 mod util;
 
@@ -74,6 +76,7 @@ use self::namespace_metadata_annotator::NamespaceMetadataAnnotator;
 use self::node_metadata_annotator::NodeMetadataAnnotator;
 use self::parser::Parser;
 use self::pod_metadata_annotator::PodMetadataAnnotator;
+use self::quotas::{Quotas, QuotasConfig};
 
 /// The key we use for `file` field.
 const FILE_KEY: &str = "file";
@@ -145,6 +148,14 @@ pub struct Config {
     #[configurable(metadata(docs::examples = "/var/local/lib/vector/"))]
     data_dir: Option<PathBuf>,
 
+    /// Keeps a copy of the file checkpoint positions in a shared store.
+    ///
+    /// The checkpoints are kept separately for each Node, and restored from the store when there
+    /// are none in the data directory, so that a replacement Vector Pod on the same Node resumes
+    /// where the previous one left off.
+    #[configurable(derived)]
+    checkpoint_store: Option<CheckpointStoreConfig>,
+
     #[configurable(derived)]
     #[serde(alias = "annotation_fields")]
     pod_annotation_fields: pod_metadata_annotator::FieldsSpec,
@@ -228,6 +239,9 @@ pub struct Config {
     #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
     delay_deletion_ms: Duration,
 
+    #[configurable(derived)]
+    quotas: QuotasConfig,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -258,6 +272,7 @@ impl Default for Config {
             extra_field_selector: "".to_string(),
             auto_partial_merge: true,
             data_dir: None,
+            checkpoint_store: None,
             pod_annotation_fields: pod_metadata_annotator::FieldsSpec::default(),
             namespace_annotation_fields: namespace_metadata_annotator::FieldsSpec::default(),
             node_annotation_fields: node_metadata_annotator::FieldsSpec::default(),
@@ -272,6 +287,7 @@ impl Default for Config {
             timezone: None,
             kube_config_file: None,
             delay_deletion_ms: default_delay_deletion_ms(),
+            quotas: QuotasConfig::default(),
             log_namespace: None,
         }
     }
@@ -283,15 +299,32 @@ impl SourceConfig for Config {
         let log_namespace = cx.log_namespace(self.log_namespace);
         let source = Source::new(self, &cx.globals, &cx.key).await?;
 
-        Ok(Box::pin(
-            source
-                .run(cx.out, cx.shutdown, log_namespace)
-                .map(|result| {
-                    result.map_err(|error| {
-                        error!(message = "Source future failed.", %error);
-                    })
-                }),
-        ))
+        // The source runs on every Node, so each one keeps its own checkpoints in the store.
+        let checkpoints_name = format!("{}/checkpoints", source.self_node_name);
+        let checkpoints_path = source.data_dir.join(CHECKPOINT_FILE_NAME);
+        let checkpoint_store = match &self.checkpoint_store {
+            Some(config) => {
+                let store = config.build(&cx).await?;
+                store
+                    .restore_file(&checkpoints_name, &checkpoints_path)
+                    .await
+                    .map_err(|error| format!("Could not restore checkpoints: {}", error))?;
+                Some(store)
+            }
+            None => None,
+        };
+
+        let source: sources::Source = Box::pin(source.run(cx.out, cx.shutdown, log_namespace).map(
+            |result| {
+                result.map_err(|error| {
+                    error!(message = "Source future failed.", %error);
+                })
+            },
+        ));
+        Ok(match checkpoint_store {
+            Some(store) => store.mirror_file(checkpoints_name, checkpoints_path, source),
+            None => source,
+        })
     }
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
@@ -519,6 +552,7 @@ struct Source {
     glob_minimum_cooldown: Duration,
     ingestion_timestamp_field: Option<OwnedTargetPath>,
     delay_deletion: Duration,
+    quotas: QuotasConfig,
 }
 
 impl Source {
@@ -595,6 +629,7 @@ impl Source {
             glob_minimum_cooldown,
             ingestion_timestamp_field,
             delay_deletion,
+            quotas: config.quotas.clone(),
         })
     }
 
@@ -625,6 +660,7 @@ impl Source {
             glob_minimum_cooldown,
             ingestion_timestamp_field,
             delay_deletion,
+            quotas,
         } = self;
 
         let mut reflectors = Vec::new();
@@ -767,6 +803,26 @@ impl Source {
 
         let checkpoints = checkpointer.view();
         let events = file_source_rx.flat_map(futures::stream::iter);
+        let events: Pin<Box<dyn Stream<Item = Line> + Send>> = match Quotas::new(&quotas) {
+            Some(quotas) => {
+                let quotas = Arc::new(quotas);
+                let checkpoints = Arc::clone(&checkpoints);
+                Box::pin(events.filter_map(move |line| {
+                    let quotas = Arc::clone(&quotas);
+                    let checkpoints = Arc::clone(&checkpoints);
+                    async move {
+                        if quotas.admit(&line.filename).await {
+                            Some(line)
+                        } else {
+                            // Dropped lines are not read again.
+                            checkpoints.update(line.file_id, line.end_offset);
+                            None
+                        }
+                    }
+                }))
+            }
+            None => Box::pin(events),
+        };
         let bytes_received = register!(BytesReceived::from(Protocol::HTTP));
         let events = events.map(move |line| {
             let byte_size = line.text.len();
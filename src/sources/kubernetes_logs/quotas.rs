@@ -0,0 +1,271 @@
+//! Ingestion quotas, which keep a few busy Pods from starving the log collection of the other
+//! workloads of the Node.
+
+use std::{
+    collections::HashMap,
+    num::NonZeroU32,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use governor::{DefaultDirectRateLimiter, DefaultKeyedRateLimiter, Quota, RateLimiter};
+use vector_config::configurable_component;
+
+use super::path_helpers::{parse_log_file_path, LogFileInfo};
+use crate::internal_events::KubernetesLogsQuotaExceeded;
+
+/// The number of checks between the removals of the Pods and Namespaces that are no longer
+/// limited from the rate limiters.
+const RETAIN_RECENT_INTERVAL: usize = 10_000;
+
+/// Configuration of the ingestion quotas.
+///
+/// The quotas are applied to the lines read from the log files, before they are parsed and partial
+/// events are merged. Lines are checked against the quota of their Pod first, then of their
+/// Namespace, and of the Node.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct QuotasConfig {
+    /// The maximum number of lines read per second from the log files of all Pods of the Node.
+    #[configurable(metadata(docs::examples = 10000))]
+    node_lines_per_second: Option<NonZeroU32>,
+
+    /// The maximum number of lines read per second from the log files of the Pods of each
+    /// Namespace.
+    #[configurable(metadata(docs::examples = 2000))]
+    namespace_lines_per_second: Option<NonZeroU32>,
+
+    /// The maximum number of lines read per second from the log files of the Pods of specific
+    /// Namespaces, overriding `namespace_lines_per_second`.
+    #[configurable(metadata(
+        docs::additional_props_description = "The maximum number of lines read per second from the Namespace."
+    ))]
+    #[configurable(metadata(docs::examples = "example_namespace_overrides()"))]
+    namespace_overrides: HashMap<String, NonZeroU32>,
+
+    /// The maximum number of lines read per second from the log files of each Pod.
+    #[configurable(metadata(docs::examples = 500))]
+    pod_lines_per_second: Option<NonZeroU32>,
+
+    #[configurable(derived)]
+    shedding: SheddingPolicy,
+}
+
+fn example_namespace_overrides() -> HashMap<String, NonZeroU32> {
+    HashMap::from([(
+        "kube-system".to_owned(),
+        NonZeroU32::new(5000).expect("non-zero"),
+    )])
+}
+
+/// What to do with the lines exceeding a quota.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SheddingPolicy {
+    /// Drop the lines exceeding a quota.
+    ///
+    /// The checkpoints still move past the dropped lines, so they are never read again.
+    #[default]
+    Drop,
+
+    /// Wait for the quota to allow the lines.
+    ///
+    /// No line is lost, but waiting for the quota of a Pod or a Namespace also delays the reading
+    /// of the log files of the other Pods.
+    Backpressure,
+}
+
+/// The rate limiters of the configured quotas.
+pub(super) struct Quotas {
+    node: Option<DefaultDirectRateLimiter>,
+    namespace: Option<DefaultKeyedRateLimiter<String>>,
+    namespace_overrides: HashMap<String, DefaultDirectRateLimiter>,
+    pod: Option<DefaultKeyedRateLimiter<String>>,
+    shedding: SheddingPolicy,
+    checks: AtomicUsize,
+}
+
+impl Quotas {
+    /// Builds the rate limiters of the configured quotas, if any.
+    pub(super) fn new(config: &QuotasConfig) -> Option<Self> {
+        let quotas = Self {
+            node: config
+                .node_lines_per_second
+                .map(|lines| RateLimiter::direct(Quota::per_second(lines))),
+            namespace: config
+                .namespace_lines_per_second
+                .map(|lines| RateLimiter::keyed(Quota::per_second(lines))),
+            namespace_overrides: config
+                .namespace_overrides
+                .iter()
+                .map(|(namespace, lines)| {
+                    (
+                        namespace.clone(),
+                        RateLimiter::direct(Quota::per_second(*lines)),
+                    )
+                })
+                .collect(),
+            pod: config
+                .pod_lines_per_second
+                .map(|lines| RateLimiter::keyed(Quota::per_second(lines))),
+            shedding: config.shedding,
+            checks: AtomicUsize::new(0),
+        };
+
+        (quotas.node.is_some()
+            || quotas.namespace.is_some()
+            || !quotas.namespace_overrides.is_empty()
+            || quotas.pod.is_some())
+        .then_some(quotas)
+    }
+
+    /// Checks a line read from a log file against the quotas, returning whether the line is kept.
+    ///
+    /// With the `backpressure` shedding policy, this waits for the quotas to allow the line, and
+    /// always keeps it.
+    pub(super) async fn admit(&self, file: &str) -> bool {
+        if self.checks.fetch_add(1, Ordering::Relaxed) % RETAIN_RECENT_INTERVAL == 0 {
+            self.retain_recent();
+        }
+
+        let info = parse_log_file_path(file);
+        if let Some(info) = &info {
+            if let Some(pod) = &self.pod {
+                let key = info.pod_uid.to_owned();
+                if pod.check_key(&key).is_err() {
+                    if !self.exceeded("pod", Some(info)) {
+                        return false;
+                    }
+                    pod.until_key_ready(&key).await;
+                }
+            }
+
+            if let Some(namespace) = self.namespace_overrides.get(info.pod_namespace) {
+                if namespace.check().is_err() {
+                    if !self.exceeded("namespace", Some(info)) {
+                        return false;
+                    }
+                    namespace.until_ready().await;
+                }
+            } else if let Some(namespace) = &self.namespace {
+                let key = info.pod_namespace.to_owned();
+                if namespace.check_key(&key).is_err() {
+                    if !self.exceeded("namespace", Some(info)) {
+                        return false;
+                    }
+                    namespace.until_key_ready(&key).await;
+                }
+            }
+        }
+
+        if let Some(node) = &self.node {
+            if node.check().is_err() {
+                if !self.exceeded("node", info.as_ref()) {
+                    return false;
+                }
+                node.until_ready().await;
+            }
+        }
+
+        true
+    }
+
+    /// Reports a line exceeding a quota, returning whether to wait for the quota.
+    fn exceeded(&self, scope: &'static str, info: Option<&LogFileInfo<'_>>) -> bool {
+        let wait = self.shedding == SheddingPolicy::Backpressure;
+        emit!(KubernetesLogsQuotaExceeded {
+            scope,
+            pod_name: info.map_or("", |info| info.pod_name),
+            pod_namespace: info.map_or("", |info| info.pod_namespace),
+            dropped: !wait,
+        });
+        wait
+    }
+
+    fn retain_recent(&self) {
+        if let Some(namespace) = &self.namespace {
+            namespace.retain_recent();
+        }
+        if let Some(pod) = &self.pod {
+            pod.retain_recent();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(namespace: &str, pod: &str) -> String {
+        format!(
+            "/var/log/pods/{}_{}_{}-uid/container/0.log",
+            namespace, pod, pod
+        )
+    }
+
+    fn quotas(config: QuotasConfig) -> Quotas {
+        Quotas::new(&config).expect("quotas are configured")
+    }
+
+    #[test]
+    fn no_quotas() {
+        assert!(Quotas::new(&QuotasConfig::default()).is_none());
+    }
+
+    #[tokio::test]
+    async fn drops_lines_over_pod_quota() {
+        let quotas = quotas(QuotasConfig {
+            pod_lines_per_second: NonZeroU32::new(2),
+            ..Default::default()
+        });
+
+        assert!(quotas.admit(&file("ns", "busy")).await);
+        assert!(quotas.admit(&file("ns", "busy")).await);
+        assert!(!quotas.admit(&file("ns", "busy")).await);
+        assert!(quotas.admit(&file("ns", "quiet")).await);
+    }
+
+    #[tokio::test]
+    async fn drops_lines_over_namespace_quota() {
+        let quotas = quotas(QuotasConfig {
+            namespace_lines_per_second: NonZeroU32::new(1),
+            namespace_overrides: HashMap::from([("big".to_owned(), NonZeroU32::new(3).unwrap())]),
+            ..Default::default()
+        });
+
+        assert!(quotas.admit(&file("small", "a")).await);
+        assert!(!quotas.admit(&file("small", "b")).await);
+        assert!(quotas.admit(&file("other", "a")).await);
+
+        for pod in ["a", "b", "c"] {
+            assert!(quotas.admit(&file("big", pod)).await);
+        }
+        assert!(!quotas.admit(&file("big", "d")).await);
+    }
+
+    #[tokio::test]
+    async fn drops_lines_over_node_quota() {
+        let quotas = quotas(QuotasConfig {
+            node_lines_per_second: NonZeroU32::new(1),
+            ..Default::default()
+        });
+
+        assert!(quotas.admit(&file("ns", "a")).await);
+        assert!(!quotas.admit(&file("other", "b")).await);
+        assert!(!quotas.admit("/not/a/pod/log/file").await);
+    }
+
+    #[tokio::test]
+    async fn backpressure_keeps_lines() {
+        let quotas = quotas(QuotasConfig {
+            pod_lines_per_second: NonZeroU32::new(1000),
+            shedding: SheddingPolicy::Backpressure,
+            ..Default::default()
+        });
+
+        for _ in 0..1010 {
+            assert!(quotas.admit(&file("ns", "busy")).await);
+        }
+    }
+}
@@ -158,6 +158,7 @@ impl SourceConfig for LogstashConfig {
             shutdown_secs,
             tls,
             tls_client_metadata_key,
+            None,
             self.receive_buffer_bytes,
             None,
             cx,
@@ -151,6 +151,7 @@ impl SourceConfig for SocketConfig {
                     config.shutdown_timeout_secs(),
                     tls,
                     tls_client_metadata_key,
+                    config.proxy_protocol().as_ref(),
                     config.receive_buffer_bytes(),
                     config.max_connection_duration_secs(),
                     cx,
@@ -398,7 +399,7 @@ mod test {
             components::{assert_source_compliance, SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS},
             next_addr, random_string, send_lines, send_lines_tls, wait_for_tcp,
         },
-        tls::{self, TlsConfig, TlsEnableableConfig, TlsSourceConfig},
+        tls::{self, ProxyProtocolConfig, TlsConfig, TlsEnableableConfig, TlsSourceConfig},
         SourceSender,
     };
 
@@ -433,6 +434,63 @@ mod test {
         .await;
     }
 
+    #[tokio::test]
+    async fn tcp_it_includes_proxied_host() {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
+            let (tx, mut rx) = SourceSender::new_test();
+            let addr = next_addr();
+            let mut conf = TcpConfig::from_address(addr.into());
+            conf.set_proxy_protocol(Some(ProxyProtocolConfig {
+                trusted_proxies: vec!["127.0.0.0/8".to_owned()],
+            }));
+
+            let server = SocketConfig::from(conf)
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(server);
+
+            wait_for_tcp(addr).await;
+            // The newline appended to the header completes its CRLF.
+            let header = "PROXY TCP4 192.0.2.1 198.51.100.1 56324 9000\r".to_owned();
+            send_lines(addr, vec![header, "test".to_owned()].into_iter())
+                .await
+                .unwrap();
+
+            let event = rx.next().await.unwrap();
+
+            assert_eq!(event.as_log()["message"], "test".into());
+            assert_eq!(event.as_log()["host"], "192.0.2.1".into());
+            assert_eq!(event.as_log()["port"], 56324.into());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn tcp_closes_connections_of_untrusted_proxies() {
+        let (tx, mut rx) = SourceSender::new_test();
+        let addr = next_addr();
+        let mut conf = TcpConfig::from_address(addr.into());
+        conf.set_proxy_protocol(Some(ProxyProtocolConfig {
+            trusted_proxies: vec!["192.0.2.0/24".to_owned()],
+        }));
+
+        let server = SocketConfig::from(conf)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        wait_for_tcp(addr).await;
+        let header = "PROXY TCP4 192.0.2.1 198.51.100.1 56324 9000\r".to_owned();
+        // The connection may be closed before all the lines are sent.
+        let _ = send_lines(addr, vec![header, "test".to_owned()].into_iter()).await;
+
+        assert!(timeout(Duration::from_millis(500), rx.next())
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn tcp_it_includes_vector_namespaced_fields() {
         assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
@@ -14,7 +14,7 @@ use crate::{
     serde::default_decoding,
     sources::util::net::{SocketListenAddr, TcpNullAcker, TcpSource},
     tcp::TcpKeepaliveConfig,
-    tls::TlsSourceConfig,
+    tls::{ProxyProtocolConfig, TlsSourceConfig},
 };
 
 use super::{default_host_key, default_max_length, SocketConfig};
@@ -72,6 +72,9 @@ pub struct TcpConfig {
     #[configurable(derived)]
     tls: Option<TlsSourceConfig>,
 
+    #[configurable(derived)]
+    proxy_protocol: Option<ProxyProtocolConfig>,
+
     /// The size of the receive buffer used for each connection.
     ///
     /// Generally this should not need to be configured.
@@ -119,6 +122,7 @@ impl TcpConfig {
             host_key: default_host_key(),
             port_key: default_port_key(),
             tls: None,
+            proxy_protocol: None,
             receive_buffer_bytes: None,
             max_connection_duration_secs: None,
             framing: None,
@@ -140,6 +144,10 @@ impl TcpConfig {
         &self.tls
     }
 
+    pub const fn proxy_protocol(&self) -> &Option<ProxyProtocolConfig> {
+        &self.proxy_protocol
+    }
+
     pub const fn framing(&self) -> &Option<FramingConfig> {
         &self.framing
     }
@@ -192,6 +200,11 @@ impl TcpConfig {
         self
     }
 
+    pub fn set_proxy_protocol(&mut self, val: Option<ProxyProtocolConfig>) -> &mut Self {
+        self.proxy_protocol = val;
+        self
+    }
+
     pub fn set_framing(&mut self, val: Option<FramingConfig>) -> &mut Self {
         self.framing = val;
         self
@@ -164,6 +164,7 @@ impl SourceConfig for StatsdConfig {
                     config.shutdown_timeout_secs,
                     tls,
                     tls_client_metadata_key,
+                    None,
                     config.receive_buffer_bytes,
                     None,
                     cx,
@@ -30,7 +30,7 @@ use crate::{
     shutdown::ShutdownSignal,
     sources::util::net::{try_bind_udp_socket, SocketListenAddr, TcpNullAcker, TcpSource},
     tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsSettings, TlsSourceConfig},
+    tls::{MaybeTlsSettings, ProxyProtocolConfig, TlsSourceConfig},
     udp, SourceSender,
 };
 
@@ -81,6 +81,9 @@ pub enum Mode {
         #[configurable(derived)]
         tls: Option<TlsSourceConfig>,
 
+        #[configurable(derived)]
+        proxy_protocol: Option<ProxyProtocolConfig>,
+
         /// The size of the receive buffer used for each connection.
         ///
         /// This should not typically needed to be changed.
@@ -139,6 +142,7 @@ impl Default for SyslogConfig {
                 address: SocketListenAddr::SocketAddr("0.0.0.0:514".parse().unwrap()),
                 keepalive: None,
                 tls: None,
+                proxy_protocol: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
             },
@@ -169,6 +173,7 @@ impl SourceConfig for SyslogConfig {
                 address,
                 keepalive,
                 tls,
+                proxy_protocol,
                 receive_buffer_bytes,
                 connection_limit,
             } => {
@@ -190,6 +195,7 @@ impl SourceConfig for SyslogConfig {
                     shutdown_secs,
                     tls,
                     tls_client_metadata_key,
+                    proxy_protocol.as_ref(),
                     receive_buffer_bytes,
                     None,
                     cx,
@@ -1043,6 +1049,7 @@ mod test {
                 address: in_addr.into(),
                 keepalive: None,
                 tls: None,
+                proxy_protocol: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
             });
@@ -1186,6 +1193,7 @@ mod test {
                 address: in_addr.into(),
                 keepalive: None,
                 tls: None,
+                proxy_protocol: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
             });
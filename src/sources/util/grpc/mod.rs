@@ -5,23 +5,95 @@ use crate::{
 use futures::FutureExt;
 use http::{Request, Response};
 use hyper::Body;
-use std::{convert::Infallible, net::SocketAddr};
+use std::{convert::Infallible, net::SocketAddr, time::Duration};
 use tonic::{
     body::BoxBody,
     transport::server::{NamedService, Server},
 };
 use tower::Service;
 use tracing::{Instrument, Span};
+use vector_config::configurable_component;
 
 mod decompression;
 pub use self::decompression::{DecompressionAndMetrics, DecompressionAndMetricsLayer};
 
+/// HTTP/2 settings of the gRPC server.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcServerHttp2Config {
+    /// The interval between the pings sent to clients to keep their connections alive.
+    ///
+    /// Connections whose client doesn't acknowledge a ping within `keepalive_timeout_secs` are
+    /// closed. If not set, no pings are sent.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 60))]
+    pub keepalive_interval_secs: Option<u64>,
+
+    /// How long to wait for the acknowledgement of a ping before closing the connection.
+    #[serde(default = "default_keepalive_timeout_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub keepalive_timeout_secs: u64,
+
+    /// The initial flow control window of streams.
+    ///
+    /// Larger windows help throughput over links with high latency.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 1048576))]
+    pub initial_stream_window_size: Option<u32>,
+
+    /// The initial flow control window of connections.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 4194304))]
+    pub initial_connection_window_size: Option<u32>,
+}
+
+const fn default_keepalive_timeout_secs() -> u64 {
+    20
+}
+
+impl Default for GrpcServerHttp2Config {
+    fn default() -> Self {
+        Self {
+            keepalive_interval_secs: None,
+            keepalive_timeout_secs: default_keepalive_timeout_secs(),
+            initial_stream_window_size: None,
+            initial_connection_window_size: None,
+        }
+    }
+}
+
 pub async fn run_grpc_server<S>(
     address: SocketAddr,
     tls_settings: MaybeTlsSettings,
     service: S,
     shutdown: ShutdownSignal,
 ) -> crate::Result<()>
+where
+    S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
+        + NamedService
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    run_grpc_server_with_http2(
+        address,
+        tls_settings,
+        &GrpcServerHttp2Config::default(),
+        service,
+        shutdown,
+    )
+    .await
+}
+
+pub async fn run_grpc_server_with_http2<S>(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    http2: &GrpcServerHttp2Config,
+    service: S,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()>
 where
     S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
         + NamedService
@@ -38,6 +110,10 @@ where
     info!(%address, "Building gRPC server.");
 
     Server::builder()
+        .http2_keepalive_interval(http2.keepalive_interval_secs.map(Duration::from_secs))
+        .http2_keepalive_timeout(Some(Duration::from_secs(http2.keepalive_timeout_secs)))
+        .initial_stream_window_size(http2.initial_stream_window_size)
+        .initial_connection_window_size(http2.initial_connection_window_size)
         .trace_fn(move |_| span.clone())
         // This layer explicitly decompresses payloads, if compressed, and reports the number of message bytes we've
         // received if the message is processed successfully, aka `BytesReceived`. We do this because otherwise the only
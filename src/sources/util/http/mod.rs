@@ -22,6 +22,6 @@ pub use encoding::decode;
 pub use error::ErrorMessage;
 pub use method::HttpMethod;
 #[cfg(feature = "sources-utils-http-prelude")]
-pub use prelude::HttpSource;
+pub use prelude::{path_matches, HttpSource};
 #[cfg(feature = "sources-utils-http-query")]
 pub use query::add_query_parameters;
@@ -2,7 +2,7 @@ use std::{collections::HashMap, convert::TryFrom, fmt, net::SocketAddr};
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use futures::{FutureExt, TryFutureExt};
+use futures::FutureExt;
 use tracing::Span;
 use vector_core::{
     config::SourceAcknowledgementsConfig,
@@ -10,10 +10,7 @@ use vector_core::{
     EstimatedJsonEncodedSizeOf,
 };
 use warp::{
-    filters::{
-        path::{FullPath, Tail},
-        BoxedFilter,
-    },
+    filters::{path::FullPath, BoxedFilter},
     http::{HeaderMap, StatusCode},
     reject::Rejection,
     Filter,
@@ -45,6 +42,18 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         path: &str,
     ) -> Result<Vec<Event>, ErrorMessage>;
 
+    /// The URL paths of the routes of the source, whose requests are accepted in addition to the
+    /// requests to the configured path.
+    fn route_paths(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// The named output the events of a request to `path` are sent to, or `None` for the default
+    /// output.
+    fn route_output(&self, _path: &str) -> Option<String> {
+        None
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn run(
         self,
@@ -60,11 +69,13 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         let tls = MaybeTlsSettings::from_config(tls, true)?;
         let protocol = tls.http_protocol_name();
         let auth = HttpSourceAuth::try_from(auth.as_ref())?;
-        let path = path.to_owned();
+        let paths = std::iter::once(path.to_owned())
+            .chain(self.route_paths())
+            .collect::<Vec<_>>();
         let acknowledgements = cx.do_acknowledgements(acknowledgements);
         Ok(Box::pin(async move {
             let span = Span::current();
-            let mut filter: BoxedFilter<()> = match method {
+            let filter: BoxedFilter<()> = match method {
                 HttpMethod::Head => warp::head().boxed(),
                 HttpMethod::Get => warp::get().boxed(),
                 HttpMethod::Put => warp::put().boxed(),
@@ -73,24 +84,24 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                 HttpMethod::Delete => warp::delete().boxed(),
             };
 
-            // https://github.com/rust-lang/rust-clippy/issues/8148
-            #[allow(clippy::unnecessary_to_owned)]
-            for s in path.split('/').filter(|&x| !x.is_empty()) {
-                filter = filter.and(warp::path(s.to_string())).boxed()
-            }
             let svc = filter
-                .and(warp::path::tail())
-                .and_then(move |tail: Tail| async move {
-                    if !strict_path || tail.as_str().is_empty() {
-                        Ok(())
-                    } else {
-                        emit!(HttpInternalError {
-                            message: "Path not found."
-                        });
-                        Err(warp::reject::custom(ErrorMessage::new(
-                            StatusCode::NOT_FOUND,
-                            "Not found".to_string(),
-                        )))
+                .and(warp::path::full())
+                .and_then(move |request_path: FullPath| {
+                    let found = paths
+                        .iter()
+                        .any(|path| path_matches(path, request_path.as_str(), strict_path));
+                    async move {
+                        if found {
+                            Ok(())
+                        } else {
+                            emit!(HttpInternalError {
+                                message: "Path not found."
+                            });
+                            Err(warp::reject::custom(ErrorMessage::new(
+                                StatusCode::NOT_FOUND,
+                                "Not found".to_string(),
+                            )))
+                        }
                     }
                 })
                 .untuple_one()
@@ -131,7 +142,8 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                                 events
                             });
 
-                        handle_request(events, acknowledgements, cx.out.clone())
+                        let output = self.route_output(path.as_str());
+                        handle_request(events, acknowledgements, output, cx.out.clone())
                     },
                 )
                 .with(warp::trace(move |_info| span.clone()));
@@ -171,6 +183,19 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
     }
 }
 
+/// Returns whether the URL path of a request matches a configured path.
+///
+/// The path matches if it starts with the segments of the configured path, or, if `strict` is set,
+/// if it has exactly the segments of the configured path.
+pub fn path_matches(configured: &str, request: &str, strict: bool) -> bool {
+    let mut request = request.split('/').filter(|segment| !segment.is_empty());
+    configured
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .all(|segment| request.next() == Some(segment))
+        && (!strict || request.next().is_none())
+}
+
 struct RejectShuttingDown;
 
 impl fmt::Debug for RejectShuttingDown {
@@ -184,6 +209,7 @@ impl warp::reject::Reject for RejectShuttingDown {}
 async fn handle_request(
     events: Result<Vec<Event>, ErrorMessage>,
     acknowledgements: bool,
+    output: Option<String>,
     mut out: SourceSender,
 ) -> Result<impl warp::Reply, Rejection> {
     match events {
@@ -191,15 +217,17 @@ async fn handle_request(
             let receiver = BatchNotifier::maybe_apply_to(acknowledgements, &mut events);
 
             let count = events.len();
-            out.send_batch(events)
-                .map_err(move |error: crate::source_sender::ClosedError| {
-                    // can only fail if receiving end disconnected, so we are shutting down,
-                    // probably not gracefully.
-                    emit!(StreamClosedError { error, count });
-                    warp::reject::custom(RejectShuttingDown)
-                })
-                .and_then(|_| handle_batch_status(receiver))
-                .await
+            let sent = match output {
+                Some(output) => out.send_batch_named(&output, events).await,
+                None => out.send_batch(events).await,
+            };
+            if let Err(error) = sent {
+                // can only fail if receiving end disconnected, so we are shutting down,
+                // probably not gracefully.
+                emit!(StreamClosedError { error, count });
+                return Err(warp::reject::custom(RejectShuttingDown));
+            }
+            handle_batch_status(receiver).await
         }
         Err(error) => {
             emit!(HttpBadRequest::new(error.code(), error.message()));
@@ -1,6 +1,6 @@
 mod request_limiter;
 
-use std::{collections::BTreeMap, io, mem::drop, net::SocketAddr, time::Duration};
+use std::{collections::BTreeMap, io, mem::drop, time::Duration};
 
 use bytes::Bytes;
 use codecs::StreamDecodingError;
@@ -37,7 +37,10 @@ use crate::{
     shutdown::ShutdownSignal,
     sources::util::AfterReadExt,
     tcp::TcpKeepaliveConfig,
-    tls::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
+    tls::{
+        CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings,
+        ProxyProtocolConfig, ProxyProtocolSettings,
+    },
     SourceSender,
 };
 
@@ -112,6 +115,7 @@ where
         shutdown_timeout_secs: Duration,
         tls: MaybeTlsSettings,
         tls_client_metadata_key: Option<OwnedValuePath>,
+        proxy_protocol: Option<&ProxyProtocolConfig>,
         receive_buffer_bytes: Option<usize>,
         max_connection_duration_secs: Option<u64>,
         cx: SourceContext,
@@ -121,11 +125,15 @@ where
         log_namespace: LogNamespace,
     ) -> crate::Result<crate::sources::Source> {
         let acknowledgements = cx.do_acknowledgements(acknowledgements);
+        let proxy_protocol = proxy_protocol
+            .map(ProxyProtocolSettings::from_config)
+            .transpose()?;
 
         Ok(Box::pin(async move {
             let listenfd = ListenFd::from_env();
             let listener = try_bind_tcp_listener(addr, listenfd, &tls)
                 .await
+                .map(|listener| listener.with_proxy_protocol(proxy_protocol))
                 .map_err(|error| {
                     emit!(SocketBindError {
                         mode: SocketMode::Tcp,
@@ -204,7 +212,6 @@ where
                                 max_connection_duration_secs,
                                 source,
                                 tripwire,
-                                peer_addr,
                                 out,
                                 acknowledgements,
                                 request_limiter,
@@ -238,7 +245,6 @@ async fn handle_stream<T>(
     max_connection_duration_secs: Option<u64>,
     source: T,
     mut tripwire: BoxFuture<'static, ()>,
-    peer_addr: SocketAddr,
     mut out: SourceSender,
     acknowledgements: bool,
     request_limiter: RequestLimiter,
@@ -261,6 +267,10 @@ async fn handle_stream<T>(
         }
     };
 
+    // The handshake reads the PROXY protocol header, which holds the address of the original
+    // client, if any.
+    let peer_addr = socket.peer_addr();
+
     if let Some(keepalive) = keepalive {
         if let Err(error) = socket.set_keepalive(keepalive) {
             warn!(message = "Failed configuring TCP keepalive.", %error);
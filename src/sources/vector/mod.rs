@@ -1,10 +1,15 @@
 //! The `vector` source. See [VectorConfig].
-use std::net::SocketAddr;
+use std::{
+    collections::HashSet,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+};
 
 use chrono::Utc;
 use codecs::NativeDeserializerConfig;
 use futures::TryFutureExt;
-use tonic::{Request, Response, Status};
+use openssl::x509::X509;
+use tonic::{transport::Certificate, Request, Response, Status};
 use vector_common::internal_event::{CountByteSize, InternalEventHandle as _};
 use vector_config::configurable_component;
 use vector_core::{
@@ -18,11 +23,14 @@ use crate::{
         DataType, GenerateConfig, Output, Resource, SourceAcknowledgementsConfig, SourceConfig,
         SourceContext,
     },
-    internal_events::{EventsReceived, StreamClosedError},
+    internal_events::{EventsReceived, GrpcClientNotAllowedError, StreamClosedError},
     proto::vector as proto,
     serde::bool_or_struct,
-    sources::{util::grpc::run_grpc_server, Source},
-    tls::{MaybeTlsSettings, TlsEnableableConfig},
+    sources::{
+        util::grpc::{run_grpc_server_with_http2, GrpcServerHttp2Config},
+        Source,
+    },
+    tls::{MaybeTlsConnectInfo, MaybeTlsSettings, TlsEnableableConfig},
     SourceSender,
 };
 
@@ -40,6 +48,64 @@ struct Service {
     pipeline: SourceSender,
     acknowledgements: bool,
     log_namespace: LogNamespace,
+    allowed_client_sans: Arc<HashSet<String>>,
+}
+
+impl Service {
+    /// Checks that the certificate of the client holds one of the allowed subject alternative
+    /// names, if any are set.
+    fn authorize<T>(&self, request: &Request<T>) -> Result<(), Status> {
+        if self.allowed_client_sans.is_empty() {
+            return Ok(());
+        }
+
+        let connect_info = request.extensions().get::<MaybeTlsConnectInfo>();
+        let allowed = connect_info
+            .and_then(|info| info.peer_cert.as_ref())
+            .map_or(false, |cert| {
+                subject_alt_names(cert)
+                    .iter()
+                    .any(|name| self.allowed_client_sans.contains(name))
+            });
+
+        if allowed {
+            Ok(())
+        } else {
+            emit!(GrpcClientNotAllowedError {
+                remote_addr: connect_info.map(|info| info.remote_addr),
+            });
+            Err(Status::permission_denied(
+                "client certificate holds none of the allowed subject alternative names",
+            ))
+        }
+    }
+}
+
+/// The DNS names, IP addresses, URIs, and email addresses of a certificate.
+fn subject_alt_names(cert: &Certificate) -> Vec<String> {
+    let names = match X509::from_pem(cert.get_ref()) {
+        Ok(cert) => cert.subject_alt_names(),
+        Err(_) => None,
+    };
+
+    names
+        .iter()
+        .flatten()
+        .filter_map(|name| {
+            if let Some(ip) = name.ipaddress() {
+                let ip = match ip.len() {
+                    4 => IpAddr::from(Ipv4Addr::from(<[u8; 4]>::try_from(ip).ok()?)),
+                    16 => IpAddr::from(Ipv6Addr::from(<[u8; 16]>::try_from(ip).ok()?)),
+                    _ => return None,
+                };
+                return Some(ip.to_string());
+            }
+            name.dnsname()
+                .or_else(|| name.uri())
+                .or_else(|| name.email())
+                .map(str::to_owned)
+        })
+        .collect()
 }
 
 #[tonic::async_trait]
@@ -48,6 +114,8 @@ impl proto::Service for Service {
         &self,
         request: Request<proto::PushEventsRequest>,
     ) -> Result<Response<proto::PushEventsResponse>, Status> {
+        self.authorize(&request)?;
+
         let mut events: Vec<Event> = request
             .into_inner()
             .events
@@ -90,8 +158,10 @@ impl proto::Service for Service {
     // TODO: figure out a way to determine if the current Vector instance is "healthy".
     async fn health_check(
         &self,
-        _: Request<proto::HealthCheckRequest>,
+        request: Request<proto::HealthCheckRequest>,
     ) -> Result<Response<proto::HealthCheckResponse>, Status> {
+        self.authorize(&request)?;
+
         let message = proto::HealthCheckResponse {
             status: proto::ServingStatus::Serving.into(),
         };
@@ -130,6 +200,20 @@ pub struct VectorConfig {
     #[serde(default)]
     tls: Option<TlsEnableableConfig>,
 
+    /// The subject alternative names of the clients allowed to send events.
+    ///
+    /// If set, requests are only accepted from clients whose certificate holds one of these DNS
+    /// names, IP addresses, URIs, or email addresses. This requires `tls.verify_certificate` to be
+    /// enabled, so that clients must present a certificate signed by a trusted authority.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "agent.example.com"))]
+    #[configurable(metadata(docs::examples = "spiffe://example.com/agent"))]
+    allowed_client_sans: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    http2: GrpcServerHttp2Config,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -156,6 +240,8 @@ impl Default for VectorConfig {
             version: None,
             address: "0.0.0.0:6000".parse().unwrap(),
             tls: None,
+            allowed_client_sans: Vec::new(),
+            http2: GrpcServerHttp2Config::default(),
             acknowledgements: Default::default(),
             log_namespace: None,
         }
@@ -175,17 +261,36 @@ impl SourceConfig for VectorConfig {
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
         let log_namespace = cx.log_namespace(self.log_namespace);
 
+        let verify_certificate = self
+            .tls
+            .as_ref()
+            .and_then(|tls| tls.options.verify_certificate)
+            .unwrap_or(false);
+        if !self.allowed_client_sans.is_empty() && !(tls_settings.is_tls() && verify_certificate) {
+            return Err(
+                "`allowed_client_sans` requires TLS to be enabled with `tls.verify_certificate`"
+                    .into(),
+            );
+        }
+
         let service = proto::Server::new(Service {
             pipeline: cx.out,
             acknowledgements,
             log_namespace,
+            allowed_client_sans: Arc::new(self.allowed_client_sans.iter().cloned().collect()),
         })
         .accept_compressed(tonic::codec::CompressionEncoding::Gzip);
 
-        let source =
-            run_grpc_server(self.address, tls_settings, service, cx.shutdown).map_err(|error| {
-                error!(message = "Source future failed.", %error);
-            });
+        let source = run_grpc_server_with_http2(
+            self.address,
+            tls_settings,
+            &self.http2,
+            service,
+            cx.shutdown,
+        )
+        .map_err(|error| {
+            error!(message = "Source future failed.", %error);
+        });
 
         Ok(Box::pin(source))
     }
@@ -193,7 +298,7 @@ impl SourceConfig for VectorConfig {
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
         let log_namespace = global_log_namespace.merge(self.log_namespace);
 
-        let schema_definition = NativeDeserializerConfig
+        let schema_definition = NativeDeserializerConfig::default()
             .schema_definition(log_namespace)
             .with_standard_vector_source_metadata();
 
@@ -343,4 +448,36 @@ mod tests {
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn receive_zstd_compressed_message() {
+        let addr = test_util::next_addr();
+
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let config = format!(
+                r#"address = "{}"
+            compression="zstd""#,
+                addr
+            );
+            run_test(&config, addr).await;
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn allowed_client_sans_require_verified_certificates() {
+        let config: VectorConfig = toml::from_str(
+            r#"
+            address = "127.0.0.1:6000"
+            allowed_client_sans = ["agent.example.com"]
+            "#,
+        )
+        .unwrap();
+        let (tx, _rx) = SourceSender::new_test();
+
+        assert!(config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .is_err());
+    }
 }
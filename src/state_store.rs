@@ -0,0 +1,332 @@
+//! Persistent state for stateful transforms.
+//!
+//! Transforms such as `dedupe`, `reduce` and `throttle` hold state that is lost when Vector
+//! restarts. A [`StateStore`] periodically checkpoints that state to a file in the data directory,
+//! and restores it when the transform is next built.
+//!
+//! The transform stays the owner of its state: the store only serializes it when a checkpoint is
+//! taken, and writes it out on a blocking thread so that the transform isn't held up by disk IO.
+//! Entries are handed to the store from most to least recently used, and a checkpoint stops once
+//! the configured `max_bytes` is reached, so what gets persisted is always the most recently used
+//! part of the transform's own state.
+
+use std::{
+    fs,
+    future::Future,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::value::RawValue;
+use tokio::task::JoinHandle;
+use tracing::Span;
+use vector_config::configurable_component;
+
+use crate::{
+    config::TransformContext,
+    internal_events::{StateStoreCheckpointError, StateStoreCheckpointed, StateStoreLoadError},
+};
+
+const STATE_FILENAME: &str = "state.json";
+const STATE_VERSION: u32 = 1;
+
+/// Persistence configuration for the state of a transform.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct StateStoreConfig {
+    /// Whether or not the state of the transform is persisted, so that it survives restarts.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// The directory used to persist the state.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used. Make sure the running
+    /// user has write permissions to this directory.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    pub data_dir: Option<PathBuf>,
+
+    /// How often the state is checkpointed to disk, in seconds.
+    ///
+    /// The state is also checkpointed when the transform is stopped.
+    #[serde(default = "default_checkpoint_interval_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub checkpoint_interval_secs: u64,
+
+    /// The maximum size of a checkpoint of the state, in bytes.
+    ///
+    /// Only the most recently used entries that fit within this size are checkpointed, so the
+    /// state restored after a restart may be smaller than the state that was held in memory.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_bytes: Option<usize>,
+}
+
+impl Default for StateStoreConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            data_dir: None,
+            checkpoint_interval_secs: default_checkpoint_interval_secs(),
+            max_bytes: None,
+        }
+    }
+}
+
+const fn default_checkpoint_interval_secs() -> u64 {
+    10
+}
+
+impl StateStoreConfig {
+    /// Opens the state store for the transform being built, if persistence is enabled.
+    pub fn build(&self, cx: &TransformContext) -> crate::Result<Option<StateStore>> {
+        if !self.enabled {
+            return Ok(None);
+        }
+        if self.checkpoint_interval_secs == 0 {
+            return Err("`state.checkpoint_interval_secs` must be greater than zero.".into());
+        }
+        let key = cx
+            .key
+            .as_ref()
+            .ok_or("Persisting state requires the transform to have an ID.")?;
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), key.id())?;
+
+        Ok(Some(StateStore::new(
+            data_dir.join(STATE_FILENAME),
+            Duration::from_secs(self.checkpoint_interval_secs),
+            self.max_bytes,
+        )))
+    }
+}
+
+#[derive(Deserialize)]
+struct Checkpoint<E> {
+    version: u32,
+    entries: E,
+}
+
+/// Checkpoints the state of a transform to disk.
+pub struct StateStore {
+    path: PathBuf,
+    max_bytes: Option<usize>,
+    checkpoint_interval: Duration,
+    changed: bool,
+    pending: Option<JoinHandle<()>>,
+}
+
+impl StateStore {
+    fn new(path: PathBuf, checkpoint_interval: Duration, max_bytes: Option<usize>) -> Self {
+        Self {
+            path,
+            max_bytes,
+            checkpoint_interval,
+            changed: false,
+            pending: None,
+        }
+    }
+
+    pub const fn checkpoint_interval(&self) -> Duration {
+        self.checkpoint_interval
+    }
+
+    /// Loads the entries of the last checkpoint, from most to least recently used.
+    ///
+    /// Entries that can't be deserialized as `T` are skipped.
+    pub async fn restore<T>(&self) -> Vec<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let path = self.path.clone();
+        let span = Span::current();
+        tokio::task::spawn_blocking(move || {
+            let _enter = span.enter();
+            match load(&path) {
+                Ok(entries) => entries
+                    .iter()
+                    .filter_map(|entry| serde_json::from_str(entry.get()).ok())
+                    .collect(),
+                // A missing checkpoint simply means there is no state to restore yet.
+                Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+                Err(error) => {
+                    emit!(StateStoreLoadError { error, path: &path });
+                    Vec::new()
+                }
+            }
+        })
+        .await
+        .unwrap_or_default()
+    }
+
+    /// Records that the state has changed since the last checkpoint.
+    pub fn mark_changed(&mut self) {
+        self.changed = true;
+    }
+
+    /// Checkpoints the given entries, ordered from most to least recently used, in the background.
+    ///
+    /// Nothing is done if the state hasn't changed since the last checkpoint, or if the last
+    /// checkpoint is still being written, in which case the next call catches up.
+    pub fn checkpoint<T: Serialize>(&mut self, entries: impl IntoIterator<Item = T>) {
+        if !self.changed
+            || self
+                .pending
+                .as_ref()
+                .map_or(false, |task| !task.is_finished())
+        {
+            return;
+        }
+        self.changed = false;
+
+        let (data, count) = self.serialize(entries);
+        self.pending = Some(spawn_write(self.path.clone(), data, count));
+    }
+
+    /// Checkpoints the given entries, and returns a future that resolves once the checkpoint is
+    /// written.
+    ///
+    /// This is called when the transform stops, so that its final state isn't lost. The entries
+    /// are serialized right away, so the returned future doesn't borrow them.
+    pub fn flush<T: Serialize>(
+        &mut self,
+        entries: impl IntoIterator<Item = T>,
+    ) -> impl Future<Output = ()> {
+        let previous = self.pending.take();
+        let checkpoint = self.changed.then(|| self.serialize(entries));
+        self.changed = false;
+        let path = self.path.clone();
+
+        async move {
+            // Checkpoints are written one at a time, so the last one is the one left in place.
+            if let Some(task) = previous {
+                _ = task.await;
+            }
+            if let Some((data, count)) = checkpoint {
+                _ = spawn_write(path, data, count).await;
+            }
+        }
+    }
+
+    fn serialize<T: Serialize>(&self, entries: impl IntoIterator<Item = T>) -> (Vec<u8>, usize) {
+        let mut data = format!("{{\"version\":{},\"entries\":[", STATE_VERSION).into_bytes();
+        let mut count = 0;
+        for entry in entries {
+            let Ok(entry) = serde_json::to_vec(&entry) else {
+                continue;
+            };
+            if let Some(max_bytes) = self.max_bytes {
+                if data.len() + entry.len() + 3 > max_bytes {
+                    break;
+                }
+            }
+            if count > 0 {
+                data.push(b',');
+            }
+            data.extend_from_slice(&entry);
+            count += 1;
+        }
+        data.extend_from_slice(b"]}");
+        (data, count)
+    }
+}
+
+fn spawn_write(path: PathBuf, data: Vec<u8>, count: usize) -> JoinHandle<()> {
+    let span = Span::current();
+    tokio::task::spawn_blocking(move || {
+        let _enter = span.enter();
+        match write(&path, &data) {
+            Ok(()) => emit!(StateStoreCheckpointed {
+                entries: count,
+                byte_size: data.len(),
+            }),
+            Err(error) => emit!(StateStoreCheckpointError { error, path: &path }),
+        }
+    })
+}
+
+/// Writes a checkpoint to a temporary file first and then moves it into place, so a crash while
+/// checkpointing leaves the previous checkpoint intact.
+fn write(path: &Path, data: &[u8]) -> io::Result<()> {
+    let tmp_path = path.with_extension("json.tmp");
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+    fs::rename(tmp_path, path)
+}
+
+fn load(path: &Path) -> io::Result<Vec<Box<RawValue>>> {
+    let data = fs::read(path)?;
+    let checkpoint: Checkpoint<Vec<Box<RawValue>>> = serde_json::from_slice(&data)?;
+    if checkpoint.version != STATE_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported state version {}", checkpoint.version),
+        ));
+    }
+    Ok(checkpoint.entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(path: &Path, max_bytes: Option<usize>) -> StateStore {
+        StateStore::new(
+            path.join(STATE_FILENAME),
+            Duration::from_secs(10),
+            max_bytes,
+        )
+    }
+
+    #[tokio::test]
+    async fn restores_checkpointed_state() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut state = store(dir.path(), None);
+        state.mark_changed();
+        state.flush([("a", 1), ("b", 2)]).await;
+
+        let state = store(dir.path(), None);
+        assert_eq!(
+            state.restore::<(String, i64)>().await,
+            vec![("a".to_string(), 1), ("b".to_string(), 2)]
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_unchanged_state() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut state = store(dir.path(), None);
+        state.flush([1]).await;
+
+        assert!(!dir.path().join(STATE_FILENAME).exists());
+    }
+
+    #[tokio::test]
+    async fn keeps_most_recent_entries_within_max_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // An empty checkpoint takes 26 bytes, and each of these entries adds at most two more.
+        let mut state = store(dir.path(), Some(30));
+        state.mark_changed();
+        state.flush([1, 2, 3, 4]).await;
+
+        let state = store(dir.path(), None);
+        assert_eq!(state.restore::<i64>().await, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn ignores_corrupt_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(STATE_FILENAME), "{").unwrap();
+
+        let state = store(dir.path(), None);
+        assert!(state.restore::<i64>().await.is_empty());
+    }
+}
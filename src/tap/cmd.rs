@@ -7,7 +7,7 @@ use vector_api_client::{
     connect_subscription_client,
     gql::{
         output_events_by_component_id_patterns_subscription::OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns,
-        TapEncodingFormat, TapSubscriptionExt,
+        TapEncodingFormat, TapFilter, TapSubscriptionExt,
     },
     Client,
 };
@@ -106,6 +106,11 @@ async fn run(
             opts.format,
             opts.limit as i64,
             opts.interval as i64,
+            TapFilter {
+                condition: opts.filter.clone(),
+                sample_rate: opts.sample_rate.map(Into::into),
+                max_events_per_second: opts.max_events_per_second.map(Into::into),
+            },
         );
     };
 
@@ -115,6 +120,14 @@ async fn run(
     loop {
         let message = stream.next().await;
         if let Some(Some(res)) = message {
+            // Errors are only returned when the subscription is rejected, such as for an invalid
+            // filter, and so retrying wouldn't help.
+            if let Some(errors) = res.errors {
+                for error in errors {
+                    eprintln!("[tap] {}", error.message);
+                }
+                return exitcode::USAGE;
+            }
             if let Some(d) = res.data {
                 for tap_event in d.output_events_by_component_id_patterns.iter() {
                     match tap_event {
@@ -199,7 +212,7 @@ impl EventFormatter {
                     )
                     .into()
                 }
-                TapEncodingFormat::Logfmt => format!(
+                TapEncodingFormat::Logfmt | TapEncodingFormat::Raw => format!(
                     "{}={} {}={} {}={} {}",
                     self.component_id_label,
                     component_id.green(),
@@ -20,10 +20,23 @@ pub struct Opts {
     #[arg(default_value = "100", short = 'l', long)]
     limit: u32,
 
-    /// Encoding format for events printed to screen
+    /// Encoding format for events printed to screen. `raw` prints the message of logs, and the
+    /// text representation of metrics
     #[arg(default_value = "json", short = 'f', long)]
     format: TapEncodingFormat,
 
+    /// VRL condition that events must match to be printed, evaluated by the Vector instance
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// Print one out of every N events matching the filter
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    sample_rate: Option<u32>,
+
+    /// Maximum number of events to print each second
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    max_events_per_second: Option<u32>,
+
     /// Components IDs to observe (comma-separated; accepts glob patterns)
     #[arg(value_delimiter(','))]
     component_id_patterns: Vec<String>,
@@ -689,7 +689,7 @@ pub async fn start_topology(
 ) {
     config.healthchecks.set_require_healthy(require_healthy);
     let diff = ConfigDiff::initial(&config);
-    let pieces = topology::build_or_log_errors(&config, &diff, HashMap::new())
+    let pieces = topology::build_or_log_errors(&config, &diff, HashMap::new(), Default::default())
         .await
         .unwrap();
     topology::start_validated(config, diff, pieces)
@@ -7,6 +7,7 @@ use vector_api_client::{connect_subscription_client, Client};
 
 use super::{
     dashboard::{init_dashboard, is_tty},
+    export::export_snapshots,
     metrics,
     state::{self, ConnectionStatus, EventType},
 };
@@ -18,8 +19,8 @@ const RECONNECT_DELAY: u64 = 5000;
 /// CLI command func for displaying Vector components, and communicating with a local/remote
 /// Vector API server via HTTP/WebSockets
 pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
-    // Exit early if the terminal is not a teletype
-    if !is_tty() {
+    // Exit early if the terminal is not a teletype, unless the dashboard isn't displayed
+    if opts.export.is_none() && !is_tty() {
         #[allow(clippy::print_stderr)]
         {
             eprintln!("Terminal must be a teletype (TTY) to display a Vector dashboard.");
@@ -44,7 +45,7 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
 
     // Create a channel for updating state via event messages
     let (tx, rx) = tokio::sync::mpsc::channel(20);
-    let state_rx = state::updater(rx).await;
+    let state_rx = state::updater(rx, opts.history_len()).await;
 
     // Change the HTTP schema to WebSockets
     let mut ws_url = url.clone();
@@ -105,6 +106,21 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
         }
     });
 
+    if let Some(format) = opts.export {
+        let result = export_snapshots(format, opts.interval, state_rx, shutdown_rx).await;
+        connection.abort();
+        return match result {
+            Ok(()) => exitcode::OK,
+            Err(error) => {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!("Couldn't write snapshot: {}.", error);
+                }
+                exitcode::IOERR
+            }
+        };
+    }
+
     // Initialize the dashboard
     match init_dashboard(url.as_str(), opts, state_rx, shutdown_rx).await {
         Ok(_) => {
@@ -9,7 +9,7 @@ use crossterm::{
 };
 use num_format::{Locale, ToFormattedString};
 use number_prefix::NumberPrefix;
-use std::io::stdout;
+use std::{collections::VecDeque, io::stdout};
 use tokio::sync::oneshot;
 use tui::{
     backend::{Backend, CrosstermBackend},
@@ -104,10 +104,40 @@ fn format_metric(total: i64, throughput: i64, human_metrics: bool) -> String {
     }
 }
 
+/// Renders a history of throughputs as a sparkline of at most `width` characters. When there are
+/// more samples than characters, each character shows the highest throughput of the samples it
+/// covers, so that spikes remain visible.
+fn sparkline(samples: &VecDeque<i64>, width: usize) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    if samples.is_empty() || width == 0 {
+        return "--".into();
+    }
+
+    let samples = samples.iter().copied().collect::<Vec<_>>();
+    let chunk_size = (samples.len() + width - 1) / width;
+    let buckets = samples
+        .chunks(chunk_size)
+        .map(|chunk| chunk.iter().copied().max().unwrap_or_default().max(0))
+        .collect::<Vec<_>>();
+    let max = buckets.iter().copied().max().unwrap_or_default();
+
+    buckets
+        .into_iter()
+        .map(|v| match max {
+            0 => BARS[0],
+            max => BARS[(v * (BARS.len() as i64 - 1) / max) as usize],
+        })
+        .collect()
+}
+
+/// Width of the throughput history sparklines, in characters.
+const SPARKLINE_WIDTH: usize = 20;
+
 const NUM_COLUMNS: usize = if is_allocation_tracking_enabled() {
-    9
+    10
 } else {
-    8
+    9
 };
 
 static HEADER: [&str; NUM_COLUMNS] = [
@@ -121,6 +151,7 @@ static HEADER: [&str; NUM_COLUMNS] = [
     "Errors",
     #[cfg(feature = "allocation-tracing")]
     "Mem Usage Bytes",
+    "Events Out History",
 ];
 
 struct Widgets<'a> {
@@ -158,6 +189,10 @@ impl<'a> Widgets<'a> {
                 format!(" | Sampling @ {}ms", self.opts.interval.thousands_format()),
                 Style::default().fg(Color::Gray),
             ),
+            Span::styled(
+                format!(" | History: {}m", self.opts.history_minutes),
+                Style::default().fg(Color::Gray),
+            ),
             Span::from(" | "),
             Span::styled(connection_status.to_string(), connection_status.style()),
         ])];
@@ -218,6 +253,11 @@ impl<'a> Widgets<'a> {
                 },
                 #[cfg(feature = "allocation-tracing")]
                 r.allocated_bytes.human_format(),
+                state
+                    .history
+                    .get(&r.key)
+                    .map(|h| sparkline(&h.sent_events_throughput_sec, SPARKLINE_WIDTH))
+                    .unwrap_or_else(|| "--".into()),
             ];
 
             data.extend_from_slice(&formatted_metrics);
@@ -248,26 +288,28 @@ impl<'a> Widgets<'a> {
             .column_spacing(2)
             .widths(if is_allocation_tracking_enabled() {
                 &[
-                    Constraint::Percentage(15), // ID
-                    Constraint::Percentage(6),  // Output
-                    Constraint::Percentage(8),  // Kind
-                    Constraint::Percentage(10), // Type
+                    Constraint::Percentage(12), // ID
+                    Constraint::Percentage(5),  // Output
+                    Constraint::Percentage(7),  // Kind
+                    Constraint::Percentage(8),  // Type
                     Constraint::Percentage(10), // Events In
                     Constraint::Percentage(10), // Events Out
-                    Constraint::Percentage(10), // Bytes
+                    Constraint::Percentage(9),  // Bytes
                     Constraint::Percentage(5),  // Errors
-                    Constraint::Percentage(16), // Allocated Bytes
+                    Constraint::Percentage(12), // Allocated Bytes
+                    Constraint::Percentage(22), // Events Out History
                 ]
             } else {
                 &[
-                    Constraint::Percentage(15), // ID
-                    Constraint::Percentage(15), // Output
-                    Constraint::Percentage(10), // Kind
-                    Constraint::Percentage(10), // Type
+                    Constraint::Percentage(13), // ID
+                    Constraint::Percentage(10), // Output
+                    Constraint::Percentage(8),  // Kind
+                    Constraint::Percentage(9),  // Type
                     Constraint::Percentage(10), // Events In
                     Constraint::Percentage(10), // Events Out
                     Constraint::Percentage(10), // Bytes
-                    Constraint::Percentage(10), // Errors
+                    Constraint::Percentage(8),  // Errors
+                    Constraint::Percentage(22), // Events Out History
                 ]
             });
         f.render_widget(w, area);
@@ -467,6 +509,15 @@ mod tests {
         assert_eq!(N.human_format(), "1.10 T");
     }
 
+    #[test]
+    /// Sparklines should be scaled to the highest throughput, and keep spikes when downsampled
+    fn format_sparkline() {
+        assert_eq!(sparkline(&VecDeque::new(), 4), "--");
+        assert_eq!(sparkline(&VecDeque::from([0, 0]), 4), "▁▁");
+        assert_eq!(sparkline(&VecDeque::from([0, 7, 14]), 4), "▁▄█");
+        assert_eq!(sparkline(&VecDeque::from([0, 14, 0, 0, 7, 0]), 3), "█▁▄");
+    }
+
     #[test]
     /// Should format bytes
     fn format_bytes() {
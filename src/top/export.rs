@@ -0,0 +1,165 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::oneshot;
+
+use super::{state, ExportFormat};
+
+/// Snapshot of the metrics of all components at a point in time.
+#[derive(Serialize)]
+struct Snapshot<'a> {
+    timestamp: DateTime<Utc>,
+    connection_status: String,
+    components: Vec<ComponentSnapshot<'a>>,
+}
+
+#[derive(Serialize)]
+struct ComponentSnapshot<'a> {
+    id: &'a str,
+    kind: &'a str,
+    #[serde(rename = "type")]
+    component_type: &'a str,
+    received_events_total: i64,
+    received_events_throughput_sec: i64,
+    sent_events_total: i64,
+    sent_events_throughput_sec: i64,
+    processed_bytes_total: i64,
+    processed_bytes_throughput_sec: i64,
+    errors: i64,
+    outputs: BTreeMap<&'a str, OutputSnapshot>,
+}
+
+#[derive(Serialize)]
+struct OutputSnapshot {
+    sent_events_total: i64,
+    sent_events_throughput_sec: i64,
+}
+
+impl<'a> Snapshot<'a> {
+    fn new(state: &'a state::State) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            connection_status: state.connection_status.to_string(),
+            components: state
+                .components
+                .values()
+                .map(|r| ComponentSnapshot {
+                    id: r.key.id(),
+                    kind: &r.kind,
+                    component_type: &r.component_type,
+                    received_events_total: r.received_events_total,
+                    received_events_throughput_sec: r.received_events_throughput_sec,
+                    sent_events_total: r.sent_events_total,
+                    sent_events_throughput_sec: r.sent_events_throughput_sec,
+                    processed_bytes_total: r.processed_bytes_total,
+                    processed_bytes_throughput_sec: r.processed_bytes_throughput_sec,
+                    errors: r.errors,
+                    outputs: r
+                        .outputs
+                        .iter()
+                        .map(|(id, o)| {
+                            (
+                                id.as_str(),
+                                OutputSnapshot {
+                                    sent_events_total: o.sent_events_total,
+                                    sent_events_throughput_sec: o.sent_events_throughput_sec,
+                                },
+                            )
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn write_snapshot(
+    writer: &mut impl Write,
+    format: ExportFormat,
+    state: &state::State,
+) -> io::Result<()> {
+    let snapshot = Snapshot::new(state);
+    match format {
+        ExportFormat::Json => serde_json::to_writer(&mut *writer, &snapshot)?,
+    }
+    writeln!(writer)?;
+    writer.flush()
+}
+
+/// Writes a snapshot of the latest state to stdout at every interval, until the connection is
+/// shut down. This is the counterpart of the dashboard, for when `vector top` isn't used
+/// interactively.
+pub async fn export_snapshots(
+    format: ExportFormat,
+    interval: u32,
+    mut state_rx: state::StateRx,
+    mut shutdown_rx: oneshot::Receiver<()>,
+) -> io::Result<()> {
+    let mut ticker = tokio::time::interval(Duration::from_millis(interval.max(1) as u64));
+    let mut latest = None;
+
+    loop {
+        tokio::select! {
+            state = state_rx.recv() => match state {
+                Some(state) => latest = Some(state),
+                None => break,
+            },
+            _ = ticker.tick() => {
+                if let Some(state) = &latest {
+                    write_snapshot(&mut io::stdout().lock(), format, state)?;
+                }
+            }
+            _ = &mut shutdown_rx => break,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use super::*;
+    use crate::config::ComponentKey;
+
+    #[test]
+    fn writes_json_snapshot() {
+        let key = ComponentKey::from("in");
+        let row = state::ComponentRow {
+            key: key.clone(),
+            kind: "source".to_string(),
+            component_type: "demo_logs".to_string(),
+            outputs: HashMap::from([("_default".to_string(), state::OutputMetrics::from(3))]),
+            processed_bytes_total: 10,
+            processed_bytes_throughput_sec: 5,
+            received_events_total: 3,
+            received_events_throughput_sec: 1,
+            sent_events_total: 3,
+            sent_events_throughput_sec: 1,
+            #[cfg(feature = "allocation-tracing")]
+            allocated_bytes: 0,
+            errors: 0,
+        };
+        let state = state::State::new(BTreeMap::from([(key, row)]));
+
+        let mut out = Vec::new();
+        write_snapshot(&mut out, ExportFormat::Json, &state).unwrap();
+        assert_eq!(out.last(), Some(&b'\n'));
+
+        let snapshot: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let component = &snapshot["components"][0];
+        assert_eq!(component["id"], "in");
+        assert_eq!(component["type"], "demo_logs");
+        assert_eq!(component["sent_events_total"], 3);
+        assert_eq!(
+            component["outputs"]["_default"]["sent_events_throughput_sec"],
+            0
+        );
+    }
+}
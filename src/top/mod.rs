@@ -1,6 +1,7 @@
 mod cmd;
 mod dashboard;
 mod events;
+mod export;
 mod metrics;
 mod state;
 
@@ -26,4 +27,26 @@ pub struct Opts {
     /// Whether to reconnect if the underlying Vector API connection drops. By default, top will attempt to reconnect if the connection drops.
     #[arg(short, long)]
     no_reconnect: bool,
+
+    /// Minutes of per-component event throughputs to keep, and display as sparklines
+    #[arg(default_value = "5", long)]
+    history_minutes: u32,
+
+    /// Instead of displaying the dashboard, write a snapshot of the metrics of all components to
+    /// stdout at every interval, in the given format
+    #[arg(long, value_enum)]
+    export: Option<ExportFormat>,
+}
+
+impl Opts {
+    /// The number of samples to keep in the history of each component.
+    fn history_len(&self) -> usize {
+        (self.history_minutes as u64 * 60_000 / self.interval.max(1) as u64) as usize
+    }
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Newline delimited JSON
+    Json,
 }
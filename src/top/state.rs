@@ -1,5 +1,5 @@
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, VecDeque},
     fmt::Display,
 };
 
@@ -79,6 +79,7 @@ impl Display for ConnectionStatus {
 pub struct State {
     pub connection_status: ConnectionStatus,
     pub components: BTreeMap<ComponentKey, ComponentRow>,
+    pub history: HashMap<ComponentKey, ComponentHistory>,
 }
 
 impl State {
@@ -86,6 +87,7 @@ impl State {
         Self {
             connection_status: ConnectionStatus::Pending,
             components,
+            history: HashMap::new(),
         }
     }
 }
@@ -134,10 +136,27 @@ impl ComponentRow {
     }
 }
 
+/// Rolling history of a component's throughputs, holding one sample per interval, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct ComponentHistory {
+    pub received_events_throughput_sec: VecDeque<i64>,
+    pub sent_events_throughput_sec: VecDeque<i64>,
+}
+
+/// Appends a sample to a history, dropping the oldest samples past `len`.
+fn record(history: &mut VecDeque<i64>, sample: i64, len: usize) {
+    history.push_back(sample);
+    while history.len() > len {
+        history.pop_front();
+    }
+}
+
 /// Takes the receiver `EventRx` channel, and returns a `StateRx` state receiver. This
 /// represents the single destination for handling subscriptions and returning 'immutable' state
 /// for re-rendering the dashboard. This approach uses channels vs. mutexes.
-pub async fn updater(mut event_rx: EventRx) -> StateRx {
+///
+/// The last `history_len` throughputs of each component are kept in the state's history.
+pub async fn updater(mut event_rx: EventRx, history_len: usize) -> StateRx {
     let (tx, rx) = mpsc::channel(20);
 
     let mut state = State::new(BTreeMap::new());
@@ -145,7 +164,11 @@ pub async fn updater(mut event_rx: EventRx) -> StateRx {
         while let Some(event_type) = event_rx.recv().await {
             match event_type {
                 EventType::InitializeState(new_state) => {
+                    // History is kept across reconnects, for the components that still exist
+                    let mut history = std::mem::take(&mut state.history);
+                    history.retain(|key, _| new_state.components.contains_key(key));
                     state = new_state;
+                    state.history = history;
                 }
                 EventType::ReceivedEventsTotals(rows) => {
                     for (key, v) in rows {
@@ -159,6 +182,15 @@ pub async fn updater(mut event_rx: EventRx) -> StateRx {
                         if let Some(r) = state.components.get_mut(&key) {
                             r.received_events_throughput_sec =
                                 (v as f64 * (1000.0 / interval as f64)) as i64;
+                            record(
+                                &mut state
+                                    .history
+                                    .entry(key)
+                                    .or_default()
+                                    .received_events_throughput_sec,
+                                r.received_events_throughput_sec,
+                                history_len,
+                            );
                         }
                     }
                 }
@@ -187,6 +219,15 @@ pub async fn updater(mut event_rx: EventRx) -> StateRx {
                                     .or_insert_with(OutputMetrics::default)
                                     .sent_events_throughput_sec = throughput;
                             }
+                            record(
+                                &mut state
+                                    .history
+                                    .entry(m.key)
+                                    .or_default()
+                                    .sent_events_throughput_sec,
+                                r.sent_events_throughput_sec,
+                                history_len,
+                            );
                         }
                     }
                 }
@@ -225,6 +266,7 @@ pub async fn updater(mut event_rx: EventRx) -> StateRx {
                 }
                 EventType::ComponentRemoved(key) => {
                     let _ = state.components.remove(&key);
+                    let _ = state.history.remove(&key);
                 }
                 EventType::ConnectionUpdated(status) => {
                     state.connection_status = status;
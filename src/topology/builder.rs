@@ -1,8 +1,8 @@
 use std::{
     collections::HashMap,
     future::ready,
-    num::NonZeroUsize,
-    sync::{Arc, Mutex},
+    num::{NonZeroU64, NonZeroUsize},
+    sync::{atomic::Ordering, Arc, Mutex},
     time::Instant,
 };
 
@@ -25,7 +25,7 @@ use vector_core::{
     buffers::{
         topology::{
             builder::TopologyBuilder,
-            channel::{BufferReceiver, BufferSender},
+            channel::{BufferControl, BufferReceiver, BufferSender},
         },
         BufferType, WhenFull,
     },
@@ -35,19 +35,35 @@ use vector_core::{
 
 use super::{
     fanout::{self, Fanout},
-    schema,
+    health, input_priority, resources, schema,
+    source_filter::SourceFilter,
     task::{Task, TaskOutput, TaskResult},
     BuiltBuffer, ConfigDiff,
 };
 use crate::{
+    capture::Capture,
     config::{
         ComponentKey, DataType, EnrichmentTableConfig, Input, Inputs, Output, OutputId,
         ProxyConfig, SinkConfig, SinkContext, SourceConfig, SourceContext, TransformContext,
         TransformOuter,
     },
-    event::{EventArray, EventContainer},
-    internal_events::EventsReceived,
+    dead_letter::DeadLetter,
+    event::{
+        array::events_into_arrays, Event, EventArray, EventContainer, EventStatus, Finalizable,
+    },
+    event_latency, event_trace,
+    internal_events::{CircuitBreakerEventsDiverted, EventsReceived, StaleEventsShed},
+    internal_telemetry::otlp,
     shutdown::SourceShutdownCoordinator,
+    sinks::util::{
+        event_age::MaxEventAge,
+        partition_key::PartitionKey,
+        schema_mapping::SchemaMapping,
+        service::{
+            circuit_breaker::CircuitBreaker,
+            rate_limit_group::{RateLimitGroups, SharedRateLimit},
+        },
+    },
     source_sender::CHUNK_SIZE,
     spawn_named,
     topology::task::TaskError,
@@ -128,6 +144,10 @@ pub(self) async fn load_enrichment_tables<'a>(
     (&ENRICHMENT_TABLES, errors)
 }
 
+/// The input of the dead letter queue sink that a sink falls back to while its circuit breaker is
+/// open, connected by the running topology.
+pub(super) type DeadLetterSender = Arc<tokio::sync::Mutex<Option<BufferSender<EventArray>>>>;
+
 pub struct Pieces {
     pub(super) inputs: HashMap<ComponentKey, (BufferSender<EventArray>, Inputs<OutputId>)>,
     pub(crate) outputs: HashMap<ComponentKey, HashMap<Option<String>, fanout::ControlChannel>>,
@@ -136,6 +156,10 @@ pub struct Pieces {
     pub(super) healthchecks: HashMap<ComponentKey, Task>,
     pub(crate) shutdown_coordinator: SourceShutdownCoordinator,
     pub(crate) detach_triggers: HashMap<ComponentKey, Trigger>,
+    pub(super) dead_letter_senders: HashMap<ComponentKey, Vec<(ComponentKey, DeadLetterSender)>>,
+    pub(super) buffer_controls: HashMap<ComponentKey, BufferControl<EventArray>>,
+    pub(super) input_senders: HashMap<ComponentKey, HashMap<OutputId, BufferSender<EventArray>>>,
+    pub(super) rate_limit_groups: RateLimitGroups,
 }
 
 /// Builds only the new pieces, and doesn't check their topology.
@@ -143,6 +167,7 @@ pub async fn build_pieces(
     config: &super::Config,
     diff: &ConfigDiff,
     mut buffers: HashMap<ComponentKey, BuiltBuffer>,
+    mut rate_limit_groups: RateLimitGroups,
 ) -> Result<Pieces, Vec<String>> {
     let mut inputs = HashMap::new();
     let mut outputs = HashMap::new();
@@ -151,12 +176,32 @@ pub async fn build_pieces(
     let mut healthchecks = HashMap::new();
     let mut shutdown_coordinator = SourceShutdownCoordinator::default();
     let mut detach_triggers = HashMap::new();
+    let mut dead_letter_senders = HashMap::new();
+    let mut buffer_controls = HashMap::new();
+    let mut input_senders = HashMap::new();
 
     let mut errors = vec![];
 
     let (enrichment_tables, enrichment_errors) = load_enrichment_tables(config, diff).await;
     errors.extend(enrichment_errors);
 
+    event_trace::configure(&config.global.event_tracing);
+    event_latency::configure(config.global.end_to_end_latency);
+    otlp::configure(&config.global.self_telemetry, &config.global.proxy);
+
+    resources::check_memory_limits_enforced(
+        config
+            .transforms()
+            .filter(|(_, transform)| transform.max_allocated_bytes.is_some())
+            .map(|(key, _)| key)
+            .chain(
+                config
+                    .sinks()
+                    .filter(|(_, sink)| sink.max_allocated_bytes.is_some())
+                    .map(|(key, _)| key),
+            ),
+    );
+
     // Build sources
     for (key, source) in config
         .sources()
@@ -188,14 +233,56 @@ pub async fn build_pieces(
         let mut controls = HashMap::new();
         let mut schema_definitions = HashMap::with_capacity(source_outputs.len());
 
+        let capture = match source
+            .capture
+            .as_ref()
+            .map(|capture| Capture::open(capture, key, typetag))
+            .transpose()
+        {
+            Ok(capture) => capture,
+            Err(error) => {
+                errors.push(format!("Source \"{}\": {}", key, error));
+                continue;
+            }
+        };
+
+        let filter = match source
+            .filter
+            .as_ref()
+            .map(|condition| condition.build(enrichment_tables))
+            .transpose()
+        {
+            Ok(condition) => condition.map(|condition| Arc::new(SourceFilter::new(condition))),
+            Err(error) => {
+                errors.push(format!("Source \"{}\": Invalid `filter`: {}", key, error));
+                continue;
+            }
+        };
+
         for output in source_outputs {
             let mut rx = builder.add_output(output.clone());
 
             let (mut fanout, control) = Fanout::new();
+            let source_key = key.clone();
+            let latency_key = Arc::new(key.clone());
+            // Only the default output is captured, since it is the one replayed.
+            let capture = capture.clone().filter(|_| output.port.is_none());
+            let filter = filter.clone();
             let pump = async move {
                 debug!("Source pump starting.");
 
-                while let Some(array) = rx.next().await {
+                while let Some(mut array) = rx.next().await {
+                    if let Some(filter) = &filter {
+                        array = filter.apply(array);
+                        if array.is_empty() {
+                            continue;
+                        }
+                    }
+                    event_trace::start(&mut array, &source_key);
+                    event_latency::start(&mut array, &latency_key);
+                    if let Some(capture) = &capture {
+                        capture.record(&array);
+                    }
                     fanout.send(array).await.map_err(|e| {
                         debug!("Source pump finished with an error.");
                         TaskError::wrapped(e)
@@ -269,6 +356,21 @@ pub async fn build_pieces(
             schema_definitions,
             schema: config.schema,
         };
+        #[cfg(any(
+            feature = "leader-election-kubernetes",
+            feature = "leader-election-redis"
+        ))]
+        let source = match &source.leader_election {
+            Some(election) => match election.build(&context).await {
+                Ok(election) => Ok(election.run(source.inner.clone(), context)),
+                Err(error) => Err(error),
+            },
+            None => source.inner.build(context).await,
+        };
+        #[cfg(not(any(
+            feature = "leader-election-kubernetes",
+            feature = "leader-election-redis"
+        )))]
         let source = source.inner.build(context).await;
         let server = match source {
             Err(error) => {
@@ -439,6 +541,7 @@ pub async fn build_pieces(
             let buffer_type = match sink.buffer.stages().first().expect("cant ever be empty") {
                 BufferType::Memory { .. } => "memory",
                 BufferType::DiskV2 { .. } => "disk",
+                BufferType::Hybrid { .. } => "hybrid",
             };
             let buffer_span = error_span!(
                 "sink",
@@ -460,13 +563,128 @@ pub async fn build_pieces(
                 Ok((tx, rx)) => (tx, Arc::new(Mutex::new(Some(rx.into_stream())))),
             }
         };
+        if let Some(rx) = rx.lock().unwrap().as_ref() {
+            buffer_controls.insert(key.clone(), rx.control());
+        }
+
+        if let Some(input) = sink.input_priorities.keys().find(|input| {
+            !sink_inputs
+                .iter()
+                .any(|sink_input| sink_input.to_string() == **input)
+        }) {
+            errors.push(format!(
+                "Sink \"{}\": Unknown input \"{}\" in `input_priorities`.",
+                key, input
+            ));
+            continue;
+        }
+        let mut sink_input_senders = HashMap::new();
+        let mut input_queues = Vec::new();
+        for input in sink_inputs {
+            if let Some(priority) = sink.input_priorities.get(&input.to_string()) {
+                let (input_tx, input_rx) =
+                    TopologyBuilder::standalone_memory(priority.max_events, priority.when_full)
+                        .await;
+                sink_input_senders.insert(input.clone(), input_tx);
+                input_queues.push((priority.priority, input_rx.into_stream()));
+            }
+        }
+
+        let rate_limit = sink.rate_limit_group().and_then(|name| {
+            config
+                .global
+                .rate_limit_groups
+                .get(name)
+                .map(|group| rate_limit_groups.get_or_create(name, group))
+        });
+
+        let breaker = sink.circuit_breaker().map(CircuitBreaker::new);
 
         let cx = SinkContext {
             healthcheck,
             globals: config.global.clone(),
             proxy: ProxyConfig::merge_with_env(&config.global.proxy, sink.proxy()),
             schema: config.schema,
+            schema_definition: schema::sink_input_definition(
+                sink_inputs,
+                config,
+                &mut definition_cache,
+            ),
+            rate_limit_group: rate_limit.clone(),
+            circuit_breaker: breaker.clone(),
+            request_settings_taken: Arc::default(),
+        };
+        let request_settings_taken = Arc::clone(&cx.request_settings_taken);
+
+        let max_allocated_bytes = sink.max_allocated_bytes;
+        let mut partition_key = match sink
+            .partition_key
+            .as_deref()
+            .map(|source| PartitionKey::build(source, enrichment_tables))
+            .transpose()
+        {
+            Ok(partition_key) => partition_key,
+            Err(error) => {
+                errors.push(format!(
+                    "Sink \"{}\": Invalid `partition_key`: {}",
+                    key, error
+                ));
+                continue;
+            }
+        };
+        let schema_mapping = match sink
+            .schema_mapping
+            .as_ref()
+            .map(SchemaMapping::build)
+            .transpose()
+        {
+            Ok(schema_mapping) => schema_mapping,
+            Err(error) => {
+                errors.push(format!(
+                    "Sink \"{}\": Invalid `schema_mapping`: {}",
+                    key, error
+                ));
+                continue;
+            }
         };
+        let dead_letter_queue = sink.circuit_breaker().and_then(|breaker| {
+            breaker.dead_letter_queue(config.global.dead_letter_queue.as_ref())
+        });
+        let open_circuit = breaker.clone().map(|breaker| {
+            let dead_letter_queue = dead_letter_queue.map(|queue| {
+                let sender = DeadLetterSender::default();
+                dead_letter_senders
+                    .entry(key.clone())
+                    .or_insert_with(Vec::new)
+                    .push((queue.clone(), Arc::clone(&sender)));
+                sender
+            });
+            Arc::new(OpenCircuit {
+                breaker,
+                dead_letter_queue,
+                component_key: key.clone(),
+                component_type: typetag,
+            })
+        });
+
+        let stale_events = sink.max_event_age.as_ref().map(|max_event_age| {
+            let dead_letter_queue = max_event_age
+                .dead_letter_queue(config.global.dead_letter_queue.as_ref())
+                .map(|queue| {
+                    let sender = DeadLetterSender::default();
+                    dead_letter_senders
+                        .entry(key.clone())
+                        .or_insert_with(Vec::new)
+                        .push((queue.clone(), Arc::clone(&sender)));
+                    sender
+                });
+            Arc::new(StaleEvents {
+                max_event_age: MaxEventAge::new(max_event_age),
+                dead_letter_queue,
+                component_key: key.clone(),
+                component_type: typetag,
+            })
+        });
 
         let (sink, healthcheck) = match sink.inner.build(cx).await {
             Err(error) => {
@@ -475,8 +693,30 @@ pub async fn build_pieces(
             }
             Ok(built) => built,
         };
+        let request_settings_taken = request_settings_taken.load(Ordering::Relaxed);
+        if let Some(group) = rate_limit
+            .as_ref()
+            .filter(|group| group.limits_requests() && !request_settings_taken)
+        {
+            errors.push(format!(
+                "Sink \"{}\": The `{}` sink doesn't support the `request_limit` of rate limit group \"{}\".",
+                key,
+                typetag,
+                group.name()
+            ));
+            continue;
+        }
+        if open_circuit.is_some() && !request_settings_taken {
+            errors.push(format!(
+                "Sink \"{}\": The `{}` sink doesn't support `circuit_breaker`.",
+                key, typetag
+            ));
+            continue;
+        }
 
         let (trigger, tripwire) = Tripwire::new();
+        let byte_limit = rate_limit.filter(SharedRateLimit::limits_bytes);
+        let sink_key = key.clone();
 
         let sink = async move {
             debug!("Sink starting.");
@@ -495,17 +735,68 @@ pub async fn build_pieces(
 
             let mut rx = wrap(rx);
 
+            if let Some(open_circuit) = &open_circuit {
+                open_circuit.breaker.emit_state();
+            }
+
             let events_received = register!(EventsReceived);
             sink.run(
-                rx.by_ref()
-                    .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
-                    .inspect(|events| {
-                        events_received.emit(CountByteSize(
-                            events.len(),
-                            events.estimated_json_encoded_size_of(),
-                        ))
-                    })
-                    .take_until_if(tripwire),
+                resources::limit_memory(
+                    input_priority::prioritize(rx.by_ref(), input_queues),
+                    max_allocated_bytes,
+                )
+                .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
+                .filter_map(move |events| {
+                    let stale_events = stale_events.clone();
+                    async move {
+                        match stale_events {
+                            Some(stale_events) => stale_events.shed(events).await,
+                            None => Some(events),
+                        }
+                    }
+                })
+                .map(move |events| match &mut partition_key {
+                    Some(partition_key) => partition_key.apply(events),
+                    None => events,
+                })
+                .map(move |events| match &schema_mapping {
+                    Some(schema_mapping) => schema_mapping.apply(events),
+                    None => events,
+                })
+                .inspect(|events| {
+                    events_received.emit(CountByteSize(
+                        events.len(),
+                        events.estimated_json_encoded_size_of(),
+                    ));
+                    event_trace::record(events, &sink_key, "sink");
+                })
+                .map(|mut events| {
+                    event_latency::track(&mut events);
+                    events
+                })
+                .take_until_if(tripwire)
+                .filter_map(move |events| {
+                    let open_circuit = open_circuit.clone();
+                    async move {
+                        match open_circuit {
+                            Some(open_circuit) => open_circuit.divert(events).await,
+                            None => Some(events),
+                        }
+                    }
+                })
+                // Throttled after the tripwire, so that events already taken from the buffer
+                // are still sent when the sink is shut down.
+                .then(move |events| {
+                    let byte_limit = byte_limit.clone();
+                    async move {
+                        if let Some(byte_limit) = byte_limit {
+                            byte_limit
+                                .acquire_bytes(events.estimated_json_encoded_size_of())
+                                .await;
+                        }
+                        events
+                    }
+                }),
             )
             .await
             .map(|_| {
@@ -522,15 +813,18 @@ pub async fn build_pieces(
 
         let component_key = key.clone();
         let healthcheck_task = async move {
+            let tracker = health::HealthcheckTracker::start(component_key.clone());
             if enable_healthcheck {
                 let duration = Duration::from_secs(10);
                 timeout(duration, healthcheck)
                     .map(|result| match result {
                         Ok(Ok(_)) => {
                             info!("Healthcheck passed.");
+                            tracker.finish(health::HealthcheckStatus::Passed);
                             Ok(TaskOutput::Healthcheck)
                         }
                         Ok(Err(error)) => {
+                            tracker.finish(health::HealthcheckStatus::Failed(error.to_string()));
                             error!(
                                 msg = "Healthcheck failed.",
                                 %error,
@@ -543,6 +837,8 @@ pub async fn build_pieces(
                             Err(TaskError::wrapped(error))
                         }
                         Err(e) => {
+                            tracker
+                                .finish(health::HealthcheckStatus::Failed("timed out".to_string()));
                             error!(
                                 msg = "Healthcheck timed out.",
                                 component_kind = "sink",
@@ -557,6 +853,7 @@ pub async fn build_pieces(
                     .await
             } else {
                 info!("Healthcheck disabled.");
+                tracker.finish(health::HealthcheckStatus::Disabled);
                 Ok(TaskOutput::Healthcheck)
             }
         };
@@ -564,6 +861,9 @@ pub async fn build_pieces(
         let healthcheck_task = Task::new(key.clone(), typetag, healthcheck_task);
 
         inputs.insert(key.clone(), (tx, sink_inputs.clone()));
+        if !sink_input_senders.is_empty() {
+            input_senders.insert(key.clone(), sink_input_senders);
+        }
         healthchecks.insert(key.clone(), healthcheck_task);
         tasks.insert(key.clone(), task);
         detach_triggers.insert(key.clone(), trigger);
@@ -590,6 +890,10 @@ pub async fn build_pieces(
             healthchecks,
             shutdown_coordinator,
             detach_triggers,
+            dead_letter_senders,
+            buffer_controls,
+            input_senders,
+            rate_limit_groups,
         };
 
         Ok(pieces)
@@ -614,6 +918,7 @@ struct TransformNode {
     input_details: Input,
     outputs: Vec<Output>,
     enable_concurrency: bool,
+    max_allocated_bytes: Option<NonZeroU64>,
 }
 
 impl TransformNode {
@@ -632,6 +937,122 @@ impl TransformNode {
                 .inner
                 .outputs(schema_definition, global_log_namespace),
             enable_concurrency: transform.inner.enable_concurrency(),
+            max_allocated_bytes: transform.max_allocated_bytes,
+        }
+    }
+}
+
+/// Diverts the events sent to a sink while its circuit breaker is open.
+struct OpenCircuit {
+    breaker: CircuitBreaker,
+    dead_letter_queue: Option<DeadLetterSender>,
+    component_key: ComponentKey,
+    component_type: &'static str,
+}
+
+impl OpenCircuit {
+    /// Returns the events to send to the sink, or `None` if they were diverted to the fallback of
+    /// the circuit breaker.
+    async fn divert(&self, mut events: EventArray) -> Option<EventArray> {
+        if !self.breaker.is_open() {
+            return Some(events);
+        }
+
+        let count = events.len();
+        if let Some(dead_letter_queue) = &self.dead_letter_queue {
+            let dead_letter = DeadLetter {
+                reason: "circuit_open",
+                message: "The circuit breaker of the sink is open.".into(),
+                component_id: Some(&self.component_key),
+                component_type: self.component_type,
+                component_kind: "sink",
+            };
+            if let Some(sender) = dead_letter_queue.lock().await.as_mut() {
+                send_to_dead_letter_queue(sender, &dead_letter, events.into_events()).await;
+                emit!(CircuitBreakerEventsDiverted {
+                    count,
+                    dead_lettered: true,
+                });
+                return None;
+            }
+        }
+
+        events
+            .take_finalizers()
+            .update_status(EventStatus::Rejected);
+        emit!(CircuitBreakerEventsDiverted {
+            count,
+            dead_lettered: false,
+        });
+        None
+    }
+}
+
+struct StaleEvents {
+    max_event_age: MaxEventAge,
+    dead_letter_queue: Option<DeadLetterSender>,
+    component_key: ComponentKey,
+    component_type: &'static str,
+}
+
+impl StaleEvents {
+    /// Returns the events to send to the sink, or `None` if they were all shed for being older than
+    /// the maximum event age of the sink.
+    async fn shed(&self, events: EventArray) -> Option<EventArray> {
+        let (events, stale) = self.max_event_age.split(events);
+        if stale.is_empty() {
+            return Some(events);
+        }
+
+        let count = stale.len();
+        let mut dead_letter_queue = match &self.dead_letter_queue {
+            Some(dead_letter_queue) => Some(dead_letter_queue.lock().await),
+            None => None,
+        };
+        let dead_lettered = match dead_letter_queue
+            .as_mut()
+            .and_then(|sender| sender.as_mut())
+        {
+            Some(sender) => {
+                let dead_letter = DeadLetter {
+                    reason: "stale_event",
+                    message: "The event is older than the maximum event age of the sink.".into(),
+                    component_id: Some(&self.component_key),
+                    component_type: self.component_type,
+                    component_kind: "sink",
+                };
+                send_to_dead_letter_queue(sender, &dead_letter, stale.into_iter()).await;
+                true
+            }
+            None => {
+                for mut event in stale {
+                    event.take_finalizers().update_status(EventStatus::Rejected);
+                }
+                false
+            }
+        };
+        emit!(StaleEventsShed {
+            count,
+            dead_lettered,
+        });
+
+        (!events.is_empty()).then_some(events)
+    }
+}
+
+/// Sends the events to a dead letter queue sink, annotated with the given envelope.
+async fn send_to_dead_letter_queue(
+    sender: &mut BufferSender<EventArray>,
+    dead_letter: &DeadLetter<'_>,
+    events: impl Iterator<Item = Event>,
+) {
+    let events = events.map(|mut event| {
+        dead_letter.annotate(&mut event);
+        event
+    });
+    for array in events_into_arrays(events, None) {
+        if let Err(error) = sender.send(array).await {
+            error!(message = "Failed to send events to the dead letter queue.", %error);
         }
     }
 }
@@ -651,6 +1072,7 @@ fn build_transform(
             node.input_details.data_type(),
             node.typetag,
             &node.key,
+            node.max_allocated_bytes,
         ),
     }
 }
@@ -662,7 +1084,14 @@ fn build_sync_transform(
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
     let (outputs, controls) = TransformOutputs::new(node.outputs);
 
-    let runner = Runner::new(t, input_rx, node.input_details.data_type(), outputs);
+    let runner = Runner::new(
+        t,
+        input_rx,
+        node.input_details.data_type(),
+        outputs,
+        node.key.clone(),
+        node.max_allocated_bytes,
+    );
     let transform = if node.enable_concurrency {
         runner.run_concurrently().boxed()
     } else {
@@ -702,6 +1131,8 @@ struct Runner {
     input_rx: Option<BufferReceiver<EventArray>>,
     input_type: DataType,
     outputs: TransformOutputs,
+    key: ComponentKey,
+    max_allocated_bytes: Option<NonZeroU64>,
     timer: crate::utilization::Timer,
     last_report: Instant,
     events_received: Registered<EventsReceived>,
@@ -713,13 +1144,18 @@ impl Runner {
         input_rx: BufferReceiver<EventArray>,
         input_type: DataType,
         outputs: TransformOutputs,
+        key: ComponentKey,
+        max_allocated_bytes: Option<NonZeroU64>,
     ) -> Self {
+        let timer = crate::utilization::Timer::new().with_queue_depth(input_rx.depth());
         Self {
             transform,
             input_rx: Some(input_rx),
             input_type,
             outputs,
-            timer: crate::utilization::Timer::new(),
+            key,
+            max_allocated_bytes,
+            timer,
             last_report: Instant::now(),
             events_received: register!(EventsReceived),
         }
@@ -736,6 +1172,7 @@ impl Runner {
             events.len(),
             events.estimated_json_encoded_size_of(),
         ));
+        event_trace::record(events, &self.key, "transform");
     }
 
     async fn send_outputs(&mut self, outputs_buf: &mut TransformOutputsBuf) -> crate::Result<()> {
@@ -749,11 +1186,12 @@ impl Runner {
 
         let mut outputs_buf = self.outputs.new_buf_with_capacity(INLINE_BATCH_SIZE);
 
-        let mut input_rx = self
+        let input_rx = self
             .input_rx
             .take()
             .expect("can't run runner twice")
-            .into_stream()
+            .into_stream();
+        let mut input_rx = resources::limit_memory(input_rx, self.max_allocated_bytes)
             .filter(move |events| ready(filter_events_type(events, self.input_type)));
 
         self.timer.start_wait();
@@ -773,7 +1211,8 @@ impl Runner {
             .input_rx
             .take()
             .expect("can't run runner twice")
-            .into_stream()
+            .into_stream();
+        let input_rx = resources::limit_memory(input_rx, self.max_allocated_bytes)
             .filter(move |events| ready(filter_events_type(events, self.input_type)));
 
         let mut input_rx =
@@ -842,19 +1281,26 @@ fn build_task_transform(
     input_type: DataType,
     typetag: &str,
     key: &ComponentKey,
+    max_allocated_bytes: Option<NonZeroU64>,
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
     let (mut fanout, control) = Fanout::new();
 
-    let input_rx = crate::utilization::wrap(input_rx.into_stream());
+    let queue_depth = input_rx.depth();
+    let input_rx = resources::limit_memory(
+        crate::utilization::wrap(input_rx.into_stream()).with_queue_depth(queue_depth),
+        max_allocated_bytes,
+    );
 
     let events_received = register!(EventsReceived);
+    let component_key = key.clone();
     let filtered = input_rx
         .filter(move |events| ready(filter_events_type(events, input_type)))
         .inspect(move |events| {
             events_received.emit(CountByteSize(
                 events.len(),
                 events.estimated_json_encoded_size_of(),
-            ))
+            ));
+            event_trace::record(events, &component_key, "transform");
         });
     let events_sent = register!(EventsSent::from(internal_event::Output(None)));
     let stream = t
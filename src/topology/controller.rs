@@ -1,3 +1,7 @@
+use std::time::Duration;
+
+use serde_json::json;
+
 #[cfg(feature = "enterprise")]
 use futures_util::future::BoxFuture;
 
@@ -10,7 +14,7 @@ use crate::config::enterprise::{
 use crate::internal_events::{
     VectorConfigLoadError, VectorRecoveryError, VectorReloadError, VectorReloaded,
 };
-use crate::{config, topology::RunningTopology};
+use crate::{audit, config, topology::RunningTopology};
 
 pub struct TopologyController {
     pub topology: RunningTopology,
@@ -45,6 +49,7 @@ impl TopologyController {
 
         if new_config.is_none() {
             emit!(VectorConfigLoadError);
+            audit::record("config_reload", json!({ "outcome": "invalid" }));
             return NoConfig;
         }
         let mut new_config = new_config.unwrap();
@@ -69,6 +74,7 @@ impl TopologyController {
             Err(err) => {
                 if let EnterpriseError::MissingApiKey = err {
                     emit!(VectorReloadError);
+                    audit::record("config_reload", json!({ "outcome": "missing_api_key" }));
                     return MissingApiKey;
                 }
             }
@@ -82,6 +88,10 @@ impl TopologyController {
                     api_server.update_config(self.topology.config());
                 }
 
+                // The new config may change the audit log, which records the reload itself.
+                audit::configure(self.topology.config().global.audit_log.as_ref());
+                audit::record("config_reload", json!({ "outcome": "applied" }));
+
                 emit!(VectorReloaded {
                     config_paths: &self.config_paths
                 });
@@ -89,18 +99,20 @@ impl TopologyController {
             }
             Ok(false) => {
                 emit!(VectorReloadError);
+                audit::record("config_reload", json!({ "outcome": "rolled_back" }));
                 RolledBack
             }
             // Trigger graceful shutdown for what remains of the topology
             Err(()) => {
                 emit!(VectorReloadError);
+                audit::record("config_reload", json!({ "outcome": "failed" }));
                 emit!(VectorRecoveryError);
                 FatalError
             }
         }
     }
 
-    pub async fn stop(self) {
-        self.topology.stop().await;
+    pub async fn stop(self, timeout: Duration) {
+        self.topology.stop_within(timeout).await;
     }
 }
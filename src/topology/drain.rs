@@ -0,0 +1,101 @@
+//! Progress of draining the topology.
+//!
+//! Draining stops all sources, then waits for transforms and sinks to flush the events already
+//! in flight, up to a deadline after which the remaining components are forcefully stopped. The
+//! progress is shared with the API, so orchestrators can poll it while Vector shuts down.
+
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tokio::time::Instant;
+
+/// The state of the topology with regards to draining.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DrainState {
+    /// The topology is running normally.
+    Running,
+    /// Sources are stopped, and the remaining components are flushing their events.
+    Draining,
+    /// All components have stopped.
+    Drained,
+}
+
+/// A snapshot of the drain progress.
+#[derive(Clone, Debug, Serialize)]
+pub struct DrainProgress {
+    pub state: DrainState,
+    /// The number of seconds left before the remaining components are forcefully stopped.
+    pub seconds_remaining: Option<u64>,
+    /// The components that haven't finished draining yet.
+    pub remaining_components: Vec<String>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: DrainState,
+    requested: bool,
+    deadline: Option<Instant>,
+    remaining_components: Vec<String>,
+}
+
+/// Shared handle to the drain progress of a running topology.
+#[derive(Clone, Debug)]
+pub struct DrainStatus(Arc<Mutex<Inner>>);
+
+impl Default for DrainStatus {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(Inner {
+            state: DrainState::Running,
+            requested: false,
+            deadline: None,
+            remaining_components: Vec::new(),
+        })))
+    }
+}
+
+impl DrainStatus {
+    /// Marks a drain as requested.
+    ///
+    /// Returns `false` if a drain was already requested, or the topology is already draining, in
+    /// which case the drain shouldn't be requested again.
+    pub fn request(&self) -> bool {
+        let mut inner = self.0.lock().expect("poisoned lock");
+        if inner.requested || inner.state != DrainState::Running {
+            return false;
+        }
+        inner.requested = true;
+        true
+    }
+
+    pub fn progress(&self) -> DrainProgress {
+        let inner = self.0.lock().expect("poisoned lock");
+        DrainProgress {
+            state: inner.state,
+            seconds_remaining: inner.deadline.map(|deadline| {
+                deadline
+                    .checked_duration_since(Instant::now())
+                    .map_or(0, |remaining| remaining.as_secs())
+            }),
+            remaining_components: inner.remaining_components.clone(),
+        }
+    }
+
+    pub(super) fn start(&self, deadline: Instant, remaining_components: Vec<String>) {
+        let mut inner = self.0.lock().expect("poisoned lock");
+        inner.state = DrainState::Draining;
+        inner.deadline = Some(deadline);
+        inner.remaining_components = remaining_components;
+    }
+
+    pub(super) fn update(&self, remaining_components: Vec<String>) {
+        self.0.lock().expect("poisoned lock").remaining_components = remaining_components;
+    }
+
+    pub(super) fn finish(&self) {
+        let mut inner = self.0.lock().expect("poisoned lock");
+        inner.state = DrainState::Drained;
+        inner.deadline = None;
+        inner.remaining_components.clear();
+    }
+}
@@ -0,0 +1,293 @@
+//! Health of the running components.
+//!
+//! The topology records whether the task of each component is running and, for sinks, the outcome
+//! of their healthcheck. The API combines this with the usage of the buffers of sinks to report
+//! the liveness and readiness of each component, so orchestrators can probe them individually.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        RwLock,
+    },
+};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use vector_buffers::topology::channel::BufferControl;
+
+use crate::{config::ComponentKey, event::EventArray};
+
+static COMPONENTS: Lazy<RwLock<HashMap<ComponentKey, ComponentState>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentKind {
+    Source,
+    Transform,
+    Sink,
+}
+
+/// The outcome of the healthcheck of a sink.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HealthcheckStatus {
+    Pending,
+    Passed,
+    Disabled,
+    Failed(String),
+}
+
+#[derive(Clone, Debug)]
+struct ComponentState {
+    kind: ComponentKind,
+    /// Identifies the task currently running for the component, so that the task of a previous
+    /// version of the component finishing after a reload doesn't mark the new one as stopped.
+    generation: u64,
+    running: bool,
+    healthcheck: Option<HealthcheckStatus>,
+}
+
+impl ComponentState {
+    const fn new(kind: ComponentKind) -> Self {
+        Self {
+            kind,
+            generation: 0,
+            running: false,
+            healthcheck: None,
+        }
+    }
+}
+
+fn update(key: &ComponentKey, kind: ComponentKind, f: impl FnOnce(&mut ComponentState)) {
+    let mut components = COMPONENTS.write().expect("poisoned lock");
+    f(components
+        .entry(key.clone())
+        .or_insert_with(|| ComponentState::new(kind)));
+}
+
+/// Records that the task of a component started, returning the generation to pass to [`stopped`]
+/// once it finishes.
+pub(super) fn started(key: &ComponentKey, kind: ComponentKind) -> u64 {
+    let generation = GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    update(key, kind, |state| {
+        state.kind = kind;
+        state.generation = generation;
+        state.running = true;
+    });
+    generation
+}
+
+/// Records that the task of a component finished.
+pub(super) fn stopped(key: &ComponentKey, generation: u64) {
+    let mut components = COMPONENTS.write().expect("poisoned lock");
+    if let Some(state) = components.get_mut(key) {
+        if state.generation == generation {
+            state.running = false;
+        }
+    }
+}
+
+/// Forgets a component that was removed from the topology.
+pub(super) fn removed(key: &ComponentKey) {
+    COMPONENTS.write().expect("poisoned lock").remove(key);
+}
+
+/// Tracks the healthcheck of a sink while it runs.
+///
+/// If the healthcheck is cancelled before it completes, such as when the healthcheck of another
+/// sink fails first, the sink goes back to not having been checked.
+pub(super) struct HealthcheckTracker {
+    key: ComponentKey,
+    finished: bool,
+}
+
+impl HealthcheckTracker {
+    pub(super) fn start(key: ComponentKey) -> Self {
+        update(&key, ComponentKind::Sink, |state| {
+            state.healthcheck = Some(HealthcheckStatus::Pending);
+        });
+        Self {
+            key,
+            finished: false,
+        }
+    }
+
+    pub(super) fn finish(mut self, status: HealthcheckStatus) {
+        self.finished = true;
+        update(&self.key, ComponentKind::Sink, |state| {
+            state.healthcheck = Some(status);
+        });
+    }
+}
+
+impl Drop for HealthcheckTracker {
+    fn drop(&mut self) {
+        if !self.finished {
+            // The sink may have been removed in the meantime, so it isn't added back.
+            let mut components = COMPONENTS.write().expect("poisoned lock");
+            if let Some(state) = components.get_mut(&self.key) {
+                state.healthcheck = None;
+            }
+        }
+    }
+}
+
+/// The health of a single component.
+#[derive(Clone, Debug, Serialize)]
+pub struct ComponentHealth {
+    pub kind: ComponentKind,
+    /// Whether the task of the component is running.
+    pub live: bool,
+    /// Whether the component is able to handle events.
+    pub ready: bool,
+    /// Whether the component is taken into account for the overall readiness.
+    pub gating: bool,
+    /// Why the component isn't ready.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reasons: Vec<String>,
+}
+
+/// The health of all of the components.
+#[derive(Clone, Debug, Serialize)]
+pub struct HealthReport {
+    /// Whether all of the gating components are live.
+    pub live: bool,
+    /// Whether all of the gating components are ready.
+    pub ready: bool,
+    pub components: BTreeMap<String, ComponentHealth>,
+}
+
+/// The policy deciding which components gate the overall readiness, and when a sink is ready.
+#[derive(Clone, Debug)]
+pub struct ReadinessPolicy {
+    /// Patterns of the IDs of the components gating readiness. All components gate readiness when
+    /// there are none.
+    pub components: Vec<glob::Pattern>,
+    /// The usage of the buffer of a sink, between 0 and 1, above which the sink isn't ready.
+    pub max_buffer_usage: f64,
+}
+
+impl ReadinessPolicy {
+    fn gates(&self, key: &ComponentKey) -> bool {
+        self.components.is_empty()
+            || self
+                .components
+                .iter()
+                .any(|pattern| pattern.matches(key.id()))
+    }
+}
+
+fn component_health(
+    state: &ComponentState,
+    buffer_usage: Option<f64>,
+    max_buffer_usage: f64,
+) -> ComponentHealth {
+    let mut reasons = Vec::new();
+    if !state.running {
+        reasons.push("not running".to_string());
+    }
+    match &state.healthcheck {
+        Some(HealthcheckStatus::Pending) => reasons.push("healthcheck pending".to_string()),
+        Some(HealthcheckStatus::Failed(error)) => {
+            reasons.push(format!("healthcheck failed: {}", error))
+        }
+        Some(HealthcheckStatus::Passed | HealthcheckStatus::Disabled) | None => {}
+    }
+    if let Some(usage) = buffer_usage.filter(|usage| *usage > max_buffer_usage) {
+        reasons.push(format!("buffer {:.0}% full", usage * 100.0));
+    }
+
+    ComponentHealth {
+        kind: state.kind,
+        live: state.running,
+        ready: reasons.is_empty(),
+        gating: true,
+        reasons,
+    }
+}
+
+/// Reports the health of the components, given the buffers of the sinks.
+pub fn report(
+    buffers: &HashMap<ComponentKey, BufferControl<EventArray>>,
+    policy: &ReadinessPolicy,
+) -> HealthReport {
+    let components = COMPONENTS.read().expect("poisoned lock");
+    let components = components
+        .iter()
+        .map(|(key, state)| {
+            let buffer_usage = buffers.get(key).and_then(BufferControl::usage);
+            let mut health = component_health(state, buffer_usage, policy.max_buffer_usage);
+            health.gating = policy.gates(key);
+            (key.id().to_string(), health)
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    let gating = || components.values().filter(|health| health.gating);
+    HealthReport {
+        live: gating().all(|health| health.live),
+        ready: gating().all(|health| health.ready),
+        components,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sink(running: bool, healthcheck: Option<HealthcheckStatus>) -> ComponentState {
+        ComponentState {
+            running,
+            healthcheck,
+            ..ComponentState::new(ComponentKind::Sink)
+        }
+    }
+
+    #[test]
+    fn sink_readiness() {
+        let health = component_health(&sink(true, Some(HealthcheckStatus::Passed)), None, 0.9);
+        assert!(health.live && health.ready);
+
+        let health = component_health(&sink(true, None), Some(0.5), 0.9);
+        assert!(health.ready);
+
+        let health = component_health(&sink(true, Some(HealthcheckStatus::Pending)), None, 0.9);
+        assert!(health.live && !health.ready);
+        assert_eq!(health.reasons, vec!["healthcheck pending"]);
+
+        let health = component_health(
+            &sink(
+                false,
+                Some(HealthcheckStatus::Failed("refused".to_string())),
+            ),
+            Some(0.95),
+            0.9,
+        );
+        assert!(!health.live && !health.ready);
+        assert_eq!(
+            health.reasons,
+            vec![
+                "not running",
+                "healthcheck failed: refused",
+                "buffer 95% full"
+            ]
+        );
+    }
+
+    #[test]
+    fn gating_components() {
+        let policy = ReadinessPolicy {
+            components: vec![glob::Pattern::new("in_*").unwrap()],
+            max_buffer_usage: 0.9,
+        };
+        assert!(policy.gates(&ComponentKey::from("in_logs")));
+        assert!(!policy.gates(&ComponentKey::from("out_logs")));
+
+        let policy = ReadinessPolicy {
+            components: Vec::new(),
+            max_buffer_usage: 0.9,
+        };
+        assert!(policy.gates(&ComponentKey::from("out_logs")));
+    }
+}
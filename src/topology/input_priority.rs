@@ -0,0 +1,173 @@
+use std::{
+    iter,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt};
+use vector_core::buffers::topology::channel::BufferReceiverStream;
+
+use crate::event::EventArray;
+
+/// An input of a sink read by [`Prioritized`].
+enum Input<S> {
+    /// The buffer of the sink, holding the events of the inputs without a priority.
+    Buffer(S),
+
+    /// The queue of an input with a priority.
+    Queue(BufferReceiverStream<EventArray>),
+}
+
+impl<S> Input<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<EventArray>> {
+        match self {
+            Self::Buffer(buffer) => buffer.poll_next_unpin(cx),
+            Self::Queue(queue) => queue.poll_next_unpin(cx),
+        }
+    }
+}
+
+/// The inputs sharing a priority, read in turn.
+struct Level<S> {
+    priority: u8,
+    inputs: Vec<Option<Input<S>>>,
+    next: usize,
+}
+
+/// The events of the inputs of a sink, read by priority.
+///
+/// The events of the inputs with the highest priority are read first, so that under backpressure,
+/// the events of the inputs with a lower priority wait in their queue, where they are blocked or
+/// dropped when it is full. The inputs sharing a priority are read in turn.
+pub(super) struct Prioritized<S> {
+    levels: Vec<Level<S>>,
+}
+
+/// Reads the events of the buffer of a sink, which has a priority of 0, and of the queues of the
+/// inputs of the sink that have a priority.
+pub(super) fn prioritize<S>(
+    buffer: S,
+    queues: Vec<(u8, BufferReceiverStream<EventArray>)>,
+) -> Prioritized<S> {
+    let inputs = iter::once((0, Input::Buffer(buffer))).chain(
+        queues
+            .into_iter()
+            .map(|(priority, queue)| (priority, Input::Queue(queue))),
+    );
+
+    let mut levels: Vec<Level<S>> = Vec::new();
+    for (priority, input) in inputs {
+        match levels.iter_mut().find(|level| level.priority == priority) {
+            Some(level) => level.inputs.push(Some(input)),
+            None => levels.push(Level {
+                priority,
+                inputs: vec![Some(input)],
+                next: 0,
+            }),
+        }
+    }
+    levels.sort_by(|a, b| b.priority.cmp(&a.priority));
+
+    Prioritized { levels }
+}
+
+impl<S> Stream for Prioritized<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    type Item = EventArray;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut open = false;
+        for level in &mut self.levels {
+            let count = level.inputs.len();
+            for offset in 0..count {
+                let index = (level.next + offset) % count;
+                let Some(input) = &mut level.inputs[index] else {
+                    continue;
+                };
+                match input.poll_next(cx) {
+                    Poll::Ready(Some(events)) => {
+                        level.next = (index + 1) % count;
+                        return Poll::Ready(Some(events));
+                    }
+                    Poll::Ready(None) => level.inputs[index] = None,
+                    Poll::Pending => open = true,
+                }
+            }
+        }
+
+        if open {
+            Poll::Pending
+        } else {
+            Poll::Ready(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use futures::stream;
+    use vector_core::buffers::{topology::builder::TopologyBuilder, WhenFull};
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn events(message: &str) -> EventArray {
+        LogEvent::from(message).into()
+    }
+
+    fn message(events: EventArray) -> String {
+        match events {
+            EventArray::Logs(logs) => logs[0]["message"].to_string_lossy().into_owned(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_inputs_by_priority() {
+        let max_events = NonZeroUsize::new(10).unwrap();
+        let (mut audit_tx, audit_rx) =
+            TopologyBuilder::standalone_memory(max_events, WhenFull::Block).await;
+        let (mut debug_tx, debug_rx) =
+            TopologyBuilder::standalone_memory(max_events, WhenFull::Block).await;
+        debug_tx.send(events("debug 1")).await.unwrap();
+        debug_tx.send(events("debug 2")).await.unwrap();
+        audit_tx.send(events("audit 1")).await.unwrap();
+        audit_tx.send(events("audit 2")).await.unwrap();
+        drop((audit_tx, debug_tx));
+
+        let buffer = stream::iter(vec![events("buffer")]);
+        let prioritized = prioritize(
+            buffer,
+            vec![(1, debug_rx.into_stream()), (10, audit_rx.into_stream())],
+        );
+        let messages = prioritized.map(message).collect::<Vec<_>>().await;
+
+        assert_eq!(
+            messages,
+            vec!["audit 1", "audit 2", "debug 1", "debug 2", "buffer"]
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_inputs_sharing_a_priority_in_turn() {
+        let max_events = NonZeroUsize::new(10).unwrap();
+        let (mut queue_tx, queue_rx) =
+            TopologyBuilder::standalone_memory(max_events, WhenFull::Block).await;
+        queue_tx.send(events("queue 1")).await.unwrap();
+        queue_tx.send(events("queue 2")).await.unwrap();
+        drop(queue_tx);
+
+        let buffer = stream::iter(vec![events("buffer 1"), events("buffer 2")]);
+        let prioritized = prioritize(buffer, vec![(0, queue_rx.into_stream())]);
+        let messages = prioritized.map(message).collect::<Vec<_>>().await;
+
+        assert_eq!(messages, vec!["buffer 1", "queue 1", "buffer 2", "queue 2"]);
+    }
+}
@@ -12,8 +12,13 @@ pub mod schema;
 
 pub mod builder;
 mod controller;
+pub mod drain;
+pub mod health;
+mod input_priority;
 mod ready_arrays;
+mod resources;
 mod running;
+mod source_filter;
 mod task;
 
 #[cfg(test)]
@@ -27,13 +32,15 @@ use std::{
 
 pub use controller::{ReloadOutcome, TopologyController};
 use futures::{Future, FutureExt};
+pub use resources::ACCOUNT_CPU_TIME;
 pub(super) use running::RunningTopology;
 use tokio::sync::{mpsc, watch};
-use vector_buffers::topology::channel::{BufferReceiverStream, BufferSender};
+use vector_buffers::topology::channel::{BufferControl, BufferReceiverStream, BufferSender};
 
 use crate::{
     config::{ComponentKey, Config, ConfigDiff, Inputs, OutputId},
     event::EventArray,
+    sinks::util::service::rate_limit_group::RateLimitGroups,
     topology::{builder::Pieces, task::Task},
 };
 
@@ -68,6 +75,8 @@ pub struct TapResource {
     pub sink_keys: Vec<String>,
     // Components removed on a reload (used to drop TapSinks)
     pub removals: HashSet<ComponentKey>,
+    // Sinks and the controls of their buffers (used to inspect and drain buffers)
+    pub buffers: HashMap<ComponentKey, BufferControl<EventArray>>,
 }
 
 // Watcher types for topology changes.
@@ -127,8 +136,9 @@ pub async fn build_or_log_errors(
     config: &Config,
     diff: &ConfigDiff,
     buffers: HashMap<ComponentKey, BuiltBuffer>,
+    rate_limit_groups: RateLimitGroups,
 ) -> Option<Pieces> {
-    match builder::build_pieces(config, diff, buffers).await {
+    match builder::build_pieces(config, diff, buffers, rate_limit_groups).await {
         Err(errors) => {
             for error in errors {
                 error!(message = "Configuration error.", %error);
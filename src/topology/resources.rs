@@ -0,0 +1,172 @@
+//! Per-component resource accounting and limits.
+//!
+//! When enabled with `--cpu-time-accounting`, the CPU time spent polling each component task is
+//! accounted for in internal metrics. This requires a per-thread CPU clock, so it is only available
+//! on Unix, and adds two system calls to every poll of a component task. Components
+//! can also be given a limit on the memory they allocate: once reached, the component stops
+//! pulling events from its input until its allocations fall back under the limit, so upstream
+//! components are backpressured instead of the whole process running out of memory.
+//!
+//! Work that a component offloads to separate tasks, such as the concurrent execution of
+//! synchronous transforms, isn't included in its CPU time.
+
+use std::{
+    future::Future,
+    num::NonZeroU64,
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use futures::Stream;
+use pin_project::pin_project;
+use tokio::time::{sleep, Sleep};
+use vector_common::internal_event::Registered;
+
+use crate::internal_events::{ComponentCpuTime, ComponentMemoryLimitReached};
+
+/// How often the memory allocated by a paused component is checked again.
+const MEMORY_LIMIT_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Whether the CPU time of component tasks is accounted for.
+///
+/// This is set once on startup, before any component is spawned.
+pub static ACCOUNT_CPU_TIME: AtomicBool = AtomicBool::new(false);
+
+/// Accounts for the CPU time spent polling a component task, if enabled.
+///
+/// This must be created within the span of the component, so the CPU time is attributed to it.
+pub(crate) fn account_cpu_time<F: Future>(inner: F) -> CpuAccounted<F> {
+    let enabled = cfg!(unix) && ACCOUNT_CPU_TIME.load(Ordering::Relaxed);
+    CpuAccounted {
+        inner,
+        cpu_time: enabled.then(|| register!(ComponentCpuTime)),
+    }
+}
+
+#[pin_project]
+pub(crate) struct CpuAccounted<F> {
+    #[pin]
+    inner: F,
+    #[cfg_attr(not(unix), allow(dead_code))]
+    cpu_time: Option<Registered<ComponentCpuTime>>,
+}
+
+impl<F: Future> Future for CpuAccounted<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        #[cfg(unix)]
+        if let Some(cpu_time) = this.cpu_time {
+            let start = thread_cpu_time();
+            let result = this.inner.poll(cx);
+            cpu_time.emit(thread_cpu_time().saturating_sub(start));
+            return result;
+        }
+        this.inner.poll(cx)
+    }
+}
+
+/// Gets the CPU time consumed by the current thread.
+///
+/// A task is polled on a single thread, so the difference between two calls around a poll is the
+/// CPU time spent in that poll.
+#[cfg(unix)]
+fn thread_cpu_time() -> Duration {
+    use nix::time::{clock_gettime, ClockId};
+
+    clock_gettime(ClockId::CLOCK_THREAD_CPUTIME_ID)
+        .map(Duration::from)
+        .unwrap_or_default()
+}
+
+/// Pauses a component input stream while the component is over its memory limit.
+///
+/// The memory allocated by a component is only known when allocation tracing is enabled, otherwise
+/// the input is never paused.
+pub(crate) fn limit_memory<S: Stream>(inner: S, limit: Option<NonZeroU64>) -> MemoryLimited<S> {
+    MemoryLimited {
+        inner,
+        limit: limit.map(NonZeroU64::get),
+        paused: None,
+    }
+}
+
+/// Warns if memory limits are configured, but can't be enforced.
+pub(crate) fn check_memory_limits_enforced<'a>(
+    limited_components: impl IntoIterator<Item = &'a crate::config::ComponentKey>,
+) {
+    let limited_components = limited_components
+        .into_iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    if !limited_components.is_empty() && !allocation_tracing_enabled() {
+        warn!(
+            message = "Memory limits are ignored, as allocation tracing isn't enabled. Run Vector with `--allocation-tracing` to enforce them.",
+            components = ?limited_components,
+        );
+    }
+}
+
+#[pin_project]
+pub(crate) struct MemoryLimited<S> {
+    #[pin]
+    inner: S,
+    limit: Option<u64>,
+    paused: Option<Pin<Box<Sleep>>>,
+}
+
+impl<S: Stream> Stream for MemoryLimited<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if let Some(limit) = *this.limit {
+            loop {
+                if let Some(paused) = this.paused.as_mut() {
+                    ready!(paused.as_mut().poll(cx));
+                }
+
+                match allocated_bytes() {
+                    Some(allocated_bytes) if allocated_bytes > limit => {
+                        if this.paused.is_none() {
+                            emit!(ComponentMemoryLimitReached {
+                                allocated_bytes,
+                                limit,
+                            });
+                        }
+                        *this.paused = Some(Box::pin(sleep(MEMORY_LIMIT_CHECK_INTERVAL)));
+                    }
+                    _ => {
+                        *this.paused = None;
+                        break;
+                    }
+                }
+            }
+        }
+        this.inner.poll_next(cx)
+    }
+}
+
+#[cfg(feature = "allocation-tracing")]
+fn allocated_bytes() -> Option<u64> {
+    crate::internal_telemetry::allocations::current_group_allocated_bytes()
+}
+
+#[cfg(not(feature = "allocation-tracing"))]
+const fn allocated_bytes() -> Option<u64> {
+    None
+}
+
+#[cfg(feature = "allocation-tracing")]
+fn allocation_tracing_enabled() -> bool {
+    crate::internal_telemetry::allocations::TRACK_ALLOCATIONS
+        .load(std::sync::atomic::Ordering::Relaxed)
+}
+
+#[cfg(not(feature = "allocation-tracing"))]
+const fn allocation_tracing_enabled() -> bool {
+    false
+}
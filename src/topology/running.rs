@@ -6,13 +6,16 @@ use std::{
     },
 };
 
-use futures::{future, Future, FutureExt};
+use futures::{
+    future::{self, Shared},
+    Future, FutureExt,
+};
 use tokio::{
     sync::{mpsc, watch},
     time::{interval, sleep_until, Duration, Instant},
 };
-use tracing::Instrument;
-use vector_buffers::topology::channel::BufferSender;
+use tracing::{Instrument, Span};
+use vector_buffers::topology::channel::{BufferControl, BufferSender};
 use vector_common::trigger::DisabledTrigger;
 
 use super::{TapOutput, TapResource};
@@ -23,13 +26,15 @@ use crate::{
     },
     event::EventArray,
     shutdown::SourceShutdownCoordinator,
+    sinks::util::service::rate_limit_group::RateLimitGroups,
     spawn_named,
     topology::{
         build_or_log_errors, builder,
-        builder::Pieces,
+        builder::{DeadLetterSender, Pieces},
+        drain::DrainStatus,
         fanout::{ControlChannel, ControlMessage},
-        handle_errors, retain, take_healthchecks,
-        task::TaskOutput,
+        handle_errors, health, resources, retain, take_healthchecks,
+        task::{Task, TaskOutput, TaskResult},
         BuiltBuffer, TaskHandle, WatchRx, WatchTx,
     },
 };
@@ -44,12 +49,20 @@ pub struct RunningTopology {
     tasks: HashMap<ComponentKey, TaskHandle>,
     shutdown_coordinator: SourceShutdownCoordinator,
     detach_triggers: HashMap<ComponentKey, DisabledTrigger>,
+    dead_letter_senders: HashMap<ComponentKey, Vec<(ComponentKey, DeadLetterSender)>>,
+    buffer_controls: HashMap<ComponentKey, BufferControl<EventArray>>,
+    input_senders: HashMap<ComponentKey, HashMap<OutputId, BufferSender<EventArray>>>,
     pub(crate) config: Config,
     abort_tx: mpsc::UnboundedSender<()>,
     watch: (WatchTx, WatchRx),
     pub(crate) running: Arc<AtomicBool>,
+    drain_status: DrainStatus,
+    rate_limit_groups: RateLimitGroups,
 }
 
+/// The default amount of time given to components to shut down gracefully.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60);
+
 impl RunningTopology {
     pub fn new(config: Config, abort_tx: mpsc::UnboundedSender<()>) -> Self {
         Self {
@@ -60,11 +73,16 @@ impl RunningTopology {
             config,
             shutdown_coordinator: SourceShutdownCoordinator::default(),
             detach_triggers: HashMap::new(),
+            dead_letter_senders: HashMap::new(),
+            buffer_controls: HashMap::new(),
+            input_senders: HashMap::new(),
             source_tasks: HashMap::new(),
             tasks: HashMap::new(),
             abort_tx,
             watch: watch::channel(TapResource::default()),
             running: Arc::new(AtomicBool::new(true)),
+            drain_status: DrainStatus::default(),
+            rate_limit_groups: RateLimitGroups::default(),
         }
     }
 
@@ -80,6 +98,11 @@ impl RunningTopology {
         self.watch.1.clone()
     }
 
+    /// Gets a handle to the drain progress of this topology, updated while it is stopped.
+    pub fn drain_status(&self) -> DrainStatus {
+        self.drain_status.clone()
+    }
+
     /// Signal that all sources in this topology are ended.
     ///
     /// The future returned by this function will finish once all the sources in
@@ -105,6 +128,15 @@ impl RunningTopology {
     /// dropped then everything from this RunningTopology instance is fully
     /// dropped.
     pub fn stop(self) -> impl Future<Output = ()> {
+        self.stop_within(DEFAULT_SHUTDOWN_TIMEOUT)
+    }
+
+    /// Shut down all topology components, forcefully stopping any component that hasn't
+    /// finished within the given timeout.
+    ///
+    /// See [`RunningTopology::stop`] for details. The progress of the shutdown is reported
+    /// through the topology's [`DrainStatus`].
+    pub fn stop_within(self, timeout: Duration) -> impl Future<Output = ()> {
         // Update the API's health endpoint to signal shutdown
         self.running.store(false, Ordering::Relaxed);
         // Create handy handles collections of all tasks for the subsequent
@@ -124,7 +156,10 @@ impl RunningTopology {
         }
 
         // If we reach this, we will forcefully shutdown the sources.
-        let deadline = Instant::now() + Duration::from_secs(60);
+        let deadline = Instant::now() + timeout;
+
+        let drain_status = self.drain_status;
+        drain_status.start(deadline, remaining_components(&mut check_handles));
 
         // If we reach the deadline, this future will print out which components
         // won't gracefully shutdown since we will start to forcefully shutdown
@@ -132,16 +167,7 @@ impl RunningTopology {
         let mut check_handles2 = check_handles.clone();
         let timeout = async move {
             sleep_until(deadline).await;
-            // Remove all tasks that have shutdown.
-            check_handles2.retain(|_key, handles| {
-                retain(handles, |handle| handle.peek().is_none());
-                !handles.is_empty()
-            });
-            let remaining_components = check_handles2
-                .keys()
-                .map(|item| item.to_string())
-                .collect::<Vec<_>>()
-                .join(", ");
+            let remaining_components = remaining_components(&mut check_handles2).join(", ");
 
             error!(
                 components = ?remaining_components,
@@ -150,21 +176,17 @@ impl RunningTopology {
         };
 
         // Reports in intervals which components are still running.
-        let mut interval = interval(Duration::from_secs(5));
+        let mut interval = interval(Duration::from_secs(1));
+        let reporter_drain_status = drain_status.clone();
         let reporter = async move {
-            loop {
+            for tick in 0u64.. {
                 interval.tick().await;
 
-                // Remove all tasks that have shutdown.
-                check_handles.retain(|_key, handles| {
-                    retain(handles, |handle| handle.peek().is_none());
-                    !handles.is_empty()
-                });
-                let remaining_components = check_handles
-                    .keys()
-                    .map(|item| item.to_string())
-                    .collect::<Vec<_>>()
-                    .join(", ");
+                let remaining_components = remaining_components(&mut check_handles);
+                reporter_drain_status.update(remaining_components.clone());
+                if tick % 5 != 0 {
+                    continue;
+                }
 
                 let time_remaining = match deadline.checked_duration_since(Instant::now()) {
                     Some(remaining) => format!("{} seconds left", remaining.as_secs()),
@@ -172,7 +194,7 @@ impl RunningTopology {
                 };
 
                 info!(
-                    remaining_components = ?remaining_components,
+                    remaining_components = ?remaining_components.join(", "),
                     time_remaining = ?time_remaining,
                     "Shutting down... Waiting on running components."
                 );
@@ -192,7 +214,8 @@ impl RunningTopology {
         // Now kick off the shutdown process by shutting down the sources.
         let source_shutdown_complete = self.shutdown_coordinator.shutdown_all(deadline);
 
-        futures::future::join(source_shutdown_complete, shutdown_complete_future).map(|_| ())
+        futures::future::join(source_shutdown_complete, shutdown_complete_future)
+            .map(move |_| drain_status.finish())
     }
 
     /// Attempts to load a new configuration and update this running topology.
@@ -241,7 +264,9 @@ impl RunningTopology {
         // successfully build them, we'll attempt to connect them up to the topology and spawn their
         // respective component tasks.
         let new_bufs = buffers.clone();
-        if let Some(mut new_pieces) = build_or_log_errors(&new_config, &diff, new_bufs).await {
+        if let Some(mut new_pieces) =
+            build_or_log_errors(&new_config, &diff, new_bufs, self.rate_limit_groups.clone()).await
+        {
             // If healthchecks are configured for any of the changing/new components, try running
             // them before moving forward with connecting and spawning.  In some cases, healthchecks
             // failing may be configured as a non-blocking issue and so we'll still continue on.
@@ -265,7 +290,9 @@ impl RunningTopology {
         warn!("Failed to completely load new configuration. Restoring old configuration.");
 
         let diff = diff.flip();
-        if let Some(mut new_pieces) = build_or_log_errors(&self.config, &diff, buffers).await {
+        if let Some(mut new_pieces) =
+            build_or_log_errors(&self.config, &diff, buffers, self.rate_limit_groups.clone()).await
+        {
             if self
                 .run_healthchecks(&diff, &mut new_pieces, self.config.healthchecks)
                 .await
@@ -333,6 +360,7 @@ impl RunningTopology {
             let deadline = Instant::now() + timeout;
             for key in &diff.sources.to_remove {
                 debug!(component = %key, "Removing source.");
+                health::removed(key);
 
                 let previous = self.tasks.remove(key).unwrap();
                 drop(previous); // detach and forget
@@ -373,6 +401,7 @@ impl RunningTopology {
         // which is why we don't do any manual triggering of shutdown here.
         for key in &diff.transforms.to_remove {
             debug!(component = %key, "Removing transform.");
+            health::removed(key);
 
             let previous = self.tasks.remove(key).unwrap();
             drop(previous); // detach and forget
@@ -442,6 +471,7 @@ impl RunningTopology {
         // First, we remove any inputs to removed sinks so they can naturally shut down.
         for key in &diff.sinks.to_remove {
             debug!(component = %key, "Removing sink.");
+            health::removed(key);
             self.remove_inputs(key, diff, new_config).await;
         }
 
@@ -599,6 +629,23 @@ impl RunningTopology {
         // sources/transforms, to ensure we're connecting components in order.
         self.reattach_severed_inputs(diff);
 
+        // Connect the circuit breakers and stale events of sinks to their dead letter queue sink,
+        // whose input may have been replaced.
+        for key in diff.sinks.removed_and_changed() {
+            self.dead_letter_senders.remove(key);
+        }
+        self.dead_letter_senders
+            .extend(new_pieces.dead_letter_senders.drain());
+        for (queue, sender) in self.dead_letter_senders.values().flatten() {
+            *sender.lock().await = self.inputs.get(queue).cloned();
+        }
+
+        for key in &diff.sinks.to_remove {
+            self.buffer_controls.remove(key);
+        }
+        self.buffer_controls
+            .extend(new_pieces.buffer_controls.drain());
+
         // Broadcast any topology changes to subscribers.
         if !self.watch.0.is_closed() {
             let outputs = self
@@ -641,6 +688,7 @@ impl RunningTopology {
                     // Note, only sources and transforms are relevant. Sinks do
                     // not have outputs to tap.
                     removals,
+                    buffers: self.buffer_controls.clone(),
                 })
                 .expect("Couldn't broadcast config changes.");
         }
@@ -667,6 +715,8 @@ impl RunningTopology {
         new_pieces: &mut builder::Pieces,
     ) {
         let (tx, inputs) = new_pieces.inputs.remove(key).unwrap();
+        // Inputs with a priority send their events to their own queue instead of the buffer.
+        let input_senders = new_pieces.input_senders.remove(key).unwrap_or_default();
 
         let old_inputs = self
             .config
@@ -681,6 +731,7 @@ impl RunningTopology {
 
         for input in inputs {
             let output = self.outputs.get_mut(&input).expect("unknown output");
+            let tx = input_senders.get(&input).unwrap_or(&tx);
 
             if diff.contains(&input.component) || inputs_to_add.contains(&input) {
                 // If the input we're connecting to is changing, that means its outputs will have been
@@ -701,6 +752,9 @@ impl RunningTopology {
         }
 
         self.inputs.insert(key.clone(), tx);
+        if !input_senders.is_empty() {
+            self.input_senders.insert(key.clone(), input_senders);
+        }
         new_pieces
             .detach_triggers
             .remove(key)
@@ -713,8 +767,17 @@ impl RunningTopology {
 
     async fn remove_inputs(&mut self, key: &ComponentKey, diff: &ConfigDiff, new_config: &Config) {
         self.inputs.remove(key);
+        self.input_senders.remove(key);
         self.detach_triggers.remove(key);
 
+        // Sinks falling back to this sink as their dead letter queue must let go of its input, so
+        // that it can shut down. They are connected again once the new topology is in place.
+        for (queue, sender) in self.dead_letter_senders.values().flatten() {
+            if queue == key {
+                *sender.lock().await = None;
+            }
+        }
+
         let old_inputs = self.config.inputs_for_node(key).expect("node exists");
         let new_inputs = new_config
             .inputs_for_node(key)
@@ -778,7 +841,13 @@ impl RunningTopology {
             for output_id in changed_outputs {
                 debug!(component = %sink_key, fanout_id = %output_id.component, "Reattaching component input to fanout.");
 
-                let input = self.inputs.get(sink_key).cloned().unwrap();
+                let input = self
+                    .input_senders
+                    .get(sink_key)
+                    .and_then(|senders| senders.get(&output_id))
+                    .or_else(|| self.inputs.get(sink_key))
+                    .cloned()
+                    .unwrap();
                 let output = self.outputs.get_mut(&output_id).unwrap();
                 let _ = output.send(ControlMessage::Add(sink_key.clone(), input));
             }
@@ -816,6 +885,15 @@ impl RunningTopology {
             trace!(message = "Spawning new sink.", key = %key);
             self.spawn_sink(key, &mut new_pieces);
         }
+
+        // Keep the limits of the rate limit groups for the sinks built on later reloads.
+        self.rate_limit_groups = new_pieces.rate_limit_groups;
+    }
+
+    /// Wraps a component task to handle its errors, and account for its CPU time if enabled.
+    fn wrap_task(&self, task: Task, span: &Span) -> impl Future<Output = TaskResult> {
+        let _entered = span.enter();
+        resources::account_cpu_time(handle_errors(task, self.abort_tx.clone()))
     }
 
     fn spawn_sink(&mut self, key: &ComponentKey, new_pieces: &mut builder::Pieces) {
@@ -848,7 +926,8 @@ impl RunningTopology {
         }
 
         let task_name = format!(">> {} ({})", task.typetag(), task.id());
-        let task = handle_errors(task, self.abort_tx.clone()).instrument(task_span);
+        let task = self.wrap_task(task, &task_span).instrument(task_span);
+        let task = track_health(key, health::ComponentKind::Sink, task);
         let spawned = spawn_named(task, task_name.as_ref());
         if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
             drop(previous); // detach and forget
@@ -885,7 +964,8 @@ impl RunningTopology {
         }
 
         let task_name = format!(">> {} ({}) >>", task.typetag(), task.id());
-        let task = handle_errors(task, self.abort_tx.clone()).instrument(task_span);
+        let task = self.wrap_task(task, &task_span).instrument(task_span);
+        let task = track_health(key, health::ComponentKind::Transform, task);
         let spawned = spawn_named(task, task_name.as_ref());
         if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
             drop(previous); // detach and forget
@@ -923,7 +1003,9 @@ impl RunningTopology {
         }
 
         let task_name = format!("{} ({}) >>", task.typetag(), task.id());
-        let task = handle_errors(task, self.abort_tx.clone()).instrument(task_span.clone());
+        let task = self
+            .wrap_task(task, &task_span)
+            .instrument(task_span.clone());
         let spawned = spawn_named(task, task_name.as_ref());
         if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
             drop(previous); // detach and forget
@@ -934,12 +1016,44 @@ impl RunningTopology {
 
         // Now spawn the actual source task.
         let source_task = new_pieces.source_tasks.remove(key).unwrap();
-        let source_task = handle_errors(source_task, self.abort_tx.clone()).instrument(task_span);
+        let source_task = self
+            .wrap_task(source_task, &task_span)
+            .instrument(task_span);
+        let source_task = track_health(key, health::ComponentKind::Source, source_task);
         self.source_tasks
             .insert(key.clone(), spawn_named(source_task, task_name.as_ref()));
     }
 }
 
+/// Records whether the task of a component is running, for the health of the component.
+fn track_health(
+    key: &ComponentKey,
+    kind: health::ComponentKind,
+    task: impl Future<Output = TaskResult>,
+) -> impl Future<Output = TaskResult> {
+    let key = key.clone();
+    let generation = health::started(&key, kind);
+    task.inspect(move |_| health::stopped(&key, generation))
+}
+
+/// Removes the tasks that have finished, returning the components that are still running.
+fn remaining_components<F>(check_handles: &mut HashMap<ComponentKey, Vec<Shared<F>>>) -> Vec<String>
+where
+    F: Future,
+    F::Output: Clone,
+{
+    check_handles.retain(|_key, handles| {
+        retain(handles, |handle| handle.peek().is_none());
+        !handles.is_empty()
+    });
+    let mut components = check_handles
+        .keys()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    components.sort();
+    components
+}
+
 fn get_changed_outputs(diff: &ConfigDiff, output_ids: Inputs<OutputId>) -> Vec<OutputId> {
     let mut changed_outputs = Vec::new();
 
@@ -107,6 +107,18 @@ pub fn merged_definition(
     definition
 }
 
+/// Create a new [`Definition`] of the events received by a sink, by merging all of its inputs.
+///
+/// Unlike [`merged_definition`], the schema definitions of the components are used whether or not
+/// schemas are enabled, so that the fields of the events can be inferred.
+pub fn sink_input_definition(
+    inputs: &[OutputId],
+    config: &dyn ComponentContainer,
+    cache: &mut HashMap<(bool, Vec<OutputId>), Definition>,
+) -> Definition {
+    merged_definition(inputs, &SchemaEnabled(config), cache)
+}
+
 /// Get a list of definitions from individual pipelines feeding into a component.
 ///
 /// For example, given the following topology:
@@ -303,6 +315,31 @@ fn get_output_for_port(outputs: Vec<Output>, port: &Option<String>) -> Option<Ou
     outputs.into_iter().find(|output| &output.port == port)
 }
 
+/// A [`ComponentContainer`] with schemas enabled, whatever the config says.
+struct SchemaEnabled<'a>(&'a dyn ComponentContainer);
+
+impl<'a> ComponentContainer for SchemaEnabled<'a> {
+    fn schema_enabled(&self) -> bool {
+        true
+    }
+
+    fn source_outputs(&self, key: &ComponentKey) -> Option<Vec<Output>> {
+        self.0.source_outputs(key)
+    }
+
+    fn transform_inputs(&self, key: &ComponentKey) -> Option<&[OutputId]> {
+        self.0.transform_inputs(key)
+    }
+
+    fn transform_outputs(
+        &self,
+        key: &ComponentKey,
+        merged_definition: &Definition,
+    ) -> Option<Vec<Output>> {
+        self.0.transform_outputs(key, merged_definition)
+    }
+}
+
 impl ComponentContainer for Config {
     fn schema_enabled(&self) -> bool {
         self.schema.enabled
@@ -0,0 +1,105 @@
+use vector_common::internal_event::{Count, InternalEventHandle as _, Registered};
+
+use crate::{
+    conditions::Condition,
+    event::{Event, EventArray, EventContainer},
+    internal_events::SourceFilterEventsDropped,
+    register,
+};
+
+/// The filter of the events of a source.
+///
+/// The filter is applied to the events as the source sends them, before they are sent to the
+/// components consuming the outputs of the source, so that unwanted events are dropped as early as
+/// possible.
+pub(super) struct SourceFilter {
+    condition: Condition,
+    events_dropped: Registered<SourceFilterEventsDropped>,
+}
+
+impl SourceFilter {
+    /// Creates a filter forwarding the events matched by the condition.
+    ///
+    /// The dropped events are counted for the component of the current span.
+    pub(super) fn new(condition: Condition) -> Self {
+        Self {
+            condition,
+            events_dropped: register!(SourceFilterEventsDropped),
+        }
+    }
+
+    /// Drops the events that aren't matched by the condition.
+    pub(super) fn apply(&self, array: EventArray) -> EventArray {
+        let count = array.len();
+        let array = match array {
+            EventArray::Logs(logs) => EventArray::Logs(self.retain(logs, Event::into_log)),
+            EventArray::Metrics(metrics) => {
+                EventArray::Metrics(self.retain(metrics, Event::into_metric))
+            }
+            EventArray::Traces(traces) => {
+                EventArray::Traces(self.retain(traces, Event::into_trace))
+            }
+        };
+
+        let dropped = count - array.len();
+        if dropped > 0 {
+            self.events_dropped.emit(Count(dropped));
+        }
+        array
+    }
+
+    fn retain<T: Into<Event>>(&self, events: Vec<T>, from_event: fn(Event) -> T) -> Vec<T> {
+        events
+            .into_iter()
+            .filter_map(|event| {
+                let (matched, event) = self.condition.check(event.into());
+                matched.then(|| from_event(event))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        conditions::{AnyCondition, ConditionConfig},
+        event::{LogEvent, Metric, MetricKind, MetricValue},
+    };
+
+    fn vrl_filter(source: &str) -> SourceFilter {
+        let condition = AnyCondition::String(source.to_owned());
+        SourceFilter::new(condition.build(&Default::default()).unwrap())
+    }
+
+    #[test]
+    fn drops_unmatched_events() {
+        let filter = vrl_filter(r#".path != "/health""#);
+        let mut health_check = LogEvent::from("health check");
+        health_check.insert("path", "/health");
+        let array = EventArray::Logs(vec![LogEvent::from("hello"), health_check]);
+
+        let array = filter.apply(array);
+
+        assert_eq!(array.len(), 1);
+        assert_eq!(
+            array.into_events().next().unwrap().as_log()["message"],
+            "hello".into()
+        );
+    }
+
+    #[test]
+    fn filters_metrics() {
+        let condition = AnyCondition::Map(ConditionConfig::IsLog);
+        let filter = SourceFilter::new(condition.build(&Default::default()).unwrap());
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        );
+
+        let array = filter.apply(EventArray::Metrics(vec![metric]));
+
+        assert!(array.is_empty());
+    }
+}
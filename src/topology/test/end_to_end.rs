@@ -101,7 +101,7 @@ uri = "http://{address2}/"
     )
     .unwrap();
     let diff = ConfigDiff::initial(&config);
-    let pieces = topology::build_or_log_errors(&config, &diff, HashMap::new())
+    let pieces = topology::build_or_log_errors(&config, &diff, HashMap::new(), Default::default())
         .await
         .unwrap();
     let (_topology, _) = topology::start_validated(config, diff, pieces)
@@ -17,7 +17,7 @@ use crate::{
         },
         start_topology, trace_init,
     },
-    topology,
+    topology::{self, drain::DrainState},
 };
 use futures::{future, stream, StreamExt};
 use tokio::{
@@ -149,6 +149,42 @@ async fn topology_source_and_sink() {
     assert_eq!(vec![event], res);
 }
 
+#[tokio::test]
+async fn topology_reports_drain_progress() {
+    trace_init();
+
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_sink("out1", &["in1"], sink1);
+
+    let (topology, _) = start_topology(config.build().unwrap(), false).await;
+    let drain_status = topology.drain_status();
+    assert_eq!(drain_status.progress().state, DrainState::Running);
+    assert!(drain_status.request());
+    assert!(!drain_status.request());
+
+    let event = Event::Log(LogEvent::from("this"));
+    in1.send_event(event.clone()).await.unwrap();
+
+    let stop = topology.stop_within(Duration::from_secs(30));
+    let progress = drain_status.progress();
+    assert_eq!(progress.state, DrainState::Draining);
+    assert_eq!(progress.remaining_components, vec!["in1", "out1"]);
+    assert!(progress.seconds_remaining.unwrap() <= 30);
+
+    stop.await;
+
+    let progress = drain_status.progress();
+    assert_eq!(progress.state, DrainState::Drained);
+    assert!(progress.remaining_components.is_empty());
+
+    let res = out1.flat_map(into_event_stream).collect::<Vec<_>>().await;
+    assert_eq!(vec![event], res);
+}
+
 #[tokio::test]
 async fn topology_multiple_sources() {
     trace_init();
@@ -701,7 +737,7 @@ async fn topology_required_healthcheck_fails_start() {
     let mut config = basic_config_with_sink_failing_healthcheck();
     config.healthchecks.require_healthy = true;
     let diff = ConfigDiff::initial(&config);
-    let pieces = topology::build_or_log_errors(&config, &diff, HashMap::new())
+    let pieces = topology::build_or_log_errors(&config, &diff, HashMap::new(), Default::default())
         .await
         .unwrap();
 
@@ -714,7 +750,7 @@ async fn topology_required_healthcheck_fails_start() {
 async fn topology_optional_healthcheck_does_not_fail_start() {
     let config = basic_config_with_sink_failing_healthcheck();
     let diff = ConfigDiff::initial(&config);
-    let pieces = topology::build_or_log_errors(&config, &diff, HashMap::new())
+    let pieces = topology::build_or_log_errors(&config, &diff, HashMap::new(), Default::default())
         .await
         .unwrap();
     assert!(topology::start_validated(config, diff, pieces)
@@ -786,6 +822,7 @@ async fn topology_disk_buffer_flushes_on_idle() {
     sink1_outer.buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: std::num::NonZeroU64::new(268435488).unwrap(),
         when_full: WhenFull::DropNewest,
+        encryption: None,
     });
     config.add_sink_outer("out1", sink1_outer);
 
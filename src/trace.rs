@@ -53,6 +53,11 @@ static SUBSCRIBERS: Mutex<Option<Vec<oneshot::Sender<Vec<LogEvent>>>>> =
 /// has been initialized.
 static SENDER: OnceCell<Sender<LogEvent>> = OnceCell::new();
 
+/// DIAGNOSTICS_SENDER receives the component errors and dropped events reported via `tracing`, regardless of the log
+/// level and the internal log rate limit.
+#[cfg(feature = "api")]
+static DIAGNOSTICS_SENDER: OnceCell<Sender<LogEvent>> = OnceCell::new();
+
 fn metrics_layer_enabled() -> bool {
     !matches!(std::env::var("DISABLE_INTERNAL_METRICS_TRACING_INTEGRATION"), Ok(x) if x == "true")
 }
@@ -71,7 +76,13 @@ pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64)
 
     let subscriber = tracing_subscriber::registry()
         .with(metrics_layer)
-        .with(broadcast_layer);
+        .with(broadcast_layer)
+        .with(crate::internal_telemetry::otlp::SpanLayer.with_filter(LevelFilter::INFO));
+
+    #[cfg(feature = "api")]
+    let subscriber = subscriber.with(
+        DiagnosticsLayer::new().with_filter(tracing_subscriber::filter::filter_fn(is_diagnostic)),
+    );
 
     #[cfg(feature = "tokio-console")]
     let subscriber = {
@@ -286,6 +297,121 @@ impl TraceSubscription {
     }
 }
 
+/// Subscribes to the component errors and dropped events reported via `tracing`.
+///
+/// Errors are the events at the `error` level following the component error specification, with an `error_type` field,
+/// and dropped events those emitted by `ComponentEventsDropped`, with an `intentional` field. The events are converted
+/// to log events in the same way as for [`TraceSubscription`], including the `component_*` fields of their spans.
+#[cfg(feature = "api")]
+pub fn subscribe_diagnostics() -> impl Stream<Item = LogEvent> + Unpin {
+    let rx = DIAGNOSTICS_SENDER
+        .get_or_init(|| broadcast::channel(99).0)
+        .subscribe();
+    BroadcastStream::new(rx).filter_map(|event| ready(event.ok()))
+}
+
+/// Whether the callsite reports a component error or dropped events, or is the span of a component, whose fields
+/// are added to them.
+#[cfg(feature = "api")]
+fn is_diagnostic(metadata: &tracing::Metadata<'_>) -> bool {
+    let fields = metadata.fields();
+    if metadata.is_span() {
+        fields.field("component_id").is_some()
+    } else {
+        fields.field("intentional").is_some()
+            || (*metadata.level() == tracing::Level::ERROR && fields.field("error_type").is_some())
+    }
+}
+
+/// Converts a tracing event to a log event, with the `component_*` fields of its spans and the
+/// rate limiting it reports, if any.
+fn to_log_event<S>(event: &Event<'_>, ctx: &Context<'_, S>) -> LogEvent
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    let mut log = LogEvent::from(event);
+    if let Some(rate_limit) = tracing_limit::current_rate_limit() {
+        log.insert(
+            event_path!("metadata", "rate_limit", "active"),
+            rate_limit.active,
+        );
+        log.insert(
+            event_path!("metadata", "rate_limit", "suppressed_count"),
+            rate_limit.suppressed_count,
+        );
+        log.insert(
+            event_path!("metadata", "rate_limit", "period_secs"),
+            rate_limit.period_secs,
+        );
+    }
+    // Add span fields if available
+    if let Some(parent_span) = ctx.event_span(event) {
+        for span in parent_span.scope().from_root() {
+            if let Some(fields) = span.extensions().get::<SpanFields>() {
+                for (k, v) in &fields.0 {
+                    log.insert(event_path!("vector", *k), v.clone());
+                }
+            }
+        }
+    }
+    log
+}
+
+/// Records the `component_*` fields of a new span, unless another layer already did.
+fn record_span_fields<S>(
+    attrs: &tracing_core::span::Attributes<'_>,
+    id: &tracing_core::span::Id,
+    ctx: &Context<'_, S>,
+) where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    let span = ctx.span(id).expect("span must already exist!");
+    let mut extensions = span.extensions_mut();
+    if extensions.get_mut::<SpanFields>().is_none() {
+        let mut fields = SpanFields::default();
+        attrs.values().record(&mut fields);
+        extensions.insert(fields);
+    }
+}
+
+#[cfg(feature = "api")]
+struct DiagnosticsLayer<S> {
+    _subscriber: PhantomData<S>,
+}
+
+#[cfg(feature = "api")]
+impl<S> DiagnosticsLayer<S> {
+    const fn new() -> Self {
+        DiagnosticsLayer {
+            _subscriber: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "api")]
+impl<S> Layer<S> for DiagnosticsLayer<S>
+where
+    S: Subscriber + 'static + for<'lookup> LookupSpan<'lookup>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        // Events are only converted while the API has subscribers.
+        if let Some(sender) = DIAGNOSTICS_SENDER.get() {
+            if sender.receiver_count() > 0 {
+                let _ = sender.send(to_log_event(event, &ctx));
+            }
+        }
+    }
+
+    fn on_new_span(
+        &self,
+        attrs: &tracing_core::span::Attributes<'_>,
+        id: &tracing_core::span::Id,
+        ctx: Context<'_, S>,
+    ) {
+        record_span_fields(attrs, id, &ctx);
+    }
+}
+
 struct BroadcastLayer<S> {
     _subscriber: PhantomData<S>,
 }
@@ -304,17 +430,7 @@ where
 {
     fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
         if should_process_tracing_event() {
-            let mut log = LogEvent::from(event);
-            // Add span fields if available
-            if let Some(parent_span) = ctx.event_span(event) {
-                for span in parent_span.scope().from_root() {
-                    if let Some(fields) = span.extensions().get::<SpanFields>() {
-                        for (k, v) in &fields.0 {
-                            log.insert(event_path!("vector", *k), v.clone());
-                        }
-                    }
-                }
-            }
+            let log = to_log_event(event, &ctx);
             // Try buffering the event, and if we're not buffering anymore, try to
             // send it along via the trace sender if it's been established.
             if !try_buffer_event(&log) {
@@ -329,10 +445,7 @@ where
         id: &tracing_core::span::Id,
         ctx: Context<'_, S>,
     ) {
-        let span = ctx.span(id).expect("span must already exist!");
-        let mut fields = SpanFields::default();
-        attrs.values().record(&mut fields);
-        span.extensions_mut().insert(fields);
+        record_span_fields(attrs, id, &ctx);
     }
 }
 
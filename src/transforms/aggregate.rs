@@ -68,27 +68,30 @@ impl Aggregate {
     }
 
     fn record(&mut self, event: Event) {
-        let (series, data, metadata) = event.into_metric().into_parts();
-
-        match data.kind {
-            metric::MetricKind::Incremental => match self.map.entry(series) {
-                Entry::Occupied(mut entry) => {
-                    let existing = entry.get_mut();
-                    // In order to update (add) the new and old kind's must match
-                    if existing.0.kind == data.kind && existing.0.update(&data) {
-                        existing.1.merge(metadata);
-                    } else {
+        let (series, data, mut metadata) = event.into_metric().into_parts();
+
+        match self.map.entry(series) {
+            Entry::Occupied(mut entry) => {
+                let existing = entry.get_mut();
+                // In order to update (add) the new and old kind's must match
+                if data.kind == metric::MetricKind::Incremental
+                    && existing.0.kind == data.kind
+                    && existing.0.update(&data)
+                {
+                    existing.1.merge(metadata);
+                } else {
+                    if data.kind == metric::MetricKind::Incremental {
                         emit!(AggregateUpdateFailed);
-                        *existing = (data, metadata);
                     }
+                    // The replaced metric is never sent downstream, so its finalizers are carried
+                    // over to the metric replacing it. Otherwise, its source would be acknowledged
+                    // before the aggregated metric is delivered.
+                    metadata.merge_finalizers(existing.1.take_finalizers());
+                    *existing = (data, metadata);
                 }
-                Entry::Vacant(entry) => {
-                    entry.insert((data, metadata));
-                }
-            },
-            metric::MetricKind::Absolute => {
-                // Always replace/store
-                self.map.insert(series, (data, metadata));
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((data, metadata));
             }
         };
 
@@ -152,7 +155,7 @@ mod tests {
 
     use super::*;
     use crate::{
-        event::{metric, Event, Metric},
+        event::{metric, BatchNotifier, BatchStatus, Event, EventStatus, Metric},
         test_util::components::assert_transform_compliance,
         transforms::test::create_topology,
     };
@@ -305,6 +308,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn holds_acknowledgements_of_replaced_metrics() {
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 1000_u64,
+        })
+        .unwrap();
+
+        let (batch_1, mut receiver_1) = BatchNotifier::new_with_receiver();
+        let (batch_2, mut receiver_2) = BatchNotifier::new_with_receiver();
+        let gauge_a_1 = make_metric(
+            "gauge_a",
+            metric::MetricKind::Absolute,
+            metric::MetricValue::Gauge { value: 42.0 },
+        )
+        .with_batch_notifier(&batch_1);
+        let gauge_a_2 = make_metric(
+            "gauge_a",
+            metric::MetricKind::Absolute,
+            metric::MetricValue::Gauge { value: 43.0 },
+        )
+        .with_batch_notifier(&batch_2);
+        drop(batch_1);
+        drop(batch_2);
+
+        // The first gauge is superseded, but must not be acknowledged until the second one is.
+        agg.record(gauge_a_1);
+        agg.record(gauge_a_2);
+        assert!(receiver_1.try_recv().is_err());
+
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert_eq!(1, out.len());
+        assert!(receiver_1.try_recv().is_err());
+        assert!(receiver_2.try_recv().is_err());
+
+        out[0].metadata().update_status(EventStatus::Rejected);
+        drop(out);
+        assert_eq!(receiver_1.try_recv(), Ok(BatchStatus::Rejected));
+        assert_eq!(receiver_2.try_recv(), Ok(BatchStatus::Rejected));
+    }
+
     #[test]
     fn conflicting_value_type() {
         let mut agg = Aggregate::new(&AggregateConfig {
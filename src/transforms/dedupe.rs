@@ -1,8 +1,10 @@
 use std::{future::ready, num::NonZeroUsize, pin::Pin};
 
+use async_stream::stream;
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
 use lru::LruCache;
+use serde::{Deserialize, Serialize};
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -13,6 +15,7 @@ use crate::{
     event::{Event, Value},
     internal_events::DedupeEventsDropped,
     schema,
+    state_store::{StateStore, StateStoreConfig},
     transforms::{TaskTransform, Transform},
 };
 
@@ -79,6 +82,12 @@ pub struct DedupeConfig {
     #[configurable(derived)]
     #[serde(default = "default_cache_config")]
     pub cache: CacheConfig,
+
+    /// Persists the cache of previously seen events, so that duplicates are still detected after
+    /// Vector restarts.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub state: StateStoreConfig,
 }
 
 fn default_cache_config() -> CacheConfig {
@@ -126,6 +135,7 @@ impl DedupeConfig {
 pub struct Dedupe {
     fields: FieldMatchConfig,
     cache: LruCache<CacheEntry, bool>,
+    state: Option<StateStore>,
 }
 
 impl GenerateConfig for DedupeConfig {
@@ -133,6 +143,7 @@ impl GenerateConfig for DedupeConfig {
         toml::Value::try_from(Self {
             fields: None,
             cache: default_cache_config(),
+            state: StateStoreConfig::default(),
         })
         .unwrap()
     }
@@ -141,8 +152,12 @@ impl GenerateConfig for DedupeConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "dedupe")]
 impl TransformConfig for DedupeConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::event_task(Dedupe::new(self.clone())))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let mut dedupe = Dedupe::new(self.clone());
+        if let Some(state) = self.state.build(context)? {
+            dedupe = dedupe.with_state(state).await;
+        }
+        Ok(Transform::event_task(dedupe))
     }
 
     fn input(&self) -> Input {
@@ -179,7 +194,7 @@ type TypeId = u8;
 /// iterating over the fields of the incoming Events, we know that the
 /// CacheEntries for 2 equivalent events will always contain the fields in the
 /// same order.
-#[derive(PartialEq, Eq, Hash)]
+#[derive(PartialEq, Eq, Hash, Deserialize, Serialize)]
 enum CacheEntry {
     Match(Vec<Option<(TypeId, Bytes)>>),
     Ignore(Vec<(String, TypeId, Bytes)>),
@@ -207,11 +222,28 @@ impl Dedupe {
         Self {
             fields,
             cache: LruCache::new(num_entries),
+            state: None,
         }
     }
 
+    /// Restores the cache from the given state store, and checkpoints the cache to it from then
+    /// on.
+    pub async fn with_state(mut self, state: StateStore) -> Self {
+        // Entries come out of the store from most to least recently seen, so they're put back into
+        // the cache in reverse.
+        for entry in state.restore::<CacheEntry>().await.into_iter().rev() {
+            self.cache.put(entry, true);
+        }
+        self.state = Some(state);
+        self
+    }
+
     fn transform_one(&mut self, event: Event) -> Option<Event> {
         let cache_entry = build_cache_entry(&event, &self.fields);
+        if let Some(state) = &mut self.state {
+            // Even a duplicate moves its entry to the front of the cache.
+            state.mark_changed();
+        }
         if self.cache.put(cache_entry, true).is_some() {
             emit!(DedupeEventsDropped { count: 1 });
             None
@@ -219,6 +251,20 @@ impl Dedupe {
             Some(event)
         }
     }
+
+    fn checkpoint(&mut self) {
+        if let Some(state) = &mut self.state {
+            // The cache iterates from most to least recently seen, which is the order the store
+            // expects.
+            state.checkpoint(self.cache.iter().map(|(entry, _)| entry));
+        }
+    }
+
+    async fn flush_state(&mut self) {
+        if let Some(state) = &mut self.state {
+            state.flush(self.cache.iter().map(|(entry, _)| entry)).await;
+        }
+    }
 }
 
 /// Takes in an Event and returns a CacheEntry to place into the LRU cache
@@ -266,7 +312,27 @@ impl TaskTransform<Event> for Dedupe {
         Self: 'static,
     {
         let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+        let Some(checkpoint_interval) = inner.state.as_ref().map(StateStore::checkpoint_interval)
+        else {
+            return Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))));
+        };
+
+        let mut input = task;
+        let mut checkpoint = tokio::time::interval(checkpoint_interval);
+        Box::pin(stream! {
+            loop {
+                tokio::select! {
+                    maybe_event = input.next() => match maybe_event {
+                        Some(event) => if let Some(event) = inner.transform_one(event) {
+                            yield event;
+                        },
+                        None => break,
+                    },
+                    _ = checkpoint.tick() => inner.checkpoint(),
+                }
+            }
+            inner.flush_state().await;
+        })
     }
 }
 
@@ -278,10 +344,12 @@ mod tests {
     use tokio_stream::wrappers::ReceiverStream;
 
     use crate::{
+        config::{ComponentKey, TransformContext},
         event::{Event, LogEvent, Value},
+        state_store::StateStoreConfig,
         test_util::components::assert_transform_compliance,
         transforms::{
-            dedupe::{CacheConfig, DedupeConfig, FieldMatchConfig},
+            dedupe::{CacheConfig, Dedupe, DedupeConfig, FieldMatchConfig},
             test::create_topology,
         },
     };
@@ -297,6 +365,7 @@ mod tests {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
             },
             fields: Some(FieldMatchConfig::MatchFields(fields)),
+            state: Default::default(),
         }
     }
 
@@ -310,6 +379,7 @@ mod tests {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
             },
             fields: Some(FieldMatchConfig::IgnoreFields(fields)),
+            state: Default::default(),
         }
     }
 
@@ -325,6 +395,51 @@ mod tests {
         basic(transform_config).await;
     }
 
+    #[tokio::test]
+    async fn dedupe_restores_persisted_cache() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut config = make_match_transform_config(5, vec!["matched".into()]);
+        config.state = StateStoreConfig {
+            enabled: true,
+            data_dir: Some(data_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let context = TransformContext {
+            key: Some(ComponentKey::from("dedupe")),
+            ..Default::default()
+        };
+        let build = || async {
+            let state = config.state.build(&context).unwrap().unwrap();
+            Dedupe::new(config.clone()).with_state(state).await
+        };
+
+        let events = ["a", "b", "c"].map(|value| {
+            let mut event = LogEvent::from("message");
+            event.insert("matched", value);
+            Event::from(event)
+        });
+
+        let mut dedupe = build().await;
+        for event in &events {
+            assert!(dedupe.transform_one(event.clone()).is_some());
+        }
+        // Seeing "a" again makes "b" the least recently seen event.
+        assert!(dedupe.transform_one(events[0].clone()).is_none());
+        dedupe.flush_state().await;
+
+        let mut dedupe = build().await;
+        let expected = [&events[0], &events[2], &events[1]]
+            .map(|event| super::build_cache_entry(event, &dedupe.fields));
+        assert!(dedupe
+            .cache
+            .iter()
+            .map(|(entry, _)| entry)
+            .eq(expected.iter()));
+        for event in &events {
+            assert!(dedupe.transform_one(event.clone()).is_none());
+        }
+    }
+
     async fn basic(transform_config: DedupeConfig) {
         assert_transform_compliance(async {
             let (tx, rx) = mpsc::channel(1);
@@ -8,7 +8,7 @@ use crate::{
     event::Event,
     internal_events::FilterEventsDropped,
     schema,
-    transforms::{FunctionTransform, OutputBuffer, Transform},
+    transforms::{FunctionTransform, OutputBuffer, TraceScope, Transform},
 };
 
 /// Configuration for the `filter` transform.
@@ -21,11 +21,24 @@ pub struct FilterConfig {
     ///
     /// If an event is matched by the condition, it is forwarded. Otherwise, the event is dropped.
     condition: AnyCondition,
+
+    /// The scope trace events are filtered at.
+    ///
+    /// When set to `span`, the condition is matched against each span of trace events, and only the
+    /// spans it matches are forwarded. Trace events left without any span are dropped.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    trace_scope: TraceScope,
 }
 
 impl From<AnyCondition> for FilterConfig {
     fn from(condition: AnyCondition) -> Self {
-        Self { condition }
+        Self {
+            condition,
+            trace_scope: TraceScope::default(),
+        }
     }
 }
 
@@ -39,9 +52,10 @@ impl GenerateConfig for FilterConfig {
 #[typetag::serde(name = "filter")]
 impl TransformConfig for FilterConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Filter::new(
-            self.condition.build(&context.enrichment_tables)?,
-        )))
+        Ok(Transform::function(
+            Filter::new(self.condition.build(&context.enrichment_tables)?)
+                .with_trace_scope(self.trace_scope),
+        ))
     }
 
     fn input(&self) -> Input {
@@ -60,6 +74,7 @@ impl TransformConfig for FilterConfig {
 #[derive(Clone)]
 pub struct Filter {
     condition: Condition,
+    trace_scope: TraceScope,
     events_dropped: Registered<FilterEventsDropped>,
 }
 
@@ -67,13 +82,34 @@ impl Filter {
     pub fn new(condition: Condition) -> Self {
         Self {
             condition,
+            trace_scope: TraceScope::Trace,
             events_dropped: register!(FilterEventsDropped),
         }
     }
+
+    pub fn with_trace_scope(mut self, trace_scope: TraceScope) -> Self {
+        self.trace_scope = trace_scope;
+        self
+    }
 }
 
 impl FunctionTransform for Filter {
     fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        let event = match (self.trace_scope, event) {
+            (TraceScope::Span, Event::Trace(mut trace)) => {
+                let mut spans = trace.take_spans();
+                spans.retain(|span| self.condition.check_span(&trace, span));
+                if spans.is_empty() {
+                    self.events_dropped.emit(Count(1));
+                } else {
+                    trace.set_spans(spans);
+                    output.push(Event::Trace(trace));
+                }
+                return;
+            }
+            (_, event) => event,
+        };
+
         let (result, event) = self.condition.check(event);
         if result {
             output.push(event);
@@ -87,14 +123,15 @@ impl FunctionTransform for Filter {
 mod test {
     use tokio::sync::mpsc;
     use tokio_stream::wrappers::ReceiverStream;
+    use vector_common::btreemap;
     use vector_core::event::{Metric, MetricKind, MetricValue};
 
     use super::*;
     use crate::{
-        conditions::ConditionConfig,
-        event::{Event, LogEvent},
+        conditions::{ConditionConfig, ConditionalConfig, VrlConfig},
+        event::{Event, LogEvent, TraceEvent, Value},
         test_util::components::assert_transform_compliance,
-        transforms::test::create_topology,
+        transforms::test::{create_topology, transform_one},
     };
 
     #[test]
@@ -129,4 +166,35 @@ mod test {
         })
         .await;
     }
+
+    #[test]
+    fn filter_spans() {
+        let condition = VrlConfig {
+            source: r#".name == "db.query""#.to_owned(),
+            runtime: Default::default(),
+        }
+        .build(&Default::default())
+        .unwrap();
+        let mut filter = Filter::new(condition).with_trace_scope(TraceScope::Span);
+
+        let mut trace = TraceEvent::default();
+        trace.insert("trace_id", 1);
+        trace.set_spans(vec![
+            Value::from(btreemap!("name" => "db.query")),
+            Value::from(btreemap!("name" => "http.request")),
+        ]);
+
+        let trace = transform_one(&mut filter, trace.into())
+            .unwrap()
+            .into_trace();
+        assert_eq!(trace.get("trace_id"), Some(&Value::from(1)));
+        assert_eq!(
+            trace.spans(),
+            &[Value::from(btreemap!("name" => "db.query"))]
+        );
+
+        let mut trace = TraceEvent::default();
+        trace.set_spans(vec![Value::from(btreemap!("name" => "http.request"))]);
+        assert!(transform_one(&mut filter, trace.into()).is_none());
+    }
 }
@@ -3,6 +3,7 @@
 use std::collections::HashSet;
 
 use snafu::Snafu;
+use vector_config::configurable_component;
 
 #[cfg(feature = "transforms-aggregate")]
 pub mod aggregate;
@@ -27,14 +28,32 @@ pub mod route;
 pub mod sample;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
+#[cfg(feature = "transforms-tenant_quota")]
+pub mod tenant_quota;
 #[cfg(feature = "transforms-throttle")]
 pub mod throttle;
+#[cfg(feature = "transforms-transcode")]
+pub mod transcode;
 
 pub use vector_core::transform::{
     FunctionTransform, OutputBuffer, SyncTransform, TaskTransform, Transform, TransformOutputs,
     TransformOutputsBuf,
 };
 
+/// The scope trace events are processed at.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceScope {
+    /// Each trace event is processed as a whole, with the fields of the trace at the root of the event.
+    #[default]
+    Trace,
+
+    /// Each span of a trace event is processed on its own, with the fields of the span at the root
+    /// of the event.
+    Span,
+}
+
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("Invalid regular expression: {}", source))]
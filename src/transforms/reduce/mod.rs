@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 use std::{
-    cmp::min,
+    cmp::{min, Reverse},
     collections::{hash_map, HashMap},
     num::NonZeroUsize,
     pin::Pin,
@@ -21,6 +21,7 @@ use crate::{
     event::{discriminant::Discriminant, Event, EventMetadata, LogEvent},
     internal_events::ReduceStaleEventFlushed,
     schema,
+    state_store::{StateStore, StateStoreConfig},
     transforms::{TaskTransform, Transform},
 };
 
@@ -102,6 +103,12 @@ pub struct ReduceConfig {
     /// If this condition resolves to `true` for an event, the previous transaction is flushed
     /// (without this event) and a new transaction is started.
     pub starts_when: Option<AnyCondition>,
+
+    /// Persists the events of the groups that haven't been flushed yet, so that they're reduced
+    /// together with the events that follow them if Vector exits without stopping the transform.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub state: StateStoreConfig,
 }
 
 const fn default_expire_after_ms() -> Duration {
@@ -118,7 +125,11 @@ impl_generate_config_from_default!(ReduceConfig);
 #[typetag::serde(name = "reduce")]
 impl TransformConfig for ReduceConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Reduce::new(self, &context.enrichment_tables).map(Transform::event_task)
+        let mut reduce = Reduce::new(self, &context.enrichment_tables)?;
+        if let Some(state) = self.state.build(context)? {
+            reduce = reduce.with_state(state).await;
+        }
+        Ok(Transform::event_task(reduce))
     }
 
     fn input(&self) -> Input {
@@ -220,10 +231,13 @@ struct ReduceState {
     stale_since: Instant,
     last_flushed_at: Instant,
     metadata: EventMetadata,
+    /// The events added to the group so far, kept only when the state is persisted so that the
+    /// group can be rebuilt after a restart.
+    inputs: Option<Vec<Value>>,
 }
 
 impl ReduceState {
-    fn new() -> Self {
+    fn new(persisted: bool) -> Self {
         let fields = HashMap::new();
         let metadata = EventMetadata::default();
 
@@ -233,12 +247,16 @@ impl ReduceState {
             last_flushed_at: Instant::now(),
             fields,
             metadata,
+            inputs: persisted.then(Vec::new),
         }
     }
 
     fn add_event(&mut self, e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) {
         let (value, metadata) = e.into_parts();
         self.metadata.merge(metadata);
+        if let Some(inputs) = &mut self.inputs {
+            inputs.push(value.clone());
+        }
 
         let fields = if let Value::Object(fields) = value {
             fields
@@ -295,6 +313,7 @@ pub struct Reduce {
     ends_when: Option<Condition>,
     starts_when: Option<Condition>,
     max_events: Option<usize>,
+    state: Option<StateStore>,
 }
 
 impl Reduce {
@@ -328,9 +347,39 @@ impl Reduce {
             ends_when,
             starts_when,
             max_events,
+            state: None,
         })
     }
 
+    /// Rebuilds the groups checkpointed to the given state store, and checkpoints them to it from
+    /// then on.
+    pub async fn with_state(mut self, state: StateStore) -> Self {
+        let groups = state.restore::<Vec<Value>>().await;
+        self.state = Some(state);
+        for events in groups.into_iter().rev() {
+            for event in events {
+                let event = LogEvent::from(event);
+                let discriminant = Discriminant::from_log_event(&event, &self.group_by);
+                self.push_or_new_reduce_state(event, discriminant);
+            }
+        }
+        self
+    }
+
+    fn checkpoint(&mut self) {
+        if let Some(state) = &mut self.state {
+            state.checkpoint(persisted_groups(&self.reduce_merge_states));
+        }
+    }
+
+    async fn flush_state(&mut self) {
+        if let Some(state) = &mut self.state {
+            state
+                .flush(persisted_groups(&self.reduce_merge_states))
+                .await;
+        }
+    }
+
     fn flush_into(&mut self, output: &mut Vec<Event>) {
         let mut flush_discriminants = Vec::new();
         let now = Instant::now();
@@ -346,18 +395,25 @@ impl Reduce {
                 output.push(Event::from(t.flush()));
             }
         }
+        if let (Some(state), false) = (&mut self.state, flush_discriminants.is_empty()) {
+            state.mark_changed();
+        }
     }
 
     fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        if let (Some(state), false) = (&mut self.state, self.reduce_merge_states.is_empty()) {
+            state.mark_changed();
+        }
         self.reduce_merge_states
             .drain()
             .for_each(|(_, s)| output.push(Event::from(s.flush())));
     }
 
     fn push_or_new_reduce_state(&mut self, event: LogEvent, discriminant: Discriminant) {
+        let persisted = self.state.is_some();
         match self.reduce_merge_states.entry(discriminant) {
             hash_map::Entry::Vacant(entry) => {
-                let mut state = ReduceState::new();
+                let mut state = ReduceState::new(persisted);
                 state.add_event(event, &self.merge_strategies);
                 entry.insert(state);
             }
@@ -380,6 +436,9 @@ impl Reduce {
 
         let event = event.into_log();
         let discriminant = Discriminant::from_log_event(&event, &self.group_by);
+        if let Some(state) = &mut self.state {
+            state.mark_changed();
+        }
 
         if let Some(max_events) = self.max_events {
             if max_events == 1 {
@@ -405,7 +464,7 @@ impl Reduce {
                     state.flush().into()
                 }
                 None => {
-                    let mut state = ReduceState::new();
+                    let mut state = ReduceState::new(false);
                     state.add_event(event, &self.merge_strategies);
                     state.flush().into()
                 }
@@ -416,6 +475,17 @@ impl Reduce {
     }
 }
 
+/// Lists the events of each group, from the most to the least recently updated group, which is the
+/// order the state store expects.
+fn persisted_groups(states: &HashMap<Discriminant, ReduceState>) -> Vec<&[Value]> {
+    let mut groups = states
+        .values()
+        .filter_map(|state| Some((state.stale_since, state.inputs.as_deref()?)))
+        .collect::<Vec<_>>();
+    groups.sort_unstable_by_key(|(stale_since, _)| Reverse(*stale_since));
+    groups.into_iter().map(|(_, inputs)| inputs).collect()
+}
+
 impl TaskTransform<Event> for Reduce {
     fn transform(
         self: Box<Self>,
@@ -429,6 +499,11 @@ impl TaskTransform<Event> for Reduce {
         let poll_period = me.flush_period;
 
         let mut flush_stream = tokio::time::interval(poll_period);
+        let mut checkpoint = tokio::time::interval(
+            me.state
+                .as_ref()
+                .map_or(poll_period, StateStore::checkpoint_interval),
+        );
 
         Box::pin(
             stream! {
@@ -439,6 +514,10 @@ impl TaskTransform<Event> for Reduce {
                       me.flush_into(&mut output);
                       false
                     }
+                    _ = checkpoint.tick(), if me.state.is_some() => {
+                      me.checkpoint();
+                      false
+                    }
                     maybe_event = input_rx.next() => {
                       match maybe_event {
                         None => {
@@ -455,6 +534,7 @@ impl TaskTransform<Event> for Reduce {
                 yield stream::iter(output.into_iter());
                 if done { break }
               }
+              me.flush_state().await;
             }
             .flatten(),
         )
@@ -470,7 +550,8 @@ mod test {
     use value::Kind;
 
     use super::*;
-    use crate::event::{LogEvent, Value};
+    use crate::config::ComponentKey;
+    use crate::event::{BatchNotifier, BatchStatus, EventStatus, LogEvent, Value};
     use crate::test_util::components::assert_transform_compliance;
     use crate::transforms::test::create_topology;
     use lookup::owned_value_path;
@@ -480,6 +561,96 @@ mod test {
         crate::test_util::test_generate_config::<ReduceConfig>();
     }
 
+    #[test]
+    fn holds_acknowledgements_until_reduced_event_is_delivered() {
+        let reduce_config =
+            toml::from_str::<ReduceConfig>(r#"group_by = [ "request_id" ]"#).unwrap();
+        let mut reduce =
+            Reduce::new(&reduce_config, &enrichment::TableRegistry::default()).unwrap();
+
+        let (batch_1, mut receiver_1) = BatchNotifier::new_with_receiver();
+        let (batch_2, mut receiver_2) = BatchNotifier::new_with_receiver();
+        let mut e_1 = LogEvent::from("test message 1").with_batch_notifier(&batch_1);
+        e_1.insert("request_id", "1");
+        let mut e_2 = LogEvent::from("test message 2").with_batch_notifier(&batch_2);
+        e_2.insert("request_id", "1");
+        drop(batch_1);
+        drop(batch_2);
+
+        let mut output = Vec::new();
+        reduce.transform_one(&mut output, e_1.into());
+        reduce.transform_one(&mut output, e_2.into());
+        assert!(output.is_empty());
+        assert!(receiver_1.try_recv().is_err());
+        assert!(receiver_2.try_recv().is_err());
+
+        reduce.flush_all_into(&mut output);
+        assert_eq!(output.len(), 1);
+        // Flushing alone doesn't acknowledge the source events.
+        assert!(receiver_1.try_recv().is_err());
+        assert!(receiver_2.try_recv().is_err());
+
+        output[0].metadata().update_status(EventStatus::Rejected);
+        drop(output);
+        assert_eq!(receiver_1.try_recv(), Ok(BatchStatus::Rejected));
+        assert_eq!(receiver_2.try_recv(), Ok(BatchStatus::Rejected));
+    }
+
+    #[tokio::test]
+    async fn reduce_restores_persisted_groups() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut config = toml::from_str::<ReduceConfig>(r#"group_by = [ "request_id" ]"#).unwrap();
+        config.state = StateStoreConfig {
+            enabled: true,
+            data_dir: Some(data_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let context = TransformContext {
+            key: Some(ComponentKey::from("reduce")),
+            ..Default::default()
+        };
+        let build = || async {
+            let state = config.state.build(&context).unwrap().unwrap();
+            Reduce::new(&config, &context.enrichment_tables)
+                .unwrap()
+                .with_state(state)
+                .await
+        };
+
+        let mut output = Vec::new();
+        let mut reduce = build().await;
+        for (request_id, counter) in [("1", 1), ("2", 10), ("1", 2)] {
+            let mut event = LogEvent::from("test message");
+            event.insert("request_id", request_id);
+            event.insert("counter", counter);
+            reduce.transform_one(&mut output, event.into());
+        }
+        assert!(output.is_empty());
+        // Vector exits after the state was checkpointed, without stopping the transform.
+        reduce.flush_state().await;
+        drop(reduce);
+
+        let mut reduce = build().await;
+        reduce.flush_all_into(&mut output);
+        let counters = output
+            .iter()
+            .map(|event| {
+                let log = event.as_log();
+                (
+                    log["request_id"].to_string_lossy().into_owned(),
+                    log["counter"].clone(),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        assert_eq!(
+            counters,
+            HashMap::from([
+                ("1".to_string(), Value::from(3)),
+                ("2".to_string(), Value::from(10)),
+            ])
+        );
+    }
+
     #[tokio::test]
     async fn reduce_from_condition() {
         let reduce_config = toml::from_str::<ReduceConfig>(
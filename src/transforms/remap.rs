@@ -8,7 +8,7 @@ use std::{
 
 use codecs::MetricTagValues;
 use lookup::lookup_v2::{parse_value_path, ValuePath};
-use lookup::{metadata_path, owned_value_path, path, OwnedTargetPath, PathPrefix};
+use lookup::{owned_value_path, OwnedTargetPath};
 use snafu::{ResultExt, Snafu};
 use value::Kind;
 use vector_common::TimeZone;
@@ -26,16 +26,18 @@ use vrl::{
 
 use crate::{
     config::{
-        log_schema, ComponentKey, DataType, Input, Output, TransformConfig, TransformContext,
+        log_schema, unit_test::coverage, ComponentKey, DataType, Input, Output, TransformConfig,
+        TransformContext,
     },
-    event::{Event, TargetEvents, VrlTarget},
+    dead_letter::{DeadLetter, DEAD_LETTER_OUTPUT},
+    event::{Event, TargetEvents, TraceEvent, VrlTarget},
     internal_events::{RemapMappingAbort, RemapMappingError},
     schema,
-    transforms::{SyncTransform, Transform, TransformOutputsBuf},
+    transforms::{SyncTransform, TraceScope, Transform, TransformOutputsBuf},
     Result,
 };
 
-const DROPPED: &str = "dropped";
+const DROPPED: &str = DEAD_LETTER_OUTPUT;
 
 /// Configuration for the `remap` transform.
 #[configurable_component(transform(
@@ -128,12 +130,41 @@ pub struct RemapConfig {
     #[serde(default = "crate::serde::default_false")]
     pub reroute_dropped: bool,
 
+    /// The scope trace events are remapped at.
+    ///
+    /// When set to `span`, the program is run on each span of trace events, with the fields of the
+    /// span at the root of the event. A span the program fails or aborts on is kept unmodified, or
+    /// removed from the trace if `drop_on_error` or `drop_on_abort` apply, as dropped spans are not
+    /// rerouted.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub trace_scope: TraceScope,
+
     #[configurable(derived, metadata(docs::hidden))]
     #[serde(default)]
     pub runtime: VrlRuntime,
 }
 
 impl RemapConfig {
+    fn source(&self) -> Result<String> {
+        match (&self.source, &self.file) {
+            (Some(source), None) => Ok(source.to_owned()),
+            (None, Some(path)) => {
+                let mut buffer = String::new();
+
+                File::open(path)
+                    .with_context(|_| FileOpenFailedSnafu { path })?
+                    .read_to_string(&mut buffer)
+                    .with_context(|_| FileReadFailedSnafu { path })?;
+
+                Ok(buffer)
+            }
+            _ => Err(Box::new(BuildError::SourceAndOrFile)),
+        }
+    }
+
     fn compile_vrl_program(
         &self,
         enrichment_tables: enrichment::TableRegistry,
@@ -144,20 +175,7 @@ impl RemapConfig {
         Vec<Box<dyn vrl::Function>>,
         CompileConfig,
     )> {
-        let source = match (&self.source, &self.file) {
-            (Some(source), None) => source.to_owned(),
-            (None, Some(path)) => {
-                let mut buffer = String::new();
-
-                File::open(path)
-                    .with_context(|_| FileOpenFailedSnafu { path })?
-                    .read_to_string(&mut buffer)
-                    .with_context(|_| FileReadFailedSnafu { path })?;
-
-                buffer
-            }
-            _ => return Err(Box::new(BuildError::SourceAndOrFile)),
-        };
+        let source = self.source()?;
 
         let mut functions = vrl_stdlib::all();
         functions.append(&mut enrichment::vrl_functions());
@@ -174,6 +192,7 @@ impl RemapConfig {
 
         config.set_custom(enrichment_tables);
         config.set_custom(MeaningList::default());
+        config.set_track_coverage(coverage::enabled());
 
         compile_vrl(&source, &functions, &state, config)
             .map_err(|diagnostics| {
@@ -333,6 +352,7 @@ where
     dropped_schema_definition: Arc<schema::Definition>,
     runner: Runner,
     metric_tag_values: MetricTagValues,
+    trace_scope: TraceScope,
 }
 
 pub trait VrlRunner {
@@ -347,13 +367,23 @@ pub trait VrlRunner {
 #[derive(Debug)]
 pub struct AstRunner {
     pub runtime: Runtime,
+    /// The transform to attribute the coverage of the program to, when unit tests track coverage.
+    coverage: Option<ComponentKey>,
+}
+
+impl AstRunner {
+    fn new(coverage: Option<ComponentKey>) -> Self {
+        let mut runtime = Runtime::default();
+        if coverage.is_some() {
+            runtime.enable_coverage();
+        }
+        Self { runtime, coverage }
+    }
 }
 
 impl Clone for AstRunner {
     fn clone(&self) -> Self {
-        Self {
-            runtime: Runtime::default(),
-        }
+        Self::new(self.coverage.clone())
     }
 }
 
@@ -366,6 +396,9 @@ impl VrlRunner for AstRunner {
     ) -> std::result::Result<value::Value, Terminate> {
         let result = self.runtime.resolve(target, program, timezone);
         self.runtime.clear();
+        if let Some(key) = &self.coverage {
+            coverage::record_program(key, self.runtime.take_coverage());
+        }
         result
     }
 }
@@ -380,8 +413,11 @@ impl Remap<AstRunner> {
             context.merged_schema_definition.clone(),
         )?;
 
-        let runtime = Runtime::default();
-        let runner = AstRunner { runtime };
+        let coverage = context.key.clone().filter(|_| coverage::enabled());
+        if let Some(key) = &coverage {
+            coverage::register_program(key, &config.source()?, program.block_spans());
+        }
+        let runner = AstRunner::new(coverage);
 
         Self::new(config, context, program, runner).map(|remap| (remap, warnings))
     }
@@ -423,6 +459,7 @@ where
             dropped_schema_definition: Arc::new(dropped_schema_definition),
             runner,
             metric_tag_values: config.metric_tag_values,
+            trace_scope: config.trace_scope,
         })
     }
 
@@ -431,7 +468,7 @@ where
         &self.runner
     }
 
-    fn dropped_data(&self, reason: &str, error: ExpressionError) -> serde_json::Value {
+    fn annotate_dropped(&self, event: &mut Event, reason: &str, error: ExpressionError) {
         let message = error
             .notes()
             .iter()
@@ -439,59 +476,61 @@ where
             .last()
             .map(|note| note.to_string())
             .unwrap_or_else(|| error.to_string());
-        serde_json::json!({
-                "reason": reason,
-                "message": message,
-                "component_id": self.component_key,
-                "component_type": "remap",
-                "component_kind": "transform",
-        })
-    }
 
-    fn annotate_dropped(&self, event: &mut Event, reason: &str, error: ExpressionError) {
-        match event {
-            Event::Log(ref mut log) => match log.namespace() {
-                LogNamespace::Legacy => {
-                    log.insert(
-                        (
-                            PathPrefix::Event,
-                            log_schema().metadata_key().concat(path!("dropped")),
-                        ),
-                        self.dropped_data(reason, error),
-                    );
-                }
-                LogNamespace::Vector => {
-                    log.insert(
-                        metadata_path!("vector", "dropped"),
-                        self.dropped_data(reason, error),
-                    );
-                }
-            },
-            Event::Metric(ref mut metric) => {
-                let m = log_schema().metadata_key();
-                metric.replace_tag(format!("{}.dropped.reason", m), reason.into());
-                metric.replace_tag(
-                    format!("{}.dropped.component_id", m),
-                    self.component_key
-                        .as_ref()
-                        .map(ToString::to_string)
-                        .unwrap_or_else(String::new),
-                );
-                metric.replace_tag(format!("{}.dropped.component_type", m), "remap".into());
-                metric.replace_tag(format!("{}.dropped.component_kind", m), "transform".into());
-            }
-            Event::Trace(ref mut trace) => {
-                trace.insert(
-                    log_schema().metadata_key(),
-                    self.dropped_data(reason, error),
-                );
-            }
+        DeadLetter {
+            reason,
+            message,
+            component_id: self.component_key.as_ref(),
+            component_type: "remap",
+            component_kind: "transform",
         }
+        .annotate(event);
     }
 
     fn run_vrl(&mut self, target: &mut VrlTarget) -> std::result::Result<value::Value, Terminate> {
         self.runner.run(target, &self.program, &self.timezone)
     }
+
+    /// Runs the program on each span of the trace on its own.
+    fn remap_spans(&mut self, mut trace: TraceEvent) -> TraceEvent {
+        let spans = trace.take_spans();
+        let mut remapped = Vec::with_capacity(spans.len());
+        for span in spans {
+            let mut target = VrlTarget::new(
+                Event::Log(trace.span_event(span.clone())),
+                self.program.info(),
+                false,
+            );
+            match self.run_vrl(&mut target) {
+                Ok(_) => match target.into_events() {
+                    TargetEvents::One(event) => remapped.push(event.into_log().into_parts().0),
+                    TargetEvents::Logs(events) => {
+                        remapped.extend(events.map(|event| event.into_log().into_parts().0))
+                    }
+                    TargetEvents::Traces(_) => unreachable!("spans are remapped as log events"),
+                },
+                Err(Terminate::Abort(_)) => {
+                    emit!(RemapMappingAbort {
+                        event_dropped: self.drop_on_abort,
+                    });
+                    if !self.drop_on_abort {
+                        remapped.push(span);
+                    }
+                }
+                Err(Terminate::Error(error)) => {
+                    emit!(RemapMappingError {
+                        error: error.to_string(),
+                        event_dropped: self.drop_on_error,
+                    });
+                    if !self.drop_on_error {
+                        remapped.push(span);
+                    }
+                }
+            }
+        }
+        trace.set_spans(remapped);
+        trace
+    }
 }
 
 impl<Runner> SyncTransform for Remap<Runner>
@@ -499,6 +538,14 @@ where
     Runner: VrlRunner + Clone + Send + Sync,
 {
     fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let event = match (self.trace_scope, event) {
+            (TraceScope::Span, Event::Trace(trace)) => {
+                let trace = self.remap_spans(trace);
+                return push_default(trace.into(), output, &self.default_schema_definition);
+            }
+            (_, event) => event,
+        };
+
         // If a program can fail or abort at runtime and we know that we will still need to forward
         // the event in that case (either to the main output or `dropped`, depending on the
         // config), we need to clone the original event and keep it around, to allow us to discard
@@ -825,6 +872,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn check_remap_spans() {
+        let mut trace = TraceEvent::default();
+        trace.insert("trace_id", 1);
+        trace.set_spans(vec![
+            Value::from(btreemap!("name" => "db.query", "duration" => 2)),
+            Value::from(btreemap!("name" => "http.request")),
+        ]);
+
+        let conf = RemapConfig {
+            source: Some(
+                indoc! {r#"
+                .duration_ms = int!(.duration) * 1000
+            "#}
+                .to_owned(),
+            ),
+            drop_on_error: true,
+            trace_scope: TraceScope::Span,
+            ..Default::default()
+        };
+        let mut tform = remap(conf).unwrap();
+
+        let out = collect_outputs(&mut tform, trace.into());
+        assert_eq!(1, out.primary.len());
+        let trace = out.primary.into_events().next().unwrap().into_trace();
+        assert_eq!(trace.get("trace_id"), Some(&Value::from(1)));
+        // The span without a duration fails the program, and is dropped.
+        assert_eq!(
+            trace.spans(),
+            &[Value::from(
+                btreemap!("name" => "db.query", "duration" => 2, "duration_ms" => 2000)
+            )]
+        );
+    }
+
     #[test]
     fn check_remap_error() {
         let event = {
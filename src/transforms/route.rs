@@ -1,14 +1,14 @@
 use indexmap::IndexMap;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
-use vector_core::transform::SyncTransform;
+use vector_core::transform::{SyncTransform, TransformOutputsBuf};
 
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
-    event::Event,
+    event::{Event, TraceEvent},
     schema,
-    transforms::Transform,
+    transforms::{TraceScope, Transform},
 };
 
 pub(crate) const UNMATCHED_ROUTE: &str = "_unmatched";
@@ -16,6 +16,7 @@ pub(crate) const UNMATCHED_ROUTE: &str = "_unmatched";
 #[derive(Clone)]
 pub struct Route {
     conditions: Vec<(String, Condition)>,
+    trace_scope: TraceScope,
 }
 
 impl Route {
@@ -25,16 +26,53 @@ impl Route {
             let condition = condition.build(&context.enrichment_tables)?;
             conditions.push((output_name.clone(), condition));
         }
-        Ok(Self { conditions })
+        Ok(Self {
+            conditions,
+            trace_scope: config.trace_scope,
+        })
+    }
+
+    /// Routes each span of the trace on its own, forwarding to each route a copy of the trace holding
+    /// the spans it matches.
+    fn transform_spans(&self, mut trace: TraceEvent, output: &mut TransformOutputsBuf) {
+        let spans = trace.take_spans();
+        let mut matched = vec![false; spans.len()];
+        for (output_name, condition) in &self.conditions {
+            let route_spans = spans
+                .iter()
+                .zip(matched.iter_mut())
+                .filter(|(span, _)| condition.check_span(&trace, span))
+                .map(|(span, matched)| {
+                    *matched = true;
+                    span.clone()
+                })
+                .collect::<Vec<_>>();
+            if !route_spans.is_empty() {
+                let mut route_trace = trace.clone();
+                route_trace.set_spans(route_spans);
+                output.push_named(output_name, route_trace.into());
+            }
+        }
+
+        let unmatched_spans = spans
+            .into_iter()
+            .zip(matched)
+            .filter_map(|(span, matched)| (!matched).then_some(span))
+            .collect::<Vec<_>>();
+        if !unmatched_spans.is_empty() {
+            trace.set_spans(unmatched_spans);
+            output.push_named(UNMATCHED_ROUTE, trace.into());
+        }
     }
 }
 
 impl SyncTransform for Route {
-    fn transform(
-        &mut self,
-        event: Event,
-        output: &mut vector_core::transform::TransformOutputsBuf,
-    ) {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let event = match (self.trace_scope, event) {
+            (TraceScope::Span, Event::Trace(trace)) => return self.transform_spans(trace, output),
+            (_, event) => event,
+        };
+
         let mut check_failed: usize = 0;
         for (output_name, condition) in &self.conditions {
             let (result, event) = condition.check(event.clone());
@@ -68,12 +106,24 @@ pub struct RouteConfig {
     /// as a route name.
     #[configurable(metadata(docs::additional_props_description = "An individual route."))]
     route: IndexMap<String, AnyCondition>,
+
+    /// The scope trace events are routed at.
+    ///
+    /// When set to `span`, the conditions are matched against each span of trace events, and each
+    /// route is sent the trace holding only the spans it matches. The spans not matching any route
+    /// are sent to the `<transform_name>._unmatched` output.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    trace_scope: TraceScope,
 }
 
 impl GenerateConfig for RouteConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             route: IndexMap::new(),
+            trace_scope: TraceScope::default(),
         })
         .unwrap()
     }
@@ -127,11 +177,12 @@ impl TransformConfig for RouteConfig {
 #[cfg(test)]
 mod test {
     use indoc::indoc;
-    use vector_core::transform::TransformOutputsBuf;
+    use vector_common::btreemap;
 
     use super::*;
     use crate::{
         config::{build_unit_tests, ConfigBuilder},
+        event::Value,
         test_util::components::{init_test, COMPONENT_MULTIPLE_OUTPUTS_TESTS},
     };
 
@@ -282,6 +333,59 @@ mod test {
         }
     }
 
+    #[test]
+    fn route_spans() {
+        let output_names = vec!["db", "error", UNMATCHED_ROUTE];
+        let mut trace = TraceEvent::default();
+        trace.insert("trace_id", 1);
+        trace.set_spans(vec![
+            Value::from(btreemap!("name" => "db.query", "error" => 1)),
+            Value::from(btreemap!("name" => "db.query", "error" => 0)),
+            Value::from(btreemap!("name" => "http.request", "error" => 0)),
+        ]);
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            trace_scope = "span"
+            route.db = '.name == "db.query"'
+            route.error = '.error == 1'
+        "#,
+        )
+        .unwrap();
+
+        let mut transform = Route::new(&config, &Default::default()).unwrap();
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            output_names
+                .iter()
+                .map(|output_name| {
+                    Output::default(DataType::all()).with_port(output_name.to_owned())
+                })
+                .collect(),
+            1,
+        );
+
+        transform.transform(trace.into(), &mut outputs);
+        for (output_name, span_names) in [
+            ("db", vec!["db.query", "db.query"]),
+            ("error", vec!["db.query"]),
+            (UNMATCHED_ROUTE, vec!["http.request"]),
+        ] {
+            let mut events: Vec<_> = outputs.drain_named(output_name).collect();
+            assert_eq!(events.len(), 1);
+            let trace = events.pop().unwrap().into_trace();
+            assert_eq!(trace.get("trace_id"), Some(&Value::from(1)));
+            let names = trace
+                .spans()
+                .iter()
+                .map(|span| {
+                    span.as_object().unwrap()["name"]
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(names, span_names);
+        }
+    }
+
     #[tokio::test]
     async fn route_metrics_with_output_tag() {
         init_test();
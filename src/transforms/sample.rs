@@ -4,10 +4,10 @@ use vector_core::config::LogNamespace;
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
-    event::Event,
+    event::{Event, TraceEvent, Value},
     internal_events::SampleEventDiscarded,
     schema,
-    transforms::{FunctionTransform, OutputBuffer, Transform},
+    transforms::{FunctionTransform, OutputBuffer, TraceScope, Transform},
 };
 
 /// Configuration for the `sample` transform.
@@ -35,6 +35,17 @@ pub struct SampleConfig {
 
     /// A logical condition used to exclude events from sampling.
     pub exclude: Option<AnyCondition>,
+
+    /// The scope trace events are sampled at.
+    ///
+    /// When set to `span`, each span of trace events is sampled on its own, with `key_field` and
+    /// `exclude` applied to the fields of the span, and only the sampled spans are forwarded. Trace
+    /// events left without any span are dropped.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub trace_scope: TraceScope,
 }
 
 impl GenerateConfig for SampleConfig {
@@ -43,6 +54,7 @@ impl GenerateConfig for SampleConfig {
             rate: 10,
             key_field: None,
             exclude: None::<AnyCondition>,
+            trace_scope: TraceScope::default(),
         })
         .unwrap()
     }
@@ -52,14 +64,17 @@ impl GenerateConfig for SampleConfig {
 #[typetag::serde(name = "sample")]
 impl TransformConfig for SampleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Sample::new(
-            self.rate,
-            self.key_field.clone(),
-            self.exclude
-                .as_ref()
-                .map(|condition| condition.build(&context.enrichment_tables))
-                .transpose()?,
-        )))
+        Ok(Transform::function(
+            Sample::new(
+                self.rate,
+                self.key_field.clone(),
+                self.exclude
+                    .as_ref()
+                    .map(|condition| condition.build(&context.enrichment_tables))
+                    .transpose()?,
+            )
+            .with_trace_scope(self.trace_scope),
+        ))
     }
 
     fn input(&self) -> Input {
@@ -77,6 +92,7 @@ pub struct Sample {
     rate: u64,
     key_field: Option<String>,
     exclude: Option<Condition>,
+    trace_scope: TraceScope,
     count: u64,
 }
 
@@ -86,13 +102,67 @@ impl Sample {
             rate,
             key_field,
             exclude,
+            trace_scope: TraceScope::Trace,
             count: 0,
         }
     }
+
+    pub const fn with_trace_scope(mut self, trace_scope: TraceScope) -> Self {
+        self.trace_scope = trace_scope;
+        self
+    }
+
+    /// Whether the next event, with the given value of the key field, is sampled.
+    fn is_sampled(&mut self, value: Option<&Value>) -> bool {
+        let num = if let Some(value) = value {
+            seahash::hash(value.to_string_lossy().as_bytes())
+        } else {
+            self.count
+        };
+
+        self.count = (self.count + 1) % self.rate;
+
+        num % self.rate == 0
+    }
+
+    /// Samples each span of the trace on its own, forwarding the trace with the sampled spans.
+    fn transform_spans(&mut self, mut trace: TraceEvent, output: &mut OutputBuffer) {
+        let mut spans = trace.take_spans();
+        spans.retain_mut(|span| {
+            if let Some(condition) = self.exclude.as_ref() {
+                if condition.check_span(&trace, span) {
+                    return true;
+                }
+            }
+
+            let value = self
+                .key_field
+                .as_ref()
+                .and_then(|key_field| span.get(key_field.as_str()));
+            if self.is_sampled(value) {
+                span.insert("sample_rate", self.rate.to_string());
+                true
+            } else {
+                false
+            }
+        });
+
+        if spans.is_empty() {
+            emit!(SampleEventDiscarded);
+        } else {
+            trace.set_spans(spans);
+            output.push(Event::Trace(trace));
+        }
+    }
 }
 
 impl FunctionTransform for Sample {
     fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        let event = match (self.trace_scope, event) {
+            (TraceScope::Span, Event::Trace(trace)) => return self.transform_spans(trace, output),
+            (_, event) => event,
+        };
+
         let mut event = {
             if let Some(condition) = self.exclude.as_ref() {
                 let (result, event) = condition.check(event);
@@ -107,25 +177,13 @@ impl FunctionTransform for Sample {
             }
         };
 
-        let value = self
-            .key_field
-            .as_ref()
-            .and_then(|key_field| match &event {
-                Event::Log(event) => event.get(key_field.as_str()),
-                Event::Trace(event) => event.get(key_field.as_str()),
-                Event::Metric(_) => panic!("component can never receive metric events"),
-            })
-            .map(|v| v.to_string_lossy());
+        let value = self.key_field.as_ref().and_then(|key_field| match &event {
+            Event::Log(event) => event.get(key_field.as_str()),
+            Event::Trace(event) => event.get(key_field.as_str()),
+            Event::Metric(_) => panic!("component can never receive metric events"),
+        });
 
-        let num = if let Some(value) = value {
-            seahash::hash(value.as_bytes())
-        } else {
-            self.count
-        };
-
-        self.count = (self.count + 1) % self.rate;
-
-        if num % self.rate == 0 {
+        if self.is_sampled(value) {
             match event {
                 Event::Log(ref mut event) => event.insert("sample_rate", self.rate.to_string()),
                 Event::Trace(ref mut event) => event.insert("sample_rate", self.rate.to_string()),
@@ -152,6 +210,7 @@ mod tests {
     };
     use tokio::sync::mpsc;
     use tokio_stream::wrappers::ReceiverStream;
+    use vector_common::btreemap;
 
     fn condition_contains(key: &str, needle: &str) -> Condition {
         let vrl_config = VrlConfig {
@@ -343,6 +402,39 @@ mod tests {
         assert_eq!(total_passed, 1);
     }
 
+    #[test]
+    fn samples_spans() {
+        let mut sampler = Sample::new(2, None, Some(condition_contains("name", "db")))
+            .with_trace_scope(TraceScope::Span);
+
+        let mut trace = TraceEvent::default();
+        trace.insert("trace_id", 1);
+        trace.set_spans(vec![
+            Value::from(btreemap!("name" => "db.query")),
+            Value::from(btreemap!("name" => "http.request")),
+            Value::from(btreemap!("name" => "http.response")),
+            Value::from(btreemap!("name" => "http.retry")),
+        ]);
+
+        let trace = transform_one(&mut sampler, trace.into())
+            .unwrap()
+            .into_trace();
+        assert_eq!(trace.get("trace_id"), Some(&Value::from(1)));
+        assert!(trace.get("sample_rate").is_none());
+        assert_eq!(
+            trace.spans(),
+            &[
+                Value::from(btreemap!("name" => "db.query")),
+                Value::from(btreemap!("name" => "http.request", "sample_rate" => "2")),
+                Value::from(btreemap!("name" => "http.retry", "sample_rate" => "2")),
+            ]
+        );
+
+        let mut trace = TraceEvent::default();
+        trace.set_spans(vec![Value::from(btreemap!("name" => "http.request"))]);
+        assert!(transform_one(&mut sampler, trace.into()).is_none());
+    }
+
     #[tokio::test]
     async fn emits_internal_events() {
         assert_transform_compliance(async move {
@@ -350,6 +442,7 @@ mod tests {
                 rate: 1,
                 key_field: None,
                 exclude: None,
+                trace_scope: TraceScope::default(),
             };
             let (tx, rx) = mpsc::channel(1);
             let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
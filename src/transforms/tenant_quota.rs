@@ -0,0 +1,693 @@
+use std::{collections::HashMap, collections::VecDeque, pin::Pin, time::Duration};
+
+use async_stream::stream;
+use futures::{FutureExt, Stream, StreamExt};
+use indexmap::IndexMap;
+use tokio::time::{sleep_until, Instant};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::Event,
+    internal_events::{
+        TemplateRenderingError, TenantQuotaEventDiscarded, TenantQuotaTenantLimitReached,
+    },
+    schema,
+    template::Template,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `tenant_quota` transform.
+#[configurable_component(transform(
+    "tenant_quota",
+    "Enforce per-tenant quotas on a pipeline shared by several tenants."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TenantQuotaConfig {
+    /// The key identifying the tenant an event belongs to.
+    ///
+    /// Events for which the key cannot be rendered are assigned to `fallback_tenant`.
+    #[configurable(metadata(
+        docs::examples = "{{ tenant_id }}",
+        docs::examples = "{{ kubernetes.pod_namespace }}"
+    ))]
+    pub tenant_key: Template,
+
+    /// The tenant that events are assigned to when `tenant_key` cannot be rendered.
+    #[serde(default = "default_fallback_tenant")]
+    pub fallback_tenant: String,
+
+    /// The field to tag each event with the name of its tenant.
+    ///
+    /// For metrics, this is the name of the tag to set instead. If not specified, events are
+    /// not tagged.
+    #[configurable(metadata(docs::examples = "tenant"))]
+    pub tag_field: Option<String>,
+
+    /// The quota applied to tenants that are not listed in `tenants`.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub default_quota: TenantQuota,
+
+    /// Quotas for specific tenants, keyed by tenant name.
+    #[configurable(metadata(docs::additional_props_description = "The quota for a tenant."))]
+    #[serde(default)]
+    pub tenants: HashMap<String, TenantQuota>,
+
+    /// The maximum number of tenants tracked at once.
+    ///
+    /// A tenant is tracked while it has events held, and until its `events_per_sec` quota has
+    /// fully recovered. Events of other tenants received while this many tenants are tracked are
+    /// dropped.
+    #[serde(default = "default_max_tenants")]
+    pub max_tenants: usize,
+
+    /// The maximum total size, in bytes, of the events held for all tenants.
+    ///
+    /// Once the events held reach this size, the transform stops reading events from its inputs
+    /// until some of them are forwarded.
+    #[serde(default = "default_max_buffered_bytes")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_buffered_bytes: usize,
+}
+
+/// The quota enforced for a tenant.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TenantQuota {
+    /// The maximum number of events per second forwarded for the tenant.
+    ///
+    /// Up to one second's worth of events may be forwarded in a burst. If not specified, the
+    /// number of events forwarded for the tenant is not limited.
+    #[configurable(metadata(docs::type_unit = "events"))]
+    pub events_per_sec: Option<u32>,
+
+    /// The maximum size, in bytes, of the events held for the tenant while they wait to be
+    /// forwarded.
+    ///
+    /// Events received for a tenant whose held events already reach this size are dropped.
+    #[serde(default = "default_buffer_bytes")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub buffer_bytes: usize,
+}
+
+impl Default for TenantQuota {
+    fn default() -> Self {
+        Self {
+            events_per_sec: None,
+            buffer_bytes: default_buffer_bytes(),
+        }
+    }
+}
+
+fn default_fallback_tenant() -> String {
+    "default".to_string()
+}
+
+const fn default_buffer_bytes() -> usize {
+    10 * 1024 * 1024
+}
+
+const fn default_max_tenants() -> usize {
+    10_000
+}
+
+const fn default_max_buffered_bytes() -> usize {
+    100 * 1024 * 1024
+}
+
+impl GenerateConfig for TenantQuotaConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc::indoc! {r#"
+            tenant_key = "{{ tenant_id }}"
+            default_quota.events_per_sec = 1000
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "tenant_quota")]
+impl TransformConfig for TenantQuotaConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        TenantQuotaTransform::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn outputs(&self, merged_definition: &schema::Definition, _: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::all()).with_schema_definition(merged_definition.clone())]
+    }
+}
+
+pub struct TenantQuotaTransform {
+    tenant_key: Template,
+    fallback_tenant: String,
+    tag_field: Option<String>,
+    scheduler: Scheduler,
+}
+
+impl TenantQuotaTransform {
+    pub fn new(config: &TenantQuotaConfig) -> crate::Result<Self> {
+        let quotas = std::iter::once(("default_quota".to_string(), &config.default_quota)).chain(
+            config
+                .tenants
+                .iter()
+                .map(|(name, quota)| (format!("tenants.{}", name), quota)),
+        );
+        for (name, quota) in quotas {
+            if quota.events_per_sec == Some(0) {
+                return Err(format!("`{}.events_per_sec` must be non-zero.", name).into());
+            }
+            if quota.buffer_bytes == 0 {
+                return Err(format!("`{}.buffer_bytes` must be non-zero.", name).into());
+            }
+        }
+        if config.max_tenants == 0 {
+            return Err("`max_tenants` must be non-zero.".into());
+        }
+        if config.max_buffered_bytes == 0 {
+            return Err("`max_buffered_bytes` must be non-zero.".into());
+        }
+
+        Ok(Self {
+            tenant_key: config.tenant_key.clone(),
+            fallback_tenant: config.fallback_tenant.clone(),
+            tag_field: config.tag_field.clone(),
+            scheduler: Scheduler::new(
+                config.default_quota,
+                config.tenants.clone(),
+                config.max_tenants,
+                config.max_buffered_bytes,
+            ),
+        })
+    }
+
+    fn enqueue(&mut self, mut event: Event) {
+        let tenant = self
+            .tenant_key
+            .render_string(&event)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("tenant_key"),
+                    drop_event: false,
+                })
+            })
+            .unwrap_or_else(|_| self.fallback_tenant.clone());
+
+        if let Some(field) = &self.tag_field {
+            match &mut event {
+                Event::Log(log) => {
+                    log.insert(field.as_str(), tenant.clone());
+                }
+                Event::Metric(metric) => {
+                    metric.replace_tag(field.clone(), tenant.clone());
+                }
+                Event::Trace(trace) => {
+                    trace.insert(field.as_str(), tenant.clone());
+                }
+            }
+        }
+
+        self.scheduler.enqueue(tenant, event, Instant::now());
+    }
+}
+
+impl TaskTransform<Event> for TenantQuotaTransform {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        Box::pin(stream! {
+            let mut input_done = false;
+            loop {
+                // Queue up everything that is already available, so that tenants compete for the
+                // output on equal terms rather than in the order their events arrived.
+                while !input_done && !self.scheduler.is_full() {
+                    match input_rx.next().now_or_never() {
+                        Some(Some(event)) => self.enqueue(event),
+                        Some(None) => input_done = true,
+                        None => break,
+                    }
+                }
+
+                if let Some(event) = self.scheduler.next_ready(Instant::now()) {
+                    yield event;
+                    continue;
+                }
+
+                if input_done {
+                    // Don't hold up shutdown waiting for quota to free up.
+                    for event in self.scheduler.drain() {
+                        yield event;
+                    }
+                    break;
+                }
+
+                // While the held events are at their limit, stop reading input until some are
+                // forwarded. Some tenant is then waiting for its quota, so `wake_at` is set.
+                let wake_at = self.scheduler.next_refill();
+                let accepting = !self.scheduler.is_full();
+                tokio::select! {
+                    biased;
+
+                    maybe_event = input_rx.next(), if accepting => match maybe_event {
+                        Some(event) => self.enqueue(event),
+                        None => input_done = true,
+                    },
+                    _ = sleep_until(wake_at.unwrap_or_else(Instant::now)), if wake_at.is_some() => {}
+                }
+            }
+        })
+    }
+}
+
+/// Holds the events of each tenant until its quota allows them to be forwarded, taking turns
+/// between the tenants that have events ready.
+struct Scheduler {
+    default_quota: TenantQuota,
+    quotas: HashMap<String, TenantQuota>,
+    tenants: IndexMap<String, TenantQueue>,
+    next: usize,
+    max_tenants: usize,
+    max_buffered_bytes: usize,
+    buffered_bytes: usize,
+}
+
+impl Scheduler {
+    fn new(
+        default_quota: TenantQuota,
+        quotas: HashMap<String, TenantQuota>,
+        max_tenants: usize,
+        max_buffered_bytes: usize,
+    ) -> Self {
+        Self {
+            default_quota,
+            quotas,
+            tenants: IndexMap::new(),
+            next: 0,
+            max_tenants,
+            max_buffered_bytes,
+            buffered_bytes: 0,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.buffered_bytes >= self.max_buffered_bytes
+    }
+
+    fn enqueue(&mut self, tenant: String, event: Event, now: Instant) {
+        if !self.tenants.contains_key(&tenant) && self.tenants.len() >= self.max_tenants {
+            self.forget_idle(now);
+            if self.tenants.len() >= self.max_tenants {
+                emit!(TenantQuotaTenantLimitReached {
+                    tenant: &tenant,
+                    max_tenants: self.max_tenants,
+                });
+                return;
+            }
+        }
+
+        let quota = self.quotas.get(&tenant).unwrap_or(&self.default_quota);
+        let entry = self.tenants.entry(tenant);
+        let index = entry.index();
+        entry.or_insert_with(|| TenantQueue::new(quota, now));
+        let (tenant, queue) = self
+            .tenants
+            .get_index_mut(index)
+            .expect("tenant was inserted");
+
+        let size = event.size_of();
+        if queue.queued_bytes + size > queue.buffer_bytes {
+            emit!(TenantQuotaEventDiscarded { tenant });
+            return;
+        }
+        queue.queued_bytes += size;
+        queue.events.push_back((event, size));
+        self.buffered_bytes += size;
+    }
+
+    /// Forgets about the tenants that have nothing queued and have fully recovered their quota.
+    fn forget_idle(&mut self, now: Instant) {
+        self.tenants.retain(|_, queue| !queue.is_idle(now));
+        self.next = 0;
+    }
+
+    fn next_ready(&mut self, now: Instant) -> Option<Event> {
+        let count = self.tenants.len();
+        for offset in 0..count {
+            let index = (self.next + offset) % count;
+            let (_, queue) = self.tenants.get_index_mut(index).expect("index in bounds");
+            if queue.events.is_empty() || !queue.take_token(now) {
+                continue;
+            }
+            let (event, size) = queue.events.pop_front().expect("queue is not empty");
+            queue.queued_bytes -= size;
+            self.buffered_bytes -= size;
+            self.next = index + 1;
+            return Some(event);
+        }
+
+        // Nothing can be sent right now, so this is a good time to forget about idle tenants.
+        self.forget_idle(now);
+        None
+    }
+
+    fn next_refill(&self) -> Option<Instant> {
+        self.tenants
+            .values()
+            .filter(|queue| !queue.events.is_empty())
+            .filter_map(TenantQueue::next_token)
+            .min()
+    }
+
+    fn drain(&mut self) -> impl Iterator<Item = Event> + '_ {
+        self.buffered_bytes = 0;
+        self.tenants
+            .drain(..)
+            .flat_map(|(_, queue)| queue.events.into_iter().map(|(event, _)| event))
+    }
+}
+
+struct TenantQueue {
+    events: VecDeque<(Event, usize)>,
+    queued_bytes: usize,
+    buffer_bytes: usize,
+    bucket: Option<TokenBucket>,
+}
+
+impl TenantQueue {
+    fn new(quota: &TenantQuota, now: Instant) -> Self {
+        Self {
+            events: VecDeque::new(),
+            queued_bytes: 0,
+            buffer_bytes: quota.buffer_bytes,
+            bucket: quota
+                .events_per_sec
+                .map(|rate| TokenBucket::new(f64::from(rate), now)),
+        }
+    }
+
+    fn take_token(&mut self, now: Instant) -> bool {
+        self.bucket
+            .as_mut()
+            .map_or(true, |bucket| bucket.try_take(now))
+    }
+
+    fn next_token(&self) -> Option<Instant> {
+        self.bucket.as_ref().map(TokenBucket::next_token)
+    }
+
+    fn is_idle(&mut self, now: Instant) -> bool {
+        self.events.is_empty()
+            && self
+                .bucket
+                .as_mut()
+                .map_or(true, |bucket| bucket.is_full(now))
+    }
+}
+
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, now: Instant) -> Self {
+        Self {
+            rate,
+            tokens: rate,
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.updated = now;
+    }
+
+    fn try_take(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next_token(&self) -> Instant {
+        let missing = (1.0 - self.tokens).max(0.0);
+        self.updated + Duration::from_secs_f64(missing / self.rate)
+    }
+
+    fn is_full(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= self.rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::Poll;
+
+    use futures::SinkExt;
+
+    use super::*;
+    use crate::event::{LogEvent, Value};
+
+    fn log(tenant: &str, message: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert("tenant_id", tenant);
+        log.into()
+    }
+
+    fn transform(config: &str) -> Box<dyn TaskTransform<Event>> {
+        let config = toml::from_str::<TenantQuotaConfig>(config).unwrap();
+        Box::new(TenantQuotaTransform::new(&config).unwrap())
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TenantQuotaConfig>();
+    }
+
+    #[test]
+    fn rejects_zero_quotas() {
+        let config = toml::from_str::<TenantQuotaConfig>(
+            r#"
+tenant_key = "{{ tenant_id }}"
+tenants.a.events_per_sec = 0
+"#,
+        )
+        .unwrap();
+        assert!(TenantQuotaTransform::new(&config).is_err());
+
+        let config = toml::from_str::<TenantQuotaConfig>(
+            r#"
+tenant_key = "{{ tenant_id }}"
+max_tenants = 0
+"#,
+        )
+        .unwrap();
+        assert!(TenantQuotaTransform::new(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn takes_turns_between_tenants() {
+        let transform = transform(
+            r#"
+tenant_key = "{{ tenant_id }}"
+tag_field = "tenant"
+"#,
+        );
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+        for event in [
+            log("a", "a1"),
+            log("a", "a2"),
+            log("a", "a3"),
+            log("b", "b1"),
+            log("b", "b2"),
+        ] {
+            tx.send(event).await.unwrap();
+        }
+        tx.disconnect();
+
+        let output = transform
+            .transform(Box::pin(rx))
+            .map(|event| {
+                let log = event.into_log();
+                assert_eq!(log.get("tenant"), log.get("tenant_id"));
+                log.get("message").unwrap().to_string_lossy().into_owned()
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(output, vec!["a1", "b1", "a2", "b2", "a3"]);
+    }
+
+    #[tokio::test]
+    async fn limits_events_per_tenant() {
+        tokio::time::pause();
+
+        let transform = transform(
+            r#"
+tenant_key = "{{ tenant_id }}"
+default_quota.events_per_sec = 1
+tenants.b.events_per_sec = 2
+"#,
+        );
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+        let mut out_stream = transform.transform(Box::pin(rx));
+
+        for event in [
+            log("a", "a1"),
+            log("a", "a2"),
+            log("b", "b1"),
+            log("b", "b2"),
+        ] {
+            tx.send(event).await.unwrap();
+        }
+
+        let mut messages = Vec::new();
+        for _ in 0..3 {
+            let event = out_stream.next().await.unwrap();
+            messages.push(
+                event
+                    .as_log()
+                    .get("message")
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+        }
+        assert_eq!(messages, vec!["a1", "b1", "b2"]);
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        let event = out_stream.next().await.unwrap();
+        assert_eq!(event.as_log().get("message"), Some(&Value::from("a2")));
+
+        tx.disconnect();
+        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+    }
+
+    #[tokio::test]
+    async fn drops_events_over_buffer_quota() {
+        tokio::time::pause();
+
+        let event = log("a", "a1");
+        let transform = transform(&format!(
+            r#"
+tenant_key = "{{{{ tenant_id }}}}"
+default_quota.events_per_sec = 1
+default_quota.buffer_bytes = {}
+"#,
+            event.size_of() * 2
+        ));
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+        for message in ["a1", "a2", "a3", "a4"] {
+            tx.send(log("a", message)).await.unwrap();
+        }
+        tx.send(log("b", "b1")).await.unwrap();
+        tx.disconnect();
+
+        let output = transform
+            .transform(Box::pin(rx))
+            .map(|event| {
+                event
+                    .as_log()
+                    .get("message")
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(output, vec!["a1", "b1", "a2"]);
+    }
+
+    #[tokio::test]
+    async fn drops_events_of_tenants_over_limit() {
+        tokio::time::pause();
+
+        let transform = transform(
+            r#"
+tenant_key = "{{ tenant_id }}"
+default_quota.events_per_sec = 1
+max_tenants = 1
+"#,
+        );
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+        let mut out_stream = transform.transform(Box::pin(rx));
+
+        tx.send(log("a", "a1")).await.unwrap();
+        let event = out_stream.next().await.unwrap();
+        assert_eq!(event.as_log().get("message"), Some(&Value::from("a1")));
+
+        // `a` is still tracked until its quota has recovered.
+        tx.send(log("b", "b1")).await.unwrap();
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        tx.send(log("b", "b2")).await.unwrap();
+        let event = out_stream.next().await.unwrap();
+        assert_eq!(event.as_log().get("message"), Some(&Value::from("b2")));
+
+        tx.disconnect();
+        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+    }
+
+    #[tokio::test]
+    async fn stops_reading_input_when_buffers_are_full() {
+        tokio::time::pause();
+
+        // Reading all of the input at once would go over the buffer quota of the tenant, and
+        // drop events.
+        let size = log("a", "a1").size_of();
+        let transform = transform(&format!(
+            r#"
+tenant_key = "{{{{ tenant_id }}}}"
+default_quota.events_per_sec = 1
+default_quota.buffer_bytes = {}
+max_buffered_bytes = {}
+"#,
+            size * 2,
+            size * 2
+        ));
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+        for message in ["a1", "a2", "a3", "a4", "a5"] {
+            tx.send(log("a", message)).await.unwrap();
+        }
+        tx.disconnect();
+
+        let output = transform
+            .transform(Box::pin(rx))
+            .map(|event| {
+                event
+                    .as_log()
+                    .get("message")
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(output, vec!["a1", "a2", "a3", "a4", "a5"]);
+    }
+}
@@ -1,8 +1,10 @@
-use std::{num::NonZeroU32, pin::Pin, time::Duration};
+use std::{collections::HashMap, num::NonZeroU32, pin::Pin, time::Duration};
 
 use async_stream::stream;
+use chrono::Utc;
 use futures::{Stream, StreamExt};
 use governor::{clock, Quota, RateLimiter};
+use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use snafu::Snafu;
 use vector_config::configurable_component;
@@ -14,6 +16,7 @@ use crate::{
     event::Event,
     internal_events::{TemplateRenderingError, ThrottleEventDiscarded},
     schema,
+    state_store::{StateStore, StateStoreConfig},
     template::Template,
     transforms::{TaskTransform, Transform},
 };
@@ -44,6 +47,12 @@ pub struct ThrottleConfig {
 
     /// A logical condition used to exclude events from sampling.
     exclude: Option<AnyCondition>,
+
+    /// Persists how many events each key let through in the current window, so that the
+    /// threshold still applies across Vector restarts.
+    #[configurable(derived)]
+    #[serde(default)]
+    state: StateStoreConfig,
 }
 
 impl_generate_config_from_default!(ThrottleConfig);
@@ -52,7 +61,11 @@ impl_generate_config_from_default!(ThrottleConfig);
 #[typetag::serde(name = "throttle")]
 impl TransformConfig for ThrottleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Throttle::new(self, context, clock::MonotonicClock).map(Transform::event_task)
+        let mut throttle = Throttle::new(self, context, clock::MonotonicClock)?;
+        if let Some(state) = self.state.build(context)? {
+            throttle = throttle.with_state(state).await;
+        }
+        Ok(Transform::event_task(throttle))
     }
 
     fn input(&self) -> Input {
@@ -65,13 +78,82 @@ impl TransformConfig for ThrottleConfig {
     }
 }
 
-#[derive(Clone)]
 pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
     quota: Quota,
     flush_keys_interval: Duration,
     key_field: Option<Template>,
     exclude: Option<Condition>,
     clock: C,
+    state: Option<ThrottleState>,
+}
+
+/// The number of events a key let through in a window, as checkpointed.
+#[derive(Deserialize, Serialize)]
+struct ThrottleWindow {
+    key: Option<String>,
+    start_ms: i64,
+    count: u32,
+}
+
+/// Tracks how many events each key let through in the current window, so that the rate limiter
+/// can be brought back to the same point after a restart.
+///
+/// The rate limiter refills its quota gradually rather than per window, so this is an
+/// approximation: a restored key starts out with its count already used up, and gets its quota back
+/// at the usual rate from then on.
+struct ThrottleState {
+    store: StateStore,
+    window_ms: i64,
+    windows: HashMap<Option<String>, (i64, u32)>,
+}
+
+impl ThrottleState {
+    fn record(&mut self, key: &Option<String>) {
+        let now = Utc::now().timestamp_millis();
+        let window = self.windows.entry(key.clone()).or_insert((now, 0));
+        if now - window.0 >= self.window_ms {
+            *window = (now, 0);
+        }
+        window.1 += 1;
+        self.store.mark_changed();
+    }
+
+    fn retain_current(&mut self) {
+        let now = Utc::now().timestamp_millis();
+        let window_ms = self.window_ms;
+        let len = self.windows.len();
+        self.windows
+            .retain(|_, (start_ms, _)| now - *start_ms < window_ms);
+        if self.windows.len() != len {
+            self.store.mark_changed();
+        }
+    }
+
+    /// Lists the windows from most to least recently started, which is the order the store
+    /// expects.
+    fn entries(&self) -> Vec<ThrottleWindow> {
+        let mut entries = self
+            .windows
+            .iter()
+            .map(|(key, (start_ms, count))| ThrottleWindow {
+                key: key.clone(),
+                start_ms: *start_ms,
+                count: *count,
+            })
+            .collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|window| std::cmp::Reverse(window.start_ms));
+        entries
+    }
+
+    fn checkpoint(&mut self) {
+        let entries = self.entries();
+        self.store.checkpoint(entries);
+    }
+
+    async fn flush(&mut self) {
+        let entries = self.entries();
+        self.store.flush(entries).await;
+    }
 }
 
 impl<C, I> Throttle<C, I>
@@ -109,8 +191,29 @@ where
             flush_keys_interval,
             key_field: config.key_field.clone(),
             exclude,
+            state: None,
         })
     }
+
+    /// Restores the windows checkpointed to the given state store, and checkpoints them to it
+    /// from then on.
+    pub async fn with_state(mut self, store: StateStore) -> Self {
+        let window_ms = self.flush_keys_interval.as_millis() as i64;
+        let now = Utc::now().timestamp_millis();
+        let windows = store
+            .restore::<ThrottleWindow>()
+            .await
+            .into_iter()
+            .filter(|window| now - window.start_ms < window_ms)
+            .map(|window| (window.key, (window.start_ms, window.count)))
+            .collect();
+        self.state = Some(ThrottleState {
+            store,
+            window_ms,
+            windows,
+        });
+        self
+    }
 }
 
 impl<C, I> TaskTransform<Event> for Throttle<C, I>
@@ -119,7 +222,7 @@ where
     I: clock::Reference + Send + 'static,
 {
     fn transform(
-        self: Box<Self>,
+        mut self: Box<Self>,
         mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
     ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
     where
@@ -129,6 +232,22 @@ where
 
         let limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
 
+        let mut checkpoint = tokio::time::interval(
+            self.state
+                .as_ref()
+                .map_or(self.flush_keys_interval, |state| {
+                    state.store.checkpoint_interval()
+                }),
+        );
+        if let Some(state) = &self.state {
+            // Use up the quota the restored keys had already used before the restart.
+            for (key, (_, count)) in &state.windows {
+                for _ in 0..(*count).min(self.quota.burst_size().get()) {
+                    _ = limiter.check_key(key);
+                }
+            }
+        }
+
         Box::pin(stream! {
           loop {
             let done = tokio::select! {
@@ -160,6 +279,9 @@ where
 
                                 match limiter.check_key(&key) {
                                     Ok(()) => {
+                                        if let Some(state) = self.state.as_mut() {
+                                            state.record(&key);
+                                        }
                                         Some(event)
                                     }
                                     _ => {
@@ -183,11 +305,23 @@ where
                 }
                 _ = flush_keys.tick() => {
                     limiter.retain_recent();
+                    if let Some(state) = self.state.as_mut() {
+                        state.retain_current();
+                    }
+                    false
+                }
+                _ = checkpoint.tick(), if self.state.is_some() => {
+                    if let Some(state) = self.state.as_mut() {
+                        state.checkpoint();
+                    }
                     false
                 }
             };
             if done { break }
           }
+          if let Some(state) = self.state.as_mut() {
+              state.flush().await;
+          }
         })
     }
 }
@@ -206,7 +340,7 @@ mod tests {
 
     use super::*;
     use crate::{
-        event::LogEvent, test_util::components::assert_transform_compliance,
+        config::ComponentKey, event::LogEvent, test_util::components::assert_transform_compliance,
         transforms::test::create_topology,
     };
     use tokio::sync::mpsc;
@@ -406,6 +540,47 @@ key_field = "{{ bucket }}"
         assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
     }
 
+    #[tokio::test]
+    async fn throttle_restores_persisted_windows() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 2
+window_secs = 60
+"#,
+        )
+        .unwrap();
+        config.state = StateStoreConfig {
+            enabled: true,
+            data_dir: Some(data_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let context = TransformContext {
+            key: Some(ComponentKey::from("throttle")),
+            ..Default::default()
+        };
+        let clock = clock::FakeRelativeClock::default();
+
+        let run = |events: usize| {
+            let state = config.state.build(&context).unwrap().unwrap();
+            let throttle = Throttle::new(&config, &context, clock.clone()).unwrap();
+            async move {
+                let throttle = throttle.with_state(state).await;
+                let input =
+                    futures::stream::iter((0..events).map(|_| Event::from(LogEvent::default())));
+                Transform::event_task(throttle)
+                    .into_task()
+                    .transform_events(Box::pin(input))
+                    .count()
+                    .await
+            }
+        };
+
+        assert_eq!(run(1).await, 1);
+        // Only one event of the threshold is left in the window after the restart.
+        assert_eq!(run(3).await, 1);
+    }
+
     #[tokio::test]
     async fn emits_internal_events() {
         assert_transform_compliance(async move {
@@ -414,6 +589,7 @@ key_field = "{{ bucket }}"
                 window_secs: Duration::from_secs_f64(1.0),
                 key_field: None,
                 exclude: None,
+                state: Default::default(),
             };
             let (tx, rx) = mpsc::channel(1);
             let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
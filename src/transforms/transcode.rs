@@ -0,0 +1,395 @@
+use bytes::BytesMut;
+use codecs::{
+    decoding::{format::Deserializer as _, Deserializer, DeserializerConfig},
+    encoding::{Serializer, SerializerConfig},
+};
+use lookup::lookup_v2::parse_value_path;
+use tokio_util::codec::Encoder as _;
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{Event, LogEvent, Value},
+    internal_events::{ParserMissingFieldError, TranscodeFailed, DROP_EVENT, RETAIN_EVENT},
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Configuration for the `transcode` transform.
+#[configurable_component(transform(
+    "transcode",
+    "Re-encode an embedded payload field from one format to another."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TranscodeConfig {
+    /// The log field holding the payload to transcode.
+    #[configurable(metadata(docs::examples = "payload"))]
+    pub field: String,
+
+    /// The log field to write the transcoded payload to.
+    ///
+    /// If not specified, the payload is written back to `field`.
+    #[configurable(metadata(docs::examples = "decoded"))]
+    pub target_field: Option<String>,
+
+    /// Decodes the payload bytes into a structured value.
+    ///
+    /// If not specified, the value of `field` is used as-is. When a payload decodes into more than
+    /// one event, the decoded values are stored as an array.
+    #[configurable(derived)]
+    pub decoding: Option<DeserializerConfig>,
+
+    /// Encodes the (decoded) payload into bytes.
+    ///
+    /// If not specified, the decoded structured value is written to `target_field` as-is.
+    #[configurable(derived)]
+    pub encoding: Option<SerializerConfig>,
+
+    /// Drops any event whose payload could not be transcoded.
+    ///
+    /// Normally, if the payload is missing or cannot be decoded or encoded, the original,
+    /// unmodified event is sent downstream.
+    #[serde(default = "crate::serde::default_false")]
+    pub drop_on_error: bool,
+}
+
+impl GenerateConfig for TranscodeConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc::indoc! {r#"
+            field = "payload"
+            decoding.codec = "json"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "transcode")]
+impl TransformConfig for TranscodeConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if self.decoding.is_none() && self.encoding.is_none() {
+            return Err("At least one of `decoding` or `encoding` must be specified.".into());
+        }
+
+        let serializer = self
+            .encoding
+            .as_ref()
+            .map(|encoding| encoding.build())
+            .transpose()?;
+
+        Ok(Transform::function(Transcode {
+            field: self.field.clone(),
+            target_field: self
+                .target_field
+                .clone()
+                .unwrap_or_else(|| self.field.clone()),
+            deserializer: self.decoding.as_ref().map(DeserializerConfig::build),
+            serializer,
+            drop_on_error: self.drop_on_error,
+        }))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, merged_definition: &schema::Definition, _: LogNamespace) -> Vec<Output> {
+        let mut schema_definition = merged_definition.clone();
+
+        let target_field = self.target_field.as_ref().unwrap_or(&self.field);
+        if let Ok(path) = parse_value_path(target_field) {
+            if path.is_root() || schema_definition.event_kind().as_object().is_some() {
+                let mut kind = if self.encoding.is_some() {
+                    Kind::bytes()
+                } else {
+                    Kind::any()
+                };
+                // Events whose payload can't be transcoded are sent with the target field as-is.
+                if !self.drop_on_error {
+                    kind = kind.union(schema_definition.event_kind().at_path(&path));
+                }
+                schema_definition = schema_definition.with_event_field(&path, kind, None);
+            }
+        }
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+pub struct Transcode {
+    field: String,
+    target_field: String,
+    deserializer: Option<Deserializer>,
+    serializer: Option<Serializer>,
+    drop_on_error: bool,
+}
+
+impl Transcode {
+    fn decode(&self, value: Value) -> vector_common::Result<Value> {
+        let deserializer = match &self.deserializer {
+            Some(deserializer) => deserializer,
+            None => return Ok(value),
+        };
+
+        let bytes = match value {
+            Value::Bytes(bytes) => bytes,
+            value => {
+                return Err(format!(
+                    "Expected a bytes payload for decoding, found {}.",
+                    value.kind_str()
+                )
+                .into())
+            }
+        };
+
+        let mut values = deserializer
+            .parse(bytes, LogNamespace::Legacy)?
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Log(log) => Some(log.into_parts().0),
+                Event::Metric(_) | Event::Trace(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        Ok(match values.len() {
+            0 => Value::Null,
+            1 => values.remove(0),
+            _ => Value::Array(values),
+        })
+    }
+
+    fn encode(&mut self, value: Value) -> vector_common::Result<Value> {
+        let serializer = match &mut self.serializer {
+            Some(serializer) => serializer,
+            None => return Ok(value),
+        };
+
+        let mut buffer = BytesMut::new();
+        serializer.encode(Event::from(LogEvent::from(value)), &mut buffer)?;
+        Ok(Value::Bytes(buffer.freeze()))
+    }
+}
+
+impl FunctionTransform for Transcode {
+    fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        let log = event.as_mut_log();
+
+        let value = match log.get(self.field.as_str()) {
+            Some(value) => value.clone(),
+            None => {
+                if self.drop_on_error {
+                    emit!(ParserMissingFieldError::<DROP_EVENT> { field: &self.field });
+                    return;
+                }
+                emit!(ParserMissingFieldError::<RETAIN_EVENT> { field: &self.field });
+                output.push(event);
+                return;
+            }
+        };
+
+        match self.decode(value).and_then(|value| self.encode(value)) {
+            Ok(value) => {
+                log.insert(self.target_field.as_str(), value);
+            }
+            Err(error) => {
+                emit!(TranscodeFailed {
+                    field: &self.field,
+                    error,
+                    drop_event: self.drop_on_error,
+                });
+                if self.drop_on_error {
+                    return;
+                }
+            }
+        }
+
+        output.push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codecs::{JsonSerializerConfig, NativeJsonSerializerConfig};
+    use lookup::owned_value_path;
+    use similar_asserts::assert_eq;
+
+    use super::*;
+    use crate::transforms::test::transform_one;
+
+    async fn transcode(config: TranscodeConfig) -> Box<dyn FunctionTransform> {
+        config
+            .build(&TransformContext::default())
+            .await
+            .unwrap()
+            .into_function()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TranscodeConfig>();
+    }
+
+    #[tokio::test]
+    async fn decodes_json_payload_into_fields() {
+        let mut transform = transcode(TranscodeConfig {
+            field: "payload".into(),
+            target_field: Some("decoded".into()),
+            decoding: Some(DeserializerConfig::Json),
+            encoding: None,
+            drop_on_error: false,
+        })
+        .await;
+
+        let mut log = LogEvent::default();
+        log.insert("payload", r#"{"foo":"bar","count":3}"#);
+
+        let output = transform_one(transform.as_mut(), log.into()).unwrap();
+        let output = output.as_log();
+        assert_eq!(
+            output.get("payload"),
+            Some(&Value::from(r#"{"foo":"bar","count":3}"#))
+        );
+        assert_eq!(output.get("decoded.foo"), Some(&Value::from("bar")));
+        assert_eq!(output.get("decoded.count"), Some(&Value::from(3)));
+    }
+
+    #[tokio::test]
+    async fn encodes_structured_field_into_bytes() {
+        let mut transform = transcode(TranscodeConfig {
+            field: "payload".into(),
+            target_field: None,
+            decoding: None,
+            encoding: Some(JsonSerializerConfig::default().into()),
+            drop_on_error: false,
+        })
+        .await;
+
+        let mut log = LogEvent::default();
+        log.insert("payload.foo", "bar");
+
+        let output = transform_one(transform.as_mut(), log.into()).unwrap();
+        assert_eq!(
+            output.as_log().get("payload"),
+            Some(&Value::from(r#"{"foo":"bar"}"#))
+        );
+    }
+
+    #[tokio::test]
+    async fn converts_between_formats() {
+        let mut transform = transcode(TranscodeConfig {
+            field: "payload".into(),
+            target_field: None,
+            decoding: Some(DeserializerConfig::NativeJson),
+            encoding: Some(JsonSerializerConfig::default().into()),
+            drop_on_error: false,
+        })
+        .await;
+
+        let mut inner = LogEvent::default();
+        inner.insert("foo", "bar");
+        let mut buffer = BytesMut::new();
+        NativeJsonSerializerConfig
+            .build()
+            .encode(inner.into(), &mut buffer)
+            .unwrap();
+
+        let mut log = LogEvent::default();
+        log.insert("payload", Value::Bytes(buffer.freeze()));
+
+        let output = transform_one(transform.as_mut(), log.into()).unwrap();
+        assert_eq!(
+            output.as_log().get("payload"),
+            Some(&Value::from(r#"{"foo":"bar"}"#))
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_payload_is_forwarded_unmodified() {
+        let mut transform = transcode(TranscodeConfig {
+            field: "payload".into(),
+            target_field: Some("decoded".into()),
+            decoding: Some(DeserializerConfig::Json),
+            encoding: None,
+            drop_on_error: false,
+        })
+        .await;
+
+        let mut log = LogEvent::default();
+        log.insert("payload", "not json");
+
+        let output = transform_one(transform.as_mut(), log.clone().into()).unwrap();
+        assert_eq!(output, log.into());
+    }
+
+    #[tokio::test]
+    async fn invalid_payload_is_dropped_on_error() {
+        let mut transform = transcode(TranscodeConfig {
+            field: "payload".into(),
+            target_field: None,
+            decoding: Some(DeserializerConfig::Json),
+            encoding: None,
+            drop_on_error: true,
+        })
+        .await;
+
+        let mut log = LogEvent::default();
+        log.insert("payload", "not json");
+        assert_eq!(transform_one(transform.as_mut(), log.into()), None);
+
+        assert_eq!(
+            transform_one(transform.as_mut(), LogEvent::default().into()),
+            None
+        );
+    }
+
+    #[test]
+    fn target_field_schema_definition() {
+        let config = TranscodeConfig {
+            field: "payload".into(),
+            target_field: Some("decoded".into()),
+            decoding: None,
+            encoding: Some(JsonSerializerConfig::default().into()),
+            drop_on_error: false,
+        };
+        let merged_definition =
+            schema::Definition::empty_legacy_namespace().unknown_fields(Kind::bytes());
+
+        let definition = config.outputs(&merged_definition, LogNamespace::Legacy)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+        assert_eq!(
+            definition
+                .event_kind()
+                .at_path(&owned_value_path!("decoded")),
+            Kind::bytes().or_undefined()
+        );
+
+        let config = TranscodeConfig {
+            drop_on_error: true,
+            ..config
+        };
+        let definition = config.outputs(&merged_definition, LogNamespace::Legacy)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+        assert_eq!(
+            definition
+                .event_kind()
+                .at_path(&owned_value_path!("decoded")),
+            Kind::bytes()
+        );
+        assert_eq!(
+            definition.event_kind().at_path(&owned_value_path!("other")),
+            Kind::bytes().or_undefined()
+        );
+    }
+}
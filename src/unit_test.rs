@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use clap::Parser;
 use colored::*;
 
-use crate::config::{self, UnitTestResult};
+use crate::config::{self, unit_test::coverage, UnitTestResult};
 use crate::signal;
 
 #[derive(Parser, Debug)]
@@ -39,6 +39,21 @@ pub struct Opts {
         value_delimiter(',')
     )]
     pub config_dirs: Vec<PathBuf>,
+
+    /// Report which transforms, branches of transforms with several outputs,
+    /// such as `route`, and lines of `remap` programs were exercised by the
+    /// tests.
+    #[arg(long)]
+    coverage: bool,
+
+    /// Fail if the percentage of transforms, branches and `remap` program
+    /// lines exercised by the tests is below this value. Implies `--coverage`.
+    #[arg(
+        long,
+        value_name = "PERCENT",
+        value_parser = clap::value_parser!(u8).range(0..=100)
+    )]
+    min_coverage: Option<u8>,
 }
 
 impl Opts {
@@ -57,6 +72,10 @@ impl Opts {
         )
         .collect()
     }
+
+    const fn coverage(&self) -> bool {
+        self.coverage || self.min_coverage.is_some()
+    }
 }
 
 pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exitcode::ExitCode {
@@ -68,6 +87,10 @@ pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exi
         None => return exitcode::CONFIG,
     };
 
+    if opts.coverage() {
+        coverage::enable();
+    }
+
     #[allow(clippy::print_stdout)]
     {
         println!("Running tests");
@@ -104,6 +127,8 @@ pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exi
         }
     }
 
+    let enough_coverage = !opts.coverage() || report_coverage(opts.min_coverage);
+
     if !aggregated_test_errors.is_empty() {
         #[allow(clippy::print_stdout)]
         {
@@ -122,8 +147,38 @@ pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exi
             }
         }
 
+        exitcode::CONFIG
+    } else if !enough_coverage {
         exitcode::CONFIG
     } else {
         exitcode::OK
     }
 }
+
+/// Prints the coverage of the tests, returning whether it reaches the minimum.
+fn report_coverage(min_coverage: Option<u8>) -> bool {
+    let report = coverage::report();
+    #[allow(clippy::print_stdout)]
+    {
+        println!("\ncoverage:\n{}", report);
+    }
+
+    match min_coverage {
+        Some(min_coverage) if report.total().percent() < f64::from(min_coverage) => {
+            #[allow(clippy::print_stdout)]
+            {
+                println!(
+                    "{}",
+                    format!(
+                        "Coverage of {:.1}% is below the minimum of {}%.",
+                        report.total().percent(),
+                        min_coverage
+                    )
+                    .red()
+                );
+            }
+            false
+        }
+        _ => true,
+    }
+}
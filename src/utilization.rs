@@ -9,8 +9,9 @@ use metrics::gauge;
 use pin_project::pin_project;
 use tokio::time::interval;
 use tokio_stream::wrappers::IntervalStream;
+use vector_buffers::topology::channel::QueueDepth;
 
-use crate::stats;
+use crate::{internal_events::ComponentQueuedEvents, stats};
 
 #[pin_project]
 pub(crate) struct Utilization<S> {
@@ -28,6 +29,12 @@ impl<S> Utilization<S> {
     pub(crate) fn into_inner(self) -> S {
         self.inner
     }
+
+    /// Also reports the number of events queued in the input of the component.
+    pub(crate) fn with_queue_depth(mut self, queue_depth: Option<QueueDepth>) -> Self {
+        self.timer = self.timer.with_queue_depth(queue_depth);
+        self
+    }
 }
 
 impl<S> Stream for Utilization<S>
@@ -86,6 +93,7 @@ pub(super) struct Timer {
     waiting: bool,
     total_wait: Duration,
     ewma: stats::Ewma,
+    queue_depth: Option<QueueDepth>,
 }
 
 /// A simple, specialized timer for tracking spans of waiting vs not-waiting
@@ -104,9 +112,16 @@ impl Timer {
             waiting: false,
             total_wait: Duration::new(0, 0),
             ewma: stats::Ewma::new(0.9),
+            queue_depth: None,
         }
     }
 
+    /// Also reports the number of events queued in the input of the component.
+    pub(crate) fn with_queue_depth(mut self, queue_depth: Option<QueueDepth>) -> Self {
+        self.queue_depth = queue_depth;
+        self
+    }
+
     /// Begin a new span representing time spent waiting
     pub(crate) fn start_wait(&mut self) {
         if !self.waiting {
@@ -144,6 +159,12 @@ impl Timer {
         debug!(utilization = %avg);
         gauge!("utilization", avg);
 
+        if let Some(queue_depth) = &self.queue_depth {
+            emit!(ComponentQueuedEvents {
+                count: queue_depth.events(),
+            });
+        }
+
         // Reset overall statistics for the next reporting period.
         self.overall_start = self.span_start;
         self.total_wait = Duration::new(0, 0);
@@ -1,12 +1,23 @@
 #![allow(missing_docs)]
-use std::{collections::HashMap, fmt, fs::remove_dir_all, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::remove_dir_all,
+    io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
 use clap::Parser;
 use colored::*;
 use exitcode::ExitCode;
+use serde::Serialize;
+use tokio::net::{TcpListener, UdpSocket};
 
 use crate::{
-    config::{self, Config, ConfigDiff},
+    config::{self, ComponentKey, Config, ConfigDiff, Protocol, ProxyConfig, Resource},
+    sinks::Healthcheck,
     topology::{self, builder::Pieces},
 };
 
@@ -24,6 +35,20 @@ pub struct Opts {
     #[arg(short, long)]
     pub deny_warnings: bool,
 
+    /// Checks that sources can bind to their addresses, that sources reading from a remote
+    /// service can reach it with their credentials, and that sinks can reach their endpoints, by
+    /// running the health checks of all sinks, even those that are disabled.
+    #[arg(long, conflicts_with = "no_environment")]
+    pub check_connectivity: bool,
+
+    /// Timeout for each connectivity check, in seconds.
+    #[arg(long, default_value = "10")]
+    pub connectivity_timeout_secs: u64,
+
+    /// Writes a JSON report of the connectivity checks to the given file.
+    #[arg(long, requires = "check_connectivity")]
+    pub connectivity_report: Option<PathBuf>,
+
     /// Vector config files in TOML format to validate.
     #[arg(
         id = "config-toml",
@@ -101,15 +126,26 @@ pub async fn validate(opts: &Opts, color: bool) -> ExitCode {
         None => return exitcode::CONFIG,
     };
 
+    let mut report = opts.check_connectivity.then(Vec::new);
+
     if !opts.no_environment {
         if let Some(tmp_directory) = create_tmp_directory(&mut config, &mut fmt) {
-            validated &= validate_environment(opts, &config, &mut fmt).await;
+            if let Some(report) = report.as_mut() {
+                enable_healthchecks(&mut config);
+                validated &= validate_source_bindings(opts, &config, report, &mut fmt).await;
+                validated &= validate_source_healthchecks(opts, &config, report, &mut fmt).await;
+            }
+            validated &= validate_environment(opts, &config, report.as_mut(), &mut fmt).await;
             remove_tmp_directory(tmp_directory);
         } else {
             validated = false;
         }
     }
 
+    if let (Some(checks), Some(path)) = (report, &opts.connectivity_report) {
+        validated &= write_connectivity_report(path, checks, &mut fmt);
+    }
+
     if validated {
         fmt.validated();
         exitcode::OK
@@ -168,7 +204,12 @@ pub fn validate_config(opts: &Opts, fmt: &mut Formatter) -> Option<Config> {
     Some(config)
 }
 
-async fn validate_environment(opts: &Opts, config: &Config, fmt: &mut Formatter) -> bool {
+async fn validate_environment(
+    opts: &Opts,
+    config: &Config,
+    report: Option<&mut Vec<ConnectivityCheck>>,
+    fmt: &mut Formatter,
+) -> bool {
     let diff = ConfigDiff::initial(config);
 
     let mut pieces = if let Some(pieces) = validate_components(config, &diff, fmt).await {
@@ -177,7 +218,7 @@ async fn validate_environment(opts: &Opts, config: &Config, fmt: &mut Formatter)
         return false;
     };
 
-    validate_healthchecks(opts, config, &diff, &mut pieces, fmt).await
+    validate_healthchecks(opts, config, &diff, &mut pieces, report, fmt).await
 }
 
 async fn validate_components(
@@ -185,7 +226,7 @@ async fn validate_components(
     diff: &ConfigDiff,
     fmt: &mut Formatter,
 ) -> Option<Pieces> {
-    match topology::builder::build_pieces(config, diff, HashMap::new()).await {
+    match topology::builder::build_pieces(config, diff, HashMap::new(), Default::default()).await {
         Ok(pieces) => {
             fmt.success("Component configuration");
             Some(pieces)
@@ -203,6 +244,7 @@ async fn validate_healthchecks(
     config: &Config,
     diff: &ConfigDiff,
     pieces: &mut Pieces,
+    mut report: Option<&mut Vec<ConnectivityCheck>>,
     fmt: &mut Formatter,
 ) -> bool {
     if !config.healthchecks.enabled {
@@ -210,18 +252,17 @@ async fn validate_healthchecks(
         return !opts.deny_warnings;
     }
 
+    let timeout = report
+        .is_some()
+        .then(|| Duration::from_secs(opts.connectivity_timeout_secs));
     let healthchecks = topology::take_healthchecks(diff, pieces);
     // We are running health checks in serial so it's easier for the users
     // to parse which errors/warnings/etc. belong to which healthcheck.
     let mut validated = true;
     for (id, healthcheck) in healthchecks {
-        let mut failed = |error| {
-            validated = false;
-            fmt.error(error);
-        };
-
-        match tokio::spawn(healthcheck).await {
-            Ok(Ok(_)) => {
+        let started = Instant::now();
+        let result = match run_healthcheck(healthcheck, timeout).await {
+            Ok(()) => {
                 if config
                     .sink(&id)
                     .expect("Sink not present")
@@ -233,18 +274,251 @@ async fn validate_healthchecks(
                     fmt.warning(format!("Health check disabled for \"{}\"", id));
                     validated &= !opts.deny_warnings;
                 }
+                Ok(())
             }
-            Ok(Err(e)) => failed(format!("Health check for \"{}\" failed: {}", id, e)),
-            Err(error) if error.is_cancelled() => {
-                failed(format!("Health check for \"{}\" was cancelled", id))
+            Err((status, reason)) => {
+                Err((status, format!("Health check for \"{}\" {}", id, reason)))
             }
-            Err(_) => failed(format!("Health check for \"{}\" panicked", id)),
+        };
+        if let Err((_, error)) = &result {
+            validated = false;
+            fmt.error(error);
+        }
+
+        if let Some(report) = report.as_mut() {
+            report.push(ConnectivityCheck::new(
+                &id,
+                "sink",
+                "healthcheck",
+                None,
+                started,
+                result,
+            ));
         }
     }
 
     validated
 }
 
+/// Runs a health check, giving up on it once `timeout` has elapsed if one is given.
+///
+/// On failure, the reason is worded to follow the name of the check.
+async fn run_healthcheck(
+    healthcheck: Healthcheck,
+    timeout: Option<Duration>,
+) -> Result<(), (ConnectivityStatus, String)> {
+    let mut handle = tokio::spawn(healthcheck);
+    let result = match timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, &mut handle).await {
+            Ok(result) => result,
+            Err(_) => {
+                handle.abort();
+                return Err((ConnectivityStatus::TimedOut, "timed out".to_owned()));
+            }
+        },
+        None => handle.await,
+    };
+
+    match result {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(error)) => Err((ConnectivityStatus::Failed, format!("failed: {}", error))),
+        Err(error) if error.is_cancelled() => {
+            Err((ConnectivityStatus::Failed, "was cancelled".to_owned()))
+        }
+        Err(_) => Err((ConnectivityStatus::Failed, "panicked".to_owned())),
+    }
+}
+
+/// Checks that sources can reach the services they read from with their credentials, by running
+/// the health checks of the sources that have one.
+async fn validate_source_healthchecks(
+    opts: &Opts,
+    config: &Config,
+    report: &mut Vec<ConnectivityCheck>,
+    fmt: &mut Formatter,
+) -> bool {
+    let timeout = Duration::from_secs(opts.connectivity_timeout_secs);
+    let mut validated = true;
+    for (id, source) in config.sources() {
+        let proxy = ProxyConfig::merge_with_env(&config.global.proxy, &source.proxy);
+        let healthcheck = match source.inner.healthcheck(&proxy) {
+            Some(healthcheck) => healthcheck,
+            None => continue,
+        };
+
+        let started = Instant::now();
+        let result = match run_healthcheck(healthcheck, Some(timeout)).await {
+            Ok(()) => {
+                fmt.success(format!("Health check \"{}\"", id));
+                Ok(())
+            }
+            Err((status, reason)) => {
+                Err((status, format!("Health check for \"{}\" {}", id, reason)))
+            }
+        };
+        if let Err((_, error)) = &result {
+            validated = false;
+            fmt.error(error);
+        }
+
+        report.push(ConnectivityCheck::new(
+            id,
+            "source",
+            "healthcheck",
+            None,
+            started,
+            result,
+        ));
+    }
+
+    validated
+}
+
+/// Enables the health checks of all sinks, so they are run as connectivity checks.
+fn enable_healthchecks(config: &mut Config) {
+    config.healthchecks.enabled = true;
+    for (_, sink) in config.sinks_mut() {
+        sink.healthcheck.enabled = true;
+    }
+}
+
+/// Checks that the addresses sources listen on are available, by binding to them.
+async fn validate_source_bindings(
+    opts: &Opts,
+    config: &Config,
+    report: &mut Vec<ConnectivityCheck>,
+    fmt: &mut Formatter,
+) -> bool {
+    let timeout = Duration::from_secs(opts.connectivity_timeout_secs);
+    let mut validated = true;
+    for (id, source) in config.sources() {
+        for resource in source.inner.resources() {
+            let (address, protocol) = match resource {
+                Resource::Port(address, protocol) => (address, protocol),
+                _ => continue,
+            };
+
+            let started = Instant::now();
+            let result = match tokio::time::timeout(timeout, bind(address, protocol)).await {
+                Ok(Ok(())) => {
+                    fmt.success(format!("Bind check \"{}\" on {}/{}", id, address, protocol));
+                    Ok(())
+                }
+                Ok(Err(error)) => Err((
+                    ConnectivityStatus::Failed,
+                    format!(
+                        "Bind check for \"{}\" on {}/{} failed: {}",
+                        id, address, protocol, error
+                    ),
+                )),
+                Err(_) => Err((
+                    ConnectivityStatus::TimedOut,
+                    format!(
+                        "Bind check for \"{}\" on {}/{} timed out",
+                        id, address, protocol
+                    ),
+                )),
+            };
+            if let Err((_, error)) = &result {
+                validated = false;
+                fmt.error(error);
+            }
+
+            report.push(ConnectivityCheck::new(
+                id,
+                "source",
+                "bind",
+                Some(format!("{}/{}", address, protocol)),
+                started,
+                result,
+            ));
+        }
+    }
+
+    validated
+}
+
+async fn bind(address: SocketAddr, protocol: Protocol) -> io::Result<()> {
+    match protocol {
+        Protocol::Tcp => TcpListener::bind(address).await.map(drop),
+        Protocol::Udp => UdpSocket::bind(address).await.map(drop),
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ConnectivityStatus {
+    Ok,
+    Failed,
+    TimedOut,
+}
+
+/// The outcome of a single connectivity check, as written to the connectivity report.
+#[derive(Debug, Serialize)]
+struct ConnectivityCheck {
+    component_id: String,
+    component_kind: &'static str,
+    check: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
+    status: ConnectivityStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    duration_ms: u64,
+}
+
+impl ConnectivityCheck {
+    fn new(
+        id: &ComponentKey,
+        component_kind: &'static str,
+        check: &'static str,
+        target: Option<String>,
+        started: Instant,
+        result: Result<(), (ConnectivityStatus, String)>,
+    ) -> Self {
+        let (status, error) = match result {
+            Ok(()) => (ConnectivityStatus::Ok, None),
+            Err((status, error)) => (status, Some(error)),
+        };
+        Self {
+            component_id: id.to_string(),
+            component_kind,
+            check,
+            target,
+            status,
+            error,
+            duration_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ConnectivityReport {
+    checks: Vec<ConnectivityCheck>,
+}
+
+fn write_connectivity_report(
+    path: &Path,
+    checks: Vec<ConnectivityCheck>,
+    fmt: &mut Formatter,
+) -> bool {
+    let report = serde_json::to_vec_pretty(&ConnectivityReport { checks })
+        .expect("connectivity report should serialize");
+    match std::fs::write(path, report) {
+        Ok(()) => {
+            fmt.success(format!("Wrote connectivity report to {:?}", path));
+            true
+        }
+        Err(error) => {
+            fmt.error(format!(
+                "Failed to write connectivity report to {:?}: {}",
+                path, error
+            ));
+            false
+        }
+    }
+}
+
 /// For data directory that we write to:
 /// 1. Create a tmp directory in it.
 /// 2. Change config to point to that tmp directory.
@@ -1,4 +1,9 @@
-use std::{fs::read_dir, process::Command};
+use std::{
+    fs::{read, read_dir},
+    net::TcpListener,
+    path::Path,
+    process::Command,
+};
 
 use assert_cmd::prelude::*;
 
@@ -132,6 +137,123 @@ fn validate_ignore_healthcheck() {
     );
 }
 
+#[test]
+fn validate_connectivity_report_failing_healthcheck() {
+    let dir = create_directory();
+    let report = dir.join("report.json");
+
+    // Connectivity checks run the health checks of sinks even when they are disabled.
+    let code = validate_connectivity(
+        &format!(
+            r#"
+        healthchecks.enabled = false
+        {}
+        "#,
+            FAILING_HEALTHCHECK
+        ),
+        &dir,
+        &report,
+    );
+    assert_eq!(code, exitcode::CONFIG);
+
+    let checks = read_connectivity_report(&report);
+    assert_eq!(checks.len(), 1);
+    assert_eq!(checks[0]["component_id"], "out");
+    assert_eq!(checks[0]["component_kind"], "sink");
+    assert_eq!(checks[0]["check"], "healthcheck");
+    assert_ne!(checks[0]["status"], "ok");
+    assert!(checks[0]["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Health check for \"out\""));
+}
+
+#[test]
+fn validate_connectivity_report() {
+    let dir = create_directory();
+    let report = dir.join("report.json");
+    let address = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let code = validate_connectivity(
+        &source_config(&format!(
+            r#"
+    type = "socket"
+    mode = "tcp"
+    address = "{}""#,
+            address
+        )),
+        &dir,
+        &report,
+    );
+    assert_eq!(code, exitcode::OK);
+
+    let checks = read_connectivity_report(&report);
+    assert_eq!(checks.len(), 2);
+    assert_eq!(checks[0]["component_id"], "in");
+    assert_eq!(checks[0]["check"], "bind");
+    assert_eq!(checks[0]["target"], format!("{}/tcp", address));
+    assert_eq!(checks[0]["status"], "ok");
+    assert_eq!(checks[1]["component_id"], "out");
+    assert_eq!(checks[1]["check"], "healthcheck");
+    assert_eq!(checks[1]["status"], "ok");
+    assert!(checks.iter().all(|check| check.get("error").is_none()));
+}
+
+#[test]
+fn validate_connectivity_report_address_in_use() {
+    let dir = create_directory();
+    let report = dir.join("report.json");
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let code = validate_connectivity(
+        &source_config(&format!(
+            r#"
+    type = "socket"
+    mode = "tcp"
+    address = "{}""#,
+            address
+        )),
+        &dir,
+        &report,
+    );
+    assert_eq!(code, exitcode::CONFIG);
+
+    let checks = read_connectivity_report(&report);
+    assert_eq!(checks[0]["check"], "bind");
+    assert_eq!(checks[0]["status"], "failed");
+    drop(listener);
+}
+
+fn validate_connectivity(config: &str, dir: &Path, report: &Path) -> i32 {
+    let config = create_file(config);
+
+    let mut cmd = Command::cargo_bin("vector").unwrap();
+    cmd.arg("validate")
+        .arg("--check-connectivity")
+        .arg("--connectivity-timeout-secs")
+        .arg("1")
+        .arg("--connectivity-report")
+        .arg(report)
+        .arg(config)
+        .env("VECTOR_DATA_DIR", dir);
+
+    let output = cmd.output().unwrap();
+    println!(
+        "{}",
+        String::from_utf8(output.stdout).expect("Vector output isn't a valid utf8 string")
+    );
+    output.status.code().unwrap()
+}
+
+fn read_connectivity_report(path: &Path) -> Vec<serde_json::Value> {
+    let report: serde_json::Value = serde_json::from_slice(&read(path).unwrap()).unwrap();
+    report["checks"].as_array().unwrap().clone()
+}
+
 fn validate(config: &str) -> i32 {
     let dir = create_directory();
 
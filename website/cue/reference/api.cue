@@ -47,6 +47,59 @@ api: {
 				of the address set using the `bind` parameter.
 				"""
 		}
+		reload_token: {
+			common:   false
+			required: false
+			type: string: {
+				default: null
+				examples: ["${VECTOR_API_RELOAD_TOKEN}"]
+			}
+			description: """
+				The token used to authenticate config reload, drain, and buffer
+				mutation requests. The `/config` endpoint, `POST /drain`, and the
+				buffer mutations of the GraphQL API are only available when this is
+				set, and clients must send the token as a bearer token in the
+				`Authorization` header.
+				"""
+		}
+		profiling: {
+			common:   false
+			required: false
+			type: bool: default: false
+			description: """
+				Whether the profiling endpoints are enabled for the API. CPU profiles
+				require Vector to be built with the `profiling` feature, and heap
+				profiles with the `heap-profiling` feature.
+				"""
+		}
+		readiness: {
+			common:      false
+			required:    false
+			description: "Options deciding when components are reported as ready by the `/health/ready` endpoint."
+			type: object: options: {
+				components: {
+					common:   false
+					required: false
+					type: array: {
+						default: []
+						items: type: string: examples: ["kafka_*"]
+					}
+					description: """
+						Patterns of the IDs of the components gating the overall readiness.
+						All components gate the overall readiness when none are set.
+						"""
+				}
+				max_buffer_usage_percent: {
+					common:   false
+					required: false
+					type: uint: default: 90
+					description: """
+						The usage of the buffer of a sink above which the sink isn't ready.
+						Only applies to buffers limited by a number of events.
+						"""
+				}
+			}
+		}
 	}
 
 	endpoints: {
@@ -54,7 +107,23 @@ api: {
 			POST: {
 				description: """
 					Main endpoint for receiving and processing
-					GraphQL queries.
+					GraphQL queries. Besides introspection, the
+					`purgeBuffer` and `exportBuffer` mutations remove
+					the events held in the buffer of a sink, either
+					dropping them, or writing them as newline delimited
+					native JSON to a new file under the `buffer_exports`
+					subdirectory of the data directory. The removed
+					events are rejected, so sources acknowledging them
+					don't consider them delivered. The
+					`requeueBuffer` mutation adds the events of an
+					exported file back to the buffer of a sink, ahead of
+					the events it holds. The mutations require the
+					`reload_token` as a bearer token in the
+					`Authorization` header. Over WebSockets, the
+					`componentErrors` and `componentEventsDropped`
+					subscriptions stream the errors and dropped events
+					reported by components as they happen, regardless
+					of the log level and of the internal log rate limit.
 					"""
 				responses: {
 					"200": {
@@ -69,6 +138,119 @@ api: {
 				}
 			}
 		}
+		"/config": {
+			POST: {
+				description: """
+					Validates the config in the request body (JSON), and applies it to
+					the running instance. If any of the new components fail to start,
+					Vector rolls back to the previous config. The response contains the
+					component-level diff between the running and the new config. Set the
+					`dry_run=true` query parameter to validate the config and compute the
+					diff without applying it. Only available when `reload_token` is set.
+					"""
+				responses: {
+					"200": {
+						description: "The config is valid. Nothing was applied, as this was a dry run."
+					}
+					"201": {
+						description: "The config was applied."
+					}
+					"401": {
+						description: "The reload token is missing or invalid."
+					}
+					"422": {
+						description: """
+							The config is invalid, or could not be applied and Vector rolled
+							back to the previous config.
+							"""
+					}
+					"503": {
+						description: """
+							The config could not be applied, and the previous config could
+							not be restored. Vector is shutting down.
+							"""
+					}
+				}
+			}
+		}
+		"/debug/pprof/profile": {
+			GET: {
+				description: """
+					Samples the stacks of all threads for the number of seconds set
+					with the `seconds` query parameter, 30 by default and at most
+					300, and responds with a CPU profile in the pprof protobuf format.
+					Only available when `profiling` is enabled.
+					"""
+				responses: {
+					"200": {
+						description: "The CPU profile."
+					}
+					"500": {
+						description: "The profile could not be collected, such as when another one is being collected."
+					}
+					"501": {
+						description: "Vector was built without the `profiling` feature."
+					}
+				}
+			}
+		}
+		"/debug/pprof/heap": {
+			GET: {
+				description: """
+					Responds with a heap profile of the live allocations sampled by
+					jemalloc, in jemalloc's heap profile format, which pprof can read.
+					Only available when `profiling` is enabled.
+					"""
+				responses: {
+					"200": {
+						description: "The heap profile."
+					}
+					"501": {
+						description: "Vector was built without the `heap-profiling` feature."
+					}
+				}
+			}
+		}
+		"/drain": {
+			POST: {
+				description: """
+					Drains Vector and then shuts it down: all sources stop accepting
+					data, and transforms and sinks flush the events already in flight,
+					including those in buffers and partial batches. Components that
+					haven't finished within the graceful shutdown limit are forcefully
+					stopped, which can be overridden with the `timeout_secs` query
+					parameter. Requesting a drain while one is underway has no effect.
+					The response contains the drain progress, as returned by `GET`.
+					Only available when `reload_token` is set. Sending Vector a
+					`SIGUSR2` signal also requests a drain.
+					"""
+				responses: {
+					"202": {
+						description: "The drain has been requested."
+					}
+					"401": {
+						description: "The reload token is missing or invalid."
+					}
+					"503": {
+						description: "The drain could not be requested."
+					}
+				}
+			}
+			GET: {
+				description: """
+					Reports the drain progress: the `state` (`running`, `draining`, or
+					`drained`), the `seconds_remaining` before the remaining components
+					are forcefully stopped, and the `remaining_components` that haven't
+					finished draining. Useful to wait for the drain to complete, for
+					example from a Kubernetes `preStop` hook.
+					"""
+				responses: {
+					"200": {
+						description: "The drain progress."
+					}
+				}
+			}
+		}
 		"/health": {
 			GET: {
 				description: """
@@ -82,6 +264,43 @@ api: {
 				}
 			}
 		}
+		"/health/live": {
+			GET: {
+				description: """
+					Liveness endpoint. Reports whether the task of each component
+					is running. Components whose ID doesn't match the `readiness.components`
+					patterns are reported, but don't affect the response status.
+					"""
+				responses: {
+					"200": {
+						description: "Vector is running, and so are all of the gating components."
+					}
+					"503": {
+						description: "Vector is shutting down, or a gating component stopped running."
+					}
+				}
+			}
+		}
+		"/health/ready": {
+			GET: {
+				description: """
+					Readiness endpoint. Reports whether each component is ready:
+					sources and transforms are ready while running, and sinks while
+					running, not failing their healthcheck, and with a buffer below
+					`readiness.max_buffer_usage_percent`. Components whose ID doesn't
+					match the `readiness.components` patterns are reported, but don't
+					affect the response status.
+					"""
+				responses: {
+					"200": {
+						description: "Vector is running, and all of the gating components are ready."
+					}
+					"503": {
+						description: "Vector is shutting down, or a gating component isn't ready."
+					}
+				}
+			}
+		}
 		"/playground": {
 			GET: {
 				description: """
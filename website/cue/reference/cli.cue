@@ -85,6 +85,10 @@ cli: {
 	name: "vector"
 
 	flags: _default_flags & {
+		"cpu-time-accounting": {
+			description: env_vars.VECTOR_CPU_TIME_ACCOUNTING.description
+			env_var:     "VECTOR_CPU_TIME_ACCOUNTING"
+		}
 		"quiet": {
 			_short: "q"
 			description: """
@@ -169,6 +173,17 @@ cli: {
 			type:        "integer"
 			env_var:     "VECTOR_INTERNAL_LOG_RATE_LIMIT"
 		}
+		"secrets-refresh-interval-secs": {
+			description: env_vars.VECTOR_SECRETS_REFRESH_INTERVAL_SECS.description
+			type:        "integer"
+			env_var:     "VECTOR_SECRETS_REFRESH_INTERVAL_SECS"
+		}
+		"graceful-shutdown-limit-secs": {
+			description: env_vars.VECTOR_GRACEFUL_SHUTDOWN_LIMIT_SECS.description
+			default:     env_vars.VECTOR_GRACEFUL_SHUTDOWN_LIMIT_SECS.type.uint.default
+			type:        "integer"
+			env_var:     "VECTOR_GRACEFUL_SHUTDOWN_LIMIT_SECS"
+		}
 	}
 
 	options: _core_options
@@ -186,6 +201,99 @@ cli: {
 
 			options: _core_options
 		}
+		"schema show": {
+			description: """
+				Show the schema of the events encoded by a sink, inferred from the schema
+				definitions of the sources and transforms feeding into it, and from the
+				`only_fields`, `except_fields`, and `timestamp_format` options of its
+				encoding. The schema definitions are used even when `schema.enabled`
+				isn't set. A warning is logged if the events may have other fields
+				"""
+
+			example: "vector schema show clickhouse_out /etc/vector/vector.toml --format clickhouse"
+
+			options: {
+				"config-dir": {
+					description: env_vars.VECTOR_CONFIG_DIR.description
+					type:        "string"
+					env_var:     "VECTOR_CONFIG_DIR"
+				}
+				"format": {
+					description: "The format of the schema"
+					type:        "enum"
+					default:     "json"
+					enum: {
+						json:       "The inferred fields, with their types, as JSON"
+						clickhouse: "The columns of a ClickHouse table"
+						bigquery:   "A BigQuery table schema, as JSON"
+					}
+				}
+			}
+
+			args: {
+				sink: {
+					description: "The ID of the sink"
+					type:        "string"
+					required:    true
+				}
+				paths: _paths_arg & {
+					description: """
+						Any number of Vector config files. If none are specified the default
+						config path `/etc/vector/vector.toml` will be targeted
+						"""
+				}
+			}
+		}
+		"config convert": {
+			description: """
+				Convert a config file between the TOML, YAML, and JSON formats. Key order
+				is preserved, as are comments when converting from TOML to TOML or YAML.
+				Environment variables aren't interpolated, and options left to their
+				defaults aren't added
+				"""
+
+			example: "vector config convert /etc/vector/vector.toml --to yaml --rewrite-deprecated"
+
+			flags: _default_flags & {
+				"rewrite-deprecated": {
+					description: "Rewrite deprecated options to their replacements"
+				}
+			}
+
+			options: {
+				"from": {
+					description: "The format of the config file to convert. Detected from the file name if not set"
+					type:        "enum"
+					enum: {
+						toml: "TOML format"
+						yaml: "YAML format"
+						json: "JSON format"
+					}
+				}
+				"to": {
+					description: "The format to convert the config file to. Detected from the output file name if not set"
+					type:        "enum"
+					enum: {
+						toml: "TOML format"
+						yaml: "YAML format"
+						json: "JSON format"
+					}
+				}
+				"output": {
+					_short:      "o"
+					description: "The file to write the converted config to, instead of stdout"
+					type:        "string"
+				}
+			}
+
+			args: {
+				input: {
+					description: "The config file to convert"
+					type:        "string"
+				}
+			}
+		}
+
 		"generate": {
 			description: "Generate a Vector configuration containing a list of components"
 
@@ -194,6 +302,14 @@ cli: {
 					_short:      "f"
 					description: "Whether to skip the generation of global fields"
 				}
+				"interactive": {
+					_short: "i"
+					description: """
+						Prompt for the sources, transforms, and sinks of the pipeline and
+						their required options, validated against the schema of each
+						component, instead of taking them from a pipeline expression
+						"""
+				}
 			}
 
 			options: {
@@ -206,7 +322,7 @@ cli: {
 
 			args: {
 				pipeline: {
-					description: "Pipeline expression, e.g. `stdin/remap,filter/console`"
+					description: "Pipeline expression, e.g. `stdin/remap,filter/console`. Not required with `--interactive`."
 					type:        "string"
 				}
 			}
@@ -241,7 +357,24 @@ cli: {
 				out the [unit testing documentation](\(urls.vector_unit_tests)).
 				"""
 
+			flags: {
+				"coverage": {
+					description: """
+						Report which transforms, branches of transforms with several outputs
+						such as `route`, and lines of `remap` programs were exercised by the tests
+						"""
+				}
+			}
+
 			options: {
+				"min-coverage": {
+					description: """
+						Fail if the percentage of transforms, branches, and `remap` program lines
+						exercised by the tests is below this value. Implies `--coverage`
+						"""
+					type:        "integer"
+					example:     "80"
+				}
 				"config-toml": {
 					description: env_vars.VECTOR_CONFIG_TOML.description
 					type:        "string"
@@ -269,6 +402,195 @@ cli: {
 			}
 		}
 
+		"benchmark": {
+			description: """
+				Send events at full speed through encoding codecs, or through the
+				transforms and sinks of a config whose sources are replaced, and report
+				the throughput, latency, and allocations of each codec or component,
+				then exit. Allocations are only reported when Vector runs with
+				`--allocation-tracing`
+				"""
+
+			example: "vector benchmark --codec json --codec logfmt --input events.log"
+
+			flags: {
+				"keep-sinks": {
+					description: """
+						Keep the sinks of the config, instead of discarding the events they
+						receive
+						"""
+				}
+			}
+
+			options: {
+				"codec": {
+					description: """
+						Benchmark an encoding codec instead of a config. Either the name of a
+						codec, such as `json`, or an inline TOML table of its options. Can be
+						repeated to compare codecs
+						"""
+					type:        "string"
+					example:     "{ codec = \"csv\", csv.fields = [\"message\"] }"
+				}
+				"config-dir": {
+					description: env_vars.VECTOR_CONFIG_DIR.description
+					type:        "string"
+					env_var:     "VECTOR_CONFIG_DIR"
+				}
+				"events": {
+					_short: "n"
+					description: """
+						The number of events sent by each source, or to each codec. Recorded
+						events are repeated as needed
+						"""
+					type:    "integer"
+					default: 100000
+				}
+				"input": {
+					_short: "i"
+					description: """
+						File of recorded events, one per line. Lines that are JSON objects are
+						the fields of log events, and other lines their message. Synthetic log
+						events are sent if not set
+						"""
+					type: "string"
+				}
+				"insert-at": {
+					description: """
+						Send the events to these transforms of the config, instead of to its
+						sources, to benchmark a chain of transforms
+						"""
+					type: "list"
+				}
+			}
+
+			args: {
+				paths: _paths_arg & {
+					description: """
+						Any number of Vector config files to benchmark. If none are specified
+						and no codec is, the default config path `/etc/vector/vector.toml`
+						will be targeted
+						"""
+				}
+			}
+		}
+
+		"replay": {
+			description: """
+				Replay the events captured by the `capture` option of sources through a
+				config, then exit. The sources of the config are replaced with ones
+				sending the captured events at the pace they were ingested, or faster,
+				and the transforms and sinks run as configured
+				"""
+
+			example: "vector replay --capture /var/lib/vector/capture/in.jsonl --speed 10 vector.toml"
+
+			flags: {
+				"max-speed": {
+					description: """
+						Replay the events as fast as possible, ignoring the time they were
+						ingested
+						"""
+				}
+			}
+
+			options: {
+				"capture": {
+					description: """
+						Capture files to replay, as written by the `capture` option of
+						sources
+						"""
+					type: "list"
+				}
+				"config-dir": {
+					description: env_vars.VECTOR_CONFIG_DIR.description
+					type:        "string"
+					env_var:     "VECTOR_CONFIG_DIR"
+				}
+				"speed": {
+					description: "Replay the events this many times faster than they were ingested"
+					type:        "integer"
+					default:     1
+				}
+			}
+
+			args: {
+				paths: _paths_arg & {
+					description: """
+						Any number of Vector config files to replay the events through. If
+						none are specified, the default config path `/etc/vector/vector.toml`
+						will be targeted
+						"""
+				}
+			}
+		}
+
+		"reload": {
+			description: """
+				Reload the config of a running Vector instance through its API. The
+				config is validated, and the component-level changes are printed. If
+				any of the new components fail to start, Vector rolls back to the
+				previous config
+				"""
+
+			flags: _default_flags & {
+				"via-api": {
+					description: """
+						Send the config to the reload endpoint of a running Vector's API.
+						Currently required, as this is the only supported way of reloading
+						"""
+				}
+				"dry-run": {
+					description: "Validate the config and show what would change, without applying it"
+				}
+			}
+
+			options: {
+				"url": {
+					_short:      "u"
+					description: "Vector API config reload endpoint"
+					type:        "string"
+					default:     "http://127.0.0.1:8686/config"
+				}
+				"token": {
+					_short:      "t"
+					description: "Token used to authenticate with the reload endpoint, as configured by `api.reload_token`"
+					type:        "string"
+					env_var:     "VECTOR_API_RELOAD_TOKEN"
+				}
+				"config-toml": {
+					description: """
+						Any number of Vector config files to reload with.
+						TOML file format is assumed.
+						"""
+					type: "string"
+				}
+				"config-json": {
+					description: """
+						Any number of Vector config files to reload with.
+						JSON file format is assumed.
+						"""
+					type: "string"
+				}
+				"config-yaml": {
+					description: """
+						Any number of Vector config files to reload with.
+						YAML file format is assumed.
+						"""
+					type: "string"
+				}
+			}
+
+			args: {
+				paths: _paths_arg & {
+					description: """
+						Any number of Vector config files to reload with. If none are specified
+						the default config path `/etc/vector/vector.toml` will be targeted
+						"""
+				}
+			}
+		}
+
 		"tap": {
 			description: """
 				Observe events flowing into components (transforms, sinks) and
@@ -318,8 +640,21 @@ cli: {
 						json:   "Output events as JSON"
 						yaml:   "Output events as YAML"
 						logfmt: "Output events as logfmt"
+						raw:    "Output the message of logs and the text representation of metrics. Traces are output as JSON"
 					}
 				}
+				"filter": {
+					description: "VRL condition events must match to be sampled, evaluated by the Vector instance being observed"
+					type:        "string"
+				}
+				"sample-rate": {
+					description: "Sample one out of every N events matching the filter"
+					type:        "integer"
+				}
+				"max-events-per-second": {
+					description: "Maximum number of events to sample each second, across all observed components"
+					type:        "integer"
+				}
 				"inputs-of": {
 					description: "Components (transforms, sinks) to observe for their inputs (comma-separated; accepts glob patterns)"
 					type:        "list"
@@ -377,6 +712,21 @@ cli: {
 					description: "The URL for the GraphQL endpoint of the running Vector instance"
 					type:        "string"
 				}
+				"history-minutes": {
+					description: "Minutes of per-component event throughputs to keep, and display as sparklines"
+					type:        "integer"
+					default:     5
+				}
+				"export": {
+					description: """
+						Instead of displaying the dashboard, write a snapshot of the metrics of all
+						components to stdout at every interval, in the given format
+						"""
+					type: "enum"
+					enum: {
+						json: "Output snapshots as newline delimited JSON"
+					}
+				}
 			}
 		}
 
@@ -395,9 +745,32 @@ cli: {
 					_short:      "d"
 					description: "Fail validation on warnings"
 				}
+				"check-connectivity": {
+					description: """
+						After validating the configuration, check that sources can bind
+						to their addresses, that sources reading from a remote service,
+						such as `aws_s3`, `aws_sqs`, and `kafka`, can reach it with their
+						credentials, and that sinks can reach their endpoints, by running
+						the health checks of all sinks, even those that are disabled.
+						Can't be used with `--no-environment`
+						"""
+				}
 			}
 
 			options: {
+				"connectivity-timeout-secs": {
+					description: "The timeout for each connectivity check, in seconds"
+					type:        "integer"
+					default:     10
+				}
+				"connectivity-report": {
+					description: """
+						Write a JSON report of the connectivity checks to the given file.
+						Each check lists the component, the kind of check, its status
+						(`ok`, `failed`, or `timed_out`), any error, and its duration
+						"""
+					type: "string"
+				}
 				"config-toml": {
 					description: """
 						Any number of Vector config files to validate.
@@ -474,6 +847,38 @@ cli: {
 				}
 			}
 		}
+
+		"audit verify": {
+			description: """
+				Check that the entries of an audit log, written when the global
+				`audit_log` option is set, weren't modified, reordered, or removed
+				"""
+
+			example: "vector audit verify /var/lib/vector/audit.log"
+
+			options: {
+				"key": {
+					description: "The key the entries of the audit log are hashed with, if `audit_log.key` is set"
+					type:        "string"
+					env_var:     "VECTOR_AUDIT_LOG_KEY"
+				}
+				"anchor": {
+					description: """
+						The hash of an entry seen outside of this host, such as the last one emitted as an
+						internal event when `audit_log.internal_events` is set. The audit log must include
+						this entry, so that removing entries from its end is detected
+						"""
+					type: "string"
+				}
+			}
+
+			args: {
+				path: {
+					description: "The path of the audit log"
+					type:        "string"
+				}
+			}
+		}
 	}
 
 	env_vars: {
@@ -579,6 +984,14 @@ cli: {
 				}
 			}
 		}
+		VECTOR_CPU_TIME_ACCOUNTING: {
+			description: """
+				Account for the CPU time spent running each component in the `component_cpu_time_nanoseconds_total`
+				internal metric. This adds a small overhead to every poll of a component task, and is only
+				available on Unix.
+				"""
+			type: bool: default: false
+		}
 		VECTOR_REQUIRE_HEALTHY: {
 			description: "Exit on startup if any sinks fail healthchecks."
 			type: bool: default: false
@@ -596,6 +1009,28 @@ cli: {
 			description: "Watch for changes in the configuration file and reload accordingly"
 			type: bool: default: false
 		}
+		VECTOR_SECRETS_REFRESH_INTERVAL_SECS: {
+			description: """
+				Periodically re-resolve secrets from the configured secret backends, every given number of seconds.
+				Components using a secret whose value has been rotated are rebuilt, and the components they
+				replace are drained gracefully, so credential rotations don't require a restart. Disabled by default.
+				"""
+			type: uint: {
+				default: null
+				unit:    "seconds"
+			}
+		}
+		VECTOR_GRACEFUL_SHUTDOWN_LIMIT_SECS: {
+			description: """
+				The maximum amount of time to wait for components to drain their events when shutting down,
+				whether on a termination signal or through the API's `/drain` endpoint. Components that
+				haven't finished by then are forcefully stopped.
+				"""
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
 		VECTOR_INTERNAL_LOG_RATE_LIMIT: {
 			description: "Set the internal log rate limit. This limits Vector from emitting identical logs more than once over the given number of seconds."
 			type: uint: {
@@ -702,6 +702,33 @@ components: {
 							examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 						}
 					}
+					reload_interval_secs: {
+						common: false
+						description: """
+							How often the certificate files are checked for changes, in seconds.
+
+							When the files change, new connections are made with the new certificates, while established connections are
+							kept. If not set, the files are only loaded at startup.
+							"""
+						required: false
+						type: uint: {
+							default: null
+							unit:    "seconds"
+						}
+					}
+					spiffe_endpoint_socket: {
+						common: false
+						description: """
+							Path to the Unix socket of the [SPIFFE Workload API](https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api)
+							to get the certificates from, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the
+							Workload API issues new ones.
+							"""
+						required: false
+						type: string: {
+							default: null
+							examples: ["/run/spire/sockets/agent.sock"]
+						}
+					}
 
 					if Args.can_verify_certificate {
 						verify_certificate: {
@@ -807,6 +834,33 @@ components: {
 							examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 						}
 					}
+					reload_interval_secs: {
+						common: false
+						description: """
+							How often the certificate files are checked for changes, in seconds.
+
+							When the files change, new connections are made with the new certificates, while established connections are
+							kept. If not set, the files are only loaded at startup.
+							"""
+						required: false
+						type: uint: {
+							default: null
+							unit:    "seconds"
+						}
+					}
+					spiffe_endpoint_socket: {
+						common: false
+						description: """
+							Path to the Unix socket of the [SPIFFE Workload API](https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api)
+							to get the certificates from, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the
+							Workload API issues new ones.
+							"""
+						required: false
+						type: string: {
+							default: null
+							examples: ["/run/spire/sockets/agent.sock"]
+						}
+					}
 					alpn_protocols: {
 						common: false
 						description: """
@@ -878,6 +932,28 @@ components: {
 					"""
 				required: false
 				type: object: options: {
+					auth: {
+						common:      false
+						description: "Basic authentication to the proxy endpoints, overriding the credentials of the proxy endpoint URIs."
+						required:    false
+						type: object: options: {
+							password: {
+								description: "The basic authentication password."
+								required:    true
+								type: string: examples: ["${PROXY_PASSWORD}", "password"]
+							}
+							strategy: {
+								description: "The authentication strategy to use."
+								required:    true
+								type: string: enum: basic: "Basic authentication, sent in the `Proxy-Authorization` header."
+							}
+							user: {
+								description: "The basic authentication username."
+								required:    true
+								type: string: examples: ["${PROXY_USERNAME}", "username"]
+							}
+						}
+					}
 					enabled: {
 						common:      false
 						description: "Enables proxying support."
@@ -929,6 +1005,26 @@ components: {
 							}
 						}
 					}
+					tls: {
+						common: false
+						description: """
+							TLS configuration of the connections to the proxy endpoints using `https`, such as to trust the
+							certificate of the proxy with a custom CA. Takes the same options as the `tls` option of components.
+							"""
+						required: false
+						type: object: options: {
+							ca_file: {
+								description: "Absolute path to an additional CA certificate file."
+								required:    false
+								type: string: examples: ["/path/to/proxy_ca.crt"]
+							}
+							verify_certificate: {
+								description: "Enables certificate verification."
+								required:    false
+								type: bool: default: true
+							}
+						}
+					}
 				}
 			}
 
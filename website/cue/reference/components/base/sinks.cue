@@ -12,9 +12,46 @@ base: components: sinks: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			encryption: {
+				description: """
+					Encryption at rest for disk buffers.
+
+					Records are encrypted with AES-256-GCM before being written to disk. Records that were written
+					before encryption was enabled can still be read.
+					"""
+				relevant_when: "type = \"disk\" or type = \"hybrid\""
+				required:      false
+				type: object: options: {
+					key: {
+						description: """
+							The key used to encrypt records written to the buffer.
+
+							Any string can be used as a key, as the actual encryption key is derived from it. The key
+							should be provided through a secrets backend rather than being written into the
+							configuration directly.
+							"""
+						required: true
+						type: string: examples: ["SECRET[backend.buffer_key]"]
+					}
+					previous_keys: {
+						description: """
+							Keys that were previously used to encrypt records written to the buffer.
+
+							When rotating the encryption key, the old key should be added here so that any records
+							still in the buffer can be read. Once those records have been processed, the old key can be
+							removed.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["SECRET[backend.previous_buffer_key]"]
+						}
+					}
+				}
+			}
 			max_events: {
 				description:   "The maximum number of events allowed in the buffer."
-				relevant_when: "type = \"memory\""
+				relevant_when: "type = \"memory\" or type = \"hybrid\""
 				required:      false
 				type: uint: default: 500
 			}
@@ -24,7 +61,7 @@ base: components: sinks: configuration: {
 
 					Must be at least ~256 megabytes (268435488 bytes).
 					"""
-				relevant_when: "type = \"disk\""
+				relevant_when: "type = \"disk\" or type = \"hybrid\""
 				required:      true
 				type: uint: unit: "bytes"
 			}
@@ -42,6 +79,18 @@ base: components: sinks: configuration: {
 
 														Data is synchronized to disk every 500ms.
 														"""
+						hybrid: """
+														Events are buffered in memory, spilling over to disk when full.
+
+														Events are buffered in memory until `max_events` is reached, at which point any further
+														events are written to disk instead. As soon as there is free space in memory again, events
+														go back to being buffered in memory, while the events that spilled over to disk are drained
+														alongside them.
+
+														This avoids the latency cost of a disk buffer when the sink keeps up, while still absorbing
+														backpressure durably when it does not. Events held in memory will be lost if Vector is
+														restarted forcefully or crashes.
+														"""
 						memory: """
 														Events are buffered in memory.
 
@@ -76,6 +125,68 @@ base: components: sinks: configuration: {
 			}
 		}
 	}
+	circuit_breaker: {
+		description: """
+			Circuit breaker configuration.
+
+			Sinks that don't send requests to a downstream service fail to build with a circuit breaker.
+			"""
+		required: false
+		type: object: options: {
+			dead_letter_queue: {
+				description: """
+					The sink to send events to while the circuit is open, when `fallback` is set to
+					`dead_letter_queue`.
+
+					Defaults to the global `dead_letter_queue` option.
+					"""
+				required: false
+				type: string: examples: ["dead_letters"]
+			}
+			failure_threshold: {
+				description: """
+					The number of consecutive failed requests after which the circuit is opened.
+
+					Requests that failed with an error that would not be retried, such as a request rejected as
+					invalid by the downstream service, are not counted as failures.
+					"""
+				required: false
+				type: uint: default: 5
+			}
+			fallback: {
+				description: "What to do with the events sent to the sink while the circuit is open."
+				required:    false
+				type: string: {
+					default: "drop"
+					enum: {
+						dead_letter_queue: """
+							Send the events to the dead letter queue sink.
+
+							The events are annotated with the reason they were dropped, in the same way as events sent
+							to the `dropped` output of other components.
+							"""
+						drop: """
+							Drop the events.
+
+							The events are rejected, which is reported to sources that have end-to-end acknowledgements
+							enabled.
+							"""
+					}
+				}
+			}
+			reset_timeout_secs: {
+				description: """
+					The time to wait, once the circuit is opened, before probing the downstream service with a
+					single request.
+					"""
+				required: false
+				type: uint: {
+					default: 30
+					unit:    "seconds"
+				}
+			}
+		}
+	}
 	healthcheck: {
 		description: "Healthcheck configuration."
 		required:    false
@@ -97,6 +208,61 @@ base: components: sinks: configuration: {
 			}
 		}
 	}
+	input_priorities: {
+		description: """
+			The priorities of the inputs of this sink, by input.
+
+			The events of each input with a priority are queued separately from the buffer of the sink,
+			and the events of the inputs with the highest priority are sent to the sink first. Under
+			backpressure, the events of the inputs with a lower priority wait in their queue, and are
+			blocked or dropped when it is full, according to its `when_full` option. The inputs without
+			a priority have a priority of `0`, and go through the buffer of the sink.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "The priority of the input."
+			required:    true
+			type: object: options: {
+				max_events: {
+					description: "The maximum number of events queued for the input."
+					required:    false
+					type: uint: default: 500
+				}
+				priority: {
+					description: """
+						The priority of the input.
+
+						The events of the inputs with a higher priority are sent to the sink first.
+						"""
+					required: true
+					type: uint: {}
+				}
+				when_full: {
+					description: "Event handling behavior when a buffer is full."
+					required:    false
+					type: string: {
+						default: "block"
+						enum: {
+							block: """
+								Wait for free space in the buffer.
+
+								This applies backpressure up the topology, signalling that sources should slow down
+								the acceptance/consumption of events. This means that while no data is lost, data will pile
+								up at the edge.
+								"""
+							drop_newest: """
+								Drops the event instead of waiting for free space in buffer.
+
+								The event will be intentionally dropped. This mode is typically used when performance is the
+								highest priority, and it is preferable to temporarily lose events rather than cause a
+								slowdown in the acceptance/consumption of events.
+								"""
+						}
+					}
+				}
+			}
+		}
+	}
 	inputs: {
 		description: """
 			A list of upstream [source][sources] or [transform][transforms] IDs.
@@ -112,6 +278,88 @@ base: components: sinks: configuration: {
 		required: true
 		type: array: items: type: string: examples: ["my-source-or-transform-id", "prefix-*"]
 	}
+	max_allocated_bytes: {
+		description: """
+			The maximum number of bytes this sink can have allocated.
+
+			When reached, the sink stops accepting events until its allocated memory falls back under
+			the limit, applying backpressure to upstream components. This requires Vector to be run with
+			`--allocation-tracing`, and the allocated memory is only measured at the allocation tracing
+			reporting interval.
+			"""
+		required: false
+		type: uint: unit: "bytes"
+	}
+	max_event_age: {
+		description: """
+			Maximum event age configuration.
+
+			Events older than the maximum age are shed before they reach the sink, instead of being
+			delivered late, such as after an outage of the downstream service.
+			"""
+		required: false
+		type: object: options: {
+			dead_letter_queue: {
+				description: """
+					The sink to send stale events to, when `fallback` is set to `dead_letter_queue`.
+
+					Defaults to the global `dead_letter_queue` option.
+					"""
+				required: false
+				type: string: examples: ["dead_letters"]
+			}
+			fallback: {
+				description: "What to do with the events older than the maximum event age."
+				required:    false
+				type: string: {
+					default: "drop"
+					enum: {
+						dead_letter_queue: """
+							Send the events to the dead letter queue sink.
+
+							The events are annotated with the reason they were dropped, in the same way as events sent
+							to the `dropped` output of other components.
+							"""
+						drop: """
+							Drop the events.
+
+							The events are rejected, which is reported to sources that have end-to-end acknowledgements
+							enabled.
+							"""
+					}
+				}
+			}
+			max_age_secs: {
+				description: """
+					The maximum age of the events sent to the sink.
+
+					The age of an event is measured from its timestamp, which sources set when they ingest it,
+					to the time the sink receives it. Events without a timestamp, and traces, are never shed.
+					"""
+				required: true
+				type: uint: {
+					examples: [3600]
+					unit: "seconds"
+				}
+			}
+		}
+	}
+	partition_key: {
+		description: """
+			A [Vector Remap Language][vrl] (VRL) expression computing the partition key of each event.
+
+			The expression is evaluated once for each log event, and its result can be referred to as
+			`{{ %partition_key }}` by the templated options of the sink, such as key prefixes, topics,
+			indices, or labels. This allows partitioning events in ways the template syntax can't
+			express, such as bucketing them by a hash of their tenant ID. Events for which the
+			expression fails or resolves to `null` have no partition key. Only log events get a partition
+			key, metrics and traces never have one.
+
+			[vrl]: https://vector.dev/docs/reference/vrl
+			"""
+		required: false
+		type: string: examples: ["to_string(mod(seahash(.tenant_id), 8))"]
+	}
 	proxy: {
 		description: """
 			Proxy configuration.
@@ -124,6 +372,38 @@ base: components: sinks: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			auth: {
+				description: """
+					Authentication to the proxy endpoints.
+
+					Overrides the credentials of the proxy endpoint URIs, if any.
+					"""
+				required: false
+				type: object: options: {
+					password: {
+						description: "The basic authentication password."
+						required:    true
+						type: string: examples: ["${PROXY_PASSWORD}", "password"]
+					}
+					strategy: {
+						description: "The authentication strategy to use."
+						required:    true
+						type: string: enum: basic: """
+							Basic authentication.
+
+							The username and password are concatenated and encoded via [base64][base64], and sent in the
+							`Proxy-Authorization` header.
+
+							[base64]: https://en.wikipedia.org/wiki/Base64
+							"""
+					}
+					user: {
+						description: "The basic authentication username."
+						required:    true
+						type: string: examples: ["${PROXY_USERNAME}", "username"]
+					}
+				}
+			}
 			enabled: {
 				description: "Enables proxying support."
 				required:    false
@@ -169,6 +449,187 @@ base: components: sinks: configuration: {
 					items: type: string: examples: ["localhost", ".foo.bar", "*"]
 				}
 			}
+			tls: {
+				description: """
+					TLS configuration of the connections to the proxy endpoints using `https`.
+
+					Only applies to the connections to the proxy itself, such as to trust the certificate of the proxy with a
+					custom CA, while the connections tunneled through the proxy use the TLS configuration of the component.
+					"""
+				required: false
+				type: object: options: {
+					alpn_protocols: {
+						description: """
+							Sets the list of supported ALPN protocols.
+
+							Declare the supported ALPN protocols, which are used during negotiation with peer. Prioritized in the order
+							they are defined.
+							"""
+						required: false
+						type: array: items: type: string: examples: ["h2"]
+					}
+					ca_file: {
+						description: """
+							Absolute path to an additional CA certificate file.
+
+							The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/certificate_authority.crt"]
+					}
+					crt_file: {
+						description: """
+							Absolute path to a certificate file used to identify this server.
+
+							The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+							an inline string in PEM format.
+
+							If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.crt"]
+					}
+					key_file: {
+						description: """
+							Absolute path to a private key file used to identify this server.
+
+							The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.key"]
+					}
+					key_pass: {
+						description: """
+							Passphrase used to unlock the encrypted key file.
+
+							This has no effect unless `key_file` is set.
+							"""
+						required: false
+						type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+					}
+					reload_interval_secs: {
+						description: """
+							How often the certificate files are checked for changes, in seconds.
+
+							When the files change, new connections are made with the new certificates, while established connections are
+							kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+							yet, are retried at the next check.
+
+							If not set, the files are only loaded at startup.
+							"""
+						required: false
+						type: uint: unit: "seconds"
+					}
+					spiffe_endpoint_socket: {
+						description: """
+							Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+							When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+							domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+							API issues new ones, while established connections are kept.
+
+							[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+							"""
+						required: false
+						type: string: examples: ["/run/spire/sockets/agent.sock"]
+					}
+					verify_certificate: {
+						description: """
+							Enables certificate verification.
+
+							If enabled, certificates must be valid in terms of not being expired, as well as being issued by a trusted
+							issuer. This verification operates in a hierarchical manner, checking that not only the leaf certificate (the
+							certificate presented by the client/server) is valid, but also that the issuer of that certificate is valid, and
+							so on until reaching a root certificate.
+
+							Relevant for both incoming and outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+							"""
+						required: false
+						type: bool: {}
+					}
+					verify_hostname: {
+						description: """
+							Enables hostname verification.
+
+							If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+							the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+							Only relevant for outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+							"""
+						required: false
+						type: bool: {}
+					}
+				}
+			}
+		}
+	}
+	rate_limit_group: {
+		description: """
+			The name of the rate limit group, from the global `rate_limit_groups` option, that this
+			sink is part of.
+
+			The requests and bytes sent by this sink then count against the limits shared by all sinks
+			of the group.
+			"""
+		required: false
+		type: string: examples: ["elasticsearch"]
+	}
+	schema_mapping: {
+		description: """
+			Maps the fields of log events to a target schema before they reach the sink.
+
+			This allows a pipeline to feed backends expecting different field names, such as Elasticsearch
+			with the Elastic Common Schema and OpenTelemetry backends with the OpenTelemetry log data
+			model, without transforming the events for each of them.
+			"""
+		required: false
+		type: object: options: {
+			fields: {
+				description: """
+					Fields to rename, in addition to the ones renamed for the target schema.
+
+					Renaming a field that the target schema also renames overrides the mapping of the target
+					schema.
+					"""
+				required: false
+				type: object: {
+					examples: [{
+						user_id:        "user.id"
+						"request.path": "url.path"
+					}]
+					options: "*": {
+						description: "The path the field at this path is moved to."
+						required:    true
+						type: string: {}
+					}
+				}
+			}
+			target: {
+				description: """
+					The schema the fields of the events are mapped to.
+
+					If not set, only the fields in `fields` are renamed.
+					"""
+				required: false
+				type: string: enum: {
+					ecs: """
+						The [Elastic Common Schema][ecs] (ECS).
+
+						[ecs]: https://www.elastic.co/guide/en/ecs/current/index.html
+						"""
+					opentelemetry: """
+						The [OpenTelemetry log data model][otel].
+
+						The fields outside of the data model are moved to `attributes`.
+
+						[otel]: https://opentelemetry.io/docs/specs/otel/logs/data-model/
+						"""
+				}
+			}
 		}
 	}
 }
@@ -1,60 +1,318 @@
 package metadata
 
-base: components: sources: configuration: proxy: {
-	description: """
-		Proxy configuration.
-
-		Configure to proxy traffic through an HTTP(S) proxy when making external requests.
-
-		Similar to common proxy configuration convention, users can set different proxies
-		to use based on the type of traffic being proxied, as well as set specific hosts that
-		should not be proxied.
-		"""
-	required: false
-	type: object: options: {
-		enabled: {
-			description: "Enables proxying support."
-			required:    false
-			type: bool: default: true
-		}
-		http: {
-			description: """
-				Proxy endpoint to use when proxying HTTP traffic.
-
-				Must be a valid URI string.
-				"""
-			required: false
-			type: string: examples: ["http://foo.bar:3128"]
-		}
-		https: {
-			description: """
-				Proxy endpoint to use when proxying HTTPS traffic.
-
-				Must be a valid URI string.
-				"""
-			required: false
-			type: string: examples: ["http://foo.bar:3128"]
+base: components: sources: configuration: {
+	filter: {
+		description: """
+			A condition that the events of this source must match to be forwarded.
+
+			The condition is matched against the events as soon as the source emits them, before they
+			are sent to the components consuming them, so that unwanted events, such as health checks
+			or debug messages, are dropped as early as possible. The events that don't match are
+			dropped, and counted as discarded by the source.
+			"""
+		required: false
+		type: condition: {}
+	}
+	leader_election: {
+		description: """
+			Runs this source on only one instance at a time, elected among the instances sharing the
+			same backend.
+
+			The source is started on another instance when the instance running it stops.
+			"""
+		required: false
+		type: object: options: {
+			endpoint: {
+				description: """
+					The URL of the Redis endpoint to connect to.
+
+					The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
+					`redis` or `rediss` for connections secured via TLS.
+					"""
+				relevant_when: "type = \"redis\""
+				required:      true
+				type: string: examples: ["redis://127.0.0.1:6379/0"]
+			}
+			key_prefix: {
+				description: """
+					The prefix of the Redis key the lock is held at.
+
+					The key is the prefix followed by the ID of the source.
+					"""
+				relevant_when: "type = \"redis\""
+				required:      false
+				type: string: default: "vector:leader:"
+			}
+			kube_config_file: {
+				description: """
+					Optional path to a readable [kubeconfig][kubeconfig] file.
+
+					If not set, a connection to Kubernetes is made using the in-cluster configuration.
+
+					[kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+					"""
+				relevant_when: "type = \"kubernetes_lease\""
+				required:      false
+				type: string: examples: ["/path/to/.kube/config"]
+			}
+			lease_duration_secs: {
+				description: """
+					How long the lock is held without being renewed, in seconds.
+
+					If the leader fails to renew the lock for this long, another instance takes it over.
+					"""
+				required: false
+				type: uint: {
+					default: 15
+					unit:    "seconds"
+				}
+			}
+			lease_name: {
+				description: """
+					The name of the `Lease` object.
+
+					If not set, the name is `vector-` followed by the ID of the source.
+					"""
+				relevant_when: "type = \"kubernetes_lease\""
+				required:      false
+				type: string: examples: ["vector-k8s-events"]
+			}
+			namespace: {
+				description: """
+					The namespace of the `Lease` object.
+
+					If not set, the namespace Vector is running in is used.
+					"""
+				relevant_when: "type = \"kubernetes_lease\""
+				required:      false
+				type: string: examples: ["vector"]
+			}
+			renew_interval_secs: {
+				description: """
+					How often the leader renews the lock, and followers try to acquire it, in seconds.
+
+					Must be less than `lease_duration_secs`.
+					"""
+				required: false
+				type: uint: {
+					default: 5
+					unit:    "seconds"
+				}
+			}
+			type: {
+				description: "The backend holding the lock."
+				required:    true
+				type: string: enum: {
+					kubernetes_lease: "Holds the lock in a Kubernetes `Lease` object."
+					redis:            "Holds the lock in a Redis key."
+				}
+			}
 		}
-		no_proxy: {
-			description: """
-				A list of hosts to avoid proxying.
-
-				Multiple patterns are allowed:
-
-				| Pattern             | Example match                                                               |
-				| ------------------- | --------------------------------------------------------------------------- |
-				| Domain names        | `example.com` matches requests to `example.com`                     |
-				| Wildcard domains    | `.example.com` matches requests to `example.com` and its subdomains |
-				| IP addresses        | `127.0.0.1` matches requests to `127.0.0.1`                         |
-				| [CIDR][cidr] blocks | `192.168.0.0/16` matches requests to any IP addresses in this range     |
-				| Splat               | `*` matches all hosts                                                   |
-
-				[cidr]: https://en.wikipedia.org/wiki/Classless_Inter-Domain_Routing
-				"""
-			required: false
-			type: array: {
-				default: []
-				items: type: string: examples: ["localhost", ".foo.bar", "*"]
+	}
+	proxy: {
+		description: """
+			Proxy configuration.
+
+			Configure to proxy traffic through an HTTP(S) proxy when making external requests.
+
+			Similar to common proxy configuration convention, users can set different proxies
+			to use based on the type of traffic being proxied, as well as set specific hosts that
+			should not be proxied.
+			"""
+		required: false
+		type: object: options: {
+			auth: {
+				description: """
+					Authentication to the proxy endpoints.
+
+					Overrides the credentials of the proxy endpoint URIs, if any.
+					"""
+				required: false
+				type: object: options: {
+					password: {
+						description: "The basic authentication password."
+						required:    true
+						type: string: examples: ["${PROXY_PASSWORD}", "password"]
+					}
+					strategy: {
+						description: "The authentication strategy to use."
+						required:    true
+						type: string: enum: basic: """
+							Basic authentication.
+
+							The username and password are concatenated and encoded via [base64][base64], and sent in the
+							`Proxy-Authorization` header.
+
+							[base64]: https://en.wikipedia.org/wiki/Base64
+							"""
+					}
+					user: {
+						description: "The basic authentication username."
+						required:    true
+						type: string: examples: ["${PROXY_USERNAME}", "username"]
+					}
+				}
+			}
+			enabled: {
+				description: "Enables proxying support."
+				required:    false
+				type: bool: default: true
+			}
+			http: {
+				description: """
+					Proxy endpoint to use when proxying HTTP traffic.
+
+					Must be a valid URI string.
+					"""
+				required: false
+				type: string: examples: ["http://foo.bar:3128"]
+			}
+			https: {
+				description: """
+					Proxy endpoint to use when proxying HTTPS traffic.
+
+					Must be a valid URI string.
+					"""
+				required: false
+				type: string: examples: ["http://foo.bar:3128"]
+			}
+			no_proxy: {
+				description: """
+					A list of hosts to avoid proxying.
+
+					Multiple patterns are allowed:
+
+					| Pattern             | Example match                                                               |
+					| ------------------- | --------------------------------------------------------------------------- |
+					| Domain names        | `example.com` matches requests to `example.com`                     |
+					| Wildcard domains    | `.example.com` matches requests to `example.com` and its subdomains |
+					| IP addresses        | `127.0.0.1` matches requests to `127.0.0.1`                         |
+					| [CIDR][cidr] blocks | `192.168.0.0/16` matches requests to any IP addresses in this range     |
+					| Splat               | `*` matches all hosts                                                   |
+
+					[cidr]: https://en.wikipedia.org/wiki/Classless_Inter-Domain_Routing
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["localhost", ".foo.bar", "*"]
+				}
+			}
+			tls: {
+				description: """
+					TLS configuration of the connections to the proxy endpoints using `https`.
+
+					Only applies to the connections to the proxy itself, such as to trust the certificate of the proxy with a
+					custom CA, while the connections tunneled through the proxy use the TLS configuration of the component.
+					"""
+				required: false
+				type: object: options: {
+					alpn_protocols: {
+						description: """
+							Sets the list of supported ALPN protocols.
+
+							Declare the supported ALPN protocols, which are used during negotiation with peer. Prioritized in the order
+							they are defined.
+							"""
+						required: false
+						type: array: items: type: string: examples: ["h2"]
+					}
+					ca_file: {
+						description: """
+							Absolute path to an additional CA certificate file.
+
+							The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/certificate_authority.crt"]
+					}
+					crt_file: {
+						description: """
+							Absolute path to a certificate file used to identify this server.
+
+							The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+							an inline string in PEM format.
+
+							If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.crt"]
+					}
+					key_file: {
+						description: """
+							Absolute path to a private key file used to identify this server.
+
+							The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.key"]
+					}
+					key_pass: {
+						description: """
+							Passphrase used to unlock the encrypted key file.
+
+							This has no effect unless `key_file` is set.
+							"""
+						required: false
+						type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+					}
+					reload_interval_secs: {
+						description: """
+							How often the certificate files are checked for changes, in seconds.
+
+							When the files change, new connections are made with the new certificates, while established connections are
+							kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+							yet, are retried at the next check.
+
+							If not set, the files are only loaded at startup.
+							"""
+						required: false
+						type: uint: unit: "seconds"
+					}
+					spiffe_endpoint_socket: {
+						description: """
+							Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+							When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+							domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+							API issues new ones, while established connections are kept.
+
+							[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+							"""
+						required: false
+						type: string: examples: ["/run/spire/sockets/agent.sock"]
+					}
+					verify_certificate: {
+						description: """
+							Enables certificate verification.
+
+							If enabled, certificates must be valid in terms of not being expired, as well as being issued by a trusted
+							issuer. This verification operates in a hierarchical manner, checking that not only the leaf certificate (the
+							certificate presented by the client/server) is valid, but also that the issuer of that certificate is valid, and
+							so on until reaching a root certificate.
+
+							Relevant for both incoming and outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+							"""
+						required: false
+						type: bool: {}
+					}
+					verify_hostname: {
+						description: """
+							Enables hostname verification.
+
+							If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+							the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+							Only relevant for outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+							"""
+						required: false
+						type: bool: {}
+					}
+				}
 			}
 		}
 	}
@@ -1,17 +1,41 @@
 package metadata
 
-base: components: transforms: configuration: inputs: {
-	description: """
-		A list of upstream [source][sources] or [transform][transforms] IDs.
+base: components: transforms: configuration: {
+	dead_letter_queue: {
+		description: """
+			The sink to send events dropped by this transform to.
 
-		Wildcards (`*`) are supported.
+			Only applies to transforms that route dropped events to their `dropped` output. Overrides
+			the global `dead_letter_queue` option.
+			"""
+		required: false
+		type: string: {}
+	}
+	inputs: {
+		description: """
+			A list of upstream [source][sources] or [transform][transforms] IDs.
 
-		See [configuration][configuration] for more info.
+			Wildcards (`*`) are supported.
 
-		[sources]: https://vector.dev/docs/reference/configuration/sources/
-		[transforms]: https://vector.dev/docs/reference/configuration/transforms/
-		[configuration]: https://vector.dev/docs/reference/configuration/
-		"""
-	required: true
-	type: array: items: type: string: examples: ["my-source-or-transform-id", "prefix-*"]
+			See [configuration][configuration] for more info.
+
+			[sources]: https://vector.dev/docs/reference/configuration/sources/
+			[transforms]: https://vector.dev/docs/reference/configuration/transforms/
+			[configuration]: https://vector.dev/docs/reference/configuration/
+			"""
+		required: true
+		type: array: items: type: string: examples: ["my-source-or-transform-id", "prefix-*"]
+	}
+	max_allocated_bytes: {
+		description: """
+			The maximum number of bytes this transform can have allocated.
+
+			When reached, the transform stops accepting events until its allocated memory falls back under
+			the limit, applying backpressure to upstream components. This requires Vector to be run with
+			`--allocation-tracing`, and the allocated memory is only measured at the allocation tracing
+			reporting interval.
+			"""
+		required: false
+		type: uint: unit: "bytes"
+	}
 }
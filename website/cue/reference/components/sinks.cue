@@ -203,8 +203,8 @@ components: sinks: [Name=string]: {
 												relevant_when: "codec = `avro`"
 												type: object: options: {
 													schema: {
-														description: "The Avro schema."
-														required:    true
+														description: "The Avro schema. Either `schema` or `schema_registry` must be set."
+														required:    false
 														type: string: {
 															examples: [
 																"""
@@ -340,6 +340,24 @@ components: sinks: [Name=string]: {
 												required:    false
 												type: float: default: 0.7
 											}
+											max_concurrency: {
+												common:      false
+												description: "The maximum concurrency limit. The concurrency limit is never increased above this value. Values above 200 are capped to 200."
+												required:    false
+												type: uint: default: 200
+											}
+											min_concurrency: {
+												common:      false
+												description: "The minimum concurrency limit. The concurrency limit starts at this value, and is never decreased below it, regardless of the response times or back pressure observed. Must not be greater than `max_concurrency`."
+												required:    false
+												type: uint: default: 1
+											}
+											pinned: {
+												common:      false
+												description: "Whether or not to pin the concurrency limit at `max_concurrency`. When pinned, the adaptive concurrency algorithm keeps measuring response times and reporting the adjustments it would have made, through the `adaptive_concurrency_decisions_total` internal metric, but doesn't apply them. This is useful to find out whether its adjustments are responsible for a drop in throughput."
+												required:    false
+												type: bool: default: false
+											}
 											rtt_deviation_scale: {
 												common: false
 												description: """
@@ -659,10 +677,12 @@ components: sinks: [Name=string]: {
 		utilization:                          components.sources.internal_metrics.output.metrics.utilization
 		buffer_byte_size:                     components.sources.internal_metrics.output.metrics.buffer_byte_size
 		buffer_events:                        components.sources.internal_metrics.output.metrics.buffer_events
+		buffer_oldest_event_age_seconds:      components.sources.internal_metrics.output.metrics.buffer_oldest_event_age_seconds
 		buffer_received_events_total:         components.sources.internal_metrics.output.metrics.buffer_received_events_total
 		buffer_received_event_bytes_total:    components.sources.internal_metrics.output.metrics.buffer_received_event_bytes_total
 		buffer_sent_events_total:             components.sources.internal_metrics.output.metrics.buffer_sent_events_total
 		buffer_sent_event_bytes_total:        components.sources.internal_metrics.output.metrics.buffer_sent_event_bytes_total
 		buffer_discarded_events_total:        components.sources.internal_metrics.output.metrics.buffer_discarded_events_total
+		end_to_end_latency_seconds:           components.sources.internal_metrics.output.metrics.end_to_end_latency_seconds
 	}
 }
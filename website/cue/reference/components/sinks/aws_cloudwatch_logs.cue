@@ -78,9 +78,34 @@ components: sinks: aws_cloudwatch_logs: components._aws & {
 	}
 
 	input: {
-		logs:    true
-		metrics: null
-		traces:  false
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    false
+			summary:      false
+			set:          true
+		}
+		traces: false
+	}
+
+	how_it_works: embedded_metrics: {
+		title: "Embedded Metric Format"
+		body:  """
+			When `embedded_metrics` is set, events are sent as CloudWatch
+			[Embedded Metric Format](\(urls.aws_cloudwatch_emf)) documents, and CloudWatch extracts
+			metrics from them, without a separate metrics pipeline. Metric events are accepted in this
+			case, and are turned into documents holding their value, with their tags as dimensions.
+			Aggregated histograms and summaries aren't supported, and are dropped.
+
+			Log events holding any of the fields of `embedded_metrics.metrics` are turned into
+			documents holding these metrics, with the dimensions of `embedded_metrics.dimensions`.
+			Other log events are sent as is.
+
+			The documents must be encoded with the `json` codec, and the `x-amzn-logs-format: json/emf`
+			header is added to the requests.
+			"""
 	}
 
 	permissions: iam: [
@@ -100,6 +100,18 @@ components: sinks: aws_cloudwatch_metrics: components._aws & {
 		traces: false
 	}
 
+	how_it_works: api_limits: {
+		title: "API limits"
+		body:  """
+			CloudWatch accepts up to 30 dimensions per metric. The tags of metrics are sent as their
+			dimensions, ordered by name, and the dimensions past this limit are dropped. Set
+			`dimensions` to choose which tags are sent, and in which order.
+
+			Requests are split so that each of them holds at most 1000 metrics, and distributions with
+			more than 150 distinct values are split into several metrics, which CloudWatch aggregates.
+			"""
+	}
+
 	permissions: iam: [
 		{
 			platform:  "aws"
@@ -47,25 +47,293 @@ base: components: sinks: amqp: configuration: {
 		description: "Configures how events are encoded into raw bytes."
 		required:    true
 		type: object: options: {
+			arrow_stream: {
+				description:   "Apache Arrow IPC stream-specific encoder options."
+				relevant_when: "codec = \"arrow_stream\""
+				required:      true
+				type: object: options: fields: {
+					description: """
+						The fields to encode, which make up the columns of the schema of the streams, in order.
+
+						Fields missing from an event, or holding a null value, are encoded as null values. Events
+						with fields holding values that don't match the type of their column fail to encode.
+						"""
+					required: true
+					type: array: items: type: object: options: {
+						name: {
+							description: "The path of the field, which is also the name of its column."
+							required:    true
+							type: string: examples: ["message", "status"]
+						}
+						type: {
+							description: "The type of the column."
+							required:    true
+							type: string: enum: {
+								boolean: "A boolean."
+								float: """
+									A 64-bit floating point number.
+
+									Integer values are converted to floating point numbers.
+									"""
+								integer: "A 64-bit signed integer."
+								string: """
+									A UTF-8 string.
+
+									Values other than strings are encoded as their string representation, with arrays and
+									objects encoded as JSON.
+									"""
+								timestamp: "A UTC timestamp, with microsecond precision."
+							}
+						}
+					}
+				}
+			}
 			avro: {
 				description:   "Apache Avro-specific encoder options."
 				relevant_when: "codec = \"avro\""
 				required:      true
-				type: object: options: schema: {
-					description: "The Avro schema."
-					required:    true
-					type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+				type: object: options: {
+					schema: {
+						description: """
+							The Avro schema.
+
+							Either `schema` or `schema_registry` must be set.
+							"""
+						required: false
+						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					}
+					schema_registry: {
+						description: """
+							Confluent-compatible schema registry options.
+
+							The schema is fetched from the registry when the sink starts, and each event is prefixed with
+							the [wire format][wire_format] header referencing it: a zero magic byte followed by the ID of
+							the schema, as a 32-bit unsigned integer, big endian.
+
+							The schema registry is only supported by the `kafka` sink.
+
+							[wire_format]: https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format
+							"""
+						required: false
+						type: object: options: {
+							auth: {
+								description: "Basic authentication to the schema registry."
+								required:    false
+								type: object: options: {
+									password: {
+										description: "The basic authentication password."
+										required:    true
+										type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+									}
+									user: {
+										description: "The basic authentication username."
+										required:    true
+										type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+									}
+								}
+							}
+							subject: {
+								description: """
+									The subject the schema is registered under.
+
+									With the default subject name strategy of Kafka clients, the subject of the schema of the
+									values of a topic is the name of the topic suffixed with `-value`.
+									"""
+								required: true
+								type: string: examples: ["logs-value"]
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							version: {
+								description: """
+									The version of the schema.
+
+									Defaults to the latest version registered under the subject.
+									"""
+								required: false
+								type: uint: examples: [3]
+							}
+						}
+					}
+				}
+			}
+			cbor: {
+				description:   "CBOR-specific encoder options."
+				relevant_when: "codec = \"cbor\""
+				required:      false
+				type: object: options: {
+					packed: {
+						description: """
+							Encode maps and arrays with their definite length.
+
+							By default, maps and arrays are encoded with an indefinite length, terminated by a break
+							marker. Definite lengths make for smaller items, which are simpler to decode on constrained
+							devices.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					sequence: {
+						description: """
+							Encode batches of events as a [CBOR sequence][cbor_seq].
+
+							The items of a sequence are concatenated without any framing, so events are framed with
+							the `bytes` framing by default, rather than with the `length_delimited` one.
+
+							[cbor_seq]: https://www.rfc-editor.org/rfc/rfc8742
+							"""
+						required: false
+						type: bool: default: false
+					}
+				}
+			}
+			cef: {
+				description:   "CEF-specific encoder options."
+				relevant_when: "codec = \"cef\""
+				required:      true
+				type: object: options: {
+					default_severity: {
+						description: """
+							The CEF severity of events whose severity field is missing, or holds a value that isn't
+							mapped by `severity_mapping`.
+							"""
+						required: false
+						type: uint: default: 5
+					}
+					device_event_class_id: {
+						description: "The identifier of the type of the events."
+						required:    true
+						type: string: examples: ["100", "login"]
+					}
+					device_product: {
+						description: "The product that produced the events."
+						required:    true
+						type: string: examples: ["Firewall"]
+					}
+					device_vendor: {
+						description: "The vendor of the device that produced the events."
+						required:    true
+						type: string: examples: ["Acme"]
+					}
+					device_version: {
+						description: "The version of the product that produced the events."
+						required:    true
+						type: string: examples: ["1.0"]
+					}
+					extensions: {
+						description: """
+							A mapping of CEF extension keys to the fields holding their values.
+
+							Extensions whose field is missing from an event, or holds a null value, are left out.
+							Timestamps are encoded as milliseconds since the Unix epoch.
+							"""
+						required: false
+						type: object: {
+							examples: [{
+								rt:    "timestamp"
+								src:   "client.ip"
+								suser: "user.name"
+							}]
+							options: "*": {
+								description: "The path of the field holding the value of the extension."
+								required:    true
+								type: string: {}
+							}
+						}
+					}
+					name_key: {
+						description: "The field holding the human-readable description of events, used as their name."
+						required:    false
+						type: string: {
+							default: "message"
+							examples: ["message", "event.name"]
+						}
+					}
+					severity_key: {
+						description: """
+							The field holding the severity of events.
+
+							Integers from 0 to 10 are used as the severity as is, and other values are mapped with
+							`severity_mapping`.
+							"""
+						required: false
+						type: string: {
+							default: "severity"
+							examples: ["severity", "level"]
+						}
+					}
+					severity_mapping: {
+						description: "A mapping of the values of the severity field to CEF severities, from 0 to 10."
+						required:    false
+						type: object: {
+							examples: [{
+								error:   8
+								info:    3
+								warning: 6
+							}]
+							options: "*": {
+								description: "A CEF severity, from 0 to 10."
+								required:    true
+								type: uint: {}
+							}
+						}
+					}
 				}
 			}
 			codec: {
 				description: "The codec to use for encoding events."
 				required:    true
 				type: string: enum: {
+					arrow_stream: """
+						Encodes a batch of events as an [Apache Arrow IPC stream][arrow_ipc_stream].
+
+						Arrow is a columnar format, so events are encoded a batch at a time, with each batch
+						written as a complete stream holding a single record batch. This codec is meant for sinks
+						writing batches of events to files or objects, such as `aws_s3`, `gcp_cloud_storage`, and
+						`file`, for Arrow-native consumers to read. Other sinks write each event as its own stream.
+
+						The `file` sink holds the events of each file until the file is closed, and can't append to
+						existing files, so its `path` should be unique to each file, such as by including a
+						timestamp.
+
+						[arrow_ipc_stream]: https://arrow.apache.org/docs/format/Columnar.html#ipc-streaming-format
+						"""
 					avro: """
 						Encodes an event as an [Apache Avro][apache_avro] message.
 
 						[apache_avro]: https://avro.apache.org/
 						"""
+					cbor: """
+						Encodes an event as a [CBOR][cbor] map.
+
+						Strings that aren't valid UTF-8 are encoded as byte strings, and timestamps as epoch-based
+						date/time values.
+
+						[cbor]: https://cbor.io/
+						"""
+					cef: """
+						Encodes an event in the [ArcSight Common Event Format][cef] (CEF).
+
+						The header of each event holds the configured device fields, and the name and severity of
+						the event. Its extensions hold the values of the configured fields.
+
+						[cef]: https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors-8.3/cef-implementation-standard/
+						"""
+					csv: """
+						Encodes an event as a [CSV][csv] row.
+
+						[csv]: https://datatracker.ietf.org/doc/html/rfc4180
+						"""
+					fixed_width: """
+						Encodes an event as a fixed-width record, in which each value is padded to the width of
+						its column.
+
+						This is usually used for integrating with mainframes and legacy batch files, which can't
+						take delimited data.
+						"""
 					gelf: """
 						Encodes an event as a [GELF][gelf] message.
 
@@ -76,11 +344,27 @@ base: components: sinks: amqp: configuration: {
 
 						[json]: https://www.json.org/
 						"""
+					leef: """
+						Encodes an event in the [Log Event Extended Format][leef] (LEEF) 2.0 of IBM QRadar.
+
+						The header of each event holds the configured product fields and event ID, and the
+						delimiter of its attributes. Its attributes hold the values of the configured fields.
+
+						[leef]: https://www.ibm.com/docs/en/dsm?topic=leef-overview
+						"""
 					logfmt: """
 						Encodes an event as a [logfmt][logfmt] message.
 
 						[logfmt]: https://brandur.org/logfmt
 						"""
+					msgpack: """
+						Encodes an event as a [MessagePack][msgpack] map.
+
+						Strings that aren't valid UTF-8 are encoded as binary values, and timestamps with the
+						MessagePack timestamp extension type.
+
+						[msgpack]: https://msgpack.org/
+						"""
 					native: """
 						Encodes an event in Vector’s [native Protocol Buffers format][vector_native_protobuf].
 
@@ -97,6 +381,36 @@ base: components: sinks: amqp: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					parquet: """
+						Encodes a batch of events as an [Apache Parquet][apache_parquet] file.
+
+						Parquet is a columnar file format, so events are encoded a batch at a time, with each batch
+						written as a complete file. This codec is meant for sinks writing files or objects, such as
+						`aws_s3`, `gcp_cloud_storage`, and `file`.
+
+						The `file` sink holds the events of each file until the file is closed, and can't append to
+						existing files, so its `path` should be unique to each file, such as by including a
+						timestamp.
+
+						[apache_parquet]: https://parquet.apache.org/
+						"""
+					pretty: """
+						Encodes an event as a human-readable text, for interactive debugging.
+
+						A log event is rendered as a line holding its timestamp, level, and message, followed by a
+						line for each of its other fields, with their keys aligned. A metric is rendered the same
+						way as with the `text` codec.
+
+						This codec is meant for the `console` sink, which colors the output when writing to a
+						terminal.
+						"""
+					protobuf: """
+						Encodes an event as a [Protocol Buffers][protobuf] message of a user-provided type.
+
+						The fields of the event are mapped onto the fields of the message with the same name.
+
+						[protobuf]: https://protobuf.dev/
+						"""
 					raw_message: """
 						No encoding.
 
@@ -106,6 +420,14 @@ base: components: sinks: amqp: configuration: {
 						transform, etc) and removing the message field while doing additional parsing on it, as this
 						could lead to the encoding emitting empty strings for the given event.
 						"""
+					syslog: """
+						Encodes an event as a [syslog][syslog] message, in the format of RFC 5424 or RFC 3164.
+
+						The facility and severity of the message are read from fields of the event, and its host,
+						timestamp, and message from the fields of the log schema.
+
+						[syslog]: https://www.rfc-editor.org/rfc/rfc5424
+						"""
 					text: """
 						Plain text encoding.
 
@@ -116,6 +438,139 @@ base: components: sinks: amqp: configuration: {
 						transform, etc) and removing the message field while doing additional parsing on it, as this
 						could lead to the encoding emitting empty strings for the given event.
 						"""
+					xml: """
+						Encodes an event as an [XML][xml] document.
+
+						The fields of the event are written as the child elements of its record element, or as its
+						attributes.
+
+						[xml]: https://www.w3.org/TR/xml/
+						"""
+				}
+			}
+			csv: {
+				description:   "CSV-specific encoder options."
+				relevant_when: "codec = \"csv\""
+				required:      true
+				type: object: options: {
+					delimiter: {
+						description: "The ASCII (7-bit) character written between the fields of a record."
+						required:    false
+						type: uint: default: 44
+					}
+					fields: {
+						description: """
+							The fields to encode, in the order they appear in the output.
+
+							Fields missing from the event are encoded as `missing_value`, fields holding null values are
+							encoded as `null_value`, and fields holding arrays, objects, or regular expressions are
+							encoded according to `nested_value_handling`.
+
+							If empty, the fields are discovered from the first event of each batch: the top-level fields
+							of that event, ordered by name, are encoded for the events of the batch. This is useful for
+							ad-hoc exports of events whose fields aren't known ahead of time.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["timestamp", "message"]
+						}
+					}
+					headers: {
+						description: """
+							Emits a header row with the names of the fields before the events.
+
+							The header row is written once at the start of each batch of events, so sinks writing each
+							batch as an object, such as `aws_s3`, write it once per object, while the `file` sink writes
+							it once at the start of each file.
+
+							When the fields are discovered, the header row is written along with the first event of the
+							batch, once its fields are known.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					missing_value: {
+						description: """
+							The placeholder written for fields missing from the event.
+
+							Defaults to an empty string, which can't be told apart from an empty string value.
+							"""
+						required: false
+						type: string: {
+							default: ""
+							examples: ["\\N", "NULL"]
+						}
+					}
+					nested_value_handling: {
+						description: "How the CSV serializer encodes fields holding arrays, objects, or regular expressions."
+						required:    false
+						type: object: options: {
+							delimiter: {
+								description:   "The delimiter written between the nested values."
+								relevant_when: "strategy = \"flatten_join\""
+								required:      true
+								type: string: examples: ["|", ";"]
+							}
+							strategy: {
+								description: "The strategy for encoding arrays, objects, and regular expressions."
+								required:    true
+								type: string: enum: {
+									empty: "Encodes the field as an empty string."
+									flatten_join: """
+										Encodes the values nested in the field, joined by a delimiter.
+
+										Nested arrays and objects are flattened, and the values of objects are ordered by key.
+										"""
+									json: "Encodes the field as JSON."
+								}
+							}
+						}
+					}
+					null_value: {
+						description: """
+							The placeholder written for fields holding a null value.
+
+							Defaults to an empty string, which can't be told apart from an empty string value. A
+							placeholder such as `\\N`, expected by MySQL's `LOAD DATA`, lets consumers distinguish them.
+							"""
+						required: false
+						type: string: {
+							default: ""
+							examples: ["\\N", "NULL"]
+						}
+					}
+					quote: {
+						description: """
+							The ASCII (7-bit) character that quotes fields holding delimiters, quotes, or line breaks.
+
+							Quotes inside quoted fields are escaped by doubling them.
+							"""
+						required: false
+						type: uint: default: 34
+					}
+					terminator: {
+						description: "The terminator written after each record."
+						required:    false
+						type: string: {
+							default: "none"
+							enum: {
+								crlf: """
+									Records are terminated by a carriage return followed by a line feed (`\\r\\n`), as expected by
+									Windows consumers.
+
+									This is the terminator defined by [RFC 4180][rfc4180].
+
+									[rfc4180]: https://www.rfc-editor.org/rfc/rfc4180
+									"""
+								lf: "Records are terminated by a line feed (`\\n`)."
+								none: """
+									Records aren't terminated, leaving their separation to the framing, which defaults to
+									`newline_delimited`.
+									"""
+							}
+						}
+					}
 				}
 			}
 			except_fields: {
@@ -123,6 +578,202 @@ base: components: sinks: amqp: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			fixed_width: {
+				description:   "Fixed-width-specific encoder options."
+				relevant_when: "codec = \"fixed_width\""
+				required:      true
+				type: object: options: columns: {
+					description: "The columns of the records, in order."
+					required:    true
+					type: array: items: type: object: options: {
+						alignment: {
+							description: "The alignment of the values in their column."
+							required:    false
+							type: string: {
+								default: "left"
+								enum: {
+									left: "Align the values to the left, padding them on the right."
+									right: """
+										Align the values to the right, padding them on the left.
+
+										This is usually used for numbers, such as with a padding of `0`.
+										"""
+								}
+							}
+						}
+						field: {
+							description: """
+								The field holding the value of the column.
+
+								If the field is missing from an event, or holds a null value, the column only holds padding.
+								"""
+							required: true
+							type: string: examples: ["account.id", "amount"]
+						}
+						padding: {
+							description: "The ASCII (7-bit) character padding the values narrower than the column."
+							required:    false
+							type: uint: default: 32
+						}
+						truncation: {
+							description: "What to do with the values wider than their column."
+							required:    false
+							type: string: {
+								default: "end"
+								enum: {
+									end:   "Keep the start of the values, dropping the characters past the width of the column."
+									error: "Drop the events, with an error."
+									start: "Keep the end of the values, dropping their first characters."
+								}
+							}
+						}
+						width: {
+							description: "The width of the column, in characters."
+							required:    true
+							type: uint: examples: [10]
+						}
+					}
+				}
+			}
+			gelf: {
+				description:   "GELF-specific encoder options."
+				relevant_when: "codec = \"gelf\""
+				required:      false
+				type: object: options: compression: {
+					description: """
+						The compression of the messages.
+
+						Graylog only supports compressed messages over UDP, so compression should only be used by
+						the `socket` sink in `udp` mode.
+						"""
+					required: false
+					type: string: {
+						default: "none"
+						enum: {
+							gzip: """
+								[Gzip][gzip] compression.
+
+								[gzip]: https://www.gzip.org/
+								"""
+							none: "No compression."
+							zlib: """
+								[Zlib][zlib] compression.
+
+								[zlib]: https://zlib.net/
+								"""
+						}
+					}
+				}
+			}
+			json: {
+				description:   "Options for the JSON serializer."
+				relevant_when: "codec = \"json\""
+				required:      false
+				type: object: options: {
+					max_bytes: {
+						description: """
+							The maximum size of encoded events.
+
+							Larger events are handled according to `oversize_action`.
+							"""
+						required: false
+						type: uint: {
+							examples: [1048576]
+							unit: "bytes"
+						}
+					}
+					oversize_action: {
+						description: "What to do with the events encoded to more than `max_bytes`."
+						required:    false
+						type: string: {
+							default: "drop"
+							enum: {
+								drop: "Drop the events, with an error."
+								truncate: """
+									Truncate the longest string values of the events until they fit.
+
+									The events are dropped, with an error, if they don't fit once all their string values are
+									empty.
+									"""
+							}
+						}
+					}
+					pretty: {
+						description: "Whether to pretty-print the JSON, with indentation and line breaks."
+						required:    false
+						type: bool: default: false
+					}
+					sort_keys: {
+						description: """
+							Whether to sort the keys of objects.
+
+							The fields of logs and traces are always sorted, so this mostly makes the encoding of
+							metrics deterministic.
+							"""
+						required: false
+						type: bool: default: false
+					}
+				}
+			}
+			leef: {
+				description:   "LEEF-specific encoder options."
+				relevant_when: "codec = \"leef\""
+				required:      true
+				type: object: options: {
+					attributes: {
+						description: """
+							A mapping of LEEF attribute keys to the fields holding their values.
+
+							Attributes whose field is missing from an event, or holds a null value, are left out.
+							Timestamps are encoded as milliseconds since the Unix epoch, which is the default format of
+							the `devTime` attribute.
+							"""
+						required: false
+						type: object: {
+							examples: [{
+								devTime: "timestamp"
+								src:     "client.ip"
+								usrName: "user.name"
+							}]
+							options: "*": {
+								description: "The path of the field holding the value of the attribute."
+								required:    true
+								type: string: {}
+							}
+						}
+					}
+					delimiter: {
+						description: """
+							The ASCII (7-bit) character that delimits the attributes.
+
+							The delimiter can't be `=` or `|`. Occurrences of the delimiter in the values of attributes
+							are replaced by spaces, as LEEF has no way to escape them.
+							"""
+						required: false
+						type: uint: default: 9
+					}
+					event_id: {
+						description: "The identifier of the type of the events."
+						required:    true
+						type: string: examples: ["100", "login"]
+					}
+					product: {
+						description: "The product that produced the events."
+						required:    true
+						type: string: examples: ["Firewall"]
+					}
+					product_version: {
+						description: "The version of the product that produced the events."
+						required:    true
+						type: string: examples: ["1.0"]
+					}
+					vendor: {
+						description: "The vendor of the product that produced the events."
+						required:    true
+						type: string: examples: ["Acme"]
+					}
+				}
+			}
 			metric_tag_values: {
 				description: """
 					Controls how metric tag values are encoded.
@@ -144,11 +795,409 @@ base: components: sinks: amqp: configuration: {
 					}
 				}
 			}
+			native: {
+				description:   "Native-specific encoder options."
+				relevant_when: "codec = \"native\""
+				required:      false
+				type: object: options: version: {
+					description: """
+						The version of the native format to encode events with.
+
+						During rolling upgrades, set this to the version supported by the oldest Vector instances
+						decoding the events.
+						"""
+					required: false
+					type: string: {
+						default: "v2"
+						enum: {
+							v1: """
+								The format of Vector versions before v0.26.
+
+								Metric tags only have a single value, so tags with multiple values are encoded with their
+								last assigned value, and null values are left out.
+								"""
+							v2: """
+								The format of Vector versions since v0.26.
+
+								Metric tags can have multiple values.
+								"""
+						}
+					}
+				}
+			}
 			only_fields: {
-				description: "List of fields that will be included in the encoded event."
-				required:    false
+				description: """
+					List of fields that will be included in the encoded event.
+
+					Fields prefixed with `%metadata.` are read from the metadata of the event rather than from
+					the event, and are included in the encoded event under `metadata`. This gives access to the
+					source metadata of events from sources using the `vector` log namespace, such as
+					`%metadata.kafka.offset` or `%metadata.vector.ingest_timestamp`.
+					"""
+				required: false
 				type: array: items: type: string: {}
 			}
+			parquet: {
+				description:   "Apache Parquet-specific encoder options."
+				relevant_when: "codec = \"parquet\""
+				required:      true
+				type: object: options: {
+					compression: {
+						description: "The compression codec of the column chunks of the Parquet files."
+						required:    false
+						type: string: {
+							default: "snappy"
+							enum: {
+								gzip: """
+									[Gzip][gzip] compression.
+
+									[gzip]: https://www.gzip.org/
+									"""
+								none: "No compression."
+								snappy: """
+									[Snappy][snappy] compression.
+
+									[snappy]: https://github.com/google/snappy
+									"""
+								zstd: """
+									[Zstandard][zstd] compression.
+
+									[zstd]: https://facebook.github.io/zstd/
+									"""
+							}
+						}
+					}
+					fields: {
+						description: """
+							The fields to encode, which make up the columns of the schema of the files, in order.
+
+							Fields missing from an event, or holding a null value, are encoded as null values. Events
+							with fields holding values that don't match the type of their column fail to encode.
+							"""
+						required: true
+						type: array: items: type: object: options: {
+							name: {
+								description: "The path of the field, which is also the name of its column."
+								required:    true
+								type: string: examples: ["message", "status"]
+							}
+							type: {
+								description: "The type of the column."
+								required:    true
+								type: string: enum: {
+									boolean: "A boolean."
+									float: """
+										A 64-bit floating point number.
+
+										Integer values are converted to floating point numbers.
+										"""
+									integer: "A 64-bit signed integer."
+									string: """
+										A UTF-8 string.
+
+										Values other than strings are encoded as their string representation, with arrays and
+										objects encoded as JSON.
+										"""
+									timestamp: "A timestamp, with microsecond precision."
+								}
+							}
+						}
+					}
+					row_group_size: {
+						description: """
+							The maximum number of events in each row group of the files.
+
+							Larger row groups compress better and are read more efficiently, at the cost of the memory
+							needed to write and read them.
+							"""
+						required: false
+						type: uint: {
+							default: 10000
+							unit:    "events"
+						}
+					}
+				}
+			}
+			pretty: {
+				description:   "Pretty-specific encoder options."
+				relevant_when: "codec = \"pretty\""
+				required:      false
+				type: object: options: {
+					colors: {
+						description: "Whether to color the output."
+						required:    false
+						type: string: {
+							default: "auto"
+							enum: {
+								always: "Always color the output."
+								auto: """
+									Color the output when it's written to a terminal.
+
+									Only the `console` sink writes to a terminal, so the output of other sinks is never colored.
+									"""
+								never: "Never color the output."
+							}
+						}
+					}
+					level_key: {
+						description: """
+							The field holding the severity level of log events.
+
+							The level is rendered after the timestamp of the event, colored by its severity.
+							"""
+						required: false
+						type: string: {
+							default: "level"
+							examples: ["level", "severity"]
+						}
+					}
+					max_fields: {
+						description: """
+							The maximum number of fields rendered for an event, besides its timestamp, level, and
+							message.
+
+							The number of the fields left out is rendered instead of them.
+							"""
+						required: false
+						type: uint: {}
+					}
+					max_value_length: {
+						description: """
+							The maximum number of characters rendered for a value.
+
+							Longer values are truncated, and end with an ellipsis.
+							"""
+						required: false
+						type: uint: {}
+					}
+				}
+			}
+			protobuf: {
+				description:   "Protobuf-specific encoder options."
+				relevant_when: "codec = \"protobuf\""
+				required:      true
+				type: object: options: {
+					desc_file: {
+						description: """
+							The path to the Protobuf descriptor set file.
+
+							This file is the output of `protoc --include_imports --descriptor_set_out <desc_file>`, and
+							must include the message type and the types it depends on.
+							"""
+						required: true
+						type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
+					}
+					message_type: {
+						description: "The fully qualified name of the message type to encode events as."
+						required:    true
+						type: string: examples: ["package.Message"]
+					}
+				}
+			}
+			redact: {
+				description: """
+					Fields whose values are masked or hashed in the encoded event.
+
+					Redaction is applied last, when the event is encoded, so the values of these fields never
+					reach the sink, regardless of the transforms the events went through.
+					"""
+				required: false
+				type: object: options: {
+					fields: {
+						description: """
+							The paths of the redacted fields.
+
+							Fields missing from an event are ignored.
+							"""
+						required: true
+						type: array: items: type: string: examples: ["user.email", "password"]
+					}
+					mask: {
+						description: "The value replacing the values of the fields when they are masked."
+						required:    false
+						type: string: {
+							default: "[REDACTED]"
+							examples: ["***"]
+						}
+					}
+					method: {
+						description: "How the values of the fields are redacted."
+						required:    false
+						type: string: {
+							default: "mask"
+							enum: {
+								hash: """
+									The values are replaced by the hex-encoded SHA-256 hash of their contents.
+
+									Equal values have equal hashes, so events can still be correlated on the fields.
+									Objects and arrays are hashed as JSON.
+									"""
+								mask: "The values are replaced by `mask`."
+							}
+						}
+					}
+				}
+			}
+			syslog: {
+				description:   "Syslog-specific encoder options."
+				relevant_when: "codec = \"syslog\""
+				required:      false
+				type: object: options: {
+					app_name: {
+						description: """
+							The name of the application that produced the events.
+
+							The name can reference the fields of events, such as `{{ service }}`. If a referenced
+							field is missing, the name is left out.
+							"""
+						required: false
+						type: string: {
+							default: "vector"
+							examples: ["vector", "{{ service }}"]
+						}
+					}
+					default_facility: {
+						description: "The facility of events whose facility field is missing, or holds an unknown facility."
+						required:    false
+						type: string: {
+							default: "user"
+							enum: {
+								alert:    "Log alert."
+								audit:    "Log audit."
+								auth:     "Security/authorization messages."
+								authpriv: "Private security/authorization messages."
+								clockd:   "Clock daemon."
+								cron:     "Scheduling daemon."
+								daemon:   "System daemons."
+								ftp:      "FTP daemon."
+								kern:     "Kernel messages."
+								local0:   "Local use 0."
+								local1:   "Local use 1."
+								local2:   "Local use 2."
+								local3:   "Local use 3."
+								local4:   "Local use 4."
+								local5:   "Local use 5."
+								local6:   "Local use 6."
+								local7:   "Local use 7."
+								lpr:      "Line printer subsystem."
+								mail:     "Mail system."
+								news:     "Network news subsystem."
+								ntp:      "NTP subsystem."
+								syslog:   "Messages generated internally by syslogd."
+								user:     "User-level messages."
+								uucp:     "UUCP subsystem."
+							}
+						}
+					}
+					default_severity: {
+						description: "The severity of events whose severity field is missing, or holds an unknown severity."
+						required:    false
+						type: string: {
+							default: "info"
+							enum: {
+								alert:   "Action must be taken immediately."
+								crit:    "Critical conditions."
+								debug:   "Debug-level messages."
+								emerg:   "System is unusable."
+								err:     "Error conditions."
+								info:    "Informational messages."
+								notice:  "Normal but significant conditions."
+								warning: "Warning conditions."
+							}
+						}
+					}
+					facility_key: {
+						description: """
+							The field holding the facility of events.
+
+							Both the names and the numeric codes of facilities are supported.
+							"""
+						required: false
+						type: string: {
+							default: "facility"
+							examples: ["facility"]
+						}
+					}
+					msg_id: {
+						description: """
+							The ID of the type of the events.
+
+							The ID can reference the fields of events, such as `{{ event.type }}`. If a referenced
+							field is missing, the ID is left out. This is only supported by RFC 5424 messages.
+							"""
+						required: false
+						type: string: examples: ["{{ event.type }}"]
+					}
+					proc_id: {
+						description: """
+							The ID of the process that produced the events.
+
+							The ID can reference the fields of events, such as `{{ pid }}`. If a referenced field is
+							missing, the ID is left out.
+							"""
+						required: false
+						type: string: examples: ["{{ pid }}"]
+					}
+					rfc: {
+						description: "The format of the messages."
+						required:    false
+						type: string: {
+							default: "rfc5424"
+							enum: {
+								rfc3164: """
+									The legacy BSD format of [RFC 3164][rfc3164].
+
+									[rfc3164]: https://www.rfc-editor.org/rfc/rfc3164
+									"""
+								rfc5424: """
+									The format of [RFC 5424][rfc5424].
+
+									[rfc5424]: https://www.rfc-editor.org/rfc/rfc5424
+									"""
+							}
+						}
+					}
+					severity_key: {
+						description: """
+							The field holding the severity of events.
+
+							Both the names and the numeric codes of severities are supported, as well as the usual
+							logging library levels, such as `error`, `warn`, and `fatal`.
+							"""
+						required: false
+						type: string: {
+							default: "severity"
+							examples: ["severity", "level"]
+						}
+					}
+					structured_data_key: {
+						description: """
+							The field holding the structured data of events.
+
+							The field must hold an object whose keys are the IDs of the structured data elements, and
+							whose values are objects holding the parameters of the elements. This is only supported by
+							RFC 5424 messages.
+							"""
+						required: false
+						type: string: examples: ["structured_data"]
+					}
+				}
+			}
+			text: {
+				description:   "Options for the text serializer."
+				relevant_when: "codec = \"text\""
+				required:      false
+				type: object: options: template: {
+					description: """
+						A template for the encoded logs, referencing their fields as `{{ field }}`.
+
+						Missing fields are rendered as empty strings. When unset, only the message of logs is
+						encoded. Metrics are not affected.
+						"""
+					required: false
+					type: string: examples: ["{{ host }} [{{ level }}] {{ message }}"]
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -157,6 +1206,48 @@ base: components: sinks: amqp: configuration: {
 					unix:    "Represent the timestamp as a Unix timestamp."
 				}
 			}
+			xml: {
+				description:   "XML-specific encoder options."
+				relevant_when: "codec = \"xml\""
+				required:      false
+				type: object: options: {
+					attributes: {
+						description: """
+							The top-level fields written as attributes of the record element, instead of as its child
+							elements.
+
+							Fields holding objects or arrays are always written as elements.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["id", "timestamp"]
+						}
+					}
+					declaration: {
+						description: "Whether to start each document with an XML declaration."
+						required:    false
+						type: bool: default: false
+					}
+					record_element: {
+						description: "The name of the element holding the fields of each event."
+						required:    false
+						type: string: {
+							default: "event"
+							examples: ["event", "record"]
+						}
+					}
+					root_element: {
+						description: """
+							The name of the element wrapping the record element of each event.
+
+							If not set, the record element is the root of the document.
+							"""
+						required: false
+						type: string: examples: ["Body", "request"]
+					}
+				}
+			}
 		}
 	}
 	exchange: {
@@ -222,6 +1313,32 @@ base: components: sinks: amqp: configuration: {
 				required: false
 				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 			}
+			reload_interval_secs: {
+				description: """
+					How often the certificate files are checked for changes, in seconds.
+
+					When the files change, new connections are made with the new certificates, while established connections are
+					kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+					yet, are retried at the next check.
+
+					If not set, the files are only loaded at startup.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			spiffe_endpoint_socket: {
+				description: """
+					Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+					When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+					domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+					API issues new ones, while established connections are kept.
+
+					[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+					"""
+				required: false
+				type: string: examples: ["/run/spire/sockets/agent.sock"]
+			}
 			verify_certificate: {
 				description: """
 					Enables certificate verification.
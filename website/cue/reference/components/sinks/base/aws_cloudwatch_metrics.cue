@@ -168,11 +168,21 @@ base: components: sinks: aws_cloudwatch_metrics: configuration: {
 					[gzip]: https://www.gzip.org/
 					"""
 				none: "No compression."
+				snappy: """
+					[Snappy][snappy] compression, in the raw block format.
+
+					[snappy]: https://github.com/google/snappy
+					"""
 				zlib: """
 					[Zlib][zlib] compression.
 
 					[zlib]: https://zlib.net/
 					"""
+				zstd: """
+					[Zstandard][zstd] compression.
+
+					[zstd]: https://facebook.github.io/zstd/
+					"""
 			}
 		}
 	}
@@ -188,11 +198,33 @@ base: components: sinks: aws_cloudwatch_metrics: configuration: {
 		required: true
 		type: string: examples: ["service"]
 	}
+	dimensions: {
+		description: """
+			The tags sent as the dimensions of the metrics, in order.
+
+			Tags missing from a metric are left out, and other tags are dropped. By default, all the
+			tags are sent, ordered by name, up to the limit of 30 dimensions per metric.
+			"""
+		required: false
+		type: array: items: type: string: examples: ["host", "region"]
+	}
 	endpoint: {
 		description: "Custom endpoint for use with AWS-compatible services."
 		required:    false
 		type: string: examples: ["http://127.0.0.0:5000/path/to/service"]
 	}
+	high_resolution: {
+		description: """
+			Whether to store the metrics at a [high resolution][high_resolution] of one second.
+
+			By default, metrics are stored at a resolution of one minute. High-resolution metrics can
+			be retrieved with periods of one second, but cost more.
+
+			[high_resolution]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/monitoring/cloudwatch_concepts.html#Resolution_definition
+			"""
+		required: false
+		type: bool: default: false
+	}
 	region: {
 		description: """
 			The [AWS region][aws_region] of the target service.
@@ -244,6 +276,38 @@ base: components: sinks: aws_cloudwatch_metrics: configuration: {
 						required: false
 						type: float: default: 0.4
 					}
+					max_concurrency: {
+						description: """
+																The maximum concurrency limit.
+
+																The concurrency limit is never increased above this value. Values above `200` are capped to `200`.
+																"""
+						required: false
+						type: uint: default: 200
+					}
+					min_concurrency: {
+						description: """
+																The minimum concurrency limit.
+
+																The concurrency limit starts at this value, and is never decreased below it, regardless of the response
+																times or back pressure observed.
+
+																Must not be greater than `max_concurrency`.
+																"""
+						required: false
+						type: uint: default: 1
+					}
+					pinned: {
+						description: """
+																Whether or not to pin the concurrency limit at `max_concurrency`.
+
+																When pinned, ARC keeps measuring response times and reporting the adjustments it would have made, through
+																the `adaptive_concurrency_decisions_total` internal metric, but doesn't apply them. This is useful to find out
+																whether ARC's adjustments are responsible for a drop in throughput.
+																"""
+						required: false
+						type: bool: default: false
+					}
 					rtt_deviation_scale: {
 						description: """
 																Scale of RTT deviations which are not considered anomalous.
@@ -398,6 +462,32 @@ base: components: sinks: aws_cloudwatch_metrics: configuration: {
 				required: false
 				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 			}
+			reload_interval_secs: {
+				description: """
+					How often the certificate files are checked for changes, in seconds.
+
+					When the files change, new connections are made with the new certificates, while established connections are
+					kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+					yet, are retried at the next check.
+
+					If not set, the files are only loaded at startup.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			spiffe_endpoint_socket: {
+				description: """
+					Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+					When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+					domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+					API issues new ones, while established connections are kept.
+
+					[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+					"""
+				required: false
+				type: string: examples: ["/run/spire/sockets/agent.sock"]
+			}
 			verify_certificate: {
 				description: """
 					Enables certificate verification.
@@ -43,11 +43,50 @@ base: components: sinks: axiom: configuration: {
 					[gzip]: https://www.gzip.org/
 					"""
 				none: "No compression."
+				snappy: """
+					[Snappy][snappy] compression, in the raw block format.
+
+					[snappy]: https://github.com/google/snappy
+					"""
 				zlib: """
 					[Zlib][zlib] compression.
 
 					[zlib]: https://zlib.net/
 					"""
+				zstd: """
+					[Zstandard][zstd] compression.
+
+					[zstd]: https://facebook.github.io/zstd/
+					"""
+			}
+		}
+	}
+	create_dataset: {
+		description: "Creation of the dataset when it doesn't exist."
+		required:    false
+		type: object: options: {
+			description: {
+				description: "The description of the created dataset."
+				required:    false
+				type: string: examples: ["Logs of the checkout service"]
+			}
+			enabled: {
+				description: """
+					Whether to create the dataset when the sink is started, if it doesn't exist yet.
+
+					The token must be allowed to create datasets, which ingest tokens aren't.
+					"""
+				required: false
+				type: bool: default: false
+			}
+			retention_days: {
+				description: """
+					The number of days the events of the created dataset are retained for.
+
+					If not set, the retention of the organization applies.
+					"""
+				required: false
+				type: uint: examples: [30]
 			}
 		}
 	}
@@ -103,6 +142,38 @@ base: components: sinks: axiom: configuration: {
 						required: false
 						type: float: default: 0.4
 					}
+					max_concurrency: {
+						description: """
+																The maximum concurrency limit.
+
+																The concurrency limit is never increased above this value. Values above `200` are capped to `200`.
+																"""
+						required: false
+						type: uint: default: 200
+					}
+					min_concurrency: {
+						description: """
+																The minimum concurrency limit.
+
+																The concurrency limit starts at this value, and is never decreased below it, regardless of the response
+																times or back pressure observed.
+
+																Must not be greater than `max_concurrency`.
+																"""
+						required: false
+						type: uint: default: 1
+					}
+					pinned: {
+						description: """
+																Whether or not to pin the concurrency limit at `max_concurrency`.
+
+																When pinned, ARC keeps measuring response times and reporting the adjustments it would have made, through
+																the `adaptive_concurrency_decisions_total` internal metric, but doesn't apply them. This is useful to find out
+																whether ARC's adjustments are responsible for a drop in throughput.
+																"""
+						required: false
+						type: bool: default: false
+					}
 					rtt_deviation_scale: {
 						description: """
 																Scale of RTT deviations which are not considered anomalous.
@@ -272,6 +343,32 @@ base: components: sinks: axiom: configuration: {
 				required: false
 				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 			}
+			reload_interval_secs: {
+				description: """
+					How often the certificate files are checked for changes, in seconds.
+
+					When the files change, new connections are made with the new certificates, while established connections are
+					kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+					yet, are retried at the next check.
+
+					If not set, the files are only loaded at startup.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			spiffe_endpoint_socket: {
+				description: """
+					Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+					When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+					domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+					API issues new ones, while established connections are kept.
+
+					[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+					"""
+				required: false
+				type: string: examples: ["/run/spire/sockets/agent.sock"]
+			}
 			verify_certificate: {
 				description: """
 					Enables certificate verification.
@@ -305,7 +402,15 @@ base: components: sinks: axiom: configuration: {
 		}
 	}
 	token: {
-		description: "The Axiom API token."
+		description: """
+			The Axiom API token.
+
+			To rotate the token without restarting Vector, read it from a [secret backend][secrets],
+			such as `SECRET[vault.axiom_token]`, and run Vector with `--secrets-refresh-interval-secs`.
+			The sink is then reloaded with the rotated token once the secret backend returns it.
+
+			[secrets]: https://vector.dev/docs/reference/configuration/global-options/#secret
+			"""
 		required:    true
 		type: string: examples: ["${AXIOM_TOKEN}", "123abc"]
 	}
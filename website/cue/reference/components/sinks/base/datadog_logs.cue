@@ -64,9 +64,9 @@ base: components: sinks: datadog_logs: configuration: {
 	}
 	compression: {
 		description: """
-			Compression configuration.
+			The compression of the payloads.
 
-			All compression algorithms use the default compression level unless otherwise specified.
+			The Datadog Logs API accepts payloads compressed with `gzip`, `zlib`, or `zstd`.
 			"""
 		required: false
 		type: string: enum: {
@@ -76,11 +76,21 @@ base: components: sinks: datadog_logs: configuration: {
 				[gzip]: https://www.gzip.org/
 				"""
 			none: "No compression."
+			snappy: """
+				[Snappy][snappy] compression, in the raw block format.
+
+				[snappy]: https://github.com/google/snappy
+				"""
 			zlib: """
 				[Zlib][zlib] compression.
 
 				[zlib]: https://zlib.net/
 				"""
+			zstd: """
+				[Zstandard][zstd] compression.
+
+				[zstd]: https://facebook.github.io/zstd/
+				"""
 		}
 	}
 	default_api_key: {
@@ -105,10 +115,61 @@ base: components: sinks: datadog_logs: configuration: {
 				type: array: items: type: string: {}
 			}
 			only_fields: {
-				description: "List of fields that will be included in the encoded event."
-				required:    false
+				description: """
+					List of fields that will be included in the encoded event.
+
+					Fields prefixed with `%metadata.` are read from the metadata of the event rather than from
+					the event, and are included in the encoded event under `metadata`. This gives access to the
+					source metadata of events from sources using the `vector` log namespace, such as
+					`%metadata.kafka.offset` or `%metadata.vector.ingest_timestamp`.
+					"""
+				required: false
 				type: array: items: type: string: {}
 			}
+			redact: {
+				description: """
+					Fields whose values are masked or hashed in the encoded event.
+
+					Redaction is applied last, when the event is encoded, so the values of these fields never
+					reach the sink, regardless of the transforms the events went through.
+					"""
+				required: false
+				type: object: options: {
+					fields: {
+						description: """
+							The paths of the redacted fields.
+
+							Fields missing from an event are ignored.
+							"""
+						required: true
+						type: array: items: type: string: examples: ["user.email", "password"]
+					}
+					mask: {
+						description: "The value replacing the values of the fields when they are masked."
+						required:    false
+						type: string: {
+							default: "[REDACTED]"
+							examples: ["***"]
+						}
+					}
+					method: {
+						description: "How the values of the fields are redacted."
+						required:    false
+						type: string: {
+							default: "mask"
+							enum: {
+								hash: """
+									The values are replaced by the hex-encoded SHA-256 hash of their contents.
+
+									Equal values have equal hashes, so events can still be correlated on the fields.
+									Objects and arrays are hashed as JSON.
+									"""
+								mask: "The values are replaced by `mask`."
+							}
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -179,6 +240,38 @@ base: components: sinks: datadog_logs: configuration: {
 						required: false
 						type: float: default: 0.4
 					}
+					max_concurrency: {
+						description: """
+																The maximum concurrency limit.
+
+																The concurrency limit is never increased above this value. Values above `200` are capped to `200`.
+																"""
+						required: false
+						type: uint: default: 200
+					}
+					min_concurrency: {
+						description: """
+																The minimum concurrency limit.
+
+																The concurrency limit starts at this value, and is never decreased below it, regardless of the response
+																times or back pressure observed.
+
+																Must not be greater than `max_concurrency`.
+																"""
+						required: false
+						type: uint: default: 1
+					}
+					pinned: {
+						description: """
+																Whether or not to pin the concurrency limit at `max_concurrency`.
+
+																When pinned, ARC keeps measuring response times and reporting the adjustments it would have made, through
+																the `adaptive_concurrency_decisions_total` internal metric, but doesn't apply them. This is useful to find out
+																whether ARC's adjustments are responsible for a drop in throughput.
+																"""
+						required: false
+						type: bool: default: false
+					}
 					rtt_deviation_scale: {
 						description: """
 																Scale of RTT deviations which are not considered anomalous.
@@ -295,6 +388,52 @@ base: components: sinks: datadog_logs: configuration: {
 			}
 		}
 	}
+	reserved_attributes: {
+		description: """
+			Datadog [reserved attributes][reserved_attributes] to set on each log event.
+
+			Each attribute is set to the rendered template, replacing any value the event already holds,
+			so that events don't need to be remapped beforehand. Attributes whose template fails to render
+			are left as is.
+
+			[reserved_attributes]: https://docs.datadoghq.com/logs/log_configuration/attributes_naming_convention/#reserved-attributes
+			"""
+		required: false
+		type: object: options: {
+			ddsource: {
+				description: "The name of the integration, that is the technology the log events originate from."
+				required:    false
+				type: string: {
+					examples: ["nginx", "{{ source_type }}"]
+					syntax: "template"
+				}
+			}
+			ddtags: {
+				description: "The tags of the log events, as a comma-separated list of `key:value` pairs."
+				required:    false
+				type: string: {
+					examples: ["env:prod,team:{{ team }}"]
+					syntax: "template"
+				}
+			}
+			hostname: {
+				description: "The name of the host that generated the log events."
+				required:    false
+				type: string: {
+					examples: ["{{ kubernetes.pod_node_name }}"]
+					syntax: "template"
+				}
+			}
+			service: {
+				description: "The name of the application or service that generated the log events."
+				required:    false
+				type: string: {
+					examples: ["checkout", "{{ app }}"]
+					syntax: "template"
+				}
+			}
+		}
+	}
 	site: {
 		description: """
 			The Datadog [site][dd_site] to send observability data to.
@@ -370,6 +509,32 @@ base: components: sinks: datadog_logs: configuration: {
 				required: false
 				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 			}
+			reload_interval_secs: {
+				description: """
+					How often the certificate files are checked for changes, in seconds.
+
+					When the files change, new connections are made with the new certificates, while established connections are
+					kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+					yet, are retried at the next check.
+
+					If not set, the files are only loaded at startup.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			spiffe_endpoint_socket: {
+				description: """
+					Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+					When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+					domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+					API issues new ones, while established connections are kept.
+
+					[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+					"""
+				required: false
+				type: string: examples: ["/run/spire/sockets/agent.sock"]
+			}
 			verify_certificate: {
 				description: """
 					Enables certificate verification.
@@ -231,7 +231,8 @@ base: components: sinks: elasticsearch: configuration: {
 				description: """
 					Action to use when making requests to the [Elasticsearch Bulk API][es_bulk].
 
-					Currently, Vector only supports `index` and `create`. `update` and `delete` actions are not supported.
+					Currently, Vector only supports `index`, `create`, and `update`. The `delete` action is not
+					supported.
 
 					[es_bulk]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html
 					"""
@@ -251,6 +252,64 @@ base: components: sinks: elasticsearch: configuration: {
 					syntax: "template"
 				}
 			}
+			update: {
+				description: """
+					Elasticsearch bulk `update` action configuration.
+
+					Documents are updated by ID, so events sent with the `update` action must have an ID, set by
+					`id_key`.
+					"""
+				required: false
+				type: object: options: {
+					doc_as_upsert: {
+						description: """
+							Whether to index the event as a new document when the document to update doesn't exist.
+
+							Only applies if `script` isn't set.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					script: {
+						description: """
+							The script updating the documents.
+
+							If not set, the fields of the event are merged into the document.
+							"""
+						required: false
+						type: object: options: {
+							lang: {
+								description: """
+									The language of the script.
+
+									Defaults to the default language of Elasticsearch, `painless`.
+									"""
+								required: false
+								type: string: examples: ["painless"]
+							}
+							scripted_upsert: {
+								description: """
+									Whether to run the script when the document doesn't exist, instead of indexing the event as
+									a new document.
+
+									The script then creates the document, starting from an empty `ctx._source`.
+									"""
+								required: false
+								type: bool: default: false
+							}
+							source: {
+								description: """
+									The source of the script.
+
+									The event is available to the script as `params.event`.
+									"""
+								required: true
+								type: string: examples: ["ctx._source.count += params.event.count"]
+							}
+						}
+					}
+				}
+			}
 		}
 	}
 	compression: {
@@ -269,11 +328,21 @@ base: components: sinks: elasticsearch: configuration: {
 					[gzip]: https://www.gzip.org/
 					"""
 				none: "No compression."
+				snappy: """
+					[Snappy][snappy] compression, in the raw block format.
+
+					[snappy]: https://github.com/google/snappy
+					"""
 				zlib: """
 					[Zlib][zlib] compression.
 
 					[zlib]: https://zlib.net/
 					"""
+				zstd: """
+					[Zstandard][zstd] compression.
+
+					[zstd]: https://facebook.github.io/zstd/
+					"""
 			}
 		}
 	}
@@ -377,10 +446,61 @@ base: components: sinks: elasticsearch: configuration: {
 				type: array: items: type: string: {}
 			}
 			only_fields: {
-				description: "List of fields that will be included in the encoded event."
-				required:    false
+				description: """
+					List of fields that will be included in the encoded event.
+
+					Fields prefixed with `%metadata.` are read from the metadata of the event rather than from
+					the event, and are included in the encoded event under `metadata`. This gives access to the
+					source metadata of events from sources using the `vector` log namespace, such as
+					`%metadata.kafka.offset` or `%metadata.vector.ingest_timestamp`.
+					"""
+				required: false
 				type: array: items: type: string: {}
 			}
+			redact: {
+				description: """
+					Fields whose values are masked or hashed in the encoded event.
+
+					Redaction is applied last, when the event is encoded, so the values of these fields never
+					reach the sink, regardless of the transforms the events went through.
+					"""
+				required: false
+				type: object: options: {
+					fields: {
+						description: """
+							The paths of the redacted fields.
+
+							Fields missing from an event are ignored.
+							"""
+						required: true
+						type: array: items: type: string: examples: ["user.email", "password"]
+					}
+					mask: {
+						description: "The value replacing the values of the fields when they are masked."
+						required:    false
+						type: string: {
+							default: "[REDACTED]"
+							examples: ["***"]
+						}
+					}
+					method: {
+						description: "How the values of the fields are redacted."
+						required:    false
+						type: string: {
+							default: "mask"
+							enum: {
+								hash: """
+									The values are replaced by the hex-encoded SHA-256 hash of their contents.
+
+									Equal values have equal hashes, so events can still be correlated on the fields.
+									Objects and arrays are hashed as JSON.
+									"""
+								mask: "The values are replaced by `mask`."
+							}
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -500,9 +620,16 @@ base: components: sinks: elasticsearch: configuration: {
 		}
 	}
 	pipeline: {
-		description: "The name of the pipeline to apply."
-		required:    false
-		type: string: examples: ["pipeline-name"]
+		description: """
+			The name of the pipeline to apply.
+
+			If templated, the pipeline is set for each event, rather than for each request.
+			"""
+		required: false
+		type: string: {
+			examples: ["pipeline-name", "{{ pipeline }}"]
+			syntax: "template"
+		}
 	}
 	query: {
 		description: "Custom parameters to add to the query string for each HTTP request sent to Elasticsearch."
@@ -556,6 +683,38 @@ base: components: sinks: elasticsearch: configuration: {
 						required: false
 						type: float: default: 0.4
 					}
+					max_concurrency: {
+						description: """
+																The maximum concurrency limit.
+
+																The concurrency limit is never increased above this value. Values above `200` are capped to `200`.
+																"""
+						required: false
+						type: uint: default: 200
+					}
+					min_concurrency: {
+						description: """
+																The minimum concurrency limit.
+
+																The concurrency limit starts at this value, and is never decreased below it, regardless of the response
+																times or back pressure observed.
+
+																Must not be greater than `max_concurrency`.
+																"""
+						required: false
+						type: uint: default: 1
+					}
+					pinned: {
+						description: """
+																Whether or not to pin the concurrency limit at `max_concurrency`.
+
+																When pinned, ARC keeps measuring response times and reporting the adjustments it would have made, through
+																the `adaptive_concurrency_decisions_total` internal metric, but doesn't apply them. This is useful to find out
+																whether ARC's adjustments are responsible for a drop in throughput.
+																"""
+						required: false
+						type: bool: default: false
+					}
 					rtt_deviation_scale: {
 						description: """
 																Scale of RTT deviations which are not considered anomalous.
@@ -681,6 +840,30 @@ base: components: sinks: elasticsearch: configuration: {
 		required: false
 		type: bool: default: false
 	}
+	require_alias: {
+		description: """
+			Whether the index of each event must be an index alias.
+
+			Events are only required to target an alias if the rendered value is `true`.
+			"""
+		required: false
+		type: string: {
+			examples: ["true", "{{ require_alias }}"]
+			syntax: "template"
+		}
+	}
+	routing: {
+		description: """
+			The [routing value][es_routing] used to route each event to a shard.
+
+			[es_routing]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-routing-field.html
+			"""
+		required: false
+		type: string: {
+			examples: ["{{ tenant_id }}"]
+			syntax: "template"
+		}
+	}
 	suppress_type_name: {
 		deprecated:         true
 		deprecated_message: "This option has been deprecated, the `api_version` option should be used instead."
@@ -747,6 +930,32 @@ base: components: sinks: elasticsearch: configuration: {
 				required: false
 				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 			}
+			reload_interval_secs: {
+				description: """
+					How often the certificate files are checked for changes, in seconds.
+
+					When the files change, new connections are made with the new certificates, while established connections are
+					kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+					yet, are retried at the next check.
+
+					If not set, the files are only loaded at startup.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			spiffe_endpoint_socket: {
+				description: """
+					Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+					When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+					domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+					API issues new ones, while established connections are kept.
+
+					[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+					"""
+				required: false
+				type: string: examples: ["/run/spire/sockets/agent.sock"]
+			}
 			verify_certificate: {
 				description: """
 					Enables certificate verification.
@@ -28,10 +28,34 @@ base: components: sinks: honeycomb: configuration: {
 		}
 	}
 	api_key: {
-		description: "The team key that will be used to authenticate against Honeycomb."
-		required:    true
+		description: """
+			The team key that will be used to authenticate against Honeycomb.
+
+			Events sent to datasets that have a key in `api_keys` use that key instead.
+			"""
+		required: true
 		type: string: examples: ["${HONEYCOMB_API_KEY}", "some-api-key"]
 	}
+	api_keys: {
+		description: """
+			The team keys that will be used to authenticate against Honeycomb, by dataset.
+
+			This allows a single sink to send events to the datasets of several teams, each with their
+			own key. Events sent to other datasets use `api_key`.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				"payments-logs": "${PAYMENTS_HONEYCOMB_API_KEY}"
+				"search-logs":   "${SEARCH_HONEYCOMB_API_KEY}"
+			}]
+			options: "*": {
+				description: "The team key of a dataset."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
 	batch: {
 		description: "Event batching behavior."
 		required:    false
@@ -65,9 +89,16 @@ base: components: sinks: honeycomb: configuration: {
 		}
 	}
 	dataset: {
-		description: "The dataset to which logs are sent."
-		required:    true
-		type: string: examples: ["my-honeycomb-dataset"]
+		description: """
+			The dataset to which logs are sent.
+
+			Events for which the template fails to render are dropped.
+			"""
+		required: true
+		type: string: {
+			examples: ["my-honeycomb-dataset", "{{ team }}-logs"]
+			syntax: "template"
+		}
 	}
 	encoding: {
 		description: "Transformations to prepare an event for serialization."
@@ -79,10 +110,61 @@ base: components: sinks: honeycomb: configuration: {
 				type: array: items: type: string: {}
 			}
 			only_fields: {
-				description: "List of fields that will be included in the encoded event."
-				required:    false
+				description: """
+					List of fields that will be included in the encoded event.
+
+					Fields prefixed with `%metadata.` are read from the metadata of the event rather than from
+					the event, and are included in the encoded event under `metadata`. This gives access to the
+					source metadata of events from sources using the `vector` log namespace, such as
+					`%metadata.kafka.offset` or `%metadata.vector.ingest_timestamp`.
+					"""
+				required: false
 				type: array: items: type: string: {}
 			}
+			redact: {
+				description: """
+					Fields whose values are masked or hashed in the encoded event.
+
+					Redaction is applied last, when the event is encoded, so the values of these fields never
+					reach the sink, regardless of the transforms the events went through.
+					"""
+				required: false
+				type: object: options: {
+					fields: {
+						description: """
+							The paths of the redacted fields.
+
+							Fields missing from an event are ignored.
+							"""
+						required: true
+						type: array: items: type: string: examples: ["user.email", "password"]
+					}
+					mask: {
+						description: "The value replacing the values of the fields when they are masked."
+						required:    false
+						type: string: {
+							default: "[REDACTED]"
+							examples: ["***"]
+						}
+					}
+					method: {
+						description: "How the values of the fields are redacted."
+						required:    false
+						type: string: {
+							default: "mask"
+							enum: {
+								hash: """
+									The values are replaced by the hex-encoded SHA-256 hash of their contents.
+
+									Equal values have equal hashes, so events can still be correlated on the fields.
+									Objects and arrays are hashed as JSON.
+									"""
+								mask: "The values are replaced by `mask`."
+							}
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -93,6 +175,19 @@ base: components: sinks: honeycomb: configuration: {
 			}
 		}
 	}
+	markers: {
+		description: """
+			Send events as [markers][markers] rather than as events.
+
+			Markers annotate the graphs of a dataset with occurrences such as deploys. Each event is
+			sent as a marker, using the `message`, `type`, and `url` fields of the event as the
+			message, type, and URL of the marker, and the timestamp of the event as its start time.
+
+			[markers]: https://docs.honeycomb.io/api/markers/
+			"""
+		required: false
+		type: bool: default: false
+	}
 	request: {
 		description: """
 			Middleware settings for outbound requests.
@@ -135,6 +230,38 @@ base: components: sinks: honeycomb: configuration: {
 						required: false
 						type: float: default: 0.4
 					}
+					max_concurrency: {
+						description: """
+																The maximum concurrency limit.
+
+																The concurrency limit is never increased above this value. Values above `200` are capped to `200`.
+																"""
+						required: false
+						type: uint: default: 200
+					}
+					min_concurrency: {
+						description: """
+																The minimum concurrency limit.
+
+																The concurrency limit starts at this value, and is never decreased below it, regardless of the response
+																times or back pressure observed.
+
+																Must not be greater than `max_concurrency`.
+																"""
+						required: false
+						type: uint: default: 1
+					}
+					pinned: {
+						description: """
+																Whether or not to pin the concurrency limit at `max_concurrency`.
+
+																When pinned, ARC keeps measuring response times and reporting the adjustments it would have made, through
+																the `adaptive_concurrency_decisions_total` internal metric, but doesn't apply them. This is useful to find out
+																whether ARC's adjustments are responsible for a drop in throughput.
+																"""
+						required: false
+						type: bool: default: false
+					}
 					rtt_deviation_scale: {
 						description: """
 																Scale of RTT deviations which are not considered anomalous.
@@ -41,7 +41,7 @@ base: components: sinks: statsd: configuration: {
 	}
 	batch: {
 		description:   "Event batching behavior."
-		relevant_when: "mode = \"udp\""
+		relevant_when: "mode = \"udp\" or mode = \"unix_datagram\""
 		required:      false
 		type: object: options: {
 			max_bytes: {
@@ -75,6 +75,19 @@ base: components: sinks: statsd: configuration: {
 			}
 		}
 	}
+	container_id: {
+		description: """
+			The ID of the container the metrics originate from.
+
+			When set, it is appended to every metric as the [DogStatsD container ID field][container_id]
+			(`|c:<id>`), which the Datadog Agent uses to tag metrics with the tags of the container
+			they originate from. This is only understood by DogStatsD servers.
+
+			[container_id]: https://docs.datadoghq.com/developers/dogstatsd/datagram_shell/?tab=metrics#dogstatsd-protocol-v12
+			"""
+		required: false
+		type: string: examples: ["83c0a8a8e9b4f7a0f8b55a0bc1e5e4a6a45ad8a0e4f27ed1b5c5f3b3a8b8c4d1"]
+	}
 	default_namespace: {
 		description: """
 			Sets the default namespace for any metrics sent.
@@ -101,7 +114,12 @@ base: components: sinks: statsd: configuration: {
 		type: string: enum: {
 			tcp:  "Send over TCP."
 			udp:  "Send over UDP."
-			unix: "Send over a Unix domain socket (UDS)."
+			unix: "Send over a Unix domain socket (UDS), as a stream."
+			unix_datagram: """
+				Send over a Unix domain socket (UDS), as datagrams.
+
+				This is the type of socket the DogStatsD server of the Datadog Agent listens on.
+				"""
 		}
 	}
 	path: {
@@ -110,7 +128,7 @@ base: components: sinks: statsd: configuration: {
 
 			This should be an absolute path.
 			"""
-		relevant_when: "mode = \"unix\""
+		relevant_when: "mode = \"unix\" or mode = \"unix_datagram\""
 		required:      true
 		type: string: examples: ["/path/to/socket"]
 	}
@@ -193,6 +211,32 @@ base: components: sinks: statsd: configuration: {
 				required: false
 				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 			}
+			reload_interval_secs: {
+				description: """
+					How often the certificate files are checked for changes, in seconds.
+
+					When the files change, new connections are made with the new certificates, while established connections are
+					kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+					yet, are retried at the next check.
+
+					If not set, the files are only loaded at startup.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			spiffe_endpoint_socket: {
+				description: """
+					Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+					When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+					domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+					API issues new ones, while established connections are kept.
+
+					[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+					"""
+				required: false
+				type: string: examples: ["/run/spire/sockets/agent.sock"]
+			}
 			verify_certificate: {
 				description: """
 					Enables certificate verification.
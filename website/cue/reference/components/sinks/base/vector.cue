@@ -72,14 +72,92 @@ base: components: sinks: vector: configuration: {
 	}
 	compression: {
 		description: """
-			Whether or not to compress requests.
+			The compression algorithm of requests.
 
-			If set to `true`, requests will be compressed with [`gzip`][gzip_docs].
-
-			[gzip_docs]: https://www.gzip.org/
+			For compatibility, `true` and `false` are accepted as aliases of `gzip` and `none`.
 			"""
 		required: false
-		type: bool: default: false
+		type: string: {
+			default: "none"
+			enum: {
+				gzip: """
+					[Gzip][gzip] compression.
+
+					[gzip]: https://www.gzip.org/
+					"""
+				none: "No compression."
+				zstd: """
+					[Zstandard][zstd] compression.
+
+					Zstandard is usually both faster and more efficient than gzip. If the downstream Vector
+					doesn't support it, requests are compressed with gzip instead.
+
+					[zstd]: https://facebook.github.io/zstd/
+					"""
+			}
+		}
+	}
+	http2: {
+		description: "HTTP/2 settings of the connections to the downstream Vector."
+		required:    false
+		type: object: options: {
+			adaptive_window: {
+				description: """
+					Whether to adapt the flow control windows to the measured bandwidth and latency.
+
+					If enabled, `initial_stream_window_size` and `initial_connection_window_size` are ignored.
+					"""
+				required: false
+				type: bool: default: false
+			}
+			initial_connection_window_size: {
+				description: "The initial flow control window of connections."
+				required:    false
+				type: uint: {
+					examples: [4194304]
+					unit: "bytes"
+				}
+			}
+			initial_stream_window_size: {
+				description: """
+					The initial flow control window of streams.
+
+					Larger windows help throughput over links with high latency.
+					"""
+				required: false
+				type: uint: {
+					examples: [1048576]
+					unit: "bytes"
+				}
+			}
+			keepalive_interval_secs: {
+				description: """
+					The interval between the pings sent to keep connections alive.
+
+					Connections whose peer doesn't acknowledge a ping within `keepalive_timeout_secs` are
+					closed, which detects broken connections that wouldn't be noticed otherwise, such as behind
+					load balancers. If not set, no pings are sent.
+					"""
+				required: false
+				type: uint: {
+					examples: [30]
+					unit: "seconds"
+				}
+			}
+			keepalive_timeout_secs: {
+				description: "How long to wait for the acknowledgement of a ping before closing the connection."
+				required:    false
+				type: uint: {
+					default: 20
+					unit:    "seconds"
+				}
+			}
+			keepalive_while_idle: {
+				description: "Whether to send pings while no requests are in flight."
+				required:    false
+				type: bool: default: false
+			}
+		}
 	}
 	request: {
 		description: """
@@ -123,6 +201,38 @@ base: components: sinks: vector: configuration: {
 						required: false
 						type: float: default: 0.4
 					}
+					max_concurrency: {
+						description: """
+																The maximum concurrency limit.
+
+																The concurrency limit is never increased above this value. Values above `200` are capped to `200`.
+																"""
+						required: false
+						type: uint: default: 200
+					}
+					min_concurrency: {
+						description: """
+																The minimum concurrency limit.
+
+																The concurrency limit starts at this value, and is never decreased below it, regardless of the response
+																times or back pressure observed.
+
+																Must not be greater than `max_concurrency`.
+																"""
+						required: false
+						type: uint: default: 1
+					}
+					pinned: {
+						description: """
+																Whether or not to pin the concurrency limit at `max_concurrency`.
+
+																When pinned, ARC keeps measuring response times and reporting the adjustments it would have made, through
+																the `adaptive_concurrency_decisions_total` internal metric, but doesn't apply them. This is useful to find out
+																whether ARC's adjustments are responsible for a drop in throughput.
+																"""
+						required: false
+						type: bool: default: false
+					}
 					rtt_deviation_scale: {
 						description: """
 																Scale of RTT deviations which are not considered anomalous.
@@ -287,6 +397,32 @@ base: components: sinks: vector: configuration: {
 				required: false
 				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 			}
+			reload_interval_secs: {
+				description: """
+					How often the certificate files are checked for changes, in seconds.
+
+					When the files change, new connections are made with the new certificates, while established connections are
+					kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+					yet, are retried at the next check.
+
+					If not set, the files are only loaded at startup.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			spiffe_endpoint_socket: {
+				description: """
+					Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+					When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+					domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+					API issues new ones, while established connections are kept.
+
+					[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+					"""
+				required: false
+				type: string: examples: ["/run/spire/sockets/agent.sock"]
+			}
 			verify_certificate: {
 				description: """
 					Enables certificate verification.
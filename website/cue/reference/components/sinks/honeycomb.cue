@@ -76,6 +76,16 @@ components: sinks: honeycomb: {
 				curl option and use the key provided with the curl example.
 				"""
 		}
+
+		dataset_routing: {
+			title: "Dataset routing"
+			body:  """
+				The `dataset` option can be a template, so that a single sink sends events to several
+				datasets, such as one per team. Each dataset can use its own team key, set in
+				`api_keys`, with the other datasets using `api_key`. The healthcheck validates each of
+				the keys.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
@@ -99,6 +99,7 @@ components: sinks: http: {
 		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
 		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
 		http_bad_requests_total:          components.sources.internal_metrics.output.metrics.http_bad_requests_total
+		http_compression_fallbacks_total: components.sources.internal_metrics.output.metrics.http_compression_fallbacks_total
 		processed_bytes_total:            components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:           components.sources.internal_metrics.output.metrics.processed_events_total
 	}
@@ -187,6 +187,39 @@ components: sources: [Name=string]: {
 
 											[gelf]: https://docs.graylog.org/docs/gelf
 											"""
+										msgpack: """
+											Decodes the raw bytes as [MessagePack][msgpack].
+
+											Each frame can hold several concatenated MessagePack maps, which are decoded as separate
+											events. Binary values are decoded as bytes, and values of the timestamp extension type as
+											timestamps.
+
+											[msgpack]: https://msgpack.org/
+											"""
+										csv: """
+											Decodes the raw bytes as [CSV][csv] rows.
+
+											The columns are decoded as strings into the configured fields, or into the fields named by
+											the header row of each stream.
+
+											[csv]: https://www.rfc-editor.org/rfc/rfc4180
+											"""
+										avro: """
+											Decodes the raw bytes as an [Apache Avro][apache_avro] message.
+
+											Messages can be bare datums, or be in the single-object encoding or the Confluent wire
+											format, whose schemas can be fetched from a schema registry by the `kafka` source.
+
+											[apache_avro]: https://avro.apache.org/
+											"""
+										parquet: """
+											Decodes the raw bytes as an [Apache Parquet][apache_parquet] file.
+
+											Each row of the file is decoded as an event, reading the row groups one after the other.
+											As Parquet files can only be read as a whole, the default framing of this codec is `bytes`.
+
+											[apache_parquet]: https://parquet.apache.org/
+											"""
 										syslog: """
 											Decodes the raw bytes as a Syslog message.
 
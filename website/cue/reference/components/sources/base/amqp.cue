@@ -49,48 +49,242 @@ base: components: sources: amqp: configuration: {
 	decoding: {
 		description: "Configures how events are decoded from raw bytes."
 		required:    false
-		type: object: options: codec: {
-			description: "The codec to use for decoding events."
-			required:    false
-			type: string: {
-				default: "bytes"
-				enum: {
-					bytes: "Uses the raw bytes as-is."
-					gelf: """
-						Decodes the raw bytes as a [GELF][gelf] message.
-
-						[gelf]: https://docs.graylog.org/docs/gelf
-						"""
-					json: """
-						Decodes the raw bytes as [JSON][json].
+		type: object: options: {
+			avro: {
+				description:   "Apache Avro-specific decoding options."
+				relevant_when: "codec = \"avro\""
+				required:      false
+				type: object: options: {
+					format: {
+						description: "The encoding of the Avro messages."
+						required:    false
+						type: string: {
+							default: "datum"
+							enum: {
+								confluent: """
+									Each message is in the Confluent [wire format][wire_format]: a zero magic byte, followed by
+									the ID of the schema in the schema registry, as a 32-bit unsigned integer, big endian, and
+									the datum.
+
+									The datum is read with the schema fetched from `schema_registry` if set, or with `schema`
+									otherwise.
+
+									[wire_format]: https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format
+									"""
+								datum: "Each message is a bare Avro datum, written with `schema`."
+								single_object: """
+									Each message is in the [single-object encoding][single_object]: the `C3 01` marker,
+									followed by the CRC-64-AVRO fingerprint of the schema, and the datum.
+
+									Messages written with a schema other than `schema` are rejected.
+
+									[single_object]: https://avro.apache.org/docs/1.11.1/specification/#single-object-encoding
+									"""
+							}
+						}
+					}
+					schema: {
+						description: """
+							The Avro schema.
 
-						[json]: https://www.json.org/
-						"""
-					native: """
-						Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+							Required unless the schemas are fetched from `schema_registry`.
+							"""
+						required: false
+						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					}
+					schema_registry: {
+						description: """
+							Confluent-compatible schema registry options.
 
-						This codec is **[experimental][experimental]**.
+							The schemas referenced by the messages are fetched from the registry the first time they are
+							seen, and cached for the lifetime of the source.
 
-						[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
-						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
-						"""
-					native_json: """
-						Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+							The schema registry is only supported by the `kafka` source, with the `confluent` format.
+							"""
+						required: false
+						type: object: options: {
+							auth: {
+								description: "Basic authentication to the schema registry."
+								required:    false
+								type: object: options: {
+									password: {
+										description: "The basic authentication password."
+										required:    true
+										type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+									}
+									user: {
+										description: "The basic authentication username."
+										required:    true
+										type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+									}
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+						}
+					}
+				}
+			}
+			codec: {
+				description: "The codec to use for decoding events."
+				required:    false
+				type: string: {
+					default: "bytes"
+					enum: {
+						avro: """
+							Decodes the raw bytes as an [Apache Avro][apache_avro] message.
+
+							Messages can be bare datums, or be in the single-object encoding or the Confluent wire
+							format, whose schemas can be fetched from a schema registry by the `kafka` source.
+
+							[apache_avro]: https://avro.apache.org/
+							"""
+						bytes: "Uses the raw bytes as-is."
+						csv: """
+							Decodes the raw bytes as [CSV][csv] rows.
+
+							The columns are decoded as strings into the configured fields, or into the fields named by
+							the header row of each stream.
+
+							[csv]: https://www.rfc-editor.org/rfc/rfc4180
+							"""
+						gelf: """
+							Decodes the raw bytes as a [GELF][gelf] message.
+
+							[gelf]: https://docs.graylog.org/docs/gelf
+							"""
+						json: """
+							Decodes the raw bytes as [JSON][json].
+
+							[json]: https://www.json.org/
+							"""
+						msgpack: """
+							Decodes the raw bytes as [MessagePack][msgpack].
+
+							Each frame can hold several concatenated MessagePack maps, which are decoded as separate
+							events. Binary values are decoded as bytes, and values of the timestamp extension type as
+							timestamps.
+
+							[msgpack]: https://msgpack.org/
+							"""
+						native: """
+							Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+
+							This codec is **[experimental][experimental]**.
+
+							[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+							[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+							"""
+						native_json: """
+							Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+
+							This codec is **[experimental][experimental]**.
+
+							[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+							[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+							"""
+						parquet: """
+							Decodes the raw bytes as an [Apache Parquet][apache_parquet] file.
+
+							Each row of the file is decoded as an event, reading the row groups one after the other.
+							As Parquet files can only be read as a whole, the default framing of this codec is `bytes`.
+
+							[apache_parquet]: https://parquet.apache.org/
+							"""
+						syslog: """
+							Decodes the raw bytes as a Syslog message.
+
+							Will decode either as the [RFC 3164][rfc3164]-style format ("old" style) or the more modern
+							[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+							[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+							[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+							"""
+					}
+				}
+			}
+			csv: {
+				description:   "CSV-specific decoding options."
+				relevant_when: "codec = \"csv\""
+				required:      false
+				type: object: options: {
+					delimiter: {
+						description: "The ASCII (7-bit) character that delimits the columns."
+						required:    false
+						type: uint: default: 44
+					}
+					fields: {
+						description: """
+							The fields the columns are decoded into, in the order the columns appear in the rows.
 
-						This codec is **[experimental][experimental]**.
+							Columns beyond the last field are ignored, and fields beyond the last column of a row are
+							left out of the event.
 
-						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
-						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
-						"""
-					syslog: """
-						Decodes the raw bytes as a Syslog message.
+							If empty, the first row of each stream, such as each file of the `file` source or each
+							connection of the `socket` source, is read as a header row, and the columns are decoded into
+							the top-level fields named by the header row.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["timestamp", "message"]
+						}
+					}
+					headers: {
+						description: """
+							Skips the first row of each stream as a header row.
 
-						Will decode either as the [RFC 3164][rfc3164]-style format ("old" style) or the more modern
-						[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+							Only relevant if `fields` is set, as the header row is always read if `fields` is empty.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					quote: {
+						description: """
+							The ASCII (7-bit) character that quotes columns holding delimiters, quotes, or line breaks.
+
+							Quotes inside quoted columns are escaped by doubling them.
+							"""
+						required: false
+						type: uint: default: 34
+					}
+				}
+			}
+			native: {
+				description:   "Native-specific decoding options."
+				relevant_when: "codec = \"native\""
+				required:      false
+				type: object: options: reject_newer_versions: {
+					description: """
+						Whether to reject the events encoded with a newer version of the native format.
 
-						[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
-						[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+						By default, such events are decoded on a best-effort basis, leaving out the parts of the
+						events that this version of Vector doesn't know about, so that Vector instances can be
+						upgraded in any order.
 						"""
+					required: false
+					type: bool: default: false
+				}
+			}
+			parquet: {
+				description:   "Apache Parquet-specific decoding options."
+				relevant_when: "codec = \"parquet\""
+				required:      false
+				type: object: options: fields: {
+					description: """
+						The names of the top-level columns to read.
+
+						Only these columns are read from the files, and decoded into the top-level fields of the
+						same names. If empty, all the columns are read.
+						"""
+					required: false
+					type: array: {
+						default: []
+						items: type: string: examples: ["timestamp", "message"]
+					}
 				}
 			}
 		}
@@ -259,6 +453,32 @@ base: components: sources: amqp: configuration: {
 				required: false
 				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 			}
+			reload_interval_secs: {
+				description: """
+					How often the certificate files are checked for changes, in seconds.
+
+					When the files change, new connections are made with the new certificates, while established connections are
+					kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+					yet, are retried at the next check.
+
+					If not set, the files are only loaded at startup.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			spiffe_endpoint_socket: {
+				description: """
+					Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+					When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+					domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+					API issues new ones, while established connections are kept.
+
+					[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+					"""
+				required: false
+				type: string: examples: ["/run/spire/sockets/agent.sock"]
+			}
 			verify_certificate: {
 				description: """
 					Enables certificate verification.
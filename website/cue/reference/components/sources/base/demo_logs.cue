@@ -13,48 +13,323 @@ base: components: sources: demo_logs: configuration: {
 	decoding: {
 		description: "Configures how events are decoded from raw bytes."
 		required:    false
-		type: object: options: codec: {
-			description: "The codec to use for decoding events."
-			required:    false
-			type: string: {
-				default: "bytes"
-				enum: {
-					bytes: "Uses the raw bytes as-is."
-					gelf: """
-						Decodes the raw bytes as a [GELF][gelf] message.
-
-						[gelf]: https://docs.graylog.org/docs/gelf
-						"""
-					json: """
-						Decodes the raw bytes as [JSON][json].
+		type: object: options: {
+			avro: {
+				description:   "Apache Avro-specific decoding options."
+				relevant_when: "codec = \"avro\""
+				required:      false
+				type: object: options: {
+					format: {
+						description: "The encoding of the Avro messages."
+						required:    false
+						type: string: {
+							default: "datum"
+							enum: {
+								confluent: """
+									Each message is in the Confluent [wire format][wire_format]: a zero magic byte, followed by
+									the ID of the schema in the schema registry, as a 32-bit unsigned integer, big endian, and
+									the datum.
 
-						[json]: https://www.json.org/
-						"""
-					native: """
-						Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+									The datum is read with the schema fetched from `schema_registry` if set, or with `schema`
+									otherwise.
 
-						This codec is **[experimental][experimental]**.
+									[wire_format]: https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format
+									"""
+								datum: "Each message is a bare Avro datum, written with `schema`."
+								single_object: """
+									Each message is in the [single-object encoding][single_object]: the `C3 01` marker,
+									followed by the CRC-64-AVRO fingerprint of the schema, and the datum.
 
-						[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
-						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
-						"""
-					native_json: """
-						Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+									Messages written with a schema other than `schema` are rejected.
+
+									[single_object]: https://avro.apache.org/docs/1.11.1/specification/#single-object-encoding
+									"""
+							}
+						}
+					}
+					schema: {
+						description: """
+							The Avro schema.
+
+							Required unless the schemas are fetched from `schema_registry`.
+							"""
+						required: false
+						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					}
+					schema_registry: {
+						description: """
+							Confluent-compatible schema registry options.
+
+							The schemas referenced by the messages are fetched from the registry the first time they are
+							seen, and cached for the lifetime of the source.
+
+							The schema registry is only supported by the `kafka` source, with the `confluent` format.
+							"""
+						required: false
+						type: object: options: {
+							auth: {
+								description: "Basic authentication to the schema registry."
+								required:    false
+								type: object: options: {
+									password: {
+										description: "The basic authentication password."
+										required:    true
+										type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+									}
+									user: {
+										description: "The basic authentication username."
+										required:    true
+										type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+									}
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+						}
+					}
+				}
+			}
+			codec: {
+				description: "The codec to use for decoding events."
+				required:    false
+				type: string: {
+					default: "bytes"
+					enum: {
+						avro: """
+							Decodes the raw bytes as an [Apache Avro][apache_avro] message.
+
+							Messages can be bare datums, or be in the single-object encoding or the Confluent wire
+							format, whose schemas can be fetched from a schema registry by the `kafka` source.
+
+							[apache_avro]: https://avro.apache.org/
+							"""
+						bytes: "Uses the raw bytes as-is."
+						csv: """
+							Decodes the raw bytes as [CSV][csv] rows.
+
+							The columns are decoded as strings into the configured fields, or into the fields named by
+							the header row of each stream.
+
+							[csv]: https://www.rfc-editor.org/rfc/rfc4180
+							"""
+						gelf: """
+							Decodes the raw bytes as a [GELF][gelf] message.
+
+							[gelf]: https://docs.graylog.org/docs/gelf
+							"""
+						json: """
+							Decodes the raw bytes as [JSON][json].
+
+							[json]: https://www.json.org/
+							"""
+						msgpack: """
+							Decodes the raw bytes as [MessagePack][msgpack].
+
+							Each frame can hold several concatenated MessagePack maps, which are decoded as separate
+							events. Binary values are decoded as bytes, and values of the timestamp extension type as
+							timestamps.
+
+							[msgpack]: https://msgpack.org/
+							"""
+						native: """
+							Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+
+							This codec is **[experimental][experimental]**.
 
-						This codec is **[experimental][experimental]**.
+							[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+							[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+							"""
+						native_json: """
+							Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
 
-						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
-						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+							This codec is **[experimental][experimental]**.
+
+							[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+							[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+							"""
+						parquet: """
+							Decodes the raw bytes as an [Apache Parquet][apache_parquet] file.
+
+							Each row of the file is decoded as an event, reading the row groups one after the other.
+							As Parquet files can only be read as a whole, the default framing of this codec is `bytes`.
+
+							[apache_parquet]: https://parquet.apache.org/
+							"""
+						syslog: """
+							Decodes the raw bytes as a Syslog message.
+
+							Will decode either as the [RFC 3164][rfc3164]-style format ("old" style) or the more modern
+							[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+							[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+							[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+							"""
+					}
+				}
+			}
+			csv: {
+				description:   "CSV-specific decoding options."
+				relevant_when: "codec = \"csv\""
+				required:      false
+				type: object: options: {
+					delimiter: {
+						description: "The ASCII (7-bit) character that delimits the columns."
+						required:    false
+						type: uint: default: 44
+					}
+					fields: {
+						description: """
+							The fields the columns are decoded into, in the order the columns appear in the rows.
+
+							Columns beyond the last field are ignored, and fields beyond the last column of a row are
+							left out of the event.
+
+							If empty, the first row of each stream, such as each file of the `file` source or each
+							connection of the `socket` source, is read as a header row, and the columns are decoded into
+							the top-level fields named by the header row.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["timestamp", "message"]
+						}
+					}
+					headers: {
+						description: """
+							Skips the first row of each stream as a header row.
+
+							Only relevant if `fields` is set, as the header row is always read if `fields` is empty.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					quote: {
+						description: """
+							The ASCII (7-bit) character that quotes columns holding delimiters, quotes, or line breaks.
+
+							Quotes inside quoted columns are escaped by doubling them.
+							"""
+						required: false
+						type: uint: default: 34
+					}
+				}
+			}
+			native: {
+				description:   "Native-specific decoding options."
+				relevant_when: "codec = \"native\""
+				required:      false
+				type: object: options: reject_newer_versions: {
+					description: """
+						Whether to reject the events encoded with a newer version of the native format.
+
+						By default, such events are decoded on a best-effort basis, leaving out the parts of the
+						events that this version of Vector doesn't know about, so that Vector instances can be
+						upgraded in any order.
 						"""
-					syslog: """
-						Decodes the raw bytes as a Syslog message.
+					required: false
+					type: bool: default: false
+				}
+			}
+			parquet: {
+				description:   "Apache Parquet-specific decoding options."
+				relevant_when: "codec = \"parquet\""
+				required:      false
+				type: object: options: fields: {
+					description: """
+						The names of the top-level columns to read.
 
-						Will decode either as the [RFC 3164][rfc3164]-style format ("old" style) or the more modern
-						[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+						Only these columns are read from the files, and decoded into the top-level fields of the
+						same names. If empty, all the columns are read.
+						"""
+					required: false
+					type: array: {
+						default: []
+						items: type: string: examples: ["timestamp", "message"]
+					}
+				}
+			}
+		}
+	}
+	fields: {
+		description:   "The fields referenced by `template`, with the distributions of their values."
+		relevant_when: "format = \"template\""
+		required:      false
+		type: object: options: "*": {
+			description: "A field of the lines."
+			required:    true
+			type: object: options: {
+				format: {
+					description: """
+						The [format][chrono_format] of the time.
 
-						[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
-						[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+						[chrono_format]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html
 						"""
+					relevant_when: "type = \"timestamp\""
+					required:      false
+					type: string: default: "%+"
+				}
+				max: {
+					description:   "The largest value."
+					relevant_when: "type = \"integer\""
+					required:      true
+					type: int: {}
+				}
+				mean: {
+					description:   "The mean of the distribution."
+					relevant_when: "type = \"normal\""
+					required:      true
+					type: float: {}
+				}
+				min: {
+					description:   "The smallest value."
+					relevant_when: "type = \"integer\""
+					required:      true
+					type: int: {}
+				}
+				precision: {
+					description:   "The number of decimal places of the values."
+					relevant_when: "type = \"normal\""
+					required:      false
+					type: uint: default: 0
+				}
+				std_dev: {
+					description:   "The standard deviation of the distribution."
+					relevant_when: "type = \"normal\""
+					required:      true
+					type: float: {}
+				}
+				type: {
+					description: "The distribution of the field values."
+					required:    true
+					type: string: enum: {
+						choice:    "Values chosen at random from a list."
+						integer:   "Integers uniformly distributed between `min` and `max`, inclusive."
+						normal:    "Numbers following a normal distribution."
+						sequence:  "The sequence number of the line, starting at 0."
+						timestamp: "The current time."
+					}
+				}
+				values: {
+					description:   "The values to choose from."
+					relevant_when: "type = \"choice\""
+					required:      true
+					type: array: items: type: string: examples: ["GET", "POST", "DELETE"]
+				}
+				weights: {
+					description: """
+						The relative weights of the values, in the same order.
+
+						By default, all values are equally likely.
+						"""
+					relevant_when: "type = \"choice\""
+					required:      false
+					type: array: {
+						default: []
+						items: type: float: examples: [8.0, 1.5, 0.5]
+					}
 				}
 			}
 		}
@@ -89,6 +364,15 @@ base: components: sources: demo_logs: configuration: {
 
 				[syslog_5424]: https://tools.ietf.org/html/rfc5424
 				"""
+			template: "Lines rendered from `template`, with the values of its fields generated at random."
+			vrl: """
+				Lines generated by a [Vector Remap Language][vrl] (VRL) program.
+
+				The program runs against an event holding the `sequence` number of the line, starting at
+				0, and the line is the value the program resolves to, encoded as JSON unless it's a string.
+
+				[vrl]: https://vector.dev/docs/reference/vrl
+				"""
 		}
 	}
 	framing: {
@@ -202,10 +486,87 @@ base: components: sources: demo_logs: configuration: {
 		required:      true
 		type: array: items: type: string: examples: ["line1", "line2"]
 	}
+	rate: {
+		description: "Shapes the rate of output lines, instead of outputting one line per `interval`."
+		required:    false
+		type: object: options: {
+			bursts: {
+				description: "Periodic bursts of the rate, starting with the source."
+				required:    false
+				type: object: options: {
+					duration_secs: {
+						description: "The length of the bursts, in seconds."
+						required:    true
+						type: float: examples: [10.0]
+					}
+					every_secs: {
+						description: "The time between the starts of consecutive bursts, in seconds."
+						required:    true
+						type: float: examples: [300.0]
+					}
+					multiplier: {
+						description: "The factor the rate is multiplied by during the bursts."
+						required:    true
+						type: float: examples: [10.0]
+					}
+				}
+			}
+			diurnal: {
+				description: "A daily cycle of the rate, starting at its lowest."
+				required:    false
+				type: object: options: {
+					amplitude: {
+						description: """
+							The amplitude of the cycle, relative to the base rate.
+
+							The rate varies between `1 - amplitude` and `1 + amplitude` times the base rate, so this
+							must be between 0 and 1.
+							"""
+						required: false
+						type: float: default: 0.5
+					}
+					period_secs: {
+						description: """
+							The length of the cycle, in seconds.
+
+							Shorter cycles make it possible to go through a whole day of traffic in a load test.
+							"""
+						required: false
+						type: float: {
+							default: 86400.0
+							examples: [3600.0]
+						}
+					}
+				}
+			}
+			lines_per_sec: {
+				description: "The base rate of output lines, in lines per second."
+				required:    true
+				type: float: examples: [100.0]
+			}
+			ramp_up_secs: {
+				description: "The time, in seconds, over which the rate ramps up linearly from zero to the base rate."
+				required:    false
+				type: float: examples: [60.0]
+			}
+		}
+	}
 	sequence: {
 		description:   "If `true`, each output line starts with an increasing sequence number, beginning with 0."
 		relevant_when: "format = \"shuffle\""
 		required:      false
 		type: bool: default: false
 	}
+	source: {
+		description:   "The VRL program."
+		relevant_when: "format = \"vrl\""
+		required:      true
+		type: string: examples: ["{ \"id\": .sequence, \"level\": \"info\", \"latency_ms\": random_int(1, 500) }"]
+	}
+	template: {
+		description:   "The template of the lines, referencing the fields defined in `fields` as `{{ field }}`."
+		relevant_when: "format = \"template\""
+		required:      true
+		type: string: examples: ["{{ client }} - - [{{ time }}] \"{{ method }} /api HTTP/1.1\" {{ status }}"]
+	}
 }
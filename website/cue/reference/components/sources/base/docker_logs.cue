@@ -6,6 +6,16 @@ base: components: sources: docker_logs: configuration: {
 		required:    false
 		type: bool: default: true
 	}
+	compose_metadata: {
+		description: """
+			Adds the Docker Compose metadata of the containers under `compose`.
+
+			The `project`, `service`, and `container_number` fields are read from the labels Docker
+			Compose sets on the containers it creates, and are left out for other containers.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	docker_host: {
 		description: """
 			Docker host to connect to.
@@ -80,6 +90,40 @@ base: components: sources: docker_logs: configuration: {
 		required: false
 		type: array: items: type: string: examples: ["org.opencontainers.image.vendor=Vector", "com.mycorp.internal.animal=fish"]
 	}
+	label_fields: {
+		description: """
+			A mapping of container labels to the fields their values are added as.
+
+			Like the `labels` option of Docker's logging drivers, this promotes the labels used for
+			correlation to fields of their own. All labels are still added under `label`.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				"com.docker.compose.project": "project"
+				"com.mycorp.team":            "team"
+			}]
+			options: "*": {
+				description: "The field the value of the label is added as."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
+	lifecycle_events: {
+		description: """
+			Emits the `start`, `stop`, and `die` events of the containers as log events.
+
+			The message of these events is the action, which is also added as `action`, and the exit
+			code of the container is added as `exit_code` for `die` events. They have the ID, image, and
+			name of the container, as well as the label fields and the Docker Compose metadata, so they
+			can be correlated with the logs of the container.
+
+			The events are subject to the same container filters as the logs.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	multiline: {
 		description: """
 			Multiline aggregation configuration.
@@ -22,6 +22,92 @@ base: components: sources: file: configuration: {
 			type: bool: {}
 		}
 	}
+	checkpoint_store: {
+		description: """
+			Keeps a copy of the file checkpoint positions in a shared store.
+
+			The checkpoints are restored from the store when there are none in the data directory, so
+			that an instance replacing another one, such as in an ephemeral or horizontally scaled
+			deployment, resumes where the previous instance left off.
+			"""
+		required: false
+		type: object: options: {
+			auth: {
+				description:   "Configuration of the authentication strategy for interacting with AWS services."
+				relevant_when: "type = \"aws_dynamodb\""
+				required:      false
+				type: object: {}
+			}
+			endpoint: {
+				description: """
+					The URL of the Redis endpoint to connect to.
+
+					The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
+					`redis` or `rediss` for connections secured via TLS.
+					"""
+				relevant_when: "type = \"redis\""
+				required:      true
+				type: string: examples: ["redis://127.0.0.1:6379/0"]
+			}
+			key_prefix: {
+				description:   "The prefix of the Redis keys the checkpoints are stored at."
+				relevant_when: "type = \"redis\""
+				required:      false
+				type: string: default: "vector:checkpoints:"
+			}
+			path: {
+				description: """
+					The directory the checkpoints are stored in.
+
+					Make sure the running user has write permissions to this directory.
+					"""
+				relevant_when: "type = \"local\""
+				required:      true
+				type: string: examples: ["/mnt/checkpoints"]
+			}
+			region: {
+				description: """
+					The [AWS region][aws_region] of the target service.
+
+					[aws_region]: https://docs.aws.amazon.com/general/latest/gr/rande.html#regional-endpoints
+					"""
+				relevant_when: "type = \"aws_dynamodb\""
+				required:      false
+				type: string: examples: ["us-east-1"]
+			}
+			sync_interval_secs: {
+				description: """
+					How often the checkpoints are copied to the store, in seconds.
+
+					The checkpoints are also copied when the source stops.
+					"""
+				required: false
+				type: uint: {
+					default: 5
+					unit:    "seconds"
+				}
+			}
+			table: {
+				description: """
+					The name of the table the checkpoints are stored in.
+
+					The partition key of the table must be a string attribute named `key`.
+					"""
+				relevant_when: "type = \"aws_dynamodb\""
+				required:      true
+				type: string: examples: ["vector-checkpoints"]
+			}
+			type: {
+				description: "The backend of the checkpoint store."
+				required:    true
+				type: string: enum: {
+					aws_dynamodb: "Stores the checkpoints in an AWS DynamoDB table."
+					local:        "Stores the checkpoints in a directory, such as a volume that outlives the instance."
+					redis:        "Stores the checkpoints in Redis."
+				}
+			}
+		}
+	}
 	data_dir: {
 		description: """
 			The directory used to persist file checkpoint positions.
@@ -99,7 +185,7 @@ base: components: sources: file: configuration: {
 
 					This can be helpful if all files share a common header that should be skipped.
 					"""
-				relevant_when: "strategy = \"checksum\""
+				relevant_when: "strategy = \"checksum\" or strategy = \"chain\""
 				required:      false
 				type: uint: {
 					default: 0
@@ -114,13 +200,35 @@ base: components: sources: file: configuration: {
 
 					If the file has less than this amount of lines, it won’t be read at all.
 					"""
-				relevant_when: "strategy = \"checksum\""
+				relevant_when: "strategy = \"checksum\" or strategy = \"chain\""
 				required:      false
 				type: uint: {
 					default: 1
 					unit:    "lines"
 				}
 			}
+			strategies: {
+				description:   "The strategies of the chain, from the preferred one to the last resort."
+				relevant_when: "strategy = \"chain\""
+				required:      false
+				type: array: {
+					default: ["checksum", "device_and_inode", "path"]
+					items: type: string: enum: {
+						checksum: "Compute a checksum over the first lines of the file."
+						device_and_inode: """
+							Use the [device and inode][inode] of the file.
+
+							[inode]: https://en.wikipedia.org/wiki/Inode
+							"""
+						path: """
+							Use the path of the file.
+
+							Rotated files that are renamed are identified as new files by this strategy, so it should
+							only be the last resort.
+							"""
+					}
+				}
+			}
 			strategy: {
 				description: """
 					The strategy used to uniquely identify files.
@@ -131,6 +239,17 @@ base: components: sources: file: configuration: {
 				type: string: {
 					default: "checksum"
 					enum: {
+						chain: """
+							Use a chain of strategies, identifying files with the first strategy that succeeds.
+
+							Files that can't be identified by the preferred strategies yet, such as files that are too
+							short to be checksummed, are identified by the next strategies instead of being skipped.
+							Their checkpoints are migrated, and persisted, once a preferred strategy identifies them,
+							so they are not read again when their fingerprint changes.
+
+							With the default chain, files copied or moved to another filesystem keep their checksum,
+							so they are not ingested again.
+							"""
 						checksum: "Read lines from the beginning of the file and compute a checksum over them."
 						device_and_inode: """
 															Use the [device and inode][inode] as the identifier.
@@ -323,6 +442,345 @@ base: components: sources: file: configuration: {
 		required:    false
 		type: bool: default: false
 	}
+	overrides: {
+		description: """
+			Decoding options for the files matching specific patterns.
+
+			Files matching the patterns of an override are read along with the files matching
+			`include`, and are decoded with the options of the first override matching them instead of
+			being read as plain text lines. This makes it possible to read files of different formats,
+			such as `*.json` and `*.log` files, with a single source.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				decoding: {
+					description: "Configures how events are decoded from raw bytes."
+					required:    false
+					type: object: options: {
+						avro: {
+							description:   "Apache Avro-specific decoding options."
+							relevant_when: "codec = \"avro\""
+							required:      false
+							type: object: options: {
+								format: {
+									description: "The encoding of the Avro messages."
+									required:    false
+									type: string: {
+										default: "datum"
+										enum: {
+											confluent: """
+												Each message is in the Confluent [wire format][wire_format]: a zero magic byte, followed by
+												the ID of the schema in the schema registry, as a 32-bit unsigned integer, big endian, and
+												the datum.
+
+												The datum is read with the schema fetched from `schema_registry` if set, or with `schema`
+												otherwise.
+
+												[wire_format]: https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format
+												"""
+											datum: "Each message is a bare Avro datum, written with `schema`."
+											single_object: """
+												Each message is in the [single-object encoding][single_object]: the `C3 01` marker,
+												followed by the CRC-64-AVRO fingerprint of the schema, and the datum.
+
+												Messages written with a schema other than `schema` are rejected.
+
+												[single_object]: https://avro.apache.org/docs/1.11.1/specification/#single-object-encoding
+												"""
+										}
+									}
+								}
+								schema: {
+									description: """
+										The Avro schema.
+
+										Required unless the schemas are fetched from `schema_registry`.
+										"""
+									required: false
+									type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+								}
+								schema_registry: {
+									description: """
+										Confluent-compatible schema registry options.
+
+										The schemas referenced by the messages are fetched from the registry the first time they are
+										seen, and cached for the lifetime of the source.
+
+										The schema registry is only supported by the `kafka` source, with the `confluent` format.
+										"""
+									required: false
+									type: object: options: {
+										auth: {
+											description: "Basic authentication to the schema registry."
+											required:    false
+											type: object: options: {
+												password: {
+													description: "The basic authentication password."
+													required:    true
+													type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+												}
+												user: {
+													description: "The basic authentication username."
+													required:    true
+													type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+												}
+											}
+										}
+										url: {
+											description: "The URL of the schema registry."
+											required:    true
+											type: string: examples: ["http://localhost:8081"]
+										}
+									}
+								}
+							}
+						}
+						codec: {
+							description: "The codec to use for decoding events."
+							required:    false
+							type: string: {
+								default: "bytes"
+								enum: {
+									avro: """
+										Decodes the raw bytes as an [Apache Avro][apache_avro] message.
+
+										Messages can be bare datums, or be in the single-object encoding or the Confluent wire
+										format, whose schemas can be fetched from a schema registry by the `kafka` source.
+
+										[apache_avro]: https://avro.apache.org/
+										"""
+									bytes: "Uses the raw bytes as-is."
+									csv: """
+										Decodes the raw bytes as [CSV][csv] rows.
+
+										The columns are decoded as strings into the configured fields, or into the fields named by
+										the header row of each stream.
+
+										[csv]: https://www.rfc-editor.org/rfc/rfc4180
+										"""
+									gelf: """
+										Decodes the raw bytes as a [GELF][gelf] message.
+
+										[gelf]: https://docs.graylog.org/docs/gelf
+										"""
+									json: """
+										Decodes the raw bytes as [JSON][json].
+
+										[json]: https://www.json.org/
+										"""
+									msgpack: """
+										Decodes the raw bytes as [MessagePack][msgpack].
+
+										Each frame can hold several concatenated MessagePack maps, which are decoded as separate
+										events. Binary values are decoded as bytes, and values of the timestamp extension type as
+										timestamps.
+
+										[msgpack]: https://msgpack.org/
+										"""
+									native: """
+										Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+
+										This codec is **[experimental][experimental]**.
+
+										[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+										[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+										"""
+									native_json: """
+										Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+
+										This codec is **[experimental][experimental]**.
+
+										[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+										[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+										"""
+									parquet: """
+										Decodes the raw bytes as an [Apache Parquet][apache_parquet] file.
+
+										Each row of the file is decoded as an event, reading the row groups one after the other.
+										As Parquet files can only be read as a whole, the default framing of this codec is `bytes`.
+
+										[apache_parquet]: https://parquet.apache.org/
+										"""
+									syslog: """
+										Decodes the raw bytes as a Syslog message.
+
+										Will decode either as the [RFC 3164][rfc3164]-style format ("old" style) or the more modern
+										[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+										[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+										[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+										"""
+								}
+							}
+						}
+						csv: {
+							description:   "CSV-specific decoding options."
+							relevant_when: "codec = \"csv\""
+							required:      false
+							type: object: options: {
+								delimiter: {
+									description: "The ASCII (7-bit) character that delimits the columns."
+									required:    false
+									type: uint: default: 44
+								}
+								fields: {
+									description: """
+										The fields the columns are decoded into, in the order the columns appear in the rows.
+
+										Columns beyond the last field are ignored, and fields beyond the last column of a row are
+										left out of the event.
+
+										If empty, the first row of each stream, such as each file of the `file` source or each
+										connection of the `socket` source, is read as a header row, and the columns are decoded into
+										the top-level fields named by the header row.
+										"""
+									required: false
+									type: array: {
+										default: []
+										items: type: string: examples: ["timestamp", "message"]
+									}
+								}
+								headers: {
+									description: """
+										Skips the first row of each stream as a header row.
+
+										Only relevant if `fields` is set, as the header row is always read if `fields` is empty.
+										"""
+									required: false
+									type: bool: default: false
+								}
+								quote: {
+									description: """
+										The ASCII (7-bit) character that quotes columns holding delimiters, quotes, or line breaks.
+
+										Quotes inside quoted columns are escaped by doubling them.
+										"""
+									required: false
+									type: uint: default: 34
+								}
+							}
+						}
+						native: {
+							description:   "Native-specific decoding options."
+							relevant_when: "codec = \"native\""
+							required:      false
+							type: object: options: reject_newer_versions: {
+								description: """
+									Whether to reject the events encoded with a newer version of the native format.
+
+									By default, such events are decoded on a best-effort basis, leaving out the parts of the
+									events that this version of Vector doesn't know about, so that Vector instances can be
+									upgraded in any order.
+									"""
+								required: false
+								type: bool: default: false
+							}
+						}
+						parquet: {
+							description:   "Apache Parquet-specific decoding options."
+							relevant_when: "codec = \"parquet\""
+							required:      false
+							type: object: options: fields: {
+								description: """
+									The names of the top-level columns to read.
+
+									Only these columns are read from the files, and decoded into the top-level fields of the
+									same names. If empty, all the columns are read.
+									"""
+								required: false
+								type: array: {
+									default: []
+									items: type: string: examples: ["timestamp", "message"]
+								}
+							}
+						}
+					}
+				}
+				include: {
+					description: """
+						Array of file patterns the options apply to. [Globbing](https://vector.dev/docs/reference/configuration/sources/file/#globbing) is supported.
+
+						The `exclude` option of the source applies to these patterns as well.
+						"""
+					required: true
+					type: array: items: type: string: examples: ["/var/log/**/*.json"]
+				}
+				multiline: {
+					description: """
+						Multiline aggregation configuration of the files.
+
+						If not specified, multiline aggregation is disabled for these files, regardless of the
+						`multiline` option of the source.
+						"""
+					required: false
+					type: object: options: {
+						condition_pattern: {
+							description: """
+								Regular expression pattern that is used to determine whether or not more lines should be read.
+
+								This setting must be configured in conjunction with `mode`.
+								"""
+							required: true
+							type: string: examples: ["^[\\s]+", "\\\\$", "^(INFO|ERROR) ", ";$"]
+						}
+						mode: {
+							description: """
+								Aggregation mode.
+
+								This setting must be configured in conjunction with `condition_pattern`.
+								"""
+							required: true
+							type: string: enum: {
+								continue_past: """
+									All consecutive lines matching this pattern, plus one additional line, are included in the group.
+
+									This is useful in cases where a log message ends with a continuation marker, such as a backslash, indicating
+									that the following line is part of the same message.
+									"""
+								continue_through: """
+									All consecutive lines matching this pattern are included in the group.
+
+									The first line (the line that matched the start pattern) does not need to match the `ContinueThrough` pattern.
+
+									This is useful in cases such as a Java stack trace, where some indicator in the line (such as leading
+									whitespace) indicates that it is an extension of the proceeding line.
+									"""
+								halt_before: """
+									All consecutive lines not matching this pattern are included in the group.
+
+									This is useful where a log line contains a marker indicating that it begins a new message.
+									"""
+								halt_with: """
+									All consecutive lines, up to and including the first line matching this pattern, are included in the group.
+
+									This is useful where a log line ends with a termination marker, such as a semicolon.
+									"""
+							}
+						}
+						start_pattern: {
+							description: "Regular expression pattern that is used to match the start of a new message."
+							required:    true
+							type: string: examples: ["^[\\s]+", "\\\\$", "^(INFO|ERROR) ", ";$"]
+						}
+						timeout_ms: {
+							description: """
+								The maximum amount of time to wait for the next additional line, in milliseconds.
+
+								Once this timeout is reached, the buffered message is guaranteed to be flushed, even if incomplete.
+								"""
+							required: true
+							type: uint: {
+								examples: [1000, 600000]
+								unit: "milliseconds"
+							}
+						}
+					}
+				}
+			}
+		}
+	}
 	read_from: {
 		description: "File position to use when reading a new file."
 		required:    false
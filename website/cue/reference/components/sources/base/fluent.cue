@@ -46,6 +46,31 @@ base: components: sources: fluent: configuration: {
 			type: uint: unit: "seconds"
 		}
 	}
+	proxy_protocol: {
+		description: """
+			Configuration of the [PROXY protocol][proxy_protocol] header at the start of the connections.
+
+			Configure this when Vector sits behind a load balancer sending the header, such as HAProxy or
+			an AWS Network Load Balancer, so that the peer address of the events is the address of the
+			original client instead of the load balancer. Both versions of the protocol are supported.
+
+			[proxy_protocol]: https://www.haproxy.org/download/2.8/doc/proxy-protocol.txt
+			"""
+		required: false
+		type: object: options: trusted_proxies: {
+			description: """
+				The load balancers trusted to send the header, as [CIDR][cidr] blocks.
+
+				The header is only read from the connections of these addresses, and the other connections
+				are closed, so that clients can't forge their address by sending a header themselves. The
+				connections without a valid header are closed as well.
+
+				[cidr]: https://en.wikipedia.org/wiki/Classless_Inter-Domain_Routing
+				"""
+			required: true
+			type: array: items: type: string: examples: ["10.0.0.0/8", "192.0.2.1/32", "2001:db8::/32"]
+		}
+	}
 	receive_buffer_bytes: {
 		description: """
 			The size of the receive buffer used for each connection.
@@ -128,6 +153,32 @@ base: components: sources: fluent: configuration: {
 				required: false
 				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 			}
+			reload_interval_secs: {
+				description: """
+					How often the certificate files are checked for changes, in seconds.
+
+					When the files change, new connections are made with the new certificates, while established connections are
+					kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+					yet, are retried at the next check.
+
+					If not set, the files are only loaded at startup.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			spiffe_endpoint_socket: {
+				description: """
+					Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+					When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+					domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+					API issues new ones, while established connections are kept.
+
+					[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+					"""
+				required: false
+				type: string: examples: ["/run/spire/sockets/agent.sock"]
+			}
 			verify_certificate: {
 				description: """
 					Enables certificate verification.
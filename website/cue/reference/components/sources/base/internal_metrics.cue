@@ -1,6 +1,65 @@
 package metadata
 
 base: components: sources: internal_metrics: configuration: {
+	aggregate_tags: {
+		description: """
+			The tags to aggregate the metrics over.
+
+			These tags are removed from the metrics, and the metrics which only differed by them are
+			merged: counters and gauges are summed, and histograms are combined. This reduces the
+			cardinality of the metrics of topologies holding many components.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["component_id", "component_name"]
+		}
+	}
+	exclude_metrics: {
+		description: """
+			The names of the metrics not to emit.
+
+			Names can hold glob patterns, such as `*`. Metrics matching both `include_metrics` and
+			`exclude_metrics` aren't emitted.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["*_seconds"]
+		}
+	}
+	histogram_buckets: {
+		description: """
+			The upper limits of the buckets of histograms.
+
+			Internal histograms are recorded in buckets whose upper limits are powers of two, from
+			2^-6 to 2^12. If set, the count of each of these buckets is added to the first
+			configured bucket whose upper limit is greater or equal, so limits which are powers of two
+			keep the histograms exact. A bucket without upper limit is always added.
+
+			If not set, the internal buckets are emitted as is.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: float: examples: [0.25, 1.0, 4.0]
+		}
+	}
+	include_metrics: {
+		description: """
+			The names of the metrics to emit.
+
+			Names can hold [glob patterns][glob_patterns], such as `*`. If empty, all metrics are
+			emitted, except those matching `exclude_metrics`.
+
+			[glob_patterns]: https://en.wikipedia.org/wiki/Glob_(programming)
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["component_*_total", "buffer_byte_size"]
+		}
+	}
 	namespace: {
 		description: "Overrides the default namespace for the metrics emitted by the source."
 		required:    false
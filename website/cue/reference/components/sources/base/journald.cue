@@ -34,6 +34,92 @@ base: components: sources: journald: configuration: {
 			unit:    "events"
 		}
 	}
+	checkpoint_store: {
+		description: """
+			Keeps a copy of the journal checkpoint in a shared store.
+
+			The checkpoint is restored from the store when there is none in the data directory, so that
+			an instance replacing another one, such as in an ephemeral deployment, resumes where the
+			previous instance left off.
+			"""
+		required: false
+		type: object: options: {
+			auth: {
+				description:   "Configuration of the authentication strategy for interacting with AWS services."
+				relevant_when: "type = \"aws_dynamodb\""
+				required:      false
+				type: object: {}
+			}
+			endpoint: {
+				description: """
+					The URL of the Redis endpoint to connect to.
+
+					The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
+					`redis` or `rediss` for connections secured via TLS.
+					"""
+				relevant_when: "type = \"redis\""
+				required:      true
+				type: string: examples: ["redis://127.0.0.1:6379/0"]
+			}
+			key_prefix: {
+				description:   "The prefix of the Redis keys the checkpoints are stored at."
+				relevant_when: "type = \"redis\""
+				required:      false
+				type: string: default: "vector:checkpoints:"
+			}
+			path: {
+				description: """
+					The directory the checkpoints are stored in.
+
+					Make sure the running user has write permissions to this directory.
+					"""
+				relevant_when: "type = \"local\""
+				required:      true
+				type: string: examples: ["/mnt/checkpoints"]
+			}
+			region: {
+				description: """
+					The [AWS region][aws_region] of the target service.
+
+					[aws_region]: https://docs.aws.amazon.com/general/latest/gr/rande.html#regional-endpoints
+					"""
+				relevant_when: "type = \"aws_dynamodb\""
+				required:      false
+				type: string: examples: ["us-east-1"]
+			}
+			sync_interval_secs: {
+				description: """
+					How often the checkpoints are copied to the store, in seconds.
+
+					The checkpoints are also copied when the source stops.
+					"""
+				required: false
+				type: uint: {
+					default: 5
+					unit:    "seconds"
+				}
+			}
+			table: {
+				description: """
+					The name of the table the checkpoints are stored in.
+
+					The partition key of the table must be a string attribute named `key`.
+					"""
+				relevant_when: "type = \"aws_dynamodb\""
+				required:      true
+				type: string: examples: ["vector-checkpoints"]
+			}
+			type: {
+				description: "The backend of the checkpoint store."
+				required:    true
+				type: string: enum: {
+					aws_dynamodb: "Stores the checkpoints in an AWS DynamoDB table."
+					local:        "Stores the checkpoints in a directory, such as a volume that outlives the instance."
+					redis:        "Stores the checkpoints in Redis."
+				}
+			}
+		}
+	}
 	current_boot_only: {
 		description: "Only include entries that occurred after the current boot of the system."
 		required:    false
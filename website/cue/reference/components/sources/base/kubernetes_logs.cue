@@ -11,6 +11,92 @@ base: components: sources: kubernetes_logs: configuration: {
 		required: false
 		type: bool: default: true
 	}
+	checkpoint_store: {
+		description: """
+			Keeps a copy of the file checkpoint positions in a shared store.
+
+			The checkpoints are kept separately for each Node, and restored from the store when there
+			are none in the data directory, so that a replacement Vector Pod on the same Node resumes
+			where the previous one left off.
+			"""
+		required: false
+		type: object: options: {
+			auth: {
+				description:   "Configuration of the authentication strategy for interacting with AWS services."
+				relevant_when: "type = \"aws_dynamodb\""
+				required:      false
+				type: object: {}
+			}
+			endpoint: {
+				description: """
+					The URL of the Redis endpoint to connect to.
+
+					The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
+					`redis` or `rediss` for connections secured via TLS.
+					"""
+				relevant_when: "type = \"redis\""
+				required:      true
+				type: string: examples: ["redis://127.0.0.1:6379/0"]
+			}
+			key_prefix: {
+				description:   "The prefix of the Redis keys the checkpoints are stored at."
+				relevant_when: "type = \"redis\""
+				required:      false
+				type: string: default: "vector:checkpoints:"
+			}
+			path: {
+				description: """
+					The directory the checkpoints are stored in.
+
+					Make sure the running user has write permissions to this directory.
+					"""
+				relevant_when: "type = \"local\""
+				required:      true
+				type: string: examples: ["/mnt/checkpoints"]
+			}
+			region: {
+				description: """
+					The [AWS region][aws_region] of the target service.
+
+					[aws_region]: https://docs.aws.amazon.com/general/latest/gr/rande.html#regional-endpoints
+					"""
+				relevant_when: "type = \"aws_dynamodb\""
+				required:      false
+				type: string: examples: ["us-east-1"]
+			}
+			sync_interval_secs: {
+				description: """
+					How often the checkpoints are copied to the store, in seconds.
+
+					The checkpoints are also copied when the source stops.
+					"""
+				required: false
+				type: uint: {
+					default: 5
+					unit:    "seconds"
+				}
+			}
+			table: {
+				description: """
+					The name of the table the checkpoints are stored in.
+
+					The partition key of the table must be a string attribute named `key`.
+					"""
+				relevant_when: "type = \"aws_dynamodb\""
+				required:      true
+				type: string: examples: ["vector-checkpoints"]
+			}
+			type: {
+				description: "The backend of the checkpoint store."
+				required:    true
+				type: string: enum: {
+					aws_dynamodb: "Stores the checkpoints in an AWS DynamoDB table."
+					local:        "Stores the checkpoints in a directory, such as a volume that outlives the instance."
+					redis:        "Stores the checkpoints in Redis."
+				}
+			}
+		}
+	}
 	data_dir: {
 		description: """
 			The directory used to persist file checkpoint positions.
@@ -359,6 +445,73 @@ base: components: sources: kubernetes_logs: configuration: {
 			}
 		}
 	}
+	quotas: {
+		description: """
+			Configuration of the ingestion quotas.
+
+			The quotas are applied to the lines read from the log files, before they are parsed and partial
+			events are merged. Lines are checked against the quota of their Pod first, then of their
+			Namespace, and of the Node.
+			"""
+		required: false
+		type: object: options: {
+			namespace_lines_per_second: {
+				description: """
+					The maximum number of lines read per second from the log files of the Pods of each
+					Namespace.
+					"""
+				required: false
+				type: uint: examples: [2000]
+			}
+			namespace_overrides: {
+				description: """
+					The maximum number of lines read per second from the log files of the Pods of specific
+					Namespaces, overriding `namespace_lines_per_second`.
+					"""
+				required: false
+				type: object: {
+					examples: [{
+						"kube-system": 5000
+					}]
+					options: "*": {
+						description: "The maximum number of lines read per second from the Namespace."
+						required:    true
+						type: uint: {}
+					}
+				}
+			}
+			node_lines_per_second: {
+				description: "The maximum number of lines read per second from the log files of all Pods of the Node."
+				required:    false
+				type: uint: examples: [10000]
+			}
+			pod_lines_per_second: {
+				description: "The maximum number of lines read per second from the log files of each Pod."
+				required:    false
+				type: uint: examples: [500]
+			}
+			shedding: {
+				description: "What to do with the lines exceeding a quota."
+				required:    false
+				type: string: {
+					default: "drop"
+					enum: {
+						backpressure: """
+							Wait for the quota to allow the lines.
+
+							No line is lost, but waiting for the quota of a Pod or a Namespace also delays the reading
+							of the log files of the other Pods.
+							"""
+						drop: """
+							Drop the lines exceeding a quota.
+
+							The checkpoints still move past the dropped lines, so they are never read again.
+							"""
+					}
+				}
+			}
+		}
+	}
 	read_from: {
 		description: "File position to use when reading a new file."
 		required:    false
@@ -102,6 +102,32 @@ base: components: sources: opentelemetry: configuration: {
 							required: false
 							type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 						}
+						reload_interval_secs: {
+							description: """
+								How often the certificate files are checked for changes, in seconds.
+
+								When the files change, new connections are made with the new certificates, while established connections are
+								kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+								yet, are retried at the next check.
+
+								If not set, the files are only loaded at startup.
+								"""
+							required: false
+							type: uint: unit: "seconds"
+						}
+						spiffe_endpoint_socket: {
+							description: """
+								Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+								When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+								domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+								API issues new ones, while established connections are kept.
+
+								[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+								"""
+							required: false
+							type: string: examples: ["/run/spire/sockets/agent.sock"]
+						}
 						verify_certificate: {
 							description: """
 																Enables certificate verification.
@@ -217,6 +243,32 @@ base: components: sources: opentelemetry: configuration: {
 							required: false
 							type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 						}
+						reload_interval_secs: {
+							description: """
+								How often the certificate files are checked for changes, in seconds.
+
+								When the files change, new connections are made with the new certificates, while established connections are
+								kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+								yet, are retried at the next check.
+
+								If not set, the files are only loaded at startup.
+								"""
+							required: false
+							type: uint: unit: "seconds"
+						}
+						spiffe_endpoint_socket: {
+							description: """
+								Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+								When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+								domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+								API issues new ones, while established connections are kept.
+
+								[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+								"""
+							required: false
+							type: string: examples: ["/run/spire/sockets/agent.sock"]
+						}
 						verify_certificate: {
 							description: """
 																Enables certificate verification.
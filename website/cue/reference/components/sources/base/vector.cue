@@ -31,6 +31,67 @@ base: components: sources: vector: configuration: {
 		required: true
 		type: string: {}
 	}
+	allowed_client_sans: {
+		description: """
+			The subject alternative names of the clients allowed to send events.
+
+			If set, requests are only accepted from clients whose certificate holds one of these DNS
+			names, IP addresses, URIs, or email addresses. This requires `tls.verify_certificate` to be
+			enabled, so that clients must present a certificate signed by a trusted authority.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["agent.example.com", "spiffe://example.com/agent"]
+		}
+	}
+	http2: {
+		description: "HTTP/2 settings of the gRPC server."
+		required:    false
+		type: object: options: {
+			initial_connection_window_size: {
+				description: "The initial flow control window of connections."
+				required:    false
+				type: uint: {
+					examples: [4194304]
+					unit: "bytes"
+				}
+			}
+			initial_stream_window_size: {
+				description: """
+					The initial flow control window of streams.
+
+					Larger windows help throughput over links with high latency.
+					"""
+				required: false
+				type: uint: {
+					examples: [1048576]
+					unit: "bytes"
+				}
+			}
+			keepalive_interval_secs: {
+				description: """
+					The interval between the pings sent to clients to keep their connections alive.
+
+					Connections whose client doesn't acknowledge a ping within `keepalive_timeout_secs` are
+					closed. If not set, no pings are sent.
+					"""
+				required: false
+				type: uint: {
+					examples: [60]
+					unit: "seconds"
+				}
+			}
+			keepalive_timeout_secs: {
+				description: "How long to wait for the acknowledgement of a ping before closing the connection."
+				required:    false
+				type: uint: {
+					default: 20
+					unit:    "seconds"
+				}
+			}
+		}
+	}
 	tls: {
 		description: "Configures the TLS options for incoming/outgoing connections."
 		required:    false
@@ -94,6 +155,32 @@ base: components: sources: vector: configuration: {
 				required: false
 				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 			}
+			reload_interval_secs: {
+				description: """
+					How often the certificate files are checked for changes, in seconds.
+
+					When the files change, new connections are made with the new certificates, while established connections are
+					kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+					yet, are retried at the next check.
+
+					If not set, the files are only loaded at startup.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			spiffe_endpoint_socket: {
+				description: """
+					Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+					When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+					domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+					API issues new ones, while established connections are kept.
+
+					[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+					"""
+				required: false
+				type: string: examples: ["/run/spire/sockets/agent.sock"]
+			}
 			verify_certificate: {
 				description: """
 					Enables certificate verification.
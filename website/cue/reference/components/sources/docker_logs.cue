@@ -193,6 +193,20 @@ components: sources: docker_logs: {
 	]
 
 	how_it_works: {
+		lifecycle_events: {
+			title: "Container Lifecycle Events"
+			body: """
+				With `lifecycle_events` enabled, the `start`, `stop`, and `die` events of the
+				containers are emitted as log events alongside the logs of the containers. Their
+				message is the action, which is also added as `action`, and `die` events carry the
+				exit code of the container as `exit_code`.
+
+				Lifecycle events have the `container_id`, `container_name`, and `image` fields of the
+				logs, as well as the fields configured with `label_fields` and, if
+				`compose_metadata` is enabled, the `compose` fields, so that a container exiting can
+				be correlated with its last logs.
+				"""
+		}
 		message_merging: {
 			title: "Merging Split Messages"
 			body: """
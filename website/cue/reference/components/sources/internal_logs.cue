@@ -73,6 +73,43 @@ components: sources: internal_logs: {
 				required:    true
 				type: "*": {}
 			}
+			error_type: {
+				description: "The type of the error, for the logs reporting component errors."
+				required:    false
+				type: string: {
+					examples: ["request_failed"]
+				}
+			}
+			vector: {
+				description: "The fields of the component that emitted the log, if any."
+				required:    false
+				type: object: {
+					examples: []
+					options: {
+						component_id: {
+							description: "The ID of the component."
+							required:    true
+							type: string: {
+								examples: ["my_source"]
+							}
+						}
+						component_kind: {
+							description: "The kind of the component."
+							required:    true
+							type: string: {
+								examples: ["source"]
+							}
+						}
+						component_type: {
+							description: "The type of the component."
+							required:    true
+							type: string: {
+								examples: ["file"]
+							}
+						}
+					}
+				}
+			}
 			metadata: {
 				description: "Metadata from the source log or trace event."
 				required:    true
@@ -109,6 +146,38 @@ components: sources: internal_logs: {
 								examples: ["vector::internal_events::heartbeat"]
 							}
 						}
+						rate_limit: {
+							description: """
+								The rate limiting of the log, set on the logs emitted in place of rate limited logs.
+								"""
+							required: false
+							type: object: {
+								examples: []
+								options: {
+									active: {
+										description: "Whether the log is still being rate limited."
+										required:    true
+										type: bool: {}
+									}
+									period_secs: {
+										description: "The length of the rate limiting period."
+										required:    true
+										type: uint: {
+											examples: [10]
+											unit: "seconds"
+										}
+									}
+									suppressed_count: {
+										description: "The number of times the log was suppressed in the current period."
+										required:    true
+										type: uint: {
+											examples: [9]
+											unit: null
+										}
+									}
+								}
+							}
+						}
 						target: {
 							description: "Describes the part of the system where the span or event that this metadata describes occurred."
 							required:    true
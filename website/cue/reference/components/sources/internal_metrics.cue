@@ -78,6 +78,30 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		audit_log_entries_total: {
+			description:       "The total number of entries written to the audit log, when they're emitted as internal events."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
+		audit_log_write_errors_total: {
+			description:       "The total number of errors writing entries to the audit log."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
+		capture_write_errors_total: {
+			description:       "The total number of errors writing the events captured from a source."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		checkpoint_store_errors_total: {
+			description:       "The total number of errors copying checkpoints to the checkpoint store."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		config_load_errors_total: {
 			description:       "The total number of errors loading the Vector configuration."
 			type:              "counter"
@@ -144,6 +168,21 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		self_telemetry_export_errors_total: {
+			description:       "The total number of errors exporting Vector's own telemetry to an OpenTelemetry collector."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _internal_metrics_tags & {
+				signal: {
+					description: "The kind of telemetry that failed to be exported."
+					required:    true
+					enum: {
+						metrics: "Internal metrics."
+						traces:  "Component spans."
+					}
+				}
+			}
+		}
 		started_total: {
 			description:       "The total number of times the Vector instance has been started."
 			type:              "counter"
@@ -165,6 +204,32 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		adaptive_concurrency_current_limit: {
+			description:       "The concurrency limit currently applied by the adaptive concurrency feature."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		adaptive_concurrency_decisions_total: {
+			description:       "The total number of adjustments of the concurrency limit decided on by the adaptive concurrency feature, at the end of each window."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				decision: {
+					description: "The adjustment decided on."
+					required:    true
+					enum: {
+						increase: "The concurrency limit was increased."
+						decrease: "The concurrency limit was decreased."
+						hold:     "The concurrency limit was left unchanged."
+					}
+				}
+				pinned: {
+					description: "Whether the concurrency limit is pinned, in which case the decision was not applied."
+					required:    true
+				}
+			}
+		}
 		adaptive_concurrency_in_flight: {
 			description:       "The number of outbound requests currently awaiting a response."
 			type:              "histogram"
@@ -183,6 +248,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		adaptive_concurrency_past_rtt_deviation: {
+			description:       "The deviation of the past round-trip times (RTT) that the current window is compared against."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		checkpoint_write_errors_total: {
 			description:       "The total number of errors writing checkpoints. This metric is deprecated in favor of `component_errors_total`."
 			type:              "counter"
@@ -203,6 +274,28 @@ components: sources: internal_metrics: {
 				file: _file
 			}
 		}
+		circuit_breaker_dead_lettered_events_total: {
+			description:       "The total number of events sent to the dead letter queue by a sink's circuit breaker, while the circuit was open."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		circuit_breaker_state: {
+			description:       "The state of a sink's circuit breaker. The gauge is `1` for the current state, and `0` for the others."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				state: _circuit_breaker_state
+			}
+		}
+		circuit_breaker_transitions_total: {
+			description:       "The total number of times a sink's circuit breaker changed state."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				state: _circuit_breaker_state
+			}
+		}
 		collect_completed_total: {
 			description:       "The total number of metrics collections completed for this component."
 			type:              "counter"
@@ -360,6 +453,45 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		kubernetes_logs_quota_exceeded_total: {
+			description:       "The total number of lines read from Kubernetes log files in excess of an ingestion quota."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				scope: {
+					description: "The scope of the exceeded quota."
+					required:    true
+					enum: {
+						namespace: "The quota of the Namespace of the Pod."
+						node:      "The quota of the Node."
+						pod:       "The quota of the Pod."
+					}
+				}
+				pod_name: {
+					description: "The name of the Pod the line was read from."
+					required:    true
+				}
+				pod_namespace: {
+					description: "The Namespace of the Pod the line was read from."
+					required:    true
+				}
+			}
+		}
+		end_to_end_latency_seconds: {
+			description: """
+				The time from when a source emitted an event to when this sink acknowledged it. Only
+				recorded when the global `end_to_end_latency` option is enabled.
+				"""
+			type:              "histogram"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				source_component_id: {
+					description: "The ID of the source that emitted the event."
+					required:    true
+					examples: ["my_source"]
+				}
+			}
+		}
 		encode_errors_total: {
 			description:       "The total number of errors encountered when encoding an event."
 			type:              "counter"
@@ -424,6 +556,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		buffer_oldest_event_age_seconds: {
+			description:       "The age of the oldest event currently in the buffer, measured with a precision of a few seconds."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		buffer_discarded_events_total: {
 			description:       "The number of events dropped by this non-blocking buffer."
 			type:              "counter"
@@ -454,6 +592,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		component_cpu_time_nanoseconds_total: {
+			description:       "The total CPU time, in nanoseconds, spent running the tasks of this component. Only reported when Vector runs with `--cpu-time-accounting`, on Unix."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		component_discarded_events_total: {
 			description:       "The number of events dropped by this component."
 			type:              "counter"
@@ -469,6 +613,18 @@ components: sources: internal_metrics: {
 				stage:      _stage
 			}
 		}
+		component_memory_limit_reached_total: {
+			description:       "The total number of times this component reached its `max_allocated_bytes` limit and paused its input."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		component_queued_events: {
+			description:       "The number of events waiting in the input of this transform. This value is updated every 5 seconds."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		component_received_bytes_total: {
 			description:       string | *"The number of raw bytes accepted by this component from source origins."
 			type:              "counter"
@@ -782,6 +938,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		http_compression_fallbacks_total: {
+			description:       "The total number of times an HTTP sink fell back to another compression after the endpoint rejected the compression of its requests."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		http_error_response_total: {
 			description:       "The total number of HTTP error responses for this component."
 			type:              "counter"
@@ -812,6 +974,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		leader_election_errors_total: {
+			description:       "The total number of errors acquiring or renewing the leader election lock of a source."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		leader_election_is_leader: {
+			description:       "Whether this instance is the elected leader running the source, `1` if it is and `0` otherwise."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		logging_driver_errors_total: {
 			description: """
 				The total number of logging driver errors encountered caused by not using either
@@ -845,6 +1019,14 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		oversized_events_total: {
+			description:       "The total number of events over the size budget of a sink, by how they were handled."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				action: _oversized_event_action
+			}
+		}
 		parse_errors_total: {
 			description:       "The total number of errors parsing metrics for this component."
 			type:              "counter"
@@ -1020,6 +1202,26 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		stale_events_shed_total: {
+			description:       "The total number of events shed by a sink for being older than its maximum event age."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				fallback: _stale_events_fallback
+			}
+		}
+		state_store_byte_size: {
+			description:       "The size in bytes of the persisted state of a stateful transform, as of its last checkpoint."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		state_store_entries: {
+			description:       "The number of entries in the persisted state of a stateful transform, as of its last checkpoint."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		stdin_reads_failed_total: {
 			description:       "The total number of errors reading from stdin."
 			type:              "counter"
@@ -1035,6 +1237,17 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		tenant_events_discarded_total: {
+			description:       "The total number of events discarded because their tenant's `buffer_bytes` quota was exceeded."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				tenant: {
+					description: "The tenant the discarded events belong to."
+					required:    true
+				}
+			}
+		}
 		timestamp_parse_errors_total: {
 			description:       "The total number of errors encountered parsing [RFC 3339](\(urls.rfc_3339)) timestamps."
 			type:              "counter"
@@ -1168,6 +1381,15 @@ components: sources: internal_metrics: {
 			description: "Which collector this metric comes from."
 			required:    true
 		}
+		_circuit_breaker_state: {
+			description: "The state of the circuit breaker."
+			required:    true
+			enum: {
+				closed:    "Requests are sent normally."
+				open:      "Requests are failed without being sent, and events are diverted to the fallback."
+				half_open: "A single request is sent to probe whether the downstream service has recovered."
+			}
+		}
 		_component_kind: {
 			description: "The Vector component kind."
 			required:    true
@@ -1248,6 +1470,15 @@ components: sources: internal_metrics: {
 			description: "The specific output of the component."
 			required:    false
 		}
+		_oversized_event_action: {
+			description: "How the event over the size budget was handled."
+			required:    true
+			enum: {
+				dropped:   "The event was dropped."
+				split:     "The event was split into smaller events."
+				truncated: "A field of the event was truncated."
+			}
+		}
 		_stage: {
 			description: "The stage within the component at which the error occurred."
 			required:    true
@@ -1257,6 +1488,14 @@ components: sources: internal_metrics: {
 				sending:    "While sending data."
 			}
 		}
+		_stale_events_fallback: {
+			description: "What was done with the stale events."
+			required:    true
+			enum: {
+				drop:              "The events were dropped."
+				dead_letter_queue: "The events were sent to the dead letter queue sink."
+			}
+		}
 		_status: {
 			description: "The HTTP status code of the request."
 			required:    false
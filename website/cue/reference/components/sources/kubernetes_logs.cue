@@ -278,6 +278,21 @@ components: sources: kubernetes_logs: {
 				"""
 		}
 
+		quotas: {
+			title: "Ingestion quotas"
+			body: """
+				A few busy Pods can take up the whole throughput of Vector on a Node, delaying the
+				collection of the logs of every other workload. The `quotas` option limits the number of
+				lines read per second from each Pod, from the Pods of each Namespace, and from the whole
+				Node.
+
+				By default, lines in excess of a quota are dropped, and counted by the
+				`kubernetes_logs_quota_exceeded_total` and `component_discarded_events_total` metrics.
+				With the `backpressure` shedding policy, Vector waits for the quota instead, without losing
+				lines, at the cost of delaying the logs of the other Pods too.
+				"""
+		}
+
 		globbing: {
 			title: "Globbing"
 			body:  """
@@ -469,6 +484,7 @@ components: sources: kubernetes_logs: {
 		k8s_watch_stream_failed_total:          components.sources.internal_metrics.output.metrics.k8s_watch_stream_failed_total
 		k8s_watch_stream_items_obtained_total:  components.sources.internal_metrics.output.metrics.k8s_watch_stream_items_obtained_total
 		k8s_watcher_http_error_total:           components.sources.internal_metrics.output.metrics.k8s_watcher_http_error_total
+		kubernetes_logs_quota_exceeded_total:   components.sources.internal_metrics.output.metrics.kubernetes_logs_quota_exceeded_total
 		processed_bytes_total:                  components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:                 components.sources.internal_metrics.output.metrics.processed_events_total
 		component_discarded_events_total:       components.sources.internal_metrics.output.metrics.component_discarded_events_total
@@ -31,6 +31,38 @@ base: components: transforms: aws_ec2_metadata: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			auth: {
+				description: """
+					Authentication to the proxy endpoints.
+
+					Overrides the credentials of the proxy endpoint URIs, if any.
+					"""
+				required: false
+				type: object: options: {
+					password: {
+						description: "The basic authentication password."
+						required:    true
+						type: string: examples: ["${PROXY_PASSWORD}", "password"]
+					}
+					strategy: {
+						description: "The authentication strategy to use."
+						required:    true
+						type: string: enum: basic: """
+							Basic authentication.
+
+							The username and password are concatenated and encoded via [base64][base64], and sent in the
+							`Proxy-Authorization` header.
+
+							[base64]: https://en.wikipedia.org/wiki/Base64
+							"""
+					}
+					user: {
+						description: "The basic authentication username."
+						required:    true
+						type: string: examples: ["${PROXY_USERNAME}", "username"]
+					}
+				}
+			}
 			enabled: {
 				description: "Enables proxying support."
 				required:    false
@@ -76,6 +108,122 @@ base: components: transforms: aws_ec2_metadata: configuration: {
 					items: type: string: examples: ["localhost", ".foo.bar", "*"]
 				}
 			}
+			tls: {
+				description: """
+					TLS configuration of the connections to the proxy endpoints using `https`.
+
+					Only applies to the connections to the proxy itself, such as to trust the certificate of the proxy with a
+					custom CA, while the connections tunneled through the proxy use the TLS configuration of the component.
+					"""
+				required: false
+				type: object: options: {
+					alpn_protocols: {
+						description: """
+							Sets the list of supported ALPN protocols.
+
+							Declare the supported ALPN protocols, which are used during negotiation with peer. Prioritized in the order
+							they are defined.
+							"""
+						required: false
+						type: array: items: type: string: examples: ["h2"]
+					}
+					ca_file: {
+						description: """
+							Absolute path to an additional CA certificate file.
+
+							The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/certificate_authority.crt"]
+					}
+					crt_file: {
+						description: """
+							Absolute path to a certificate file used to identify this server.
+
+							The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+							an inline string in PEM format.
+
+							If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.crt"]
+					}
+					key_file: {
+						description: """
+							Absolute path to a private key file used to identify this server.
+
+							The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.key"]
+					}
+					key_pass: {
+						description: """
+							Passphrase used to unlock the encrypted key file.
+
+							This has no effect unless `key_file` is set.
+							"""
+						required: false
+						type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+					}
+					reload_interval_secs: {
+						description: """
+							How often the certificate files are checked for changes, in seconds.
+
+							When the files change, new connections are made with the new certificates, while established connections are
+							kept. Changes to files that don't make a valid identity together, such as a certificate whose key isn't updated
+							yet, are retried at the next check.
+
+							If not set, the files are only loaded at startup.
+							"""
+						required: false
+						type: uint: unit: "seconds"
+					}
+					spiffe_endpoint_socket: {
+						description: """
+							Path to the Unix socket of the [SPIFFE Workload API][spiffe_workload_api] to get the certificates from.
+
+							When set, the X.509 SVID of the workload is used as the identity certificate, and the trust bundle of its trust
+							domain as the CA certificates, instead of `crt_file`, `key_file`, and `ca_file`. SVIDs are rotated as the Workload
+							API issues new ones, while established connections are kept.
+
+							[spiffe_workload_api]: https://spiffe.io/docs/latest/spiffe-about/spiffe-concepts/#spiffe-workload-api
+							"""
+						required: false
+						type: string: examples: ["/run/spire/sockets/agent.sock"]
+					}
+					verify_certificate: {
+						description: """
+							Enables certificate verification.
+
+							If enabled, certificates must be valid in terms of not being expired, as well as being issued by a trusted
+							issuer. This verification operates in a hierarchical manner, checking that not only the leaf certificate (the
+							certificate presented by the client/server) is valid, but also that the issuer of that certificate is valid, and
+							so on until reaching a root certificate.
+
+							Relevant for both incoming and outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+							"""
+						required: false
+						type: bool: {}
+					}
+					verify_hostname: {
+						description: """
+							Enables hostname verification.
+
+							If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+							the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+							Only relevant for outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+							"""
+						required: false
+						type: bool: {}
+					}
+				}
+			}
 		}
 	}
 	refresh_interval_secs: {
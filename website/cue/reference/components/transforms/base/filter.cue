@@ -1,11 +1,32 @@
 package metadata
 
-base: components: transforms: filter: configuration: condition: {
-	description: """
-		The condition that every input event is matched against.
+base: components: transforms: filter: configuration: {
+	condition: {
+		description: """
+			The condition that every input event is matched against.
 
-		If an event is matched by the condition, it is forwarded. Otherwise, the event is dropped.
-		"""
-	required: true
-	type: condition: {}
+			If an event is matched by the condition, it is forwarded. Otherwise, the event is dropped.
+			"""
+		required: true
+		type: condition: {}
+	}
+	trace_scope: {
+		description: """
+			The scope trace events are filtered at.
+
+			When set to `span`, the condition is matched against each span of trace events, and only the
+			spans it matches are forwarded. Trace events left without any span are dropped.
+			"""
+		required: false
+		type: string: {
+			default: "trace"
+			enum: {
+				span: """
+					Each span of a trace event is processed on its own, with the fields of the span at the root
+					of the event.
+					"""
+				trace: "Each trace event is processed as a whole, with the fields of the trace at the root of the event."
+			}
+		}
+	}
 }
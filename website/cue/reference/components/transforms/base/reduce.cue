@@ -100,4 +100,51 @@ base: components: transforms: reduce: configuration: {
 		required: false
 		type: condition: {}
 	}
-}
+	state: {
+		description: """
+			Persists the events of the groups that haven't been flushed yet, so that they're reduced
+			together with the events that follow them if Vector exits without stopping the transform.
+			"""
+		required: false
+		type: object: options: {
+			checkpoint_interval_secs: {
+				description: """
+					How often the state is checkpointed to disk, in seconds.
+
+					The state is also checkpointed when the transform is stopped.
+					"""
+				required: false
+				type: uint: {
+					default: 10
+					unit:    "seconds"
+				}
+			}
+			data_dir: {
+				description: """
+					The directory used to persist the state.
+
+					By default, the [global `data_dir` option][global_data_dir] is used. Make sure the running
+					user has write permissions to this directory.
+
+					[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+					"""
+				required: false
+				type: string: examples: ["/var/lib/vector"]
+			}
+			enabled: {
+				description: "Whether or not the state of the transform is persisted, so that it survives restarts."
+				required:    false
+				type: bool: default: false
+			}
+			max_bytes: {
+				description: """
+					The maximum size of a checkpoint of the state, in bytes.
+
+					Only the most recently used entries that fit within this size are checkpointed, so the
+					state restored after a restart may be smaller than the state that was held in memory.
+					"""
+				required: false
+				type: uint: unit: "bytes"
+			}
+		}
+	}
@@ -119,4 +119,25 @@ base: components: transforms: remap: configuration: {
 		required: false
 		type: string: examples: ["local", "America/New_York", "EST5EDT"]
 	}
+	trace_scope: {
+		description: """
+			The scope trace events are remapped at.
+
+			When set to `span`, the program is run on each span of trace events, with the fields of the
+			span at the root of the event. A span the program fails or aborts on is kept unmodified, or
+			removed from the trace if `drop_on_error` or `drop_on_abort` apply, as dropped spans are not
+			rerouted.
+			"""
+		required: false
+		type: string: {
+			default: "trace"
+			enum: {
+				span: """
+					Each span of a trace event is processed on its own, with the fields of the span at the root
+					of the event.
+					"""
+				trace: "Each trace event is processed as a whole, with the fields of the trace at the root of the event."
+			}
+		}
+	}
 }
@@ -1,20 +1,42 @@
 package metadata
 
-base: components: transforms: route: configuration: route: {
-	description: """
-		A table of route identifiers to logical conditions representing the filter of the route.
+base: components: transforms: route: configuration: {
+	route: {
+		description: """
+			A table of route identifiers to logical conditions representing the filter of the route.
 
-		Each route can then be referenced as an input by other components with the name
-		`<transform_name>.<route_id>`. If an event doesn’t match any route, it will be sent to the
-		`<transform_name>._unmatched` output.
+			Each route can then be referenced as an input by other components with the name
+			`<transform_name>.<route_id>`. If an event doesn’t match any route, it will be sent to the
+			`<transform_name>._unmatched` output.
 
-		Both `_unmatched`, as well as `_default`, are reserved output names and thus cannot be used
-		as a route name.
-		"""
-	required: false
-	type: object: options: "*": {
-		description: "An individual route."
-		required:    true
-		type: condition: {}
+			Both `_unmatched`, as well as `_default`, are reserved output names and thus cannot be used
+			as a route name.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "An individual route."
+			required:    true
+			type: condition: {}
+		}
+	}
+	trace_scope: {
+		description: """
+			The scope trace events are routed at.
+
+			When set to `span`, the conditions are matched against each span of trace events, and each
+			route is sent the trace holding only the spans it matches. The spans not matching any route
+			are sent to the `<transform_name>._unmatched` output.
+			"""
+		required: false
+		type: string: {
+			default: "trace"
+			enum: {
+				span: """
+					Each span of a trace event is processed on its own, with the fields of the span at the root
+					of the event.
+					"""
+				trace: "Each trace event is processed as a whole, with the fields of the trace at the root of the event."
+			}
+		}
 	}
 }
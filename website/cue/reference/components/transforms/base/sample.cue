@@ -28,4 +28,24 @@ base: components: transforms: sample: configuration: {
 		required: true
 		type: uint: {}
 	}
+	trace_scope: {
+		description: """
+			The scope trace events are sampled at.
+
+			When set to `span`, each span of trace events is sampled on its own, with `key_field` and
+			`exclude` applied to the fields of the span, and only the sampled spans are forwarded. Trace
+			events left without any span are dropped.
+			"""
+		required: false
+		type: string: {
+			default: "trace"
+			enum: {
+				span: """
+					Each span of a trace event is processed on its own, with the fields of the span at the root
+					of the event.
+					"""
+				trace: "Each trace event is processed as a whole, with the fields of the trace at the root of the event."
+			}
+		}
+	}
 }
@@ -0,0 +1,117 @@
+package metadata
+
+base: components: transforms: tenant_quota: configuration: {
+	default_quota: {
+		description: "The quota applied to tenants that are not listed in `tenants`."
+		required:    false
+		type: object: options: {
+			buffer_bytes: {
+				description: """
+					The maximum size, in bytes, of the events held for the tenant while they wait to be
+					forwarded.
+
+					Events received for a tenant whose held events already reach this size are dropped.
+					"""
+				required: false
+				type: uint: {
+					default: 10485760
+					unit:    "bytes"
+				}
+			}
+			events_per_sec: {
+				description: """
+					The maximum number of events per second forwarded for the tenant.
+
+					Up to one second's worth of events may be forwarded in a burst. If not specified, the
+					number of events forwarded for the tenant is not limited.
+					"""
+				required: false
+				type: uint: unit: "events"
+			}
+		}
+	}
+	fallback_tenant: {
+		description: "The tenant that events are assigned to when `tenant_key` cannot be rendered."
+		required:    false
+		type: string: default: "default"
+	}
+	max_buffered_bytes: {
+		description: """
+			The maximum total size, in bytes, of the events held for all tenants.
+
+			Once the events held reach this size, the transform stops reading events from its inputs
+			until some of them are forwarded.
+			"""
+		required: false
+		type: uint: {
+			default: 104857600
+			unit:    "bytes"
+		}
+	}
+	max_tenants: {
+		description: """
+			The maximum number of tenants tracked at once.
+
+			A tenant is tracked while it has events held, and until its `events_per_sec` quota has
+			fully recovered. Events of other tenants received while this many tenants are tracked are
+			dropped.
+			"""
+		required: false
+		type: uint: default: 10000
+	}
+	tag_field: {
+		description: """
+			The field to tag each event with the name of its tenant.
+
+			For metrics, this is the name of the tag to set instead. If not specified, events are
+			not tagged.
+			"""
+		required: false
+		type: string: examples: ["tenant"]
+	}
+	tenant_key: {
+		description: """
+			The key identifying the tenant an event belongs to.
+
+			Events for which the key cannot be rendered are assigned to `fallback_tenant`.
+			"""
+		required: true
+		type: string: {
+			examples: ["{{ tenant_id }}", "{{ kubernetes.pod_namespace }}"]
+			syntax: "template"
+		}
+	}
+	tenants: {
+		description: "Quotas for specific tenants, keyed by tenant name."
+		required:    false
+		type: object: options: "*": {
+			description: "The quota for a tenant."
+			required:    true
+			type: object: options: {
+				buffer_bytes: {
+					description: """
+						The maximum size, in bytes, of the events held for the tenant while they wait to be
+						forwarded.
+
+						Events received for a tenant whose held events already reach this size are dropped.
+						"""
+					required: false
+					type: uint: {
+						default: 10485760
+						unit:    "bytes"
+					}
+				}
+				events_per_sec: {
+					description: """
+						The maximum number of events per second forwarded for the tenant.
+
+						Up to one second's worth of events may be forwarded in a burst. If not specified, the
+						number of events forwarded for the tenant is not limited.
+						"""
+					required: false
+					type: uint: unit: "events"
+				}
+			}
+		}
+	}
+}
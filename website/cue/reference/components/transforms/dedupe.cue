@@ -83,6 +83,23 @@ components: transforms: dedupe: {
 				"""
 		}
 
+		persisted_state: {
+			title: "Persisted State"
+			body: """
+				By default, the cache only lives in memory and is empty when Vector
+				restarts, so an event that was seen just before a restart is not
+				recognized as a duplicate just after it. Setting `state.enabled`
+				persists the cache to a `state.json` file in a subdirectory of the
+				data directory named after the component, which is checkpointed
+				every `state.checkpoint_interval_secs` and when the transform stops,
+				and restored when the transform starts.
+
+				`state.max_bytes` bounds the size of the persisted state. Once it is
+				reached, the least recently seen entries are no longer persisted,
+				even while they remain in the in-memory cache.
+				"""
+		}
+
 		missing_fields: {
 			title: "Missing Fields"
 			body: """
@@ -96,5 +113,7 @@ components: transforms: dedupe: {
 
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+		state_store_byte_size:  components.sources.internal_metrics.output.metrics.state_store_byte_size
+		state_store_entries:    components.sources.internal_metrics.output.metrics.state_store_entries
 	}
 }
@@ -144,7 +144,31 @@ components: transforms: reduce: {
 		},
 	]
 
+	how_it_works: {
+		persisted_state: {
+			title: "Persisted State"
+			body: """
+				Events that are being reduced only live in memory. Groups that
+				haven't been flushed yet are flushed when the transform stops, but
+				are lost if Vector exits without stopping it, for example when it
+				crashes. Setting `state.enabled` keeps the events of each group
+				until it's flushed, and checkpoints them to a `state.json` file in a
+				subdirectory of the data directory named after the component every
+				`state.checkpoint_interval_secs`. When the transform starts, the
+				checkpointed events are reduced again, so the groups carry on where
+				they left off.
+
+				Groups flushed after the last checkpoint are reduced and sent again
+				after such a restart, and keeping the events of each group increases
+				the memory used by the transform. `state.max_bytes` bounds the size
+				of the persisted state, keeping the most recently updated groups.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		stale_events_flushed_total: components.sources.internal_metrics.output.metrics.stale_events_flushed_total
+		state_store_byte_size:      components.sources.internal_metrics.output.metrics.state_store_byte_size
+		state_store_entries:        components.sources.internal_metrics.output.metrics.state_store_entries
 	}
 }
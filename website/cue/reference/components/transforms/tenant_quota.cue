@@ -0,0 +1,98 @@
+package metadata
+
+components: transforms: tenant_quota: {
+	title: "Tenant Quota"
+
+	description: """
+		Enforces per-tenant quotas on a pipeline shared by several tenants, so that a single noisy
+		tenant can't starve the others of throughput or buffer space.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.tenant_quota.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	examples: [
+		{
+			title: "Tag events with their tenant"
+			configuration: {
+				tenant_key: "{{ tenant_id }}"
+				tag_field:  "tenant"
+				tenants: acme: events_per_sec: 500
+			}
+			input: log: {
+				tenant_id: "acme"
+				message:   "User logged in"
+			}
+			output: log: {
+				tenant_id: "acme"
+				tenant:    "acme"
+				message:   "User logged in"
+			}
+		},
+	]
+
+	how_it_works: {
+		tenants: {
+			title: "Tenants"
+			body: """
+				Each event is assigned to the tenant named by rendering `tenant_key` against it. The
+				quota for a tenant is taken from `tenants`, falling back to `default_quota` for tenants
+				that are not listed.
+				"""
+		}
+		fair_scheduling: {
+			title: "Fair scheduling"
+			body: """
+				Events are held in a queue per tenant, and the transform takes turns between the tenants
+				that have events ready to send, forwarding one event from each in a round-robin. A
+				tenant sending a large volume of events therefore can't delay the events of other
+				tenants sharing the pipeline.
+
+				Events are only forwarded while their tenant is within its `events_per_sec` quota. Once
+				the events held for a tenant reach its `buffer_bytes` quota, further events for that
+				tenant are dropped rather than applying backpressure to every tenant.
+
+				Backpressure is only applied once the events held for all tenants reach
+				`max_buffered_bytes`, at which point the transform stops reading its inputs until some of
+				them are forwarded. At most `max_tenants` tenants are tracked at once, and the events of
+				further tenants are dropped until some of the tracked tenants are idle again.
+
+				Held events are forwarded without waiting for their quota when Vector shuts down.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		tenant_events_discarded_total:    components.sources.internal_metrics.output.metrics.tenant_events_discarded_total
+	}
+}
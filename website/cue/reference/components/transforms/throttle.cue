@@ -34,6 +34,8 @@ components: transforms: throttle: {
 
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+		state_store_byte_size:  components.sources.internal_metrics.output.metrics.state_store_byte_size
+		state_store_entries:    components.sources.internal_metrics.output.metrics.state_store_entries
 	}
 
 	examples: [
@@ -112,5 +114,19 @@ components: transforms: throttle: {
 				},
 			]
 		}
+
+		persisted_state: {
+			title: "Persisted State"
+			body: """
+				By default, the rate limiters only live in memory, so every bucket
+				starts with its full `threshold` again when Vector restarts. Setting
+				`state.enabled` records how many events each bucket let through in
+				its current `window_secs`, checkpoints that to a `state.json` file in
+				a subdirectory of the data directory named after the component
+				every `state.checkpoint_interval_secs` and when the transform stops,
+				and uses up as many cells of each bucket when the transform starts.
+				Cells then replenish at the usual rate.
+				"""
+		}
 	}
 }
@@ -0,0 +1,75 @@
+package metadata
+
+components: transforms: transcode: {
+	title: "Transcode"
+
+	description: """
+		Re-encodes an embedded payload field from one format to another, such as decoding a JSON
+		payload carried in an envelope into structured fields, or encoding structured fields into
+		bytes, using the same codecs available to sources and sinks.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.transcode.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Decode an embedded JSON payload"
+			configuration: {
+				field:        "payload"
+				target_field: "decoded"
+				decoding: codec: "json"
+			}
+			input: log: {
+				source:  "gateway"
+				payload: #"{"user":"alice","action":"login"}"#
+			}
+			output: log: {
+				source:  "gateway"
+				payload: #"{"user":"alice","action":"login"}"#
+				decoded: {
+					user:   "alice"
+					action: "login"
+				}
+			}
+		},
+	]
+
+	how_it_works: {
+		transcoding: {
+			title: "Transcoding"
+			body: """
+				The payload in `field` is first decoded with `decoding`, if set, and the resulting value
+				is then encoded with `encoding`, if set. At least one of the two must be configured.
+				Setting both converts a payload from one wire format to another without touching the
+				rest of the event.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
@@ -90,6 +90,183 @@ configuration: {
 			}
 		}
 
+		dead_letter_queue: {
+			common: false
+			description: """
+				The sink to send dropped events to by default.
+
+				Any component that routes dropped events to its `dropped` output, such as the
+				`remap` transform with `reroute_dropped` enabled, will have that output automatically
+				connected to this sink. Dropped events are annotated with the reason they were dropped,
+				and the ID, type, and kind of the component that dropped them.
+
+				Individual transforms can send their dropped events to a different sink by setting their
+				own `dead_letter_queue` option. Sinks send the events diverted by the `dead_letter_queue`
+				fallback of their `circuit_breaker` and `max_event_age` options to this sink, unless
+				those options configure their own.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["dead_letters"]
+			}
+		}
+
+		event_tracing: {
+			common: false
+			description: """
+				Event tracing configuration.
+
+				When enabled, a sample of the events emitted by sources are followed through the topology,
+				recording each component that receives them, the time at which they received them, and
+				whether the event was modified since the previous component. This makes it possible to
+				find out where an event was dropped, being the last component that received it, or which
+				component modified it.
+
+				The most recent traces are kept in memory and can be queried with the `eventTraces` query
+				of the [GraphQL API](/docs/reference/api/).
+				"""
+			required: false
+			type: object: options: {
+				enabled: {
+					description: "Whether or not event tracing is enabled."
+					required:    false
+					type: bool: default: false
+				}
+				sample_rate: {
+					description: """
+						The rate at which events emitted by sources are sampled for tracing.
+
+						For example, a rate of `1000` traces one out of every 1000 events.
+						"""
+					required: false
+					type: uint: default: 1000
+				}
+				max_traces: {
+					description: """
+						The maximum number of traces to keep.
+
+						Once this many traces are recorded, the oldest trace is discarded for each new one.
+						"""
+					required: false
+					type: uint: default: 1000
+				}
+			}
+		}
+
+		end_to_end_latency: {
+			common: false
+			description: """
+				Whether or not to measure the end-to-end latency of events.
+
+				When enabled, the time from when a source emits an event to when a sink acknowledges it is
+				recorded in the `end_to_end_latency_seconds` internal metric, for each pair of source and
+				sink. Events going through a disk buffer aren't measured.
+				"""
+			required: false
+			type: bool: default: false
+		}
+
+		audit_log: {
+			common: false
+			description: """
+				Audit log configuration.
+
+				When set, control-plane actions, such as config reloads, API mutations, tap sessions, and
+				drain requests, are appended to a file as JSON objects, one per line. Each entry includes
+				the hash of the previous entry and its own hash, so that modifying, reordering, or
+				removing entries can be detected with `vector audit verify`. Entries are written in the
+				background, so recording an action doesn't hold it up.
+				"""
+			required: false
+			type: object: options: {
+				path: {
+					description: "The path of the audit log. Entries are appended if it already exists."
+					required:    true
+					type: string: examples: ["/var/lib/vector/audit.log"]
+				}
+				key: {
+					description: """
+						The key used to authenticate the entries of the audit log. When set, the hash of
+						each entry is an HMAC-SHA256 keyed with it, rather than a SHA-256, so that entries
+						can't be forged or rewritten without the key, which `vector audit verify --key`
+						then needs. It can be loaded from a secret backend with `SECRET[<backend>.<key>]`.
+						A log must either be keyed from its first entry, or not at all.
+						"""
+					required: false
+					type: string: examples: ["${AUDIT_LOG_KEY}", "SECRET[backend.audit_log_key]"]
+				}
+				internal_events: {
+					description: """
+						Whether or not each entry is also emitted as an internal log event, with its action,
+						sequence number, and hash. The events can be collected with the `internal_logs`
+						source and sent to any sink, to keep a copy of the chain outside of this host, as
+						removing the last entries of the log doesn't break the chain. Passing the last hash
+						seen there to `vector audit verify --anchor` detects it.
+						"""
+					required: false
+					type: bool: default: false
+				}
+			}
+		}
+
+		self_telemetry: {
+			common: false
+			description: """
+				Self-telemetry configuration.
+
+				When enabled, Vector's internal metrics and the spans of its components are periodically
+				sent to an OpenTelemetry collector with OTLP/HTTP, encoded as JSON. This doesn't depend
+				on the configured topology, so Vector can be observed even when its pipeline is unhealthy.
+				"""
+			required: false
+			type: object: options: {
+				enabled: {
+					description: "Whether or not self-telemetry is exported."
+					required:    false
+					type: bool: default: false
+				}
+				endpoint: {
+					description: """
+						The base URL of the OpenTelemetry collector.
+
+						Metrics are sent to the `/v1/metrics` path, and spans to the `/v1/traces` path.
+						"""
+					required: false
+					type: string: {
+						default: "http://localhost:4318"
+						examples: ["http://otel-collector:4318"]
+					}
+				}
+				interval_secs: {
+					description: "The interval between two exports, in seconds."
+					required:    false
+					type: uint: {
+						default: 10
+						unit:    "seconds"
+					}
+				}
+				metrics: {
+					description: "Whether or not internal metrics are exported."
+					required:    false
+					type: bool: default: true
+				}
+				traces: {
+					description: "Whether or not the spans of components are exported."
+					required:    false
+					type: bool: default: true
+				}
+				headers: {
+					description: "Headers to add to the export requests, such as for authentication."
+					required:    false
+					type: object: {
+						examples: [{"Authorization": "Bearer ${OTEL_TOKEN}"}]
+						options: {}
+					}
+				}
+			}
+		}
+
 		enrichment_tables: {
 			common:      false
 			description: """
@@ -365,11 +542,70 @@ configuration: {
 			}
 		}
 
+		rate_limit_groups: {
+			common: false
+			description: """
+				Named rate limits shared by groups of sinks.
+
+				Sinks join a group with their `rate_limit_group` option, and then share a single budget of
+				requests and bytes, on top of their own `request` rate limits. This is useful to prevent
+				several sinks sending to the same service, such as multiple `elasticsearch` sinks writing
+				to the same cluster, from overloading it together.
+
+				The budget of a group is kept across config reloads, as long as the group itself is
+				unchanged.
+				"""
+			required: false
+			type: object: options: {
+				"*": {
+					description: "A rate limit group."
+					required:    true
+					type: object: options: {
+						duration_secs: {
+							description: "The time window used for the `request_limit` and `byte_limit` options."
+							required:    false
+							type: uint: {
+								default: 1
+								unit:    "seconds"
+							}
+						}
+						request_limit: {
+							description: """
+								The maximum number of requests allowed within the `duration_secs` time window, across all
+								sinks of the group.
+
+								A sink that doesn't support request limits fails to build when it is part of a group with
+								a request limit.
+								"""
+							required: false
+							type: uint: {
+								examples: [100]
+								unit: "requests"
+							}
+						}
+						byte_limit: {
+							description: """
+								The maximum number of bytes allowed within the `duration_secs` time window, across all sinks
+								of the group.
+
+								Bytes are measured as the estimated JSON-encoded size of the events sent to the sinks.
+								"""
+							required: false
+							type: uint: {
+								examples: [10485760]
+								unit: "bytes"
+							}
+						}
+					}
+				}
+			}
+		}
+
 		secret: {
 			common: false
 			description: """
 				Configuration options to retrieve secrets from external backend in order to avoid storing secrets in plaintext
-				in Vector config. Currently, only the exec backend is supported. Multiple backends can be configured. To signify
+				in Vector config. The exec and vault backends are supported. Multiple backends can be configured. To signify
 				Vector that it should look for a secret to retrieve use the `SECRET[<backend_name>.<secret_key>]`. This placeholder
 				will then be replaced by the secret retrieved from the relevant backend.
 				"""
@@ -424,6 +660,100 @@ configuration: {
 						}
 					}
 				}
+				vault: {
+					required: true
+					description: """
+						Retrieve secrets from [HashiCorp Vault](https://www.vaultproject.io/).
+
+						Secrets are referenced as `SECRET[<backend_name>.<path>:<key>]`, where `path` is the path of
+						the secret in Vault and `key` is the field of the secret to use. For example,
+						`SECRET[vault.secret/data/my-app:password]` uses the `password` field of the `my-app` secret of
+						the KV v2 secrets engine mounted at `secret`.
+
+						Tokens obtained by logging in with the `approle` or `kubernetes` strategy, and secrets with a
+						lease, such as dynamic database credentials, are cached and renewed before they expire, so
+						that they are reused across configuration reloads. Secrets without a lease are read again on
+						every reload.
+						"""
+					type: object: options: {
+						endpoint: {
+							description: "The address of the Vault server."
+							required:    true
+							type: string: examples: ["https://vault.example.com:8200"]
+						}
+						namespace: {
+							description: "The Vault Enterprise namespace to send requests to."
+							required:    false
+							common:      false
+							type: string: {
+								default: null
+								examples: ["team-a"]
+							}
+						}
+						auth: {
+							description: "The authentication strategy to use."
+							required:    true
+							type: object: options: {
+								strategy: {
+									description: "The authentication strategy to use."
+									required:    true
+									type: string: enum: {
+										token:      "Authenticate with a static token."
+										approle:    "Authenticate with the AppRole auth method."
+										kubernetes: "Authenticate with the Kubernetes auth method, using the token of the service account Vector is running as."
+									}
+								}
+								token: {
+									description:   "The Vault token."
+									relevant_when: "strategy = \"token\""
+									required:      true
+									type: string: examples: ["${VAULT_TOKEN}"]
+								}
+								role_id: {
+									description:   "The role ID."
+									relevant_when: "strategy = \"approle\""
+									required:      true
+									type: string: {}
+								}
+								secret_id: {
+									description:   "The secret ID."
+									relevant_when: "strategy = \"approle\""
+									required:      true
+									type: string: examples: ["${VAULT_SECRET_ID}"]
+								}
+								role: {
+									description:   "The name of the role to log in as."
+									relevant_when: "strategy = \"kubernetes\""
+									required:      true
+									type: string: {}
+								}
+								token_path: {
+									description:   "The path of the service account token."
+									relevant_when: "strategy = \"kubernetes\""
+									required:      false
+									common:        false
+									type: string: default: "/var/run/secrets/kubernetes.io/serviceaccount/token"
+								}
+								mount: {
+									description:   "The path the auth method is mounted at. Defaults to `approle` or `kubernetes`, depending on the strategy."
+									relevant_when: "strategy = \"approle\" or strategy = \"kubernetes\""
+									required:      false
+									common:        false
+									type: string: default: null
+								}
+							}
+						}
+						timeout: {
+							description: "The amount of time Vector will wait for each request to Vault to complete."
+							required:    false
+							common:      false
+							type: uint: {
+								default: 5
+								unit:    "seconds"
+							}
+						}
+					}
+				}
 			}
 		}
 
@@ -446,6 +776,28 @@ configuration: {
 			description: "Configures an HTTP(S) proxy for Vector to use."
 			required:    false
 			type: object: options: {
+				auth: {
+					common:      false
+					description: "Basic authentication to the proxy endpoints, overriding the credentials of the proxy endpoint URIs."
+					required:    false
+					type: object: options: {
+						password: {
+							description: "The basic authentication password."
+							required:    true
+							type: string: examples: ["${PROXY_PASSWORD}", "password"]
+						}
+						strategy: {
+							description: "The authentication strategy to use."
+							required:    true
+							type: string: enum: basic: "Basic authentication, sent in the `Proxy-Authorization` header."
+						}
+						user: {
+							description: "The basic authentication username."
+							required:    true
+							type: string: examples: ["${PROXY_USERNAME}", "username"]
+						}
+					}
+				}
 				enabled: {
 					common:      false
 					description: "If false the proxy will be disabled."
@@ -492,6 +844,26 @@ configuration: {
 						}
 					}
 				}
+				tls: {
+					common: false
+					description: """
+						TLS configuration of the connections to the proxy endpoints using `https`, such as to trust the
+						certificate of the proxy with a custom CA. Takes the same options as the `tls` option of components.
+						"""
+					required: false
+					type: object: options: {
+						ca_file: {
+							description: "Absolute path to an additional CA certificate file."
+							required:    false
+							type: string: examples: ["/path/to/proxy_ca.crt"]
+						}
+						verify_certificate: {
+							description: "Enables certificate verification."
+							required:    false
+							type: bool: default: true
+						}
+					}
+				}
 			}
 		}
 	}
@@ -653,6 +1025,49 @@ configuration: {
 				```
 				"""
 		}
+		includes: {
+			title: "Including other files"
+			body: """
+				Any configuration file can include other configuration files with the top-level `includes`
+				option, which takes a list of paths relative to the including file. [Globbing](\(urls.globbing))
+				is supported:
+
+				```toml
+				includes = ["sources/*.toml", "sinks/*.toml"]
+
+				[sinks.archive]
+				# Overrides the `batch.max_bytes` option of the included `archive` sink.
+				batch.max_bytes = 10000000
+				```
+
+				Included files are merged together in the same way as [multiple files](#multiple-files), and
+				the including file is then merged over the result, overriding any options set by the files it
+				includes. Included files can include other files in turn, but including a file that is already
+				being included results in an error, as the includes would form a cycle.
+				"""
+		}
+		profiles: {
+			title: "Environment profiles"
+			body: """
+				Configuration files can define per-environment overlays under the top-level `profiles` option.
+				The profile named by the `VECTOR_PROFILE` environment variable is merged over the rest of the
+				file, after any included files have been resolved:
+
+				```toml
+				[sinks.out]
+				type = "console"
+				inputs = ["in"]
+				encoding.codec = "json"
+
+				[profiles.development.sinks.out]
+				encoding.codec = "text"
+				```
+
+				Options set by the active profile take precedence. Profiles that are not active are ignored.
+				Within YAML files, anchors and aliases can also be used to share common settings between
+				components.
+				"""
+		}
 		automatic_namespacing: {
 			title: "Automatic namespacing of component files"
 			body: """
@@ -61,6 +61,13 @@ process: {
 			SIGTERM: {
 				description: "Initiates graceful shutdown process."
 			}
+
+			SIGUSR2: {
+				description: """
+					Drains Vector and then shuts it down, like a `POST` request to the
+					`/drain` API endpoint without a `timeout_secs`.
+					"""
+			}
 		}
 	}
 }
@@ -26,6 +26,7 @@ urls: {
 	aws_athena_console:                         "https://console.aws.amazon.com/athena/home"
 	aws_canonical_user_id:                      "\(aws_docs)/general/latest/gr/acct-identifiers.html#FindingCanonicalId"
 	aws_cloudwatch:                             "https://aws.amazon.com/cloudwatch/"
+	aws_cloudwatch_emf:                         "\(aws_docs)/AmazonCloudWatch/latest/monitoring/CloudWatch_Embedded_Metric_Format_Specification.html"
 	aws_cloudwatch_logs:                        "\(aws_docs)/AmazonCloudWatch/latest/logs/WhatIsCloudWatchLogs.html"
 	aws_cloudwatch_logs_api:                    "\(aws_docs)/AmazonCloudWatchLogs/latest/APIReference/Welcome.html"
 	aws_cloudwatch_logs_firehose:               "\(aws_docs)/firehose/latest/dev/writing-with-cloudwatch-logs.html"
@@ -208,6 +209,8 @@ urls: {
 	gcp_authentication_service_account:         "\(gcp)/docs/authentication/production#obtaining_and_providing_service_account_credentials_manually"
 	gcp_cloud_storage:                          "\(gcp)/storage"
 	gcp_chronicle:                              "https://chronicle.security"
+	gcp_chronicle_ingestion_api:                "\(gcp)/chronicle/docs/reference/ingestion-api"
+	gcp_chronicle_udm:                          "\(gcp)/chronicle/docs/reference/udm-field-list"
 	gcp_folders:                                "\(gcp)/resource-manager/docs/creating-managing-folders"
 	gcp_pubsub:                                 "\(gcp)/pubsub/"
 	gcp_pubsub_rest:                            "\(gcp)/pubsub/docs/reference/rest/"